@@ -2,10 +2,13 @@
 //!
 //! Provides NCM client with cookie management, QR login, and API wrappers.
 
+pub mod http;
 mod ncm;
 pub mod ncm_api;
 
 pub use ncm::NcmClient;
 pub use ncm_api::model::{
-    BannersInfo, LoginInfo, PlayListDetail, SongCopyright, SongInfo, SongList, TargetType, TopList,
+    AlbumRelease, ApiError, BannersInfo, Comment, CommentPage, CommentThread, CreditEntry, CreditName, LikedSongIds, LoginInfo,
+    PlayListDetail, PlayRecord, SingerInfo, SongCopyright, SongCredits, SongInfo, SongList,
+    TargetType, TopList, UserPlaylists, UserProfile,
 };
@@ -0,0 +1,184 @@
+//! Shared HTTP client
+//!
+//! API calls, cover downloads, audio streams and update checks all reuse one
+//! pooled client per proxy setting. Connections to a host stay open between
+//! requests (multiplexed over HTTP/2 where the server negotiates it), DNS
+//! answers are cached, and a per-host limit keeps a burst of requests from
+//! opening dozens of connections at once.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use reqwest::Client;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, watch};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+const MAX_IDLE_PER_HOST: usize = 8;
+
+/// Concurrent requests allowed per host
+const MAX_REQUESTS_PER_HOST: usize = 6;
+
+/// How long a resolved address is reused
+const DNS_TTL: Duration = Duration::from_secs(300);
+
+/// Clients keyed by proxy URL (`None` = direct)
+static CLIENTS: LazyLock<Mutex<HashMap<Option<String>, Client>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static HOST_LIMITS: LazyLock<Mutex<HashMap<String, Arc<Semaphore>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static DNS_CACHE: LazyLock<Mutex<DnsCache>> = LazyLock::new(|| Mutex::new(DnsCache::default()));
+
+/// Counts network changes, for downloads that need to reconnect
+static NETWORK_CHANGES: LazyLock<watch::Sender<u64>> = LazyLock::new(|| watch::channel(0).0);
+
+/// The shared client for `proxy`, created on first use
+///
+/// Fails if the proxy URL is invalid or the TLS backend can't be set up.
+pub fn client(proxy: Option<&str>) -> Result<Client> {
+    let key = proxy.map(str::to_string);
+    let mut clients = CLIENTS.lock();
+    if let Some(client) = clients.get(&key) {
+        return Ok(client.clone());
+    }
+
+    let mut builder = Client::builder()
+        .user_agent(concat!("Rustle/", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(CONNECT_TIMEOUT)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(MAX_IDLE_PER_HOST)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .http2_adaptive_window(true)
+        .dns_resolver(Arc::new(CachingResolver));
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    let client = builder.build()?;
+    clients.insert(key, client.clone());
+    Ok(client)
+}
+
+/// The shared client without a proxy
+///
+/// Panics if the TLS backend can't be set up, which no request could work
+/// without; callers that can report an error use [`client`] instead.
+pub fn direct() -> Client {
+    client(None).expect("Failed to set up the HTTP client")
+}
+
+/// Wait for a request slot on the host of `url`
+///
+/// Hold the permit until the response body has been read, except for audio
+/// streams: their bodies last as long as playback, so they hold it only
+/// while connecting. URLs without a host aren't limited.
+pub async fn host_permit(url: &str) -> Option<OwnedSemaphorePermit> {
+    let host = reqwest::Url::parse(url).ok()?.host_str()?.to_string();
+    let semaphore = HOST_LIMITS
+        .lock()
+        .entry(host)
+        .or_insert_with(|| Arc::new(Semaphore::new(MAX_REQUESTS_PER_HOST)))
+        .clone();
+    semaphore.acquire_owned().await.ok()
+}
+
+/// Start over after the active network changed
+///
+/// Pooled connections and cached DNS answers belong to the old network, so
+/// clients are rebuilt on next use and streams in flight are told to
+/// reconnect.
+pub fn network_changed() {
+    CLIENTS.lock().clear();
+    DNS_CACHE.lock().entries.clear();
+    NETWORK_CHANGES.send_modify(|count| *count += 1);
+}
+
+/// Notified whenever [`network_changed`] is called
+pub fn network_changes() -> watch::Receiver<u64> {
+    NETWORK_CHANGES.subscribe()
+}
+
+/// Resolved addresses with the time they were looked up
+#[derive(Default)]
+struct DnsCache {
+    entries: HashMap<String, (Instant, Vec<SocketAddr>)>,
+}
+
+impl DnsCache {
+    fn get(&mut self, host: &str, now: Instant) -> Option<Vec<SocketAddr>> {
+        match self.entries.get(host) {
+            Some((resolved, addrs)) if now.duration_since(*resolved) < DNS_TTL => {
+                Some(addrs.clone())
+            }
+            Some(_) => {
+                self.entries.remove(host);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&mut self, host: String, addrs: Vec<SocketAddr>, now: Instant) {
+        if !addrs.is_empty() {
+            self.entries.insert(host, (now, addrs));
+        }
+    }
+}
+
+/// System resolver with answers cached for [`DNS_TTL`]
+struct CachingResolver;
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let cached = DNS_CACHE.lock().get(&host, Instant::now());
+            let addrs = match cached {
+                Some(addrs) => addrs,
+                None => {
+                    let addrs: Vec<SocketAddr> =
+                        tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+                    DNS_CACHE.lock().insert(host, addrs.clone(), Instant::now());
+                    addrs
+                }
+            };
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dns_cache_expires_entries() {
+        let mut cache = DnsCache::default();
+        let now = Instant::now();
+        let addr: SocketAddr = "1.2.3.4:0".parse().unwrap();
+
+        cache.insert("music.163.com".to_string(), vec![addr], now);
+        assert_eq!(cache.get("music.163.com", now), Some(vec![addr]));
+
+        let later = now + DNS_TTL;
+        assert_eq!(cache.get("music.163.com", later), None);
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn test_network_change_reaches_streams() {
+        let mut changes = network_changes();
+        assert!(!changes.has_changed().unwrap());
+        network_changed();
+        assert!(changes.has_changed().unwrap());
+        changes.mark_unchanged();
+        assert!(!changes.has_changed().unwrap());
+    }
+}
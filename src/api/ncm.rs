@@ -12,7 +12,7 @@ use tracing::{debug, error};
 
 use super::ncm_api::{
     CookieJar, MusicApi,
-    model::{SongInfo, SongUrl},
+    model::{LoginInfo, SongCredits, SongInfo, SongUrl, to_lyric_credits},
 };
 
 const COOKIE_FILE: &str = "cookies.json";
@@ -139,15 +139,16 @@ impl NcmClient {
     }
 
     fn data_dir() -> PathBuf {
+        if let Some(root) = crate::utils::portable_root() {
+            return root.join("data");
+        }
         dirs::data_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("rustle")
     }
 
     fn cache_dir() -> PathBuf {
-        dirs::cache_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("rustle")
+        crate::utils::cache_dir()
     }
 
     pub fn cookie_file_path() -> PathBuf {
@@ -257,30 +258,38 @@ impl NcmClient {
         }
     }
 
-    pub async fn create_qrcode(&self) -> Result<(PathBuf, String)> {
+    /// 续期登录态并确认账号仍有效，成功后持久化新 cookie
+    pub async fn refresh_session(&self) -> Result<LoginInfo> {
+        self.client.refresh_login().await?;
+        let login_info = self.client.login_status().await?;
+        self.save_cookie_jar_to_file();
+        Ok(login_info)
+    }
+
+    /// 创建登录二维码，返回模块矩阵（`true` 为深色）与 unikey
+    pub async fn create_qrcode(&self) -> Result<(Vec<Vec<bool>>, String)> {
         let (qr_url, unikey) = self.client.login_qr_create().await?;
-        let cache_dir = Self::cache_dir();
-        fs::create_dir_all(&cache_dir)?;
+        let modules = qrcode_generator::to_matrix(qr_url, qrcode_generator::QrCodeEcc::Low)?;
+        Ok((modules, unikey))
+    }
 
-        // Clean up old QR code files
-        if let Ok(entries) = fs::read_dir(&cache_dir) {
-            for entry in entries.flatten() {
-                let file_name = entry.file_name();
-                let name = file_name.to_string_lossy();
-                if name.starts_with("qrimage_") && name.ends_with(".png") {
-                    let _ = fs::remove_file(entry.path());
-                }
-            }
-        }
+    /// 歌曲可获取的最高比特率（受账号与版权限制）
+    pub async fn max_bitrate(&self, id: u64) -> Result<u32> {
+        let urls = self
+            .client
+            .songs_url(&[id], &Self::quality_to_bitrate(4).to_string())
+            .await?;
+        urls.first()
+            .map(|u| u.rate)
+            .ok_or_else(|| anyhow::anyhow!("No URL available"))
+    }
 
-        // Use timestamp in filename to avoid iced image cache
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0);
-        let path = cache_dir.join(format!("qrimage_{}.png", timestamp));
-        qrcode_generator::to_png_to_file(qr_url, qrcode_generator::QrCodeEcc::Low, 200, &path)?;
-        Ok((path, unikey))
+    /// 以指定音质获取 URL 的客户端副本，不影响全局音质设置
+    pub fn with_quality(&self, quality: u32) -> Self {
+        Self {
+            client: self.client.clone(),
+            quality: Arc::new(AtomicU32::new(quality)),
+        }
     }
 
     /// 获取歌曲 URL
@@ -292,6 +301,26 @@ impl NcmClient {
         self.client.song_detail(ids).await
     }
 
+    /// Extended credits: artists and album from the song detail, 作词/作曲/编曲
+    /// from the lyric header and the album's record company
+    pub async fn song_credits(&self, id: u64) -> Result<SongCredits> {
+        let (credits, lyrics) =
+            tokio::join!(self.client.song_credits(id), self.client.song_lyric(id));
+        let mut credits = credits?;
+        if let Ok(lyrics) = lyrics {
+            credits.entries = to_lyric_credits(&lyrics.lyric);
+        }
+        if credits.album_id > 0 {
+            credits.company = self
+                .client
+                .album_company(credits.album_id)
+                .await
+                .ok()
+                .flatten();
+        }
+        Ok(credits)
+    }
+
     pub async fn get_lyrics(&self, si: &SongInfo) -> Result<Vec<(u64, String)>> {
         let cache_dir = Self::cache_dir();
         fs::create_dir_all(&cache_dir)?;
@@ -11,7 +11,8 @@ use lazy_static::lazy_static;
 pub use model::*;
 use parking_lot::RwLock;
 use regex::Regex;
-use reqwest::{Client, header};
+use reqwest::cookie::CookieStore;
+use reqwest::{Client, RequestBuilder, Response, header};
 use std::fmt;
 use std::sync::Arc;
 use std::{collections::HashMap, path::PathBuf, time::Duration};
@@ -27,6 +28,9 @@ static BASE_URL: &str = "https://music.163.com";
 
 const TIMEOUT: u64 = 100;
 
+/// A file download getting no data for this long gives up
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
 const LINUX_USER_AGNET: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/60.0.3112.90 Safari/537.36";
 
 const USER_AGENT_LIST: [&str; 14] = [
@@ -91,13 +95,8 @@ impl MusicApi {
             &base_url,
         );
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(TIMEOUT))
-            .cookie_provider(cookie_jar.clone())
-            .build()
-            .expect("初始化网络请求失败!");
         Self {
-            client,
+            client: super::http::direct(),
             cookie_jar,
             csrf: Arc::new(RwLock::new(String::new())),
         }
@@ -112,13 +111,8 @@ impl MusicApi {
             &base_url,
         );
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(TIMEOUT))
-            .cookie_provider(cookie_jar.clone())
-            .build()
-            .expect("初始化网络请求失败!");
         Self {
-            client,
+            client: super::http::direct(),
             cookie_jar,
             csrf: Arc::new(RwLock::new(String::new())),
         }
@@ -129,14 +123,7 @@ impl MusicApi {
     }
 
     pub fn set_proxy(&mut self, proxy: &str) -> Result<()> {
-        let proxy = reqwest::Proxy::all(proxy)?;
-        let client = Client::builder()
-            .timeout(Duration::from_secs(TIMEOUT))
-            .proxy(proxy)
-            .cookie_provider(self.cookie_jar.clone())
-            .build()
-            .expect("初始化网络请求失败!");
-        self.client = client;
+        self.client = super::http::client(Some(proxy))?;
         Ok(())
     }
 
@@ -154,6 +141,23 @@ impl MusicApi {
         }
     }
 
+    /// Attach this account's cookies; the shared client has no cookie store
+    fn with_cookies(&self, request: RequestBuilder, url: &str) -> RequestBuilder {
+        let cookies = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| self.cookie_jar.cookies(&url));
+        match cookies {
+            Some(cookies) => request.header(header::COOKIE, cookies),
+            None => request,
+        }
+    }
+
+    fn store_cookies(&self, response: &Response) {
+        let mut set_cookies = response.headers().get_all(header::SET_COOKIE).iter();
+        self.cookie_jar
+            .set_cookies(&mut set_cookies, response.url());
+    }
+
     async fn request(
         &self,
         method: Method,
@@ -200,9 +204,10 @@ impl MusicApi {
                     }
                 };
 
+                let _permit = super::http::host_permit(&url).await;
                 let response = self
-                    .client
-                    .post(&url)
+                    .with_cookies(self.client.post(&url), &url)
+                    .timeout(Duration::from_secs(TIMEOUT))
                     .header(header::ACCEPT, "*/*")
                     .header(header::ACCEPT_LANGUAGE, "en-US,en;q=0.5")
                     .header(header::CONNECTION, "keep-alive")
@@ -213,23 +218,20 @@ impl MusicApi {
                     .body(body)
                     .send()
                     .await
-                    .map_err(|e| anyhow!("Request failed: {}", e))?;
-                response
-                    .text()
-                    .await
-                    .map_err(|e| anyhow!("Failed to read response: {}", e))
+                    .map_err(|e| network_error(format!("Request failed: {}", e)))?;
+                self.store_cookies(&response);
+                read_response(response).await
             }
             Method::Get => {
+                let _permit = super::http::host_permit(&url).await;
                 let response = self
-                    .client
-                    .get(&url)
+                    .with_cookies(self.client.get(&url), &url)
+                    .timeout(Duration::from_secs(TIMEOUT))
                     .send()
                     .await
-                    .map_err(|e| anyhow!("Request failed: {}", e))?;
-                response
-                    .text()
-                    .await
-                    .map_err(|e| anyhow!("Failed to read response: {}", e))
+                    .map_err(|e| network_error(format!("Request failed: {}", e)))?;
+                self.store_cookies(&response);
+                read_response(response).await
             }
         }
     }
@@ -274,6 +276,44 @@ impl MusicApi {
         to_login_info(result)
     }
 
+    /// 刷新登录态，延长 MUSIC_U 有效期
+    pub async fn refresh_login(&self) -> Result<()> {
+        let path = "/weapi/login/token/refresh";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "pc",
+                true,
+            )
+            .await?;
+        let msg = check_session(to_msg(result)?)?;
+        if msg.code == 200 {
+            Ok(())
+        } else {
+            Err(anyhow!("Failed to refresh login: {}", msg.msg))
+        }
+    }
+
+    /// 注册游客身份，未登录时领取匿名 token（MUSIC_A）
+    pub async fn register_anonymous(&self) -> Result<()> {
+        let path = "/weapi/register/anonimous";
+        let username = Crypto::anonymous_username(&Crypto::device_id());
+        let mut params = HashMap::new();
+        params.insert("username", username.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "pc", true)
+            .await?;
+        let msg = to_msg(result)?;
+        if msg.code == 200 {
+            Ok(())
+        } else {
+            Err(anyhow!("Failed to register anonymous: {}", msg.msg))
+        }
+    }
+
     pub async fn logout(&self) {
         let path = "https://music.163.com/weapi/logout";
         let _ = self
@@ -288,7 +328,8 @@ impl MusicApi {
             .await;
     }
 
-    pub async fn user_song_id_list(&self, uid: u64) -> Result<Vec<u64>> {
+    /// 喜欢的音乐 id 列表，附带列表最后变动的时间
+    pub async fn user_song_id_list(&self, uid: u64) -> Result<LikedSongIds> {
         let path = "/weapi/song/like/get";
         let mut params = HashMap::new();
         let uid = uid.to_string();
@@ -314,6 +355,54 @@ impl MusicApi {
         to_song_list(result, Parse::Usl)
     }
 
+    /// 用户创建和收藏的歌单
+    pub async fn user_playlists(&self, uid: u64, limit: u16) -> Result<UserPlaylists> {
+        let path = "/weapi/user/playlist";
+        let mut params = HashMap::new();
+        let uid_str = uid.to_string();
+        let limit = limit.to_string();
+        params.insert("uid", uid_str.as_str());
+        params.insert("offset", "0");
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_user_playlists(result, uid)
+    }
+
+    /// 用户主页：昵称、头像、等级与累计听歌数
+    pub async fn user_detail(&self, uid: u64) -> Result<UserProfile> {
+        let path = format!("/weapi/v1/user/detail/{}", uid);
+        let result = self
+            .request(
+                Method::Post,
+                &path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_user_profile(result)
+    }
+
+    /// 听歌排行，`all_time` 为 false 时取最近一周；用户隐藏排行时返回 None
+    pub async fn user_play_record(
+        &self,
+        uid: u64,
+        all_time: bool,
+    ) -> Result<Option<Vec<PlayRecord>>> {
+        let path = "/weapi/v1/play/record";
+        let mut params = HashMap::new();
+        let uid = uid.to_string();
+        params.insert("uid", uid.as_str());
+        params.insert("type", if all_time { "0" } else { "1" });
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_play_records(result)
+    }
+
     #[allow(dead_code)]
     pub async fn user_cloud_disk(&self) -> Result<Vec<SongInfo>> {
         let path = "/weapi/v1/cloud/get";
@@ -327,42 +416,56 @@ impl MusicApi {
     }
 
     pub async fn song_list_detail(&self, songlist_id: u64) -> Result<PlayListDetail> {
+        let mut detail = self.song_list_page(songlist_id, 1000).await?;
+
+        // If there are more songs than we got, fetch the rest using song_detail
+        if detail.track_count > detail.songs.len() as u64 {
+            let remaining_ids = self.remaining_track_ids(songlist_id, &detail.songs).await?;
+            for chunk in remaining_ids.chunks(500) {
+                if let Ok(songs) = self.song_detail(chunk).await {
+                    detail.songs.extend(songs);
+                }
+            }
+        }
+
+        Ok(detail)
+    }
+
+    /// Playlist info with only the first `limit` tracks
+    pub async fn song_list_page(&self, songlist_id: u64, limit: usize) -> Result<PlayListDetail> {
         let csrf_token = self.csrf.read().clone();
         let path = "/weapi/v6/playlist/detail";
         let mut params = HashMap::new();
         let songlist_id_str = songlist_id.to_string();
+        let limit = limit.to_string();
         params.insert("id", songlist_id_str.as_str());
         params.insert("offset", "0");
         params.insert("total", "true");
-        params.insert("limit", "1000");
-        params.insert("n", "1000");
+        params.insert("limit", limit.as_str());
+        params.insert("n", limit.as_str());
         params.insert("csrf_token", &csrf_token);
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
-        let mut detail = to_mix_detail(&serde_json::from_str(&result)?)?;
-
-        // If there are more songs than we got, fetch the rest using song_detail
-        if detail.track_count > detail.songs.len() as u64 {
-            let track_ids = self.playlist_track_ids(songlist_id).await?;
-            let existing_ids: std::collections::HashSet<u64> =
-                detail.songs.iter().map(|s| s.id).collect();
-            let remaining_ids: Vec<u64> = track_ids
-                .into_iter()
-                .filter(|id| !existing_ids.contains(id))
-                .collect();
-
-            for chunk in remaining_ids.chunks(500) {
-                if let Ok(songs) = self.song_detail(chunk).await {
-                    detail.songs.extend(songs);
-                }
-            }
-        }
+        to_mix_detail(&serde_json::from_str(&result)?)
+    }
 
-        Ok(detail)
+    /// IDs of the playlist's tracks not already in `loaded`, in playlist order
+    pub async fn remaining_track_ids(
+        &self,
+        playlist_id: u64,
+        loaded: &[SongInfo],
+    ) -> Result<Vec<u64>> {
+        let existing_ids: std::collections::HashSet<u64> = loaded.iter().map(|s| s.id).collect();
+        let track_ids = self.playlist_track_ids(playlist_id).await?;
+        Ok(track_ids
+            .into_iter()
+            .filter(|id| !existing_ids.contains(id))
+            .collect())
     }
 
-    async fn playlist_track_ids(&self, playlist_id: u64) -> Result<Vec<u64>> {
+    /// All track IDs of a playlist in playlist order, without song details
+    pub async fn playlist_track_ids(&self, playlist_id: u64) -> Result<Vec<u64>> {
         let csrf_token = self.csrf.read().clone();
         let path = "/weapi/v6/playlist/detail";
         let mut params = HashMap::new();
@@ -483,6 +586,34 @@ impl MusicApi {
         to_song_info(result, Parse::Usl)
     }
 
+    /// 歌曲制作信息：演唱者与所属专辑
+    pub async fn song_credits(&self, music_id: u64) -> Result<SongCredits> {
+        let path = "/weapi/v3/song/detail";
+        let mut params = HashMap::new();
+        let c = format!("[{{\"id\":{}}}]", music_id);
+        params.insert("c", &c[..]);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_song_credits(result)
+    }
+
+    /// 专辑发行公司
+    pub async fn album_company(&self, album_id: u64) -> Result<Option<String>> {
+        let path = format!("/weapi/v1/album/{}", album_id);
+        let result = self
+            .request(
+                Method::Post,
+                &path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_album_company(result)
+    }
+
     pub async fn song_lyric(&self, music_id: u64) -> Result<Lyrics> {
         let csrf_token = self.csrf.read().clone();
         let path = "/weapi/song/lyric";
@@ -501,6 +632,18 @@ impl MusicApi {
         to_lyric(result)
     }
 
+    /// 每日签到，`platform` 为 0（移动端）或 1（网页端）
+    pub async fn daily_signin(&self, platform: u8) -> Result<Option<i64>> {
+        let path = "/weapi/point/dailyTask";
+        let mut params = HashMap::new();
+        let platform = platform.to_string();
+        params.insert("type", platform.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_daily_signin(result)
+    }
+
     /// 红心/取消红心歌曲
     pub async fn like_song(&self, track_id: u64, like: bool) -> Result<()> {
         let csrf_token = self.csrf.read().clone();
@@ -513,9 +656,10 @@ impl MusicApi {
         params.insert("like", &like_str);
         params.insert("time", "3");
         params.insert("csrf_token", &csrf_token);
-        let _result = self
+        let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
+        check_session(to_msg(result)?)?;
         Ok(())
     }
 
@@ -541,10 +685,31 @@ impl MusicApi {
     {
         if !path.exists() {
             let url = url.into();
-            let image_url = format!("{}?param={}y{}", url, width, height);
-            let response = self.client.get(&image_url).send().await?;
+            // 宽高为 0 时下载原图
+            let image_url = if width == 0 || height == 0 {
+                url
+            } else {
+                format!("{}?param={}y{}", url, width, height)
+            };
+            let _permit = super::http::host_permit(&image_url).await;
+            let response = self
+                .client
+                .get(&image_url)
+                .timeout(Duration::from_secs(TIMEOUT))
+                .send()
+                .await?;
             if response.status().is_success() {
-                let bytes = response.bytes().await?;
+                use futures_util::StreamExt;
+
+                // Streamed so the bandwidth limit applies while downloading;
+                // written in one go so a failed download leaves no file
+                let mut bytes = Vec::new();
+                let mut stream = response.bytes_stream();
+                while let Some(chunk) = stream.next().await {
+                    let chunk = chunk?;
+                    crate::features::bandwidth::throttle(chunk.len()).await;
+                    bytes.extend_from_slice(&chunk);
+                }
                 std::fs::write(&path, bytes)?;
             }
         }
@@ -557,10 +722,37 @@ impl MusicApi {
     {
         if !path.exists() {
             let url = url.into();
-            let response = self.client.get(&url).send().await?;
+            let response = {
+                // Only while connecting, so a long song download doesn't
+                // hold up covers and API calls to the same host
+                let _permit = super::http::host_permit(&url).await;
+                self.client.get(&url).send().await?
+            };
             if response.status().is_success() {
-                let bytes = response.bytes().await?;
-                std::fs::write(&path, bytes)?;
+                use futures_util::StreamExt;
+                use std::io::Write;
+
+                // Streamed so the bandwidth limit applies while downloading
+                let mut file = std::fs::File::create(&path)?;
+                let mut stream = response.bytes_stream();
+                let written: Result<()> = async {
+                    while let Some(chunk) = tokio::time::timeout(READ_TIMEOUT, stream.next())
+                        .await
+                        .map_err(|_| anyhow!("Download of {} stalled", url))?
+                    {
+                        let chunk = chunk?;
+                        crate::features::bandwidth::throttle(chunk.len()).await;
+                        file.write_all(&chunk)?;
+                    }
+                    Ok(())
+                }
+                .await;
+                if written.is_err() {
+                    // A partial file would pass for a finished one next time
+                    drop(file);
+                    let _ = std::fs::remove_file(&path);
+                }
+                written?;
             }
         }
         Ok(())
@@ -579,7 +771,7 @@ impl MusicApi {
         let result = self
             .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
             .await?;
-        let msg = to_msg(result)?;
+        let msg = check_session(to_msg(result)?)?;
         if msg.code == 200 {
             Ok(())
         } else {
@@ -595,6 +787,134 @@ impl MusicApi {
         }
     }
 
+    /// 新建歌单，返回歌单 id
+    pub async fn playlist_create(&self, name: &str, private: bool) -> Result<u64> {
+        let path = "/weapi/playlist/create";
+        let mut params = HashMap::new();
+        params.insert("name", name);
+        params.insert("privacy", if private { "10" } else { "0" });
+        params.insert("type", "NORMAL");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_created_playlist(result)
+    }
+
+    /// 向歌单添加歌曲，新歌曲排在歌单最前
+    pub async fn playlist_add_tracks(&self, playlist_id: u64, track_ids: &[u64]) -> Result<()> {
+        self.playlist_manipulate_tracks("add", playlist_id, track_ids)
+            .await
+    }
+
+    /// 从歌单删除歌曲
+    pub async fn playlist_remove_tracks(&self, playlist_id: u64, track_ids: &[u64]) -> Result<()> {
+        self.playlist_manipulate_tracks("del", playlist_id, track_ids)
+            .await
+    }
+
+    /// 按给出的顺序重排歌单歌曲
+    pub async fn playlist_order_tracks(&self, playlist_id: u64, track_ids: &[u64]) -> Result<()> {
+        self.playlist_manipulate_tracks("update", playlist_id, track_ids)
+            .await
+    }
+
+    async fn playlist_manipulate_tracks(
+        &self,
+        op: &str,
+        playlist_id: u64,
+        track_ids: &[u64],
+    ) -> Result<()> {
+        let path = "/weapi/playlist/manipulate/tracks";
+        let mut params = HashMap::new();
+        let pid = playlist_id.to_string();
+        let ids = serde_json::to_string(track_ids)?;
+        params.insert("op", op);
+        params.insert("pid", pid.as_str());
+        params.insert("trackIds", ids.as_str());
+        params.insert("imme", "true");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        let msg = check_session(to_message(result)?)?;
+        if msg.code == 200 {
+            Ok(())
+        } else {
+            Err(anyhow!("Failed to {} playlist tracks: {}", op, msg.msg))
+        }
+    }
+
+    /// 已关注的歌手
+    pub async fn artist_sublist(&self, offset: u16, limit: u16) -> Result<Vec<SingerInfo>> {
+        let path = "/weapi/artist/sublist";
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("offset", &offset[..]);
+        params.insert("limit", &limit[..]);
+        params.insert("total", "true");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_singer_list(result)
+    }
+
+    /// 关注/取消关注歌手
+    pub async fn artist_subscribe(&self, subscribe: bool, artist_id: u64) -> Result<()> {
+        let path = if subscribe {
+            "/weapi/artist/sub"
+        } else {
+            "/weapi/artist/unsub"
+        };
+        let mut params = HashMap::new();
+        let id = artist_id.to_string();
+        let ids = format!("[{}]", artist_id);
+        params.insert("artistId", id.as_str());
+        params.insert("artistIds", ids.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        let msg = check_session(to_msg(result)?)?;
+        if msg.code == 200 {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to {} artist: {}",
+                if subscribe { "follow" } else { "unfollow" },
+                msg.msg
+            ))
+        }
+    }
+
+    /// 歌手专辑，按发行时间从新到旧
+    pub async fn artist_albums(&self, artist_id: u64, limit: u16) -> Result<Vec<AlbumRelease>> {
+        let path = format!("/weapi/artist/albums/{}", artist_id);
+        let mut params = HashMap::new();
+        let limit = limit.to_string();
+        params.insert("offset", "0");
+        params.insert("limit", &limit[..]);
+        params.insert("total", "true");
+        let result = self
+            .request(Method::Post, &path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_album_releases(result)
+    }
+
+    /// 专辑内容
+    pub async fn album(&self, album_id: u64) -> Result<Vec<SongInfo>> {
+        let path = format!("/weapi/v1/album/{}", album_id);
+        let result = self
+            .request(
+                Method::Post,
+                &path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+            )
+            .await?;
+        to_song_info(result, Parse::Album)
+    }
+
     /// 私人FM - 获取推荐歌曲
     pub async fn personal_fm(&self) -> Result<Vec<SongInfo>> {
         let path = "/api/v1/radio/get";
@@ -611,6 +931,21 @@ impl MusicApi {
         to_song_info(result, Parse::PersonalFm)
     }
 
+    /// 相似歌曲
+    pub async fn simi_song(&self, song_id: u64, limit: u16) -> Result<Vec<SongInfo>> {
+        let path = "/weapi/v1/discovery/simiSong";
+        let mut params = HashMap::new();
+        let song_id = song_id.to_string();
+        let limit = limit.to_string();
+        params.insert("songid", song_id.as_str());
+        params.insert("limit", limit.as_str());
+        params.insert("offset", "0");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_song_info(result, Parse::SimiSong)
+    }
+
     /// 搜索 - 搜索歌曲、专辑、歌手、歌单
     /// search_type: 1=songs, 10=albums, 100=artists, 1000=playlists
     pub async fn search(
@@ -635,6 +970,136 @@ impl MusicApi {
             .await?;
         to_search_response(result, search_type)
     }
+
+    /// 热搜列表
+    pub async fn search_hot(&self) -> Result<Vec<String>> {
+        let path = "/weapi/search/hot";
+        let mut params = HashMap::new();
+        params.insert("type", "1111");
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_hot_searches(result)
+    }
+
+    /// 搜索建议 - 输入时的关键词联想
+    pub async fn search_suggest(&self, keywords: &str) -> Result<Vec<String>> {
+        let path = "/weapi/search/suggest/keyword";
+        let mut params = HashMap::new();
+        params.insert("s", keywords);
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_search_suggestions(result)
+    }
+
+    /// 评论列表，`offset` 为 0 时同时返回热门评论
+    pub async fn comments(
+        &self,
+        thread: CommentThread,
+        offset: u32,
+        limit: u32,
+    ) -> Result<CommentPage> {
+        let thread_id = thread.id();
+        let path = format!("/weapi/v1/resource/comments/{}", thread_id);
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("rid", thread_id.as_str());
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        params.insert("beforeTime", "0");
+        let result = self
+            .request(Method::Post, &path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_comment_page(result)
+    }
+
+    /// 发表评论，`reply_to` 为被回复的评论 id，返回新评论
+    pub async fn comment_post(
+        &self,
+        thread: CommentThread,
+        content: &str,
+        reply_to: Option<u64>,
+    ) -> Result<Comment> {
+        let thread_id = thread.id();
+        let comment_id = reply_to.map(|id| id.to_string());
+        let mut params = HashMap::new();
+        params.insert("threadId", thread_id.as_str());
+        params.insert("content", content);
+        let path = match &comment_id {
+            Some(id) => {
+                params.insert("commentId", id.as_str());
+                "/weapi/resource/comments/reply"
+            }
+            None => "/weapi/resource/comments/add",
+        };
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_posted_comment(result)
+    }
+
+    /// 删除自己的评论
+    pub async fn comment_delete(&self, thread: CommentThread, comment_id: u64) -> Result<()> {
+        let path = "/weapi/resource/comments/delete";
+        let thread_id = thread.id();
+        let comment_id = comment_id.to_string();
+        let mut params = HashMap::new();
+        params.insert("threadId", thread_id.as_str());
+        params.insert("commentId", comment_id.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_comment_done(result)
+    }
+
+    /// 点赞/取消点赞评论
+    pub async fn comment_like(
+        &self,
+        thread: CommentThread,
+        comment_id: u64,
+        like: bool,
+    ) -> Result<()> {
+        let path = if like {
+            "/weapi/v1/comment/like"
+        } else {
+            "/weapi/v1/comment/unlike"
+        };
+        let thread_id = thread.id();
+        let comment_id = comment_id.to_string();
+        let mut params = HashMap::new();
+        params.insert("threadId", thread_id.as_str());
+        params.insert("commentId", comment_id.as_str());
+        let result = self
+            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .await?;
+        to_comment_done(result)
+    }
+}
+
+/// 301、风控等状态码转为 [`ApiError`]，便于调用方给出具体提示
+fn check_session(msg: Msg) -> Result<Msg> {
+    match ApiError::from_code(msg.code.into()) {
+        Some(err) => Err(err.into()),
+        None => Ok(msg),
+    }
+}
+
+/// A transport failure, kept as [`ApiError::Network`] under the message
+fn network_error(msg: String) -> anyhow::Error {
+    anyhow::Error::new(ApiError::Network).context(msg)
+}
+
+/// Response body, or the throttling error when the gateway turned us away
+async fn read_response(response: Response) -> Result<String> {
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(ApiError::RateLimited.into());
+    }
+    response
+        .text()
+        .await
+        .map_err(|e| network_error(format!("Failed to read response: {}", e)))
 }
 
 fn choose_user_agent(ua: &str) -> &str {
@@ -24,6 +24,7 @@ lazy_static! {
     static ref RSA_MODULUS: Vec<u8> = hex::decode("00e0b509f6259df8642dbc35662901477df22677ec152b5ff68ace615bb7b725152b3ab17a876aea8a5aa76d2e417629ec4ee341f56135fccf695280104e0312ecbda92557c93870114af6c9d05c4f7f0c3685b7a46bee255932575cce10b424d813cfe4875d3e82047b97ddef52741d546b8e289dc6935b3ece0462db0a22b8e7").unwrap();
     static ref RSA_EXPONENT: Vec<u8> = hex::decode("010001").unwrap();
     static ref EAPIKEY: Vec<u8> = "e82ckenh8dichen8".as_bytes().to_vec();
+    static ref ID_XOR_KEY: Vec<u8> = "3go8&$8*3*3h0k(2)2".as_bytes().to_vec();
 }
 
 pub struct Crypto;
@@ -80,6 +81,22 @@ impl Crypto {
         format!("eparams={}", urlencoding::encode(&params))
     }
 
+    /// 随机设备 ID（32 位大写十六进制）
+    pub fn device_id() -> String {
+        hex::encode_upper(rand::random::<[u8; 16]>())
+    }
+
+    /// 游客注册用户名：base64("{device_id} {base64(md5(device_id ^ key))}")
+    pub fn anonymous_username(device_id: &str) -> String {
+        let xored: Vec<u8> = device_id
+            .bytes()
+            .zip(ID_XOR_KEY.iter().cycle())
+            .map(|(b, k)| b ^ k)
+            .collect();
+        let digest = general_purpose::STANDARD.encode(md5::compute(&xored).0);
+        general_purpose::STANDARD.encode(format!("{} {}", device_id, digest))
+    }
+
     pub fn aes_encrypt(
         data: &str,
         key: &[u8],
@@ -109,7 +109,7 @@ pub fn to_lyric(json: String) -> Result<Lyrics> {
             .collect::<Vec<String>>();
         return Ok(Lyrics { lyric, tlyric });
     }
-    Err(anyhow!("none"))
+    Err(code_error(code))
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -131,6 +131,7 @@ pub fn to_song_url(json: String) -> Result<Vec<SongUrl>> {
     let code: i64 = get_val!(value, "code")?;
     if code == 200 {
         let mut vec: Vec<SongUrl> = Vec::new();
+        let mut blocked = None;
         let array: &Vec<Value> = get_val!(value, "data")?;
         for v in array.iter() {
             let url: String = get_val!(v, "url").unwrap_or_default();
@@ -140,11 +141,31 @@ pub fn to_song_url(json: String) -> Result<Vec<SongUrl>> {
                     url,
                     rate: get_val!(v, "br")?,
                 });
+            } else if blocked.is_none() {
+                blocked = missing_url_reason(v);
             }
         }
-        return Ok(vec);
+        // Only fail when nothing is playable, a batch keeps what it got
+        return match blocked {
+            Some(reason) if vec.is_empty() => Err(reason.into()),
+            _ => Ok(vec),
+        };
+    }
+    Err(code_error(code))
+}
+
+/// Why a song came back without a URL: paid songs say so through `fee`,
+/// songs without copyright here through the per-song `code`
+fn missing_url_reason(v: &Value) -> Option<ApiError> {
+    let fee: i32 = get_val!(v, "fee").unwrap_or_default();
+    if matches!(SongCopyright::from_fee(fee), SongCopyright::VipOnly | SongCopyright::Payment) {
+        return Some(ApiError::VipRequired);
+    }
+    let code: i64 = get_val!(v, "code").unwrap_or(200);
+    match code {
+        404 => Some(ApiError::RegionBlocked),
+        _ => ApiError::from_code(code),
     }
-    Err(anyhow!("none"))
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
@@ -196,6 +217,12 @@ pub struct SongInfo {
     pub duration: u64,
     pub song_url: String,
     pub copyright: SongCopyright,
+    /// Popularity from 0 to 100
+    #[serde(default)]
+    pub popularity: f32,
+    /// Whether a Hi-Res version is available
+    #[serde(default)]
+    pub hires: bool,
 }
 
 impl PartialEq for SongInfo {
@@ -216,6 +243,8 @@ impl Default for SongInfo {
             duration: 0,
             song_url: String::new(),
             copyright: SongCopyright::Unknown,
+            popularity: 0.0,
+            hires: false,
         }
     }
 }
@@ -237,6 +266,7 @@ pub enum Parse {
     Radio,
     Intelligence,
     PersonalFm,
+    SimiSong,
 }
 
 pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
@@ -253,9 +283,17 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                 if array.is_empty() {
                     array = get_val!(value, "playlist", "tracks")?;
                 }
+                // 歌曲详情附带的版权信息，按歌曲 id 对应
+                let privileges: &Vec<Value> = get_val!(value, "privileges").unwrap_or(&list);
                 for v in array.iter() {
+                    let id: u64 = get_val!(v, "id")?;
+                    let copyright = privileges
+                        .iter()
+                        .find(|p| get_val!(@as u64, *p, "id").ok() == Some(id))
+                        .and_then(|p| SongCopyright::from_privilege(p).ok())
+                        .unwrap_or(SongCopyright::Unknown);
                     vec.push(SongInfo {
-                        id: get_val!(v, "id")?,
+                        id,
                         name: get_val!(v, "name")?,
                         singer: get_val!(@as &Vec<Value>, v, "ar")?
                             .first()
@@ -266,7 +304,9 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         pic_url: get_val!(v, "al", "picUrl").unwrap_or_default(),
                         duration: get_val!(v, "dt")?,
                         song_url: String::new(),
-                        copyright: SongCopyright::Unknown,
+                        copyright,
+                        popularity: get_val!(v, "pop").unwrap_or_default(),
+                        hires: !v["hr"].is_null(),
                     });
                 }
             }
@@ -283,6 +323,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "simpleSong", "dt")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -302,6 +344,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "duration")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -321,6 +365,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "duration")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -340,6 +386,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "duration")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -359,6 +407,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "dt")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -375,6 +425,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "dt")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -394,6 +446,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "dt")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -411,6 +465,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "duration")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                     num -= 1;
                 }
@@ -432,12 +488,20 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "songInfo", "dt")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
-            Parse::PersonalFm => {
+            Parse::PersonalFm | Parse::SimiSong => {
                 // 私人FM返回格式: { data: [{ id, name, artists: [{name}], album: {name, id, picUrl}, duration }] }
-                let array: &Vec<Value> = get_val!(value, "data")?;
+                // 相似歌曲同样格式, 列表在 songs 下
+                let key = if matches!(parse, Parse::SimiSong) {
+                    "songs"
+                } else {
+                    "data"
+                };
+                let array: &Vec<Value> = get_val!(value, key)?;
                 for v in array.iter() {
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
@@ -452,6 +516,8 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         duration: get_val!(v, "duration")?,
                         song_url: String::new(),
                         copyright: SongCopyright::Unknown,
+                        popularity: 0.0,
+                        hires: false,
                     });
                 }
             }
@@ -459,7 +525,7 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
         }
         return Ok(vec);
     }
-    Err(anyhow!("none"))
+    Err(code_error(code))
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -505,6 +571,8 @@ pub fn to_mix_detail(json: &Value) -> Result<PlayListDetail> {
                 duration: get_val!(v, "dt")?,
                 song_url: String::new(),
                 copyright: SongCopyright::from_privilege(p)?,
+                popularity: get_val!(v, "pop").unwrap_or_default(),
+                hires: !v["hr"].is_null(),
             });
         }
 
@@ -525,7 +593,7 @@ pub fn to_mix_detail(json: &Value) -> Result<PlayListDetail> {
             songs,
         });
     }
-    Err(anyhow!("none"))
+    Err(code_error(code))
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -640,14 +708,475 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
     Err(anyhow!("none"))
 }
 
-pub fn to_song_id_list(json: String) -> Result<Vec<u64>> {
+/// An album published by an artist
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AlbumRelease {
+    pub id: u64,
+    pub name: String,
+    pub cover_img_url: String,
+    pub artist_id: u64,
+    pub artist_name: String,
+    /// Publish time in milliseconds since the Unix epoch
+    pub publish_time: i64,
+}
+
+/// Parse the artists the user follows (`/weapi/artist/sublist`)
+pub fn to_singer_list(json: String) -> Result<Vec<SingerInfo>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "data")?;
+        let mut vec = Vec::new();
+        for v in array.iter() {
+            vec.push(SingerInfo {
+                id: get_val!(v, "id")?,
+                name: get_val!(v, "name")?,
+                pic_url: get_val!(v, "picUrl").unwrap_or_default(),
+            });
+        }
+        return Ok(vec);
+    }
+    Err(code_error(code))
+}
+
+/// Parse an artist's albums (`/weapi/artist/albums/{id}`), newest first
+pub fn to_album_releases(json: String) -> Result<Vec<AlbumRelease>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "hotAlbums")?;
+        let mut vec = Vec::new();
+        for v in array.iter() {
+            vec.push(AlbumRelease {
+                id: get_val!(v, "id")?,
+                name: get_val!(v, "name")?,
+                cover_img_url: get_val!(v, "picUrl").unwrap_or_default(),
+                artist_id: get_val!(v, "artist", "id")?,
+                artist_name: get_val!(v, "artist", "name").unwrap_or_default(),
+                publish_time: get_val!(v, "publishTime").unwrap_or_default(),
+            });
+        }
+        vec.sort_by(|a, b| b.publish_time.cmp(&a.publish_time));
+        return Ok(vec);
+    }
+    Err(code_error(code))
+}
+
+/// A person credited on a song; `id` is set when NCM links them to an artist
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreditName {
+    pub id: Option<u64>,
+    pub name: String,
+}
+
+/// One credit role (作词, 作曲, 编曲, ...) and the people credited for it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreditEntry {
+    pub role: String,
+    pub names: Vec<CreditName>,
+}
+
+/// Extended credits of a song
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SongCredits {
+    pub artists: Vec<CreditName>,
+    pub album_id: u64,
+    pub album: String,
+    /// Record company of the album
+    pub company: Option<String>,
+    pub entries: Vec<CreditEntry>,
+}
+
+/// Parse the performing artists and album of a song (`/weapi/v3/song/detail`)
+pub fn to_song_credits(json: String) -> Result<SongCredits> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let songs: &Vec<Value> = get_val!(value, "songs")?;
+        let song = songs.first().ok_or_else(|| anyhow!("none"))?;
+        let artists: &Vec<Value> = get_val!(song, "ar")?;
+        return Ok(SongCredits {
+            artists: artists
+                .iter()
+                .filter_map(|v| {
+                    let name: String = get_val!(v, "name").ok()?;
+                    let id: u64 = get_val!(v, "id").unwrap_or_default();
+                    Some(CreditName {
+                        id: (id > 0).then_some(id),
+                        name,
+                    })
+                })
+                .collect(),
+            album_id: get_val!(song, "al", "id").unwrap_or_default(),
+            album: get_val!(song, "al", "name").unwrap_or_default(),
+            ..Default::default()
+        });
+    }
+    Err(code_error(code))
+}
+
+/// Parse the record company of an album (`/weapi/v1/album/{id}`)
+pub fn to_album_company(json: String) -> Result<Option<String>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let company: String = get_val!(value, "album", "company").unwrap_or_default();
+        let company = company.trim();
+        return Ok((!company.is_empty()).then(|| company.to_string()));
+    }
+    Err(code_error(code))
+}
+
+/// Roles recognised in plain-text credit lines; anything else with a colon
+/// is treated as a lyric
+const CREDIT_ROLE_KEYWORDS: [&str; 24] = [
+    "作词", "作曲", "编曲", "制作", "监制", "混音", "母带", "录音", "和声", "配唱", "吉他", "贝斯",
+    "鼓", "键盘", "弦乐", "出品", "发行", "lyric", "compos", "arrang", "produc", "mix", "master",
+    "record",
+];
+
+const CREDIT_SEPARATORS: [char; 5] = ['/', '、', ',', '，', '&'];
+
+/// Collect the credit lines NCM puts at the top of its lyrics, either as
+/// JSON (`{"t":0,"c":[{"tx":"作词: "},{"tx":"name","or":"orpheus://nm/artist/home?id=1"}]}`)
+/// or as timestamped text (`[00:00.00] 作曲 : name`)
+pub fn to_lyric_credits(lines: &[String]) -> Vec<CreditEntry> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.starts_with('{') {
+                credit_from_json_line(line)
+            } else {
+                credit_from_text_line(line)
+            }
+        })
+        .collect()
+}
+
+fn credit_from_json_line(line: &str) -> Option<CreditEntry> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    let parts = value.get("c")?.as_array()?;
+    let (first, rest) = parts.split_first()?;
+    let (role, first_names) = split_credit(first.get("tx")?.as_str()?)?;
+
+    let mut names = Vec::new();
+    push_credit_names(&mut names, first_names, None);
+    for part in rest {
+        let Some(text) = part.get("tx").and_then(Value::as_str) else {
+            continue;
+        };
+        let id = part
+            .get("or")
+            .and_then(Value::as_str)
+            .and_then(artist_id_from_link);
+        push_credit_names(&mut names, text, id);
+    }
+    (!names.is_empty()).then_some(CreditEntry { role, names })
+}
+
+fn credit_from_text_line(line: &str) -> Option<CreditEntry> {
+    let mut rest = line;
+    while rest.starts_with('[') {
+        rest = rest.split_once(']')?.1.trim_start();
+    }
+    let (role, text) = split_credit(rest)?;
+    let lower = role.to_lowercase();
+    if !CREDIT_ROLE_KEYWORDS.iter().any(|k| lower.contains(k)) {
+        return None;
+    }
+    let mut names = Vec::new();
+    push_credit_names(&mut names, text, None);
+    (!names.is_empty()).then_some(CreditEntry { role, names })
+}
+
+/// Split `role : names` at the first half- or full-width colon
+fn split_credit(text: &str) -> Option<(String, &str)> {
+    let (role, names) = text.split_once([':', '：'])?;
+    let role = role.trim();
+    if role.is_empty() || role.chars().count() > 12 {
+        return None;
+    }
+    Some((role.to_string(), names))
+}
+
+/// Add the names in `text`; a linked part is one artist, unlinked text may
+/// list several separated by `/`, `、` or commas
+fn push_credit_names(names: &mut Vec<CreditName>, text: &str, id: Option<u64>) {
+    if id.is_some() {
+        let name = text.trim().trim_matches(CREDIT_SEPARATORS).trim();
+        if !name.is_empty() {
+            names.push(CreditName {
+                id,
+                name: name.to_string(),
+            });
+        }
+        return;
+    }
+    names.extend(
+        text.split(CREDIT_SEPARATORS)
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| CreditName {
+                id: None,
+                name: name.to_string(),
+            }),
+    );
+}
+
+/// Artist id from an `orpheus://nm/artist/home?id=123` link
+fn artist_id_from_link(link: &str) -> Option<u64> {
+    if !link.contains("artist") {
+        return None;
+    }
+    let (_, query) = link.split_once("id=")?;
+    let digits: String = query.chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// 喜欢的音乐 id 列表
+#[derive(Debug, Clone, Default)]
+pub struct LikedSongIds {
+    pub ids: Vec<u64>,
+    /// 列表最后变动的时间（秒），没有时为 0
+    pub check_point: i64,
+}
+
+pub fn to_song_id_list(json: String) -> Result<LikedSongIds> {
     let value = &serde_json::from_str::<Value>(&json)?;
     let code: i64 = get_val!(value, "code")?;
     if code == 200 {
         let id_array: &Vec<Value> = get_val!(value, "ids")?;
-        return id_array.iter().map(u64::dval).collect();
+        let check_point: i64 = get_val!(value, "checkPoint").unwrap_or(0);
+        return Ok(LikedSongIds {
+            ids: id_array.iter().map(u64::dval).collect::<Result<_>>()?,
+            check_point: check_point / 1000,
+        });
+    }
+    Err(code_error(code))
+}
+
+/// 用户主页信息
+#[derive(Debug, Clone, Default)]
+pub struct UserProfile {
+    pub user_id: u64,
+    pub nickname: String,
+    pub avatar_url: String,
+    pub signature: String,
+    /// 等级，0 到 10
+    pub level: u32,
+    /// 累计听歌数
+    pub listen_songs: u64,
+    pub follows: u64,
+    pub followeds: u64,
+    pub vip_type: i32,
+}
+
+/// Parse a user's profile (`/weapi/v1/user/detail/{uid}`)
+pub fn to_user_profile(json: String) -> Result<UserProfile> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(UserProfile {
+            user_id: get_val!(value, "profile", "userId")?,
+            nickname: get_val!(value, "profile", "nickname")?,
+            avatar_url: get_val!(value, "profile", "avatarUrl").unwrap_or_default(),
+            signature: get_val!(value, "profile", "signature").unwrap_or_default(),
+            level: get_val!(value, "level").unwrap_or_default(),
+            listen_songs: get_val!(value, "listenSongs").unwrap_or_default(),
+            follows: get_val!(value, "profile", "follows").unwrap_or_default(),
+            followeds: get_val!(value, "profile", "followeds").unwrap_or_default(),
+            vip_type: get_val!(value, "profile", "vipType").unwrap_or_default(),
+        });
+    }
+    Err(code_error(code))
+}
+
+/// 听歌排行中的一首歌
+#[derive(Debug, Clone)]
+pub struct PlayRecord {
+    pub song: SongInfo,
+    pub play_count: u64,
+    /// 相对排行第一的分数，0 到 100
+    pub score: u32,
+}
+
+/// Parse a user's listening rank (`/weapi/v1/play/record`)
+///
+/// Returns `None` when the user keeps their rank private.
+pub fn to_play_records(json: String) -> Result<Option<Vec<PlayRecord>>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let list = vec![];
+        let mut array: &Vec<Value> = get_val!(value, "weekData").unwrap_or(&list);
+        if array.is_empty() {
+            array = get_val!(value, "allData").unwrap_or(&list);
+        }
+        let unk = "unknown".to_string();
+        let mut records = Vec::with_capacity(array.len());
+        for v in array.iter() {
+            let song = &v["song"];
+            records.push(PlayRecord {
+                song: SongInfo {
+                    id: get_val!(song, "id")?,
+                    name: get_val!(song, "name")?,
+                    singer: get_val!(@as &Vec<Value>, song, "ar")?
+                        .first()
+                        .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
+                        .unwrap_or_else(|| unk.clone()),
+                    album: get_val!(song, "al", "name").unwrap_or_else(|_| unk.clone()),
+                    album_id: get_val!(song, "al", "id").unwrap_or_default(),
+                    pic_url: get_val!(song, "al", "picUrl").unwrap_or_default(),
+                    duration: get_val!(song, "dt").unwrap_or_default(),
+                    ..Default::default()
+                },
+                play_count: get_val!(v, "playCount").unwrap_or_default(),
+                score: get_val!(v, "score").unwrap_or_default(),
+            });
+        }
+        return Ok(Some(records));
+    }
+    // 用户隐藏了听歌排行
+    if code == -2 {
+        return Ok(None);
+    }
+    Err(code_error(code))
+}
+
+/// 用户创建和收藏的歌单
+#[derive(Debug, Clone, Default)]
+pub struct UserPlaylists {
+    pub created: Vec<SongList>,
+    pub subscribed: Vec<SongList>,
+}
+
+/// Parse a user's playlists (`/weapi/user/playlist`), split by whether the
+/// user made them
+pub fn to_user_playlists(json: String, uid: u64) -> Result<UserPlaylists> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let array: &Vec<Value> = get_val!(value, "playlist")?;
+        let mut playlists = UserPlaylists::default();
+        for v in array.iter() {
+            let creator_id: u64 = get_val!(v, "creator", "userId").unwrap_or_default();
+            let playlist = SongList {
+                id: get_val!(v, "id")?,
+                name: get_val!(v, "name")?,
+                cover_img_url: get_val!(v, "coverImgUrl")?,
+                author: get_val!(v, "creator", "nickname")?,
+            };
+            if creator_id == uid {
+                playlists.created.push(playlist);
+            } else {
+                playlists.subscribed.push(playlist);
+            }
+        }
+        return Ok(playlists);
+    }
+    Err(code_error(code))
+}
+
+/// Why an NCM request failed, for failures the user can do something about
+///
+/// Travels inside the `anyhow::Error` returned by client calls; use
+/// [`ApiError::of`] to get it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiError {
+    /// 登录态已失效（接口返回 301，或账号信息为空）
+    AuthExpired,
+    /// 请求过于频繁，被风控拦截
+    RateLimited,
+    /// 需要先在网页端完成安全验证（验证码）
+    CaptchaRequired,
+    /// 当前地区无版权
+    RegionBlocked,
+    /// 需要会员或单独购买
+    VipRequired,
+    /// 连不上服务器或请求超时
+    Network,
+}
+
+impl ApiError {
+    /// Classify a response code, `None` for codes without a specific meaning
+    pub fn from_code(code: i64) -> Option<Self> {
+        match code {
+            c if c == NEED_LOGIN_CODE as i64 => Some(Self::AuthExpired),
+            405 | 429 | -460 => Some(Self::RateLimited),
+            -462 => Some(Self::CaptchaRequired),
+            -110 | -447 => Some(Self::RegionBlocked),
+            _ => None,
+        }
+    }
+
+    /// The classified failure inside an error returned by a client call
+    pub fn of(err: &anyhow::Error) -> Option<Self> {
+        err.downcast_ref::<Self>().copied()
+    }
+
+    /// Whether `err` means the saved login is no longer valid
+    pub fn is_auth_expired(err: &anyhow::Error) -> bool {
+        Self::of(err) == Some(Self::AuthExpired)
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::AuthExpired => "login session expired",
+            Self::RateLimited => "too many requests",
+            Self::CaptchaRequired => "verification required",
+            Self::RegionBlocked => "not available in this region",
+            Self::VipRequired => "VIP required",
+            Self::Network => "network unreachable",
+        })
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Error for a response code that is not 200
+fn code_error(code: i64) -> anyhow::Error {
+    match ApiError::from_code(code) {
+        Some(err) => err.into(),
+        None => anyhow!("none"),
+    }
+}
+
+/// 需要登录
+pub const NEED_LOGIN_CODE: i32 = 301;
+
+/// 今日已签到
+const SIGNIN_REPEATED_CODE: i32 = -2;
+
+/// 每日签到结果：领取的云贝数，今日已签到时为 `None`
+pub fn to_daily_signin(json: String) -> Result<Option<i64>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    match code {
+        200 => Ok(Some(get_val!(value, "point").unwrap_or(0))),
+        SIGNIN_REPEATED_CODE => Ok(None),
+        NEED_LOGIN_CODE => Err(ApiError::AuthExpired.into()),
+        _ => {
+            let msg: String = get_val!(value, "msg").unwrap_or_default();
+            Err(anyhow!("Daily signin failed ({}): {}", code, msg))
+        }
+    }
+}
+
+/// 新建歌单的 id
+pub fn to_created_playlist(json: String) -> Result<u64> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    match code {
+        200 => Ok(get_val!(value, "id")?),
+        NEED_LOGIN_CODE => Err(ApiError::AuthExpired.into()),
+        _ => {
+            let msg: String = get_val!(value, "message").unwrap_or_default();
+            Err(anyhow!("Playlist create failed ({}): {}", code, msg))
+        }
     }
-    Err(anyhow!("none"))
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -696,6 +1225,9 @@ pub struct LoginInfo {
 pub fn to_login_info(json: String) -> Result<LoginInfo> {
     let value = &serde_json::from_str::<Value>(&json)?;
     let code: i32 = get_val!(value, "code")?;
+    if code == NEED_LOGIN_CODE || (code == 200 && value["profile"].is_null()) {
+        return Err(ApiError::AuthExpired.into());
+    }
     if code.eq(&200) {
         return Ok(LoginInfo {
             code,
@@ -724,6 +1256,8 @@ pub struct BannersInfo {
     pub target_id: u64,
     pub target_type: TargetType,
     pub type_title: String,
+    /// 外链地址，仅网页类型的 banner 有值
+    pub url: String,
 }
 
 pub fn to_banners_info(json: String) -> Result<Vec<BannersInfo>> {
@@ -738,12 +1272,13 @@ pub fn to_banners_info(json: String) -> Result<Vec<BannersInfo>> {
                 target_id: get_val!(v, "targetId")?,
                 target_type: TargetType::from(get_val!(@as i32, v, "targetType")?),
                 type_title: get_val!(v, "typeTitle").unwrap_or_default(),
+                url: get_val!(v, "url").unwrap_or_default(),
             };
             vec.push(bi);
         }
         return Ok(vec);
     }
-    Err(anyhow!("none"))
+    Err(code_error(code.into()))
 }
 
 pub fn to_unikey(json: String) -> Result<String> {
@@ -797,6 +1332,9 @@ pub enum Method {
 pub enum TargetType {
     Song,
     Album,
+    Playlist,
+    Mv,
+    Web,
     Unknown,
 }
 
@@ -805,6 +1343,9 @@ impl From<i32> for TargetType {
         match t {
             1 => Self::Song,
             10 => Self::Album,
+            1000 => Self::Playlist,
+            1004 => Self::Mv,
+            3000 => Self::Web,
             _ => Self::Unknown,
         }
     }
@@ -875,6 +1416,8 @@ pub fn to_search_response(json: String, search_type: SearchType) -> Result<Searc
                     duration: get_val!(v, "dt")?,
                     song_url: String::new(),
                     copyright: SongCopyright::Unknown,
+                    popularity: 0.0,
+                    hires: false,
                 });
             }
         }
@@ -922,3 +1465,238 @@ pub fn to_search_response(json: String, search_type: SearchType) -> Result<Searc
     
     Ok(response)
 }
+
+/// 热搜关键词
+pub fn to_hot_searches(json: String) -> Result<Vec<String>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(anyhow!("Hot search API returned code: {}", code));
+    }
+    let hots: &Vec<Value> = get_val!(value, "result", "hots")?;
+    Ok(hots
+        .iter()
+        .filter_map(|v| get_val!(v, "first").ok())
+        .collect())
+}
+
+/// 输入联想的关键词，无匹配时为空
+pub fn to_search_suggestions(json: String) -> Result<Vec<String>> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(anyhow!("Search suggest API returned code: {}", code));
+    }
+    let empty_vec = vec![];
+    let matches: &Vec<Value> = get_val!(value, "result", "allMatch").unwrap_or(&empty_vec);
+    Ok(matches
+        .iter()
+        .filter_map(|v| get_val!(v, "keyword").ok())
+        .collect())
+}
+
+// ============ Comments ============
+
+/// Where a comment thread hangs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentThread {
+    Song(u64),
+    Album(u64),
+    Playlist(u64),
+}
+
+impl CommentThread {
+    /// Thread id the comment endpoints take
+    pub fn id(&self) -> String {
+        match self {
+            Self::Song(id) => format!("R_SO_4_{}", id),
+            Self::Album(id) => format!("R_AL_3_{}", id),
+            Self::Playlist(id) => format!("A_PL_0_{}", id),
+        }
+    }
+}
+
+/// 一条评论
+#[derive(Debug, Clone, Default)]
+pub struct Comment {
+    pub id: u64,
+    pub user_id: u64,
+    pub nickname: String,
+    pub avatar_url: String,
+    pub content: String,
+    /// 发表时间，毫秒时间戳
+    pub time: i64,
+    pub liked_count: u64,
+    /// 当前账号是否已点赞
+    pub liked: bool,
+    /// 被回复的评论：昵称和内容
+    pub replied: Option<(String, String)>,
+}
+
+/// 一页评论
+#[derive(Debug, Clone, Default)]
+pub struct CommentPage {
+    /// 热门评论，只在第一页返回
+    pub hot: Vec<Comment>,
+    pub comments: Vec<Comment>,
+    pub total: u64,
+    pub has_more: bool,
+}
+
+fn to_comment(v: &Value) -> Result<Comment> {
+    let replied = get_val!(@as &Vec<Value>, v, "beReplied")
+        .ok()
+        .and_then(|list| list.first())
+        .and_then(|r| {
+            Some((
+                get_val!(r, "user", "nickname").ok()?,
+                get_val!(r, "content").unwrap_or_default(),
+            ))
+        });
+    Ok(Comment {
+        id: get_val!(v, "commentId")?,
+        user_id: get_val!(v, "user", "userId").unwrap_or_default(),
+        nickname: get_val!(v, "user", "nickname").unwrap_or_default(),
+        avatar_url: get_val!(v, "user", "avatarUrl").unwrap_or_default(),
+        content: get_val!(v, "content").unwrap_or_default(),
+        time: get_val!(v, "time").unwrap_or_default(),
+        liked_count: get_val!(v, "likedCount").unwrap_or_default(),
+        liked: get_val!(v, "liked").unwrap_or_default(),
+        replied,
+    })
+}
+
+fn to_comment_list(value: &Value, key: &str) -> Vec<Comment> {
+    get_val!(@as &Vec<Value>, value, key)
+        .map(|list| list.iter().filter_map(|v| to_comment(v).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Parse a page of a comment thread (`/weapi/v1/resource/comments/{thread}`)
+pub fn to_comment_page(json: String) -> Result<CommentPage> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(code_error(code));
+    }
+    Ok(CommentPage {
+        hot: to_comment_list(value, "hotComments"),
+        comments: to_comment_list(value, "comments"),
+        total: get_val!(value, "total").unwrap_or_default(),
+        has_more: get_val!(value, "more").unwrap_or_default(),
+    })
+}
+
+/// Parse the comment a post or reply created
+pub fn to_posted_comment(json: String) -> Result<Comment> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(comment_error(value, code));
+    }
+    to_comment(get_val!(value, "comment")?)
+}
+
+/// Check the answer to liking or deleting a comment
+pub fn to_comment_done(json: String) -> Result<()> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code != 200 {
+        return Err(comment_error(value, code));
+    }
+    Ok(())
+}
+
+/// Error for a refused comment action, keeping the server's reason (a
+/// blocked word, a comment already deleted) when the code has no meaning
+/// of its own
+fn comment_error(value: &Value, code: i64) -> anyhow::Error {
+    if let Some(err) = ApiError::from_code(code) {
+        return err.into();
+    }
+    let msg: String = get_val!(value, "message")
+        .or_else(|_| get_val!(value, "msg"))
+        .unwrap_or_default();
+    anyhow!("Comment action failed ({}): {}", code, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_codes_are_classified() {
+        let err = to_lyric(r#"{"code":301}"#.to_string()).unwrap_err();
+        assert_eq!(ApiError::of(&err), Some(ApiError::AuthExpired));
+
+        let err = to_lyric(r#"{"code":-460}"#.to_string()).unwrap_err();
+        assert_eq!(ApiError::of(&err), Some(ApiError::RateLimited));
+
+        let err = to_lyric(r#"{"code":500}"#.to_string()).unwrap_err();
+        assert_eq!(ApiError::of(&err), None);
+
+        let err = to_comment_done(r#"{"code":-462,"message":"verify"}"#.to_string()).unwrap_err();
+        assert_eq!(ApiError::of(&err), Some(ApiError::CaptchaRequired));
+    }
+
+    #[test]
+    fn test_comment_page() {
+        let json = r#"{"code":200,"total":2,"more":true,"hotComments":[],"comments":[
+            {"commentId":11,"content":"first","time":1700000000000,"likedCount":3,"liked":true,
+             "user":{"userId":7,"nickname":"a","avatarUrl":"http://p1.music.126.net/a.jpg"},
+             "beReplied":[]},
+            {"commentId":12,"content":"reply","time":1700000001000,"likedCount":0,"liked":false,
+             "user":{"userId":8,"nickname":"b"},
+             "beReplied":[{"content":"first","user":{"nickname":"a"}}]}
+        ]}"#;
+        let page = to_comment_page(json.to_string()).unwrap();
+        assert!(page.has_more);
+        assert_eq!(page.total, 2);
+        assert_eq!(page.comments.len(), 2);
+        assert!(page.comments[0].liked);
+        assert_eq!(page.comments[0].replied, None);
+        assert_eq!(
+            page.comments[1].replied,
+            Some(("a".to_string(), "first".to_string()))
+        );
+
+        let refused = r#"{"code":400,"message":"内容包含敏感词"}"#;
+        let err = to_posted_comment(refused.to_string()).unwrap_err();
+        assert_eq!(ApiError::of(&err), None);
+        assert!(err.to_string().contains("敏感词"));
+    }
+
+    #[test]
+    fn test_song_popularity_and_hires() {
+        let json = r#"{"code":200,"songs":[
+            {"id":1,"name":"a","ar":[{"name":"x"}],"al":{"id":2,"name":"b"},"dt":1000,
+             "pop":85.0,"hr":{"br":1999000,"size":1}},
+            {"id":3,"name":"c","ar":[],"al":{"id":4,"name":"d"},"dt":1000,"hr":null}
+        ],"privileges":[]}"#;
+        let songs = to_song_info(json.to_string(), Parse::Usl).unwrap();
+        assert_eq!(songs[0].popularity, 85.0);
+        assert!(songs[0].hires);
+        assert_eq!(songs[1].popularity, 0.0);
+        assert!(!songs[1].hires);
+    }
+
+    #[test]
+    fn test_missing_song_url_reason() {
+        let vip = r#"{"code":200,"data":[{"id":1,"url":null,"br":0,"fee":1,"code":200}]}"#;
+        let err = to_song_url(vip.to_string()).unwrap_err();
+        assert_eq!(ApiError::of(&err), Some(ApiError::VipRequired));
+
+        let blocked = r#"{"code":200,"data":[{"id":1,"url":null,"br":0,"fee":0,"code":404}]}"#;
+        let err = to_song_url(blocked.to_string()).unwrap_err();
+        assert_eq!(ApiError::of(&err), Some(ApiError::RegionBlocked));
+
+        // One playable song is enough for a batch to succeed
+        let mixed = r#"{"code":200,"data":[
+            {"id":1,"url":null,"br":0,"fee":1,"code":200},
+            {"id":2,"url":"http://m.music.126.net/a.mp3","br":320000,"fee":0,"code":200}
+        ]}"#;
+        let urls = to_song_url(mixed.to_string()).unwrap();
+        assert_eq!(urls.len(), 1);
+        assert_eq!(urls[0].id, 2);
+    }
+}
@@ -10,55 +10,86 @@ use iced::{Task, Theme};
 use std::sync::Arc;
 
 use crate::i18n::{Language, Locale};
-pub use message::{IconId, Message, SettingsSection, SidebarId};
+pub use message::{IconId, Message, SettingsSection, SharedElement, SidebarId};
 pub use state::{
-    App, CoreState, DiscoverPageState, DiscoverViewMode, HomePageState, LibraryState, Route,
-    SearchPageState, SearchTab, UiState, UserInfo,
+    AnnualReportPageState, App, BlocklistPageState, CoreState, CoverViewer, DiscoverPageState,
+    DiscoverViewMode, ExportDialog, ExportState, HealthDialog, HeardSong, HomePageState,
+    LibraryState, ListenState, LogsPageState, LyricsShareDialog, LyricsShareStage,
+    PluginsPageState, ProfilePageState, PublishDialog, PublishStage, Route, SearchPageState,
+    SearchSource, SearchTab, SongInfoDialog, TrashPageState, UiState, UpdateStatus, UserInfo,
+    WhatsNew,
 };
 
 impl App {
     /// Create new application instance
+    ///
+    /// Only what the first frame needs happens here; everything else waits
+    /// for [`Message::FirstFrame`] (see [`App::deferred_startup`]).
     pub fn new() -> (Self, Task<Message>) {
+        use crate::features::startup;
+
         // 0. Clean up orphan temp files from interrupted downloads
-        crate::cache::cleanup_temp_files();
+        startup::stage("temp cleanup", crate::cache::cleanup_temp_files);
 
         // 1. Load settings first to initialize locale correctly
-        let settings = crate::features::Settings::load();
-        let locale = {
-            let lang = if settings.display.language == "zh" {
-                Language::Chinese
-            } else {
-                Language::English
-            };
-            Locale::new(lang)
-        };
+        let settings = startup::stage("settings", crate::features::Settings::load);
+        let locale = Locale::new(Language::from_code(&settings.display.language));
+
+        crate::features::bandwidth::set_limit_kbps(settings.network.bandwidth_limit_kbps);
 
         // 2. Initialize audio system
-        let (audio, audio_chain, audio_listener_task) = helpers::init_audio(&settings);
+        let (audio, audio_chain, audio_listener_task) =
+            startup::stage("audio", || helpers::init_audio(&settings));
+
+        let start_minimized = settings.system.start_minimized;
 
         // 3. Initialize sub-states
-        let core = CoreState::new(settings, locale, audio, audio_chain);
+        let mut core = CoreState::new(settings, locale, audio, audio_chain);
+        core.window_hidden = start_minimized;
         let library = LibraryState::default();
-        let ui = UiState::new();
+        let mut ui = UiState::new();
+
+        // Offer to restore the session if the previous run crashed
+        if let Some(report) = crate::features::crash::take_pending() {
+            tracing::warn!("Previous session ended with a crash");
+            ui.dialogs.crash_report = Some(report);
+            ui.dialogs.crash_animation.start();
+        }
 
-        let app = Self { core, library, ui };
+        let mut app = Self { core, library, ui };
 
-        // 4. Open main window
-        let (window_id, open_window) =
-            iced::window::open(crate::platform::window::window_settings());
+        // 4. Open main window (hidden when starting minimized to tray)
+        let (window_id, open_window) = iced::window::open(iced::window::Settings {
+            visible: !start_minimized,
+            ..crate::platform::window::window_settings()
+        });
         tracing::info!("Opening main window with id: {:?}", window_id);
+        app.core.main_window = Some(window_id);
 
-        // 5. Initialize async tasks
+        // A hidden window draws no frame to wait for
+        let deferred_task = if start_minimized {
+            app.deferred_startup()
+        } else {
+            Task::none()
+        };
+
+        // 5. Initialize async tasks the first frame depends on
         let init_task = Task::batch([
             open_window.discard(),
-            Task::perform(helpers::init_database(), |result| match result {
-                Ok(db) => Message::DatabaseReady(Arc::new(db)),
-                Err(e) => Message::DatabaseError(e.to_string()),
-            }),
-            Task::perform(helpers::init_cover_cache(), |result| match result {
-                Ok(cache) => Message::CoverCacheReady(Arc::new(cache)),
-                Err(e) => Message::DatabaseError(format!("Cover cache error: {}", e)),
-            }),
+            Task::perform(
+                startup::stage_async("database", helpers::init_database()),
+                |result| match result {
+                    Ok(db) => Message::DatabaseReady(Arc::new(db)),
+                    Err(e) => Message::DatabaseError(e.to_string()),
+                },
+            ),
+            Task::perform(
+                startup::stage_async("cover cache", helpers::init_cover_cache()),
+                |result| match result {
+                    Ok(cache) => Message::CoverCacheReady(Arc::new(cache)),
+                    Err(e) => Message::DatabaseError(format!("Cover cache error: {}", e)),
+                },
+            ),
             crate::platform::tray::init_task(Message::TrayStarted),
             Task::perform(helpers::init_mpris(), |result| match result {
                 Ok((handle, rx)) => Message::MprisStartedWithHandle(handle, rx),
@@ -67,17 +98,40 @@ impl App {
                     Message::Noop
                 }
             }),
-            Task::perform(helpers::init_font_system(), |font_system| {
-                Message::LyricsFontSystemReady(font_system)
-            }),
-            Task::done(Message::TryAutoLogin(0)),
-            Task::done(Message::EnforceCacheLimit),
             audio_listener_task,
+            deferred_task,
         ]);
 
         (app, init_task)
     }
 
+    /// Start the initialization that can wait until the window is up
+    ///
+    /// Runs once, on the first frame or right away when starting minimized.
+    fn deferred_startup(&mut self) -> Task<Message> {
+        use crate::features::startup;
+
+        self.core.startup_pending = false;
+
+        // Show what's new after an update and check for a newer release
+        let updater_task = startup::stage("updater", || self.init_updater());
+
+        Task::batch([
+            Task::perform(
+                startup::stage_async("lyrics fonts", helpers::init_font_system()),
+                Message::LyricsFontSystemReady,
+            ),
+            // Builds the NCM client, then loads the homepage
+            Task::done(Message::TryAutoLogin(0)),
+            Task::done(Message::EnforceCacheLimit),
+            Task::done(Message::ReloadPlugins),
+            Task::done(Message::DetectMeteredConnection),
+            Task::done(Message::CheckNetworkRoute),
+            Task::run(crate::platform::power::events(), Message::PowerEvent),
+            updater_task,
+        ])
+    }
+
     /// Application theme for a specific window
     pub fn theme(&self, _window_id: iced::window::Id) -> Theme {
         // Access settings via core state
@@ -108,6 +162,10 @@ impl App {
         // Check if power saving mode is enabled
         let power_saving = self.core.settings.display.power_saving_mode;
 
+        // Timers stay off while the system sleeps and start afresh on wake,
+        // rather than catching up on every tick missed in between
+        let suspended = self.core.suspended;
+
         // 1. UI animations (disabled in power saving mode)
         let has_animations = if power_saving {
             false
@@ -123,11 +181,11 @@ impl App {
             .map(|p| p.is_playing())
             .unwrap_or(false);
 
-        // 3. Lyrics page needs continuous updates for smooth scrolling
+        // 3. Lyrics page, only while something on it is moving
         let lyrics_needs_frames = if power_saving {
             false
         } else {
-            self.ui.lyrics.is_open
+            self.lyrics_needs_frames(is_playing)
         };
 
         // 4. Audio engine visualization
@@ -137,6 +195,15 @@ impl App {
             matches!(self.ui.current_route, Route::AudioEngine) && is_playing
         };
 
+        // 4b. Artwork backdrop behind playlist headers
+        let backdrop_needs_frames = !power_saving && self.playlist_backdrop_needs_frames();
+
+        // 4c. Shimmer of the playlist skeleton while its songs load
+        let skeleton_needs_frames = !power_saving && self.playlist_skeleton_needs_frames();
+
+        // 4d. Playlist page easing into a new cover's colors
+        let tint_needs_frames = !power_saving && self.playlist_tint_needs_frames();
+
         // 5. Keyboard events
         let keyboard_sub = if !self.core.window_hidden {
             keyboard::listen().filter_map(|event| match event {
@@ -150,19 +217,25 @@ impl App {
         };
 
         // 6. Window events
-        let close_request_sub = iced::window::close_requests().map(|_id| Message::RequestClose);
+        let close_request_sub = iced::window::close_requests().map(Message::WindowCloseRequested);
         let close_event_sub =
             iced::window::close_events().map(|_id| Message::WindowOperationComplete);
 
         // 7. Animation subscription (165fps)
-        let animation_sub = if has_animations || lyrics_needs_frames || audio_engine_needs_frames {
+        let animation_sub = if has_animations
+            || lyrics_needs_frames
+            || audio_engine_needs_frames
+            || backdrop_needs_frames
+            || skeleton_needs_frames
+            || tint_needs_frames
+        {
             iced::time::every(Duration::from_micros(6060)).map(|_| Message::AnimationTick)
         } else {
             iced::Subscription::none()
         };
 
         // 8. Playback monitoring (100ms/500ms intervals)
-        let playback_sub = if is_playing {
+        let playback_sub = if is_playing && !suspended {
             let interval = if power_saving { 500 } else { 100 };
             iced::time::every(Duration::from_millis(interval)).map(|_| Message::PlaybackTick)
         } else {
@@ -170,38 +243,132 @@ impl App {
         };
 
         // 9. Carousel auto-advance (5s)
-        let carousel_sub =
-            if !power_saving && !self.ui.home.banners.is_empty() && !self.core.window_hidden {
-                iced::time::every(Duration::from_secs(5)).map(|_| Message::CarouselTick)
-            } else {
-                iced::Subscription::none()
-            };
+        let carousel_sub = if !power_saving
+            && !suspended
+            && !self.ui.home.banners.is_empty()
+            && !self.core.window_hidden
+        {
+            iced::time::every(Duration::from_secs(5)).map(|_| Message::CarouselTick)
+        } else {
+            iced::Subscription::none()
+        };
 
         // 10. Window resize
         let resize_sub =
-            iced::window::resize_events().map(|(_id, size)| Message::WindowResized(size));
+            iced::window::resize_events().map(|(id, size)| Message::WindowResized(id, size));
 
         // 11. Mouse events for window dragging and sidebar resize
         let mouse_sub = if !self.core.window_hidden {
-            iced::event::listen().filter_map(|event| match event {
-                iced::Event::Mouse(iced::mouse::Event::ButtonPressed(
-                    iced::mouse::Button::Left,
-                )) => Some(Message::MousePressed),
-                iced::Event::Mouse(iced::mouse::Event::ButtonReleased(
-                    iced::mouse::Button::Left,
-                )) => Some(Message::MouseReleased),
-                iced::Event::Mouse(iced::mouse::Event::CursorMoved { position }) => {
-                    Some(Message::MouseMoved(position))
+            iced::event::listen_with(|event, status, id| {
+                if status == iced::event::Status::Captured {
+                    return None;
+                }
+                match event {
+                    iced::Event::Mouse(iced::mouse::Event::ButtonPressed(
+                        iced::mouse::Button::Left,
+                    )) => Some(Message::MousePressed(id)),
+                    iced::Event::Mouse(iced::mouse::Event::ButtonReleased(
+                        iced::mouse::Button::Left,
+                    )) => Some(Message::MouseReleased),
+                    iced::Event::Mouse(iced::mouse::Event::CursorMoved { position }) => {
+                        Some(Message::MouseMoved(id, position))
+                    }
+                    _ => None,
                 }
-                _ => None,
             })
         } else {
             iced::Subscription::none()
         };
 
-        // 12. Player events - handled via Task::run in initialization, not subscription
+        // 12. Metered connection status (only reported on Windows)
+        let metered_sub = if cfg!(target_os = "windows")
+            && !suspended
+            && self.core.settings.network.metered_mode == crate::features::MeteredMode::Auto
+        {
+            iced::time::every(Duration::from_secs(300)).map(|_| Message::DetectMeteredConnection)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 12b. Network changes, so streams can reconnect on the new one
+        let network_sub = if !suspended {
+            iced::time::every(Duration::from_secs(5)).map(|_| Message::CheckNetworkRoute)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 13. Player events - handled via Task::run in initialization, not subscription
         // (see handle_player_event_receiver_ready message)
 
+        // 14. First frame, which starts deferred initialization
+        let first_frame_sub = if self.core.startup_pending && !self.core.window_hidden {
+            iced::window::frames().map(|_| Message::FirstFrame)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 15. Memory sampling: live while the overlay is open, otherwise
+        // just often enough to enforce the soft budgets
+        let memory_interval = if self.ui.memory.overlay_visible && !self.core.window_hidden {
+            Duration::from_secs(2)
+        } else {
+            Duration::from_secs(60)
+        };
+        let memory_sub = if !suspended {
+            iced::time::every(memory_interval).map(|_| Message::SampleMemory)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 16. NCM session keep-alive, well inside the token lifetime so a
+        // dead login is noticed before the next like or subscribe
+        let session_sub = if self.core.ncm_client.is_some() && !suspended {
+            iced::time::every(Duration::from_secs(30 * 60)).map(|_| Message::RefreshSession)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 17. Output latency calibration clicks
+        let calibration_sub = if self.ui.latency_calibration.is_some() {
+            iced::time::every(crate::features::latency::BEAT_INTERVAL)
+                .map(|_| Message::LatencyCalibrationBeat)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 18. Listen mode lyrics, following the song another app plays
+        let listen_sub = if self.ui.listen.heard.is_some() && !self.core.window_hidden && !suspended
+        {
+            iced::time::every(Duration::from_millis(250)).map(|_| Message::ListenTick)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 19. Scheduled playback, kept running while hidden to the tray
+        let schedule_sub = if self.core.settings.schedules.iter().any(|s| s.enabled) && !suspended {
+            iced::time::every(Duration::from_secs(15)).map(|_| Message::CheckSchedules)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 20. Volume fade-in after a schedule starts
+        let ramp_sub = if self.ui.schedule_ramp.is_some() {
+            iced::time::every(Duration::from_secs(1)).map(|_| Message::ScheduleRampTick)
+        } else {
+            iced::Subscription::none()
+        };
+
+        // 21. Microphone use by call apps, kept running while hidden
+        let call_sub = if self.core.settings.playback.call_ducking.action
+            != crate::features::CallAction::Off
+            && !suspended
+        {
+            iced::time::every(crate::features::call_ducking::CHECK_INTERVAL)
+                .map(|_| Message::CheckMicrophone)
+        } else {
+            iced::Subscription::none()
+        };
+
         // Batch all subscriptions
         iced::Subscription::batch([
             keyboard_sub,
@@ -212,6 +379,16 @@ impl App {
             carousel_sub,
             resize_sub,
             mouse_sub,
+            metered_sub,
+            network_sub,
+            first_frame_sub,
+            memory_sub,
+            session_sub,
+            calibration_sub,
+            listen_sub,
+            schedule_sub,
+            ramp_sub,
+            call_sub,
         ])
     }
 }
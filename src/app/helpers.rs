@@ -1,17 +1,29 @@
 //! Async helper functions for database operations
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use iced::Task;
 
 use crate::app::Message;
 use crate::audio::chain::AudioProcessingChain;
-use crate::database::{Database, DbPlaybackState, DbPlaylist, DbSong, NewPlaylist};
+use crate::database::{
+    Database, DbPlaybackState, DbPlaylist, DbSong, DbTrashedPlaylist, DbTrashedSong, NewPlaylist,
+    SongId,
+};
 use crate::features::PlayMode;
+use crate::features::blocklist::{BlockedSong, Blocklist};
+use crate::features::favorites::{Favorite, Favorites, LikedMirror};
 use crate::features::import::{CoverCache, default_cache_dir};
+use crate::features::jobs::{Category, Job};
+use crate::features::media::{mosaic, palette};
+use crate::features::song_analysis::SongAnalysis;
+use crate::features::song_source::SongSource;
+use crate::i18n::{Key, Locale};
 use crate::platform::media_controls::{MediaCommand, MediaHandle, start_media_controls};
-use crate::platform::tray::{TrayHandle, TrayState};
+use crate::platform::tray::{TrayHandle, TrayQueueItem, TrayState, UP_NEXT_LEN};
 use crate::ui::pages;
 use crate::utils::format_relative_time;
 
@@ -30,12 +42,15 @@ pub fn init_audio(
     audio_chain.set_equalizer_enabled(settings.playback.equalizer_enabled);
     audio_chain.set_equalizer_gains(settings.playback.equalizer_values);
     audio_chain.set_preamp(settings.playback.equalizer_preamp);
+    apply_dsp_presets(&audio_chain, settings.dsp_presets());
 
     // Spawn audio thread
     let device_name = settings.system.audio_output_device.as_deref();
     match crate::audio::spawn_audio_thread(device_name, audio_chain.clone()) {
         Ok(mut thread_handle) => {
             let handle = thread_handle.handle.clone();
+            handle.set_output_latency(Duration::from_millis(settings.output_latency_ms() as u64));
+            handle.set_match_sample_rate(settings.playback.match_sample_rate);
             let event_rx = thread_handle.take_event_rx();
             tracing::info!("Audio thread spawned successfully");
 
@@ -68,11 +83,16 @@ pub fn init_audio(
     }
 }
 
+/// Switch the chain's DSP stages to `presets`
+pub fn apply_dsp_presets(chain: &AudioProcessingChain, presets: crate::features::DspPresets) {
+    chain.set_crossfeed(presets.crossfeed);
+    chain.set_bass_boost(presets.bass_boost);
+    chain.set_night_mode(presets.night_mode);
+}
+
 /// Initialize database connection
 pub async fn init_database() -> anyhow::Result<Database> {
-    let data_dir = directories::ProjectDirs::from("com", "rustle", "Rustle")
-        .map(|dirs| dirs.data_dir().to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("."));
+    let data_dir = crate::utils::data_dir();
 
     std::fs::create_dir_all(&data_dir)?;
     let db_path = data_dir.join("rustle.db");
@@ -91,6 +111,43 @@ pub async fn load_playlists(db: Arc<Database>) -> Vec<DbPlaylist> {
     db.get_all_playlists().await.unwrap_or_default()
 }
 
+/// Build cover mosaics for the given local playlists, keyed by playlist ID
+///
+/// Playlists without any song covers are left out.
+pub async fn load_playlist_mosaics(
+    db: Arc<Database>,
+    playlist_ids: Vec<i64>,
+) -> HashMap<i64, String> {
+    let dir = crate::utils::covers_cache_dir();
+    let mut mosaics = HashMap::new();
+    for playlist_id in playlist_ids {
+        let paths = db
+            .get_playlist_cover_paths(playlist_id, (mosaic::TILES * 2) as i64)
+            .await
+            .unwrap_or_default();
+        let covers = mosaic::pick_covers(paths);
+        let dir = dir.clone();
+        let shown = Job::new(Category::Disk)
+            .prefetch()
+            .run_blocking(move || mosaic::ensure(&dir, playlist_id, &covers))
+            .await;
+        match shown {
+            Some(Ok(Some(path))) => {
+                mosaics.insert(playlist_id, path.to_string_lossy().to_string());
+            }
+            Some(Err(e)) => {
+                tracing::warn!(
+                    "Failed to build cover mosaic for playlist {}: {}",
+                    playlist_id,
+                    e
+                )
+            }
+            _ => {}
+        }
+    }
+    mosaics
+}
+
 /// Load playback state from database
 pub async fn load_playback_state(db: Arc<Database>) -> Option<DbPlaybackState> {
     db.get_playback_state().await.ok()
@@ -101,9 +158,103 @@ pub async fn load_queue(db: Arc<Database>) -> Vec<DbSong> {
     db.get_queue().await.unwrap_or_default()
 }
 
-/// Validate all songs in database and remove entries for missing files
+/// Load blocked songs and artists from database
+pub async fn load_blocklist(db: Arc<Database>) -> Blocklist {
+    let songs = db.get_blocked_songs().await.unwrap_or_default();
+    let artists = db.get_blocked_artists().await.unwrap_or_default();
+    Blocklist::new(
+        songs
+            .into_iter()
+            .map(|song| BlockedSong {
+                id: song.song_id,
+                title: song.title,
+                artist: song.artist,
+            })
+            .collect(),
+        artists.into_iter().map(|artist| artist.name).collect(),
+    )
+}
+
+/// Load trashed playlists and songs from database
+pub async fn load_trash(db: Arc<Database>) -> (Vec<DbTrashedPlaylist>, Vec<DbTrashedSong>) {
+    let playlists = db.get_trashed_playlists().await.unwrap_or_default();
+    let songs = db.get_trashed_songs().await.unwrap_or_default();
+    (playlists, songs)
+}
+
+/// Permanently delete trash past its retention period
+pub async fn purge_expired_trash(db: Arc<Database>) {
+    match db.purge_expired_trash().await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("Purged {} expired items from the trash", count),
+        Err(e) => tracing::error!("Failed to purge expired trash: {}", e),
+    }
+}
+
+/// Load the NCM metadata overrides from database into the override store
+pub async fn load_song_overrides(db: Arc<Database>) {
+    match db.get_song_overrides().await {
+        Ok(rows) => crate::features::song_overrides::load(rows),
+        Err(e) => tracing::error!("Failed to load song overrides: {}", e),
+    }
+}
+
+/// Load liked and unliked songs from database
+pub async fn load_favorites(db: Arc<Database>) -> Favorites {
+    let favorites = db.get_favorites().await.unwrap_or_default();
+    Favorites::new(favorites.into_iter().map(|favorite| {
+        (
+            favorite.song_id,
+            Favorite {
+                liked: favorite.liked,
+                updated_at: favorite.updated_at,
+            },
+        )
+    }))
+}
+
+/// Load the saved liked songs playlist from database
+pub async fn load_liked_mirror(db: Arc<Database>) -> Option<LikedMirror> {
+    let mirror = db.get_liked_mirror().await.ok()??;
+    let songs = serde_json::from_str(&mirror.songs)
+        .map_err(|e| tracing::warn!("Discarding unreadable liked songs mirror: {}", e))
+        .ok()?;
+    Some(LikedMirror {
+        user_id: mirror.user_id as u64,
+        playlist: crate::api::SongList {
+            id: mirror.playlist_id as u64,
+            name: mirror.name,
+            cover_img_url: mirror.cover_img_url,
+            author: mirror.author,
+        },
+        songs,
+    })
+}
+
+/// Load local song to NCM track links from database
+pub async fn load_ncm_links(db: Arc<Database>) -> HashMap<SongId, u64> {
+    db.get_ncm_links()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|link| (SongId::library(link.song_id), link.ncm_id as u64))
+        .collect()
+}
+
+/// Load stored song analyses from database
+pub async fn load_song_analyses(db: Arc<Database>) -> HashMap<SongId, SongAnalysis> {
+    db.get_song_analyses()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|row| (SongId::library(row.song_id), SongAnalysis::from(row)))
+        .collect()
+}
+
+/// Validate all songs in database and move entries for missing files to the
+/// trash
 /// Returns the number of invalid songs removed
-/// NCM songs (file_path starts with "ncm://") are skipped as they are cloud songs
+/// NCM songs are skipped as they are cloud songs
 pub async fn validate_songs(db: Arc<Database>) -> u32 {
     let songs = match db.get_all_songs().await {
         Ok(songs) => songs,
@@ -117,15 +268,15 @@ pub async fn validate_songs(db: Arc<Database>) -> u32 {
 
     for song in songs {
         // Skip NCM cloud songs - they don't have local files
-        if song.file_path.starts_with("ncm://") {
+        let Some(row_id) = song.id.library_id() else {
             continue;
-        }
+        };
 
         let path = std::path::Path::new(&song.file_path);
         if !path.exists() {
-            tracing::info!("Removing invalid song (file not found): {}", song.file_path);
-            if let Err(e) = db.delete_song(song.id).await {
-                tracing::error!("Failed to delete invalid song {}: {}", song.id, e);
+            tracing::info!("Trashing invalid song (file not found): {}", song.file_path);
+            if let Err(e) = db.trash_song(row_id).await {
+                tracing::error!("Failed to trash invalid song {}: {}", song.id, e);
             } else {
                 removed_count += 1;
             }
@@ -151,7 +302,8 @@ pub async fn init_font_system() -> crate::features::lyrics::engine::SharedFontSy
     tokio::task::spawn_blocking(|| {
         tracing::info!("Initializing FontSystem for lyrics...");
         let start = std::time::Instant::now();
-        let font_system = cosmic_text::FontSystem::new();
+        let mut font_system = cosmic_text::FontSystem::new();
+        crate::features::lyrics::engine::fonts::load_bundled_fonts(&mut font_system);
         tracing::info!("FontSystem initialized in {:?}", start.elapsed());
 
         // Warm up font cache with common character sets
@@ -184,6 +336,8 @@ fn warm_up_font_cache(font_system: &crate::features::lyrics::engine::SharedFontS
         "안녕하세요 세계, 한국어 테스트",
         // Numbers and punctuation
         "0123456789 !@#$%^&*()[]{}",
+        // Emoji and symbols, found through the fallback fonts
+        "🎵🎶❤️✨🌙 ♪♫☆→∞",
     ];
 
     let mut fs = font_system.lock();
@@ -226,45 +380,10 @@ pub fn set_mpris_handle(handle: MediaHandle) {
     MPRIS_HANDLE.set(handle).ok();
 }
 
-/// Update tray state with full info including play mode
-pub fn update_tray_state_full(
-    is_playing: bool,
-    title: Option<String>,
-    artist: Option<String>,
-    play_mode: PlayMode,
-) {
-    update_tray_state_with_favorite(is_playing, title, artist, play_mode, None, false);
-}
-
-/// Update tray state with full info including favorite status
-pub fn update_tray_state_with_favorite(
-    is_playing: bool,
-    title: Option<String>,
-    artist: Option<String>,
-    play_mode: PlayMode,
-    ncm_song_id: Option<u64>,
-    is_favorited: bool,
-) {
-    if let Some(handle) = get_tray_handle() {
-        let state = TrayState {
-            is_playing,
-            title,
-            artist,
-            play_mode,
-            ncm_song_id,
-            is_favorited,
-        };
-        let handle = handle.clone();
-        tokio::spawn(async move {
-            handle.update(state).await;
-        });
-    }
-}
-
 /// Open folder dialog
-pub async fn open_folder_dialog() -> Option<PathBuf> {
+pub async fn open_folder_dialog(title: &'static str) -> Option<PathBuf> {
     rfd::AsyncFileDialog::new()
-        .set_title("选择音乐文件夹")
+        .set_title(title)
         .pick_folder()
         .await
         .map(|handle| handle.path().to_path_buf())
@@ -290,8 +409,10 @@ pub async fn create_playlist_from_import(
     for path in scanned_paths {
         let path_str = path.to_string_lossy().to_string();
         // Find song by path in database
-        if let Ok(Some(song)) = db.get_song_by_path(&path_str).await {
-            if let Err(e) = db.add_song_to_playlist(playlist_id, song.id).await {
+        if let Ok(Some(song)) = db.get_song_by_path(&path_str).await
+            && let Some(row_id) = song.id.library_id()
+        {
+            if let Err(e) = db.add_song_to_playlist(playlist_id, row_id).await {
                 tracing::warn!("Failed to add song {} to playlist: {}", song.id, e);
             }
         } else {
@@ -306,6 +427,7 @@ pub async fn create_playlist_from_import(
 pub async fn load_playlist_view(
     db: Arc<Database>,
     playlist_id: i64,
+    locale: Locale,
 ) -> Option<pages::PlaylistView> {
     // Get playlist info
     let playlist = db.get_playlist(playlist_id).await.ok()??;
@@ -320,63 +442,56 @@ pub async fn load_playlist_view(
     let song_views: Vec<pages::PlaylistSongView> = songs
         .iter()
         .enumerate()
-        .map(|(i, song)| {
+        .map(|(i, row)| {
+            let song = &row.song;
             let duration_secs = song.duration_secs as u64;
             let mins = duration_secs / 60;
             let secs = duration_secs % 60;
 
             // Format added_at as relative time
-            let added_date = format_relative_time(song.added_at);
+            let added_date = format_relative_time(row.added_at, locale);
 
             pages::PlaylistSongView::new(
                 song.id,
                 i + 1,
                 song.title.clone(),
                 if song.artist.is_empty() {
-                    "未知艺术家".to_string()
+                    locale.get(Key::UnknownArtist).to_string()
                 } else {
                     song.artist.clone()
                 },
                 if song.album.is_empty() {
-                    "未知专辑".to_string()
+                    locale.get(Key::UnknownAlbum).to_string()
                 } else {
                     song.album.clone()
                 },
                 format!("{}:{:02}", mins, secs),
                 added_date,
                 song.cover_path.clone(),
+                SongSource::of(song),
             )
         })
         .collect();
 
     // Calculate total duration
-    let total_secs: u64 = songs.iter().map(|s| s.duration_secs as u64).sum();
-    let total_mins = total_secs / 60;
-    let total_hours = total_mins / 60;
-    let remaining_mins = total_mins % 60;
-    let total_duration = if total_hours > 0 {
-        format!("约 {} 小时 {} 分钟", total_hours, remaining_mins)
-    } else {
-        format!("{} 分钟", total_mins)
-    };
+    let total_duration_secs: u64 = songs.iter().map(|s| s.song.duration_secs as u64).sum();
 
     // Extract color palette from cover image
-    let palette = playlist
-        .cover_path
-        .as_ref()
-        .map(|p| crate::utils::ColorPalette::from_image_path(std::path::Path::new(p)))
-        .unwrap_or_default();
+    let palette = match &playlist.cover_path {
+        Some(path) => palette::color_palette(Some(db.clone()), PathBuf::from(path)).await,
+        None => crate::utils::ColorPalette::default(),
+    };
 
     Some(pages::PlaylistView {
         id: playlist.id,
         name: playlist.name,
         description: playlist.description,
         cover_path: playlist.cover_path,
-        owner: "本地".to_string(),
+        owner: locale.get(Key::LocalPlaylistOwner).to_string(),
         owner_avatar_path: None,
         creator_id: 0,
         song_count: songs.len() as u32,
-        total_duration,
+        total_duration_secs,
         like_count: String::new(),
         songs: song_views,
         palette,
@@ -385,10 +500,102 @@ pub async fn load_playlist_view(
     })
 }
 
-// ============ Personal FM Mode Helpers ============
-
 use crate::app::state::App;
 
+// ============ System Tray Helpers ============
+
+impl App {
+    /// Push current playback, queue and favorite state to the system tray
+    pub fn update_tray_state(&self, is_playing: bool) {
+        let Some(handle) = get_tray_handle() else {
+            return;
+        };
+
+        let current = self.library.current_song.as_ref();
+        let is_favorited = current.is_some_and(|s| self.is_favorite(s.id));
+
+        let state = TrayState {
+            is_playing,
+            title: current.map(|s| s.title.clone()),
+            artist: current.map(|s| s.artist.clone()),
+            play_mode: self.core.settings.play_mode,
+            is_favorited,
+            stop_after_current: self.library.stop_after_current,
+            up_next: self.tray_up_next(),
+            lyrics_open: self.ui.lyrics.is_open,
+            output_devices: self.core.output_devices.clone(),
+            output_device: self.core.settings.system.audio_output_device.clone(),
+            locale: self.core.locale,
+        };
+
+        let handle = handle.clone();
+        tokio::spawn(async move {
+            handle.update(state).await;
+        });
+    }
+
+    /// Re-sync the tray using the audio thread's current play state
+    pub fn refresh_tray_state(&self) {
+        let is_playing = self
+            .core
+            .audio
+            .as_ref()
+            .map(|p| p.is_playing())
+            .unwrap_or(false);
+        self.update_tray_state(is_playing);
+    }
+
+    /// Upcoming queue entries in playback order (wraps around in loop-all mode)
+    ///
+    /// Shuffle only settles one song ahead, so that is all it lists.
+    fn tray_up_next(&self) -> Vec<TrayQueueItem> {
+        let queue = &self.library.queue;
+        let upcoming: Vec<usize> = if self.core.settings.play_mode == PlayMode::Shuffle {
+            self.library.shuffle_cache.next.into_iter().collect()
+        } else {
+            let start = self.library.queue_index.map(|i| i + 1).unwrap_or(0);
+            let wrap = self.core.settings.play_mode == PlayMode::LoopAll;
+            let count = if wrap {
+                queue.len().saturating_sub(1)
+            } else {
+                queue.len().saturating_sub(start)
+            };
+            (0..count)
+                .map(|offset| (start + offset) % queue.len().max(1))
+                .take(UP_NEXT_LEN)
+                .collect()
+        };
+
+        upcoming
+            .into_iter()
+            .filter_map(|index| {
+                queue.get(index).map(|song| TrayQueueItem {
+                    index,
+                    label: if song.artist.is_empty() {
+                        song.title.clone()
+                    } else {
+                        format!("{} - {}", song.title, song.artist)
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+// ============ NCM Link Helpers ============
+
+impl App {
+    /// NCM track of a song: its own for NCM songs, the matched one for
+    /// linked local songs
+    pub fn ncm_id_of(&self, song: &DbSong) -> Option<u64> {
+        song.id
+            .ncm_id()
+            .or_else(|| self.library.ncm_links.get(&song.id).copied())
+    }
+}
+
+// ============ Personal FM Mode Helpers ============
+
 impl App {
     /// Check if currently in Personal FM mode
     pub fn is_fm_mode(&self) -> bool {
@@ -447,6 +654,7 @@ impl App {
     pub fn fetch_more_fm_songs_and_play(&self) -> Task<Message> {
         if let Some(client) = &self.core.ncm_client {
             let client = client.clone();
+            let failed = self.core.locale.get(Key::PersonalFmFetchFailed);
             Task::perform(
                 async move {
                     match client.client.personal_fm().await {
@@ -454,17 +662,19 @@ impl App {
                         _ => None,
                     }
                 },
-                |songs_opt| {
+                move |songs_opt| {
                     if let Some(songs) = songs_opt {
                         // FM mode: append songs and start playback
                         Message::AddNcmPlaylist(songs, true)
                     } else {
-                        Message::ShowToast("获取私人FM歌曲失败".to_string())
+                        Message::ShowToast(failed.to_string())
                     }
                 },
             )
         } else {
-            Task::done(Message::ShowToast("请先登录".to_string()))
+            Task::done(Message::ShowToast(
+                self.core.locale.get(Key::LoginRequired).to_string(),
+            ))
         }
     }
 }
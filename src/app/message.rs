@@ -1,15 +1,25 @@
 //! Application messages
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use iced::keyboard::{Key, Modifiers};
 
-use crate::api::{BannersInfo, LoginInfo, PlayListDetail, SongInfo, SongList};
-use crate::app::state::UserInfo;
-use crate::database::{Database, DbPlaybackState, DbPlaylist, DbSong};
+use crate::api::{
+    AlbumRelease, ApiError, BannersInfo, LoginInfo, PlayListDetail, PlayRecord, SongCredits,
+    SongInfo, SongList, UserPlaylists, UserProfile,
+};
+use crate::database::{
+    Database, DbCheckIn, DbPlaybackState, DbPlaylist, DbQueueSnapshot, DbSong, SongId,
+};
 use crate::features::Action;
+use crate::features::home_layout::HomeSection;
 use crate::features::import::{CoverCache, ScanProgress, WatchEvent};
+use crate::features::lazy_covers::CoverKey;
+use crate::features::media::chapters::Chapter;
+use crate::features::song_source::SongSource;
+use crate::ui::components::playlist_view::{PlaylistFilter, PlaylistSort};
 use crate::ui::components::{LibraryItem, NavItem};
 use crate::ui::pages;
 
@@ -18,6 +28,7 @@ use crate::ui::pages;
 pub enum SettingsSection {
     Account,
     Playback,
+    Schedules,
     Display,
     System,
     Network,
@@ -36,6 +47,17 @@ pub struct SearchResultsPayload {
     pub total_count: u32,
 }
 
+/// Result of checking followed artists for new albums
+#[derive(Debug, Clone, Default)]
+pub struct NewReleaseFeed {
+    /// NCM IDs of every followed artist
+    pub followed: Vec<u64>,
+    /// Recent albums for the Discover feed, newest first
+    pub releases: Vec<AlbumRelease>,
+    /// Albums published since the previous check
+    pub fresh: Vec<AlbumRelease>,
+}
+
 /// Application messages
 #[derive(Clone)]
 pub enum Message {
@@ -53,6 +75,24 @@ pub enum Message {
     LibrarySelect(LibraryItem),
     /// Search query changed
     SearchChanged(String),
+    /// Open or close the search history and suggestions dropdown
+    ToggleSearchDropdown,
+    /// Close the search dropdown
+    CloseSearchDropdown,
+    /// Search history loaded from the database
+    SearchHistoryLoaded(Vec<String>),
+    /// NCM hot search keywords loaded
+    HotSearchesLoaded(Vec<String>),
+    /// Typing paused; fetch suggestions if this is still the latest keystroke
+    SearchSuggestDue(u64),
+    /// Suggestions loaded for a query
+    SearchSuggestionsLoaded(String, Vec<String>),
+    /// Search for a keyword picked from the dropdown
+    SelectSearchKeyword(String),
+    /// Remove one keyword from the search history
+    DeleteSearchHistory(String),
+    /// Remove every keyword from the search history
+    ClearSearchHistory,
     /// Play hero banner playlist
     PlayHero,
     /// Import local playlist
@@ -65,18 +105,28 @@ pub enum Message {
     WindowMinimize,
     /// Maximize window
     WindowMaximize,
-    /// Mouse pressed (for window drag detection)
-    MousePressed,
+    /// Mouse pressed in a window (for window drag detection)
+    MousePressed(iced::window::Id),
     /// Mouse released (for sidebar resize end)
     MouseReleased,
-    /// Mouse moved (track cursor position for drag area)
-    MouseMoved(iced::Point),
+    /// Mouse moved in a window (track cursor position for drag area)
+    MouseMoved(iced::window::Id, iced::Point),
     /// Open settings
     OpenSettings,
     /// Open settings and close lyrics page
     OpenSettingsWithCloseLyrics,
     /// Open audio engine page
     OpenAudioEngine,
+    /// Open plugin manager page
+    OpenPlugins,
+    /// Open log viewer page
+    OpenLogs,
+    /// Open the year-in-review page
+    OpenAnnualReport,
+    /// Open the blocklist manager page
+    OpenBlocklist,
+    /// Open the recently deleted page
+    OpenTrash,
 
     // ============ Settings ============
     /// Update close behavior
@@ -86,11 +136,31 @@ pub enum Message {
     /// Update playback settings
     UpdateFadeInOut(bool),
     UpdateVolumeNormalization(bool),
+    UpdateUiSounds(bool),
+    UpdateAutoplaySimilar(bool),
+    UpdateSmartResume(bool),
+    UpdateSmartResumeAfter(u32),
+    UpdateSmartResumeRewind(u32),
+    UpdateCallAction(crate::features::CallAction),
+    UpdateCallLowerPercent(u32),
+    UpdateCallAnyApp(bool),
+    UpdateCallAfter(u32),
+    UpdateAutoDjPool(crate::features::AutoDjPool),
+    UpdateAutoDjCrossfade(u32),
+    UpdateSkipBackSecs(u32),
+    UpdateSkipForwardSecs(u32),
+    /// Seek key and seek bar wheel step in seconds changed
+    UpdateSeekStepSecs(u32),
+    /// Volume key and player bar wheel step in percent changed
+    UpdateVolumeStepPercent(u32),
     UpdateMusicQuality(crate::features::MusicQuality),
     UpdateEqualizerEnabled(bool),
     UpdateEqualizerPreset(crate::features::EqualizerPreset),
     UpdateEqualizerValues([f32; 10]),
     UpdateEqualizerPreamp(f32),
+    /// Headphone DSP stages for the current output device
+    UpdateDspPresets(crate::features::DspPresets),
+    UpdateMatchSampleRate(bool),
     /// Update spectrum analyzer settings
     UpdateSpectrumDecay(f32),
     UpdateSpectrumBarsMode(bool),
@@ -99,6 +169,27 @@ pub enum Message {
     UpdateAppLanguage(String),
     /// Update power saving mode
     UpdatePowerSavingMode(bool),
+    /// Update default lyrics sub-lines
+    UpdateLyricsTranslationDefault(bool),
+    UpdateLyricsRomanizationDefault(bool),
+    /// Show or hide the advanced lyrics tuning group
+    ToggleLyricsTuningPanel,
+    /// Update advanced lyrics animation tuning
+    UpdateLyricsTuning(crate::features::LyricsTuning),
+    ResetLyricsTuning,
+    /// Update whether edited lyrics files are offered for sharing
+    UpdateLyricsShareOffer(bool),
+    /// Update the name credited in shared lyrics
+    UpdateLyricsShareCredit(String),
+    /// Update whether fetched lyrics are offered for saving into local files
+    UpdateLyricsSaveOffer(bool),
+    /// Update where saved lyrics go
+    UpdateLyricsSaveTarget(crate::features::lyrics_save::LyricsSaveTarget),
+    /// Update the artwork backdrop behind playlist headers
+    UpdateArtworkBackdrop(crate::features::ArtworkBackdrop),
+    UpdateCoverTheming(bool),
+    /// Pick the lyrics page background
+    UpdateLyricsBackground(crate::features::BackgroundStyle),
     /// Update storage settings
     UpdateMaxCacheMb(u64),
     ClearCache,
@@ -110,25 +201,102 @@ pub enum Message {
     EnforceCacheLimit,
     /// Update system settings
     UpdateAudioOutputDevice(Option<String>),
+    /// Output latency in ms for the current output device
+    UpdateOutputLatency(u32),
+    /// Start tapping along to measure the output latency
+    StartLatencyCalibration,
+    /// Calibration click is due
+    LatencyCalibrationBeat,
+    /// User tapped along with the calibration click
+    LatencyCalibrationTap,
+    /// Stop calibrating, keeping the measured latency
+    FinishLatencyCalibration,
+    /// Stop calibrating without changing the latency
+    CancelLatencyCalibration,
     UpdateAudioBufferSize(u32),
+    UpdateLaunchAtLogin(bool),
+    /// Launch at login was registered or removed (requested state, result)
+    LaunchAtLoginUpdated(bool, Result<(), String>),
+    UpdateStartMinimized(bool),
+    UpdateReleaseNotifications(bool),
+    UpdateDailyCheckIn(bool),
+    UpdateResumeOnStartup(bool),
+    UpdateUpdateChannel(crate::features::UpdateChannel),
+    UpdateAutoCheckUpdates(bool),
     /// Update network settings
     UpdateProxyType(crate::features::ProxyType),
     UpdateProxyHost(String),
     UpdateProxyPort(String),
     UpdateProxyUsername(String),
     UpdateProxyPassword(String),
+    UpdateBandwidthLimit(u32),
+    UpdateMeteredMode(crate::features::MeteredMode),
     /// Apply proxy settings to the NCM client
     ApplyProxySettings,
+    /// Query the system for the metered connection status
+    DetectMeteredConnection,
+    /// System metered status (None if the platform can't tell)
+    MeteredStatusDetected(Option<bool>),
+    /// Look up which network traffic currently goes out through
+    CheckNetworkRoute,
+    /// Local address of the internet route (None while offline)
+    NetworkRouteChecked(Option<std::net::IpAddr>),
     /// Settings navigation
     ScrollToSection(SettingsSection),
     /// Settings page scrolled (y offset in pixels)
     SettingsScrolled(f32),
+    /// Settings search query edited
+    SettingsSearchChanged(String),
+    /// Settings search narrowed to a category, or to all of them
+    SettingsCategorySelected(Option<pages::settings_search::SettingsCategory>),
     /// Start editing a keybinding for an action
     StartEditingKeybinding(Action),
     /// Cancel keybinding edit
     CancelEditingKeybinding,
     /// Key pressed while editing keybinding
     KeybindingKeyPressed(Key, Modifiers),
+    /// Pick where to export the settings profile
+    ExportSettingsProfile,
+    /// Export destination picked (None if cancelled)
+    SettingsProfileExportPicked(Option<PathBuf>),
+    /// Profile written (toast text on failure)
+    SettingsProfileExported(Result<PathBuf, String>),
+    /// Pick a settings profile to import
+    ImportSettingsProfile,
+    /// Profile to import picked (None if cancelled)
+    SettingsProfileImportPicked(Option<PathBuf>),
+    /// Profile read, with what happened to its credentials (toast text on failure)
+    SettingsProfileImported(
+        Result<
+            (
+                Box<crate::features::Settings>,
+                crate::features::settings_profile::SecretsImport,
+            ),
+            String,
+        >,
+    ),
+    /// Passphrase for credentials in settings profiles edited
+    UpdateSettingsProfilePassphrase(String),
+    /// Add an event hook
+    AddEventHook,
+    /// Remove the event hook at an index
+    RemoveEventHook(usize),
+    /// Replace the event hook at an index
+    UpdateEventHook(usize, crate::features::hooks::EventHook),
+    /// Run the event hook at an index with the current song
+    TestEventHook(usize),
+    /// Test run finished
+    EventHookTested(Result<(), String>),
+    /// Toggle now-playing output files
+    UpdateNowPlayingOutput(bool),
+    /// Toggle the now-playing artwork PNG
+    UpdateNowPlayingArtwork(bool),
+    /// Pick the folder now-playing files go to
+    PickNowPlayingFolder,
+    /// Now-playing folder picked (None if cancelled)
+    NowPlayingFolderPicked(Option<PathBuf>),
+    /// Open the now-playing folder in the file manager
+    OpenNowPlayingFolder,
 
     // ============ Database ============
     /// Database initialized
@@ -139,6 +307,8 @@ pub enum Message {
     SongsLoaded(Vec<DbSong>),
     /// Playlists loaded from database
     PlaylistsLoaded(Vec<DbPlaylist>),
+    /// Cover mosaics built for local playlists without a cover
+    PlaylistMosaicsReady(std::collections::HashMap<i64, String>),
     /// Playback state loaded
     PlaybackStateLoaded(DbPlaybackState),
     /// Queue restored from database on startup (does not auto-play)
@@ -153,7 +323,8 @@ pub enum Message {
     /// Songs validated - invalid entries removed
     SongsValidated(u32),
     /// Queue loaded from playlist (starts playing)
-    QueueLoaded(Vec<DbSong>),
+    /// (playlist_id, songs)
+    QueueLoaded(i64, Vec<DbSong>),
     /// Recently played loaded
     RecentlyPlayedLoaded(Vec<DbSong>),
 
@@ -192,6 +363,15 @@ pub enum Message {
     PlaylistDeleted(i64),
     /// Playlist view loaded from database
     PlaylistViewLoaded(pages::PlaylistView),
+    /// Saved playback preferences of a playlist loaded (playlist_id, playback)
+    PlaylistPlaybackLoaded(i64, crate::features::PlaylistPlayback),
+    /// Show or hide the playlist playback options
+    TogglePlaylistPlaybackPanel,
+    /// Change the open playlist's playback preferences
+    UpdatePlaylistPlayback(crate::features::PlaylistPlayback),
+    /// Playlist cover decoded for the artwork backdrop
+    /// (playlist_id, path, rgb_data, width, height)
+    PlaylistBackdropReady(i64, String, Vec<u8>, u32, u32),
     /// NCM playlist songs converted (async cover check complete)
     /// (playlist_id, song_views, cover_path, palette, avatar_path)
     NcmPlaylistSongsReady(
@@ -201,10 +381,27 @@ pub enum Message {
         crate::utils::ColorPalette,
         Option<String>,
     ),
+    /// Batch of a paged NCM playlist's songs
+    /// (playlist_id, songs, song_views, track IDs fetched so far, total tracks)
+    NcmPlaylistSongsPage(
+        i64,
+        Vec<SongInfo>,
+        Vec<crate::ui::pages::PlaylistSongView>,
+        usize,
+        usize,
+    ),
+    /// Changes to a reopened NCM playlist
+    /// (playlist_id, latest track IDs, added songs, added song_views)
+    NcmPlaylistTracksSynced(
+        i64,
+        Vec<u64>,
+        Vec<SongInfo>,
+        Vec<crate::ui::pages::PlaylistSongView>,
+    ),
     /// Play a specific song
-    PlaySong(i64),
+    PlaySong(SongId),
     /// Hover over a song in playlist
-    HoverSong(Option<i64>),
+    HoverSong(Option<SongId>),
     /// Hover over an icon button
     HoverIcon(Option<IconId>),
     /// Hover over a sidebar item
@@ -219,6 +416,10 @@ pub enum Message {
     PlaylistSearchSubmit,
     /// Playlist search input lost focus
     PlaylistSearchBlur,
+    /// Order of the NCM playlist's songs chosen
+    PlaylistSortChanged(PlaylistSort),
+    /// Filters of the NCM playlist's songs changed
+    PlaylistFilterChanged(PlaylistFilter),
 
     // ============ Edit dialog ============
     /// Edit playlist (open edit dialog)
@@ -238,6 +439,18 @@ pub enum Message {
     /// Playlist updated in database (with playlist id to reload)
     PlaylistUpdated(i64),
 
+    // ============ Shared-element transitions ============
+    /// A card's cover was pressed, with its bounds in the window
+    SharedCoverPressed(iced::widget::image::Handle, iced::Rectangle),
+    /// A transition end moved to new bounds in the window
+    SharedElementMoved(SharedElement, iced::Rectangle),
+
+    // ============ Frosted backdrop ============
+    /// Window captured as a modal opened (None if it couldn't be)
+    BackdropCaptured(Option<iced::window::Screenshot>),
+    /// Blurred window snapshot ready to show behind modals
+    BackdropFrosted(Option<iced::widget::image::Handle>),
+
     // ============ Lyrics page ============
     /// Open lyrics page
     OpenLyricsPage,
@@ -245,28 +458,36 @@ pub enum Message {
     CloseLyricsPage,
     /// Scroll lyrics manually (delta in pixels)
     LyricsScroll(f32),
+    /// Return manually scrolled lyrics to the current line
+    ResumeLyricsAutoScroll,
     /// Window resized (for lyrics viewport calculation)
-    WindowResized(iced::Size),
+    WindowResized(iced::window::Id, iced::Size),
+    /// Pop the lyrics page out into its own window, or bring that window up
+    OpenLyricsWindow,
+    /// Close the lyrics window
+    CloseLyricsWindow,
+    /// Toggle fullscreen of the lyrics window
+    ToggleLyricsWindowFullscreen,
     /// Font system initialized asynchronously (for lyrics text shaping)
     LyricsFontSystemReady(crate::features::lyrics::engine::SharedFontSystem),
     /// Lyrics loaded from online (song_id, lyrics_lines)
-    LyricsLoaded(i64, Vec<crate::ui::pages::LyricLine>),
+    LyricsLoaded(SongId, Vec<crate::ui::pages::LyricLine>),
     /// Lyrics loading failed
-    LyricsLoadFailed(i64, String),
+    LyricsLoadFailed(SongId, String),
     /// Preload lyrics for a song (song_id, ncm_id, song_name, singer, album)
-    PreloadLyrics(i64, u64, String, String, String),
+    PreloadLyrics(SongId, u64, String, String, String),
     /// Local/cached lyrics loaded asynchronously (song_id, lyrics_lines)
-    LocalLyricsReady(i64, Vec<crate::ui::pages::LyricLine>),
+    LocalLyricsReady(SongId, Vec<crate::ui::pages::LyricLine>),
     /// Engine lines pre-computed asynchronously (song_id, engine_lines)
     LyricsEngineLinesReady(
-        i64,
+        SongId,
         std::sync::Arc<Vec<crate::features::lyrics::engine::LyricLineData>>,
     ),
     /// 异步预计算的 shaped lines (song_id, shaped_lines, pre_generated_sdf_bitmaps)
     /// 文本布局的唯一数据源，在后台线程计算
     /// 包含预生成的 SDF 位图，避免首次渲染时阻塞主线程
     LyricsShapedLinesReady(
-        i64,
+        SongId,
         std::sync::Arc<Vec<crate::features::lyrics::engine::CachedShapedLine>>,
         std::collections::HashMap<
             cosmic_text::CacheKey,
@@ -274,9 +495,59 @@ pub enum Message {
         >,
     ),
     /// Background colors extracted asynchronously (song_id, primary, secondary, tertiary)
-    LyricsBackgroundReady(i64, [f32; 4], [f32; 4], [f32; 4]),
+    LyricsBackgroundReady(SongId, [f32; 4], [f32; 4], [f32; 4]),
     /// Album cover image loaded asynchronously for lyrics background (song_id, image_data, width, height)
-    LyricsCoverImageReady(i64, Vec<u8>, u32, u32),
+    LyricsCoverImageReady(SongId, Vec<u8>, u32, u32),
+    /// High-resolution cover downloaded for the lyrics page (song_id, path)
+    LargeCoverReady(SongId, String),
+    /// Show/hide the translation line for the current song
+    ToggleLyricsTranslation,
+    /// Show/hide the romanization line for the current song
+    ToggleLyricsRomanization,
+    /// Saved sub-line preference loaded for a song (song_id, sub_lines)
+    LyricsSubLinesLoaded(SongId, crate::features::LyricsSubLines),
+    /// Show/hide the credits view on the lyrics page
+    ToggleSongCredits,
+    /// Credits fetched for a song (song_id, credits), None if unavailable
+    SongCreditsLoaded(SongId, Option<SongCredits>),
+    /// Search for a credited artist by name
+    OpenCreditArtist(String),
+    /// A local song's LRC file was checked (song_id, whether it changed since
+    /// it was last shared or passed on; None without one)
+    LyricsShareChecked(SongId, Option<bool>),
+    /// Preview the current song's lyrics for sharing on LRCLIB
+    OpenLyricsShare,
+    /// Lyrics read for sharing (song_id, submission or error message)
+    LyricsShareReady(
+        SongId,
+        Result<crate::features::lyrics_share::Submission, String>,
+    ),
+    /// Credit the lyrics to the name from settings or not
+    ToggleLyricsShareCredit(bool),
+    /// Publish the previewed lyrics
+    ConfirmLyricsShare,
+    /// Lyrics published, or error message
+    LyricsShareFinished(Result<(), String>),
+    /// Close the share dialog; lyrics not shared count as passed on
+    CloseLyricsShareDialog,
+    /// A local song was checked for lyrics of its own (song_id, whether the
+    /// lyrics shown could be saved into its files)
+    LyricsSaveChecked(SongId, bool),
+    /// Save the current song's lyrics into its files
+    SaveLyricsToFile,
+    /// Lyrics saved (song_id, file written or `None` when there were none
+    /// to save, or error message)
+    LyricsSaved(SongId, Result<Option<PathBuf>, String>),
+    /// Pick a folder to save lyrics for
+    PickLyricsSaveFolder,
+    LyricsSaveFolderPicked(Option<PathBuf>),
+    /// Saving lyrics for a folder started (song count)
+    LyricsSaveBatchStarted(usize),
+    /// A song of the folder was handled (whether lyrics were saved)
+    LyricsSaveBatchSongDone(bool),
+    LyricsSaveBatchFinished,
+    /// Stop saving lyrics for a folder
+    CancelLyricsSaveBatch,
 
     // ============ Playback controls ============
     /// Toggle play/pause
@@ -289,14 +560,56 @@ pub enum Message {
     SeekPreview(f32),
     /// Finish seeking and apply the preview position
     SeekRelease,
+    /// Qualities the current NCM song is available in (song_id, qualities)
+    TrackQualitiesLoaded(SongId, Vec<crate::features::MusicQuality>),
+    /// Replay the current NCM song at another quality
+    SelectTrackQuality(crate::features::MusicQuality),
+    /// Cursor moved over the seek bar (0.0 to 1.0), None when it leaves
+    SeekHover(Option<f32>),
     /// Set volume (0.0 to 1.0)
     SetVolume(f32),
+    /// Mouse wheel over the player bar
+    VolumeWheel(iced::mouse::ScrollDelta),
+    /// Mouse wheel over the seek bar
+    SeekWheel(iced::mouse::ScrollDelta),
     /// Playback tick (for progress updates)
     PlaybackTick,
     /// Toggle queue panel visibility
     ToggleQueue,
+    /// Where the queued songs play from, by song ID
+    QueueSourcesLoaded(HashMap<SongId, SongSource>),
+    /// Toggle showing only offline-playable songs in the queue
+    ToggleQueueOfflineOnly,
+    /// Switch the queue panel between the queue and earlier sessions
+    ToggleQueueSessions,
+    /// Earlier queues, newest first
+    QueueSessionsLoaded(Vec<DbQueueSnapshot>),
+    /// Replace the queue with an earlier one
+    RestoreQueueSession(i64),
+    /// Songs of a restored queue and the index that was playing
+    QueueSessionRestored(Option<(Vec<DbSong>, usize)>),
     /// Cycle to next play mode
     CyclePlayMode,
+    /// Toggle stopping once the current song ends
+    ToggleStopAfterCurrent,
+    /// Open or close the player context menu
+    TogglePlayerMenu,
+    /// Close the player context menu
+    ClosePlayerMenu,
+    /// Jump back by the configured skip interval
+    SkipBack,
+    /// Jump forward by the configured skip interval
+    SkipForward,
+    /// Chapter markers read from a song file - (song_id, chapters)
+    ChaptersLoaded(SongId, Vec<Chapter>),
+    /// Toggle chapter list visibility
+    ToggleChapters,
+    /// Jump to the start of a chapter by index
+    SeekToChapter(usize),
+    /// Jump to the previous chapter (or the start of the current one)
+    PrevChapter,
+    /// Jump to the next chapter
+    NextChapter,
     /// Audio preload ready (local file cached) - (queue_index, file_path, is_next)
     PreloadReady(usize, String, bool),
     /// Audio preload ready with SharedBuffer for streaming playback
@@ -321,7 +634,7 @@ pub enum Message {
         Option<u64>,
     ),
     /// Song resolution failed
-    SongResolveFailed,
+    SongResolveFailed(Option<ApiError>),
     /// Remove song from queue by index
     RemoveFromQueue(usize),
     /// Clear the entire queue
@@ -336,6 +649,8 @@ pub enum Message {
     // ============ Exit dialog ============
     /// Request to close the window (triggers exit dialog if needed)
     RequestClose,
+    /// The system asked to close a window
+    WindowCloseRequested(iced::window::Id),
     /// Confirm exit and close the application
     ConfirmExit,
     /// Minimize to system tray
@@ -354,6 +669,8 @@ pub enum Message {
     ),
     /// Tray command received
     TrayCommand(crate::features::TrayCommand),
+    /// Audio output devices listed for the tray's device menu
+    OutputDevicesLoaded(Vec<crate::audio::AudioDevice>),
 
     // ============ Media Controls ============
     /// Media controls service started
@@ -367,12 +684,16 @@ pub enum Message {
     ),
     /// Media controls command received
     MprisCommand(crate::platform::media_controls::MediaCommand),
+    /// Media session artwork cached for (song id, cover source); `None` if it failed
+    MediaArtworkReady(SongId, String, Option<std::path::PathBuf>),
     /// Show window from tray
     ShowWindow,
     /// Toggle window visibility
     ToggleWindow,
     /// Window operation completed (for debouncing)
     WindowOperationComplete,
+    /// The main window drew its first frame; starts deferred initialization
+    FirstFrame,
 
     // ============ NCM Login ============
     /// Try to auto-login with saved cookies
@@ -382,7 +703,7 @@ pub enum Message {
     /// Request QR code for login
     RequestQrCode,
     /// QR code generated
-    QrCodeReady(PathBuf, String),
+    QrCodeReady(Vec<Vec<bool>>, String),
     /// Check QR code scan status
     CheckQrStatus(String),
     /// QR code login result
@@ -391,8 +712,18 @@ pub enum Message {
     LoginSuccess(LoginInfo),
     /// Logout
     Logout,
-    /// User info loaded
-    UserInfoLoaded(UserInfo),
+    /// Renew the login or guest token
+    RefreshSession,
+    /// Login token renewed and account still valid
+    SessionRefreshed(LoginInfo),
+    /// The server rejected the saved login
+    SessionExpired,
+    /// An NCM request failed for a reason the user can act on
+    ApiFailed(ApiError),
+    /// Claim today's check-in rewards (if enabled) and reload their history
+    RunDailyCheckIn,
+    /// Daily check-in finished: points claimed, whether any platform failed, history
+    DailyCheckInDone(i64, bool, Vec<DbCheckIn>),
     /// User avatar loaded
     UserAvatarLoaded(std::path::PathBuf),
     /// Toggle login popup visibility
@@ -407,22 +738,42 @@ pub enum Message {
     BannerImageLoaded(usize, PathBuf, u32, u32),
     /// Banner play button clicked
     BannerPlay(usize),
+    /// Open the pending external link in the system browser
+    ConfirmOpenLink,
+    /// Dismiss the external link confirmation
+    CancelOpenLink,
     /// Carousel navigate
     CarouselNavigate(i32),
     /// Carousel auto-advance tick
     CarouselTick,
     /// Top picks (trending playlists) loaded
     TopPicksLoaded(Vec<SongList>),
+    /// Official charts for the homepage, as playlist cards
+    HomeToplistsLoaded(Vec<SongList>),
+    /// Daily recommended songs for the homepage
+    DailySongsLoaded(Vec<SongInfo>),
+    /// Recently played songs for the homepage
+    HomeRecentLoaded(Vec<DbSong>),
+    /// Enter or leave homepage customization
+    ToggleHomeCustomize,
+    /// Show or hide a homepage section
+    ToggleHomeSection(HomeSection),
+    /// Move a homepage section up (negative) or down (positive)
+    MoveHomeSection(HomeSection, isize),
     /// Trending songs (飙升榜) loaded
     TrendingSongsLoaded(Vec<SongInfo>),
     /// Navigate to trending songs page
     OpenTrendingSongs,
     /// Song cover loaded
     SongCoverLoaded(u64, PathBuf),
-    /// Toggle favorite status for a song
-    ToggleFavorite(u64),
-    /// Favorite status changed
-    FavoriteStatusChanged(u64, bool),
+    /// A card without a cover scrolled into view (key, cover URL)
+    CoverShown(CoverKey, String),
+    /// A card scrolled out of view before its cover arrived
+    CoverHidden(CoverKey),
+    /// A lazily requested cover download ended
+    LazyCoverLoaded(CoverKey, Option<PathBuf>),
+    /// Like or unlike a song
+    ToggleFavorite(SongId),
     /// Play NCM song
     PlayNcmSong(SongInfo),
     /// Play NCM song by URL with optional cover path
@@ -442,13 +793,15 @@ pub enum Message {
     /// NCM playlist detail loaded
     NcmPlaylistDetailLoaded(PlayListDetail),
     /// Current playing song cover downloaded (song_id, local_path)
-    CurrentSongCoverReady(i64, String),
+    CurrentSongCoverReady(SongId, String),
     /// NCM playlist song covers batch loaded (vec of (song_id, local_path))
-    NcmPlaylistSongCoversBatchLoaded(Vec<(i64, String)>),
+    NcmPlaylistSongCoversBatchLoaded(Vec<(SongId, String)>),
     /// Request lazy loading of song covers for visible items (song_id, pic_url)
-    RequestSongCoversLazy(Vec<(i64, String)>),
+    RequestSongCoversLazy(Vec<(SongId, String)>),
     /// NCM playlist cover loaded (playlist_id, local_path)
     NcmPlaylistCoverLoaded(i64, String),
+    /// Palette extracted from a playlist cover (playlist_id, palette)
+    NcmPlaylistPaletteReady(i64, crate::utils::ColorPalette),
     /// NCM playlist creator avatar loaded (playlist_id, local_path)
     NcmPlaylistCreatorAvatarLoaded(i64, String),
     /// Toggle playlist subscription (subscribe/unsubscribe)
@@ -464,6 +817,8 @@ pub enum Message {
     RecommendedPlaylistsLoaded(Vec<SongList>),
     /// Hot playlists loaded (playlists, has_more)
     HotPlaylistsLoaded(Vec<SongList>, bool),
+    /// A page of hot playlists failed to load
+    HotPlaylistsFailed,
     /// Discover playlist cover loaded (playlist_id, local_path)
     DiscoverPlaylistCoverLoaded(u64, PathBuf),
     /// Discover playlist cover GPU allocation completed
@@ -481,6 +836,14 @@ pub enum Message {
     SeeAllRecommended,
     /// See all hot playlists
     SeeAllHot,
+    /// Followed artists checked for new albums
+    NewReleasesLoaded(NewReleaseFeed),
+    /// New release cover downloaded (album_id, local_path)
+    NewReleaseCoverLoaded(u64, PathBuf),
+    /// Play an album from the new releases feed
+    PlayNewRelease(u64),
+    /// Follow or unfollow an artist from the search results
+    ToggleFollowArtist(u64),
 
     // ============ Search Page ============
     /// Submit search query (Enter pressed in search bar)
@@ -489,6 +852,10 @@ pub enum Message {
     SearchTabChanged(crate::app::state::SearchTab),
     /// Search results loaded
     SearchResultsLoaded(SearchResultsPayload),
+    /// Local library matches loaded for a keyword
+    LocalSearchLoaded(String, Vec<DbSong>),
+    /// Change the song results source filter
+    SearchSourceChanged(crate::app::state::SearchSource),
     /// Search failed
     SearchFailed(String),
     /// Change search page (pagination)
@@ -507,9 +874,329 @@ pub enum Message {
     SidebarResizeStart,
     /// Stop dragging sidebar resize handle
     SidebarResizeEnd,
+
+    // ============ Plugins ============
+    /// Rescan the plugins directory and reload enabled plugins
+    ReloadPlugins,
+    /// Plugins loaded in the background
+    PluginsLoaded(Arc<crate::features::plugins::PluginRegistry>),
+    /// Enable or disable a plugin (plugin_id, enabled)
+    TogglePlugin(String, bool),
+    /// Open the plugins directory in the file manager
+    OpenPluginsFolder,
+    /// Panels rendered by UI plugins (plugin_id, panel)
+    PluginPanelsLoaded(Vec<(String, crate::features::plugins::PluginPanel)>),
+    /// Plugin panel button pressed (plugin_id, action_id)
+    PluginPanelAction(String, String),
+
+    // ============ Logs & Diagnostics ============
+    /// Re-read the in-memory log buffer
+    RefreshLogs,
+    /// Change the minimum level shown in the log viewer
+    SetLogLevelFilter(crate::features::logs::LogLevelFilter),
+    /// Ask where to save a diagnostic bundle
+    ExportDiagnostics,
+    /// Save location picked for the diagnostic bundle (None if cancelled)
+    DiagnosticsPathPicked(Option<PathBuf>),
+    /// Diagnostic bundle written (path or error message)
+    DiagnosticsExported(Result<PathBuf, String>),
+
+    // ============ Annual Report ============
+    /// Years with play history loaded (newest first)
+    AnnualReportYearsLoaded(Vec<i32>),
+    /// Show the report for another year
+    SelectAnnualReportYear(i32),
+    /// Report built from play history
+    AnnualReportLoaded(crate::features::annual_report::AnnualReport),
+    /// Ask where to save the report images
+    ExportAnnualReport,
+    /// Folder picked for the report images (None if cancelled)
+    AnnualReportFolderPicked(Option<PathBuf>),
+    /// Report images written (folder or error message)
+    AnnualReportExported(Result<PathBuf, String>),
+
+    // ============ Blocklist ============
+    /// Blocked songs and artists loaded from the database
+    BlocklistLoaded(crate::features::blocklist::Blocklist),
+    /// Block the current song and skip it
+    BlockCurrentSong,
+    /// Remove a song from the blocklist
+    UnblockSong(SongId),
+    /// Artist name input on the blocklist page changed
+    BlockArtistInputChanged(String),
+    /// Block the artist typed on the blocklist page
+    BlockArtist,
+    /// Remove an artist from the blocklist
+    UnblockArtist(String),
+
+    // ============ Song Info ============
+    /// Overrides for NCM song details loaded into the override store
+    SongOverridesLoaded,
+    /// Open the editor for the current NCM song's details
+    EditSongInfo,
+    /// Title field of the song info editor changed
+    SongInfoTitleChanged(String),
+    /// Artist field of the song info editor changed
+    SongInfoArtistChanged(String),
+    /// Album field of the song info editor changed
+    SongInfoAlbumChanged(String),
+    /// Save the edited details as an override
+    SaveSongInfo,
+    /// Drop the override and show NCM's details again
+    ResetSongInfo,
+    /// Close the song info editor without saving
+    CloseSongInfo,
+
+    // ============ Trash ============
+    /// Trashed playlists and songs loaded from the database
+    TrashLoaded(
+        Vec<crate::database::DbTrashedPlaylist>,
+        Vec<crate::database::DbTrashedSong>,
+    ),
+    /// Take a playlist out of the trash
+    RestoreTrashedPlaylist(i64),
+    /// Take a song out of the trash
+    RestoreTrashedSong(i64),
+    /// Permanently delete a trashed playlist
+    PurgeTrashedPlaylist(i64),
+    /// Permanently delete a trashed song
+    PurgeTrashedSong(i64),
+    /// Permanently delete everything in the trash (asks for a second click)
+    EmptyTrash,
+    /// The trash changed in the database; reload it and the library
+    TrashChanged,
+
+    // ============ User Profile ============
+    /// Open an NCM user's profile page
+    OpenUserProfile(u64),
+    /// Profile loaded (user ID, profile or error message)
+    UserProfileLoaded(u64, Result<UserProfile, String>),
+    /// Profile avatar downloaded (user ID, path)
+    UserProfileAvatarLoaded(u64, PathBuf),
+    /// Created and subscribed playlists loaded (user ID, playlists or error message)
+    UserProfilePlaylistsLoaded(u64, Result<UserPlaylists, String>),
+    /// Listening rank loaded (user ID, all time, records or None if private)
+    UserPlayRecordLoaded(u64, bool, Result<Option<Vec<PlayRecord>>, String>),
+    /// Show the all-time listening rank instead of last week's
+    SetProfileRankAllTime(bool),
+
+    // ============ Favorites ============
+    /// Liked songs loaded from the database
+    FavoritesLoaded(crate::features::favorites::Favorites),
+    /// Compare local favorites with NCM likes
+    SyncFavorites,
+    /// NCM likes fetched for comparison (or error message)
+    NcmLikesFetched(Result<crate::api::LikedSongIds, String>),
+    /// Saved liked songs playlist loaded from the database
+    LikedMirrorLoaded(Option<crate::features::favorites::LikedMirror>),
+    /// Bring the saved liked songs playlist up to date with NCM
+    SyncLikedMirror,
+    /// Liked songs playlist synced (mirror, songs added since the last copy)
+    LikedMirrorSynced(Result<(crate::features::favorites::LikedMirror, Vec<SongInfo>), String>),
+
+    // ============ NCM Matching ============
+    /// Local song to NCM track links loaded from the database
+    NcmLinksLoaded(std::collections::HashMap<SongId, u64>),
+    /// Look up a local song on NCM by its audio
+    MatchSongOnNcm(SongId),
+    /// Lookup finished (song ID, matched NCM ID and name, or error message)
+    NcmMatchFinished(SongId, Result<Option<(u64, String)>, String>),
+    /// Start publishing a local playlist as an NCM playlist
+    PublishPlaylistToNcm(i64),
+    /// One song of the playlist being published was looked up
+    PublishTrackMatched(crate::features::ncm_publish::PublishTrack),
+    /// Pick a result for a close call (track index, result index or none to skip)
+    ChoosePublishMatch(usize, Option<usize>),
+    /// Create the NCM playlist from the reviewed matches
+    ConfirmPublish,
+    /// NCM playlist created (its ID) or error message
+    PublishFinished(Result<u64, String>),
+    /// Open the NCM playlist just published
+    OpenPublishedPlaylist,
+    /// Close the publish dialog, stopping any lookups
+    ClosePublishDialog,
+    /// Check which songs of an NCM playlist can't be played (internal playlist ID)
+    CheckPlaylistHealth(i64),
+    /// Availability check finished (NCM playlist ID, report or error message)
+    PlaylistHealthChecked(
+        u64,
+        Result<crate::features::playlist_health::HealthReport, String>,
+    ),
+    /// Remove the unavailable songs from the checked playlist
+    RemoveUnavailableTracks,
+    /// Swap flagged songs for other versions that play
+    ReplaceFlaggedTracks,
+    /// Flagged songs removed (count) or error message
+    FlaggedTracksRemoved(Result<usize, String>),
+    /// Flagged songs replaced (replaced, flagged) or error message
+    FlaggedTracksReplaced(Result<(usize, usize), String>),
+    /// Save the list of flagged songs to a file
+    ExportFlaggedTracks,
+    /// Save location picked for the flagged song list
+    FlaggedTracksExportPathPicked(Option<PathBuf>),
+    /// Flagged song list written (path) or error message
+    FlaggedTracksExported(Result<PathBuf, String>),
+    /// Close the availability check dialog
+    ClosePlaylistHealth,
+
+    // ============ Song Analysis ============
+    /// Stored tempo, key and energy of local songs, by song ID
+    SongAnalysesLoaded(
+        std::collections::HashMap<SongId, crate::features::song_analysis::SongAnalysis>,
+    ),
+    /// Toggle analyzing new local songs in the background
+    UpdateSongAnalysis(bool),
+    /// Analyze the local songs that haven't been yet
+    AnalyzeLibrary,
+    /// Analysis run started with this many songs to go
+    SongAnalysisStarted(usize),
+    /// One song analyzed
+    SongAnalyzed(SongId, crate::features::song_analysis::SongAnalysis),
+    /// Analysis run finished
+    SongAnalysisFinished,
+    /// Queue library songs close in energy to the current one after it
+    QueueSimilarEnergy,
+    /// Cycle the playlist page through tempo ascending, descending and unsorted
+    SortPlaylistByTempo,
+
+    // ============ Listen Mode ============
+    /// Allow or forbid recording what other apps play
+    UpdateListenPermission(bool),
+    /// Start or stop listen mode
+    ToggleListenMode,
+    /// Listen mode started fingerprinting this many library songs
+    ListenIndexStarted(usize),
+    /// One library song fingerprinted
+    ListenSongIndexed,
+    /// Library fingerprints loaded, for the run with this generation
+    ListenIndexReady(u64, Arc<crate::features::listen::ListenIndex>),
+    /// Record and look up the next clip, for the run with this generation
+    ListenCapture(u64),
+    /// A clip was looked up: the song heard, or an error from recording
+    ListenHeard(
+        u64,
+        Result<Option<crate::features::listen::ListenMatch>, String>,
+    ),
+    /// Lyrics of a song heard in listen mode
+    ListenLyricsLoaded(SongId, Vec<crate::ui::pages::LyricLine>),
+    /// Redraw the listen mode lyrics
+    ListenTick,
+
+    // ============ Scheduled Playback ============
+    /// Add a schedule
+    AddSchedule,
+    /// Remove the schedule at an index
+    RemoveSchedule(usize),
+    /// Replace the schedule at an index
+    UpdateSchedule(usize, crate::features::schedules::Schedule),
+    /// Start any schedule that is due
+    CheckSchedules,
+    /// Raise the volume of a schedule that started playing
+    ScheduleRampTick,
+
+    // ============ Call Ducking ============
+    /// See which apps are recording from the microphone
+    CheckMicrophone,
+    /// Apps recording, or `None` where the platform doesn't say
+    MicrophoneChecked(Option<Vec<String>>),
+
+    // ============ Export ============
+    /// Open the export dialog for a playlist (internal ID)
+    ExportPlaylist(i64),
+    /// Songs of the playlist to export loaded (playlist name, songs)
+    ExportSongsLoaded(String, Vec<DbSong>),
+    ExportFormatChanged(crate::features::export::ExportFormat),
+    ExportBitrateChanged(u32),
+    /// Pick the folder to export to
+    PickExportFolder,
+    ExportFolderPicked(Option<std::path::PathBuf>),
+    /// Queue the dialog's songs for export
+    StartExport,
+    CloseExport,
+    /// Export the next queued song
+    ExportNext,
+    /// A song finished exporting (generation, file written or `None` when
+    /// the song isn't on this device)
+    ExportSongDone(u64, Result<Option<std::path::PathBuf>, String>),
+    /// Stop exporting and drop the queue
+    CancelExport,
+    /// Open the folder songs were exported to
+    OpenExportFolder,
+
+    // ============ Cover viewer ============
+    /// Show the playing song's cover full-screen
+    OpenCoverViewer,
+    CloseCoverViewer,
+    /// The original cover of a song was downloaded (song_id, path)
+    FullCoverReady(SongId, String),
+    /// Ask where to save the cover shown
+    SaveCoverImage,
+    CoverSavePicked(Option<std::path::PathBuf>),
+    /// The cover was copied (file written, or the error)
+    CoverImageSaved(Result<std::path::PathBuf, String>),
+
+    // ============ Auto-DJ ============
+    /// Songs fetched from the Auto-DJ pool (and whether to play the first one added)
+    AutoDjSongsFetched(Result<Vec<DbSong>, String>, bool),
+
+    // ============ Autoplay ============
+    /// Similar songs fetched from NCM to carry on after the queue ended
+    AutoplaySongsFetched(Result<Vec<DbSong>, String>),
+
+    // ============ Power ============
+    /// The system is about to sleep or just woke up
+    PowerEvent(crate::platform::power::PowerEvent),
+
+    // ============ Memory ============
+    /// Show or hide the memory overlay
+    ToggleMemoryOverlay,
+    /// Take a memory sample and enforce the soft budgets
+    SampleMemory,
+    /// Memory sample taken (None if the job was cancelled)
+    MemorySampled(Option<crate::features::memory::MemoryReport>),
+
+    // ============ Crash Recovery ============
+    /// Restore the session saved when the app last crashed
+    RestoreCrashedSession,
+    /// Crashed session written back to the database (state, queue)
+    CrashedSessionRestored(Option<(DbPlaybackState, Vec<DbSong>)>),
+    /// Open a pre-filled issue with the anonymized crash report
+    SubmitCrashReport,
+    /// Close the crash recovery dialog
+    DismissCrashReport,
+
+    // ============ Updater ============
+    /// Check GitHub releases for a newer version (true when user-initiated)
+    CheckForUpdates(bool),
+    /// Update check finished (manual flag, newer release if any)
+    UpdateCheckFinished(
+        bool,
+        Result<Option<crate::features::updater::ReleaseInfo>, String>,
+    ),
+    /// Background download finished (verified file or error message)
+    UpdateDownloaded(Result<PathBuf, String>),
+    /// Install the downloaded update and restart
+    InstallUpdate,
+    /// Open the release page of the available update
+    OpenReleasePage,
+    /// Close the update prompt
+    DismissUpdatePrompt,
+    /// Running version recorded, with the version that ran before it
+    AppVersionRecorded(Option<String>),
+    /// Release notes of the version just updated to
+    ChangelogLoaded(crate::features::updater::ReleaseInfo),
+    /// Close the changelog dialog
+    DismissChangelog,
+    /// Show every version with its highlights, from Settings → About
+    OpenVersionHistory,
+    /// Versions that ran here, for the history
+    VersionHistoryLoaded(Vec<crate::database::DbAppVersion>),
+    /// Toggle the what's-new dialog after updates
+    UpdateShowWhatsNew(bool),
+
     // ============ Player Events (Event-Driven Architecture) ============
     /// Streaming download event (song_id, event)
-    StreamingEvent(i64, crate::audio::streaming::StreamingEvent),
+    StreamingEvent(SongId, crate::audio::streaming::StreamingEvent),
     /// Audio thread event
     AudioEvent(crate::audio::AudioEvent),
 }
@@ -524,6 +1211,21 @@ pub enum IconId {
     Sort,
     Like,
     Download,
+    Publish,
+    Health,
+    Export,
+    PlaybackOptions,
+}
+
+/// Places elements fly between in shared-element transitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SharedElement {
+    /// Cover in the playlist page header
+    PlaylistHeaderCover,
+    /// Current song cover in the player bar
+    PlayerBarCover,
+    /// Cover on the lyrics page
+    LyricsCover,
 }
 
 /// Sidebar item identifiers for hover tracking
@@ -566,12 +1268,14 @@ impl std::fmt::Debug for Message {
             Self::AnimationTick => simple!("AnimationTick"),
             Self::PlaybackTick => simple!("PlaybackTick"),
             Self::CarouselTick => simple!("CarouselTick"),
+            Self::SharedElementMoved(element, _) => simple!("SharedElementMoved", "{:?}", element),
             Self::Noop => simple!("Noop"),
             Self::NoOp => simple!("NoOp"),
 
             // Large Vec data - only show count
             Self::SongsLoaded(v) => simple!("SongsLoaded", "{} songs", v.len()),
             Self::PlaylistsLoaded(v) => simple!("PlaylistsLoaded", "{} playlists", v.len()),
+            Self::PlaylistMosaicsReady(m) => simple!("PlaylistMosaicsReady", "{} mosaics", m.len()),
             Self::QueueRestored(v) => simple!("QueueRestored", "{} songs", v.len()),
             Self::SongResolvedForRestore(idx, result, pos) => {
                 simple!(
@@ -582,10 +1286,18 @@ impl std::fmt::Debug for Message {
                     pos
                 )
             }
-            Self::QueueLoaded(v) => simple!("QueueLoaded", "{} songs", v.len()),
+            Self::QueueLoaded(id, v) => simple!("QueueLoaded", "id={}, {} songs", id, v.len()),
             Self::RecentlyPlayedLoaded(v) => simple!("RecentlyPlayedLoaded", "{} songs", v.len()),
             Self::BannersLoaded(v) => simple!("BannersLoaded", "{} banners", v.len()),
             Self::TopPicksLoaded(v) => simple!("TopPicksLoaded", "{} picks", v.len()),
+            Self::HomeToplistsLoaded(v) => simple!("HomeToplistsLoaded", "{} charts", v.len()),
+            Self::DailySongsLoaded(v) => simple!("DailySongsLoaded", "{} songs", v.len()),
+            Self::HomeRecentLoaded(v) => simple!("HomeRecentLoaded", "{} songs", v.len()),
+            Self::ToggleHomeCustomize => simple!("ToggleHomeCustomize"),
+            Self::ToggleHomeSection(s) => simple!("ToggleHomeSection", "{:?}", s),
+            Self::MoveHomeSection(s, offset) => {
+                simple!("MoveHomeSection", "{:?} by {}", s, offset)
+            }
             Self::TrendingSongsLoaded(v) => simple!("TrendingSongsLoaded", "{} songs", v.len()),
             Self::UserPlaylistsLoaded(v) => simple!("UserPlaylistsLoaded", "{} playlists", v.len()),
             Self::NcmPlaylistSongCoversBatchLoaded(v) => {
@@ -600,17 +1312,40 @@ impl std::fmt::Debug for Message {
             Self::DatabaseReady(_) => simple!("DatabaseReady"),
             Self::CoverCacheReady(_) => simple!("CoverCacheReady"),
             Self::TrayStarted(_) => simple!("TrayStarted"),
+            Self::OutputDevicesLoaded(v) => simple!("OutputDevicesLoaded", "{} devices", v.len()),
 
             // Complex types - show key identifier only
             Self::NcmPlaylistDetailLoaded(d) => simple!("NcmPlaylistDetailLoaded", "id={}", d.id),
             Self::PlaylistViewLoaded(v) => simple!("PlaylistViewLoaded", "id={}", v.id),
+            Self::PlaylistPlaybackLoaded(id, p) => {
+                simple!("PlaylistPlaybackLoaded", "id={}, {:?}", id, p)
+            }
+            Self::TogglePlaylistPlaybackPanel => simple!("TogglePlaylistPlaybackPanel"),
+            Self::UpdatePlaylistPlayback(p) => simple!("UpdatePlaylistPlayback", "{:?}", p),
+            Self::PlaylistBackdropReady(id, _, _, w, h) => {
+                simple!("PlaylistBackdropReady", "id={}, {}x{}", id, w, h)
+            }
             Self::NcmPlaylistSongsReady(id, songs, _, _, _) => {
                 simple!("NcmPlaylistSongsReady", "id={}, {} songs", id, songs.len())
             }
+            Self::NcmPlaylistSongsPage(id, songs, _, fetched, total) => simple!(
+                "NcmPlaylistSongsPage",
+                "id={}, {} songs, {}/{}",
+                id,
+                songs.len(),
+                fetched,
+                total
+            ),
+            Self::NcmPlaylistTracksSynced(id, track_ids, added, _) => simple!(
+                "NcmPlaylistTracksSynced",
+                "id={}, {} tracks, {} added",
+                id,
+                track_ids.len(),
+                added.len()
+            ),
             Self::PlaybackStateLoaded(_) => simple!("PlaybackStateLoaded"),
             Self::ScanProgressUpdate(_) => simple!("ScanProgressUpdate"),
             Self::LoginSuccess(_) => simple!("LoginSuccess"),
-            Self::UserInfoLoaded(_) => simple!("UserInfoLoaded"),
             Self::AutoLoginResult(r, retry) => simple!(
                 "AutoLoginResult",
                 "success={}, retry={}",
@@ -627,6 +1362,17 @@ impl std::fmt::Debug for Message {
             Self::NavigateForward => simple!("NavigateForward"),
             Self::LibrarySelect(item) => simple!("LibrarySelect", "{:?}", item),
             Self::SearchChanged(_) => simple!("SearchChanged"),
+            Self::ToggleSearchDropdown => simple!("ToggleSearchDropdown"),
+            Self::CloseSearchDropdown => simple!("CloseSearchDropdown"),
+            Self::SearchHistoryLoaded(v) => simple!("SearchHistoryLoaded", "{} items", v.len()),
+            Self::HotSearchesLoaded(v) => simple!("HotSearchesLoaded", "{} items", v.len()),
+            Self::SearchSuggestDue(g) => simple!("SearchSuggestDue", "{}", g),
+            Self::SearchSuggestionsLoaded(_, v) => {
+                simple!("SearchSuggestionsLoaded", "{} items", v.len())
+            }
+            Self::SelectSearchKeyword(_) => simple!("SelectSearchKeyword"),
+            Self::DeleteSearchHistory(_) => simple!("DeleteSearchHistory"),
+            Self::ClearSearchHistory => simple!("ClearSearchHistory"),
             Self::PlayHero => simple!("PlayHero"),
             Self::ImportLocalPlaylist => simple!("ImportLocalPlaylist"),
             Self::FolderSelected(p) => simple!("FolderSelected", "{:?}", p.as_ref().map(|_| "...")),
@@ -634,46 +1380,145 @@ impl std::fmt::Debug for Message {
             // Window
             Self::WindowMinimize => simple!("WindowMinimize"),
             Self::WindowMaximize => simple!("WindowMaximize"),
-            Self::MousePressed => simple!("MousePressed"),
+            Self::MousePressed(_) => simple!("MousePressed"),
             Self::MouseReleased => simple!("MouseReleased"),
-            Self::MouseMoved(_) => simple!("MouseMoved"),
+            Self::MouseMoved(..) => simple!("MouseMoved"),
             Self::OpenSettings => simple!("OpenSettings"),
             Self::OpenSettingsWithCloseLyrics => simple!("OpenSettingsWithCloseLyrics"),
             Self::OpenAudioEngine => simple!("OpenAudioEngine"),
+            Self::OpenPlugins => simple!("OpenPlugins"),
+            Self::OpenLogs => simple!("OpenLogs"),
+            Self::OpenAnnualReport => simple!("OpenAnnualReport"),
+            Self::OpenBlocklist => simple!("OpenBlocklist"),
+            Self::OpenTrash => simple!("OpenTrash"),
 
             // Settings - most are simple
             Self::UpdateCloseBehavior(b) => simple!("UpdateCloseBehavior", "{:?}", b),
             Self::SaveSettings => simple!("SaveSettings"),
             Self::UpdateFadeInOut(b) => simple!("UpdateFadeInOut", "{}", b),
             Self::UpdateVolumeNormalization(b) => simple!("UpdateVolumeNormalization", "{}", b),
+            Self::UpdateUiSounds(b) => simple!("UpdateUiSounds", "{}", b),
+            Self::UpdateAutoplaySimilar(b) => simple!("UpdateAutoplaySimilar", "{}", b),
+            Self::UpdateSmartResume(b) => simple!("UpdateSmartResume", "{}", b),
+            Self::UpdateSmartResumeAfter(m) => simple!("UpdateSmartResumeAfter", "{}", m),
+            Self::UpdateSmartResumeRewind(s) => simple!("UpdateSmartResumeRewind", "{}", s),
+            Self::UpdateCallAction(a) => simple!("UpdateCallAction", "{:?}", a),
+            Self::UpdateCallLowerPercent(p) => simple!("UpdateCallLowerPercent", "{}", p),
+            Self::UpdateCallAnyApp(b) => simple!("UpdateCallAnyApp", "{}", b),
+            Self::UpdateCallAfter(s) => simple!("UpdateCallAfter", "{}", s),
+            Self::UpdateAutoDjPool(p) => simple!("UpdateAutoDjPool", "{:?}", p),
+            Self::UpdateAutoDjCrossfade(s) => simple!("UpdateAutoDjCrossfade", "{}", s),
+            Self::UpdateSkipBackSecs(s) => simple!("UpdateSkipBackSecs", "{}", s),
+            Self::UpdateSkipForwardSecs(s) => simple!("UpdateSkipForwardSecs", "{}", s),
+            Self::UpdateSeekStepSecs(s) => simple!("UpdateSeekStepSecs", "{}", s),
+            Self::UpdateVolumeStepPercent(p) => simple!("UpdateVolumeStepPercent", "{}", p),
             Self::UpdateMusicQuality(q) => simple!("UpdateMusicQuality", "{:?}", q),
             Self::UpdateEqualizerEnabled(b) => simple!("UpdateEqualizerEnabled", "{}", b),
             Self::UpdateEqualizerPreset(p) => simple!("UpdateEqualizerPreset", "{:?}", p),
             Self::UpdateEqualizerValues(_) => simple!("UpdateEqualizerValues"),
             Self::UpdateEqualizerPreamp(v) => simple!("UpdateEqualizerPreamp", "{:.1}", v),
+            Self::UpdateDspPresets(p) => simple!("UpdateDspPresets", "{:?}", p),
+            Self::UpdateMatchSampleRate(v) => simple!("UpdateMatchSampleRate", "{}", v),
             Self::UpdateSpectrumDecay(v) => simple!("UpdateSpectrumDecay", "{:.2}", v),
             Self::UpdateSpectrumBarsMode(b) => simple!("UpdateSpectrumBarsMode", "{}", b),
             Self::UpdateDarkMode(b) => simple!("UpdateDarkMode", "{}", b),
             Self::UpdateAppLanguage(l) => simple!("UpdateAppLanguage", "{}", l),
             Self::UpdatePowerSavingMode(b) => simple!("UpdatePowerSavingMode", "{}", b),
+            Self::UpdateLyricsTranslationDefault(b) => {
+                simple!("UpdateLyricsTranslationDefault", "{}", b)
+            }
+            Self::UpdateLyricsRomanizationDefault(b) => {
+                simple!("UpdateLyricsRomanizationDefault", "{}", b)
+            }
+            Self::ToggleLyricsTuningPanel => simple!("ToggleLyricsTuningPanel"),
+            Self::UpdateLyricsTuning(t) => simple!("UpdateLyricsTuning", "{:?}", t),
+            Self::ResetLyricsTuning => simple!("ResetLyricsTuning"),
+            Self::UpdateLyricsShareOffer(b) => simple!("UpdateLyricsShareOffer", "{}", b),
+            Self::UpdateLyricsShareCredit(_) => simple!("UpdateLyricsShareCredit"),
+            Self::UpdateLyricsSaveOffer(b) => simple!("UpdateLyricsSaveOffer", "{}", b),
+            Self::UpdateLyricsSaveTarget(t) => simple!("UpdateLyricsSaveTarget", "{:?}", t),
+            Self::UpdateArtworkBackdrop(b) => simple!("UpdateArtworkBackdrop", "{:?}", b),
+            Self::UpdateCoverTheming(b) => simple!("UpdateCoverTheming", "{}", b),
+            Self::UpdateLyricsBackground(b) => simple!("UpdateLyricsBackground", "{:?}", b),
             Self::UpdateMaxCacheMb(m) => simple!("UpdateMaxCacheMb", "{}", m),
             Self::ClearCache => simple!("ClearCache"),
             Self::CacheCleared(n, b) => simple!("CacheCleared", "{} files, {} bytes", n, b),
             Self::RefreshCacheStats => simple!("RefreshCacheStats"),
             Self::EnforceCacheLimit => simple!("EnforceCacheLimit"),
             Self::UpdateAudioOutputDevice(_) => simple!("UpdateAudioOutputDevice"),
+            Self::UpdateOutputLatency(ms) => simple!("UpdateOutputLatency", "{}", ms),
+            Self::StartLatencyCalibration => simple!("StartLatencyCalibration"),
+            Self::LatencyCalibrationBeat => simple!("LatencyCalibrationBeat"),
+            Self::LatencyCalibrationTap => simple!("LatencyCalibrationTap"),
+            Self::FinishLatencyCalibration => simple!("FinishLatencyCalibration"),
+            Self::CancelLatencyCalibration => simple!("CancelLatencyCalibration"),
             Self::UpdateAudioBufferSize(s) => simple!("UpdateAudioBufferSize", "{}", s),
+            Self::UpdateLaunchAtLogin(b) => simple!("UpdateLaunchAtLogin", "{}", b),
+            Self::LaunchAtLoginUpdated(b, r) => {
+                simple!("LaunchAtLoginUpdated", "{}, {:?}", b, r)
+            }
+            Self::UpdateStartMinimized(b) => simple!("UpdateStartMinimized", "{}", b),
+            Self::UpdateReleaseNotifications(b) => {
+                simple!("UpdateReleaseNotifications", "{}", b)
+            }
+            Self::UpdateDailyCheckIn(b) => simple!("UpdateDailyCheckIn", "{}", b),
+            Self::UpdateResumeOnStartup(b) => simple!("UpdateResumeOnStartup", "{}", b),
+            Self::UpdateUpdateChannel(c) => simple!("UpdateUpdateChannel", "{:?}", c),
+            Self::UpdateAutoCheckUpdates(b) => simple!("UpdateAutoCheckUpdates", "{}", b),
             Self::UpdateProxyType(t) => simple!("UpdateProxyType", "{:?}", t),
             Self::UpdateProxyHost(_) => simple!("UpdateProxyHost"),
             Self::UpdateProxyPort(_) => simple!("UpdateProxyPort"),
             Self::UpdateProxyUsername(_) => simple!("UpdateProxyUsername"),
             Self::UpdateProxyPassword(_) => simple!("UpdateProxyPassword"),
+            Self::UpdateBandwidthLimit(k) => simple!("UpdateBandwidthLimit", "{}", k),
+            Self::UpdateMeteredMode(m) => simple!("UpdateMeteredMode", "{:?}", m),
             Self::ApplyProxySettings => simple!("ApplyProxySettings"),
+            Self::DetectMeteredConnection => simple!("DetectMeteredConnection"),
+            Self::MeteredStatusDetected(m) => simple!("MeteredStatusDetected", "{:?}", m),
+            Self::CheckNetworkRoute => simple!("CheckNetworkRoute"),
+            Self::NetworkRouteChecked(r) => simple!("NetworkRouteChecked", "{}", r.is_some()),
             Self::ScrollToSection(s) => simple!("ScrollToSection", "{:?}", s),
             Self::SettingsScrolled(y) => simple!("SettingsScrolled", "{:.0}", y),
+            Self::SettingsSearchChanged(q) => simple!("SettingsSearchChanged", "{}", q),
+            Self::SettingsCategorySelected(c) => simple!("SettingsCategorySelected", "{:?}", c),
             Self::StartEditingKeybinding(a) => simple!("StartEditingKeybinding", "{:?}", a),
             Self::CancelEditingKeybinding => simple!("CancelEditingKeybinding"),
             Self::KeybindingKeyPressed(_, _) => simple!("KeybindingKeyPressed"),
+            Self::ExportSettingsProfile => simple!("ExportSettingsProfile"),
+            Self::SettingsProfileExportPicked(path) => {
+                simple!("SettingsProfileExportPicked", "{:?}", path)
+            }
+            Self::SettingsProfileExported(r) => simple!("SettingsProfileExported", "{:?}", r),
+            Self::ImportSettingsProfile => simple!("ImportSettingsProfile"),
+            Self::SettingsProfileImportPicked(path) => {
+                simple!("SettingsProfileImportPicked", "{:?}", path)
+            }
+            Self::SettingsProfileImported(r) => simple!(
+                "SettingsProfileImported",
+                "{:?}",
+                r.as_ref().map(|(_, secrets)| secrets)
+            ),
+            // Never log the passphrase
+            Self::UpdateSettingsProfilePassphrase(_) => simple!("UpdateSettingsProfilePassphrase"),
+            // Hook targets may hold webhook tokens
+            Self::AddEventHook => simple!("AddEventHook"),
+            Self::RemoveEventHook(i) => simple!("RemoveEventHook", "{}", i),
+            Self::UpdateEventHook(i, hook) => {
+                simple!(
+                    "UpdateEventHook",
+                    "{} {:?} {:?}",
+                    i,
+                    hook.event,
+                    hook.action
+                )
+            }
+            Self::TestEventHook(i) => simple!("TestEventHook", "{}", i),
+            Self::EventHookTested(r) => simple!("EventHookTested", "{:?}", r),
+            Self::UpdateNowPlayingOutput(v) => simple!("UpdateNowPlayingOutput", "{}", v),
+            Self::UpdateNowPlayingArtwork(v) => simple!("UpdateNowPlayingArtwork", "{}", v),
+            Self::PickNowPlayingFolder => simple!("PickNowPlayingFolder"),
+            Self::NowPlayingFolderPicked(path) => simple!("NowPlayingFolderPicked", "{:?}", path),
+            Self::OpenNowPlayingFolder => simple!("OpenNowPlayingFolder"),
 
             // Database
             Self::DatabaseError(e) => simple!("DatabaseError", "{}", e),
@@ -703,6 +1548,10 @@ impl std::fmt::Debug for Message {
             Self::PlaylistSearchChanged(_) => simple!("PlaylistSearchChanged"),
             Self::PlaylistSearchSubmit => simple!("PlaylistSearchSubmit"),
             Self::PlaylistSearchBlur => simple!("PlaylistSearchBlur"),
+            Self::PlaylistSortChanged(sort) => simple!("PlaylistSortChanged", "{:?}", sort),
+            Self::PlaylistFilterChanged(filter) => {
+                simple!("PlaylistFilterChanged", "{:?}", filter)
+            }
 
             // Edit dialog
             Self::EditPlaylist(id) => simple!("EditPlaylist", "{}", id),
@@ -715,10 +1564,21 @@ impl std::fmt::Debug for Message {
             Self::PlaylistUpdated(id) => simple!("PlaylistUpdated", "{}", id),
 
             // Lyrics
+            Self::SharedCoverPressed(_, bounds) => {
+                simple!("SharedCoverPressed", "{:?}", bounds)
+            }
+            Self::BackdropCaptured(_) => simple!("BackdropCaptured"),
+            Self::BackdropFrosted(_) => simple!("BackdropFrosted"),
             Self::OpenLyricsPage => simple!("OpenLyricsPage"),
             Self::CloseLyricsPage => simple!("CloseLyricsPage"),
             Self::LyricsScroll(d) => simple!("LyricsScroll", "{:.1}", d),
-            Self::WindowResized(size) => simple!("WindowResized", "{}x{}", size.width, size.height),
+            Self::ResumeLyricsAutoScroll => simple!("ResumeLyricsAutoScroll"),
+            Self::WindowResized(_, size) => {
+                simple!("WindowResized", "{}x{}", size.width, size.height)
+            }
+            Self::OpenLyricsWindow => simple!("OpenLyricsWindow"),
+            Self::CloseLyricsWindow => simple!("CloseLyricsWindow"),
+            Self::ToggleLyricsWindowFullscreen => simple!("ToggleLyricsWindowFullscreen"),
             Self::LyricsFontSystemReady(_) => simple!("LyricsFontSystemReady"),
             Self::LyricsLoaded(id, lines) => {
                 simple!("LyricsLoaded", "id={}, {} lines", id, lines.len())
@@ -744,6 +1604,41 @@ impl std::fmt::Debug for Message {
             Self::LyricsCoverImageReady(id, _, w, h) => {
                 simple!("LyricsCoverImageReady", "id={}, {}x{}", id, w, h)
             }
+            Self::LargeCoverReady(id, _) => simple!("LargeCoverReady", "{}", id),
+            Self::ToggleLyricsTranslation => simple!("ToggleLyricsTranslation"),
+            Self::ToggleLyricsRomanization => simple!("ToggleLyricsRomanization"),
+            Self::ToggleSongCredits => simple!("ToggleSongCredits"),
+            Self::SongCreditsLoaded(id, credits) => {
+                simple!("SongCreditsLoaded", "{} ({})", id, credits.is_some())
+            }
+            Self::OpenCreditArtist(name) => simple!("OpenCreditArtist", "{}", name),
+            Self::LyricsShareChecked(id, edited) => {
+                simple!("LyricsShareChecked", "{}, {:?}", id, edited)
+            }
+            Self::OpenLyricsShare => simple!("OpenLyricsShare"),
+            Self::LyricsShareReady(id, r) => {
+                simple!("LyricsShareReady", "{}, ok={}", id, r.is_ok())
+            }
+            Self::ToggleLyricsShareCredit(b) => simple!("ToggleLyricsShareCredit", "{}", b),
+            Self::ConfirmLyricsShare => simple!("ConfirmLyricsShare"),
+            Self::LyricsShareFinished(r) => simple!("LyricsShareFinished", "{:?}", r),
+            Self::CloseLyricsShareDialog => simple!("CloseLyricsShareDialog"),
+            Self::LyricsSaveChecked(id, savable) => {
+                simple!("LyricsSaveChecked", "{}, {}", id, savable)
+            }
+            Self::SaveLyricsToFile => simple!("SaveLyricsToFile"),
+            Self::LyricsSaved(id, r) => simple!("LyricsSaved", "{}, {:?}", id, r),
+            Self::PickLyricsSaveFolder => simple!("PickLyricsSaveFolder"),
+            Self::LyricsSaveFolderPicked(path) => simple!("LyricsSaveFolderPicked", "{:?}", path),
+            Self::LyricsSaveBatchStarted(n) => simple!("LyricsSaveBatchStarted", "{}", n),
+            Self::LyricsSaveBatchSongDone(saved) => {
+                simple!("LyricsSaveBatchSongDone", "{}", saved)
+            }
+            Self::LyricsSaveBatchFinished => simple!("LyricsSaveBatchFinished"),
+            Self::CancelLyricsSaveBatch => simple!("CancelLyricsSaveBatch"),
+            Self::LyricsSubLinesLoaded(id, sub_lines) => {
+                simple!("LyricsSubLinesLoaded", "id={}, {:?}", id, sub_lines)
+            }
 
             // Playback controls
             Self::TogglePlayback => simple!("TogglePlayback"),
@@ -751,9 +1646,38 @@ impl std::fmt::Debug for Message {
             Self::PrevSong => simple!("PrevSong"),
             Self::SeekPreview(p) => simple!("SeekPreview", "{:.2}", p),
             Self::SeekRelease => simple!("SeekRelease"),
+            Self::TrackQualitiesLoaded(id, q) => {
+                simple!("TrackQualitiesLoaded", "{}: {:?}", id, q)
+            }
+            Self::SelectTrackQuality(q) => simple!("SelectTrackQuality", "{:?}", q),
+            Self::SeekHover(p) => simple!("SeekHover", "{:?}", p),
             Self::SetVolume(v) => simple!("SetVolume", "{:.2}", v),
+            Self::VolumeWheel(d) => simple!("VolumeWheel", "{:?}", d),
+            Self::SeekWheel(d) => simple!("SeekWheel", "{:?}", d),
             Self::ToggleQueue => simple!("ToggleQueue"),
+            Self::QueueSourcesLoaded(_) => simple!("QueueSourcesLoaded"),
+            Self::ToggleQueueOfflineOnly => simple!("ToggleQueueOfflineOnly"),
+            Self::ToggleQueueSessions => simple!("ToggleQueueSessions"),
+            Self::QueueSessionsLoaded(s) => simple!("QueueSessionsLoaded", "{} sessions", s.len()),
+            Self::RestoreQueueSession(id) => simple!("RestoreQueueSession", "{}", id),
+            Self::QueueSessionRestored(r) => simple!(
+                "QueueSessionRestored",
+                "{:?}",
+                r.as_ref().map(|(songs, index)| (songs.len(), index))
+            ),
             Self::CyclePlayMode => simple!("CyclePlayMode"),
+            Self::ToggleStopAfterCurrent => simple!("ToggleStopAfterCurrent"),
+            Self::TogglePlayerMenu => simple!("TogglePlayerMenu"),
+            Self::ClosePlayerMenu => simple!("ClosePlayerMenu"),
+            Self::SkipBack => simple!("SkipBack"),
+            Self::SkipForward => simple!("SkipForward"),
+            Self::ChaptersLoaded(id, chapters) => {
+                simple!("ChaptersLoaded", "{}, {} chapters", id, chapters.len())
+            }
+            Self::ToggleChapters => simple!("ToggleChapters"),
+            Self::SeekToChapter(idx) => simple!("SeekToChapter", "{}", idx),
+            Self::PrevChapter => simple!("PrevChapter"),
+            Self::NextChapter => simple!("NextChapter"),
             Self::PreloadReady(idx, _, is_next) => {
                 simple!("PreloadReady", "idx={}, next={}", idx, is_next)
             }
@@ -791,7 +1715,7 @@ impl std::fmt::Debug for Message {
                     buffer.is_some()
                 )
             }
-            Self::SongResolveFailed => simple!("SongResolveFailed"),
+            Self::SongResolveFailed(e) => simple!("SongResolveFailed", "{:?}", e),
             Self::RemoveFromQueue(i) => simple!("RemoveFromQueue", "{}", i),
             Self::ClearQueue => simple!("ClearQueue"),
 
@@ -801,6 +1725,7 @@ impl std::fmt::Debug for Message {
 
             // Exit dialog
             Self::RequestClose => simple!("RequestClose"),
+            Self::WindowCloseRequested(_) => simple!("WindowCloseRequested"),
             Self::ConfirmExit => simple!("ConfirmExit"),
             Self::MinimizeToTray => simple!("MinimizeToTray"),
             Self::CancelExit => simple!("CancelExit"),
@@ -812,9 +1737,13 @@ impl std::fmt::Debug for Message {
             // Media Controls
             Self::MprisCommand(c) => simple!("MprisCommand", "{:?}", c),
             Self::MprisStartedWithHandle(_, _) => simple!("MprisStartedWithHandle"),
+            Self::MediaArtworkReady(id, _, path) => {
+                simple!("MediaArtworkReady", "{}, {:?}", id, path)
+            }
             Self::ShowWindow => simple!("ShowWindow"),
             Self::ToggleWindow => simple!("ToggleWindow"),
             Self::WindowOperationComplete => simple!("WindowOperationComplete"),
+            Self::FirstFrame => simple!("FirstFrame"),
 
             // NCM Login
             Self::TryAutoLogin(retry) => simple!("TryAutoLogin", "retry={}", retry),
@@ -823,23 +1752,38 @@ impl std::fmt::Debug for Message {
             Self::CheckQrStatus(_) => simple!("CheckQrStatus"),
             Self::QrLoginResult(s) => simple!("QrLoginResult", "{:?}", s),
             Self::Logout => simple!("Logout"),
+            Self::RefreshSession => simple!("RefreshSession"),
+            Self::SessionRefreshed(_) => simple!("SessionRefreshed"),
+            Self::SessionExpired => simple!("SessionExpired"),
+            Self::ApiFailed(e) => simple!("ApiFailed", "{:?}", e),
+            Self::RunDailyCheckIn => simple!("RunDailyCheckIn"),
+            Self::DailyCheckInDone(claimed, failed, _) => {
+                simple!("DailyCheckInDone", "claimed={}, failed={}", claimed, failed)
+            }
             Self::UserAvatarLoaded(_) => simple!("UserAvatarLoaded"),
             Self::ToggleLoginPopup => simple!("ToggleLoginPopup"),
 
             // NCM Homepage
             Self::BannerImageLoaded(i, _, _, _) => simple!("BannerImageLoaded", "idx={}", i),
             Self::BannerPlay(i) => simple!("BannerPlay", "{}", i),
+            Self::ConfirmOpenLink => simple!("ConfirmOpenLink"),
+            Self::CancelOpenLink => simple!("CancelOpenLink"),
             Self::CarouselNavigate(d) => simple!("CarouselNavigate", "{}", d),
             Self::OpenTrendingSongs => simple!("OpenTrendingSongs"),
             Self::SongCoverLoaded(id, _) => simple!("SongCoverLoaded", "{}", id),
+            Self::CoverShown(key, _) => simple!("CoverShown", "{:?}", key),
+            Self::CoverHidden(key) => simple!("CoverHidden", "{:?}", key),
+            Self::LazyCoverLoaded(key, path) => {
+                simple!("LazyCoverLoaded", "{:?}, ok={}", key, path.is_some())
+            }
             Self::ToggleFavorite(id) => simple!("ToggleFavorite", "{}", id),
-            Self::FavoriteStatusChanged(id, s) => simple!("FavoriteStatusChanged", "{}, {}", id, s),
             Self::OpenNcmPlaylist(id) => simple!("OpenNcmPlaylist", "{}", id),
             Self::ToggleBannerFavorite(i) => simple!("ToggleBannerFavorite", "{}", i),
 
             // Cloud Playlist
             Self::CurrentSongCoverReady(id, _) => simple!("CurrentSongCoverReady", "{}", id),
             Self::NcmPlaylistCoverLoaded(id, _) => simple!("NcmPlaylistCoverLoaded", "{}", id),
+            Self::NcmPlaylistPaletteReady(id, _) => simple!("NcmPlaylistPaletteReady", "{}", id),
             Self::NcmPlaylistCreatorAvatarLoaded(id, _) => {
                 simple!("NcmPlaylistCreatorAvatarLoaded", "{}", id)
             }
@@ -862,9 +1806,20 @@ impl std::fmt::Debug for Message {
             Self::DiscoverCoverAllocated(id, _) => simple!("DiscoverCoverAllocated", "{}", id),
             Self::HoverDiscoverPlaylist(id) => simple!("HoverDiscoverPlaylist", "{:?}", id),
             Self::PlayDiscoverPlaylist(id) => simple!("PlayDiscoverPlaylist", "{}", id),
+            Self::HotPlaylistsFailed => simple!("HotPlaylistsFailed"),
             Self::LoadMoreHotPlaylists => simple!("LoadMoreHotPlaylists"),
             Self::SeeAllRecommended => simple!("SeeAllRecommended"),
             Self::SeeAllHot => simple!("SeeAllHot"),
+            Self::NewReleasesLoaded(feed) => simple!(
+                "NewReleasesLoaded",
+                "{} artists, {} releases, {} fresh",
+                feed.followed.len(),
+                feed.releases.len(),
+                feed.fresh.len()
+            ),
+            Self::NewReleaseCoverLoaded(id, _) => simple!("NewReleaseCoverLoaded", "{}", id),
+            Self::PlayNewRelease(id) => simple!("PlayNewRelease", "{}", id),
+            Self::ToggleFollowArtist(id) => simple!("ToggleFollowArtist", "{}", id),
 
             // Search Page
             Self::SearchSubmit => simple!("SearchSubmit"),
@@ -879,6 +1834,10 @@ impl std::fmt::Debug for Message {
                     payload.playlists.len()
                 )
             }
+            Self::LocalSearchLoaded(keyword, songs) => {
+                simple!("LocalSearchLoaded", "{}: {} songs", keyword, songs.len())
+            }
+            Self::SearchSourceChanged(source) => simple!("SearchSourceChanged", "{:?}", source),
             Self::SearchFailed(e) => simple!("SearchFailed", "{}", e),
             Self::SearchPageChanged(page) => simple!("SearchPageChanged", "{}", page),
             Self::HoverSearchSong(id) => simple!("HoverSearchSong", "{:?}", id),
@@ -892,6 +1851,263 @@ impl std::fmt::Debug for Message {
             Self::SidebarResizeStart => simple!("SidebarResizeStart"),
             Self::SidebarResizeEnd => simple!("SidebarResizeEnd"),
 
+            // Plugins
+            Self::ReloadPlugins => simple!("ReloadPlugins"),
+            Self::PluginsLoaded(r) => simple!("PluginsLoaded", "{} plugins", r.plugins().len()),
+            Self::TogglePlugin(id, b) => simple!("TogglePlugin", "{}, {}", id, b),
+            Self::OpenPluginsFolder => simple!("OpenPluginsFolder"),
+            Self::PluginPanelsLoaded(p) => simple!("PluginPanelsLoaded", "{} panels", p.len()),
+            Self::PluginPanelAction(id, action) => {
+                simple!("PluginPanelAction", "{}, {}", id, action)
+            }
+
+            // Logs & Diagnostics
+            Self::RefreshLogs => simple!("RefreshLogs"),
+            Self::SetLogLevelFilter(l) => simple!("SetLogLevelFilter", "{}", l),
+            Self::ExportDiagnostics => simple!("ExportDiagnostics"),
+            Self::DiagnosticsPathPicked(p) => simple!("DiagnosticsPathPicked", "{:?}", p),
+            Self::DiagnosticsExported(r) => simple!("DiagnosticsExported", "{:?}", r),
+
+            // Annual Report
+            Self::AnnualReportYearsLoaded(years) => {
+                simple!("AnnualReportYearsLoaded", "{:?}", years)
+            }
+            Self::SelectAnnualReportYear(year) => simple!("SelectAnnualReportYear", "{}", year),
+            Self::AnnualReportLoaded(report) => simple!(
+                "AnnualReportLoaded",
+                "{}, {} plays",
+                report.year,
+                report.plays
+            ),
+            Self::ExportAnnualReport => simple!("ExportAnnualReport"),
+            Self::AnnualReportFolderPicked(p) => simple!("AnnualReportFolderPicked", "{:?}", p),
+            Self::AnnualReportExported(r) => simple!("AnnualReportExported", "{:?}", r),
+
+            // Blocklist
+            Self::BlocklistLoaded(list) => simple!(
+                "BlocklistLoaded",
+                "{} songs, {} artists",
+                list.songs().len(),
+                list.artists().len()
+            ),
+            Self::BlockCurrentSong => simple!("BlockCurrentSong"),
+            Self::UnblockSong(id) => simple!("UnblockSong", "{}", id),
+            Self::BlockArtistInputChanged(s) => simple!("BlockArtistInputChanged", "{}", s),
+            Self::BlockArtist => simple!("BlockArtist"),
+            Self::UnblockArtist(name) => simple!("UnblockArtist", "{}", name),
+
+            // Song Info
+            Self::SongOverridesLoaded => simple!("SongOverridesLoaded"),
+            Self::EditSongInfo => simple!("EditSongInfo"),
+            Self::SongInfoTitleChanged(s) => simple!("SongInfoTitleChanged", "{}", s),
+            Self::SongInfoArtistChanged(s) => simple!("SongInfoArtistChanged", "{}", s),
+            Self::SongInfoAlbumChanged(s) => simple!("SongInfoAlbumChanged", "{}", s),
+            Self::SaveSongInfo => simple!("SaveSongInfo"),
+            Self::ResetSongInfo => simple!("ResetSongInfo"),
+            Self::CloseSongInfo => simple!("CloseSongInfo"),
+
+            // Trash
+            Self::TrashLoaded(playlists, songs) => simple!(
+                "TrashLoaded",
+                "{} playlists, {} songs",
+                playlists.len(),
+                songs.len()
+            ),
+            Self::RestoreTrashedPlaylist(id) => simple!("RestoreTrashedPlaylist", "{}", id),
+            Self::RestoreTrashedSong(id) => simple!("RestoreTrashedSong", "{}", id),
+            Self::PurgeTrashedPlaylist(id) => simple!("PurgeTrashedPlaylist", "{}", id),
+            Self::PurgeTrashedSong(id) => simple!("PurgeTrashedSong", "{}", id),
+            Self::EmptyTrash => simple!("EmptyTrash"),
+            Self::TrashChanged => simple!("TrashChanged"),
+
+            // NCM Matching
+            Self::OpenUserProfile(id) => simple!("OpenUserProfile", "{}", id),
+            Self::UserProfileLoaded(id, r) => {
+                simple!("UserProfileLoaded", "{}, {}", id, r.is_ok())
+            }
+            Self::UserProfileAvatarLoaded(id, _) => simple!("UserProfileAvatarLoaded", "{}", id),
+            Self::UserProfilePlaylistsLoaded(id, r) => {
+                simple!("UserProfilePlaylistsLoaded", "{}, {}", id, r.is_ok())
+            }
+            Self::UserPlayRecordLoaded(id, all_time, r) => simple!(
+                "UserPlayRecordLoaded",
+                "{}, {}, {:?}",
+                id,
+                all_time,
+                r.as_ref().map(|records| records.as_ref().map(Vec::len))
+            ),
+            Self::SetProfileRankAllTime(all_time) => {
+                simple!("SetProfileRankAllTime", "{}", all_time)
+            }
+            Self::FavoritesLoaded(_) => simple!("FavoritesLoaded"),
+            Self::SyncFavorites => simple!("SyncFavorites"),
+            Self::NcmLikesFetched(r) => {
+                simple!(
+                    "NcmLikesFetched",
+                    "{:?}",
+                    r.as_ref().map(|likes| likes.ids.len())
+                )
+            }
+            Self::LikedMirrorLoaded(m) => simple!(
+                "LikedMirrorLoaded",
+                "{:?}",
+                m.as_ref().map(|m| m.songs.len())
+            ),
+            Self::SyncLikedMirror => simple!("SyncLikedMirror"),
+            Self::LikedMirrorSynced(r) => simple!(
+                "LikedMirrorSynced",
+                "{:?}",
+                r.as_ref().map(|(m, added)| (m.songs.len(), added.len()))
+            ),
+            Self::NcmLinksLoaded(links) => simple!("NcmLinksLoaded", "{} links", links.len()),
+            Self::MatchSongOnNcm(id) => simple!("MatchSongOnNcm", "{}", id),
+            Self::NcmMatchFinished(id, r) => simple!("NcmMatchFinished", "{}, {:?}", id, r),
+            Self::PublishPlaylistToNcm(id) => simple!("PublishPlaylistToNcm", "{}", id),
+            Self::PublishTrackMatched(track) => simple!("PublishTrackMatched", "{}", track.label),
+            Self::ChoosePublishMatch(track, choice) => {
+                simple!("ChoosePublishMatch", "{}, {:?}", track, choice)
+            }
+            Self::ConfirmPublish => simple!("ConfirmPublish"),
+            Self::PublishFinished(r) => simple!("PublishFinished", "{:?}", r),
+            Self::OpenPublishedPlaylist => simple!("OpenPublishedPlaylist"),
+            Self::ClosePublishDialog => simple!("ClosePublishDialog"),
+            Self::CheckPlaylistHealth(id) => simple!("CheckPlaylistHealth", "{}", id),
+            Self::PlaylistHealthChecked(id, r) => simple!(
+                "PlaylistHealthChecked",
+                "{}, {:?}",
+                id,
+                r.as_ref().map(|report| report.flagged.len())
+            ),
+            Self::RemoveUnavailableTracks => simple!("RemoveUnavailableTracks"),
+            Self::ReplaceFlaggedTracks => simple!("ReplaceFlaggedTracks"),
+            Self::FlaggedTracksRemoved(r) => simple!("FlaggedTracksRemoved", "{:?}", r),
+            Self::FlaggedTracksReplaced(r) => simple!("FlaggedTracksReplaced", "{:?}", r),
+            Self::ExportFlaggedTracks => simple!("ExportFlaggedTracks"),
+            Self::FlaggedTracksExportPathPicked(p) => {
+                simple!("FlaggedTracksExportPathPicked", "{:?}", p)
+            }
+            Self::FlaggedTracksExported(r) => simple!("FlaggedTracksExported", "{:?}", r),
+            Self::ClosePlaylistHealth => simple!("ClosePlaylistHealth"),
+            Self::SongAnalysesLoaded(analyses) => {
+                simple!("SongAnalysesLoaded", "{} songs", analyses.len())
+            }
+            Self::UpdateSongAnalysis(v) => simple!("UpdateSongAnalysis", "{}", v),
+            Self::AnalyzeLibrary => simple!("AnalyzeLibrary"),
+            Self::SongAnalysisStarted(n) => simple!("SongAnalysisStarted", "{}", n),
+            Self::SongAnalyzed(id, analysis) => simple!("SongAnalyzed", "{}, {:?}", id, analysis),
+            Self::SongAnalysisFinished => simple!("SongAnalysisFinished"),
+            Self::QueueSimilarEnergy => simple!("QueueSimilarEnergy"),
+            Self::SortPlaylistByTempo => simple!("SortPlaylistByTempo"),
+            Self::UpdateListenPermission(v) => simple!("UpdateListenPermission", "{}", v),
+            Self::ToggleListenMode => simple!("ToggleListenMode"),
+            Self::ListenIndexStarted(n) => simple!("ListenIndexStarted", "{}", n),
+            Self::ListenSongIndexed => simple!("ListenSongIndexed"),
+            Self::ListenIndexReady(generation, index) => simple!(
+                "ListenIndexReady",
+                "{}, {} songs",
+                generation,
+                index.song_count()
+            ),
+            Self::ListenCapture(generation) => simple!("ListenCapture", "{}", generation),
+            Self::ListenHeard(generation, r) => {
+                simple!("ListenHeard", "{}, {:?}", generation, r)
+            }
+            Self::ListenLyricsLoaded(id, lines) => {
+                simple!("ListenLyricsLoaded", "{}, {} lines", id, lines.len())
+            }
+            Self::ListenTick => simple!("ListenTick"),
+            Self::AddSchedule => simple!("AddSchedule"),
+            Self::RemoveSchedule(i) => simple!("RemoveSchedule", "{}", i),
+            Self::UpdateSchedule(i, schedule) => simple!("UpdateSchedule", "{} {:?}", i, schedule),
+            Self::CheckSchedules => simple!("CheckSchedules"),
+            Self::ScheduleRampTick => simple!("ScheduleRampTick"),
+            Self::CheckMicrophone => simple!("CheckMicrophone"),
+            Self::MicrophoneChecked(apps) => simple!("MicrophoneChecked", "{:?}", apps),
+            Self::ExportPlaylist(id) => simple!("ExportPlaylist", "{}", id),
+            Self::ExportSongsLoaded(name, songs) => {
+                simple!("ExportSongsLoaded", "{}, {} songs", name, songs.len())
+            }
+            Self::ExportFormatChanged(format) => simple!("ExportFormatChanged", "{:?}", format),
+            Self::ExportBitrateChanged(kbps) => simple!("ExportBitrateChanged", "{}", kbps),
+            Self::PickExportFolder => simple!("PickExportFolder"),
+            Self::ExportFolderPicked(path) => simple!("ExportFolderPicked", "{:?}", path),
+            Self::StartExport => simple!("StartExport"),
+            Self::CloseExport => simple!("CloseExport"),
+            Self::ExportNext => simple!("ExportNext"),
+            Self::ExportSongDone(generation, result) => {
+                simple!("ExportSongDone", "{}, {:?}", generation, result)
+            }
+            Self::CancelExport => simple!("CancelExport"),
+            Self::OpenExportFolder => simple!("OpenExportFolder"),
+            Self::OpenCoverViewer => simple!("OpenCoverViewer"),
+            Self::CloseCoverViewer => simple!("CloseCoverViewer"),
+            Self::FullCoverReady(id, path) => simple!("FullCoverReady", "{}, {}", id, path),
+            Self::SaveCoverImage => simple!("SaveCoverImage"),
+            Self::CoverSavePicked(path) => simple!("CoverSavePicked", "{:?}", path),
+            Self::CoverImageSaved(result) => simple!("CoverImageSaved", "{:?}", result),
+            Self::AutoDjSongsFetched(r, play) => simple!(
+                "AutoDjSongsFetched",
+                "{}, play={}",
+                match r {
+                    Ok(songs) => format!("{} songs", songs.len()),
+                    Err(e) => e.clone(),
+                },
+                play
+            ),
+            Self::AutoplaySongsFetched(r) => simple!(
+                "AutoplaySongsFetched",
+                "{}",
+                match r {
+                    Ok(songs) => format!("{} songs", songs.len()),
+                    Err(e) => e.clone(),
+                }
+            ),
+
+            // Power
+            Self::PowerEvent(e) => simple!("PowerEvent", "{:?}", e),
+
+            // Memory
+            Self::ToggleMemoryOverlay => simple!("ToggleMemoryOverlay"),
+            Self::SampleMemory => simple!("SampleMemory"),
+            Self::MemorySampled(r) => simple!(
+                "MemorySampled",
+                "{:?}",
+                r.as_ref().map(|r| r.resident_bytes)
+            ),
+
+            // Crash recovery
+            Self::RestoreCrashedSession => simple!("RestoreCrashedSession"),
+            Self::CrashedSessionRestored(r) => {
+                simple!(
+                    "CrashedSessionRestored",
+                    "{:?}",
+                    r.as_ref().map(|(_, q)| q.len())
+                )
+            }
+            Self::SubmitCrashReport => simple!("SubmitCrashReport"),
+            Self::DismissCrashReport => simple!("DismissCrashReport"),
+
+            // Updater
+            Self::CheckForUpdates(manual) => simple!("CheckForUpdates", "{}", manual),
+            Self::UpdateCheckFinished(manual, r) => simple!(
+                "UpdateCheckFinished",
+                "{}, {:?}",
+                manual,
+                r.as_ref().map(|info| info.as_ref().map(|i| &i.version))
+            ),
+            Self::UpdateDownloaded(r) => simple!("UpdateDownloaded", "{:?}", r),
+            Self::InstallUpdate => simple!("InstallUpdate"),
+            Self::OpenReleasePage => simple!("OpenReleasePage"),
+            Self::DismissUpdatePrompt => simple!("DismissUpdatePrompt"),
+            Self::AppVersionRecorded(previous) => simple!("AppVersionRecorded", "{:?}", previous),
+            Self::ChangelogLoaded(info) => simple!("ChangelogLoaded", "{}", info.version),
+            Self::DismissChangelog => simple!("DismissChangelog"),
+            Self::OpenVersionHistory => simple!("OpenVersionHistory"),
+            Self::VersionHistoryLoaded(versions) => {
+                simple!("VersionHistoryLoaded", "{}", versions.len())
+            }
+            Self::UpdateShowWhatsNew(b) => simple!("UpdateShowWhatsNew", "{}", b),
+
             // Streaming
             Self::StreamingEvent(id, _) => simple!("StreamingEvent", "id={}", id),
 
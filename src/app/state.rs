@@ -2,18 +2,29 @@
 //! Application state definitions
 
 use iced::time::Instant;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::api::{BannersInfo, NcmClient, SongInfo, SongList, TopList};
-use crate::app::SettingsSection;
+use crate::api::{AlbumRelease, BannersInfo, NcmClient, SongInfo, SongList};
+use crate::app::{Message, SettingsSection};
 use crate::audio::AudioProcessingChain;
-use crate::database::{Database, DbPlaybackState, DbPlaylist, DbSong};
+use crate::database::{Database, DbPlaybackState, DbPlaylist, DbSong, SongId};
+use crate::features::ExportSettings;
 use crate::features::import::{CoverCache, FolderWatcher, ScanHandle, ScanProgress, ScanState};
+use crate::features::logs::{LogLevelFilter, LogRecord};
+use crate::features::lyrics_share::Submission;
+use crate::features::media::chapters::Chapter;
+use crate::features::ncm_publish::PublishPlan;
+use crate::features::playlist_health::HealthReport;
+use crate::features::playlist_sync::RecentPlaylists;
+use crate::features::plugins::{PluginPanel, PluginRegistry};
+use crate::features::song_overrides::SongTags;
+use crate::features::updater::ReleaseInfo;
 use crate::i18n::Locale;
 use crate::platform::media_controls::{MediaCommand, MediaHandle};
-use crate::ui::animation::{HoverAnimations, SingleHoverAnimation};
+use crate::ui::animation::{ColorTransition, HoverAnimations, SingleHoverAnimation};
+use crate::ui::components::playlist_view::{PlaylistFilter, PlaylistSort, SongItem, SongRows};
 use crate::ui::components::{ImportingPlaylist, NavItem};
 use crate::ui::effects::background::LyricsBackgroundProgram;
 use crate::ui::effects::textured_background::TexturedBackgroundProgram;
@@ -47,16 +58,51 @@ pub struct CoreState {
     pub ncm_client: Option<NcmClient>,
     pub user_info: Option<UserInfo>,
 
+    // Plugins
+    pub plugins: Arc<PluginRegistry>,
+
     // System Integrations
     pub cover_cache: Option<Arc<CoverCache>>,
     pub mpris_handle: Option<MediaHandle>,
+    /// Artwork handed to the media session for the current song
+    pub media_artwork: Option<MediaArtwork>,
     pub mpris_rx:
         Option<Arc<tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<MediaCommand>>>>,
+    /// Last play/pause media key press, for spotting double presses
+    pub last_media_play_pause: Option<Instant>,
+    /// When playback was last paused, for smart resume
+    pub paused_at: Option<Instant>,
     pub window_hidden: bool,
     pub window_operation_pending: bool,
     pub is_fullscreen: bool,
     /// Current mouse Y position for drag area detection
     pub mouse_position: iced::Point,
+    /// Whether the system reports the connection as metered
+    pub system_metered: bool,
+    /// Local address of the last internet route seen, to notice the
+    /// network changing underneath open connections
+    pub network_route: Option<std::net::IpAddr>,
+    /// The system is asleep, or about to be
+    pub suspended: bool,
+    /// Deferred initialization hasn't run yet (waits for the first frame)
+    pub startup_pending: bool,
+    /// Streaming quality requested by the playlist being played
+    pub quality_override: Option<crate::features::MusicQuality>,
+    /// The main window, as opposed to the popped-out lyrics window
+    pub main_window: Option<iced::window::Id>,
+    /// Audio output devices for the tray's device menu; listing them can
+    /// shell out, so it's redone when the menu is opened or after waking
+    /// rather than on every tray update
+    pub output_devices: Vec<crate::audio::AudioDevice>,
+}
+
+/// Media session artwork made, or being made, for one song's cover
+pub struct MediaArtwork {
+    pub song_id: SongId,
+    /// Cover path or URL it is made from
+    pub source: String,
+    /// `file://` URL of the cached copy; `None` until the first one is ready
+    pub url: Option<String>,
 }
 
 impl CoreState {
@@ -78,13 +124,43 @@ impl CoreState {
             is_logged_in: false,
             ncm_client: None,
             user_info: None,
+            plugins: Arc::new(PluginRegistry::default()),
             cover_cache: None,
             mpris_handle: None,
+            media_artwork: None,
             mpris_rx: None,
+            last_media_play_pause: None,
+            paused_at: None,
             window_hidden: false,
             window_operation_pending: false,
             is_fullscreen: false,
             mouse_position: iced::Point::ORIGIN,
+            system_metered: false,
+            network_route: None,
+            suspended: false,
+            startup_pending: true,
+            quality_override: None,
+            main_window: None,
+            output_devices: Vec::new(),
+        }
+    }
+
+    /// Whether data use should be kept down (manual setting or detected)
+    pub fn is_metered(&self) -> bool {
+        match self.settings.network.metered_mode {
+            crate::features::MeteredMode::Auto => self.system_metered,
+            crate::features::MeteredMode::On => true,
+            crate::features::MeteredMode::Off => false,
+        }
+    }
+
+    /// Streaming quality, capped at 128kbps on a metered connection
+    pub fn stream_quality(&self) -> crate::features::MusicQuality {
+        if self.is_metered() {
+            crate::features::MusicQuality::Standard
+        } else {
+            self.quality_override
+                .unwrap_or(self.settings.playback.music_quality)
         }
     }
 }
@@ -99,7 +175,6 @@ pub struct UserInfo {
     /// Pre-loaded avatar image handle for instant rendering
     pub avatar_handle: Option<iced::widget::image::Handle>,
     pub vip_type: i32,
-    pub like_songs: HashSet<u64>,
 }
 
 impl UserInfo {
@@ -111,7 +186,6 @@ impl UserInfo {
             avatar_path: None,
             avatar_handle: None,
             vip_type: 0,
-            like_songs: HashSet::new(),
         }
     }
 }
@@ -120,6 +194,8 @@ impl UserInfo {
 pub struct LibraryState {
     pub db_songs: Vec<DbSong>,
     pub playlists: Vec<DbPlaylist>,
+    /// Generated covers of local playlists that have none, by playlist ID
+    pub playlist_mosaics: HashMap<i64, String>,
     pub recently_played: Vec<DbSong>,
 
     // Playback Data
@@ -143,6 +219,12 @@ pub struct LibraryState {
     /// Shared buffer for streaming playback (no file I/O)
     /// This is the ONLY streaming state - no file-based streaming
     pub streaming_buffer: Option<crate::audio::SharedBuffer>,
+    /// Qualities the current NCM song is available in (song_id, qualities)
+    pub track_qualities: Option<(SongId, Vec<crate::features::MusicQuality>)>,
+    /// Quality picked for this play of a song (song_id, quality)
+    pub track_quality: Option<(SongId, crate::features::MusicQuality)>,
+    /// Queue index and position to resume at once a quality switch resolves
+    pub quality_switch_position: Option<(usize, f64)>,
 
     // Error handling
     /// Consecutive playback failures counter (reset on successful play)
@@ -154,6 +236,53 @@ pub struct LibraryState {
     pub scan_progress: Option<ScanProgress>,
     pub folder_watcher: Option<FolderWatcher>,
     pub watched_folders: Vec<PathBuf>,
+
+    /// NCM IDs of artists followed locally or on NCM
+    pub followed_artists: HashSet<u64>,
+
+    /// Songs and artists the user never wants to hear
+    pub blocklist: crate::features::blocklist::Blocklist,
+
+    /// NCM track IDs of local songs matched on NCM, by song ID
+    pub ncm_links: HashMap<SongId, u64>,
+    /// Local song currently being looked up on NCM
+    pub ncm_matching: Option<SongId>,
+    /// Tempo, key and energy of analyzed local songs, by song ID
+    pub song_analysis: HashMap<SongId, crate::features::song_analysis::SongAnalysis>,
+    /// Songs analyzed and to go in the running analysis
+    pub song_analysis_progress: Option<(usize, usize)>,
+    /// Running analysis; aborted when dropped
+    pub song_analysis_task: Option<iced::task::Handle>,
+    /// Songs changed during the running analysis, so another one follows it
+    pub song_analysis_rerun: bool,
+    /// Liked songs, kept in step with NCM likes while logged in
+    pub favorites: crate::features::favorites::Favorites,
+    /// Favorites have been read from the database
+    pub favorites_loaded: bool,
+    /// NCM likes are being compared with local favorites
+    pub favorites_syncing: bool,
+    /// Saved copy of the liked songs playlist
+    pub liked_mirror: Option<crate::features::favorites::LikedMirror>,
+    /// Where the liked songs mirror is with NCM, once a sync was tried
+    pub liked_sync: Option<crate::features::favorites::LikedSync>,
+    /// Likes changed during a sync, so another one follows it
+    pub liked_resync: bool,
+
+    /// Auto-DJ is fetching songs to top the queue up
+    pub auto_dj_filling: bool,
+    /// Song the Auto-DJ has already crossfaded out of
+    pub auto_dj_crossfaded: Option<SongId>,
+
+    /// Similar songs are being fetched to continue the finished queue
+    pub autoplay_filling: bool,
+    /// Queue entries added by autoplay, by song ID
+    pub autoplay_ids: HashSet<SongId>,
+
+    /// Stop instead of advancing when the current song ends
+    pub stop_after_current: bool,
+
+    /// Chapter markers read from a song file, keyed by song ID
+    pub chapters: Option<(SongId, Vec<Chapter>)>,
 }
 
 impl Default for LibraryState {
@@ -161,6 +290,7 @@ impl Default for LibraryState {
         Self {
             db_songs: Vec::new(),
             playlists: Vec::new(),
+            playlist_mosaics: HashMap::new(),
             recently_played: Vec::new(),
             current_song: None,
             playback_state: None,
@@ -171,12 +301,35 @@ impl Default for LibraryState {
             preload_manager: Default::default(),
             pending_resolution_idx: None,
             streaming_buffer: None,
+            track_qualities: None,
+            track_quality: None,
+            quality_switch_position: None,
             consecutive_failures: 0,
             scan_state: None,
             scan_handle: None,
             scan_progress: None,
             folder_watcher: None,
             watched_folders: Vec::new(),
+            followed_artists: HashSet::new(),
+            blocklist: Default::default(),
+            ncm_links: HashMap::new(),
+            ncm_matching: None,
+            song_analysis: HashMap::new(),
+            song_analysis_progress: None,
+            song_analysis_task: None,
+            song_analysis_rerun: false,
+            favorites: Default::default(),
+            favorites_loaded: false,
+            favorites_syncing: false,
+            liked_mirror: None,
+            liked_sync: None,
+            liked_resync: false,
+            auto_dj_filling: false,
+            auto_dj_crossfaded: None,
+            autoplay_filling: false,
+            autoplay_ids: HashSet::new(),
+            stop_after_current: false,
+            chapters: None,
         }
     }
 }
@@ -189,6 +342,11 @@ pub enum Route {
     Radio,
     Settings(SettingsSection),
     AudioEngine,
+    Plugins,
+    Logs,
+    AnnualReport,
+    Blocklist,
+    Trash,
     Playlist(i64),
     NcmPlaylist(u64),
     RecentlyPlayed,
@@ -197,6 +355,8 @@ pub enum Route {
         tab: SearchTab,
         page: u32,
     },
+    /// NCM user profile by user ID
+    UserProfile(u64),
 }
 
 impl Route {
@@ -207,10 +367,14 @@ impl Route {
             Self::Radio => Some(NavItem::Radio),
             Self::Settings(_) => Some(NavItem::Settings),
             Self::AudioEngine => Some(NavItem::AudioEngine),
+            Self::Plugins | Self::Logs | Self::AnnualReport | Self::Blocklist | Self::Trash => {
+                Some(NavItem::Settings)
+            }
             Self::Playlist(_)
             | Self::NcmPlaylist(_)
             | Self::RecentlyPlayed
-            | Self::Search { .. } => None,
+            | Self::Search { .. }
+            | Self::UserProfile(_) => None,
         }
     }
 }
@@ -298,6 +462,8 @@ pub struct UiState {
     pub search_query: String,
     pub toast: Option<Toast>,
     pub toast_visible: bool,
+    /// Button on the current toast: its label and the message it sends
+    pub toast_action: Option<(&'static str, Message)>,
 
     /// Navigation history for back/forward
     pub nav_history: NavigationHistory,
@@ -309,14 +475,57 @@ pub struct UiState {
     pub home: HomePageState,
     pub discover: DiscoverPageState,
     pub search: SearchPageState,
+    pub plugins: PluginsPageState,
+    pub logs: LogsPageState,
+    pub annual_report: AnnualReportPageState,
+    pub blocklist: BlocklistPageState,
+    pub trash: TrashPageState,
+    pub profile: ProfilePageState,
+    pub updater: UpdaterState,
+    pub memory: MemoryState,
+    pub listen: ListenState,
+    pub call_ducking: CallDuckingState,
+    pub export: ExportState,
+    /// Lyrics being saved for the songs of a folder
+    pub lyrics_save_batch: Option<LyricsSaveBatch>,
+    /// Volume being raised after a schedule started playing
+    pub schedule_ramp: Option<crate::features::schedules::VolumeRamp>,
+    /// Cover downloads driven by what is scrolled into view
+    pub lazy_covers: crate::features::lazy_covers::CoverLoader,
 
     // Global UI Layout
     pub active_settings_section: SettingsSection,
+    /// Settings search query, listing matches in place of the sections
+    pub settings_search: String,
+    /// Settings category the search is narrowed to
+    pub settings_category: Option<crate::ui::pages::settings_search::SettingsCategory>,
     pub editing_keybinding: Option<crate::features::Action>,
+    pub lyrics_tuning_expanded: bool,
+    /// Passphrase for credentials in exported and imported settings profiles
+    pub settings_profile_passphrase: String,
+    /// Tap-along output latency calibration, while it runs
+    pub latency_calibration: Option<crate::features::latency::Calibration>,
+    /// Bounds and flights of shared-element transitions
+    pub shared: SharedElements,
+    /// Frosted-glass backdrop behind modals
+    pub backdrop: BackdropState,
     pub queue_visible: bool,
+    /// Where each queued song plays from, checked when the queue is opened
+    pub queue_sources: HashMap<SongId, crate::features::song_source::SongSource>,
+    /// Show only queued songs that play without a network connection
+    pub queue_offline_only: bool,
+    /// Earlier queues listed in place of the queue, while shown
+    pub queue_sessions: Option<Vec<crate::database::DbQueueSnapshot>>,
+    pub chapters_visible: bool,
+    /// Player context menu, opened by right-clicking the player bar
+    pub player_menu_open: bool,
 
     // Playback Controls UI
     pub seek_preview_position: Option<f32>,
+    /// Seek bar position under the cursor (0.0 to 1.0)
+    pub seek_hover: Option<f32>,
+    /// Wheel movement in lines not yet turned into a volume or seek step
+    pub wheel_scroll: f32,
     pub save_position_counter: u32,
 
     // Sidebar
@@ -326,9 +535,16 @@ pub struct UiState {
     pub sidebar_width: f32,
     /// Whether the sidebar resize handle is being dragged
     pub sidebar_dragging: bool,
+    /// Window width, for dragging the sidebar when it sits on the right
+    pub window_width: f32,
+    /// Main window height, for the lyrics viewport once the lyrics window
+    /// closes
+    pub window_height: f32,
 
     // Cache statistics
     pub cache_stats: Option<crate::cache::CacheStats>,
+    /// Recent daily check-in rewards, newest first
+    pub check_in_history: Vec<crate::database::DbCheckIn>,
 }
 
 impl UiState {
@@ -338,21 +554,39 @@ impl UiState {
             search_query: String::new(),
             toast: None,
             toast_visible: false,
+            toast_action: None,
             nav_history: {
                 let mut history = NavigationHistory::default();
                 history.push(NavigationEntry::Route(Route::Home));
                 history
             },
             active_settings_section: SettingsSection::Account,
+            settings_search: String::new(),
+            settings_category: None,
             editing_keybinding: None,
+            lyrics_tuning_expanded: false,
+            settings_profile_passphrase: String::new(),
+            latency_calibration: None,
+            shared: SharedElements::default(),
+            backdrop: BackdropState::default(),
             queue_visible: false,
+            queue_sources: HashMap::new(),
+            queue_offline_only: false,
+            queue_sessions: None,
+            chapters_visible: false,
+            player_menu_open: false,
             seek_preview_position: None,
+            seek_hover: None,
+            wheel_scroll: 0.0,
             save_position_counter: 0,
             importing_playlist: None,
             sidebar_animations: Default::default(),
             sidebar_width: 240.0,
             sidebar_dragging: false,
+            window_width: 1280.0,
+            window_height: 800.0,
             cache_stats: None,
+            check_in_history: Vec::new(),
 
             playlist_page: PlaylistPageState {
                 current: None,
@@ -367,6 +601,18 @@ impl UiState {
                 )),
                 pending_cover_downloads: HashSet::new(),
                 load_state: Default::default(),
+                song_pages: None,
+                skeleton_since: None,
+                kept_ncm: Default::default(),
+                playback: Default::default(),
+                playback_panel_open: false,
+                tempo_sort: None,
+                sort: PlaylistSort::Default,
+                filter: PlaylistFilter::default(),
+                backdrop: TexturedBackgroundProgram::new(),
+                backdrop_start_time: None,
+                backdrop_last_update: None,
+                tint: ColorTransition::new(crate::utils::ColorPalette::default().primary),
             },
 
             lyrics: LyricsState {
@@ -383,6 +629,7 @@ impl UiState {
                 // This avoids blocking app startup with FontSystem::new()
                 engine: None,
                 shader_start_time: None,
+                redraw_requested: false,
                 cached_engine_lines: None,
                 cached_shaped_lines: None,
                 // FontSystem will be created asynchronously
@@ -395,6 +642,13 @@ impl UiState {
                 loading_song_id: None,
                 is_loading: false,
                 load_error: None,
+                sub_lines: Default::default(),
+                large_cover: None,
+                credits_visible: false,
+                credits: None,
+                shareable: None,
+                savable: None,
+                window: None,
             },
 
             dialogs: DialogState {
@@ -410,6 +664,22 @@ impl UiState {
                 exit_open: false,
                 exit_animation: Default::default(),
                 exit_remember: false,
+                crash_report: None,
+                crash_animation: Default::default(),
+                open_link_pending: None,
+                open_link_animation: Default::default(),
+                publish: None,
+                publish_animation: Default::default(),
+                health: None,
+                health_animation: Default::default(),
+                lyrics_share: None,
+                lyrics_share_animation: Default::default(),
+                song_info: None,
+                song_info_animation: Default::default(),
+                export: None,
+                export_animation: Default::default(),
+                cover_viewer: None,
+                cover_viewer_animation: Default::default(),
             },
 
             home: HomePageState {
@@ -419,9 +689,12 @@ impl UiState {
                 top_picks: Vec::new(),
                 toplists: Vec::new(),
                 trending_songs: Vec::new(),
+                daily_songs: Vec::new(),
+                recent_songs: Vec::new(),
+                customizing: false,
                 song_covers: std::collections::HashMap::new(),
                 login_popup_open: false,
-                qr_code_path: None,
+                qr_modules: None,
                 qr_unikey: None,
                 qr_status: None,
                 cloud_songs: Vec::new(),
@@ -441,6 +714,21 @@ impl UiState {
                 )),
                 ..Default::default()
             },
+
+            plugins: PluginsPageState::default(),
+            logs: LogsPageState::default(),
+            annual_report: AnnualReportPageState::default(),
+            blocklist: BlocklistPageState::default(),
+            trash: TrashPageState::default(),
+            profile: ProfilePageState::default(),
+            updater: UpdaterState::default(),
+            memory: MemoryState::default(),
+            listen: ListenState::default(),
+            call_ducking: CallDuckingState::default(),
+            export: ExportState::default(),
+            lyrics_save_batch: None,
+            schedule_ramp: None,
+            lazy_covers: Default::default(),
         }
     }
 
@@ -457,11 +745,22 @@ impl UiState {
             || self.dialogs.edit_animation.is_animating()
             || self.dialogs.exit_animation.is_animating()
             || self.dialogs.delete_animation.is_animating()
+            || self.dialogs.crash_animation.is_animating()
+            || self.dialogs.open_link_animation.is_animating()
+            || self.dialogs.publish_animation.is_animating()
+            || self.dialogs.health_animation.is_animating()
+            || self.dialogs.lyrics_share_animation.is_animating()
+            || self.dialogs.song_info_animation.is_animating()
+            || self.dialogs.export_animation.is_animating()
+            || self.dialogs.cover_viewer_animation.is_animating()
+            || self.updater.prompt_animation.is_animating()
+            || self.updater.changelog_animation.is_animating()
             || self.home.carousel_animation.is_animating(_now)
             || self.home.song_hover_animations.is_animating()
             || self.discover.card_animations.is_animating()
             || self.search.song_animations.is_animating()
             || self.search.card_animations.is_animating()
+            || self.shared.cover_flight.is_some()
     }
 
     /// Clean up completed animations to prevent memory leaks
@@ -476,10 +775,21 @@ impl UiState {
         self.dialogs.edit_animation.tick(now);
         self.dialogs.exit_animation.tick(now);
         self.dialogs.delete_animation.tick(now);
+        self.dialogs.crash_animation.tick(now);
+        self.dialogs.open_link_animation.tick(now);
+        self.dialogs.publish_animation.tick(now);
+        self.dialogs.health_animation.tick(now);
+        self.dialogs.lyrics_share_animation.tick(now);
+        self.dialogs.song_info_animation.tick(now);
+        self.dialogs.export_animation.tick(now);
+        self.dialogs.cover_viewer_animation.tick(now);
+        self.updater.prompt_animation.tick(now);
+        self.updater.changelog_animation.tick(now);
         self.home.song_hover_animations.tick(now);
         self.discover.card_animations.tick(now);
         self.search.song_animations.tick(now);
         self.search.card_animations.tick(now);
+        self.shared.tick(now);
 
         // Clean up completed fade-out animations
         self.sidebar_animations.cleanup_completed();
@@ -498,10 +808,43 @@ impl UiState {
     }
 }
 
+/// Where shared elements sit, and what is flying between them
+#[derive(Debug, Default)]
+pub struct SharedElements {
+    /// Cover of the card just pressed, taking off if its playlist opens
+    pub pressed_cover: Option<(iced::widget::image::Handle, iced::Rectangle, Instant)>,
+    /// Cover flying from a card into the playlist header
+    pub cover_flight: Option<crate::ui::animation::Flight<iced::widget::image::Handle>>,
+    /// Last reported bounds of each transition end
+    pub bounds: HashMap<crate::app::SharedElement, iced::Rectangle>,
+}
+
+impl SharedElements {
+    /// Advance the flight, ending it once landed
+    pub fn tick(&mut self, now: Instant) {
+        if let Some(flight) = &mut self.cover_flight {
+            flight.tick(now);
+            if flight.is_over(now) {
+                self.cover_flight = None;
+            }
+        }
+    }
+}
+
+/// Blurred snapshot of the window shown behind modals and menus
+#[derive(Debug, Default)]
+pub struct BackdropState {
+    /// Snapshot taken as the first modal opened, dropped once all fade out
+    pub frosted: Option<iced::widget::image::Handle>,
+    /// Waiting on the snapshot; popups that don't fade in stay out of the
+    /// frame meanwhile
+    pub capturing: bool,
+}
+
 pub struct PlaylistPageState {
     pub current: Option<pages::PlaylistView>,
     pub viewing_recently_played: bool,
-    pub song_animations: HoverAnimations<i64>,
+    pub song_animations: HoverAnimations<SongId>,
     pub icon_animations: HoverAnimations<crate::app::message::IconId>,
     pub search_expanded: bool,
     pub search_query: String,
@@ -509,9 +852,142 @@ pub struct PlaylistPageState {
     /// Virtual list scroll state for efficient rendering
     pub scroll_state: std::rc::Rc<std::cell::RefCell<crate::ui::widgets::VirtualListState>>,
     /// Song IDs currently being downloaded (to avoid duplicate requests)
-    pub pending_cover_downloads: HashSet<i64>,
+    pub pending_cover_downloads: HashSet<SongId>,
     /// Loading state for async playlist loading
     pub load_state: crate::app::update::page_loader::PlaylistLoadState,
+    /// Batched song loading of the open NCM playlist; aborted when dropped
+    pub song_pages: Option<iced::task::Handle>,
+    /// When the NCM playlist skeleton went up, for its shimmer
+    pub skeleton_since: Option<Instant>,
+    /// NCM playlists viewed before, shown at once when reopened
+    pub kept_ncm: RecentPlaylists<KeptPlaylist>,
+    /// Playback preferences saved for the open playlist
+    pub playback: crate::features::PlaylistPlayback,
+    pub playback_panel_open: bool,
+    /// Songs ordered by tempo, ascending when `Some(true)`
+    pub tempo_sort: Option<bool>,
+    /// Order and filters of an NCM playlist's songs
+    pub sort: PlaylistSort,
+    pub filter: PlaylistFilter,
+    /// Blurred artwork behind the header, see `DisplaySettings::artwork_backdrop`
+    pub backdrop: TexturedBackgroundProgram,
+    pub backdrop_start_time: Option<Instant>,
+    pub backdrop_last_update: Option<Instant>,
+    /// Cover palette color the page chrome is tinted with, see
+    /// `DisplaySettings::cover_theming`
+    pub tint: ColorTransition,
+}
+
+/// A fully loaded NCM playlist kept after leaving it
+pub struct KeptPlaylist {
+    pub view: pages::PlaylistView,
+    pub songs: Vec<SongInfo>,
+    /// Top visible song and how far it was scrolled past
+    pub top_song: Option<(SongId, f32)>,
+}
+
+impl PlaylistPageState {
+    /// Show a playlist view
+    ///
+    /// Reloading the playlist already on screen keeps the top visible song in
+    /// place; any other playlist starts from the top.
+    pub fn show(&mut self, view: pages::PlaylistView) {
+        let same_playlist = self.current.as_ref().is_some_and(|p| p.id == view.id);
+        let anchor = if same_playlist { self.top_song() } else { None };
+        self.tint.follow(view.palette.primary);
+        self.current = Some(view);
+        self.restore_top_song(anchor);
+    }
+
+    /// Shimmer phase of the skeleton, while an NCM playlist has no songs yet
+    pub fn skeleton_phase(&self) -> Option<f32> {
+        if !matches!(
+            self.load_state,
+            crate::app::update::page_loader::PlaylistLoadState::Loading
+        ) {
+            return None;
+        }
+        self.skeleton_since
+            .map(|since| crate::ui::widgets::skeleton::phase(since.elapsed()))
+    }
+
+    /// Stop loading the open playlist's songs
+    pub fn cancel_load(&mut self) {
+        self.song_pages = None;
+        self.skeleton_since = None;
+        self.load_state = Default::default();
+    }
+
+    /// Show a kept playlist scrolled to where it was left
+    pub fn show_kept(&mut self, kept: KeptPlaylist) {
+        self.tint.follow(kept.view.palette.primary);
+        self.current = Some(kept.view);
+        self.restore_top_song(kept.top_song);
+    }
+
+    /// Keep the open NCM playlist for reopening, once all its songs are loaded
+    pub fn keep_ncm(&mut self, songs: &[SongInfo]) {
+        if !matches!(
+            self.load_state,
+            crate::app::update::page_loader::PlaylistLoadState::Ready
+        ) {
+            return;
+        }
+        // Daily recommendations (ID 0) change every day and are never kept
+        let Some(view) = self.current.as_ref().filter(|p| p.id < 0).cloned() else {
+            return;
+        };
+        let top_song = self.top_song();
+        self.kept_ncm.insert(
+            (-view.id) as u64,
+            KeptPlaylist {
+                view,
+                songs: songs.to_vec(),
+                top_song,
+            },
+        );
+    }
+
+    /// Replace the songs of the current playlist, keeping the top visible song in place
+    pub fn replace_songs(&mut self, songs: Vec<pages::PlaylistSongView>) {
+        let anchor = self.top_song();
+        if let Some(playlist) = &mut self.current {
+            playlist.songs = songs;
+        }
+        self.restore_top_song(anchor);
+    }
+
+    /// Rows shown for `songs` with the page's search, filters and sorting
+    pub fn rows<'a>(&self, songs: &'a [SongItem]) -> SongRows<'a> {
+        SongRows::filter(songs, &self.search_query)
+            .keep(self.filter)
+            .sort(self.sort)
+            .sort_by_tempo(self.tempo_sort)
+    }
+
+    /// ID of the song at the top of the list and how far it is scrolled past
+    fn top_song(&self) -> Option<(SongId, f32)> {
+        let playlist = self.current.as_ref()?;
+        let (row, within) = self.scroll_state.borrow().anchor();
+        self.rows(&playlist.songs)
+            .get(row)
+            .map(|song| (song.id, within))
+    }
+
+    fn restore_top_song(&mut self, anchor: Option<(SongId, f32)>) {
+        let row = anchor.and_then(|(id, within)| {
+            let playlist = self.current.as_ref()?;
+            self.rows(&playlist.songs)
+                .position(id)
+                .map(|row| (row, within))
+        });
+
+        let mut scroll_state = self.scroll_state.borrow_mut();
+        match row {
+            Some((row, within)) => scroll_state.restore_anchor(row, within),
+            None => scroll_state.scroll_to_top(),
+        }
+    }
 }
 
 pub struct LyricsState {
@@ -528,6 +1004,9 @@ pub struct LyricsState {
     /// 歌词引擎 (RefCell 用于 view() 中的内部可变性)
     pub engine: Option<std::cell::RefCell<crate::features::lyrics::engine::LyricsEngine>>,
     pub shader_start_time: Option<Instant>,
+    /// Something changed that the next frame must show (new lyrics, resize,
+    /// seek, cover); cleared once a frame has been produced
+    pub redraw_requested: bool,
     /// Cached engine lines to avoid recreating every frame
     /// Using Arc for O(1) clone in view function (thread-safe for iced Primitive)
     pub cached_engine_lines:
@@ -552,11 +1031,53 @@ pub struct LyricsState {
 
     // Online lyrics loading
     /// Song ID currently loading lyrics for (to avoid duplicate requests)
-    pub loading_song_id: Option<i64>,
+    pub loading_song_id: Option<SongId>,
     /// Whether lyrics are currently being loaded
     pub is_loading: bool,
     /// Error message if lyrics loading failed
     pub load_error: Option<String>,
+    /// Sub-lines shown for the loaded song (its saved preference, else the
+    /// default from settings)
+    pub sub_lines: crate::features::LyricsSubLines,
+    /// High-resolution cover of an online song (song_id, path), fetched
+    /// for the lyrics page on top of the list thumbnail
+    pub large_cover: Option<(SongId, String)>,
+    /// Credits view shown in place of the lyrics
+    pub credits_visible: bool,
+    /// Credits fetched from NCM (song_id, credits); None inside when the
+    /// song has none or the request failed
+    pub credits: Option<(SongId, Option<crate::api::SongCredits>)>,
+    /// Local song whose LRC file can be shared on LRCLIB (song_id, and
+    /// whether the file changed since it was last shared or passed on)
+    pub shareable: Option<(SongId, bool)>,
+    /// Local song whose lyrics shown came from NCM or a plugin rather than
+    /// its own files, so they can be saved into them
+    pub savable: Option<SongId>,
+    /// Separate window the page is popped out into, for a second monitor
+    pub window: Option<LyricsWindow>,
+}
+
+/// Lyrics page in a window of its own
+#[derive(Debug, Clone, Copy)]
+pub struct LyricsWindow {
+    pub id: iced::window::Id,
+    pub fullscreen: bool,
+}
+
+impl LyricsState {
+    /// Whether the page is on screen, in the main window or its own
+    pub fn is_shown(&self) -> bool {
+        self.is_open || self.window.is_some()
+    }
+
+    /// Cover for the lyrics page: the high-resolution copy once it has
+    /// arrived, the thumbnail until then
+    pub fn cover_for<'a>(&'a self, song: &'a DbSong) -> Option<&'a str> {
+        match &self.large_cover {
+            Some((id, path)) if *id == song.id => Some(path),
+            _ => song.cover_path.as_deref(),
+        }
+    }
 }
 
 pub struct DialogState {
@@ -578,6 +1099,127 @@ pub struct DialogState {
     pub exit_open: bool,
     pub exit_animation: SingleHoverAnimation,
     pub exit_remember: bool,
+
+    // Crash recovery
+    pub crash_report: Option<crate::features::crash::CrashReport>,
+    pub crash_animation: SingleHoverAnimation,
+
+    // External link confirmation
+    pub open_link_pending: Option<String>,
+    pub open_link_animation: SingleHoverAnimation,
+
+    // Publish a local playlist to NCM
+    pub publish: Option<PublishDialog>,
+    pub publish_animation: SingleHoverAnimation,
+
+    // Song availability check of an NCM playlist
+    pub health: Option<HealthDialog>,
+    pub health_animation: SingleHoverAnimation,
+
+    // Share a local song's lyrics on LRCLIB
+    pub lyrics_share: Option<LyricsShareDialog>,
+    pub lyrics_share_animation: SingleHoverAnimation,
+
+    // Local corrections to an NCM song's details
+    pub song_info: Option<SongInfoDialog>,
+    pub song_info_animation: SingleHoverAnimation,
+
+    // Format and folder for exporting a playlist's songs
+    pub export: Option<ExportDialog>,
+    pub export_animation: SingleHoverAnimation,
+
+    // Full-screen view of the playing song's cover
+    pub cover_viewer: Option<CoverViewer>,
+    pub cover_viewer_animation: SingleHoverAnimation,
+}
+
+/// The playing song's cover, shown as large as it comes
+#[derive(Debug, Clone)]
+pub struct CoverViewer {
+    pub song_id: SongId,
+    /// "Artist - Title", the suggested name when saving
+    pub name: String,
+    /// Image shown, the best copy on disk so far
+    pub path: String,
+    /// The original is still being downloaded
+    pub loading_full: bool,
+}
+
+/// Editing what is shown for an NCM song
+#[derive(Debug, Clone)]
+pub struct SongInfoDialog {
+    pub ncm_id: u64,
+    /// Fields as being edited
+    pub tags: SongTags,
+    /// What NCM has for the song
+    pub original: SongTags,
+    /// Whether an override is saved for the song
+    pub overridden: bool,
+}
+
+/// Choosing how to export a playlist's songs
+#[derive(Debug, Clone)]
+pub struct ExportDialog {
+    /// Playlist name
+    pub name: String,
+    pub songs: Vec<DbSong>,
+    /// Choices as being edited, saved as the defaults on export
+    pub settings: ExportSettings,
+}
+
+/// Preview and sharing of a local song's lyrics on LRCLIB
+pub struct LyricsShareDialog {
+    pub song_id: SongId,
+    pub submission: Submission,
+    /// Hash of the file shown, remembered once it's shared or passed on
+    pub content_hash: String,
+    pub stage: LyricsShareStage,
+}
+
+/// Steps of sharing lyrics on LRCLIB
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LyricsShareStage {
+    /// Looking over what will be published
+    Preview,
+    /// Earning the publish token and uploading
+    Publishing,
+    Done,
+}
+
+/// Availability check of an NCM playlist and its batch actions
+pub struct HealthDialog {
+    pub playlist_id: u64,
+    pub name: String,
+    /// The user's own playlist, so flagged songs can be removed or replaced
+    pub editable: bool,
+    /// `None` while checking
+    pub report: Option<HealthReport>,
+    /// Check or batch action running; aborted when the dialog closes
+    pub job: Option<iced::task::Handle>,
+}
+
+/// Publishing a local playlist to NCM
+pub struct PublishDialog {
+    pub plan: PublishPlan,
+    pub stage: PublishStage,
+    /// Song lookups still running; aborted when the dialog closes
+    pub lookups: Option<iced::task::Handle>,
+}
+
+/// Steps of publishing a local playlist to NCM
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublishStage {
+    /// Looking the songs up on NCM
+    Matching,
+    /// Picking results for close calls before publishing
+    Review,
+    /// Creating the NCM playlist
+    Publishing,
+    /// Created, with the songs that were left out
+    Done {
+        playlist_id: u64,
+        unmatched: Vec<String>,
+    },
 }
 
 /// Discover page view mode
@@ -614,6 +1256,18 @@ impl SearchTab {
     }
 }
 
+/// Where song results come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchSource {
+    /// Local library and NCM, in separate sections
+    #[default]
+    All,
+    /// Scanned local files only
+    Local,
+    /// NCM only
+    Online,
+}
+
 /// Search page state
 pub struct SearchPageState {
     /// Current search keyword
@@ -622,6 +1276,10 @@ pub struct SearchPageState {
     pub active_tab: SearchTab,
     /// Song search results
     pub songs: Vec<SongInfo>,
+    /// Matching songs from the local library
+    pub local_songs: Vec<DbSong>,
+    /// Source filter for song results
+    pub source: SearchSource,
     /// Album search results
     pub albums: Vec<SongList>,
     /// Playlist search results
@@ -638,6 +1296,16 @@ pub struct SearchPageState {
     pub song_animations: HoverAnimations<u64>,
     /// Hover animations for grid cards
     pub card_animations: HoverAnimations<u64>,
+    /// History and suggestions dropdown under the search box
+    pub dropdown_open: bool,
+    /// Recent search keywords, newest first
+    pub history: Vec<String>,
+    /// NCM hot search keywords
+    pub hot_keywords: Vec<String>,
+    /// Completions for the current query
+    pub suggestions: Vec<String>,
+    /// Bumped on every keystroke so only the last pause fetches suggestions
+    pub suggest_generation: u64,
 }
 
 impl Default for SearchPageState {
@@ -646,6 +1314,8 @@ impl Default for SearchPageState {
             keyword: String::new(),
             active_tab: SearchTab::default(),
             songs: Vec::new(),
+            local_songs: Vec::new(),
+            source: SearchSource::default(),
             albums: Vec::new(),
             playlists: Vec::new(),
             total_count: 0,
@@ -656,6 +1326,11 @@ impl Default for SearchPageState {
             )),
             song_animations: Default::default(),
             card_animations: Default::default(),
+            dropdown_open: false,
+            history: Vec::new(),
+            hot_keywords: Vec::new(),
+            suggestions: Vec::new(),
+            suggest_generation: 0,
         }
     }
 }
@@ -668,6 +1343,14 @@ pub struct DiscoverPageState {
     pub recommended_playlists: Vec<SongList>,
     /// Hot playlists (for all users)
     pub hot_playlists: Vec<SongList>,
+    /// Recent albums from followed artists, newest first
+    pub new_releases: Vec<AlbumRelease>,
+    /// Cover image handles for new releases: album_id -> image::Handle
+    pub release_covers: std::collections::HashMap<u64, iced::widget::image::Handle>,
+    /// A new release check is running
+    pub releases_checking: bool,
+    /// Another check was requested while one was running
+    pub releases_recheck: bool,
     /// Cover image handle cache: playlist_id -> image::Handle
     /// Using Handle instead of PathBuf for instant rendering (no disk IO in render loop)
     pub playlist_covers: std::collections::HashMap<u64, iced::widget::image::Handle>,
@@ -684,6 +1367,8 @@ pub struct DiscoverPageState {
     pub hot_offset: u16,
     /// Whether more hot playlists are available
     pub hot_has_more: bool,
+    /// The last hot playlists page failed to load
+    pub hot_failed: bool,
     /// Whether data has been loaded (to avoid re-fetching)
     pub data_loaded: bool,
     /// Content area width for dynamic grid column calculation
@@ -696,6 +1381,10 @@ impl Default for DiscoverPageState {
             view_mode: DiscoverViewMode::default(),
             recommended_playlists: Vec::new(),
             hot_playlists: Vec::new(),
+            new_releases: Vec::new(),
+            release_covers: std::collections::HashMap::new(),
+            releases_checking: false,
+            releases_recheck: false,
             playlist_covers: std::collections::HashMap::new(),
             playlist_cover_allocations: std::collections::HashMap::new(),
             card_animations: Default::default(),
@@ -703,6 +1392,7 @@ impl Default for DiscoverPageState {
             hot_loading: false,
             hot_offset: 0,
             hot_has_more: true,
+            hot_failed: false,
             data_loaded: false,
             // Default width, will be updated from WindowResized
             // Assumes window width ~1280, sidebar 240, padding 64
@@ -722,15 +1412,22 @@ pub struct HomePageState {
 
     // Content sections
     pub top_picks: Vec<SongList>,
-    pub toplists: Vec<TopList>,
+    /// Official charts, as playlist cards
+    pub toplists: Vec<SongList>,
     pub trending_songs: Vec<SongInfo>,
+    /// Daily recommended songs (logged-in users only)
+    pub daily_songs: Vec<SongInfo>,
+    pub recent_songs: Vec<DbSong>,
+    /// Sections show their show/hide and reorder controls
+    pub customizing: bool,
     /// Song cover handles cache: song_id -> Handle
     /// Using Handle instead of PathBuf for instant rendering (no disk IO in render loop)
     pub song_covers: std::collections::HashMap<u64, iced::widget::image::Handle>,
 
     // Login popup
     pub login_popup_open: bool,
-    pub qr_code_path: Option<PathBuf>,
+    /// Login QR code modules, row by row
+    pub qr_modules: Option<Vec<Vec<bool>>>,
     pub qr_unikey: Option<String>,
     pub qr_status: Option<String>,
 
@@ -748,3 +1445,225 @@ pub struct HomePageState {
     pub carousel_animation: iced::animation::Animation<bool>,
     pub carousel_direction: i32,
 }
+
+/// Plugin manager page state
+#[derive(Debug, Default)]
+pub struct PluginsPageState {
+    /// Whether plugins are being (re)loaded
+    pub loading: bool,
+    /// Panels rendered by UI plugins, keyed by plugin id
+    pub panels: Vec<(String, PluginPanel)>,
+}
+
+/// Log viewer page state
+#[derive(Debug, Default)]
+pub struct LogsPageState {
+    /// Minimum level shown
+    pub level: LogLevelFilter,
+    /// Records captured when the page was last refreshed
+    pub records: Vec<LogRecord>,
+    /// Whether a diagnostic bundle is being written
+    pub exporting: bool,
+}
+
+/// Blocklist manager page state
+#[derive(Debug, Default)]
+pub struct BlocklistPageState {
+    /// Artist name being typed
+    pub artist_input: String,
+}
+
+/// Recently deleted page state
+#[derive(Debug, Default)]
+pub struct TrashPageState {
+    pub playlists: Vec<crate::database::DbTrashedPlaylist>,
+    pub songs: Vec<crate::database::DbTrashedSong>,
+    /// Empty Trash was clicked once and waits for a second click
+    pub confirming_empty: bool,
+}
+
+/// Listen mode, following songs other apps play
+#[derive(Default)]
+pub struct ListenState {
+    /// Listen mode is on
+    pub active: bool,
+    /// Bumped on every start and stop, so results of an earlier run are dropped
+    pub generation: u64,
+    /// Library fingerprints, once loaded
+    pub index: Option<Arc<crate::features::listen::ListenIndex>>,
+    /// Songs fingerprinted so far and in total while the index is built
+    pub indexing: Option<(usize, usize)>,
+    pub indexing_task: Option<iced::task::Handle>,
+    /// Song heard most recently
+    pub heard: Option<HeardSong>,
+    /// Lookups in a row that found nothing
+    pub misses: u32,
+}
+
+/// Playback lowered or paused during calls
+#[derive(Debug, Default)]
+pub struct CallDuckingState {
+    /// A microphone check is running
+    pub checking: bool,
+    /// When the current call was first noticed
+    pub since: Option<Instant>,
+    /// What was changed for the current call
+    pub ducked: Option<Ducked>,
+}
+
+/// What call ducking changed, to undo when the call ends
+#[derive(Debug, Clone, Copy)]
+pub struct Ducked {
+    /// Volume to go back to; cleared when the volume is changed by hand
+    pub volume: Option<f32>,
+    /// Playback was paused and should resume
+    pub resume: bool,
+}
+
+/// Songs being exported one at a time
+#[derive(Default)]
+pub struct ExportState {
+    /// Songs still to export with the choices they were queued with
+    pub queue: VecDeque<(DbSong, ExportSettings)>,
+    /// Title of the song being exported
+    pub current: Option<String>,
+    /// Export of the current song; dropping it stops ffmpeg
+    pub task: Option<iced::task::Handle>,
+    /// Bumped on cancel, so a result that was already on its way is dropped
+    pub generation: u64,
+    pub exported: usize,
+    /// Songs with nothing on this device to export
+    pub skipped: usize,
+    pub failed: usize,
+    /// Songs queued since the queue was last empty
+    pub total: usize,
+    /// Folder of the last song exported
+    pub folder: Option<PathBuf>,
+    /// First failure, shown in the summary
+    pub error: Option<String>,
+}
+
+impl ExportState {
+    pub fn is_running(&self) -> bool {
+        self.current.is_some() || !self.queue.is_empty()
+    }
+
+    /// Songs finished one way or another
+    pub fn done(&self) -> usize {
+        self.exported + self.skipped + self.failed
+    }
+}
+
+/// Saving lyrics for the songs of a folder
+pub struct LyricsSaveBatch {
+    /// Songs handled so far
+    pub done: usize,
+    /// Songs in the folder, once listed
+    pub total: usize,
+    /// Songs lyrics were saved for
+    pub saved: usize,
+    /// Dropping it stops the batch
+    pub task: iced::task::Handle,
+}
+
+/// A library song recognised in listen mode
+#[derive(Debug, Clone)]
+pub struct HeardSong {
+    pub song: DbSong,
+    /// Position in the song at `at`
+    pub position: std::time::Duration,
+    pub at: Instant,
+    /// Lyrics, empty until loaded or when there are none
+    pub lyrics: Vec<pages::LyricLine>,
+}
+
+impl HeardSong {
+    /// Where the other app has got to by now
+    pub fn position_now(&self) -> std::time::Duration {
+        self.position + self.at.elapsed()
+    }
+}
+
+/// NCM user profile page state
+#[derive(Debug, Default)]
+pub struct ProfilePageState {
+    /// User shown
+    pub user_id: u64,
+    pub profile: Option<crate::api::UserProfile>,
+    pub avatar: Option<iced::widget::image::Handle>,
+    /// Listening rank, top song first
+    pub rank: Vec<crate::api::PlayRecord>,
+    /// Whether the all-time rank is shown instead of last week's
+    pub rank_all_time: bool,
+    /// Whether the rank is being loaded
+    pub rank_loading: bool,
+    /// Whether the user keeps their rank private
+    pub rank_hidden: bool,
+    pub playlists: crate::api::UserPlaylists,
+    /// Whether the profile couldn't be loaded
+    pub failed: bool,
+}
+
+/// Year-in-review page state
+#[derive(Debug, Default)]
+pub struct AnnualReportPageState {
+    /// Year shown
+    pub year: i32,
+    /// Years with play history, newest first
+    pub years: Vec<i32>,
+    /// Localized cards of the shown report, empty if it has no plays
+    pub cards: Vec<crate::features::annual_report::ReportCard>,
+    /// Whether the report is being built
+    pub loading: bool,
+    /// Whether report images are being written
+    pub exporting: bool,
+}
+
+/// Memory overlay and budget sampling
+#[derive(Debug, Default)]
+pub struct MemoryState {
+    /// Whether the debug overlay is shown
+    pub overlay_visible: bool,
+    /// Latest sample
+    pub report: Option<crate::features::memory::MemoryReport>,
+    /// Whether a sample is being taken
+    pub sampling: bool,
+}
+
+/// Progress of the updater
+#[derive(Debug, Clone, Default)]
+pub enum UpdateStatus {
+    #[default]
+    Idle,
+    Checking,
+    UpToDate,
+    /// Newer release found that can't be installed in place
+    Available(ReleaseInfo),
+    Downloading(ReleaseInfo),
+    /// Verified update ready to install
+    Ready(ReleaseInfo, PathBuf),
+    Failed(String),
+}
+
+/// Updater and changelog dialog state
+#[derive(Debug, Default)]
+pub struct UpdaterState {
+    pub status: UpdateStatus,
+    /// Whether the update prompt is shown
+    pub prompt_open: bool,
+    pub prompt_animation: SingleHoverAnimation,
+    /// What's new after an update, or the version history from Settings
+    pub changelog: Option<WhatsNew>,
+    pub changelog_animation: SingleHoverAnimation,
+}
+
+/// Contents of the what's-new dialog
+#[derive(Debug, Clone)]
+pub struct WhatsNew {
+    /// Versions listed with their highlights, newest first
+    pub entries: Vec<crate::features::whats_new::Entry>,
+    /// Full notes of the running release, fetched after an update
+    pub notes: Option<ReleaseInfo>,
+    /// Opened from Settings → About rather than after an update
+    pub history: bool,
+}
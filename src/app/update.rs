@@ -1,26 +1,67 @@
 //! Message update handlers - thin dispatcher delegating to submodules
 
+mod annual_report;
+mod api_error;
+mod auto_dj;
+mod autoplay;
+mod backdrop;
+mod blocklist;
+mod call_ducking;
+mod chapters;
+mod check_in;
+mod cover_viewer;
+mod covers;
+mod crash;
+mod credits;
 mod database;
 mod discover;
+mod export;
+mod favorites;
+mod home;
+mod hooks;
 mod import;
 mod keyboard;
+mod latency;
+mod listen;
+mod logs;
 mod lyrics;
+mod lyrics_save;
+mod lyrics_share;
+mod memory;
 mod mpris;
 mod navigation;
 mod ncm;
+mod ncm_match;
+mod ncm_publish;
+mod network;
+mod now_playing;
 pub mod page_loader;
 mod playback;
 mod player_controller;
 mod playlist;
+mod playlist_health;
+mod playlist_sync;
+mod plugins;
+mod power;
 mod preload;
 pub mod preload_manager;
+mod profile;
 mod queue;
 mod router;
+mod schedules;
 pub mod queue_navigator;
 mod search;
+mod search_box;
+mod session;
 mod settings;
+mod settings_profile;
+mod song_analysis;
+mod song_info;
 pub mod song_resolver;
+mod transitions;
+mod trash;
 mod tray;
+mod updater;
 mod window;
 
 use iced::Task;
@@ -28,8 +69,20 @@ use iced::Task;
 use super::{App, Message};
 
 impl App {
-    /// Handle messages by delegating to appropriate submodule handlers
+    /// Handle a message, then run hooks and rewrite the now-playing files for
+    /// what it did to playback, and frost the backdrop if it opened a modal
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        let modal_was_open = self.frosted_open();
+        let playback = self.playback_snapshot();
+        let task = self.dispatch(message);
+        let task = self.fire_playback_hooks(playback, task);
+        let task = self.update_now_playing_files(playback, task);
+        let task = self.update_media_artwork(task);
+        self.frost_backdrop(modal_was_open, task)
+    }
+
+    /// Handle messages by delegating to appropriate submodule handlers
+    fn dispatch(&mut self, message: Message) -> Task<Message> {
         // Try each handler in order until one handles the message
         if let Some(task) = self.handle_navigation(&message) {
             return task;
@@ -52,6 +105,15 @@ impl App {
         if let Some(task) = self.handle_settings(&message) {
             return task;
         }
+        if let Some(task) = self.handle_settings_profile(&message) {
+            return task;
+        }
+        if let Some(task) = self.handle_hooks(&message) {
+            return task;
+        }
+        if let Some(task) = self.handle_now_playing(&message) {
+            return task;
+        }
         if let Some(task) = self.handle_window(&message) {
             return task;
         }
@@ -67,18 +129,162 @@ impl App {
         if let Some(task) = self.handle_lyrics(&message) {
             return task;
         }
+        if let Some(task) = self.handle_lyrics_share(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_lyrics_save(&message) {
+            return task;
+        }
         if let Some(task) = self.handle_ncm(&message) {
             return task;
         }
+        if let Some(task) = self.handle_playlist_sync(&message) {
+            return task;
+        }
         if let Some(task) = self.handle_discover(&message) {
             return task;
         }
         if let Some(task) = self.handle_search(&message) {
             return task;
         }
+        if let Some(task) = self.handle_search_box(&message) {
+            return task;
+        }
         if let Some(task) = self.handle_preload(&message) {
             return task;
         }
+        if let Some(task) = self.handle_plugins(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_logs(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_annual_report(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_blocklist(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_trash(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_song_info(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_listen(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_schedules(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_call_ducking(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_export(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_cover_viewer(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_favorites(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_ncm_match(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_ncm_publish(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_playlist_health(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_song_analysis(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_profile(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_auto_dj(&message) {
+            return task;
+        }
+        if let Some(task) = self.handle_autoplay(&message) {
+            return task;
+        }
+        if let Some(task) = self.handle_latency(&message) {
+            return task;
+        }
+        if let Some(task) = self.handle_transitions(&message) {
+            return task;
+        }
+        if let Some(task) = self.handle_backdrop(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_chapters(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_credits(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_session(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_api_error(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_check_in(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_home(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_power(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_memory(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_crash(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_updater(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_network(&message) {
+            return task;
+        }
+
+        if let Some(task) = self.handle_covers(&message) {
+            return task;
+        }
 
         // Default: no task
         Task::none()
@@ -0,0 +1,255 @@
+//! Year-in-review handlers
+
+use chrono::Datelike;
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::annual_report::{self, AnnualReport, PlayRecord, ReportCard, render};
+use crate::i18n::{Key, Locale};
+
+impl App {
+    /// Handle year-in-review messages
+    pub fn handle_annual_report(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::AnnualReportYearsLoaded(years) => {
+                let current = chrono::Local::now().year();
+                let state = &mut self.ui.annual_report;
+                state.years = if years.is_empty() {
+                    vec![current]
+                } else {
+                    years.clone()
+                };
+                if !state.years.contains(&state.year) {
+                    state.year = if state.years.contains(&current) {
+                        current
+                    } else {
+                        state.years[0]
+                    };
+                }
+                Some(self.load_annual_report(self.ui.annual_report.year))
+            }
+
+            Message::SelectAnnualReportYear(year) => {
+                if *year == self.ui.annual_report.year && !self.ui.annual_report.cards.is_empty() {
+                    return Some(Task::none());
+                }
+                self.ui.annual_report.year = *year;
+                Some(self.load_annual_report(*year))
+            }
+
+            Message::AnnualReportLoaded(report) => {
+                // A slower load for a previously selected year
+                if report.year != self.ui.annual_report.year {
+                    return Some(Task::none());
+                }
+                self.ui.annual_report.loading = false;
+                self.ui.annual_report.cards = report_cards(report, self.core.locale);
+                Some(Task::none())
+            }
+
+            Message::ExportAnnualReport => {
+                if self.ui.annual_report.exporting || self.ui.annual_report.cards.is_empty() {
+                    return Some(Task::none());
+                }
+                Some(Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .pick_folder()
+                            .await
+                            .map(|handle| handle.path().to_path_buf())
+                    },
+                    Message::AnnualReportFolderPicked,
+                ))
+            }
+
+            Message::AnnualReportFolderPicked(dir) => {
+                let Some(dir) = dir.clone() else {
+                    return Some(Task::none());
+                };
+                self.ui.annual_report.exporting = true;
+                let cards = self.ui.annual_report.cards.clone();
+                let year = self.ui.annual_report.year;
+
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            render::export(&cards, year, &dir)
+                                .map(|_| dir)
+                                .map_err(|e| e.to_string())
+                        })
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()))
+                    },
+                    Message::AnnualReportExported,
+                ))
+            }
+
+            Message::AnnualReportExported(result) => {
+                self.ui.annual_report.exporting = false;
+                let locale = self.core.locale;
+                let toast = match result {
+                    Ok(dir) => {
+                        tracing::info!("Annual report images written to {:?}", dir);
+                        locale
+                            .get(Key::AnnualReportExported)
+                            .replace("{}", &dir.display().to_string())
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to export annual report: {}", e);
+                        locale.get(Key::AnnualReportExportFailed).replace("{}", e)
+                    }
+                };
+                Some(Task::done(Message::ShowToast(toast)))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Load the years that have play history
+    pub(super) fn load_annual_report_years(&self) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                db.get_play_history_years().await.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load play history years: {}", e);
+                    Vec::new()
+                })
+            },
+            Message::AnnualReportYearsLoaded,
+        )
+    }
+
+    /// Build the report for `year` in the local time zone
+    fn load_annual_report(&mut self, year: i32) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        self.ui.annual_report.loading = true;
+        self.ui.annual_report.cards.clear();
+
+        Task::perform(
+            async move {
+                let (start, end) = annual_report::year_bounds(year, &chrono::Local);
+                let records: Vec<PlayRecord> = db
+                    .get_play_records(start, end)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to load play records for {}: {}", year, e);
+                        Vec::new()
+                    })
+                    .into_iter()
+                    .map(|r| PlayRecord {
+                        song_id: r.song_id,
+                        title: r.title,
+                        artist: r.artist,
+                        played_at: r.played_at,
+                        listened_secs: r.listened_secs,
+                    })
+                    .collect();
+                annual_report::build(year, &records, &chrono::Local)
+            },
+            Message::AnnualReportLoaded,
+        )
+    }
+}
+
+/// Turn a report into localized cards; an empty report has none
+fn report_cards(report: &AnnualReport, locale: Locale) -> Vec<ReportCard> {
+    if report.is_empty() {
+        return Vec::new();
+    }
+
+    let mut cards = vec![ReportCard {
+        title: locale
+            .get(Key::AnnualReportOverview)
+            .replace("{}", &report.year.to_string()),
+        headline: locale.count(Key::AnnualReportHours, (report.listened_secs / 3600) as u64),
+        rows: vec![
+            (
+                locale.count(Key::AnnualReportPlays, report.plays as u64),
+                String::new(),
+            ),
+            (
+                locale.count(Key::AnnualReportSongs, report.distinct_songs as u64),
+                String::new(),
+            ),
+        ],
+    }];
+
+    if let Some(top) = report.top_songs.first() {
+        cards.push(ReportCard {
+            title: locale.get(Key::AnnualReportTopSongs).to_string(),
+            headline: top.title.clone(),
+            rows: report
+                .top_songs
+                .iter()
+                .enumerate()
+                .map(|(i, song)| {
+                    (
+                        format!("{}. {}", i + 1, song.title),
+                        format!(
+                            "{} · {}",
+                            song.artist,
+                            locale.count(Key::AnnualReportPlays, song.plays as u64)
+                        ),
+                    )
+                })
+                .collect(),
+        });
+    }
+
+    if let Some(top) = report.top_artists.first() {
+        cards.push(ReportCard {
+            title: locale.get(Key::AnnualReportTopArtists).to_string(),
+            headline: top.name.clone(),
+            rows: report
+                .top_artists
+                .iter()
+                .enumerate()
+                .map(|(i, artist)| {
+                    (
+                        format!("{}. {}", i + 1, artist.name),
+                        format!(
+                            "{} · {}",
+                            locale.count(Key::AnnualReportPlays, artist.plays as u64),
+                            locale.count(
+                                Key::AnnualReportHours,
+                                (artist.listened_secs / 3600) as u64
+                            )
+                        ),
+                    )
+                })
+                .collect(),
+        });
+    }
+
+    if let Some(hour) = report.peak_hour {
+        let mut rows = vec![(
+            locale.get(Key::AnnualReportPeakHour).to_string(),
+            format!("{:02}:00 – {:02}:00", hour, (hour + 1) % 24),
+        )];
+        if let Some(streak) = report.longest_streak {
+            rows.push((
+                locale.get(Key::AnnualReportStreak).to_string(),
+                format!(
+                    "{} · {}",
+                    locale.count(Key::AnnualReportStreakDays, streak.days as u64),
+                    locale
+                        .get(Key::AnnualReportStreakFrom)
+                        .replace("{}", &streak.start.format("%Y-%m-%d").to_string())
+                ),
+            ));
+        }
+        cards.push(ReportCard {
+            title: locale.get(Key::AnnualReportHabits).to_string(),
+            headline: format!("{:02}:00", hour),
+            rows,
+        });
+    }
+
+    cards
+}
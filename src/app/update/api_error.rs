@@ -0,0 +1,63 @@
+//! Turn classified NCM failures into specific messages and actions
+
+use iced::Task;
+
+use crate::api::ApiError;
+use crate::app::{App, Message};
+use crate::features::MusicQuality;
+use crate::i18n::Key;
+
+impl App {
+    /// Handle API failure messages
+    pub fn handle_api_error(&mut self, message: &Message) -> Option<Task<Message>> {
+        let Message::ApiFailed(err) = message else {
+            return None;
+        };
+        tracing::debug!("NCM request failed: {}", err);
+
+        let locale = self.core.locale;
+        let (text, action) = match err {
+            // A saved login going stale is handled by the session flow
+            ApiError::AuthExpired if self.core.is_logged_in => {
+                return Some(Task::done(Message::SessionExpired));
+            }
+            ApiError::AuthExpired => (
+                locale.get(Key::LoginRequired),
+                Some((locale.get(Key::LoginAgain), Message::RequestQrCode)),
+            ),
+            ApiError::RateLimited => (locale.get(Key::ApiRateLimited), None),
+            ApiError::CaptchaRequired => (locale.get(Key::ApiCaptchaRequired), None),
+            ApiError::RegionBlocked => (locale.get(Key::ApiRegionBlocked), None),
+            ApiError::VipRequired => {
+                let lower = lower_quality(self.core.settings.playback.music_quality);
+                (
+                    locale.get(Key::ApiVipRequired),
+                    lower.map(|q| {
+                        (locale.get(Key::ApiLowerQuality), Message::UpdateMusicQuality(q))
+                    }),
+                )
+            }
+            ApiError::Network => (locale.get(Key::ApiNetworkError), None),
+        };
+
+        let task = self.update(Message::ShowErrorToast(text.to_string()));
+        self.ui.toast_action = action;
+        Some(task)
+    }
+}
+
+/// The next quality down, `None` at the lowest
+fn lower_quality(quality: MusicQuality) -> Option<MusicQuality> {
+    let all = MusicQuality::all();
+    let idx = all.iter().position(|q| *q == quality)?;
+    idx.checked_sub(1).map(|i| all[i])
+}
+
+/// Message reporting `err`: its classified failure if it has one, otherwise
+/// a generic error toast with `fallback`
+pub fn failure_message(err: &anyhow::Error, fallback: &str) -> Message {
+    match ApiError::of(err) {
+        Some(api_error) => Message::ApiFailed(api_error),
+        None => Message::ShowErrorToast(fallback.to_string()),
+    }
+}
@@ -0,0 +1,216 @@
+//! Auto-DJ play mode: queue top-ups and crossfades
+
+use std::time::Duration;
+
+use iced::Task;
+use rand::seq::{IndexedRandom, SliceRandom};
+use tracing::{debug, error};
+
+use super::ncm::queue_song_from_ncm;
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::auto_dj::{self, BATCH_SIZE};
+use crate::features::{AutoDjPool, PlayMode};
+use crate::i18n::Key;
+
+/// Songs requested from the pool per top-up, before picking
+const CANDIDATES: usize = 30;
+
+/// Tracks shorter than this many crossfades play out in full
+const MIN_TRACK_CROSSFADES: u32 = 3;
+
+impl App {
+    /// Handle Auto-DJ messages
+    pub fn handle_auto_dj(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::AutoDjSongsFetched(result, play) => {
+                self.library.auto_dj_filling = false;
+                if !self.is_auto_dj() {
+                    return Some(Task::none());
+                }
+                let locale = self.core.locale;
+
+                let mut candidates = match result {
+                    Ok(songs) => songs.clone(),
+                    Err(e) => {
+                        error!("Auto-DJ failed to fetch songs: {}", e);
+                        return Some(Task::done(Message::ShowToast(
+                            locale.get(Key::AutoDjFetchFailed).replace("{}", e),
+                        )));
+                    }
+                };
+                candidates.shuffle(&mut rand::rng());
+
+                let picked = auto_dj::pick(
+                    candidates,
+                    &self.library.queue,
+                    &self.library.blocklist,
+                    BATCH_SIZE,
+                );
+                if picked.is_empty() {
+                    return Some(Task::done(Message::ShowToast(
+                        locale.get(Key::AutoDjPoolEmpty).to_string(),
+                    )));
+                }
+                debug!("Auto-DJ: adding {} songs to the queue", picked.len());
+
+                let first_added = self.library.queue.len();
+                self.library.queue.extend(picked);
+                if let Some(db) = &self.core.db {
+                    db.save_queue_with_songs(self.library.queue.clone(), None);
+                }
+
+                if *play {
+                    return Some(self.play_song_at_index(first_added));
+                }
+                Some(self.preload_adjacent_tracks_with_ncm())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Whether the Auto-DJ is driving the queue
+    pub(super) fn is_auto_dj(&self) -> bool {
+        self.core.settings.play_mode == PlayMode::AutoDj && !self.is_fm_mode()
+    }
+
+    /// Top the queue up if the Auto-DJ is running low on songs ahead
+    pub(super) fn auto_dj_top_up_if_needed(&mut self) -> Task<Message> {
+        if self.is_auto_dj()
+            && auto_dj::needs_top_up(self.library.queue.len(), self.library.queue_index)
+        {
+            self.auto_dj_top_up(false)
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Fetch songs from the Auto-DJ pool, optionally playing the first added
+    pub(super) fn auto_dj_top_up(&mut self, play: bool) -> Task<Message> {
+        if self.library.auto_dj_filling {
+            return Task::none();
+        }
+        let locale = self.core.locale;
+        let done = move |result| Message::AutoDjSongsFetched(result, play);
+
+        let task = match self.core.settings.playback.auto_dj.pool {
+            AutoDjPool::Playlist(playlist_id) => {
+                let Some(db) = self.core.db.clone() else {
+                    return Task::none();
+                };
+                Task::perform(
+                    async move {
+                        db.get_playlist_songs(playlist_id)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    done,
+                )
+            }
+
+            AutoDjPool::LikedSongs => {
+                let Some(client) = self
+                    .core
+                    .ncm_client
+                    .clone()
+                    .filter(|_| self.core.user_info.is_some())
+                else {
+                    return Task::done(Message::ShowToast(
+                        locale.get(Key::LoginRequired).to_string(),
+                    ));
+                };
+                let liked = self.library.favorites.liked_ncm_ids();
+                let ids: Vec<u64> = liked
+                    .choose_multiple(&mut rand::rng(), CANDIDATES)
+                    .copied()
+                    .collect();
+                Task::perform(
+                    async move {
+                        client
+                            .song_detail(&ids)
+                            .await
+                            .map(|songs| songs.iter().map(queue_song_from_ncm).collect())
+                            .map_err(|e| e.to_string())
+                    },
+                    done,
+                )
+            }
+
+            AutoDjPool::SimilarSongs => {
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Task::done(Message::ShowToast(
+                        locale.get(Key::LoginRequired).to_string(),
+                    ));
+                };
+                // Follow the most recent song NCM knows about
+                let current = self.library.queue_index.unwrap_or(0);
+                let seed = self
+                    .library
+                    .queue
+                    .iter()
+                    .take(current + 1)
+                    .rev()
+                    .find_map(|song| self.ncm_id_of(song));
+                let Some(seed) = seed else {
+                    return Task::done(Message::ShowToast(
+                        locale.get(Key::AutoDjNoSeed).to_string(),
+                    ));
+                };
+                Task::perform(
+                    async move {
+                        client
+                            .client
+                            .simi_song(seed, CANDIDATES as u16)
+                            .await
+                            .map(|songs| songs.iter().map(queue_song_from_ncm).collect())
+                            .map_err(|e| e.to_string())
+                    },
+                    done,
+                )
+            }
+        };
+
+        self.library.auto_dj_filling = true;
+        task
+    }
+
+    /// Start the next song under the end of the current one
+    ///
+    /// Only crossfades into a preloaded track, which starts at once; anything
+    /// else is left to the normal end-of-track handling.
+    pub(super) fn auto_dj_crossfade(&mut self) -> Task<Message> {
+        let secs = self.core.settings.playback.auto_dj.crossfade_secs;
+        if secs == 0 || !self.is_auto_dj() || self.library.stop_after_current {
+            return Task::none();
+        }
+        let (Some(player), Some(song)) = (&self.core.audio, &self.library.current_song) else {
+            return Task::none();
+        };
+        if !player.is_playing() || self.library.auto_dj_crossfaded == Some(song.id) {
+            return Task::none();
+        }
+
+        let crossfade = Duration::from_secs(secs as u64);
+        let info = player.get_info();
+        if info.duration < crossfade * MIN_TRACK_CROSSFADES
+            || info.duration.saturating_sub(info.position) > crossfade
+        {
+            return Task::none();
+        }
+        let Some(next_idx) = self.calculate_next_index() else {
+            return Task::none();
+        };
+        if !self.library.preload_manager.is_ready_for(next_idx, true) {
+            return Task::none();
+        }
+
+        debug!("Auto-DJ: crossfading out of {}", song.title);
+        if let Some(db) = &self.core.db {
+            db.record_play(song.id, song.duration_secs, true);
+        }
+        player.crossfade_next(crossfade);
+        self.library.auto_dj_crossfaded = Some(song.id);
+        self.play_next_song()
+    }
+}
@@ -0,0 +1,121 @@
+//! Queue autoplay: carry on with similar songs once the queue has finished
+
+use iced::Task;
+use rand::seq::SliceRandom;
+use tracing::{debug, warn};
+
+use super::ncm::queue_song_from_ncm;
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::database::DbSong;
+use crate::features::auto_dj::{self, BATCH_SIZE};
+use crate::features::autoplay;
+use crate::i18n::Key;
+
+/// Similar songs requested from NCM, before picking
+const CANDIDATES: u16 = 30;
+
+impl App {
+    /// Handle autoplay messages
+    pub fn handle_autoplay(&mut self, message: &Message) -> Option<Task<Message>> {
+        let Message::AutoplaySongsFetched(result) = message else {
+            return None;
+        };
+        self.library.autoplay_filling = false;
+        // Turned off while the request was in flight
+        if !self.core.settings.playback.autoplay_similar {
+            self.handle_queue_finished();
+            return Some(Task::none());
+        }
+
+        let mut candidates = match result {
+            Ok(songs) if !songs.is_empty() => songs.clone(),
+            Ok(_) => self.local_similar_songs(),
+            Err(e) => {
+                warn!(
+                    "Autoplay: similar songs unavailable, using the library: {}",
+                    e
+                );
+                self.local_similar_songs()
+            }
+        };
+        candidates.shuffle(&mut rand::rng());
+
+        let picked = auto_dj::pick(
+            candidates,
+            &self.library.queue,
+            &self.library.blocklist,
+            BATCH_SIZE,
+        );
+        if picked.is_empty() {
+            debug!("Autoplay: nothing similar to add");
+            self.handle_queue_finished();
+            return Some(Task::none());
+        }
+        debug!("Autoplay: adding {} songs to the queue", picked.len());
+
+        let first_added = self.library.queue.len();
+        self.library
+            .autoplay_ids
+            .extend(picked.iter().map(|song| song.id));
+        self.library.queue.extend(picked);
+        self.clear_shuffle_cache();
+        if let Some(db) = &self.core.db {
+            db.save_queue_with_songs(self.library.queue.clone(), None);
+        }
+
+        let toast = Task::done(Message::ShowToast(
+            self.core.locale.get(Key::AutoplayStarted).to_string(),
+        ));
+        Some(Task::batch([toast, self.play_song_at_index(first_added)]))
+    }
+
+    /// Fetch songs similar to the last one played, if autoplay is on
+    ///
+    /// Returns `None` when the queue should simply finish.
+    pub(super) fn autoplay_similar(&mut self) -> Option<Task<Message>> {
+        if !self.core.settings.playback.autoplay_similar || self.library.queue.is_empty() {
+            return None;
+        }
+        if self.library.autoplay_filling {
+            return Some(Task::none());
+        }
+
+        let current = self.library.queue_index.unwrap_or(0);
+        let seed = self
+            .library
+            .queue
+            .iter()
+            .take(current + 1)
+            .rev()
+            .find_map(|song| self.ncm_id_of(song));
+
+        let task = match (self.core.ncm_client.clone(), seed) {
+            (Some(client), Some(seed)) => Task::perform(
+                async move {
+                    client
+                        .client
+                        .simi_song(seed, CANDIDATES)
+                        .await
+                        .map(|songs| songs.iter().map(queue_song_from_ncm).collect())
+                        .map_err(|e| e.to_string())
+                },
+                Message::AutoplaySongsFetched,
+            ),
+            // Nothing to ask NCM about, go straight to the library
+            _ => Task::done(Message::AutoplaySongsFetched(Ok(Vec::new()))),
+        };
+
+        self.library.autoplay_filling = true;
+        Some(task)
+    }
+
+    /// Library songs similar to the one that just finished
+    fn local_similar_songs(&self) -> Vec<DbSong> {
+        let current = self.library.queue_index.unwrap_or(0);
+        let Some(seed) = self.library.queue.get(current) else {
+            return Vec::new();
+        };
+        autoplay::local_similar(&self.library.db_songs, seed)
+    }
+}
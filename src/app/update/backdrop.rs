@@ -0,0 +1,140 @@
+//! Frosted backdrop handlers
+//!
+//! When the first modal or the player menu opens, the main window is
+//! captured, blurred off the UI thread, and shown behind them until the last
+//! one has faded out. The backdrop is a still of the moment they opened:
+//! the window behind keeps running but the glass doesn't follow it.
+//!
+//! Dialogs start fully transparent, so the capture taken as they open
+//! doesn't show them and they don't wait for it. The login popup and the
+//! player menu appear at once, so only they sit out the captured frame.
+
+use iced::Task;
+use iced::widget::image;
+
+use crate::app::{App, Message};
+use crate::ui::effects::frosted;
+
+impl App {
+    /// Handle frosted backdrop messages
+    pub fn handle_backdrop(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::BackdropCaptured(screenshot) => {
+                self.ui.backdrop.capturing = false;
+                let Some(screenshot) = screenshot.clone() else {
+                    return Some(Task::none());
+                };
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            frosted::frost(
+                                &screenshot.rgba,
+                                screenshot.size.width,
+                                screenshot.size.height,
+                            )
+                        })
+                        .await
+                        .ok()
+                        .flatten()
+                    },
+                    |frosted| {
+                        Message::BackdropFrosted(frosted.map(|image| {
+                            image::Handle::from_rgba(image.width, image.height, image.data)
+                        }))
+                    },
+                ))
+            }
+            Message::BackdropFrosted(handle) => {
+                // The modal may already be gone by the time the blur is done
+                if self.frosted_open() {
+                    self.ui.backdrop.frosted = handle.clone();
+                }
+                Some(Task::none())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a dialog or the login popup is open
+    pub fn modal_open(&self) -> bool {
+        let dialogs = &self.ui.dialogs;
+        dialogs.edit_open
+            || dialogs.exit_open
+            || dialogs.delete_pending_id.is_some()
+            || dialogs.crash_report.is_some()
+            || dialogs.open_link_pending.is_some()
+            || dialogs.publish.is_some()
+            || dialogs.health.is_some()
+            || dialogs.lyrics_share.is_some()
+            || dialogs.song_info.is_some()
+            || dialogs.export.is_some()
+            || self.ui.updater.prompt_open
+            || self.ui.updater.changelog.is_some()
+            || self.ui.home.login_popup_open
+    }
+
+    /// Whether anything the frosted backdrop goes behind is open
+    pub fn frosted_open(&self) -> bool {
+        self.modal_open() || self.ui.player_menu_open
+    }
+
+    /// How far the most visible modal has faded in
+    pub fn modal_progress(&self) -> f32 {
+        let dialogs = &self.ui.dialogs;
+        // The login popup and the player menu don't animate
+        let popup = if self.ui.home.login_popup_open || self.ui.player_menu_open {
+            1.0
+        } else {
+            0.0
+        };
+        [
+            dialogs.edit_animation.progress(),
+            dialogs.exit_animation.progress(),
+            dialogs.delete_animation.progress(),
+            dialogs.crash_animation.progress(),
+            dialogs.open_link_animation.progress(),
+            dialogs.publish_animation.progress(),
+            dialogs.health_animation.progress(),
+            dialogs.lyrics_share_animation.progress(),
+            dialogs.song_info_animation.progress(),
+            dialogs.export_animation.progress(),
+            dialogs.cover_viewer_animation.progress(),
+            self.ui.updater.prompt_animation.progress(),
+            self.ui.updater.changelog_animation.progress(),
+            popup,
+        ]
+        .into_iter()
+        .fold(0.0, f32::max)
+    }
+
+    /// Capture the main window as the first modal opens, and drop the
+    /// snapshot once every modal has faded out
+    pub(super) fn frost_backdrop(
+        &mut self,
+        modal_was_open: bool,
+        task: Task<Message>,
+    ) -> Task<Message> {
+        let modal_open = self.frosted_open();
+        if !modal_open && self.ui.backdrop.frosted.is_some() && self.modal_progress() <= 0.01 {
+            self.ui.backdrop.frosted = None;
+        }
+        if modal_was_open
+            || !modal_open
+            || self.ui.backdrop.capturing
+            || self.core.settings.display.power_saving_mode
+        {
+            return task;
+        }
+        // Never the lyrics window, which may have been opened since
+        let Some(window) = self.core.main_window else {
+            return task;
+        };
+
+        // Dialogs are still transparent in the captured frame; popups that
+        // show at once are held back until it comes in
+        self.ui.backdrop.capturing = true;
+        let capture =
+            iced::window::screenshot(window).map(|shot| Message::BackdropCaptured(Some(shot)));
+        Task::batch([task, capture])
+    }
+}
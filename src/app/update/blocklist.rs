@@ -0,0 +1,151 @@
+//! Blocklist handlers
+
+use iced::Task;
+use tracing::error;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::PlayMode;
+use crate::features::blocklist::BlockedSong;
+use crate::i18n::Key;
+
+impl App {
+    /// Handle blocklist messages
+    pub fn handle_blocklist(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::BlocklistLoaded(blocklist) => {
+                self.library.blocklist = blocklist.clone();
+                self.cache_shuffle_indices();
+                Some(Task::none())
+            }
+
+            Message::BlockCurrentSong => {
+                self.ui.player_menu_open = false;
+                let Some(song) = self.library.current_song.clone() else {
+                    return Some(Task::none());
+                };
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+
+                self.library.blocklist.insert_song(BlockedSong {
+                    id: song.id,
+                    title: song.title.clone(),
+                    artist: song.artist.clone(),
+                });
+                let toast = self
+                    .core
+                    .locale
+                    .get(Key::SongBlocked)
+                    .replace("{}", &song.title);
+                let save = async move {
+                    if let Err(e) = db.block_song(song.id, &song.title, &song.artist).await {
+                        error!("Failed to block song {}: {}", song.id, e);
+                    }
+                };
+
+                // The shuffle pick may be the song just blocked
+                if self.core.settings.play_mode == PlayMode::Shuffle {
+                    self.clear_shuffle_cache();
+                    self.cache_shuffle_indices();
+                }
+
+                Some(Task::batch([
+                    Task::done(Message::ShowToast(toast)),
+                    Task::perform(save, |_| Message::NoOp),
+                    self.play_next_song(),
+                ]))
+            }
+
+            Message::UnblockSong(id) => {
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                if !self.library.blocklist.remove_song(*id) {
+                    return Some(Task::none());
+                }
+                let id = *id;
+                let save = async move {
+                    if let Err(e) = db.unblock_song(id).await {
+                        error!("Failed to unblock song {}: {}", id, e);
+                    }
+                };
+
+                Some(Task::batch([
+                    Task::perform(save, |_| Message::NoOp),
+                    self.reshuffle_after_blocklist_change(),
+                ]))
+            }
+
+            Message::BlockArtistInputChanged(value) => {
+                self.ui.blocklist.artist_input = value.clone();
+                Some(Task::none())
+            }
+
+            Message::BlockArtist => {
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                let name = std::mem::take(&mut self.ui.blocklist.artist_input)
+                    .trim()
+                    .to_string();
+                if !self.library.blocklist.insert_artist(name.clone()) {
+                    return Some(Task::none());
+                }
+
+                let toast = self
+                    .core
+                    .locale
+                    .get(Key::ArtistBlocked)
+                    .replace("{}", &name);
+                let save = async move {
+                    if let Err(e) = db.block_artist(&name).await {
+                        error!("Failed to block artist {}: {}", name, e);
+                    }
+                };
+
+                Some(Task::batch([
+                    Task::done(Message::ShowToast(toast)),
+                    Task::perform(save, |_| Message::NoOp),
+                    self.reshuffle_after_blocklist_change(),
+                ]))
+            }
+
+            Message::UnblockArtist(name) => {
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                if !self.library.blocklist.remove_artist(name) {
+                    return Some(Task::none());
+                }
+                let name = name.clone();
+                let save = async move {
+                    if let Err(e) = db.unblock_artist(&name).await {
+                        error!("Failed to unblock artist {}: {}", name, e);
+                    }
+                };
+
+                Some(Task::batch([
+                    Task::perform(save, |_| Message::NoOp),
+                    self.reshuffle_after_blocklist_change(),
+                ]))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Re-pick the shuffle neighbours so they respect the current blocklist
+    fn reshuffle_after_blocklist_change(&mut self) -> Task<Message> {
+        if self.core.settings.play_mode != PlayMode::Shuffle || self.is_fm_mode() {
+            return Task::none();
+        }
+        self.clear_shuffle_cache();
+        self.cache_shuffle_indices();
+        if self.library.current_song.is_some() {
+            self.preload_adjacent_tracks_with_ncm()
+        } else {
+            Task::none()
+        }
+    }
+}
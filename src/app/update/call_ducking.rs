@@ -0,0 +1,115 @@
+//! Call ducking handlers
+
+use iced::Task;
+use iced::time::Instant;
+use tracing::info;
+
+use crate::app::message::Message;
+use crate::app::state::{App, Ducked};
+use crate::features::CallAction;
+use crate::features::call_ducking;
+use crate::features::jobs::{Category, Job};
+
+impl App {
+    /// Handle microphone checks for call ducking
+    pub fn handle_call_ducking(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::CheckMicrophone => {
+                if self.ui.call_ducking.checking {
+                    return Some(Task::none());
+                }
+                self.ui.call_ducking.checking = true;
+                Some(Task::perform(
+                    async {
+                        Job::new(Category::Disk)
+                            .run_blocking(crate::platform::microphone::recording_apps)
+                            .await
+                            .flatten()
+                    },
+                    Message::MicrophoneChecked,
+                ))
+            }
+
+            Message::MicrophoneChecked(apps) => {
+                self.ui.call_ducking.checking = false;
+                let settings = self.core.settings.playback.call_ducking;
+                let in_call = apps
+                    .as_deref()
+                    .is_some_and(|apps| call_ducking::in_call(apps, settings.any_app));
+
+                if !in_call {
+                    return Some(self.end_call());
+                }
+                let since = *self.ui.call_ducking.since.get_or_insert_with(Instant::now);
+                if self.ui.call_ducking.ducked.is_some()
+                    || since.elapsed().as_secs() < u64::from(settings.after_secs)
+                {
+                    return Some(Task::none());
+                }
+                Some(self.start_call(settings.action, settings.lower_percent))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Lower or pause playback for a call that just started
+    fn start_call(&mut self, action: CallAction, lower_percent: u32) -> Task<Message> {
+        let Some(player) = &self.core.audio else {
+            return Task::none();
+        };
+        let playing = player.is_playing();
+        info!("Call started, {:?} playback", action);
+
+        match action {
+            CallAction::Off => Task::none(),
+            CallAction::Lower => {
+                // A schedule's fade-in would undo the lowering
+                let volume = self
+                    .ui
+                    .schedule_ramp
+                    .take()
+                    .map_or_else(|| player.get_info().volume, |ramp| ramp.target);
+                player.set_volume(volume * lower_percent as f32 / 100.0);
+                self.ui.call_ducking.ducked = Some(Ducked {
+                    volume: Some(volume),
+                    resume: false,
+                });
+                self.update_mpris_state();
+                Task::none()
+            }
+            CallAction::Pause => {
+                self.ui.call_ducking.ducked = Some(Ducked {
+                    volume: None,
+                    resume: playing,
+                });
+                if playing {
+                    return self.update(Message::TogglePlayback);
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Undo what was changed for a call, if anything
+    pub(super) fn end_call(&mut self) -> Task<Message> {
+        self.ui.call_ducking.since = None;
+        let Some(ducked) = self.ui.call_ducking.ducked.take() else {
+            return Task::none();
+        };
+        info!("Call ended, restoring playback");
+
+        if let Some(volume) = ducked.volume
+            && let Some(player) = &self.core.audio
+        {
+            player.set_volume(volume);
+            self.update_mpris_state();
+        }
+        // Left alone if playback was started again by hand during the call
+        let paused = self.core.audio.as_ref().is_some_and(|p| !p.is_playing());
+        if ducked.resume && paused {
+            return self.update(Message::TogglePlayback);
+        }
+        Task::none()
+    }
+}
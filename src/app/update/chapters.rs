@@ -0,0 +1,113 @@
+//! Chapter list and chapter navigation
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::database::DbSong;
+use crate::features::media::chapters::{self, Chapter};
+
+impl App {
+    /// Handle chapter messages
+    pub fn handle_chapters(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::ChaptersLoaded(song_id, chapters) => {
+                if self.library.current_song.as_ref().map(|s| s.id) == Some(*song_id) {
+                    tracing::debug!("Loaded {} chapters", chapters.len());
+                    self.library.chapters = Some((*song_id, chapters.clone()));
+                    if chapters.is_empty() {
+                        self.ui.chapters_visible = false;
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::ToggleChapters => {
+                self.ui.chapters_visible =
+                    !self.ui.chapters_visible && !self.current_chapters().is_empty();
+                if self.ui.chapters_visible {
+                    self.ui.queue_visible = false;
+                }
+                Some(Task::none())
+            }
+
+            Message::SeekToChapter(idx) => {
+                self.seek_to_chapter(*idx);
+                Some(Task::none())
+            }
+
+            Message::PrevChapter => {
+                if let Some(position_ms) = self.position_ms() {
+                    if let Some(idx) =
+                        chapters::previous_chapter(self.current_chapters(), position_ms)
+                    {
+                        self.seek_to_chapter(idx);
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::NextChapter => {
+                if let Some(position_ms) = self.position_ms() {
+                    if let Some(idx) = chapters::next_chapter(self.current_chapters(), position_ms)
+                    {
+                        self.seek_to_chapter(idx);
+                    }
+                }
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Chapters of the current song, empty if it has none
+    pub fn current_chapters(&self) -> &[Chapter] {
+        match (&self.library.chapters, &self.library.current_song) {
+            (Some((id, chapters)), Some(song)) if *id == song.id => chapters,
+            _ => &[],
+        }
+    }
+
+    /// Index of the chapter being played
+    pub fn current_chapter_index(&self) -> Option<usize> {
+        chapters::chapter_at(self.current_chapters(), self.position_ms()?)
+    }
+
+    /// Read chapter markers from a local song file in the background
+    pub(super) fn load_chapters(&self, song: &DbSong) -> Task<Message> {
+        let path = PathBuf::from(&song.file_path);
+        if song.file_path.is_empty() || !path.exists() {
+            return Task::none();
+        }
+        let song_id = song.id;
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || chapters::read_chapters(&path))
+                    .await
+                    .unwrap_or_default()
+            },
+            move |chapters| Message::ChaptersLoaded(song_id, chapters),
+        )
+    }
+
+    fn seek_to_chapter(&mut self, idx: usize) {
+        let Some(chapter) = self.current_chapters().get(idx) else {
+            return;
+        };
+        let position = Duration::from_millis(chapter.start_ms);
+        if let Some(player) = &self.core.audio {
+            player.seek(position);
+        }
+        self.ui.lyrics.redraw_requested = true;
+        self.update_mpris_state();
+    }
+
+    fn position_ms(&self) -> Option<u64> {
+        let player = self.core.audio.as_ref()?;
+        Some(player.get_info().position.as_millis() as u64)
+    }
+}
@@ -0,0 +1,97 @@
+//! Automatic NCM daily check-in
+
+use iced::Task;
+use tracing::{debug, warn};
+
+use crate::api::ApiError;
+use crate::app::{App, Message};
+use crate::i18n::Key;
+
+/// Check-in platforms as the API numbers them: mobile, then web
+const PLATFORMS: [u8; 2] = [0, 1];
+
+/// Rewards listed under the Settings toggle
+const HISTORY_LIMIT: i64 = 10;
+
+impl App {
+    /// Handle daily check-in messages
+    pub fn handle_check_in(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::RunDailyCheckIn => {
+                let (Some(db), Some(user_info)) = (self.core.db.clone(), &self.core.user_info)
+                else {
+                    return Some(Task::none());
+                };
+                let uid = user_info.user_id;
+                // With the toggle off this only loads the history for Settings
+                let client = self.core.ncm_client.clone().filter(|_| {
+                    self.core.is_logged_in && self.core.settings.account.daily_check_in
+                });
+
+                Some(Task::perform(
+                    async move {
+                        let mut claimed = 0;
+                        let mut failed = false;
+                        let mut expired = false;
+                        if let Some(client) = client {
+                            for platform in PLATFORMS {
+                                match client.client.daily_signin(platform).await {
+                                    Ok(Some(points)) => {
+                                        claimed += points;
+                                        if let Err(e) =
+                                            db.record_check_in(uid, platform, points).await
+                                        {
+                                            warn!("Failed to record check-in: {}", e);
+                                        }
+                                    }
+                                    Ok(None) => {
+                                        debug!("Already checked in today (platform {})", platform)
+                                    }
+                                    Err(e) => {
+                                        warn!(
+                                            "Daily check-in failed (platform {}): {}",
+                                            platform, e
+                                        );
+                                        expired |= ApiError::is_auth_expired(&e);
+                                        failed = true;
+                                    }
+                                }
+                            }
+                        }
+                        let history = db
+                            .get_check_ins(uid, HISTORY_LIMIT)
+                            .await
+                            .unwrap_or_default();
+                        (claimed, failed, expired, history)
+                    },
+                    |(claimed, failed, expired, history)| {
+                        if expired {
+                            Message::SessionExpired
+                        } else {
+                            Message::DailyCheckInDone(claimed, failed, history)
+                        }
+                    },
+                ))
+            }
+
+            Message::DailyCheckInDone(claimed, failed, history) => {
+                self.ui.check_in_history = history.clone();
+                let locale = self.core.locale;
+                if *claimed > 0 {
+                    let toast = locale
+                        .get(Key::CheckInClaimed)
+                        .replace("{}", &claimed.to_string());
+                    Some(Task::done(Message::ShowToast(toast)))
+                } else if *failed {
+                    Some(Task::done(Message::ShowErrorToast(
+                        locale.get(Key::CheckInFailed).to_string(),
+                    )))
+                } else {
+                    Some(Task::none())
+                }
+            }
+
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Full-screen cover viewer handlers
+//!
+//! The viewer opens on the best cover already on disk, the lyrics page's
+//! large copy or the list thumbnail, and swaps in NCM's original once it
+//! has been downloaded.
+
+use std::path::PathBuf;
+
+use iced::Task;
+use tracing::{info, warn};
+
+use crate::app::message::Message;
+use crate::app::state::{App, CoverViewer};
+use crate::features::export;
+use crate::features::jobs::{Category, Job};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle opening, closing and saving from the cover viewer
+    pub fn handle_cover_viewer(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::OpenCoverViewer => {
+                let Some(song) = self.library.current_song.clone() else {
+                    return Some(Task::none());
+                };
+                let Some(path) = self
+                    .ui
+                    .lyrics
+                    .cover_for(&song)
+                    .filter(|path| !path.starts_with("http"))
+                    .map(String::from)
+                else {
+                    return Some(Task::none());
+                };
+
+                let full = self.fetch_full_cover(&song);
+                self.ui.dialogs.cover_viewer = Some(CoverViewer {
+                    song_id: song.id,
+                    name: export::file_stem(&song.artist, &song.title),
+                    path,
+                    loading_full: full.is_some(),
+                });
+                self.ui.dialogs.cover_viewer_animation.start();
+                Some(full.unwrap_or_else(Task::none))
+            }
+
+            Message::CloseCoverViewer => {
+                self.ui.dialogs.cover_viewer_animation.stop();
+                self.ui.dialogs.cover_viewer = None;
+                Some(Task::none())
+            }
+
+            Message::FullCoverReady(song_id, path) => {
+                if let Some(viewer) = self
+                    .ui
+                    .dialogs
+                    .cover_viewer
+                    .as_mut()
+                    .filter(|viewer| viewer.song_id == *song_id)
+                {
+                    viewer.loading_full = false;
+                    if !path.is_empty() {
+                        viewer.path = path.clone();
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::SaveCoverImage => {
+                let Some(viewer) = &self.ui.dialogs.cover_viewer else {
+                    return Some(Task::none());
+                };
+                let extension = PathBuf::from(&viewer.path)
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "jpg".to_string());
+                let file_name = format!("{}.{}", viewer.name, extension);
+                Some(Task::perform(
+                    async move {
+                        rfd::AsyncFileDialog::new()
+                            .set_file_name(file_name)
+                            .add_filter("Image", &[extension.as_str()])
+                            .save_file()
+                            .await
+                            .map(|handle| handle.path().to_path_buf())
+                    },
+                    Message::CoverSavePicked,
+                ))
+            }
+
+            Message::CoverSavePicked(target) => {
+                let (Some(target), Some(viewer)) = (target.clone(), &self.ui.dialogs.cover_viewer)
+                else {
+                    return Some(Task::none());
+                };
+                let source = PathBuf::from(&viewer.path);
+                Some(Task::perform(
+                    async move {
+                        tokio::fs::copy(&source, &target)
+                            .await
+                            .map(|_| target)
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::CoverImageSaved,
+                ))
+            }
+
+            Message::CoverImageSaved(result) => {
+                let locale = self.core.locale;
+                Some(Task::done(match result {
+                    Ok(path) => {
+                        info!("Saved cover to {:?}", path);
+                        Message::ShowToast(
+                            locale
+                                .get(Key::CoverSaved)
+                                .replace("{}", &path.display().to_string()),
+                        )
+                    }
+                    Err(e) => {
+                        warn!("Failed to save cover: {}", e);
+                        Message::ShowErrorToast(locale.get(Key::CoverSaveFailed).replace("{}", e))
+                    }
+                }))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Download the original cover of an online or linked song, or `None`
+    /// when there is nothing better to fetch
+    fn fetch_full_cover(&self, song: &crate::database::DbSong) -> Option<Task<Message>> {
+        let ncm_id = self.ncm_id_of(song)?;
+        let song_id = song.id;
+        let stem = format!("cover_{}_full", ncm_id);
+        let cover_dir = crate::utils::covers_cache_dir();
+        if let Some(path) = crate::utils::find_cached_image(&cover_dir, &stem) {
+            return Some(Task::done(Message::FullCoverReady(
+                song_id,
+                path.to_string_lossy().to_string(),
+            )));
+        }
+        if self.core.is_metered() {
+            return None;
+        }
+        let client = self.core.ncm_client.clone()?;
+
+        Some(Task::perform(
+            Job::new(Category::Cover).song().run(async move {
+                let url = client
+                    .song_detail(&[ncm_id])
+                    .await
+                    .ok()?
+                    .first()
+                    .map(|s| s.pic_url.clone())?;
+                crate::utils::download_full_cover(&client, ncm_id, &url)
+                    .await
+                    .map(|path| path.to_string_lossy().to_string())
+            }),
+            // An empty path just clears the loading note
+            move |result| Message::FullCoverReady(song_id, result.flatten().unwrap_or_default()),
+        ))
+    }
+}
@@ -0,0 +1,90 @@
+//! Cover downloads for cards scrolled into view
+
+use iced::Task;
+
+use super::discover::download_playlist_cover;
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::jobs::{Category, Job};
+use crate::features::lazy_covers::CoverKey;
+
+impl App {
+    /// Handle lazy cover loading messages
+    pub fn handle_covers(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::CoverShown(key, url) => {
+                if self.has_cover(*key) {
+                    return Some(Task::none());
+                }
+                // Remembered even when metered, so it can be resumed later
+                if !self.ui.lazy_covers.show(*key, url.clone()) || self.core.is_metered() {
+                    return Some(Task::none());
+                }
+                Some(self.start_cover_download(*key, url.clone()))
+            }
+
+            Message::CoverHidden(key) => {
+                self.ui.lazy_covers.hide(*key);
+                Some(Task::none())
+            }
+
+            Message::LazyCoverLoaded(key, path) => {
+                self.ui.lazy_covers.finish(*key, path.is_some());
+                let Some(path) = path.clone() else {
+                    return Some(Task::none());
+                };
+                Some(Task::done(match *key {
+                    CoverKey::Playlist(id) => Message::DiscoverPlaylistCoverLoaded(id, path),
+                    CoverKey::Song(id) => Message::SongCoverLoaded(id, path),
+                }))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Download covers of visible cards that were held back or failed
+    pub(super) fn resume_lazy_covers(&mut self) -> Task<Message> {
+        let pending: Vec<_> = self
+            .ui
+            .lazy_covers
+            .pending()
+            .into_iter()
+            .filter(|(key, _)| !self.has_cover(*key))
+            .collect();
+        Task::batch(
+            pending
+                .into_iter()
+                .map(|(key, url)| self.start_cover_download(key, url))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn has_cover(&self, key: CoverKey) -> bool {
+        match key {
+            CoverKey::Playlist(id) => self.ui.discover.playlist_covers.contains_key(&id),
+            CoverKey::Song(id) => self.ui.home.song_covers.contains_key(&id),
+        }
+    }
+
+    fn start_cover_download(&mut self, key: CoverKey, url: String) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        let (download, handle) = Job::new(Category::Cover).run_cancellable(async move {
+            match key {
+                CoverKey::Playlist(id) => download_playlist_cover(&client, id, &url)
+                    .await
+                    .map(|(_, path)| path),
+                CoverKey::Song(id) => crate::utils::download_cover(&client, id, &url).await,
+            }
+        });
+        self.ui.lazy_covers.track(key, handle);
+
+        Task::perform(download, move |result| match result {
+            Some(path) => Message::LazyCoverLoaded(key, path),
+            // Scrolled away; the card was already forgotten
+            None => Message::NoOp,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+//! Crash recovery dialog handlers
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::crash;
+use crate::i18n::Key;
+
+impl App {
+    /// Close the crash dialog and forget the pending report
+    fn close_crash_dialog(&mut self) {
+        self.ui.dialogs.crash_report = None;
+        self.ui.dialogs.crash_animation.stop();
+    }
+
+    /// Record the current queue and position for the panic hook
+    pub fn record_crash_session(&self, position_secs: f64) {
+        crash::update_session(&self.library.queue, self.library.queue_index, position_secs);
+    }
+
+    /// Handle crash recovery messages
+    pub fn handle_crash(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::RestoreCrashedSession => {
+                let session = self
+                    .ui
+                    .dialogs
+                    .crash_report
+                    .as_ref()
+                    .and_then(|report| report.session.clone());
+                self.close_crash_dialog();
+
+                let (Some(session), Some(db)) = (session, self.core.db.clone()) else {
+                    return Some(Task::none());
+                };
+                tracing::info!(
+                    "Restoring crashed session: {} songs, index {:?}",
+                    session.queue.len(),
+                    session.queue_index
+                );
+
+                Some(Task::perform(
+                    async move {
+                        let index = session.queue_index.unwrap_or(0);
+                        let song_id = session.queue.get(index).map(|song| song.id);
+
+                        db.save_queue_with_songs(session.queue, None);
+                        db.flush().await.ok()?;
+                        db.update_playback_position(song_id, index as i64, session.position_secs)
+                            .await
+                            .ok()?;
+
+                        let state = db.get_playback_state().await.ok()?;
+                        let queue = db.get_queue().await.ok()?;
+                        Some((state, queue))
+                    },
+                    Message::CrashedSessionRestored,
+                ))
+            }
+
+            Message::CrashedSessionRestored(result) => {
+                let Some((state, queue)) = result else {
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::CrashRestoreFailed).to_string(),
+                    )));
+                };
+                self.library.playback_state = Some(state.clone());
+                Some(Task::done(Message::QueueRestored(queue.clone())))
+            }
+
+            Message::SubmitCrashReport => {
+                if let Some(report) = &self.ui.dialogs.crash_report {
+                    let url = crash::issue_url(&report.report);
+                    if let Err(e) = crate::platform::shell::open_url(&url) {
+                        tracing::warn!("Failed to open crash report URL: {}", e);
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::DismissCrashReport => {
+                self.close_crash_dialog();
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+}
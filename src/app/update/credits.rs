@@ -0,0 +1,70 @@
+//! Song credits on the lyrics page
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::{App, Route, SearchTab};
+use crate::database::DbSong;
+
+impl App {
+    /// Handle credits messages
+    pub fn handle_credits(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::ToggleSongCredits => {
+                self.ui.lyrics.credits_visible = !self.ui.lyrics.credits_visible;
+                if !self.ui.lyrics.credits_visible {
+                    return Some(Task::none());
+                }
+                let Some(song) = self.library.current_song.clone() else {
+                    return Some(Task::none());
+                };
+                if self.ui.lyrics.credits.as_ref().map(|(id, _)| *id) == Some(song.id) {
+                    return Some(Task::none());
+                }
+                Some(self.load_song_credits(&song))
+            }
+
+            Message::SongCreditsLoaded(song_id, credits) => {
+                if self.library.current_song.as_ref().map(|s| s.id) == Some(*song_id) {
+                    self.ui.lyrics.credits = Some((*song_id, credits.clone()));
+                }
+                Some(Task::none())
+            }
+
+            Message::OpenCreditArtist(name) => {
+                let route = Route::Search {
+                    keyword: name.clone(),
+                    tab: SearchTab::Songs,
+                    page: 0,
+                };
+                Some(self.navigate_to_route(route, true))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Fetch credits of an NCM-linked song; songs without a link resolve to
+    /// no credits straight away
+    pub(super) fn load_song_credits(&mut self, song: &DbSong) -> Task<Message> {
+        let song_id = song.id;
+        let (Some(ncm_id), Some(client)) = (self.ncm_id_of(song), self.core.ncm_client.clone())
+        else {
+            self.ui.lyrics.credits = Some((song_id, None));
+            return Task::none();
+        };
+        self.ui.lyrics.credits = None;
+        Task::perform(
+            async move {
+                match client.song_credits(ncm_id).await {
+                    Ok(credits) => Some(credits),
+                    Err(e) => {
+                        tracing::warn!("Failed to load credits for {}: {}", ncm_id, e);
+                        None
+                    }
+                }
+            },
+            move |credits| Message::SongCreditsLoaded(song_id, credits),
+        )
+    }
+}
@@ -4,13 +4,39 @@
 use iced::Task;
 
 use crate::app::helpers::{
-    load_playback_state, load_playlists, load_queue, load_songs, validate_songs,
+    load_blocklist, load_favorites, load_liked_mirror, load_ncm_links, load_playback_state,
+    load_playlists, load_queue, load_song_analyses, load_song_overrides, load_songs,
+    purge_expired_trash, validate_songs,
 };
 use crate::app::message::Message;
 use crate::app::state::App;
+use crate::features::song_overrides;
 use crate::ui::pages;
 
 impl App {
+    /// Load the restored song paused at its saved position, resuming right
+    /// away when resume-on-startup is enabled
+    fn load_restored_song(&mut self, path: std::path::PathBuf, position_secs: f64) {
+        let Some(player) = &self.core.audio else {
+            return;
+        };
+
+        player.play(path);
+        // Pause immediately and seek to saved position
+        player.pause();
+        let position = std::time::Duration::from_secs_f64(position_secs);
+        player.seek(position);
+        // Update cached position for UI display
+        player.update_paused_position(position);
+        tracing::info!("Loaded song and seeked to {:?}", position);
+
+        if self.core.settings.playback.resume_on_startup {
+            player.resume_with_fade(self.core.settings.playback.fade_in_out);
+            self.update_tray_state(true);
+            self.update_mpris_state();
+        }
+    }
+
     /// Handle database-related messages
     pub fn handle_database(&mut self, message: &Message) -> Option<Task<Message>> {
         match message {
@@ -22,6 +48,8 @@ impl App {
                 Some(Task::batch([
                     // Background validation - runs first to clean up invalid entries
                     Task::perform(validate_songs(db.clone()), Message::SongsValidated),
+                    // Trash older than the retention period goes for good
+                    Task::perform(purge_expired_trash(db.clone()), |_| Message::NoOp),
                     // Load data (will run in parallel, but validation is fast)
                     Task::perform(load_songs(db.clone()), Message::SongsLoaded),
                     Task::perform(load_playlists(db.clone()), Message::PlaylistsLoaded),
@@ -29,7 +57,17 @@ impl App {
                         Some(state) => Message::PlaybackStateLoaded(state),
                         None => Message::DatabaseError("No playback state".into()),
                     }),
+                    Task::perform(load_blocklist(db.clone()), Message::BlocklistLoaded),
+                    Task::perform(load_ncm_links(db.clone()), Message::NcmLinksLoaded),
+                    Task::perform(load_song_overrides(db.clone()), |_| {
+                        Message::SongOverridesLoaded
+                    }),
+                    Task::perform(load_song_analyses(db.clone()), Message::SongAnalysesLoaded),
+                    Task::perform(load_favorites(db.clone()), Message::FavoritesLoaded),
+                    Task::perform(load_liked_mirror(db.clone()), Message::LikedMirrorLoaded),
                     Task::perform(load_queue(db.clone()), Message::QueueRestored),
+                    self.check_new_releases(),
+                    self.record_app_version(db.clone()),
                 ]))
             }
 
@@ -44,7 +82,11 @@ impl App {
                     tracing::info!("Validated songs: {} invalid entries removed", removed_count);
                     // Reload songs after validation to get clean list
                     if let Some(db) = &self.core.db {
-                        return Some(Task::perform(load_songs(db.clone()), Message::SongsLoaded));
+                        return Some(Task::batch([
+                            Task::perform(load_songs(db.clone()), Message::SongsLoaded),
+                            // Removed songs may have been on playlist mosaics
+                            self.refresh_playlist_mosaics(),
+                        ]));
                     }
                 }
                 Some(Task::none())
@@ -53,12 +95,18 @@ impl App {
             Message::SongsLoaded(songs) => {
                 tracing::info!("Loaded {} songs from database", songs.len());
                 self.library.db_songs = songs.clone();
-                Some(Task::none())
+                // New songs from a scan get analyzed as they come in
+                Some(self.start_song_analysis())
             }
 
             Message::PlaylistsLoaded(playlists) => {
                 tracing::info!("Loaded {} playlists from database", playlists.len());
                 self.library.playlists = playlists.clone();
+                Some(self.refresh_playlist_mosaics())
+            }
+
+            Message::PlaylistMosaicsReady(mosaics) => {
+                self.library.playlist_mosaics = mosaics.clone();
                 Some(Task::none())
             }
 
@@ -78,6 +126,11 @@ impl App {
             Message::QueueRestored(queue) => {
                 tracing::info!("Restored {} songs in queue", queue.len());
                 self.library.queue = queue.clone();
+                // Stored NCM entries hold NCM's own titles
+                self.library
+                    .queue
+                    .iter_mut()
+                    .for_each(song_overrides::apply);
 
                 // Initialize shuffle cache for preloading (must be done before preload)
                 self.cache_shuffle_indices();
@@ -91,10 +144,7 @@ impl App {
                         if let Some(song) = self.library.queue.get(idx) {
                             self.library.current_song = Some(song.clone());
 
-                            // Check if this is an NCM song (negative ID or ncm:// path)
-                            let is_ncm = song.id < 0 || song.file_path.starts_with("ncm://");
-
-                            if is_ncm {
+                            if crate::features::song_source::is_ncm(song) {
                                 // NCM song - resolve and load just like local songs
                                 tracing::info!(
                                     "Restoring NCM song: {} - {}",
@@ -119,6 +169,7 @@ impl App {
                                                 event_tx,
                                             )
                                             .await
+                                            .ok()
                                         },
                                         move |result| {
                                             Message::SongResolvedForRestore(
@@ -133,19 +184,10 @@ impl App {
                                 }
                             } else {
                                 // Local song - load into audio player and trigger preload
-                                if let Some(player) = &self.core.audio {
-                                    let path_buf = std::path::PathBuf::from(&song.file_path);
-                                    if path_buf.exists() {
-                                        player.play(path_buf.clone());
-                                        // Pause immediately and seek to saved position
-                                        player.pause();
-                                        let position =
-                                            std::time::Duration::from_secs_f64(state.position_secs);
-                                        player.seek(position);
-                                        // Update cached position for UI display
-                                        player.update_paused_position(position);
-                                        tracing::info!("Loaded song and seeked to {:?}", position);
-                                    }
+                                let path_buf = std::path::PathBuf::from(&song.file_path);
+                                if path_buf.exists() {
+                                    let saved_position = state.position_secs;
+                                    self.load_restored_song(path_buf, saved_position);
                                 }
 
                                 // Trigger preload for adjacent tracks after queue is restored
@@ -178,16 +220,8 @@ impl App {
                         }
 
                         // Load into audio player
-                        if let Some(player) = &self.core.audio {
-                            let path_buf = std::path::PathBuf::from(&resolved.file_path);
-                            player.play(path_buf);
-                            // Pause immediately and seek to saved position
-                            player.pause();
-                            let position = std::time::Duration::from_secs_f64(*saved_position);
-                            player.seek(position);
-                            player.update_paused_position(position);
-                            tracing::info!("Loaded NCM song and seeked to {:?}", position);
-                        }
+                        let path_buf = std::path::PathBuf::from(&resolved.file_path);
+                        self.load_restored_song(path_buf, *saved_position);
 
                         // Trigger preload for adjacent tracks after NCM song is restored
                         let preload_task = self.preload_adjacent_tracks_with_ncm();
@@ -201,6 +235,8 @@ impl App {
 
             Message::RecentlyPlayedLoaded(songs) => {
                 tracing::info!("Loaded {} recently played songs", songs.len());
+                let mut songs = songs.clone();
+                songs.iter_mut().for_each(song_overrides::apply);
                 self.library.recently_played = songs.clone();
 
                 // Create a playlist view for recently played
@@ -224,20 +260,13 @@ impl App {
                                 .get(crate::i18n::Key::RecentlyPlayedList)
                                 .to_string(),
                             song.cover_path.clone(),
+                            crate::features::song_source::SongSource::of(song),
                         )
                     })
                     .collect();
 
                 // Calculate total duration
-                let total_secs: u64 = songs.iter().map(|s| s.duration_secs as u64).sum();
-                let total_mins = total_secs / 60;
-                let total_hours = total_mins / 60;
-                let remaining_mins = total_mins % 60;
-                let total_duration = if total_hours > 0 {
-                    format!("约 {} 小时 {} 分钟", total_hours, remaining_mins)
-                } else {
-                    format!("{} 分钟", total_mins)
-                };
+                let total_duration_secs: u64 = songs.iter().map(|s| s.duration_secs as u64).sum();
 
                 // Create playlist view with special ID for recently played
                 let playlist_view = pages::PlaylistView {
@@ -254,11 +283,15 @@ impl App {
                             .to_string(),
                     ),
                     cover_path: None,
-                    owner: "本地".to_string(),
+                    owner: self
+                        .core
+                        .locale
+                        .get(crate::i18n::Key::LocalPlaylistOwner)
+                        .to_string(),
                     owner_avatar_path: None,
                     creator_id: 0,
                     song_count: songs.len() as u32,
-                    total_duration,
+                    total_duration_secs,
                     like_count: String::new(),
                     songs: song_views,
                     palette: crate::utils::ColorPalette::default(), // Use default colors
@@ -266,9 +299,9 @@ impl App {
                     is_subscribed: false,
                 };
 
-                self.ui.playlist_page.current = Some(playlist_view);
+                self.ui.playlist_page.show(playlist_view);
 
-                Some(Task::none())
+                Some(self.playlist_shown())
             }
 
             _ => None,
@@ -1,16 +1,34 @@
 //! Discover page message handlers
 
+use futures_util::StreamExt;
 use iced::Task;
 use rand::SeedableRng;
 use rand::seq::SliceRandom;
+use std::collections::{BTreeSet, HashSet};
 use std::path::PathBuf;
-use tracing::{debug, error};
+use std::sync::Arc;
+use tracing::{debug, error, warn};
 
-use crate::api::SongList;
-use crate::app::message::Message;
+use crate::api::{AlbumRelease, ApiError, NcmClient, SongList};
+use crate::app::message::{Message, NewReleaseFeed};
 use crate::app::state::{App, Route};
+use crate::database::Database;
+use crate::features::jobs::{Category, Job};
 use crate::i18n::Key;
 
+use super::api_error::failure_message;
+
+/// How long an album stays in the new releases feed (ms)
+const NEW_RELEASE_WINDOW_MS: i64 = 60 * 24 * 60 * 60 * 1000;
+/// Latest albums looked at per followed artist
+const RELEASES_PER_ARTIST: u16 = 5;
+/// Artist album lookups in flight at once
+const RELEASE_LOOKUP_CONCURRENCY: usize = 4;
+/// Followed artists pulled from the NCM account
+const NCM_FOLLOW_LIMIT: u16 = 200;
+/// Above this many new albums, a single summary notification is shown
+const MAX_RELEASE_NOTIFICATIONS: usize = 3;
+
 /// Get a daily seed based on current date
 fn get_daily_seed() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -47,13 +65,9 @@ impl App {
                 self.ui.discover.recommended_playlists = all_playlists;
                 self.ui.discover.recommended_loading = false;
 
-                // Pre-populate covers from local cache (sync check) and request GPU allocations
-                let allocation_task = self.preload_cached_covers(playlists);
-
-                // Download missing covers asynchronously
-                let download_task = self.download_discover_covers(playlists);
-
-                Some(Task::batch([allocation_task, download_task]))
+                // Pre-populate covers from local cache (sync check) and request GPU allocations;
+                // missing ones are downloaded as their cards scroll into view
+                Some(self.preload_cached_covers(playlists))
             }
 
             Message::HotPlaylistsLoaded(playlists, has_more) => {
@@ -72,11 +86,16 @@ impl App {
                     shuffle_daily(&mut shuffled);
                     self.ui.discover.hot_playlists = shuffled;
                 } else {
-                    // Subsequent batches: append without shuffling (pagination)
-                    self.ui.discover.hot_playlists.extend(playlists.clone());
+                    // Subsequent batches: append without shuffling (pagination).
+                    // The hot ranking shifts between requests, so a playlist can
+                    // show up again on a later page
+                    let hot = &mut self.ui.discover.hot_playlists;
+                    let known: std::collections::HashSet<u64> = hot.iter().map(|p| p.id).collect();
+                    hot.extend(playlists.iter().filter(|p| !known.contains(&p.id)).cloned());
                 }
 
                 self.ui.discover.hot_loading = false;
+                self.ui.discover.hot_failed = false;
                 self.ui.discover.hot_has_more = *has_more;
 
                 // Update offset for next page
@@ -84,13 +103,16 @@ impl App {
                     self.ui.discover.hot_offset += playlists.len() as u16;
                 }
 
-                // Pre-populate covers from local cache (sync check) and request GPU allocations
-                let allocation_task = self.preload_cached_covers(playlists);
-
-                // Download missing covers asynchronously
-                let download_task = self.download_discover_covers(playlists);
+                // Pre-populate covers from local cache (sync check) and request GPU allocations;
+                // missing ones are downloaded as their cards scroll into view
+                Some(self.preload_cached_covers(playlists))
+            }
 
-                Some(Task::batch([allocation_task, download_task]))
+            Message::HotPlaylistsFailed => {
+                // Keep `hot_has_more` so the page can offer a retry
+                self.ui.discover.hot_loading = false;
+                self.ui.discover.hot_failed = true;
+                Some(Task::none())
             }
 
             Message::DiscoverPlaylistCoverLoaded(playlist_id, path) => {
@@ -170,26 +192,18 @@ impl App {
 
                     return Some(Task::perform(
                         async move {
-                            match client.client.song_list_detail(playlist_id).await {
-                                Ok(detail) => {
-                                    // Songs are already included in the detail
-                                    if detail.songs.is_empty() {
-                                        return None;
-                                    }
-                                    Some(detail.songs)
-                                }
-                                Err(e) => {
-                                    error!("Failed to get playlist detail: {}", e);
-                                    None
-                                }
-                            }
+                            // Songs are already included in the detail
+                            client
+                                .client
+                                .song_list_detail(playlist_id)
+                                .await
+                                .map(|detail| detail.songs)
+                                .inspect_err(|e| error!("Failed to get playlist detail: {}", e))
                         },
-                        move |songs_opt| {
-                            if let Some(songs) = songs_opt {
-                                Message::AddNcmPlaylist(songs, true)
-                            } else {
-                                Message::ShowToast(error_msg)
-                            }
+                        move |result| match result {
+                            Ok(songs) if !songs.is_empty() => Message::AddNcmPlaylist(songs, true),
+                            Ok(_) => Message::ShowToast(error_msg),
+                            Err(e) => failure_message(&e, &error_msg),
                         },
                     ));
                 }
@@ -202,6 +216,7 @@ impl App {
                 }
 
                 self.ui.discover.hot_loading = true;
+                self.ui.discover.hot_failed = false;
 
                 if let Some(client) = &self.core.ncm_client {
                     let client = client.clone();
@@ -217,17 +232,18 @@ impl App {
                             {
                                 Ok(playlists) => {
                                     let has_more = playlists.len() >= limit as usize;
-                                    (playlists, has_more)
+                                    Message::HotPlaylistsLoaded(playlists, has_more)
                                 }
                                 Err(e) => {
                                     error!("Failed to load more hot playlists: {}", e);
-                                    (Vec::new(), false)
+                                    Message::HotPlaylistsFailed
                                 }
                             }
                         },
-                        |(playlists, has_more)| Message::HotPlaylistsLoaded(playlists, has_more),
+                        |msg| msg,
                     ));
                 }
+                self.ui.discover.hot_loading = false;
                 Some(Task::none())
             }
 
@@ -236,6 +252,117 @@ impl App {
                 Some(self.navigate_to_route(route, true))
             }
 
+            Message::NewReleasesLoaded(feed) => {
+                self.library.followed_artists = feed.followed.iter().copied().collect();
+                self.ui.discover.new_releases = feed.releases.clone();
+                self.ui.discover.releases_checking = false;
+                if self.core.settings.system.release_notifications {
+                    self.notify_new_releases(&feed.fresh);
+                }
+                let recheck = if std::mem::take(&mut self.ui.discover.releases_recheck) {
+                    self.check_new_releases()
+                } else {
+                    Task::none()
+                };
+                Some(Task::batch([self.download_release_covers(), recheck]))
+            }
+
+            Message::NewReleaseCoverLoaded(album_id, path) => {
+                let handle = iced::widget::image::Handle::from_path(path);
+                self.ui.discover.release_covers.insert(*album_id, handle);
+                Some(Task::none())
+            }
+
+            Message::PlayNewRelease(album_id) => {
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::none());
+                };
+                let album_id = *album_id;
+                let error_msg = self
+                    .core
+                    .locale
+                    .get(Key::DiscoverPlaylistLoadFailed)
+                    .to_string();
+                Some(Task::perform(
+                    async move {
+                        client
+                            .client
+                            .album(album_id)
+                            .await
+                            .inspect_err(|e| error!("Failed to load album {}: {}", album_id, e))
+                    },
+                    move |result| match result {
+                        Ok(songs) if !songs.is_empty() => Message::AddNcmPlaylist(songs, true),
+                        Ok(_) => Message::ShowErrorToast(error_msg),
+                        Err(e) => failure_message(&e, &error_msg),
+                    },
+                ))
+            }
+
+            Message::ToggleFollowArtist(artist_id) => {
+                let Some(artist) = self
+                    .ui
+                    .search
+                    .albums
+                    .iter()
+                    .find(|a| a.id == *artist_id)
+                    .cloned()
+                else {
+                    return Some(Task::none());
+                };
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+
+                let follow = !self.library.followed_artists.contains(&artist.id);
+                let toast_key = if follow {
+                    self.library.followed_artists.insert(artist.id);
+                    Key::ArtistFollowed
+                } else {
+                    self.library.followed_artists.remove(&artist.id);
+                    Key::ArtistUnfollowed
+                };
+                let toast = self.core.locale.get(toast_key).replace("{}", &artist.name);
+
+                // Mirror the change on the NCM account so other clients see it
+                let client = self
+                    .core
+                    .ncm_client
+                    .clone()
+                    .filter(|_| self.core.is_logged_in);
+                let save = async move {
+                    let id = artist.id as i64;
+                    let saved = if follow {
+                        db.follow_artist(id, &artist.name, &artist.cover_img_url)
+                            .await
+                    } else {
+                        db.unfollow_artist(id).await
+                    };
+                    if let Err(e) = saved {
+                        error!("Failed to save followed artist {}: {}", id, e);
+                    }
+                    if let Some(client) = client {
+                        if let Err(e) = client.client.artist_subscribe(follow, artist.id).await {
+                            warn!("Failed to sync artist follow to NCM: {}", e);
+                            return ApiError::is_auth_expired(&e);
+                        }
+                    }
+                    false
+                };
+
+                Some(Task::batch([
+                    Task::done(Message::ShowToast(toast)),
+                    Task::perform(save, |expired| {
+                        if expired {
+                            Message::SessionExpired
+                        } else {
+                            Message::NoOp
+                        }
+                    })
+                    .chain(self.check_new_releases()),
+                ]))
+            }
+
             Message::SeeAllHot => {
                 let route = Route::Discover(crate::app::state::DiscoverViewMode::AllHot);
                 let needs_more = self.ui.discover.hot_playlists.len() < 30 && self.ui.discover.hot_has_more;
@@ -251,53 +378,132 @@ impl App {
         }
     }
 
-    /// Download covers for discover playlists
-    fn download_discover_covers(&self, playlists: &[SongList]) -> Task<Message> {
-        if let Some(client) = &self.core.ncm_client {
-            let mut tasks = Vec::new();
-            let covers_dir = crate::utils::covers_cache_dir();
+    /// Check followed artists for new albums
+    ///
+    /// Pulls the NCM follow list into the local one first when logged in.
+    pub(super) fn check_new_releases(&mut self) -> Task<Message> {
+        let (Some(db), Some(client)) = (self.core.db.clone(), self.core.ncm_client.clone()) else {
+            return Task::none();
+        };
+        // Overlapping checks would read the same release markers and
+        // announce the same albums twice
+        if self.ui.discover.releases_checking {
+            self.ui.discover.releases_recheck = true;
+            return Task::none();
+        }
+        self.ui.discover.releases_checking = true;
+        let logged_in = self.core.is_logged_in;
+        Task::perform(
+            fetch_new_releases(db, client, logged_in),
+            Message::NewReleasesLoaded,
+        )
+    }
 
-            for playlist in playlists.iter() {
-                // Skip if already in memory cache
-                if self.ui.discover.playlist_covers.contains_key(&playlist.id) {
-                    continue;
-                }
+    /// Announce albums that came out since the last check
+    fn notify_new_releases(&self, fresh: &[AlbumRelease]) {
+        let locale = &self.core.locale;
+        let notifications: Vec<(String, String)> = if fresh.len() > MAX_RELEASE_NOTIFICATIONS {
+            let artists: Vec<&str> = fresh
+                .iter()
+                .map(|a| a.artist_name.as_str())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            vec![(
+                locale.count(Key::NotifyNewReleases, fresh.len() as u64),
+                artists.join(", "),
+            )]
+        } else {
+            fresh
+                .iter()
+                .map(|a| {
+                    (
+                        locale
+                            .get(Key::NotifyNewRelease)
+                            .replace("{}", &a.artist_name),
+                        a.name.clone(),
+                    )
+                })
+                .collect()
+        };
+
+        for (title, body) in notifications {
+            if let Err(e) = crate::platform::notification::show(&title, &body) {
+                warn!("Failed to show release notification: {}", e);
+            }
+        }
+    }
 
-                // Skip if already cached on disk (will be loaded by preload_cached_covers)
-                let cover_path = covers_dir.join(format!("playlist_{}.jpg", playlist.id));
-                if cover_path.exists() {
-                    continue;
-                }
+    /// Download covers for the new releases feed
+    ///
+    /// On a metered connection only covers already on disk are loaded.
+    fn download_release_covers(&self) -> Task<Message> {
+        let Some(client) = &self.core.ncm_client else {
+            return Task::none();
+        };
+        let metered = self.core.is_metered();
+        let covers_dir = crate::utils::covers_cache_dir();
 
+        let tasks: Vec<_> = self
+            .ui
+            .discover
+            .new_releases
+            .iter()
+            .filter(|a| !self.ui.discover.release_covers.contains_key(&a.id))
+            .map(|album| (album, covers_dir.join(format!("album_{}.jpg", album.id))))
+            .filter(|(_, cover_path)| !metered || cover_path.exists())
+            .map(|(album, cover_path)| {
                 let client = client.clone();
-                let playlist_id = playlist.id;
-                let cover_url = playlist.cover_img_url.clone();
-
-                tasks.push(Task::perform(
-                    async move { download_playlist_cover(&client, playlist_id, &cover_url).await },
-                    |result| {
-                        if let Some((id, path)) = result {
-                            Message::DiscoverPlaylistCoverLoaded(id, path)
-                        } else {
-                            Message::NoOp
+                let album_id = album.id;
+                let cover_url = album.cover_img_url.clone();
+                let covers_dir = covers_dir.clone();
+                Task::perform(
+                    Job::new(Category::Cover).run(async move {
+                        if !cover_path.exists() {
+                            if let Err(e) = tokio::fs::create_dir_all(&covers_dir).await {
+                                error!("Failed to create covers cache dir: {}", e);
+                                return None;
+                            }
+                            if let Err(e) = client
+                                .client
+                                .download_file(&cover_url, cover_path.clone())
+                                .await
+                            {
+                                error!("Failed to download album cover {}: {}", album_id, e);
+                                return None;
+                            }
                         }
+                        Some((album_id, cover_path))
+                    }),
+                    |result| match result.flatten() {
+                        Some((id, path)) => Message::NewReleaseCoverLoaded(id, path),
+                        None => Message::NoOp,
                     },
-                ));
-            }
+                )
+            })
+            .collect();
+        Task::batch(tasks)
+    }
 
-            if tasks.is_empty() {
-                Task::none()
-            } else {
-                Task::batch(tasks)
-            }
-        } else {
-            Task::none()
-        }
+    /// Pin loaded covers in GPU memory again after a memory budget unpinned them
+    pub(super) fn pin_discover_covers(&self) -> Task<Message> {
+        let tasks: Vec<_> = self
+            .ui
+            .discover
+            .playlist_covers
+            .iter()
+            .filter(|(id, _)| !self.ui.discover.playlist_cover_allocations.contains_key(id))
+            .map(|(&playlist_id, handle)| {
+                iced::widget::image::allocate(handle.clone())
+                    .map(move |result| Message::DiscoverCoverAllocated(playlist_id, result))
+            })
+            .collect();
+        Task::batch(tasks)
     }
 
     /// Pre-populate covers from local disk cache (synchronous)
     /// Returns a task to allocate the images in GPU memory
-    fn preload_cached_covers(&mut self, playlists: &[SongList]) -> Task<Message> {
+    pub(super) fn preload_cached_covers(&mut self, playlists: &[SongList]) -> Task<Message> {
         let covers_dir = crate::utils::covers_cache_dir();
         let mut allocation_tasks = Vec::new();
 
@@ -333,8 +539,106 @@ impl App {
     }
 }
 
+/// Sync the follow list and look up each followed artist's latest albums
+async fn fetch_new_releases(
+    db: Arc<Database>,
+    client: NcmClient,
+    logged_in: bool,
+) -> NewReleaseFeed {
+    if logged_in {
+        match client.client.artist_sublist(0, NCM_FOLLOW_LIMIT).await {
+            Ok(artists) => {
+                for artist in artists {
+                    if let Err(e) = db
+                        .follow_artist(artist.id as i64, &artist.name, &artist.pic_url)
+                        .await
+                    {
+                        error!("Failed to save followed artist {}: {}", artist.id, e);
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to load followed artists from NCM: {}", e),
+        }
+    }
+
+    let artists = match db.get_followed_artists().await {
+        Ok(artists) => artists,
+        Err(e) => {
+            error!("Failed to load followed artists: {}", e);
+            return NewReleaseFeed::default();
+        }
+    };
+    let followed = artists.iter().map(|a| a.artist_id as u64).collect();
+
+    let api = &client.client;
+    let lookups: Vec<_> = futures_util::stream::iter(artists)
+        .map(|artist| async move {
+            let albums = api
+                .artist_albums(artist.artist_id as u64, RELEASES_PER_ARTIST)
+                .await;
+            (artist, albums)
+        })
+        .buffer_unordered(RELEASE_LOOKUP_CONCURRENCY)
+        .collect()
+        .await;
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let mut releases = Vec::new();
+    let mut fresh = Vec::new();
+    for (artist, albums) in lookups {
+        let albums = match albums {
+            Ok(albums) => albums,
+            Err(e) => {
+                warn!(
+                    "Failed to load albums of artist {}: {}",
+                    artist.artist_id, e
+                );
+                continue;
+            }
+        };
+        let Some(newest) = albums.first().map(|a| a.publish_time) else {
+            continue;
+        };
+
+        // The first check only records where the artist is, so following
+        // someone doesn't announce their back catalogue
+        if artist.last_release_at > 0 {
+            fresh.extend(
+                albums
+                    .iter()
+                    .filter(|a| a.publish_time > artist.last_release_at)
+                    .cloned(),
+            );
+        }
+        if newest > artist.last_release_at {
+            if let Err(e) = db.set_artist_last_release(artist.artist_id, newest).await {
+                error!("Failed to save release marker: {}", e);
+            }
+        }
+
+        releases.extend(
+            albums
+                .into_iter()
+                .filter(|a| now_ms - a.publish_time <= NEW_RELEASE_WINDOW_MS),
+        );
+    }
+
+    // Collaborations show up once per followed artist
+    let mut seen = HashSet::new();
+    releases.retain(|a| seen.insert(a.id));
+    releases.sort_by(|a, b| b.publish_time.cmp(&a.publish_time));
+    let mut seen = HashSet::new();
+    fresh.retain(|a| seen.insert(a.id));
+
+    NewReleaseFeed {
+        followed,
+        releases,
+        fresh,
+    }
+}
+
 /// Download and cache a playlist cover image
-async fn download_playlist_cover(
+pub(super) async fn download_playlist_cover(
     client: &crate::api::NcmClient,
     playlist_id: u64,
     cover_url: &str,
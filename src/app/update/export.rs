@@ -0,0 +1,276 @@
+//! Song export handlers
+//!
+//! Songs are exported one at a time from a queue, so a long playlist doesn't
+//! start dozens of ffmpeg processes at once and can be cancelled between or
+//! during songs.
+
+use iced::Task;
+use tracing::{info, warn};
+
+use super::ncm::queue_song_from_ncm;
+use crate::app::message::Message;
+use crate::app::state::{App, ExportDialog};
+use crate::features::export::{self, ExportItem};
+use crate::features::jobs::{Category, Job};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle the export dialog and queue
+    pub fn handle_export(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::ExportPlaylist(id) => {
+                let id = *id;
+                let Some(name) = self
+                    .ui
+                    .playlist_page
+                    .current
+                    .as_ref()
+                    .filter(|p| p.id == id)
+                    .map(|p| p.name.clone())
+                else {
+                    return Some(Task::none());
+                };
+
+                // Same sources as playing the playlist
+                if id == -1 {
+                    let songs = self.library.recently_played.clone();
+                    return Some(Task::done(Message::ExportSongsLoaded(name, songs)));
+                }
+                if id <= 0 {
+                    let songs = self
+                        .ui
+                        .home
+                        .current_ncm_playlist_songs
+                        .iter()
+                        .map(queue_song_from_ncm)
+                        .collect();
+                    return Some(Task::done(Message::ExportSongsLoaded(name, songs)));
+                }
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                Some(Task::perform(
+                    async move { db.get_playlist_songs(id).await.unwrap_or_default() },
+                    move |songs| Message::ExportSongsLoaded(name, songs),
+                ))
+            }
+
+            Message::ExportSongsLoaded(name, songs) => {
+                if songs.is_empty() {
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::ExportNothing).to_string(),
+                    )));
+                }
+                self.ui.dialogs.export = Some(ExportDialog {
+                    name: name.clone(),
+                    songs: songs.clone(),
+                    settings: self.core.settings.storage.export.clone(),
+                });
+                self.ui.dialogs.export_animation.start();
+                Some(Task::none())
+            }
+
+            Message::ExportFormatChanged(format) => {
+                if let Some(dialog) = &mut self.ui.dialogs.export {
+                    dialog.settings.format = *format;
+                }
+                Some(Task::none())
+            }
+
+            Message::ExportBitrateChanged(kbps) => {
+                if let Some(dialog) = &mut self.ui.dialogs.export {
+                    dialog.settings.bitrate_kbps = *kbps;
+                }
+                Some(Task::none())
+            }
+
+            Message::PickExportFolder => Some(Task::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .pick_folder()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                },
+                Message::ExportFolderPicked,
+            )),
+
+            Message::ExportFolderPicked(path) => {
+                if let (Some(path), Some(dialog)) = (path, &mut self.ui.dialogs.export) {
+                    dialog.settings.folder = Some(path.to_string_lossy().to_string());
+                }
+                Some(Task::none())
+            }
+
+            Message::StartExport => {
+                let Some(dialog) = self.close_export_dialog() else {
+                    return Some(Task::none());
+                };
+                info!(
+                    "Exporting {} songs of {} as {:?}",
+                    dialog.songs.len(),
+                    dialog.name,
+                    dialog.settings.format
+                );
+                let export = &mut self.ui.export;
+                if !export.is_running() {
+                    export.exported = 0;
+                    export.skipped = 0;
+                    export.failed = 0;
+                    export.total = 0;
+                    export.error = None;
+                }
+                export.total += dialog.songs.len();
+                export.queue.extend(
+                    dialog
+                        .songs
+                        .into_iter()
+                        .map(|song| (song, dialog.settings.clone())),
+                );
+                let start = if export.current.is_none() {
+                    Task::done(Message::ExportNext)
+                } else {
+                    Task::none()
+                };
+
+                self.core.settings.storage.export = dialog.settings;
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    start,
+                ]))
+            }
+
+            Message::CloseExport => {
+                self.close_export_dialog();
+                Some(Task::none())
+            }
+
+            Message::ExportNext => Some(self.export_next()),
+
+            Message::ExportSongDone(generation, result) => {
+                if *generation != self.ui.export.generation {
+                    return Some(Task::none());
+                }
+                let export = &mut self.ui.export;
+                let title = export.current.take().unwrap_or_default();
+                export.task = None;
+                match result {
+                    Ok(Some(path)) => {
+                        export.exported += 1;
+                        export.folder = path.parent().map(|dir| dir.to_path_buf());
+                    }
+                    Ok(None) => export.skipped += 1,
+                    Err(e) => {
+                        warn!("Failed to export {}: {}", title, e);
+                        export.failed += 1;
+                        export.error.get_or_insert_with(|| e.clone());
+                    }
+                }
+                Some(self.export_next())
+            }
+
+            Message::CancelExport => {
+                let export = &mut self.ui.export;
+                info!(
+                    "Export cancelled with {} songs left",
+                    export.queue.len() + usize::from(export.current.is_some())
+                );
+                export.generation += 1;
+                export.queue.clear();
+                export.current = None;
+                // Dropping the handle kills ffmpeg and removes the partial file
+                export.task = None;
+                Some(self.finish_export())
+            }
+
+            Message::OpenExportFolder => {
+                let dir = self
+                    .ui
+                    .export
+                    .folder
+                    .clone()
+                    .unwrap_or_else(|| self.core.settings.storage.export.dir());
+                if let Err(e) = std::fs::create_dir_all(&dir)
+                    .and_then(|_| crate::platform::shell::open_path(&dir))
+                {
+                    warn!("Failed to open export folder {:?}: {}", dir, e);
+                }
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    fn close_export_dialog(&mut self) -> Option<ExportDialog> {
+        self.ui.dialogs.export_animation.stop();
+        self.ui.dialogs.export.take()
+    }
+
+    /// Start exporting the next queued song, or sum up once none are left
+    fn export_next(&mut self) -> Task<Message> {
+        let export = &mut self.ui.export;
+        if export.current.is_some() {
+            return Task::none();
+        }
+        let Some((song, settings)) = export.queue.pop_front() else {
+            return self.finish_export();
+        };
+        export.current = Some(song.title.clone());
+
+        let generation = export.generation;
+        let (task, handle) = Task::perform(
+            async move {
+                let Some(item) = Job::new(Category::Disk)
+                    .run_blocking(move || ExportItem::prepare(&song))
+                    .await
+                    .flatten()
+                else {
+                    return Ok(None);
+                };
+                export::export(
+                    item,
+                    settings.format,
+                    settings.bitrate_kbps,
+                    &settings.dir(),
+                )
+                .await
+                .map(Some)
+                .map_err(|e| format!("{:#}", e))
+            },
+            move |result| Message::ExportSongDone(generation, result),
+        )
+        .abortable();
+        export.task = Some(handle.abort_on_drop());
+        task
+    }
+
+    /// Toast what the finished or cancelled export did
+    fn finish_export(&mut self) -> Task<Message> {
+        let export = &self.ui.export;
+        if export.total == 0 {
+            return Task::none();
+        }
+        let locale = self.core.locale;
+        let mut summary = locale
+            .get(Key::ExportFinished)
+            .replacen("{}", &export.exported.to_string(), 1)
+            .replacen("{}", &export.total.to_string(), 1);
+        if export.skipped > 0 {
+            summary.push_str(" · ");
+            summary.push_str(&locale.count(Key::ExportSkipped, export.skipped as u64));
+        }
+        let toast = match &export.error {
+            Some(e) => Message::ShowErrorToast(format!("{} · {}", summary, e)),
+            None => Message::ShowToast(summary),
+        };
+        let exported = export.exported > 0;
+        self.ui.export.total = 0;
+
+        let toast = self.update(toast);
+        if exported {
+            self.ui.toast_action =
+                Some((locale.get(Key::ExportOpenFolder), Message::OpenExportFolder));
+        }
+        toast
+    }
+}
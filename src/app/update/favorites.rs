@@ -0,0 +1,400 @@
+//! Favorite song handlers
+//!
+//! Hearts work the same for local, cached and online songs; NCM songs are
+//! also liked on NCM, see `features::favorites` for how the two are synced.
+//! The liked songs playlist is mirrored in the database and kept up to date
+//! with NCM in the background.
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use iced::Task;
+use tracing::{debug, error, warn};
+
+use super::ncm::{PLAYLIST_PAGE_SIZE, total_duration_secs};
+use crate::api::{ApiError, NcmClient, SongInfo, SongList};
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::app::update::page_loader::{self, PlaylistLoadState};
+use crate::audio::UiSound;
+use crate::database::{Database, DbLikedMirror, SongId};
+use crate::features::favorites::{Favorite, LikedMirror, LikedSync, favorite_key};
+use crate::features::hooks::HookEvent;
+use crate::features::playlist_sync::{TrackDiff, arrange};
+
+impl App {
+    /// Handle favorite toggles and the sync with NCM likes
+    pub fn handle_favorites(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::FavoritesLoaded(favorites) => {
+                self.library.favorites = favorites.clone();
+                self.library.favorites_loaded = true;
+                self.refresh_tray_state();
+                // A login that finished first left the sync for now
+                Some(Task::done(Message::SyncFavorites))
+            }
+
+            Message::ToggleFavorite(song_id) => {
+                let key = favorite_key(*song_id, &self.library.ncm_links);
+                let favorite = Favorite {
+                    liked: !self.library.favorites.is_liked(key),
+                    updated_at: now_secs(),
+                };
+                self.library.favorites.set(key, favorite);
+                if favorite.liked {
+                    self.play_ui_sound(UiSound::Like);
+                }
+                self.refresh_tray_state();
+
+                let mut tasks = vec![self.save_favorites(vec![(key, favorite)])];
+                if let Some(ncm_id) = key.ncm_id() {
+                    tasks.push(self.push_likes(vec![(ncm_id, favorite.liked)]));
+                }
+                let event = if favorite.liked {
+                    HookEvent::Like
+                } else {
+                    HookEvent::Unlike
+                };
+                tasks.push(self.fire_hooks(event, self.loaded_song(*song_id)));
+                Some(Task::batch(tasks))
+            }
+
+            Message::SyncFavorites => {
+                if !self.library.favorites_loaded
+                    || self.library.favorites_syncing
+                    || !self.core.is_logged_in
+                {
+                    return Some(Task::none());
+                }
+                let (Some(client), Some(uid)) = (
+                    self.core.ncm_client.clone(),
+                    self.core.user_info.as_ref().map(|user| user.user_id),
+                ) else {
+                    return Some(Task::none());
+                };
+                self.library.favorites_syncing = true;
+
+                Some(Task::perform(
+                    async move {
+                        client
+                            .client
+                            .user_song_id_list(uid)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::NcmLikesFetched,
+                ))
+            }
+
+            Message::NcmLikesFetched(result) => {
+                self.library.favorites_syncing = false;
+                let likes = match result {
+                    Ok(likes) => likes,
+                    Err(e) => {
+                        error!("Failed to fetch NCM likes: {}", e);
+                        return Some(Task::none());
+                    }
+                };
+
+                let plan = self
+                    .library
+                    .favorites
+                    .plan_sync(&likes.ids, likes.check_point);
+                debug!(
+                    "Favorites sync: {} pushed to NCM, {} taken from NCM",
+                    plan.push.len(),
+                    plan.adopt.len()
+                );
+                for (key, favorite) in &plan.adopt {
+                    self.library.favorites.set(*key, *favorite);
+                }
+                if !plan.adopt.is_empty() {
+                    self.refresh_tray_state();
+                }
+
+                Some(Task::batch([
+                    self.save_favorites(plan.adopt),
+                    self.push_likes(plan.push),
+                ]))
+            }
+
+            Message::LikedMirrorLoaded(mirror) => {
+                // A sync that finished first has the newer copy
+                if self.library.liked_mirror.is_some() {
+                    return Some(Task::none());
+                }
+                let user_id = self.core.user_info.as_ref().map(|user| user.user_id);
+                self.library.liked_mirror = mirror
+                    .clone()
+                    .filter(|m| user_id.is_none_or(|id| id == m.user_id));
+                self.show_liked_playlist_offline();
+                Some(Task::none())
+            }
+
+            Message::SyncLikedMirror => {
+                if !self.core.is_logged_in {
+                    return Some(Task::none());
+                }
+                if self.library.liked_sync == Some(LikedSync::Syncing) {
+                    self.library.liked_resync = true;
+                    return Some(Task::none());
+                }
+                let (Some(client), Some(user_id)) = (
+                    self.core.ncm_client.clone(),
+                    self.core.user_info.as_ref().map(|user| user.user_id),
+                ) else {
+                    return Some(Task::none());
+                };
+                // NCM always lists the liked songs playlist first
+                let Some(playlist) = self.ui.home.user_playlists.first().cloned() else {
+                    return Some(Task::none());
+                };
+                let kept = self
+                    .library
+                    .liked_mirror
+                    .as_ref()
+                    .filter(|m| m.user_id == user_id && m.playlist.id == playlist.id)
+                    .map(|m| m.songs.clone())
+                    .unwrap_or_default();
+                self.library.liked_sync = Some(LikedSync::Syncing);
+
+                Some(Task::perform(
+                    sync_liked_mirror(client, self.core.db.clone(), user_id, playlist, kept),
+                    Message::LikedMirrorSynced,
+                ))
+            }
+
+            Message::LikedMirrorSynced(result) => {
+                let mut tasks = Vec::new();
+                if std::mem::take(&mut self.library.liked_resync) {
+                    tasks.push(Task::done(Message::SyncLikedMirror));
+                }
+                let (mirror, added) = match result {
+                    Ok(synced) => synced.clone(),
+                    Err(e) => {
+                        warn!("Failed to sync liked songs: {}", e);
+                        self.library.liked_sync = Some(LikedSync::Offline);
+                        return Some(Task::batch(tasks));
+                    }
+                };
+                self.library.liked_sync = Some(LikedSync::Synced);
+
+                let track_ids = mirror.track_ids();
+                let changed = self
+                    .library
+                    .liked_mirror
+                    .as_ref()
+                    .is_none_or(|previous| previous.track_ids() != track_ids);
+                let playlist_id = mirror.playlist.id;
+                self.library.liked_mirror = Some(mirror);
+
+                // Patch the playlist on screen once it's fully there; one still
+                // loading gets the latest tracks anyway
+                if changed
+                    && self.is_viewing_ncm_playlist(playlist_id)
+                    && matches!(self.ui.playlist_page.load_state, PlaylistLoadState::Ready)
+                {
+                    let internal_id = -(playlist_id as i64);
+                    let locale = self.core.locale;
+                    tasks.push(Task::perform(
+                        page_loader::load_ncm_song_views(added.clone(), 0, locale),
+                        move |views| {
+                            Message::NcmPlaylistTracksSynced(internal_id, track_ids, added, views)
+                        },
+                    ));
+                }
+                Some(Task::batch(tasks))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Whether a song shows as liked
+    pub fn is_favorite(&self, song_id: SongId) -> bool {
+        self.library
+            .favorites
+            .is_liked(favorite_key(song_id, &self.library.ncm_links))
+    }
+
+    /// Show the saved liked songs playlist in place of loading it from NCM
+    ///
+    /// Returns `None` when `playlist_id` isn't the mirrored playlist.
+    pub(super) fn show_liked_mirror(&mut self, playlist_id: u64) -> Option<Task<Message>> {
+        let mirror = self
+            .library
+            .liked_mirror
+            .as_ref()
+            .filter(|m| m.playlist.id == playlist_id)?;
+        debug!("Showing saved liked songs playlist {}", playlist_id);
+
+        let internal_id = -(playlist_id as i64);
+        let songs = mirror.songs.clone();
+        let count = songs.len();
+        if let Some(playlist) = &mut self.ui.playlist_page.current {
+            playlist.creator_id = mirror.user_id;
+            playlist.song_count = count as u32;
+            playlist.total_duration_secs = total_duration_secs(&songs);
+        }
+        self.ui.home.current_ncm_playlist_songs.clear();
+
+        let locale = self.core.locale;
+        Some(Task::batch([
+            Task::perform(
+                page_loader::load_ncm_song_views(songs.clone(), 0, locale),
+                move |views| Message::NcmPlaylistSongsPage(internal_id, songs, views, count, count),
+            ),
+            Task::done(Message::SyncLikedMirror),
+        ]))
+    }
+
+    /// List the saved liked songs playlist while NCM's list isn't there,
+    /// e.g. when starting offline
+    pub(super) fn show_liked_playlist_offline(&mut self) {
+        if !self.ui.home.user_playlists.is_empty() {
+            return;
+        }
+        if let Some(mirror) = &self.library.liked_mirror {
+            self.ui.home.user_playlists.push(mirror.playlist.clone());
+        }
+    }
+
+    /// Move a local song's favorite to the NCM track it was just linked to,
+    /// unless the track has a favorite of its own
+    pub(super) fn carry_favorite_to_ncm(&mut self, song_id: SongId, ncm_id: u64) -> Task<Message> {
+        let key = SongId::ncm(ncm_id);
+        if !self.library.favorites.is_liked(song_id) || self.library.favorites.is_liked(key) {
+            return Task::none();
+        }
+        let favorite = Favorite {
+            liked: true,
+            updated_at: now_secs(),
+        };
+        self.library.favorites.set(key, favorite);
+        Task::batch([
+            self.save_favorites(vec![(key, favorite)]),
+            self.push_likes(vec![(ncm_id, true)]),
+        ])
+    }
+
+    fn save_favorites(&self, favorites: Vec<(SongId, Favorite)>) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        if favorites.is_empty() {
+            return Task::none();
+        }
+        Task::perform(
+            async move {
+                for (key, favorite) in favorites {
+                    if let Err(e) = db
+                        .set_favorite(key, favorite.liked, favorite.updated_at)
+                        .await
+                    {
+                        error!("Failed to save favorite {}: {}", key, e);
+                    }
+                }
+            },
+            |_| Message::NoOp,
+        )
+    }
+
+    /// Like or unlike NCM tracks on NCM; failures are left for the next sync
+    fn push_likes(&self, likes: Vec<(u64, bool)>) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        if likes.is_empty() || !self.core.is_logged_in {
+            return Task::none();
+        }
+        Task::perform(
+            async move {
+                for (ncm_id, liked) in likes {
+                    if let Err(e) = client.client.like_song(ncm_id, liked).await {
+                        if ApiError::is_auth_expired(&e) {
+                            return Message::SessionExpired;
+                        }
+                        warn!("Failed to update like of {} on NCM: {}", ncm_id, e);
+                    }
+                }
+                // The liked songs playlist changed with them
+                Message::SyncLikedMirror
+            },
+            |msg| msg,
+        )
+    }
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Fetch the liked songs playlist's track IDs and details of only the songs
+/// the saved copy lacks, then save the result
+async fn sync_liked_mirror(
+    client: NcmClient,
+    db: Option<Arc<Database>>,
+    user_id: u64,
+    playlist: SongList,
+    kept: Vec<SongInfo>,
+) -> Result<(LikedMirror, Vec<SongInfo>), String> {
+    let track_ids = client
+        .client
+        .playlist_track_ids(playlist.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    // An empty list is more likely a bad response than every like undone
+    if track_ids.is_empty() && !kept.is_empty() {
+        return Err("Empty liked songs list".to_string());
+    }
+
+    let kept_ids: Vec<u64> = kept.iter().map(|song| song.id).collect();
+    let diff = TrackDiff::between(&kept_ids, &track_ids);
+    debug!(
+        "Liked songs changed: {} added, {} removed",
+        diff.added.len(),
+        diff.removed
+    );
+    let mut added = Vec::new();
+    for chunk in diff.added.chunks(PLAYLIST_PAGE_SIZE) {
+        // A copy with holes would pass for complete, so stop at the first failure
+        let songs = client
+            .client
+            .song_detail(chunk)
+            .await
+            .map_err(|e| e.to_string())?;
+        added.extend(songs);
+    }
+
+    let songs = arrange(kept, added.clone(), &track_ids, |song| song.id);
+    let mirror = LikedMirror {
+        user_id,
+        playlist,
+        songs,
+    };
+
+    if let Some(db) = db {
+        let saved = match serde_json::to_string(&mirror.songs) {
+            Ok(songs) => db
+                .save_liked_mirror(&DbLikedMirror {
+                    user_id: user_id as i64,
+                    playlist_id: mirror.playlist.id as i64,
+                    name: mirror.playlist.name.clone(),
+                    cover_img_url: mirror.playlist.cover_img_url.clone(),
+                    author: mirror.playlist.author.clone(),
+                    songs,
+                    synced_at: now_secs(),
+                })
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = saved {
+            error!("Failed to save liked songs mirror: {}", e);
+        }
+    }
+    Ok((mirror, added))
+}
@@ -0,0 +1,203 @@
+//! Homepage sections: per-section loading and layout customization
+
+use iced::Task;
+use tracing::error;
+
+use crate::api::SongList;
+use crate::app::{App, Message};
+use crate::database::SongId;
+use crate::features::home_layout::HomeSection;
+
+/// Songs shown in the recently played section
+const RECENT_SONGS_LIMIT: i64 = 8;
+
+impl App {
+    /// Handle homepage layout messages
+    pub fn handle_home(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::ToggleHomeCustomize => {
+                self.ui.home.customizing = !self.ui.home.customizing;
+                Some(Task::none())
+            }
+
+            Message::ToggleHomeSection(section) => {
+                let shown = self.core.settings.display.home_layout.toggle(*section);
+                let save = Task::perform(async { Message::SaveSettings }, |m| m);
+                if shown {
+                    Some(Task::batch([save, self.load_home_section(*section)]))
+                } else {
+                    Some(save)
+                }
+            }
+
+            Message::MoveHomeSection(section, offset) => {
+                self.core
+                    .settings
+                    .display
+                    .home_layout
+                    .move_section(*section, *offset);
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::HomeToplistsLoaded(toplists) => {
+                self.ui.home.toplists = toplists.clone();
+                Some(self.preload_cached_covers(toplists))
+            }
+
+            Message::DailySongsLoaded(songs) => {
+                let blocklist = &self.library.blocklist;
+                self.ui.home.daily_songs = songs
+                    .iter()
+                    .filter(|song| !blocklist.blocks(SongId::ncm(song.id), &song.singer))
+                    .cloned()
+                    .collect();
+                Some(Task::none())
+            }
+
+            Message::HomeRecentLoaded(songs) => {
+                self.ui.home.recent_songs = songs.clone();
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Fetch the data behind one homepage section
+    pub(super) fn load_home_section(&mut self, section: HomeSection) -> Task<Message> {
+        match section {
+            HomeSection::Banners => self.load_home_banners(),
+            HomeSection::Trending => self.load_home_trending(),
+            HomeSection::TopPicks => self.load_home_top_picks(),
+            HomeSection::Toplists => self.load_home_toplists(),
+            HomeSection::DailyRecommend => self.load_home_daily_songs(),
+            HomeSection::RecentlyPlayed => self.refresh_home_recent(),
+        }
+    }
+
+    /// Reload the recently played section, if it is shown
+    pub(super) fn refresh_home_recent(&self) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        if !self
+            .core
+            .settings
+            .display
+            .home_layout
+            .is_enabled(HomeSection::RecentlyPlayed)
+        {
+            return Task::none();
+        }
+        Task::perform(
+            async move {
+                db.get_recently_played(RECENT_SONGS_LIMIT)
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!("Failed to load recently played: {}", e);
+                        Vec::new()
+                    })
+            },
+            Message::HomeRecentLoaded,
+        )
+    }
+
+    fn load_home_banners(&self) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                client.client.banners().await.unwrap_or_else(|e| {
+                    error!("Failed to load banners: {:?}", e);
+                    Vec::new()
+                })
+            },
+            Message::BannersLoaded,
+        )
+    }
+
+    fn load_home_trending(&self) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                const TRENDING_CHART_ID: u64 = 19723756;
+                match client.client.song_list_detail(TRENDING_CHART_ID).await {
+                    Ok(detail) => detail.songs,
+                    Err(e) => {
+                        error!("Failed to load trending songs: {:?}", e);
+                        Vec::new()
+                    }
+                }
+            },
+            Message::TrendingSongsLoaded,
+        )
+    }
+
+    fn load_home_top_picks(&self) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                client
+                    .client
+                    .top_song_list("全部", "hot", 0, 8)
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!("Failed to load top picks: {:?}", e);
+                        Vec::new()
+                    })
+            },
+            Message::TopPicksLoaded,
+        )
+    }
+
+    fn load_home_toplists(&self) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                match client.client.toplist().await {
+                    // Shown as playlist cards; a chart is a playlist under the hood
+                    Ok(toplists) => toplists
+                        .into_iter()
+                        .take(8)
+                        .map(|t| SongList {
+                            id: t.id,
+                            name: t.name,
+                            cover_img_url: t.cover,
+                            author: t.update,
+                        })
+                        .collect(),
+                    Err(e) => {
+                        error!("Failed to load toplists: {:?}", e);
+                        Vec::new()
+                    }
+                }
+            },
+            Message::HomeToplistsLoaded,
+        )
+    }
+
+    fn load_home_daily_songs(&self) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        if !self.core.is_logged_in {
+            return Task::none();
+        }
+        Task::perform(
+            async move {
+                client.client.recommend_songs().await.unwrap_or_else(|e| {
+                    error!("Failed to load daily recommendations: {:?}", e);
+                    Vec::new()
+                })
+            },
+            Message::DailySongsLoaded,
+        )
+    }
+}
@@ -0,0 +1,148 @@
+//! Event hook handlers
+//!
+//! Playback hooks are found by comparing the current song and play state
+//! before and after each message, so every path that changes track or pauses
+//! is covered without each one remembering to fire them.
+
+use iced::Task;
+
+use crate::app::{App, Message};
+use crate::database::{DbSong, SongId};
+use crate::features::hooks::{self, EventHook, HookEvent, HookVars};
+use crate::i18n::Key;
+
+/// Current song and whether it is playing
+pub(super) type PlaybackSnapshot = (Option<SongId>, bool);
+
+impl App {
+    /// Handle event hook settings messages
+    pub fn handle_hooks(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::AddEventHook => {
+                self.core.settings.hooks.push(EventHook::default());
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::RemoveEventHook(index) => {
+                if *index < self.core.settings.hooks.len() {
+                    self.core.settings.hooks.remove(*index);
+                }
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::UpdateEventHook(index, hook) => {
+                if let Some(existing) = self.core.settings.hooks.get_mut(*index) {
+                    *existing = hook.clone();
+                }
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::TestEventHook(index) => {
+                let Some(hook) = self.core.settings.hooks.get(*index).cloned() else {
+                    return Some(Task::none());
+                };
+                let vars = HookVars::new(hook.event, self.library.current_song.as_ref());
+                let proxy = self.core.settings.network.proxy_url();
+                Some(Task::perform(
+                    async move {
+                        hooks::run(&hook, &vars, proxy.as_deref())
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::EventHookTested,
+                ))
+            }
+
+            Message::EventHookTested(result) => {
+                let toast = match result {
+                    Ok(()) => self.core.locale.get(Key::SettingsHookTestDone).to_string(),
+                    Err(e) => self
+                        .core
+                        .locale
+                        .get(Key::SettingsHookTestFailed)
+                        .replace("{}", e),
+                };
+                Some(Task::done(Message::ShowToast(toast)))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// A song that is playing, queued or in the library
+    pub(super) fn loaded_song(&self, song_id: SongId) -> Option<&DbSong> {
+        self.library
+            .current_song
+            .iter()
+            .chain(&self.library.queue)
+            .chain(&self.library.db_songs)
+            .find(|song| song.id == song_id)
+    }
+
+    pub(super) fn playback_snapshot(&self) -> PlaybackSnapshot {
+        let playing = self
+            .core
+            .audio
+            .as_ref()
+            .is_some_and(|audio| audio.is_playing());
+        (self.library.current_song.as_ref().map(|s| s.id), playing)
+    }
+
+    /// Run hooks for a track change, play or pause since `before`
+    pub(super) fn fire_playback_hooks(
+        &self,
+        before: PlaybackSnapshot,
+        task: Task<Message>,
+    ) -> Task<Message> {
+        if self.core.settings.hooks.is_empty() {
+            return task;
+        }
+        let (song_id, playing) = self.playback_snapshot();
+        let event = if song_id != before.0 {
+            song_id.map(|_| HookEvent::TrackChange)
+        } else if playing != before.1 && song_id.is_some() {
+            Some(if playing {
+                HookEvent::Play
+            } else {
+                HookEvent::Pause
+            })
+        } else {
+            None
+        };
+        match event {
+            Some(event) => Task::batch([
+                task,
+                self.fire_hooks(event, self.library.current_song.as_ref()),
+            ]),
+            None => task,
+        }
+    }
+
+    /// Run every enabled hook for `event` in the background
+    pub(super) fn fire_hooks(&self, event: HookEvent, song: Option<&DbSong>) -> Task<Message> {
+        let matching: Vec<EventHook> = self
+            .core
+            .settings
+            .hooks
+            .iter()
+            .filter(|hook| hook.enabled && hook.event == event)
+            .cloned()
+            .collect();
+        if matching.is_empty() {
+            return Task::none();
+        }
+        let vars = HookVars::new(event, song);
+        let proxy = self.core.settings.network.proxy_url();
+
+        Task::perform(
+            async move {
+                for hook in matching {
+                    if let Err(e) = hooks::run(&hook, &vars, proxy.as_deref()).await {
+                        tracing::warn!("{} hook failed: {:#}", event.name(), e);
+                    }
+                }
+            },
+            |_| Message::NoOp,
+        )
+    }
+}
@@ -10,6 +10,8 @@ use crate::app::state::App;
 use crate::features::import::{
     ScanConfig, ScanHandle, ScanProgress, ScanState, progress_channel, scan_and_import,
 };
+use crate::features::jobs::{Category, Job};
+use crate::i18n::Key;
 use crate::ui::components::ImportingPlaylist;
 use crate::ui::widgets::Toast;
 
@@ -62,13 +64,13 @@ impl App {
                     }
                     WatchEvent::FileDeleted(path) => {
                         tracing::info!("File deleted: {:?}", path);
-                        // Update database to mark song as unavailable or remove it
+                        // Move the song to the trash; importing the file again restores it
                         if let Some(db) = &self.core.db {
                             let db = db.clone();
                             let path_str = path.to_string_lossy().to_string();
-                            tokio::spawn(async move {
-                                if let Err(e) = db.delete_song_by_path(&path_str).await {
-                                    tracing::error!("Failed to remove deleted song from db: {}", e);
+                            Job::new(Category::Database).spawn(async move {
+                                if let Err(e) = db.trash_song_by_path(&path_str).await {
+                                    tracing::error!("Failed to trash deleted song: {}", e);
                                 }
                             });
                         }
@@ -84,7 +86,7 @@ impl App {
                             let db = db.clone();
                             let old_path = old.to_string_lossy().to_string();
                             let new_path = new.to_string_lossy().to_string();
-                            tokio::spawn(async move {
+                            Job::new(Category::Database).spawn(async move {
                                 if let Err(e) = db.update_song_path(&old_path, &new_path).await {
                                     tracing::error!("Failed to update renamed song path: {}", e);
                                 }
@@ -126,6 +128,7 @@ impl App {
 
             Message::HideToast => {
                 self.ui.toast_visible = false;
+                self.ui.toast_action = None;
                 if let Some(playlist) = &self.ui.importing_playlist {
                     if playlist.completed {
                         return Some(Task::perform(
@@ -154,7 +157,7 @@ impl App {
             let folder_name = path
                 .file_name()
                 .and_then(|n| n.to_str())
-                .unwrap_or("导入的歌单")
+                .unwrap_or(self.core.locale.get(Key::ImportDefaultPlaylistName))
                 .to_string();
             self.ui.importing_playlist = Some(ImportingPlaylist::new(folder_name));
 
@@ -167,10 +170,13 @@ impl App {
             self.library.scan_handle = Some(handle);
 
             let (tx, mut rx) = progress_channel();
-            let config = ScanConfig::default();
+            let config = ScanConfig {
+                metadata_plugins: Some(self.core.plugins.clone()),
+                ..Default::default()
+            };
             let path_clone = path.clone();
 
-            tokio::spawn(async move {
+            Job::new(Category::Scan).spawn(async move {
                 if let Err(e) = scan_and_import(db, path_clone, config, cache, state, tx).await {
                     tracing::error!("Scan error: {}", e);
                 }
@@ -245,13 +251,20 @@ impl App {
                 let is_success = *imported > 0 || *skipped > 0;
 
                 let total_processed = *imported + *skipped + *errors;
+                let locale = self.core.locale;
                 let toast = if total_processed == 0 {
                     self.ui.importing_playlist = None;
-                    Toast::error("导入失败：未找到任何音频文件")
+                    Toast::error(locale.get(Key::ImportNoAudioFiles))
                 } else if *errors == 0 {
-                    Toast::success(format!("导入完成！成功导入 {} 首歌曲", imported))
+                    Toast::success(locale.count(Key::ImportSucceeded, *imported))
                 } else {
-                    Toast::warning(format!("导入完成：{} 首成功，{} 首失败", imported, errors))
+                    // Imported count first, failed count second
+                    Toast::warning(
+                        locale
+                            .get(Key::ImportPartiallyFailed)
+                            .replacen("{}", &imported.to_string(), 1)
+                            .replacen("{}", &errors.to_string(), 1),
+                    )
                 };
                 self.ui.toast = Some(toast);
                 self.ui.toast_visible = true;
@@ -312,7 +325,9 @@ impl App {
                 self.library.scan_handle = None;
                 self.ui.importing_playlist = None;
 
-                self.ui.toast = Some(Toast::error("导入已取消".to_string()));
+                self.ui.toast = Some(Toast::error(
+                    self.core.locale.get(Key::ImportCancelled).to_string(),
+                ));
                 self.ui.toast_visible = true;
                 return Task::perform(
                     async {
@@ -323,7 +338,9 @@ impl App {
             }
             ScanProgress::Error(e) => {
                 tracing::error!("Scan error: {}", e);
-                self.ui.toast = Some(Toast::error(format!("导入失败：{}", e)));
+                self.ui.toast = Some(Toast::error(
+                    self.core.locale.get(Key::ImportFailed).replace("{}", e),
+                ));
                 self.ui.toast_visible = true;
             }
             _ => {}
@@ -45,18 +45,14 @@ impl App {
                 return self.update(Message::PrevSong);
             }
             Action::VolumeUp => {
-                if let Some(player) = &self.core.audio {
-                    let current = player.get_info().volume;
-                    player.set_volume((current + 0.05).min(1.0));
-                }
+                return self.step_volume(1);
             }
             Action::VolumeDown => {
-                if let Some(player) = &self.core.audio {
-                    let current = player.get_info().volume;
-                    player.set_volume((current - 0.05).max(0.0));
-                }
+                return self.step_volume(-1);
             }
             Action::VolumeMute => {
+                // Muting ends a schedule's fade-in like any volume change
+                self.ui.schedule_ramp = None;
                 if let Some(player) = &self.core.audio {
                     let current = player.get_info().volume;
                     if current > 0.0 {
@@ -72,22 +68,10 @@ impl App {
                 }
             }
             Action::SeekForward => {
-                if let Some(player) = &self.core.audio {
-                    let info = player.get_info();
-                    let new_pos = info.position + std::time::Duration::from_secs(10);
-                    if new_pos < info.duration {
-                        player.seek(new_pos);
-                    }
-                }
+                self.skip_by(self.core.settings.playback.seek_step_secs as i64);
             }
             Action::SeekBackward => {
-                if let Some(player) = &self.core.audio {
-                    let info = player.get_info();
-                    let new_pos = info
-                        .position
-                        .saturating_sub(std::time::Duration::from_secs(10));
-                    player.seek(new_pos);
-                }
+                self.skip_by(-(self.core.settings.playback.seek_step_secs as i64));
             }
             Action::GoHome => {
                 return self.navigate_to_route(Route::Home, true);
@@ -97,7 +81,7 @@ impl App {
                 tracing::info!("Go to search - not yet implemented");
             }
             Action::ToggleQueue => {
-                self.ui.queue_visible = !self.ui.queue_visible;
+                return self.update(Message::ToggleQueue);
             }
             Action::ToggleFullscreen => {
                 // Toggle window fullscreen mode
@@ -107,7 +91,10 @@ impl App {
                 } else {
                     iced::window::Mode::Windowed
                 };
-                return iced::window::latest().and_then(move |id| iced::window::set_mode(id, mode));
+                return iced::window::oldest().and_then(move |id| iced::window::set_mode(id, mode));
+            }
+            Action::ToggleMemoryOverlay => {
+                return self.update(Message::ToggleMemoryOverlay);
             }
         }
         Task::none()
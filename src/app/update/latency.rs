@@ -0,0 +1,86 @@
+//! Output latency compensation and its tap-along calibration
+
+use std::time::{Duration, Instant};
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::audio::UiSound;
+use crate::features::latency::{Calibration, MAX_LATENCY_MS};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle output latency messages
+    pub fn handle_latency(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::UpdateOutputLatency(latency_ms) => {
+                self.core
+                    .settings
+                    .set_output_latency_ms((*latency_ms).min(MAX_LATENCY_MS));
+                self.apply_output_latency();
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::StartLatencyCalibration => {
+                self.ui.latency_calibration = Some(Calibration::default());
+                Some(Task::none())
+            }
+
+            Message::LatencyCalibrationBeat => {
+                let Some(calibration) = &mut self.ui.latency_calibration else {
+                    return Some(Task::none());
+                };
+                // Clicks skip the UI sounds setting: calibration needs them
+                if let Some(player) = &self.core.audio {
+                    player.play_ui_sound(UiSound::Click);
+                }
+                calibration.beat(Instant::now());
+                Some(Task::none())
+            }
+
+            Message::LatencyCalibrationTap => {
+                if let Some(calibration) = &mut self.ui.latency_calibration {
+                    calibration.tap(Instant::now());
+                }
+                Some(Task::none())
+            }
+
+            Message::FinishLatencyCalibration => {
+                let Some(calibration) = self.ui.latency_calibration.take() else {
+                    return Some(Task::none());
+                };
+                let locale = self.core.locale;
+                let Some(latency_ms) = calibration.estimate_ms() else {
+                    return Some(Task::done(Message::ShowToast(
+                        locale.get(Key::LatencyCalibrationTooFewTaps).to_string(),
+                    )));
+                };
+                tracing::info!("Calibrated output latency: {}ms", latency_ms);
+                Some(Task::batch([
+                    Task::done(Message::UpdateOutputLatency(latency_ms)),
+                    Task::done(Message::ShowToast(
+                        locale
+                            .get(Key::LatencyCalibrationDone)
+                            .replace("{}", &latency_ms.to_string()),
+                    )),
+                ]))
+            }
+
+            Message::CancelLatencyCalibration => {
+                self.ui.latency_calibration = None;
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Tell the player the latency stored for the current output device
+    pub(super) fn apply_output_latency(&self) {
+        if let Some(player) = &self.core.audio {
+            let latency_ms = self.core.settings.output_latency_ms();
+            player.set_output_latency(Duration::from_millis(latency_ms as u64));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Listen mode handlers
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use iced::Task;
+use iced::time::Instant;
+use tracing::{info, warn};
+
+use crate::app::message::Message;
+use crate::app::state::{App, HeardSong};
+use crate::database::{Database, DbSong};
+use crate::features::jobs::{Category, Job};
+use crate::features::listen::{self, ListenIndex, ListenMatch, capture};
+use crate::i18n::Key;
+
+/// Wait before checking that the same song is still playing
+const RECHECK_AFTER: Duration = Duration::from_secs(20);
+
+/// Wait before listening again after hearing nothing known
+const RETRY_AFTER: Duration = Duration::from_secs(4);
+
+/// Lookups in a row that find nothing before the song is taken to be over
+const MISSES_BEFORE_CLEAR: u32 = 2;
+
+impl App {
+    /// Handle listen mode messages
+    pub fn handle_listen(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::UpdateListenPermission(allowed) => {
+                self.core.settings.system.listen_mode = *allowed;
+                if !*allowed {
+                    self.stop_listening();
+                }
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::ToggleListenMode => {
+                if self.ui.listen.active {
+                    self.stop_listening();
+                    return Some(Task::none());
+                }
+                Some(self.start_listening())
+            }
+
+            Message::ListenIndexStarted(total) => {
+                self.ui.listen.indexing = Some((0, *total));
+                Some(Task::none())
+            }
+
+            Message::ListenSongIndexed => {
+                if let Some((done, _)) = &mut self.ui.listen.indexing {
+                    *done += 1;
+                }
+                Some(Task::none())
+            }
+
+            Message::ListenIndexReady(generation, index) => {
+                if !self.is_listening(*generation) {
+                    return Some(Task::none());
+                }
+                self.ui.listen.indexing = None;
+                self.ui.listen.indexing_task = None;
+                info!("Listen mode knows {} songs", index.song_count());
+                if index.song_count() == 0 {
+                    self.stop_listening();
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::ListenNothingToMatch).to_string(),
+                    )));
+                }
+                self.ui.listen.index = Some(index.clone());
+                Some(Task::done(Message::ListenCapture(*generation)))
+            }
+
+            Message::ListenCapture(generation) => {
+                if !self.is_listening(*generation) {
+                    return Some(Task::none());
+                }
+                // Only Rustle itself would be heard
+                if self.core.audio.as_ref().is_some_and(|p| p.is_playing()) {
+                    return Some(self.listen_again(RETRY_AFTER));
+                }
+                let Some(index) = self.ui.listen.index.clone() else {
+                    return Some(Task::none());
+                };
+                let generation = *generation;
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || listen_once(&index))
+                            .await
+                            .map_err(|e| e.to_string())
+                            .and_then(|result| result.map_err(|e| e.to_string()))
+                    },
+                    move |result| Message::ListenHeard(generation, result),
+                ))
+            }
+
+            Message::ListenHeard(generation, result) => {
+                if !self.is_listening(*generation) {
+                    return Some(Task::none());
+                }
+                match result {
+                    Ok(Some(heard)) => {
+                        self.ui.listen.misses = 0;
+                        let lyrics = self.hear(heard);
+                        Some(Task::batch([lyrics, self.listen_again(RECHECK_AFTER)]))
+                    }
+                    Ok(None) => {
+                        self.ui.listen.misses += 1;
+                        if self.ui.listen.misses >= MISSES_BEFORE_CLEAR {
+                            self.ui.listen.heard = None;
+                        }
+                        Some(self.listen_again(RETRY_AFTER))
+                    }
+                    Err(e) => {
+                        warn!("Listen mode could not record: {}", e);
+                        self.stop_listening();
+                        Some(Task::done(Message::ShowToast(
+                            self.core
+                                .locale
+                                .get(Key::ListenCaptureFailed)
+                                .replace("{}", e),
+                        )))
+                    }
+                }
+            }
+
+            Message::ListenLyricsLoaded(song_id, lines) => {
+                if let Some(heard) = &mut self.ui.listen.heard
+                    && heard.song.id == *song_id
+                {
+                    heard.lyrics = lines.clone();
+                }
+                Some(Task::none())
+            }
+
+            Message::ListenTick => Some(Task::none()),
+
+            _ => None,
+        }
+    }
+
+    /// Start listen mode, fingerprinting new library songs first
+    fn start_listening(&mut self) -> Task<Message> {
+        if !self.core.settings.system.listen_mode {
+            return Task::done(Message::ShowToast(
+                self.core.locale.get(Key::ListenNeedsPermission).to_string(),
+            ));
+        }
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+
+        let listen = &mut self.ui.listen;
+        listen.active = true;
+        listen.generation += 1;
+        listen.misses = 0;
+        let (task, handle) = Task::run(build_index(db, listen.generation), |msg| msg).abortable();
+        listen.indexing_task = Some(handle.abort_on_drop());
+        task
+    }
+
+    fn stop_listening(&mut self) {
+        let listen = &mut self.ui.listen;
+        listen.active = false;
+        listen.generation += 1;
+        // Dropping the handle aborts fingerprinting
+        listen.indexing_task = None;
+        listen.indexing = None;
+        listen.index = None;
+        listen.heard = None;
+    }
+
+    fn is_listening(&self, generation: u64) -> bool {
+        self.ui.listen.active && self.ui.listen.generation == generation
+    }
+
+    /// Record the next clip after `delay`
+    fn listen_again(&self, delay: Duration) -> Task<Message> {
+        let generation = self.ui.listen.generation;
+        Task::perform(tokio::time::sleep(delay), move |_| {
+            Message::ListenCapture(generation)
+        })
+    }
+
+    /// Follow a recognised song, loading its lyrics when it is a new one
+    fn hear(&mut self, heard: &ListenMatch) -> Task<Message> {
+        if let Some(current) = &mut self.ui.listen.heard
+            && current.song.id == heard.song_id
+        {
+            current.position = heard.position;
+            current.at = Instant::now();
+            return Task::none();
+        }
+
+        let Some(song) = self
+            .library
+            .db_songs
+            .iter()
+            .find(|song| song.id == heard.song_id)
+            .cloned()
+        else {
+            return Task::none();
+        };
+        info!("Listen mode heard {} - {}", song.artist, song.title);
+        let lyrics = self.load_heard_lyrics(&song);
+        self.ui.listen.heard = Some(HeardSong {
+            song,
+            position: heard.position,
+            at: Instant::now(),
+            lyrics: Vec::new(),
+        });
+        lyrics
+    }
+
+    /// Lyrics of a heard song from its file, the cache or NCM
+    fn load_heard_lyrics(&self, song: &DbSong) -> Task<Message> {
+        let song_id = song.id;
+        let path = PathBuf::from(&song.file_path);
+        let ncm_id = self.ncm_id_of(song);
+        let client = self.core.ncm_client.clone();
+
+        Task::perform(
+            async move {
+                let stored = Job::new(Category::Disk)
+                    .run_blocking(move || {
+                        crate::features::media::lyrics::find_lyrics(&path)
+                            .map(crate::features::media::lyrics::to_ui_lyric_lines)
+                            .or_else(|| {
+                                ncm_id
+                                    .and_then(crate::features::lyrics::load_cached_lyrics)
+                                    .map(crate::features::lyrics::to_ui_lyrics)
+                            })
+                    })
+                    .await
+                    .flatten();
+                if let Some(lines) = stored {
+                    return lines;
+                }
+
+                let (Some(client), Some(ncm_id)) = (client, ncm_id) else {
+                    return Vec::new();
+                };
+                crate::features::lyrics::fetch_lyrics(&client, ncm_id)
+                    .await
+                    .map(crate::features::lyrics::to_ui_lyrics)
+                    .unwrap_or_default()
+            },
+            move |lines| Message::ListenLyricsLoaded(song_id, lines),
+        )
+    }
+}
+
+/// Record a clip and look it up
+///
+/// The position of a match is moved on from where the clip started to
+/// where the song has got to once the lookup is done.
+fn listen_once(index: &ListenIndex) -> anyhow::Result<Option<ListenMatch>> {
+    let clip = capture::record(listen::CLIP_SECS)?;
+    let recorded = Instant::now();
+    let heard = index.identify(&listen::fingerprint_clip(&clip));
+    Ok(heard.map(|mut heard| {
+        heard.position += Duration::from_secs(listen::CLIP_SECS.into()) + recorded.elapsed();
+        heard
+    }))
+}
+
+/// Fingerprint library songs that haven't been yet, then load the index
+///
+/// Songs that fail to decode are stored without words so they aren't tried
+/// again every time listen mode starts.
+fn build_index(db: Arc<Database>, generation: u64) -> impl futures_util::Stream<Item = Message> {
+    async_stream::stream! {
+        let songs = match db.get_unfingerprinted_songs().await {
+            Ok(songs) => songs,
+            Err(e) => {
+                warn!("Failed to list songs to fingerprint: {}", e);
+                Vec::new()
+            }
+        };
+        if !songs.is_empty() {
+            info!("Fingerprinting {} songs for listen mode", songs.len());
+            yield Message::ListenIndexStarted(songs.len());
+        }
+
+        for song in songs {
+            let Some(row_id) = song.id.library_id() else {
+                continue;
+            };
+            let path = PathBuf::from(&song.file_path);
+            let result = Job::new(Category::Disk)
+                .prefetch()
+                .run_blocking(move || listen::fingerprint_file(&path))
+                .await;
+            let words = match result {
+                Some(Ok(words)) => words,
+                Some(Err(e)) => {
+                    warn!("Failed to fingerprint {}: {}", song.file_path, e);
+                    Vec::new()
+                }
+                None => {
+                    warn!("Fingerprinting {} panicked", song.file_path);
+                    Vec::new()
+                }
+            };
+            if let Err(e) = db.save_song_fingerprint(row_id, &words).await {
+                warn!("Failed to save fingerprint of song {}: {}", song.id, e);
+            }
+            yield Message::ListenSongIndexed;
+        }
+
+        let rows = match db.get_song_fingerprints().await {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!("Failed to load song fingerprints: {}", e);
+                Vec::new()
+            }
+        };
+        let index = Job::new(Category::Disk)
+            .run_blocking(move || {
+                ListenIndex::build(rows.iter().map(|row| (row.song_id, row.words.as_slice())))
+            })
+            .await
+            .unwrap_or_default();
+        yield Message::ListenIndexReady(generation, Arc::new(index));
+    }
+}
@@ -0,0 +1,85 @@
+//! Log viewer and diagnostics export handlers
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::logs::LogLevelFilter;
+use crate::features::{diagnostics, logs};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle log viewer and diagnostics messages
+    pub fn handle_logs(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::RefreshLogs => {
+                self.ui.logs.records = logs::snapshot();
+                Some(Task::none())
+            }
+
+            Message::SetLogLevelFilter(level) => {
+                self.ui.logs.level = *level;
+                logs::set_debug(*level == LogLevelFilter::Debug);
+                Some(Task::none())
+            }
+
+            Message::ExportDiagnostics => {
+                if self.ui.logs.exporting {
+                    return Some(Task::none());
+                }
+                Some(Task::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_file_name(diagnostics::default_file_name())
+                            .add_filter("Text", &["txt"])
+                            .save_file()
+                            .await
+                            .map(|handle| handle.path().to_path_buf())
+                    },
+                    Message::DiagnosticsPathPicked,
+                ))
+            }
+
+            Message::DiagnosticsPathPicked(path) => {
+                let Some(path) = path.clone() else {
+                    return Some(Task::none());
+                };
+                self.ui.logs.exporting = true;
+                let settings = self.core.settings.clone();
+
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            diagnostics::export_bundle(&path, &settings)
+                                .map(|_| path)
+                                .map_err(|e| e.to_string())
+                        })
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()))
+                    },
+                    Message::DiagnosticsExported,
+                ))
+            }
+
+            Message::DiagnosticsExported(result) => {
+                self.ui.logs.exporting = false;
+                let locale = self.core.locale;
+                let toast = match result {
+                    Ok(path) => {
+                        tracing::info!("Diagnostic bundle written to {:?}", path);
+                        locale
+                            .get(Key::LogsExported)
+                            .replace("{}", &path.display().to_string())
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to export diagnostic bundle: {}", e);
+                        locale.get(Key::LogsExportFailed).replace("{}", e)
+                    }
+                };
+                Some(Task::done(Message::ShowToast(toast)))
+            }
+
+            _ => None,
+        }
+    }
+}
@@ -9,7 +9,12 @@
 use iced::Task;
 
 use crate::app::message::Message;
-use crate::app::state::App;
+use crate::app::state::{App, LyricsWindow};
+use crate::database::{DbSong, SongId};
+use crate::features::LyricsSubLines;
+use crate::features::jobs::{Category, Job};
+use crate::features::lyrics::engine::{LyricLineData, LyricsEngineConfig, WordData};
+use crate::features::media::palette;
 use crate::ui::effects::background::color_to_array;
 
 impl App {
@@ -17,34 +22,72 @@ impl App {
     pub fn handle_lyrics(&mut self, message: &Message) -> Option<Task<Message>> {
         match message {
             Message::OpenLyricsPage => {
+                // The page lives in its own window while that is open
+                if let Some(window) = self.ui.lyrics.window {
+                    return Some(iced::window::gain_focus(window.id));
+                }
                 // Only open if there's a song playing
                 if let Some(song) = self.library.current_song.clone() {
                     self.ui.lyrics.is_open = true;
                     self.ui.lyrics.animation.start();
-
-                    // 智能加载歌词：
-                    // 检查当前歌词是否属于当前歌曲
-                    // 注意：loading_song_id 表示"正在加载或已加载的歌曲ID"
-                    // 如果 loading_song_id 不匹配当前歌曲，需要重新加载
-                    let lyrics_need_load = self.ui.lyrics.loading_song_id != Some(song.id);
-
-                    if lyrics_need_load {
-                        tracing::debug!("Loading lyrics for song: {} (id={})", song.title, song.id);
-                        // Use async loading
-                        return Some(self.load_lyrics_async(&song));
-                    } else {
-                        tracing::debug!(
-                            "Lyrics already loaded for song: {} (id={})",
-                            song.title,
-                            song.id
-                        );
-                        // Still need to update background if cover changed
-                        return Some(self.update_background_async(&song));
-                    }
+                    self.ui.lyrics.redraw_requested = true;
+                    self.refresh_tray_state();
+                    return Some(self.prepare_lyrics_page(&song));
                 }
                 Some(Task::none())
             }
 
+            Message::OpenLyricsWindow => {
+                if let Some(window) = self.ui.lyrics.window {
+                    return Some(iced::window::gain_focus(window.id));
+                }
+                let settings = crate::platform::window::lyrics_window_settings();
+                let size = settings.size;
+                let (id, open_window) = iced::window::open(settings);
+                tracing::info!("Opening lyrics window with id: {:?}", id);
+                self.ui.lyrics.window = Some(LyricsWindow {
+                    id,
+                    fullscreen: false,
+                });
+
+                // The page moves out of the main window instead of sliding away
+                self.ui.lyrics.is_open = false;
+                self.ui.lyrics.animation = Default::default();
+                self.set_lyrics_viewport(size);
+                self.refresh_tray_state();
+
+                let lyrics_task = match self.library.current_song.clone() {
+                    Some(song) => self.prepare_lyrics_page(&song),
+                    None => Task::none(),
+                };
+                Some(Task::batch([open_window.discard(), lyrics_task]))
+            }
+
+            Message::CloseLyricsWindow => {
+                let Some(window) = self.ui.lyrics.window.take() else {
+                    return Some(Task::none());
+                };
+                tracing::info!("Closing lyrics window");
+                self.set_lyrics_viewport(iced::Size::new(
+                    self.ui.window_width,
+                    self.ui.window_height,
+                ));
+                Some(iced::window::close(window.id))
+            }
+
+            Message::ToggleLyricsWindowFullscreen => {
+                let Some(window) = &mut self.ui.lyrics.window else {
+                    return Some(Task::none());
+                };
+                window.fullscreen = !window.fullscreen;
+                let mode = if window.fullscreen {
+                    iced::window::Mode::Fullscreen
+                } else {
+                    iced::window::Mode::Windowed
+                };
+                Some(iced::window::set_mode(window.id, mode))
+            }
+
             Message::CloseLyricsPage => {
                 // Start close animation, actual close happens when animation completes
                 self.ui.lyrics.animation.stop();
@@ -56,18 +99,20 @@ impl App {
                 Some(Task::none())
             }
 
-            Message::WindowResized(size) => {
-                self.ui.lyrics.viewport_width = (size.width * 0.6 - 60.0).max(100.0);
-                self.ui.lyrics.viewport_height = size.height;
-
-                if let Some(engine_cell) = &self.ui.lyrics.engine {
-                    let mut engine = engine_cell.borrow_mut();
-                    engine
-                        .line_animations_mut()
-                        .set_viewport_height(size.height);
+            Message::ResumeLyricsAutoScroll => {
+                self.resume_lyrics_auto_scroll();
+                Some(Task::none())
+            }
 
-                    // Force re-layout by invalidating cached dimensions
-                    engine.invalidate_layout();
+            Message::WindowResized(id, size) => {
+                if self.is_lyrics_window(*id) {
+                    self.set_lyrics_viewport(*size);
+                    return Some(Task::none());
+                }
+                self.ui.window_width = size.width;
+                self.ui.window_height = size.height;
+                if self.ui.lyrics.window.is_none() {
+                    self.set_lyrics_viewport(*size);
                 }
 
                 // Update discover page content width
@@ -89,7 +134,7 @@ impl App {
                 if self.ui.lyrics.engine.is_none() {
                     self.ui.lyrics.engine = Some(std::cell::RefCell::new(
                         crate::features::lyrics::engine::LyricsEngine::new_with_font_system(
-                            crate::features::lyrics::engine::LyricsEngineConfig::default(),
+                            self.lyrics_engine_config(),
                             font_system.clone(),
                         ),
                     ));
@@ -107,12 +152,17 @@ impl App {
                     return Some(Task::none());
                 }
 
+                let sub_lines_task = if self.ui.lyrics.loading_song_id == Some(song_id) {
+                    Task::none()
+                } else {
+                    self.load_lyrics_sub_lines(song_id)
+                };
                 self.ui.lyrics.loading_song_id = Some(song_id);
                 self.ui.lyrics.is_loading = true;
 
                 if let Some(client) = self.core.ncm_client.clone() {
-                    Some(Task::perform(
-                        async move {
+                    let lyrics_task = Task::perform(
+                        Job::new(Category::Lyrics).song().run(async move {
                             match crate::features::lyrics::fetch_lyrics(&client, ncm_id).await {
                                 Ok(lines) => {
                                     let ui_lines = crate::features::lyrics::to_ui_lyrics(lines);
@@ -120,12 +170,14 @@ impl App {
                                 }
                                 Err(e) => Message::LyricsLoadFailed(song_id, e.to_string()),
                             }
-                        },
-                        |msg| msg,
-                    ))
+                        }),
+                        // Superseded by a newer song
+                        |msg| msg.unwrap_or(Message::Noop),
+                    );
+                    Some(Task::batch([sub_lines_task, lyrics_task]))
                 } else {
                     self.ui.lyrics.is_loading = false;
-                    Some(Task::none())
+                    Some(sub_lines_task)
                 }
             }
 
@@ -140,64 +192,10 @@ impl App {
                     );
 
                     // Trigger async engine line preparation
-                    let lines_for_task = lines.clone();
-                    let song_id = *song_id;
-                    return Some(Task::perform(
-                        async move {
-                            tokio::task::spawn_blocking(move || {
-                                // Pre-compute engine lines in background thread
-                                let engine_lines: Vec<
-                                    crate::features::lyrics::engine::LyricLineData,
-                                > = lines_for_task
-                                    .iter()
-                                    .map(|line| {
-                                        let word_count = line.words.len();
-                                        let mut line_data =
-                                            crate::features::lyrics::engine::LyricLineData {
-                                                text: line.text.clone(),
-                                                words: line
-                                                    .words
-                                                    .iter()
-                                                    .enumerate()
-                                                    .map(|(i, w)| {
-                                                        crate::features::lyrics::engine::WordData {
-                                                            text: w.word.clone(),
-                                                            start_ms: w.start_ms,
-                                                            end_ms: w.end_ms,
-                                                            roman_word: None,
-                                                            emphasize: false,
-                                                            x_start: 0.0,
-                                                            x_end: 0.0,
-                                                            is_last_word: i
-                                                                == word_count.saturating_sub(1),
-                                                        }
-                                                    })
-                                                    .collect(),
-                                                translated: line.translated.clone(),
-                                                romanized: line.romanized.clone(),
-                                                start_ms: line.start_ms,
-                                                end_ms: line.end_ms,
-                                                is_duet: line.is_duet,
-                                                is_bg: line.is_background,
-                                                mask_animation: None,
-                                            };
-                                        line_data.compute_mask_animation();
-                                        line_data
-                                    })
-                                    .collect();
-                                (song_id, std::sync::Arc::new(engine_lines))
-                            })
-                            .await
-                            .ok()
-                        },
-                        |result| {
-                            if let Some((song_id, engine_lines)) = result {
-                                Message::LyricsEngineLinesReady(song_id, engine_lines)
-                            } else {
-                                Message::Noop
-                            }
-                        },
-                    ));
+                    return Some(Task::batch([
+                        self.prepare_engine_lines(*song_id),
+                        self.check_lyrics_savable(*song_id),
+                    ]));
                 }
                 Some(Task::none())
             }
@@ -233,64 +231,12 @@ impl App {
                         song_id
                     );
 
-                    // Trigger async engine line preparation (same as LyricsLoaded)
-                    let lines_for_task = lines.clone();
-                    let song_id = *song_id;
-                    return Some(Task::perform(
-                        async move {
-                            tokio::task::spawn_blocking(move || {
-                                let engine_lines: Vec<
-                                    crate::features::lyrics::engine::LyricLineData,
-                                > = lines_for_task
-                                    .iter()
-                                    .map(|line| {
-                                        let word_count = line.words.len();
-                                        let mut line_data =
-                                            crate::features::lyrics::engine::LyricLineData {
-                                                text: line.text.clone(),
-                                                words: line
-                                                    .words
-                                                    .iter()
-                                                    .enumerate()
-                                                    .map(|(i, w)| {
-                                                        crate::features::lyrics::engine::WordData {
-                                                            text: w.word.clone(),
-                                                            start_ms: w.start_ms,
-                                                            end_ms: w.end_ms,
-                                                            roman_word: None,
-                                                            emphasize: false,
-                                                            x_start: 0.0,
-                                                            x_end: 0.0,
-                                                            is_last_word: i
-                                                                == word_count.saturating_sub(1),
-                                                        }
-                                                    })
-                                                    .collect(),
-                                                translated: line.translated.clone(),
-                                                romanized: line.romanized.clone(),
-                                                start_ms: line.start_ms,
-                                                end_ms: line.end_ms,
-                                                is_duet: line.is_duet,
-                                                is_bg: line.is_background,
-                                                mask_animation: None,
-                                            };
-                                        line_data.compute_mask_animation();
-                                        line_data
-                                    })
-                                    .collect();
-                                (song_id, std::sync::Arc::new(engine_lines))
-                            })
-                            .await
-                            .ok()
-                        },
-                        |result| {
-                            if let Some((song_id, engine_lines)) = result {
-                                Message::LyricsEngineLinesReady(song_id, engine_lines)
-                            } else {
-                                Message::Noop
-                            }
-                        },
-                    ));
+                    // Trigger async engine line preparation
+                    return Some(Task::batch([
+                        self.prepare_engine_lines(*song_id),
+                        self.check_lyrics_edit(*song_id),
+                        self.check_lyrics_savable(*song_id),
+                    ]));
                 }
                 Some(Task::none())
             }
@@ -322,123 +268,118 @@ impl App {
                     let viewport_height = self.ui.lyrics.viewport_height;
 
                     return Some(Task::perform(
-                        async move {
-                            tokio::task::spawn_blocking(move || {
-                                use crate::features::lyrics::engine::{
-                                    CachedShapedLine, SdfPreGenerator, TextShaper,
-                                };
-
-                                // Calculate font sizes (same as in LyricsEngine::calculate_line_heights)
-                                let content_width = viewport_width * 0.9;
-                                let font_size = (viewport_height * 0.055).clamp(24.0, 72.0);
-                                let trans_height_ratio = 0.7;
-                                let roman_height_ratio = 0.6;
-                                let trans_font_size = (font_size * trans_height_ratio).max(10.0);
-                                let roman_font_size = (font_size * roman_height_ratio).max(10.0);
-
-                                // Create text shaper with shared font system
-                                let text_shaper = TextShaper::new(font_system.clone());
-
-                                // Shape all lines
-                                let shaped_lines: Vec<CachedShapedLine> = lines_for_shaping
-                                    .iter()
-                                    .map(|line| {
-                                        // Shape main lyrics
-                                        let main_shaped = text_shaper.shape_line(
-                                            &line.text,
-                                            &line.words,
-                                            font_size,
-                                            content_width,
-                                        );
-                                        let mut total_height = main_shaped.height;
-
-                                        // Shape translation line if present
-                                        let translation_shaped =
-                                            if let Some(ref translated) = line.translated {
-                                                if !translated.is_empty() {
-                                                    let shaped = text_shaper.shape_simple(
-                                                        translated,
-                                                        trans_font_size,
-                                                        content_width,
-                                                    );
-                                                    total_height += shaped.height;
-                                                    Some(shaped)
-                                                } else {
-                                                    None
-                                                }
+                        Job::new(Category::Disk).song().run_blocking(move || {
+                            use crate::features::lyrics::engine::{
+                                CachedShapedLine, SdfPreGenerator, TextShaper,
+                            };
+
+                            // Calculate font sizes (same as in LyricsEngine::calculate_line_heights)
+                            let content_width = viewport_width * 0.9;
+                            let font_size = (viewport_height * 0.055).clamp(24.0, 72.0);
+                            let trans_height_ratio = 0.7;
+                            let roman_height_ratio = 0.6;
+                            let trans_font_size = (font_size * trans_height_ratio).max(10.0);
+                            let roman_font_size = (font_size * roman_height_ratio).max(10.0);
+
+                            // Create text shaper with shared font system
+                            let text_shaper = TextShaper::new(font_system.clone());
+
+                            // Shape all lines
+                            let shaped_lines: Vec<CachedShapedLine> = lines_for_shaping
+                                .iter()
+                                .map(|line| {
+                                    // Shape main lyrics
+                                    let main_shaped = text_shaper.shape_line(
+                                        &line.text,
+                                        &line.words,
+                                        font_size,
+                                        content_width,
+                                    );
+                                    let mut total_height = main_shaped.height;
+
+                                    // Shape translation line if present
+                                    let translation_shaped =
+                                        if let Some(ref translated) = line.translated {
+                                            if !translated.is_empty() {
+                                                let shaped = text_shaper.shape_simple(
+                                                    translated,
+                                                    trans_font_size,
+                                                    content_width,
+                                                );
+                                                total_height += shaped.height;
+                                                Some(shaped)
                                             } else {
                                                 None
-                                            };
-
-                                        // Shape romanized line if present
-                                        let romanized_shaped =
-                                            if let Some(ref romanized) = line.romanized {
-                                                if !romanized.is_empty() {
-                                                    let shaped = text_shaper.shape_simple(
-                                                        romanized,
-                                                        roman_font_size,
-                                                        content_width,
-                                                    );
-                                                    total_height += shaped.height;
-                                                    Some(shaped)
-                                                } else {
-                                                    None
-                                                }
+                                            }
+                                        } else {
+                                            None
+                                        };
+
+                                    // Shape romanized line if present
+                                    let romanized_shaped =
+                                        if let Some(ref romanized) = line.romanized {
+                                            if !romanized.is_empty() {
+                                                let shaped = text_shaper.shape_simple(
+                                                    romanized,
+                                                    roman_font_size,
+                                                    content_width,
+                                                );
+                                                total_height += shaped.height;
+                                                Some(shaped)
                                             } else {
                                                 None
-                                            };
-
-                                        CachedShapedLine {
-                                            main: main_shaped,
-                                            translation: translation_shaped,
-                                            romanized: romanized_shaped,
-                                            total_height,
-                                        }
-                                    })
-                                    .collect();
-
-                                // Pre-generate SDF glyphs in background thread
-                                let start = std::time::Instant::now();
-                                let sdf_pre_gen = SdfPreGenerator::new(font_system);
-
-                                // Collect all cache keys from shaped lines
-                                let cache_keys: Vec<cosmic_text::CacheKey> = shaped_lines
-                                    .iter()
-                                    .flat_map(|line| {
-                                        let main_keys =
-                                            line.main.glyphs.iter().map(|g| g.cache_key);
-                                        let trans_keys = line
-                                            .translation
-                                            .iter()
-                                            .flat_map(|t| t.glyphs.iter().map(|g| g.cache_key));
-                                        let roman_keys = line
-                                            .romanized
-                                            .iter()
-                                            .flat_map(|r| r.glyphs.iter().map(|g| g.cache_key));
-                                        main_keys.chain(trans_keys).chain(roman_keys)
-                                    })
-                                    .collect();
-
-                                // Pre-generate all SDF glyphs
-                                let generated = sdf_pre_gen.generate_all(&cache_keys);
-                                let pre_generated_bitmaps = sdf_pre_gen.take_all();
-
-                                tracing::info!(
-                                    "Pre-generated {} SDF glyphs in {:?} (total keys: {})",
-                                    generated,
-                                    start.elapsed(),
-                                    cache_keys.len()
-                                );
-
-                                (
-                                    song_id,
-                                    std::sync::Arc::new(shaped_lines),
-                                    pre_generated_bitmaps,
-                                )
-                            })
-                            .await
-                            .ok()
-                        },
+                                            }
+                                        } else {
+                                            None
+                                        };
+
+                                    CachedShapedLine {
+                                        main: main_shaped,
+                                        translation: translation_shaped,
+                                        romanized: romanized_shaped,
+                                        total_height,
+                                    }
+                                })
+                                .collect();
+
+                            // Pre-generate SDF glyphs in background thread
+                            let start = std::time::Instant::now();
+                            let sdf_pre_gen = SdfPreGenerator::new(font_system);
+
+                            // Collect all cache keys from shaped lines
+                            let cache_keys: Vec<cosmic_text::CacheKey> = shaped_lines
+                                .iter()
+                                .flat_map(|line| {
+                                    let main_keys = line.main.glyphs.iter().map(|g| g.cache_key);
+                                    let trans_keys = line
+                                        .translation
+                                        .iter()
+                                        .flat_map(|t| t.glyphs.iter().map(|g| g.cache_key));
+                                    let roman_keys = line
+                                        .romanized
+                                        .iter()
+                                        .flat_map(|r| r.glyphs.iter().map(|g| g.cache_key));
+                                    main_keys.chain(trans_keys).chain(roman_keys)
+                                })
+                                .collect();
+
+                            // Pre-generate all SDF glyphs
+                            let generated = sdf_pre_gen.generate_all(&cache_keys);
+                            let pre_generated_bitmaps = sdf_pre_gen.take_all();
+
+                            tracing::info!(
+                                "Pre-generated {} SDF glyphs in {:?} (total keys: {})",
+                                generated,
+                                start.elapsed(),
+                                cache_keys.len()
+                            );
+
+                            (
+                                song_id,
+                                std::sync::Arc::new(shaped_lines),
+                                pre_generated_bitmaps,
+                            )
+                        }),
                         |result| {
                             if let Some((song_id, shaped_lines, pre_generated_bitmaps)) = result {
                                 Message::LyricsShapedLinesReady(
@@ -459,6 +400,7 @@ impl App {
             Message::LyricsShapedLinesReady(song_id, shaped_lines, pre_generated_bitmaps) => {
                 if self.ui.lyrics.loading_song_id == Some(*song_id) {
                     self.ui.lyrics.cached_shaped_lines = Some(shaped_lines.clone());
+                    self.ui.lyrics.redraw_requested = true;
                     tracing::info!(
                         "Shaped lines ready for song {}: {} lines",
                         song_id,
@@ -487,6 +429,28 @@ impl App {
                 Some(Task::none())
             }
 
+            Message::ToggleLyricsTranslation => {
+                self.ui.lyrics.sub_lines.translation = !self.ui.lyrics.sub_lines.translation;
+                self.save_lyrics_sub_lines();
+                Some(self.relayout_lyrics())
+            }
+
+            Message::ToggleLyricsRomanization => {
+                self.ui.lyrics.sub_lines.romanization = !self.ui.lyrics.sub_lines.romanization;
+                self.save_lyrics_sub_lines();
+                Some(self.relayout_lyrics())
+            }
+
+            Message::LyricsSubLinesLoaded(song_id, sub_lines) => {
+                if self.ui.lyrics.loading_song_id == Some(*song_id)
+                    && self.ui.lyrics.sub_lines != *sub_lines
+                {
+                    self.ui.lyrics.sub_lines = *sub_lines;
+                    return Some(self.relayout_lyrics());
+                }
+                Some(Task::none())
+            }
+
             // NEW: Handle async background colors
             Message::LyricsBackgroundReady(song_id, primary, secondary, tertiary) => {
                 // Only apply if this is still the current song
@@ -495,6 +459,7 @@ impl App {
                         .lyrics
                         .bg_shader
                         .set_colors(*primary, *secondary, *tertiary);
+                    self.ui.lyrics.redraw_requested = true;
 
                     // Convert to iced Color for bg_colors
                     self.ui.lyrics.bg_colors = crate::utils::DominantColors {
@@ -533,6 +498,7 @@ impl App {
                             .lyrics
                             .textured_bg_shader
                             .set_album_image(dynamic_img, None);
+                        self.ui.lyrics.redraw_requested = true;
                         tracing::debug!(
                             "Applied cover image for song {} ({}x{})",
                             song_id,
@@ -544,6 +510,21 @@ impl App {
                 Some(Task::none())
             }
 
+            Message::LargeCoverReady(song_id, path) => {
+                let Some(song) = self
+                    .library
+                    .current_song
+                    .clone()
+                    .filter(|s| s.id == *song_id)
+                else {
+                    return Some(Task::none());
+                };
+                self.ui.lyrics.large_cover = Some((*song_id, path.clone()));
+                self.ui.lyrics.redraw_requested = true;
+                // Swap the thumbnail behind the lyrics for the sharper copy
+                Some(self.update_background_async(&song))
+            }
+
             _ => None,
         }
     }
@@ -556,6 +537,7 @@ impl App {
         self.ui.lyrics.is_loading = false;
         self.ui.lyrics.load_error = None;
         self.ui.lyrics.current_line_idx = None;
+        self.ui.lyrics.redraw_requested = true;
 
         // Clear engine's cached data for re-layout, but keep the engine instance
         // (engine is pre-created at app startup to avoid FontSystem::new() delay)
@@ -566,24 +548,208 @@ impl App {
         }
     }
 
+    /// Pre-compute engine lines for the current lyrics in a background thread
+    fn prepare_engine_lines(&self, song_id: SongId) -> Task<Message> {
+        let lines = self.ui.lyrics.lines.clone();
+        let sub_lines = self.ui.lyrics.sub_lines;
+        Task::perform(
+            Job::new(Category::Disk).song().run_blocking(move || {
+                let engine_lines = build_engine_lines(&lines, sub_lines);
+                (song_id, std::sync::Arc::new(engine_lines))
+            }),
+            |result| {
+                if let Some((song_id, engine_lines)) = result {
+                    Message::LyricsEngineLinesReady(song_id, engine_lines)
+                } else {
+                    Message::Noop
+                }
+            },
+        )
+    }
+
+    /// Engine config with the user's tuning applied over the defaults
+    fn lyrics_engine_config(&self) -> LyricsEngineConfig {
+        let tuning = &self.core.settings.display.lyrics_tuning;
+        LyricsEngineConfig {
+            word_fade_width: tuning.word_fade_width,
+            emphasis_amount_scale: tuning.emphasis_amount,
+            emphasis_blur_scale: tuning.emphasis_blur,
+            spring_stiffness: tuning.spring_stiffness,
+            spring_damping: tuning.spring_damping,
+            align_position: tuning.align_position,
+            scroll_timeout: tuning.scroll_resume_delay,
+            ..LyricsEngineConfig::default()
+        }
+    }
+
+    /// Push changed tuning settings into the running engine so the lyrics
+    /// page reflects them right away
+    pub(super) fn apply_lyrics_tuning(&mut self) {
+        let config = self.lyrics_engine_config();
+        if let Some(engine_cell) = &self.ui.lyrics.engine {
+            engine_cell.borrow_mut().set_config(config);
+        }
+        self.ui.lyrics.redraw_requested = true;
+    }
+
+    /// Drop the laid-out lines and build them again, after the visible
+    /// sub-lines changed
+    fn relayout_lyrics(&mut self) -> Task<Message> {
+        self.ui.lyrics.cached_engine_lines = None;
+        self.ui.lyrics.cached_shaped_lines = None;
+        self.ui.lyrics.redraw_requested = true;
+        if let Some(engine_cell) = &self.ui.lyrics.engine {
+            engine_cell.borrow_mut().set_cached_shaped_lines(Vec::new());
+        }
+
+        match self.ui.lyrics.loading_song_id {
+            Some(song_id) if !self.ui.lyrics.lines.is_empty() => self.prepare_engine_lines(song_id),
+            _ => Task::none(),
+        }
+    }
+
+    /// Start a song with the default sub-lines and look up the ones saved
+    /// for it
+    fn load_lyrics_sub_lines(&mut self, song_id: SongId) -> Task<Message> {
+        self.ui.lyrics.sub_lines = self.core.settings.display.lyrics_sub_lines;
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            Job::new(Category::Database)
+                .song()
+                .run(async move { db.get_lyrics_preference(song_id).await }),
+            move |result| match result {
+                Some(Ok(Some(preference))) => Message::LyricsSubLinesLoaded(
+                    song_id,
+                    LyricsSubLines {
+                        translation: preference.show_translation,
+                        romanization: preference.show_romanization,
+                    },
+                ),
+                Some(Err(e)) => {
+                    tracing::warn!("Failed to load lyrics preference for {}: {}", song_id, e);
+                    Message::Noop
+                }
+                _ => Message::Noop,
+            },
+        )
+    }
+
+    /// Remember the current sub-lines for the song whose lyrics are shown
+    fn save_lyrics_sub_lines(&self) {
+        let (Some(song_id), Some(db)) = (self.ui.lyrics.loading_song_id, self.core.db.clone())
+        else {
+            return;
+        };
+        let sub_lines = self.ui.lyrics.sub_lines;
+        Job::new(Category::Database).spawn(async move {
+            if let Err(e) = db
+                .save_lyrics_preference(song_id, sub_lines.translation, sub_lines.romanization)
+                .await
+            {
+                tracing::warn!("Failed to save lyrics preference for {}: {}", song_id, e);
+            }
+        });
+    }
+
+    /// Whether `id` is the lyrics window
+    pub(super) fn is_lyrics_window(&self, id: iced::window::Id) -> bool {
+        self.ui.lyrics.window.is_some_and(|window| window.id == id)
+    }
+
+    /// Size the lyrics viewport to the window the page is shown in
+    fn set_lyrics_viewport(&mut self, size: iced::Size) {
+        self.ui.lyrics.viewport_width = (size.width * 0.6 - 60.0).max(100.0);
+        self.ui.lyrics.viewport_height = size.height;
+        self.ui.lyrics.redraw_requested = true;
+
+        if let Some(engine_cell) = &self.ui.lyrics.engine {
+            let mut engine = engine_cell.borrow_mut();
+            engine
+                .line_animations_mut()
+                .set_viewport_height(size.height);
+
+            // Force re-layout by invalidating cached dimensions
+            engine.invalidate_layout();
+        }
+    }
+
+    /// Load the song's lyrics for the page, or just its background when
+    /// they are already loaded
+    fn prepare_lyrics_page(&mut self, song: &DbSong) -> Task<Message> {
+        // 智能加载歌词：
+        // 检查当前歌词是否属于当前歌曲
+        // 注意：loading_song_id 表示"正在加载或已加载的歌曲ID"
+        // 如果 loading_song_id 不匹配当前歌曲，需要重新加载
+        let lyrics_need_load = self.ui.lyrics.loading_song_id != Some(song.id);
+
+        if lyrics_need_load {
+            tracing::debug!("Loading lyrics for song: {} (id={})", song.title, song.id);
+            // Use async loading
+            self.load_lyrics_async(song)
+        } else {
+            tracing::debug!(
+                "Lyrics already loaded for song: {} (id={})",
+                song.title,
+                song.id
+            );
+            // Still need to update background if cover changed
+            self.update_background_async(song)
+        }
+    }
+
     /// Check if lyrics page should be fully closed (animation complete)
     pub fn check_lyrics_page_close(&mut self) {
         let progress = self.ui.lyrics.animation.progress();
         if progress < 0.01 && !self.ui.lyrics.animation.is_animating() && self.ui.lyrics.is_open {
             self.ui.lyrics.is_open = false;
+            self.refresh_tray_state();
         }
     }
 
+    /// Whether the lyrics page needs animation frames
+    ///
+    /// Frames are requested only while something on the page can change:
+    /// playback (word highlights, background flow), the open/close
+    /// transition, user scrolling, engine springs and fades, a cover
+    /// crossfade, or a pending redraw. A paused, settled page draws nothing.
+    pub fn lyrics_needs_frames(&self, is_playing: bool) -> bool {
+        let lyrics = &self.ui.lyrics;
+        if !lyrics.is_shown() {
+            return false;
+        }
+
+        is_playing
+            || lyrics.redraw_requested
+            || lyrics.user_scrolling
+            || lyrics.animation.is_animating()
+            || lyrics.textured_bg_shader.is_transitioning()
+            || lyrics
+                .engine
+                .as_ref()
+                .is_some_and(|engine| engine.borrow().is_animating())
+    }
+
     /// Update lyrics line animations based on current playback position
     pub fn update_lyrics_animations(&mut self) -> Task<Message> {
         let now = std::time::Instant::now();
         let delta_secs = if let Some(last) = self.ui.lyrics.last_update {
-            let delta = now.duration_since(last).as_secs_f32();
-            delta.clamp(0.001, 0.1)
+            let gap = now.duration_since(last).as_secs_f32();
+            let delta = gap.clamp(0.001, 0.1);
+            // Frames stopped while the page was static; move the shader
+            // clock past the gap so the background resumes where it froze
+            if gap > delta {
+                if let Some(start_time) = self.ui.lyrics.shader_start_time.as_mut() {
+                    *start_time += std::time::Duration::from_secs_f32(gap - delta);
+                }
+            }
+            delta
         } else {
             0.016
         };
         self.ui.lyrics.last_update = Some(now);
+        self.ui.lyrics.redraw_requested = false;
 
         if let Some(start_time) = self.ui.lyrics.shader_start_time {
             let elapsed_ms = now.duration_since(start_time).as_secs_f32() * 1000.0;
@@ -603,7 +769,8 @@ impl App {
         let position_ms = if let Some(player) = &self.core.audio {
             let info = player.get_info();
             if info.duration.as_secs_f32() > 0.0 {
-                (info.position.as_secs_f32() * 1000.0) as u64
+                // Follow what is heard, not what was decoded
+                (player.heard_position().as_secs_f32() * 1000.0) as u64
             } else {
                 0
             }
@@ -640,7 +807,7 @@ impl App {
         let time_ms = if let Some(player) = &self.core.audio {
             let info = player.get_info();
             if info.duration.as_secs_f32() > 0.0 {
-                info.position.as_secs_f64() * 1000.0
+                player.heard_position().as_secs_f64() * 1000.0
             } else {
                 self.library
                     .playback_state
@@ -692,9 +859,7 @@ impl App {
     }
 
     /// Get or create cached engine lines
-    fn get_or_create_engine_lines(
-        &mut self,
-    ) -> std::sync::Arc<Vec<crate::features::lyrics::engine::LyricLineData>> {
+    fn get_or_create_engine_lines(&mut self) -> std::sync::Arc<Vec<LyricLineData>> {
         let cache_valid = self
             .ui
             .lyrics
@@ -707,60 +872,35 @@ impl App {
             return self.ui.lyrics.cached_engine_lines.clone().unwrap();
         }
 
-        let engine_lines: Vec<crate::features::lyrics::engine::LyricLineData> = self
-            .ui
-            .lyrics
-            .lines
-            .iter()
-            .map(|line| {
-                let mut line_data = crate::features::lyrics::engine::LyricLineData {
-                    text: line.text.clone(),
-                    words: {
-                        let word_count = line.words.len();
-                        line.words
-                            .iter()
-                            .enumerate()
-                            .map(|(i, w)| crate::features::lyrics::engine::WordData {
-                                text: w.word.clone(),
-                                start_ms: w.start_ms,
-                                end_ms: w.end_ms,
-                                roman_word: None,
-                                emphasize: false,
-                                x_start: 0.0,
-                                x_end: 0.0,
-                                is_last_word: i == word_count.saturating_sub(1),
-                            })
-                            .collect()
-                    },
-                    translated: line.translated.clone(),
-                    romanized: line.romanized.clone(),
-                    start_ms: line.start_ms,
-                    end_ms: line.end_ms,
-                    is_duet: line.is_duet,
-                    is_bg: line.is_background,
-                    mask_animation: None,
-                };
-                line_data.compute_mask_animation();
-                line_data
-            })
-            .collect();
+        let engine_lines = build_engine_lines(&self.ui.lyrics.lines, self.ui.lyrics.sub_lines);
 
         let arc = std::sync::Arc::new(engine_lines);
         self.ui.lyrics.cached_engine_lines = Some(arc.clone());
         arc
     }
 
+    /// Seconds left before manually scrolled lyrics return to the current
+    /// line, while they are scrolled
+    pub fn lyrics_scroll_resume_in(&self) -> Option<f32> {
+        if !self.ui.lyrics.user_scrolling {
+            return None;
+        }
+        let last_scroll = self.ui.lyrics.last_scroll_time?;
+        let delay = self.core.settings.display.lyrics_tuning.scroll_resume_delay;
+        Some((delay - last_scroll.elapsed().as_secs_f32()).max(0.0))
+    }
+
     /// Handle scroll bounce-back after user inactivity
     fn update_scroll_bounce_back(&mut self, delta_secs: f32) {
-        const BOUNCE_BACK_DELAY_SECS: f32 = 3.0;
         const BOUNCE_BACK_SPEED: f32 = 8.0;
 
+        let delay = self.core.settings.display.lyrics_tuning.scroll_resume_delay;
         if let Some(last_scroll) = self.ui.lyrics.last_scroll_time {
             let elapsed = std::time::Instant::now()
                 .duration_since(last_scroll)
                 .as_secs_f32();
 
-            if elapsed > BOUNCE_BACK_DELAY_SECS {
+            if elapsed > delay {
                 let lerp_factor = 1.0 - (-BOUNCE_BACK_SPEED * delta_secs).exp();
                 self.ui.lyrics.manual_scroll_offset *= 1.0 - lerp_factor;
 
@@ -773,6 +913,17 @@ impl App {
         }
     }
 
+    /// Return manually scrolled lyrics to the current line right away
+    fn resume_lyrics_auto_scroll(&mut self) {
+        self.ui.lyrics.user_scrolling = false;
+        self.ui.lyrics.last_scroll_time = None;
+        self.ui.lyrics.manual_scroll_offset = 0.0;
+        self.ui.lyrics.redraw_requested = true;
+        if let Some(engine_cell) = &self.ui.lyrics.engine {
+            engine_cell.borrow_mut().resume_auto_scroll();
+        }
+    }
+
     /// Handle user scroll event on lyrics
     pub fn handle_lyrics_scroll(&mut self, delta: f32) {
         tracing::debug!("Lyrics scroll: delta={}", delta);
@@ -798,6 +949,9 @@ impl App {
         self.ui.lyrics.cached_shaped_lines = None;
         self.ui.lyrics.current_line_idx = None;
         self.ui.lyrics.load_error = None;
+        self.ui.lyrics.shareable = None;
+        self.ui.lyrics.savable = None;
+        let sub_lines_task = self.load_lyrics_sub_lines(song.id);
         self.ui.lyrics.loading_song_id = Some(song.id);
         self.ui.lyrics.is_loading = true;
 
@@ -809,55 +963,73 @@ impl App {
 
         let song_id = song.id;
         let file_path = song.file_path.clone();
-        let is_ncm = song.id < 0;
-        let ncm_id = if is_ncm { (-song.id) as u64 } else { 0 };
+        let is_ncm = song_id.is_ncm();
+        let ncm_id = self.ncm_id_of(song);
+        let plugins = self.core.plugins.clone();
+        let plugin_query = crate::features::plugins::TrackQuery {
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            album: song.album.clone(),
+            duration_secs: song.duration_secs,
+            path: (!is_ncm && !file_path.is_empty()).then(|| file_path.clone()),
+        };
 
         // Also start background color extraction
         let bg_task = self.update_background_async(song);
 
         // Create async task for lyrics loading
-        // CRITICAL: Use spawn_blocking for synchronous I/O operations
+        // CRITICAL: Use the blocking pool for synchronous I/O operations
         let lyrics_task = Task::perform(
             async move {
-                // Use spawn_blocking to move sync I/O to blocking thread pool
-                tokio::task::spawn_blocking(move || {
-                    // Priority 1: Local lyrics file or embedded
-                    if !file_path.is_empty() {
-                        let audio_path = std::path::Path::new(&file_path);
-                        if let Some(lrc_lines) =
-                            crate::features::media::lyrics::find_lyrics(audio_path)
-                        {
-                            let ui_lines =
-                                crate::features::media::lyrics::to_ui_lyric_lines(lrc_lines);
-                            return Some((song_id, ui_lines, false)); // false = no online fetch needed
+                // Dropped unrun if another song starts first
+                Job::new(Category::Disk)
+                    .song()
+                    .run_blocking(move || {
+                        // Priority 1: Local lyrics file or embedded
+                        if !file_path.is_empty() {
+                            let audio_path = std::path::Path::new(&file_path);
+                            if let Some(lrc_lines) =
+                                crate::features::media::lyrics::find_lyrics(audio_path)
+                            {
+                                let ui_lines =
+                                    crate::features::media::lyrics::to_ui_lyric_lines(lrc_lines);
+                                return Some((song_id, ui_lines, None)); // None = no online fetch needed
+                            }
                         }
-                    }
 
-                    // Priority 2: Cached online lyrics (for NCM songs)
-                    if is_ncm {
-                        if let Some(cached_lines) =
-                            crate::features::lyrics::load_cached_lyrics(ncm_id)
-                        {
-                            let ui_lines = crate::features::lyrics::to_ui_lyrics(cached_lines);
-                            return Some((song_id, ui_lines, false));
+                        // Priority 2: Cached online lyrics (NCM songs and linked local songs)
+                        if let Some(ncm_id) = ncm_id {
+                            if let Some(cached_lines) =
+                                crate::features::lyrics::load_cached_lyrics(ncm_id)
+                            {
+                                let ui_lines = crate::features::lyrics::to_ui_lyrics(cached_lines);
+                                return Some((song_id, ui_lines, None));
+                            }
+                            // Need online fetch
+                            return Some((song_id, Vec::new(), Some(ncm_id)));
                         }
-                        // Need online fetch
-                        return Some((song_id, Vec::new(), true)); // true = need online fetch
-                    }
 
-                    // No lyrics found for local song
-                    Some((song_id, Vec::new(), false))
-                })
-                .await
-                .ok()
-                .flatten()
+                        // Priority 3: Lyrics provider plugins (unlinked local songs only)
+                        if let Some(content) = plugins.lookup_lyrics(&plugin_query) {
+                            let lines = crate::features::lyrics::parse_lyrics(&content);
+                            if !lines.is_empty() {
+                                let ui_lines =
+                                    crate::features::media::lyrics::to_ui_lyric_lines(lines);
+                                return Some((song_id, ui_lines, None));
+                            }
+                        }
+
+                        // No lyrics found for local song
+                        Some((song_id, Vec::new(), None))
+                    })
+                    .await
+                    .flatten()
             },
             |result| {
                 match result {
-                    Some((song_id, lines, needs_online)) => {
-                        if needs_online {
+                    Some((song_id, lines, online_id)) => {
+                        if let Some(ncm_id) = online_id {
                             // Trigger online fetch via PreloadLyrics
-                            let ncm_id = (-song_id) as u64;
                             Message::PreloadLyrics(
                                 song_id,
                                 ncm_id,
@@ -877,13 +1049,14 @@ impl App {
             },
         );
 
-        Task::batch([bg_task, lyrics_task])
+        Task::batch([bg_task, sub_lines_task, lyrics_task])
     }
 
     /// Update background asynchronously (color extraction + texture)
     fn update_background_async(&mut self, song: &crate::database::DbSong) -> Task<Message> {
         let song_id = song.id;
-        let cover_path = song.cover_path.clone();
+        let large_cover_task = self.fetch_large_cover(song);
+        let cover_path = self.ui.lyrics.cover_for(song).map(String::from);
 
         // Reset shader time if needed
         if self.ui.lyrics.shader_start_time.is_none() {
@@ -893,20 +1066,20 @@ impl App {
         // If no cover, just clear and return
         let Some(path) = cover_path else {
             self.ui.lyrics.textured_bg_shader.clear_cover();
-            return Task::none();
+            return large_cover_task;
         };
 
         // Skip if cover is a URL (not downloaded yet)
         if path.starts_with("http://") || path.starts_with("https://") {
             tracing::debug!("Cover is URL, waiting for download: {}", path);
-            return Task::none();
+            return large_cover_task;
         }
 
         // Check if we already have this image cached (fast path)
         let path_obj = std::path::Path::new(&path);
         if self.ui.lyrics.textured_bg_shader.is_same_image(path_obj) {
             tracing::debug!("Cover image already cached for song {}", song_id);
-            return Task::none();
+            return large_cover_task;
         }
 
         // Load both image and colors asynchronously
@@ -916,21 +1089,22 @@ impl App {
         // Task 1: Load cover image for textured background
         let image_task = Task::perform(
             async move {
-                tokio::task::spawn_blocking(move || match image::open(&path_for_image) {
-                    Ok(img) => {
-                        let rgb = img.to_rgb8();
-                        let (width, height) = rgb.dimensions();
-                        let data = rgb.into_raw();
-                        Some((song_id, data, width, height))
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to load cover image: {}", e);
-                        None
-                    }
-                })
-                .await
-                .ok()
-                .flatten()
+                Job::new(Category::Disk)
+                    .song()
+                    .run_blocking(move || match image::open(&path_for_image) {
+                        Ok(img) => {
+                            let rgb = img.to_rgb8();
+                            let (width, height) = rgb.dimensions();
+                            let data = rgb.into_raw();
+                            Some((song_id, data, width, height))
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to load cover image: {}", e);
+                            None
+                        }
+                    })
+                    .await
+                    .flatten()
             },
             |result| match result {
                 Some((song_id, data, width, height)) => {
@@ -941,23 +1115,23 @@ impl App {
         );
 
         // Task 2: Extract colors
+        let db = self.core.db.clone();
         let colors_task = Task::perform(
             async move {
-                tokio::task::spawn_blocking(move || {
-                    if let Some(colors) =
-                        crate::utils::DominantColors::from_image_path(&path_for_colors)
-                    {
+                Job::new(Category::Disk)
+                    .song()
+                    .run(palette::dominant_colors(
+                        db,
+                        std::path::PathBuf::from(path_for_colors),
+                    ))
+                    .await
+                    .flatten()
+                    .map(|colors| {
                         let primary = color_to_array(colors.primary);
                         let secondary = color_to_array(colors.secondary);
                         let tertiary = color_to_array(colors.tertiary);
-                        Some((song_id, primary, secondary, tertiary))
-                    } else {
-                        None
-                    }
-                })
-                .await
-                .ok()
-                .flatten()
+                        (song_id, primary, secondary, tertiary)
+                    })
             },
             |result| match result {
                 Some((song_id, primary, secondary, tertiary)) => {
@@ -967,7 +1141,7 @@ impl App {
             },
         );
 
-        Task::batch([image_task, colors_task])
+        Task::batch([image_task, colors_task, large_cover_task])
     }
 
     /// 更新歌词页面背景（切歌时调用）
@@ -978,6 +1152,55 @@ impl App {
         self.load_lyrics_async(song)
     }
 
+    /// Fetch the high-resolution cover of an online or linked song; until it
+    /// arrives the lyrics page shows the thumbnail
+    fn fetch_large_cover(&mut self, song: &crate::database::DbSong) -> Task<Message> {
+        let Some(ncm_id) = self.ncm_id_of(song) else {
+            return Task::none();
+        };
+        if self.core.is_metered() {
+            return Task::none();
+        }
+        if matches!(&self.ui.lyrics.large_cover, Some((id, _)) if *id == song.id) {
+            return Task::none();
+        }
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+
+        let song_id = song.id;
+        let stem = format!("cover_{}_large", ncm_id);
+        if let Some(path) =
+            crate::utils::find_cached_image(&crate::utils::covers_cache_dir(), &stem)
+        {
+            self.ui.lyrics.large_cover = Some((song_id, path.to_string_lossy().to_string()));
+            return Task::none();
+        }
+
+        // Once the thumbnail is cached the song only keeps its local path
+        let known_url = song.cover_path.clone().filter(|p| p.starts_with("http"));
+        Task::perform(
+            Job::new(Category::Cover).song().run(async move {
+                let url = match known_url {
+                    Some(url) => url,
+                    None => client
+                        .song_detail(&[ncm_id])
+                        .await
+                        .ok()?
+                        .first()
+                        .map(|s| s.pic_url.clone())?,
+                };
+                crate::utils::download_large_cover(&client, ncm_id, &url)
+                    .await
+                    .map(|path| (song_id, path.to_string_lossy().to_string()))
+            }),
+            |result| match result.flatten() {
+                Some((song_id, path)) => Message::LargeCoverReady(song_id, path),
+                None => Message::Noop,
+            },
+        )
+    }
+
     /// 只更新歌词页面背景（封面下载完成后调用）
     /// 不重新加载歌词
     pub fn update_lyrics_background_only(
@@ -987,3 +1210,44 @@ impl App {
         self.update_background_async(song)
     }
 }
+
+/// Engine lines for the lyrics, with only the sub-lines the user wants so
+/// hidden ones are never shaped or drawn
+fn build_engine_lines(
+    lines: &[crate::ui::pages::LyricLine],
+    sub_lines: LyricsSubLines,
+) -> Vec<LyricLineData> {
+    lines
+        .iter()
+        .map(|line| {
+            let word_count = line.words.len();
+            let mut line_data = LyricLineData {
+                text: line.text.clone(),
+                words: line
+                    .words
+                    .iter()
+                    .enumerate()
+                    .map(|(i, w)| WordData {
+                        text: w.word.clone(),
+                        start_ms: w.start_ms,
+                        end_ms: w.end_ms,
+                        roman_word: None,
+                        emphasize: false,
+                        x_start: 0.0,
+                        x_end: 0.0,
+                        is_last_word: i == word_count.saturating_sub(1),
+                    })
+                    .collect(),
+                translated: line.translated.clone().filter(|_| sub_lines.translation),
+                romanized: line.romanized.clone().filter(|_| sub_lines.romanization),
+                start_ms: line.start_ms,
+                end_ms: line.end_ms,
+                is_duet: line.is_duet,
+                is_bg: line.is_background,
+                mask_animation: None,
+            };
+            line_data.compute_mask_animation();
+            line_data
+        })
+        .collect()
+}
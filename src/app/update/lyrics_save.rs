@@ -0,0 +1,237 @@
+//! Handlers for saving fetched lyrics into local files
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use iced::Task;
+use tracing::{info, warn};
+
+use crate::api::NcmClient;
+use crate::app::message::Message;
+use crate::app::state::{App, LyricsSaveBatch};
+use crate::database::{Database, SongId};
+use crate::features::jobs::{Category, Job};
+use crate::features::lyrics_save::{self, LyricsSaveTarget, SongLyrics};
+use crate::features::plugins::PluginRegistry;
+use crate::i18n::Key;
+
+impl App {
+    /// Handle saving lyrics for the current song and for whole folders
+    pub fn handle_lyrics_save(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::LyricsSaveChecked(song_id, savable) => {
+                if self.ui.lyrics.loading_song_id != Some(*song_id)
+                    || !*savable
+                    || self.ui.lyrics.lines.is_empty()
+                {
+                    return Some(Task::none());
+                }
+                self.ui.lyrics.savable = Some(*song_id);
+                if !self.core.settings.display.lyrics_save.offer {
+                    return Some(Task::none());
+                }
+                let locale = self.core.locale;
+                let toast = self.update(Message::ShowToast(
+                    locale.get(Key::LyricsSaveOffer).to_string(),
+                ));
+                self.ui.toast_action =
+                    Some((locale.get(Key::LyricsSaveToFile), Message::SaveLyricsToFile));
+                Some(toast)
+            }
+
+            Message::SaveLyricsToFile => {
+                let Some(song) = self
+                    .library
+                    .current_song
+                    .as_ref()
+                    .filter(|song| Some(song.id) == self.ui.lyrics.savable)
+                else {
+                    return Some(Task::none());
+                };
+                let song_id = song.id;
+                let lyrics = SongLyrics::new(song, self.ncm_id_of(song));
+                let client = self.core.ncm_client.clone();
+                let plugins = self.core.plugins.clone();
+                let target = self.core.settings.display.lyrics_save.target;
+                Some(Task::perform(
+                    async move {
+                        lyrics_save::save_for_song(lyrics, client, plugins, target)
+                            .await
+                            .map_err(|e| format!("{:#}", e))
+                    },
+                    move |result| Message::LyricsSaved(song_id, result),
+                ))
+            }
+
+            Message::LyricsSaved(song_id, result) => {
+                let locale = self.core.locale;
+                let message = match result {
+                    Ok(Some(path)) => {
+                        info!("Saved lyrics of song {} to {:?}", song_id, path);
+                        if self.ui.lyrics.savable == Some(*song_id) {
+                            self.ui.lyrics.savable = None;
+                        }
+                        let name = path
+                            .file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        Message::ShowToast(locale.get(Key::LyricsSaved).replace("{}", &name))
+                    }
+                    Ok(None) => Message::ShowToast(locale.get(Key::LyricsSaveNothing).to_string()),
+                    Err(e) => {
+                        warn!("Failed to save lyrics of song {}: {}", song_id, e);
+                        Message::ShowErrorToast(locale.get(Key::LyricsSaveFailed).replace("{}", e))
+                    }
+                };
+                Some(Task::done(message))
+            }
+
+            Message::PickLyricsSaveFolder => Some(Task::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .pick_folder()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                },
+                Message::LyricsSaveFolderPicked,
+            )),
+
+            Message::LyricsSaveFolderPicked(dir) => {
+                let (Some(dir), Some(db)) = (dir.clone(), self.core.db.clone()) else {
+                    return Some(Task::none());
+                };
+                if self.ui.lyrics_save_batch.is_some() {
+                    return Some(Task::none());
+                }
+                let (task, handle) = Task::run(
+                    save_folder_lyrics(
+                        db,
+                        dir,
+                        self.library.ncm_links.clone(),
+                        self.core.ncm_client.clone(),
+                        self.core.plugins.clone(),
+                        self.core.settings.display.lyrics_save.target,
+                    ),
+                    |msg| msg,
+                )
+                .abortable();
+                self.ui.lyrics_save_batch = Some(LyricsSaveBatch {
+                    done: 0,
+                    total: 0,
+                    saved: 0,
+                    task: handle.abort_on_drop(),
+                });
+                Some(task)
+            }
+
+            Message::LyricsSaveBatchStarted(total) => {
+                if let Some(batch) = &mut self.ui.lyrics_save_batch {
+                    batch.total = *total;
+                }
+                Some(Task::none())
+            }
+
+            Message::LyricsSaveBatchSongDone(saved) => {
+                if let Some(batch) = &mut self.ui.lyrics_save_batch {
+                    batch.done += 1;
+                    batch.saved += usize::from(*saved);
+                }
+                Some(Task::none())
+            }
+
+            Message::LyricsSaveBatchFinished => {
+                let Some(batch) = self.ui.lyrics_save_batch.take() else {
+                    return Some(Task::none());
+                };
+                info!("Saved lyrics for {} of {} songs", batch.saved, batch.total);
+                Some(Task::done(Message::ShowToast(
+                    self.core
+                        .locale
+                        .get(Key::LyricsSaveBatchDone)
+                        .replacen("{}", &batch.saved.to_string(), 1)
+                        .replacen("{}", &batch.total.to_string(), 1),
+                )))
+            }
+
+            Message::CancelLyricsSaveBatch => {
+                // Dropping the handle stops the batch; a file already being
+                // written is still finished
+                self.ui.lyrics_save_batch = None;
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Check whether the lyrics shown for a local song came from elsewhere
+    /// than its own files
+    pub(super) fn check_lyrics_savable(&self, song_id: SongId) -> Task<Message> {
+        if song_id.is_ncm() {
+            return Task::none();
+        }
+        let Some(song) = self
+            .library
+            .current_song
+            .as_ref()
+            .filter(|song| song.id == song_id)
+        else {
+            return Task::none();
+        };
+        let path = PathBuf::from(&song.file_path);
+
+        Task::perform(
+            async move {
+                Job::new(Category::Disk)
+                    .run_blocking(move || !lyrics_save::has_own_lyrics(&path))
+                    .await
+                    .unwrap_or(false)
+            },
+            move |savable| Message::LyricsSaveChecked(song_id, savable),
+        )
+    }
+}
+
+/// Save lyrics for every library song under `dir` that has none of its own
+fn save_folder_lyrics(
+    db: Arc<Database>,
+    dir: PathBuf,
+    ncm_links: HashMap<SongId, u64>,
+    client: Option<NcmClient>,
+    plugins: Arc<PluginRegistry>,
+    target: LyricsSaveTarget,
+) -> impl futures_util::Stream<Item = Message> {
+    async_stream::stream! {
+        let songs: Vec<_> = match db.get_all_songs().await {
+            Ok(songs) => songs
+                .into_iter()
+                .filter(|song| Path::new(&song.file_path).starts_with(&dir))
+                .collect(),
+            Err(e) => {
+                warn!("Failed to list songs to save lyrics for: {}", e);
+                Vec::new()
+            }
+        };
+        info!("Saving lyrics for {} songs in {:?}", songs.len(), dir);
+        yield Message::LyricsSaveBatchStarted(songs.len());
+
+        for song in songs {
+            let ncm_id = ncm_links.get(&song.id).copied();
+            let lyrics = SongLyrics::new(&song, ncm_id);
+            let saved =
+                match lyrics_save::save_for_song(lyrics, client.clone(), plugins.clone(), target)
+                    .await
+                {
+                    Ok(path) => path.is_some(),
+                    Err(e) => {
+                        warn!("Failed to save lyrics for {}: {:#}", song.file_path, e);
+                        false
+                    }
+                };
+            yield Message::LyricsSaveBatchSongDone(saved);
+        }
+
+        yield Message::LyricsSaveBatchFinished;
+    }
+}
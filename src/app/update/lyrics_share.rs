@@ -0,0 +1,247 @@
+//! Handlers for sharing lyrics fixes on LRCLIB
+
+use std::path::Path;
+
+use iced::Task;
+use tracing::{error, warn};
+
+use crate::app::message::Message;
+use crate::app::state::{App, LyricsShareDialog, LyricsShareStage};
+use crate::database::SongId;
+use crate::features::lyrics_share::{self, Submission};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle the lyrics share offer and dialog
+    pub fn handle_lyrics_share(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::LyricsShareChecked(song_id, edited) => {
+                if self.ui.lyrics.loading_song_id != Some(*song_id) {
+                    return Some(Task::none());
+                }
+                self.ui.lyrics.shareable = edited.map(|edited| (*song_id, edited));
+                if *edited == Some(true) && self.core.settings.display.lyrics_share.offer {
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::LyricsShareOffer).to_string(),
+                    )));
+                }
+                Some(Task::none())
+            }
+
+            Message::OpenLyricsShare => {
+                if self.ui.dialogs.lyrics_share.is_some() {
+                    return Some(Task::none());
+                }
+                let shareable = self.ui.lyrics.shareable.map(|(id, _)| id);
+                let Some(song) = self
+                    .library
+                    .current_song
+                    .as_ref()
+                    .filter(|song| Some(song.id) == shareable)
+                else {
+                    return Some(Task::none());
+                };
+                let song_id = song.id;
+                let audio_path = song.file_path.clone();
+                let mut submission = Submission {
+                    track_name: song.title.clone(),
+                    artist_name: song.artist.clone(),
+                    album_name: song.album.clone(),
+                    duration_secs: song.duration_secs.max(0) as u64,
+                    lrc: String::new(),
+                    credit: self.lyrics_credit(),
+                };
+                Some(Task::perform(
+                    async move {
+                        let path = lyrics_share::lrc_path(Path::new(&audio_path))
+                            .ok_or_else(|| "LRC file is gone".to_string())?;
+                        submission.lrc = tokio::fs::read_to_string(path)
+                            .await
+                            .map_err(|e| e.to_string())?;
+                        Ok(submission)
+                    },
+                    move |result| Message::LyricsShareReady(song_id, result),
+                ))
+            }
+
+            Message::LyricsShareReady(song_id, result) => match result {
+                Ok(submission) if !submission.plain_lyrics().is_empty() => {
+                    self.ui.dialogs.lyrics_share = Some(LyricsShareDialog {
+                        song_id: *song_id,
+                        content_hash: lyrics_share::content_hash(&submission.lrc),
+                        submission: submission.clone(),
+                        stage: LyricsShareStage::Preview,
+                    });
+                    self.ui.dialogs.lyrics_share_animation.start();
+                    Some(Task::none())
+                }
+                Ok(_) => Some(Task::done(Message::ShowToast(
+                    self.core.locale.get(Key::LyricsShareEmpty).to_string(),
+                ))),
+                Err(e) => {
+                    error!(
+                        "Failed to read lyrics of song {} for sharing: {}",
+                        song_id, e
+                    );
+                    Some(Task::done(Message::ShowErrorToast(
+                        self.core
+                            .locale
+                            .get(Key::LyricsShareFailed)
+                            .replace("{}", e),
+                    )))
+                }
+            },
+
+            Message::ToggleLyricsShareCredit(enabled) => {
+                let credit = self.lyrics_credit().filter(|_| *enabled);
+                if let Some(dialog) = &mut self.ui.dialogs.lyrics_share {
+                    dialog.submission.credit = credit;
+                }
+                Some(Task::none())
+            }
+
+            Message::ConfirmLyricsShare => {
+                let Some(dialog) = self
+                    .ui
+                    .dialogs
+                    .lyrics_share
+                    .as_mut()
+                    .filter(|d| d.stage == LyricsShareStage::Preview)
+                else {
+                    return Some(Task::none());
+                };
+                dialog.stage = LyricsShareStage::Publishing;
+
+                let submission = dialog.submission.clone();
+                let proxy = self.core.settings.network.proxy_url();
+                Some(Task::perform(
+                    async move {
+                        lyrics_share::publish(&submission, proxy)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::LyricsShareFinished,
+                ))
+            }
+
+            Message::LyricsShareFinished(result) => {
+                let Some(dialog) = &mut self.ui.dialogs.lyrics_share else {
+                    return Some(Task::none());
+                };
+                match result {
+                    Ok(()) => {
+                        dialog.stage = LyricsShareStage::Done;
+                        Some(self.remember_lyrics_share())
+                    }
+                    Err(e) => {
+                        error!("Failed to share lyrics on LRCLIB: {}", e);
+                        dialog.stage = LyricsShareStage::Preview;
+                        Some(Task::done(Message::ShowErrorToast(
+                            self.core
+                                .locale
+                                .get(Key::LyricsShareFailed)
+                                .replace("{}", e),
+                        )))
+                    }
+                }
+            }
+
+            Message::CloseLyricsShareDialog => {
+                // Passing on a preview settles the edit as much as sharing it
+                let passed_on = self
+                    .ui
+                    .dialogs
+                    .lyrics_share
+                    .as_ref()
+                    .is_some_and(|d| d.stage == LyricsShareStage::Preview);
+                let task = if passed_on {
+                    self.remember_lyrics_share()
+                } else {
+                    Task::none()
+                };
+                self.ui.dialogs.lyrics_share = None;
+                self.ui.dialogs.lyrics_share_animation.stop();
+                Some(task)
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Check whether a local song's LRC file changed since it was last shared
+    /// or passed on; a file seen for the first time is only remembered
+    pub(super) fn check_lyrics_edit(&self, song_id: SongId) -> Task<Message> {
+        if song_id.is_ncm() {
+            return Task::none();
+        }
+        let song = self
+            .library
+            .current_song
+            .as_ref()
+            .filter(|song| song.id == song_id);
+        let (Some(db), Some(song)) = (self.core.db.clone(), song) else {
+            return Task::none();
+        };
+        let audio_path = song.file_path.clone();
+
+        Task::perform(
+            async move {
+                let path = lyrics_share::lrc_path(Path::new(&audio_path))?;
+                let lrc = tokio::fs::read_to_string(path).await.ok()?;
+                let hash = lyrics_share::content_hash(&lrc);
+                match db.get_lyrics_share_hash(song_id).await {
+                    Ok(Some(seen)) => Some(seen != hash),
+                    Ok(None) => {
+                        if let Err(e) = db.save_lyrics_share_hash(song_id, &hash).await {
+                            warn!("Failed to remember lyrics of song {}: {}", song_id, e);
+                        }
+                        Some(false)
+                    }
+                    Err(e) => {
+                        warn!("Failed to check lyrics of song {}: {}", song_id, e);
+                        Some(false)
+                    }
+                }
+            },
+            move |edited| Message::LyricsShareChecked(song_id, edited),
+        )
+    }
+
+    /// Name to credit from settings, if one is set
+    fn lyrics_credit(&self) -> Option<String> {
+        let credit = self.core.settings.display.lyrics_share.credit.trim();
+        (!credit.is_empty()).then(|| credit.to_string())
+    }
+
+    /// Settle the lyrics in the dialog so they aren't offered again
+    fn remember_lyrics_share(&mut self) -> Task<Message> {
+        let Some(dialog) = &self.ui.dialogs.lyrics_share else {
+            return Task::none();
+        };
+        let song_id = dialog.song_id;
+        let hash = dialog.content_hash.clone();
+        if self
+            .ui
+            .lyrics
+            .shareable
+            .is_some_and(|(id, _)| id == song_id)
+        {
+            self.ui.lyrics.shareable = Some((song_id, false));
+        }
+
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                if let Err(e) = db.save_lyrics_share_hash(song_id, &hash).await {
+                    error!(
+                        "Failed to remember shared lyrics of song {}: {}",
+                        song_id, e
+                    );
+                }
+            },
+            |_| Message::NoOp,
+        )
+    }
+}
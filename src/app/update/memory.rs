@@ -0,0 +1,135 @@
+//! Memory overlay and soft budget enforcement
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::{App, Route};
+use crate::features::jobs::{self, Category, Job};
+use crate::features::lyrics::engine::sdf_cache;
+use crate::features::memory::{self, Budget};
+
+impl App {
+    /// Handle memory overlay and sampling messages
+    pub fn handle_memory(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::ToggleMemoryOverlay => {
+                self.ui.memory.overlay_visible = !self.ui.memory.overlay_visible;
+                if self.ui.memory.overlay_visible {
+                    return Some(self.sample_memory());
+                }
+                Some(Task::none())
+            }
+
+            Message::SampleMemory => Some(self.sample_memory()),
+
+            Message::MemorySampled(report) => {
+                self.ui.memory.sampling = false;
+                let Some(report) = report.clone() else {
+                    return Some(Task::none());
+                };
+                let exceeded = report.exceeded();
+                self.ui.memory.report = Some(report);
+                if exceeded.is_empty() {
+                    return Some(Task::none());
+                }
+                Some(self.enforce_memory_budgets(&exceeded))
+            }
+
+            _ => None,
+        }
+    }
+
+    fn sample_memory(&mut self) -> Task<Message> {
+        if self.ui.memory.sampling {
+            return Task::none();
+        }
+        self.ui.memory.sampling = true;
+
+        let snapshot = self.memory_snapshot();
+        Task::perform(
+            Job::new(Category::Disk).run_blocking(move || memory::sample(snapshot)),
+            Message::MemorySampled,
+        )
+    }
+
+    /// What the sampler can't see from outside the app state
+    fn memory_snapshot(&self) -> memory::Snapshot {
+        let discover = &self.ui.discover;
+        // Same file names as download_playlist_cover
+        let covers_dir = crate::utils::covers_cache_dir();
+        let pinned_images = discover
+            .playlist_cover_allocations
+            .keys()
+            .map(|id| covers_dir.join(format!("playlist_{}.jpg", id)))
+            .collect();
+
+        let running_jobs = jobs::metrics()
+            .iter()
+            .map(|(_, metrics)| metrics.running)
+            .sum();
+
+        memory::Snapshot {
+            pinned_images,
+            db_cache_limit_bytes: self
+                .core
+                .db
+                .as_ref()
+                .map(|db| db.cache_capacity_bytes())
+                .unwrap_or(0),
+            counters: vec![
+                ("Library songs", self.library.db_songs.len()),
+                ("Queue", self.library.queue.len()),
+                (
+                    "Playlist rows",
+                    self.ui
+                        .playlist_page
+                        .current
+                        .as_ref()
+                        .map(|playlist| playlist.songs.len())
+                        .unwrap_or(0),
+                ),
+                ("Discover covers", discover.playlist_covers.len()),
+                ("Home covers", self.ui.home.song_covers.len()),
+                ("Lyric lines", self.ui.lyrics.lines.len()),
+                ("Running jobs", running_jobs),
+            ],
+        }
+    }
+
+    /// Evict the caches behind each exceeded budget
+    ///
+    /// Everything evicted here reloads on demand: pinned covers are decoded
+    /// again from the disk cache, glyph bitmaps are regenerated on upload.
+    fn enforce_memory_budgets(&mut self, exceeded: &[Budget]) -> Task<Message> {
+        tracing::info!("Memory over budget {:?}, evicting caches", exceeded);
+        let process = exceeded.contains(&Budget::Process);
+
+        if process || exceeded.contains(&Budget::PinnedImages) {
+            self.unpin_offscreen_covers();
+        }
+        if (process || exceeded.contains(&Budget::PendingGlyphs)) && !self.ui.lyrics.is_loading {
+            sdf_cache::clear_global_cache();
+        }
+
+        match (&self.core.db, process) {
+            (Some(db), true) => {
+                let db = db.clone();
+                Task::future(async move {
+                    if let Err(e) = db.shrink_memory().await {
+                        tracing::warn!("Failed to shrink database memory: {}", e);
+                    }
+                })
+                .discard()
+            }
+            _ => Task::none(),
+        }
+    }
+
+    /// Let go of Discover covers kept in GPU memory while the page is hidden
+    fn unpin_offscreen_covers(&mut self) {
+        if matches!(self.ui.current_route, Route::Discover(_)) {
+            return;
+        }
+        self.ui.discover.playlist_cover_allocations.clear();
+    }
+}
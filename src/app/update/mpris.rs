@@ -2,14 +2,20 @@
 //! Media controls message handlers
 
 use iced::Task;
+use iced::time::Instant;
 use std::time::Duration;
 
 use crate::app::message::Message;
-use crate::app::state::App;
+use crate::app::state::{App, MediaArtwork};
+use crate::features::jobs::{Category, Job};
+use crate::features::media_artwork;
 use crate::platform::media_controls::{
-    MediaCommand, MediaMetadata, MediaPlaybackStatus, MediaState, is_available,
+    MediaCommand, MediaMetadata, MediaPlaybackStatus, MediaState, art_url, is_available,
 };
 
+/// Second play/pause press within this window counts as a double press
+const DOUBLE_PRESS_WINDOW: Duration = Duration::from_millis(400);
+
 impl App {
     /// Handle media controls related messages
     pub fn handle_mpris(&mut self, message: &Message) -> Option<Task<Message>> {
@@ -47,6 +53,22 @@ impl App {
 
             Message::MprisCommand(cmd) => self.handle_mpris_command(cmd),
 
+            Message::MediaArtworkReady(song_id, source, path) => {
+                if let Some(artwork) = self
+                    .core
+                    .media_artwork
+                    .as_mut()
+                    .filter(|artwork| artwork.song_id == *song_id && artwork.source == *source)
+                {
+                    // On failure the previous copy for this song stays up
+                    if let Some(path) = path {
+                        artwork.url = Some(art_url(&path.to_string_lossy()));
+                    }
+                    self.update_mpris_state();
+                }
+                Some(Task::none())
+            }
+
             _ => None,
         }
     }
@@ -78,7 +100,23 @@ impl App {
                 }
             }
 
-            MediaCommand::PlayPause => Some(self.update(Message::TogglePlayback)),
+            MediaCommand::PlayPause => {
+                // A quick second press undoes the first and skips forward,
+                // so single-button headsets can get through podcasts
+                let now = Instant::now();
+                let is_double = self
+                    .core
+                    .last_media_play_pause
+                    .is_some_and(|last| now.duration_since(last) < DOUBLE_PRESS_WINDOW);
+                if is_double {
+                    self.core.last_media_play_pause = None;
+                    let toggle = self.update(Message::TogglePlayback);
+                    let skip = self.update(Message::SkipForward);
+                    return Some(Task::batch([toggle, skip]));
+                }
+                self.core.last_media_play_pause = Some(now);
+                Some(self.update(Message::TogglePlayback))
+            }
 
             MediaCommand::Stop => {
                 if let Some(player) = &self.core.audio {
@@ -111,7 +149,9 @@ impl App {
             }
 
             MediaCommand::SetVolume(volume) => {
-                Some(self.update(Message::SetVolume(*volume as f32)))
+                // Absolute volume from Bluetooth headsets can overshoot
+                let volume = volume.clamp(0.0, 1.0) as f32;
+                Some(self.update(Message::SetVolume(volume)))
             }
 
             MediaCommand::Raise => {
@@ -123,7 +163,7 @@ impl App {
                 }
             }
 
-            MediaCommand::Quit => Some(iced::exit()),
+            MediaCommand::Quit => Some(self.exit_app()),
         }
     }
 
@@ -143,21 +183,33 @@ impl App {
             };
 
             let metadata = if let Some(song) = &self.library.current_song {
-                let art_url = song.cover_path.as_ref().map(|path| {
-                    if path.starts_with("http") {
-                        path.clone()
-                    } else {
-                        format!("file://{}", path)
+                // Only the cached local copy; never a remote URL
+                let art_url = self
+                    .core
+                    .media_artwork
+                    .as_ref()
+                    .filter(|artwork| artwork.song_id == song.id)
+                    .and_then(|artwork| artwork.url.clone());
+
+                // Long-form tracks show the chapter being played
+                let title = match self
+                    .current_chapter_index()
+                    .map(|idx| &self.current_chapters()[idx].title)
+                {
+                    Some(chapter) if !chapter.is_empty() => {
+                        format!("{} - {}", song.title, chapter)
                     }
-                });
+                    _ => song.title.clone(),
+                };
 
                 MediaMetadata {
                     track_id: Some(song.id.to_string()),
-                    title: Some(song.title.clone()),
+                    title: Some(title),
                     artists: vec![song.artist.clone()],
                     album: Some(song.album.clone()),
                     album_artists: vec![],
                     length_us: Some(song.duration_secs as i64 * 1_000_000),
+                    track_number: song.track_number.map(|n| n as i32),
                     art_url,
                 }
             } else {
@@ -201,4 +253,80 @@ impl App {
             handle.update(state);
         }
     }
+
+    /// Start caching the media session artwork if the current song or its
+    /// best cover changed
+    pub(super) fn update_media_artwork(&mut self, task: Task<Message>) -> Task<Message> {
+        if self.core.mpris_handle.is_none() {
+            return task;
+        }
+        let Some(song) = &self.library.current_song else {
+            return task;
+        };
+        let Some(source) = self.ui.lyrics.cover_for(song) else {
+            return task;
+        };
+        let song_id = song.id;
+        if self
+            .core
+            .media_artwork
+            .as_ref()
+            .is_some_and(|artwork| artwork.song_id == song_id && artwork.source == source)
+        {
+            return task;
+        }
+        let remote = source.starts_with("http://") || source.starts_with("https://");
+        if remote && self.core.is_metered() {
+            return task;
+        }
+
+        let source = source.to_string();
+        // A thumbnail's artwork stays up while the large cover's is made
+        let url = self
+            .core
+            .media_artwork
+            .take()
+            .filter(|artwork| artwork.song_id == song_id)
+            .and_then(|artwork| artwork.url);
+        self.core.media_artwork = Some(MediaArtwork {
+            song_id,
+            source: source.clone(),
+            url,
+        });
+
+        let proxy = self.core.settings.network.proxy_url();
+        let cover = source.clone();
+        let cache = Task::perform(
+            Job::new(Category::Cover).song().run(async move {
+                let path = media_artwork::cache_path(song_id, &cover);
+                if path.exists() {
+                    return Some(path);
+                }
+                let bytes = match super::now_playing::load_cover(&cover, proxy).await {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        tracing::warn!("Failed to load media artwork cover: {:#}", e);
+                        return None;
+                    }
+                };
+                let written = tokio::task::spawn_blocking(move || {
+                    media_artwork::write(&path, &bytes).map(|_| path)
+                })
+                .await;
+                match written {
+                    Ok(Ok(path)) => Some(path),
+                    Ok(Err(e)) => {
+                        tracing::warn!("Failed to cache media artwork: {:#}", e);
+                        None
+                    }
+                    Err(e) => {
+                        tracing::warn!("Media artwork writer panicked: {}", e);
+                        None
+                    }
+                }
+            }),
+            move |path| Message::MediaArtworkReady(song_id, source.clone(), path.flatten()),
+        );
+        Task::batch([task, cache])
+    }
 }
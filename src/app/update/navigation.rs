@@ -6,6 +6,7 @@ use iced::Task;
 use crate::app::helpers::open_folder_dialog;
 use crate::app::message::Message;
 use crate::app::state::{App, NavigationEntry};
+use crate::i18n::Key;
 
 impl App {
     /// Handle navigation-related messages
@@ -37,18 +38,22 @@ impl App {
                 Some(self.navigate_to_route(route, true))
             }
 
-            Message::LibrarySelect(_) | Message::OpenSettings | Message::OpenSettingsWithCloseLyrics | Message::OpenAudioEngine => {
+            Message::LibrarySelect(_)
+            | Message::OpenSettings
+            | Message::OpenSettingsWithCloseLyrics
+            | Message::OpenAudioEngine
+            | Message::OpenPlugins
+            | Message::OpenLogs
+            | Message::OpenAnnualReport
+            | Message::OpenBlocklist
+            | Message::OpenTrash
+            | Message::OpenUserProfile(_) => {
                 let Some(route) = self.route_for_message(message) else {
                     return Some(Task::none());
                 };
                 Some(self.navigate_to_route(route, true))
             }
 
-            Message::SearchChanged(query) => {
-                self.ui.search_query = query.clone();
-                Some(Task::none())
-            }
-
             Message::PlayHero => {
                 tracing::info!("Playing Global Hits 2024");
                 Some(Task::none())
@@ -56,33 +61,46 @@ impl App {
 
             Message::ImportLocalPlaylist => {
                 tracing::info!("Import local playlist");
-                Some(Task::perform(open_folder_dialog(), Message::FolderSelected))
+                Some(Task::perform(
+                    open_folder_dialog(self.core.locale.get(Key::ImportSelectFolder)),
+                    Message::FolderSelected,
+                ))
             }
 
             Message::WindowMinimize => {
-                Some(iced::window::latest().and_then(|id| iced::window::minimize(id, true)))
+                Some(iced::window::oldest().and_then(|id| iced::window::minimize(id, true)))
             }
 
             Message::WindowMaximize => {
-                Some(iced::window::latest().and_then(|id| iced::window::toggle_maximize(id)))
+                Some(iced::window::oldest().and_then(|id| iced::window::toggle_maximize(id)))
             }
 
-            Message::MouseMoved(position) => {
+            Message::MouseMoved(id, position) => {
+                // The lyrics window has a title bar of its own
+                if self.is_lyrics_window(*id) {
+                    return Some(Task::none());
+                }
                 self.core.mouse_position = *position;
                 // Update sidebar width if dragging
                 if self.ui.sidebar_dragging {
                     const MIN_WIDTH: f32 = 200.0;
                     const MAX_WIDTH: f32 = 400.0;
-                    self.ui.sidebar_width = position.x.clamp(MIN_WIDTH, MAX_WIDTH);
+                    // Right-to-left layouts put the sidebar on the right edge
+                    let width = if self.core.locale.is_rtl() {
+                        self.ui.window_width - position.x
+                    } else {
+                        position.x
+                    };
+                    self.ui.sidebar_width = width.clamp(MIN_WIDTH, MAX_WIDTH);
                 }
                 Some(Task::none())
             }
 
-            Message::MousePressed => {
+            Message::MousePressed(id) => {
                 // Drag window if mouse is in top 48px area (title bar)
                 const DRAG_AREA_HEIGHT: f32 = 48.0;
-                if self.core.mouse_position.y < DRAG_AREA_HEIGHT {
-                    Some(iced::window::latest().and_then(|id| iced::window::drag(id)))
+                if !self.is_lyrics_window(*id) && self.core.mouse_position.y < DRAG_AREA_HEIGHT {
+                    Some(iced::window::drag(*id))
                 } else {
                     Some(Task::none())
                 }
@@ -1,19 +1,57 @@
 //! NCM (Netease Cloud Music) related message handlers
 
 use iced::Task;
+use iced::time::Instant;
 use std::time::Duration;
 use tracing::{debug, error, info};
 
-use crate::api::{LoginInfo, NcmClient};
+use crate::api::{LoginInfo, NcmClient, SongInfo};
 use crate::app::message::QrLoginStatus;
 use crate::app::state::UserInfo;
+use crate::app::update::page_loader;
 use crate::app::{App, Message, Route};
-use crate::i18n::Key;
+use crate::audio::UiSound;
+use crate::database::{DbSong, SongId};
+use crate::features::jobs::{Category, Job};
+use crate::features::media::palette;
+use crate::features::song_overrides;
+use crate::i18n::{Key, Locale};
+
+use super::api_error::failure_message;
+
+/// Queue entry for an NCM song, resolved to a stream when played
+pub(super) fn queue_song_from_ncm(song: &SongInfo) -> DbSong {
+    let mut queue_song = DbSong {
+        id: SongId::ncm(song.id),
+        file_path: String::new(),
+        title: song.name.clone(),
+        artist: song.singer.clone(),
+        album: song.album.clone(),
+        duration_secs: (song.duration / 1000) as i64,
+        track_number: None,
+        year: None,
+        genre: None,
+        cover_path: if song.pic_url.is_empty() {
+            None
+        } else {
+            Some(song.pic_url.clone())
+        },
+        file_hash: None,
+        file_size: 0,
+        format: Some("mp3".to_string()),
+        play_count: 0,
+        last_played: None,
+        last_modified: 0,
+        created_at: 0,
+    };
+    song_overrides::apply(&mut queue_song);
+    queue_song
+}
 
 impl App {
     /// Set the NCM client and sync quality settings
-    fn set_ncm_client(&mut self, client: NcmClient) {
-        client.set_quality(self.core.settings.playback.music_quality.to_api_rate());
+    pub(super) fn set_ncm_client(&mut self, client: NcmClient) {
+        client.set_quality(self.core.stream_quality().to_api_rate());
         self.core.ncm_client = Some(client);
     }
 
@@ -52,6 +90,12 @@ impl App {
         }
     }
 
+    /// Ask before opening a link in the system browser
+    fn request_open_link(&mut self, url: String) {
+        self.ui.dialogs.open_link_pending = Some(url);
+        self.ui.dialogs.open_link_animation.start();
+    }
+
     pub(super) fn open_ncm_playlist_route(&mut self, playlist_id: u64) -> Task<Message> {
         let is_daily_recommend = playlist_id == 0;
 
@@ -74,6 +118,12 @@ impl App {
         debug!("Opening NCM playlist: {}", playlist_id);
         self.reset_playlist_page_state();
 
+        if !is_daily_recommend {
+            if let Some(task) = self.reopen_ncm_playlist(playlist_id) {
+                return task;
+            }
+        }
+
         let (name, owner, cover_url) = if is_daily_recommend {
             let locale = &self.core.locale;
             (
@@ -92,7 +142,8 @@ impl App {
                 .iter()
                 .find(|p| p.id == playlist_id)
                 .map(|p| (p.name.clone(), p.author.clone(), p.cover_img_url.clone()))
-                .unwrap_or_else(|| ("加载中...".to_string(), String::new(), String::new()))
+                // An empty name shimmers in the skeleton header
+                .unwrap_or_default()
         };
 
         let internal_id = if is_daily_recommend {
@@ -110,7 +161,7 @@ impl App {
             owner_avatar_path: None,
             creator_id: 0,
             song_count: 0,
-            total_duration: String::new(),
+            total_duration_secs: 0,
             like_count: String::new(),
             songs: Vec::new(),
             palette: crate::utils::ColorPalette::default(),
@@ -118,9 +169,10 @@ impl App {
             is_subscribed: false,
         };
 
-        self.ui.playlist_page.current = Some(skeleton_view);
+        self.ui.playlist_page.show(skeleton_view);
         self.ui.playlist_page.load_state =
             crate::app::update::page_loader::PlaylistLoadState::Loading;
+        self.ui.playlist_page.skeleton_since = Some(Instant::now());
 
         let cover_task = if !cover_url.is_empty() {
             if let Some(client) = &self.core.ncm_client {
@@ -146,7 +198,9 @@ impl App {
             Task::none()
         };
 
-        let api_task = if let Some(client) = &self.core.ncm_client {
+        let api_task = if let Some(task) = self.show_liked_mirror(playlist_id) {
+            task
+        } else if let Some(client) = &self.core.ncm_client {
             let client = client.clone();
             if is_daily_recommend {
                 let locale = &self.core.locale;
@@ -159,23 +213,30 @@ impl App {
                 let creator = locale
                     .get(crate::i18n::Key::DiscoverDailyRecommendCreator)
                     .to_string();
-                Task::perform(
+                let failed = locale.get(Key::DiscoverLoadFailed);
+                let blocklist = self.library.blocklist.clone();
+                let (task, handle) = Task::perform(
                     async move {
                         match client.client.recommend_songs().await {
-                            Ok(songs) => Some(crate::api::PlayListDetail {
-                                id: 0,
-                                name,
-                                cover_img_url: String::new(),
-                                description: desc,
-                                create_time: 0,
-                                track_update_time: 0,
-                                creator_id: 0,
-                                creator_nickname: creator,
-                                creator_avatar_url: String::new(),
-                                track_count: songs.len() as u64,
-                                subscribed: false,
-                                songs,
-                            }),
+                            Ok(mut songs) => {
+                                songs.retain(|song| {
+                                    !blocklist.blocks(SongId::ncm(song.id), &song.singer)
+                                });
+                                Some(crate::api::PlayListDetail {
+                                    id: 0,
+                                    name,
+                                    cover_img_url: String::new(),
+                                    description: desc,
+                                    create_time: 0,
+                                    track_update_time: 0,
+                                    creator_id: 0,
+                                    creator_nickname: creator,
+                                    creator_avatar_url: String::new(),
+                                    track_count: songs.len() as u64,
+                                    subscribed: false,
+                                    songs,
+                                })
+                            }
                             Err(e) => {
                                 error!("Failed to load daily recommend: {:?}", e);
                                 None
@@ -186,35 +247,28 @@ impl App {
                         if let Some(detail) = result {
                             Message::NcmPlaylistDetailLoaded(detail)
                         } else {
-                            Message::ShowErrorToast("加载每日推荐失败".to_string())
+                            Message::ShowErrorToast(failed.to_string())
                         }
                     },
                 )
+                .abortable();
+                self.ui.playlist_page.song_pages = Some(handle.abort_on_drop());
+                task
             } else {
-                Task::perform(
-                    async move {
-                        match client.client.song_list_detail(playlist_id).await {
-                            Ok(detail) => Some(detail),
-                            Err(e) => {
-                                error!("Failed to load NCM playlist detail: {:?}", e);
-                                None
-                            }
-                        }
-                    },
-                    move |result| {
-                        if let Some(detail) = result {
-                            Message::NcmPlaylistDetailLoaded(detail)
-                        } else {
-                            Message::ShowErrorToast("加载歌单失败".to_string())
-                        }
-                    },
+                let (task, handle) = Task::run(
+                    ncm_playlist_pages(client, playlist_id, internal_id, self.core.locale),
+                    |msg| msg,
                 )
+                .abortable();
+                // Opening another playlist drops the handle and stops the remaining batches
+                self.ui.playlist_page.song_pages = Some(handle.abort_on_drop());
+                task
             }
         } else {
             Task::none()
         };
 
-        Task::batch([cover_task, api_task])
+        Task::batch([cover_task, api_task, self.playlist_shown()])
     }
 
     /// Handle NCM-related messages
@@ -223,14 +277,9 @@ impl App {
             Message::TryAutoLogin(retry_count) => {
                 let retry = *retry_count;
                 let proxy_url = self.core.settings.network.proxy_url();
-                if let Some((cookie_jar, csrf_token)) =
-                    NcmClient::load_cookie_jar_from_file()
-                {
-                    let client = NcmClient::from_cookie_jar_with_proxy(
-                        cookie_jar,
-                        csrf_token,
-                        proxy_url,
-                    );
+                if let Some((cookie_jar, csrf_token)) = NcmClient::load_cookie_jar_from_file() {
+                    let client =
+                        NcmClient::from_cookie_jar_with_proxy(cookie_jar, csrf_token, proxy_url);
                     self.set_ncm_client(client.clone());
 
                     Some(Task::perform(
@@ -247,7 +296,10 @@ impl App {
                     ))
                 } else {
                     self.set_ncm_client(NcmClient::with_proxy(proxy_url));
-                    Some(self.load_homepage_data())
+                    Some(Task::batch([
+                        self.load_homepage_data(),
+                        Task::done(Message::RefreshSession),
+                    ]))
                 }
             }
 
@@ -274,25 +326,8 @@ impl App {
 
                     Some(Task::batch([
                         self.load_homepage_data(),
-                        Task::perform(
-                            {
-                                let client = client.clone();
-                                async move {
-                                    if let Some(client) = client {
-                                        if let Ok(song_ids) =
-                                            client.client.user_song_id_list(uid).await
-                                        {
-                                            let mut user_info =
-                                                UserInfo::new(uid, String::new(), String::new());
-                                            user_info.like_songs = song_ids.into_iter().collect();
-                                            return user_info;
-                                        }
-                                    }
-                                    UserInfo::new(uid, String::new(), String::new())
-                                }
-                            },
-                            Message::UserInfoLoaded,
-                        ),
+                        Task::done(Message::RunDailyCheckIn),
+                        Task::done(Message::SyncFavorites),
                         Task::perform(
                             {
                                 let client = client.clone();
@@ -344,45 +379,57 @@ impl App {
 
             Message::RequestQrCode => {
                 self.ui.home.login_popup_open = true;
-                self.ui.home.qr_status = Some("正在生成二维码...".to_string());
+                self.ui.home.qr_status =
+                    Some(self.core.locale.get(Key::LoginGeneratingQr).to_string());
                 // Clear old QR code data to force refresh
-                self.ui.home.qr_code_path = None;
+                self.ui.home.qr_modules = None;
                 self.ui.home.qr_unikey = None;
 
                 let client = self.core.ncm_client.clone().unwrap_or_default();
 
+                let failed = self.core.locale.get(Key::LoginQrFailed);
                 Some(Task::perform(
                     async move {
                         match client.create_qrcode().await {
-                            Ok((path, unikey)) => Some((path, unikey)),
+                            Ok((modules, unikey)) => Some((modules, unikey)),
                             Err(e) => {
                                 error!("Failed to create QR code: {:?}", e);
                                 None
                             }
                         }
                     },
-                    |result| {
-                        if let Some((path, unikey)) = result {
-                            Message::QrCodeReady(path, unikey)
+                    move |result| {
+                        if let Some((modules, unikey)) = result {
+                            Message::QrCodeReady(modules, unikey)
                         } else {
-                            Message::ShowErrorToast("生成二维码失败".to_string())
+                            Message::ShowErrorToast(failed.to_string())
                         }
                     },
                 ))
             }
 
-            Message::QrCodeReady(path, unikey) => {
-                self.ui.home.qr_code_path = Some(path.clone());
+            Message::QrCodeReady(modules, unikey) => {
+                // A code arriving after the popup was closed is already stale
+                if !self.ui.home.login_popup_open {
+                    return Some(Task::none());
+                }
+                self.ui.home.qr_modules = Some(modules.clone());
                 self.ui.home.qr_unikey = Some(unikey.clone());
-                self.ui.home.qr_status = Some("请使用网易云音乐App扫码登录".to_string());
+                self.ui.home.qr_status =
+                    Some(self.core.locale.get(Key::LoginScanWithApp).to_string());
 
                 let unikey = unikey.clone();
                 Some(Task::done(Message::CheckQrStatus(unikey)))
             }
 
             Message::CheckQrStatus(unikey) => {
+                // Stop polling once the popup is closed or the code replaced,
+                // and while asleep; waking up starts over with a new code
                 let current_unikey = self.ui.home.qr_unikey.clone();
-                if current_unikey.as_ref() != Some(unikey) {
+                if !self.ui.home.login_popup_open
+                    || current_unikey.as_ref() != Some(unikey)
+                    || self.core.suspended
+                {
                     return Some(Task::none());
                 }
 
@@ -408,7 +455,8 @@ impl App {
 
             Message::QrLoginResult(status) => match status {
                 QrLoginStatus::WaitingForScan => {
-                    self.ui.home.qr_status = Some("等待扫码...".to_string());
+                    self.ui.home.qr_status =
+                        Some(self.core.locale.get(Key::LoginWaitingForScan).to_string());
                     let unikey = self.ui.home.qr_unikey.clone();
                     if let Some(unikey) = unikey {
                         Some(Task::perform(
@@ -423,7 +471,8 @@ impl App {
                     }
                 }
                 QrLoginStatus::WaitingForConfirm => {
-                    self.ui.home.qr_status = Some("已扫码，请在App中确认登录".to_string());
+                    self.ui.home.qr_status =
+                        Some(self.core.locale.get(Key::LoginScanned).to_string());
                     let unikey = self.ui.home.qr_unikey.clone();
                     if let Some(unikey) = unikey {
                         Some(Task::perform(
@@ -438,14 +487,18 @@ impl App {
                     }
                 }
                 QrLoginStatus::Expired => {
-                    self.ui.home.qr_status = Some("二维码已过期，请刷新".to_string());
-                    self.ui.home.login_popup_open = false;
-                    Some(Task::done(Message::ShowErrorToast(
-                        "二维码已过期".to_string(),
-                    )))
+                    // Swap in a fresh code while the popup is showing
+                    self.ui.home.qr_modules = None;
+                    self.ui.home.qr_unikey = None;
+                    if self.ui.home.login_popup_open {
+                        Some(Task::done(Message::RequestQrCode))
+                    } else {
+                        Some(Task::none())
+                    }
                 }
                 QrLoginStatus::Success => {
-                    self.ui.home.qr_status = Some("登录成功！".to_string());
+                    self.ui.home.qr_status =
+                        Some(self.core.locale.get(Key::LoginSuccess).to_string());
 
                     if let Some(client) = &self.core.ncm_client {
                         let client = client.clone();
@@ -468,12 +521,12 @@ impl App {
                     Some(Task::none())
                 }
                 QrLoginStatus::Error(err) => {
-                    self.ui.home.qr_status = Some(format!("登录错误: {}", err));
+                    self.ui.home.qr_status =
+                        Some(self.core.locale.get(Key::LoginError).replace("{}", err));
                     self.ui.home.login_popup_open = false;
-                    Some(Task::done(Message::ShowErrorToast(format!(
-                        "登录失败: {}",
-                        err
-                    ))))
+                    Some(Task::done(Message::ShowErrorToast(
+                        self.core.locale.get(Key::LoginFailed).replace("{}", err),
+                    )))
                 }
             },
 
@@ -495,8 +548,12 @@ impl App {
                 let avatar_url = login_info.avatar_url.clone();
 
                 Some(Task::batch([
-                    Task::done(Message::ShowToast("登录成功！".to_string())),
+                    Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::LoginSuccess).to_string(),
+                    )),
                     self.load_homepage_data(),
+                    Task::done(Message::RunDailyCheckIn),
+                    Task::done(Message::SyncFavorites),
                     Task::perform(
                         async move {
                             if let Some(client) = client {
@@ -537,24 +594,34 @@ impl App {
                 NcmClient::clean_cookie_file();
                 self.core.is_logged_in = false;
                 self.core.user_info = None;
+                self.ui.check_in_history.clear();
+                // The account's playlists and its saved likes leave with it
+                self.ui.home.user_playlists.clear();
+                self.library.liked_mirror = None;
+                self.library.liked_sync = None;
+                if let Some(db) = self.core.db.clone() {
+                    Job::new(Category::Database).spawn(async move {
+                        if let Err(e) = db.clear_liked_mirror().await {
+                            error!("Failed to clear liked songs mirror: {}", e);
+                        }
+                    });
+                }
                 let proxy_url = self.core.settings.network.proxy_url();
                 self.set_ncm_client(NcmClient::with_proxy(proxy_url));
 
-                Some(Task::done(Message::ShowToast("已退出登录".to_string())))
-            }
-
-            Message::UserInfoLoaded(user_info) => {
-                if let Some(existing) = &mut self.core.user_info {
-                    existing.like_songs = user_info.like_songs.clone();
-                } else {
-                    self.core.user_info = Some(user_info.clone());
-                }
-                Some(Task::none())
+                Some(Task::batch([
+                    Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::LoginLoggedOut).to_string(),
+                    )),
+                    Task::done(Message::RefreshSession),
+                ]))
             }
 
             Message::ToggleLoginPopup => {
                 self.ui.home.login_popup_open = !self.ui.home.login_popup_open;
-                if self.ui.home.login_popup_open && self.ui.home.qr_code_path.is_none() {
+                // Polling stops while the popup is closed, so reopening needs a
+                // fresh code
+                if self.ui.home.login_popup_open && !self.core.is_logged_in {
                     Some(Task::done(Message::RequestQrCode))
                 } else {
                     Some(Task::none())
@@ -573,7 +640,7 @@ impl App {
                         let target_id = banner.target_id;
 
                         tasks.push(Task::perform(
-                            async move {
+                            Job::new(Category::Cover).run(async move {
                                 if let Some(path) =
                                     crate::utils::download_banner(&client, target_id, &pic_url)
                                         .await
@@ -596,9 +663,9 @@ impl App {
                                 } else {
                                     None
                                 }
-                            },
+                            }),
                             |result| {
-                                if let Some((idx, path, w, h)) = result {
+                                if let Some((idx, path, w, h)) = result.flatten() {
                                     Message::BannerImageLoaded(idx, path, w, h)
                                 } else {
                                     Message::NoOp
@@ -633,6 +700,7 @@ impl App {
                             let song_id = banner.target_id;
                             if let Some(client) = &self.core.ncm_client {
                                 let client = client.clone();
+                                let unavailable = self.core.locale.get(Key::NcmSongInfoUnavailable);
                                 return Some(Task::perform(
                                     async move {
                                         match client.song_detail(&[song_id]).await {
@@ -643,18 +711,30 @@ impl App {
                                             }
                                         }
                                     },
-                                    |song_opt| {
+                                    move |song_opt| {
                                         if let Some(song) = song_opt {
                                             Message::PlayNcmSong(song)
                                         } else {
-                                            Message::ShowToast("无法获取歌曲信息".to_string())
+                                            Message::ShowToast(unavailable.to_string())
                                         }
                                     },
                                 ));
                             }
                         }
                         crate::api::TargetType::Album => {
-                            debug!("Album playback from banner not implemented yet");
+                            return Some(Task::done(Message::PlayNewRelease(banner.target_id)));
+                        }
+                        crate::api::TargetType::Playlist => {
+                            return Some(Task::done(Message::OpenNcmPlaylist(banner.target_id)));
+                        }
+                        // There is no in-app video player, so MVs open on the web
+                        crate::api::TargetType::Mv => {
+                            let url = format!("https://music.163.com/#/mv?id={}", banner.target_id);
+                            self.request_open_link(url);
+                        }
+                        crate::api::TargetType::Web if !banner.url.is_empty() => {
+                            let url = banner.url.clone();
+                            self.request_open_link(url);
                         }
                         _ => {
                             debug!("Unsupported banner target type: {:?}", banner.target_type);
@@ -664,11 +744,32 @@ impl App {
                 Some(Task::none())
             }
 
+            Message::ConfirmOpenLink => {
+                self.ui.dialogs.open_link_animation.stop();
+                if let Some(url) = self.ui.dialogs.open_link_pending.take() {
+                    if let Err(e) = crate::platform::shell::open_url(&url) {
+                        error!("Failed to open link {}: {}", url, e);
+                        return Some(Task::done(Message::ShowErrorToast(
+                            self.core.locale.get(Key::OpenLinkFailed).to_string(),
+                        )));
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::CancelOpenLink => {
+                self.ui.dialogs.open_link_pending = None;
+                self.ui.dialogs.open_link_animation.stop();
+                Some(Task::none())
+            }
+
             Message::ToggleBannerFavorite(index) => {
                 if let Some(banner) = self.ui.home.banners.get(*index) {
                     match banner.target_type {
                         crate::api::TargetType::Song => {
-                            return Some(self.update(Message::ToggleFavorite(banner.target_id)));
+                            return Some(
+                                self.update(Message::ToggleFavorite(SongId::ncm(banner.target_id))),
+                            );
                         }
                         _ => {
                             debug!(
@@ -715,42 +816,17 @@ impl App {
 
             Message::TopPicksLoaded(playlists) => {
                 self.ui.home.top_picks = playlists.clone();
-                Some(Task::none())
+                Some(self.preload_cached_covers(playlists))
             }
 
             Message::TrendingSongsLoaded(songs) => {
-                self.ui.home.trending_songs = songs.clone();
-
-                if let Some(client) = &self.core.ncm_client {
-                    let mut tasks = Vec::new();
-                    for song in songs.iter().take(10) {
-                        let client = client.clone();
-                        let pic_url = song.pic_url.clone();
-                        let song_id = song.id;
-
-                        tasks.push(Task::perform(
-                            async move {
-                                if let Some(path) =
-                                    crate::utils::download_cover(&client, song_id, &pic_url).await
-                                {
-                                    Some((song_id, path))
-                                } else {
-                                    None
-                                }
-                            },
-                            |result| {
-                                if let Some((id, path)) = result {
-                                    Message::SongCoverLoaded(id, path)
-                                } else {
-                                    Message::NoOp
-                                }
-                            },
-                        ));
-                    }
-                    Some(Task::batch(tasks))
-                } else {
-                    Some(Task::none())
-                }
+                let blocklist = &self.library.blocklist;
+                self.ui.home.trending_songs = songs
+                    .iter()
+                    .filter(|song| !blocklist.blocks(SongId::ncm(song.id), &song.singer))
+                    .cloned()
+                    .collect();
+                Some(Task::none())
             }
 
             Message::OpenTrendingSongs => Some(self.navigate_to_route(
@@ -765,81 +841,13 @@ impl App {
                 Some(Task::none())
             }
 
-            Message::ToggleFavorite(song_id) => {
-                if !self.core.is_logged_in {
-                    return Some(Task::done(Message::ShowToast("请先登录".to_string())));
-                }
-
-                if let Some(client) = &self.core.ncm_client {
-                    let client = client.clone();
-                    let is_liked = if let Some(ref user_info) = self.core.user_info {
-                        user_info.like_songs.contains(&song_id)
-                    } else {
-                        false
-                    };
-                    let song_id = *song_id;
-
-                    Some(Task::perform(
-                        async move {
-                            match client.client.like_song(song_id, !is_liked).await {
-                                Ok(_) => Some(!is_liked),
-                                Err(e) => {
-                                    error!("Failed to toggle like: {}", e);
-                                    None
-                                }
-                            }
-                        },
-                        move |result| {
-                            if let Some(liked) = result {
-                                Message::FavoriteStatusChanged(song_id, liked)
-                            } else {
-                                Message::ShowToast("操作失败".to_string())
-                            }
-                        },
-                    ))
-                } else {
-                    Some(Task::none())
-                }
-            }
-
-            Message::FavoriteStatusChanged(song_id, liked) => {
-                if let Some(ref mut user_info) = self.core.user_info {
-                    if *liked {
-                        user_info.like_songs.insert(*song_id);
-                    } else {
-                        user_info.like_songs.remove(song_id);
-                    }
-                }
-
-                // Update tray state if this is the current song
-                if let Some(current) = &self.library.current_song {
-                    if current.id < 0 && (-current.id) as u64 == *song_id {
-                        let is_playing = self
-                            .core
-                            .audio
-                            .as_ref()
-                            .map(|p| p.is_playing())
-                            .unwrap_or(false);
-                        crate::app::helpers::update_tray_state_with_favorite(
-                            is_playing,
-                            Some(current.title.clone()),
-                            Some(current.artist.clone()),
-                            self.core.settings.play_mode,
-                            Some(*song_id),
-                            *liked,
-                        );
-                    }
-                }
-
-                Some(Task::none())
-            }
-
             Message::PlayNcmSong(song_info) => {
                 debug!("Playing NCM song: {}", song_info.name);
 
                 if let Some(client) = &self.core.ncm_client {
                     let client = client.clone();
                     let song_info_clone = song_info.clone();
+                    let unplayable = self.core.locale.get(Key::NcmSongUnplayable);
 
                     Some(Task::perform(
                         async move {
@@ -926,16 +934,18 @@ impl App {
                                 }
                             }
                         },
-                        |result| {
+                        move |result| {
                             if let Some((song_info, path, cover_path)) = result {
                                 Message::PlayNcmUrl(song_info, path, cover_path)
                             } else {
-                                Message::ShowToast("无法播放歌曲".to_string())
+                                Message::ShowToast(unplayable.to_string())
                             }
                         },
                     ))
                 } else {
-                    Some(Task::done(Message::ShowToast("请先登录".to_string())))
+                    Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::LoginRequired).to_string(),
+                    )))
                 }
             }
 
@@ -953,8 +963,8 @@ impl App {
                     }
                 });
 
-                let temp_song = crate::database::DbSong {
-                    id: -(song_info.id as i64),
+                let mut temp_song = crate::database::DbSong {
+                    id: SongId::ncm(song_info.id),
                     file_path: url.clone(),
                     title: song_info.name.clone(),
                     artist: song_info.singer.clone(),
@@ -983,10 +993,12 @@ impl App {
                         .unwrap()
                         .as_secs() as i64,
                 };
+                song_overrides::apply(&mut temp_song);
 
                 if let Some(db) = &self.core.db {
                     let db = db.clone();
                     let song_clone = temp_song.clone();
+                    let db_error = self.core.locale.get(Key::NcmDatabaseError);
                     Some(Task::perform(
                         async move {
                             match db.upsert_ncm_song(&song_clone).await {
@@ -1003,7 +1015,7 @@ impl App {
                                 final_song.id = id;
                                 Message::PlayResolvedNcmSong(final_song)
                             } else {
-                                Message::ShowToast("数据库错误".to_string())
+                                Message::ShowToast(db_error.to_string())
                             }
                         },
                     ))
@@ -1018,17 +1030,14 @@ impl App {
                     player.play(path);
                     info!("Started playing resolved NCM song: {}", song.title);
 
+                    self.set_quality_override(None);
                     self.library.current_song = Some(song.clone());
                     self.library.queue.clear();
                     self.library.queue.push(song.clone());
                     self.library.queue_index = Some(0);
 
                     if let Some(db) = &self.core.db {
-                        let db = db.clone();
-                        let song_id = song.id;
-                        tokio::spawn(async move {
-                            let _ = db.record_play(song_id, 0, false).await;
-                        });
+                        db.record_play(song.id, 0, false);
                     }
 
                     self.update_mpris_state();
@@ -1045,71 +1054,50 @@ impl App {
                     play_now
                 );
 
-                let db_songs: Vec<crate::database::DbSong> = songs
-                    .iter()
-                    .map(|song| crate::database::DbSong {
-                        id: -(song.id as i64),
-                        file_path: String::new(),
-                        title: song.name.clone(),
-                        artist: song.singer.clone(),
-                        album: song.album.clone(),
-                        duration_secs: (song.duration / 1000) as i64,
-                        track_number: None,
-                        year: None,
-                        genre: None,
-                        cover_path: if song.pic_url.is_empty() {
-                            None
+                let mut db_songs: Vec<crate::database::DbSong> =
+                    songs.iter().map(queue_song_from_ncm).collect();
+
+                if self.is_fm_mode() {
+                    let blocklist = &self.library.blocklist;
+                    db_songs.retain(|song| !blocklist.blocks(song.id, &song.artist));
+                    if db_songs.is_empty() {
+                        debug!("FM mode: every fetched song is blocked");
+                        return Some(if *play_now {
+                            self.fetch_more_fm_songs_and_play()
                         } else {
-                            Some(song.pic_url.clone())
-                        },
-                        file_hash: None,
-                        file_size: 0,
-                        format: Some("mp3".to_string()),
-                        play_count: 0,
-                        last_played: None,
-                        last_modified: 0,
-                        created_at: 0,
-                    })
-                    .collect();
+                            Task::none()
+                        });
+                    }
+                }
 
                 if self.is_fm_mode() && !*play_now {
                     debug!("FM mode: appending {} songs to queue", db_songs.len());
                     self.library.queue.extend(db_songs.clone());
 
                     if let Some(db) = &self.core.db {
-                        let db = db.clone();
-                        let queue_clone = self.library.queue.clone();
-                        tokio::spawn(async move {
-                            let _ = db.save_queue_with_songs(&queue_clone, None).await;
-                        });
+                        db.save_queue_with_songs(self.library.queue.clone(), None);
                     }
                     return Some(Task::none());
                 }
 
                 if *play_now {
+                    self.set_quality_override(None);
                     self.library.queue = db_songs.clone();
                     self.library.queue_index = Some(0);
 
                     // Save queue to database
                     if let Some(db) = &self.core.db {
-                        let db = db.clone();
-                        let queue_clone = db_songs;
-                        tokio::spawn(async move {
-                            let _ = db.save_queue_with_songs(&queue_clone, None).await;
-                        });
+                        db.save_queue_with_songs(db_songs, None);
                     }
 
                     return Some(self.update(Message::PlayQueueIndex(0)));
                 } else {
                     self.library.queue.extend(db_songs);
+                    self.play_ui_sound(UiSound::QueueAdd);
 
                     // Save updated queue to database
                     if let Some(db) = &self.core.db {
-                        let db = db.clone();
-                        let queue_clone = self.library.queue.clone();
-                        tokio::spawn(async move {
-                            let _ = db.save_queue_with_songs(&queue_clone, None).await;
-                        });
+                        db.save_queue_with_songs(self.library.queue.clone(), None);
                     }
                 }
 
@@ -1118,7 +1106,12 @@ impl App {
 
             Message::UserPlaylistsLoaded(playlists) => {
                 self.ui.home.user_playlists = playlists.clone();
-                Some(Task::none())
+                // A failed load leaves the saved liked songs reachable
+                self.show_liked_playlist_offline();
+                if playlists.is_empty() {
+                    return Some(Task::none());
+                }
+                Some(Task::done(Message::SyncLikedMirror))
             }
 
             Message::HoverTrendingSong(song_id_opt) => {
@@ -1130,6 +1123,7 @@ impl App {
             }
 
             Message::OpenNcmPlaylist(playlist_id) => {
+                self.take_off_pressed_cover();
                 let route = Route::NcmPlaylist(*playlist_id);
                 if self.ui.current_route != route {
                     return Some(self.navigate_to_route(route, true));
@@ -1146,17 +1140,7 @@ impl App {
                 );
 
                 let playlist_id = -(detail.id as i64);
-
-                // Calculate total duration
-                let total_secs: u64 = detail.songs.iter().map(|s| s.duration / 1000).sum();
-                let total_mins = total_secs / 60;
-                let total_hours = total_mins / 60;
-                let remaining_mins = total_mins % 60;
-                let total_duration = if total_hours > 0 {
-                    format!("约 {} 小时 {} 分钟", total_hours, remaining_mins)
-                } else {
-                    format!("{} 分钟", total_mins)
-                };
+                let total_secs = total_duration_secs(&detail.songs);
 
                 // Update existing PlaylistView with full details (keep cover_path if already loaded)
                 if let Some(playlist) = &mut self.ui.playlist_page.current {
@@ -1168,13 +1152,14 @@ impl App {
                             Some(detail.description.clone())
                         };
                         playlist.owner = if detail.creator_nickname.is_empty() {
-                            "网易云音乐".to_string()
+                            self.core.locale.get(Key::NcmOfficialOwner).to_string()
                         } else {
                             detail.creator_nickname.clone()
                         };
                         playlist.creator_id = detail.creator_id;
-                        playlist.song_count = detail.songs.len() as u32;
-                        playlist.total_duration = total_duration;
+                        playlist.song_count =
+                            detail.track_count.max(detail.songs.len() as u64) as u32;
+                        playlist.total_duration_secs = total_secs;
                         playlist.is_subscribed = detail.subscribed;
                     }
                 }
@@ -1230,11 +1215,17 @@ impl App {
                     Task::none()
                 };
 
+                // Paged playlists deliver their songs through NcmPlaylistSongsPage
+                if detail.songs.is_empty() {
+                    return Some(avatar_task);
+                }
+
                 // Spawn async task to convert songs (cover download already started in OpenNcmPlaylist)
                 let songs = detail.songs.clone();
                 let cover_cache_dir = crate::utils::covers_cache_dir();
                 let avatars_cache_dir = crate::utils::avatars_cache_dir();
                 let ncm_playlist_id = detail.id;
+                let locale = self.core.locale;
 
                 // Start songs conversion task
                 let songs_task = Task::perform(
@@ -1258,6 +1249,8 @@ impl App {
                                 crate::app::update::page_loader::convert_ncm_songs_to_views(
                                     &songs,
                                     &cover_paths,
+                                    0,
+                                    locale,
                                 );
 
                             // Check creator avatar
@@ -1297,10 +1290,10 @@ impl App {
                 // Update existing playlist view with songs
                 if let Some(playlist) = &mut self.ui.playlist_page.current {
                     if playlist.id == *playlist_id {
-                        playlist.songs = song_views.clone();
                         if let Some(avatar) = avatar_path {
                             playlist.owner_avatar_path = Some(avatar.clone());
                         }
+                        self.ui.playlist_page.replace_songs(song_views.clone());
                     }
                 }
 
@@ -1308,11 +1301,38 @@ impl App {
                 self.ui.playlist_page.load_state =
                     crate::app::update::page_loader::PlaylistLoadState::Ready;
 
-                // Scroll to top
-                Some(iced::widget::operation::snap_to(
-                    iced::widget::Id::new("playlist_scroll"),
-                    iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
-                ))
+                Some(Task::none())
+            }
+
+            Message::NcmPlaylistSongsPage(playlist_id, songs, song_views, fetched, total) => {
+                let Some(playlist) = &mut self.ui.playlist_page.current else {
+                    return Some(Task::none());
+                };
+                if playlist.id != *playlist_id {
+                    return Some(Task::none());
+                }
+                debug!("NCM playlist page: {}/{} songs", fetched, total);
+
+                // Rows are only appended, so the ones on screen stay in place
+                playlist.songs.extend(song_views.iter().cloned());
+                self.ui
+                    .home
+                    .current_ncm_playlist_songs
+                    .extend(songs.iter().cloned());
+                playlist.total_duration_secs =
+                    total_duration_secs(&self.ui.home.current_ncm_playlist_songs);
+
+                self.ui.playlist_page.load_state = if fetched >= total {
+                    self.ui.playlist_page.song_pages = None;
+                    crate::app::update::page_loader::PlaylistLoadState::Ready
+                } else {
+                    crate::app::update::page_loader::PlaylistLoadState::Partial {
+                        loaded: *fetched,
+                        total: *total,
+                    }
+                };
+
+                Some(self.check_visible_song_covers().unwrap_or_else(Task::none))
             }
 
             Message::CurrentSongCoverReady(song_id, path) => {
@@ -1339,7 +1359,7 @@ impl App {
                             if let Some(db) = &self.core.db {
                                 let db = db.clone();
                                 let song_clone = queue_song.clone();
-                                tokio::spawn(async move {
+                                Job::new(Category::Database).spawn(async move {
                                     if let Err(e) = db.upsert_ncm_song(&song_clone).await {
                                         tracing::warn!(
                                             "Failed to update cover path in database: {}",
@@ -1353,7 +1373,7 @@ impl App {
                 }
 
                 // If lyrics page is open, update the background with new cover
-                if self.ui.lyrics.is_open {
+                if self.ui.lyrics.is_shown() {
                     if let Some(song) = self.library.current_song.clone() {
                         if song.id == *song_id {
                             return Some(self.update_lyrics_background_only(&song));
@@ -1409,11 +1429,7 @@ impl App {
                         }
                         // Skip if cover already exists locally
                         let cover_cache_dir = crate::utils::covers_cache_dir();
-                        let ncm_id = if *song_id < 0 {
-                            (-*song_id) as u64
-                        } else {
-                            *song_id as u64
-                        };
+                        let ncm_id = song_id.id;
                         let stem = format!("cover_{}", ncm_id);
                         crate::utils::find_cached_image(&cover_cache_dir, &stem).is_none()
                     })
@@ -1432,27 +1448,24 @@ impl App {
                         .insert(*song_id);
                 }
 
-                // Start download task
+                // Start download jobs; they are dropped if the user leaves the playlist
                 if let Some(client) = &self.core.ncm_client {
-                    let client = client.clone();
+                    let downloads = songs_to_download.into_iter().map(|(song_id, pic_url)| {
+                        let client = client.clone();
+                        let ncm_id = song_id.id;
+                        Job::new(Category::Cover).page().run(async move {
+                            crate::utils::download_cover(&client, ncm_id, &pic_url)
+                                .await
+                                .map(|path| (song_id, path.to_string_lossy().to_string()))
+                        })
+                    });
                     return Some(Task::perform(
-                        async move {
-                            let mut results = Vec::new();
-                            for (song_id, pic_url) in songs_to_download {
-                                let ncm_id = if song_id < 0 {
-                                    (-song_id) as u64
-                                } else {
-                                    song_id as u64
-                                };
-                                if let Some(path) =
-                                    crate::utils::download_cover(&client, ncm_id, &pic_url).await
-                                {
-                                    results.push((song_id, path.to_string_lossy().to_string()));
-                                }
-                            }
-                            results
+                        futures_util::future::join_all(downloads),
+                        |results| {
+                            Message::NcmPlaylistSongCoversBatchLoaded(
+                                results.into_iter().flatten().flatten().collect(),
+                            )
                         },
-                        Message::NcmPlaylistSongCoversBatchLoaded,
                     ));
                 }
                 Some(Task::none())
@@ -1468,19 +1481,43 @@ impl App {
                         playlist.cover_path = Some(path.clone());
 
                         // Also extract color palette from the downloaded cover
-                        let palette = crate::utils::ColorPalette::from_image_path(
-                            std::path::Path::new(&path),
+                        let db = self.core.db.clone();
+                        let path = std::path::PathBuf::from(path);
+                        let playlist_id = *playlist_id;
+                        let palette_task = Task::perform(
+                            Job::new(Category::Cover)
+                                .page()
+                                .run(palette::color_palette(db, path)),
+                            move |palette| match palette {
+                                Some(palette) => {
+                                    Message::NcmPlaylistPaletteReady(playlist_id, palette)
+                                }
+                                None => Message::NoOp,
+                            },
                         );
-                        tracing::info!(
+                        return Some(Task::batch([
+                            palette_task,
+                            self.refresh_playlist_backdrop(),
+                        ]));
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::NcmPlaylistPaletteReady(playlist_id, palette) => {
+                if let Some(playlist) = &mut self.ui.playlist_page.current {
+                    if playlist.id == *playlist_id {
+                        tracing::debug!(
                             "Updated palette from cover: primary=({:.2}, {:.2}, {:.2})",
                             palette.primary.r,
                             palette.primary.g,
                             palette.primary.b
                         );
-                        playlist.palette = palette;
+                        playlist.palette = palette.clone();
+                        self.ui.playlist_page.tint.follow(palette.primary);
                     }
                 }
-                Some(Task::none())
+                Some(self.refresh_playlist_backdrop())
             }
 
             Message::NcmPlaylistCreatorAvatarLoaded(playlist_id, path) => {
@@ -1500,7 +1537,9 @@ impl App {
 
             Message::TogglePlaylistSubscribe(playlist_id) => {
                 if !self.core.is_logged_in {
-                    return Some(Task::done(Message::ShowToast("请先登录".to_string())));
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::LoginRequired).to_string(),
+                    )));
                 }
 
                 // Get current subscription status
@@ -1516,24 +1555,19 @@ impl App {
                     let client = client.clone();
                     let playlist_id = *playlist_id;
                     let new_status = !is_subscribed;
+                    let failed = self.core.locale.get(Key::NcmOperationFailed);
 
                     Some(Task::perform(
                         async move {
                             // NCM playlist IDs are stored as negative in our system
                             let ncm_id = (-playlist_id) as u64;
-                            match client.client.playlist_subscribe(new_status, ncm_id).await {
-                                Ok(_) => Some((playlist_id, new_status)),
-                                Err(e) => {
-                                    error!("Failed to toggle playlist subscription: {}", e);
-                                    None
-                                }
-                            }
+                            client.client.playlist_subscribe(new_status, ncm_id).await
                         },
-                        |result| {
-                            if let Some((id, subscribed)) = result {
-                                Message::PlaylistSubscribeChanged(id, subscribed)
-                            } else {
-                                Message::ShowToast("操作失败".to_string())
+                        move |result| match result {
+                            Ok(_) => Message::PlaylistSubscribeChanged(playlist_id, new_status),
+                            Err(e) => {
+                                error!("Failed to toggle playlist subscription: {}", e);
+                                failure_message(&e, failed)
                             }
                         },
                     ))
@@ -1549,11 +1583,11 @@ impl App {
                         playlist.is_subscribed = *subscribed;
                     }
                 }
-                let msg = if *subscribed {
-                    "已收藏歌单"
+                let msg = self.core.locale.get(if *subscribed {
+                    Key::NcmPlaylistSubscribed
                 } else {
-                    "已取消收藏"
-                };
+                    Key::NcmPlaylistUnsubscribed
+                });
                 Some(Task::done(Message::ShowToast(msg.to_string())))
             }
 
@@ -1561,74 +1595,19 @@ impl App {
         }
     }
 
-    /// Load homepage data (banners, top picks, trending songs)
-    fn load_homepage_data(&self) -> Task<Message> {
-        let client = self.core.ncm_client.clone();
-
-        Task::batch([
-            Task::perform(
-                {
-                    let client = client.clone();
-                    async move {
-                        if let Some(client) = client {
-                            match client.client.banners().await {
-                                Ok(banners) => banners,
-                                Err(e) => {
-                                    error!("Failed to load banners: {:?}", e);
-                                    Vec::new()
-                                }
-                            }
-                        } else {
-                            Vec::new()
-                        }
-                    }
-                },
-                Message::BannersLoaded,
-            ),
-            Task::perform(
-                {
-                    let client = client.clone();
-                    async move {
-                        if let Some(client) = client {
-                            const TRENDING_CHART_ID: u64 = 19723756;
-                            match client.client.song_list_detail(TRENDING_CHART_ID).await {
-                                Ok(detail) => detail.songs,
-                                Err(e) => {
-                                    error!("Failed to load trending songs: {:?}", e);
-                                    Vec::new()
-                                }
-                            }
-                        } else {
-                            Vec::new()
-                        }
-                    }
-                },
-                Message::TrendingSongsLoaded,
-            ),
-            Task::perform(
-                {
-                    let client = client.clone();
-                    async move {
-                        if let Some(client) = client {
-                            match client.client.top_song_list("全部", "hot", 0, 8).await {
-                                Ok(playlists) => playlists,
-                                Err(e) => {
-                                    error!("Failed to load top picks: {:?}", e);
-                                    Vec::new()
-                                }
-                            }
-                        } else {
-                            Vec::new()
-                        }
-                    }
-                },
-                Message::TopPicksLoaded,
-            ),
-        ])
+    /// Load homepage data (banners, top picks, trending songs) and check for new releases
+    fn load_homepage_data(&mut self) -> Task<Message> {
+        let mut tasks = vec![self.check_new_releases()];
+        let layout = self.core.settings.display.home_layout.clone();
+        // Hidden sections are fetched when they are switched back on
+        for entry in layout.sections().iter().filter(|e| e.enabled) {
+            tasks.push(self.load_home_section(entry.section));
+        }
+        Task::batch(tasks)
     }
 
     /// Load user playlists (liked songs + collected playlists)
-    fn load_user_playlists(&self) -> Task<Message> {
+    pub(super) fn load_user_playlists(&self) -> Task<Message> {
         let client = self.core.ncm_client.clone();
         let uid = self.core.user_info.as_ref().map(|u| u.user_id).unwrap_or(0);
         let nickname = self
@@ -1637,6 +1616,11 @@ impl App {
             .as_ref()
             .map(|u| u.nickname.clone())
             .unwrap_or_default();
+        let liked_name = self
+            .core
+            .locale
+            .get(Key::NcmLikedSongs)
+            .replace("{}", &nickname);
 
         if uid == 0 {
             return Task::none();
@@ -1649,7 +1633,7 @@ impl App {
                         Ok(mut playlists) => {
                             // First playlist is "liked songs", rename it
                             if let Some(first) = playlists.first_mut() {
-                                first.name = format!("{} 喜欢的音乐", nickname);
+                                first.name = liked_name;
                             }
                             playlists
                         }
@@ -1709,21 +1693,84 @@ impl App {
                         match client.client.top_song_list("全部", "hot", 0, 30).await {
                             Ok(playlists) => {
                                 let has_more = playlists.len() >= 30;
-                                (playlists, has_more)
+                                Message::HotPlaylistsLoaded(playlists, has_more)
                             }
                             Err(e) => {
                                 error!("Failed to load hot playlists: {:?}", e);
-                                (Vec::new(), false)
+                                Message::HotPlaylistsFailed
                             }
                         }
                     } else {
-                        (Vec::new(), false)
+                        Message::HotPlaylistsLoaded(Vec::new(), false)
                     }
                 }
             },
-            |(playlists, has_more)| Message::HotPlaylistsLoaded(playlists, has_more),
+            |msg| msg,
         ));
 
         Task::batch(tasks)
     }
 }
+
+/// Songs fetched per request while paging through a playlist
+pub(super) const PLAYLIST_PAGE_SIZE: usize = 200;
+
+/// Load an NCM playlist in batches
+///
+/// Yields the playlist info first, then its songs in playlist order so rows
+/// show up while the rest of a large playlist is still being fetched.
+fn ncm_playlist_pages(
+    client: NcmClient,
+    playlist_id: u64,
+    internal_id: i64,
+    locale: Locale,
+) -> impl futures_util::Stream<Item = Message> {
+    async_stream::stream! {
+        let mut detail = match client.client.song_list_page(playlist_id, PLAYLIST_PAGE_SIZE).await {
+            Ok(detail) => detail,
+            Err(e) => {
+                error!("Failed to load NCM playlist detail: {:?}", e);
+                yield failure_message(&e, locale.get(Key::DiscoverPlaylistLoadFailed));
+                return;
+            }
+        };
+
+        let first_page = std::mem::take(&mut detail.songs);
+        let remaining_ids = if detail.track_count > first_page.len() as u64 {
+            client
+                .client
+                .remaining_track_ids(playlist_id, &first_page)
+                .await
+                .unwrap_or_else(|e| {
+                    error!("Failed to load NCM playlist track IDs: {:?}", e);
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+        let total = first_page.len() + remaining_ids.len();
+
+        yield Message::NcmPlaylistDetailLoaded(detail);
+
+        let mut fetched = first_page.len();
+        let mut loaded = first_page.len();
+        let song_views = page_loader::load_ncm_song_views(first_page.clone(), 0, locale).await;
+        yield Message::NcmPlaylistSongsPage(internal_id, first_page, song_views, fetched, total);
+
+        for chunk in remaining_ids.chunks(PLAYLIST_PAGE_SIZE) {
+            let songs = client.client.song_detail(chunk).await.unwrap_or_else(|e| {
+                error!("Failed to load NCM playlist songs: {:?}", e);
+                Vec::new()
+            });
+            let song_views = page_loader::load_ncm_song_views(songs.clone(), loaded, locale).await;
+            fetched += chunk.len();
+            loaded += songs.len();
+            yield Message::NcmPlaylistSongsPage(internal_id, songs, song_views, fetched, total);
+        }
+    }
+}
+
+/// Combined duration of a song list in seconds
+pub(super) fn total_duration_secs(songs: &[crate::api::SongInfo]) -> u64 {
+    songs.iter().map(|s| s.duration / 1000).sum()
+}
@@ -0,0 +1,133 @@
+//! "Find this on NCM" handlers
+
+use iced::Task;
+use tracing::error;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::ncm_match::{self, LocalTrack};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle NCM matching messages
+    pub fn handle_ncm_match(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::NcmLinksLoaded(links) => {
+                self.library.ncm_links = links.clone();
+                self.refresh_tray_state();
+                Some(Task::none())
+            }
+
+            Message::MatchSongOnNcm(song_id) => {
+                let song_id = *song_id;
+                if song_id.is_ncm() || self.library.ncm_matching.is_some() {
+                    return Some(Task::none());
+                }
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::NotLoggedIn).to_string(),
+                    )));
+                };
+                let Some(song) = self
+                    .library
+                    .current_song
+                    .iter()
+                    .chain(&self.library.queue)
+                    .chain(&self.library.db_songs)
+                    .find(|song| song.id == song_id && !song.file_path.is_empty())
+                else {
+                    return Some(Task::none());
+                };
+
+                let track = LocalTrack {
+                    path: song.file_path.clone().into(),
+                    title: song.title.clone(),
+                    artist: song.artist.clone(),
+                    duration_secs: song.duration_secs.max(0) as u64,
+                };
+                self.library.ncm_matching = Some(song_id);
+
+                Some(Task::perform(
+                    async move {
+                        ncm_match::find(&client, track)
+                            .await
+                            .map(|found| {
+                                found.map(|song| {
+                                    let label = if song.singer.is_empty() {
+                                        song.name
+                                    } else {
+                                        format!("{} - {}", song.name, song.singer)
+                                    };
+                                    (song.id, label)
+                                })
+                            })
+                            .map_err(|e| e.to_string())
+                    },
+                    move |result| Message::NcmMatchFinished(song_id, result),
+                ))
+            }
+
+            Message::NcmMatchFinished(song_id, result) => {
+                let song_id = *song_id;
+                if self.library.ncm_matching == Some(song_id) {
+                    self.library.ncm_matching = None;
+                }
+                let locale = self.core.locale;
+
+                let (ncm_id, label) = match result {
+                    Ok(Some(found)) => found.clone(),
+                    Ok(None) => {
+                        return Some(Task::done(Message::ShowToast(
+                            locale.get(Key::NcmMatchNotFound).to_string(),
+                        )));
+                    }
+                    Err(e) => {
+                        error!("Failed to match song {} on NCM: {}", song_id, e);
+                        return Some(Task::done(Message::ShowToast(
+                            locale.get(Key::NcmMatchFailed).replace("{}", e),
+                        )));
+                    }
+                };
+
+                self.library.ncm_links.insert(song_id, ncm_id);
+                let favorite = self.carry_favorite_to_ncm(song_id, ncm_id);
+                // Only library songs are matched, NCM tracks already are one
+                let save = self
+                    .core
+                    .db
+                    .clone()
+                    .zip(song_id.library_id())
+                    .map(|(db, row_id)| {
+                        Task::perform(
+                            async move {
+                                if let Err(e) = db.link_ncm_song(row_id, ncm_id).await {
+                                    error!("Failed to save NCM link for song {}: {}", song_id, e);
+                                }
+                            },
+                            |_| Message::NoOp,
+                        )
+                    });
+
+                // Pick up online lyrics, the large cover and the like state now
+                let mut reload = Task::none();
+                if let Some(song) = self.library.current_song.clone() {
+                    if song.id == song_id {
+                        reload = self.load_lyrics_async(&song);
+                        self.refresh_tray_state();
+                    }
+                }
+
+                Some(Task::batch([
+                    Task::done(Message::ShowToast(
+                        locale.get(Key::NcmMatchLinked).replace("{}", &label),
+                    )),
+                    save.unwrap_or_else(Task::none),
+                    favorite,
+                    reload,
+                ]))
+            }
+
+            _ => None,
+        }
+    }
+}
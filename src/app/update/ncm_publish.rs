@@ -0,0 +1,211 @@
+//! "Publish to NCM" handlers for local playlists
+
+use iced::Task;
+use tracing::{error, warn};
+
+use crate::api::NcmClient;
+use crate::app::message::Message;
+use crate::app::state::{App, PublishDialog, PublishStage, Route};
+use crate::database::SongId;
+use crate::features::ncm_match::LocalTrack;
+use crate::features::ncm_publish::{self, PublishPlan, PublishTrack, TrackMatch};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle publishing local playlists to NCM
+    pub fn handle_ncm_publish(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::PublishPlaylistToNcm(playlist_id) => {
+                if self.ui.dialogs.publish.is_some() {
+                    return Some(Task::none());
+                }
+                let Some(client) = self
+                    .core
+                    .ncm_client
+                    .clone()
+                    .filter(|_| self.core.is_logged_in)
+                else {
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::NotLoggedIn).to_string(),
+                    )));
+                };
+                let Some(playlist) = self
+                    .ui
+                    .playlist_page
+                    .current
+                    .as_ref()
+                    .filter(|p| p.id == *playlist_id && p.is_local)
+                else {
+                    return Some(Task::none());
+                };
+                let name = playlist.name.clone();
+                let total = playlist.songs.len();
+
+                // Tags come from the library; the row is enough for songs not in it
+                let tracks: Vec<(SongId, LocalTrack)> = playlist
+                    .songs
+                    .iter()
+                    .map(|row| {
+                        let song = self.library.db_songs.iter().find(|s| s.id == row.id);
+                        let track = LocalTrack {
+                            path: song.map(|s| s.file_path.clone()).unwrap_or_default().into(),
+                            title: row.title.clone(),
+                            artist: row.artist.clone(),
+                            duration_secs: song.map_or(0, |s| s.duration_secs.max(0) as u64),
+                        };
+                        (row.id, track)
+                    })
+                    .collect();
+                let links = tracks
+                    .iter()
+                    .map(|(id, _)| self.library.ncm_links.get(id).copied())
+                    .collect();
+
+                let (task, handle) =
+                    Task::run(match_tracks(client, tracks, links), |msg| msg).abortable();
+                self.ui.dialogs.publish = Some(PublishDialog {
+                    plan: PublishPlan::new(name, total),
+                    stage: PublishStage::Matching,
+                    lookups: Some(handle.abort_on_drop()),
+                });
+                self.ui.dialogs.publish_animation.start();
+                Some(self.advance_publish(task))
+            }
+
+            Message::PublishTrackMatched(track) => {
+                if let Some(dialog) = &mut self.ui.dialogs.publish {
+                    dialog.plan.tracks.push(track.clone());
+                }
+                Some(self.advance_publish(Task::none()))
+            }
+
+            Message::ChoosePublishMatch(track, choice) => {
+                if let Some(dialog) = &mut self.ui.dialogs.publish {
+                    dialog.plan.choose(*track, *choice);
+                }
+                Some(Task::none())
+            }
+
+            Message::ConfirmPublish => {
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::none());
+                };
+                let Some(dialog) = self
+                    .ui
+                    .dialogs
+                    .publish
+                    .as_mut()
+                    .filter(|d| d.stage == PublishStage::Review)
+                else {
+                    return Some(Task::none());
+                };
+                let track_ids = dialog.plan.track_ids();
+                if track_ids.is_empty() {
+                    return Some(Task::none());
+                }
+                dialog.stage = PublishStage::Publishing;
+
+                let name = dialog.plan.name.clone();
+                Some(Task::perform(
+                    async move {
+                        ncm_publish::publish(&client, &name, &track_ids)
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::PublishFinished,
+                ))
+            }
+
+            Message::PublishFinished(result) => {
+                let Some(dialog) = &mut self.ui.dialogs.publish else {
+                    return Some(Task::none());
+                };
+                match result {
+                    Ok(playlist_id) => {
+                        dialog.stage = PublishStage::Done {
+                            playlist_id: *playlist_id,
+                            unmatched: dialog.plan.unmatched(),
+                        };
+                        // Show the new playlist in the sidebar
+                        Some(self.load_user_playlists())
+                    }
+                    Err(e) => {
+                        error!("Failed to publish playlist to NCM: {}", e);
+                        dialog.stage = PublishStage::Review;
+                        Some(Task::done(Message::ShowErrorToast(
+                            self.core.locale.get(Key::PublishFailed).replace("{}", e),
+                        )))
+                    }
+                }
+            }
+
+            Message::OpenPublishedPlaylist => {
+                let Some(PublishStage::Done { playlist_id, .. }) =
+                    self.ui.dialogs.publish.as_ref().map(|d| d.stage.clone())
+                else {
+                    return Some(Task::none());
+                };
+                self.close_publish_dialog();
+                Some(self.navigate_to_route(Route::NcmPlaylist(playlist_id), true))
+            }
+
+            Message::ClosePublishDialog => {
+                self.close_publish_dialog();
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Move on to review once every song has been looked up
+    fn advance_publish(&mut self, task: Task<Message>) -> Task<Message> {
+        let ready = self
+            .ui
+            .dialogs
+            .publish
+            .as_mut()
+            .filter(|d| d.stage == PublishStage::Matching && d.plan.is_complete());
+        if let Some(dialog) = ready {
+            dialog.stage = PublishStage::Review;
+            dialog.lookups = None;
+        }
+        task
+    }
+
+    fn close_publish_dialog(&mut self) {
+        // Dropping the dialog aborts lookups still running
+        self.ui.dialogs.publish = None;
+        self.ui.dialogs.publish_animation.stop();
+    }
+}
+
+/// Look the songs up one after another, reusing links made before
+fn match_tracks(
+    client: NcmClient,
+    tracks: Vec<(SongId, LocalTrack)>,
+    links: Vec<Option<u64>>,
+) -> impl futures_util::Stream<Item = Message> {
+    async_stream::stream! {
+        for ((song_id, track), link) in tracks.into_iter().zip(links) {
+            let label = if track.artist.is_empty() {
+                track.title.clone()
+            } else {
+                format!("{} - {}", track.title, track.artist)
+            };
+            let result = match link {
+                Some(id) => TrackMatch::Matched {
+                    id,
+                    label: label.clone(),
+                },
+                None => ncm_publish::match_track(&client, track)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("Failed to look up song {} on NCM: {}", song_id, e);
+                        TrackMatch::Unmatched
+                    }),
+            };
+            yield Message::PublishTrackMatched(PublishTrack { label, result });
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Metered connection handling and network changes
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::MeteredMode;
+
+impl App {
+    /// Apply a change of metered status: adjust streaming quality and resume
+    /// deferred downloads once the connection is no longer metered
+    pub(super) fn on_metered_changed(&mut self, was_metered: bool) -> Task<Message> {
+        let metered = self.core.is_metered();
+        if metered == was_metered {
+            return Task::none();
+        }
+        tracing::info!("Metered connection: {}", metered);
+
+        if let Some(client) = &self.core.ncm_client {
+            client.set_quality(self.core.stream_quality().to_api_rate());
+        }
+        if metered {
+            return Task::none();
+        }
+
+        let mut tasks = vec![
+            self.resume_lazy_covers(),
+            self.preload_adjacent_tracks_with_ncm(),
+        ];
+        if let Some(task) = self.check_visible_song_covers() {
+            tasks.push(task);
+        }
+        Task::batch(tasks)
+    }
+
+    /// Handle metered connection detection and network changes
+    pub fn handle_network(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::DetectMeteredConnection => {
+                if self.core.settings.network.metered_mode != MeteredMode::Auto {
+                    return Some(Task::none());
+                }
+                Some(Task::perform(
+                    async {
+                        tokio::task::spawn_blocking(crate::platform::network::is_metered)
+                            .await
+                            .ok()
+                            .flatten()
+                    },
+                    Message::MeteredStatusDetected,
+                ))
+            }
+
+            Message::MeteredStatusDetected(status) => {
+                let Some(metered) = *status else {
+                    return Some(Task::none());
+                };
+                let was_metered = self.core.is_metered();
+                self.core.system_metered = metered;
+                Some(self.on_metered_changed(was_metered))
+            }
+
+            Message::CheckNetworkRoute => Some(Task::perform(
+                async {
+                    tokio::task::spawn_blocking(crate::platform::network::route_address)
+                        .await
+                        .ok()
+                        .flatten()
+                },
+                Message::NetworkRouteChecked,
+            )),
+
+            Message::NetworkRouteChecked(route) => {
+                // Offline for now; whatever comes back is compared with the
+                // last network that worked
+                let Some(route) = *route else {
+                    return Some(Task::none());
+                };
+                let previous = self.core.network_route.replace(route);
+                if previous.is_none_or(|previous| previous == route) {
+                    return Some(Task::none());
+                }
+                tracing::info!("Network changed, reconnecting streams");
+                crate::api::http::network_changed();
+                Some(Task::done(Message::DetectMeteredConnection))
+            }
+
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Now-playing output file handlers
+
+use std::time::Duration;
+
+use iced::Task;
+
+use super::hooks::PlaybackSnapshot;
+use crate::app::{App, Message};
+use crate::features::now_playing::{self, NowPlaying};
+
+/// How long a cover download may take before it is given up on
+const COVER_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Read a cover from disk or the web
+pub(super) async fn load_cover(cover: &str, proxy: Option<String>) -> anyhow::Result<Vec<u8>> {
+    if cover.starts_with("http://") || cover.starts_with("https://") {
+        let bytes = crate::api::http::client(proxy.as_deref())?
+            .get(cover)
+            .timeout(COVER_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok(bytes.to_vec())
+    } else {
+        Ok(tokio::fs::read(cover).await?)
+    }
+}
+
+impl App {
+    /// Handle now-playing output settings messages
+    pub fn handle_now_playing(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::UpdateNowPlayingOutput(enabled) => {
+                self.core.settings.system.now_playing.enabled = *enabled;
+                let write = if *enabled {
+                    self.write_now_playing_files(true)
+                } else {
+                    Task::none()
+                };
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    write,
+                ]))
+            }
+
+            Message::UpdateNowPlayingArtwork(enabled) => {
+                self.core.settings.system.now_playing.artwork = *enabled;
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    self.write_now_playing_files(true),
+                ]))
+            }
+
+            Message::PickNowPlayingFolder => Some(Task::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .pick_folder()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                },
+                Message::NowPlayingFolderPicked,
+            )),
+
+            Message::NowPlayingFolderPicked(path) => {
+                let Some(path) = path else {
+                    return Some(Task::none());
+                };
+                self.core.settings.system.now_playing.directory =
+                    Some(path.to_string_lossy().to_string());
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    self.write_now_playing_files(true),
+                ]))
+            }
+
+            Message::OpenNowPlayingFolder => {
+                let dir = self.core.settings.system.now_playing.dir();
+                if let Err(e) = std::fs::create_dir_all(&dir)
+                    .and_then(|_| crate::platform::shell::open_path(&dir))
+                {
+                    tracing::warn!("Failed to open now-playing folder {:?}: {}", dir, e);
+                }
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Rewrite the now-playing files if the song or play state changed
+    /// since `before`
+    pub(super) fn update_now_playing_files(
+        &self,
+        before: PlaybackSnapshot,
+        task: Task<Message>,
+    ) -> Task<Message> {
+        if !self.core.settings.system.now_playing.enabled {
+            return task;
+        }
+        let after = self.playback_snapshot();
+        if after == before {
+            return task;
+        }
+        // Play and pause only change the JSON; the artwork stays
+        Task::batch([task, self.write_now_playing_files(after.0 != before.0)])
+    }
+
+    /// Write the now-playing files for the current song in the background
+    ///
+    /// The text files go out first; the artwork follows once its cover has
+    /// loaded, unless a later song has claimed it by then.
+    fn write_now_playing_files(&self, with_artwork: bool) -> Task<Message> {
+        let output = &self.core.settings.system.now_playing;
+        if !output.enabled {
+            return Task::none();
+        }
+        let dir = output.dir();
+        let with_artwork = with_artwork && output.artwork;
+        let (_, playing) = self.playback_snapshot();
+        let current = self
+            .library
+            .current_song
+            .as_ref()
+            .map(|song| NowPlaying::new(song, playing));
+        let proxy = self.core.settings.network.proxy_url();
+        let generation = now_playing::next_generation();
+        let artwork_generation = with_artwork.then(now_playing::next_artwork_generation);
+
+        Task::perform(
+            async move {
+                let cover = current.as_ref().and_then(|np| np.cover.clone());
+                let text_dir = dir.clone();
+                let written = tokio::task::spawn_blocking(move || {
+                    now_playing::write(&text_dir, current.as_ref(), generation)
+                })
+                .await;
+                log_written(written);
+
+                let Some(artwork_generation) = artwork_generation else {
+                    return;
+                };
+                let cover = match cover {
+                    Some(cover) => match load_cover(&cover, proxy).await {
+                        Ok(bytes) => Some(bytes),
+                        Err(e) => {
+                            tracing::warn!("Failed to load now-playing artwork: {:#}", e);
+                            None
+                        }
+                    },
+                    None => None,
+                };
+                let written = tokio::task::spawn_blocking(move || {
+                    now_playing::write_artwork(&dir, cover.as_deref(), artwork_generation)
+                })
+                .await;
+                log_written(written);
+            },
+            |_| Message::NoOp,
+        )
+    }
+}
+
+fn log_written(written: Result<anyhow::Result<()>, tokio::task::JoinError>) {
+    match written {
+        Ok(Err(e)) => tracing::warn!("Failed to write now-playing files: {:#}", e),
+        Err(e) => tracing::warn!("Now-playing writer panicked: {}", e),
+        Ok(Ok(())) => {}
+    }
+}
@@ -5,6 +5,11 @@
 //! - Unified handling for local and NCM playlists
 
 use crate::app::state::App;
+use crate::database::SongId;
+use crate::features::jobs::{Category, Job};
+use crate::features::song_overrides::{self, SongTags};
+use crate::features::song_source::{self, SongSource};
+use crate::i18n::{Key, Locale};
 
 /// Loading state for playlist pages
 #[derive(Debug, Clone, Default)]
@@ -14,10 +19,22 @@ pub enum PlaylistLoadState {
     Idle,
     /// Loading playlist (shows skeleton)
     Loading,
+    /// Songs are arriving in batches
+    Partial { loaded: usize, total: usize },
     /// Fully loaded
     Ready,
 }
 
+impl PlaylistLoadState {
+    /// Songs loaded so far and the playlist's total while batches are arriving
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Partial { loaded, total } => Some((*loaded, *total)),
+            _ => None,
+        }
+    }
+}
+
 impl App {
     /// Check if we're already viewing the specified playlist
     /// Returns true if we should skip loading
@@ -38,9 +55,15 @@ impl App {
 }
 
 /// Convert NCM songs to PlaylistSongView with pre-checked cover paths
+///
+/// `first_index` is the position of the first song in the playlist, so that
+/// batches appended later keep their track numbers. The song cache is checked
+/// for each song, so this runs off the UI thread.
 pub fn convert_ncm_songs_to_views(
     songs: &[crate::api::SongInfo],
     cover_paths: &[(u64, Option<String>)],
+    first_index: usize,
+    locale: Locale,
 ) -> Vec<crate::ui::pages::PlaylistSongView> {
     let cover_map: std::collections::HashMap<u64, Option<String>> =
         cover_paths.iter().cloned().collect();
@@ -59,26 +82,62 @@ pub fn convert_ncm_songs_to_views(
             } else {
                 None
             };
+            let tags = song_overrides::tags(song.id).unwrap_or_else(|| SongTags {
+                title: song.name.clone(),
+                artist: song.singer.clone(),
+                album: song.album.clone(),
+            });
 
             crate::ui::components::playlist_view::SongItem::with_pic_url(
-                -(song.id as i64),
-                i + 1,
-                song.name.clone(),
-                if song.singer.is_empty() {
-                    "未知艺术家".to_string()
+                SongId::ncm(song.id),
+                first_index + i + 1,
+                tags.title,
+                if tags.artist.is_empty() {
+                    locale.get(Key::UnknownArtist).to_string()
                 } else {
-                    song.singer.clone()
+                    tags.artist
                 },
-                if song.album.is_empty() {
-                    "未知专辑".to_string()
+                if tags.album.is_empty() {
+                    locale.get(Key::UnknownAlbum).to_string()
                 } else {
-                    song.album.clone()
+                    tags.album
                 },
                 format!("{}:{:02}", mins, secs),
                 String::new(),
                 cover_path,
                 pic_url,
+                if song_source::cached_audio(song.id).is_some() {
+                    SongSource::Cached
+                } else {
+                    SongSource::Ncm
+                },
             )
+            .with_ncm_details(duration_secs, song.popularity, song.hires)
         })
         .collect()
 }
+
+/// Convert a batch of NCM songs off the UI thread, checking the cover cache for each
+pub async fn load_ncm_song_views(
+    songs: Vec<crate::api::SongInfo>,
+    first_index: usize,
+    locale: Locale,
+) -> Vec<crate::ui::pages::PlaylistSongView> {
+    Job::new(Category::Disk)
+        .page()
+        .run_blocking(move || {
+            let cover_cache_dir = crate::utils::covers_cache_dir();
+            let cover_paths: Vec<(u64, Option<String>)> = songs
+                .iter()
+                .map(|song| {
+                    let stem = format!("cover_{}", song.id);
+                    let cover_path = crate::utils::find_cached_image(&cover_cache_dir, &stem)
+                        .map(|p| p.to_string_lossy().to_string());
+                    (song.id, cover_path)
+                })
+                .collect();
+            convert_ncm_songs_to_views(&songs, &cover_paths, first_index, locale)
+        })
+        .await
+        .unwrap_or_default()
+}
@@ -2,11 +2,20 @@
 //! Playback control message handlers
 
 use iced::Task;
+use iced::mouse::ScrollDelta;
+use iced::time::Instant;
+use std::time::Duration;
 
-use crate::app::helpers::update_tray_state_full;
+use super::ncm::queue_song_from_ncm;
 use crate::app::message::Message;
 use crate::app::state::App;
-use crate::audio::AudioEvent;
+use crate::audio::{AudioEvent, UiSound};
+use crate::database::{DbSong, SongId};
+use crate::features::MusicQuality;
+use crate::features::jobs::{Category, Job};
+use crate::features::media::chapters;
+use crate::i18n::Key;
+use crate::ui::pages;
 
 impl App {
     /// Handle playback-related messages
@@ -27,8 +36,7 @@ impl App {
                 }
 
                 // Try NCM playlist songs
-                if *id < 0 {
-                    let ncm_id = (-*id) as u64;
+                if let Some(ncm_id) = id.ncm_id() {
                     if let Some(song_info) = self
                         .ui
                         .home
@@ -36,29 +44,7 @@ impl App {
                         .iter()
                         .find(|s| s.id == ncm_id)
                     {
-                        let db_song = crate::database::DbSong {
-                            id: -(song_info.id as i64),
-                            file_path: String::new(),
-                            title: song_info.name.clone(),
-                            artist: song_info.singer.clone(),
-                            album: song_info.album.clone(),
-                            duration_secs: (song_info.duration / 1000) as i64,
-                            track_number: None,
-                            year: None,
-                            genre: None,
-                            cover_path: if song_info.pic_url.is_empty() {
-                                None
-                            } else {
-                                Some(song_info.pic_url.clone())
-                            },
-                            file_hash: None,
-                            file_size: 0,
-                            format: Some("mp3".to_string()),
-                            play_count: 0,
-                            last_played: None,
-                            last_modified: 0,
-                            created_at: 0,
-                        };
+                        let db_song = queue_song_from_ncm(song_info);
                         self.library.queue.push(db_song);
                         let idx = self.library.queue.len() - 1;
                         return Some(self.play_song_at_index(idx));
@@ -84,17 +70,73 @@ impl App {
 
             Message::SeekRelease => Some(self.apply_seek()),
 
+            Message::TrackQualitiesLoaded(song_id, qualities) => {
+                if self.library.current_song.as_ref().map(|s| s.id) == Some(*song_id) {
+                    self.library.track_qualities = Some((*song_id, qualities.clone()));
+                }
+                Some(Task::none())
+            }
+
+            Message::SelectTrackQuality(quality) => Some(self.switch_track_quality(*quality)),
+
+            Message::SeekHover(position) => {
+                self.ui.seek_hover = *position;
+                // The preview needs the current song's lyrics even when the
+                // lyrics page has never been opened for it
+                if position.is_some() {
+                    if let Some(song) = self.library.current_song.clone() {
+                        if self.ui.lyrics.loading_song_id != Some(song.id) {
+                            return Some(self.load_lyrics_async(&song));
+                        }
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::SkipBack => {
+                let secs = self.core.settings.playback.skip_back_secs;
+                self.skip_by(-(secs as i64));
+                Some(Task::none())
+            }
+
+            Message::SkipForward => {
+                let secs = self.core.settings.playback.skip_forward_secs;
+                self.skip_by(secs as i64);
+                Some(Task::none())
+            }
+
             Message::SetVolume(volume) => {
+                // Touching the volume ends a schedule's fade-in, and keeps
+                // a call from putting back the volume from before it
+                self.ui.schedule_ramp = None;
+                if let Some(ducked) = &mut self.ui.call_ducking.ducked {
+                    ducked.volume = None;
+                }
                 if let Some(player) = &self.core.audio {
                     player.set_volume(*volume);
                     if let Some(db) = &self.core.db {
                         let db = db.clone();
                         let vol = *volume as f64;
-                        tokio::spawn(async move {
+                        Job::new(Category::Database).spawn(async move {
                             let _ = db.update_volume(vol).await;
                         });
                     }
                 }
+                self.update_mpris_state();
+                Some(Task::none())
+            }
+
+            Message::VolumeWheel(delta) => {
+                let steps = self.wheel_steps(*delta);
+                Some(self.step_volume(steps))
+            }
+
+            Message::SeekWheel(delta) => {
+                let steps = self.wheel_steps(*delta);
+                if steps != 0 {
+                    let secs = self.core.settings.playback.seek_step_secs as i64;
+                    self.skip_by(steps as i64 * secs);
+                }
                 Some(Task::none())
             }
 
@@ -103,38 +145,51 @@ impl App {
             Message::CyclePlayMode => {
                 if self.is_fm_mode() {
                     return Some(Task::done(Message::ShowErrorToast(
-                        "私人FM模式下无法更改播放模式".to_string(),
+                        self.core.locale.get(Key::PlayModeLockedInFm).to_string(),
                     )));
                 }
 
                 self.core.settings.play_mode = self.core.settings.play_mode.next();
                 let _ = self.core.settings.save();
-                tracing::info!(
-                    "Play mode changed to: {}",
-                    self.core.settings.play_mode.display_name()
-                );
-
-                let (title, artist) = self
-                    .library
-                    .current_song
-                    .as_ref()
-                    .map(|s| (Some(s.title.clone()), Some(s.artist.clone())))
-                    .unwrap_or((None, None));
-                let is_playing = self
-                    .core
-                    .audio
-                    .as_ref()
-                    .map(|p| p.is_playing())
-                    .unwrap_or(false);
-                update_tray_state_full(is_playing, title, artist, self.core.settings.play_mode);
+                tracing::info!("Play mode changed to: {:?}", self.core.settings.play_mode);
 
                 // Clear shuffle cache and re-calculate for new mode
                 self.clear_shuffle_cache();
                 self.cache_shuffle_indices();
+                self.refresh_tray_state();
                 let _ = self.preload_adjacent_tracks_with_ncm();
+                Some(self.auto_dj_top_up_if_needed())
+            }
+
+            Message::TogglePlayerMenu => {
+                self.ui.player_menu_open =
+                    !self.ui.player_menu_open && self.library.current_song.is_some();
                 Some(Task::none())
             }
 
+            Message::ClosePlayerMenu => {
+                self.ui.player_menu_open = false;
+                Some(Task::none())
+            }
+
+            Message::ToggleStopAfterCurrent => {
+                self.ui.player_menu_open = false;
+                if self.library.current_song.is_none() {
+                    return Some(Task::none());
+                }
+                self.library.stop_after_current = !self.library.stop_after_current;
+                self.refresh_tray_state();
+
+                let key = if self.library.stop_after_current {
+                    Key::StopAfterCurrentOn
+                } else {
+                    Key::StopAfterCurrentOff
+                };
+                Some(Task::done(Message::ShowToast(
+                    self.core.locale.get(key).to_string(),
+                )))
+            }
+
             // Streaming playback messages
             Message::StreamingEvent(song_id, event) => {
                 Some(self.handle_streaming_event(*song_id, event.clone()))
@@ -170,17 +225,12 @@ impl App {
 
                 // Fallback: try to play from current_song directly (for local songs)
                 if let Some(song) = self.library.current_song.as_ref() {
-                    let is_ncm = song.id < 0
-                        || song.file_path.is_empty()
-                        || song.file_path.starts_with("ncm://");
-                    if is_ncm {
+                    if crate::features::song_source::is_ncm(song) || song.file_path.is_empty() {
                         tracing::warn!("Cannot play NCM song without queue index");
                         return Task::none();
                     }
 
                     let file_path = song.file_path.clone();
-                    let title = song.title.clone();
-                    let artist = song.artist.clone();
                     let playback_pos = self
                         .library
                         .playback_state
@@ -201,7 +251,7 @@ impl App {
                             player.seek(seek_pos);
                         }
                     }
-                    self.update_tray_and_mpris(true, Some(title), Some(artist));
+                    self.update_tray_and_mpris_current(true);
                 }
             }
             PlaybackStatus::Playing => {
@@ -213,10 +263,11 @@ impl App {
                 if let (Some(pos), Some(db), Some(song)) =
                     (position_info, &self.core.db, &self.library.current_song)
                 {
+                    self.record_crash_session(pos);
                     let db = db.clone();
                     let song_id = song.id;
                     let queue_pos = self.library.queue_index.unwrap_or(0) as i64;
-                    tokio::spawn(async move {
+                    Job::new(Category::Database).spawn(async move {
                         let _ = db
                             .update_playback_position(Some(song_id), queue_pos, pos)
                             .await;
@@ -227,11 +278,26 @@ impl App {
                 if let Some(player) = &self.core.audio {
                     player.pause_with_fade(fade);
                 }
+                self.core.paused_at = Some(Instant::now());
                 self.update_tray_and_mpris_current(false);
             }
             PlaybackStatus::Paused => {
+                // Replay the last few seconds after a long pause, so the
+                // thread of a podcast isn't lost
+                let rewind = self.core.paused_at.take().map_or(Duration::ZERO, |at| {
+                    self.core
+                        .settings
+                        .playback
+                        .smart_resume
+                        .rewind_for(at.elapsed())
+                });
                 let fade = self.core.settings.playback.fade_in_out;
                 if let Some(player) = &self.core.audio {
+                    if !rewind.is_zero() {
+                        let position = player.get_info().position;
+                        player.seek(position.saturating_sub(rewind));
+                        self.ui.lyrics.redraw_requested = true;
+                    }
                     player.resume_with_fade(fade);
                 }
                 self.update_tray_and_mpris_current(true);
@@ -241,6 +307,7 @@ impl App {
                 if let Some(player) = &self.core.audio {
                     player.pause_with_fade(fade);
                 }
+                self.core.paused_at = Some(Instant::now());
                 self.update_tray_and_mpris_current(false);
             }
         }
@@ -248,29 +315,14 @@ impl App {
         Task::none()
     }
 
-    fn update_tray_and_mpris(
-        &mut self,
-        is_playing: bool,
-        title: Option<String>,
-        artist: Option<String>,
-    ) {
-        update_tray_state_full(is_playing, title, artist, self.core.settings.play_mode);
-        self.update_mpris_state();
-    }
-
     fn update_tray_and_mpris_current(&mut self, is_playing: bool) {
-        let (title, artist) = self
-            .library
-            .current_song
-            .as_ref()
-            .map(|s| (Some(s.title.clone()), Some(s.artist.clone())))
-            .unwrap_or((None, None));
-        update_tray_state_full(is_playing, title, artist, self.core.settings.play_mode);
+        self.update_tray_state(is_playing);
         self.update_mpris_state();
     }
 
     fn apply_seek(&mut self) -> Task<Message> {
         if let Some(preview_pos) = self.ui.seek_preview_position.take() {
+            self.ui.lyrics.redraw_requested = true;
             if let Some(player) = &self.core.audio {
                 let info = player.get_info();
                 if info.duration.as_secs_f32() > 0.0 {
@@ -294,6 +346,139 @@ impl App {
         Task::none()
     }
 
+    /// Whole wheel notches in `delta`, keeping the rest of a smooth
+    /// trackpad scroll for the next event (up is positive)
+    fn wheel_steps(&mut self, delta: ScrollDelta) -> i32 {
+        /// Trackpad travel that counts as one notch
+        const PIXELS_PER_STEP: f32 = 40.0;
+
+        let lines = match delta {
+            ScrollDelta::Lines { y, .. } => y,
+            ScrollDelta::Pixels { y, .. } => y / PIXELS_PER_STEP,
+        };
+        // Turning back drops what was left over the other way
+        if lines.signum() != self.ui.wheel_scroll.signum() {
+            self.ui.wheel_scroll = 0.0;
+        }
+        self.ui.wheel_scroll += lines;
+        let steps = self.ui.wheel_scroll.trunc();
+        self.ui.wheel_scroll -= steps;
+        steps as i32
+    }
+
+    /// Raise or lower the volume by `steps` volume steps
+    pub(super) fn step_volume(&mut self, steps: i32) -> Task<Message> {
+        let Some(player) = &self.core.audio else {
+            return Task::none();
+        };
+        if steps == 0 {
+            return Task::none();
+        }
+        let step = self.core.settings.playback.volume_step_percent as f32 / 100.0;
+        let volume = (player.get_info().volume + step * steps as f32).clamp(0.0, 1.0);
+        self.update(Message::SetVolume(volume))
+    }
+
+    /// Jump `secs` seconds from the current position (negative goes back)
+    pub(super) fn skip_by(&mut self, secs: i64) {
+        if self.library.current_song.is_none() {
+            return;
+        }
+        if let Some(player) = &self.core.audio {
+            player.seek_by(secs * 1000);
+            self.ui.lyrics.redraw_requested = true;
+            self.update_mpris_state();
+        }
+    }
+
+    /// Qualities the current NCM song is available in, and the one it
+    /// streams at: the pick for this play, else the global setting, capped
+    /// at the best the track offers
+    pub fn current_track_quality(&self) -> Option<(MusicQuality, &[MusicQuality])> {
+        let song = self.library.current_song.as_ref()?;
+        let (id, available) = self.library.track_qualities.as_ref()?;
+        if *id != song.id {
+            return None;
+        }
+        let wanted = match self.library.track_quality {
+            Some((id, quality)) if id == song.id => quality,
+            _ => self.core.stream_quality(),
+        };
+        let quality = available
+            .iter()
+            .rev()
+            .copied()
+            .find(|q| q.to_api_rate() <= wanted.to_api_rate())?;
+        Some((quality, available))
+    }
+
+    /// Look up which qualities an NCM song can be streamed at
+    pub(super) fn load_track_qualities(&self, song: &DbSong) -> Task<Message> {
+        let Some(client) = &self.core.ncm_client else {
+            return Task::none();
+        };
+        let Some(ncm_id) = song.id.ncm_id() else {
+            return Task::none();
+        };
+        let client = client.clone();
+        let song_id = song.id;
+        Task::perform(
+            async move { client.max_bitrate(ncm_id).await },
+            move |result| match result {
+                Ok(bitrate) => {
+                    Message::TrackQualitiesLoaded(song_id, MusicQuality::available_up_to(bitrate))
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to look up qualities for {}: {}", ncm_id, e);
+                    Message::NoOp
+                }
+            },
+        )
+    }
+
+    /// Chapter title and lyric line at the hovered seek bar position
+    pub fn seek_hover_preview(&self, duration_secs: f32) -> Option<String> {
+        let song = self.library.current_song.as_ref()?;
+        let position_ms = (self.ui.seek_hover? * duration_secs * 1000.0) as u64;
+
+        let song_chapters = self.current_chapters();
+        let chapter = chapters::chapter_at(song_chapters, position_ms).map(|idx| {
+            let title = &song_chapters[idx].title;
+            if title.is_empty() {
+                self.core
+                    .locale
+                    .get(Key::ChapterUntitled)
+                    .replace("{}", &(idx + 1).to_string())
+            } else {
+                title.clone()
+            }
+        });
+
+        let lyrics_ready =
+            self.ui.lyrics.loading_song_id == Some(song.id) && !self.ui.lyrics.is_loading;
+        let line = pages::find_current_line(&self.ui.lyrics.lines, position_ms)
+            .filter(|_| lyrics_ready)
+            .map(|idx| self.ui.lyrics.lines[idx].text.trim())
+            .filter(|text| !text.is_empty());
+
+        match (chapter, line) {
+            (Some(chapter), Some(line)) => Some(format!("{}\n{}", chapter, line)),
+            (Some(chapter), None) => Some(chapter),
+            (None, Some(line)) => Some(line.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// Confirm an action with a sound, if the user enabled UI sounds
+    pub(super) fn play_ui_sound(&self, sound: UiSound) {
+        if !self.core.settings.playback.ui_sounds {
+            return;
+        }
+        if let Some(player) = &self.core.audio {
+            player.play_ui_sound(sound);
+        }
+    }
+
     pub fn update_audio_tick(&self) {
         if let Some(player) = &self.core.audio {
             player.tick();
@@ -304,13 +489,14 @@ impl App {
         self.update_audio_tick();
         self.update_mpris_state();
 
-        let lyrics_scroll_task = if self.ui.lyrics.is_open {
+        let lyrics_scroll_task = if self.ui.lyrics.is_shown() {
             self.update_lyrics_animations()
         } else {
             Task::none()
         };
 
         self.check_lyrics_page_close();
+        let crossfade_task = self.auto_dj_crossfade();
 
         // Auto-save position every 5 seconds
         self.ui.save_position_counter += 1;
@@ -322,10 +508,11 @@ impl App {
                 if player.is_playing() {
                     let info = player.get_info();
                     let position_secs = info.position.as_secs_f64();
+                    self.record_crash_session(position_secs);
                     let db = db.clone();
                     let song_id = song.id;
                     let queue_pos = self.library.queue_index.unwrap_or(0) as i64;
-                    tokio::spawn(async move {
+                    Job::new(Category::Database).spawn(async move {
                         let _ = db
                             .update_playback_position(Some(song_id), queue_pos, position_secs)
                             .await;
@@ -334,7 +521,7 @@ impl App {
             }
         }
 
-        lyrics_scroll_task
+        Task::batch([lyrics_scroll_task, crossfade_task])
     }
 
     pub fn handle_audio_event(&mut self, event: AudioEvent) -> Task<Message> {
@@ -357,7 +544,9 @@ impl App {
             AudioEvent::SeekFailed { error } => {
                 tracing::warn!("Seek failed: {}", error);
                 if error.contains("not supported") {
-                    return Task::done(Message::ShowToast("该格式不支持拖动进度条".to_string()));
+                    return Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::SeekUnsupported).to_string(),
+                    ));
                 }
                 if error.contains("end of stream") || error.contains("streaming") {
                     let progress = self
@@ -366,10 +555,12 @@ impl App {
                         .as_ref()
                         .map(|b| (b.progress() * 100.0) as u32)
                         .unwrap_or(0);
-                    return Task::done(Message::ShowToast(format!(
-                        "正在缓冲中 ({}%)，请稍候再拖动进度",
-                        progress
-                    )));
+                    return Task::done(Message::ShowToast(
+                        self.core
+                            .locale
+                            .get(Key::SeekBuffering)
+                            .replace("{}", &progress.to_string()),
+                    ));
                 }
             }
             AudioEvent::SeekStarted { target_position } => {
@@ -418,15 +609,22 @@ impl App {
             AudioEvent::PreloadFailed { request_id, error } => {
                 tracing::warn!("Preload failed: request_id={}, error={}", request_id, error);
             }
+            AudioEvent::PreloadsDropped { request_ids } => {
+                tracing::debug!("Preloads dropped: {:?}", request_ids);
+                self.library.preload_manager.drop_requests(&request_ids);
+                return self.preload_adjacent_tracks_with_ncm();
+            }
             AudioEvent::DeviceSwitched { restore_state } => {
                 tracing::info!("Audio device switched: {:?}", restore_state);
             }
             AudioEvent::DeviceSwitchFailed { error } => {
                 tracing::error!("Device switch failed: {}", error);
-                return Task::done(Message::ShowErrorToast(format!(
-                    "切换音频设备失败: {}",
-                    error
-                )));
+                return Task::done(Message::ShowErrorToast(
+                    self.core
+                        .locale
+                        .get(Key::AudioDeviceSwitchFailed)
+                        .replace("{}", &error),
+                ));
             }
             AudioEvent::Finished => {
                 tracing::info!("Song finished (AudioEvent::Finished)");
@@ -438,7 +636,12 @@ impl App {
             }
             AudioEvent::Error { message } => {
                 tracing::error!("Audio error: {}", message);
-                return Task::done(Message::ShowErrorToast(format!("播放错误: {}", message)));
+                return Task::done(Message::ShowErrorToast(
+                    self.core
+                        .locale
+                        .get(Key::PlaybackError)
+                        .replace("{}", &message),
+                ));
             }
         }
         Task::none()
@@ -446,7 +649,7 @@ impl App {
 
     fn handle_streaming_event(
         &mut self,
-        song_id: i64,
+        song_id: SongId,
         event: crate::audio::streaming::StreamingEvent,
     ) -> Task<Message> {
         use crate::audio::streaming::StreamingEvent;
@@ -475,7 +678,12 @@ impl App {
             StreamingEvent::Error(err) => {
                 tracing::error!("Streaming error for song {}: {}", song_id, err);
                 self.library.streaming_buffer = None;
-                return Task::done(Message::ShowErrorToast(format!("下载失败: {}", err)));
+                return Task::done(Message::ShowErrorToast(
+                    self.core
+                        .locale
+                        .get(Key::DownloadFailed)
+                        .replace("{}", &err),
+                ));
             }
         }
         Task::none()
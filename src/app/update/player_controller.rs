@@ -7,11 +7,13 @@ use std::path::PathBuf;
 
 use iced::Task;
 
-use crate::app::helpers::update_tray_state_full;
+use crate::api::ApiError;
 use crate::app::message::Message;
 use crate::app::state::App;
-use crate::database::DbSong;
-use crate::features::PlayMode;
+use crate::database::{DbSong, SongId};
+use crate::features::jobs::{self, Category, Job, Scope};
+use crate::features::{MusicQuality, PlayMode};
+use crate::i18n::Key;
 
 use super::queue_navigator::QueueNavigator;
 
@@ -74,9 +76,9 @@ impl App {
                 self.library.consecutive_failures
             );
 
-            Task::done(Message::ShowErrorToast(format!(
-                "连续 {} 首歌曲播放失败，已停止播放",
-                MAX_CONSECUTIVE_FAILURES
+            Task::done(Message::ShowErrorToast(self.core.locale.count(
+                Key::PlaybackStoppedAfterFailures,
+                MAX_CONSECUTIVE_FAILURES as u64,
             )))
         } else {
             Task::none()
@@ -97,7 +99,7 @@ impl App {
     fn try_play_song(&mut self, song: &DbSong) -> PlayResult {
         let path = PathBuf::from(&song.file_path);
         if song.file_path.is_empty() || !path.exists() {
-            if song.file_path.starts_with("ncm://") || song.id < 0 {
+            if crate::features::song_source::is_ncm(song) {
                 return PlayResult::NeedsResolution;
             }
             return PlayResult::Failed(format!("File not found: {}", song.file_path));
@@ -114,50 +116,34 @@ impl App {
     fn on_song_started(&mut self, idx: usize, song: DbSong) -> Task<Message> {
         tracing::info!("Playing: {} - {}", song.title, song.artist);
 
+        self.cancel_song_jobs();
+
         // Ensure cover path is local (not remote URL)
         let (song, needs_cover_download) = self.ensure_local_cover_path_with_download(idx, song);
 
         self.library.current_song = Some(song.clone());
+        self.library.auto_dj_crossfaded = None;
+        self.library.stop_after_current = false;
+        self.library.quality_switch_position = None;
+        if self
+            .library
+            .track_quality
+            .is_some_and(|(id, _)| id != song.id)
+        {
+            self.library.track_quality = None;
+        }
 
         if let Some(db) = &self.core.db {
-            let db = db.clone();
-            let song_id = song.id;
-            tokio::spawn(async move {
-                let _ = db.record_play(song_id, 0, false).await;
-            });
+            db.record_play(song.id, 0, false);
         }
 
-        update_tray_state_full(
-            true,
-            Some(song.title.clone()),
-            Some(song.artist.clone()),
-            self.core.settings.play_mode,
-        );
-
-        // Update tray with favorite status for NCM songs
-        if song.id < 0 {
-            let ncm_id = (-song.id) as u64;
-            let is_favorited = self
-                .core
-                .user_info
-                .as_ref()
-                .map(|u| u.like_songs.contains(&ncm_id))
-                .unwrap_or(false);
-            crate::app::helpers::update_tray_state_with_favorite(
-                true,
-                Some(song.title.clone()),
-                Some(song.artist.clone()),
-                self.core.settings.play_mode,
-                Some(ncm_id),
-                is_favorited,
-            );
-        }
+        self.update_tray_state(true);
 
         if let Some(db) = &self.core.db {
             let db = db.clone();
             let song_id = song.id;
             let queue_pos = idx as i64;
-            tokio::spawn(async move {
+            Job::new(Category::Database).spawn(async move {
                 let _ = db
                     .update_playback_position(Some(song_id), queue_pos, 0.0)
                     .await;
@@ -183,7 +169,7 @@ impl App {
         };
 
         // 3. 歌词页面相关更新
-        let lyrics_task = if self.ui.lyrics.is_open {
+        let lyrics_task = if self.ui.lyrics.is_shown() {
             // 歌词页面已打开：加载歌词 + 更新背景
             self.load_lyrics_for_current_song(&song)
         } else {
@@ -191,7 +177,31 @@ impl App {
             self.preload_lyrics_for_song(&song)
         };
 
-        Task::batch([preload_task, cover_task, lyrics_task])
+        // 4. Auto-DJ 补充队列
+        let auto_dj_task = self.auto_dj_top_up_if_needed();
+
+        // 5. 读取章节标记（本地文件）
+        let chapters_task = self.load_chapters(&song);
+
+        // 6. 查询在线歌曲可用音质
+        let quality_task = self.load_track_qualities(&song);
+
+        // 7. 制作信息视图打开时刷新
+        let credits_task = if self.ui.lyrics.credits_visible {
+            self.load_song_credits(&song)
+        } else {
+            Task::none()
+        };
+
+        Task::batch([
+            preload_task,
+            cover_task,
+            lyrics_task,
+            auto_dj_task,
+            chapters_task,
+            quality_task,
+            credits_task,
+        ])
     }
 
     /// 为当前歌曲加载歌词和背景（歌词页面打开时调用）
@@ -209,12 +219,10 @@ impl App {
         idx: usize,
         mut song: DbSong,
     ) -> (DbSong, Option<(u64, String)>) {
-        // Only process NCM songs (negative ID)
-        if song.id >= 0 {
+        // Only process NCM songs
+        let Some(ncm_id) = song.id.ncm_id() else {
             return (song, None);
-        }
-
-        let ncm_id = (-song.id) as u64;
+        };
         let cover_cache_dir = crate::utils::covers_cache_dir();
         let stem = format!("cover_{}", ncm_id);
 
@@ -265,18 +273,25 @@ impl App {
         (song, Some((ncm_id, String::new())))
     }
 
+    /// Abort cover and lyric work still running for the previous song
+    fn cancel_song_jobs(&mut self) {
+        jobs::cancel(Scope::Song);
+        // An aborted lyric fetch never reports back, so don't wait for it
+        self.ui.lyrics.is_loading = false;
+    }
+
     /// Download cover for current playing song
     /// If cover_url is empty, fetch from API first
     fn download_current_song_cover(
         &self,
-        song_id: i64,
+        song_id: SongId,
         ncm_id: u64,
         cover_url: String,
     ) -> Task<Message> {
         if let Some(client) = &self.core.ncm_client {
             let client = client.clone();
             Task::perform(
-                async move {
+                Job::new(Category::Cover).song().run(async move {
                     // If cover_url is empty, we need to fetch it from API first
                     let actual_url = if cover_url.is_empty() {
                         tracing::info!("Fetching cover URL from API for ncm_id={}", ncm_id);
@@ -318,9 +333,9 @@ impl App {
                     } else {
                         None
                     }
-                },
+                }),
                 |result| {
-                    if let Some((song_id, path)) = result {
+                    if let Some((song_id, path)) = result.flatten() {
                         Message::CurrentSongCoverReady(song_id, path)
                     } else {
                         Message::NoOp
@@ -335,10 +350,12 @@ impl App {
     /// 预计算并缓存 shuffle 模式的 next/prev 索引
     /// 确保预加载和实际播放使用相同的索引
     pub fn cache_shuffle_indices(&mut self) {
-        let queue_len = self.library.queue.len();
-
         if self.core.settings.play_mode == PlayMode::Shuffle {
-            self.library.shuffle_cache.regenerate(queue_len);
+            self.library
+                .shuffle_cache
+                .regenerate(&self.library.queue, &self.library.blocklist);
+            // The tray lists the song shuffle picked next
+            self.refresh_tray_state();
         } else {
             self.library.shuffle_cache.clear();
         }
@@ -356,7 +373,12 @@ impl App {
         self.library.pending_resolution_idx = Some(idx);
 
         if let Some(client) = &self.core.ncm_client {
-            let client = std::sync::Arc::new(client.clone());
+            // A quality picked for this play overrides the global setting
+            let client = match self.library.track_quality {
+                Some((id, quality)) if id == song.id => client.with_quality(quality.to_api_rate()),
+                _ => client.clone(),
+            };
+            let client = std::sync::Arc::new(client);
             let song_id = song.id;
 
             // Create channel for streaming events
@@ -369,18 +391,15 @@ impl App {
                         .await
                         .map(|resolved| (idx, resolved))
                 },
-                move |result| {
-                    if let Some((idx, resolved)) = result {
-                        Message::SongResolvedStreaming(
-                            idx,
-                            resolved.file_path,
-                            resolved.cover_path,
-                            resolved.shared_buffer,
-                            resolved.duration_secs,
-                        )
-                    } else {
-                        Message::SongResolveFailed
-                    }
+                move |result| match result {
+                    Ok((idx, resolved)) => Message::SongResolvedStreaming(
+                        idx,
+                        resolved.file_path,
+                        resolved.cover_path,
+                        resolved.shared_buffer,
+                        resolved.duration_secs,
+                    ),
+                    Err(e) => Message::SongResolveFailed(ApiError::of(&e)),
                 },
             );
 
@@ -407,7 +426,9 @@ impl App {
             Task::batch([resolve_task, event_task])
         } else {
             self.library.pending_resolution_idx = None;
-            Task::done(Message::ShowToast("请先登录".to_string()))
+            Task::done(Message::ShowToast(
+                self.core.locale.get(Key::LoginRequired).to_string(),
+            ))
         }
     }
 
@@ -437,7 +458,7 @@ impl App {
             if let Some(db) = &self.core.db {
                 let db = db.clone();
                 let song_clone = song.clone();
-                tokio::spawn(async move {
+                Job::new(Category::Database).spawn(async move {
                     let _ = db.upsert_ncm_song(&song_clone).await;
                 });
             }
@@ -454,13 +475,23 @@ impl App {
             return Task::none();
         }
 
+        // A quality switch carries on where the song was
+        let quality_switch = match self.library.quality_switch_position {
+            Some((switch_idx, position)) if switch_idx == idx => {
+                self.library.quality_switch_position = None;
+                Some(position)
+            }
+            _ => None,
+        };
+
         // Check if we should restore playback position (for app restart scenario)
-        let restore_position = self
-            .library
-            .playback_state
-            .as_ref()
-            .filter(|s| s.position_secs > 0.0 && s.queue_position == idx as i64)
-            .map(|s| s.position_secs);
+        let restore_position = quality_switch.or_else(|| {
+            self.library
+                .playback_state
+                .as_ref()
+                .filter(|s| s.position_secs > 0.0 && s.queue_position == idx as i64)
+                .map(|s| s.position_secs)
+        });
 
         // Clear pending state
         self.library.pending_resolution_idx = None;
@@ -502,6 +533,9 @@ impl App {
                             state.position_secs = 0.0;
                         }
                     }
+                    if quality_switch.is_some() {
+                        return self.on_quality_switched(song);
+                    }
                     return self.on_song_started(idx, song);
                 }
             }
@@ -521,6 +555,9 @@ impl App {
                             state.position_secs = 0.0;
                         }
                     }
+                    if quality_switch.is_some() {
+                        return self.on_quality_switched(song);
+                    }
                     self.on_song_started(idx, song)
                 }
                 _ => self.skip_to_next_playable(idx),
@@ -530,7 +567,59 @@ impl App {
         }
     }
 
-    fn calculate_next_index(&self) -> Option<usize> {
+    /// Replay the current NCM song at `quality` from where it is; the pick
+    /// lasts for this play only
+    pub(super) fn switch_track_quality(&mut self, quality: MusicQuality) -> Task<Message> {
+        let (Some(idx), Some(song)) = (self.library.queue_index, self.library.current_song.clone())
+        else {
+            return Task::none();
+        };
+        let Some(ncm_id) = song.id.ncm_id() else {
+            return Task::none();
+        };
+        if self.current_track_quality().map(|(q, _)| q) == Some(quality) {
+            return Task::none();
+        }
+
+        let position = self
+            .core
+            .audio
+            .as_ref()
+            .map(|p| p.get_info().position.as_secs_f64())
+            .unwrap_or(0.0);
+        if let Some(player) = &self.core.audio {
+            player.stop();
+        }
+        if let Some(buffer) = self.library.streaming_buffer.take() {
+            buffer.cancel();
+        }
+
+        // The cached copy is at the old quality
+        if let Some(cached) =
+            crate::utils::find_cached_audio(&crate::utils::songs_cache_dir(), &ncm_id.to_string())
+        {
+            if let Err(e) = std::fs::remove_file(&cached) {
+                tracing::warn!("Failed to remove cached {:?}: {}", cached, e);
+            }
+        }
+
+        tracing::info!("Switching {} to {:?}", song.title, quality);
+        self.library.track_quality = Some((song.id, quality));
+        self.library.quality_switch_position = Some((idx, position));
+        self.resolve_and_play(idx, song)
+    }
+
+    /// The song is playing again after a quality switch; unlike a new song
+    /// this is not another play
+    fn on_quality_switched(&mut self, song: DbSong) -> Task<Message> {
+        self.library.consecutive_failures = 0;
+        self.library.current_song = Some(song);
+        self.update_mpris_state();
+        self.refresh_tray_state();
+        Task::none()
+    }
+
+    pub(super) fn calculate_next_index(&self) -> Option<usize> {
         let play_mode = if self.is_fm_mode() {
             PlayMode::Sequential
         } else {
@@ -570,6 +659,14 @@ impl App {
                 tracing::info!("FM mode: no next song, fetching more songs");
                 return self.fetch_more_fm_songs_and_play();
             }
+            if self.is_auto_dj() {
+                tracing::info!("Auto-DJ: queue ran out, fetching more songs");
+                return self.auto_dj_top_up(true);
+            }
+            if let Some(task) = self.autoplay_similar() {
+                tracing::info!("Queue finished, autoplaying similar songs");
+                return task;
+            }
             self.handle_queue_finished();
             return Task::none();
         }
@@ -657,12 +754,7 @@ impl App {
         );
 
         if let (Some(db), Some(song)) = (&self.core.db, &self.library.current_song) {
-            let db = db.clone();
-            let song_id = song.id;
-            let duration_secs = song.duration_secs;
-            tokio::spawn(async move {
-                let _ = db.record_play(song_id, duration_secs, true).await;
-            });
+            db.record_play(song.id, song.duration_secs, true);
         }
 
         // 清除播放完成状态，防止重复触发
@@ -670,18 +762,36 @@ impl App {
             player.stop();
         }
 
+        if self.library.stop_after_current {
+            tracing::info!("Stopping after the finished song as requested");
+            self.library.stop_after_current = false;
+            // Line up the next song so pressing play carries on with the queue
+            match self.calculate_next_index() {
+                Some(next_idx) => self.stop_at_index(next_idx),
+                None => self.handle_queue_finished(),
+            }
+            return Task::none();
+        }
+
         self.play_next_song()
     }
 
-    fn handle_queue_finished(&mut self) {
+    pub(super) fn handle_queue_finished(&mut self) {
         tracing::info!("Queue finished");
         if self.library.queue.is_empty() {
             return;
         }
+        self.stop_at_index(0);
+    }
 
-        self.library.queue_index = Some(0);
-        let first_song = self.library.queue[0].clone();
-        self.library.current_song = Some(first_song.clone());
+    /// Stop playback with the song at `idx` selected but not started
+    fn stop_at_index(&mut self, idx: usize) {
+        let Some(song) = self.library.queue.get(idx).cloned() else {
+            return;
+        };
+        let song_id = song.id;
+        self.library.queue_index = Some(idx);
+        self.library.current_song = Some(song);
 
         if let Some(player) = &self.core.audio {
             player.stop();
@@ -689,9 +799,11 @@ impl App {
 
         if let Some(db) = &self.core.db {
             let db = db.clone();
-            let song_id = first_song.id;
-            tokio::spawn(async move {
-                let _ = db.update_playback_position(Some(song_id), 0, 0.0).await;
+            let queue_pos = idx as i64;
+            Job::new(Category::Database).spawn(async move {
+                let _ = db
+                    .update_playback_position(Some(song_id), queue_pos, 0.0)
+                    .await;
             });
         }
 
@@ -699,22 +811,15 @@ impl App {
             state.position_secs = 0.0;
         }
 
-        update_tray_state_full(
-            false,
-            Some(first_song.title.clone()),
-            Some(first_song.artist.clone()),
-            self.core.settings.play_mode,
-        );
+        self.update_tray_state(false);
     }
 
     /// Preload lyrics for a song (triggers online fetch for NCM songs)
     pub fn preload_lyrics_for_song(&mut self, song: &DbSong) -> Task<Message> {
-        // Only preload for NCM songs (negative ID)
-        if song.id >= 0 {
+        // Only preload for NCM songs
+        let Some(ncm_id) = song.id.ncm_id() else {
             return Task::none();
-        }
-
-        let ncm_id = (-song.id) as u64;
+        };
 
         // Check if already cached
         if crate::features::lyrics::is_lyrics_cached(ncm_id) {
@@ -4,9 +4,14 @@
 use iced::Task;
 use iced::time::Instant;
 
-use crate::app::helpers::load_playlist_view;
+use crate::app::helpers::{load_playlist_mosaics, load_playlist_view};
 use crate::app::message::Message;
 use crate::app::state::{App, Route};
+use crate::audio::UiSound;
+use crate::database::SongId;
+use crate::features::jobs::{self, Category, Job, Scope};
+use crate::features::{ArtworkBackdrop, MusicQuality, PlaylistPlayback};
+use crate::i18n::Key;
 use crate::ui::widgets::Toast;
 
 impl App {
@@ -23,25 +28,276 @@ impl App {
 
         if let Some(db) = &self.core.db {
             let db = db.clone();
-            Task::perform(load_playlist_view(db, playlist_id), |result| match result {
-                Some(view) => Message::PlaylistViewLoaded(view),
-                None => Message::DatabaseError("Playlist not found".into()),
-            })
+            Task::perform(
+                load_playlist_view(db, playlist_id, self.core.locale),
+                |result| match result {
+                    Some(view) => Message::PlaylistViewLoaded(view),
+                    None => Message::DatabaseError("Playlist not found".into()),
+                },
+            )
         } else {
             Task::none()
         }
     }
 
     pub(super) fn reset_playlist_page_state(&mut self) {
+        self.keep_ncm_playlist();
         self.ui.playlist_page.search_expanded = false;
         self.ui.playlist_page.search_query.clear();
         self.ui.playlist_page.viewing_recently_played = false;
+        self.ui.playlist_page.song_pages = None;
+        self.ui.playlist_page.skeleton_since = None;
+        self.ui.playlist_page.playback_panel_open = false;
+        self.ui.playlist_page.tempo_sort = None;
+        self.ui.playlist_page.sort = Default::default();
+        self.ui.playlist_page.filter = Default::default();
+        self.cancel_page_jobs();
         self.ui.clear_playlist_animations();
 
         if self.ui.lyrics.is_open {
             self.ui.lyrics.is_open = false;
             self.ui.lyrics.animation.stop();
+            self.refresh_tray_state();
+        }
+    }
+
+    /// Drop cover downloads and other work for the page being left
+    pub(super) fn cancel_page_jobs(&mut self) {
+        jobs::cancel(Scope::Page);
+        // Cancelled downloads never report back, so let them be requested again
+        self.ui.playlist_page.pending_cover_downloads.clear();
+    }
+
+    /// Per-page work once a playlist is shown
+    pub(super) fn playlist_shown(&mut self) -> Task<Message> {
+        self.show_song_analysis();
+        Task::batch([
+            self.refresh_playlist_backdrop(),
+            self.load_playlist_playback(),
+        ])
+    }
+
+    /// Playback preferences for a playlist, known only while its page is open
+    pub(super) fn playlist_playback_for(&self, playlist_id: i64) -> PlaylistPlayback {
+        let page = &self.ui.playlist_page;
+        match &page.current {
+            Some(playlist) if playlist.id == playlist_id => page.playback,
+            _ => PlaylistPlayback::default(),
+        }
+    }
+
+    /// Reset the page to default preferences and look up the saved ones
+    fn load_playlist_playback(&mut self) -> Task<Message> {
+        let page = &mut self.ui.playlist_page;
+        page.playback = PlaylistPlayback::default();
+        let (Some(playlist_id), Some(db)) =
+            (page.current.as_ref().map(|p| p.id), self.core.db.clone())
+        else {
+            return Task::none();
+        };
+        Task::perform(
+            Job::new(Category::Database)
+                .page()
+                .run(async move { db.get_playlist_preference(playlist_id).await }),
+            move |result| match result {
+                Some(Ok(Some(preference))) => Message::PlaylistPlaybackLoaded(
+                    playlist_id,
+                    PlaylistPlayback {
+                        shuffle: preference.shuffle,
+                        quality: preference
+                            .quality
+                            .and_then(|rate| u32::try_from(rate).ok())
+                            .and_then(MusicQuality::from_api_rate),
+                        append_to_queue: preference.append_to_queue,
+                    },
+                ),
+                Some(Err(e)) => {
+                    tracing::warn!(
+                        "Failed to load playlist preference for {}: {}",
+                        playlist_id,
+                        e
+                    );
+                    Message::Noop
+                }
+                _ => Message::Noop,
+            },
+        )
+    }
+
+    /// Remember the open playlist's playback preferences
+    fn save_playlist_playback(&self) {
+        let (Some(playlist_id), Some(db)) = (
+            self.ui.playlist_page.current.as_ref().map(|p| p.id),
+            self.core.db.clone(),
+        ) else {
+            return;
+        };
+        let playback = self.ui.playlist_page.playback;
+        Job::new(Category::Database).spawn(async move {
+            let quality = playback.quality.map(|q| i64::from(q.to_api_rate()));
+            if let Err(e) = db
+                .save_playlist_preference(
+                    playlist_id,
+                    playback.shuffle,
+                    quality,
+                    playback.append_to_queue,
+                )
+                .await
+            {
+                tracing::warn!(
+                    "Failed to save playlist preference for {}: {}",
+                    playlist_id,
+                    e
+                );
+            }
+        });
+    }
+
+    /// Whether playlist pages draw the artwork backdrop at all
+    pub fn playlist_backdrop_enabled(&self) -> bool {
+        self.core.settings.display.artwork_backdrop != ArtworkBackdrop::Off
+            && !self.core.settings.display.power_saving_mode
+    }
+
+    /// Whether the artwork backdrop is drawn on the current page
+    fn playlist_backdrop_visible(&self) -> bool {
+        self.playlist_backdrop_enabled()
+            && self.ui.playlist_page.current.is_some()
+            && matches!(
+                self.ui.current_route,
+                Route::Playlist(_) | Route::NcmPlaylist(_) | Route::RecentlyPlayed
+            )
+    }
+
+    /// Cover color the playlist page is tinted with, if theming is on
+    pub fn playlist_tint(&self) -> Option<iced::Color> {
+        let display = &self.core.settings.display;
+        if !display.cover_theming {
+            return None;
+        }
+        let tint = &self.ui.playlist_page.tint;
+        // Power saving gets no frames to ease the color with
+        Some(if display.power_saving_mode {
+            tint.target()
+        } else {
+            tint.color()
+        })
+    }
+
+    /// The tint wants frames while easing toward a new cover's color
+    pub fn playlist_tint_needs_frames(&self) -> bool {
+        self.core.settings.display.cover_theming && self.ui.playlist_page.tint.is_transitioning()
+    }
+
+    /// The NCM playlist skeleton wants frames for its shimmer
+    pub fn playlist_skeleton_needs_frames(&self) -> bool {
+        self.ui.playlist_page.skeleton_phase().is_some()
+            && matches!(self.ui.current_route, Route::NcmPlaylist(_))
+    }
+
+    /// The backdrop wants frames while animated, or to finish a crossfade
+    pub fn playlist_backdrop_needs_frames(&self) -> bool {
+        self.playlist_backdrop_visible()
+            && (self.core.settings.display.artwork_backdrop == ArtworkBackdrop::Animated
+                || self.ui.playlist_page.backdrop.is_transitioning())
+    }
+
+    /// Rebuild cover mosaics for local playlists without a usable cover
+    pub(super) fn refresh_playlist_mosaics(&self) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        let playlist_ids: Vec<i64> = self
+            .library
+            .playlists
+            .iter()
+            .filter(|p| {
+                p.cover_path
+                    .as_deref()
+                    .is_none_or(|c| c.is_empty() || !std::path::Path::new(c).exists())
+            })
+            .map(|p| p.id)
+            .collect();
+
+        Task::perform(
+            load_playlist_mosaics(db, playlist_ids),
+            Message::PlaylistMosaicsReady,
+        )
+    }
+
+    /// Point the backdrop at the open playlist's cover, falling back to a
+    /// gradient from its palette until a local cover is available
+    pub(super) fn refresh_playlist_backdrop(&mut self) -> Task<Message> {
+        if !self.playlist_backdrop_enabled() {
+            return Task::none();
+        }
+        let page = &mut self.ui.playlist_page;
+        let Some(playlist) = &page.current else {
+            return Task::none();
+        };
+        let playlist_id = playlist.id;
+        let primary = playlist.palette.primary;
+        let cover = playlist
+            .cover_path
+            .iter()
+            .chain(playlist.songs.first().and_then(|s| s.cover_path.as_ref()))
+            .find(|p| !p.starts_with("http") && std::path::Path::new(p).exists())
+            .cloned();
+
+        page.backdrop_start_time.get_or_insert_with(Instant::now);
+        let Some(path) = cover else {
+            page.backdrop.set_palette(primary);
+            return Task::none();
+        };
+        if page.backdrop.is_same_image(std::path::Path::new(&path)) {
+            return Task::none();
+        }
+
+        Task::perform(
+            Job::new(Category::Disk).page().run_blocking(move || {
+                let rgb = image::open(&path)
+                    .inspect_err(|e| tracing::warn!("Failed to load backdrop cover: {}", e))
+                    .ok()?
+                    .to_rgb8();
+                let (width, height) = rgb.dimensions();
+                Some((path, rgb.into_raw(), width, height))
+            }),
+            move |result| match result.flatten() {
+                Some((path, data, width, height)) => {
+                    Message::PlaylistBackdropReady(playlist_id, path, data, width, height)
+                }
+                None => Message::Noop,
+            },
+        )
+    }
+
+    /// Advance the backdrop clock and crossfade
+    fn update_playlist_backdrop(&mut self, now: Instant) {
+        let page = &mut self.ui.playlist_page;
+        let delta_ms = match page.backdrop_last_update {
+            Some(last) => {
+                let gap = now.duration_since(last).as_secs_f32();
+                let delta = gap.clamp(0.001, 0.1);
+                // Resume the drift where it stopped instead of jumping ahead
+                if gap > delta {
+                    if let Some(start_time) = page.backdrop_start_time.as_mut() {
+                        *start_time += std::time::Duration::from_secs_f32(gap - delta);
+                    }
+                }
+                delta * 1000.0
+            }
+            None => 16.0,
+        };
+        page.backdrop_last_update = Some(now);
+
+        // A still backdrop keeps its initial shape and only crossfades
+        if self.core.settings.display.artwork_backdrop == ArtworkBackdrop::Animated {
+            if let Some(start_time) = page.backdrop_start_time {
+                let elapsed_ms = now.duration_since(start_time).as_secs_f32() * 1000.0;
+                page.backdrop.set_time(elapsed_ms / 10000.0);
+            }
         }
+        page.backdrop.update(delta_ms);
     }
 
     /// Handle playlist-related messages
@@ -71,7 +327,13 @@ impl App {
                         let db = db.clone();
                         return Some(Task::perform(
                             async move {
-                                db.delete_playlist(playlist_id).await.ok();
+                                if let Err(e) = db.trash_playlist(playlist_id).await {
+                                    tracing::error!(
+                                        "Failed to trash playlist {}: {}",
+                                        playlist_id,
+                                        e
+                                    );
+                                }
                                 playlist_id
                             },
                             Message::PlaylistDeleted,
@@ -97,7 +359,9 @@ impl App {
                     self.ui.playlist_page.current = None;
                 }
                 // Show toast with auto-dismiss after 3 seconds
-                self.ui.toast = Some(Toast::success("歌单已删除".to_string()));
+                self.ui.toast = Some(Toast::success(
+                    self.core.locale.get(Key::PlaylistDeleted).to_string(),
+                ));
                 self.ui.toast_visible = true;
                 Some(Task::perform(
                     async {
@@ -109,14 +373,48 @@ impl App {
 
             Message::PlaylistViewLoaded(view) => {
                 tracing::info!("Playlist view loaded: {}", view.name);
-                self.ui.playlist_page.current = Some(view.clone());
+                self.ui.playlist_page.show(view.clone());
                 self.ui.playlist_page.load_state =
                     crate::app::update::page_loader::PlaylistLoadState::Ready;
-                // Reset scroll position for playlist page
-                Some(iced::widget::operation::snap_to(
-                    iced::widget::Id::new("playlist_scroll"),
-                    iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
-                ))
+                Some(self.playlist_shown())
+            }
+
+            Message::PlaylistPlaybackLoaded(playlist_id, playback) => {
+                let page = &mut self.ui.playlist_page;
+                if page.current.as_ref().is_some_and(|p| p.id == *playlist_id) {
+                    page.playback = *playback;
+                }
+                Some(Task::none())
+            }
+
+            Message::TogglePlaylistPlaybackPanel => {
+                self.ui.playlist_page.playback_panel_open =
+                    !self.ui.playlist_page.playback_panel_open;
+                Some(Task::none())
+            }
+
+            Message::UpdatePlaylistPlayback(playback) => {
+                self.ui.playlist_page.playback = *playback;
+                self.save_playlist_playback();
+                Some(Task::none())
+            }
+
+            Message::PlaylistBackdropReady(playlist_id, path, data, width, height) => {
+                let is_current = self
+                    .ui
+                    .playlist_page
+                    .current
+                    .as_ref()
+                    .is_some_and(|p| p.id == *playlist_id);
+                if is_current {
+                    if let Some(img) = image::RgbImage::from_raw(*width, *height, data.clone()) {
+                        self.ui.playlist_page.backdrop.set_album_image(
+                            image::DynamicImage::ImageRgb8(img),
+                            Some(path.into()),
+                        );
+                    }
+                }
+                Some(Task::none())
             }
 
             Message::HoverSong(id) => {
@@ -150,10 +448,17 @@ impl App {
                 self.check_lyrics_page_close();
 
                 // Update lyrics animations if lyrics page is open
-                if self.ui.lyrics.is_open {
+                if self.ui.lyrics.is_shown() {
                     let _ = self.update_lyrics_animations();
                 }
 
+                if self.playlist_backdrop_needs_frames() {
+                    self.update_playlist_backdrop(now);
+                } else {
+                    self.ui.playlist_page.backdrop_last_update = None;
+                }
+                self.ui.playlist_page.tint.tick(now);
+
                 // 清理已完成的淡出动画
                 self.ui.cleanup_animations(now);
 
@@ -255,9 +560,11 @@ impl App {
                     let db2 = db.clone();
                     let id = *playlist_id;
                     return Some(Task::batch([
-                        Task::perform(load_playlist_view(db1, id), |result| match result {
-                            Some(view) => Message::PlaylistViewLoaded(view),
-                            None => Message::DatabaseError("Playlist not found".into()),
+                        Task::perform(load_playlist_view(db1, id, self.core.locale), |result| {
+                            match result {
+                                Some(view) => Message::PlaylistViewLoaded(view),
+                                None => Message::DatabaseError("Playlist not found".into()),
+                            }
                         }),
                         Task::perform(
                             async move { db2.get_all_playlists().await.unwrap_or_default() },
@@ -271,6 +578,7 @@ impl App {
             Message::ShowToast(msg) => {
                 self.ui.toast = Some(Toast::success(msg.clone()));
                 self.ui.toast_visible = true;
+                self.ui.toast_action = None;
                 // Auto-hide toast after 3 seconds
                 Some(Task::perform(
                     async {
@@ -283,6 +591,8 @@ impl App {
             Message::ShowErrorToast(msg) => {
                 self.ui.toast = Some(Toast::error(msg.clone()));
                 self.ui.toast_visible = true;
+                self.ui.toast_action = None;
+                self.play_ui_sound(UiSound::Error);
                 // Auto-hide error toast after 4 seconds
                 Some(Task::perform(
                     async {
@@ -312,6 +622,20 @@ impl App {
                 Some(Task::none())
             }
 
+            Message::PlaylistSortChanged(sort) => {
+                let page = &mut self.ui.playlist_page;
+                page.sort = *sort;
+                page.scroll_state.borrow_mut().scroll_to_top();
+                Some(Task::none())
+            }
+
+            Message::PlaylistFilterChanged(filter) => {
+                let page = &mut self.ui.playlist_page;
+                page.filter = *filter;
+                page.scroll_state.borrow_mut().scroll_to_top();
+                Some(Task::none())
+            }
+
             Message::PlaylistSearchSubmit => {
                 // Search is already applied via filtering in view
                 // This just handles the Enter key press
@@ -335,7 +659,12 @@ impl App {
 
     /// Check visible songs and request cover downloads for those missing covers
     /// Returns a Task if there are covers to download, None otherwise
-    fn check_visible_song_covers(&mut self) -> Option<Task<Message>> {
+    /// Deferred on a metered connection until it becomes unmetered
+    pub(super) fn check_visible_song_covers(&mut self) -> Option<Task<Message>> {
+        if self.core.is_metered() {
+            return None;
+        }
+
         // Only check if we have a playlist and it's an NCM playlist (negative ID)
         let playlist = self.ui.playlist_page.current.as_ref()?;
         if playlist.id >= 0 {
@@ -347,12 +676,13 @@ impl App {
         let (start, end) = scroll_state.visible_range();
         drop(scroll_state);
 
-        // Collect songs that need cover download
-        let mut songs_to_download: Vec<(i64, String)> = Vec::new();
+        // Visible rows index the filtered list while searching
+        let rows = self.ui.playlist_page.rows(&playlist.songs);
 
-        for idx in start..end.min(playlist.songs.len()) {
-            let song = &playlist.songs[idx];
+        // Collect songs that need cover download
+        let mut songs_to_download: Vec<(SongId, String)> = Vec::new();
 
+        for song in (start..end).filter_map(|row| rows.get(row)) {
             // Skip if already has cover_handle (cover loaded)
             if song.cover_handle.is_some() {
                 continue;
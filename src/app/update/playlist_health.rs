@@ -0,0 +1,325 @@
+//! Song availability check handlers for NCM playlists
+
+use iced::Task;
+use tracing::error;
+
+use super::ncm::PLAYLIST_PAGE_SIZE;
+use crate::api::NcmClient;
+use crate::app::message::Message;
+use crate::app::state::{App, HealthDialog};
+use crate::features::playlist_health::{self, HealthReport};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle playlist availability checks and their batch actions
+    pub fn handle_playlist_health(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::CheckPlaylistHealth(internal_id) => {
+                if self.ui.dialogs.health.is_some() {
+                    return Some(Task::none());
+                }
+                if self.core.ncm_client.is_none() || !self.core.is_logged_in {
+                    return Some(Task::done(Message::ShowToast(
+                        self.core.locale.get(Key::NotLoggedIn).to_string(),
+                    )));
+                }
+                // Daily recommendations (ID 0) have no track list to check
+                let Some(playlist) = self
+                    .ui
+                    .playlist_page
+                    .current
+                    .as_ref()
+                    .filter(|p| p.id == *internal_id && p.id < 0 && !p.is_local)
+                else {
+                    return Some(Task::none());
+                };
+                let editable = self
+                    .core
+                    .user_info
+                    .as_ref()
+                    .is_some_and(|user| user.user_id == playlist.creator_id);
+                let playlist_id = (-playlist.id) as u64;
+                let name = playlist.name.clone();
+
+                self.ui.dialogs.health = Some(HealthDialog {
+                    playlist_id,
+                    name,
+                    editable,
+                    report: None,
+                    job: None,
+                });
+                self.ui.dialogs.health_animation.start();
+                Some(self.start_health_check())
+            }
+
+            Message::PlaylistHealthChecked(playlist_id, result) => {
+                let Some(dialog) = self
+                    .ui
+                    .dialogs
+                    .health
+                    .as_mut()
+                    .filter(|d| d.playlist_id == *playlist_id)
+                else {
+                    return Some(Task::none());
+                };
+                dialog.job = None;
+                match result {
+                    Ok(report) => {
+                        dialog.report = Some(report.clone());
+                        Some(Task::none())
+                    }
+                    Err(e) => {
+                        error!("Failed to check NCM playlist {}: {}", playlist_id, e);
+                        self.close_health_dialog();
+                        Some(Task::done(Message::ShowErrorToast(
+                            self.core.locale.get(Key::HealthFailed).replace("{}", e),
+                        )))
+                    }
+                }
+            }
+
+            Message::RemoveUnavailableTracks => {
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::none());
+                };
+                let Some((dialog, report)) = self.idle_health_dialog() else {
+                    return Some(Task::none());
+                };
+                let ids = report.unavailable_ids();
+                if ids.is_empty() {
+                    return Some(Task::none());
+                }
+                let playlist_id = dialog.playlist_id;
+
+                let (task, handle) = Task::perform(
+                    async move {
+                        client
+                            .client
+                            .playlist_remove_tracks(playlist_id, &ids)
+                            .await
+                            .map(|_| ids.len())
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::FlaggedTracksRemoved,
+                )
+                .abortable();
+                dialog.job = Some(handle);
+                Some(task)
+            }
+
+            Message::ReplaceFlaggedTracks => {
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::none());
+                };
+                let is_vip = self.is_vip();
+                let Some((dialog, report)) = self.idle_health_dialog() else {
+                    return Some(Task::none());
+                };
+                if report.flagged.is_empty() {
+                    return Some(Task::none());
+                }
+                let playlist_id = dialog.playlist_id;
+
+                let (task, handle) = Task::perform(
+                    async move {
+                        playlist_health::replace_flagged(&client, playlist_id, &report, is_vip)
+                            .await
+                            .map(|replaced| (replaced, report.flagged.len()))
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::FlaggedTracksReplaced,
+                )
+                .abortable();
+                dialog.job = Some(handle);
+                Some(task)
+            }
+
+            Message::FlaggedTracksRemoved(result) => {
+                let locale = self.core.locale;
+                Some(self.finish_health_action(result.as_ref().map(|removed| {
+                    (
+                        *removed > 0,
+                        locale.count(Key::HealthRemoved, *removed as u64),
+                    )
+                })))
+            }
+
+            Message::FlaggedTracksReplaced(result) => {
+                let locale = self.core.locale;
+                Some(
+                    self.finish_health_action(result.as_ref().map(|(replaced, flagged)| {
+                        let toast = if *replaced == 0 {
+                            locale.get(Key::HealthNoAlternates).to_string()
+                        } else {
+                            locale
+                                .get(Key::HealthReplaced)
+                                .replacen("{}", &replaced.to_string(), 1)
+                                .replacen("{}", &flagged.to_string(), 1)
+                        };
+                        (*replaced > 0, toast)
+                    })),
+                )
+            }
+
+            Message::ExportFlaggedTracks => {
+                let Some(name) = self.ui.dialogs.health.as_ref().map(|d| d.name.clone()) else {
+                    return Some(Task::none());
+                };
+                Some(Task::perform(
+                    async move {
+                        rfd::AsyncFileDialog::new()
+                            .set_file_name(format!("{}.txt", name))
+                            .add_filter("Text", &["txt"])
+                            .save_file()
+                            .await
+                            .map(|handle| handle.path().to_path_buf())
+                    },
+                    Message::FlaggedTracksExportPathPicked,
+                ))
+            }
+
+            Message::FlaggedTracksExportPathPicked(path) => {
+                let Some(path) = path.clone() else {
+                    return Some(Task::none());
+                };
+                let locale = self.core.locale;
+                let Some(report) = self
+                    .ui
+                    .dialogs
+                    .health
+                    .as_ref()
+                    .and_then(|d| d.report.as_ref())
+                else {
+                    return Some(Task::none());
+                };
+                let text = report.export_text(|problem| problem.display_name(locale));
+
+                Some(Task::perform(
+                    async move {
+                        tokio::fs::write(&path, text)
+                            .await
+                            .map(|_| path)
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::FlaggedTracksExported,
+                ))
+            }
+
+            Message::FlaggedTracksExported(result) => {
+                let locale = self.core.locale;
+                Some(Task::done(match result {
+                    Ok(path) => Message::ShowToast(
+                        locale
+                            .get(Key::HealthExported)
+                            .replace("{}", &path.display().to_string()),
+                    ),
+                    Err(e) => {
+                        error!("Failed to export flagged songs: {}", e);
+                        Message::ShowErrorToast(
+                            locale.get(Key::HealthExportFailed).replace("{}", e),
+                        )
+                    }
+                }))
+            }
+
+            Message::ClosePlaylistHealth => {
+                self.close_health_dialog();
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Fetch the playlist's songs with their privileges and flag them
+    fn start_health_check(&mut self) -> Task<Message> {
+        let is_vip = self.is_vip();
+        let (Some(client), Some(dialog)) = (
+            self.core.ncm_client.clone(),
+            self.ui.dialogs.health.as_mut(),
+        ) else {
+            return Task::none();
+        };
+        dialog.report = None;
+
+        let (task, handle) =
+            Task::perform(check_playlist(client, dialog.playlist_id, is_vip), |msg| {
+                msg
+            })
+            .abortable();
+        // Closing the dialog drops the handle and stops the check
+        dialog.job = Some(handle.abort_on_drop());
+        task
+    }
+
+    /// The dialog and a copy of its report, when the playlist can be changed
+    /// and nothing is running
+    fn idle_health_dialog(&mut self) -> Option<(&mut HealthDialog, HealthReport)> {
+        let dialog = self
+            .ui
+            .dialogs
+            .health
+            .as_mut()
+            .filter(|d| d.editable && d.job.is_none())?;
+        let report = dialog.report.clone()?;
+        Some((dialog, report))
+    }
+
+    /// Report a batch action; a changed playlist is checked and shown again
+    fn finish_health_action(&mut self, result: Result<(bool, String), &String>) -> Task<Message> {
+        let Some(dialog) = &mut self.ui.dialogs.health else {
+            return Task::none();
+        };
+        dialog.job = None;
+        let playlist_id = dialog.playlist_id;
+
+        match result {
+            Ok((changed, toast)) => {
+                let toast = Task::done(Message::ShowToast(toast));
+                if !changed {
+                    return toast;
+                }
+                Task::batch([
+                    toast,
+                    self.start_health_check(),
+                    self.refresh_ncm_playlist(playlist_id),
+                ])
+            }
+            Err(e) => {
+                error!("Failed to update NCM playlist {}: {}", playlist_id, e);
+                Task::done(Message::ShowErrorToast(
+                    self.core
+                        .locale
+                        .get(Key::HealthUpdateFailed)
+                        .replace("{}", e),
+                ))
+            }
+        }
+    }
+
+    fn is_vip(&self) -> bool {
+        self.core
+            .user_info
+            .as_ref()
+            .is_some_and(|user| user.vip_type > 0)
+    }
+
+    fn close_health_dialog(&mut self) {
+        self.ui.dialogs.health = None;
+        self.ui.dialogs.health_animation.stop();
+    }
+}
+
+/// Load every song of the playlist and flag those that won't play
+async fn check_playlist(client: NcmClient, playlist_id: u64, is_vip: bool) -> Message {
+    let result = async {
+        let track_ids = client.client.playlist_track_ids(playlist_id).await?;
+        let mut songs = Vec::with_capacity(track_ids.len());
+        for chunk in track_ids.chunks(PLAYLIST_PAGE_SIZE) {
+            songs.extend(client.client.song_detail(chunk).await?);
+        }
+        anyhow::Ok(HealthReport::check(track_ids, &songs, is_vip))
+    }
+    .await;
+    Message::PlaylistHealthChecked(playlist_id, result.map_err(|e| e.to_string()))
+}
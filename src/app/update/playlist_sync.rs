@@ -0,0 +1,144 @@
+//! Reopening NCM playlists viewed before
+//!
+//! The kept copy is shown right away, then patched with the tracks added or
+//! removed since, see `features::playlist_sync`.
+
+use iced::Task;
+use tracing::{debug, error};
+
+use super::ncm::{PLAYLIST_PAGE_SIZE, total_duration_secs};
+use crate::api::NcmClient;
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::app::update::page_loader::{self, PlaylistLoadState};
+use crate::features::playlist_sync::{TrackDiff, arrange};
+use crate::i18n::Locale;
+
+impl App {
+    /// Handle updates to reopened NCM playlists
+    pub fn handle_playlist_sync(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::NcmPlaylistTracksSynced(playlist_id, track_ids, added, added_views) => {
+                let Some(playlist) = &mut self.ui.playlist_page.current else {
+                    return Some(Task::none());
+                };
+                if playlist.id != *playlist_id {
+                    return Some(Task::none());
+                }
+                self.ui.playlist_page.song_pages = None;
+
+                let songs = arrange(
+                    std::mem::take(&mut self.ui.home.current_ncm_playlist_songs),
+                    added.clone(),
+                    track_ids,
+                    |song| song.id,
+                );
+                let mut song_views = arrange(
+                    playlist.songs.clone(),
+                    added_views.clone(),
+                    track_ids,
+                    |song| song.id.id,
+                );
+                for (i, song) in song_views.iter_mut().enumerate() {
+                    song.index_str = (i + 1).to_string();
+                }
+
+                playlist.song_count = song_views.len() as u32;
+                playlist.total_duration_secs = total_duration_secs(&songs);
+                self.ui.home.current_ncm_playlist_songs = songs;
+                self.ui.playlist_page.replace_songs(song_views);
+
+                Some(self.check_visible_song_covers().unwrap_or_else(Task::none))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Keep the NCM playlist on screen before it is replaced or cleared
+    pub(super) fn keep_ncm_playlist(&mut self) {
+        let songs = &self.ui.home.current_ncm_playlist_songs;
+        self.ui.playlist_page.keep_ncm(songs);
+    }
+
+    /// Bring the NCM playlist on screen up to date after changing it
+    pub(super) fn refresh_ncm_playlist(&mut self, playlist_id: u64) -> Task<Message> {
+        if !self.is_viewing_ncm_playlist(playlist_id) {
+            return Task::none();
+        }
+        self.keep_ncm_playlist();
+        self.reopen_ncm_playlist(playlist_id)
+            .unwrap_or_else(Task::none)
+    }
+
+    /// Show a kept copy of an NCM playlist and fetch only its track IDs
+    ///
+    /// Returns `None` when the playlist isn't kept, so it loads in full.
+    pub(super) fn reopen_ncm_playlist(&mut self, playlist_id: u64) -> Option<Task<Message>> {
+        let client = self.core.ncm_client.clone()?;
+        let kept = self.ui.playlist_page.kept_ncm.take(playlist_id)?;
+        debug!("Reopening kept NCM playlist {}", playlist_id);
+
+        let internal_id = kept.view.id;
+        let kept_ids: Vec<u64> = kept.songs.iter().map(|song| song.id).collect();
+        self.ui.home.current_ncm_playlist_songs = kept.songs.clone();
+        self.ui.playlist_page.show_kept(kept);
+        self.ui.playlist_page.load_state = PlaylistLoadState::Ready;
+
+        let (sync_task, handle) = Task::perform(
+            sync_track_ids(client, playlist_id, internal_id, kept_ids, self.core.locale),
+            |msg| msg,
+        )
+        .abortable();
+        // Opening another playlist drops the handle and stops the sync
+        self.ui.playlist_page.song_pages = Some(handle.abort_on_drop());
+
+        Some(Task::batch([
+            sync_task,
+            self.playlist_shown(),
+            self.check_visible_song_covers().unwrap_or_else(Task::none),
+        ]))
+    }
+}
+
+/// Fetch the playlist's track IDs and details of only the added tracks
+async fn sync_track_ids(
+    client: NcmClient,
+    playlist_id: u64,
+    internal_id: i64,
+    kept_ids: Vec<u64>,
+    locale: Locale,
+) -> Message {
+    let track_ids = match client.client.playlist_track_ids(playlist_id).await {
+        Ok(track_ids) => track_ids,
+        Err(e) => {
+            // The kept copy stays on screen as it was
+            error!("Failed to refresh NCM playlist track IDs: {:?}", e);
+            return Message::NoOp;
+        }
+    };
+    // An empty list is more likely a bad response than an emptied playlist
+    if track_ids.is_empty() || track_ids == kept_ids {
+        return Message::NoOp;
+    }
+
+    let diff = TrackDiff::between(&kept_ids, &track_ids);
+    debug!(
+        "NCM playlist {} changed: {} added, {} removed",
+        playlist_id,
+        diff.added.len(),
+        diff.removed
+    );
+
+    let mut added = Vec::new();
+    for chunk in diff.added.chunks(PLAYLIST_PAGE_SIZE) {
+        match client.client.song_detail(chunk).await {
+            Ok(songs) => added.extend(songs),
+            Err(e) => error!("Failed to load added NCM playlist songs: {:?}", e),
+        }
+    }
+    // Track numbers are assigned once the rows are in playlist order
+    let added_views = page_loader::load_ncm_song_views(added.clone(), 0, locale).await;
+
+    Message::NcmPlaylistTracksSynced(internal_id, track_ids, added, added_views)
+}
@@ -0,0 +1,118 @@
+//! Plugin manager message handlers
+
+use std::sync::Arc;
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::features::plugins::{PluginRegistry, plugins_dir};
+
+impl App {
+    /// Rescan the plugins directory on a blocking thread
+    fn reload_plugins(&mut self) -> Task<Message> {
+        self.ui.plugins.loading = true;
+        let enabled = self.core.settings.plugins.enabled.clone();
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || PluginRegistry::load(&plugins_dir(), &enabled))
+                    .await
+                    .unwrap_or_default()
+            },
+            |registry| Message::PluginsLoaded(Arc::new(registry)),
+        )
+    }
+
+    /// Handle plugin-related messages
+    pub fn handle_plugins(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::ReloadPlugins => Some(self.reload_plugins()),
+
+            Message::PluginsLoaded(registry) => {
+                self.ui.plugins.loading = false;
+                self.ui.plugins.panels.clear();
+                self.core.plugins = registry.clone();
+                self.core
+                    .audio_chain
+                    .set_sample_processors(registry.audio_processors());
+
+                let registry = registry.clone();
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || registry.panels())
+                            .await
+                            .unwrap_or_default()
+                    },
+                    Message::PluginPanelsLoaded,
+                ))
+            }
+
+            Message::PluginPanelsLoaded(panels) => {
+                for (plugin_id, panel) in panels {
+                    match self
+                        .ui
+                        .plugins
+                        .panels
+                        .iter_mut()
+                        .find(|(id, _)| id == plugin_id)
+                    {
+                        Some((_, existing)) => *existing = panel.clone(),
+                        None => self
+                            .ui
+                            .plugins
+                            .panels
+                            .push((plugin_id.clone(), panel.clone())),
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::TogglePlugin(plugin_id, enabled) => {
+                let enabled_ids = &mut self.core.settings.plugins.enabled;
+                enabled_ids.retain(|id| id != plugin_id);
+                if *enabled {
+                    enabled_ids.push(plugin_id.clone());
+                }
+                tracing::info!("Plugin {} enabled: {}", plugin_id, enabled);
+
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    self.reload_plugins(),
+                ]))
+            }
+
+            Message::OpenPluginsFolder => {
+                let dir = plugins_dir();
+                if let Err(e) = std::fs::create_dir_all(&dir)
+                    .and_then(|_| crate::platform::shell::open_path(&dir))
+                {
+                    tracing::warn!("Failed to open plugins folder {:?}: {}", dir, e);
+                }
+                Some(Task::none())
+            }
+
+            Message::PluginPanelAction(plugin_id, action_id) => {
+                let registry = self.core.plugins.clone();
+                let plugin_id = plugin_id.clone();
+                let action_id = action_id.clone();
+
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            registry
+                                .panel_action(&plugin_id, &action_id)
+                                .map(|panel| vec![(plugin_id, panel)])
+                                .unwrap_or_default()
+                        })
+                        .await
+                        .unwrap_or_default()
+                    },
+                    Message::PluginPanelsLoaded,
+                ))
+            }
+
+            _ => None,
+        }
+    }
+}
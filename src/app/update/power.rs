@@ -0,0 +1,69 @@
+//! System sleep and wake
+
+use std::time::Duration;
+
+use iced::Task;
+use tracing::info;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::platform::power::PowerEvent;
+
+/// Time for the network to come back before sessions are checked on wake
+const NETWORK_SETTLE: Duration = Duration::from_secs(5);
+
+impl App {
+    /// Handle the system going to sleep and waking up
+    pub fn handle_power(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::PowerEvent(PowerEvent::Suspending) => {
+                if self.core.suspended {
+                    return Some(Task::none());
+                }
+                info!("System is going to sleep");
+                self.core.suspended = true;
+
+                // Pausing here saves the position too; playback stays paused
+                // after waking rather than starting up in an empty room
+                let playing = self.core.audio.as_ref().is_some_and(|p| p.is_playing());
+                if playing {
+                    return Some(self.update(Message::TogglePlayback));
+                }
+                Some(Task::none())
+            }
+
+            Message::PowerEvent(PowerEvent::Resumed) => {
+                info!("System woke up");
+                self.core.suspended = false;
+
+                // The output device is often gone or reset after sleep
+                if let Some(player) = &self.core.audio {
+                    player.switch_device(self.core.settings.system.audio_output_device.clone());
+                }
+                let devices = self.load_output_devices();
+
+                // Whatever QR code was showing has expired by now
+                let login_popup_open = self.ui.home.login_popup_open;
+                if login_popup_open {
+                    self.ui.home.qr_modules = None;
+                    self.ui.home.qr_unikey = None;
+                }
+
+                let mut settled = vec![Message::RefreshSession, Message::DetectMeteredConnection];
+                if login_popup_open {
+                    settled.push(Message::RequestQrCode);
+                }
+                Some(Task::batch(
+                    settled
+                        .into_iter()
+                        .map(|message| {
+                            Task::perform(tokio::time::sleep(NETWORK_SETTLE), move |_| message)
+                        })
+                        .chain([devices]),
+                ))
+            }
+
+            _ => None,
+        }
+    }
+}
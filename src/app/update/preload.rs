@@ -101,6 +101,11 @@ impl App {
             return None;
         }
 
+        // Don't prefetch over a metered connection
+        if self.core.is_metered() {
+            return None;
+        }
+
         // Mark as pending and create download task
         self.library.preload_manager.mark_pending(idx, is_next);
 
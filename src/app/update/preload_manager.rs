@@ -307,6 +307,22 @@ impl PreloadManager {
         }
     }
 
+    /// Forget ready preloads whose sinks the audio thread no longer has
+    pub fn drop_requests(&mut self, request_ids: &[u64]) {
+        for slot_ref in [&mut self.next, &mut self.prev] {
+            let dropped = slot_ref
+                .as_ref()
+                .and_then(|slot| slot.request_id)
+                .is_some_and(|id| request_ids.contains(&id));
+            if dropped {
+                if let Some(buffer) = slot_ref.as_ref().and_then(|slot| slot.buffer.as_ref()) {
+                    buffer.cancel();
+                }
+                *slot_ref = None;
+            }
+        }
+    }
+
     /// Get current slot state (for debugging/UI)
     #[allow(dead_code)]
     pub fn get_state(&self, is_next: bool) -> Option<&SlotState> {
@@ -366,10 +382,8 @@ async fn download_audio_streaming(
     song: DbSong,
     is_next: bool,
 ) -> Message {
-    let ncm_id = if song.id < 0 {
-        (-song.id) as u64
-    } else {
-        song.id as u64
+    let Some(ncm_id) = song.id.ncm_id() else {
+        return Message::PreloadAudioFailed(idx, is_next);
     };
 
     tracing::info!(
@@ -0,0 +1,159 @@
+//! NCM user profile page handlers
+
+use iced::Task;
+use tracing::error;
+
+use crate::app::message::Message;
+use crate::app::state::{App, ProfilePageState, Route};
+
+/// Playlists shown per user, created and subscribed together
+const PLAYLIST_LIMIT: u16 = 100;
+
+impl App {
+    /// Handle profile page loading and rank switching
+    pub fn handle_profile(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::UserProfileLoaded(uid, result) => {
+                if !self.is_viewing_profile(*uid) {
+                    return Some(Task::none());
+                }
+                let profile = match result {
+                    Ok(profile) => profile,
+                    Err(e) => {
+                        error!("Failed to load profile of user {}: {}", uid, e);
+                        self.ui.profile.failed = true;
+                        return Some(Task::none());
+                    }
+                };
+                self.ui.profile.profile = Some(profile.clone());
+
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::none());
+                };
+                let uid = *uid;
+                let avatar_url = profile.avatar_url.clone();
+                Some(Task::perform(
+                    async move { crate::utils::download_avatar(&client, uid, &avatar_url).await },
+                    move |path| match path {
+                        Some(path) => Message::UserProfileAvatarLoaded(uid, path),
+                        None => Message::NoOp,
+                    },
+                ))
+            }
+
+            Message::UserProfileAvatarLoaded(uid, path) => {
+                if self.is_viewing_profile(*uid) {
+                    self.ui.profile.avatar = Some(iced::widget::image::Handle::from_path(path));
+                }
+                Some(Task::none())
+            }
+
+            Message::UserProfilePlaylistsLoaded(uid, result) => {
+                if !self.is_viewing_profile(*uid) {
+                    return Some(Task::none());
+                }
+                match result {
+                    Ok(playlists) => self.ui.profile.playlists = playlists.clone(),
+                    Err(e) => error!("Failed to load playlists of user {}: {}", uid, e),
+                }
+                Some(Task::none())
+            }
+
+            Message::UserPlayRecordLoaded(uid, all_time, result) => {
+                let profile = &mut self.ui.profile;
+                if profile.user_id != *uid || profile.rank_all_time != *all_time {
+                    return Some(Task::none());
+                }
+                profile.rank_loading = false;
+                match result {
+                    Ok(Some(records)) => profile.rank = records.clone(),
+                    Ok(None) => profile.rank_hidden = true,
+                    Err(e) => error!("Failed to load listening rank of user {}: {}", uid, e),
+                }
+                Some(Task::none())
+            }
+
+            Message::SetProfileRankAllTime(all_time) => {
+                if self.ui.profile.rank_all_time == *all_time || self.ui.profile.rank_hidden {
+                    return Some(Task::none());
+                }
+                self.ui.profile.rank_all_time = *all_time;
+                Some(self.load_play_record())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Load the profile, playlists and listening rank of a user; going back
+    /// to the profile shown last keeps what was loaded
+    pub(super) fn open_user_profile_route(&mut self, uid: u64) -> Task<Message> {
+        let scroll_to_top = iced::widget::operation::snap_to(
+            iced::widget::Id::new("profile_scroll"),
+            iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
+        );
+        if self.ui.profile.user_id == uid && self.ui.profile.profile.is_some() {
+            return scroll_to_top;
+        }
+        self.ui.profile = ProfilePageState {
+            user_id: uid,
+            ..Default::default()
+        };
+        let Some(client) = self.core.ncm_client.clone() else {
+            self.ui.profile.failed = true;
+            return scroll_to_top;
+        };
+
+        let detail = {
+            let client = client.clone();
+            Task::perform(
+                async move {
+                    client
+                        .client
+                        .user_detail(uid)
+                        .await
+                        .map_err(|e| e.to_string())
+                },
+                move |result| Message::UserProfileLoaded(uid, result),
+            )
+        };
+        let playlists = Task::perform(
+            async move {
+                client
+                    .client
+                    .user_playlists(uid, PLAYLIST_LIMIT)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::UserProfilePlaylistsLoaded(uid, result),
+        );
+
+        Task::batch([scroll_to_top, detail, playlists, self.load_play_record()])
+    }
+
+    /// Fetch the listening rank for the range picked on the profile page
+    fn load_play_record(&mut self) -> Task<Message> {
+        let Some(client) = self.core.ncm_client.clone() else {
+            return Task::none();
+        };
+        let uid = self.ui.profile.user_id;
+        let all_time = self.ui.profile.rank_all_time;
+        self.ui.profile.rank.clear();
+        self.ui.profile.rank_loading = true;
+
+        Task::perform(
+            async move {
+                client
+                    .client
+                    .user_play_record(uid, all_time)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            move |result| Message::UserPlayRecordLoaded(uid, all_time, result),
+        )
+    }
+
+    fn is_viewing_profile(&self, uid: u64) -> bool {
+        self.ui.profile.user_id == uid && self.ui.current_route == Route::UserProfile(uid)
+    }
+}
@@ -1,101 +1,163 @@
 // src/app/update/queue.rs
 //! Queue management message handlers
 
+use std::collections::HashMap;
+
 use iced::Task;
 
+use super::ncm::queue_song_from_ncm;
+use crate::api::ApiError;
 use crate::app::message::Message;
 use crate::app::state::App;
+use crate::audio::UiSound;
+use crate::database::DbSong;
+use crate::features::jobs::{Category, Job};
+use crate::features::song_source::SongSource;
+use crate::features::{MusicQuality, PlayMode};
+use crate::i18n::Key;
 
 impl App {
+    /// Play a playlist's songs with the playback preferences saved for it
+    ///
+    /// Appending while something is playing only extends the queue; otherwise
+    /// playback starts at the first new song, or a random one when shuffling.
+    fn start_playlist(&mut self, playlist_id: i64, songs: Vec<DbSong>) -> Task<Message> {
+        if songs.is_empty() {
+            return Task::none();
+        }
+
+        let playback = self.playlist_playback_for(playlist_id);
+        self.set_quality_override(playback.quality);
+
+        if playback.shuffle && self.core.settings.play_mode != PlayMode::Shuffle {
+            self.core.settings.play_mode = PlayMode::Shuffle;
+            let _ = self.core.settings.save();
+            tracing::info!("Playlist {} switched play mode to shuffle", playlist_id);
+        }
+        let offset = if playback.shuffle {
+            use rand::Rng;
+            rand::rng().random_range(0..songs.len())
+        } else {
+            0
+        };
+
+        let added = songs.len();
+        let start = if playback.append_to_queue {
+            let start = self.library.queue.len();
+            self.library.queue.extend(songs);
+            start
+        } else {
+            self.library.queue = songs;
+            self.library.autoplay_ids.clear();
+            0
+        };
+        self.clear_shuffle_cache();
+
+        // Save queue to database
+        if let Some(db) = &self.core.db {
+            db.save_queue_with_songs(self.library.queue.clone(), None);
+        }
+
+        let is_playing = self.core.audio.as_ref().is_some_and(|a| a.is_playing());
+        if playback.append_to_queue && is_playing {
+            self.cache_shuffle_indices();
+            let _ = self.preload_adjacent_tracks_with_ncm();
+            self.refresh_tray_state();
+            self.play_ui_sound(UiSound::QueueAdd);
+            return Task::done(Message::ShowToast(
+                self.core
+                    .locale
+                    .count(Key::PlaylistAddedToQueue, added as u64),
+            ));
+        }
+
+        self.refresh_tray_state();
+        self.play_song_at_index(start + offset)
+    }
+
+    /// Stream at the quality a playlist asks for, or the global one for `None`
+    pub(super) fn set_quality_override(&mut self, quality: Option<MusicQuality>) {
+        if self.core.quality_override == quality {
+            return;
+        }
+        self.core.quality_override = quality;
+        if let Some(client) = &self.core.ncm_client {
+            client.set_quality(self.core.stream_quality().to_api_rate());
+        }
+    }
+
+    /// Check where each queued song plays from, off the UI thread
+    fn load_queue_sources(&self) -> Task<Message> {
+        let queue = self.library.queue.clone();
+        Task::perform(
+            Job::new(Category::Disk).run_blocking(move || {
+                queue
+                    .iter()
+                    .map(|song| (song.id, SongSource::of(song)))
+                    .collect::<HashMap<_, _>>()
+            }),
+            |sources| Message::QueueSourcesLoaded(sources.unwrap_or_default()),
+        )
+    }
+
     /// Handle queue-related messages
     pub fn handle_queue(&mut self, message: &Message) -> Option<Task<Message>> {
         match message {
             Message::ToggleQueue => {
                 self.ui.queue_visible = !self.ui.queue_visible;
+                self.ui.queue_sessions = None;
 
                 // When opening the queue, scroll to center the current song
                 if self.ui.queue_visible {
+                    self.ui.chapters_visible = false;
                     let offset = crate::ui::components::queue_panel::calculate_scroll_offset(
                         self.library.queue.len(),
                         self.library.queue_index,
                     );
-                    return Some(iced::widget::operation::snap_to(
-                        iced::widget::Id::new(
-                            crate::ui::components::queue_panel::QUEUE_SCROLLABLE_ID,
+                    return Some(Task::batch([
+                        iced::widget::operation::snap_to(
+                            iced::widget::Id::new(
+                                crate::ui::components::queue_panel::QUEUE_SCROLLABLE_ID,
+                            ),
+                            iced::widget::scrollable::RelativeOffset { x: 0.0, y: offset },
                         ),
-                        iced::widget::scrollable::RelativeOffset { x: 0.0, y: offset },
-                    ));
+                        self.load_queue_sources(),
+                    ]));
                 }
                 Some(Task::none())
             }
 
+            Message::QueueSourcesLoaded(sources) => {
+                self.ui.queue_sources = sources.clone();
+                Some(Task::none())
+            }
+
+            Message::ToggleQueueOfflineOnly => {
+                self.ui.queue_offline_only = !self.ui.queue_offline_only;
+                // Songs cached since the queue was opened count as offline now
+                Some(self.load_queue_sources())
+            }
+
             Message::PlayPlaylist(playlist_id) => {
                 self.exit_fm_mode();
                 let id = *playlist_id;
 
                 // For recently played (id = -1), use the recently_played list
                 if id == -1 {
-                    if !self.library.recently_played.is_empty() {
-                        let db_songs = self.library.recently_played.clone();
-                        self.library.queue = db_songs.clone();
-
-                        // Save queue to database
-                        if let Some(db) = &self.core.db {
-                            let db = db.clone();
-                            tokio::spawn(async move {
-                                let _ = db.save_queue_with_songs(&db_songs, None).await;
-                            });
-                        }
-
-                        return Some(self.play_song_at_index(0));
-                    }
-                    return Some(Task::none());
+                    let songs = self.library.recently_played.clone();
+                    return Some(self.start_playlist(id, songs));
                 }
 
                 // For NCM playlists (negative ID), use the cached NCM playlist songs
                 if id <= 0 {
-                    let ncm_songs = &self.ui.home.current_ncm_playlist_songs;
-                    if !ncm_songs.is_empty() {
-                        let db_songs: Vec<crate::database::DbSong> = ncm_songs
-                            .iter()
-                            .map(|song| crate::database::DbSong {
-                                id: -(song.id as i64),
-                                file_path: String::new(),
-                                title: song.name.clone(),
-                                artist: song.singer.clone(),
-                                album: song.album.clone(),
-                                duration_secs: (song.duration / 1000) as i64,
-                                track_number: None,
-                                year: None,
-                                genre: None,
-                                cover_path: if song.pic_url.is_empty() {
-                                    None
-                                } else {
-                                    Some(song.pic_url.clone())
-                                },
-                                file_hash: None,
-                                file_size: 0,
-                                format: Some("mp3".to_string()),
-                                play_count: 0,
-                                last_played: None,
-                                last_modified: 0,
-                                created_at: 0,
-                            })
-                            .collect();
-
-                        self.library.queue = db_songs.clone();
-
-                        // Save queue to database
-                        if let Some(db) = &self.core.db {
-                            let db = db.clone();
-                            tokio::spawn(async move {
-                                let _ = db.save_queue_with_songs(&db_songs, None).await;
-                            });
-                        }
-
-                        return Some(self.play_song_at_index(0));
-                    }
-                    return Some(Task::none());
+                    let db_songs: Vec<crate::database::DbSong> = self
+                        .ui
+                        .home
+                        .current_ncm_playlist_songs
+                        .iter()
+                        .map(queue_song_from_ncm)
+                        .collect();
+                    return Some(self.start_playlist(id, db_songs));
                 }
 
                 // For local playlists, load from database
@@ -103,27 +165,15 @@ impl App {
                     let db = db.clone();
                     return Some(Task::perform(
                         async move { db.get_playlist_songs(id).await.unwrap_or_default() },
-                        Message::QueueLoaded,
+                        move |songs| Message::QueueLoaded(id, songs),
                     ));
                 }
                 Some(Task::none())
             }
 
-            Message::QueueLoaded(songs) => {
+            Message::QueueLoaded(playlist_id, songs) => {
                 self.exit_fm_mode();
-                if !songs.is_empty() {
-                    if let Some(db) = &self.core.db {
-                        let db = db.clone();
-                        let songs_clone = songs.clone();
-                        tokio::spawn(async move {
-                            let _ = db.save_queue_with_songs(&songs_clone, None).await;
-                        });
-                    }
-
-                    self.library.queue = songs.clone();
-                    return Some(self.play_song_at_index(0));
-                }
-                Some(Task::none())
+                Some(self.start_playlist(*playlist_id, songs.clone()))
             }
 
             Message::PlayQueueIndex(idx) => Some(self.play_song_at_index(*idx)),
@@ -142,13 +192,32 @@ impl App {
                 *duration_secs,
             )),
 
-            Message::SongResolveFailed => {
+            Message::SongResolveFailed(api_error) => {
                 tracing::error!("Failed to resolve song");
+                let explain = match api_error {
+                    // Every other song would fail the same way, don't skip through the queue
+                    Some(
+                        err @ (ApiError::AuthExpired
+                        | ApiError::Network
+                        | ApiError::RateLimited
+                        | ApiError::CaptchaRequired),
+                    ) => {
+                        self.library.consecutive_failures = 0;
+                        return Some(Task::done(Message::ApiFailed(*err)));
+                    }
+                    Some(err) => Task::done(Message::ApiFailed(*err)),
+                    None => Task::none(),
+                };
                 // Use handle_playback_failure for consistent failure tracking
                 if let Some(idx) = self.library.queue_index {
-                    return Some(self.handle_playback_failure(idx, "Song resolution failed"));
+                    return Some(Task::batch([
+                        explain,
+                        self.handle_playback_failure(idx, "Song resolution failed"),
+                    ]));
                 }
-                Some(Task::done(Message::ShowToast("无法加载歌曲".to_string())))
+                Some(Task::done(Message::ShowToast(
+                    self.core.locale.get(Key::SongLoadFailed).to_string(),
+                )))
             }
 
             Message::RemoveFromQueue(idx) => {
@@ -166,16 +235,15 @@ impl App {
                         }
                     }
 
+                    // Goes through the same buffer as full saves so it can't be overwritten
+                    // by an older pending snapshot
                     if let Some(db) = &self.core.db {
-                        let db = db.clone();
-                        let position = *idx as i64;
-                        tokio::spawn(async move {
-                            let _ = db.remove_from_queue(position).await;
-                        });
+                        db.save_queue_with_songs(self.library.queue.clone(), None);
                     }
 
                     // Re-preload adjacent tracks after queue change
                     let _ = self.preload_adjacent_tracks_with_ncm();
+                    self.refresh_tray_state();
                 }
                 Some(Task::none())
             }
@@ -185,14 +253,79 @@ impl App {
                 self.library.queue_index = None;
 
                 if let Some(db) = &self.core.db {
-                    let db = db.clone();
-                    tokio::spawn(async move {
-                        let _ = db.clear_queue().await;
-                    });
+                    db.save_queue_with_songs(Vec::new(), None);
+                }
+                self.refresh_tray_state();
+                Some(Task::none())
+            }
+
+            Message::ToggleQueueSessions => {
+                if self.ui.queue_sessions.take().is_some() {
+                    return Some(Task::none());
                 }
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                Some(Task::perform(
+                    async move { db.get_queue_snapshots().await },
+                    |result| {
+                        Message::QueueSessionsLoaded(result.unwrap_or_else(|e| {
+                            tracing::warn!("Failed to load queue sessions: {}", e);
+                            Vec::new()
+                        }))
+                    },
+                ))
+            }
+
+            Message::QueueSessionsLoaded(sessions) => {
+                self.ui.queue_sessions = Some(sessions.clone());
                 Some(Task::none())
             }
 
+            Message::RestoreQueueSession(snapshot_id) => {
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                let snapshot_id = *snapshot_id;
+                Some(Task::perform(
+                    async move { db.get_queue_snapshot_songs(snapshot_id).await },
+                    |result| {
+                        Message::QueueSessionRestored(
+                            result
+                                .map_err(|e| tracing::warn!("Failed to restore queue: {}", e))
+                                .ok(),
+                        )
+                    },
+                ))
+            }
+
+            Message::QueueSessionRestored(restored) => {
+                let locale = self.core.locale;
+                let Some((songs, index)) = restored.clone().filter(|(songs, _)| !songs.is_empty())
+                else {
+                    return Some(Task::done(Message::ShowToast(
+                        locale.get(Key::QueueSessionUnavailable).to_string(),
+                    )));
+                };
+                self.exit_fm_mode();
+                self.ui.queue_sessions = None;
+
+                let added = songs.len();
+                self.library.queue = songs;
+                self.library.autoplay_ids.clear();
+                self.clear_shuffle_cache();
+                if let Some(db) = &self.core.db {
+                    db.save_queue_with_songs(self.library.queue.clone(), None);
+                }
+                self.refresh_tray_state();
+                Some(Task::batch([
+                    self.play_song_at_index(index),
+                    Task::done(Message::ShowToast(
+                        locale.count(Key::QueueSessionRestored, added as u64),
+                    )),
+                ]))
+            }
+
             _ => None,
         }
     }
@@ -6,6 +6,8 @@
 
 use crate::database::DbSong;
 use crate::features::PlayMode;
+use crate::features::blocklist::Blocklist;
+use crate::features::song_source;
 
 /// Cached shuffle indices for consistent preloading
 /// When in shuffle mode, we pre-calculate the next/prev indices so that
@@ -20,17 +22,25 @@ pub struct ShuffleCache {
 
 impl ShuffleCache {
     /// Generate new random indices for shuffle mode
-    pub fn regenerate(&mut self, queue_len: usize) {
-        if queue_len == 0 {
+    /// Blocked songs are only picked when nothing else is left in the queue.
+    pub fn regenerate(&mut self, queue: &[DbSong], blocklist: &Blocklist) {
+        if queue.is_empty() {
             self.next = None;
             self.prev = None;
             return;
         }
 
-        use rand::Rng;
+        use rand::seq::IndexedRandom;
+        let mut candidates: Vec<usize> = (0..queue.len())
+            .filter(|&i| !blocklist.blocks(queue[i].id, &queue[i].artist))
+            .collect();
+        if candidates.is_empty() {
+            candidates = (0..queue.len()).collect();
+        }
+
         let mut rng = rand::rng();
-        self.next = Some(rng.random_range(0..queue_len));
-        self.prev = Some(rng.random_range(0..queue_len));
+        self.next = candidates.choose(&mut rng).copied();
+        self.prev = candidates.choose(&mut rng).copied();
 
         tracing::debug!(
             "ShuffleCache regenerated: next={:?}, prev={:?}",
@@ -89,7 +99,8 @@ impl<'a> QueueNavigator<'a> {
             }
             PlayMode::LoopOne => Some(self.current_idx),
             PlayMode::LoopAll => Some((self.current_idx + 1) % self.queue_len),
-            PlayMode::Sequential => {
+            // Auto-DJ appends songs ahead of time, so it plays straight through
+            PlayMode::Sequential | PlayMode::AutoDj => {
                 let next = self.current_idx + 1;
                 if next >= self.queue_len {
                     None
@@ -122,7 +133,7 @@ impl<'a> QueueNavigator<'a> {
                     Some(self.current_idx - 1)
                 }
             }
-            PlayMode::Sequential => {
+            PlayMode::Sequential | PlayMode::AutoDj => {
                 if self.current_idx == 0 {
                     None
                 } else {
@@ -155,26 +166,7 @@ pub struct AdjacentIndices {
 
 /// Helper to check if a song needs NCM resolution
 pub fn needs_ncm_download(song: &DbSong) -> bool {
-    let is_ncm = song.id < 0 || song.file_path.is_empty() || song.file_path.starts_with("ncm://");
-    if !is_ncm {
-        return false;
-    }
-
-    // Check if already cached
-    let ncm_id = if song.id < 0 {
-        (-song.id) as u64
-    } else if song.file_path.starts_with("ncm://") {
-        song.file_path
-            .trim_start_matches("ncm://")
-            .parse()
-            .unwrap_or(0)
-    } else {
-        return false;
-    };
-
-    let song_cache_dir = crate::utils::songs_cache_dir();
-    let stem = ncm_id.to_string();
-    crate::utils::find_cached_audio(&song_cache_dir, &stem).is_none()
+    song_source::ncm_id(song).is_some_and(|id| song_source::cached_audio(id).is_none())
 }
 
 /// Skip to next playable track, handling failures
@@ -210,22 +202,12 @@ pub fn skip_to_next_playable(
 
 /// Helper to get local file path for a song (if available)
 pub fn get_local_path(song: &DbSong) -> Option<std::path::PathBuf> {
-    // Check if it's an NCM song
-    let is_ncm = song.id < 0 || song.file_path.is_empty() || song.file_path.starts_with("ncm://");
-
-    if is_ncm {
-        // For NCM songs, check cache with any audio extension
-        let ncm_id = if song.id < 0 {
-            (-song.id) as u64
-        } else if song.file_path.starts_with("ncm://") {
-            song.file_path.trim_start_matches("ncm://").parse().ok()?
-        } else {
-            return None;
-        };
-
-        let song_cache_dir = crate::utils::songs_cache_dir();
-        let stem = ncm_id.to_string();
-        return crate::utils::find_cached_audio(&song_cache_dir, &stem);
+    // For NCM songs, check cache with any audio extension
+    if let Some(ncm_id) = song_source::ncm_id(song) {
+        return song_source::cached_audio(ncm_id);
+    }
+    if song.file_path.is_empty() {
+        return None;
     }
 
     // For local songs, check if file exists
@@ -12,6 +12,7 @@ impl App {
         if self.ui.lyrics.is_open {
             self.ui.lyrics.is_open = false;
             self.ui.lyrics.animation.stop();
+            self.refresh_tray_state();
         }
     }
 
@@ -22,7 +23,11 @@ impl App {
     }
 
     fn clear_playlist_route_markers(&mut self) {
+        self.keep_ncm_playlist();
         self.ui.playlist_page.current = None;
+        // Leaving mid-load stops hydration, and lets the next open start fresh
+        self.ui.playlist_page.cancel_load();
+        self.cancel_page_jobs();
         self.ui.playlist_page.viewing_recently_played = false;
     }
 
@@ -53,7 +58,21 @@ impl App {
                 self.clear_playlist_route_markers();
                 self.ui.active_settings_section = *section;
             }
-            Route::AudioEngine => {
+            Route::AudioEngine | Route::Plugins => {
+                self.ui.search.keyword.clear();
+                self.clear_playlist_route_markers();
+            }
+            Route::Logs => {
+                self.ui.search.keyword.clear();
+                self.clear_playlist_route_markers();
+                self.ui.logs.records = crate::features::logs::snapshot();
+            }
+            Route::AnnualReport | Route::Blocklist | Route::UserProfile(_) => {
+                self.ui.search.keyword.clear();
+                self.clear_playlist_route_markers();
+            }
+            Route::Trash => {
+                self.ui.trash.confirming_empty = false;
                 self.ui.search.keyword.clear();
                 self.clear_playlist_route_markers();
             }
@@ -63,11 +82,15 @@ impl App {
             }
             Route::RecentlyPlayed => {
                 self.ui.search.keyword.clear();
+                self.keep_ncm_playlist();
                 self.ui.playlist_page.current = None;
                 self.ui.playlist_page.viewing_recently_played = true;
             }
             Route::Search { keyword, tab, page } => {
                 self.clear_playlist_route_markers();
+                if self.ui.search.keyword != *keyword {
+                    self.ui.search.local_songs.clear();
+                }
                 self.ui.search.keyword = keyword.clone();
                 self.ui.search.active_tab = *tab;
                 self.ui.search.current_page = *page;
@@ -86,15 +109,18 @@ impl App {
 
     fn route_effects(&mut self, route: &Route, should_reload_search: bool) -> Task<Message> {
         match route {
-            Route::Home => iced::widget::operation::snap_to(
-                iced::widget::Id::new("home_scroll"),
-                iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
-            ),
+            Route::Home => Task::batch([
+                iced::widget::operation::snap_to(
+                    iced::widget::Id::new("home_scroll"),
+                    iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
+                ),
+                self.refresh_home_recent(),
+            ]),
             Route::Discover(_) => {
                 let load_task = if !self.ui.discover.data_loaded {
                     self.load_discover_data()
                 } else {
-                    Task::none()
+                    self.pin_discover_covers()
                 };
                 Task::batch([
                     iced::widget::operation::snap_to(
@@ -125,6 +151,33 @@ impl App {
                 iced::widget::Id::new("audio_engine_scroll"),
                 iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
             ),
+            Route::Plugins => iced::widget::operation::snap_to(
+                iced::widget::Id::new("plugins_scroll"),
+                iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
+            ),
+            Route::Logs => iced::widget::operation::snap_to(
+                iced::widget::Id::new("logs_scroll"),
+                iced::widget::scrollable::RelativeOffset { x: 0.0, y: 1.0 },
+            ),
+            Route::AnnualReport => Task::batch([
+                iced::widget::operation::snap_to(
+                    iced::widget::Id::new("annual_report_scroll"),
+                    iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
+                ),
+                self.load_annual_report_years(),
+            ]),
+            Route::Blocklist => iced::widget::operation::snap_to(
+                iced::widget::Id::new("blocklist_scroll"),
+                iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
+            ),
+            Route::Trash => Task::batch([
+                iced::widget::operation::snap_to(
+                    iced::widget::Id::new("trash_scroll"),
+                    iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
+                ),
+                self.load_trash(),
+            ]),
+            Route::UserProfile(uid) => self.open_user_profile_route(*uid),
             Route::Playlist(id) => self.open_local_playlist_route(*id),
             Route::NcmPlaylist(id) => self.open_ncm_playlist_route(*id),
             Route::RecentlyPlayed => {
@@ -152,12 +205,19 @@ impl App {
                 } else {
                     Task::none()
                 };
+                // Local matches are not paginated, so they only load with the first page
+                let local_task = if should_reload_search && *tab == SearchTab::Songs && *page == 0 {
+                    self.fetch_local_search_results(keyword.clone())
+                } else {
+                    Task::none()
+                };
                 Task::batch([
                     iced::widget::operation::snap_to(
                         iced::widget::Id::new("search_scroll"),
                         iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
                     ),
                     fetch_task,
+                    local_task,
                 ])
             }
         }
@@ -176,8 +236,14 @@ impl App {
                 Some(Route::Settings(self.ui.active_settings_section))
             }
             Message::OpenAudioEngine => Some(Route::AudioEngine),
+            Message::OpenPlugins => Some(Route::Plugins),
+            Message::OpenLogs => Some(Route::Logs),
+            Message::OpenAnnualReport => Some(Route::AnnualReport),
+            Message::OpenBlocklist => Some(Route::Blocklist),
+            Message::OpenTrash => Some(Route::Trash),
             Message::OpenPlaylist(id) => Some(Route::Playlist(*id)),
             Message::OpenNcmPlaylist(id) => Some(Route::NcmPlaylist(*id)),
+            Message::OpenUserProfile(uid) => Some(Route::UserProfile(*uid)),
             Message::ScrollToSection(section) => Some(Route::Settings(*section)),
             Message::SearchSubmit => {
                 let keyword = self.ui.search_query.trim().to_string();
@@ -0,0 +1,114 @@
+//! Scheduled playback handlers
+
+use iced::Task;
+use tracing::info;
+
+use crate::app::{App, Message};
+use crate::features::schedules::VolumeRamp;
+use crate::i18n::Key;
+
+impl App {
+    /// Handle scheduled playback messages
+    pub fn handle_schedules(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::AddSchedule => {
+                self.core.settings.schedules.push(Default::default());
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::RemoveSchedule(index) => {
+                if *index < self.core.settings.schedules.len() {
+                    self.core.settings.schedules.remove(*index);
+                }
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::UpdateSchedule(index, schedule) => {
+                if let Some(existing) = self.core.settings.schedules.get_mut(*index) {
+                    let mut schedule = schedule.clone();
+                    // Moved to a time still ahead today, it should go off again
+                    if (schedule.hour, schedule.minute, schedule.days)
+                        != (existing.hour, existing.minute, existing.days)
+                    {
+                        schedule.last_run = None;
+                    }
+                    *existing = schedule;
+                }
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+
+            Message::CheckSchedules => Some(self.run_due_schedules()),
+
+            Message::ScheduleRampTick => {
+                let Some(ramp) = self.ui.schedule_ramp.as_mut() else {
+                    return Some(Task::none());
+                };
+                let Some(player) = &self.core.audio else {
+                    return Some(Task::none());
+                };
+                // Volume set by hand since the last step, through any route
+                if ramp.overridden(player.get_info().volume) {
+                    self.ui.schedule_ramp = None;
+                    return Some(Task::none());
+                }
+                let (volume, done) = ramp.step();
+                // Set on the player only; the saved volume stays the target
+                player.set_volume(volume);
+                if done {
+                    self.ui.schedule_ramp = None;
+                    self.update_mpris_state();
+                }
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Start the first schedule that is due, marking every due one as run
+    ///
+    /// Two schedules at the same time would only fight over the queue.
+    fn run_due_schedules(&mut self) -> Task<Message> {
+        let now = chrono::Local::now().naive_local();
+        let mut started = None;
+        for schedule in &mut self.core.settings.schedules {
+            if schedule.is_due(now) {
+                if started.is_none() {
+                    started = Some((schedule.playlist_id, schedule.ramp_minutes));
+                }
+                schedule.mark_run(now);
+            }
+        }
+        let Some((Some(playlist_id), ramp_minutes)) = started else {
+            return Task::none();
+        };
+
+        let name = self
+            .library
+            .playlists
+            .iter()
+            .find(|p| p.id == playlist_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+        info!("Schedule starting playlist {} ({})", playlist_id, name);
+
+        if ramp_minutes > 0
+            && let Some(player) = &self.core.audio
+        {
+            let ramp = VolumeRamp::new(player.get_info().volume, ramp_minutes);
+            player.set_volume(0.0);
+            self.ui.schedule_ramp = Some(ramp);
+        }
+
+        Task::batch([
+            Task::perform(async { Message::SaveSettings }, |m| m),
+            Task::done(Message::PlayPlaylist(playlist_id)),
+            Task::done(Message::ShowToast(
+                self.core
+                    .locale
+                    .get(Key::ScheduleStarted)
+                    .replace("{}", &name),
+            )),
+        ])
+    }
+}
@@ -6,20 +6,29 @@ use iced::Task;
 use crate::api::ncm_api::SearchType;
 use crate::app::message::{Message, SearchResultsPayload};
 use crate::app::state::{App, Route, SearchTab};
+use crate::database::SongId;
+use crate::i18n::Key;
 
 /// Default number of results per page
 const PAGE_SIZE: u32 = 50;
+/// Most local library matches shown for one keyword
+const LOCAL_RESULT_LIMIT: i64 = 200;
 
 impl App {
     /// Handle search-related messages
     pub fn handle_search(&mut self, message: &Message) -> Option<Task<Message>> {
         match message {
             Message::SearchSubmit => {
+                self.ui.search.dropdown_open = false;
                 let Some(route) = self.route_for_message(message) else {
                     return Some(Task::none());
                 };
 
-                Some(self.navigate_to_route(route, true))
+                let record = match &route {
+                    Route::Search { keyword, .. } => self.record_search(keyword.clone()),
+                    _ => Task::none(),
+                };
+                Some(Task::batch([record, self.navigate_to_route(route, true)]))
             }
 
             Message::SearchTabChanged(tab) => {
@@ -40,10 +49,28 @@ impl App {
 
                 match payload.tab {
                     SearchTab::Songs => {
-                        self.ui.search.songs = payload.songs.clone();
+                        let blocklist = &self.library.blocklist;
+                        self.ui.search.songs = payload
+                            .songs
+                            .iter()
+                            .filter(|song| !blocklist.blocks(SongId::ncm(song.id), &song.singer))
+                            .cloned()
+                            .collect();
+                        self.ui.search.total_count = payload.total_count;
+                        // New keyword or page: start from the first result
+                        self.ui.search.scroll_state.borrow_mut().scroll_to_top();
+                    }
+                    SearchTab::Artists => {
+                        let blocklist = &self.library.blocklist;
+                        self.ui.search.albums = payload
+                            .albums
+                            .iter()
+                            .filter(|artist| !blocklist.blocks_artist(&artist.name))
+                            .cloned()
+                            .collect();
                         self.ui.search.total_count = payload.total_count;
                     }
-                    SearchTab::Artists | SearchTab::Albums => {
+                    SearchTab::Albums => {
                         self.ui.search.albums = payload.albums.clone();
                         self.ui.search.total_count = payload.total_count;
                     }
@@ -56,13 +83,32 @@ impl App {
                 Some(Task::none())
             }
 
+            Message::LocalSearchLoaded(keyword, songs) => {
+                // A newer search replaced this keyword while it was loading
+                if *keyword != self.ui.search.keyword {
+                    return Some(Task::none());
+                }
+                let blocklist = &self.library.blocklist;
+                self.ui.search.local_songs = songs
+                    .iter()
+                    .filter(|song| !blocklist.blocks(song.id, &song.artist))
+                    .cloned()
+                    .collect();
+                Some(Task::none())
+            }
+
+            Message::SearchSourceChanged(source) => {
+                self.ui.search.source = *source;
+                self.ui.search.scroll_state.borrow_mut().scroll_to_top();
+                Some(Task::none())
+            }
+
             Message::SearchFailed(error) => {
                 self.ui.search.loading = false;
                 tracing::error!("Search failed: {}", error);
-                Some(Task::done(Message::ShowErrorToast(format!(
-                    "搜索失败: {}",
-                    error
-                ))))
+                Some(Task::done(Message::ShowErrorToast(
+                    self.core.locale.get(Key::SearchFailed).replace("{}", error),
+                )))
             }
 
             Message::SearchPageChanged(page) => {
@@ -90,7 +136,11 @@ impl App {
 
             Message::PlaySearchSong(song_info) => {
                 // Convert SongInfo to playable format and play
-                tracing::info!("Playing search result: {} - {}", song_info.name, song_info.singer);
+                tracing::info!(
+                    "Playing search result: {} - {}",
+                    song_info.name,
+                    song_info.singer
+                );
                 Some(Task::done(Message::PlayNcmSong(song_info.clone())))
             }
 
@@ -105,8 +155,8 @@ impl App {
                         return Some(Task::done(Message::OpenNcmPlaylist(*id)));
                     }
                     SearchTab::Artists => {
-                        // TODO: Open artist page
-                        tracing::info!("Open artist: {}", id);
+                        // No artist page yet; an artist card toggles following
+                        return Some(Task::done(Message::ToggleFollowArtist(*id)));
                     }
                     _ => {}
                 }
@@ -117,6 +167,27 @@ impl App {
         }
     }
 
+    /// Search the local library for songs matching a keyword
+    pub(super) fn fetch_local_search_results(&self, keyword: String) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+
+        Task::perform(
+            async move {
+                let songs = db
+                    .search_local_songs(&keyword, LOCAL_RESULT_LIMIT)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Local search failed: {}", e);
+                        Vec::new()
+                    });
+                (keyword, songs)
+            },
+            |(keyword, songs)| Message::LocalSearchLoaded(keyword, songs),
+        )
+    }
+
     /// Fetch search results from NCM API
     pub(super) fn fetch_search_results(
         &self,
@@ -125,7 +196,9 @@ impl App {
         page: u32,
     ) -> Task<Message> {
         let Some(client) = &self.core.ncm_client else {
-            return Task::done(Message::SearchFailed("未登录".to_string()));
+            return Task::done(Message::SearchFailed(
+                self.core.locale.get(Key::NotLoggedIn).to_string(),
+            ));
         };
 
         let api = client.client.clone();
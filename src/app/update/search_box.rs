@@ -0,0 +1,193 @@
+//! Search box dropdown: history, hot searches and as-you-type suggestions
+
+use std::time::Duration;
+
+use iced::Task;
+use tracing::error;
+
+use crate::app::message::Message;
+use crate::app::state::App;
+
+/// Keywords shown in the history section
+const HISTORY_LIMIT: i64 = 10;
+/// Pause in typing before suggestions are fetched
+const SUGGEST_DELAY: Duration = Duration::from_millis(250);
+
+impl App {
+    /// Handle search box and dropdown messages
+    pub fn handle_search_box(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::SearchChanged(query) => {
+                self.ui.search_query = query.clone();
+                self.ui.search.dropdown_open = true;
+                self.ui.search.suggest_generation += 1;
+
+                if query.trim().is_empty() {
+                    self.ui.search.suggestions.clear();
+                    return Some(self.load_search_dropdown());
+                }
+                let generation = self.ui.search.suggest_generation;
+                Some(Task::perform(
+                    async move {
+                        tokio::time::sleep(SUGGEST_DELAY).await;
+                        generation
+                    },
+                    Message::SearchSuggestDue,
+                ))
+            }
+
+            Message::ToggleSearchDropdown => {
+                self.ui.search.dropdown_open = !self.ui.search.dropdown_open;
+                if self.ui.search.dropdown_open {
+                    Some(self.load_search_dropdown())
+                } else {
+                    Some(Task::none())
+                }
+            }
+
+            Message::CloseSearchDropdown => {
+                self.ui.search.dropdown_open = false;
+                Some(Task::none())
+            }
+
+            Message::SearchHistoryLoaded(history) => {
+                self.ui.search.history = history.clone();
+                Some(Task::none())
+            }
+
+            Message::HotSearchesLoaded(keywords) => {
+                self.ui.search.hot_keywords = keywords.clone();
+                Some(Task::none())
+            }
+
+            Message::SearchSuggestDue(generation) => {
+                if *generation != self.ui.search.suggest_generation {
+                    return Some(Task::none());
+                }
+                let keyword = self.ui.search_query.trim().to_string();
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::none());
+                };
+                if keyword.is_empty() {
+                    return Some(Task::none());
+                }
+                Some(Task::perform(
+                    async move {
+                        let suggestions = client
+                            .client
+                            .search_suggest(&keyword)
+                            .await
+                            .unwrap_or_else(|e| {
+                                error!("Failed to load search suggestions: {}", e);
+                                Vec::new()
+                            });
+                        (keyword, suggestions)
+                    },
+                    |(keyword, suggestions)| Message::SearchSuggestionsLoaded(keyword, suggestions),
+                ))
+            }
+
+            Message::SearchSuggestionsLoaded(keyword, suggestions) => {
+                // Typing may have moved on while the request was in flight
+                if self.ui.search_query.trim() == keyword {
+                    self.ui.search.suggestions = suggestions.clone();
+                }
+                Some(Task::none())
+            }
+
+            Message::SelectSearchKeyword(keyword) => {
+                self.ui.search_query = keyword.clone();
+                Some(Task::done(Message::SearchSubmit))
+            }
+
+            Message::DeleteSearchHistory(keyword) => {
+                self.ui.search.history.retain(|k| k != keyword);
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                let keyword = keyword.clone();
+                Some(Task::perform(
+                    async move {
+                        if let Err(e) = db.delete_search_history(&keyword).await {
+                            error!("Failed to delete search history entry: {}", e);
+                        }
+                    },
+                    |_| Message::NoOp,
+                ))
+            }
+
+            Message::ClearSearchHistory => {
+                self.ui.search.history.clear();
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                Some(Task::perform(
+                    async move {
+                        if let Err(e) = db.clear_search_history().await {
+                            error!("Failed to clear search history: {}", e);
+                        }
+                    },
+                    |_| Message::NoOp,
+                ))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Save a submitted keyword and move it to the top of the history
+    pub(super) fn record_search(&mut self, keyword: String) -> Task<Message> {
+        self.ui.search.history.retain(|k| *k != keyword);
+        self.ui.search.history.insert(0, keyword.clone());
+        self.ui.search.history.truncate(HISTORY_LIMIT as usize);
+
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        Task::perform(
+            async move {
+                if let Err(e) = db.record_search(&keyword).await {
+                    error!("Failed to record search: {}", e);
+                }
+            },
+            |_| Message::NoOp,
+        )
+    }
+
+    /// Refresh the history and, once per session, the hot search list
+    fn load_search_dropdown(&self) -> Task<Message> {
+        let mut tasks = Vec::new();
+        if let Some(db) = self.core.db.clone() {
+            tasks.push(Task::perform(
+                async move {
+                    db.get_search_history(HISTORY_LIMIT)
+                        .await
+                        .unwrap_or_else(|e| {
+                            error!("Failed to load search history: {}", e);
+                            Vec::new()
+                        })
+                },
+                Message::SearchHistoryLoaded,
+            ));
+        }
+        let hot_client = self
+            .ui
+            .search
+            .hot_keywords
+            .is_empty()
+            .then(|| self.core.ncm_client.clone())
+            .flatten();
+        if let Some(client) = hot_client {
+            tasks.push(Task::perform(
+                async move {
+                    client.client.search_hot().await.unwrap_or_else(|e| {
+                        error!("Failed to load hot searches: {}", e);
+                        Vec::new()
+                    })
+                },
+                Message::HotSearchesLoaded,
+            ));
+        }
+        Task::batch(tasks)
+    }
+}
@@ -0,0 +1,88 @@
+//! NCM session keep-alive and expiry handling
+
+use iced::Task;
+use tracing::{debug, info, warn};
+
+use crate::api::{ApiError, NcmClient};
+use crate::app::{App, Message};
+use crate::i18n::Key;
+
+impl App {
+    /// Handle session refresh messages
+    pub fn handle_session(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::RefreshSession => {
+                let Some(client) = self.core.ncm_client.clone() else {
+                    return Some(Task::none());
+                };
+
+                if !self.core.is_logged_in {
+                    // Guests still need a token for search, lyrics and streams
+                    return Some(Task::perform(
+                        async move {
+                            if let Err(e) = client.client.register_anonymous().await {
+                                warn!("Failed to renew guest token: {}", e);
+                            }
+                        },
+                        |_| Message::NoOp,
+                    ));
+                }
+
+                Some(Task::perform(
+                    async move {
+                        match client.refresh_session().await {
+                            Ok(login_info) => Some(Some(login_info)),
+                            Err(e) if ApiError::is_auth_expired(&e) => Some(None),
+                            Err(e) => {
+                                // Network trouble says nothing about the session
+                                warn!("Failed to refresh session: {}", e);
+                                None
+                            }
+                        }
+                    },
+                    |result| match result {
+                        Some(Some(login_info)) => Message::SessionRefreshed(login_info),
+                        Some(None) => Message::SessionExpired,
+                        None => Message::NoOp,
+                    },
+                ))
+            }
+
+            Message::SessionRefreshed(login_info) => {
+                debug!("Session refreshed for uid {}", login_info.uid);
+                if let Some(user_info) = &mut self.core.user_info {
+                    user_info.vip_type = login_info.vip_type;
+                }
+                Some(Task::none())
+            }
+
+            Message::SessionExpired => {
+                if !self.core.is_logged_in {
+                    return Some(Task::none());
+                }
+                info!("NCM session expired, prompting for login");
+
+                NcmClient::clean_cookie_file();
+                self.core.is_logged_in = false;
+                self.core.user_info = None;
+                self.ui.check_in_history.clear();
+                let proxy_url = self.core.settings.network.proxy_url();
+                self.set_ncm_client(NcmClient::with_proxy(proxy_url));
+
+                let toast = self.update(Message::ShowToast(
+                    self.core.locale.get(Key::LoginSessionExpired).to_string(),
+                ));
+                self.ui.toast_action =
+                    Some((self.core.locale.get(Key::LoginAgain), Message::RequestQrCode));
+
+                Some(Task::batch([
+                    toast,
+                    Task::done(Message::RequestQrCode),
+                    Task::done(Message::RefreshSession),
+                ]))
+            }
+
+            _ => None,
+        }
+    }
+}
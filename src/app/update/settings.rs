@@ -1,23 +1,27 @@
 //! Settings update handlers
 
 use crate::app::SettingsSection;
+use crate::app::helpers::apply_dsp_presets;
 use crate::app::message::Message;
 use crate::app::state::{App, Route};
+use crate::audio::UiSound;
 use crate::cache;
 use crate::features::keybindings::{KeyBinding, KeyCode, ModifierSet};
+use crate::i18n::{Key as I18nKey, Language, Locale};
 use iced::Task;
 use iced::keyboard::Key;
 
 /// Section positions when user is NOT logged in
-const SECTION_POSITIONS_LOGGED_OUT: [(SettingsSection, f32); 8] = [
+const SECTION_POSITIONS_LOGGED_OUT: [(SettingsSection, f32); 9] = [
     (SettingsSection::Account, 0.0),
     (SettingsSection::Playback, 150.0),
-    (SettingsSection::Display, 500.0),
-    (SettingsSection::System, 850.0),
-    (SettingsSection::Network, 1000.0),
-    (SettingsSection::Storage, 1150.0),
-    (SettingsSection::Shortcuts, 1390.0),
-    (SettingsSection::About, 1965.0),
+    (SettingsSection::Schedules, 800.0),
+    (SettingsSection::Display, 960.0),
+    (SettingsSection::System, 1535.0),
+    (SettingsSection::Network, 2020.0),
+    (SettingsSection::Storage, 2332.0),
+    (SettingsSection::Shortcuts, 2572.0),
+    (SettingsSection::About, 3147.0),
 ];
 
 /// Offset to add when user IS logged in (Account section is larger)
@@ -160,6 +164,54 @@ impl App {
         }
     }
 
+    /// Switch the UI language in place
+    ///
+    /// Views read the locale on every render; only the few strings that were
+    /// translated into state when their page loaded need rewriting here.
+    pub(super) fn apply_language(&mut self, language: Language) {
+        self.core.locale = Locale::new(language);
+        let locale = self.core.locale;
+        self.refresh_tray_state();
+
+        if let Some(daily) = self
+            .ui
+            .discover
+            .recommended_playlists
+            .iter_mut()
+            .find(|playlist| playlist.id == 0)
+        {
+            daily.name = locale.get(I18nKey::DiscoverDailyRecommend).to_string();
+            daily.author = locale.get(I18nKey::DiscoverDailyRecommendDesc).to_string();
+        }
+
+        let Some(playlist) = &mut self.ui.playlist_page.current else {
+            return;
+        };
+        match playlist.id {
+            // Recently played
+            -1 => {
+                playlist.name = locale.get(I18nKey::RecentlyPlayed).to_string();
+                playlist.description =
+                    Some(locale.get(I18nKey::RecentlyPlayedDescription).to_string());
+                playlist.owner = locale.get(I18nKey::LocalPlaylistOwner).to_string();
+                let source = locale.get(I18nKey::RecentlyPlayedList);
+                for song in &mut playlist.songs {
+                    song.added_date = source.to_string();
+                }
+            }
+            // Daily recommend
+            0 => {
+                playlist.name = locale.get(I18nKey::DiscoverDailyRecommend).to_string();
+                playlist.description =
+                    Some(locale.get(I18nKey::DiscoverDailyRecommendDesc).to_string());
+                playlist.owner = locale
+                    .get(I18nKey::DiscoverDailyRecommendCreator)
+                    .to_string();
+            }
+            _ => {}
+        }
+    }
+
     pub(super) fn refresh_cache_stats(&mut self) {
         let stats = cache::calculate_cache_stats();
         self.ui.cache_stats = Some(stats);
@@ -180,11 +232,78 @@ impl App {
                 self.core.settings.playback.volume_normalization = *enabled;
                 Some(Task::perform(async { Message::SaveSettings }, |m| m))
             }
+            Message::UpdateUiSounds(enabled) => {
+                self.core.settings.playback.ui_sounds = *enabled;
+                // Let the user hear what they just turned on
+                self.play_ui_sound(UiSound::QueueAdd);
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateAutoplaySimilar(enabled) => {
+                self.core.settings.playback.autoplay_similar = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateSmartResume(enabled) => {
+                self.core.settings.playback.smart_resume.enabled = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateSmartResumeAfter(mins) => {
+                self.core.settings.playback.smart_resume.after_mins = *mins;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateSmartResumeRewind(secs) => {
+                self.core.settings.playback.smart_resume.rewind_secs = *secs;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateCallAction(action) => {
+                self.core.settings.playback.call_ducking.action = *action;
+                // Whatever the old action changed is undone right away
+                let restore = self.end_call();
+                Some(Task::batch([
+                    restore,
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                ]))
+            }
+            Message::UpdateCallLowerPercent(percent) => {
+                self.core.settings.playback.call_ducking.lower_percent = *percent;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateCallAnyApp(any_app) => {
+                self.core.settings.playback.call_ducking.any_app = *any_app;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateCallAfter(secs) => {
+                self.core.settings.playback.call_ducking.after_secs = *secs;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateAutoDjPool(pool) => {
+                self.core.settings.playback.auto_dj.pool = *pool;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateSkipBackSecs(secs) => {
+                self.core.settings.playback.skip_back_secs = *secs;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateSkipForwardSecs(secs) => {
+                self.core.settings.playback.skip_forward_secs = *secs;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateSeekStepSecs(secs) => {
+                self.core.settings.playback.seek_step_secs = *secs;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateVolumeStepPercent(percent) => {
+                self.core.settings.playback.volume_step_percent = *percent;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateAutoDjCrossfade(secs) => {
+                self.core.settings.playback.auto_dj.crossfade_secs = *secs;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
             Message::UpdateMusicQuality(quality) => {
                 self.core.settings.playback.music_quality = *quality;
                 // Update NcmClient's quality setting
                 if let Some(client) = &self.core.ncm_client {
-                    client.set_quality(quality.to_api_rate());
+                    client.set_quality(self.core.stream_quality().to_api_rate());
                 }
                 tracing::info!("Music quality changed to: {:?}", quality);
                 Some(Task::perform(async { Message::SaveSettings }, |m| m))
@@ -222,6 +341,18 @@ impl App {
                 self.core.audio_chain.set_preamp(*preamp);
                 Some(Task::perform(async { Message::SaveSettings }, |m| m))
             }
+            Message::UpdateDspPresets(presets) => {
+                self.core.settings.set_dsp_presets(*presets);
+                apply_dsp_presets(&self.core.audio_chain, *presets);
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateMatchSampleRate(enabled) => {
+                self.core.settings.playback.match_sample_rate = *enabled;
+                if let Some(player) = &self.core.audio {
+                    player.set_match_sample_rate(*enabled);
+                }
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
             Message::UpdateSpectrumDecay(decay) => {
                 self.core.settings.playback.spectrum_decay = *decay;
                 // Apply to audio analysis
@@ -239,19 +370,74 @@ impl App {
             }
             Message::UpdateAppLanguage(language) => {
                 self.core.settings.display.language = language.clone();
-                // Update locale for i18n
-                let lang = if language == "zh" {
-                    crate::i18n::Language::Chinese
-                } else {
-                    crate::i18n::Language::English
-                };
-                self.core.locale = crate::i18n::Locale::new(lang);
+                self.apply_language(crate::i18n::Language::from_code(language));
                 tracing::info!("Language changed to: {}", language);
                 Some(Task::perform(async { Message::SaveSettings }, |m| m))
             }
             Message::UpdatePowerSavingMode(enabled) => {
                 self.core.settings.display.power_saving_mode = *enabled;
                 tracing::info!("Power saving mode: {}", enabled);
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    self.refresh_playlist_backdrop(),
+                ]))
+            }
+            Message::UpdateLyricsTranslationDefault(enabled) => {
+                self.core.settings.display.lyrics_sub_lines.translation = *enabled;
+                tracing::info!("Lyrics translation shown by default: {}", enabled);
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateLyricsRomanizationDefault(enabled) => {
+                self.core.settings.display.lyrics_sub_lines.romanization = *enabled;
+                tracing::info!("Lyrics romanization shown by default: {}", enabled);
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateLyricsShareOffer(enabled) => {
+                self.core.settings.display.lyrics_share.offer = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateLyricsShareCredit(credit) => {
+                self.core.settings.display.lyrics_share.credit = credit.clone();
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateLyricsSaveOffer(enabled) => {
+                self.core.settings.display.lyrics_save.offer = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateLyricsSaveTarget(target) => {
+                self.core.settings.display.lyrics_save.target = *target;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::ToggleLyricsTuningPanel => {
+                self.ui.lyrics_tuning_expanded = !self.ui.lyrics_tuning_expanded;
+                Some(Task::none())
+            }
+            Message::UpdateLyricsTuning(tuning) => {
+                self.core.settings.display.lyrics_tuning = *tuning;
+                self.apply_lyrics_tuning();
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::ResetLyricsTuning => {
+                self.core.settings.display.lyrics_tuning = Default::default();
+                self.apply_lyrics_tuning();
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateLyricsBackground(style) => {
+                self.core.settings.display.lyrics_background = *style;
+                tracing::info!("Lyrics background: {:?}", style);
+                self.ui.lyrics.redraw_requested = true;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateArtworkBackdrop(backdrop) => {
+                self.core.settings.display.artwork_backdrop = *backdrop;
+                tracing::info!("Artwork backdrop: {:?}", backdrop);
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    self.refresh_playlist_backdrop(),
+                ]))
+            }
+            Message::UpdateCoverTheming(enabled) => {
+                self.core.settings.display.cover_theming = *enabled;
                 Some(Task::perform(async { Message::SaveSettings }, |m| m))
             }
             Message::UpdateMaxCacheMb(size_mb) => {
@@ -319,6 +505,10 @@ impl App {
                 if let Some(player) = &self.core.audio {
                     player.switch_device(device.clone());
                 }
+                // Each device keeps its own DSP stages and latency
+                apply_dsp_presets(&self.core.audio_chain, self.core.settings.dsp_presets());
+                self.apply_output_latency();
+                self.refresh_tray_state();
                 Some(Task::perform(async { Message::SaveSettings }, |m| m))
             }
             Message::UpdateAudioBufferSize(size) => {
@@ -326,6 +516,68 @@ impl App {
                 tracing::info!("Audio buffer size changed to: {}", size);
                 Some(Task::perform(async { Message::SaveSettings }, |m| m))
             }
+            Message::UpdateLaunchAtLogin(enabled) => {
+                // Writes to the registry or the user's config, off the UI thread
+                let enabled = *enabled;
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            crate::platform::autostart::set_enabled(enabled)
+                                .map_err(|e| e.to_string())
+                        })
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()))
+                    },
+                    move |result| Message::LaunchAtLoginUpdated(enabled, result),
+                ))
+            }
+            Message::LaunchAtLoginUpdated(enabled, result) => {
+                if let Err(e) = result {
+                    tracing::error!("Failed to update launch at login: {}", e);
+                    return Some(Task::done(Message::ShowToast(
+                        self.core
+                            .locale
+                            .get(I18nKey::SettingsLaunchAtLoginFailed)
+                            .to_string(),
+                    )));
+                }
+                self.core.settings.system.launch_at_login = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateStartMinimized(enabled) => {
+                self.core.settings.system.start_minimized = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateReleaseNotifications(enabled) => {
+                self.core.settings.system.release_notifications = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateDailyCheckIn(enabled) => {
+                self.core.settings.account.daily_check_in = *enabled;
+                let save = Task::perform(async { Message::SaveSettings }, |m| m);
+                if *enabled {
+                    Some(Task::batch([save, Task::done(Message::RunDailyCheckIn)]))
+                } else {
+                    Some(save)
+                }
+            }
+            Message::UpdateResumeOnStartup(enabled) => {
+                self.core.settings.playback.resume_on_startup = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateUpdateChannel(channel) => {
+                self.core.settings.system.update_channel = *channel;
+                tracing::info!("Update channel changed to: {:?}", channel);
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateAutoCheckUpdates(enabled) => {
+                self.core.settings.system.auto_check_updates = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateShowWhatsNew(enabled) => {
+                self.core.settings.system.show_whats_new = *enabled;
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
             Message::UpdateProxyType(proxy_type) => {
                 self.core.settings.network.proxy_type = *proxy_type;
                 tracing::info!("Proxy type changed to: {:?}", proxy_type);
@@ -378,6 +630,7 @@ impl App {
                 ]))
             }
             Message::ApplyProxySettings => {
+                let quality = self.core.stream_quality().to_api_rate();
                 if let Some(client) = &mut self.core.ncm_client {
                     if let Some(proxy_url) = self.core.settings.network.proxy_url() {
                         match client.set_proxy(proxy_url.clone()) {
@@ -388,7 +641,6 @@ impl App {
                         tracing::info!("Proxy disabled");
                         // When proxy is disabled, recreate client without proxy
                         // and sync quality setting
-                        let quality = self.core.settings.playback.music_quality.to_api_rate();
                         if let Some((cookie_jar, csrf_token)) =
                             crate::api::NcmClient::load_cookie_jar_from_file()
                         {
@@ -404,7 +656,25 @@ impl App {
                 }
                 Some(Task::none())
             }
+            Message::UpdateBandwidthLimit(kbps) => {
+                self.core.settings.network.bandwidth_limit_kbps = *kbps;
+                crate::features::bandwidth::set_limit_kbps(*kbps);
+                tracing::info!("Bandwidth limit changed to: {} KB/s", kbps);
+                Some(Task::perform(async { Message::SaveSettings }, |m| m))
+            }
+            Message::UpdateMeteredMode(mode) => {
+                let was_metered = self.core.is_metered();
+                self.core.settings.network.metered_mode = *mode;
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    Task::done(Message::DetectMeteredConnection),
+                    self.on_metered_changed(was_metered),
+                ]))
+            }
             Message::ScrollToSection(section) => {
+                // Leaving search shows the sections again
+                self.ui.settings_search.clear();
+                self.ui.settings_category = None;
                 self.sync_settings_section_route(*section);
                 // Get target scroll position for section based on login state
                 let is_logged_in = self.core.is_logged_in;
@@ -417,6 +687,14 @@ impl App {
                     },
                 ))
             }
+            Message::SettingsSearchChanged(query) => {
+                self.ui.settings_search = query.clone();
+                Some(Task::none())
+            }
+            Message::SettingsCategorySelected(category) => {
+                self.ui.settings_category = *category;
+                Some(Task::none())
+            }
             Message::SettingsScrolled(y_offset) => {
                 // Update active section based on scroll position and login state
                 let is_logged_in = self.core.is_logged_in;
@@ -0,0 +1,170 @@
+//! Settings profile export and import handlers
+
+use iced::Task;
+
+use crate::app::helpers::apply_dsp_presets;
+use crate::app::{App, Message};
+use crate::features::Settings;
+use crate::features::settings_profile::{self, SecretsImport, WrongPassphrase};
+use crate::i18n::{Key, Language};
+
+impl App {
+    /// Passphrase for profile credentials, if one was entered
+    fn profile_passphrase(&self) -> Option<String> {
+        Some(self.ui.settings_profile_passphrase.clone()).filter(|p| !p.is_empty())
+    }
+
+    /// Switch to imported settings, applying what isn't read live
+    fn apply_imported_settings(&mut self, settings: Settings) {
+        self.core.settings = settings;
+        let settings = &self.core.settings;
+
+        let chain = &self.core.audio_chain;
+        chain.set_equalizer_enabled(settings.playback.equalizer_enabled);
+        chain.set_equalizer_gains(settings.playback.equalizer_values);
+        chain.set_preamp(settings.playback.equalizer_preamp);
+        chain.analysis().set_decay(settings.playback.spectrum_decay);
+        apply_dsp_presets(chain, settings.dsp_presets());
+        crate::features::bandwidth::set_limit_kbps(settings.network.bandwidth_limit_kbps);
+        if let Some(client) = &self.core.ncm_client {
+            client.set_quality(self.core.stream_quality().to_api_rate());
+        }
+        if let Some(player) = &self.core.audio {
+            player.set_match_sample_rate(settings.playback.match_sample_rate);
+        }
+
+        self.apply_language(Language::from_code(&self.core.settings.display.language));
+        self.apply_output_latency();
+        self.apply_lyrics_tuning();
+    }
+
+    /// Handle settings profile messages
+    pub fn handle_settings_profile(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::UpdateSettingsProfilePassphrase(passphrase) => {
+                self.ui.settings_profile_passphrase = passphrase.clone();
+                Some(Task::none())
+            }
+
+            Message::ExportSettingsProfile => Some(Task::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .set_file_name(settings_profile::FILE_NAME)
+                        .add_filter("JSON", &["json"])
+                        .save_file()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                },
+                Message::SettingsProfileExportPicked,
+            )),
+
+            Message::SettingsProfileExportPicked(path) => {
+                let Some(path) = path.clone() else {
+                    return Some(Task::none());
+                };
+                let settings = self.core.settings.clone();
+                let passphrase = self.profile_passphrase();
+                let locale = self.core.locale;
+
+                // Stretching the passphrase takes a moment
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            settings_profile::export(&path, &settings, passphrase.as_deref())
+                                .map(|_| path)
+                                .map_err(|e| {
+                                    tracing::error!("Failed to export settings: {:#}", e);
+                                    locale
+                                        .get(Key::SettingsProfileExportFailed)
+                                        .replace("{}", &e.to_string())
+                                })
+                        })
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()))
+                    },
+                    Message::SettingsProfileExported,
+                ))
+            }
+
+            Message::SettingsProfileExported(result) => {
+                let toast = match result {
+                    Ok(path) => {
+                        tracing::info!("Settings exported to {:?}", path);
+                        self.core
+                            .locale
+                            .get(Key::SettingsProfileExported)
+                            .replace("{}", &path.display().to_string())
+                    }
+                    Err(toast) => toast.clone(),
+                };
+                Some(Task::done(Message::ShowToast(toast)))
+            }
+
+            Message::ImportSettingsProfile => Some(Task::perform(
+                async {
+                    rfd::AsyncFileDialog::new()
+                        .add_filter("JSON", &["json"])
+                        .pick_file()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                },
+                Message::SettingsProfileImportPicked,
+            )),
+
+            Message::SettingsProfileImportPicked(path) => {
+                let Some(path) = path.clone() else {
+                    return Some(Task::none());
+                };
+                let current = self.core.settings.clone();
+                let passphrase = self.profile_passphrase();
+                let locale = self.core.locale;
+
+                Some(Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            settings_profile::import(&path, &current, passphrase.as_deref())
+                                .map(|(settings, secrets)| (Box::new(settings), secrets))
+                                .map_err(|e| {
+                                    tracing::error!("Failed to import settings: {:#}", e);
+                                    if e.downcast_ref::<WrongPassphrase>().is_some() {
+                                        locale.get(Key::SettingsProfileWrongPassphrase).to_string()
+                                    } else {
+                                        locale
+                                            .get(Key::SettingsProfileImportFailed)
+                                            .replace("{}", &e.to_string())
+                                    }
+                                })
+                        })
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()))
+                    },
+                    Message::SettingsProfileImported,
+                ))
+            }
+
+            Message::SettingsProfileImported(result) => {
+                let (settings, secrets) = match result {
+                    Ok(imported) => imported.clone(),
+                    Err(toast) => return Some(Task::done(Message::ShowToast(toast.clone()))),
+                };
+                tracing::info!("Settings imported (credentials: {:?})", secrets);
+                self.apply_imported_settings(*settings);
+                self.ui.settings_profile_passphrase.clear();
+
+                let key = match secrets {
+                    SecretsImport::Locked => Key::SettingsProfileImportedLocked,
+                    SecretsImport::NotIncluded | SecretsImport::Restored => {
+                        Key::SettingsProfileImported
+                    }
+                };
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    Task::done(Message::ApplyProxySettings),
+                    Task::done(Message::ShowToast(self.core.locale.get(key).to_string())),
+                ]))
+            }
+
+            _ => None,
+        }
+    }
+}
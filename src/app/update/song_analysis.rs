@@ -0,0 +1,251 @@
+//! Song tempo, key and energy analysis handlers
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use iced::Task;
+use tracing::{info, warn};
+
+use crate::app::message::Message;
+use crate::app::state::App;
+use crate::audio::UiSound;
+use crate::database::{Database, SongId};
+use crate::features::jobs::{Category, Job};
+use crate::features::song_analysis::{self, SongAnalysis};
+use crate::i18n::Key;
+
+/// Songs queued by "similar energy" at most
+const SIMILAR_ENERGY_SONGS: usize = 25;
+
+impl App {
+    /// Handle song analysis messages
+    pub fn handle_song_analysis(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::SongAnalysesLoaded(analyses) => {
+                self.library.song_analysis = analyses.clone();
+                self.show_song_analysis();
+                Some(Task::none())
+            }
+
+            Message::UpdateSongAnalysis(enabled) => {
+                self.core.settings.system.analyze_songs = *enabled;
+                let analysis = if *enabled {
+                    self.start_song_analysis()
+                } else {
+                    self.stop_song_analysis();
+                    Task::none()
+                };
+                Some(Task::batch([
+                    Task::perform(async { Message::SaveSettings }, |m| m),
+                    analysis,
+                ]))
+            }
+
+            Message::AnalyzeLibrary => Some(self.run_song_analysis()),
+
+            Message::SongAnalysisStarted(total) => {
+                self.library.song_analysis_progress = Some((0, *total));
+                Some(Task::none())
+            }
+
+            Message::SongAnalyzed(song_id, analysis) => {
+                self.library.song_analysis.insert(*song_id, *analysis);
+                if let Some((done, _)) = &mut self.library.song_analysis_progress {
+                    *done += 1;
+                }
+                if let Some(playlist) = &mut self.ui.playlist_page.current {
+                    for song in playlist.songs.iter_mut().filter(|s| s.id == *song_id) {
+                        song.set_analysis(Some(analysis));
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::SongAnalysisFinished => {
+                if let Some((done, _)) = self.library.song_analysis_progress {
+                    info!("Song analysis finished: {} songs", done);
+                }
+                self.library.song_analysis_progress = None;
+                self.library.song_analysis_task = None;
+                // Songs added during the run haven't been looked at yet
+                if std::mem::take(&mut self.library.song_analysis_rerun) {
+                    return Some(self.start_song_analysis());
+                }
+                Some(Task::none())
+            }
+
+            Message::QueueSimilarEnergy => Some(self.queue_similar_energy()),
+
+            Message::SortPlaylistByTempo => {
+                let page = &mut self.ui.playlist_page;
+                page.tempo_sort = match page.tempo_sort {
+                    None => Some(true),
+                    Some(true) => Some(false),
+                    Some(false) => None,
+                };
+                page.scroll_state.borrow_mut().scroll_to_top();
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Analyze new local songs if enabled, or once the running analysis
+    /// is done if one is underway
+    pub(super) fn start_song_analysis(&mut self) -> Task<Message> {
+        if !self.core.settings.system.analyze_songs {
+            return Task::none();
+        }
+        if self.library.song_analysis_task.is_some() {
+            self.library.song_analysis_rerun = true;
+            return Task::none();
+        }
+        self.run_song_analysis()
+    }
+
+    /// Analyze every local song without an analysis, one at a time
+    fn run_song_analysis(&mut self) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        if self.library.song_analysis_task.is_some() {
+            return Task::none();
+        }
+        let (task, handle) = Task::run(analyze_songs(db), |msg| msg).abortable();
+        self.library.song_analysis_task = Some(handle.abort_on_drop());
+        task
+    }
+
+    fn stop_song_analysis(&mut self) {
+        // Dropping the handle aborts the run
+        self.library.song_analysis_task = None;
+        self.library.song_analysis_rerun = false;
+        self.library.song_analysis_progress = None;
+    }
+
+    /// Fill in the tempo and key columns of the open playlist
+    pub(super) fn show_song_analysis(&mut self) {
+        let Some(playlist) = &mut self.ui.playlist_page.current else {
+            return;
+        };
+        for song in &mut playlist.songs {
+            song.set_analysis(self.library.song_analysis.get(&song.id));
+        }
+    }
+
+    /// Queue library songs close in energy, tempo and key to the current
+    /// one right after it
+    fn queue_similar_energy(&mut self) -> Task<Message> {
+        let locale = self.core.locale;
+        let Some(seed) = self
+            .library
+            .current_song
+            .as_ref()
+            .and_then(|song| self.library.song_analysis.get(&song.id))
+        else {
+            return Task::done(Message::ShowToast(
+                locale.get(Key::SimilarEnergyNotAnalyzed).to_string(),
+            ));
+        };
+
+        let queued: HashSet<SongId> = self.library.queue.iter().map(|song| song.id).collect();
+        let blocklist = &self.library.blocklist;
+        let candidates = self
+            .library
+            .db_songs
+            .iter()
+            .filter(|song| !queued.contains(&song.id) && !blocklist.blocks(song.id, &song.artist))
+            .filter_map(|song| {
+                let analysis = self.library.song_analysis.get(&song.id)?;
+                Some((song.id, analysis))
+            });
+        let picked = song_analysis::closest(seed, candidates, SIMILAR_ENERGY_SONGS);
+        if picked.is_empty() {
+            return Task::done(Message::ShowToast(
+                locale.get(Key::SimilarEnergyNoneFound).to_string(),
+            ));
+        }
+
+        let songs: Vec<_> = picked
+            .iter()
+            .filter_map(|id| self.library.db_songs.iter().find(|song| song.id == *id))
+            .cloned()
+            .collect();
+        let added = songs.len();
+        let at = self
+            .library
+            .queue_index
+            .map_or(self.library.queue.len(), |idx| idx + 1);
+        self.library.queue.splice(at..at, songs);
+        self.clear_shuffle_cache();
+        if let Some(db) = &self.core.db {
+            db.save_queue_with_songs(self.library.queue.clone(), None);
+        }
+
+        self.cache_shuffle_indices();
+        let _ = self.preload_adjacent_tracks_with_ncm();
+        self.refresh_tray_state();
+        self.play_ui_sound(UiSound::QueueAdd);
+        Task::done(Message::ShowToast(
+            locale.count(Key::SimilarEnergyQueued, added as u64),
+        ))
+    }
+}
+
+/// Analyze songs one after another, storing each result as it comes
+///
+/// Songs that fail to decode are stored without results so they aren't
+/// tried again on every start.
+fn analyze_songs(db: Arc<Database>) -> impl futures_util::Stream<Item = Message> {
+    async_stream::stream! {
+        let songs = match db.get_unanalyzed_songs().await {
+            Ok(songs) => songs,
+            Err(e) => {
+                warn!("Failed to list songs to analyze: {}", e);
+                Vec::new()
+            }
+        };
+        if !songs.is_empty() {
+            info!("Analyzing {} songs", songs.len());
+            yield Message::SongAnalysisStarted(songs.len());
+        }
+
+        for song in songs {
+            let Some(row_id) = song.id.library_id() else {
+                continue;
+            };
+            let path = PathBuf::from(&song.file_path);
+            let result = Job::new(Category::Disk)
+                .prefetch()
+                .run_blocking(move || song_analysis::analyze_file(&path))
+                .await;
+            let analysis = match result {
+                Some(Ok(analysis)) => analysis,
+                Some(Err(e)) => {
+                    warn!("Failed to analyze {}: {}", song.file_path, e);
+                    SongAnalysis::default()
+                }
+                None => {
+                    warn!("Analysis of {} panicked", song.file_path);
+                    SongAnalysis::default()
+                }
+            };
+
+            if let Err(e) = db
+                .save_song_analysis(
+                    row_id,
+                    analysis.bpm.map(f64::from),
+                    analysis.key.map(|key| key.index()),
+                    analysis.energy.map(f64::from),
+                )
+                .await
+            {
+                warn!("Failed to save analysis of song {}: {}", song.id, e);
+            }
+            yield Message::SongAnalyzed(song.id, analysis);
+        }
+        yield Message::SongAnalysisFinished;
+    }
+}
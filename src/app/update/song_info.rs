@@ -0,0 +1,182 @@
+//! Song info editor handlers
+//!
+//! Corrections to an NCM song's title, artist and album are saved as local
+//! overrides and shown right away in the queue, player, open playlist and
+//! MPRIS.
+
+use iced::Task;
+use tracing::error;
+
+use crate::app::message::Message;
+use crate::app::state::{App, SongInfoDialog};
+use crate::database::SongId;
+use crate::features::song_overrides::{self, SongOverride, SongTags};
+use crate::features::song_source;
+
+impl App {
+    /// Handle song info editor messages
+    pub fn handle_song_info(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::SongOverridesLoaded => {
+                self.library
+                    .queue
+                    .iter_mut()
+                    .for_each(song_overrides::apply);
+                if let Some(song) = &mut self.library.current_song {
+                    song_overrides::apply(song);
+                }
+                self.update_mpris_state();
+                self.refresh_tray_state();
+                Some(Task::none())
+            }
+
+            Message::EditSongInfo => {
+                let Some(song) = &self.library.current_song else {
+                    return Some(Task::none());
+                };
+                let Some(ncm_id) = song_source::ncm_id(song) else {
+                    return Some(Task::none());
+                };
+                let saved = song_overrides::get(ncm_id);
+                self.ui.dialogs.song_info = Some(SongInfoDialog {
+                    ncm_id,
+                    tags: SongTags::of(song),
+                    original: saved
+                        .as_ref()
+                        .map_or_else(|| SongTags::of(song), |o| o.original.clone()),
+                    overridden: saved.is_some(),
+                });
+                self.ui.dialogs.song_info_animation.start();
+                Some(Task::none())
+            }
+
+            Message::SongInfoTitleChanged(value) => {
+                if let Some(dialog) = &mut self.ui.dialogs.song_info {
+                    dialog.tags.title = value.clone();
+                }
+                Some(Task::none())
+            }
+
+            Message::SongInfoArtistChanged(value) => {
+                if let Some(dialog) = &mut self.ui.dialogs.song_info {
+                    dialog.tags.artist = value.clone();
+                }
+                Some(Task::none())
+            }
+
+            Message::SongInfoAlbumChanged(value) => {
+                if let Some(dialog) = &mut self.ui.dialogs.song_info {
+                    dialog.tags.album = value.clone();
+                }
+                Some(Task::none())
+            }
+
+            Message::SaveSongInfo => {
+                // A song needs at least a title
+                if self
+                    .ui
+                    .dialogs
+                    .song_info
+                    .as_ref()
+                    .is_none_or(|dialog| dialog.tags.title.trim().is_empty())
+                {
+                    return Some(Task::none());
+                }
+                let Some(dialog) = self.close_song_info() else {
+                    return Some(Task::none());
+                };
+                let tags = SongTags {
+                    title: dialog.tags.title.trim().to_string(),
+                    artist: dialog.tags.artist.trim().to_string(),
+                    album: dialog.tags.album.trim().to_string(),
+                };
+                // Matching NCM again is the same as no override
+                if tags == dialog.original {
+                    return Some(self.reset_song_info(dialog));
+                }
+
+                let song_override = SongOverride {
+                    tags: tags.clone(),
+                    original: dialog.original,
+                };
+                let row = song_override.to_row(dialog.ncm_id);
+                song_overrides::set(dialog.ncm_id, song_override);
+                self.retag_song(dialog.ncm_id, &tags);
+
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                Some(Task::perform(
+                    async move {
+                        if let Err(e) = db.set_song_override(&row).await {
+                            error!("Failed to save song override {}: {}", row.ncm_id, e);
+                        }
+                    },
+                    |_| Message::NoOp,
+                ))
+            }
+
+            Message::ResetSongInfo => {
+                let Some(dialog) = self.close_song_info() else {
+                    return Some(Task::none());
+                };
+                Some(self.reset_song_info(dialog))
+            }
+
+            Message::CloseSongInfo => {
+                self.close_song_info();
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+
+    fn close_song_info(&mut self) -> Option<SongInfoDialog> {
+        self.ui.dialogs.song_info_animation.stop();
+        self.ui.dialogs.song_info.take()
+    }
+
+    /// Go back to NCM's details for the song of a closed editor
+    fn reset_song_info(&mut self, dialog: SongInfoDialog) -> Task<Message> {
+        if song_overrides::remove(dialog.ncm_id).is_none() {
+            return Task::none();
+        }
+        self.retag_song(dialog.ncm_id, &dialog.original);
+
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        let ncm_id = dialog.ncm_id as i64;
+        Task::perform(
+            async move {
+                if let Err(e) = db.remove_song_override(ncm_id).await {
+                    error!("Failed to remove song override {}: {}", ncm_id, e);
+                }
+            },
+            |_| Message::NoOp,
+        )
+    }
+
+    /// Show `tags` for an NCM song everywhere it is already on screen
+    fn retag_song(&mut self, ncm_id: u64, tags: &SongTags) {
+        let is_song = |song: &crate::database::DbSong| song_source::ncm_id(song) == Some(ncm_id);
+        self.library
+            .queue
+            .iter_mut()
+            .chain(self.library.current_song.as_mut())
+            .chain(self.library.recently_played.iter_mut())
+            .filter(|song| is_song(song))
+            .for_each(|song| tags.apply_to(song));
+
+        let key = SongId::ncm(ncm_id);
+        if let Some(playlist) = &mut self.ui.playlist_page.current {
+            for item in playlist.songs.iter_mut().filter(|item| item.id == key) {
+                item.set_tags(tags.title.clone(), tags.artist.clone(), tags.album.clone());
+            }
+        }
+
+        self.update_mpris_state();
+        self.refresh_tray_state();
+    }
+}
@@ -10,6 +10,7 @@ use std::sync::Arc;
 use crate::api::NcmClient;
 use crate::audio::streaming::{SharedBuffer, StreamingEvent, start_buffer_download};
 use crate::database::DbSong;
+use crate::features::song_source;
 
 /// Result of resolving a song with streaming support
 #[derive(Debug, Clone)]
@@ -26,10 +27,7 @@ pub struct ResolvedSong {
 
 /// Check if a song needs resolution (NCM song without local file)
 pub fn needs_resolution(song: &DbSong) -> bool {
-    // NCM songs have negative IDs or file_path starting with "ncm://"
-    let is_ncm = song.id < 0 || song.file_path.starts_with("ncm://");
-
-    if !is_ncm {
+    if !song_source::is_ncm(song) {
         return false;
     }
 
@@ -42,20 +40,6 @@ pub fn needs_resolution(song: &DbSong) -> bool {
     !std::path::Path::new(&song.file_path).exists()
 }
 
-/// Get NCM song ID from DbSong
-pub fn get_ncm_id(song: &DbSong) -> u64 {
-    if song.id < 0 {
-        (-song.id) as u64
-    } else if song.file_path.starts_with("ncm://") {
-        song.file_path
-            .trim_start_matches("ncm://")
-            .parse()
-            .unwrap_or(song.id as u64)
-    } else {
-        song.id as u64
-    }
-}
-
 /// Resolve a song with streaming support
 ///
 /// This function:
@@ -66,14 +50,16 @@ pub async fn resolve_song(
     client: Arc<NcmClient>,
     song: &DbSong,
     event_tx: tokio::sync::mpsc::Sender<StreamingEvent>,
-) -> Option<ResolvedSong> {
-    let ncm_id = get_ncm_id(song);
+) -> anyhow::Result<ResolvedSong> {
+    let Some(ncm_id) = song_source::ncm_id(song) else {
+        anyhow::bail!("Song {} is not an NCM track", song.id);
+    };
 
     let song_cache_dir = crate::utils::songs_cache_dir();
     let cover_cache_dir = crate::utils::covers_cache_dir();
 
-    std::fs::create_dir_all(&song_cache_dir).ok()?;
-    std::fs::create_dir_all(&cover_cache_dir).ok()?;
+    std::fs::create_dir_all(&song_cache_dir)?;
+    std::fs::create_dir_all(&cover_cache_dir)?;
 
     // Use stem for cache lookup - actual extension determined by format detection
     let song_stem = ncm_id.to_string();
@@ -107,7 +93,7 @@ pub async fn resolve_song(
             );
             let _ = event_tx.send(StreamingEvent::Playable).await;
             let _ = event_tx.send(StreamingEvent::Complete).await;
-            return Some(ResolvedSong {
+            return Ok(ResolvedSong {
                 file_path: cached_path.to_string_lossy().to_string(),
                 cover_path,
                 shared_buffer: None,
@@ -131,7 +117,7 @@ pub async fn resolve_song(
         Err(e) => {
             tracing::error!("Failed to get song URL for {}: {}", ncm_id, e);
             let _ = event_tx.send(StreamingEvent::Error(e.to_string())).await;
-            return None;
+            return Err(e);
         }
     };
 
@@ -142,7 +128,7 @@ pub async fn resolve_song(
             let _ = event_tx
                 .send(StreamingEvent::Error("No URL available".to_string()))
                 .await;
-            return None;
+            anyhow::bail!("No URL available for song {}", ncm_id);
         }
     };
 
@@ -155,7 +141,7 @@ pub async fn resolve_song(
 
     // Return immediately with the buffer
     // Note: file_path uses stem only - actual cached file will have correct extension
-    Some(ResolvedSong {
+    Ok(ResolvedSong {
         file_path: cache_path.to_string_lossy().to_string(),
         cover_path,
         shared_buffer: Some(shared_buffer),
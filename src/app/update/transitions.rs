@@ -0,0 +1,78 @@
+//! Shared-element transition handlers
+
+use iced::Task;
+use iced::time::Instant;
+use std::time::Duration;
+
+use crate::app::{App, Message, SharedElement};
+use crate::ui::animation::{Flight, lerp_rect};
+
+/// A card press older than this doesn't belong to the playlist being opened
+const PRESS_WINDOW: Duration = Duration::from_secs(1);
+
+impl App {
+    /// Handle shared-element transition messages
+    pub fn handle_transitions(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::SharedCoverPressed(handle, bounds) => {
+                self.ui.shared.pressed_cover = Some((handle.clone(), *bounds, Instant::now()));
+                Some(Task::none())
+            }
+            Message::SharedElementMoved(element, bounds) => {
+                self.ui.shared.bounds.insert(*element, *bounds);
+                if *element == SharedElement::PlaylistHeaderCover {
+                    if let Some(flight) = &mut self.ui.shared.cover_flight {
+                        flight.land_at(*bounds);
+                    }
+                }
+                Some(Task::none())
+            }
+            _ => None,
+        }
+    }
+
+    /// Fly the pressed card's cover into the header of the playlist opening
+    pub(super) fn take_off_pressed_cover(&mut self) {
+        let Some((handle, from, pressed_at)) = self.ui.shared.pressed_cover.take() else {
+            return;
+        };
+        if pressed_at.elapsed() > PRESS_WINDOW || self.core.settings.display.power_saving_mode {
+            return;
+        }
+        let mut flight = Flight::new(handle, from);
+        // The header sits in the same place for every playlist
+        if let Some(to) = self
+            .ui
+            .shared
+            .bounds
+            .get(&SharedElement::PlaylistHeaderCover)
+        {
+            flight.land_at(*to);
+        }
+        self.ui.shared.cover_flight = Some(flight);
+    }
+
+    /// Where the song cover is drawn while the lyrics page opens or closes,
+    /// moving between the player bar and the lyrics page with the animation
+    pub fn lyrics_cover_flight(&self) -> Option<iced::Rectangle> {
+        let animation = &self.ui.lyrics.animation;
+        if !animation.is_animating() || self.core.settings.display.power_saving_mode {
+            return None;
+        }
+        let from = self.ui.shared.bounds.get(&SharedElement::PlayerBarCover)?;
+        let to = self.ui.shared.bounds.get(&SharedElement::LyricsCover)?;
+        Some(lerp_rect(*from, centered_square(*to), animation.progress()))
+    }
+}
+
+/// The square cover inside the lyrics page's cover slot, which spans the
+/// panel width
+fn centered_square(slot: iced::Rectangle) -> iced::Rectangle {
+    let side = slot.width.min(slot.height);
+    iced::Rectangle {
+        x: slot.x + (slot.width - side) / 2.0,
+        width: side,
+        height: side,
+        ..slot
+    }
+}
@@ -0,0 +1,107 @@
+//! Recently deleted handlers
+
+use iced::Task;
+use tracing::error;
+
+use crate::app::helpers::{load_playlists, load_songs, load_trash};
+use crate::app::message::Message;
+use crate::app::state::App;
+
+impl App {
+    /// Handle trash messages
+    pub fn handle_trash(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::TrashLoaded(playlists, songs) => {
+                self.ui.trash.playlists = playlists.clone();
+                self.ui.trash.songs = songs.clone();
+                Some(Task::none())
+            }
+
+            Message::RestoreTrashedPlaylist(id) => {
+                let id = *id;
+                Some(self.change_trash(move |db| async move {
+                    if let Err(e) = db.restore_playlist(id).await {
+                        error!("Failed to restore playlist {}: {}", id, e);
+                    }
+                }))
+            }
+
+            Message::RestoreTrashedSong(id) => {
+                let id = *id;
+                Some(self.change_trash(move |db| async move {
+                    if let Err(e) = db.restore_song(id).await {
+                        error!("Failed to restore song {}: {}", id, e);
+                    }
+                }))
+            }
+
+            Message::PurgeTrashedPlaylist(id) => {
+                let id = *id;
+                Some(self.change_trash(move |db| async move {
+                    if let Err(e) = db.purge_playlist(id).await {
+                        error!("Failed to delete playlist {}: {}", id, e);
+                    }
+                }))
+            }
+
+            Message::PurgeTrashedSong(id) => {
+                let id = *id;
+                Some(self.change_trash(move |db| async move {
+                    if let Err(e) = db.purge_song(id).await {
+                        error!("Failed to delete song {}: {}", id, e);
+                    }
+                }))
+            }
+
+            Message::EmptyTrash => {
+                if !self.ui.trash.confirming_empty {
+                    self.ui.trash.confirming_empty = true;
+                    return Some(Task::none());
+                }
+                self.ui.trash.confirming_empty = false;
+                Some(self.change_trash(|db| async move {
+                    match db.empty_trash().await {
+                        Ok(count) => tracing::info!("Emptied {} items from the trash", count),
+                        Err(e) => error!("Failed to empty trash: {}", e),
+                    }
+                }))
+            }
+
+            Message::TrashChanged => {
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::none());
+                };
+                // Restored items show up in the library again
+                Some(Task::batch([
+                    self.load_trash(),
+                    Task::perform(load_songs(db.clone()), Message::SongsLoaded),
+                    Task::perform(load_playlists(db), Message::PlaylistsLoaded),
+                ]))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Reload the recently deleted page
+    pub(super) fn load_trash(&self) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        Task::perform(load_trash(db), |(playlists, songs)| {
+            Message::TrashLoaded(playlists, songs)
+        })
+    }
+
+    /// Run a trash change in the database, then reload
+    fn change_trash<F, Fut>(&self, change: F) -> Task<Message>
+    where
+        F: FnOnce(std::sync::Arc<crate::database::Database>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let Some(db) = self.core.db.clone() else {
+            return Task::none();
+        };
+        Task::perform(change(db), |_| Message::TrashChanged)
+    }
+}
@@ -3,10 +3,10 @@
 
 use iced::Task;
 
-use crate::app::helpers::update_tray_state_full;
 use crate::app::message::Message;
 use crate::app::state::App;
 use crate::features::TrayCommand;
+use crate::features::jobs::{Category, Job};
 
 impl App {
     /// Handle tray-related messages
@@ -15,7 +15,7 @@ impl App {
             Message::TrayStarted(rx) => {
                 tracing::info!("Tray service started");
                 let rx = rx.clone();
-                Some(Task::run(
+                let commands = Task::run(
                     async_stream::stream! {
                         loop {
                             let cmd = rx.lock().await.recv().await;
@@ -27,7 +27,14 @@ impl App {
                         }
                     },
                     Message::TrayCommand,
-                ))
+                );
+                Some(Task::batch([commands, self.load_output_devices()]))
+            }
+
+            Message::OutputDevicesLoaded(devices) => {
+                self.core.output_devices = devices.clone();
+                self.refresh_tray_state();
+                Some(Task::none())
             }
 
             Message::TrayCommand(cmd) => {
@@ -35,6 +42,9 @@ impl App {
                     TrayCommand::ShowOrFocusWindow => {
                         return Some(self.update(Message::ShowWindow));
                     }
+                    TrayCommand::MenuOpening => {
+                        return Some(self.load_output_devices());
+                    }
                     TrayCommand::ToggleWindow => {
                         return Some(self.update(Message::ToggleWindow));
                     }
@@ -54,29 +64,37 @@ impl App {
                         self.clear_shuffle_cache();
                         self.cache_shuffle_indices();
                         let _ = self.preload_adjacent_tracks_with_ncm();
-                        let (title, artist) = self
-                            .library
-                            .current_song
-                            .as_ref()
-                            .map(|s| (Some(s.title.clone()), Some(s.artist.clone())))
-                            .unwrap_or((None, None));
-                        let is_playing = self
-                            .core
-                            .audio
-                            .as_ref()
-                            .map(|p| p.is_playing())
-                            .unwrap_or(false);
-                        update_tray_state_full(is_playing, title, artist, *mode);
+                        self.refresh_tray_state();
+                        return Some(self.auto_dj_top_up_if_needed());
                     }
                     TrayCommand::ToggleFavorite => {
-                        // Toggle favorite for current NCM song
                         if let Some(song) = &self.library.current_song {
-                            if song.id < 0 {
-                                let ncm_id = (-song.id) as u64;
-                                return Some(self.update(Message::ToggleFavorite(ncm_id)));
-                            }
+                            return Some(self.update(Message::ToggleFavorite(song.id)));
                         }
                     }
+                    TrayCommand::ToggleStopAfterCurrent => {
+                        return Some(self.update(Message::ToggleStopAfterCurrent));
+                    }
+                    TrayCommand::PlayQueueIndex(index) => {
+                        if *index < self.library.queue.len() {
+                            return Some(self.update(Message::PlayQueueIndex(*index)));
+                        }
+                    }
+                    TrayCommand::ToggleLyrics => {
+                        let mut tasks = Vec::new();
+                        if self.core.window_hidden {
+                            tasks.push(self.update(Message::ShowWindow));
+                        }
+                        tasks.push(if self.ui.lyrics.is_open {
+                            self.update(Message::CloseLyricsPage)
+                        } else {
+                            self.update(Message::OpenLyricsPage)
+                        });
+                        return Some(Task::batch(tasks));
+                    }
+                    TrayCommand::SetOutputDevice(device) => {
+                        return Some(self.update(Message::UpdateAudioOutputDevice(device.clone())));
+                    }
                     TrayCommand::Quit => {
                         return Some(self.update(Message::ConfirmExit));
                     }
@@ -87,4 +105,12 @@ impl App {
             _ => None,
         }
     }
+
+    /// List the audio output devices for the tray off the UI thread
+    pub(super) fn load_output_devices(&self) -> Task<Message> {
+        Task::perform(
+            Job::new(Category::Disk).run_blocking(crate::audio::get_audio_devices),
+            |devices| Message::OutputDevicesLoaded(devices.unwrap_or_default()),
+        )
+    }
 }
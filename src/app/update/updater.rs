@@ -0,0 +1,277 @@
+//! Updater and changelog handlers
+
+use std::sync::Arc;
+
+use iced::Task;
+
+use crate::app::message::Message;
+use crate::app::state::{App, UpdateStatus, WhatsNew};
+use crate::database::Database;
+use crate::features::updater::{self, ReleaseInfo, Version};
+use crate::features::whats_new;
+use crate::i18n::Key;
+
+impl App {
+    /// Startup bookkeeping: schedule the update check
+    pub fn init_updater(&mut self) -> Task<Message> {
+        if self.core.settings.system.auto_check_updates {
+            Task::done(Message::CheckForUpdates(false))
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Record the running version in the history, to notice an update
+    pub fn record_app_version(&self, db: Arc<Database>) -> Task<Message> {
+        let legacy = self.core.settings.system.last_run_version.clone();
+        Task::perform(
+            async move {
+                db.record_app_version(&Version::current().to_string(), &legacy)
+                    .await
+            },
+            |result| match result {
+                Ok(previous) => Message::AppVersionRecorded(previous),
+                Err(e) => {
+                    tracing::warn!("Failed to record running version: {}", e);
+                    Message::Noop
+                }
+            },
+        )
+    }
+
+    fn open_changelog(&mut self, whats_new: WhatsNew) {
+        self.ui.updater.changelog = Some(whats_new);
+        self.ui.updater.changelog_animation.start();
+    }
+
+    fn close_update_prompt(&mut self) {
+        self.ui.updater.prompt_open = false;
+        self.ui.updater.prompt_animation.stop();
+    }
+
+    fn open_update_prompt(&mut self) {
+        self.ui.updater.prompt_open = true;
+        self.ui.updater.prompt_animation.start();
+    }
+
+    /// Download a release in the background
+    fn download_update(&mut self, release: ReleaseInfo) -> Task<Message> {
+        tracing::info!("Downloading update {}", release.version);
+        self.ui.updater.status = UpdateStatus::Downloading(release.clone());
+        let proxy = self.core.settings.network.proxy_url();
+
+        Task::perform(
+            async move { updater::download(&release, proxy).await },
+            |result| Message::UpdateDownloaded(result.map_err(|e| e.to_string())),
+        )
+    }
+
+    /// Handle updater messages
+    pub fn handle_updater(&mut self, message: &Message) -> Option<Task<Message>> {
+        match message {
+            Message::CheckForUpdates(manual) => {
+                // A verified update is waiting: show the prompt again instead
+                if matches!(self.ui.updater.status, UpdateStatus::Ready(..)) {
+                    if *manual {
+                        self.open_update_prompt();
+                    }
+                    return Some(Task::none());
+                }
+                if matches!(
+                    self.ui.updater.status,
+                    UpdateStatus::Checking | UpdateStatus::Downloading(_)
+                ) {
+                    return Some(Task::none());
+                }
+
+                self.ui.updater.status = UpdateStatus::Checking;
+                let manual = *manual;
+                let channel = self.core.settings.system.update_channel;
+                let proxy = self.core.settings.network.proxy_url();
+
+                Some(Task::perform(
+                    async move { updater::check_for_update(channel, proxy).await },
+                    move |result| {
+                        Message::UpdateCheckFinished(manual, result.map_err(|e| e.to_string()))
+                    },
+                ))
+            }
+
+            Message::UpdateCheckFinished(manual, result) => match result {
+                Ok(Some(release)) => {
+                    tracing::info!("Update available: {}", release.version);
+                    if release.is_installable() {
+                        Some(self.download_update(release.clone()))
+                    } else {
+                        self.ui.updater.status = UpdateStatus::Available(release.clone());
+                        self.open_update_prompt();
+                        Some(Task::none())
+                    }
+                }
+                Ok(None) => {
+                    self.ui.updater.status = UpdateStatus::UpToDate;
+                    if *manual {
+                        return Some(Task::done(Message::ShowToast(
+                            self.core.locale.get(Key::UpdateStatusUpToDate).to_string(),
+                        )));
+                    }
+                    Some(Task::none())
+                }
+                Err(e) => {
+                    tracing::warn!("Update check failed: {}", e);
+                    self.ui.updater.status = UpdateStatus::Failed(e.clone());
+                    if *manual {
+                        return Some(Task::done(Message::ShowToast(
+                            self.core.locale.get(Key::UpdateCheckFailed).to_string(),
+                        )));
+                    }
+                    Some(Task::none())
+                }
+            },
+
+            Message::UpdateDownloaded(result) => {
+                let UpdateStatus::Downloading(release) = &self.ui.updater.status else {
+                    return Some(Task::none());
+                };
+                let release = release.clone();
+
+                match result {
+                    Ok(path) => {
+                        tracing::info!("Update {} downloaded to {:?}", release.version, path);
+                        self.ui.updater.status = UpdateStatus::Ready(release, path.clone());
+                    }
+                    Err(e) => {
+                        // Fall back to the release page rather than failing silently
+                        tracing::error!("Update download failed: {}", e);
+                        self.ui.updater.status = UpdateStatus::Available(release);
+                    }
+                }
+                self.open_update_prompt();
+                Some(Task::none())
+            }
+
+            Message::InstallUpdate => {
+                let UpdateStatus::Ready(release, path) = &self.ui.updater.status else {
+                    return Some(Task::none());
+                };
+                tracing::info!("Installing update {}", release.version);
+
+                match updater::install_and_restart(path) {
+                    Ok(()) => Some(self.exit_app()),
+                    Err(e) => {
+                        tracing::error!("Failed to install update: {}", e);
+                        self.ui.updater.status = UpdateStatus::Failed(e.to_string());
+                        self.close_update_prompt();
+                        Some(Task::done(Message::ShowToast(
+                            self.core.locale.get(Key::UpdateInstallFailed).to_string(),
+                        )))
+                    }
+                }
+            }
+
+            Message::OpenReleasePage => {
+                let url = match &self.ui.updater.status {
+                    UpdateStatus::Available(release)
+                    | UpdateStatus::Downloading(release)
+                    | UpdateStatus::Ready(release, _) => release.page_url.as_str(),
+                    _ => updater::RELEASES_PAGE,
+                };
+                if let Err(e) = crate::platform::shell::open_url(url) {
+                    tracing::warn!("Failed to open release page: {}", e);
+                }
+                self.close_update_prompt();
+                Some(Task::none())
+            }
+
+            Message::DismissUpdatePrompt => {
+                self.close_update_prompt();
+                Some(Task::none())
+            }
+
+            Message::AppVersionRecorded(previous) => {
+                // The history in the database has taken over from settings
+                if !self.core.settings.system.last_run_version.is_empty() {
+                    self.core.settings.system.last_run_version.clear();
+                    if let Err(e) = self.core.settings.save() {
+                        tracing::warn!("Failed to save settings: {}", e);
+                    }
+                }
+
+                // Fresh installs have nothing to compare against
+                let current = Version::current().to_string();
+                let Some(previous) = previous.as_deref().filter(|p| *p != current) else {
+                    return Some(Task::none());
+                };
+                tracing::info!("Updated from {} to {}", previous, current);
+                updater::cleanup();
+
+                if !whats_new::is_upgrade(previous, &current)
+                    || !self.core.settings.system.show_whats_new
+                {
+                    return Some(Task::none());
+                }
+                self.open_changelog(WhatsNew {
+                    entries: whats_new::since(previous, &current),
+                    notes: None,
+                    history: false,
+                });
+                let proxy = self.core.settings.network.proxy_url();
+                Some(Task::perform(
+                    async move { updater::fetch_release(&current, proxy).await },
+                    |result| match result {
+                        Ok(release) => Message::ChangelogLoaded(release),
+                        Err(e) => {
+                            tracing::warn!("Failed to fetch release notes: {}", e);
+                            Message::Noop
+                        }
+                    },
+                ))
+            }
+
+            Message::ChangelogLoaded(release) => {
+                if let Some(whats_new) = &mut self.ui.updater.changelog {
+                    if !whats_new.history {
+                        whats_new.notes = Some(release.clone());
+                    }
+                }
+                Some(Task::none())
+            }
+
+            Message::OpenVersionHistory => {
+                let Some(db) = self.core.db.clone() else {
+                    return Some(Task::done(Message::VersionHistoryLoaded(Vec::new())));
+                };
+                Some(Task::perform(
+                    async move {
+                        db.get_app_versions().await.unwrap_or_else(|e| {
+                            tracing::warn!("Failed to load version history: {}", e);
+                            Vec::new()
+                        })
+                    },
+                    Message::VersionHistoryLoaded,
+                ))
+            }
+
+            Message::VersionHistoryLoaded(versions) => {
+                let ran: Vec<(String, i64)> = versions
+                    .iter()
+                    .map(|v| (v.version.clone(), v.first_run_at))
+                    .collect();
+                self.open_changelog(WhatsNew {
+                    entries: whats_new::history(&ran),
+                    notes: None,
+                    history: true,
+                });
+                Some(Task::none())
+            }
+
+            Message::DismissChangelog => {
+                // Notes stay in place so the dialog can fade out
+                self.ui.updater.changelog_animation.stop();
+                Some(Task::none())
+            }
+
+            _ => None,
+        }
+    }
+}
@@ -19,7 +19,7 @@ impl App {
                         self.ui.dialogs.exit_animation.start();
                     }
                     CloseBehavior::Exit => {
-                        return Some(iced::exit());
+                        return Some(self.exit_app());
                     }
                     CloseBehavior::MinimizeToTray => {
                         tracing::info!("Hiding window to tray");
@@ -30,12 +30,19 @@ impl App {
                 Some(Task::none())
             }
 
+            Message::WindowCloseRequested(id) => {
+                if self.is_lyrics_window(*id) {
+                    return Some(self.update(Message::CloseLyricsWindow));
+                }
+                Some(self.update(Message::RequestClose))
+            }
+
             Message::ConfirmExit => {
                 if self.ui.dialogs.exit_remember {
                     self.core.settings.close_behavior = CloseBehavior::Exit;
                     let _ = self.core.settings.save();
                 }
-                Some(iced::exit())
+                Some(self.exit_app())
             }
 
             Message::MinimizeToTray => {
@@ -84,6 +91,14 @@ impl App {
                 Some(Task::none())
             }
 
+            Message::FirstFrame => {
+                if !self.core.startup_pending {
+                    return Some(Task::none());
+                }
+                crate::features::startup::first_frame();
+                Some(self.deferred_startup())
+            }
+
             // Sidebar resize
             Message::SidebarResizeStart => {
                 self.ui.sidebar_dragging = true;
@@ -98,4 +113,17 @@ impl App {
             _ => None,
         }
     }
+
+    /// Exit once buffered database writes are on disk
+    pub(super) fn exit_app(&self) -> Task<Message> {
+        let Some(db) = self.core.db.clone() else {
+            return iced::exit();
+        };
+        Task::future(async move { db.close().await }).then(|result| {
+            if let Err(e) = result {
+                tracing::warn!("Failed to flush database before exit: {}", e);
+            }
+            iced::exit()
+        })
+    }
 }
@@ -1,94 +1,39 @@
 // src/app/view.rs
 //! Application view rendering
 
-use iced::widget::{column, container, row, stack, Space};
+use iced::widget::{Row, Space, column, container, stack};
 use iced::{Alignment, Element, Fill};
 
 use super::message::Message;
 use super::{App, Route};
+use crate::database::DbSong;
 use crate::ui::{components, pages, theme, widgets};
 
 impl App {
     /// Build the view for a specific window
-    pub fn view(&self, _window_id: iced::window::Id) -> Element<'_, Message> {
+    pub fn view(&self, window_id: iced::window::Id) -> Element<'_, Message> {
+        if let Some(window) = self
+            .ui
+            .lyrics
+            .window
+            .filter(|window| window.id == window_id)
+        {
+            return self.lyrics_window_view(window.fullscreen);
+        }
+
         // Check if lyrics page is open or animating
         let lyrics_progress = self.ui.lyrics.animation.progress();
         let lyrics_animating = self.ui.lyrics.animation.is_animating();
+        let lyrics_cover_flight = self.lyrics_cover_flight();
         let lyrics_overlay: Element<'_, Message> =
             if self.ui.lyrics.is_open || lyrics_animating || lyrics_progress > 0.01 {
                 if let Some(song) = &self.library.current_song {
-                    // Get playback info - same logic as player bar for consistency
-                    let (is_playing, position, duration) = if let Some(player) = &self.core.audio {
-                        let info = player.get_info();
-                        if info.duration.as_secs_f32() > 0.0 {
-                            // Player has loaded a file
-                            (
-                                player.is_playing(),
-                                info.position.as_secs_f32() / info.duration.as_secs_f32().max(1.0),
-                                info.duration.as_secs_f32(),
-                            )
-                        } else {
-                            // Player exists but no file loaded yet (e.g., NCM song still resolving)
-                            // Use saved state for display
-                            let saved_pos = self
-                                .library
-                                .playback_state
-                                .as_ref()
-                                .map(|s| s.position_secs as f32)
-                                .unwrap_or(0.0);
-                            let song_duration = song.duration_secs.max(1) as f32;
-                            (false, saved_pos / song_duration, song_duration)
-                        }
-                    } else {
-                        // No player - use saved state
-                        let saved_pos = self
-                            .library
-                            .playback_state
-                            .as_ref()
-                            .map(|s| s.position_secs as f32)
-                            .unwrap_or(0.0);
-                        let song_duration = song.duration_secs.max(1) as f32;
-                        (false, saved_pos / song_duration, song_duration)
-                    };
-
-                    // Use preview position while seeking, otherwise use actual position
-                    let display_position = if self.ui.seek_preview_position.is_some() {
-                        self.ui.seek_preview_position.unwrap()
-                    } else {
-                        position
-                    };
-
-                    // Calculate current lyric line based on playback position
-                    let position_ms = (position * duration * 1000.0) as u64;
-                    let current_line = pages::find_current_line(&self.ui.lyrics.lines, position_ms);
-
-                    pages::lyrics::view(
+                    self.lyrics_page(
                         song,
-                        is_playing,
-                        display_position,
-                        duration,
-                        self.ui.lyrics.cached_engine_lines.as_ref(), // Use cached engine lines (Rc)
-                        current_line,
-                        self.core.settings.play_mode,
                         lyrics_progress,
-                        &self.ui.lyrics.bg_colors,
-                        &self.ui.lyrics.bg_shader,
-                        &self.ui.lyrics.textured_bg_shader,
-                        self.ui.lyrics.engine.as_ref(),
-                        self.core.settings.display.power_saving_mode,
-                        // Check if current song is liked
-                        if song.id < 0 {
-                            let ncm_id = (-song.id) as u64;
-                            self.core
-                                .user_info
-                                .as_ref()
-                                .map(|u| u.like_songs.contains(&ncm_id))
-                                .unwrap_or(false)
-                        } else {
-                            false
+                        pages::lyrics::LyricsHost::Overlay {
+                            cover_in_flight: lyrics_cover_flight.is_some(),
                         },
-                        self.core.audio.as_ref().and_then(|p| p.buffer_progress()),
-                        self.is_fm_mode(),
                     )
                 } else {
                     Space::new().width(0).height(0).into()
@@ -105,6 +50,7 @@ impl App {
             self.core.user_info.as_ref(),
             self.ui.importing_playlist.as_ref(),
             &self.library.playlists,
+            &self.library.playlist_mosaics,
             &self.ui.home.user_playlists,
             &self.ui.sidebar_animations,
             self.ui.sidebar_width,
@@ -114,14 +60,11 @@ impl App {
         let resize_handle = components::sidebar_resize_handle::view(self.ui.sidebar_dragging);
 
         // Determine main content: playlist page or nav page
-        // Get liked songs for playlist view (empty set if not logged in)
+        // Liked songs for the playlist view
         let liked_songs = self
-            .core
-            .user_info
-            .as_ref()
-            .map(|u| &u.like_songs)
-            .cloned()
-            .unwrap_or_default();
+            .library
+            .favorites
+            .liked_song_ids(&self.library.ncm_links);
 
         let current_user_id = self.core.user_info.as_ref().map(|u| u.user_id);
 
@@ -137,20 +80,53 @@ impl App {
                         &self.ui.playlist_page.search_animation,
                         self.ui.playlist_page.search_expanded,
                         &self.ui.playlist_page.search_query,
+                        self.ui.playlist_page.tempo_sort,
+                        self.ui.playlist_page.sort,
+                        self.ui.playlist_page.filter,
                         liked_songs,
+                        &self.library.blocklist,
+                        self.library
+                            .playlist_mosaics
+                            .get(&playlist.id)
+                            .map(String::as_str),
                         self.core.locale,
                         self.ui.playlist_page.scroll_state.clone(),
                         current_user_id,
                         current_playing_id,
+                        self.ui.playlist_page.load_state.progress(),
+                        self.ui.playlist_page.skeleton_phase(),
+                        self.ui.shared.cover_flight.is_some(),
+                        self.playlist_backdrop_enabled()
+                            .then_some(&self.ui.playlist_page.backdrop),
+                        self.playlist_tint(),
+                        self.ui
+                            .playlist_page
+                            .playback_panel_open
+                            .then_some(self.ui.playlist_page.playback),
+                        self.library
+                            .liked_mirror
+                            .as_ref()
+                            .filter(|m| -(m.playlist.id as i64) == playlist.id)
+                            .map(|_| {
+                                self.library
+                                    .liked_sync
+                                    .unwrap_or(crate::features::favorites::LikedSync::Offline)
+                            }),
                     )
                 } else {
                     Space::new().width(Fill).height(Fill).into()
                 }
             }
-            Route::Search { .. } => pages::search::view(&self.ui.search, self.core.locale),
+            Route::Search { .. } => pages::search::view(
+                &self.ui.search,
+                &self.library.followed_artists,
+                self.core.locale,
+            ),
             Route::Home => pages::home::view(
                 &self.ui.search_query,
                 &self.ui.home,
+                &self.ui.discover,
+                &self.core.settings.display.home_layout,
                 self.core.locale,
                 self.core.is_logged_in,
             ),
@@ -161,6 +137,8 @@ impl App {
             Route::Radio => pages::home::view(
                 &self.ui.search_query,
                 &self.ui.home,
+                &self.ui.discover,
+                &self.core.settings.display.home_layout,
                 self.core.locale,
                 self.core.is_logged_in,
             ),
@@ -169,23 +147,67 @@ impl App {
                 pages::settings::view(
                     &self.core.settings,
                     self.ui.active_settings_section,
+                    &self.ui.settings_search,
+                    self.ui.settings_category,
                     self.core.locale,
                     self.ui.editing_keybinding,
+                    self.ui.lyrics_tuning_expanded,
                     self.core.is_logged_in,
                     self.core.user_info.as_ref(),
                     self.ui.cache_stats.as_ref(),
+                    &self.ui.updater.status,
+                    &self.library.playlists,
+                    &self.ui.check_in_history,
+                    &self.ui.settings_profile_passphrase,
+                    self.library.song_analysis.len(),
+                    self.library.song_analysis_progress,
+                    self.ui.listen.active,
+                    self.ui
+                        .lyrics_save_batch
+                        .as_ref()
+                        .map(|batch| (batch.done, batch.total)),
+                    &self.ui.lyrics.textured_bg_shader,
                 )
             }
             Route::AudioEngine => pages::audio_engine::view(
                 &self.core.settings,
                 self.core.locale,
                 Some(self.core.audio_chain.analysis()),
+                self.ui.latency_calibration.as_ref(),
+                self.core.audio.as_ref().map(|audio| audio.sample_rates()),
             ),
+            Route::Plugins => {
+                pages::plugins::view(&self.core.plugins, &self.ui.plugins, self.core.locale)
+            }
+            Route::Logs => pages::logs::view(&self.ui.logs, self.core.locale),
+            Route::AnnualReport => {
+                pages::annual_report::view(&self.ui.annual_report, self.core.locale)
+            }
+            Route::Blocklist => pages::blocklist::view(
+                &self.library.blocklist,
+                &self.ui.blocklist,
+                self.core.locale,
+            ),
+            Route::Trash => pages::trash::view(&self.ui.trash, self.core.locale),
+            Route::UserProfile(_) => {
+                pages::profile::view(&self.ui.profile, &self.ui.discover, self.core.locale)
+            }
         };
 
         let needs_top_padding = !matches!(
             self.ui.current_route,
-            Route::Settings(_) | Route::AudioEngine | Route::Playlist(_) | Route::NcmPlaylist(_) | Route::RecentlyPlayed | Route::Search { .. }
+            Route::Settings(_)
+                | Route::AudioEngine
+                | Route::Plugins
+                | Route::Logs
+                | Route::AnnualReport
+                | Route::Blocklist
+                | Route::Trash
+                | Route::Playlist(_)
+                | Route::NcmPlaylist(_)
+                | Route::RecentlyPlayed
+                | Route::Search { .. }
+                | Route::UserProfile(_)
         );
 
         let main_content = if needs_top_padding {
@@ -206,9 +228,23 @@ impl App {
         );
         let controls_overlay = container(top_bar).width(Fill).padding(0);
 
+        // Search history and suggestions under the search box
+        let search_dropdown: Element<'_, Message> = if self.ui.search.dropdown_open {
+            components::search_dropdown::view(
+                &self.ui.search_query,
+                &self.ui.search.history,
+                &self.ui.search.hot_keywords,
+                &self.ui.search.suggestions,
+                self.core.locale,
+            )
+            .unwrap_or_else(|| Space::new().width(0).height(0).into())
+        } else {
+            Space::new().width(0).height(0).into()
+        };
+
         // Right panel with content and window controls overlay
         let right_panel = container(
-            stack![main_content, controls_overlay,]
+            stack![main_content, controls_overlay, search_dropdown]
                 .width(Fill)
                 .height(Fill),
         )
@@ -223,7 +259,7 @@ impl App {
                 let info = player.get_info();
                 if info.duration.as_secs_f32() > 0.0 {
                     // Player has loaded a file
-                    let display_pos = player.display_position().as_secs_f32();
+                    let display_pos = player.heard_position().as_secs_f32();
                     (
                         player.is_playing(),
                         display_pos,
@@ -306,6 +342,16 @@ impl App {
                 self.core.audio.as_ref().and_then(|p| p.buffer_progress()),
                 is_fm_mode,
                 is_first_song,
+                !self.current_chapters().is_empty(),
+                (
+                    self.core.settings.playback.skip_back_secs,
+                    self.core.settings.playback.skip_forward_secs,
+                ),
+                self.seek_hover_preview(duration),
+                self.current_track_quality()
+                    .map(|(quality, available)| (quality, available.to_vec())),
+                lyrics_cover_flight.is_some(),
+                self.core.locale,
             );
 
             // Build content with player bar - always use stack to keep layout consistent
@@ -313,18 +359,61 @@ impl App {
                 let queue_popup = components::queue_panel::view(
                     &self.library.queue,
                     self.library.queue_index,
+                    &self.ui.queue_sources,
+                    self.ui.queue_offline_only,
+                    self.core.settings.playback.autoplay_similar,
+                    &self.library.autoplay_ids,
                     self.core.locale,
                     is_fm_mode,
+                    self.library
+                        .current_song
+                        .as_ref()
+                        .and_then(|song| self.library.song_analysis.get(&song.id))
+                        .is_some_and(|analysis| analysis.energy.is_some()),
+                    self.ui.queue_sessions.as_deref(),
                 );
 
-                // Position queue popup above player bar, aligned to the right
+                // Position queue popup above player bar, next to the queue button
+                let padding = if self.core.locale.is_rtl() {
+                    iced::Padding::new(0.0).left(20.0).bottom(8.0)
+                } else {
+                    iced::Padding::new(0.0).right(20.0).bottom(8.0)
+                };
                 container(
                     column![
                         Space::new().height(Fill),
                         container(queue_popup)
                             .width(Fill)
-                            .align_x(Alignment::End)
-                            .padding(iced::Padding::new(0.0).right(20.0).bottom(8.0)),
+                            .align_x(self.core.locale.end())
+                            .padding(padding),
+                        Space::new().height(components::PLAYER_BAR_HEIGHT),
+                    ]
+                    .width(Fill)
+                    .height(Fill),
+                )
+                .width(Fill)
+                .height(Fill)
+                .into()
+            } else if self.ui.chapters_visible && !self.current_chapters().is_empty() {
+                let chapter_popup = components::chapter_panel::view(
+                    self.current_chapters(),
+                    self.current_chapter_index(),
+                    self.core.locale,
+                );
+
+                // Same spot as the queue popup; the two never show together
+                let padding = if self.core.locale.is_rtl() {
+                    iced::Padding::new(0.0).left(20.0).bottom(8.0)
+                } else {
+                    iced::Padding::new(0.0).right(20.0).bottom(8.0)
+                };
+                container(
+                    column![
+                        Space::new().height(Fill),
+                        container(chapter_popup)
+                            .width(Fill)
+                            .align_x(self.core.locale.end())
+                            .padding(padding),
                         Space::new().height(components::PLAYER_BAR_HEIGHT),
                     ]
                     .width(Fill)
@@ -338,9 +427,50 @@ impl App {
                 Space::new().width(0).height(0).into()
             };
 
+            // Listen mode and export cards, on the other side from the queue popup
+            let mut cards = column![].spacing(8);
+            if self.ui.export.is_running() {
+                cards = cards.push(components::export_card::view(
+                    &self.ui.export,
+                    self.core.locale,
+                ));
+            }
+            if self.ui.listen.active {
+                cards = cards.push(components::listen_card::view(
+                    &self.ui.listen,
+                    self.core.locale,
+                ));
+            }
+            let has_cards = self.ui.listen.active || self.ui.export.is_running();
+            let cards_overlay: Element<'_, Message> = if has_cards {
+                let padding = if self.core.locale.is_rtl() {
+                    iced::Padding::new(0.0).right(20.0).bottom(8.0)
+                } else {
+                    iced::Padding::new(0.0).left(20.0).bottom(8.0)
+                };
+                container(
+                    column![
+                        Space::new().height(Fill),
+                        container(cards)
+                            .width(Fill)
+                            .align_x(self.core.locale.start())
+                            .padding(padding),
+                        Space::new().height(components::PLAYER_BAR_HEIGHT),
+                    ]
+                    .width(Fill)
+                    .height(Fill),
+                )
+                .width(Fill)
+                .height(Fill)
+                .into()
+            } else {
+                Space::new().width(0).height(0).into()
+            };
+
             // Always use stack layout to preserve scrollable state
             stack![
                 column![right_panel, player_bar,].width(Fill).height(Fill),
+                cards_overlay,
                 queue_overlay,
             ]
             .width(Fill)
@@ -348,8 +478,14 @@ impl App {
             .into()
         };
 
-        // Main layout: sidebar + resize handle + right content (with player bar and queue popup)
-        let main_layout: Element<'_, Message> = row![sidebar, resize_handle, right_content]
+        // Main layout: sidebar + resize handle + right content (with player bar and queue popup),
+        // mirrored for right-to-left languages
+        let main_layout: Element<'_, Message> =
+            Row::with_children(self.core.locale.reading_order(vec![
+                sidebar,
+                resize_handle,
+                right_content,
+            ]))
             .width(Fill)
             .height(Fill)
             .into();
@@ -359,7 +495,12 @@ impl App {
         // Toast overlay (empty space if not visible)
         let toast_overlay: Element<'_, Message> = if self.ui.toast_visible {
             if let Some(toast) = &self.ui.toast {
-                let toast_widget = widgets::view_toast(toast);
+                let action = self
+                    .ui
+                    .toast_action
+                    .as_ref()
+                    .map(|(label, message)| (*label, message.clone()));
+                let toast_widget = widgets::view_toast(toast, action);
                 container(toast_widget)
                     .width(Fill)
                     .padding(20)
@@ -420,28 +561,337 @@ impl App {
                 Space::new().width(0).height(0).into()
             };
 
-        // Login popup overlay
+        // Crash recovery dialog overlay
+        let crash_dialog_progress = self.ui.dialogs.crash_animation.progress();
+        let crash_dialog_overlay: Element<'_, Message> =
+            if self.ui.dialogs.crash_report.is_some() || crash_dialog_progress > 0.01 {
+                let has_session = self
+                    .ui
+                    .dialogs
+                    .crash_report
+                    .as_ref()
+                    .is_some_and(|report| report.session.is_some());
+                components::crash_dialog::view(has_session, crash_dialog_progress, self.core.locale)
+            } else {
+                Space::new().width(0).height(0).into()
+            };
+
+        // External link confirmation overlay
+        let open_link_progress = self.ui.dialogs.open_link_animation.progress();
+        let open_link_overlay: Element<'_, Message> =
+            if self.ui.dialogs.open_link_pending.is_some() || open_link_progress > 0.01 {
+                components::open_link_dialog::view(
+                    self.ui
+                        .dialogs
+                        .open_link_pending
+                        .as_deref()
+                        .unwrap_or_default(),
+                    open_link_progress,
+                    self.core.locale,
+                )
+            } else {
+                Space::new().width(0).height(0).into()
+            };
+
+        // Publish to NCM overlay
+        let publish_overlay: Element<'_, Message> = match &self.ui.dialogs.publish {
+            Some(dialog) => components::publish_dialog::view(
+                dialog,
+                self.ui.dialogs.publish_animation.progress(),
+                self.core.locale,
+            ),
+            None => Space::new().width(0).height(0).into(),
+        };
+
+        // Playlist availability overlay
+        let health_overlay: Element<'_, Message> = match &self.ui.dialogs.health {
+            Some(dialog) => components::health_dialog::view(
+                dialog,
+                self.ui.dialogs.health_animation.progress(),
+                self.core.locale,
+            ),
+            None => Space::new().width(0).height(0).into(),
+        };
+
+        // Lyrics share overlay
+        let lyrics_share_overlay: Element<'_, Message> = match &self.ui.dialogs.lyrics_share {
+            Some(dialog) => components::lyrics_share_dialog::view(
+                dialog,
+                self.ui.dialogs.lyrics_share_animation.progress(),
+                Some(self.core.settings.display.lyrics_share.credit.trim())
+                    .filter(|name| !name.is_empty()),
+                self.core.locale,
+            ),
+            None => Space::new().width(0).height(0).into(),
+        };
+
+        // Song info editor overlay
+        let song_info_overlay: Element<'_, Message> = match &self.ui.dialogs.song_info {
+            Some(dialog) => components::song_info_dialog::view(
+                dialog,
+                self.ui.dialogs.song_info_animation.progress(),
+                self.core.locale,
+            ),
+            None => Space::new().width(0).height(0).into(),
+        };
+
+        // Export dialog overlay
+        let export_overlay: Element<'_, Message> = match &self.ui.dialogs.export {
+            Some(dialog) => components::export_dialog::view(
+                dialog,
+                self.ui.dialogs.export_animation.progress(),
+                self.core.locale,
+            ),
+            None => Space::new().width(0).height(0).into(),
+        };
+
+        // Cover viewer overlay
+        let cover_viewer_overlay: Element<'_, Message> = match &self.ui.dialogs.cover_viewer {
+            Some(viewer) => components::cover_viewer::view(
+                viewer,
+                self.ui.dialogs.cover_viewer_animation.progress(),
+                self.core.locale,
+            ),
+            None => Space::new().width(0).height(0).into(),
+        };
+
+        // Update prompt overlay
+        let update_prompt_progress = self.ui.updater.prompt_animation.progress();
+        let update_prompt_overlay: Element<'_, Message> =
+            if self.ui.updater.prompt_open || update_prompt_progress > 0.01 {
+                components::update_dialog::prompt_view(
+                    &self.ui.updater.status,
+                    update_prompt_progress,
+                    self.core.locale,
+                )
+            } else {
+                Space::new().width(0).height(0).into()
+            };
+
+        // What's new overlay (contents are kept while the dialog fades out)
+        let changelog_overlay: Element<'_, Message> = match &self.ui.updater.changelog {
+            Some(whats_new) => components::update_dialog::changelog_view(
+                whats_new,
+                self.ui.updater.changelog_animation.progress(),
+                self.core.locale,
+            ),
+            None => Space::new().width(0).height(0).into(),
+        };
+
+        // Login popup overlay, held back from the frame the backdrop is
+        // captured from since it doesn't fade in
         let login_popup_overlay = components::login_popup::view(
-            self.ui.home.login_popup_open,
-            self.ui.home.qr_code_path.as_ref(),
+            self.ui.home.login_popup_open && !self.ui.backdrop.capturing,
+            self.ui.home.qr_modules.as_deref(),
             self.ui.home.qr_status.as_deref(),
             self.core.user_info.as_ref(),
             self.core.is_logged_in,
             self.core.locale,
         );
 
+        // Memory debug overlay, above everything else
+        let memory_overlay: Element<'_, Message> = if self.ui.memory.overlay_visible {
+            components::memory_overlay::view(self.ui.memory.report.as_ref())
+        } else {
+            Space::new().width(0).height(0).into()
+        };
+
+        // Covers flying between pages, above everything but the memory overlay
+        let flight_overlay: Element<'_, Message> =
+            if let Some(flight) = &self.ui.shared.cover_flight {
+                widgets::shared_element::cover(flight.content.clone(), flight.bounds(), 8.0)
+            } else if let Some(bounds) = lyrics_cover_flight {
+                match self
+                    .library
+                    .current_song
+                    .as_ref()
+                    .and_then(|song| self.ui.lyrics.cover_for(song))
+                    .filter(|path| !path.starts_with("http"))
+                {
+                    Some(path) => widgets::shared_element::cover(
+                        iced::widget::image::Handle::from_path(path),
+                        bounds,
+                        // Player bar corners round up to the lyrics page's
+                        4.0 + 8.0 * lyrics_progress,
+                    ),
+                    None => Space::new().width(0).height(0).into(),
+                }
+            } else {
+                Space::new().width(0).height(0).into()
+            };
+
+        // Blurred window behind the modals, fading in with them
+        let frosted_overlay = widgets::frosted_backdrop::view(
+            self.ui
+                .backdrop
+                .frosted
+                .as_ref()
+                .filter(|_| !self.ui.backdrop.capturing),
+            self.modal_progress(),
+        );
+
+        let modal_overlay = stack![
+            edit_dialog_overlay,
+            exit_dialog_overlay,
+            delete_dialog_overlay,
+            crash_dialog_overlay,
+            open_link_overlay,
+            publish_overlay,
+            health_overlay,
+            lyrics_share_overlay,
+            song_info_overlay,
+            export_overlay,
+            cover_viewer_overlay,
+            update_prompt_overlay,
+            changelog_overlay,
+            login_popup_overlay,
+        ]
+        .width(Fill)
+        .height(Fill);
+
+        // Player context menu on the frosted backdrop, held back from the
+        // frame it is captured from like the login popup
+        let player_menu_overlay: Element<'_, Message> =
+            if self.ui.player_menu_open && !self.ui.backdrop.capturing {
+                components::player_menu::view(self.library.stop_after_current, self.core.locale)
+            } else {
+                Space::new().width(0).height(0).into()
+            };
+
         // Always use consistent stack structure to preserve scroll position
         stack![
             main_layout,
             lyrics_overlay,
             toast_overlay,
-            edit_dialog_overlay,
-            exit_dialog_overlay,
-            delete_dialog_overlay,
-            login_popup_overlay,
+            frosted_overlay,
+            modal_overlay,
+            player_menu_overlay,
+            flight_overlay,
+            memory_overlay,
         ]
         .width(Fill)
         .height(Fill)
         .into()
     }
+
+    /// The lyrics page popped out into its own window
+    fn lyrics_window_view(&self, fullscreen: bool) -> Element<'_, Message> {
+        match &self.library.current_song {
+            Some(song) => {
+                self.lyrics_page(song, 1.0, pages::lyrics::LyricsHost::Window { fullscreen })
+            }
+            None => container(Space::new())
+                .width(Fill)
+                .height(Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(iced::Color::BLACK)),
+                    ..Default::default()
+                })
+                .into(),
+        }
+    }
+
+    /// The lyrics page for `song`, in the main window or its own
+    fn lyrics_page<'a>(
+        &'a self,
+        song: &'a DbSong,
+        animation_progress: f32,
+        host: pages::lyrics::LyricsHost,
+    ) -> Element<'a, Message> {
+        // Get playback info - same logic as player bar for consistency
+        let (is_playing, position, duration) = if let Some(player) = &self.core.audio {
+            let info = player.get_info();
+            if info.duration.as_secs_f32() > 0.0 {
+                // Player has loaded a file
+                (
+                    player.is_playing(),
+                    player.heard_position().as_secs_f32() / info.duration.as_secs_f32().max(1.0),
+                    info.duration.as_secs_f32(),
+                )
+            } else {
+                // Player exists but no file loaded yet (e.g., NCM song still resolving)
+                // Use saved state for display
+                let saved_pos = self
+                    .library
+                    .playback_state
+                    .as_ref()
+                    .map(|s| s.position_secs as f32)
+                    .unwrap_or(0.0);
+                let song_duration = song.duration_secs.max(1) as f32;
+                (false, saved_pos / song_duration, song_duration)
+            }
+        } else {
+            // No player - use saved state
+            let saved_pos = self
+                .library
+                .playback_state
+                .as_ref()
+                .map(|s| s.position_secs as f32)
+                .unwrap_or(0.0);
+            let song_duration = song.duration_secs.max(1) as f32;
+            (false, saved_pos / song_duration, song_duration)
+        };
+
+        // Use preview position while seeking, otherwise use actual position
+        let display_position = if self.ui.seek_preview_position.is_some() {
+            self.ui.seek_preview_position.unwrap()
+        } else {
+            position
+        };
+
+        // Calculate current lyric line based on playback position
+        let position_ms = (position * duration * 1000.0) as u64;
+        let current_line = pages::find_current_line(&self.ui.lyrics.lines, position_ms);
+
+        pages::lyrics::view(
+            song,
+            self.ui.lyrics.cover_for(song),
+            is_playing,
+            display_position,
+            duration,
+            self.ui.lyrics.cached_engine_lines.as_ref(), // Use cached engine lines (Rc)
+            current_line,
+            self.core.settings.play_mode,
+            animation_progress,
+            &self.ui.lyrics.bg_colors,
+            &self.ui.lyrics.bg_shader,
+            &self.ui.lyrics.textured_bg_shader,
+            self.core.settings.display.lyrics_background,
+            self.ui.lyrics.engine.as_ref(),
+            self.core.settings.display.power_saving_mode,
+            match self.ncm_id_of(song) {
+                Some(_) => pages::lyrics::SongLink::Ncm,
+                None if self.library.ncm_matching == Some(song.id) => {
+                    pages::lyrics::SongLink::Matching
+                }
+                None => pages::lyrics::SongLink::Unlinked,
+            },
+            self.is_favorite(song.id),
+            self.core.audio.as_ref().and_then(|p| p.buffer_progress()),
+            self.is_fm_mode(),
+            self.ui.lyrics.sub_lines,
+            pages::available_sub_lines(&self.ui.lyrics.lines),
+            self.seek_hover_preview(duration),
+            if !self.ui.lyrics.credits_visible || self.ncm_id_of(song).is_none() {
+                pages::lyrics::CreditsView::Hidden
+            } else {
+                match &self.ui.lyrics.credits {
+                    Some((id, Some(credits))) if *id == song.id => {
+                        pages::lyrics::CreditsView::Loaded(credits)
+                    }
+                    Some((id, None)) if *id == song.id => pages::lyrics::CreditsView::Unavailable,
+                    _ => pages::lyrics::CreditsView::Loading,
+                }
+            },
+            self.ui
+                .lyrics
+                .shareable
+                .filter(|(id, _)| *id == song.id)
+                .map(|(_, edited)| edited && self.core.settings.display.lyrics_share.offer),
+            self.ui.lyrics.savable == Some(song.id),
+            self.lyrics_scroll_resume_in(),
+            host,
+            self.core.locale,
+        )
+    }
 }
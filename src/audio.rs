@@ -3,11 +3,13 @@
 //! This module provides audio playback with real-time processing:
 //! - `AudioHandle`: Non-blocking audio control from UI thread
 //! - `AudioPlayer`: Playback control
-//! - `AudioProcessingChain`: Unified audio processing (preamp, EQ, analyzer)
+//! - `AudioProcessingChain`: Unified audio processing (preamp, EQ, DSP stages, analyzer)
+//! - `crossfade`: Overlapping the end of one track with the start of the next
 //! - `AudioAnalysisData`: Real-time visualization data
 //! - `streaming`: Streaming buffer and download utilities
 //! - `events`: Commands and events for audio thread communication
 //! - `thread`: Audio thread spawning and management
+//! - `ui_sounds`: Synthesized feedback sounds mixed next to the track
 //!
 //! ## Architecture
 //! ```text
@@ -18,6 +20,8 @@
 
 pub mod analyzer;
 pub mod chain;
+mod crossfade;
+mod dsp;
 mod equalizer;
 pub mod events;
 mod fade;
@@ -25,11 +29,13 @@ mod handle;
 mod player;
 pub mod streaming;
 pub mod thread;
+mod ui_sounds;
 
 pub use analyzer::AudioAnalysisData;
-pub use chain::AudioProcessingChain;
+pub use chain::{AudioProcessingChain, SampleProcessor};
 pub use events::AudioEvent;
 pub use handle::AudioHandle;
-pub use player::{PlaybackInfo, PlaybackStatus, get_audio_devices};
+pub use player::{AudioDevice, PlaybackInfo, PlaybackStatus, get_audio_devices};
 pub use streaming::{SharedBuffer, StreamingBuffer};
 pub use thread::spawn_audio_thread;
+pub use ui_sounds::UiSound;
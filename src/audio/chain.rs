@@ -3,17 +3,40 @@
 //! Unified audio processing pipeline that combines:
 //! - Preamp (gain control before EQ)
 //! - 10-band parametric equalizer
+//! - Headphone DSP stages (crossfeed, bass boost, night mode)
+//! - External sample processors (plugin DSP)
 //! - Fade envelope
 //! - Real-time audio analyzer for visualization
 //!
 
 use rodio::Source;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 
 use super::analyzer::{AnalyzingSource, AudioAnalysisData};
+use super::dsp::{Dsp, DspParams};
 use super::equalizer::{Equalizer, EqualizerParams};
 use super::fade::{FadeControl, FadeEnvelope};
 
+/// Block-based processor inserted into the chain after the DSP stages
+///
+/// Implementations must not block: they run on the audio thread.
+pub trait SampleProcessor: Send + Sync {
+    /// Process interleaved samples in place
+    fn process(&self, samples: &mut [f32], channels: u16, sample_rate: u32);
+}
+
+/// Number of frames buffered per processor call
+const PROCESSOR_BLOCK_FRAMES: usize = 1024;
+
+/// Shared list of external sample processors
+#[derive(Clone, Default)]
+struct ProcessorSlot {
+    processors: Arc<RwLock<Vec<Arc<dyn SampleProcessor>>>>,
+    /// Cheap per-sample check so the chain stays pass-through when empty
+    active: Arc<AtomicBool>,
+}
+
 /// Shared audio processing chain parameters
 ///
 /// This struct holds all audio processing parameters and can be cloned
@@ -24,10 +47,14 @@ pub struct AudioProcessingChain {
     inner: Arc<RwLock<ChainInner>>,
     /// Equalizer parameters (has its own Arc<RwLock>)
     eq_params: EqualizerParams,
+    /// Which DSP stages are on
+    dsp_params: DspParams,
     /// Fade control for smooth volume transitions
     fade_control: FadeControl,
     /// Audio analysis data for visualization
     analysis: AudioAnalysisData,
+    /// External processors (plugin DSP)
+    processors: ProcessorSlot,
 }
 
 struct ChainInner {
@@ -52,8 +79,10 @@ impl AudioProcessingChain {
         Self {
             inner: Arc::new(RwLock::new(ChainInner::default())),
             eq_params: EqualizerParams::new(44100),
+            dsp_params: DspParams::default(),
             fade_control: FadeControl::new(1.0),
             analysis: AudioAnalysisData::new(),
+            processors: ProcessorSlot::default(),
         }
     }
     pub fn fade_to(&self, volume: f32, duration: std::time::Duration) {
@@ -90,6 +119,38 @@ impl AudioProcessingChain {
         self.eq_params.set_gains(gains);
     }
 
+    // ========================================================================
+    // DSP stages
+    // ========================================================================
+
+    /// Enable or disable headphone crossfeed
+    pub fn set_crossfeed(&self, enabled: bool) {
+        self.dsp_params.set_crossfeed(enabled);
+    }
+
+    /// Enable or disable the bass boost shelf
+    pub fn set_bass_boost(&self, enabled: bool) {
+        self.dsp_params.set_bass_boost(enabled);
+    }
+
+    /// Enable or disable the night mode compressor
+    pub fn set_night_mode(&self, enabled: bool) {
+        self.dsp_params.set_night_mode(enabled);
+    }
+
+    // ========================================================================
+    // External processors
+    // ========================================================================
+
+    /// Replace the external sample processors (applied in order after the DSP stages)
+    pub fn set_sample_processors(&self, processors: Vec<Arc<dyn SampleProcessor>>) {
+        let active = !processors.is_empty();
+        if let Ok(mut slot) = self.processors.processors.write() {
+            *slot = processors;
+        }
+        self.processors.active.store(active, Ordering::Relaxed);
+    }
+
     // ========================================================================
     // Analysis data access
     // ========================================================================
@@ -133,7 +194,10 @@ impl AudioProcessingChain {
     /// Processing order:
     /// 1. Preamp (gain adjustment)
     /// 2. Equalizer (10-band parametric EQ)
-    /// 3. Analyzer (for visualization, doesn't modify audio)
+    /// 3. DSP stages (crossfeed, bass boost, night mode)
+    /// 4. External processors (plugin DSP)
+    /// 5. Fade envelope
+    /// 6. Analyzer (for visualization, doesn't modify audio)
     pub fn apply<S>(&self, source: S) -> ProcessedSource<S>
     where
         S: Source<Item = f32>,
@@ -159,7 +223,7 @@ where
     S: Source<Item = f32>,
 {
     /// Inner source with full processing chain applied
-    inner: AnalyzingSource<FadeEnvelope<Equalizer<PreampSource<S>>>>,
+    inner: AnalyzingSource<FadeEnvelope<ProcessorSource<Dsp<Equalizer<PreampSource<S>>>>>>,
 }
 
 impl<S> ProcessedSource<S>
@@ -167,10 +231,12 @@ where
     S: Source<Item = f32>,
 {
     fn new(source: S, chain: AudioProcessingChain) -> Self {
-        // Build processing chain: Source -> Preamp -> EQ -> Fade -> Analyzer
+        // Build processing chain: Source -> Preamp -> EQ -> DSP -> Processors -> Fade -> Analyzer
         let preamp_source = PreampSource::new(source, chain.inner.clone());
         let eq_source = Equalizer::new(preamp_source, chain.eq_params.clone());
-        let fade_source = FadeEnvelope::new(eq_source, chain.fade_control.clone());
+        let dsp_source = Dsp::new(eq_source, chain.dsp_params.clone());
+        let processed = ProcessorSource::new(dsp_source, chain.processors.clone());
+        let fade_source = FadeEnvelope::new(processed, chain.fade_control.clone());
         let analyzed = AnalyzingSource::new(fade_source, chain.analysis.clone());
 
         Self { inner: analyzed }
@@ -296,6 +362,130 @@ where
     }
 }
 
+/// Source wrapper that runs external processors over fixed-size blocks
+struct ProcessorSource<S>
+where
+    S: Source<Item = f32>,
+{
+    source: S,
+    slot: ProcessorSlot,
+    block: Vec<f32>,
+    pos: usize,
+    /// Format of the buffered block, which the source may have moved past
+    block_channels: u16,
+    block_sample_rate: u32,
+}
+
+impl<S> ProcessorSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn new(source: S, slot: ProcessorSlot) -> Self {
+        Self {
+            source,
+            slot,
+            block: Vec::new(),
+            pos: 0,
+            block_channels: 1,
+            block_sample_rate: 0,
+        }
+    }
+
+    /// Samples of the current block not handed on yet
+    fn buffered(&self) -> usize {
+        self.block.len() - self.pos
+    }
+
+    /// Pull the next block from the source and run all processors over it
+    ///
+    /// A block ends where the source's span does, so it never mixes formats.
+    fn fill_block(&mut self) {
+        let channels = self.source.channels();
+        let sample_rate = self.source.sample_rate();
+        let mut block_len = PROCESSOR_BLOCK_FRAMES * channels.max(1) as usize;
+        if let Some(span_len) = self.source.current_span_len()
+            && span_len > 0
+        {
+            block_len = block_len.min(span_len);
+        }
+
+        self.block.clear();
+        self.block.extend(self.source.by_ref().take(block_len));
+        self.pos = 0;
+        self.block_channels = channels;
+        self.block_sample_rate = sample_rate;
+
+        if let Ok(processors) = self.slot.processors.read() {
+            for processor in processors.iter() {
+                processor.process(&mut self.block, channels, sample_rate);
+            }
+        }
+    }
+}
+
+impl<S> Iterator for ProcessorSource<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.block.len() {
+            if !self.slot.active.load(Ordering::Relaxed) {
+                return self.source.next();
+            }
+            self.fill_block();
+        }
+
+        let sample = self.block.get(self.pos).copied()?;
+        self.pos += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buffered();
+        let (lower, upper) = self.source.size_hint();
+        (lower + buffered, upper.map(|u| u + buffered))
+    }
+}
+
+impl<S> Source for ProcessorSource<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_span_len(&self) -> Option<usize> {
+        match self.buffered() {
+            0 => self.source.current_span_len(),
+            buffered => Some(buffered),
+        }
+    }
+
+    fn channels(&self) -> u16 {
+        match self.buffered() {
+            0 => self.source.channels(),
+            _ => self.block_channels,
+        }
+    }
+
+    fn sample_rate(&self) -> u32 {
+        match self.buffered() {
+            0 => self.source.sample_rate(),
+            _ => self.block_sample_rate,
+        }
+    }
+
+    fn total_duration(&self) -> Option<std::time::Duration> {
+        self.source.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: std::time::Duration) -> Result<(), rodio::source::SeekError> {
+        // Drop samples buffered from before the seek point
+        self.block.clear();
+        self.pos = 0;
+        self.source.try_seek(pos)
+    }
+}
+
 /// Soft clipping function to prevent harsh digital clipping
 fn soft_clip(x: f32) -> f32 {
     if x.abs() < 0.9 {
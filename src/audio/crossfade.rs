@@ -0,0 +1,80 @@
+//! Crossfade between consecutive tracks
+//!
+//! The outgoing track keeps its own sink while the next one starts, and the
+//! two sink volumes are moved along an equal-power curve on every audio tick
+//! so the perceived loudness stays level through the overlap.
+
+use std::f32::consts::FRAC_PI_2;
+use std::time::{Duration, Instant};
+
+use rodio::Sink;
+
+/// An outgoing track fading out under the current one
+pub struct Crossfade {
+    outgoing: Sink,
+    started: Instant,
+    duration: Duration,
+}
+
+impl Crossfade {
+    pub fn new(outgoing: Sink, duration: Duration) -> Self {
+        Self {
+            outgoing,
+            started: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Set both sinks for the current moment; false once the overlap is over
+    ///
+    /// `volume` is the level the incoming track settles at.
+    pub fn step(&self, incoming: Option<&Sink>, volume: f32) -> bool {
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()
+        };
+        let (out_gain, in_gain) = gains(progress);
+
+        if let Some(sink) = incoming {
+            sink.set_volume(volume * in_gain);
+        }
+        if progress >= 1.0 || self.outgoing.empty() {
+            self.outgoing.stop();
+            return false;
+        }
+        self.outgoing.set_volume(volume * out_gain);
+        true
+    }
+}
+
+/// Outgoing and incoming gains at `progress` (0.0 to 1.0) through the overlap
+pub fn gains(progress: f32) -> (f32, f32) {
+    let angle = progress.clamp(0.0, 1.0) * FRAC_PI_2;
+    (angle.cos(), angle.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_with_outgoing_and_ends_with_incoming() {
+        let (out_gain, in_gain) = gains(0.0);
+        assert!((out_gain - 1.0).abs() < 1e-6 && in_gain.abs() < 1e-6);
+
+        let (out_gain, in_gain) = gains(1.0);
+        assert!(out_gain.abs() < 1e-6 && (in_gain - 1.0).abs() < 1e-6);
+
+        assert_eq!(gains(-0.5), gains(0.0));
+        assert_eq!(gains(1.5), gains(1.0));
+    }
+
+    #[test]
+    fn keeps_power_constant() {
+        for step in 0..=20 {
+            let (out_gain, in_gain) = gains(step as f32 / 20.0);
+            assert!((out_gain * out_gain + in_gain * in_gain - 1.0).abs() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Headphone and listening DSP stages
+//!
+//! Optional stages that run after the equalizer:
+//! - Crossfeed: blends a low-passed copy of each stereo channel into the other,
+//!   so hard-panned mixes sound less tiring on headphones
+//! - Bass boost: low shelf below 100Hz
+//! - Night mode: compressor that narrows the gap between loud and quiet parts
+
+use rodio::Source;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::equalizer::{BiquadCoeffs, BiquadState, calc_low_shelf, soft_clip};
+
+/// Crossfeed low-pass corner in Hz (head shadowing above this)
+const CROSSFEED_CUTOFF_HZ: f32 = 700.0;
+/// Level of the opposite channel blended in (about -7dB)
+const CROSSFEED_LEVEL: f32 = 0.45;
+
+/// Bass boost shelf corner in Hz
+const BASS_BOOST_HZ: f32 = 100.0;
+/// Bass boost shelf gain in dB
+const BASS_BOOST_DB: f32 = 6.0;
+
+/// Night mode compressor threshold in dB
+const NIGHT_THRESHOLD_DB: f32 = -24.0;
+/// Night mode compression ratio
+const NIGHT_RATIO: f32 = 4.0;
+/// Night mode makeup gain in dB, lifting the quiet parts
+const NIGHT_MAKEUP_DB: f32 = 8.0;
+/// Night mode envelope attack in seconds
+const NIGHT_ATTACK_SECS: f32 = 0.005;
+/// Night mode envelope release in seconds
+const NIGHT_RELEASE_SECS: f32 = 0.2;
+
+/// Which DSP stages are on, shared with the audio thread
+#[derive(Clone, Default)]
+pub struct DspParams {
+    crossfeed: Arc<AtomicBool>,
+    bass_boost: Arc<AtomicBool>,
+    night_mode: Arc<AtomicBool>,
+}
+
+impl DspParams {
+    pub fn set_crossfeed(&self, enabled: bool) {
+        self.crossfeed.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn set_bass_boost(&self, enabled: bool) {
+        self.bass_boost.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn set_night_mode(&self, enabled: bool) {
+        self.night_mode.store(enabled, Ordering::Relaxed);
+    }
+
+    fn stages(&self) -> (bool, bool, bool) {
+        (
+            self.crossfeed.load(Ordering::Relaxed),
+            self.bass_boost.load(Ordering::Relaxed),
+            self.night_mode.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Filter coefficients derived from the sample rate
+#[derive(Clone, Copy, Default)]
+struct Coeffs {
+    sample_rate: u32,
+    /// One-pole low-pass factor for crossfeed
+    crossfeed_alpha: f32,
+    bass_shelf: BiquadCoeffs,
+    attack: f32,
+    release: f32,
+}
+
+impl Coeffs {
+    fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate.max(1) as f32;
+        let smoothing = |secs: f32| (-1.0 / (secs * rate)).exp();
+        Self {
+            sample_rate,
+            crossfeed_alpha: 1.0 - (-2.0 * std::f32::consts::PI * CROSSFEED_CUTOFF_HZ / rate).exp(),
+            bass_shelf: calc_low_shelf(BASS_BOOST_HZ, BASS_BOOST_DB, rate),
+            attack: smoothing(NIGHT_ATTACK_SECS),
+            release: smoothing(NIGHT_RELEASE_SECS),
+        }
+    }
+}
+
+/// Source wrapper running the enabled DSP stages
+///
+/// Works a frame at a time, since crossfeed needs both channels together.
+pub struct Dsp<S>
+where
+    S: Source<Item = f32>,
+{
+    source: S,
+    params: DspParams,
+    coeffs: Coeffs,
+    /// Processed samples of the current frame
+    frame: Vec<f32>,
+    pos: usize,
+    /// Channel of the next sample while passing through, so processing
+    /// only starts on a frame boundary
+    channel: usize,
+    /// Crossfeed low-pass state per stereo channel
+    crossfeed_lp: [f32; 2],
+    bass_states: [BiquadState; 2],
+    /// Night mode level envelope (linear peak)
+    envelope: f32,
+}
+
+impl<S> Dsp<S>
+where
+    S: Source<Item = f32>,
+{
+    pub fn new(source: S, params: DspParams) -> Self {
+        let coeffs = Coeffs::new(source.sample_rate());
+        Self {
+            source,
+            params,
+            coeffs,
+            frame: Vec::with_capacity(2),
+            pos: 0,
+            channel: 0,
+            crossfeed_lp: [0.0; 2],
+            bass_states: [BiquadState::default(); 2],
+            envelope: 0.0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.frame.clear();
+        self.pos = 0;
+        self.channel = 0;
+        self.crossfeed_lp = [0.0; 2];
+        for state in &mut self.bass_states {
+            state.reset();
+        }
+        self.envelope = 0.0;
+    }
+
+    /// Pull and process the next frame, false at the end of the source
+    fn fill_frame(&mut self, stages: (bool, bool, bool)) -> bool {
+        let (crossfeed, bass_boost, night_mode) = stages;
+        let channels = self.source.channels().max(1) as usize;
+
+        self.frame.clear();
+        self.frame.extend(self.source.by_ref().take(channels));
+        self.pos = 0;
+        if self.frame.is_empty() {
+            return false;
+        }
+
+        let sample_rate = self.source.sample_rate();
+        if sample_rate != self.coeffs.sample_rate {
+            self.coeffs = Coeffs::new(sample_rate);
+        }
+
+        if crossfeed && self.frame.len() == 2 {
+            let alpha = self.coeffs.crossfeed_alpha;
+            for (lp, &sample) in self.crossfeed_lp.iter_mut().zip(self.frame.iter()) {
+                *lp += alpha * (sample - *lp);
+            }
+            let (left, right) = (self.frame[0], self.frame[1]);
+            let norm = 1.0 / (1.0 + CROSSFEED_LEVEL);
+            self.frame[0] = (left + CROSSFEED_LEVEL * self.crossfeed_lp[1]) * norm;
+            self.frame[1] = (right + CROSSFEED_LEVEL * self.crossfeed_lp[0]) * norm;
+        }
+
+        if bass_boost {
+            for (i, sample) in self.frame.iter_mut().enumerate() {
+                *sample = self.bass_states[i.min(1)].process(&self.coeffs.bass_shelf, *sample);
+            }
+        }
+
+        if night_mode {
+            // Linked detection so the stereo image doesn't wander
+            let peak = self.frame.iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+            let smoothing = if peak > self.envelope {
+                self.coeffs.attack
+            } else {
+                self.coeffs.release
+            };
+            self.envelope = peak + smoothing * (self.envelope - peak);
+
+            let gain = 10.0_f32.powf(night_gain_db(self.envelope) / 20.0);
+            for sample in &mut self.frame {
+                *sample *= gain;
+            }
+        }
+
+        if bass_boost || night_mode {
+            for sample in &mut self.frame {
+                *sample = soft_clip(*sample);
+            }
+        }
+        true
+    }
+}
+
+/// Night mode gain in dB for an envelope level, makeup included
+fn night_gain_db(envelope: f32) -> f32 {
+    let level_db = 20.0 * envelope.max(1e-6).log10();
+    let reduction = if level_db > NIGHT_THRESHOLD_DB {
+        (NIGHT_THRESHOLD_DB - level_db) * (1.0 - 1.0 / NIGHT_RATIO)
+    } else {
+        0.0
+    };
+    reduction + NIGHT_MAKEUP_DB
+}
+
+impl<S> Iterator for Dsp<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.frame.len() {
+            let stages = self.params.stages();
+            if stages == (false, false, false) || self.channel != 0 {
+                let channels = self.source.channels().max(1) as usize;
+                self.channel = (self.channel + 1) % channels;
+                return self.source.next();
+            }
+            if !self.fill_frame(stages) {
+                return None;
+            }
+        }
+
+        let sample = self.frame[self.pos];
+        self.pos += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.frame.len() - self.pos;
+        let (lower, upper) = self.source.size_hint();
+        (lower + buffered, upper.map(|u| u + buffered))
+    }
+}
+
+impl<S> Source for Dsp<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_span_len(&self) -> Option<usize> {
+        self.source.current_span_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.source.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.source.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<std::time::Duration> {
+        self.source.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: std::time::Duration) -> Result<(), rodio::source::SeekError> {
+        // Filter memory from before the seek point would click
+        self.reset();
+        self.source.try_seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    fn run(params: &DspParams, channels: u16, samples: Vec<f32>) -> Vec<f32> {
+        Dsp::new(SamplesBuffer::new(channels, 44100, samples), params.clone()).collect()
+    }
+
+    #[test]
+    fn passes_through_when_off() {
+        let samples: Vec<f32> = (0..64).map(|i| (i as f32 * 0.1).sin() * 0.5).collect();
+        assert_eq!(run(&DspParams::default(), 2, samples.clone()), samples);
+    }
+
+    #[test]
+    fn crossfeed_bleeds_into_silent_channel() {
+        let params = DspParams::default();
+        params.set_crossfeed(true);
+        // Constant signal in the left channel only
+        let samples: Vec<f32> = (0..4410).flat_map(|_| [0.5, 0.0]).collect();
+
+        let out = run(&params, 2, samples);
+        let (left, right) = (out[out.len() - 2], out[out.len() - 1]);
+        assert!(right > 0.1 && right < left);
+    }
+
+    #[test]
+    fn night_mode_narrows_dynamic_range() {
+        let params = DspParams::default();
+        params.set_night_mode(true);
+        let tone =
+            |amp: f32| -> Vec<f32> { (0..8820).map(|i| (i as f32 * 0.05).sin() * amp).collect() };
+        let peak = |samples: &[f32]| samples[4410..].iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+
+        let loud = peak(&run(&params, 1, tone(0.9)));
+        let quiet = peak(&run(&params, 1, tone(0.01)));
+        assert!(loud / quiet < 0.9 / 0.01 / 4.0);
+        assert!(quiet > 0.01);
+    }
+}
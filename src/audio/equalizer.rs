@@ -13,7 +13,7 @@ pub const EQ_FREQUENCIES: [f32; 10] = [
 
 /// Biquad filter coefficients
 #[derive(Clone, Copy, Default)]
-struct BiquadCoeffs {
+pub(super) struct BiquadCoeffs {
     b0: f32,
     b1: f32,
     b2: f32,
@@ -23,7 +23,7 @@ struct BiquadCoeffs {
 
 /// Biquad filter state for one channel
 #[derive(Clone, Copy, Default)]
-struct BiquadState {
+pub(super) struct BiquadState {
     x1: f32,
     x2: f32,
     y1: f32,
@@ -31,7 +31,7 @@ struct BiquadState {
 }
 
 impl BiquadState {
-    fn process(&mut self, coeffs: &BiquadCoeffs, input: f32) -> f32 {
+    pub(super) fn process(&mut self, coeffs: &BiquadCoeffs, input: f32) -> f32 {
         let output = coeffs.b0 * input + coeffs.b1 * self.x1 + coeffs.b2 * self.x2
             - coeffs.a1 * self.y1
             - coeffs.a2 * self.y2;
@@ -44,7 +44,7 @@ impl BiquadState {
         output
     }
 
-    fn reset(&mut self) {
+    pub(super) fn reset(&mut self) {
         self.x1 = 0.0;
         self.x2 = 0.0;
         self.y1 = 0.0;
@@ -92,6 +92,34 @@ fn calc_peaking_eq(freq: f32, gain_db: f32, sample_rate: f32, q: f32) -> BiquadC
     }
 }
 
+/// Calculate low-shelf filter coefficients (shelf slope 1)
+/// gain_db: gain below the corner frequency in decibels
+/// freq: corner frequency in Hz
+/// sample_rate: audio sample rate
+pub(super) fn calc_low_shelf(freq: f32, gain_db: f32, sample_rate: f32) -> BiquadCoeffs {
+    let a = 10.0_f32.powf(gain_db / 40.0);
+    let omega = 2.0 * std::f32::consts::PI * freq / sample_rate;
+    let sin_omega = omega.sin();
+    let cos_omega = omega.cos();
+    let alpha = sin_omega / 2.0 * std::f32::consts::SQRT_2;
+    let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+    let b0 = a * ((a + 1.0) - (a - 1.0) * cos_omega + sqrt_a_alpha);
+    let b1 = 2.0 * a * ((a - 1.0) - (a + 1.0) * cos_omega);
+    let b2 = a * ((a + 1.0) - (a - 1.0) * cos_omega - sqrt_a_alpha);
+    let a0 = (a + 1.0) + (a - 1.0) * cos_omega + sqrt_a_alpha;
+    let a1 = -2.0 * ((a - 1.0) + (a + 1.0) * cos_omega);
+    let a2 = (a + 1.0) + (a - 1.0) * cos_omega - sqrt_a_alpha;
+
+    BiquadCoeffs {
+        b0: b0 / a0,
+        b1: b1 / a0,
+        b2: b2 / a0,
+        a1: a1 / a0,
+        a2: a2 / a0,
+    }
+}
+
 /// Shared equalizer parameters that can be updated in real-time
 #[derive(Clone)]
 pub struct EqualizerParams {
@@ -343,7 +371,7 @@ where
 }
 
 /// Soft clipping function to prevent harsh digital clipping
-fn soft_clip(x: f32) -> f32 {
+pub(super) fn soft_clip(x: f32) -> f32 {
     if x.abs() < 0.9 {
         x
     } else if x > 0.0 {
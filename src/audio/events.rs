@@ -22,6 +22,7 @@ use parking_lot::RwLock;
 
 use super::PlaybackStatus;
 use super::streaming::StreamingBuffer;
+use super::ui_sounds::UiSound;
 
 // ============ Commands (UI -> Audio Thread) ============
 
@@ -46,10 +47,19 @@ pub enum AudioCommand {
     Stop,
     /// Seek to position
     Seek { position: Duration },
+    /// Seek relative to the position at the time the command runs
+    /// (negative offsets go back)
+    SeekBy { offset_ms: i64 },
     /// Set volume (0.0 - 1.0)
     SetVolume { volume: f32 },
     /// Set track gain for normalization
     SetTrackGain { gain: f32 },
+    /// Play a short UI feedback sound alongside the track
+    PlayUiSound { sound: UiSound },
+    /// Overlap the next track started with the current one
+    CrossfadeNext { duration: Duration },
+    /// Switch the output to hi-res tracks' own sample rate instead of resampling
+    SetMatchSampleRate { enabled: bool },
     /// Create preload sink for a local file (async, returns via PreloadReady event)
     CreatePreloadSink { path: PathBuf, request_id: u64 },
     /// Create preload sink for streaming (async, returns via PreloadReady event)
@@ -94,12 +104,27 @@ impl std::fmt::Debug for AudioCommand {
             Self::Resume { fade_in } => f.debug_struct("Resume").field("fade_in", fade_in).finish(),
             Self::Stop => write!(f, "Stop"),
             Self::Seek { position } => f.debug_struct("Seek").field("position", position).finish(),
+            Self::SeekBy { offset_ms } => f
+                .debug_struct("SeekBy")
+                .field("offset_ms", offset_ms)
+                .finish(),
             Self::SetVolume { volume } => {
                 f.debug_struct("SetVolume").field("volume", volume).finish()
             }
             Self::SetTrackGain { gain } => {
                 f.debug_struct("SetTrackGain").field("gain", gain).finish()
             }
+            Self::PlayUiSound { sound } => {
+                f.debug_struct("PlayUiSound").field("sound", sound).finish()
+            }
+            Self::CrossfadeNext { duration } => f
+                .debug_struct("CrossfadeNext")
+                .field("duration", duration)
+                .finish(),
+            Self::SetMatchSampleRate { enabled } => f
+                .debug_struct("SetMatchSampleRate")
+                .field("enabled", enabled)
+                .finish(),
             Self::CreatePreloadSink { path, request_id } => f
                 .debug_struct("CreatePreloadSink")
                 .field("path", path)
@@ -182,6 +207,8 @@ pub enum AudioEvent {
     },
     /// Preload failed
     PreloadFailed { request_id: u64, error: String },
+    /// Preloaded sinks discarded because the output stream was reopened
+    PreloadsDropped { request_ids: Vec<u64> },
     /// Device switched successfully
     DeviceSwitched {
         /// State to restore: (path, position, was_playing)
@@ -225,6 +252,10 @@ struct PlaybackStateInner {
     /// and enter Buffering state. When buffer is ready, exit_buffering()
     /// checks this field and executes the seek before resuming playback.
     pub pending_seek_target: Option<Duration>,
+    /// Sample rate of the track playing, 0 when none
+    pub source_rate: u32,
+    /// Sample rate the output device runs at
+    pub output_rate: u32,
 }
 
 impl Default for PlaybackStateInner {
@@ -239,6 +270,8 @@ impl Default for PlaybackStateInner {
             buffered_bytes: 0,
             total_bytes: 0,
             pending_seek_target: None,
+            source_rate: 0,
+            output_rate: 0,
         }
     }
 }
@@ -352,6 +385,12 @@ impl SharedPlaybackState {
         self.inner.read().current_path.clone()
     }
 
+    /// Get the track's and the output's sample rates
+    pub fn sample_rates(&self) -> (u32, u32) {
+        let inner = self.inner.read();
+        (inner.source_rate, inner.output_rate)
+    }
+
     // ---- Update methods (called by audio thread) ----
     /// Update status
     pub fn set_status(&self, status: PlaybackStatus) {
@@ -379,6 +418,13 @@ impl SharedPlaybackState {
         self.inner.write().current_path = path;
     }
 
+    /// Update the track's and the output's sample rates
+    pub fn set_sample_rates(&self, source_rate: u32, output_rate: u32) {
+        let mut inner = self.inner.write();
+        inner.source_rate = source_rate;
+        inner.output_rate = output_rate;
+    }
+
     /// Update buffer bytes info
     pub fn set_buffer_bytes(&self, buffered: u64, total: u64) {
         let mut inner = self.inner.write();
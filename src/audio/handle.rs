@@ -5,12 +5,14 @@
 //! State is read from `SharedPlaybackState` without blocking.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use super::PlaybackInfo;
 use super::events::{AudioCommand, AudioCommandSender, SharedPlaybackState};
 use super::streaming::StreamingBuffer;
+use super::ui_sounds::UiSound;
 
 /// Counter for generating unique preload request IDs
 static PRELOAD_REQUEST_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -26,6 +28,8 @@ static PRELOAD_REQUEST_COUNTER: AtomicU64 = AtomicU64::new(0);
 pub struct AudioHandle {
     command_tx: AudioCommandSender,
     state: SharedPlaybackState,
+    /// Delay between samples leaving the app and reaching the ears, in ms
+    output_latency_ms: Arc<AtomicU64>,
 }
 
 impl std::fmt::Debug for AudioHandle {
@@ -39,7 +43,11 @@ impl std::fmt::Debug for AudioHandle {
 impl AudioHandle {
     /// Create a new audio handle
     pub fn new(command_tx: AudioCommandSender, state: SharedPlaybackState) -> Self {
-        Self { command_tx, state }
+        Self {
+            command_tx,
+            state,
+            output_latency_ms: Arc::new(AtomicU64::new(0)),
+        }
     }
 
     // ============ Playback Control ============
@@ -120,8 +128,21 @@ impl AudioHandle {
         let _ = self.command_tx.send(AudioCommand::Seek { position });
     }
 
+    /// Seek relative to the current position (negative offsets go back)
+    ///
+    /// The target is worked out on the audio thread from the sink's own
+    /// position, so repeated presses add up exactly instead of starting from
+    /// a position the UI last polled.
+    pub fn seek_by(&self, offset_ms: i64) {
+        let _ = self.command_tx.send(AudioCommand::SeekBy { offset_ms });
+    }
+
     /// Set volume
+    ///
+    /// The shared state volume is updated immediately, so media controls
+    /// report it back to headsets without waiting for the audio thread.
     pub fn set_volume(&self, volume: f32) {
+        self.state.set_volume(volume);
         let _ = self.command_tx.send(AudioCommand::SetVolume { volume });
     }
 
@@ -130,6 +151,20 @@ impl AudioHandle {
         let _ = self.command_tx.send(AudioCommand::SetTrackGain { gain });
     }
 
+    /// Play a UI feedback sound
+    pub fn play_ui_sound(&self, sound: UiSound) {
+        let _ = self.command_tx.send(AudioCommand::PlayUiSound { sound });
+    }
+
+    /// Overlap the next track started with the current one
+    ///
+    /// Send just before the play command for the next track.
+    pub fn crossfade_next(&self, duration: Duration) {
+        let _ = self
+            .command_tx
+            .send(AudioCommand::CrossfadeNext { duration });
+    }
+
     /// Tick handler - checks buffer status and syncs position
     pub fn tick(&self) {
         let _ = self.command_tx.send(AudioCommand::Tick);
@@ -213,6 +248,37 @@ impl AudioHandle {
         self.state.display_position()
     }
 
+    /// Set how long the output device takes to play what it is given
+    pub fn set_output_latency(&self, latency: Duration) {
+        self.output_latency_ms
+            .store(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Switch the output to hi-res tracks' own sample rate instead of resampling
+    pub fn set_match_sample_rate(&self, enabled: bool) {
+        let _ = self
+            .command_tx
+            .send(AudioCommand::SetMatchSampleRate { enabled });
+    }
+
+    /// Sample rates of the track playing (0 if none) and of the output
+    pub fn sample_rates(&self) -> (u32, u32) {
+        self.state.sample_rates()
+    }
+
+    /// Position the user is hearing right now
+    ///
+    /// Trails `display_position` by the output latency while playing, so
+    /// lyrics and the seek bar follow the sound rather than the decoder.
+    pub fn heard_position(&self) -> Duration {
+        let position = self.display_position();
+        if !self.is_playing() {
+            return position;
+        }
+        let latency = Duration::from_millis(self.output_latency_ms.load(Ordering::Relaxed));
+        position.saturating_sub(latency)
+    }
+
     /// Check if in loading state (buffering)
     pub fn is_loading(&self) -> bool {
         self.state.is_loading()
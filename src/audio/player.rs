@@ -16,11 +16,16 @@ use rodio::cpal::traits::{DeviceTrait, HostTrait};
 use rodio::{Decoder, OutputStream, OutputStreamBuilder, Sink, Source};
 
 use super::chain::AudioProcessingChain;
+use super::crossfade::Crossfade;
 use super::streaming::StreamingBuffer;
+use super::ui_sounds::UiSound;
 
 /// Cached audio devices to avoid repeated enumeration (which triggers Jack/ALSA warnings)
 static AUDIO_DEVICES_CACHE: OnceLock<Vec<AudioDevice>> = OnceLock::new();
 
+/// Source rates above this count as hi-res for output rate matching
+const HI_RES_RATE: u32 = 48_000;
+
 /// Playback status
 #[derive(Debug, Clone, PartialEq)]
 pub enum PlaybackStatus {
@@ -112,6 +117,21 @@ pub struct AudioPlayer {
     state: Arc<Mutex<PlayerState>>,
     chain: AudioProcessingChain,
     is_streaming: bool,
+    /// Overlap requested for the next track started
+    crossfade_next: Option<Duration>,
+    /// Previous track still fading out under the current one
+    crossfade: Option<Crossfade>,
+    /// Switch the output to hi-res tracks' own rate instead of resampling
+    match_source_rate: bool,
+    /// Rate the device opens at by default
+    device_rate: u32,
+    /// Rate the output stream runs at
+    output_rate: u32,
+    /// Rate of the track playing, 0 when none has been decoded
+    source_rate: u32,
+    /// Bumped whenever the output stream is reopened; sinks made on an
+    /// earlier stream play nowhere
+    stream_generation: u64,
 }
 
 impl AudioPlayer {
@@ -131,6 +151,7 @@ impl AudioPlayer {
             OutputStreamBuilder::open_default_stream()
                 .map_err(|e| format!("Failed to create audio output: {}", e))?
         };
+        let rate = stream.config().sample_rate();
 
         let mut state = PlayerState::default();
         state.device_name = device_name.map(|s| s.to_string());
@@ -142,30 +163,147 @@ impl AudioPlayer {
             state: Arc::new(Mutex::new(state)),
             chain,
             is_streaming: false,
+            crossfade_next: None,
+            crossfade: None,
+            match_source_rate: false,
+            device_rate: rate,
+            output_rate: rate,
+            source_rate: 0,
+            stream_generation: 0,
         })
     }
 
-    /// Create output stream for a specific device by name
-    fn create_stream_for_device(device_name: &str) -> Result<OutputStream, String> {
+    /// Find an output device by name, or the system default
+    fn find_device(device_name: Option<&str>) -> Result<rodio::cpal::Device, String> {
         let host = rodio::cpal::default_host();
 
-        let device = host
-            .output_devices()
-            .map_err(|e| format!("Failed to enumerate devices: {}", e))?
-            .find(|d| d.name().map(|n| n == device_name).unwrap_or(false))
-            .ok_or_else(|| format!("Device not found: {}", device_name))?;
-
-        let config = device
-            .default_output_config()
-            .map_err(|e| format!("Failed to get device config: {}", e))?;
+        match device_name {
+            Some(name) => host
+                .output_devices()
+                .map_err(|e| format!("Failed to enumerate devices: {}", e))?
+                .find(|d| d.name().map(|n| n == name).unwrap_or(false))
+                .ok_or_else(|| format!("Device not found: {}", name)),
+            None => host
+                .default_output_device()
+                .ok_or_else(|| "No default output device".to_string()),
+        }
+    }
 
+    /// Open an output stream on `device` at `sample_rate`
+    fn open_stream(device: rodio::cpal::Device, sample_rate: u32) -> Result<OutputStream, String> {
         OutputStreamBuilder::from_device(device)
             .map_err(|e| format!("Failed to create stream builder: {}", e))?
-            .with_sample_rate(config.sample_rate().0)
+            .with_sample_rate(sample_rate)
             .open_stream()
             .map_err(|e| format!("Failed to open stream: {}", e))
     }
 
+    /// Create output stream for a specific device by name
+    fn create_stream_for_device(device_name: &str) -> Result<OutputStream, String> {
+        let device = Self::find_device(Some(device_name))?;
+
+        let config = device
+            .default_output_config()
+            .map_err(|e| format!("Failed to get device config: {}", e))?;
+
+        Self::open_stream(device, config.sample_rate().0)
+    }
+
+    /// Replace the output stream, dropping every sink playing on the old one
+    fn replace_stream(&mut self, stream: OutputStream) {
+        self.output_rate = stream.config().sample_rate();
+        self._stream = stream;
+        self.stream_generation += 1;
+    }
+
+    /// Turn output rate matching on or off; a switched rate is kept until
+    /// the next track or stop
+    pub fn set_match_source_rate(&mut self, enabled: bool) {
+        self.match_source_rate = enabled;
+    }
+
+    /// Reopen the output at the rate a track decoded at `source_rate` should
+    /// play at: its own for hi-res tracks when matching is on (and the
+    /// device supports it), the device's default otherwise
+    fn match_output_rate(&mut self, source_rate: u32) {
+        self.source_rate = source_rate;
+        let wanted = (self.match_source_rate && source_rate > HI_RES_RATE).then_some(source_rate);
+        // Enumerating devices is slow and noisy, so skip it when nothing changes
+        if wanted.unwrap_or(self.device_rate) == self.output_rate {
+            return;
+        }
+        // The outgoing track still plays on the current stream
+        if self.crossfade.is_some() {
+            return;
+        }
+
+        let device_name = self.state.lock().unwrap().device_name.clone();
+        let device = match Self::find_device(device_name.as_deref()) {
+            Ok(device) => device,
+            Err(e) => {
+                tracing::warn!("Output rate unchanged: {}", e);
+                return;
+            }
+        };
+        let supported = wanted.filter(|rate| {
+            device
+                .supported_output_configs()
+                .map(|mut configs| {
+                    configs.any(|c| (c.min_sample_rate().0..=c.max_sample_rate().0).contains(rate))
+                })
+                .unwrap_or(false)
+        });
+        if wanted.is_some() && supported.is_none() {
+            tracing::info!(
+                "Output device does not support {}Hz, resampling",
+                source_rate
+            );
+        }
+        let rate = supported.unwrap_or(self.device_rate);
+        if rate == self.output_rate {
+            return;
+        }
+
+        match Self::open_stream(device, rate) {
+            Ok(stream) => {
+                tracing::info!("Output rate switched {}Hz -> {}Hz", self.output_rate, rate);
+                self.replace_stream(stream);
+            }
+            Err(e) => tracing::warn!("Failed to switch output rate to {}Hz: {}", rate, e),
+        }
+    }
+
+    /// Put the output back at the device's default rate, once nothing plays
+    pub fn restore_output_rate(&mut self) {
+        if self.current_sink.is_none() {
+            self.match_output_rate(0);
+        }
+    }
+
+    /// Whether a preloaded track could play on the current output without
+    /// wanting another rate
+    fn fits_output_rate(&self, source_rate: u32) -> bool {
+        if !self.match_source_rate {
+            return true;
+        }
+        let wanted = if source_rate > HI_RES_RATE {
+            source_rate
+        } else {
+            self.device_rate
+        };
+        wanted == self.output_rate
+    }
+
+    /// Rate of the track playing (0 if none) and of the output stream
+    pub fn sample_rates(&self) -> (u32, u32) {
+        (self.source_rate, self.output_rate)
+    }
+
+    /// Changes whenever the output stream is reopened
+    pub fn stream_generation(&self) -> u64 {
+        self.stream_generation
+    }
+
     /// Switch to a different audio output device
     pub fn switch_device(
         &mut self,
@@ -192,7 +330,8 @@ impl AudioPlayer {
             state.device_name = device_name.map(|s| s.to_string());
         }
 
-        self._stream = stream;
+        self.device_rate = stream.config().sample_rate();
+        self.replace_stream(stream);
 
         tracing::info!("Switched audio device to: {:?}", device_name);
         Ok(playback_state)
@@ -212,25 +351,64 @@ impl AudioPlayer {
         self.chain.set_fade_volume(1.0);
     }
 
+    /// Overlap the next track started with the current one for `duration`
+    pub fn crossfade_next(&mut self, duration: Duration) {
+        self.crossfade_next = Some(duration);
+    }
+
+    /// Stop the current track, or leave it fading out under the next one
+    /// when a crossfade was requested
+    fn stop_for_next_track(&mut self) {
+        let duration = self.crossfade_next.take();
+        let outgoing = if duration.is_some() && self.is_playing() {
+            self.current_sink.take()
+        } else {
+            None
+        };
+        self.stop();
+        if let (Some(sink), Some(duration)) = (outgoing, duration) {
+            self.crossfade = Some(Crossfade::new(sink, duration));
+        }
+    }
+
+    /// Move an active crossfade along; call on every tick
+    pub fn tick_crossfade(&mut self) {
+        if let Some(crossfade) = &self.crossfade {
+            let volume = self.get_effective_volume();
+            if !crossfade.step(self.current_sink.as_ref(), volume) {
+                self.crossfade = None;
+            }
+        }
+    }
+
+    /// Level a new sink starts at: silent when it crossfades in
+    fn initial_sink_volume(&self) -> f32 {
+        if self.crossfade.is_some() {
+            0.0
+        } else {
+            self.get_effective_volume()
+        }
+    }
+
     /// Play a file with fade in option
     pub fn play_with_fade(&mut self, path: PathBuf, fade_in: bool) -> Result<(), String> {
-        self.stop();
+        self.stop_for_next_track();
         self.prepare_for_new_track();
 
         let file = File::open(&path).map_err(|e| format!("Failed to open file: {}", e))?;
         let reader = BufReader::new(file);
         let source = Decoder::new(reader).map_err(|e| format!("Failed to decode audio: {}", e))?;
         let duration = source.total_duration().unwrap_or(Duration::ZERO);
+        self.match_output_rate(source.sample_rate());
 
         let processed = self.chain.apply(source);
 
         let sink = Sink::connect_new(self._stream.mixer());
         sink.append(processed);
+        sink.set_volume(self.initial_sink_volume());
 
-        let volume = self.get_effective_volume();
-        sink.set_volume(volume);
-
-        if fade_in {
+        // The fade envelope is shared, so it would silence the outgoing track
+        if fade_in && self.crossfade.is_none() {
             self.chain.set_fade_volume(0.0);
             self.chain.fade_to(1.0, Duration::from_millis(300));
         }
@@ -251,12 +429,22 @@ impl AudioPlayer {
     }
 
     /// Create a preload sink for external use (by PreloadManager)
-    /// Returns (Sink, Duration) - sink is paused and ready for playback
-    pub fn create_preload_sink(&self, path: &Path) -> Result<(Sink, Duration), String> {
+    /// Returns (Sink, Duration, source sample rate) - sink is paused and ready for playback
+    ///
+    /// Fails for tracks that should switch the output rate, so they are
+    /// played fresh instead
+    pub fn create_preload_sink(&self, path: &Path) -> Result<(Sink, Duration, u32), String> {
         let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
         let reader = BufReader::new(file);
         let source = Decoder::new(reader).map_err(|e| format!("Failed to decode audio: {}", e))?;
         let duration = source.total_duration().unwrap_or(Duration::ZERO);
+        let source_rate = source.sample_rate();
+        if !self.fits_output_rate(source_rate) {
+            return Err(format!(
+                "Needs the output at another rate than {}Hz",
+                source_rate
+            ));
+        }
 
         let processed = self.chain.apply(source);
 
@@ -265,7 +453,7 @@ impl AudioPlayer {
         sink.set_volume(self.get_effective_volume());
         sink.pause(); // Start paused
 
-        Ok((sink, duration))
+        Ok((sink, duration, source_rate))
     }
 
     /// Create a preload sink from StreamingBuffer for NCM songs
@@ -274,12 +462,12 @@ impl AudioPlayer {
     /// in the background while playback proceeds. The StreamingBuffer's read()
     /// method blocks when data is not yet available.
     ///
-    /// Returns (Sink, Duration) - sink is paused and ready for playback
+    /// Returns (Sink, Duration, source sample rate) - sink is paused and ready for playback
     pub fn create_preload_sink_streaming(
         &self,
         buffer: StreamingBuffer,
         duration: Duration,
-    ) -> Result<(Sink, Duration), String> {
+    ) -> Result<(Sink, Duration, u32), String> {
         // Wait for total_size to be set (from Content-Length header)
         // This is critical for FLAC and other formats that need byte_len for seeking
         let start = std::time::Instant::now();
@@ -325,6 +513,13 @@ impl AudioPlayer {
 
         // Use provided duration since streaming buffer may not know total duration
         let actual_duration = source.total_duration().unwrap_or(duration);
+        let source_rate = source.sample_rate();
+        if !self.fits_output_rate(source_rate) {
+            return Err(format!(
+                "Needs the output at another rate than {}Hz",
+                source_rate
+            ));
+        }
 
         let processed = self.chain.apply(source);
 
@@ -333,7 +528,7 @@ impl AudioPlayer {
         sink.set_volume(self.get_effective_volume());
         sink.pause(); // Start paused
 
-        Ok((sink, actual_duration))
+        Ok((sink, actual_duration, source_rate))
     }
 
     /// Play a preloaded sink (from PreloadManager)
@@ -342,13 +537,15 @@ impl AudioPlayer {
         &mut self,
         sink: Sink,
         duration: Duration,
+        source_rate: u32,
         path: PathBuf,
         is_streaming: bool,
     ) -> Result<(), String> {
-        self.stop();
+        self.stop_for_next_track();
         self.chain.set_fade_volume(1.0);
+        self.source_rate = source_rate;
 
-        sink.set_volume(self.get_effective_volume());
+        sink.set_volume(self.initial_sink_volume());
         sink.play();
 
         {
@@ -380,7 +577,7 @@ impl AudioPlayer {
         duration: Duration,
         cache_path: Option<PathBuf>,
     ) -> Result<(), String> {
-        self.stop();
+        self.stop_for_next_track();
         self.prepare_for_new_track();
 
         // Wait for total_size to be set (from Content-Length header)
@@ -426,14 +623,13 @@ impl AudioPlayer {
             .with_seekable(byte_len > 0)
             .build()
             .map_err(|e| format!("Failed to decode streaming audio: {}", e))?;
+        self.match_output_rate(source.sample_rate());
 
         let processed = self.chain.apply(source);
 
         let sink = Sink::connect_new(self._stream.mixer());
         sink.append(processed);
-
-        let volume = self.get_effective_volume();
-        sink.set_volume(volume);
+        sink.set_volume(self.initial_sink_volume());
 
         {
             let mut state = self.state.lock().unwrap();
@@ -458,6 +654,8 @@ impl AudioPlayer {
 
     /// Pause playback with optional fade out
     pub fn pause_with_fade(&mut self, _fade_out: bool) {
+        // Pausing ends an overlap rather than leaving the old track playing
+        self.crossfade = None;
         if let Some(sink) = self.current_sink.as_ref() {
             let current_pos = sink.get_pos();
             sink.pause();
@@ -512,6 +710,8 @@ impl AudioPlayer {
         if let Some(sink) = self.current_sink.take() {
             sink.stop();
         }
+        self.crossfade_next = None;
+        self.crossfade = None;
         self.chain.reset_analysis();
         let mut state = self.state.lock().unwrap();
         state.status = PlaybackStatus::Stopped;
@@ -525,12 +725,28 @@ impl AudioPlayer {
             state.volume = volume;
         }
 
+        // A running crossfade picks the new level up on its next step
+        if self.crossfade.is_some() {
+            return;
+        }
         if let Some(sink) = &self.current_sink {
             let effective_volume = self.get_effective_volume();
             sink.set_volume(effective_volume);
         }
     }
 
+    /// Mix a UI sound over the current output at the user's volume
+    ///
+    /// Uses its own detached sink, so it neither interrupts nor is affected
+    /// by the track's pause state, fades or track gain.
+    pub fn play_ui_sound(&self, sound: UiSound) {
+        let volume = self.state.lock().unwrap().volume;
+        let sink = Sink::connect_new(self._stream.mixer());
+        sink.set_volume(volume);
+        sink.append(sound.source());
+        sink.detach();
+    }
+
     /// Seek to position
     pub fn seek(&mut self, position: Duration) -> Result<(), String> {
         if let Some(sink) = &mut self.current_sink {
@@ -630,6 +846,9 @@ impl AudioPlayer {
         state.track_gain = gain;
         drop(state);
 
+        if self.crossfade.is_some() {
+            return;
+        }
         if let Some(sink) = &self.current_sink {
             let effective_volume = self.get_effective_volume();
             sink.set_volume(effective_volume);
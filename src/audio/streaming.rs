@@ -7,12 +7,15 @@
 //! - `start_buffer_download()`: Unified download function
 //!
 //! Download thread writes to buffer, playback thread reads from it.
-//! Blocks when data is not yet available.
+//! Blocks when data is not yet available. A download cut off by a dropped
+//! connection, a stall or a network change reconnects and carries on from
+//! the last byte it has.
 
 use std::io::{self, Read, Seek, SeekFrom};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
 
 use futures_util::StreamExt;
 use parking_lot::{Condvar, Mutex, RwLock};
@@ -25,6 +28,19 @@ pub const LOW_WATER_MARK_BYTES: u64 = 40 * 1024;
 /// When buffered data exceeds this, exit Buffering and resume Playing.
 pub const HIGH_WATER_MARK_BYTES: u64 = 400 * 1024;
 
+/// A download getting no data for this long reconnects
+const STALL_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// How often a download waiting to connect checks whether it was cancelled
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
+/// Reconnection attempts before an interrupted download gives up
+const RESUME_ATTEMPTS: u32 = 8;
+
+/// Reconnections in a row that brought no data before a download gives up,
+/// so a server cutting off at the same byte every time can't loop forever
+const RESUMES_WITHOUT_DATA: u32 = 3;
+
 /// Valid audio extensions for URL parsing
 const VALID_AUDIO_EXTENSIONS: &[&str] = &["mp3", "flac", "m4a", "aac", "ogg", "wav"];
 
@@ -494,8 +510,13 @@ pub fn start_buffer_download(
     let url_extension = extract_extension_from_url(&url);
 
     tokio::spawn(async move {
-        let http_client = reqwest::Client::new();
-        let response = match http_client.get(&url).send().await {
+        let mut network_changes = crate::api::http::network_changes();
+        // Skipping through songs cancels downloads still waiting to connect
+        let Some(response) = unless_cancelled(&buffer_clone, request_from(&url, 0)).await else {
+            tracing::debug!("Buffer download cancelled before it connected");
+            return;
+        };
+        let response = match response {
             Ok(r) if r.status().is_success() => r,
             Ok(r) => {
                 let status = r.status();
@@ -508,9 +529,9 @@ pub fn start_buffer_download(
                 return;
             }
             Err(e) => {
-                buffer_clone.set_error(e.to_string());
+                buffer_clone.set_error(e.clone());
                 if let Some(tx) = &event_tx {
-                    let _ = tx.send(StreamingEvent::Error(e.to_string())).await;
+                    let _ = tx.send(StreamingEvent::Error(e)).await;
                 }
                 return;
             }
@@ -546,8 +567,23 @@ pub fn start_buffer_download(
         let mut downloaded: u64 = 0;
         let mut playable_sent = false;
         let total_size = buffer_clone.total_size();
+        // Bytes already held that a resumed response sends again
+        let mut skip: u64 = 0;
+        let mut idle_resumes = 0;
+
+        loop {
+            let chunk_result = tokio::select! {
+                next = tokio::time::timeout(STALL_TIMEOUT, stream.next()) => match next {
+                    Ok(Some(chunk)) => chunk.map_err(|e| format!("Stream error: {}", e)),
+                    Ok(None) if total_size > 0 && downloaded < total_size => {
+                        Err(format!("Stream ended at {} of {} bytes", downloaded, total_size))
+                    }
+                    Ok(None) => break,
+                    Err(_) => Err("Stream stalled".to_string()),
+                },
+                _ = network_changes.changed() => Err("Network changed".to_string()),
+            };
 
-        while let Some(chunk_result) = stream.next().await {
             if buffer_clone.is_cancelled() {
                 tracing::debug!("Buffer download cancelled, cleaning up temp file");
                 // Clean up temp file on cancel
@@ -560,12 +596,18 @@ pub fn start_buffer_download(
 
             match chunk_result {
                 Ok(chunk) => {
+                    let chunk = skip_resent(&chunk, &mut skip);
+                    if chunk.is_empty() {
+                        continue;
+                    }
+                    idle_resumes = 0;
+                    crate::features::bandwidth::throttle(chunk.len()).await;
                     let chunk_len = chunk.len() as u64;
-                    buffer_clone.append(&chunk);
+                    buffer_clone.append(chunk);
 
                     if let Some(ref mut f) = file {
                         use std::io::Write;
-                        if let Err(e) = f.write_all(&chunk) {
+                        if let Err(e) = f.write_all(chunk) {
                             tracing::warn!("Cache file write error: {}", e);
                             file = None;
                             // Clean up failed temp file
@@ -589,8 +631,24 @@ pub fn start_buffer_download(
                             .await;
                     }
                 }
-                Err(e) => {
-                    let error_msg = format!("Stream error: {}", e);
+                Err(reason) => {
+                    tracing::info!("Download interrupted at {} bytes: {}", downloaded, reason);
+                    idle_resumes += 1;
+                    let resumed = if idle_resumes <= RESUMES_WITHOUT_DATA {
+                        resume(&url, downloaded, &buffer_clone).await
+                    } else {
+                        Err(reason)
+                    };
+                    let error_msg = match resumed {
+                        Ok((response, resent)) => {
+                            tracing::info!("Download resumed at {} bytes", downloaded);
+                            stream = response.bytes_stream();
+                            skip = resent;
+                            network_changes.mark_unchanged();
+                            continue;
+                        }
+                        Err(e) => format!("Stream error: {}", e),
+                    };
                     buffer_clone.set_error(error_msg.clone());
                     if let Some(tx) = &event_tx {
                         let _ = tx.send(StreamingEvent::Error(error_msg)).await;
@@ -669,6 +727,84 @@ pub fn start_buffer_download(
     shared_buffer
 }
 
+/// GET `url`, from byte `offset` on when resuming
+///
+/// The host's request slot is held only until the response headers arrive:
+/// a body streamed for the length of a song would otherwise keep covers and
+/// API calls to the same CDN waiting.
+async fn request_from(url: &str, offset: u64) -> Result<reqwest::Response, String> {
+    let client = crate::api::http::client(None).map_err(|e| e.to_string())?;
+    let _permit = crate::api::http::host_permit(url).await;
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    request.send().await.map_err(|e| e.to_string())
+}
+
+/// Run `future` unless `buffer` is cancelled first, checking before it
+/// starts and while it waits
+async fn unless_cancelled<T>(
+    buffer: &SharedBuffer,
+    future: impl std::future::Future<Output = T>,
+) -> Option<T> {
+    if buffer.is_cancelled() {
+        return None;
+    }
+    tokio::pin!(future);
+    loop {
+        tokio::select! {
+            output = &mut future => return Some(output),
+            _ = tokio::time::sleep(CANCEL_POLL) => {
+                if buffer.is_cancelled() {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Reconnect after an interruption and pick the download up at `offset`
+///
+/// Returns the response along with how many of its leading bytes are already
+/// held, for servers that answer the range request with the whole file.
+async fn resume(
+    url: &str,
+    offset: u64,
+    buffer: &SharedBuffer,
+) -> Result<(reqwest::Response, u64), String> {
+    let mut last_error = String::new();
+    for attempt in 0..RESUME_ATTEMPTS {
+        tokio::time::sleep(resume_delay(attempt)).await;
+        let Some(response) = unless_cancelled(buffer, request_from(url, offset)).await else {
+            return Err("Download cancelled".to_string());
+        };
+        match response {
+            Ok(r) if r.status() == reqwest::StatusCode::PARTIAL_CONTENT => return Ok((r, 0)),
+            Ok(r) if r.status().is_success() => return Ok((r, offset)),
+            // An expired link or a refused range won't get better by asking again
+            Ok(r) if r.status().is_client_error() => return Err(format!("HTTP {}", r.status())),
+            Ok(r) => last_error = format!("HTTP {}", r.status()),
+            Err(e) => last_error = e,
+        }
+        tracing::debug!("Resume attempt {} failed: {}", attempt + 1, last_error);
+    }
+    Err(last_error)
+}
+
+/// Wait before a reconnection attempt, short at first since a network
+/// switch usually settles within a second or two
+fn resume_delay(attempt: u32) -> Duration {
+    Duration::from_millis(500 << attempt.min(4))
+}
+
+/// The part of `chunk` past the bytes still to be skipped
+fn skip_resent<'a>(chunk: &'a [u8], skip: &mut u64) -> &'a [u8] {
+    let skipped = (*skip).min(chunk.len() as u64);
+    *skip -= skipped;
+    &chunk[skipped as usize..]
+}
+
 /// Wait for buffer to become playable (with timeout)
 pub async fn wait_for_playable(
     event_rx: &mut tokio::sync::mpsc::Receiver<StreamingEvent>,
@@ -898,4 +1034,21 @@ mod tests {
             Some("mp3".to_string())
         );
     }
+
+    #[test]
+    fn test_skip_resent_spans_chunks() {
+        let mut skip = 5;
+        assert_eq!(skip_resent(&[1, 2, 3], &mut skip), &[] as &[u8]);
+        assert_eq!(skip_resent(&[4, 5, 6, 7], &mut skip), &[6, 7]);
+        assert_eq!(skip, 0);
+        assert_eq!(skip_resent(&[8, 9], &mut skip), &[8, 9]);
+    }
+
+    #[test]
+    fn test_resume_delay_backs_off_to_a_cap() {
+        assert_eq!(resume_delay(0), Duration::from_millis(500));
+        assert_eq!(resume_delay(1), Duration::from_secs(1));
+        assert_eq!(resume_delay(4), Duration::from_secs(8));
+        assert_eq!(resume_delay(RESUME_ATTEMPTS), Duration::from_secs(8));
+    }
 }
@@ -27,6 +27,7 @@ use super::streaming::{HIGH_WATER_MARK_BYTES, LOW_WATER_MARK_BYTES, SharedBuffer
 struct PreloadedSink {
     sink: Sink,
     duration: Duration,
+    source_rate: u32,
     #[allow(dead_code)]
     path: PathBuf,
     is_streaming: bool,
@@ -157,6 +158,9 @@ fn audio_thread_main(
     // Storage for preloaded sinks (request_id -> PreloadedSink)
     let mut preloaded_sinks: HashMap<u64, PreloadedSink> = HashMap::new();
 
+    // Output stream the preloaded sinks were made on
+    let mut stream_generation = player.stream_generation();
+
     // Current streaming buffer reference (for data availability checks)
     // Set when PlayStreaming command is processed, cleared on Play/Stop
     let mut current_buffer: Option<SharedBuffer> = None;
@@ -245,6 +249,7 @@ fn audio_thread_main(
                 }
                 current_buffer = None;
                 player.stop();
+                player.restore_output_rate();
                 update_state_from_player(&player, &state);
                 state.set_current_path(None);
                 let _ = event_tx.send(AudioEvent::Stopped);
@@ -260,6 +265,21 @@ fn audio_thread_main(
                 );
             }
 
+            AudioCommand::SeekBy { offset_ms } => {
+                let info = player.get_info();
+                // A seek still waiting for buffered data is where playback really is
+                let from = state.pending_seek_target().unwrap_or(info.position);
+                let position = relative_seek_target(from, offset_ms, info.duration);
+                state.set_position(position);
+                handle_seek(
+                    &mut player,
+                    &event_tx,
+                    &state,
+                    position,
+                    current_buffer.as_ref(),
+                );
+            }
+
             AudioCommand::SetVolume { volume } => {
                 player.set_volume(volume);
                 state.set_volume(volume);
@@ -269,6 +289,23 @@ fn audio_thread_main(
                 player.set_track_gain(gain);
             }
 
+            AudioCommand::PlayUiSound { sound } => {
+                player.play_ui_sound(sound);
+            }
+
+            AudioCommand::CrossfadeNext { duration } => {
+                player.crossfade_next(duration);
+            }
+
+            AudioCommand::SetMatchSampleRate { enabled } => {
+                player.set_match_source_rate(enabled);
+                if !enabled {
+                    player.restore_output_rate();
+                }
+                let (source_rate, output_rate) = player.sample_rates();
+                state.set_sample_rates(source_rate, output_rate);
+            }
+
             AudioCommand::CreatePreloadSink { path, request_id } => {
                 handle_create_preload_sink(
                     &player,
@@ -328,6 +365,7 @@ fn audio_thread_main(
                 if let Some(ref buf) = current_buffer {
                     check_buffer_status(&mut player, &state, &event_tx, buf);
                 }
+                player.tick_crossfade();
 
                 // Update position in shared state
                 let info = player.get_info();
@@ -396,6 +434,12 @@ fn audio_thread_main(
             }
         }
 
+        // Sinks preloaded on a stream that was since reopened would play nowhere
+        if player.stream_generation() != stream_generation {
+            stream_generation = player.stream_generation();
+            drop_preloaded_sinks(&mut preloaded_sinks, &event_tx);
+        }
+
         // Check if playback finished after each command
         check_playback_finished(&player, &event_tx, &state, current_buffer.as_ref());
     }
@@ -476,6 +520,21 @@ fn handle_play_streaming(
     }
 }
 
+/// Clamp a relative seek to the track, stopping short of the very end so
+/// the track still finishes normally
+fn relative_seek_target(from: Duration, offset_ms: i64, duration: Duration) -> Duration {
+    let offset = Duration::from_millis(offset_ms.unsigned_abs());
+    let target = if offset_ms < 0 {
+        from.saturating_sub(offset)
+    } else {
+        from + offset
+    };
+    if duration.is_zero() {
+        return target;
+    }
+    target.min(duration.saturating_sub(Duration::from_secs(1)))
+}
+
 fn handle_seek(
     player: &mut AudioPlayer,
     event_tx: &AudioEventSender,
@@ -586,13 +645,14 @@ fn handle_create_preload_sink(
     request_id: u64,
 ) {
     match player.create_preload_sink(&path) {
-        Ok((sink, duration)) => {
+        Ok((sink, duration, source_rate)) => {
             // Store the sink for later playback
             preloaded_sinks.insert(
                 request_id,
                 PreloadedSink {
                     sink,
                     duration,
+                    source_rate,
                     path: path.clone(),
                     is_streaming: false,
                     shared_buffer: None, // Local files don't have shared buffer
@@ -631,7 +691,7 @@ fn handle_create_preload_sink_streaming(
 
     // This may block waiting for streaming data
     match player.create_preload_sink_streaming(buffer, duration) {
-        Ok((sink, actual_duration)) => {
+        Ok((sink, actual_duration, source_rate)) => {
             // For streaming, we don't have a real path, use a placeholder
             let path = PathBuf::from(format!("streaming://{}", request_id));
             preloaded_sinks.insert(
@@ -639,6 +699,7 @@ fn handle_create_preload_sink_streaming(
                 PreloadedSink {
                     sink,
                     duration: actual_duration,
+                    source_rate,
                     path: path.clone(),
                     is_streaming: true,
                     shared_buffer: Some(shared_buffer), // Save for callback setup on play
@@ -695,6 +756,7 @@ fn handle_play_preloaded_by_id(
         match player.play_preloaded_sink(
             preloaded.sink,
             preloaded.duration,
+            preloaded.source_rate,
             path.clone(),
             preloaded.is_streaming,
         ) {
@@ -715,6 +777,22 @@ fn handle_play_preloaded_by_id(
     }
 }
 
+/// Drop every preloaded sink, telling the UI which requests are gone
+fn drop_preloaded_sinks(
+    preloaded_sinks: &mut HashMap<u64, PreloadedSink>,
+    event_tx: &AudioEventSender,
+) {
+    if preloaded_sinks.is_empty() {
+        return;
+    }
+    let request_ids: Vec<u64> = preloaded_sinks.drain().map(|(id, _)| id).collect();
+    tracing::debug!(
+        "Dropped preloaded sinks after output change: {:?}",
+        request_ids
+    );
+    let _ = event_tx.send(AudioEvent::PreloadsDropped { request_ids });
+}
+
 fn handle_switch_device(
     player: &mut AudioPlayer,
     event_tx: &AudioEventSender,
@@ -921,6 +999,8 @@ fn exit_buffering(
 fn update_state_from_player(player: &AudioPlayer, state: &SharedPlaybackState) {
     let info = player.get_info();
     state.update_from_info(&info);
+    let (source_rate, output_rate) = player.sample_rates();
+    state.set_sample_rates(source_rate, output_rate);
 }
 
 /// Check if playback finished and send event
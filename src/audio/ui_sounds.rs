@@ -0,0 +1,159 @@
+//! Short synthesized UI feedback sounds
+//!
+//! Sounds are rendered once into a mono buffer and peak-normalized, so every
+//! cue plays at the same level regardless of how many notes it has. They are
+//! mixed next to the music sink and skip the processing chain, keeping the EQ
+//! and spectrum analyzer limited to the track itself.
+
+use std::f32::consts::TAU;
+
+use rodio::buffer::SamplesBuffer;
+
+const SAMPLE_RATE: u32 = 48_000;
+
+/// Peak level of every UI sound before the player volume is applied
+const PEAK_LEVEL: f32 = 0.25;
+
+/// A UI event that can be confirmed with a sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiSound {
+    /// A song was added to the liked list
+    Like,
+    /// Songs were appended to the play queue
+    QueueAdd,
+    /// An operation failed
+    Error,
+    /// Metronome tick for output latency calibration
+    Click,
+}
+
+/// One sine note: frequency (Hz), start and length (ms)
+struct Note {
+    freq: f32,
+    start_ms: u32,
+    length_ms: u32,
+}
+
+impl UiSound {
+    fn notes(self) -> &'static [Note] {
+        match self {
+            // Rising major third
+            Self::Like => &[
+                Note {
+                    freq: 880.0,
+                    start_ms: 0,
+                    length_ms: 90,
+                },
+                Note {
+                    freq: 1108.7,
+                    start_ms: 70,
+                    length_ms: 140,
+                },
+            ],
+            Self::QueueAdd => &[Note {
+                freq: 660.0,
+                start_ms: 0,
+                length_ms: 110,
+            }],
+            // Falling low pair
+            Self::Error => &[
+                Note {
+                    freq: 392.0,
+                    start_ms: 0,
+                    length_ms: 120,
+                },
+                Note {
+                    freq: 311.1,
+                    start_ms: 110,
+                    length_ms: 180,
+                },
+            ],
+            // Short and bright so its onset is easy to tap along with
+            Self::Click => &[Note {
+                freq: 1760.0,
+                start_ms: 0,
+                length_ms: 40,
+            }],
+        }
+    }
+
+    /// Render the sound into peak-normalized mono samples
+    pub fn render(self) -> Vec<f32> {
+        let notes = self.notes();
+        let total_ms = notes
+            .iter()
+            .map(|n| n.start_ms + n.length_ms)
+            .max()
+            .unwrap_or(0);
+        let mut samples = vec![0.0f32; ms_to_samples(total_ms)];
+
+        for note in notes {
+            let start = ms_to_samples(note.start_ms);
+            let len = ms_to_samples(note.length_ms);
+            // Short linear attack avoids clicks, exponential tail sounds like a chime
+            let attack = ms_to_samples(5).min(len);
+            for i in 0..len {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                let envelope = if i < attack {
+                    i as f32 / attack as f32
+                } else {
+                    (-6.0 * (i - attack) as f32 / len as f32).exp()
+                };
+                samples[start + i] += (TAU * note.freq * t).sin() * envelope;
+            }
+        }
+
+        let peak = samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        if peak > 0.0 {
+            let scale = PEAK_LEVEL / peak;
+            samples.iter_mut().for_each(|s| *s *= scale);
+        }
+        samples
+    }
+
+    /// Rendered sound as a playable source
+    pub fn source(self) -> SamplesBuffer {
+        SamplesBuffer::new(1, SAMPLE_RATE, self.render())
+    }
+}
+
+fn ms_to_samples(ms: u32) -> usize {
+    (SAMPLE_RATE as u64 * ms as u64 / 1000) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [UiSound; 4] = [
+        UiSound::Like,
+        UiSound::QueueAdd,
+        UiSound::Error,
+        UiSound::Click,
+    ];
+
+    #[test]
+    fn sounds_share_the_same_peak() {
+        for sound in ALL {
+            let peak = sound.render().iter().fold(0.0f32, |m, s| m.max(s.abs()));
+            assert!((peak - PEAK_LEVEL).abs() < 1e-4, "{sound:?} peak {peak}");
+        }
+    }
+
+    #[test]
+    fn sounds_are_short_and_fade_out() {
+        for sound in ALL {
+            let samples = sound.render();
+            assert!(samples.len() < ms_to_samples(500), "{sound:?} too long");
+            let tail = samples.last().copied().unwrap_or_default().abs();
+            assert!(tail < PEAK_LEVEL * 0.05, "{sound:?} ends abruptly");
+        }
+    }
+
+    #[test]
+    fn sounds_start_silent() {
+        for sound in ALL {
+            assert_eq!(sound.render()[0], 0.0);
+        }
+    }
+}
@@ -7,6 +7,7 @@ use std::path::PathBuf;
 use std::time::SystemTime;
 use tracing::{info, warn};
 
+use crate::features::media_artwork;
 use crate::utils::{avatars_cache_dir, banners_cache_dir, covers_cache_dir, songs_cache_dir};
 
 /// Information about a cached file
@@ -42,6 +43,7 @@ impl CacheStats {
 fn cache_directories() -> Vec<PathBuf> {
     vec![
         covers_cache_dir(),
+        media_artwork::cache_dir(),
         songs_cache_dir(),
         banners_cache_dir(),
         avatars_cache_dir(),
@@ -91,10 +93,12 @@ fn collect_entries(dir: &PathBuf) -> Vec<CacheEntry> {
 pub fn calculate_cache_stats() -> CacheStats {
     let mut stats = CacheStats::default();
 
-    // Covers
-    for entry in collect_entries(&covers_cache_dir()) {
-        stats.covers_bytes += entry.size;
-        stats.file_count += 1;
+    // Covers, including the artwork scaled down for the media session
+    for dir in [covers_cache_dir(), media_artwork::cache_dir()] {
+        for entry in collect_entries(&dir) {
+            stats.covers_bytes += entry.size;
+            stats.file_count += 1;
+        }
     }
 
     // Songs
@@ -1,10 +1,12 @@
 //! Database module for persistent storage
 //! Uses SQLite via sqlx for storing playlists, songs, and playback state
 
+mod migrations;
 mod models;
 mod ops;
 mod repository;
 mod schema;
+mod writer;
 
 pub use models::*;
 pub use repository::Database;
@@ -0,0 +1,350 @@
+//! Versioned schema migrations
+//!
+//! The schema version is kept in SQLite's `user_version`. Every database is
+//! first brought up to the baseline in `schema.rs`, then the migrations in
+//! [`MIGRATIONS`] newer than its version are applied in order, each in its own
+//! transaction. Released migrations are never edited; changes go in a new one.
+
+use anyhow::{Context, Result};
+use sqlx::{Pool, Sqlite};
+
+/// One step of the schema
+pub struct Migration {
+    /// Schema version after this step, one more than the previous step
+    pub version: i64,
+    /// Short description for the log
+    pub name: &'static str,
+    /// Statements to run, separated by `;`
+    pub sql: &'static str,
+}
+
+/// Schema changes after the baseline, oldest first
+///
+/// Append new steps at the end with the next version number, e.g.
+///
+/// ```text
+/// Migration {
+///     version: 1,
+///     name: "song ratings",
+///     sql: "ALTER TABLE songs ADD COLUMN rating INTEGER",
+/// },
+/// ```
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "app versions",
+        sql: "CREATE TABLE app_versions (
+            version TEXT PRIMARY KEY,
+            first_run_at INTEGER NOT NULL,
+            last_run_at INTEGER NOT NULL
+        )",
+    },
+    Migration {
+        version: 2,
+        name: "song analysis",
+        sql: "CREATE TABLE song_analysis (
+            song_id INTEGER PRIMARY KEY,
+            bpm REAL,
+            musical_key INTEGER,
+            energy REAL,
+            analyzed_at INTEGER NOT NULL,
+            FOREIGN KEY (song_id) REFERENCES songs(id) ON DELETE CASCADE
+        )",
+    },
+    Migration {
+        version: 3,
+        name: "queue snapshots",
+        sql: "CREATE TABLE queue_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            song_ids TEXT NOT NULL,
+            song_count INTEGER NOT NULL,
+            queue_position INTEGER NOT NULL DEFAULT 0,
+            saved_at INTEGER NOT NULL
+        )",
+    },
+    Migration {
+        version: 4,
+        name: "trash",
+        sql: "ALTER TABLE songs ADD COLUMN deleted_at INTEGER;
+            ALTER TABLE playlists ADD COLUMN deleted_at INTEGER",
+    },
+    Migration {
+        version: 5,
+        name: "song overrides",
+        sql: "CREATE TABLE song_overrides (
+            ncm_id INTEGER PRIMARY KEY,
+            title TEXT NOT NULL,
+            artist TEXT NOT NULL,
+            album TEXT NOT NULL,
+            original_title TEXT NOT NULL,
+            original_artist TEXT NOT NULL,
+            original_album TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+    },
+    Migration {
+        version: 6,
+        name: "song fingerprints",
+        sql: "CREATE TABLE song_fingerprints (
+            song_id INTEGER PRIMARY KEY,
+            words BLOB NOT NULL,
+            fingerprinted_at INTEGER NOT NULL,
+            FOREIGN KEY (song_id) REFERENCES songs(id) ON DELETE CASCADE
+        )",
+    },
+    Migration {
+        version: 7,
+        name: "typed song keys",
+        // Song keys were one integer with NCM tracks negated
+        sql: "CREATE TABLE favorites_typed (
+            source TEXT NOT NULL,
+            song_id INTEGER NOT NULL,
+            liked INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (source, song_id)
+        );
+        INSERT INTO favorites_typed
+            SELECT CASE WHEN song_id < 0 THEN 'ncm' ELSE 'local' END, ABS(song_id),
+                liked, updated_at
+            FROM favorites;
+        DROP TABLE favorites;
+        ALTER TABLE favorites_typed RENAME TO favorites;
+
+        CREATE TABLE blocked_songs_typed (
+            source TEXT NOT NULL,
+            song_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            artist TEXT NOT NULL DEFAULT '',
+            blocked_at INTEGER NOT NULL,
+            PRIMARY KEY (source, song_id)
+        );
+        INSERT INTO blocked_songs_typed
+            SELECT CASE WHEN song_id < 0 THEN 'ncm' ELSE 'local' END, ABS(song_id),
+                title, artist, blocked_at
+            FROM blocked_songs;
+        DROP TABLE blocked_songs;
+        ALTER TABLE blocked_songs_typed RENAME TO blocked_songs;
+
+        CREATE TABLE lyrics_preferences_typed (
+            source TEXT NOT NULL,
+            song_id INTEGER NOT NULL,
+            show_translation INTEGER NOT NULL,
+            show_romanization INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (source, song_id)
+        );
+        INSERT INTO lyrics_preferences_typed
+            SELECT CASE WHEN song_id < 0 THEN 'ncm' ELSE 'local' END, ABS(song_id),
+                show_translation, show_romanization, updated_at
+            FROM lyrics_preferences;
+        DROP TABLE lyrics_preferences;
+        ALTER TABLE lyrics_preferences_typed RENAME TO lyrics_preferences;
+
+        CREATE TABLE lyrics_shares_typed (
+            source TEXT NOT NULL,
+            song_id INTEGER NOT NULL,
+            content_hash TEXT NOT NULL,
+            updated_at INTEGER NOT NULL,
+            PRIMARY KEY (source, song_id)
+        );
+        INSERT INTO lyrics_shares_typed
+            SELECT CASE WHEN song_id < 0 THEN 'ncm' ELSE 'local' END, ABS(song_id),
+                content_hash, updated_at
+            FROM lyrics_shares;
+        DROP TABLE lyrics_shares;
+        ALTER TABLE lyrics_shares_typed RENAME TO lyrics_shares",
+    },
+];
+
+/// The database was last written by a newer build with migrations this one
+/// doesn't know about
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaTooNew {
+    pub found: i64,
+    pub supported: i64,
+}
+
+impl std::fmt::Display for SchemaTooNew {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "database schema version {} needs a newer Rustle (this one supports up to {})",
+            self.found, self.supported
+        )
+    }
+}
+
+impl std::error::Error for SchemaTooNew {}
+
+/// Current schema version of the database
+pub async fn schema_version(pool: &Pool<Sqlite>) -> Result<i64> {
+    Ok(sqlx::query_scalar("PRAGMA user_version")
+        .fetch_one(pool)
+        .await?)
+}
+
+/// Refuse a database written by a newer build, before anything touches it
+pub async fn check_version(pool: &Pool<Sqlite>, migrations: &[Migration]) -> Result<()> {
+    let found = schema_version(pool).await?;
+    let supported = latest_version(migrations);
+    if found > supported {
+        return Err(SchemaTooNew { found, supported }.into());
+    }
+    Ok(())
+}
+
+/// Apply the migrations the database hasn't seen yet
+///
+/// A failing migration is rolled back as a whole and stops the run, leaving
+/// the database at the last version that applied cleanly.
+pub async fn apply(pool: &Pool<Sqlite>, migrations: &[Migration]) -> Result<()> {
+    debug_assert!(
+        migrations
+            .iter()
+            .enumerate()
+            .all(|(i, m)| m.version == i as i64 + 1),
+        "migration versions must count up from 1"
+    );
+
+    let current = schema_version(pool).await?;
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        tracing::info!(
+            "Applying database migration {} ({})",
+            migration.version,
+            migration.name
+        );
+        let mut tx = pool.begin().await?;
+        sqlx::query(migration.sql)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| {
+                format!(
+                    "Database migration {} ({}) failed",
+                    migration.version, migration.name
+                )
+            })?;
+        // user_version lives in the database header, so it commits with the step
+        let set_version = format!("PRAGMA user_version = {}", migration.version);
+        sqlx::query(&set_version).execute(&mut *tx).await?;
+        tx.commit().await?;
+    }
+    Ok(())
+}
+
+fn latest_version(migrations: &[Migration]) -> i64 {
+    migrations.last().map_or(0, |m| m.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    const STEPS: &[Migration] = &[
+        Migration {
+            version: 1,
+            name: "ratings",
+            sql: "CREATE TABLE ratings (song_id INTEGER PRIMARY KEY, stars INTEGER NOT NULL)",
+        },
+        Migration {
+            version: 2,
+            name: "tags",
+            sql: "CREATE TABLE tags (name TEXT PRIMARY KEY); INSERT INTO tags VALUES ('chill')",
+        },
+    ];
+
+    async fn memory_pool() -> Pool<Sqlite> {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap()
+    }
+
+    async fn table_exists(pool: &Pool<Sqlite>, name: &str) -> bool {
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = ?)")
+            .bind(name)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_applies_pending_migrations_once() {
+        let pool = memory_pool().await;
+        apply(&pool, &STEPS[..1]).await.unwrap();
+        assert_eq!(schema_version(&pool).await.unwrap(), 1);
+
+        apply(&pool, STEPS).await.unwrap();
+        apply(&pool, STEPS).await.unwrap();
+        assert_eq!(schema_version(&pool).await.unwrap(), 2);
+        let tags: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tags")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tags, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_migration_rolls_back() {
+        let pool = memory_pool().await;
+        let broken = [
+            Migration {
+                version: 1,
+                name: "ratings",
+                sql: STEPS[0].sql,
+            },
+            Migration {
+                version: 2,
+                name: "broken",
+                sql: "CREATE TABLE tags (name TEXT); INSERT INTO missing VALUES (1)",
+            },
+        ];
+
+        assert!(apply(&pool, &broken).await.is_err());
+        assert_eq!(schema_version(&pool).await.unwrap(), 1);
+        assert!(table_exists(&pool, "ratings").await);
+        assert!(!table_exists(&pool, "tags").await);
+    }
+
+    #[tokio::test]
+    async fn test_song_keys_split_by_source() {
+        let pool = memory_pool().await;
+        sqlx::query(
+            "CREATE TABLE favorites (song_id INTEGER PRIMARY KEY, liked INTEGER NOT NULL, updated_at INTEGER NOT NULL);
+            CREATE TABLE blocked_songs (song_id INTEGER PRIMARY KEY, title TEXT NOT NULL, artist TEXT NOT NULL DEFAULT '', blocked_at INTEGER NOT NULL);
+            CREATE TABLE lyrics_preferences (song_id INTEGER PRIMARY KEY, show_translation INTEGER NOT NULL, show_romanization INTEGER NOT NULL, updated_at INTEGER NOT NULL);
+            CREATE TABLE lyrics_shares (song_id INTEGER PRIMARY KEY, content_hash TEXT NOT NULL, updated_at INTEGER NOT NULL);
+            INSERT INTO favorites VALUES (-1850183, 1, 10), (7, 0, 20);
+            PRAGMA user_version = 6",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        apply(&pool, MIGRATIONS).await.unwrap();
+        let rows: Vec<(String, i64, bool)> =
+            sqlx::query_as("SELECT source, song_id, liked FROM favorites ORDER BY song_id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            rows,
+            [
+                ("local".to_string(), 7, false),
+                ("ncm".to_string(), 1850183, true)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_newer_schema_is_refused() {
+        let pool = memory_pool().await;
+        apply(&pool, STEPS).await.unwrap();
+
+        let err = check_version(&pool, &STEPS[..1]).await.unwrap_err();
+        let too_new = err.downcast_ref::<SchemaTooNew>().unwrap();
+        assert_eq!((too_new.found, too_new.supported), (2, 1));
+        assert!(check_version(&pool, STEPS).await.is_ok());
+    }
+}
@@ -2,13 +2,15 @@
 //! These models map directly to SQLite tables
 
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row};
 
 /// Song metadata stored in database
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DbSong {
-    /// Unique identifier (auto-increment)
-    pub id: i64,
+    /// Which song this is; NCM tracks saved to the library are known by
+    /// their track rather than their row
+    pub id: SongId,
     /// File path on disk
     pub file_path: String,
     /// Song title
@@ -43,6 +45,167 @@ pub struct DbSong {
     pub created_at: i64,
 }
 
+impl<'r> FromRow<'r, SqliteRow> for DbSong {
+    fn from_row(row: &'r SqliteRow) -> sqlx::Result<Self> {
+        let source: String = row.try_get("source")?;
+        Ok(Self {
+            id: SongId::stored(row.try_get("id")?, &source, row.try_get("source_id")?),
+            file_path: row.try_get("file_path")?,
+            title: row.try_get("title")?,
+            artist: row.try_get("artist")?,
+            album: row.try_get("album")?,
+            duration_secs: row.try_get("duration_secs")?,
+            track_number: row.try_get("track_number")?,
+            year: row.try_get("year")?,
+            genre: row.try_get("genre")?,
+            cover_path: row.try_get("cover_path")?,
+            file_hash: row.try_get("file_hash")?,
+            file_size: row.try_get("file_size")?,
+            format: row.try_get("format")?,
+            play_count: row.try_get("play_count")?,
+            last_played: row.try_get("last_played")?,
+            last_modified: row.try_get("last_modified")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+/// Catalog a song ID belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum IdSource {
+    /// Row in the local `songs` table
+    Library,
+    /// Track ID on NCM
+    Ncm,
+}
+
+impl IdSource {
+    /// Value of the `songs.source` column
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Library => "local",
+            Self::Ncm => "ncm",
+        }
+    }
+
+    fn from_column(value: &str) -> Option<Self> {
+        [Self::Library, Self::Ncm]
+            .into_iter()
+            .find(|source| source.as_str() == value)
+    }
+}
+
+/// A song's identity, telling library rows and NCM tracks apart
+///
+/// Tables keyed by any song (favorites, the blocklist, lyrics preferences
+/// and shares) store it in `source` and `song_id` columns and read it back
+/// flattened into their rows. Older versions wrote a single integer with NCM
+/// tracks negated into saved sessions, which is still read here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "SerializedSongId")]
+pub struct SongId {
+    pub source: IdSource,
+    pub id: u64,
+}
+
+impl SongId {
+    /// A song in the local library by its row ID
+    pub const fn library(row_id: i64) -> Self {
+        Self {
+            source: IdSource::Library,
+            id: row_id as u64,
+        }
+    }
+
+    /// A track on NCM
+    pub const fn ncm(track_id: u64) -> Self {
+        Self {
+            source: IdSource::Ncm,
+            id: track_id,
+        }
+    }
+
+    /// Identity of a `songs` row from its `source` and `source_id` columns
+    fn stored(row_id: i64, source: &str, source_id: Option<i64>) -> Self {
+        match source_id {
+            Some(track_id) if source == IdSource::Ncm.as_str() => Self::ncm(track_id as u64),
+            _ => Self::library(row_id),
+        }
+    }
+
+    const fn from_legacy(value: i64) -> Self {
+        if value < 0 {
+            Self::ncm(value.unsigned_abs())
+        } else {
+            Self::library(value)
+        }
+    }
+
+    /// Value of the `song_id` column next to `source`
+    pub const fn column(self) -> i64 {
+        self.id as i64
+    }
+
+    /// The NCM track, if this is one
+    pub const fn ncm_id(self) -> Option<u64> {
+        match self.source {
+            IdSource::Ncm => Some(self.id),
+            IdSource::Library => None,
+        }
+    }
+
+    /// The library row, if this is one
+    pub const fn library_id(self) -> Option<i64> {
+        match self.source {
+            IdSource::Library => Some(self.id as i64),
+            IdSource::Ncm => None,
+        }
+    }
+
+    pub const fn is_ncm(self) -> bool {
+        matches!(self.source, IdSource::Ncm)
+    }
+}
+
+impl std::fmt::Display for SongId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.source.as_str(), self.id)
+    }
+}
+
+/// A song ID as saved, either typed or as the signed integer of older
+/// versions
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SerializedSongId {
+    Typed { source: IdSource, id: u64 },
+    Legacy(i64),
+}
+
+impl From<SerializedSongId> for SongId {
+    fn from(value: SerializedSongId) -> Self {
+        match value {
+            SerializedSongId::Typed { source, id } => Self { source, id },
+            SerializedSongId::Legacy(value) => Self::from_legacy(value),
+        }
+    }
+}
+
+impl<'r> FromRow<'r, SqliteRow> for SongId {
+    fn from_row(row: &'r SqliteRow) -> sqlx::Result<Self> {
+        let source: String = row.try_get("source")?;
+        let source = IdSource::from_column(&source).ok_or_else(|| sqlx::Error::ColumnDecode {
+            index: "source".to_string(),
+            source: format!("unknown song source {:?}", source).into(),
+        })?;
+        let id: i64 = row.try_get("song_id")?;
+        Ok(Self {
+            source,
+            id: id as u64,
+        })
+    }
+}
+
 /// Playlist stored in database
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct DbPlaylist {
@@ -74,6 +237,35 @@ pub struct DbPlaylistSong {
     pub added_at: i64,
 }
 
+/// A playlist in the trash
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbTrashedPlaylist {
+    pub id: i64,
+    pub name: String,
+    /// Songs still in the library
+    pub song_count: i64,
+    pub deleted_at: i64,
+}
+
+/// A song removed from the library, waiting in the trash
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbTrashedSong {
+    pub id: i64,
+    pub title: String,
+    pub artist: String,
+    pub deleted_at: i64,
+}
+
+/// Days an item stays in the trash before it is purged
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Whole days left before an item trashed at `deleted_at` is purged, 0 on
+/// its last day
+pub fn trash_days_left(deleted_at: i64, now: i64) -> i64 {
+    let expires_at = deleted_at + TRASH_RETENTION_DAYS * 86_400;
+    ((expires_at - now) / 86_400).max(0)
+}
+
 /// Current playback queue item
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct DbQueueItem {
@@ -119,6 +311,16 @@ pub struct DbPlayHistory {
     pub completed: bool,
 }
 
+/// A play event joined with the song's title and artist
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbPlayRecord {
+    pub song_id: i64,
+    pub title: String,
+    pub artist: String,
+    pub played_at: i64,
+    pub listened_secs: i64,
+}
+
 // ============ Input structs for creating new records ============
 
 /// Input for creating a new song
@@ -166,24 +368,178 @@ pub struct NewPlaylist {
 /// Song with playlist-specific data (like added_at)
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct DbPlaylistSongWithDate {
-    // Song fields
-    pub id: i64,
-    pub file_path: String,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub song: DbSong,
+    // Playlist-specific field
+    pub added_at: i64,
+}
+
+/// Dominant colors of a cover image, cached by image content
+///
+/// Colors are packed as `0xRRGGBB`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbPalette {
+    /// xxh3 hash of the image file bytes
+    pub image_hash: String,
+    pub primary_color: i64,
+    pub secondary_color: i64,
+    pub tertiary_color: i64,
+    /// Average brightness (0.0 = dark, 1.0 = bright)
+    pub brightness: f64,
+    pub created_at: i64,
+}
+
+/// Playback preferences the user saved for one playlist
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbPlaylistPreference {
+    /// Playlist page ID (negative for NCM playlists)
+    pub playlist_id: i64,
+    pub shuffle: bool,
+    /// Streaming quality as its API level, `None` to follow the global setting
+    pub quality: Option<i64>,
+    pub append_to_queue: bool,
+    pub updated_at: i64,
+}
+
+/// An artist followed locally or on NCM
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbFollowedArtist {
+    /// NCM artist ID
+    pub artist_id: i64,
+    pub name: String,
+    pub pic_url: String,
+    /// Publish time of the newest album already seen, 0 until the first check
+    pub last_release_at: i64,
+    pub followed_at: i64,
+}
+
+/// A song the user never wants to hear
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbBlockedSong {
+    #[sqlx(flatten)]
+    pub song_id: SongId,
+    pub title: String,
+    pub artist: String,
+    pub blocked_at: i64,
+}
+
+/// An artist whose songs are skipped and filtered out
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbBlockedArtist {
+    /// Artist name as the user entered it; matched case-insensitively
+    pub name: String,
+    pub blocked_at: i64,
+}
+
+/// Title, artist and album shown for an NCM track instead of NCM's, along
+/// with NCM's own to go back to
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbSongOverride {
+    pub ncm_id: i64,
     pub title: String,
     pub artist: String,
     pub album: String,
-    pub duration_secs: i64,
-    pub track_number: Option<i64>,
-    pub year: Option<i64>,
-    pub genre: Option<String>,
-    pub cover_path: Option<String>,
-    pub file_hash: Option<String>,
-    pub file_size: i64,
-    pub format: Option<String>,
-    pub play_count: i64,
-    pub last_played: Option<i64>,
-    pub last_modified: i64,
-    pub created_at: i64,
-    // Playlist-specific field
-    pub added_at: i64,
+    pub original_title: String,
+    pub original_artist: String,
+    pub original_album: String,
+    pub updated_at: i64,
+}
+
+/// Sparse audio fingerprint of a local song for listen mode; `words` holds
+/// little-endian `u32`s and is empty when the file could not be decoded
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbSongFingerprint {
+    pub song_id: i64,
+    pub words: Vec<u8>,
+    pub fingerprinted_at: i64,
+}
+
+/// A local song matched to its NCM track
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbNcmLink {
+    pub song_id: i64,
+    pub ncm_id: i64,
+    pub linked_at: i64,
+}
+
+/// Whether a song is liked
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbFavorite {
+    #[sqlx(flatten)]
+    pub song_id: SongId,
+    pub liked: bool,
+    pub updated_at: i64,
+}
+
+/// Saved copy of an account's liked songs playlist
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbLikedMirror {
+    /// NCM user ID
+    pub user_id: i64,
+    pub playlist_id: i64,
+    pub name: String,
+    pub cover_img_url: String,
+    pub author: String,
+    /// JSON list of the playlist's songs
+    pub songs: String,
+    pub synced_at: i64,
+}
+
+/// A daily check-in reward claimed on NCM
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbCheckIn {
+    pub id: i64,
+    /// NCM user ID
+    pub uid: i64,
+    /// 0 for mobile, 1 for web
+    pub platform: i64,
+    pub points: i64,
+    pub claimed_at: i64,
+}
+
+/// Tempo, key and energy found for a local song; all `None` when the file
+/// could not be analyzed, so it isn't tried again
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbSongAnalysis {
+    pub song_id: i64,
+    pub bpm: Option<f64>,
+    /// 0-11 for C to B major, 12-23 for C to B minor
+    pub musical_key: Option<i64>,
+    /// 0 for quiet and slow up to 1 for loud and fast
+    pub energy: Option<f64>,
+    pub analyzed_at: i64,
+}
+
+/// An earlier queue kept so it can be restored
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbQueueSnapshot {
+    pub id: i64,
+    /// Song row IDs in queue order, as a JSON array
+    pub song_ids: String,
+    pub song_count: i64,
+    /// Index of the song that was playing
+    pub queue_position: i64,
+    /// Last time the queue was changed or played from
+    pub saved_at: i64,
+    /// Title of the first song, if it is still in the library
+    pub first_title: Option<String>,
+}
+
+/// A version of the app that has run on this machine
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbAppVersion {
+    pub version: String,
+    pub first_run_at: i64,
+    pub last_run_at: i64,
+}
+
+/// Lyrics sub-lines the user chose for one song
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DbLyricsPreference {
+    #[sqlx(flatten)]
+    pub song_id: SongId,
+    pub show_translation: bool,
+    pub show_romanization: bool,
+    pub updated_at: i64,
 }
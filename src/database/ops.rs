@@ -1,16 +1,44 @@
 //! Database operations organized by entity type
 
+mod app_versions;
+mod artists;
+mod blocklist;
+mod check_ins;
+mod favorites;
 mod history;
+mod lyrics;
+mod ncm_links;
+mod palettes;
 mod playback;
 mod playlists;
 mod queue;
+mod queue_snapshots;
+mod search_history;
+mod song_analysis;
+mod song_fingerprints;
+mod song_overrides;
 mod songs;
+mod trash;
 
+pub use app_versions::*;
+pub use artists::*;
+pub use blocklist::*;
+pub use check_ins::*;
+pub use favorites::*;
 pub use history::*;
+pub use lyrics::*;
+pub use ncm_links::*;
+pub use palettes::*;
 pub use playback::*;
 pub use playlists::*;
 pub use queue::*;
+pub use queue_snapshots::*;
+pub use search_history::*;
+pub use song_analysis::*;
+pub use song_fingerprints::*;
+pub use song_overrides::*;
 pub use songs::*;
+pub use trash::*;
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
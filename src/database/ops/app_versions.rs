@@ -0,0 +1,62 @@
+//! App version history operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::DbAppVersion;
+
+/// Record that `current` is running, returning the version that ran before
+///
+/// `legacy` is the last-run version older builds kept in settings; it seeds
+/// an empty history so the first update after the move is still noticed.
+/// Returns `None` on a fresh install.
+pub async fn record_app_version(
+    pool: &Pool<Sqlite>,
+    current: &str,
+    legacy: &str,
+) -> Result<Option<String>> {
+    let now = current_timestamp();
+    let mut previous = sqlx::query_scalar::<_, String>(
+        "SELECT version FROM app_versions ORDER BY last_run_at DESC, rowid DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    if previous.is_none() && !legacy.is_empty() {
+        // When it first ran is unknown; it just goes before the current one
+        sqlx::query(
+            "INSERT INTO app_versions (version, first_run_at, last_run_at) VALUES (?, ?, ?)",
+        )
+        .bind(legacy)
+        .bind(now - 1)
+        .bind(now - 1)
+        .execute(pool)
+        .await?;
+        previous = Some(legacy.to_string());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO app_versions (version, first_run_at, last_run_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(version) DO UPDATE SET last_run_at = excluded.last_run_at
+        "#,
+    )
+    .bind(current)
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await?;
+    Ok(previous)
+}
+
+/// Every version that has run, most recently first used first
+pub async fn get_app_versions(pool: &Pool<Sqlite>) -> Result<Vec<DbAppVersion>> {
+    let versions = sqlx::query_as::<_, DbAppVersion>(
+        "SELECT * FROM app_versions ORDER BY first_run_at DESC, rowid DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(versions)
+}
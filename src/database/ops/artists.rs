@@ -0,0 +1,63 @@
+//! Followed artist operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::DbFollowedArtist;
+
+/// Get all followed artists, most recently followed first
+pub async fn get_followed_artists(pool: &Pool<Sqlite>) -> Result<Vec<DbFollowedArtist>> {
+    let artists = sqlx::query_as::<_, DbFollowedArtist>(
+        "SELECT * FROM followed_artists ORDER BY followed_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(artists)
+}
+
+/// Follow an artist, keeping the release marker if it is already followed
+pub async fn follow_artist(
+    pool: &Pool<Sqlite>,
+    artist_id: i64,
+    name: &str,
+    pic_url: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO followed_artists (artist_id, name, pic_url, last_release_at, followed_at)
+        VALUES (?, ?, ?, 0, ?)
+        ON CONFLICT(artist_id) DO UPDATE SET name = excluded.name, pic_url = excluded.pic_url
+        "#,
+    )
+    .bind(artist_id)
+    .bind(name)
+    .bind(pic_url)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Stop following an artist
+pub async fn unfollow_artist(pool: &Pool<Sqlite>, artist_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM followed_artists WHERE artist_id = ?")
+        .bind(artist_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Remember the newest release seen for an artist (milliseconds since the epoch)
+pub async fn set_artist_last_release(
+    pool: &Pool<Sqlite>,
+    artist_id: i64,
+    published_at: i64,
+) -> Result<()> {
+    sqlx::query("UPDATE followed_artists SET last_release_at = ? WHERE artist_id = ?")
+        .bind(published_at)
+        .bind(artist_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Blocked song and artist operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::{DbBlockedArtist, DbBlockedSong, SongId};
+
+/// Get all blocked songs, most recently blocked first
+pub async fn get_blocked_songs(pool: &Pool<Sqlite>) -> Result<Vec<DbBlockedSong>> {
+    let songs =
+        sqlx::query_as::<_, DbBlockedSong>("SELECT * FROM blocked_songs ORDER BY blocked_at DESC")
+            .fetch_all(pool)
+            .await?;
+    Ok(songs)
+}
+
+/// Block a song; title and artist are kept for the Settings list
+pub async fn block_song(
+    pool: &Pool<Sqlite>,
+    song_id: SongId,
+    title: &str,
+    artist: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO blocked_songs (source, song_id, title, artist, blocked_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(source, song_id) DO UPDATE SET title = excluded.title, artist = excluded.artist
+        "#,
+    )
+    .bind(song_id.source.as_str())
+    .bind(song_id.column())
+    .bind(title)
+    .bind(artist)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Remove a song from the blocklist
+pub async fn unblock_song(pool: &Pool<Sqlite>, song_id: SongId) -> Result<()> {
+    sqlx::query("DELETE FROM blocked_songs WHERE source = ? AND song_id = ?")
+        .bind(song_id.source.as_str())
+        .bind(song_id.column())
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Get all blocked artists, most recently blocked first
+pub async fn get_blocked_artists(pool: &Pool<Sqlite>) -> Result<Vec<DbBlockedArtist>> {
+    let artists = sqlx::query_as::<_, DbBlockedArtist>(
+        "SELECT * FROM blocked_artists ORDER BY blocked_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(artists)
+}
+
+/// Block an artist by name
+pub async fn block_artist(pool: &Pool<Sqlite>, name: &str) -> Result<()> {
+    sqlx::query("INSERT OR IGNORE INTO blocked_artists (name, blocked_at) VALUES (?, ?)")
+        .bind(name)
+        .bind(current_timestamp())
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Remove an artist from the blocklist
+pub async fn unblock_artist(pool: &Pool<Sqlite>, name: &str) -> Result<()> {
+    sqlx::query("DELETE FROM blocked_artists WHERE name = ?")
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
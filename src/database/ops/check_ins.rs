@@ -0,0 +1,41 @@
+//! Daily check-in reward history operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::DbCheckIn;
+
+/// Record a claimed check-in reward
+pub async fn record_check_in(
+    pool: &Pool<Sqlite>,
+    uid: u64,
+    platform: u8,
+    points: i64,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO check_ins (uid, platform, points, claimed_at)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(uid as i64)
+    .bind(platform as i64)
+    .bind(points)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Get an account's most recent check-in rewards, newest first
+pub async fn get_check_ins(pool: &Pool<Sqlite>, uid: u64, limit: i64) -> Result<Vec<DbCheckIn>> {
+    let check_ins = sqlx::query_as::<_, DbCheckIn>(
+        "SELECT * FROM check_ins WHERE uid = ? ORDER BY claimed_at DESC, id DESC LIMIT ?",
+    )
+    .bind(uid as i64)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(check_ins)
+}
@@ -0,0 +1,77 @@
+//! Favorite song operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use crate::database::{DbFavorite, DbLikedMirror, SongId};
+
+/// Get every song ever liked, including those unliked since
+pub async fn get_favorites(pool: &Pool<Sqlite>) -> Result<Vec<DbFavorite>> {
+    let favorites = sqlx::query_as::<_, DbFavorite>("SELECT * FROM favorites")
+        .fetch_all(pool)
+        .await?;
+    Ok(favorites)
+}
+
+/// Like or unlike a song; `updated_at` is when that happened, which for
+/// states taken over from NCM is NCM's time rather than now
+pub async fn set_favorite(
+    pool: &Pool<Sqlite>,
+    song_id: SongId,
+    liked: bool,
+    updated_at: i64,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO favorites (source, song_id, liked, updated_at)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(source, song_id) DO UPDATE SET liked = excluded.liked, updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(song_id.source.as_str())
+    .bind(song_id.column())
+    .bind(liked)
+    .bind(updated_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Get the most recently synced liked songs mirror
+pub async fn get_liked_mirror(pool: &Pool<Sqlite>) -> Result<Option<DbLikedMirror>> {
+    let mirror = sqlx::query_as::<_, DbLikedMirror>(
+        "SELECT * FROM liked_mirror ORDER BY synced_at DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(mirror)
+}
+
+/// Save an account's liked songs mirror, replacing the previous copy
+pub async fn save_liked_mirror(pool: &Pool<Sqlite>, mirror: &DbLikedMirror) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO liked_mirror
+            (user_id, playlist_id, name, cover_img_url, author, songs, synced_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(mirror.user_id)
+    .bind(mirror.playlist_id)
+    .bind(&mirror.name)
+    .bind(&mirror.cover_img_url)
+    .bind(&mirror.author)
+    .bind(&mirror.songs)
+    .bind(mirror.synced_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Forget every saved liked songs mirror, e.g. on logout
+pub async fn clear_liked_mirror(pool: &Pool<Sqlite>) -> Result<()> {
+    sqlx::query("DELETE FROM liked_mirror")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -1,61 +1,41 @@
 //! Play history operations
 
 use anyhow::Result;
-use sqlx::{Pool, Sqlite};
+use sqlx::{Pool, Sqlite, SqliteConnection};
 
-use super::current_timestamp;
-use crate::database::DbSong;
+use crate::database::{DbPlayRecord, DbSong, SongId};
 
-/// Record a play event
-/// For NCM songs (negative ID), first ensure the song exists in the database
-pub async fn record_play(
-    pool: &Pool<Sqlite>,
-    song_id: i64,
+/// Record a play event (transaction version)
+/// NCM songs must already exist in the database
+pub async fn record_play_tx(
+    conn: &mut SqliteConnection,
+    song_id: SongId,
+    played_at: i64,
     listened_secs: i64,
     completed: bool,
 ) -> Result<()> {
-    let now = current_timestamp();
-
-    // For NCM songs (negative ID), we need to find the actual database ID
-    let actual_song_id = if song_id < 0 {
-        let ncm_id = (-song_id) as u64;
-        let file_path = format!("ncm://{}", ncm_id);
-
-        // Try to find existing song by file_path
-        let existing = sqlx::query_scalar::<_, i64>("SELECT id FROM songs WHERE file_path = ?")
-            .bind(&file_path)
-            .fetch_optional(pool)
-            .await?;
-
-        match existing {
-            Some(id) => id,
-            None => {
-                // Song doesn't exist in DB yet, skip recording
-                tracing::debug!("NCM song {} not in database, skipping play record", ncm_id);
-                return Ok(());
-            }
-        }
-    } else {
-        song_id
+    let Some(actual_song_id) = super::find_song_row(&mut *conn, song_id).await? else {
+        // Song doesn't exist in DB yet, skip recording
+        tracing::debug!("Song {} not in database, skipping play record", song_id);
+        return Ok(());
     };
 
     sqlx::query(
         "INSERT INTO play_history (song_id, played_at, listened_secs, completed) VALUES (?, ?, ?, ?)",
     )
     .bind(actual_song_id)
-    .bind(now)
+    .bind(played_at)
     .bind(listened_secs)
     .bind(completed)
-    .execute(pool)
+    .execute(&mut *conn)
     .await?;
 
     Ok(())
 }
 
 /// Get recently played songs (unique songs ordered by most recent play)
-/// For NCM songs (file_path starts with "ncm://"), the ID is converted to negative
 pub async fn get_recently_played(pool: &Pool<Sqlite>, limit: i64) -> Result<Vec<DbSong>> {
-    let mut songs = sqlx::query_as::<_, DbSong>(
+    let songs = sqlx::query_as::<_, DbSong>(
         r#"
         SELECT s.* FROM songs s
         INNER JOIN (
@@ -65,6 +45,7 @@ pub async fn get_recently_played(pool: &Pool<Sqlite>, limit: i64) -> Result<Vec<
             ORDER BY last_played_at DESC
             LIMIT ?
         ) ph ON s.id = ph.song_id
+        WHERE s.deleted_at IS NULL
         ORDER BY ph.last_played_at DESC
         "#,
     )
@@ -72,18 +53,45 @@ pub async fn get_recently_played(pool: &Pool<Sqlite>, limit: i64) -> Result<Vec<
     .fetch_all(pool)
     .await?;
 
-    // Convert NCM song IDs to negative for consistency with the app
-    for song in &mut songs {
-        if song.file_path.starts_with("ncm://") {
-            if let Ok(ncm_id) = song.file_path.trim_start_matches("ncm://").parse::<i64>() {
-                song.id = -ncm_id;
-            }
-        }
-    }
-
     Ok(songs)
 }
 
+/// Get every play in `[start, end)` (Unix seconds) with its song's title and artist
+pub async fn get_play_records(
+    pool: &Pool<Sqlite>,
+    start: i64,
+    end: i64,
+) -> Result<Vec<DbPlayRecord>> {
+    let records = sqlx::query_as::<_, DbPlayRecord>(
+        r#"
+        SELECT ph.song_id, s.title, s.artist, ph.played_at, ph.listened_secs
+        FROM play_history ph
+        INNER JOIN songs s ON s.id = ph.song_id
+        WHERE ph.played_at >= ? AND ph.played_at < ?
+        ORDER BY ph.played_at
+        "#,
+    )
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+    Ok(records)
+}
+
+/// Get the years (local time) that have play history, newest first
+pub async fn get_play_history_years(pool: &Pool<Sqlite>) -> Result<Vec<i32>> {
+    let years = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT DISTINCT CAST(strftime('%Y', played_at, 'unixepoch', 'localtime') AS INTEGER) AS year
+        FROM play_history
+        ORDER BY year DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(years.into_iter().map(|y| y as i32).collect())
+}
+
 /// Get play count for a song
 pub async fn get_play_count(pool: &Pool<Sqlite>, song_id: i64) -> Result<i64> {
     let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM play_history WHERE song_id = ?")
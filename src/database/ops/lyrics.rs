@@ -0,0 +1,76 @@
+//! Lyrics preference and sharing operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::{DbLyricsPreference, SongId};
+
+/// Get the lyrics sub-line preference saved for a song
+pub async fn get_lyrics_preference(
+    pool: &Pool<Sqlite>,
+    song_id: SongId,
+) -> Result<Option<DbLyricsPreference>> {
+    let preference = sqlx::query_as::<_, DbLyricsPreference>(
+        "SELECT * FROM lyrics_preferences WHERE source = ? AND song_id = ?",
+    )
+    .bind(song_id.source.as_str())
+    .bind(song_id.column())
+    .fetch_optional(pool)
+    .await?;
+    Ok(preference)
+}
+
+/// Save which lyrics sub-lines to show for a song
+pub async fn save_lyrics_preference(
+    pool: &Pool<Sqlite>,
+    song_id: SongId,
+    show_translation: bool,
+    show_romanization: bool,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO lyrics_preferences
+            (source, song_id, show_translation, show_romanization, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(song_id.source.as_str())
+    .bind(song_id.column())
+    .bind(show_translation)
+    .bind(show_romanization)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Get the hash of a song's LRC file as it was when last shared or passed on
+pub async fn get_lyrics_share_hash(pool: &Pool<Sqlite>, song_id: SongId) -> Result<Option<String>> {
+    let hash = sqlx::query_scalar::<_, String>(
+        "SELECT content_hash FROM lyrics_shares WHERE source = ? AND song_id = ?",
+    )
+    .bind(song_id.source.as_str())
+    .bind(song_id.column())
+    .fetch_optional(pool)
+    .await?;
+    Ok(hash)
+}
+
+/// Remember a song's LRC file as shared or passed on
+pub async fn save_lyrics_share_hash(
+    pool: &Pool<Sqlite>,
+    song_id: SongId,
+    content_hash: &str,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT OR REPLACE INTO lyrics_shares (source, song_id, content_hash, updated_at) VALUES (?, ?, ?, ?)",
+    )
+    .bind(song_id.source.as_str())
+    .bind(song_id.column())
+    .bind(content_hash)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! Local song to NCM track link operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::DbNcmLink;
+
+/// Get every linked local song
+pub async fn get_ncm_links(pool: &Pool<Sqlite>) -> Result<Vec<DbNcmLink>> {
+    let links = sqlx::query_as::<_, DbNcmLink>("SELECT * FROM ncm_links")
+        .fetch_all(pool)
+        .await?;
+    Ok(links)
+}
+
+/// Link a local song to an NCM track, replacing any earlier match
+pub async fn link_ncm_song(pool: &Pool<Sqlite>, song_id: i64, ncm_id: u64) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO ncm_links (song_id, ncm_id, linked_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(song_id) DO UPDATE SET ncm_id = excluded.ncm_id, linked_at = excluded.linked_at
+        "#,
+    )
+    .bind(song_id)
+    .bind(ncm_id as i64)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Palette cache operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::DbPalette;
+
+/// Get the cached palette for an image
+pub async fn get_palette(pool: &Pool<Sqlite>, image_hash: &str) -> Result<Option<DbPalette>> {
+    let palette =
+        sqlx::query_as::<_, DbPalette>("SELECT * FROM palette_cache WHERE image_hash = ?")
+            .bind(image_hash)
+            .fetch_optional(pool)
+            .await?;
+    Ok(palette)
+}
+
+/// Store a palette, replacing any previous entry for the same image
+/// `created_at` is set to the current time
+pub async fn save_palette(pool: &Pool<Sqlite>, palette: &DbPalette) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO palette_cache
+            (image_hash, primary_color, secondary_color, tertiary_color, brightness, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&palette.image_hash)
+    .bind(palette.primary_color)
+    .bind(palette.secondary_color)
+    .bind(palette.tertiary_color)
+    .bind(palette.brightness)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -4,7 +4,7 @@ use anyhow::Result;
 use sqlx::{Pool, Sqlite};
 
 use super::current_timestamp;
-use crate::database::DbPlaybackState;
+use crate::database::{DbPlaybackState, SongId};
 
 /// Get current playback state
 pub async fn get_playback_state(pool: &Pool<Sqlite>) -> Result<DbPlaybackState> {
@@ -14,31 +14,19 @@ pub async fn get_playback_state(pool: &Pool<Sqlite>) -> Result<DbPlaybackState>
     Ok(state)
 }
 
-/// Update playback position
-/// For NCM songs (negative ID), converts to database ID by looking up the song
+/// Update playback position, also in the newest queue snapshot
+/// NCM songs are looked up by their track to find the library row
 pub async fn update_playback_position(
     pool: &Pool<Sqlite>,
-    song_id: Option<i64>,
+    song_id: Option<SongId>,
     queue_position: i64,
     position_secs: f64,
 ) -> Result<()> {
     let now = current_timestamp();
 
-    // Convert NCM song ID (negative) to database ID
-    let db_song_id = if let Some(id) = song_id {
-        if id < 0 {
-            // NCM song - look up by file_path
-            let ncm_id = (-id) as u64;
-            let file_path = format!("ncm://{}", ncm_id);
-            sqlx::query_scalar::<_, i64>("SELECT id FROM songs WHERE file_path = ?")
-                .bind(&file_path)
-                .fetch_optional(pool)
-                .await?
-        } else {
-            Some(id)
-        }
-    } else {
-        None
+    let db_song_id = match song_id {
+        Some(id) => super::find_song_row(pool, id).await?,
+        None => None,
     };
 
     sqlx::query(
@@ -50,6 +38,7 @@ pub async fn update_playback_position(
     .bind(now)
     .execute(pool)
     .await?;
+    super::update_queue_snapshot_position(pool, queue_position).await?;
 
     Ok(())
 }
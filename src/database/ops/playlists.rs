@@ -4,7 +4,9 @@ use anyhow::Result;
 use sqlx::{Pool, Sqlite};
 
 use super::current_timestamp;
-use crate::database::{DbPlaylist, DbPlaylistSongWithDate, DbSong, NewPlaylist};
+use crate::database::{
+    DbPlaylist, DbPlaylistPreference, DbPlaylistSongWithDate, DbSong, NewPlaylist,
+};
 
 /// Create a new playlist
 pub async fn create_playlist(pool: &Pool<Sqlite>, playlist: NewPlaylist) -> Result<i64> {
@@ -27,18 +29,22 @@ pub async fn create_playlist(pool: &Pool<Sqlite>, playlist: NewPlaylist) -> Resu
 
 /// Get all playlists
 pub async fn get_all_playlists(pool: &Pool<Sqlite>) -> Result<Vec<DbPlaylist>> {
-    let playlists = sqlx::query_as::<_, DbPlaylist>("SELECT * FROM playlists ORDER BY name")
-        .fetch_all(pool)
-        .await?;
+    let playlists = sqlx::query_as::<_, DbPlaylist>(
+        "SELECT * FROM playlists WHERE deleted_at IS NULL ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
     Ok(playlists)
 }
 
 /// Get playlist by id
 pub async fn get_playlist(pool: &Pool<Sqlite>, id: i64) -> Result<Option<DbPlaylist>> {
-    let playlist = sqlx::query_as::<_, DbPlaylist>("SELECT * FROM playlists WHERE id = ?")
-        .bind(id)
-        .fetch_optional(pool)
-        .await?;
+    let playlist = sqlx::query_as::<_, DbPlaylist>(
+        "SELECT * FROM playlists WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
     Ok(playlist)
 }
 
@@ -85,7 +91,7 @@ pub async fn get_playlist_songs(pool: &Pool<Sqlite>, playlist_id: i64) -> Result
         r#"
         SELECT s.* FROM songs s
         INNER JOIN playlist_songs ps ON s.id = ps.song_id
-        WHERE ps.playlist_id = ?
+        WHERE ps.playlist_id = ? AND s.deleted_at IS NULL
         ORDER BY ps.position
         "#,
     )
@@ -104,7 +110,7 @@ pub async fn get_playlist_songs_with_date(
         r#"
         SELECT s.*, ps.added_at FROM songs s
         INNER JOIN playlist_songs ps ON s.id = ps.song_id
-        WHERE ps.playlist_id = ?
+        WHERE ps.playlist_id = ? AND s.deleted_at IS NULL
         ORDER BY ps.position
         "#,
     )
@@ -114,6 +120,30 @@ pub async fn get_playlist_songs_with_date(
     Ok(songs)
 }
 
+/// Distinct song cover paths of a playlist, in playlist order
+pub async fn get_playlist_cover_paths(
+    pool: &Pool<Sqlite>,
+    playlist_id: i64,
+    limit: i64,
+) -> Result<Vec<String>> {
+    let paths = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT s.cover_path FROM songs s
+        INNER JOIN playlist_songs ps ON s.id = ps.song_id
+        WHERE ps.playlist_id = ? AND s.deleted_at IS NULL
+          AND s.cover_path IS NOT NULL AND s.cover_path != ''
+        GROUP BY s.cover_path
+        ORDER BY MIN(ps.position)
+        LIMIT ?
+        "#,
+    )
+    .bind(playlist_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(paths)
+}
+
 /// Remove song from playlist
 pub async fn remove_song_from_playlist(
     pool: &Pool<Sqlite>,
@@ -128,15 +158,6 @@ pub async fn remove_song_from_playlist(
     Ok(())
 }
 
-/// Delete playlist
-pub async fn delete_playlist(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
-    sqlx::query("DELETE FROM playlists WHERE id = ?")
-        .bind(id)
-        .execute(pool)
-        .await?;
-    Ok(())
-}
-
 /// Update playlist name and description
 pub async fn update_playlist(
     pool: &Pool<Sqlite>,
@@ -174,3 +195,42 @@ pub async fn update_playlist_full(
         .await?;
     Ok(())
 }
+
+/// Get the playback preferences saved for a playlist
+pub async fn get_playlist_preference(
+    pool: &Pool<Sqlite>,
+    playlist_id: i64,
+) -> Result<Option<DbPlaylistPreference>> {
+    let preference = sqlx::query_as::<_, DbPlaylistPreference>(
+        "SELECT * FROM playlist_preferences WHERE playlist_id = ?",
+    )
+    .bind(playlist_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(preference)
+}
+
+/// Save playback preferences for a playlist
+pub async fn save_playlist_preference(
+    pool: &Pool<Sqlite>,
+    playlist_id: i64,
+    shuffle: bool,
+    quality: Option<i64>,
+    append_to_queue: bool,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO playlist_preferences
+            (playlist_id, shuffle, quality, append_to_queue, updated_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(playlist_id)
+    .bind(shuffle)
+    .bind(quality)
+    .bind(append_to_queue)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
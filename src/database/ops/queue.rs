@@ -80,27 +80,18 @@ pub async fn set_queue_tx(
 }
 
 /// Get current queue
-/// For NCM songs (file_path starts with "ncm://"), the ID is converted to negative
 pub async fn get_queue(pool: &Pool<Sqlite>) -> Result<Vec<DbSong>> {
-    let mut songs = sqlx::query_as::<_, DbSong>(
+    let songs = sqlx::query_as::<_, DbSong>(
         r#"
         SELECT s.* FROM songs s
         INNER JOIN queue q ON s.id = q.song_id
+        WHERE s.deleted_at IS NULL
         ORDER BY q.position
         "#,
     )
     .fetch_all(pool)
     .await?;
 
-    // Convert NCM song IDs to negative for consistency with the app
-    for song in &mut songs {
-        if song.file_path.starts_with("ncm://") {
-            if let Ok(ncm_id) = song.file_path.trim_start_matches("ncm://").parse::<i64>() {
-                song.id = -ncm_id;
-            }
-        }
-    }
-
     Ok(songs)
 }
 
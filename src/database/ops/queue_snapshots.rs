@@ -0,0 +1,217 @@
+//! Queue snapshot operations
+//!
+//! Every saved queue is also kept as a snapshot so earlier sessions can be
+//! restored. Edits to the same queue (adding a few songs, removing some)
+//! refresh the newest snapshot instead of adding one per change; a queue
+//! that mostly differs from it starts a new snapshot.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use sqlx::{FromRow, Pool, Sqlite, SqliteConnection};
+
+use super::current_timestamp;
+use crate::database::{DbQueueSnapshot, DbSong};
+
+/// Snapshots kept, newest first
+const KEEP_SNAPSHOTS: i64 = 30;
+
+/// What saving a queue does to the snapshots
+#[derive(Debug, PartialEq, Eq)]
+enum SnapshotWrite {
+    Skip,
+    /// Replace the songs of the newest snapshot with this ID
+    Refresh(i64),
+    Insert,
+}
+
+/// A queue sharing at least half of the larger of the two song lists with
+/// the newest snapshot is the same session
+fn snapshot_write(latest: Option<(i64, &[i64])>, song_ids: &[i64]) -> SnapshotWrite {
+    if song_ids.is_empty() {
+        return SnapshotWrite::Skip;
+    }
+    let Some((id, latest_ids)) = latest else {
+        return SnapshotWrite::Insert;
+    };
+    let latest_set: HashSet<i64> = latest_ids.iter().copied().collect();
+    let shared = song_ids
+        .iter()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .filter(|id| latest_set.contains(*id))
+        .count();
+    if shared * 2 >= song_ids.len().max(latest_ids.len()) {
+        SnapshotWrite::Refresh(id)
+    } else {
+        SnapshotWrite::Insert
+    }
+}
+
+fn parse_song_ids(song_ids: &str) -> Result<Vec<i64>> {
+    serde_json::from_str(song_ids).context("Malformed queue snapshot")
+}
+
+/// Record a saved queue of song row IDs (transaction version)
+pub async fn save_queue_snapshot_tx(conn: &mut SqliteConnection, song_ids: &[i64]) -> Result<()> {
+    let latest: Option<(i64, String)> =
+        sqlx::query_as("SELECT id, song_ids FROM queue_snapshots ORDER BY id DESC LIMIT 1")
+            .fetch_optional(&mut *conn)
+            .await?;
+    let latest_ids = match &latest {
+        Some((_, ids)) => parse_song_ids(ids).unwrap_or_default(),
+        None => Vec::new(),
+    };
+    let latest = latest.as_ref().map(|(id, _)| (*id, latest_ids.as_slice()));
+
+    let encoded = serde_json::to_string(song_ids)?;
+    let now = current_timestamp();
+    match snapshot_write(latest, song_ids) {
+        SnapshotWrite::Skip => return Ok(()),
+        SnapshotWrite::Refresh(id) => {
+            sqlx::query(
+                "UPDATE queue_snapshots SET song_ids = ?, song_count = ?, saved_at = ? WHERE id = ?",
+            )
+            .bind(&encoded)
+            .bind(song_ids.len() as i64)
+            .bind(now)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        }
+        SnapshotWrite::Insert => {
+            // A restored session moves to the top instead of showing twice
+            sqlx::query("DELETE FROM queue_snapshots WHERE song_ids = ?")
+                .bind(&encoded)
+                .execute(&mut *conn)
+                .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO queue_snapshots (song_ids, song_count, queue_position, saved_at)
+                VALUES (?, ?, COALESCE((SELECT queue_position FROM playback_state WHERE id = 1), 0), ?)
+                "#,
+            )
+            .bind(&encoded)
+            .bind(song_ids.len() as i64)
+            .bind(now)
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query(
+                "DELETE FROM queue_snapshots WHERE id NOT IN (SELECT id FROM queue_snapshots ORDER BY id DESC LIMIT ?)",
+            )
+            .bind(KEEP_SNAPSHOTS)
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Remember which song of the newest snapshot is playing
+pub async fn update_queue_snapshot_position(
+    pool: &Pool<Sqlite>,
+    queue_position: i64,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE queue_snapshots SET queue_position = ?, saved_at = ? WHERE id = (SELECT MAX(id) FROM queue_snapshots)",
+    )
+    .bind(queue_position)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Get the kept snapshots, newest first
+pub async fn get_queue_snapshots(pool: &Pool<Sqlite>) -> Result<Vec<DbQueueSnapshot>> {
+    let snapshots = sqlx::query_as::<_, DbQueueSnapshot>(
+        r#"
+        SELECT q.*, s.title AS first_title FROM queue_snapshots q
+        LEFT JOIN songs s ON s.id = json_extract(q.song_ids, '$[0]')
+        ORDER BY q.saved_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(snapshots)
+}
+
+/// A snapshot song with its place in the saved queue
+#[derive(FromRow)]
+struct SnapshotSong {
+    position: i64,
+    #[sqlx(flatten)]
+    song: DbSong,
+}
+
+/// Songs of a snapshot still in the library, with the index of the song
+/// that was playing among them
+pub async fn get_queue_snapshot_songs(
+    pool: &Pool<Sqlite>,
+    snapshot_id: i64,
+) -> Result<(Vec<DbSong>, usize)> {
+    let (song_ids, queue_position): (String, i64) =
+        sqlx::query_as("SELECT song_ids, queue_position FROM queue_snapshots WHERE id = ?")
+            .bind(snapshot_id)
+            .fetch_one(pool)
+            .await?;
+    // Checked before it goes into the query
+    parse_song_ids(&song_ids)?;
+
+    let rows = sqlx::query_as::<_, SnapshotSong>(
+        r#"
+        SELECT q.key AS position, s.* FROM json_each(?) q
+        INNER JOIN songs s ON s.id = q.value
+        WHERE s.deleted_at IS NULL
+        ORDER BY q.key
+        "#,
+    )
+    .bind(&song_ids)
+    .fetch_all(pool)
+    .await?;
+
+    let index = rows
+        .iter()
+        .position(|row| row.position == queue_position)
+        .unwrap_or(0);
+    Ok((rows.into_iter().map(|row| row.song).collect(), index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_queue_is_inserted() {
+        assert_eq!(snapshot_write(None, &[1, 2]), SnapshotWrite::Insert);
+        assert_eq!(snapshot_write(None, &[]), SnapshotWrite::Skip);
+    }
+
+    #[test]
+    fn test_edits_refresh_the_session() {
+        let latest = [1, 2, 3, 4];
+        assert_eq!(
+            snapshot_write(Some((7, &latest)), &[1, 2, 3, 4, 5]),
+            SnapshotWrite::Refresh(7)
+        );
+        assert_eq!(
+            snapshot_write(Some((7, &latest)), &[2, 3]),
+            SnapshotWrite::Refresh(7)
+        );
+    }
+
+    #[test]
+    fn test_new_queue_starts_a_session() {
+        let latest = [1, 2, 3, 4];
+        assert_eq!(
+            snapshot_write(Some((7, &latest)), &[9, 8, 7, 6]),
+            SnapshotWrite::Insert
+        );
+        // Appending a long playlist is a different session too
+        let appended: Vec<i64> = (1..=20).collect();
+        assert_eq!(
+            snapshot_write(Some((7, &latest)), &appended),
+            SnapshotWrite::Insert
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Search history operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+
+/// Keywords kept in the history; older ones are dropped
+const MAX_ENTRIES: i64 = 50;
+
+/// Record a submitted search, moving a repeated keyword to the top
+pub async fn record_search(pool: &Pool<Sqlite>, keyword: &str) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO search_history (keyword, searched_at)
+        VALUES (?, ?)
+        ON CONFLICT(keyword) DO UPDATE SET searched_at = excluded.searched_at
+        "#,
+    )
+    .bind(keyword)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        DELETE FROM search_history WHERE keyword NOT IN (
+            SELECT keyword FROM search_history ORDER BY searched_at DESC LIMIT ?
+        )
+        "#,
+    )
+    .bind(MAX_ENTRIES)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Get recent search keywords, newest first
+pub async fn get_search_history(pool: &Pool<Sqlite>, limit: i64) -> Result<Vec<String>> {
+    let keywords = sqlx::query_scalar::<_, String>(
+        "SELECT keyword FROM search_history ORDER BY searched_at DESC, rowid DESC LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(keywords)
+}
+
+/// Remove one keyword from the history
+pub async fn delete_search_history(pool: &Pool<Sqlite>, keyword: &str) -> Result<()> {
+    sqlx::query("DELETE FROM search_history WHERE keyword = ?")
+        .bind(keyword)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Remove every keyword from the history
+pub async fn clear_search_history(pool: &Pool<Sqlite>) -> Result<()> {
+    sqlx::query("DELETE FROM search_history")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Song tempo, key and energy analysis operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::{DbSong, DbSongAnalysis};
+
+/// Get every stored analysis
+pub async fn get_song_analyses(pool: &Pool<Sqlite>) -> Result<Vec<DbSongAnalysis>> {
+    let analyses = sqlx::query_as::<_, DbSongAnalysis>("SELECT * FROM song_analysis")
+        .fetch_all(pool)
+        .await?;
+    Ok(analyses)
+}
+
+/// Store the analysis of a song, replacing an earlier one
+pub async fn save_song_analysis(
+    pool: &Pool<Sqlite>,
+    song_id: i64,
+    bpm: Option<f64>,
+    musical_key: Option<i64>,
+    energy: Option<f64>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO song_analysis (song_id, bpm, musical_key, energy, analyzed_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(song_id) DO UPDATE SET
+            bpm = excluded.bpm,
+            musical_key = excluded.musical_key,
+            energy = excluded.energy,
+            analyzed_at = excluded.analyzed_at
+        "#,
+    )
+    .bind(song_id)
+    .bind(bpm)
+    .bind(musical_key)
+    .bind(energy)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Local songs that have not been analyzed yet
+pub async fn get_unanalyzed_songs(pool: &Pool<Sqlite>) -> Result<Vec<DbSong>> {
+    let songs = sqlx::query_as::<_, DbSong>(
+        r#"
+        SELECT * FROM songs
+        WHERE source = 'local'
+          AND deleted_at IS NULL
+          AND id NOT IN (SELECT song_id FROM song_analysis)
+        ORDER BY id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(songs)
+}
@@ -0,0 +1,56 @@
+//! Listen mode fingerprint operations
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::{DbSong, DbSongFingerprint};
+
+/// Get the stored fingerprints of songs in the library
+pub async fn get_song_fingerprints(pool: &Pool<Sqlite>) -> Result<Vec<DbSongFingerprint>> {
+    let fingerprints = sqlx::query_as::<_, DbSongFingerprint>(
+        r#"
+        SELECT f.* FROM song_fingerprints f
+        INNER JOIN songs s ON s.id = f.song_id
+        WHERE s.deleted_at IS NULL
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(fingerprints)
+}
+
+/// Store the fingerprint of a song, replacing an earlier one
+pub async fn save_song_fingerprint(pool: &Pool<Sqlite>, song_id: i64, words: &[u8]) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO song_fingerprints (song_id, words, fingerprinted_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(song_id) DO UPDATE SET
+            words = excluded.words,
+            fingerprinted_at = excluded.fingerprinted_at
+        "#,
+    )
+    .bind(song_id)
+    .bind(words)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Local songs that have not been fingerprinted yet
+pub async fn get_unfingerprinted_songs(pool: &Pool<Sqlite>) -> Result<Vec<DbSong>> {
+    let songs = sqlx::query_as::<_, DbSong>(
+        r#"
+        SELECT * FROM songs
+        WHERE source = 'local'
+          AND deleted_at IS NULL
+          AND id NOT IN (SELECT song_id FROM song_fingerprints)
+        ORDER BY id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(songs)
+}
@@ -0,0 +1,54 @@
+//! Display override operations for NCM track metadata
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::DbSongOverride;
+
+/// Get every song override
+pub async fn get_song_overrides(pool: &Pool<Sqlite>) -> Result<Vec<DbSongOverride>> {
+    let overrides = sqlx::query_as::<_, DbSongOverride>("SELECT * FROM song_overrides")
+        .fetch_all(pool)
+        .await?;
+    Ok(overrides)
+}
+
+/// Save an override, replacing an earlier one for the same track
+///
+/// The NCM metadata of an earlier override is kept, so it always holds what
+/// NCM itself says.
+pub async fn set_song_override(pool: &Pool<Sqlite>, song: &DbSongOverride) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO song_overrides
+            (ncm_id, title, artist, album, original_title, original_artist, original_album, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(ncm_id) DO UPDATE SET
+            title = excluded.title,
+            artist = excluded.artist,
+            album = excluded.album,
+            updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(song.ncm_id)
+    .bind(&song.title)
+    .bind(&song.artist)
+    .bind(&song.album)
+    .bind(&song.original_title)
+    .bind(&song.original_artist)
+    .bind(&song.original_album)
+    .bind(current_timestamp())
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Go back to NCM's metadata for a track
+pub async fn remove_song_override(pool: &Pool<Sqlite>, ncm_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM song_overrides WHERE ncm_id = ?")
+        .bind(ncm_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
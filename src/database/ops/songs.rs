@@ -1,15 +1,70 @@
 //! Song CRUD operations
 
 use anyhow::Result;
-use sqlx::{Pool, Sqlite};
+use sqlx::{Executor, Pool, Sqlite};
 
 use super::current_timestamp;
-use crate::database::{DbSong, NewSong};
+use crate::database::{DbSong, IdSource, NewSong, SongId};
+
+/// Library row of a song, looking NCM tracks up by their track ID
+///
+/// `None` for NCM tracks that were never saved to the library.
+pub async fn find_song_row<'c, E>(executor: E, id: SongId) -> Result<Option<i64>>
+where
+    E: Executor<'c, Database = Sqlite>,
+{
+    match id.source {
+        IdSource::Library => Ok(Some(id.id as i64)),
+        IdSource::Ncm => Ok(sqlx::query_scalar::<_, i64>(
+            "SELECT id FROM songs WHERE source = 'ncm' AND source_id = ?",
+        )
+        .bind(id.id as i64)
+        .fetch_optional(executor)
+        .await?),
+    }
+}
 
 /// Insert a new song, returns the new song id
+///
+/// A trashed song at the same path is restored instead, keeping its
+/// playlists and history.
 pub async fn insert_song(pool: &Pool<Sqlite>, song: NewSong) -> Result<i64> {
     let now = current_timestamp();
 
+    let trashed: Option<i64> =
+        sqlx::query_scalar("SELECT id FROM songs WHERE file_path = ? AND deleted_at IS NOT NULL")
+            .bind(&song.file_path)
+            .fetch_optional(pool)
+            .await?;
+    if let Some(id) = trashed {
+        // Keeps the row's plays and playlist entries, but the file may have
+        // been replaced since it was trashed, so take the new scan's tags
+        sqlx::query(
+            r#"
+            UPDATE songs SET deleted_at = NULL, title = ?, artist = ?, album = ?, duration_secs = ?,
+                track_number = ?, year = ?, genre = ?, cover_path = ?, file_hash = ?, file_size = ?,
+                format = ?, last_modified = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&song.title)
+        .bind(&song.artist)
+        .bind(&song.album)
+        .bind(song.duration_secs)
+        .bind(song.track_number)
+        .bind(song.year)
+        .bind(&song.genre)
+        .bind(&song.cover_path)
+        .bind(&song.file_hash)
+        .bind(song.file_size)
+        .bind(&song.format)
+        .bind(now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+        return Ok(id);
+    }
+
     let result = sqlx::query(
         r#"
         INSERT INTO songs (file_path, title, artist, album, duration_secs, track_number, year, genre, cover_path, file_hash, file_size, format, last_modified, created_at)
@@ -45,21 +100,24 @@ pub async fn get_song(pool: &Pool<Sqlite>, id: i64) -> Result<Option<DbSong>> {
     Ok(song)
 }
 
-/// Get song by file path
+/// Get song by file path, unless it is in the trash
 pub async fn get_song_by_path(pool: &Pool<Sqlite>, path: &str) -> Result<Option<DbSong>> {
-    let song = sqlx::query_as::<_, DbSong>("SELECT * FROM songs WHERE file_path = ?")
-        .bind(path)
-        .fetch_optional(pool)
-        .await?;
+    let song = sqlx::query_as::<_, DbSong>(
+        "SELECT * FROM songs WHERE file_path = ? AND deleted_at IS NULL",
+    )
+    .bind(path)
+    .fetch_optional(pool)
+    .await?;
     Ok(song)
 }
 
 /// Get all songs
 pub async fn get_all_songs(pool: &Pool<Sqlite>) -> Result<Vec<DbSong>> {
-    let songs =
-        sqlx::query_as::<_, DbSong>("SELECT * FROM songs ORDER BY artist, album, track_number")
-            .fetch_all(pool)
-            .await?;
+    let songs = sqlx::query_as::<_, DbSong>(
+        "SELECT * FROM songs WHERE deleted_at IS NULL ORDER BY artist, album, track_number",
+    )
+    .fetch_all(pool)
+    .await?;
     Ok(songs)
 }
 
@@ -67,7 +125,11 @@ pub async fn get_all_songs(pool: &Pool<Sqlite>) -> Result<Vec<DbSong>> {
 pub async fn search_songs(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<DbSong>> {
     let pattern = format!("%{}%", query);
     let songs = sqlx::query_as::<_, DbSong>(
-        "SELECT * FROM songs WHERE title LIKE ? OR artist LIKE ? OR album LIKE ? ORDER BY title",
+        r#"
+        SELECT * FROM songs
+        WHERE (title LIKE ? OR artist LIKE ? OR album LIKE ?) AND deleted_at IS NULL
+        ORDER BY title
+        "#,
     )
     .bind(&pattern)
     .bind(&pattern)
@@ -77,31 +139,90 @@ pub async fn search_songs(pool: &Pool<Sqlite>, query: &str) -> Result<Vec<DbSong
     Ok(songs)
 }
 
-/// Delete song by id
-pub async fn delete_song(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
-    sqlx::query("DELETE FROM songs WHERE id = ?")
-        .bind(id)
-        .execute(pool)
-        .await?;
-    Ok(())
-}
+/// Search scanned library songs by title, artist, or album through the
+/// full-text index, best matches first
+///
+/// Every word of the query must match the start of a word in the song. Falls
+/// back to substring matching when the index finds nothing, since the default
+/// tokenizer does not split CJK text into words. NCM entries are skipped.
+pub async fn search_local_songs(
+    pool: &Pool<Sqlite>,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<DbSong>> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
 
-/// Delete song by file path
-pub async fn delete_song_by_path(pool: &Pool<Sqlite>, path: &str) -> Result<()> {
-    sqlx::query("DELETE FROM songs WHERE file_path = ?")
-        .bind(path)
-        .execute(pool)
-        .await?;
-    Ok(())
+    let songs = sqlx::query_as::<_, DbSong>(
+        r#"
+        SELECT s.* FROM songs_fts
+        JOIN songs s ON s.id = songs_fts.rowid
+        WHERE songs_fts MATCH ? AND s.file_path NOT LIKE 'ncm://%' AND s.deleted_at IS NULL
+        ORDER BY songs_fts.rank
+        LIMIT ?
+        "#,
+    )
+    .bind(terms.join(" "))
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    if !songs.is_empty() {
+        return Ok(songs);
+    }
+
+    let pattern = format!("%{}%", query.trim());
+    let songs = sqlx::query_as::<_, DbSong>(
+        r#"
+        SELECT * FROM songs
+        WHERE (title LIKE ? OR artist LIKE ? OR album LIKE ?)
+          AND file_path NOT LIKE 'ncm://%'
+          AND deleted_at IS NULL
+        ORDER BY title
+        LIMIT ?
+        "#,
+    )
+    .bind(&pattern)
+    .bind(&pattern)
+    .bind(&pattern)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(songs)
 }
 
 /// Update song file path (for handling file renames)
+///
+/// A trashed song still at `new_path` is purged first: the file there is the
+/// renamed one now, and the path is unique.
 pub async fn update_song_path(pool: &Pool<Sqlite>, old_path: &str, new_path: &str) -> Result<()> {
     let now = super::current_timestamp();
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM songs WHERE file_path = ? AND deleted_at IS NOT NULL")
+        .bind(new_path)
+        .execute(&mut *tx)
+        .await?;
     sqlx::query("UPDATE songs SET file_path = ?, last_modified = ? WHERE file_path = ?")
         .bind(new_path)
         .bind(now)
         .bind(old_path)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Set the cover of a local song
+pub async fn update_song_cover(pool: &Pool<Sqlite>, path: &str, cover_path: &str) -> Result<()> {
+    let now = super::current_timestamp();
+    sqlx::query("UPDATE songs SET cover_path = ?, last_modified = ? WHERE file_path = ?")
+        .bind(cover_path)
+        .bind(now)
+        .bind(path)
         .execute(pool)
         .await?;
     Ok(())
@@ -115,16 +236,8 @@ pub async fn update_song_path(pool: &Pool<Sqlite>, old_path: &str, new_path: &st
 pub async fn upsert_ncm_song(pool: &Pool<Sqlite>, song: &DbSong) -> Result<i64> {
     let now = super::current_timestamp();
 
-    // Determine NCM ID from song.id (negative) or extract from file_path
-    let ncm_id = if song.id < 0 {
-        (-song.id) as u64
-    } else if song.file_path.starts_with("ncm://") {
-        song.file_path
-            .trim_start_matches("ncm://")
-            .parse()
-            .unwrap_or(0)
-    } else {
-        song.id as u64
+    let Some(ncm_id) = song.id.ncm_id() else {
+        anyhow::bail!("Song {} is not an NCM track", song.id);
     };
 
     // NCM songs are identified by "ncm://<id>" in file_path for DB storage
@@ -140,10 +253,7 @@ pub async fn upsert_ncm_song(pool: &Pool<Sqlite>, song: &DbSong) -> Result<i64>
     });
 
     // Check if exists
-    let existing = sqlx::query_scalar::<_, i64>("SELECT id FROM songs WHERE file_path = ?")
-        .bind(&file_path)
-        .fetch_optional(pool)
-        .await?;
+    let existing = find_song_row(pool, SongId::ncm(ncm_id)).await?;
 
     if let Some(id) = existing {
         // Update - only update cover_path if we have a valid local path
@@ -192,9 +302,10 @@ pub async fn upsert_ncm_song(pool: &Pool<Sqlite>, song: &DbSong) -> Result<i64>
             r#"
             INSERT INTO songs (
                 file_path, title, artist, album, duration_secs, 
-                cover_path, format, last_modified, created_at, play_count
+                cover_path, format, last_modified, created_at, play_count,
+                source, source_id
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 0)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 'ncm', ?)
             "#,
         )
         .bind(&file_path)
@@ -206,6 +317,7 @@ pub async fn upsert_ncm_song(pool: &Pool<Sqlite>, song: &DbSong) -> Result<i64>
         .bind("ncm")
         .bind(now)
         .bind(now)
+        .bind(ncm_id as i64)
         .execute(pool)
         .await?;
 
@@ -217,15 +329,8 @@ pub async fn upsert_ncm_song(pool: &Pool<Sqlite>, song: &DbSong) -> Result<i64>
 pub async fn upsert_ncm_song_tx(conn: &mut sqlx::SqliteConnection, song: &DbSong) -> Result<i64> {
     let now = super::current_timestamp();
 
-    let ncm_id = if song.id < 0 {
-        (-song.id) as u64
-    } else if song.file_path.starts_with("ncm://") {
-        song.file_path
-            .trim_start_matches("ncm://")
-            .parse()
-            .unwrap_or(0)
-    } else {
-        song.id as u64
+    let Some(ncm_id) = song.id.ncm_id() else {
+        anyhow::bail!("Song {} is not an NCM track", song.id);
     };
 
     let file_path = format!("ncm://{}", ncm_id);
@@ -235,10 +340,7 @@ pub async fn upsert_ncm_song_tx(conn: &mut sqlx::SqliteConnection, song: &DbSong
     let cover_path = song.cover_path.clone();
 
     // Check if exists
-    let existing = sqlx::query_scalar::<_, i64>("SELECT id FROM songs WHERE file_path = ?")
-        .bind(&file_path)
-        .fetch_optional(&mut *conn)
-        .await?;
+    let existing = find_song_row(&mut *conn, SongId::ncm(ncm_id)).await?;
 
     if let Some(id) = existing {
         // Only update cover_path if we have a new value AND it's a local path
@@ -291,9 +393,10 @@ pub async fn upsert_ncm_song_tx(conn: &mut sqlx::SqliteConnection, song: &DbSong
             r#"
             INSERT INTO songs (
                 file_path, title, artist, album, duration_secs, 
-                cover_path, format, last_modified, created_at, play_count
+                cover_path, format, last_modified, created_at, play_count,
+                source, source_id
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 0)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 'ncm', ?)
             "#,
         )
         .bind(&file_path)
@@ -305,6 +408,7 @@ pub async fn upsert_ncm_song_tx(conn: &mut sqlx::SqliteConnection, song: &DbSong
         .bind("ncm")
         .bind(now)
         .bind(now)
+        .bind(ncm_id as i64)
         .execute(&mut *conn)
         .await?;
 
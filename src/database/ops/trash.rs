@@ -0,0 +1,149 @@
+//! Trash for deleted playlists and songs removed from the library
+//!
+//! Deleting only sets `deleted_at`; the row and its playlist entries stay
+//! until the item is restored or purged. Everything that lists songs or
+//! playlists skips trashed rows.
+
+use anyhow::Result;
+use sqlx::{Pool, Sqlite};
+
+use super::current_timestamp;
+use crate::database::{DbTrashedPlaylist, DbTrashedSong, TRASH_RETENTION_DAYS};
+
+/// Move a playlist to the trash
+pub async fn trash_playlist(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
+    sqlx::query("UPDATE playlists SET deleted_at = ? WHERE id = ? AND deleted_at IS NULL")
+        .bind(current_timestamp())
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Move a song to the trash
+pub async fn trash_song(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
+    sqlx::query("UPDATE songs SET deleted_at = ? WHERE id = ? AND deleted_at IS NULL")
+        .bind(current_timestamp())
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Move the song at a file path to the trash
+pub async fn trash_song_by_path(pool: &Pool<Sqlite>, path: &str) -> Result<()> {
+    sqlx::query("UPDATE songs SET deleted_at = ? WHERE file_path = ? AND deleted_at IS NULL")
+        .bind(current_timestamp())
+        .bind(path)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Get trashed playlists, most recently deleted first
+pub async fn get_trashed_playlists(pool: &Pool<Sqlite>) -> Result<Vec<DbTrashedPlaylist>> {
+    let playlists = sqlx::query_as::<_, DbTrashedPlaylist>(
+        r#"
+        SELECT p.id, p.name, p.deleted_at,
+            (SELECT COUNT(*) FROM playlist_songs ps
+             INNER JOIN songs s ON s.id = ps.song_id
+             WHERE ps.playlist_id = p.id AND s.deleted_at IS NULL) AS song_count
+        FROM playlists p
+        WHERE p.deleted_at IS NOT NULL
+        ORDER BY p.deleted_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(playlists)
+}
+
+/// Get trashed songs, most recently removed first
+pub async fn get_trashed_songs(pool: &Pool<Sqlite>) -> Result<Vec<DbTrashedSong>> {
+    let songs = sqlx::query_as::<_, DbTrashedSong>(
+        r#"
+        SELECT id, title, artist, deleted_at FROM songs
+        WHERE deleted_at IS NOT NULL
+        ORDER BY deleted_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(songs)
+}
+
+/// Take a playlist back out of the trash
+pub async fn restore_playlist(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
+    sqlx::query("UPDATE playlists SET deleted_at = NULL WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Take a song back out of the trash
+pub async fn restore_song(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
+    sqlx::query("UPDATE songs SET deleted_at = NULL WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Permanently delete a trashed playlist
+pub async fn purge_playlist(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM playlists WHERE id = ? AND deleted_at IS NOT NULL")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM playlist_preferences WHERE playlist_id = ?")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Permanently delete a trashed song
+pub async fn purge_song(pool: &Pool<Sqlite>, id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM songs WHERE id = ? AND deleted_at IS NOT NULL")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Permanently delete everything trashed before `deleted_before`, returns
+/// the number of playlists and songs removed
+pub async fn purge_trash(pool: &Pool<Sqlite>, deleted_before: i64) -> Result<u64> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        DELETE FROM playlist_preferences WHERE playlist_id IN
+            (SELECT id FROM playlists WHERE deleted_at IS NOT NULL AND deleted_at < ?)
+        "#,
+    )
+    .bind(deleted_before)
+    .execute(&mut *tx)
+    .await?;
+    let playlists =
+        sqlx::query("DELETE FROM playlists WHERE deleted_at IS NOT NULL AND deleted_at < ?")
+            .bind(deleted_before)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+    let songs = sqlx::query("DELETE FROM songs WHERE deleted_at IS NOT NULL AND deleted_at < ?")
+        .bind(deleted_before)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    tx.commit().await?;
+    Ok(playlists + songs)
+}
+
+/// Permanently delete everything that has been in the trash for longer
+/// than [`TRASH_RETENTION_DAYS`]
+pub async fn purge_expired_trash(pool: &Pool<Sqlite>) -> Result<u64> {
+    purge_trash(pool, current_timestamp() - TRASH_RETENTION_DAYS * 86_400).await
+}
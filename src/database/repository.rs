@@ -2,15 +2,24 @@
 //! Delegates to ops modules for actual operations
 
 use anyhow::Result;
-use sqlx::{Pool, Sqlite, sqlite::SqlitePoolOptions};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::{Pool, Sqlite};
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
+use super::writer::WriteBuffer;
 use super::{models::*, ops, schema};
 
+/// Page cache per connection (default is ~8MB)
+const CACHE_SIZE_KIB: u64 = 32000;
+
 /// Database connection pool wrapper
 #[derive(Debug)]
 pub struct Database {
     pool: Pool<Sqlite>,
+    /// Coalesced play records and queue saves
+    writes: Arc<WriteBuffer>,
 }
 
 impl Database {
@@ -21,32 +30,73 @@ impl Database {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
+        // Pragmas go in the connect options so every pooled connection gets them
+        let options = SqliteConnectOptions::new()
+            .filename(db_path)
+            .create_if_missing(true)
+            // WAL keeps UI reads from being blocked by background writes
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(Duration::from_secs(5))
+            // Negative cache_size is in KiB
+            .pragma("cache_size", format!("-{}", CACHE_SIZE_KIB))
+            // Checkpoint every ~4MB of WAL and truncate the file back afterwards
+            .pragma("wal_autocheckpoint", "1000")
+            .pragma("journal_size_limit", "16777216");
 
         let pool = SqlitePoolOptions::new()
             .max_connections(5)
-            .connect(&db_url)
+            .connect_with(options)
             .await?;
 
-        // Enable WAL mode for better concurrent read/write performance
-        // This prevents UI reads from being blocked by background writes
-        sqlx::query("PRAGMA journal_mode = WAL")
-            .execute(&pool)
-            .await?;
+        schema::run_migrations(&pool).await?;
 
-        // Optimize SQLite for better performance
-        sqlx::query("PRAGMA synchronous = NORMAL")
-            .execute(&pool)
-            .await?;
+        let writes = WriteBuffer::start(pool.clone());
+        Ok(Self { pool, writes })
+    }
 
-        // Increase cache size (default is 2000 pages = ~8MB, set to ~32MB)
-        sqlx::query("PRAGMA cache_size = -32000")
-            .execute(&pool)
-            .await?;
+    /// Write out buffered play records and queue saves now
+    pub async fn flush(&self) -> Result<()> {
+        self.writes.flush(&self.pool).await
+    }
 
-        schema::run_migrations(&pool).await?;
+    /// Flush before reading, so the read sees buffered writes
+    ///
+    /// A failed flush only delays those writes; the read goes ahead.
+    async fn flush_before_read(&self) {
+        if let Err(e) = self.flush().await {
+            tracing::warn!("Reading without buffered writes: {}", e);
+        }
+    }
 
-        Ok(Self { pool })
+    /// Combined ceiling of the page caches of the open connections
+    pub fn cache_capacity_bytes(&self) -> u64 {
+        self.pool.size() as u64 * CACHE_SIZE_KIB * 1024
+    }
+
+    /// Release page cache memory held by idle connections
+    pub async fn shrink_memory(&self) -> Result<()> {
+        let mut connections = Vec::new();
+        for _ in 0..self.pool.num_idle() {
+            connections.push(self.pool.acquire().await?);
+        }
+        for conn in &mut connections {
+            sqlx::query("PRAGMA shrink_memory")
+                .execute(&mut **conn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered writes and fold the WAL back into the database file
+    pub async fn close(&self) -> Result<()> {
+        if let Err(e) = self.flush().await {
+            tracing::warn!("Closing with buffered writes unsaved: {}", e);
+        }
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
     }
 
     // ============ Song Operations ============
@@ -71,18 +121,18 @@ impl Database {
         ops::search_songs(&self.pool, query).await
     }
 
-    pub async fn delete_song(&self, id: i64) -> Result<()> {
-        ops::delete_song(&self.pool, id).await
-    }
-
-    pub async fn delete_song_by_path(&self, path: &str) -> Result<()> {
-        ops::delete_song_by_path(&self.pool, path).await
+    pub async fn search_local_songs(&self, query: &str, limit: i64) -> Result<Vec<DbSong>> {
+        ops::search_local_songs(&self.pool, query, limit).await
     }
 
     pub async fn update_song_path(&self, old_path: &str, new_path: &str) -> Result<()> {
         ops::update_song_path(&self.pool, old_path, new_path).await
     }
 
+    pub async fn update_song_cover(&self, path: &str, cover_path: &str) -> Result<()> {
+        ops::update_song_cover(&self.pool, path, cover_path).await
+    }
+
     pub async fn upsert_ncm_song(&self, song: &DbSong) -> Result<i64> {
         ops::upsert_ncm_song(&self.pool, song).await
     }
@@ -116,12 +166,16 @@ impl Database {
         ops::get_playlist_songs_with_date(&self.pool, playlist_id).await
     }
 
-    pub async fn remove_song_from_playlist(&self, playlist_id: i64, song_id: i64) -> Result<()> {
-        ops::remove_song_from_playlist(&self.pool, playlist_id, song_id).await
+    pub async fn get_playlist_cover_paths(
+        &self,
+        playlist_id: i64,
+        limit: i64,
+    ) -> Result<Vec<String>> {
+        ops::get_playlist_cover_paths(&self.pool, playlist_id, limit).await
     }
 
-    pub async fn delete_playlist(&self, id: i64) -> Result<()> {
-        ops::delete_playlist(&self.pool, id).await
+    pub async fn remove_song_from_playlist(&self, playlist_id: i64, song_id: i64) -> Result<()> {
+        ops::remove_song_from_playlist(&self.pool, playlist_id, song_id).await
     }
 
     pub async fn update_playlist(
@@ -158,38 +212,15 @@ impl Database {
     }
 
     /// Save queue with full song data, handling NCM songs properly
-    /// NCM songs (negative ID) will be upserted to the database first
-    /// Uses a transaction for better performance
-    pub async fn save_queue_with_songs(
-        &self,
-        songs: &[DbSong],
-        source_playlist_id: Option<i64>,
-    ) -> Result<()> {
-        use sqlx::Acquire;
-
-        let mut conn = self.pool.acquire().await?;
-        let mut tx = conn.begin().await?;
-
-        let mut db_song_ids = Vec::with_capacity(songs.len());
-
-        for song in songs {
-            if song.id < 0 || song.file_path.starts_with("ncm://") {
-                // NCM song - upsert to database and get the real ID
-                let db_id = ops::upsert_ncm_song_tx(&mut *tx, song).await?;
-                db_song_ids.push(db_id);
-            } else {
-                // Local song - use existing ID
-                db_song_ids.push(song.id);
-            }
-        }
-
-        ops::set_queue_tx(&mut *tx, &db_song_ids, source_playlist_id).await?;
-
-        tx.commit().await?;
-        Ok(())
+    /// NCM songs not in the library yet will be upserted to the database first
+    ///
+    /// The write is buffered; rapid successive saves only write the last queue.
+    pub fn save_queue_with_songs(&self, songs: Vec<DbSong>, source_playlist_id: Option<i64>) {
+        self.writes.save_queue(songs, source_playlist_id);
     }
 
     pub async fn get_queue(&self) -> Result<Vec<DbSong>> {
+        self.flush_before_read().await;
         ops::get_queue(&self.pool).await
     }
 
@@ -197,18 +228,30 @@ impl Database {
         ops::remove_from_queue(&self.pool, position).await
     }
 
+    pub async fn get_queue_snapshots(&self) -> Result<Vec<DbQueueSnapshot>> {
+        self.flush_before_read().await;
+        ops::get_queue_snapshots(&self.pool).await
+    }
+
+    pub async fn get_queue_snapshot_songs(&self, snapshot_id: i64) -> Result<(Vec<DbSong>, usize)> {
+        ops::get_queue_snapshot_songs(&self.pool, snapshot_id).await
+    }
+
     // ============ Playback State Operations ============
 
     pub async fn get_playback_state(&self) -> Result<DbPlaybackState> {
         ops::get_playback_state(&self.pool).await
     }
 
+    /// Also marks the song in the newest queue snapshot, so a queue saved just
+    /// before is written first
     pub async fn update_playback_position(
         &self,
-        song_id: Option<i64>,
+        song_id: Option<SongId>,
         queue_position: i64,
         position_secs: f64,
     ) -> Result<()> {
+        self.flush_before_read().await;
         ops::update_playback_position(&self.pool, song_id, queue_position, position_secs).await
     }
 
@@ -226,20 +269,296 @@ impl Database {
 
     // ============ Play History Operations ============
 
-    pub async fn record_play(
-        &self,
-        song_id: i64,
-        listened_secs: i64,
-        completed: bool,
-    ) -> Result<()> {
-        ops::record_play(&self.pool, song_id, listened_secs, completed).await
+    /// Record a play event; buffered and written with the next batch
+    pub fn record_play(&self, song_id: SongId, listened_secs: i64, completed: bool) {
+        self.writes.record_play(song_id, listened_secs, completed);
     }
 
     pub async fn get_recently_played(&self, limit: i64) -> Result<Vec<DbSong>> {
+        self.flush_before_read().await;
         ops::get_recently_played(&self.pool, limit).await
     }
 
     pub async fn get_play_count(&self, song_id: i64) -> Result<i64> {
+        self.flush_before_read().await;
         ops::get_play_count(&self.pool, song_id).await
     }
+
+    pub async fn get_play_records(&self, start: i64, end: i64) -> Result<Vec<DbPlayRecord>> {
+        self.flush_before_read().await;
+        ops::get_play_records(&self.pool, start, end).await
+    }
+
+    pub async fn get_play_history_years(&self) -> Result<Vec<i32>> {
+        self.flush_before_read().await;
+        ops::get_play_history_years(&self.pool).await
+    }
+
+    // ============ Palette Cache Operations ============
+
+    pub async fn get_palette(&self, image_hash: &str) -> Result<Option<DbPalette>> {
+        ops::get_palette(&self.pool, image_hash).await
+    }
+
+    pub async fn save_palette(&self, palette: &DbPalette) -> Result<()> {
+        ops::save_palette(&self.pool, palette).await
+    }
+
+    // ============ Playlist Preference Operations ============
+
+    pub async fn get_playlist_preference(
+        &self,
+        playlist_id: i64,
+    ) -> Result<Option<DbPlaylistPreference>> {
+        ops::get_playlist_preference(&self.pool, playlist_id).await
+    }
+
+    pub async fn save_playlist_preference(
+        &self,
+        playlist_id: i64,
+        shuffle: bool,
+        quality: Option<i64>,
+        append_to_queue: bool,
+    ) -> Result<()> {
+        ops::save_playlist_preference(&self.pool, playlist_id, shuffle, quality, append_to_queue)
+            .await
+    }
+
+    // ============ Followed Artist Operations ============
+
+    pub async fn get_followed_artists(&self) -> Result<Vec<DbFollowedArtist>> {
+        ops::get_followed_artists(&self.pool).await
+    }
+
+    pub async fn follow_artist(&self, artist_id: i64, name: &str, pic_url: &str) -> Result<()> {
+        ops::follow_artist(&self.pool, artist_id, name, pic_url).await
+    }
+
+    pub async fn unfollow_artist(&self, artist_id: i64) -> Result<()> {
+        ops::unfollow_artist(&self.pool, artist_id).await
+    }
+
+    pub async fn set_artist_last_release(&self, artist_id: i64, published_at: i64) -> Result<()> {
+        ops::set_artist_last_release(&self.pool, artist_id, published_at).await
+    }
+
+    // ============ Blocklist Operations ============
+
+    pub async fn get_blocked_songs(&self) -> Result<Vec<DbBlockedSong>> {
+        ops::get_blocked_songs(&self.pool).await
+    }
+
+    pub async fn block_song(&self, song_id: SongId, title: &str, artist: &str) -> Result<()> {
+        ops::block_song(&self.pool, song_id, title, artist).await
+    }
+
+    pub async fn unblock_song(&self, song_id: SongId) -> Result<()> {
+        ops::unblock_song(&self.pool, song_id).await
+    }
+
+    pub async fn get_blocked_artists(&self) -> Result<Vec<DbBlockedArtist>> {
+        ops::get_blocked_artists(&self.pool).await
+    }
+
+    pub async fn block_artist(&self, name: &str) -> Result<()> {
+        ops::block_artist(&self.pool, name).await
+    }
+
+    pub async fn unblock_artist(&self, name: &str) -> Result<()> {
+        ops::unblock_artist(&self.pool, name).await
+    }
+
+    // ============ Trash Operations ============
+
+    pub async fn trash_playlist(&self, id: i64) -> Result<()> {
+        ops::trash_playlist(&self.pool, id).await
+    }
+
+    pub async fn trash_song(&self, id: i64) -> Result<()> {
+        ops::trash_song(&self.pool, id).await
+    }
+
+    pub async fn trash_song_by_path(&self, path: &str) -> Result<()> {
+        ops::trash_song_by_path(&self.pool, path).await
+    }
+
+    pub async fn get_trashed_playlists(&self) -> Result<Vec<DbTrashedPlaylist>> {
+        ops::get_trashed_playlists(&self.pool).await
+    }
+
+    pub async fn get_trashed_songs(&self) -> Result<Vec<DbTrashedSong>> {
+        ops::get_trashed_songs(&self.pool).await
+    }
+
+    pub async fn restore_playlist(&self, id: i64) -> Result<()> {
+        ops::restore_playlist(&self.pool, id).await
+    }
+
+    pub async fn restore_song(&self, id: i64) -> Result<()> {
+        ops::restore_song(&self.pool, id).await
+    }
+
+    pub async fn purge_playlist(&self, id: i64) -> Result<()> {
+        ops::purge_playlist(&self.pool, id).await
+    }
+
+    pub async fn purge_song(&self, id: i64) -> Result<()> {
+        ops::purge_song(&self.pool, id).await
+    }
+
+    /// Permanently delete everything in the trash
+    pub async fn empty_trash(&self) -> Result<u64> {
+        ops::purge_trash(&self.pool, i64::MAX).await
+    }
+
+    /// Permanently delete items whose time in the trash is up
+    pub async fn purge_expired_trash(&self) -> Result<u64> {
+        ops::purge_expired_trash(&self.pool).await
+    }
+
+    // ============ Song Override Operations ============
+
+    pub async fn get_song_overrides(&self) -> Result<Vec<DbSongOverride>> {
+        ops::get_song_overrides(&self.pool).await
+    }
+
+    pub async fn set_song_override(&self, song: &DbSongOverride) -> Result<()> {
+        ops::set_song_override(&self.pool, song).await
+    }
+
+    pub async fn remove_song_override(&self, ncm_id: i64) -> Result<()> {
+        ops::remove_song_override(&self.pool, ncm_id).await
+    }
+
+    // ============ NCM Link Operations ============
+
+    pub async fn get_ncm_links(&self) -> Result<Vec<DbNcmLink>> {
+        ops::get_ncm_links(&self.pool).await
+    }
+
+    pub async fn link_ncm_song(&self, song_id: i64, ncm_id: u64) -> Result<()> {
+        ops::link_ncm_song(&self.pool, song_id, ncm_id).await
+    }
+
+    // ============ Song Analysis Operations ============
+
+    pub async fn get_song_analyses(&self) -> Result<Vec<DbSongAnalysis>> {
+        ops::get_song_analyses(&self.pool).await
+    }
+
+    pub async fn save_song_analysis(
+        &self,
+        song_id: i64,
+        bpm: Option<f64>,
+        musical_key: Option<i64>,
+        energy: Option<f64>,
+    ) -> Result<()> {
+        ops::save_song_analysis(&self.pool, song_id, bpm, musical_key, energy).await
+    }
+
+    pub async fn get_unanalyzed_songs(&self) -> Result<Vec<DbSong>> {
+        ops::get_unanalyzed_songs(&self.pool).await
+    }
+
+    // ============ Song Fingerprint Operations ============
+
+    pub async fn get_song_fingerprints(&self) -> Result<Vec<DbSongFingerprint>> {
+        ops::get_song_fingerprints(&self.pool).await
+    }
+
+    pub async fn save_song_fingerprint(&self, song_id: i64, words: &[u8]) -> Result<()> {
+        ops::save_song_fingerprint(&self.pool, song_id, words).await
+    }
+
+    pub async fn get_unfingerprinted_songs(&self) -> Result<Vec<DbSong>> {
+        ops::get_unfingerprinted_songs(&self.pool).await
+    }
+
+    // ============ Favorite Operations ============
+
+    pub async fn get_favorites(&self) -> Result<Vec<DbFavorite>> {
+        ops::get_favorites(&self.pool).await
+    }
+
+    pub async fn set_favorite(&self, song_id: SongId, liked: bool, updated_at: i64) -> Result<()> {
+        ops::set_favorite(&self.pool, song_id, liked, updated_at).await
+    }
+
+    pub async fn get_liked_mirror(&self) -> Result<Option<DbLikedMirror>> {
+        ops::get_liked_mirror(&self.pool).await
+    }
+
+    pub async fn save_liked_mirror(&self, mirror: &DbLikedMirror) -> Result<()> {
+        ops::save_liked_mirror(&self.pool, mirror).await
+    }
+
+    pub async fn clear_liked_mirror(&self) -> Result<()> {
+        ops::clear_liked_mirror(&self.pool).await
+    }
+
+    // ============ Check-in Operations ============
+
+    pub async fn record_check_in(&self, uid: u64, platform: u8, points: i64) -> Result<()> {
+        ops::record_check_in(&self.pool, uid, platform, points).await
+    }
+
+    pub async fn get_check_ins(&self, uid: u64, limit: i64) -> Result<Vec<DbCheckIn>> {
+        ops::get_check_ins(&self.pool, uid, limit).await
+    }
+
+    // ============ App Version Operations ============
+
+    pub async fn record_app_version(&self, current: &str, legacy: &str) -> Result<Option<String>> {
+        ops::record_app_version(&self.pool, current, legacy).await
+    }
+
+    pub async fn get_app_versions(&self) -> Result<Vec<DbAppVersion>> {
+        ops::get_app_versions(&self.pool).await
+    }
+
+    // ============ Search History Operations ============
+
+    pub async fn record_search(&self, keyword: &str) -> Result<()> {
+        ops::record_search(&self.pool, keyword).await
+    }
+
+    pub async fn get_search_history(&self, limit: i64) -> Result<Vec<String>> {
+        ops::get_search_history(&self.pool, limit).await
+    }
+
+    pub async fn delete_search_history(&self, keyword: &str) -> Result<()> {
+        ops::delete_search_history(&self.pool, keyword).await
+    }
+
+    pub async fn clear_search_history(&self) -> Result<()> {
+        ops::clear_search_history(&self.pool).await
+    }
+
+    // ============ Lyrics Preference Operations ============
+
+    pub async fn get_lyrics_preference(
+        &self,
+        song_id: SongId,
+    ) -> Result<Option<DbLyricsPreference>> {
+        ops::get_lyrics_preference(&self.pool, song_id).await
+    }
+
+    pub async fn save_lyrics_preference(
+        &self,
+        song_id: SongId,
+        show_translation: bool,
+        show_romanization: bool,
+    ) -> Result<()> {
+        ops::save_lyrics_preference(&self.pool, song_id, show_translation, show_romanization).await
+    }
+
+    // ============ Lyrics Share Operations ============
+
+    pub async fn get_lyrics_share_hash(&self, song_id: SongId) -> Result<Option<String>> {
+        ops::get_lyrics_share_hash(&self.pool, song_id).await
+    }
+
+    pub async fn save_lyrics_share_hash(&self, song_id: SongId, content_hash: &str) -> Result<()> {
+        ops::save_lyrics_share_hash(&self.pool, song_id, content_hash).await
+    }
 }
@@ -3,8 +3,22 @@
 use anyhow::Result;
 use sqlx::{Pool, Sqlite};
 
+use super::migrations::{self, MIGRATIONS};
+
 /// Run database migrations to create/update schema
+///
+/// A database from a newer build is refused before anything is written.
 pub async fn run_migrations(pool: &Pool<Sqlite>) -> Result<()> {
+    migrations::check_version(pool, MIGRATIONS).await?;
+    create_baseline(pool).await?;
+    migrations::apply(pool, MIGRATIONS).await
+}
+
+/// Create the tables that predate versioned migrations
+///
+/// Safe to run on any database, old or new; schema changes after this go in
+/// [`MIGRATIONS`].
+async fn create_baseline(pool: &Pool<Sqlite>) -> Result<()> {
     // Songs table
     sqlx::query(
         r#"
@@ -149,6 +163,222 @@ pub async fn run_migrations(pool: &Pool<Sqlite>) -> Result<()> {
     .execute(pool)
     .await?;
 
+    // Palette cache, keyed by the hash of the image bytes
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS palette_cache (
+            image_hash TEXT PRIMARY KEY,
+            primary_color INTEGER NOT NULL,
+            secondary_color INTEGER NOT NULL,
+            tertiary_color INTEGER NOT NULL,
+            brightness REAL NOT NULL,
+            created_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Per-song lyrics sub-line visibility for library and NCM songs alike,
+    // so there is no foreign key; migration 7 keys it by source and song_id
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS lyrics_preferences (
+            song_id INTEGER PRIMARY KEY,
+            show_translation INTEGER NOT NULL,
+            show_romanization INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Hash of each local song's LRC file when it was last shared or passed
+    // on, so only later edits are offered for sharing; migration 7 keys it
+    // by source and song_id
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS lyrics_shares (
+            song_id INTEGER PRIMARY KEY,
+            content_hash TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Per-playlist playback preferences; playlist_id is the page ID, so NCM
+    // playlists (negative) and Recently Played (-1) can have one too
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS playlist_preferences (
+            playlist_id INTEGER PRIMARY KEY,
+            shuffle INTEGER NOT NULL,
+            quality INTEGER,
+            append_to_queue INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Artists whose new releases show up on Discover; last_release_at is the
+    // publish time (ms) of the newest album already announced
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS followed_artists (
+            artist_id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            pic_url TEXT NOT NULL DEFAULT '',
+            last_release_at INTEGER NOT NULL DEFAULT 0,
+            followed_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // "Never play this" blocklist; migration 7 keys it by source and song_id
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS blocked_songs (
+            song_id INTEGER PRIMARY KEY,
+            title TEXT NOT NULL,
+            artist TEXT NOT NULL DEFAULT '',
+            blocked_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS blocked_artists (
+            name TEXT PRIMARY KEY COLLATE NOCASE,
+            blocked_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS ncm_links (
+            song_id INTEGER PRIMARY KEY,
+            ncm_id INTEGER NOT NULL,
+            linked_at INTEGER NOT NULL,
+            FOREIGN KEY (song_id) REFERENCES songs(id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Cloud bean rewards from the automatic daily check-in
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS check_ins (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            uid INTEGER NOT NULL,
+            platform INTEGER NOT NULL,
+            points INTEGER NOT NULL,
+            claimed_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Favorite state per song, keyed by source and song_id since migration
+    // 7; unliked songs stay so their time can be compared with NCM's liked
+    // list
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS favorites (
+            song_id INTEGER PRIMARY KEY,
+            liked INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Saved copy of the account's liked songs playlist, so it opens offline;
+    // songs are the playlist's SongInfo list as JSON, in playlist order
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS liked_mirror (
+            user_id INTEGER PRIMARY KEY,
+            playlist_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            cover_img_url TEXT NOT NULL,
+            author TEXT NOT NULL,
+            songs TEXT NOT NULL,
+            synced_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Keywords submitted in the search box
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS search_history (
+            keyword TEXT PRIMARY KEY,
+            searched_at INTEGER NOT NULL
+        );
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    // Full-text index over song metadata, kept in sync with the songs table
+    let fts_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'songs_fts')",
+    )
+    .fetch_one(pool)
+    .await?;
+    sqlx::query(
+        r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS songs_fts USING fts5(
+            title, artist, album,
+            content='songs', content_rowid='id'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS songs_fts_insert AFTER INSERT ON songs BEGIN
+            INSERT INTO songs_fts(rowid, title, artist, album)
+            VALUES (new.id, new.title, new.artist, new.album);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS songs_fts_delete AFTER DELETE ON songs BEGIN
+            INSERT INTO songs_fts(songs_fts, rowid, title, artist, album)
+            VALUES ('delete', old.id, old.title, old.artist, old.album);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS songs_fts_update AFTER UPDATE OF title, artist, album ON songs BEGIN
+            INSERT INTO songs_fts(songs_fts, rowid, title, artist, album)
+            VALUES ('delete', old.id, old.title, old.artist, old.album);
+            INSERT INTO songs_fts(rowid, title, artist, album)
+            VALUES (new.id, new.title, new.artist, new.album);
+        END;
+        "#,
+    )
+    .execute(pool)
+    .await?;
+    // Index songs that were scanned before the index existed
+    if !fts_exists {
+        sqlx::query("INSERT INTO songs_fts(songs_fts) VALUES ('rebuild')")
+            .execute(pool)
+            .await?;
+    }
+
     // Add new columns to songs table if they don't exist (migration)
     // SQLite doesn't support IF NOT EXISTS for columns, so we use a try approach
     let _ = sqlx::query("ALTER TABLE songs ADD COLUMN file_size INTEGER NOT NULL DEFAULT 0")
@@ -164,5 +394,26 @@ pub async fn run_migrations(pool: &Pool<Sqlite>) -> Result<()> {
         .execute(pool)
         .await;
 
+    // Which catalog a song belongs to; NCM tracks were only marked by their
+    // ncm://<id> path before, and are looked up by track ID from here on
+    let _ = sqlx::query("ALTER TABLE songs ADD COLUMN source TEXT NOT NULL DEFAULT 'local'")
+        .execute(pool)
+        .await;
+    let _ = sqlx::query("ALTER TABLE songs ADD COLUMN source_id INTEGER")
+        .execute(pool)
+        .await;
+    sqlx::query(
+        r#"
+        UPDATE songs SET source = 'ncm', source_id = CAST(substr(file_path, 7) AS INTEGER)
+        WHERE file_path LIKE 'ncm://%' AND substr(file_path, 7) GLOB '[0-9]*'
+          AND source_id IS NULL;
+
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_songs_source
+            ON songs(source, source_id) WHERE source_id IS NOT NULL;
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
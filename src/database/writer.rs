@@ -0,0 +1,268 @@
+//! Write coalescing for frequent small writes
+//!
+//! Play records and queue snapshots arrive in bursts: skipping through a
+//! playlist records a play per song, and editing the queue saves it on every
+//! change. They are buffered here and written in one transaction once the
+//! burst settles. Only the newest queue snapshot is kept, since each one
+//! replaces the whole queue anyway. A flush that fails puts its writes back
+//! to be tried again, so a busy disk delays them rather than losing them; a
+//! batch that keeps failing is logged and dropped so it can't pile up.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use sqlx::{Acquire, Pool, Sqlite};
+use tokio::sync::Notify;
+
+use super::{DbSong, SongId, ops};
+
+/// Flush once no new write has arrived for this long
+const QUIET_PERIOD: Duration = Duration::from_millis(750);
+
+/// Flush at the latest this long after the first buffered write
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Wait this long before retrying a failed flush
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Give up on a batch after this many failed flushes
+const MAX_ATTEMPTS: u32 = 3;
+
+#[derive(Debug)]
+struct PendingPlay {
+    song_id: SongId,
+    played_at: i64,
+    listened_secs: i64,
+    completed: bool,
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    queue: Option<(Vec<DbSong>, Option<i64>)>,
+    plays: Vec<PendingPlay>,
+    /// Flushes of these writes that failed so far
+    failures: u32,
+}
+
+impl Pending {
+    fn is_empty(&self) -> bool {
+        self.queue.is_none() && self.plays.is_empty()
+    }
+
+    /// Put back writes that failed to flush, ahead of what was buffered since
+    fn restore(&mut self, failed: Pending) {
+        self.failures = failed.failures + 1;
+        let newer = std::mem::replace(&mut self.plays, failed.plays);
+        self.plays.extend(newer);
+        // A snapshot taken since the failure is the newer queue
+        if self.queue.is_none() {
+            self.queue = failed.queue;
+        }
+    }
+}
+
+/// Buffered writes of one database
+#[derive(Debug, Default)]
+pub(super) struct WriteBuffer {
+    pending: Mutex<Pending>,
+    /// Wakes the flusher when something is buffered
+    notify: Notify,
+    /// Keeps flushes in order so an older queue snapshot never lands last
+    flushing: tokio::sync::Mutex<()>,
+}
+
+impl WriteBuffer {
+    /// Create the buffer and start its background flusher
+    pub fn start(pool: Pool<Sqlite>) -> Arc<Self> {
+        let buffer = Arc::new(Self::default());
+        let flusher = buffer.clone();
+        tokio::spawn(async move {
+            loop {
+                flusher.notify.notified().await;
+
+                let first_write = Instant::now();
+                while first_write.elapsed() < MAX_DELAY {
+                    let quiet = tokio::time::timeout(QUIET_PERIOD, flusher.notify.notified());
+                    if quiet.await.is_err() {
+                        break;
+                    }
+                }
+
+                if let Err(e) = flusher.flush(&pool).await {
+                    tracing::warn!("Failed to flush buffered database writes: {}", e);
+                    if !flusher.pending.lock().is_empty() {
+                        tokio::time::sleep(RETRY_DELAY).await;
+                        flusher.notify.notify_one();
+                    }
+                }
+            }
+        });
+        buffer
+    }
+
+    pub fn record_play(&self, song_id: SongId, listened_secs: i64, completed: bool) {
+        self.pending.lock().plays.push(PendingPlay {
+            song_id,
+            played_at: ops::current_timestamp(),
+            listened_secs,
+            completed,
+        });
+        self.notify.notify_one();
+    }
+
+    pub fn save_queue(&self, songs: Vec<DbSong>, source_playlist_id: Option<i64>) {
+        self.pending.lock().queue = Some((songs, source_playlist_id));
+        self.notify.notify_one();
+    }
+
+    /// Write everything buffered so far in one transaction
+    ///
+    /// On failure the writes stay buffered for the next flush, unless they
+    /// have failed [`MAX_ATTEMPTS`] times; then they are logged and dropped.
+    pub async fn flush(&self, pool: &Pool<Sqlite>) -> Result<()> {
+        let _flushing = self.flushing.lock().await;
+        let pending = std::mem::take(&mut *self.pending.lock());
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let result = write(pool, &pending).await;
+        if result.is_err() {
+            if pending.failures + 1 >= MAX_ATTEMPTS {
+                tracing::error!(
+                    "Dropping {} play records{} after {} failed writes: {:?}",
+                    pending.plays.len(),
+                    if pending.queue.is_some() {
+                        " and the queue"
+                    } else {
+                        ""
+                    },
+                    MAX_ATTEMPTS,
+                    pending.plays
+                );
+            } else {
+                self.pending.lock().restore(pending);
+            }
+        }
+        result
+    }
+}
+
+/// Write `pending` in one transaction
+async fn write(pool: &Pool<Sqlite>, pending: &Pending) -> Result<()> {
+    let mut conn = pool.acquire().await?;
+    let mut tx = conn.begin().await?;
+
+    // Queue first, so plays of NCM songs it adds find their rows
+    if let Some((songs, source_playlist_id)) = &pending.queue {
+        let mut db_song_ids = Vec::with_capacity(songs.len());
+        for song in songs {
+            match song.id.library_id() {
+                Some(row_id) => db_song_ids.push(row_id),
+                // NCM song - upsert to database and get the real ID
+                None => db_song_ids.push(ops::upsert_ncm_song_tx(&mut *tx, song).await?),
+            }
+        }
+        ops::set_queue_tx(&mut *tx, &db_song_ids, *source_playlist_id).await?;
+        ops::save_queue_snapshot_tx(&mut *tx, &db_song_ids).await?;
+    }
+
+    for play in &pending.plays {
+        ops::record_play_tx(
+            &mut *tx,
+            play.song_id,
+            play.played_at,
+            play.listened_secs,
+            play.completed,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    tracing::debug!(
+        "Flushed {} play records{}",
+        pending.plays.len(),
+        if pending.queue.is_some() {
+            " and the queue"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    fn song(id: i64) -> DbSong {
+        DbSong {
+            id: SongId::library(id),
+            file_path: format!("/music/{}.mp3", id),
+            title: String::new(),
+            artist: String::new(),
+            album: String::new(),
+            duration_secs: 0,
+            track_number: None,
+            year: None,
+            genre: None,
+            cover_path: None,
+            file_hash: None,
+            file_size: 0,
+            format: None,
+            play_count: 0,
+            last_played: None,
+            last_modified: 0,
+            created_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_keeps_writes() {
+        // No schema, so every write fails
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let buffer = WriteBuffer::default();
+        buffer.record_play(SongId::library(1), 30, true);
+        buffer.save_queue(vec![song(1)], None);
+
+        assert!(buffer.flush(&pool).await.is_err());
+        {
+            let pending = buffer.pending.lock();
+            assert_eq!(pending.plays.len(), 1);
+            assert!(pending.queue.is_some());
+        }
+
+        // Writes made after the failure come after it, and the newer queue wins
+        buffer.record_play(SongId::library(2), 10, false);
+        buffer.save_queue(vec![song(2)], Some(7));
+        assert!(buffer.flush(&pool).await.is_err());
+        let pending = buffer.pending.lock();
+        let plays: Vec<_> = pending.plays.iter().map(|play| play.song_id).collect();
+        assert_eq!(plays, [SongId::library(1), SongId::library(2)]);
+        let (songs, source) = pending.queue.as_ref().unwrap();
+        assert_eq!((songs[0].id, *source), (SongId::library(2), Some(7)));
+    }
+
+    #[tokio::test]
+    async fn test_failing_batch_is_dropped() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let buffer = WriteBuffer::default();
+        buffer.record_play(SongId::library(1), 30, true);
+
+        for _ in 0..MAX_ATTEMPTS {
+            assert!(buffer.flush(&pool).await.is_err());
+        }
+        assert!(buffer.pending.lock().is_empty());
+    }
+}
@@ -3,14 +3,53 @@
 //! Each feature module contains the core logic for a specific functionality.
 //! Features should not depend on UI components directly.
 
+pub mod annual_report;
+pub mod auto_dj;
+pub mod autoplay;
+pub mod bandwidth;
+pub mod blocklist;
+pub mod call_ducking;
+pub mod crash;
+pub mod diagnostics;
+pub mod export;
+pub mod favorites;
+pub mod home_layout;
+pub mod hooks;
 pub mod import;
+pub mod jobs;
 pub mod keybindings;
+pub mod latency;
+pub mod lazy_covers;
+pub mod listen;
+pub mod logs;
 pub mod lyrics;
+pub mod lyrics_save;
+pub mod lyrics_share;
 pub mod media;
+pub mod media_artwork;
+pub mod memory;
+pub mod ncm_match;
+pub mod ncm_publish;
+pub mod now_playing;
+pub mod playlist_health;
+pub mod playlist_sync;
+pub mod plugins;
+pub mod schedules;
 pub mod settings;
+pub mod settings_profile;
+pub mod song_analysis;
+pub mod song_overrides;
+pub mod song_source;
+pub mod startup;
+pub mod updater;
+pub mod whats_new;
 
 pub use keybindings::{Action, KeyBindings};
 
 pub use crate::platform::tray::TrayCommand;
 
-pub use settings::{CloseBehavior, EqualizerPreset, MusicQuality, PlayMode, ProxyType, Settings};
+pub use settings::{
+    ArtworkBackdrop, AutoDjPool, BackgroundStyle, CallAction, CallDuckingSettings, CloseBehavior, DspPresets, EqualizerPreset, ExportSettings, LyricsSaveSettings, LyricsShareSettings,
+    LyricsSubLines, LyricsTuning, MeteredMode, MusicQuality, NowPlayingOutput, PlayMode,
+    PlaylistPlayback, ProxyType, Settings, SmartResumeSettings, UpdateChannel,
+};
@@ -0,0 +1,287 @@
+//! Year-in-review built from local play history
+//!
+//! Aggregation is pure so it can run on any set of play records; the
+//! app turns the result into localized cards, which `render` draws into
+//! shareable images.
+
+pub mod render;
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{Datelike, NaiveDate, TimeZone, Timelike};
+
+/// Entries kept in the top songs and top artists lists
+pub const TOP_COUNT: usize = 5;
+
+/// One play event joined with its song
+#[derive(Debug, Clone)]
+pub struct PlayRecord {
+    pub song_id: i64,
+    pub title: String,
+    pub artist: String,
+    /// Unix timestamp (seconds)
+    pub played_at: i64,
+    pub listened_secs: i64,
+}
+
+/// A song ranked by play count
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedSong {
+    pub title: String,
+    pub artist: String,
+    pub plays: u32,
+}
+
+/// An artist ranked by play count
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedArtist {
+    pub name: String,
+    pub plays: u32,
+    pub listened_secs: i64,
+}
+
+/// Longest run of consecutive days with at least one play
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub days: u32,
+    pub start: NaiveDate,
+}
+
+/// Listening summary for one calendar year
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnualReport {
+    pub year: i32,
+    pub plays: u32,
+    pub distinct_songs: u32,
+    pub listened_secs: i64,
+    pub top_songs: Vec<RankedSong>,
+    pub top_artists: Vec<RankedArtist>,
+    /// Hour of day (0-23) with the most plays
+    pub peak_hour: Option<u32>,
+    pub longest_streak: Option<Streak>,
+}
+
+impl AnnualReport {
+    pub fn is_empty(&self) -> bool {
+        self.plays == 0
+    }
+}
+
+/// A localized card of the report, shown on screen and exported as an image
+#[derive(Debug, Clone)]
+pub struct ReportCard {
+    pub title: String,
+    /// Large figure at the top of the card
+    pub headline: String,
+    /// Ranked or labelled lines: (primary, secondary)
+    pub rows: Vec<(String, String)>,
+}
+
+/// Start (inclusive) and end (exclusive) Unix timestamps of a year in `tz`
+pub fn year_bounds<Tz: TimeZone>(year: i32, tz: &Tz) -> (i64, i64) {
+    let start_of = |year: i32| {
+        tz.with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+            .earliest()
+            .map_or(0, |t| t.timestamp())
+    };
+    (start_of(year), start_of(year + 1))
+}
+
+/// Build the report for `year` from play records, using `tz` for days and hours
+///
+/// Records outside the year are ignored, so a wider query is harmless.
+pub fn build<Tz: TimeZone>(year: i32, records: &[PlayRecord], tz: &Tz) -> AnnualReport {
+    let mut songs: HashMap<i64, (RankedSong, i64)> = HashMap::new();
+    let mut artists: HashMap<&str, RankedArtist> = HashMap::new();
+    let mut hours = [0u32; 24];
+    let mut days = BTreeSet::new();
+    let mut plays = 0u32;
+    let mut listened_secs = 0i64;
+
+    for record in records {
+        let Some(time) = tz.timestamp_opt(record.played_at, 0).earliest() else {
+            continue;
+        };
+        if time.year() != year {
+            continue;
+        }
+
+        plays += 1;
+        listened_secs += record.listened_secs.max(0);
+        hours[time.hour() as usize] += 1;
+        days.insert(time.date_naive());
+
+        let (song, song_secs) = songs.entry(record.song_id).or_insert_with(|| {
+            (
+                RankedSong {
+                    title: record.title.clone(),
+                    artist: record.artist.clone(),
+                    plays: 0,
+                },
+                0,
+            )
+        });
+        song.plays += 1;
+        *song_secs += record.listened_secs.max(0);
+
+        if !record.artist.is_empty() {
+            let artist = artists
+                .entry(record.artist.as_str())
+                .or_insert_with(|| RankedArtist {
+                    name: record.artist.clone(),
+                    plays: 0,
+                    listened_secs: 0,
+                });
+            artist.plays += 1;
+            artist.listened_secs += record.listened_secs.max(0);
+        }
+    }
+
+    let distinct_songs = songs.len() as u32;
+
+    // Ties go to the longer listen, then alphabetical for a stable order
+    let mut songs: Vec<_> = songs.into_values().collect();
+    songs.sort_by(|(a, a_secs), (b, b_secs)| {
+        b.plays
+            .cmp(&a.plays)
+            .then(b_secs.cmp(a_secs))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    let top_songs = songs
+        .into_iter()
+        .take(TOP_COUNT)
+        .map(|(song, _)| song)
+        .collect();
+
+    let mut top_artists: Vec<_> = artists.into_values().collect();
+    top_artists.sort_by(|a, b| {
+        b.plays
+            .cmp(&a.plays)
+            .then(b.listened_secs.cmp(&a.listened_secs))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    top_artists.truncate(TOP_COUNT);
+
+    // Earliest hour wins a tie
+    let peak_hour = (plays > 0).then(|| {
+        (0..24u32)
+            .max_by_key(|&h| (hours[h as usize], std::cmp::Reverse(h)))
+            .unwrap_or(0)
+    });
+
+    AnnualReport {
+        year,
+        plays,
+        distinct_songs,
+        listened_secs,
+        top_songs,
+        top_artists,
+        peak_hour,
+        longest_streak: longest_streak(&days),
+    }
+}
+
+/// Longest run of consecutive days; the earlier run wins a tie
+fn longest_streak(days: &BTreeSet<NaiveDate>) -> Option<Streak> {
+    let mut best: Option<Streak> = None;
+    let mut current: Option<Streak> = None;
+
+    for &day in days {
+        current = match current {
+            Some(run) if run.start + chrono::Days::new(run.days as u64) == day => Some(Streak {
+                days: run.days + 1,
+                ..run
+            }),
+            _ => Some(Streak {
+                days: 1,
+                start: day,
+            }),
+        };
+        if let Some(run) = current {
+            if best.is_none_or(|b| run.days > b.days) {
+                best = Some(run);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> i64 {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0)
+            .unwrap()
+            .timestamp()
+    }
+
+    fn play(song_id: i64, artist: &str, played_at: i64) -> PlayRecord {
+        PlayRecord {
+            song_id,
+            title: format!("Song {song_id}"),
+            artist: artist.to_string(),
+            played_at,
+            listened_secs: 180,
+        }
+    }
+
+    #[test]
+    fn ranks_songs_and_artists_by_plays() {
+        let records = vec![
+            play(1, "A", at(2025, 3, 1, 9)),
+            play(2, "B", at(2025, 3, 1, 10)),
+            play(2, "B", at(2025, 3, 2, 10)),
+            play(3, "B", at(2025, 3, 3, 10)),
+        ];
+        let report = build(2025, &records, &Utc);
+
+        assert_eq!(report.plays, 4);
+        assert_eq!(report.distinct_songs, 3);
+        assert_eq!(report.listened_secs, 4 * 180);
+        assert_eq!(report.top_songs[0].title, "Song 2");
+        assert_eq!(report.top_songs[0].plays, 2);
+        assert_eq!(report.top_artists[0].name, "B");
+        assert_eq!(report.top_artists[0].plays, 3);
+        assert_eq!(report.peak_hour, Some(10));
+    }
+
+    #[test]
+    fn ignores_plays_from_other_years() {
+        let records = vec![
+            play(1, "A", at(2024, 12, 31, 23)),
+            play(1, "A", at(2025, 1, 1, 0)),
+            play(1, "A", at(2026, 1, 1, 0)),
+        ];
+        let report = build(2025, &records, &Utc);
+        assert_eq!(report.plays, 1);
+
+        let (start, end) = year_bounds(2025, &Utc);
+        assert_eq!(start, at(2025, 1, 1, 0));
+        assert_eq!(end, at(2026, 1, 1, 0));
+    }
+
+    #[test]
+    fn finds_longest_streak_across_month_boundary() {
+        let records = vec![
+            play(1, "A", at(2025, 1, 5, 12)),
+            play(1, "A", at(2025, 1, 6, 12)),
+            play(1, "A", at(2025, 1, 30, 8)),
+            play(1, "A", at(2025, 1, 31, 22)),
+            play(1, "A", at(2025, 2, 1, 7)),
+            play(1, "A", at(2025, 2, 1, 9)),
+        ];
+        let streak = build(2025, &records, &Utc).longest_streak.unwrap();
+        assert_eq!(streak.days, 3);
+        assert_eq!(streak.start, NaiveDate::from_ymd_opt(2025, 1, 30).unwrap());
+    }
+
+    #[test]
+    fn empty_history_gives_empty_report() {
+        let report = build(2025, &[], &Utc);
+        assert!(report.is_empty());
+        assert_eq!(report.peak_hour, None);
+        assert_eq!(report.longest_streak, None);
+    }
+}
@@ -0,0 +1,254 @@
+//! Software rendering of report cards into PNG images
+//!
+//! Cards are drawn with `ab_glyph` onto an RGBA buffer so export works the
+//! same with or without a GPU, and the images look identical on every
+//! platform.
+
+use std::path::{Path, PathBuf};
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont, point};
+use anyhow::{Context, Result, anyhow};
+use image::{Rgba, RgbaImage};
+
+use super::ReportCard;
+
+/// Card size, a 4:5 portrait that fits social feeds
+pub const CARD_WIDTH: u32 = 1080;
+pub const CARD_HEIGHT: u32 = 1350;
+
+const MARGIN: f32 = 96.0;
+const TITLE_SIZE: f32 = 52.0;
+const HEADLINE_SIZE: f32 = 120.0;
+const ROW_SIZE: f32 = 44.0;
+const ROW_DETAIL_SIZE: f32 = 32.0;
+const ROW_SPACING: f32 = 40.0;
+
+/// Fonts tried in order; the CJK face covers Latin text too
+const FONT_PATHS: [&str; 2] = [
+    "assets/fonts/NotoSansCJKsc-Regular.otf",
+    "assets/fonts/Inter-Regular.ttf",
+];
+
+/// Background gradients (top, bottom), one per card in turn
+pub const GRADIENTS: [([u8; 3], [u8; 3]); 4] = [
+    ([236, 65, 101], [96, 30, 122]),
+    ([41, 98, 255], [18, 28, 84]),
+    ([0, 150, 136], [12, 52, 61]),
+    ([255, 145, 0], [140, 36, 44]),
+];
+
+const WHITE: [u8; 3] = [255, 255, 255];
+const MUTED: [u8; 3] = [225, 225, 235];
+
+/// Load the first available bundled font
+pub fn load_font() -> Result<FontVec> {
+    for path in FONT_PATHS {
+        if let Ok(data) = std::fs::read(path) {
+            if let Ok(font) = FontVec::try_from_vec(data) {
+                return Ok(font);
+            }
+        }
+    }
+    Err(anyhow!("no usable font found in assets/fonts"))
+}
+
+/// Draw one card
+pub fn render_card(card: &ReportCard, index: usize, font: &FontVec) -> RgbaImage {
+    let (top, bottom) = GRADIENTS[index % GRADIENTS.len()];
+    let mut img = RgbaImage::from_fn(CARD_WIDTH, CARD_HEIGHT, |_, y| {
+        let t = y as f32 / (CARD_HEIGHT - 1) as f32;
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Rgba([
+            mix(top[0], bottom[0]),
+            mix(top[1], bottom[1]),
+            mix(top[2], bottom[2]),
+            255,
+        ])
+    });
+
+    let max_width = CARD_WIDTH as f32 - MARGIN * 2.0;
+    let mut y = MARGIN;
+
+    draw_text(
+        &mut img,
+        font,
+        &card.title,
+        MARGIN,
+        y,
+        TITLE_SIZE,
+        MUTED,
+        max_width,
+    );
+    y += TITLE_SIZE * 1.8;
+    draw_text(
+        &mut img,
+        font,
+        &card.headline,
+        MARGIN,
+        y,
+        HEADLINE_SIZE,
+        WHITE,
+        max_width,
+    );
+    y += HEADLINE_SIZE * 1.5;
+
+    for (primary, secondary) in &card.rows {
+        if y + ROW_SIZE + ROW_DETAIL_SIZE > CARD_HEIGHT as f32 - MARGIN {
+            break;
+        }
+        draw_text(
+            &mut img, font, primary, MARGIN, y, ROW_SIZE, WHITE, max_width,
+        );
+        y += ROW_SIZE * 1.3;
+        if !secondary.is_empty() {
+            draw_text(
+                &mut img,
+                font,
+                secondary,
+                MARGIN,
+                y,
+                ROW_DETAIL_SIZE,
+                MUTED,
+                max_width,
+            );
+            y += ROW_DETAIL_SIZE * 1.3;
+        }
+        y += ROW_SPACING;
+    }
+
+    draw_text(
+        &mut img,
+        font,
+        "Rustle",
+        MARGIN,
+        CARD_HEIGHT as f32 - MARGIN - ROW_DETAIL_SIZE,
+        ROW_DETAIL_SIZE,
+        MUTED,
+        max_width,
+    );
+    img
+}
+
+/// Write every card as `rustle-<year>-<n>.png` into `dir`
+pub fn export(cards: &[ReportCard], year: i32, dir: &Path) -> Result<Vec<PathBuf>> {
+    let font = load_font()?;
+    cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| {
+            let path = dir.join(format!("rustle-{}-{}.png", year, i + 1));
+            render_card(card, i, &font)
+                .save(&path)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Draw a single line, cut with an ellipsis when wider than `max_width`
+///
+/// `top` is the top of the line box; the baseline sits one ascent below.
+#[allow(clippy::too_many_arguments)]
+fn draw_text(
+    img: &mut RgbaImage,
+    font: &FontVec,
+    text: &str,
+    left: f32,
+    top: f32,
+    size: f32,
+    color: [u8; 3],
+    max_width: f32,
+) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let text = fit_text(text, max_width, |s| line_width(font, size, s));
+    let baseline = top + scaled.ascent();
+
+    let mut x = left;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(prev) = previous {
+            x += scaled.kern(prev, id);
+        }
+        previous = Some(id);
+
+        let glyph = id.with_scale_and_position(size, point(x, baseline));
+        x += scaled.h_advance(id);
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px < 0 || py < 0 || px >= img.width() as i32 || py >= img.height() as i32 {
+                return;
+            }
+            let pixel = img.get_pixel_mut(px as u32, py as u32);
+            for (channel, &target) in pixel.0.iter_mut().zip(color.iter()) {
+                *channel =
+                    (*channel as f32 + (target as f32 - *channel as f32) * coverage).round() as u8;
+            }
+        });
+    }
+}
+
+fn line_width(font: &FontVec, size: f32, text: &str) -> f32 {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(prev) = previous {
+            width += scaled.kern(prev, id);
+        }
+        width += scaled.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+/// Shorten `text` with a trailing ellipsis until `measure` fits `max_width`
+fn fit_text(text: &str, max_width: f32, measure: impl Fn(&str) -> f32) -> String {
+    if measure(text) <= max_width {
+        return text.to_string();
+    }
+    let mut chars: Vec<char> = text.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let candidate = format!("{}…", chars.iter().collect::<String>().trim_end());
+        if measure(&candidate) <= max_width {
+            return candidate;
+        }
+    }
+    "…".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_text_keeps_short_text_and_cuts_long_text() {
+        let measure = |s: &str| s.chars().count() as f32;
+        assert_eq!(fit_text("short", 10.0, measure), "short");
+        assert_eq!(fit_text("a much longer line", 8.0, measure), "a much…");
+    }
+
+    #[test]
+    fn rendered_card_has_card_size_and_text() {
+        let font = load_font().expect("bundled font");
+        let card = ReportCard {
+            title: "2025".to_string(),
+            headline: "123 h".to_string(),
+            rows: vec![("Song".to_string(), "Artist".to_string())],
+        };
+        let img = render_card(&card, 0, &font);
+        assert_eq!(img.dimensions(), (CARD_WIDTH, CARD_HEIGHT));
+
+        // Text pixels are lighter than the gradient behind them
+        let row = (MARGIN + TITLE_SIZE * 1.8 + HEADLINE_SIZE * 0.6) as u32;
+        let background = *img.get_pixel(CARD_WIDTH - 1, row);
+        assert!((0..CARD_WIDTH).any(|x| *img.get_pixel(x, row) != background));
+    }
+}
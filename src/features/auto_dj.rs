@@ -0,0 +1,178 @@
+//! Auto-DJ song selection
+//!
+//! In Auto-DJ play mode the queue is kept a few songs ahead of the current
+//! one by drawing from a song pool. Picks skip blocked songs and anything
+//! queued recently, and never put two songs by the same lead artist next to
+//! each other.
+
+use std::collections::HashSet;
+
+use crate::database::{DbSong, SongId};
+use crate::features::blocklist::{ARTIST_SEPARATORS, Blocklist};
+
+/// Songs kept queued after the current one
+pub const QUEUE_AHEAD: usize = 3;
+
+/// Songs added per top-up
+pub const BATCH_SIZE: usize = 5;
+
+/// Queue entries, counted from the end, that are not picked again
+const RECENT_WINDOW: usize = 50;
+
+/// Whether the queue needs topping up while `current` plays
+pub fn needs_top_up(queue_len: usize, current: Option<usize>) -> bool {
+    let ahead = match current {
+        Some(idx) => queue_len.saturating_sub(idx + 1),
+        None => queue_len,
+    };
+    ahead < QUEUE_AHEAD
+}
+
+/// Choose up to `count` songs from `candidates` to append to `queue`
+///
+/// Candidates are taken in the order given, so shuffle them first for
+/// variety. One whose lead artist matches the song before it is held back
+/// and tried again after the next pick.
+pub fn pick(
+    candidates: Vec<DbSong>,
+    queue: &[DbSong],
+    blocklist: &Blocklist,
+    count: usize,
+) -> Vec<DbSong> {
+    // A small pool would run dry if everything queued was excluded
+    let window = RECENT_WINDOW.min(candidates.len() / 2);
+    let mut taken: HashSet<SongId> = queue.iter().rev().take(window).map(|s| s.id).collect();
+    let mut last_artist = queue.last().map(|song| lead_artist(&song.artist));
+
+    let mut picked = Vec::with_capacity(count);
+    let mut held: Vec<DbSong> = Vec::new();
+    let mut candidates = candidates.into_iter();
+
+    while picked.len() < count {
+        let retry = held
+            .iter()
+            .position(|song| last_artist.as_deref() != Some(lead_artist(&song.artist).as_str()));
+        let song = match retry {
+            Some(i) => held.remove(i),
+            None => match candidates.next() {
+                Some(song) => song,
+                None => break,
+            },
+        };
+
+        if taken.contains(&song.id) || blocklist.blocks(song.id, &song.artist) {
+            continue;
+        }
+        let artist = lead_artist(&song.artist);
+        if last_artist.as_deref() == Some(artist.as_str()) {
+            held.push(song);
+            continue;
+        }
+
+        taken.insert(song.id);
+        last_artist = Some(artist);
+        picked.push(song);
+    }
+
+    picked
+}
+
+/// First credited artist, compared without case
+pub(crate) fn lead_artist(artist: &str) -> String {
+    artist
+        .split(ARTIST_SEPARATORS)
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: i64, artist: &str) -> DbSong {
+        DbSong {
+            id: SongId::library(id),
+            file_path: String::new(),
+            title: format!("Song {id}"),
+            artist: artist.to_string(),
+            album: String::new(),
+            duration_secs: 200,
+            track_number: None,
+            year: None,
+            genre: None,
+            cover_path: None,
+            file_hash: None,
+            file_size: 0,
+            format: None,
+            play_count: 0,
+            last_played: None,
+            last_modified: 0,
+            created_at: 0,
+        }
+    }
+
+    fn ids(songs: &[DbSong]) -> Vec<i64> {
+        songs.iter().filter_map(|s| s.id.library_id()).collect()
+    }
+
+    #[test]
+    fn tops_up_when_few_songs_are_ahead() {
+        assert!(needs_top_up(0, None));
+        assert!(needs_top_up(5, Some(2)));
+        assert!(!needs_top_up(5, Some(1)));
+        assert!(!needs_top_up(3, None));
+    }
+
+    #[test]
+    fn never_repeats_an_artist_back_to_back() {
+        let queue = vec![song(1, "A")];
+        let candidates = vec![
+            song(2, "A / B"),
+            song(3, "a"),
+            song(4, "B"),
+            song(5, "B"),
+            song(6, "C"),
+        ];
+
+        let picked = pick(candidates, &queue, &Blocklist::default(), 5);
+        assert_eq!(ids(&picked), [4, 2, 5, 3, 6]);
+        for pair in std::iter::once(&queue[0])
+            .chain(&picked)
+            .collect::<Vec<_>>()
+            .windows(2)
+        {
+            assert_ne!(lead_artist(&pair[0].artist), lead_artist(&pair[1].artist));
+        }
+    }
+
+    #[test]
+    fn stops_rather_than_repeat_an_artist() {
+        let queue = vec![song(1, "A")];
+        let picked = pick(
+            vec![song(2, "A"), song(3, "A")],
+            &queue,
+            &Blocklist::default(),
+            3,
+        );
+        assert!(picked.is_empty());
+    }
+
+    #[test]
+    fn skips_blocked_and_recently_queued_songs() {
+        let queue = vec![song(1, "A"), song(2, "B")];
+        let blocklist = Blocklist::new(Vec::new(), vec!["Blocked".into()]);
+        let candidates = vec![
+            song(1, "A"),
+            song(2, "B"),
+            song(3, "Blocked"),
+            song(4, "C"),
+            song(4, "C"),
+            song(5, "D"),
+        ];
+
+        let picked = pick(candidates, &queue, &blocklist, 3);
+        assert_eq!(ids(&picked), [4, 5]);
+    }
+}
@@ -0,0 +1,85 @@
+//! Queue autoplay: keep going with similar songs when the queue ends
+//!
+//! NCM's similar songs are used when the last song is on NCM. Otherwise, or
+//! when NCM has nothing, songs are drawn from the local library: the same
+//! genre first, then the same lead artist.
+
+use crate::database::DbSong;
+use crate::features::auto_dj::lead_artist;
+
+/// Library songs similar to `seed`, best matches first
+///
+/// Songs sharing its genre come before songs sharing only its lead artist;
+/// `seed` itself is left out.
+pub fn local_similar(library: &[DbSong], seed: &DbSong) -> Vec<DbSong> {
+    let genre = seed
+        .genre
+        .as_deref()
+        .map(str::trim)
+        .filter(|genre| !genre.is_empty());
+    let artist = lead_artist(&seed.artist);
+
+    let same_genre = |song: &DbSong| {
+        genre.is_some_and(|genre| {
+            song.genre
+                .as_deref()
+                .is_some_and(|g| g.trim().eq_ignore_ascii_case(genre))
+        })
+    };
+    let (mut similar, rest): (Vec<DbSong>, Vec<DbSong>) = library
+        .iter()
+        .filter(|song| song.id != seed.id)
+        .cloned()
+        .partition(same_genre);
+    similar.extend(
+        rest.into_iter()
+            .filter(|song| !artist.is_empty() && lead_artist(&song.artist) == artist),
+    );
+    similar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SongId;
+
+    fn song(id: i64, artist: &str, genre: Option<&str>) -> DbSong {
+        DbSong {
+            id: SongId::library(id),
+            file_path: String::new(),
+            title: format!("Song {id}"),
+            artist: artist.to_string(),
+            album: String::new(),
+            duration_secs: 200,
+            track_number: None,
+            year: None,
+            genre: genre.map(str::to_string),
+            cover_path: None,
+            file_hash: None,
+            file_size: 0,
+            format: None,
+            play_count: 0,
+            last_played: None,
+            last_modified: 0,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn prefers_genre_then_artist() {
+        let seed = song(1, "A", Some("Jazz"));
+        let library = vec![
+            seed.clone(),
+            song(2, "B", Some("Rock")),
+            song(3, "a / C", None),
+            song(4, "D", Some(" jazz ")),
+            song(5, "E", None),
+        ];
+
+        let ids: Vec<SongId> = local_similar(&library, &seed)
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, [SongId::library(4), SongId::library(3)]);
+    }
+}
@@ -0,0 +1,172 @@
+//! "Never play this" blocklist for songs and artists
+//!
+//! Songs are keyed by their [`SongId`] so local and online tracks share one
+//! list. Artists are matched by name, ignoring case,
+//! against every artist credited in a song's artist string.
+
+use std::collections::HashSet;
+
+use crate::database::SongId;
+
+/// Separators used between artists in tags and NCM credits
+pub const ARTIST_SEPARATORS: &[char] = &['/', ',', ';', '、', '&', '，'];
+
+/// A blocked song, with the names shown in Settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedSong {
+    pub id: SongId,
+    pub title: String,
+    pub artist: String,
+}
+
+/// Blocked songs and artists, most recently blocked first
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist {
+    songs: Vec<BlockedSong>,
+    artists: Vec<String>,
+    song_ids: HashSet<SongId>,
+    artist_keys: HashSet<String>,
+}
+
+impl Blocklist {
+    pub fn new(songs: Vec<BlockedSong>, artists: Vec<String>) -> Self {
+        let mut list = Self::default();
+        // Inserting at the front reverses, so walk oldest first
+        for song in songs.into_iter().rev() {
+            list.insert_song(song);
+        }
+        for artist in artists.into_iter().rev() {
+            list.insert_artist(artist);
+        }
+        list
+    }
+
+    pub fn songs(&self) -> &[BlockedSong] {
+        &self.songs
+    }
+
+    pub fn artists(&self) -> &[String] {
+        &self.artists
+    }
+
+    pub fn contains_song(&self, id: SongId) -> bool {
+        self.song_ids.contains(&id)
+    }
+
+    /// Whether any artist credited in `artist` is blocked
+    pub fn blocks_artist(&self, artist: &str) -> bool {
+        if self.artist_keys.is_empty() || artist.trim().is_empty() {
+            return false;
+        }
+        self.artist_keys.contains(&artist_key(artist))
+            || artist
+                .split(ARTIST_SEPARATORS)
+                .any(|part| self.artist_keys.contains(&artist_key(part)))
+    }
+
+    /// Whether a song is blocked itself or through one of its artists
+    pub fn blocks(&self, song_id: SongId, artist: &str) -> bool {
+        self.contains_song(song_id) || self.blocks_artist(artist)
+    }
+
+    /// Add a song; returns false if it was already blocked
+    pub fn insert_song(&mut self, song: BlockedSong) -> bool {
+        if !self.song_ids.insert(song.id) {
+            return false;
+        }
+        self.songs.insert(0, song);
+        true
+    }
+
+    /// Remove a song; returns false if it was not blocked
+    pub fn remove_song(&mut self, id: SongId) -> bool {
+        if !self.song_ids.remove(&id) {
+            return false;
+        }
+        self.songs.retain(|song| song.id != id);
+        true
+    }
+
+    /// Add an artist; returns false if it is empty or already blocked
+    pub fn insert_artist(&mut self, name: String) -> bool {
+        let name = name.trim().to_string();
+        if name.is_empty() || !self.artist_keys.insert(artist_key(&name)) {
+            return false;
+        }
+        self.artists.insert(0, name);
+        true
+    }
+
+    /// Remove an artist; returns false if it was not blocked
+    pub fn remove_artist(&mut self, name: &str) -> bool {
+        let key = artist_key(name);
+        if !self.artist_keys.remove(&key) {
+            return false;
+        }
+        self.artists.retain(|artist| artist_key(artist) != key);
+        true
+    }
+}
+
+fn artist_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: u64) -> BlockedSong {
+        BlockedSong {
+            id: SongId::ncm(id),
+            title: format!("Song {id}"),
+            artist: String::new(),
+        }
+    }
+
+    #[test]
+    fn matches_any_credited_artist_ignoring_case() {
+        let list = Blocklist::new(
+            Vec::new(),
+            vec!["Simon & Garfunkel".into(), "周杰伦".into()],
+        );
+
+        assert!(list.blocks_artist("simon & garfunkel"));
+        assert!(list.blocks_artist("周杰伦/费玉清"));
+        assert!(list.blocks_artist("Lang Lang, 周杰伦"));
+        assert!(!list.blocks_artist("Simon"));
+        assert!(!list.blocks_artist(""));
+    }
+
+    #[test]
+    fn blocks_by_song_id_or_artist() {
+        let list = Blocklist::new(vec![song(42)], vec!["Blocked".into()]);
+
+        assert!(list.blocks(SongId::ncm(42), "Anyone"));
+        assert!(list.blocks(SongId::library(7), "Blocked"));
+        assert!(!list.blocks(SongId::library(42), "Anyone"));
+    }
+
+    #[test]
+    fn keeps_newest_first_and_ignores_duplicates() {
+        let mut list = Blocklist::new(vec![song(2), song(1)], vec!["B".into(), "A".into()]);
+        assert_eq!(list.songs()[0].id, SongId::ncm(2));
+        assert_eq!(list.artists(), ["B", "A"]);
+
+        assert!(list.insert_song(song(3)));
+        assert!(!list.insert_song(song(1)));
+        assert_eq!(
+            list.songs().iter().map(|s| s.id.id).collect::<Vec<_>>(),
+            [3, 2, 1]
+        );
+
+        assert!(!list.insert_artist(" a ".into()));
+        assert!(!list.insert_artist("   ".into()));
+        assert!(list.remove_artist("b"));
+        assert_eq!(list.artists(), ["A"]);
+
+        assert!(list.remove_song(SongId::ncm(2)));
+        assert!(!list.remove_song(SongId::ncm(2)));
+        assert!(!list.contains_song(SongId::ncm(2)));
+    }
+}
@@ -0,0 +1,87 @@
+//! Call ducking
+//!
+//! While a call app records from the microphone, playback is lowered or
+//! paused and brought back once the call ends. Which apps are recording is
+//! polled from the platform (see [`crate::platform::microphone`]); apps are
+//! recognised as call apps by name.
+
+use std::time::Duration;
+
+/// How often the microphone is checked
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Volume percents offered while lowered
+pub const LOWER_PERCENTS: [u32; 4] = [10, 20, 35, 50];
+
+/// Seconds of microphone use offered before reacting
+pub const AFTER_SECS: [u32; 4] = [1, 3, 10, 30];
+
+/// Lowercase names of call and meeting apps, including the names their
+/// Store packages go by
+const CALL_APPS: [&str; 25] = [
+    "zoom",
+    "teams",
+    "msteams",
+    "skype",
+    "skypeapp",
+    "discord",
+    "slack",
+    "webex",
+    "facetime",
+    "telegram",
+    "telegramdesktop",
+    "whatsapp",
+    "whatsappdesktop",
+    "signal",
+    "mumble",
+    "wechat",
+    "weixin",
+    "qq",
+    "dingtalk",
+    "feishu",
+    "lark",
+    "wemeet",
+    "wemeetapp",
+    "voov",
+    "voovmeeting",
+];
+
+/// Whether an app is a known call app
+///
+/// Each word of the name has to match a whole entry, so "QQMusic" or
+/// "Skylark" don't count as calls.
+pub fn is_call_app(name: &str) -> bool {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| CALL_APPS.contains(&word))
+}
+
+/// Whether the apps recording mean a call is on
+pub fn in_call(recording: &[String], any_app: bool) -> bool {
+    recording.iter().any(|app| any_app || is_call_app(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_call_apps_count_by_default() {
+        let recording = vec!["OBS Studio".to_string()];
+        assert!(!in_call(&recording, false));
+        assert!(in_call(&recording, true));
+
+        let recording = vec!["ZOOM VoiceEngine".to_string(), "OBS Studio".to_string()];
+        assert!(in_call(&recording, false));
+        assert!(!in_call(&[], true));
+    }
+
+    #[test]
+    fn test_call_apps_match_whole_names() {
+        assert!(is_call_app("Microsoft Teams"));
+        assert!(is_call_app("QQ"));
+        assert!(is_call_app("5319275A.WhatsAppDesktop"));
+        assert!(!is_call_app("QQMusic"));
+        assert!(!is_call_app("Skylark"));
+    }
+}
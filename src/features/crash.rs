@@ -0,0 +1,258 @@
+//! Crash recovery
+//!
+//! A panic hook writes the last known playback session and a crash report
+//! into the crash directory. On the next launch [`take_pending`] picks them
+//! up so the user can restore the session and optionally report the crash.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use parking_lot::Mutex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::logs;
+use crate::database::DbSong;
+
+const SESSION_FILE: &str = "session.json";
+const REPORT_FILE: &str = "report.txt";
+
+/// Number of log lines included in a crash report
+const REPORT_LOG_LINES: usize = 200;
+
+/// Issue tracker used for crash submissions
+const ISSUE_URL: &str = "https://github.com/ArcticFoxNetwork/Rustle/issues/new";
+
+/// Longest report body put into an issue URL (browsers truncate long URLs)
+const MAX_ISSUE_BODY: usize = 6000;
+
+static SESSION: Mutex<Option<SessionSnapshot>> = Mutex::new(None);
+
+/// URLs, with whatever path and query string they carry
+static URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\b[a-z][a-z0-9+.-]*://[^\s"'<>]+"#).unwrap());
+
+/// Song, user and playlist IDs, and hex tokens such as hashes and keys
+static ID: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:\d{5,}|[0-9a-fA-F]{16,})\b").unwrap());
+
+/// Playback session saved by the panic hook
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub queue: Vec<DbSong>,
+    pub queue_index: Option<usize>,
+    pub position_secs: f64,
+}
+
+/// Data left behind by a previous crash
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub report: String,
+    pub session: Option<SessionSnapshot>,
+}
+
+/// Get the directory crash data is written to
+pub fn crash_dir() -> PathBuf {
+    crate::utils::data_dir().join("crash")
+}
+
+/// Record the current session so it can be saved if the app panics
+///
+/// The queue is only copied when it differs from the last recorded one.
+pub fn update_session(queue: &[DbSong], queue_index: Option<usize>, position_secs: f64) {
+    let mut session = SESSION.lock();
+    let snapshot = session.get_or_insert_with(SessionSnapshot::default);
+
+    let queue_changed = snapshot.queue.len() != queue.len()
+        || snapshot
+            .queue
+            .iter()
+            .zip(queue)
+            .any(|(a, b)| a.id != b.id || a.file_path != b.file_path);
+    if queue_changed {
+        snapshot.queue = queue.to_vec();
+    }
+    snapshot.queue_index = queue_index;
+    snapshot.position_secs = position_secs;
+}
+
+/// Install the panic hook (keeps the default hook's stderr output)
+///
+/// Only panics that take the app down leave a report behind; the next launch
+/// would otherwise offer to restore a session that never ended.
+pub fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        if is_fatal() {
+            write_crash_files(info);
+        }
+    }));
+}
+
+/// Whether a panic on the current thread ends the app
+///
+/// With `panic = "abort"` every panic does. Otherwise only the main thread's
+/// does: worker threads and async tasks unwind and the app carries on.
+fn is_fatal() -> bool {
+    cfg!(panic = "abort") || std::thread::current().name() == Some("main")
+}
+
+fn write_crash_files(info: &std::panic::PanicHookInfo<'_>) {
+    let dir = crash_dir();
+    if std::fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+
+    // try_lock: the panic may have happened while the session was being updated
+    let session = SESSION.try_lock().and_then(|s| s.clone());
+    if let Some(json) = session
+        .filter(|s| !s.queue.is_empty())
+        .and_then(|s| serde_json::to_string(&s).ok())
+    {
+        let _ = std::fs::write(dir.join(SESSION_FILE), json);
+    }
+
+    let _ = std::fs::write(dir.join(REPORT_FILE), build_report(info));
+}
+
+fn build_report(info: &std::panic::PanicHookInfo<'_>) -> String {
+    let mut out = String::new();
+
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<non-string panic payload>".to_string());
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+    let thread = std::thread::current();
+
+    let _ = writeln!(out, "Rustle {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        out,
+        "Platform: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let _ = writeln!(out, "Time: {}", chrono::Local::now().to_rfc3339());
+    let _ = writeln!(out, "Thread: {}", thread.name().unwrap_or("<unnamed>"));
+    let _ = writeln!(out, "Panic: {}", message);
+    let _ = writeln!(out, "Location: {}", location);
+    let _ = writeln!(
+        out,
+        "\nBacktrace:\n{}",
+        std::backtrace::Backtrace::force_capture()
+    );
+
+    let _ = writeln!(out, "\nRecent log:");
+    for record in logs::try_tail(REPORT_LOG_LINES) {
+        let _ = writeln!(out, "{}", record);
+    }
+
+    out
+}
+
+/// Read and clear the data left by a previous crash, if any
+pub fn take_pending() -> Option<CrashReport> {
+    let dir = crash_dir();
+    let report = std::fs::read_to_string(dir.join(REPORT_FILE)).ok()?;
+
+    let session_path = dir.join(SESSION_FILE);
+    let session = std::fs::read_to_string(&session_path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    let _ = std::fs::remove_file(dir.join(REPORT_FILE));
+    let _ = std::fs::remove_file(session_path);
+
+    Some(CrashReport { report, session })
+}
+
+/// Strip user-identifying paths, URLs and IDs from a report
+///
+/// Log lines mention what was played and fetched, so the report can be
+/// made public without saying who it came from or what they listened to.
+pub fn anonymize(report: &str) -> String {
+    let out = URL.replace_all(report, "<url>");
+    let mut out = ID.replace_all(&out, "<id>").into_owned();
+    if let Some(home) = dirs::home_dir() {
+        let home = home.to_string_lossy();
+        if !home.is_empty() {
+            out = out.replace(home.as_ref(), "~");
+        }
+    }
+    for var in ["USER", "USERNAME"] {
+        if let Some(name) = std::env::var(var).ok().filter(|name| name.len() > 2) {
+            out = out.replace(&name, "<user>");
+        }
+    }
+    out
+}
+
+/// Issue tracker URL pre-filled with an anonymized report
+pub fn issue_url(report: &str) -> String {
+    let mut body = anonymize(report);
+    if body.len() > MAX_ISSUE_BODY {
+        let mut end = MAX_ISSUE_BODY;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+        body.push_str("\n…");
+    }
+    let body = format!("**Crash report**\n\n```\n{}\n```", body);
+
+    format!(
+        "{}?title={}&body={}",
+        ISSUE_URL,
+        urlencoding::encode("Crash report"),
+        urlencoding::encode(&body)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker_panics_are_not_fatal() {
+        let fatal = std::thread::Builder::new()
+            .name("worker".to_string())
+            .spawn(is_fatal)
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(fatal, cfg!(panic = "abort"));
+    }
+
+    #[test]
+    fn test_anonymize_home_dir() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        let report = format!("failed to open {}/Music/a.flac", home.display());
+        let anonymized = anonymize(&report);
+        assert!(anonymized.contains("~/Music/a.flac"));
+        assert!(!anonymized.contains(&home.display().to_string()));
+    }
+
+    #[test]
+    fn test_anonymize_urls_and_ids() {
+        let report = "Fetching https://music.example.com/song?id=1850183&token=ab12 for song 1850183 \
+                      (hash 9f86d081884c7d659a2feaa0c55ad015)";
+        let anonymized = anonymize(report);
+        assert_eq!(anonymized, "Fetching <url> for song <id> (hash <id>)");
+    }
+
+    #[test]
+    fn test_issue_url_truncates_body() {
+        let url = issue_url(&"x".repeat(MAX_ISSUE_BODY * 2));
+        assert!(url.starts_with(ISSUE_URL));
+        assert!(url.len() < MAX_ISSUE_BODY * 2);
+    }
+}
@@ -0,0 +1,179 @@
+//! Diagnostic bundle export
+//!
+//! Collects everything a bug report usually needs into one text file:
+//! build and platform info, GPU adapters and the lyrics renderer tier,
+//! settings with credentials redacted, cache statistics, background job
+//! counters, and the buffered application log, anonymized the same way as
+//! crash reports.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::crash;
+use super::jobs;
+use super::logs;
+use super::settings::Settings;
+use super::startup;
+use crate::cache::calculate_cache_stats;
+
+/// Setting keys whose values are replaced before export
+const SECRET_KEYS: &[&str] = &["password", "username", "token", "cookie"];
+
+/// Default file name for a new bundle
+pub fn default_file_name() -> String {
+    format!(
+        "rustle-diagnostics-{}.txt",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Build the bundle and write it to `path`
+///
+/// Enumerates GPU adapters and walks the cache directories, so call this
+/// from a blocking task.
+pub fn export_bundle(path: &Path, settings: &Settings) -> std::io::Result<()> {
+    std::fs::write(path, build_bundle(settings))
+}
+
+fn build_bundle(settings: &Settings) -> String {
+    let mut out = String::new();
+
+    section(&mut out, "Rustle diagnostics");
+    let _ = writeln!(out, "Version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        out,
+        "Platform: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let _ = writeln!(out, "Generated: {}", chrono::Local::now().to_rfc3339());
+
+    section(&mut out, "GPU adapters");
+    let instance = wgpu::Instance::default();
+    let adapters = instance.enumerate_adapters(wgpu::Backends::all());
+    if adapters.is_empty() {
+        let _ = writeln!(out, "(none found)");
+    }
+    for adapter in adapters {
+        let info = adapter.get_info();
+        let _ = writeln!(
+            out,
+            "{} [{:?}, {:?}] vendor={:#06x} device={:#06x} driver={} {}",
+            info.name,
+            info.backend,
+            info.device_type,
+            info.vendor,
+            info.device,
+            info.driver,
+            info.driver_info
+        );
+    }
+
+    section(&mut out, "Lyrics renderer");
+    match crate::features::lyrics::engine::gpu_caps::current() {
+        Some(tier) => {
+            let _ = writeln!(out, "Tier: {}", tier);
+        }
+        None => {
+            let _ = writeln!(out, "(not created yet)");
+        }
+    }
+
+    section(&mut out, "Settings");
+    match serde_json::to_value(settings) {
+        Ok(mut value) => {
+            redact(&mut value);
+            let _ = writeln!(
+                out,
+                "{}",
+                serde_json::to_string_pretty(&value).unwrap_or_default()
+            );
+        }
+        Err(e) => {
+            let _ = writeln!(out, "(failed to serialize: {})", e);
+        }
+    }
+
+    section(&mut out, "Cache");
+    let stats = calculate_cache_stats();
+    let _ = writeln!(out, "Files: {}", stats.file_count);
+    let _ = writeln!(out, "Total: {} bytes", stats.total_bytes);
+    let _ = writeln!(out, "Songs: {} bytes", stats.songs_bytes);
+    let _ = writeln!(out, "Covers: {} bytes", stats.covers_bytes);
+    let _ = writeln!(out, "Banners: {} bytes", stats.banners_bytes);
+    let _ = writeln!(out, "Avatars: {} bytes", stats.avatars_bytes);
+
+    section(&mut out, "Background jobs");
+    for (category, metrics) in jobs::metrics() {
+        let _ = writeln!(
+            out,
+            "{:?}: running={} queued={} completed={} aborted={}",
+            category, metrics.running, metrics.queued, metrics.completed, metrics.aborted
+        );
+    }
+
+    section(&mut out, "Startup");
+    for (stage, took) in startup::stages() {
+        let _ = writeln!(out, "{}: {:?}", stage, took);
+    }
+
+    section(&mut out, "Log");
+    let mut log = String::new();
+    for record in logs::snapshot() {
+        let _ = writeln!(log, "{}", record);
+    }
+    out.push_str(&crash::anonymize(&log));
+
+    out
+}
+
+fn section(out: &mut String, title: &str) {
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    let _ = writeln!(out, "===== {} =====", title);
+}
+
+/// Replace credential values anywhere in the settings tree
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SECRET_KEYS.iter().any(|secret| key.contains(secret)) {
+                    if !field.is_null() {
+                        *field = Value::String("<redacted>".to_string());
+                    }
+                } else {
+                    redact(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_proxy_credentials() {
+        let mut settings = Settings::default();
+        settings.network.proxy_host = "127.0.0.1".to_string();
+        settings.network.proxy_username = Some("alice".to_string());
+        settings.network.proxy_password = Some("hunter2".to_string());
+
+        let mut value = serde_json::to_value(&settings).unwrap();
+        redact(&mut value);
+
+        let network = &value["network"];
+        assert_eq!(network["proxy_host"], "127.0.0.1");
+        assert_eq!(network["proxy_username"], "<redacted>");
+        assert_eq!(network["proxy_password"], "<redacted>");
+        assert!(!value.to_string().contains("hunter2"));
+    }
+}
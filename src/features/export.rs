@@ -0,0 +1,315 @@
+//! Exporting songs to other formats
+//!
+//! Library files and NCM songs already in the song cache are converted with
+//! ffmpeg, which has to be installed; Rustle ships no encoders of its own.
+//! Tags, cover art and lyrics are written afterwards from what Rustle shows
+//! for the song, local corrections included, rather than copied from the
+//! source file. Each song is encoded to a `.tmp` file next to its final name
+//! and only renamed once it is complete, so a cancelled export leaves
+//! nothing half-written behind.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::media::tags::{self, TagUpdate};
+use super::song_source;
+use crate::database::DbSong;
+
+/// Bitrates offered for lossy formats, in kbps
+pub const BITRATES: [u32; 4] = [128, 192, 256, 320];
+
+/// Format songs are exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Mp3,
+    Aac,
+    Flac,
+    Opus,
+}
+
+impl ExportFormat {
+    pub const ALL: [Self; 4] = [Self::Mp3, Self::Aac, Self::Flac, Self::Opus];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Mp3 => "MP3",
+            Self::Aac => "AAC (M4A)",
+            Self::Flac => "FLAC",
+            Self::Opus => "Opus",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Aac => "m4a",
+            Self::Flac => "flac",
+            Self::Opus => "opus",
+        }
+    }
+
+    /// Whether the bitrate applies
+    pub fn is_lossy(self) -> bool {
+        self != Self::Flac
+    }
+
+    /// ffmpeg encoder and muxer arguments
+    fn codec_args(self, bitrate_kbps: u32) -> Vec<String> {
+        let (codec, muxer) = match self {
+            Self::Mp3 => ("libmp3lame", "mp3"),
+            Self::Aac => ("aac", "ipod"),
+            Self::Flac => ("flac", "flac"),
+            Self::Opus => ("libopus", "opus"),
+        };
+        let mut args = vec!["-c:a".to_string(), codec.to_string()];
+        if self.is_lossy() {
+            args.extend(["-b:a".to_string(), format!("{}k", bitrate_kbps)]);
+        }
+        args.extend(["-f".to_string(), muxer.to_string()]);
+        args
+    }
+}
+
+/// Folder exports go to unless another is chosen
+pub fn default_dir() -> PathBuf {
+    dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Rustle Export")
+}
+
+/// A song ready to convert, with the metadata to tag it with
+#[derive(Debug, Clone)]
+pub struct ExportItem {
+    pub source: PathBuf,
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub cover: Option<Vec<u8>>,
+    /// LRC text
+    pub lyrics: Option<String>,
+}
+
+impl ExportItem {
+    /// Gather what is on this device for `song`
+    ///
+    /// Returns `None` for NCM songs whose audio isn't in the song cache.
+    /// Blocking, call off the UI thread.
+    pub fn prepare(song: &DbSong) -> Option<Self> {
+        let (source, cover, lyrics) = match song_source::ncm_id(song) {
+            None => {
+                let source = PathBuf::from(&song.file_path);
+                if !source.is_file() {
+                    return None;
+                }
+                let cover = song
+                    .cover_path
+                    .as_deref()
+                    .filter(|path| !path.starts_with("http"))
+                    .and_then(|path| std::fs::read(path).ok())
+                    .or_else(|| {
+                        super::media::find_cover_art(&source).and_then(|art| art.to_bytes().ok())
+                    });
+                let lyrics = super::media::find_lyrics(&source);
+                (source, cover, lyrics)
+            }
+            Some(ncm_id) => {
+                let source = song_source::cached_audio(ncm_id)?;
+                let covers = crate::utils::covers_cache_dir();
+                let cover = [
+                    format!("cover_{}_large", ncm_id),
+                    format!("cover_{}", ncm_id),
+                ]
+                .iter()
+                .find_map(|stem| crate::utils::find_cached_image(&covers, stem))
+                .and_then(|path| std::fs::read(path).ok());
+                let lyrics = super::lyrics::load_cached_lyrics(ncm_id);
+                (source, cover, lyrics)
+            }
+        };
+
+        Some(Self {
+            source,
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            album: song.album.clone(),
+            cover,
+            lyrics: lyrics
+                .map(|lines| super::lyrics::stringify_lrc(&lines))
+                .filter(|lrc| !lrc.trim().is_empty()),
+        })
+    }
+
+    fn file_stem(&self) -> String {
+        file_stem(&self.artist, &self.title)
+    }
+}
+
+/// `Artist - Title`, safe as a file name everywhere
+pub fn file_stem(artist: &str, title: &str) -> String {
+    let name = if artist.trim().is_empty() {
+        title.to_string()
+    } else {
+        format!("{} - {}", artist, title)
+    };
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Windows refuses names ending in a dot or space
+    let cleaned = cleaned.trim().trim_end_matches('.').to_string();
+    if cleaned.is_empty() {
+        "Untitled".to_string()
+    } else if is_device_name(cleaned.split('.').next().unwrap_or_default()) {
+        format!("_{}", cleaned)
+    } else {
+        cleaned
+    }
+}
+
+/// Whether Windows reserves `name` for a device, which it does whatever
+/// extension follows
+fn is_device_name(name: &str) -> bool {
+    let name = name.trim_end().to_ascii_uppercase();
+    match name.as_str() {
+        "CON" | "PRN" | "AUX" | "NUL" => true,
+        _ => {
+            (name.starts_with("COM") || name.starts_with("LPT"))
+                && name.len() == 4
+                && name.as_bytes()[3].is_ascii_digit()
+        }
+    }
+}
+
+/// Path in `folder` that isn't taken yet
+fn free_path(folder: &Path, stem: &str, extension: &str) -> PathBuf {
+    let mut path = folder.join(format!("{}.{}", stem, extension));
+    let mut n = 2;
+    while path.exists() {
+        path = folder.join(format!("{} ({}).{}", stem, n, extension));
+        n += 1;
+    }
+    path
+}
+
+/// Removes a partly written file unless it is kept
+struct Partial(Option<PathBuf>);
+
+impl Partial {
+    fn keep(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for Partial {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Convert `item` into `folder`, returning the file written
+pub async fn export(
+    item: ExportItem,
+    format: ExportFormat,
+    bitrate_kbps: u32,
+    folder: &Path,
+) -> Result<PathBuf> {
+    tokio::fs::create_dir_all(folder)
+        .await
+        .context("Failed to create export folder")?;
+    let target = free_path(folder, &item.file_stem(), format.extension());
+    let temp = target.with_extension(format!("{}.tmp", format.extension()));
+    let partial = Partial(Some(temp.clone()));
+
+    let mut ffmpeg = tokio::process::Command::new("ffmpeg");
+    ffmpeg
+        .args(["-hide_banner", "-loglevel", "error", "-nostdin", "-y", "-i"])
+        .arg(&item.source)
+        // Audio only, without the source's tags
+        .args(["-map", "0:a:0", "-map_metadata", "-1"])
+        .args(format.codec_args(bitrate_kbps))
+        .arg(&temp)
+        .kill_on_drop(true);
+    #[cfg(target_os = "windows")]
+    ffmpeg.creation_flags(crate::platform::process::CREATE_NO_WINDOW);
+    let output = ffmpeg.output().await;
+    let output = match output {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            bail!("ffmpeg was not found; install it to export songs")
+        }
+        Err(e) => return Err(e).context("Failed to run ffmpeg"),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "ffmpeg failed: {}",
+            stderr.lines().last().unwrap_or("unknown error")
+        );
+    }
+
+    let update = TagUpdate {
+        title: Some(item.title),
+        artist: Some(item.artist),
+        album: Some(item.album),
+        cover: item.cover,
+        lyrics: item.lyrics,
+        synced_lyrics: None,
+    };
+    let tagged = temp.clone();
+    tokio::task::spawn_blocking(move || tags::write_tags(&tagged, &update)).await??;
+
+    tokio::fs::rename(&temp, &target)
+        .await
+        .context("Failed to move exported file into place")?;
+    partial.keep();
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(artist: &str, title: &str) -> ExportItem {
+        ExportItem {
+            source: PathBuf::new(),
+            title: title.to_string(),
+            artist: artist.to_string(),
+            album: String::new(),
+            cover: None,
+            lyrics: None,
+        }
+    }
+
+    #[test]
+    fn test_file_names_are_safe() {
+        assert_eq!(item("AC/DC", "T.N.T.").file_stem(), "AC_DC - T.N.T");
+        assert_eq!(item("", "What?").file_stem(), "What_");
+        assert_eq!(item(" ", " ").file_stem(), "Untitled");
+        assert_eq!(item("", "con").file_stem(), "_con");
+        assert_eq!(item("", "COM1.remix").file_stem(), "_COM1.remix");
+        assert_eq!(item("", "Console").file_stem(), "Console");
+    }
+
+    #[test]
+    fn test_bitrate_only_for_lossy_formats() {
+        assert_eq!(
+            ExportFormat::Opus.codec_args(192),
+            ["-c:a", "libopus", "-b:a", "192k", "-f", "opus"]
+        );
+        assert_eq!(
+            ExportFormat::Flac.codec_args(192),
+            ["-c:a", "flac", "-f", "flac"]
+        );
+    }
+}
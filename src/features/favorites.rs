@@ -0,0 +1,227 @@
+//! Favorites shared by local, cached and online songs
+//!
+//! Songs are keyed by their [`SongId`], like the blocklist. A local song linked to an NCM track is favorited under the
+//! track, so liking it also likes it on NCM. Unliking keeps the entry, so its
+//! time can be weighed against NCM's.
+//!
+//! NCM's liked list only has the time it last changed. When both sides
+//! disagree about a song, a local change made after that time wins and is
+//! pushed to NCM; otherwise NCM wins and the local entry follows it.
+//!
+//! The account's liked songs playlist is also mirrored with its songs, so it
+//! opens at once and offline; the mirror is reconciled with NCM in the
+//! background.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::api::{SongInfo, SongList};
+use crate::database::SongId;
+
+/// A song's favorite state and when it was last set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Favorite {
+    pub liked: bool,
+    /// Unix time in seconds
+    pub updated_at: i64,
+}
+
+/// Changes that bring local favorites and NCM likes together
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncPlan {
+    /// NCM tracks to like or unlike on NCM
+    pub push: Vec<(u64, bool)>,
+    /// Favorites to take over from NCM
+    pub adopt: Vec<(SongId, Favorite)>,
+}
+
+/// Favorite state of every song ever liked
+#[derive(Debug, Clone, Default)]
+pub struct Favorites {
+    entries: HashMap<SongId, Favorite>,
+}
+
+impl Favorites {
+    pub fn new(entries: impl IntoIterator<Item = (SongId, Favorite)>) -> Self {
+        Self {
+            entries: entries.into_iter().collect(),
+        }
+    }
+
+    pub fn is_liked(&self, key: SongId) -> bool {
+        self.entries.get(&key).is_some_and(|f| f.liked)
+    }
+
+    pub fn set(&mut self, key: SongId, favorite: Favorite) {
+        self.entries.insert(key, favorite);
+    }
+
+    /// Liked NCM tracks, e.g. for a pool of songs to pick from
+    pub fn liked_ncm_ids(&self) -> Vec<u64> {
+        self.entries
+            .iter()
+            .filter(|(_, f)| f.liked)
+            .filter_map(|(key, _)| key.ncm_id())
+            .collect()
+    }
+
+    /// Songs that show as liked, including local songs liked through their
+    /// linked NCM track
+    pub fn liked_song_ids(&self, links: &HashMap<SongId, u64>) -> HashSet<SongId> {
+        let mut liked: HashSet<SongId> = self
+            .entries
+            .iter()
+            .filter(|(_, f)| f.liked)
+            .map(|(key, _)| *key)
+            .collect();
+        liked.extend(
+            links
+                .iter()
+                .filter(|(_, ncm_id)| liked.contains(&SongId::ncm(**ncm_id)))
+                .map(|(song_id, _)| *song_id)
+                .collect::<Vec<_>>(),
+        );
+        liked
+    }
+
+    /// Compare with NCM's liked list, last changed at `check_point`
+    pub fn plan_sync(&self, remote: &[u64], check_point: i64) -> SyncPlan {
+        let remote: HashSet<u64> = remote.iter().copied().collect();
+        let mut plan = SyncPlan::default();
+
+        for (key, favorite) in &self.entries {
+            let Some(ncm_id) = key.ncm_id() else {
+                continue;
+            };
+            let remote_liked = remote.contains(&ncm_id);
+            if favorite.liked == remote_liked {
+                continue;
+            }
+            if favorite.updated_at > check_point {
+                plan.push.push((ncm_id, favorite.liked));
+            } else {
+                plan.adopt.push((
+                    *key,
+                    Favorite {
+                        liked: remote_liked,
+                        updated_at: check_point,
+                    },
+                ));
+            }
+        }
+
+        // Liked on NCM and never seen here
+        for ncm_id in remote {
+            let key = SongId::ncm(ncm_id);
+            if !self.entries.contains_key(&key) {
+                plan.adopt.push((
+                    key,
+                    Favorite {
+                        liked: true,
+                        updated_at: check_point,
+                    },
+                ));
+            }
+        }
+
+        plan.push.sort_unstable();
+        plan.adopt.sort_unstable_by_key(|(key, _)| *key);
+        plan
+    }
+}
+
+/// Saved copy of the account's liked songs playlist
+#[derive(Debug, Clone)]
+pub struct LikedMirror {
+    /// Account the playlist belongs to
+    pub user_id: u64,
+    pub playlist: SongList,
+    /// In playlist order
+    pub songs: Vec<SongInfo>,
+}
+
+impl LikedMirror {
+    pub fn track_ids(&self) -> Vec<u64> {
+        self.songs.iter().map(|song| song.id).collect()
+    }
+}
+
+/// How current the liked songs mirror is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LikedSync {
+    Syncing,
+    Synced,
+    /// Not synced yet, or the last sync failed; the saved copy is what's shown
+    Offline,
+}
+
+/// Key a song is favorited under: its linked NCM track when it has one
+pub fn favorite_key(song_id: SongId, links: &HashMap<SongId, u64>) -> SongId {
+    match links.get(&song_id) {
+        Some(ncm_id) if !song_id.is_ncm() => SongId::ncm(*ncm_id),
+        _ => song_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn favorite(liked: bool, updated_at: i64) -> Favorite {
+        Favorite { liked, updated_at }
+    }
+
+    #[test]
+    fn newer_local_changes_are_pushed() {
+        let favorites = Favorites::new([
+            (SongId::ncm(1), favorite(true, 200)),
+            (SongId::ncm(2), favorite(false, 200)),
+        ]);
+        let plan = favorites.plan_sync(&[2], 100);
+        assert_eq!(plan.push, vec![(1, true), (2, false)]);
+        assert!(plan.adopt.is_empty());
+    }
+
+    #[test]
+    fn newer_ncm_changes_are_adopted() {
+        let favorites = Favorites::new([
+            (SongId::ncm(1), favorite(true, 50)),
+            (SongId::ncm(2), favorite(false, 50)),
+        ]);
+        let plan = favorites.plan_sync(&[2, 3], 100);
+        assert!(plan.push.is_empty());
+        assert_eq!(
+            plan.adopt,
+            vec![
+                (SongId::ncm(1), favorite(false, 100)),
+                (SongId::ncm(2), favorite(true, 100)),
+                (SongId::ncm(3), favorite(true, 100)),
+            ]
+        );
+    }
+
+    #[test]
+    fn agreeing_and_local_songs_are_left_alone() {
+        let favorites = Favorites::new([
+            (SongId::ncm(1), favorite(true, 50)),
+            (SongId::library(7), favorite(true, 500)),
+        ]);
+        assert_eq!(favorites.plan_sync(&[1], 100), SyncPlan::default());
+    }
+
+    #[test]
+    fn linked_songs_share_their_track_favorite() {
+        let local = SongId::library;
+        let links = HashMap::from([(local(7), 1), (local(8), 2)]);
+        assert_eq!(favorite_key(local(7), &links), SongId::ncm(1));
+        assert_eq!(favorite_key(local(9), &links), local(9));
+        assert_eq!(favorite_key(SongId::ncm(5), &links), SongId::ncm(5));
+
+        let favorites = Favorites::new([
+            (SongId::ncm(1), favorite(true, 0)),
+            (local(9), favorite(true, 0)),
+        ]);
+        let liked = favorites.liked_song_ids(&links);
+        assert_eq!(liked, HashSet::from([SongId::ncm(1), local(7), local(9)]));
+        assert_eq!(favorites.liked_ncm_ids(), vec![1]);
+    }
+}
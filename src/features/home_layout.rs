@@ -0,0 +1,202 @@
+//! Homepage section order and visibility
+//!
+//! The layout is persisted as a list of sections with an enabled flag. On
+//! load it is normalized so duplicate entries are dropped and sections added
+//! in newer versions show up at the end.
+
+use serde::{Deserialize, Serialize};
+
+/// A section of the homepage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HomeSection {
+    Banners,
+    Trending,
+    TopPicks,
+    Toplists,
+    DailyRecommend,
+    RecentlyPlayed,
+}
+
+impl HomeSection {
+    pub const ALL: [HomeSection; 6] = [
+        HomeSection::Banners,
+        HomeSection::Trending,
+        HomeSection::TopPicks,
+        HomeSection::Toplists,
+        HomeSection::DailyRecommend,
+        HomeSection::RecentlyPlayed,
+    ];
+}
+
+/// One homepage section and whether it is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HomeSectionEntry {
+    pub section: HomeSection,
+    pub enabled: bool,
+}
+
+/// Homepage sections in display order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<HomeSectionEntry>", into = "Vec<HomeSectionEntry>")]
+pub struct HomeLayout {
+    sections: Vec<HomeSectionEntry>,
+}
+
+impl Default for HomeLayout {
+    fn default() -> Self {
+        Self {
+            sections: HomeSection::ALL
+                .iter()
+                .map(|&section| HomeSectionEntry {
+                    section,
+                    enabled: true,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Vec<HomeSectionEntry>> for HomeLayout {
+    fn from(entries: Vec<HomeSectionEntry>) -> Self {
+        let mut sections: Vec<HomeSectionEntry> = Vec::with_capacity(HomeSection::ALL.len());
+        for entry in entries {
+            if !sections.iter().any(|e| e.section == entry.section) {
+                sections.push(entry);
+            }
+        }
+        for section in HomeSection::ALL {
+            if !sections.iter().any(|e| e.section == section) {
+                sections.push(HomeSectionEntry {
+                    section,
+                    enabled: true,
+                });
+            }
+        }
+        Self { sections }
+    }
+}
+
+impl From<HomeLayout> for Vec<HomeSectionEntry> {
+    fn from(layout: HomeLayout) -> Self {
+        layout.sections
+    }
+}
+
+impl HomeLayout {
+    /// Every section in display order, hidden ones included
+    pub fn sections(&self) -> &[HomeSectionEntry] {
+        &self.sections
+    }
+
+    pub fn is_enabled(&self, section: HomeSection) -> bool {
+        self.sections
+            .iter()
+            .any(|e| e.section == section && e.enabled)
+    }
+
+    /// Show or hide a section, returning whether it is now shown
+    pub fn toggle(&mut self, section: HomeSection) -> bool {
+        match self.sections.iter_mut().find(|e| e.section == section) {
+            Some(entry) => {
+                entry.enabled = !entry.enabled;
+                entry.enabled
+            }
+            None => false,
+        }
+    }
+
+    /// Move a section up (negative) or down (positive), stopping at the ends
+    pub fn move_section(&mut self, section: HomeSection, offset: isize) {
+        let Some(from) = self.sections.iter().position(|e| e.section == section) else {
+            return;
+        };
+        let last = self.sections.len() as isize - 1;
+        let to = (from as isize + offset).clamp(0, last) as usize;
+        let entry = self.sections.remove(from);
+        self.sections.insert(to, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(layout: &HomeLayout) -> Vec<HomeSection> {
+        layout.sections().iter().map(|e| e.section).collect()
+    }
+
+    #[test]
+    fn default_shows_every_section() {
+        let layout = HomeLayout::default();
+        assert_eq!(order(&layout), HomeSection::ALL);
+        assert!(HomeSection::ALL.iter().all(|&s| layout.is_enabled(s)));
+    }
+
+    #[test]
+    fn loading_drops_duplicates_and_appends_missing_sections() {
+        let layout = HomeLayout::from(vec![
+            HomeSectionEntry {
+                section: HomeSection::RecentlyPlayed,
+                enabled: false,
+            },
+            HomeSectionEntry {
+                section: HomeSection::RecentlyPlayed,
+                enabled: true,
+            },
+            HomeSectionEntry {
+                section: HomeSection::Trending,
+                enabled: true,
+            },
+        ]);
+
+        assert_eq!(
+            order(&layout),
+            [
+                HomeSection::RecentlyPlayed,
+                HomeSection::Trending,
+                HomeSection::Banners,
+                HomeSection::TopPicks,
+                HomeSection::Toplists,
+                HomeSection::DailyRecommend,
+            ]
+        );
+        assert!(!layout.is_enabled(HomeSection::RecentlyPlayed));
+        assert!(layout.is_enabled(HomeSection::Banners));
+    }
+
+    #[test]
+    fn moving_stops_at_the_ends() {
+        let mut layout = HomeLayout::default();
+        layout.move_section(HomeSection::Banners, -1);
+        assert_eq!(order(&layout)[0], HomeSection::Banners);
+
+        layout.move_section(HomeSection::Banners, 1);
+        assert_eq!(
+            order(&layout)[..2],
+            [HomeSection::Trending, HomeSection::Banners]
+        );
+
+        layout.move_section(HomeSection::Trending, 10);
+        assert_eq!(order(&layout).last(), Some(&HomeSection::Trending));
+    }
+
+    #[test]
+    fn toggle_flips_visibility() {
+        let mut layout = HomeLayout::default();
+        assert!(!layout.toggle(HomeSection::Toplists));
+        assert!(!layout.is_enabled(HomeSection::Toplists));
+        assert!(layout.toggle(HomeSection::Toplists));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut layout = HomeLayout::default();
+        layout.toggle(HomeSection::Banners);
+        layout.move_section(HomeSection::RecentlyPlayed, -5);
+
+        let json = serde_json::to_string(&layout).unwrap();
+        let restored: HomeLayout = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, layout);
+    }
+}
@@ -0,0 +1,348 @@
+//! Event hooks
+//!
+//! User-configured shell commands and webhooks run when the track changes,
+//! playback pauses or resumes, or a song is liked, for OBS overlays and home
+//! automation without writing a plugin.
+//!
+//! Templates name metadata as `{title}`, `{artist}` and so on. In commands
+//! the placeholders become quoted references to `RUSTLE_*` environment
+//! variables rather than the text itself, so the shell reads song titles as
+//! data, never as syntax. That holds for `sh` and PowerShell, which expand a
+//! variable after parsing the line; `cmd.exe` expands `%VAR%` first and is
+//! never used. Webhooks get the placeholders URL-encoded, and the metadata as
+//! a JSON body.
+
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::database::DbSong;
+use crate::i18n::{Key, Locale};
+
+/// How long a hook may run before it is given up on
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Placeholders a template can use
+pub const VARIABLES: [&str; 8] = [
+    "event", "title", "artist", "album", "duration", "song_id", "cover", "path",
+];
+
+/// What a hook runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    #[default]
+    TrackChange,
+    Play,
+    Pause,
+    Like,
+    Unlike,
+}
+
+impl HookEvent {
+    pub fn all() -> [Self; 5] {
+        [
+            Self::TrackChange,
+            Self::Play,
+            Self::Pause,
+            Self::Like,
+            Self::Unlike,
+        ]
+    }
+
+    /// Name passed as `{event}`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TrackChange => "track_change",
+            Self::Play => "play",
+            Self::Pause => "pause",
+            Self::Like => "like",
+            Self::Unlike => "unlike",
+        }
+    }
+
+    pub fn display_name(&self, locale: Locale) -> &'static str {
+        match self {
+            Self::TrackChange => locale.get(Key::HookEventTrackChange),
+            Self::Play => locale.get(Key::HookEventPlay),
+            Self::Pause => locale.get(Key::HookEventPause),
+            Self::Like => locale.get(Key::HookEventLike),
+            Self::Unlike => locale.get(Key::HookEventUnlike),
+        }
+    }
+}
+
+/// How a hook is run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HookAction {
+    /// Run `target` with `sh`, or PowerShell on Windows
+    #[default]
+    Command,
+    /// POST the metadata to the URL in `target`
+    Webhook,
+}
+
+impl HookAction {
+    pub fn all() -> [Self; 2] {
+        [Self::Command, Self::Webhook]
+    }
+
+    pub fn display_name(&self, locale: Locale) -> &'static str {
+        match self {
+            Self::Command => locale.get(Key::HookActionCommand),
+            Self::Webhook => locale.get(Key::HookActionWebhook),
+        }
+    }
+}
+
+/// One configured hook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct EventHook {
+    pub event: HookEvent,
+    pub action: HookAction,
+    /// Command line or URL template
+    pub target: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Metadata a hook is run with
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HookVars {
+    pub event: String,
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub duration: String,
+    pub song_id: String,
+    pub cover: String,
+    pub path: String,
+}
+
+impl HookVars {
+    /// Metadata for `event`, about `song` if there is one
+    pub fn new(event: HookEvent, song: Option<&DbSong>) -> Self {
+        let mut vars = Self {
+            event: event.name().to_string(),
+            ..Default::default()
+        };
+        if let Some(song) = song {
+            vars.title = song.title.clone();
+            vars.artist = song.artist.clone();
+            vars.album = song.album.clone();
+            vars.duration = song.duration_secs.to_string();
+            vars.song_id = song.id.to_string();
+            vars.cover = song.cover_path.clone().unwrap_or_default();
+            // Streamed songs have no file to point at
+            if !song.file_path.starts_with("ncm://") {
+                vars.path = song.file_path.clone();
+            }
+        }
+        vars
+    }
+
+    fn get(&self, name: &str) -> &str {
+        match name {
+            "event" => &self.event,
+            "title" => &self.title,
+            "artist" => &self.artist,
+            "album" => &self.album,
+            "duration" => &self.duration,
+            "song_id" => &self.song_id,
+            "cover" => &self.cover,
+            "path" => &self.path,
+            _ => "",
+        }
+    }
+}
+
+/// Environment variable a placeholder is passed to commands in
+fn env_name(name: &str) -> String {
+    format!("RUSTLE_{}", name.to_uppercase())
+}
+
+/// Fill in `{name}` placeholders with what `fill` makes of each
+fn render(template: &str, fill: impl Fn(&str) -> String) -> String {
+    VARIABLES.iter().fold(template.to_string(), |text, name| {
+        text.replace(&format!("{{{}}}", name), &fill(name))
+    })
+}
+
+/// A command template with placeholders pointing at environment variables
+fn command_line(template: &str) -> String {
+    render(template, |name| {
+        if cfg!(target_os = "windows") {
+            format!("\"$env:{}\"", env_name(name))
+        } else {
+            format!("\"${}\"", env_name(name))
+        }
+    })
+}
+
+/// A webhook URL with the placeholders filled in
+fn webhook_url(template: &str, vars: &HookVars) -> String {
+    render(template, |name| {
+        urlencoding::encode(vars.get(name)).into_owned()
+    })
+}
+
+/// Run `hook` with `vars`, waiting until it finishes or times out
+pub async fn run(hook: &EventHook, vars: &HookVars, proxy: Option<&str>) -> Result<()> {
+    if hook.target.trim().is_empty() {
+        return Ok(());
+    }
+    match hook.action {
+        HookAction::Command => run_command(&hook.target, vars).await,
+        HookAction::Webhook => post_webhook(&hook.target, vars, proxy).await,
+    }
+}
+
+async fn run_command(template: &str, vars: &HookVars) -> Result<()> {
+    let line = command_line(template);
+    let mut cmd = shell(&line);
+    for name in VARIABLES {
+        cmd.env(env_name(name), vars.get(name));
+    }
+    let mut child = cmd.kill_on_drop(true).spawn()?;
+
+    match tokio::time::timeout(HOOK_TIMEOUT, child.wait()).await {
+        Ok(status) => {
+            let status = status?;
+            if !status.success() {
+                bail!("exited with {}", status);
+            }
+            Ok(())
+        }
+        // Dropping the child kills it
+        Err(_) => bail!("timed out after {}s", HOOK_TIMEOUT.as_secs()),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell(line: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.args(["-c", line]);
+    cmd
+}
+
+#[cfg(target_os = "windows")]
+fn shell(line: &str) -> tokio::process::Command {
+    use base64::Engine;
+
+    use crate::platform::process::CREATE_NO_WINDOW;
+
+    // An encoded command skips the command-line quoting rules altogether
+    let script: Vec<u8> = line.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let encoded = base64::engine::general_purpose::STANDARD.encode(script);
+    let mut cmd = tokio::process::Command::new("powershell");
+    cmd.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-ExecutionPolicy",
+        "Bypass",
+        "-EncodedCommand",
+        encoded.as_str(),
+    ])
+    .creation_flags(CREATE_NO_WINDOW);
+    cmd
+}
+
+async fn post_webhook(template: &str, vars: &HookVars, proxy: Option<&str>) -> Result<()> {
+    let url = webhook_url(template, vars);
+    crate::api::http::client(proxy)?
+        .post(&url)
+        .timeout(HOOK_TIMEOUT)
+        .json(vars)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HookVars {
+        HookVars {
+            event: "track_change".to_string(),
+            title: "Rock & Roll; rm -rf ~".to_string(),
+            artist: "$(whoami)".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_command_placeholders_become_variables() {
+        let line = command_line("notify {title} by {artist} {unknown}");
+        assert!(!line.contains("rm -rf"));
+        if cfg!(target_os = "windows") {
+            assert_eq!(
+                line,
+                "notify \"$env:RUSTLE_TITLE\" by \"$env:RUSTLE_ARTIST\" {unknown}"
+            );
+        } else {
+            assert_eq!(
+                line,
+                "notify \"$RUSTLE_TITLE\" by \"$RUSTLE_ARTIST\" {unknown}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_command_gets_metacharacters_as_text() {
+        let dir = std::env::temp_dir().join(format!("rustle_hook_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("title.txt");
+        let marker = dir.join("injected");
+        let _ = std::fs::remove_file(&marker);
+
+        let vars = HookVars {
+            title: format!("x & echo > {0} | echo > {0}", marker.display()),
+            ..Default::default()
+        };
+        let template = if cfg!(target_os = "windows") {
+            format!(
+                "Set-Content -NoNewline -Path '{}' -Value {{title}}",
+                out.display()
+            )
+        } else {
+            format!("printf %s {{title}} > '{}'", out.display())
+        };
+        let hook = EventHook {
+            target: template,
+            ..Default::default()
+        };
+        run(&hook, &vars, None).await.unwrap();
+
+        let written = std::fs::read_to_string(&out).unwrap();
+        let injected = marker.exists();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(written, vars.title);
+        assert!(!injected);
+    }
+
+    #[test]
+    fn test_webhook_url_encodes_values() {
+        let url = webhook_url("http://localhost/np?e={event}&t={title}", &vars());
+        assert_eq!(
+            url,
+            "http://localhost/np?e=track_change&t=Rock%20%26%20Roll%3B%20rm%20-rf%20~"
+        );
+    }
+
+    #[test]
+    fn test_hooks_default_to_enabled() {
+        let hook: EventHook =
+            serde_json::from_str(r#"{"event": "like", "action": "webhook", "target": "x"}"#)
+                .unwrap();
+        assert!(hook.enabled);
+        assert_eq!(hook.event, HookEvent::Like);
+    }
+}
@@ -135,10 +135,11 @@ impl CoverCache {
 }
 
 /// Get the default cover cache directory
+///
+/// Shared with downloaded online covers so cache size limits and clearing
+/// cover both.
 pub fn default_cache_dir() -> PathBuf {
-    directories::ProjectDirs::from("com", "rustle", "Rustle")
-        .map(|dirs| dirs.cache_dir().join("covers"))
-        .unwrap_or_else(|| PathBuf::from(".cache/covers"))
+    crate::utils::covers_cache_dir()
 }
 
 #[cfg(test)]
@@ -10,6 +10,7 @@ use lofty::tag::Accessor;
 use std::path::Path;
 
 use super::encoding::{decode_string, normalize_string};
+use crate::features::media::cover::embedded_picture;
 
 /// Extracted metadata from an audio file
 #[derive(Debug, Clone)]
@@ -99,17 +100,17 @@ pub fn extract_metadata(path: &Path) -> Result<AudioMetadata> {
         if let Some(genre) = tag.genre() {
             metadata.genre = Some(normalize_string(&decode_string(genre.as_bytes())));
         }
+    }
 
-        // Extract cover art
-        if let Some(picture) = tag.pictures().first() {
-            metadata.cover_data = Some(picture.data().to_vec());
-            metadata.cover_mime = Some(
-                picture
-                    .mime_type()
-                    .map(|m| m.to_string())
-                    .unwrap_or_else(|| "image/jpeg".to_string()),
-            );
-        }
+    // Cover art may live in a different tag than the text fields
+    if let Some(picture) = embedded_picture(&tagged_file) {
+        metadata.cover_data = Some(picture.data().to_vec());
+        metadata.cover_mime = Some(
+            picture
+                .mime_type()
+                .map(|m| m.to_string())
+                .unwrap_or_else(|| "image/jpeg".to_string()),
+        );
     }
 
     // If title is still unknown, use filename
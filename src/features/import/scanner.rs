@@ -18,6 +18,7 @@ use super::is_audio_file;
 use super::metadata::{AudioMetadata, apply_smart_parsing, extract_metadata};
 use super::progress::{ProgressSender, ScanProgress, ScanState, SkipReason};
 use crate::database::{Database, NewSong};
+use crate::features::plugins::{PluginRegistry, TrackQuery};
 
 /// Scanner configuration
 #[derive(Debug, Clone)]
@@ -32,6 +33,8 @@ pub struct ScanConfig {
     pub max_depth: Option<usize>,
     /// File extensions to include (empty = all supported)
     pub extensions: Vec<String>,
+    /// Plugins asked to fill in tags the file doesn't have
+    pub metadata_plugins: Option<Arc<PluginRegistry>>,
 }
 
 impl Default for ScanConfig {
@@ -42,6 +45,7 @@ impl Default for ScanConfig {
             smart_parsing: true,
             max_depth: None,
             extensions: Vec::new(),
+            metadata_plugins: None,
         }
     }
 }
@@ -139,6 +143,41 @@ fn check_external_cover(audio_path: &Path) -> (Option<String>, Option<PathBuf>)
     (None, None)
 }
 
+/// Fill unknown artist/album (and other empty tags) from metadata plugins
+fn apply_plugin_metadata(metadata: &mut AudioMetadata, path: &Path, plugins: &PluginRegistry) {
+    let defaults = AudioMetadata::default();
+    if metadata.artist != defaults.artist && metadata.album != defaults.album {
+        return;
+    }
+
+    let query = TrackQuery {
+        title: metadata.title.clone(),
+        artist: metadata.artist.clone(),
+        album: metadata.album.clone(),
+        duration_secs: metadata.duration_secs,
+        path: Some(path.to_string_lossy().into_owned()),
+    };
+    let Some(patch) = plugins.lookup_metadata(&query) else {
+        return;
+    };
+
+    // Without an artist tag the title is only a filename guess
+    let untagged = metadata.artist == defaults.artist;
+    if let Some(title) = patch.title.filter(|s| untagged && !s.is_empty()) {
+        metadata.title = title;
+    }
+    if let Some(artist) = patch.artist.filter(|s| untagged && !s.is_empty()) {
+        metadata.artist = artist;
+    }
+    if let Some(album) = patch.album.filter(|s| !s.is_empty()) {
+        if metadata.album == defaults.album {
+            metadata.album = album;
+        }
+    }
+    metadata.year = metadata.year.or(patch.year);
+    metadata.genre = metadata.genre.take().or(patch.genre);
+}
+
 /// Process a single audio file
 fn process_file(
     path: &Path,
@@ -163,6 +202,11 @@ fn process_file(
         }
     }
 
+    // Let metadata plugins fill in tags that are still missing
+    if let Some(plugins) = &config.metadata_plugins {
+        apply_plugin_metadata(&mut metadata, path, plugins);
+    }
+
     // Compute file hash if enabled
     let file_hash = if config.compute_hash {
         Some(compute_partial_hash(path)?)
@@ -240,13 +284,19 @@ pub async fn scan_and_import(
         return Ok(());
     }
 
-    // Get existing file paths from database for deduplication
-    let existing_paths: HashSet<String> = db
-        .get_all_songs()
-        .await?
-        .into_iter()
-        .map(|s| s.file_path)
+    // Get existing file paths from database for deduplication, noting the
+    // ones whose cover is missing so a rescan can fill it in
+    let existing_songs = db.get_all_songs().await?;
+    let missing_covers: HashSet<String> = existing_songs
+        .iter()
+        .filter(|s| match &s.cover_path {
+            Some(cover) if cover.starts_with("http") => false,
+            Some(cover) => !Path::new(cover).exists(),
+            None => true,
+        })
+        .map(|s| s.file_path.clone())
         .collect();
+    let existing_paths: HashSet<String> = existing_songs.into_iter().map(|s| s.file_path).collect();
 
     // Process files in parallel batches
     let batch_size = 100;
@@ -290,6 +340,14 @@ pub async fn scan_and_import(
 
             // Check if already exists
             if existing_paths.contains(&path_str) {
+                if missing_covers.contains(&path_str) {
+                    if let Some(cover) = result.as_ref().ok().and_then(|r| r.cover_path.as_ref()) {
+                        let cover = cover.to_string_lossy();
+                        if let Err(e) = db.update_song_cover(&path_str, &cover).await {
+                            tracing::warn!("Failed to update cover for {}: {}", path_str, e);
+                        }
+                    }
+                }
                 state.increment_skipped();
                 let _ = progress_tx.send(ScanProgress::Skipped {
                     current,
@@ -0,0 +1,433 @@
+//! Background job scheduler for I/O work
+//!
+//! Fire-and-forget work (database writes, cover downloads, library scans) goes
+//! through here instead of bare `tokio::spawn`. Each category has its own
+//! concurrency cap; once a category is saturated, queued user-facing jobs start
+//! before prefetch jobs.
+//!
+//! Jobs tied to the current page or song capture that scope's generation when
+//! created. [`cancel`] moves the generation on and aborts the scope's jobs, so
+//! superseded requests stop early and never deliver their results.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+use tokio::task::{AbortHandle, JoinHandle};
+
+/// Kind of work, each with its own concurrency cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    /// Database writes (queue, playback position, play history)
+    Database,
+    /// Cover and avatar downloads
+    Cover,
+    /// Online lyric fetches
+    Lyrics,
+    /// Disk- or CPU-bound work run on the blocking pool
+    Disk,
+    /// Library folder scans
+    Scan,
+}
+
+impl Category {
+    pub const ALL: [Category; 5] = [
+        Category::Database,
+        Category::Cover,
+        Category::Lyrics,
+        Category::Disk,
+        Category::Scan,
+    ];
+
+    fn max_concurrent(self) -> usize {
+        match self {
+            // SQLite serializes writers anyway
+            Category::Database => 2,
+            Category::Cover => 6,
+            Category::Lyrics => 2,
+            Category::Disk => 4,
+            Category::Scan => 1,
+        }
+    }
+
+    fn gate(self) -> &'static Mutex<Gate> {
+        &GATES[self as usize]
+    }
+}
+
+/// Which queued jobs start first when a category is saturated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// Something the user is looking at or waiting for
+    UserFacing,
+    /// Speculative work such as warming caches
+    Prefetch,
+}
+
+/// How long a job stays relevant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Runs to completion
+    App,
+    /// Cancelled when navigating to another page
+    Page,
+    /// Cancelled when another song starts playing
+    Song,
+}
+
+impl Scope {
+    fn generation(self) -> u64 {
+        GENERATIONS[self as usize].load(Ordering::Acquire)
+    }
+}
+
+/// Per-category counters for diagnostics
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JobMetrics {
+    pub running: usize,
+    pub queued: usize,
+    pub completed: u64,
+    pub aborted: u64,
+}
+
+/// Concurrency gate of one category
+struct Gate {
+    running: usize,
+    /// Waiters per priority, user-facing first
+    waiting: [VecDeque<oneshot::Sender<Permit>>; 2],
+    completed: u64,
+    aborted: u64,
+}
+
+impl Gate {
+    const fn new() -> Self {
+        Self {
+            running: 0,
+            waiting: [VecDeque::new(), VecDeque::new()],
+            completed: 0,
+            aborted: 0,
+        }
+    }
+}
+
+static GATES: [Mutex<Gate>; Category::ALL.len()] =
+    [const { Mutex::new(Gate::new()) }; Category::ALL.len()];
+
+/// Current generation of each scope, bumped by [`cancel`]
+static GENERATIONS: [AtomicU64; 3] = [const { AtomicU64::new(0) }; 3];
+
+/// Abort handles of unfinished jobs that can be cancelled
+static SCOPED_JOBS: Mutex<Vec<(u64, Scope, AbortHandle)>> = Mutex::new(Vec::new());
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Right to run one job in a category; hands itself to the next waiter on drop
+struct Permit {
+    category: Category,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut gate = self.category.gate().lock();
+        for priority in 0..gate.waiting.len() {
+            while let Some(waiter) = gate.waiting[priority].pop_front() {
+                match waiter.send(Permit {
+                    category: self.category,
+                }) {
+                    Ok(()) => return,
+                    // The waiter was cancelled; don't recurse into this lock
+                    Err(permit) => std::mem::forget(permit),
+                }
+            }
+        }
+        gate.running -= 1;
+    }
+}
+
+async fn acquire(category: Category, priority: Priority) -> Permit {
+    let waiter = {
+        let mut gate = category.gate().lock();
+        if gate.running < category.max_concurrent() {
+            gate.running += 1;
+            return Permit { category };
+        }
+        let (tx, rx) = oneshot::channel();
+        gate.waiting[priority as usize].push_back(tx);
+        rx
+    };
+
+    // Senders are only dropped after a successful send
+    waiter.await.expect("job gate dropped a waiter")
+}
+
+/// Counts the job as completed or aborted when it is dropped
+struct Tracker {
+    id: u64,
+    category: Category,
+    scope: Scope,
+    finished: bool,
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        {
+            let mut gate = self.category.gate().lock();
+            if self.finished {
+                gate.completed += 1;
+            } else {
+                gate.aborted += 1;
+            }
+        }
+        if self.scope != Scope::App {
+            SCOPED_JOBS.lock().retain(|(id, _, _)| *id != self.id);
+        }
+    }
+}
+
+/// Description of a background job
+///
+/// ```ignore
+/// Job::new(Category::Database).spawn(async move { let _ = db.update_volume(volume).await; });
+/// let path = Job::new(Category::Cover).prefetch().page().run(download).await;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Job {
+    category: Category,
+    priority: Priority,
+    scope: Scope,
+}
+
+impl Job {
+    /// A user-facing, app-scoped job
+    pub const fn new(category: Category) -> Self {
+        Self {
+            category,
+            priority: Priority::UserFacing,
+            scope: Scope::App,
+        }
+    }
+
+    /// Let user-facing jobs of the same category go first
+    pub const fn prefetch(mut self) -> Self {
+        self.priority = Priority::Prefetch;
+        self
+    }
+
+    /// Cancel the job when navigating away from the current page
+    pub const fn page(mut self) -> Self {
+        self.scope = Scope::Page;
+        self
+    }
+
+    /// Cancel the job when the current song changes
+    pub const fn song(mut self) -> Self {
+        self.scope = Scope::Song;
+        self
+    }
+
+    /// Run without waiting for the result
+    pub fn spawn<F>(self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        drop(self.start(future));
+    }
+
+    /// Run and wait for the result; `None` if the job was cancelled
+    ///
+    /// The job belongs to the scope generation current when this is called,
+    /// not when the returned future is first polled.
+    pub fn run<F, T>(self, future: F) -> impl Future<Output = Option<T>> + Send + 'static
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let generation = self.scope.generation();
+        async move { self.start_in(generation, future).await.ok().flatten() }
+    }
+
+    /// Start now and wait for the result; the job is aborted once the handle is dropped
+    ///
+    /// For work that is only wanted while something is on screen. Must be
+    /// called from within the runtime.
+    pub fn run_cancellable<F, T>(
+        self,
+        future: F,
+    ) -> (impl Future<Output = Option<T>> + Send + 'static, JobHandle)
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let join = self.start(future);
+        let handle = JobHandle(join.abort_handle());
+        (async move { join.await.ok().flatten() }, handle)
+    }
+
+    /// Run a blocking closure on the blocking pool; `None` if cancelled or it panicked
+    pub fn run_blocking<F, T>(self, f: F) -> impl Future<Output = Option<T>> + Send + 'static
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let job = self.run(async move { tokio::task::spawn_blocking(f).await.ok() });
+        async move { job.await.flatten() }
+    }
+
+    fn start<F, T>(self, future: F) -> JoinHandle<Option<T>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.start_in(self.scope.generation(), future)
+    }
+
+    fn start_in<F, T>(self, generation: u64, future: F) -> JoinHandle<Option<T>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let Job {
+            category,
+            priority,
+            scope,
+        } = self;
+
+        let handle = tokio::spawn(async move {
+            let mut tracker = Tracker {
+                id,
+                category,
+                scope,
+                finished: false,
+            };
+            let _permit = acquire(category, priority).await;
+            // Superseded while queued, or before it was even started
+            if scope.generation() != generation {
+                return None;
+            }
+            let output = future.await;
+            tracker.finished = true;
+            Some(output)
+        });
+
+        if scope != Scope::App {
+            SCOPED_JOBS.lock().push((id, scope, handle.abort_handle()));
+        }
+        handle
+    }
+}
+
+/// Aborts a job started with [`Job::run_cancellable`] when dropped
+#[derive(Debug)]
+pub struct JobHandle(AbortHandle);
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Cancel all jobs of a scope, queued or running
+pub fn cancel(scope: Scope) {
+    if scope == Scope::App {
+        return;
+    }
+    GENERATIONS[scope as usize].fetch_add(1, Ordering::AcqRel);
+
+    let cancelled: Vec<AbortHandle> = {
+        let mut jobs = SCOPED_JOBS.lock();
+        let (cancelled, kept) = std::mem::take(&mut *jobs)
+            .into_iter()
+            .partition(|(_, job_scope, _)| *job_scope == scope);
+        *jobs = kept;
+        cancelled.into_iter().map(|(_, _, handle)| handle).collect()
+    };
+    if !cancelled.is_empty() {
+        tracing::debug!("Cancelling {} {:?} jobs", cancelled.len(), scope);
+    }
+    for handle in cancelled {
+        handle.abort();
+    }
+}
+
+/// Current counters of every category
+pub fn metrics() -> Vec<(Category, JobMetrics)> {
+    Category::ALL
+        .iter()
+        .map(|&category| {
+            let gate = category.gate().lock();
+            let queued = gate
+                .waiting
+                .iter()
+                .flatten()
+                .filter(|waiter| !waiter.is_closed())
+                .count();
+            let metrics = JobMetrics {
+                running: gate.running,
+                queued,
+                completed: gate.completed,
+                aborted: gate.aborted,
+            };
+            (category, metrics)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_user_facing_jobs_start_before_prefetch() {
+        // Scans run one at a time, so holding a permit queues everything else
+        let permit = acquire(Category::Scan, Priority::UserFacing).await;
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let prefetch = {
+            let order = order.clone();
+            Job::new(Category::Scan)
+                .prefetch()
+                .start(async move { order.lock().push("prefetch") })
+        };
+        let user = {
+            let order = order.clone();
+            Job::new(Category::Scan).start(async move { order.lock().push("user") })
+        };
+        // Let both jobs reach the gate
+        while metrics()[Category::Scan as usize].1.queued < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        drop(permit);
+        prefetch.await.unwrap();
+        user.await.unwrap();
+        assert_eq!(*order.lock(), ["user", "prefetch"]);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_generation_drops_result() {
+        let stale = Job::new(Category::Lyrics).song().run(async { 1 });
+        cancel(Scope::Song);
+        // Created before the cancel, so it never runs even though it starts after
+        assert_eq!(stale.await, None);
+
+        let fresh = Job::new(Category::Lyrics).song().run(async { 2 });
+        assert_eq!(fresh.await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_dropping_handle_aborts_job() {
+        let (result, handle) = Job::new(Category::Disk).run_cancellable(async {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            1
+        });
+        drop(handle);
+        assert_eq!(result.await, None);
+
+        let (result, _handle) = Job::new(Category::Disk).run_cancellable(async { 2 });
+        assert_eq!(result.await, Some(2));
+    }
+}
@@ -29,6 +29,7 @@ pub enum Action {
     // UI controls
     ToggleQueue,
     ToggleFullscreen,
+    ToggleMemoryOverlay,
 }
 
 /// A keyboard shortcut consisting of modifiers and a key
@@ -453,6 +454,10 @@ impl Default for KeyBindings {
             Action::ToggleFullscreen,
             vec![KeyBinding::new(KeyCode::F11)],
         );
+        bindings.insert(
+            Action::ToggleMemoryOverlay,
+            vec![KeyBinding::new(KeyCode::M).primary().shift()],
+        );
 
         Self { bindings }
     }
@@ -464,6 +469,15 @@ impl KeyBindings {
         self.bindings.insert(action, bindings);
     }
 
+    /// Add default bindings for actions missing from a saved configuration
+    ///
+    /// Settings written before an action existed don't mention it at all.
+    pub fn fill_missing_defaults(&mut self) {
+        for (action, bindings) in Self::default().bindings {
+            self.bindings.entry(action).or_insert(bindings);
+        }
+    }
+
     /// Find the action that matches the given key event
     pub fn find_action(&self, key: &Key, modifiers: &Modifiers) -> Option<Action> {
         for (action, bindings) in &self.bindings {
@@ -503,6 +517,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fill_missing_defaults_keeps_custom_bindings() {
+        let mut bindings = KeyBindings {
+            bindings: HashMap::from([(Action::PlayPause, vec![KeyBinding::new(KeyCode::P)])]),
+        };
+        bindings.fill_missing_defaults();
+
+        assert_eq!(bindings.display_for_action(&Action::PlayPause), "P");
+        assert!(bindings.bindings.contains_key(&Action::ToggleMemoryOverlay));
+    }
+
     #[test]
     fn test_keybinding_display() {
         let binding = KeyBinding::new(KeyCode::P).ctrl().shift();
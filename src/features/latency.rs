@@ -0,0 +1,114 @@
+//! Output latency calibration
+//!
+//! Wireless headphones play audio 100–300ms after the app hands it over, so
+//! lyrics and the seek bar run ahead of what the user hears. Calibration plays
+//! a steady click and the user taps along with it; the typical gap between a
+//! click being sent and the tap is the device's latency.
+
+use std::time::{Duration, Instant};
+
+/// Time between calibration clicks
+pub const BEAT_INTERVAL: Duration = Duration::from_millis(750);
+
+/// Taps needed before an estimate is given
+pub const MIN_TAPS: usize = 6;
+
+/// Largest latency that can be set, in milliseconds
+pub const MAX_LATENCY_MS: u32 = 500;
+
+/// How far ahead of a click a tap may land and still count for it
+const ANTICIPATION: Duration = Duration::from_millis(150);
+
+/// A tap-along calibration in progress
+#[derive(Debug, Clone, Default)]
+pub struct Calibration {
+    /// When the most recent click was sent
+    last_beat: Option<Instant>,
+    /// Tap offsets from their click in milliseconds, negative when early
+    offsets_ms: Vec<i64>,
+}
+
+impl Calibration {
+    /// Record a click sent to the output at `at`
+    pub fn beat(&mut self, at: Instant) {
+        self.last_beat = Some(at);
+    }
+
+    /// Record a tap at `at`
+    ///
+    /// Taps just before the next click are counted as early for that click
+    /// rather than very late for the previous one.
+    pub fn tap(&mut self, at: Instant) {
+        let Some(beat) = self.last_beat else {
+            return;
+        };
+        let since = at.saturating_duration_since(beat);
+        let offset_ms = if since + ANTICIPATION >= BEAT_INTERVAL {
+            since.as_millis() as i64 - BEAT_INTERVAL.as_millis() as i64
+        } else {
+            since.as_millis() as i64
+        };
+        self.offsets_ms.push(offset_ms);
+    }
+
+    /// Number of taps so far
+    pub fn taps(&self) -> usize {
+        self.offsets_ms.len()
+    }
+
+    /// Estimated latency in milliseconds, once there are enough taps
+    ///
+    /// Uses the median so a stray tap doesn't skew the result.
+    pub fn estimate_ms(&self) -> Option<u32> {
+        if self.offsets_ms.len() < MIN_TAPS {
+            return None;
+        }
+        let mut sorted = self.offsets_ms.clone();
+        sorted.sort_unstable();
+        let median = sorted[sorted.len() / 2];
+        Some(median.clamp(0, MAX_LATENCY_MS as i64) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibrate(offsets_ms: &[i64]) -> Calibration {
+        // Leave room for early taps to look back one click
+        let start = Instant::now() + BEAT_INTERVAL;
+        let mut calibration = Calibration::default();
+        for (i, &offset) in offsets_ms.iter().enumerate() {
+            let beat = start + BEAT_INTERVAL * i as u32;
+            calibration.beat(beat);
+            let tap = if offset >= 0 {
+                beat + Duration::from_millis(offset as u64)
+            } else {
+                // An early tap comes while the previous click is still the latest
+                calibration.beat(beat - BEAT_INTERVAL);
+                beat - Duration::from_millis(offset.unsigned_abs())
+            };
+            calibration.tap(tap);
+        }
+        calibration
+    }
+
+    #[test]
+    fn estimates_median_offset() {
+        let calibration = calibrate(&[210, 190, 200, 480, 205, 195]);
+        assert_eq!(calibration.estimate_ms(), Some(205));
+    }
+
+    #[test]
+    fn early_taps_count_for_the_next_click() {
+        let calibration = calibrate(&[-40, -20, 10, -30, 0, -10]);
+        assert_eq!(calibration.estimate_ms(), Some(0));
+    }
+
+    #[test]
+    fn needs_enough_taps() {
+        let calibration = calibrate(&[200, 200, 200]);
+        assert_eq!(calibration.taps(), 3);
+        assert_eq!(calibration.estimate_ms(), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! Visibility-driven cover loading
+//!
+//! Cover grids report when a card scrolls into or out of view. A download
+//! starts when a card without a cover is shown and is aborted when the card
+//! scrolls away before it finishes. Cards that were shown while downloads were
+//! held back (metered connection) are remembered so they can be resumed.
+
+use std::collections::HashMap;
+
+use super::jobs::JobHandle;
+
+/// The image behind a lazily loaded cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CoverKey {
+    /// Cover of an NCM playlist card
+    Playlist(u64),
+    /// Album art of an NCM song row
+    Song(u64),
+}
+
+/// Visible covers still waiting for their image, and their running downloads
+#[derive(Debug)]
+pub struct CoverLoader<H = JobHandle> {
+    /// Cover URL of every visible card that has no image yet
+    visible: HashMap<CoverKey, String>,
+    /// Downloads in progress; dropping a handle aborts its download
+    in_flight: HashMap<CoverKey, H>,
+}
+
+impl<H> Default for CoverLoader<H> {
+    fn default() -> Self {
+        Self {
+            visible: HashMap::new(),
+            in_flight: HashMap::new(),
+        }
+    }
+}
+
+impl<H> CoverLoader<H> {
+    /// A card came into view; returns whether its download still needs starting
+    pub fn show(&mut self, key: CoverKey, url: String) -> bool {
+        if url.is_empty() {
+            return false;
+        }
+        self.visible.insert(key, url);
+        !self.in_flight.contains_key(&key)
+    }
+
+    /// A card left the view; its download is cancelled
+    pub fn hide(&mut self, key: CoverKey) {
+        self.visible.remove(&key);
+        self.in_flight.remove(&key);
+    }
+
+    /// Remember the download started for a visible card
+    pub fn track(&mut self, key: CoverKey, handle: H) {
+        self.in_flight.insert(key, handle);
+    }
+
+    /// A download ended, successfully or not
+    pub fn finish(&mut self, key: CoverKey, loaded: bool) {
+        self.in_flight.remove(&key);
+        if loaded {
+            self.visible.remove(&key);
+        }
+    }
+
+    /// Visible covers with no download running
+    pub fn pending(&self) -> Vec<(CoverKey, String)> {
+        self.visible
+            .iter()
+            .filter(|(key, _)| !self.in_flight.contains_key(key))
+            .map(|(key, url)| (*key, url.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Stand-in for a job handle that records being dropped
+    struct Handle(Rc<Cell<bool>>);
+
+    impl Drop for Handle {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    fn handle() -> (Handle, Rc<Cell<bool>>) {
+        let dropped = Rc::new(Cell::new(false));
+        (Handle(dropped.clone()), dropped)
+    }
+
+    #[test]
+    fn shown_cover_starts_once() {
+        let mut loader = CoverLoader::<Handle>::default();
+        let key = CoverKey::Playlist(1);
+        assert!(loader.show(key, "http://a".into()));
+
+        let (h, _) = handle();
+        loader.track(key, h);
+        assert!(!loader.show(key, "http://a".into()));
+        assert!(loader.pending().is_empty());
+    }
+
+    #[test]
+    fn empty_url_is_ignored() {
+        let mut loader = CoverLoader::<Handle>::default();
+        assert!(!loader.show(CoverKey::Song(1), String::new()));
+        assert!(loader.pending().is_empty());
+    }
+
+    #[test]
+    fn hiding_cancels_the_download() {
+        let mut loader = CoverLoader::default();
+        let key = CoverKey::Song(7);
+        loader.show(key, "http://a".into());
+        let (h, dropped) = handle();
+        loader.track(key, h);
+
+        loader.hide(key);
+        assert!(dropped.get());
+        assert!(loader.pending().is_empty());
+        assert!(loader.show(key, "http://a".into()));
+    }
+
+    #[test]
+    fn failed_download_stays_pending_while_visible() {
+        let mut loader = CoverLoader::default();
+        let failed = CoverKey::Playlist(1);
+        let loaded = CoverKey::Playlist(2);
+        for key in [failed, loaded] {
+            loader.show(key, "http://a".into());
+            let (h, _) = handle();
+            loader.track(key, h);
+        }
+
+        loader.finish(failed, false);
+        loader.finish(loaded, true);
+        assert_eq!(loader.pending(), vec![(failed, "http://a".to_string())]);
+    }
+}
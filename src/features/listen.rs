@@ -0,0 +1,239 @@
+//! Listen mode: naming the song another app is playing
+//!
+//! With the user's permission, a few seconds of what the computer plays are
+//! recorded (see [`capture`]), fingerprinted and looked up in an index of the
+//! library's fingerprints. Every local song is fingerprinted once in the
+//! background the first time listen mode starts, and only every
+//! [`INDEX_STRIDE`]th word is kept, which is plenty for a clip several
+//! seconds long to land on some of them. A hit says both which song is
+//! playing and how far into it the clip was, so its lyrics can follow along.
+//!
+//! Only songs in the library can be recognised, and nothing recorded leaves
+//! the device.
+
+pub mod capture;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::ncm_match::fingerprint::{self, FRAME_SECS, Fingerprint};
+use crate::database::SongId;
+
+/// Seconds recorded per lookup
+pub const CLIP_SECS: u32 = 8;
+
+/// Longest stretch of a song that is fingerprinted
+const MAX_SONG_SECS: u32 = 600;
+
+/// Every nth word of a song is kept in the index
+const INDEX_STRIDE: usize = 16;
+
+/// Words with more postings than this are silence or hum and say nothing
+const MAX_POSTINGS: usize = 256;
+
+/// Hits that have to agree on a song and offset before it counts
+const MIN_VOTES: u32 = 4;
+
+/// Offsets this many frames apart are counted as the same alignment
+const OFFSET_BUCKET: i64 = 2;
+
+/// A song heard in a clip
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListenMatch {
+    pub song_id: SongId,
+    /// How far into the song the clip started
+    pub position: Duration,
+}
+
+/// Library fingerprints by word, for looking up clips
+#[derive(Debug, Default)]
+pub struct ListenIndex {
+    /// Library row and frame of every indexed word
+    postings: HashMap<u32, Vec<(i64, u32)>>,
+    songs: usize,
+}
+
+impl ListenIndex {
+    /// Build from stored fingerprints by library row, as written by [`encode`]
+    pub fn build<'a>(rows: impl IntoIterator<Item = (i64, &'a [u8])>) -> Self {
+        let mut index = Self::default();
+        for (song_id, bytes) in rows {
+            let words = decode(bytes);
+            if words.is_empty() {
+                continue;
+            }
+            index.songs += 1;
+            for (i, word) in words.into_iter().enumerate() {
+                // All-zero words are what silence gives
+                if word != 0 {
+                    let frame = (i * INDEX_STRIDE) as u32;
+                    index
+                        .postings
+                        .entry(word)
+                        .or_default()
+                        .push((song_id, frame));
+                }
+            }
+        }
+        index
+    }
+
+    /// Number of songs that can be recognised
+    pub fn song_count(&self) -> usize {
+        self.songs
+    }
+
+    /// Find the song a clip was taken from
+    ///
+    /// Each word of the clip, and each word one bit away from it, is looked
+    /// up; every hit votes for its song at the offset it implies. The song
+    /// and offset with the most votes wins when it has enough of them and
+    /// clearly beats every other song.
+    pub fn identify(&self, clip: &Fingerprint) -> Option<ListenMatch> {
+        let words = clip.words();
+        let mut votes: HashMap<(i64, i64), u32> = HashMap::new();
+        for (i, &word) in words.iter().enumerate() {
+            if word == 0 {
+                continue;
+            }
+            let near = std::iter::once(word).chain((0..32).map(|bit| word ^ (1 << bit)));
+            for candidate in near {
+                let Some(postings) = self.postings.get(&candidate) else {
+                    continue;
+                };
+                if postings.len() > MAX_POSTINGS {
+                    continue;
+                }
+                for &(song_id, frame) in postings {
+                    let offset = i64::from(frame) - i as i64;
+                    *votes
+                        .entry((song_id, offset.div_euclid(OFFSET_BUCKET)))
+                        .or_default() += 1;
+                }
+            }
+        }
+
+        let mut best_per_song: HashMap<i64, (u32, i64)> = HashMap::new();
+        for ((song_id, bucket), count) in votes {
+            let best = best_per_song.entry(song_id).or_default();
+            if count > best.0 {
+                *best = (count, bucket);
+            }
+        }
+        let mut ranked: Vec<_> = best_per_song.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.0.cmp(&a.1.0));
+
+        let (song_id, (count, bucket)) = *ranked.first()?;
+        let runner_up = ranked.get(1).map_or(0, |(_, (count, _))| *count);
+        if count < MIN_VOTES || runner_up * 2 > count {
+            return None;
+        }
+
+        let start_frame = (bucket * OFFSET_BUCKET).max(0);
+        Some(ListenMatch {
+            song_id: SongId::library(song_id),
+            position: Duration::from_secs_f64(start_frame as f64 * FRAME_SECS),
+        })
+    }
+}
+
+/// Fingerprint a recorded clip
+pub fn fingerprint_clip(clip: &capture::Clip) -> Fingerprint {
+    fingerprint::compute(&clip.samples, clip.channels, clip.sample_rate)
+}
+
+/// Fingerprint a song file for the index, returning the bytes to store
+pub fn fingerprint_file(path: &Path) -> Result<Vec<u8>> {
+    let file = BufReader::new(File::open(path)?);
+    let fingerprint = fingerprint::from_reader_secs(file, MAX_SONG_SECS)?;
+    Ok(encode(&sparse(&fingerprint)))
+}
+
+/// The words of a song fingerprint that go in the index
+fn sparse(fingerprint: &Fingerprint) -> Vec<u32> {
+    fingerprint
+        .words()
+        .iter()
+        .step_by(INDEX_STRIDE)
+        .copied()
+        .collect()
+}
+
+/// Stored form of index words
+fn encode(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
+
+fn decode(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    const RATE: u32 = 11025;
+
+    /// Two tones that hop to new pitches every 150 ms, chosen by `seed`
+    fn melody(seed: u32, secs: f32) -> Vec<f32> {
+        let mut state = seed;
+        let mut next_pitch = move || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            350.0 + (state >> 8) as f32 / (1u32 << 24) as f32 * 1500.0
+        };
+
+        let segment = (RATE as f32 * 0.15) as usize;
+        let (mut low, mut high) = (0.0, 0.0);
+        (0..(RATE as f32 * secs) as usize)
+            .map(|n| {
+                if n % segment == 0 {
+                    (low, high) = (next_pitch(), next_pitch());
+                }
+                let t = n as f32 / RATE as f32;
+                0.5 * (TAU * low * t).sin() + 0.3 * (TAU * high * t).sin()
+            })
+            .collect()
+    }
+
+    fn stored(samples: &[f32]) -> Vec<u8> {
+        encode(&sparse(&fingerprint::compute(samples, 1, RATE)))
+    }
+
+    #[test]
+    fn test_identifies_song_and_position() {
+        let song = melody(7, 40.0);
+        let other = melody(99, 40.0);
+        let rows = [(1, stored(&song)), (2, stored(&other))];
+        let index = ListenIndex::build(rows.iter().map(|(id, bytes)| (*id, bytes.as_slice())));
+        assert_eq!(index.song_count(), 2);
+
+        // Quieter, as a clip recorded at a lower volume would be
+        let from = 15 * RATE as usize;
+        let to = from + CLIP_SECS as usize * RATE as usize;
+        let clip: Vec<f32> = song[from..to].iter().map(|s| s * 0.4).collect();
+        let heard = index
+            .identify(&fingerprint::compute(&clip, 1, RATE))
+            .unwrap();
+
+        assert_eq!(heard.song_id, SongId::library(1));
+        assert!((heard.position.as_secs_f64() - 15.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_unknown_audio_is_not_matched() {
+        let rows = [(1, stored(&melody(7, 40.0)))];
+        let index = ListenIndex::build(rows.iter().map(|(id, bytes)| (*id, bytes.as_slice())));
+
+        let clip = melody(1234, CLIP_SECS as f32);
+        assert_eq!(index.identify(&fingerprint::compute(&clip, 1, RATE)), None);
+    }
+}
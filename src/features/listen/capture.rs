@@ -0,0 +1,180 @@
+//! Capturing what the computer is playing
+//!
+//! Windows can record any output through WASAPI loopback, so the default
+//! output is used there. On Linux, PulseAudio and PipeWire keep their
+//! monitors away from ALSA, so the default output's monitor is recorded with
+//! `parec` as a PulseAudio client. Elsewhere loopback shows up as an input
+//! device, such as BlackHole on macOS, and is picked by name. Without one
+//! nothing is recorded: the permission given is for what the computer plays,
+//! so the microphone is never opened in its place.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use parking_lot::Mutex;
+use rodio::cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use rodio::cpal::{self, FromSample, Sample, SizedSample};
+use tracing::{debug, warn};
+
+/// Input device names that carry what the computer plays
+const LOOPBACK_NAMES: [&str; 5] = [
+    "monitor",
+    "loopback",
+    "stereo mix",
+    "blackhole",
+    "soundflower",
+];
+
+/// Interleaved samples captured from a device
+#[derive(Debug, Clone)]
+pub struct Clip {
+    pub samples: Vec<f32>,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+/// Record `secs` seconds of what the computer is playing
+///
+/// Blocks for the length of the recording.
+pub fn record(secs: u32) -> Result<Clip> {
+    #[cfg(target_os = "linux")]
+    match pulse::record(secs) {
+        Ok(clip) => return Ok(clip),
+        Err(e) => debug!("Recording the default monitor failed: {:#}", e),
+    }
+
+    let (device, config) = loopback_device()?;
+    let channels = config.channels();
+    let sample_rate = config.sample_rate().0;
+    debug!(
+        "Listening on {} ({} Hz, {} channels)",
+        device.name().unwrap_or_default(),
+        sample_rate,
+        channels
+    );
+
+    let samples = Arc::new(Mutex::new(Vec::with_capacity(
+        secs as usize * sample_rate as usize * channels as usize,
+    )));
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => open::<f32>(&device, &config.config(), samples.clone()),
+        cpal::SampleFormat::I16 => open::<i16>(&device, &config.config(), samples.clone()),
+        cpal::SampleFormat::I32 => open::<i32>(&device, &config.config(), samples.clone()),
+        cpal::SampleFormat::U16 => open::<u16>(&device, &config.config(), samples.clone()),
+        format => return Err(anyhow!("unsupported sample format {:?}", format)),
+    }?;
+    stream.play()?;
+    std::thread::sleep(Duration::from_secs(secs.into()));
+    drop(stream);
+
+    let samples = std::mem::take(&mut *samples.lock());
+    if samples.is_empty() {
+        return Err(anyhow!("no audio was captured"));
+    }
+    Ok(Clip {
+        samples,
+        channels,
+        sample_rate,
+    })
+}
+
+/// Device to record from, with the format to record in
+fn loopback_device() -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+    let host = cpal::default_host();
+
+    // WASAPI records an output device when asked for input from it
+    if cfg!(target_os = "windows")
+        && let Some(device) = host.default_output_device()
+        && let Ok(config) = device.default_output_config()
+    {
+        return Ok((device, config));
+    }
+
+    let loopback = host.input_devices().ok().and_then(|mut devices| {
+        devices.find(|device| {
+            device.name().is_ok_and(|name| {
+                let name = name.to_lowercase();
+                LOOPBACK_NAMES.iter().any(|hint| name.contains(hint))
+            })
+        })
+    });
+    let device = loopback.ok_or_else(|| anyhow!("no loopback device available"))?;
+    let config = device.default_input_config()?;
+    Ok((device, config))
+}
+
+/// Open an input stream appending to `samples`
+fn open<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: Arc<Mutex<Vec<f32>>>,
+) -> Result<cpal::Stream>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _| {
+            samples
+                .lock()
+                .extend(data.iter().map(|sample| sample.to_sample::<f32>()));
+        },
+        |e| warn!("Listen capture error: {}", e),
+        None,
+    )?;
+    Ok(stream)
+}
+
+#[cfg(target_os = "linux")]
+mod pulse {
+    use std::io::Read;
+    use std::process::{Command, Stdio};
+
+    use anyhow::{Context, Result, anyhow};
+
+    use super::Clip;
+
+    const CHANNELS: u16 = 2;
+    const SAMPLE_RATE: u32 = 48000;
+
+    /// Record `secs` seconds from the monitor of the default output
+    pub fn record(secs: u32) -> Result<Clip> {
+        let mut parec = Command::new("parec")
+            .args(["--device=@DEFAULT_MONITOR@", "--format=float32le", "--raw"])
+            .arg(format!("--channels={}", CHANNELS))
+            .arg(format!("--rate={}", SAMPLE_RATE))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to run parec")?;
+
+        // parec streams in real time, so reading this much takes `secs`
+        let len = secs as u64 * SAMPLE_RATE as u64 * CHANNELS as u64 * 4;
+        let mut bytes = Vec::with_capacity(len as usize);
+        let read = parec
+            .stdout
+            .take()
+            .context("parec has no output")?
+            .take(len)
+            .read_to_end(&mut bytes);
+        let _ = parec.kill();
+        let _ = parec.wait();
+        read.context("Failed to read from parec")?;
+
+        let samples: Vec<f32> = bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        if samples.is_empty() {
+            return Err(anyhow!("parec recorded nothing"));
+        }
+        Ok(Clip {
+            samples,
+            channels: CHANNELS,
+            sample_rate: SAMPLE_RATE,
+        })
+    }
+}
@@ -0,0 +1,228 @@
+//! In-memory log capture
+//!
+//! Installs the global tracing subscriber: the usual stderr formatter plus a
+//! layer that keeps the most recent events in a ring buffer, so the log
+//! viewer and diagnostic bundles can show what happened without a log file.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Local};
+use parking_lot::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Registry;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::reload;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Maximum number of records kept in memory
+const CAPACITY: usize = 5000;
+
+static BUFFER: Mutex<VecDeque<LogRecord>> = Mutex::new(VecDeque::new());
+
+/// Filter chosen at startup and the handle to swap the installed one
+static FILTER: OnceLock<(Targets, reload::Handle<Targets, Registry>)> = OnceLock::new();
+
+/// A captured log event
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:>5} {}: {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+/// Minimum severity shown in the log viewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogLevelFilter {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+}
+
+impl LogLevelFilter {
+    pub const ALL: [LogLevelFilter; 4] = [Self::Error, Self::Warn, Self::Info, Self::Debug];
+
+    /// Whether a record at `level` passes this filter
+    pub fn allows(self, level: Level) -> bool {
+        let max = match self {
+            Self::Error => Level::ERROR,
+            Self::Warn => Level::WARN,
+            Self::Info => Level::INFO,
+            Self::Debug => Level::TRACE,
+        };
+        level <= max
+    }
+}
+
+impl std::fmt::Display for LogLevelFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "ERROR"),
+            Self::Warn => write!(f, "WARN"),
+            Self::Info => write!(f, "INFO"),
+            Self::Debug => write!(f, "DEBUG"),
+        }
+    }
+}
+
+/// Install the global subscriber
+///
+/// Honors `RUST_LOG` (as `target=level` directives), defaulting to INFO.
+pub fn init() {
+    let targets = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|var| Targets::from_str(&var).ok())
+        .unwrap_or_else(|| Targets::new().with_default(LevelFilter::INFO));
+    let (filter, handle) = reload::Layer::new(targets.clone());
+
+    let result = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(RingBufferLayer)
+        .try_init();
+
+    match result {
+        Ok(()) => {
+            let _ = FILTER.set((targets, handle));
+        }
+        Err(e) => eprintln!("Failed to install log subscriber: {}", e),
+    }
+}
+
+/// Record DEBUG events as well, or go back to the startup level
+///
+/// The subscriber drops events below its level before they reach the
+/// buffer, so the viewer's Debug filter needs this to have anything to show.
+pub fn set_debug(enabled: bool) {
+    let Some((startup, handle)) = FILTER.get() else {
+        return;
+    };
+    let raise = enabled
+        && startup
+            .default_level()
+            .is_none_or(|level| level < LevelFilter::DEBUG);
+    let targets = if raise {
+        startup.clone().with_default(LevelFilter::DEBUG)
+    } else {
+        startup.clone()
+    };
+    if let Err(e) = handle.reload(targets) {
+        tracing::warn!("Failed to change log level: {}", e);
+    }
+}
+
+/// Copy of all buffered records, oldest first
+pub fn snapshot() -> Vec<LogRecord> {
+    BUFFER.lock().iter().cloned().collect()
+}
+
+/// Up to `count` most recent records, oldest first
+///
+/// Returns nothing instead of blocking if the buffer is locked, which makes
+/// it safe to call from a panic hook.
+pub fn try_tail(count: usize) -> Vec<LogRecord> {
+    let Some(buffer) = BUFFER.try_lock() else {
+        return Vec::new();
+    };
+    let skip = buffer.len().saturating_sub(count);
+    buffer.iter().skip(skip).cloned().collect()
+}
+
+fn push(record: LogRecord) {
+    let mut buffer = BUFFER.lock();
+    if buffer.len() >= CAPACITY {
+        buffer.pop_front();
+    }
+    buffer.push_back(record);
+}
+
+struct RingBufferLayer;
+
+impl<S: Subscriber> Layer<S> for RingBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        push(LogRecord {
+            timestamp: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Collects the `message` field followed by any other fields as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields
+        } else {
+            format!("{} {}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_filter() {
+        assert!(LogLevelFilter::Error.allows(Level::ERROR));
+        assert!(!LogLevelFilter::Error.allows(Level::WARN));
+        assert!(LogLevelFilter::Info.allows(Level::WARN));
+        assert!(!LogLevelFilter::Info.allows(Level::DEBUG));
+        assert!(LogLevelFilter::Debug.allows(Level::TRACE));
+    }
+}
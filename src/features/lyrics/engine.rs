@@ -22,7 +22,10 @@
 
 // Core modules
 pub mod conversion;
+pub mod fonts;
+pub mod gpu_caps;
 pub mod gpu_pipeline;
+pub mod growable_buffer;
 pub mod interlude_dots;
 pub mod layout;
 pub mod lens;
@@ -30,6 +33,7 @@ pub mod line_animation;
 pub mod per_line_blur;
 pub mod physics;
 pub mod pipeline;
+pub mod render_scale;
 pub mod program;
 pub mod sdf_cache;
 pub mod sdf_generator;
@@ -171,6 +175,12 @@ pub struct LyricsEngineConfig {
     /// Maximum word length for emphasis (characters)
     /// Default: 7 for non-CJK, unlimited for CJK
     pub emphasis_max_length: usize,
+    /// Multiplier on the glow/scale amount of emphasized words
+    /// Default: 1.0
+    pub emphasis_amount_scale: f32,
+    /// Multiplier on the blur of emphasized words
+    /// Default: 1.0
+    pub emphasis_blur_scale: f32,
 }
 
 /// Alignment anchor for current lyric line
@@ -243,6 +253,20 @@ impl Default for LyricsEngineConfig {
             enable_emphasis: true,
             emphasis_min_duration: 1000,
             emphasis_max_length: 7,
+            emphasis_amount_scale: 1.0,
+            emphasis_blur_scale: 1.0,
+        }
+    }
+}
+
+impl LyricsEngineConfig {
+    /// Spring parameters for line Y positions
+    pub fn pos_y_spring_params(&self) -> spring::SpringParams {
+        spring::SpringParams {
+            mass: self.spring_mass as spring::Num,
+            damping: self.spring_damping as spring::Num,
+            stiffness: self.spring_stiffness as spring::Num,
+            soft: false,
         }
     }
 }
@@ -306,6 +330,8 @@ pub struct LyricsEngine {
     last_font_size: f32,
     /// Viewport height for layout calculations
     viewport_height: f32,
+    /// Config changed in a way that needs the line targets recomputed
+    layout_dirty: bool,
 }
 
 impl LyricsEngine {
@@ -328,6 +354,7 @@ impl LyricsEngine {
         // Create line animation manager with config
         let mut line_animations = LineAnimationManager::new();
         line_animations.set_align_position(config.align_position);
+        line_animations.set_pos_y_spring_params(config.pos_y_spring_params());
         // Convert AlignAnchor from config to line_animation's AlignAnchor
         line_animations.set_align_anchor(match config.align_anchor {
             AlignAnchor::Top => line_animation::AlignAnchor::Top,
@@ -356,6 +383,7 @@ impl LyricsEngine {
             last_content_width: 0.0,
             last_font_size: 0.0,
             viewport_height: 800.0,
+            layout_dirty: false,
         }
     }
 
@@ -410,12 +438,23 @@ impl LyricsEngine {
     }
 
     /// Update configuration at runtime
-    #[allow(dead_code)]
+    ///
+    /// Lines already on screen pick up the new springs immediately and are
+    /// re-laid out on the next `set_current_time` when the alignment moved.
     pub fn set_config(&mut self, config: LyricsEngineConfig) {
         self.physics.set_friction(config.scroll_friction);
         self.physics.set_snap_threshold(config.snap_threshold);
         self.physics.set_max_overscroll(config.max_overscroll);
         self.lens.set_edge_scale_factor(config.inactive_scale);
+        if config.pos_y_spring_params() != self.config.pos_y_spring_params() {
+            self.line_animations
+                .set_pos_y_spring_params(config.pos_y_spring_params());
+        }
+        if config.align_position != self.config.align_position {
+            self.line_animations
+                .set_align_position(config.align_position);
+            self.layout_dirty = true;
+        }
         self.config = config;
     }
 
@@ -424,6 +463,11 @@ impl LyricsEngine {
         self.physics.apply_impulse(delta);
     }
 
+    /// Follow the current line again without waiting for the scroll timeout
+    pub fn resume_auto_scroll(&mut self) {
+        self.physics.start_auto_play();
+    }
+
     /// Handle mouse move
     #[allow(dead_code)]
     pub fn handle_mouse_move(&mut self, _position: iced::Point) {
@@ -508,6 +552,7 @@ impl LyricsEngine {
         // 检查歌词是否变化（切歌或首次加载）
         // 如果歌词数量变化，需要重新初始化动画并立即排版
         let lyrics_changed = self.line_animations.len() != lines.len();
+        let layout_dirty = std::mem::take(&mut self.layout_dirty);
 
         // Update hot lines (currently playing)
         let scroll_changed = self.update_hot_lines(time_ms, lines, is_seek);
@@ -520,7 +565,7 @@ impl LyricsEngine {
         // 1. buffered_lines 发生变化（scroll_changed）
         // 2. 显式 seek 操作（is_seek）
         // 3. 歌词变化（lyrics_changed）- 切歌或首次加载时立即排版
-        if scroll_changed || is_seek || lyrics_changed {
+        if scroll_changed || is_seek || lyrics_changed || layout_dirty {
             self.calc_scroll_target(lines, is_seek || lyrics_changed);
         }
     }
@@ -934,6 +979,14 @@ impl LyricsEngine {
         self.is_playing
     }
 
+    /// Whether the next frame can differ from the last one
+    ///
+    /// Word highlights and interlude dots only move while playing; otherwise
+    /// only scroll physics and line springs/fades can change the picture.
+    pub fn is_animating(&self) -> bool {
+        self.is_playing || !self.physics.is_settled() || self.line_animations.is_animating()
+    }
+
     /// Get interlude dots state for rendering
     pub fn interlude_dots(&self) -> &InterludeDots {
         &self.interlude_dots
@@ -0,0 +1,26 @@
+//! Fonts for lyrics text
+//!
+//! System fonts come first. The faces in `assets/fonts` add Inter and CJK
+//! coverage, and any emoji or symbol font packaged next to them joins
+//! cosmic-text's fallback chain for characters the main fonts lack. Color
+//! emoji fonts without outlines are drawn in one color by the SDF generator.
+
+use cosmic_text::FontSystem;
+
+/// Folder of the bundled fonts
+const FONTS_DIR: &str = "assets/fonts";
+
+/// Load every font in the bundled fonts folder, returning how many faces
+/// were added
+pub fn load_bundled_fonts(font_system: &mut FontSystem) -> usize {
+    let db = font_system.db_mut();
+    let before = db.len();
+    db.load_fonts_dir(FONTS_DIR);
+    let added = db.len() - before;
+    if added == 0 {
+        tracing::warn!("No bundled fonts found in {}", FONTS_DIR);
+    } else {
+        tracing::debug!("Loaded {} bundled font faces", added);
+    }
+    added
+}
@@ -0,0 +1,150 @@
+//! GPU capability probing for the lyrics pipeline
+//!
+//! The pipeline is built for the device it runs on. The glyph atlas shrinks
+//! to the largest texture the device accepts, and devices that can't bind a
+//! storage buffer to the vertex stage (GLES drivers, older integrated GPUs)
+//! read the per-line data from a fixed-size uniform array instead. The tier
+//! picked is remembered for the diagnostics bundle.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use futures_util::FutureExt;
+use parking_lot::Mutex;
+
+/// Atlas edge length on devices that allow it
+pub const FULL_ATLAS_SIZE: u32 = 4096;
+
+/// Tier of the pipeline created last
+static CURRENT: Mutex<Option<GpuTier>> = Mutex::new(None);
+
+/// How the shader reads per-line data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineDataPath {
+    /// Runtime-sized storage buffer
+    Storage,
+    /// Uniform array of a fixed number of lines
+    Uniform,
+}
+
+/// What the lyrics pipeline was built with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuTier {
+    pub atlas_size: u32,
+    pub line_data: LineDataPath,
+}
+
+impl GpuTier {
+    /// Pick the tier the device limits allow; `line_data_bytes` is the size
+    /// of the per-line buffer
+    pub fn probe(limits: &wgpu::Limits, line_data_bytes: u64) -> Self {
+        let storage = limits.max_storage_buffers_per_shader_stage > 0
+            && u64::from(limits.max_storage_buffer_binding_size) >= line_data_bytes;
+        Self {
+            atlas_size: FULL_ATLAS_SIZE.min(limits.max_texture_dimension_2d),
+            line_data: if storage {
+                LineDataPath::Storage
+            } else {
+                LineDataPath::Uniform
+            },
+        }
+    }
+
+    /// Whether nothing had to be scaled back
+    pub fn is_full(&self) -> bool {
+        self.atlas_size == FULL_ATLAS_SIZE && self.line_data == LineDataPath::Storage
+    }
+}
+
+impl fmt::Display for GpuTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line_data = match self.line_data {
+            LineDataPath::Storage => "storage buffer",
+            LineDataPath::Uniform => "uniform buffer",
+        };
+        write!(
+            f,
+            "{} ({}px atlas, {} line data)",
+            if self.is_full() { "full" } else { "reduced" },
+            self.atlas_size,
+            line_data
+        )
+    }
+}
+
+/// Remember the tier of a newly created pipeline
+pub fn record(tier: GpuTier) {
+    *CURRENT.lock() = Some(tier);
+}
+
+/// Tier of the running pipeline, once the lyrics page has been drawn
+pub fn current() -> Option<GpuTier> {
+    *CURRENT.lock()
+}
+
+/// Run `create` and report whether the device rejected it
+///
+/// Some drivers accept the limits but fail validation of a layout using
+/// them, which would otherwise only surface as an uncaptured error.
+pub fn validates<T>(device: &wgpu::Device, create: impl FnOnce() -> T) -> (T, bool) {
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let value = create();
+    // Native backends report errors synchronously
+    let error = device.pop_error_scope().now_or_never().flatten();
+    if let Some(error) = &error {
+        tracing::warn!("Lyrics GPU layout rejected: {}", error);
+    }
+    (value, error.is_none())
+}
+
+/// Shader source for the line data path, swapping the storage buffer
+/// binding for a uniform array of `max_lines`
+pub fn shader_source(source: &str, line_data: LineDataPath, max_lines: usize) -> Cow<'_, str> {
+    const STORAGE_BINDING: &str = "var<storage, read> lines: array<LineUniforms>;";
+    match line_data {
+        LineDataPath::Storage => Cow::Borrowed(source),
+        LineDataPath::Uniform => Cow::Owned(source.replace(
+            STORAGE_BINDING,
+            &format!("var<uniform> lines: array<LineUniforms, {}>;", max_lines),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_limits_get_full_tier() {
+        let tier = GpuTier::probe(&wgpu::Limits::default(), 4096);
+        assert!(tier.is_full());
+    }
+
+    #[test]
+    fn test_webgl2_limits_scale_back() {
+        let tier = GpuTier::probe(&wgpu::Limits::downlevel_webgl2_defaults(), 4096);
+        assert_eq!(tier.atlas_size, 2048);
+        assert_eq!(tier.line_data, LineDataPath::Uniform);
+        assert_eq!(
+            tier.to_string(),
+            "reduced (2048px atlas, uniform buffer line data)"
+        );
+    }
+
+    #[test]
+    fn test_uniform_shader_source() {
+        let source = "@group(0) @binding(1) var<storage, read> lines: array<LineUniforms>;";
+        assert_eq!(
+            shader_source(source, LineDataPath::Uniform, 128),
+            "@group(0) @binding(1) var<uniform> lines: array<LineUniforms, 128>;"
+        );
+        assert_eq!(shader_source(source, LineDataPath::Storage, 128), source);
+    }
+
+    #[test]
+    fn test_shader_has_storage_binding() {
+        // The uniform path relies on this exact declaration
+        let source = include_str!("shaders/lyrics_sdf.wgsl");
+        assert!(source.contains("var<storage, read> lines: array<LineUniforms>;"));
+    }
+}
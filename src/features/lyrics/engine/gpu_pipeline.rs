@@ -17,7 +17,7 @@
 //!     ↓
 //! SDF generator (8SSEDT algorithm)
 //!     ↓
-//! SDF Atlas (4096x4096 RGBA texture)
+//! SDF Atlas (4096x4096 RGBA texture, smaller where the GPU requires)
 //!     ↓
 //! lyrics_sdf.wgsl (SDF math + fwidth AA)
 //!     ↓
@@ -28,15 +28,19 @@ use bytemuck::{Pod, Zeroable};
 use cosmic_text::FontSystem;
 use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
+use std::time::Instant;
 use wgpu::{Device, Queue, TextureFormat};
 
-use super::CachedShapedLine;
+use super::gpu_caps::{self, GpuTier, LineDataPath};
+use super::growable_buffer::GrowableBuffer;
 use super::interlude_dots::InterludeDots;
 use super::per_line_blur::{LineRenderInfo, PerLineBlurRenderer};
+use super::render_scale::RenderScale;
 use super::sdf_cache::SdfCache;
 use super::text_shaper::{ShapedLine, TextShaper};
 use super::types::{ComputedLineStyle, FontConfig, LyricLineData};
 use super::vertex::{GlobalUniform, LineUniform, LyricGlyphVertex};
+use super::{CachedShapedLine, LyricsEngineConfig};
 
 /// Uniform data for interlude dots rendering
 #[repr(C)]
@@ -126,10 +130,14 @@ pub struct CompositeUniform {
 /// 关键：CacheKey 包含 font_id，必须匹配
 pub type SharedFontSystem = Arc<Mutex<FontSystem>>;
 
-/// Maximum number of glyphs per frame
-const MAX_GLYPHS: usize = 8192;
-/// Maximum number of lines
-const MAX_LINES: usize = 128;
+/// Glyphs the vertex and index buffers start with room for; they grow
+/// for denser lyrics
+const INITIAL_GLYPHS: usize = 8192;
+/// Lines the line buffer starts with room for
+const INITIAL_LINES: usize = 128;
+/// Length of the line array on the uniform path, which can't grow.
+/// 16 KiB is the smallest uniform binding every device allows.
+const UNIFORM_LINES: usize = 512;
 
 /// GPU resources for lyrics rendering
 pub struct LyricsGpuPipeline {
@@ -140,20 +148,28 @@ pub struct LyricsGpuPipeline {
     mrt_pipeline: wgpu::RenderPipeline,
 
     // Buffers for lyrics
-    vertex_buffer: wgpu::Buffer,
-    index_buffer: wgpu::Buffer,
+    vertex_buffer: GrowableBuffer,
+    index_buffer: GrowableBuffer,
     global_uniform_buffer: wgpu::Buffer,
-    line_uniform_buffer: wgpu::Buffer,
+    line_uniform_buffer: GrowableBuffer,
+    /// Lines the shader can read, `None` when the storage buffer grows
+    /// with them
+    max_lines: Option<usize>,
+    /// Line count of the last lyrics found past `max_lines`, so the
+    /// warning is logged once rather than every frame
+    overflow_warned: usize,
 
     // Bind groups for lyrics
     bind_group_layout: wgpu::BindGroupLayout,
     bind_group: Option<wgpu::BindGroup>,
+    /// Atlas generation `bind_group` was built against
+    bind_group_atlas: Option<u64>,
+    glyph_sampler: wgpu::Sampler,
 
     // Interlude dots rendering
     dots_pipeline: wgpu::RenderPipeline,
     dots_uniform_buffer: wgpu::Buffer,
-    dots_bind_group_layout: wgpu::BindGroupLayout,
-    dots_bind_group: Option<wgpu::BindGroup>,
+    dots_bind_group: wgpu::BindGroup,
     dots_enabled: bool,
 
     // Glyph management (SDF)
@@ -169,6 +185,8 @@ pub struct LyricsGpuPipeline {
 
     // === 逐行模糊渲染器 (正确的 Apple Music 风格模糊) ===
     per_line_blur: RwLock<PerLineBlurRenderer>,
+    /// Resolution of the per-line passes, from recent frame times
+    render_scale: RenderScale,
 
     // Composite pipeline
     composite_pipeline: wgpu::RenderPipeline,
@@ -242,60 +260,41 @@ impl LyricsGpuPipeline {
 
         let font_system: SharedFontSystem = Arc::new(Mutex::new(font_system));
 
+        // Line data goes in a storage buffer where the device allows it
+        let line_data_bytes = (std::mem::size_of::<LineUniform>() * INITIAL_LINES) as u64;
+        let mut tier = GpuTier::probe(&device.limits(), line_data_bytes);
+        let bind_group_layout = match tier.line_data {
+            LineDataPath::Storage => {
+                let (layout, valid) = gpu_caps::validates(device, || {
+                    Self::create_bind_group_layout(device, LineDataPath::Storage)
+                });
+                if valid {
+                    layout
+                } else {
+                    tier.line_data = LineDataPath::Uniform;
+                    Self::create_bind_group_layout(device, LineDataPath::Uniform)
+                }
+            }
+            LineDataPath::Uniform => Self::create_bind_group_layout(device, LineDataPath::Uniform),
+        };
+        if tier.is_full() {
+            tracing::debug!("Lyrics GPU tier: {}", tier);
+        } else {
+            tracing::info!("Lyrics GPU tier: {}", tier);
+        }
+        gpu_caps::record(tier);
+
         // Both SdfCache and TextShaper must use the SAME FontSystem instance
         // Pass debug_logging to SdfCache
-        let sdf_cache =
-            SdfCache::with_debug(device, Arc::clone(&font_system), font_config.debug_logging);
+        let sdf_cache = SdfCache::with_debug(
+            device,
+            Arc::clone(&font_system),
+            font_config.debug_logging,
+            tier.atlas_size,
+        );
         // Pass font config to TextShaper
         let text_shaper = TextShaper::with_config(Arc::clone(&font_system), font_config.clone());
 
-        // Create bind group layout for lyrics rendering
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Lyrics Bind Group Layout"),
-            entries: &[
-                // Global uniforms
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                // Line uniforms (storage buffer)
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: true },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                // Glyph atlas texture
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        multisampled: false,
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                    },
-                    count: None,
-                },
-                // Sampler
-                wgpu::BindGroupLayoutEntry {
-                    binding: 3,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                    count: None,
-                },
-            ],
-        });
-
         // Create pipeline layout
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Lyrics Pipeline Layout"),
@@ -306,7 +305,11 @@ impl LyricsGpuPipeline {
         // Load SDF shader
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Lyrics SDF Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/lyrics_sdf.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl(gpu_caps::shader_source(
+                include_str!("shaders/lyrics_sdf.wgsl"),
+                tier.line_data,
+                UNIFORM_LINES,
+            )),
         });
 
         // Create direct render pipeline (single output)
@@ -384,19 +387,19 @@ impl LyricsGpuPipeline {
         });
 
         // Create buffers
-        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Lyrics Vertex Buffer"),
-            size: (std::mem::size_of::<LyricGlyphVertex>() * MAX_GLYPHS * 4) as u64,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let vertex_buffer = GrowableBuffer::new(
+            device,
+            "Lyrics Vertex Buffer",
+            wgpu::BufferUsages::VERTEX,
+            (std::mem::size_of::<LyricGlyphVertex>() * INITIAL_GLYPHS * 4) as u64,
+        );
 
-        let index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Lyrics Index Buffer"),
-            size: (std::mem::size_of::<u32>() * MAX_GLYPHS * 6) as u64,
-            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let index_buffer = GrowableBuffer::new(
+            device,
+            "Lyrics Index Buffer",
+            wgpu::BufferUsages::INDEX,
+            (std::mem::size_of::<u32>() * INITIAL_GLYPHS * 6) as u64,
+        );
 
         let global_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Lyrics Global Uniform Buffer"),
@@ -405,17 +408,43 @@ impl LyricsGpuPipeline {
             mapped_at_creation: false,
         });
 
-        let line_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Lyrics Line Uniform Buffer"),
-            size: (std::mem::size_of::<LineUniform>() * MAX_LINES) as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
+        // A uniform binding must cover the whole array the shader declares
+        let (line_usage, max_lines) = match tier.line_data {
+            LineDataPath::Storage => (wgpu::BufferUsages::STORAGE, None),
+            LineDataPath::Uniform => (wgpu::BufferUsages::UNIFORM, Some(UNIFORM_LINES)),
+        };
+        let line_uniform_buffer = GrowableBuffer::new(
+            device,
+            "Lyrics Line Uniform Buffer",
+            line_usage,
+            (std::mem::size_of::<LineUniform>() * max_lines.unwrap_or(INITIAL_LINES)) as u64,
+        );
+
+        let glyph_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("SDF Glyph Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
         });
 
         // === Interlude Dots Pipeline ===
         let (dots_pipeline, dots_bind_group_layout, dots_uniform_buffer) =
             Self::create_dots_pipeline(device, format);
 
+        // The dots uniform buffer never changes, so neither does its bind group
+        let dots_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Interlude Dots Bind Group"),
+            layout: &dots_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: dots_uniform_buffer.as_entire_binding(),
+            }],
+        });
+
         // Create composite pipeline
         let (
             composite_pipeline,
@@ -431,12 +460,15 @@ impl LyricsGpuPipeline {
             index_buffer,
             global_uniform_buffer,
             line_uniform_buffer,
+            max_lines,
+            overflow_warned: 0,
             bind_group_layout,
             bind_group: None,
+            bind_group_atlas: None,
+            glyph_sampler,
             dots_pipeline,
             dots_uniform_buffer,
-            dots_bind_group_layout,
-            dots_bind_group: None,
+            dots_bind_group,
             dots_enabled: false,
             sdf_cache,
             text_shaper,
@@ -444,6 +476,7 @@ impl LyricsGpuPipeline {
             lyrics_texture: RwLock::new(None),
             blur_info_texture: RwLock::new(None),
             per_line_blur: RwLock::new(PerLineBlurRenderer::new(device, format)),
+            render_scale: RenderScale::default(),
             composite_pipeline,
             composite_bind_group_layout,
             composite_uniform_buffer,
@@ -461,28 +494,63 @@ impl LyricsGpuPipeline {
         }
     }
 
-    /// Load custom fonts from assets/fonts directory
+    /// Bind group layout of the lyrics pass, with line data in a storage or
+    /// uniform buffer
+    fn create_bind_group_layout(device: &Device, line_data: LineDataPath) -> wgpu::BindGroupLayout {
+        let line_data_ty = match line_data {
+            LineDataPath::Storage => wgpu::BufferBindingType::Storage { read_only: true },
+            LineDataPath::Uniform => wgpu::BufferBindingType::Uniform,
+        };
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Lyrics Bind Group Layout"),
+            entries: &[
+                // Global uniforms
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Line uniforms
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: line_data_ty,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Glyph atlas texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    },
+                    count: None,
+                },
+                // Sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        })
+    }
+
+    /// Load the bundled fonts from the assets/fonts directory
     fn load_custom_fonts(font_system: &mut FontSystem, debug_logging: bool) {
-        let font_paths = [
-            "assets/fonts/NotoSansCJKsc-Regular.otf",
-            "assets/fonts/Inter-Regular.ttf",
-        ];
-
-        for path in &font_paths {
-            match std::fs::read(path) {
-                Ok(data) => {
-                    font_system.db_mut().load_font_data(data);
-                    if debug_logging {
-                        tracing::debug!("[LyricsGpuPipeline] Loaded custom font: {}", path);
-                    }
-                }
-                Err(e) => {
-                    if debug_logging {
-                        tracing::warn!("[LyricsGpuPipeline] Failed to load font {}: {}", path, e);
-                    }
-                }
-            }
-        }
+        super::fonts::load_bundled_fonts(font_system);
 
         if debug_logging {
             // Log available font families
@@ -821,6 +889,7 @@ impl LyricsGpuPipeline {
             scroll_y,
             align_position: 0.35,
             sdf_range: 4.0, // Default SDF range for distance extrapolation
+            ..GlobalUniform::default()
         };
         queue.write_buffer(&self.global_uniform_buffer, 0, bytemuck::bytes_of(&globals));
 
@@ -831,7 +900,7 @@ impl LyricsGpuPipeline {
         let line_uniforms: Vec<LineUniform> = line_styles
             .iter()
             .enumerate()
-            .take(MAX_LINES)
+            .take(self.drawable_lines(line_styles.len()))
             .map(|(idx, style)| {
                 let actual_height = line_heights.get(idx).copied().unwrap_or(font_size * 1.4);
                 LineUniform {
@@ -847,13 +916,7 @@ impl LyricsGpuPipeline {
             })
             .collect();
 
-        if !line_uniforms.is_empty() {
-            queue.write_buffer(
-                &self.line_uniform_buffer,
-                0,
-                bytemuck::cast_slice(&line_uniforms),
-            );
-        }
+        self.write_line_uniforms(device, queue, &line_uniforms);
 
         // Build geometry (使用逐行组织，支持逐行模糊)
         // Pass scale factor so shape_line can use logical pixels for consistent wrapping
@@ -871,12 +934,10 @@ impl LyricsGpuPipeline {
         self.vertex_count = vertices.len() as u32;
         self.index_count = indices.len() as u32;
 
-        if !vertices.is_empty() {
-            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
-        }
-        if !indices.is_empty() {
-            queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&indices));
-        }
+        self.vertex_buffer
+            .write(device, queue, bytemuck::cast_slice(&vertices));
+        self.index_buffer
+            .write(device, queue, bytemuck::cast_slice(&indices));
 
         // Update bind group
         self.update_bind_group(device);
@@ -912,7 +973,7 @@ impl LyricsGpuPipeline {
         current_time_ms: f32,
         scroll_y: f32,
         font_size: f32, // Physical pixels
-        word_fade_width: f32,
+        config: &LyricsEngineConfig,
         scale: f32, // Scale factor for logical to physical conversion
     ) {
         // Update global uniforms
@@ -921,11 +982,14 @@ impl LyricsGpuPipeline {
             bounds_offset: [bounds_x, bounds_y],
             bounds_size: [bounds_width, bounds_height],
             current_time_ms,
-            word_fade_width,
+            word_fade_width: config.word_fade_width,
             font_size,
             scroll_y,
-            align_position: 0.35,
+            align_position: config.align_position,
             sdf_range: 4.0, // Default SDF range for distance extrapolation
+            emphasis_amount_scale: config.emphasis_amount_scale,
+            emphasis_blur_scale: config.emphasis_blur_scale,
+            _padding: [0.0; 2],
         };
         queue.write_buffer(&self.global_uniform_buffer, 0, bytemuck::bytes_of(&globals));
 
@@ -933,7 +997,7 @@ impl LyricsGpuPipeline {
         let line_uniforms: Vec<LineUniform> = line_styles
             .iter()
             .enumerate()
-            .take(MAX_LINES)
+            .take(self.drawable_lines(line_styles.len()))
             .map(|(idx, style)| {
                 let actual_height = line_heights.get(idx).copied().unwrap_or(font_size * 1.4);
                 LineUniform {
@@ -949,13 +1013,7 @@ impl LyricsGpuPipeline {
             })
             .collect();
 
-        if !line_uniforms.is_empty() {
-            queue.write_buffer(
-                &self.line_uniform_buffer,
-                0,
-                bytemuck::cast_slice(&line_uniforms),
-            );
-        }
+        self.write_line_uniforms(device, queue, &line_uniforms);
 
         // Build geometry from pre-shaped lines (Single Source of Truth)
         // No more duplicate shape_line calls!
@@ -974,12 +1032,10 @@ impl LyricsGpuPipeline {
         self.vertex_count = vertices.len() as u32;
         self.index_count = indices.len() as u32;
 
-        if !vertices.is_empty() {
-            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
-        }
-        if !indices.is_empty() {
-            queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&indices));
-        }
+        self.vertex_buffer
+            .write(device, queue, bytemuck::cast_slice(&vertices));
+        self.index_buffer
+            .write(device, queue, bytemuck::cast_slice(&indices));
 
         // Update bind group
         self.update_bind_group(device);
@@ -1012,8 +1068,8 @@ impl LyricsGpuPipeline {
         current_time_ms: f32,
         scale: f32, // Scale factor (physical / logical)
     ) -> (Vec<LyricGlyphVertex>, Vec<u32>) {
-        let mut all_vertices = Vec::with_capacity(MAX_GLYPHS * 4);
-        let mut all_indices = Vec::with_capacity(MAX_GLYPHS * 6);
+        let mut all_vertices = Vec::with_capacity(INITIAL_GLYPHS * 4);
+        let mut all_indices = Vec::with_capacity(INITIAL_GLYPHS * 6);
         let mut line_index_ranges = Vec::with_capacity(lines.len());
         let mut line_render_info = Vec::with_capacity(lines.len());
 
@@ -1025,7 +1081,9 @@ impl LyricsGpuPipeline {
         let logical_viewport_width = viewport_width / scale;
         let logical_font_size = font_size / scale;
 
-        for (line_idx, line) in lines.iter().enumerate() {
+        // Lines past the shader's array have no style to read
+        let drawable = self.max_lines.unwrap_or(usize::MAX);
+        for (line_idx, line) in lines.iter().enumerate().take(drawable) {
             let style = line_styles.get(line_idx).cloned().unwrap_or_default();
 
             let line_height = font_size * 1.4;
@@ -1077,7 +1135,10 @@ impl LyricsGpuPipeline {
                 };
 
                 // Line X position in physical pixels
-                let line_x = if line.is_duet {
+                // 从右到左的歌词镜像对齐：普通行靠右，对唱行靠左
+                let rtl = shaped.direction.is_rtl();
+                let right_aligned = line.is_duet != rtl;
+                let line_x = if right_aligned {
                     viewport_width - shaped.width * scale - padding_right
                 } else {
                     padding_left
@@ -1152,9 +1213,14 @@ impl LyricsGpuPipeline {
                     let char_delay_ms = word_delay + char_delay_offset;
 
                     // Calculate glyph position within word for pixel-level gradient
-                    let glyph_left_x = glyph.x;
+                    // 从右到左时从单词右边缘量起
                     let glyph_start_in_word = if word_pixel_width > 0.0 {
-                        ((glyph_left_x - word_start_x) / word_pixel_width).clamp(0.0, 1.0)
+                        let from_start = if rtl {
+                            word_start_x + word_pixel_width - (glyph.x + glyph.advance)
+                        } else {
+                            glyph.x - word_start_x
+                        };
+                        (from_start / word_pixel_width).clamp(0.0, 1.0)
                     } else {
                         0.0
                     };
@@ -1196,6 +1262,7 @@ impl LyricsGpuPipeline {
                     base.set_emphasize(emphasize);
                     base.set_bg(line.is_bg);
                     base.set_duet(line.is_duet);
+                    base.set_rtl(rtl);
 
                     for (cx, cy) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
                         let mut v = base;
@@ -1229,7 +1296,7 @@ impl LyricsGpuPipeline {
                         // Y offset in physical pixels (shaped.height is logical, multiply by scale)
                         let trans_y_offset = shaped.height * scale;
                         // X position in physical pixels
-                        let trans_x = if line.is_duet {
+                        let trans_x = if right_aligned {
                             viewport_width - trans_shaped.width * scale - padding_right
                         } else {
                             padding_left
@@ -1287,7 +1354,7 @@ impl LyricsGpuPipeline {
                         // Y offset in physical pixels
                         let roman_y_offset = shaped.height * scale + trans_height;
                         // X position in physical pixels
-                        let roman_x = if line.is_duet {
+                        let roman_x = if right_aligned {
                             viewport_width - roman_shaped.width * scale - padding_right
                         } else {
                             padding_left
@@ -1358,8 +1425,8 @@ impl LyricsGpuPipeline {
         current_time_ms: f32,
         scale: f32, // Scale factor (physical / logical)
     ) -> (Vec<LyricGlyphVertex>, Vec<u32>) {
-        let mut all_vertices = Vec::with_capacity(MAX_GLYPHS * 4);
-        let mut all_indices = Vec::with_capacity(MAX_GLYPHS * 6);
+        let mut all_vertices = Vec::with_capacity(INITIAL_GLYPHS * 4);
+        let mut all_indices = Vec::with_capacity(INITIAL_GLYPHS * 6);
         let mut line_index_ranges = Vec::with_capacity(lines.len());
         let mut line_render_info = Vec::with_capacity(lines.len());
 
@@ -1375,7 +1442,9 @@ impl LyricsGpuPipeline {
         let trans_ratio = 0.55_f32;
         let roman_ratio = 0.45_f32;
 
-        for (line_idx, line) in lines.iter().enumerate() {
+        // Lines past the shader's array have no style to read
+        let drawable = self.max_lines.unwrap_or(usize::MAX);
+        for (line_idx, line) in lines.iter().enumerate().take(drawable) {
             let style = line_styles.get(line_idx).cloned().unwrap_or_default();
 
             // Get pre-shaped data for this line
@@ -1411,7 +1480,10 @@ impl LyricsGpuPipeline {
 
                     // Line X position in physical pixels
                     // shaped.width is in logical pixels, multiply by scale
-                    let line_x = if line.is_duet {
+                    // 从右到左的歌词镜像对齐：普通行靠右，对唱行靠左
+                    let rtl = cached.main.direction.is_rtl();
+                    let right_aligned = line.is_duet != rtl;
+                    let line_x = if right_aligned {
                         viewport_width - cached.main.width * scale - padding_right
                     } else {
                         padding_left
@@ -1488,9 +1560,13 @@ impl LyricsGpuPipeline {
                         };
                         let char_delay_ms = word_delay + char_delay_offset;
 
-                        let glyph_left_x = glyph.x;
                         let glyph_start_in_word = if word_pixel_width > 0.0 {
-                            ((glyph_left_x - word_start_x) / word_pixel_width).clamp(0.0, 1.0)
+                            let from_start = if rtl {
+                                word_start_x + word_pixel_width - (glyph.x + glyph.advance)
+                            } else {
+                                glyph.x - word_start_x
+                            };
+                            (from_start / word_pixel_width).clamp(0.0, 1.0)
                         } else {
                             0.0
                         };
@@ -1530,6 +1606,7 @@ impl LyricsGpuPipeline {
                         base.set_emphasize(emphasize);
                         base.set_bg(line.is_bg);
                         base.set_duet(line.is_duet);
+                        base.set_rtl(rtl);
 
                         for (cx, cy) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
                             let mut v = base;
@@ -1551,7 +1628,7 @@ impl LyricsGpuPipeline {
                     // Add translation text using pre-shaped data
                     if let Some(ref trans_shaped) = cached.translation {
                         let trans_y_offset = cached.main.height * scale;
-                        let trans_x = if line.is_duet {
+                        let trans_x = if right_aligned {
                             viewport_width - trans_shaped.width * scale - padding_right
                         } else {
                             padding_left
@@ -1584,7 +1661,7 @@ impl LyricsGpuPipeline {
                             .map(|t| t.height * scale)
                             .unwrap_or(0.0);
                         let roman_y_offset = cached.main.height * scale + trans_height;
-                        let roman_x = if line.is_duet {
+                        let roman_x = if right_aligned {
                             viewport_width - roman_shaped.width * scale - padding_right
                         } else {
                             padding_left
@@ -1903,20 +1980,46 @@ impl LyricsGpuPipeline {
         }
     }
 
-    /// Update bind group with current atlas
+    /// Write the per-line data, dropping the bind group if the buffer had to
+    /// grow
+    /// How many of `count` lines fit the line array, warning once when
+    /// lyrics on the uniform path don't
+    fn drawable_lines(&mut self, count: usize) -> usize {
+        let Some(max) = self.max_lines else {
+            return count;
+        };
+        if count > max && self.overflow_warned != count {
+            self.overflow_warned = count;
+            tracing::warn!(
+                "Lyrics have {} lines but this GPU only fits {}; the rest won't be drawn",
+                count,
+                max
+            );
+        }
+        count.min(max)
+    }
+
+    fn write_line_uniforms(&mut self, device: &Device, queue: &Queue, lines: &[LineUniform]) {
+        if self
+            .line_uniform_buffer
+            .write(device, queue, bytemuck::cast_slice(lines))
+        {
+            self.bind_group = None;
+        }
+    }
+
+    /// Rebuild the bind group if the atlas texture changed since the last build
+    ///
+    /// Uniform and vertex data are written into persistent buffers, so only a
+    /// new atlas texture or a grown line buffer invalidates the bind group.
     fn update_bind_group(&mut self, device: &Device) {
-        let atlas_view = self.sdf_cache.atlas_view();
+        let generation = self.sdf_cache.atlas_generation();
+        if self.bind_group.is_some() && self.bind_group_atlas == Some(generation) {
+            return;
+        }
+        self.bind_group_atlas = Some(generation);
 
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("SDF Glyph Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Linear,
-            ..Default::default()
-        });
+        let atlas_view = self.sdf_cache.atlas_view();
 
         self.bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Lyrics Bind Group"),
@@ -1928,7 +2031,7 @@ impl LyricsGpuPipeline {
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: self.line_uniform_buffer.as_entire_binding(),
+                    resource: self.line_uniform_buffer.buffer().as_entire_binding(),
                 },
                 wgpu::BindGroupEntry {
                     binding: 2,
@@ -1936,7 +2039,7 @@ impl LyricsGpuPipeline {
                 },
                 wgpu::BindGroupEntry {
                     binding: 3,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
+                    resource: wgpu::BindingResource::Sampler(&self.glyph_sampler),
                 },
             ],
         }));
@@ -1954,8 +2057,11 @@ impl LyricsGpuPipeline {
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, bind_group, &[]);
-        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.buffer().slice(..));
+        render_pass.set_index_buffer(
+            self.index_buffer.buffer().slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
         render_pass.draw_indexed(0..self.index_count, 0, 0..1);
     }
 
@@ -1971,15 +2077,17 @@ impl LyricsGpuPipeline {
 
         render_pass.set_pipeline(&self.mrt_pipeline);
         render_pass.set_bind_group(0, bind_group, &[]);
-        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.buffer().slice(..));
+        render_pass.set_index_buffer(
+            self.index_buffer.buffer().slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
         render_pass.draw_indexed(0..self.index_count, 0, 0..1);
     }
 
     /// Prepare interlude dots for rendering
     pub fn prepare_interlude_dots(
         &mut self,
-        device: &Device,
         queue: &Queue,
         dots: &InterludeDots,
         viewport_width: f32,
@@ -2005,15 +2113,6 @@ impl LyricsGpuPipeline {
             0,
             bytemuck::bytes_of(&dots_uniform),
         );
-
-        self.dots_bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Interlude Dots Bind Group"),
-            layout: &self.dots_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: self.dots_uniform_buffer.as_entire_binding(),
-            }],
-        }));
     }
 
     /// Render interlude dots
@@ -2022,12 +2121,8 @@ impl LyricsGpuPipeline {
             return;
         }
 
-        let Some(bind_group) = &self.dots_bind_group else {
-            return;
-        };
-
         render_pass.set_pipeline(&self.dots_pipeline);
-        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_bind_group(0, &self.dots_bind_group, &[]);
         render_pass.draw(0..4, 0..3);
     }
 
@@ -2065,6 +2160,9 @@ impl LyricsGpuPipeline {
         // Cache viewport info
         *self.cached_viewport.write() = (viewport_width, viewport_height);
 
+        // Per-line passes drop resolution while frames run over budget
+        self.render_scale.frame(Instant::now());
+
         // Ensure textures exist for per-line blur rendering
         self.ensure_textures(device, viewport_width, viewport_height);
     }
@@ -2096,8 +2194,8 @@ impl LyricsGpuPipeline {
             return;
         };
 
-        // 获取缓存的行渲染信息
-        let line_render_info = self.cached_line_render_info.read().clone();
+        // 获取缓存的行渲染信息（只读借用，不复制）
+        let line_render_info = self.cached_line_render_info.read();
 
         if line_render_info.is_empty() {
             return;
@@ -2118,8 +2216,9 @@ impl LyricsGpuPipeline {
             &line_render_info,
             &self.pipeline,
             bind_group,
-            &self.vertex_buffer,
-            &self.index_buffer,
+            self.vertex_buffer.buffer(),
+            self.index_buffer.buffer(),
+            self.render_scale.scale(),
         );
 
         // 渲染间奏点（无模糊）
@@ -0,0 +1,96 @@
+//! GPU buffers that grow with their contents
+//!
+//! Dense lyrics (long rap verses with translation and romanization) can need
+//! far more glyphs and lines than a typical song. Instead of a fixed size that
+//! cuts them off, a buffer is reallocated at the next power of two when an
+//! upload doesn't fit, and then kept at that high-water mark so later songs
+//! don't reallocate again.
+
+use wgpu::{Buffer, BufferUsages, Device, Queue};
+
+/// A buffer reallocated to fit whatever is written into it
+pub struct GrowableBuffer {
+    buffer: Buffer,
+    label: &'static str,
+    usage: BufferUsages,
+}
+
+impl GrowableBuffer {
+    /// Create with room for `initial_size` bytes; `usage` gets `COPY_DST`
+    /// added
+    pub fn new(
+        device: &Device,
+        label: &'static str,
+        usage: BufferUsages,
+        initial_size: u64,
+    ) -> Self {
+        let usage = usage | BufferUsages::COPY_DST;
+        Self {
+            buffer: create(device, label, usage, initial_size),
+            label,
+            usage,
+        }
+    }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Write `data` at the start of the buffer, growing it first if needed
+    ///
+    /// Returns true when the buffer was replaced, so bind groups holding
+    /// the old one must be recreated.
+    pub fn write(&mut self, device: &Device, queue: &Queue, data: &[u8]) -> bool {
+        let needed = data.len() as u64;
+        let grown = match grown_size(self.buffer.size(), needed) {
+            Some(size) => {
+                tracing::debug!(
+                    "Growing {} from {} to {} bytes",
+                    self.label,
+                    self.buffer.size(),
+                    size
+                );
+                self.buffer = create(device, self.label, self.usage, size);
+                true
+            }
+            None => false,
+        };
+        if !data.is_empty() {
+            queue.write_buffer(&self.buffer, 0, data);
+        }
+        grown
+    }
+}
+
+fn create(device: &Device, label: &'static str, usage: BufferUsages, size: u64) -> Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage,
+        mapped_at_creation: false,
+    })
+}
+
+/// New size for a buffer of `current` bytes that must hold `needed`, if it
+/// has to grow
+fn grown_size(current: u64, needed: u64) -> Option<u64> {
+    if needed <= current {
+        return None;
+    }
+    // Buffer sizes stay a multiple of 4 for writes
+    Some(needed.next_power_of_two().max(4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grown_size() {
+        assert_eq!(grown_size(1024, 1024), None);
+        assert_eq!(grown_size(1024, 0), None);
+        assert_eq!(grown_size(1024, 1025), Some(2048));
+        assert_eq!(grown_size(1024, 5000), Some(8192));
+        assert_eq!(grown_size(0, 1), Some(4));
+    }
+}
@@ -267,6 +267,14 @@ impl LineAnimation {
         self.pos_y.arrived() && self.scale.arrived()
     }
 
+    /// Whether any property is still moving (springs or blur/opacity fades)
+    pub fn is_animating(&self) -> bool {
+        const SETTLED: f32 = 0.001;
+        !self.arrived()
+            || (self.target_blur - self.blur).abs() > SETTLED
+            || (self.target_opacity - self.opacity).abs() > SETTLED
+    }
+
     /// Update spring parameters (for runtime configuration)
     pub fn update_pos_y_params(&mut self, params: SpringParams) {
         self.pos_y.update_params(params);
@@ -469,6 +477,11 @@ impl LineAnimationManager {
         }
     }
 
+    /// Whether any line is still animating
+    pub fn is_animating(&self) -> bool {
+        self.animations.iter().any(LineAnimation::is_animating)
+    }
+
     /// Get current blur levels for all lines (Apple Music-style distance-based blur)
     pub fn current_blur_levels(&self) -> Vec<f32> {
         self.animations.iter().map(|a| a.blur).collect()
@@ -973,4 +986,48 @@ mod tests {
             "Blur calculation can exceed 32 (cap applied later)"
         );
     }
+
+    #[test]
+    fn test_is_animating_settles_after_layout_change() {
+        let mut manager = create_test_manager(10);
+        let line_heights = vec![48.0; 10];
+        let layout = |manager: &mut LineAnimationManager, index: usize, is_seek: bool| {
+            manager.calc_layout_full(
+                &line_heights,
+                8.0,
+                index,
+                &HashSet::from([index]),
+                true,
+                is_seek,
+                true,
+                0.97,
+                0.75,
+                0.05,
+                1.05,
+                false,
+                1920.0,
+            );
+        };
+        let settle = |manager: &mut LineAnimationManager| {
+            for _ in 0..600 {
+                manager.update(1.0 / 60.0);
+            }
+        };
+
+        layout(&mut manager, 3, true);
+        settle(&mut manager);
+        assert!(
+            !manager.is_animating(),
+            "Static layout should need no frames"
+        );
+
+        layout(&mut manager, 4, false);
+        assert!(
+            manager.is_animating(),
+            "Moving to the next line should animate"
+        );
+
+        settle(&mut manager);
+        assert!(!manager.is_animating(), "Animation should settle");
+    }
 }
@@ -12,8 +12,13 @@
 //! ## 保留的功能
 //!
 //! - 逐行渲染信息结构（用于传递 blur_level 到 shader）
-//! - 纹理池（用于合成）
+//! - 纹理池（用于合成，按尺寸复用，视口变化时才淘汰旧纹理）
 //! - 合成管线（用于层叠渲染）
+//!
+//! 帧时间超出预算时（见 `render_scale`），各行先以较低分辨率画到离屏纹理，
+//! 再由合成管线放大到目标上。
+//!
+//! 每帧的绘制顺序写入复用的缓冲区，稳定状态下渲染不产生分配。
 
 use wgpu::{Device, TextureFormat};
 
@@ -95,6 +100,10 @@ pub struct PerLineBlurRenderer {
     format: TextureFormat,
     /// 当前视口尺寸
     viewport_size: (u32, u32),
+    /// 绘制顺序（可见行的下标），每帧复用
+    draw_order: Vec<usize>,
+    /// 降分辨率渲染的离屏纹理及其合成绑定组，尺寸变化时重建
+    scaled_target: Option<(LineTexture, wgpu::BindGroup)>,
 }
 
 impl PerLineBlurRenderer {
@@ -151,7 +160,8 @@ impl PerLineBlurRenderer {
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
                     format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    // 离屏纹理经 alpha 混合画在透明底上，颜色已预乘
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
@@ -184,10 +194,15 @@ impl PerLineBlurRenderer {
             composite_sampler,
             format,
             viewport_size: (0, 0),
+            draw_order: Vec::new(),
+            scaled_target: None,
         }
     }
 
     /// 从池中获取或创建纹理
+    ///
+    /// 优先复用尺寸相同的空闲纹理，没有时才创建新纹理；
+    /// 尺寸不同的空闲纹理保留给之后的请求，不会被就地重建。
     fn acquire_texture(
         pool: &mut Vec<LineTexture>,
         device: &Device,
@@ -197,26 +212,12 @@ impl PerLineBlurRenderer {
         label_prefix: &str,
     ) -> usize {
         // 查找可用的匹配尺寸的纹理
-        for (i, tex) in pool.iter_mut().enumerate() {
-            if !tex.in_use && tex.matches_size(width, height) {
-                tex.in_use = true;
-                return i;
-            }
-        }
-
-        // 查找可用的任意纹理（需要重新创建）
-        for (i, tex) in pool.iter_mut().enumerate() {
-            if !tex.in_use {
-                *tex = LineTexture::new(
-                    device,
-                    width,
-                    height,
-                    format,
-                    &format!("{} {}", label_prefix, i),
-                );
-                tex.in_use = true;
-                return i;
-            }
+        if let Some(i) = pool
+            .iter()
+            .position(|tex| !tex.in_use && tex.matches_size(width, height))
+        {
+            pool[i].in_use = true;
+            return i;
         }
 
         // 创建新纹理
@@ -241,14 +242,49 @@ impl PerLineBlurRenderer {
     }
 
     /// 更新视口尺寸
+    ///
+    /// 视口变化后旧尺寸的纹理不会再被用到，直接释放。
     pub fn set_viewport_size(&mut self, width: u32, height: u32) {
+        if self.viewport_size == (width, height) {
+            return;
+        }
         self.viewport_size = (width, height);
+        self.release_all_textures();
+        self.texture_pool
+            .retain(|tex| tex.matches_size(width, height));
+    }
+
+    /// 确保降分辨率渲染用的离屏纹理存在且尺寸正确
+    fn ensure_scaled_target(&mut self, device: &Device, width: u32, height: u32) {
+        if !self
+            .scaled_target
+            .as_ref()
+            .is_some_and(|(tex, _)| tex.matches_size(width, height))
+        {
+            let tex = LineTexture::new(device, width, height, self.format, "Scaled Lines Texture");
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Scaled Lines Composite Bind Group"),
+                layout: &self.composite_bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&tex.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.composite_sampler),
+                    },
+                ],
+            });
+            self.scaled_target = Some((tex, bind_group));
+        }
     }
 
     /// SDF 渲染所有行
     ///
     /// SDF 版本：所有行直接渲染到目标，模糊效果在 shader 中通过 smoothstep 实现。
     /// blur_level 通过 LineUniform 传递给 shader。
+    /// `render_scale` 小于 1 时先画到缩小的离屏纹理，再放大合成到目标。
     ///
     /// 参数：
     /// - lines: 行渲染信息列表
@@ -256,10 +292,11 @@ impl PerLineBlurRenderer {
     /// - bind_group: 绑定组
     /// - vertex_buffer: 顶点缓冲区
     /// - index_buffer: 索引缓冲区
+    /// - render_scale: 渲染分辨率比例
     #[allow(clippy::too_many_arguments)]
     pub fn render_with_blur(
         &mut self,
-        _device: &Device,
+        device: &Device,
         _queue: &wgpu::Queue,
         encoder: &mut wgpu::CommandEncoder,
         target: &wgpu::TextureView,
@@ -269,6 +306,7 @@ impl PerLineBlurRenderer {
         bind_group: &wgpu::BindGroup,
         vertex_buffer: &wgpu::Buffer,
         index_buffer: &wgpu::Buffer,
+        render_scale: f32,
     ) {
         let viewport_width = self.viewport_size.0;
         let viewport_height = self.viewport_size.1;
@@ -280,56 +318,109 @@ impl PerLineBlurRenderer {
         // 释放之前的纹理
         self.release_all_textures();
 
-        // 收集可见行
-        let visible_lines: Vec<&LineRenderInfo> = lines
-            .iter()
-            .filter(|line| line.visible && line.index_range.1 > 0)
-            .collect();
+        // 收集可见行，按 blur_level 从大到小排序（远的先画，近的后画覆盖）
+        self.draw_order.clear();
+        self.draw_order.extend(
+            lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.visible && line.index_range.1 > 0)
+                .map(|(i, _)| i),
+        );
 
-        if visible_lines.is_empty() {
+        if self.draw_order.is_empty() {
             return;
         }
 
-        // SDF 渲染：所有行直接渲染到目标
-        // 模糊效果在 shader 中通过 LineUniform.blur 和 smoothstep 实现
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("SDF Lines Render Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: target,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
+        // 不稳定排序不分配内存；同级按行号排，保持原有顺序
+        self.draw_order.sort_unstable_by(|&a, &b| {
+            lines[b]
+                .blur_level
+                .partial_cmp(&lines[a].blur_level)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.cmp(&b))
         });
 
+        if render_scale >= 1.0 {
+            // 恢复全分辨率后不再需要离屏纹理
+            self.scaled_target = None;
+            // SDF 渲染：所有行直接渲染到目标
+            // 模糊效果在 shader 中通过 LineUniform.blur 和 smoothstep 实现
+            let mut render_pass = lines_pass(encoder, target, wgpu::LoadOp::Load);
+            render_pass.set_scissor_rect(
+                clip_bounds.x,
+                clip_bounds.y,
+                clip_bounds.width,
+                clip_bounds.height,
+            );
+            Self::draw_lines(
+                &mut render_pass,
+                &self.draw_order,
+                lines,
+                pipeline,
+                bind_group,
+                vertex_buffer,
+                index_buffer,
+            );
+            return;
+        }
+
+        // 降分辨率：顶点在 NDC 中，画到小纹理上即自动缩放
+        let width = ((viewport_width as f32 * render_scale).ceil() as u32).max(1);
+        let height = ((viewport_height as f32 * render_scale).ceil() as u32).max(1);
+        let scissor = scale_rect(clip_bounds, render_scale, width, height);
+        self.ensure_scaled_target(device, width, height);
+        let Some((scaled, composite_bind_group)) = &self.scaled_target else {
+            return;
+        };
+        {
+            let mut render_pass = lines_pass(
+                encoder,
+                &scaled.view,
+                wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+            );
+            render_pass.set_scissor_rect(scissor.x, scissor.y, scissor.width, scissor.height);
+            Self::draw_lines(
+                &mut render_pass,
+                &self.draw_order,
+                lines,
+                pipeline,
+                bind_group,
+                vertex_buffer,
+                index_buffer,
+            );
+        }
+
+        // 放大合成到目标
+        let mut render_pass = lines_pass(encoder, target, wgpu::LoadOp::Load);
         render_pass.set_scissor_rect(
             clip_bounds.x,
             clip_bounds.y,
             clip_bounds.width,
             clip_bounds.height,
         );
+        render_pass.set_pipeline(&self.composite_pipeline);
+        render_pass.set_bind_group(0, composite_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
 
+    /// 按绘制顺序画出各行
+    fn draw_lines(
+        render_pass: &mut wgpu::RenderPass<'_>,
+        draw_order: &[usize],
+        lines: &[LineRenderInfo],
+        pipeline: &wgpu::RenderPipeline,
+        bind_group: &wgpu::BindGroup,
+        vertex_buffer: &wgpu::Buffer,
+        index_buffer: &wgpu::Buffer,
+    ) {
         render_pass.set_pipeline(pipeline);
         render_pass.set_bind_group(0, bind_group, &[]);
         render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
         render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
 
-        // 按 blur_level 从大到小排序（远的先画，近的后画覆盖）
-        let mut sorted_lines: Vec<_> = visible_lines.iter().collect();
-        sorted_lines.sort_by(|a, b| {
-            b.blur_level
-                .partial_cmp(&a.blur_level)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        for line in sorted_lines {
-            let (start, count) = line.index_range;
+        for &i in draw_order {
+            let (start, count) = lines[i].index_range;
             render_pass.draw_indexed(start..(start + count), 0, 0..1);
         }
     }
@@ -377,3 +468,45 @@ impl PerLineBlurRenderer {
         )
     }
 }
+
+/// 开始一个画行的 render pass
+fn lines_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("SDF Lines Render Pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
+
+/// 把裁剪矩形缩放到降分辨率纹理上，向外取整并限制在纹理内
+fn scale_rect(
+    rect: &iced::Rectangle<u32>,
+    scale: f32,
+    width: u32,
+    height: u32,
+) -> iced::Rectangle<u32> {
+    let x = ((rect.x as f32 * scale).floor() as u32).min(width);
+    let y = ((rect.y as f32 * scale).floor() as u32).min(height);
+    let right = (((rect.x + rect.width) as f32 * scale).ceil() as u32).min(width);
+    let bottom = (((rect.y + rect.height) as f32 * scale).ceil() as u32).min(height);
+    iced::Rectangle {
+        x,
+        y,
+        width: right - x,
+        height: bottom - y,
+    }
+}
@@ -241,6 +241,11 @@ impl ScrollPhysics {
         self.state
     }
 
+    /// Following playback with the scroll spring at rest
+    pub fn is_settled(&self) -> bool {
+        self.state == ScrollState::AutoPlay && self.snap_spring.arrived()
+    }
+
     /// 获取距上次交互的时间
     pub fn time_since_interaction(&self) -> f32 {
         self.last_interaction_time.elapsed().as_secs_f32()
@@ -534,7 +534,7 @@ impl Primitive for LyricsEnginePrimitive {
             self.current_time_ms,
             self.scroll_position,
             font_size,
-            self.config.word_fade_width,
+            &self.config,
             scale, // Scale factor for logical to physical conversion
         );
 
@@ -548,7 +548,6 @@ impl Primitive for LyricsEnginePrimitive {
             dots_state.dot_opacities = dots.dot_opacities;
 
             gpu_pipeline.prepare_interlude_dots(
-                device,
                 queue,
                 &dots_state,
                 full_viewport_width,
@@ -0,0 +1,168 @@
+//! Dynamic render resolution for the lyrics
+//!
+//! Integrated GPUs can fall behind on a large window with many blurred
+//! lines. When frames keep missing the 60fps budget the lines are drawn at a
+//! lower resolution and upscaled onto the target; once frames are back on
+//! budget for a while, full resolution is tried again. A restore that
+//! immediately falls behind waits longer before the next try, so a device
+//! right at the edge doesn't flicker between resolutions.
+
+use std::time::{Duration, Instant};
+
+/// Resolution steps, full first
+const LEVELS: [f32; 3] = [1.0, 0.75, 0.5];
+/// Frame time at 60fps
+const FRAME_BUDGET: f32 = 1.0 / 60.0;
+/// Averaged frame time above this is falling behind
+const SLOW_FRAME: f32 = FRAME_BUDGET * 1.25;
+/// Averaged frame time below this has headroom (vsync holds frames at the
+/// budget itself)
+const STEADY_FRAME: f32 = FRAME_BUDGET * 1.1;
+/// Consecutive slow frames before stepping down
+const SLOW_FRAMES: u32 = 30;
+/// Gaps longer than this are the page being idle, not load
+const IDLE_GAP: Duration = Duration::from_millis(250);
+/// Steady time before stepping back up, doubled after each failed restore
+const RESTORE_AFTER: Duration = Duration::from_secs(3);
+const MAX_RESTORE_AFTER: Duration = Duration::from_secs(60);
+/// Falling behind this soon after a restore counts as a failed restore
+const RESTORE_GRACE: Duration = Duration::from_secs(5);
+
+/// Picks the resolution scale from observed frame times
+#[derive(Debug)]
+pub struct RenderScale {
+    level: usize,
+    last_frame: Option<Instant>,
+    /// Exponential average of frame times in seconds
+    average: Option<f32>,
+    slow_frames: u32,
+    steady_for: Duration,
+    restore_after: Duration,
+    restored_at: Option<Instant>,
+}
+
+impl Default for RenderScale {
+    fn default() -> Self {
+        Self {
+            level: 0,
+            last_frame: None,
+            average: None,
+            slow_frames: 0,
+            steady_for: Duration::ZERO,
+            restore_after: RESTORE_AFTER,
+            restored_at: None,
+        }
+    }
+}
+
+impl RenderScale {
+    /// Current scale, 1.0 at full resolution
+    pub fn scale(&self) -> f32 {
+        LEVELS[self.level]
+    }
+
+    /// Record a frame prepared at `now`
+    pub fn frame(&mut self, now: Instant) {
+        let Some(last) = self.last_frame.replace(now) else {
+            return;
+        };
+        let elapsed = now.saturating_duration_since(last);
+        if elapsed > IDLE_GAP {
+            self.average = None;
+            self.slow_frames = 0;
+            return;
+        }
+        let frame_time = elapsed.as_secs_f32();
+        let average = match self.average {
+            Some(average) => average * 0.9 + frame_time * 0.1,
+            None => frame_time,
+        };
+        self.average = Some(average);
+
+        if average > SLOW_FRAME {
+            self.steady_for = Duration::ZERO;
+            self.slow_frames += 1;
+            if self.slow_frames >= SLOW_FRAMES && self.level + 1 < LEVELS.len() {
+                self.step_down(now);
+            }
+        } else {
+            self.slow_frames = 0;
+            if average < STEADY_FRAME {
+                self.steady_for += elapsed;
+            } else {
+                self.steady_for = Duration::ZERO;
+            }
+            if self.level > 0 && self.steady_for >= self.restore_after {
+                self.level -= 1;
+                self.steady_for = Duration::ZERO;
+                self.restored_at = Some(now);
+                tracing::debug!("Lyrics render scale raised to {}", self.scale());
+            }
+        }
+    }
+
+    fn step_down(&mut self, now: Instant) {
+        if self
+            .restored_at
+            .is_some_and(|at| now.saturating_duration_since(at) < RESTORE_GRACE)
+        {
+            self.restore_after = (self.restore_after * 2).min(MAX_RESTORE_AFTER);
+        }
+        self.level += 1;
+        self.slow_frames = 0;
+        // Give the new resolution a fresh average to be judged by
+        self.average = None;
+        tracing::info!(
+            "Lyrics frames over budget, render scale lowered to {}",
+            self.scale()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(scale: &mut RenderScale, start: Instant, frames: u32, frame_ms: u64) -> Instant {
+        let mut now = start;
+        for _ in 0..frames {
+            now += Duration::from_millis(frame_ms);
+            scale.frame(now);
+        }
+        now
+    }
+
+    #[test]
+    fn test_slow_frames_lower_and_steady_frames_restore() {
+        let mut scale = RenderScale::default();
+        let now = run(&mut scale, Instant::now(), 60, 16);
+        assert_eq!(scale.scale(), 1.0);
+
+        let now = run(&mut scale, now, 60, 33);
+        assert_eq!(scale.scale(), 0.75);
+
+        // Not yet steady for long enough
+        let now = run(&mut scale, now, 60, 16);
+        assert_eq!(scale.scale(), 0.75);
+        run(&mut scale, now, 200, 16);
+        assert_eq!(scale.scale(), 1.0);
+    }
+
+    #[test]
+    fn test_idle_gaps_are_not_load() {
+        let mut scale = RenderScale::default();
+        run(&mut scale, Instant::now(), 100, 500);
+        assert_eq!(scale.scale(), 1.0);
+    }
+
+    #[test]
+    fn test_failed_restore_backs_off() {
+        let mut scale = RenderScale::default();
+        let now = run(&mut scale, Instant::now(), 60, 33);
+        let now = run(&mut scale, now, 250, 16);
+        assert_eq!(scale.scale(), 1.0);
+        run(&mut scale, now, 60, 33);
+        assert_eq!(scale.scale(), 0.75);
+        assert_eq!(scale.restore_after, RESTORE_AFTER * 2);
+    }
+}
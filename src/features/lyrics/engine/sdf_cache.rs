@@ -13,8 +13,12 @@ use cosmic_text::{CacheKey, FontSystem};
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use wgpu::{Device, Queue};
 
+/// 图集纹理代数计数器，每创建一个图集纹理递增
+static NEXT_ATLAS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
 /// 全局预生成缓存
 /// 用于在后台线程生成 SDF 位图后，在主线程导入到 SdfCache
 static GLOBAL_PRE_GENERATED: once_cell::sync::Lazy<Mutex<HashMap<CacheKey, SdfBitmap>>> =
@@ -43,10 +47,20 @@ pub fn global_cache_size() -> usize {
     GLOBAL_PRE_GENERATED.lock().len()
 }
 
-/// 纹理图集大小
+/// 获取全局预生成缓存占用的字节数
+pub fn global_cache_bytes() -> u64 {
+    GLOBAL_PRE_GENERATED
+        .lock()
+        .values()
+        .map(|bitmap| bitmap.data.len() as u64)
+        .sum()
+}
+
+/// 纹理图集默认大小（见 [`gpu_caps::FULL_ATLAS_SIZE`](super::gpu_caps::FULL_ATLAS_SIZE)）
 /// 4096x4096 可以容纳更多字形，减少清空重建的频率
 /// 对于中文歌词，常用汉字约 3000-5000 个，加上标点和英文，4096x4096 足够
-const ATLAS_SIZE: u32 = 4096;
+/// 纹理尺寸受限的 GPU 上使用更小的图集，溢出时清空重建
+const ATLAS_SIZE: u32 = super::gpu_caps::FULL_ATLAS_SIZE;
 /// 字形之间的间距（gutter），防止双线性插值时边缘渗透
 /// 4 像素足够防止相邻字形的颜色混合（线性插值需要 1 像素，安全边距 3 像素）
 const ATLAS_GUTTER: u32 = 4;
@@ -97,18 +111,20 @@ pub struct SdfAtlas {
     height: u32,
     /// 是否需要重建（溢出时）
     needs_rebuild: bool,
+    /// 纹理代数，纹理对象变化时改变（清空内容不算）
+    generation: u64,
 }
 
 impl SdfAtlas {
     /// 创建新的 SDF 图集
-    pub fn new(device: &Device) -> Self {
+    pub fn new(device: &Device, size: u32) -> Self {
         // 使用 Rgba8Unorm 格式，因为 wgpu 不支持 Rgb8Unorm
         // 我们会在上传时将 RGB 数据转换为 RGBA
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("SDF Glyph Atlas"),
             size: wgpu::Extent3d {
-                width: ATLAS_SIZE,
-                height: ATLAS_SIZE,
+                width: size,
+                height: size,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
@@ -127,9 +143,10 @@ impl SdfAtlas {
             glyphs: HashMap::new(),
             rows: Vec::new(),
             y_cursor: 0,
-            width: ATLAS_SIZE,
-            height: ATLAS_SIZE,
+            width: size,
+            height: size,
             needs_rebuild: false,
+            generation: NEXT_ATLAS_GENERATION.fetch_add(1, Ordering::Relaxed),
         }
     }
 
@@ -352,6 +369,16 @@ impl SdfAtlas {
         self.needs_rebuild
     }
 
+    /// 向内存统计报告当前占用
+    fn publish_usage(&self) {
+        crate::features::memory::record_atlas(
+            self.glyphs.len(),
+            self.y_cursor,
+            self.width,
+            self.height,
+        );
+    }
+
     /// 获取纹理
     pub fn texture(&self) -> &wgpu::Texture {
         &self.texture
@@ -491,11 +518,16 @@ impl SdfCache {
     /// IMPORTANT: font_system 必须与 TextShaper 使用同一实例！
     /// CacheKey 包含 font_id，必须匹配才能正确查找字形。
     pub fn new(device: &Device, font_system: SharedFontSystem) -> Self {
-        Self::with_debug(device, font_system, false)
+        Self::with_debug(device, font_system, false, ATLAS_SIZE)
     }
 
-    /// Create with debug logging enabled
-    pub fn with_debug(device: &Device, font_system: SharedFontSystem, debug_logging: bool) -> Self {
+    /// Create with debug logging enabled and an atlas of `atlas_size`
+    pub fn with_debug(
+        device: &Device,
+        font_system: SharedFontSystem,
+        debug_logging: bool,
+        atlas_size: u32,
+    ) -> Self {
         Self {
             // base_size = 64px, buffer = 4px
             // 64px 是速度和质量的平衡点：
@@ -504,7 +536,7 @@ impl SdfCache {
             // - buffer = 4 保持笔画清晰不粘连
             generator: SdfGenerator::new(64, 4),
             font_store: Mutex::new(FontStore::with_debug(debug_logging)),
-            atlas: Mutex::new(SdfAtlas::new(device)),
+            atlas: Mutex::new(SdfAtlas::new(device, atlas_size)),
             font_system,
             debug_logging,
             pre_generated: Mutex::new(HashMap::new()),
@@ -516,7 +548,7 @@ impl SdfCache {
         Self {
             generator: SdfGenerator::with_config(config),
             font_store: Mutex::new(FontStore::new()),
-            atlas: Mutex::new(SdfAtlas::new(device)),
+            atlas: Mutex::new(SdfAtlas::new(device, ATLAS_SIZE)),
             font_system,
             debug_logging: false,
             pre_generated: Mutex::new(HashMap::new()),
@@ -579,7 +611,7 @@ impl SdfCache {
         let mut atlas = self.atlas.lock();
 
         // 尝试缓存，如果失败（图集满了），清空后重试
-        match atlas.cache(queue, cache_key, &bitmap) {
+        let info = match atlas.cache(queue, cache_key, &bitmap) {
             Some(info) => Some(info),
             None => {
                 // 图集空间不足，清空后重试
@@ -589,7 +621,9 @@ impl SdfCache {
                 atlas.clear(queue);
                 atlas.cache(queue, cache_key, &bitmap)
             }
-        }
+        };
+        atlas.publish_usage();
+        info
     }
 
     /// 预生成 MSDF 位图（不需要 GPU，可在后台线程调用）
@@ -681,9 +715,18 @@ impl SdfCache {
             .create_view(&wgpu::TextureViewDescriptor::default())
     }
 
+    /// 获取图集纹理代数
+    ///
+    /// 只有纹理对象本身变化时才会改变，引用该纹理的 bind group 据此判断是否需要重建。
+    pub fn atlas_generation(&self) -> u64 {
+        self.atlas.lock().generation
+    }
+
     /// 清空缓存
     pub fn clear(&self, queue: &Queue) {
-        self.atlas.lock().clear(queue);
+        let mut atlas = self.atlas.lock();
+        atlas.clear(queue);
+        atlas.publish_usage();
     }
 
     /// 检查是否需要重建
@@ -10,9 +10,20 @@
 //! let bitmap = generator.generate_char(&font_data, 'A')?;
 //! ```
 
-use ab_glyph::{Font, FontRef, GlyphId, PxScale, ScaleFont};
+use ab_glyph::{Font, FontRef, GlyphId, GlyphImageFormat, PxScale, ScaleFont};
+use image::imageops::FilterType;
 use sdf_glyph_renderer::{BitmapGlyph, clamp_to_u8};
 
+/// 位图字形 (彩色 emoji) 中比这更暗的像素不计入单色轮廓，
+/// 这样眼睛、嘴巴等细节会留空而不是变成一整块
+const RASTER_DARK_CUTOFF: f32 = 0.2;
+
+/// 彩色位图像素在单色轮廓中的覆盖度
+fn raster_coverage([r, g, b, a]: [u8; 4]) -> u8 {
+    let luma = (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32) / 255.0;
+    if luma < RASTER_DARK_CUTOFF { 0 } else { a }
+}
+
 /// SDF 生成器配置
 #[derive(Debug, Clone, Copy)]
 pub struct SdfConfig {
@@ -103,9 +114,11 @@ impl SdfGenerator {
         let scale = PxScale::from(self.config.base_size as f32);
         let scaled_font = font.as_scaled(scale);
 
-        // 获取字形轮廓
+        // 获取字形轮廓；没有轮廓的彩色 emoji 退回到位图
         let glyph = glyph_id.with_scale(scale);
-        let outlined = font.outline_glyph(glyph)?;
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            return self.generate_from_raster(font, glyph_id);
+        };
 
         // 获取边界
         let bounds = outlined.px_bounds();
@@ -126,9 +139,82 @@ impl SdfGenerator {
             }
         });
 
+        // bearing_x: 纹理左边缘相对于笔触原点的 X 偏移
+        // bounds.min.x 是字形左边缘，减去 buffer 得到纹理左边缘
+        let bearing_x = bounds.min.x.floor() as i32 - self.config.buffer as i32;
+
+        // bearing_y: 纹理顶边缘相对于基线的 Y 偏移
+        // ab_glyph 的 bounds.min.y 是字形顶部（Y 向下为正，所以 min.y 是顶部）
+        // 需要取负值并加上 buffer
+        // 注意：ab_glyph 的坐标系是 Y 向下，所以 bounds.max.y 是底部，bounds.min.y 是顶部
+        // 但 bounds.min.y 通常是负数（基线以上），所以 -bounds.min.y 是正数
+        let bearing_y = -bounds.min.y.floor() as i32 + self.config.buffer as i32;
+
+        let advance = scaled_font.h_advance(glyph_id);
+
+        self.render(
+            &alpha,
+            glyph_width,
+            glyph_height,
+            bearing_x,
+            bearing_y,
+            advance,
+        )
+    }
+
+    /// 彩色 emoji (CBDT / sbix) 只有 PNG 位图，按单色轮廓生成 SDF
+    ///
+    /// 图集只有一个通道，所以 emoji 以文字颜色显示，暗色细节留空。
+    fn generate_from_raster(&self, font: &FontRef, glyph_id: GlyphId) -> Option<SdfBitmap> {
+        let base_size = self.config.base_size as f32;
+        let raster = font.glyph_raster_image2(glyph_id, self.config.base_size as u16)?;
+        if !matches!(raster.format, GlyphImageFormat::Png) || raster.pixels_per_em == 0 {
+            return None;
+        }
+        let decoded = image::load_from_memory_with_format(raster.data, image::ImageFormat::Png)
+            .ok()?
+            .to_rgba8();
+
+        // 缩放到基准字号
+        let scale = base_size / raster.pixels_per_em as f32;
+        let glyph_width = ((decoded.width() as f32 * scale).round() as u32).max(1);
+        let glyph_height = ((decoded.height() as f32 * scale).round() as u32).max(1);
+        let resized =
+            image::imageops::resize(&decoded, glyph_width, glyph_height, FilterType::Triangle);
+        let alpha: Vec<u8> = resized.pixels().map(|p| raster_coverage(p.0)).collect();
+
+        // 位图的偏移在各格式间不统一，按字体的上下沿垂直居中，水平居中于前进宽度
+        let scaled_font = font.as_scaled(PxScale::from(base_size));
+        let advance = scaled_font.h_advance(glyph_id);
+        let middle = (scaled_font.ascent() + scaled_font.descent()) / 2.0;
+        let bearing_x =
+            ((advance - glyph_width as f32) / 2.0).floor() as i32 - self.config.buffer as i32;
+        let bearing_y =
+            (middle + glyph_height as f32 / 2.0).floor() as i32 + self.config.buffer as i32;
+
+        self.render(
+            &alpha,
+            glyph_width as usize,
+            glyph_height as usize,
+            bearing_x,
+            bearing_y,
+            advance,
+        )
+    }
+
+    /// 从 alpha 位图生成 SDF
+    fn render(
+        &self,
+        alpha: &[u8],
+        glyph_width: usize,
+        glyph_height: usize,
+        bearing_x: i32,
+        bearing_y: i32,
+        advance: f32,
+    ) -> Option<SdfBitmap> {
         // 创建带 buffer 的 BitmapGlyph
         let bitmap =
-            BitmapGlyph::from_unbuffered(&alpha, glyph_width, glyph_height, self.config.buffer)
+            BitmapGlyph::from_unbuffered(alpha, glyph_width, glyph_height, self.config.buffer)
                 .ok()?;
 
         // 生成 SDF
@@ -141,20 +227,6 @@ impl SdfGenerator {
         let width = (glyph_width + self.config.buffer * 2) as u32;
         let height = (glyph_height + self.config.buffer * 2) as u32;
 
-        // 计算度量信息
-        // bearing_x: 纹理左边缘相对于笔触原点的 X 偏移
-        // bounds.min.x 是字形左边缘，减去 buffer 得到纹理左边缘
-        let bearing_x = bounds.min.x.floor() as i32 - self.config.buffer as i32;
-
-        // bearing_y: 纹理顶边缘相对于基线的 Y 偏移
-        // ab_glyph 的 bounds.min.y 是字形顶部（Y 向下为正，所以 min.y 是顶部）
-        // 需要取负值并加上 buffer
-        // 注意：ab_glyph 的坐标系是 Y 向下，所以 bounds.max.y 是底部，bounds.min.y 是顶部
-        // 但 bounds.min.y 通常是负数（基线以上），所以 -bounds.min.y 是正数
-        let bearing_y = -bounds.min.y.floor() as i32 + self.config.buffer as i32;
-
-        let advance = scaled_font.h_advance(glyph_id);
-
         Some(SdfBitmap {
             data,
             width,
@@ -199,6 +271,15 @@ mod tests {
         assert_eq!(bitmap.data.len(), (bitmap.width * bitmap.height) as usize);
     }
 
+    #[test]
+    fn test_raster_coverage_keeps_dark_details_open() {
+        assert_eq!(raster_coverage([255, 200, 0, 255]), 255);
+        assert_eq!(raster_coverage([255, 200, 0, 128]), 128);
+        // Eyes and outlines of a face emoji
+        assert_eq!(raster_coverage([20, 20, 20, 255]), 0);
+        assert_eq!(raster_coverage([0, 0, 0, 0]), 0);
+    }
+
     #[test]
     fn test_space_returns_none() {
         let font_data = std::fs::read("assets/fonts/Inter-Regular.ttf").unwrap();
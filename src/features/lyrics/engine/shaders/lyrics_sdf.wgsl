@@ -71,6 +71,9 @@ struct GlobalUniforms {
     scroll_y: f32,
     align_position: f32,
     sdf_range: f32,  // SDF distance range in pixels (typically 4.0-8.0)
+    emphasis_amount_scale: f32,  // User multiplier on emphasis glow/scale
+    emphasis_blur_scale: f32,  // User multiplier on emphasis blur
+    _padding: vec2<f32>,
 };
 
 struct LineUniforms {
@@ -135,6 +138,7 @@ fn is_duet(flags: u32) -> bool { return (flags & 8u) != 0u; }
 fn is_translation(flags: u32) -> bool { return (flags & 16u) != 0u; }
 fn is_romanized(flags: u32) -> bool { return (flags & 32u) != 0u; }
 fn is_last_word(flags: u32) -> bool { return (flags & 64u) != 0u; }
+fn is_rtl(flags: u32) -> bool { return (flags & 128u) != 0u; }
 
 // SDF 采样函数（单通道 SDF，R=G=B）
 fn median(r: f32, g: f32, b: f32) -> f32 {
@@ -459,7 +463,7 @@ fn calculate_emphasis_amount(word_duration: f32, last_word: bool) -> f32 {
     amount = select(pow(amount, 3.0), sqrt(amount), amount > 1.0);
     amount = amount * 0.6;
     amount = select(amount, amount * 1.6, last_word);
-    return min(1.2, amount);
+    return min(1.2, amount) * globals.emphasis_amount_scale;
 }
 
 fn calculate_emphasis_blur(word_duration: f32, last_word: bool) -> f32 {
@@ -468,7 +472,7 @@ fn calculate_emphasis_blur(word_duration: f32, last_word: bool) -> f32 {
     blur = select(pow(blur, 3.0), sqrt(blur), blur > 1.0);
     blur = blur * 0.5;
     blur = select(blur, blur * 1.5, last_word);
-    return min(0.8, blur);
+    return min(0.8, blur) * globals.emphasis_blur_scale;
 }
 
 fn calculate_char_emphasis_progress(
@@ -681,7 +685,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let line = lines[in.line_index];
     
     // 计算像素在单词中的位置
-    let pixel_pos_in_word = in.glyph_word_pos.x + in.local_x * in.glyph_word_pos.y;
+    // 从右到左的字形从右边缘开始高亮
+    let local_x = select(in.local_x, 1.0 - in.local_x, is_rtl(in.flags));
+    let pixel_pos_in_word = in.glyph_word_pos.x + local_x * in.glyph_word_pos.y;
     
     // === 换行高亮修复：计算全局视觉位置 ===
     // 当一行歌词换行成多个视觉行时，使用全局视觉位置来计算高亮
@@ -783,7 +789,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 fn fs_main_with_glow(in: VertexOutput) -> @location(0) vec4<f32> {
     let line = lines[in.line_index];
     
-    let pixel_pos_in_word = in.glyph_word_pos.x + in.local_x * in.glyph_word_pos.y;
+    // 从右到左的字形从右边缘开始高亮
+    let local_x = select(in.local_x, 1.0 - in.local_x, is_rtl(in.flags));
+    let pixel_pos_in_word = in.glyph_word_pos.x + local_x * in.glyph_word_pos.y;
     
     // === 换行高亮修复：计算全局视觉位置 ===
     let visual_info = unpack_visual_line_info(in.visual_line_info);
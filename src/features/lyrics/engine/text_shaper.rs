@@ -18,6 +18,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::types::{FontConfig, WordData};
+use crate::i18n::Direction;
 
 /// Cache key for shaped lines
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -69,6 +70,21 @@ pub struct ShapedLine {
     pub ascent: f32,
     /// Word boundaries (start_x, end_x) for each word
     pub word_bounds: Vec<(f32, f32)>,
+    /// 段落方向（按首个强方向字符判定，阿拉伯语/希伯来语为从右到左）
+    pub direction: Direction,
+}
+
+/// 从右到左的段落由 cosmic-text 靠右排在 max_width 内，平移到从 0 开始，
+/// 行的对齐交给渲染端处理。返回平移量
+fn shift_to_origin(glyphs: &mut [ShapedGlyph]) -> f32 {
+    let offset = glyphs.iter().map(|g| g.x).fold(f32::MAX, f32::min);
+    if glyphs.is_empty() || offset <= 0.0 {
+        return 0.0;
+    }
+    for glyph in glyphs {
+        glyph.x -= offset;
+    }
+    offset
 }
 
 /// Text shaper using cosmic-text
@@ -143,6 +159,7 @@ impl TextShaper {
                 height: font_size * 1.4,
                 ascent: font_size,
                 word_bounds: Vec::new(),
+                direction: Direction::LeftToRight,
             };
         }
 
@@ -208,12 +225,16 @@ impl TextShaper {
         let single_line_height = font_size * 1.4;
         let mut first_line_ascent = font_size;
         let mut first_run = true;
+        let mut direction = Direction::LeftToRight;
 
         for run in buffer.layout_runs() {
             // First line's ascent
             if first_run {
                 first_line_ascent = run.line_y;
                 first_run = false;
+                if run.rtl {
+                    direction = Direction::RightToLeft;
+                }
             }
 
             for glyph in run.glyphs.iter() {
@@ -264,6 +285,17 @@ impl TextShaper {
             }
         }
 
+        if direction == Direction::RightToLeft {
+            let offset = shift_to_origin(&mut shaped_glyphs);
+            for (min_x, max_x) in &mut word_bounds {
+                if *min_x != f32::MAX {
+                    *min_x -= offset;
+                    *max_x -= offset;
+                }
+            }
+            total_width -= offset;
+        }
+
         // Fix word bounds for words with no glyphs
         for i in 0..word_bounds.len() {
             if word_bounds[i].0 == f32::MAX {
@@ -325,9 +357,13 @@ impl TextShaper {
 
                     // Calculate position within visual line (0.0 = start, 1.0 = end)
                     // Use the center of the glyph for position calculation
+                    // 从右到左的行以右边缘为起点
                     let glyph_center_x = glyph.x + glyph.advance * 0.5;
-                    glyph.pos_in_visual_line =
-                        ((glyph_center_x - line_min_x) / line_width).clamp(0.0, 1.0);
+                    let from_start = match direction {
+                        Direction::LeftToRight => glyph_center_x - line_min_x,
+                        Direction::RightToLeft => line_max_x - glyph_center_x,
+                    };
+                    glyph.pos_in_visual_line = (from_start / line_width).clamp(0.0, 1.0);
                 }
             }
         }
@@ -342,6 +378,7 @@ impl TextShaper {
             height: final_height,
             ascent: first_line_ascent,
             word_bounds,
+            direction,
         }
     }
 
@@ -391,6 +428,7 @@ impl TextShaper {
                 height: font_size * 1.3,
                 ascent: font_size,
                 word_bounds: Vec::new(),
+                direction: Direction::LeftToRight,
             };
         }
 
@@ -439,9 +477,13 @@ impl TextShaper {
         let mut shaped_glyphs = Vec::new();
         let mut total_width = 0.0f32;
         let mut ascent = font_size;
+        let mut direction = Direction::LeftToRight;
 
         for run in buffer.layout_runs() {
             ascent = run.line_y;
+            if run.rtl && shaped_glyphs.is_empty() {
+                direction = Direction::RightToLeft;
+            }
 
             for glyph in run.glyphs.iter() {
                 let cache_key = glyph.physical((0.0, 0.0), 1.0).cache_key;
@@ -464,6 +506,10 @@ impl TextShaper {
             }
         }
 
+        if direction == Direction::RightToLeft {
+            total_width -= shift_to_origin(&mut shaped_glyphs);
+        }
+
         // Calculate total height based on visual line count (same as shape_line_uncached)
         let final_height = single_line_height * visual_line_count as f32;
 
@@ -473,6 +519,7 @@ impl TextShaper {
             height: final_height,
             ascent,
             word_bounds: vec![(0.0, total_width)],
+            direction,
         }
     }
 
@@ -650,6 +697,27 @@ mod tests {
         );
     }
 
+    /// Right-to-left lines are detected and start at x = 0
+    #[test]
+    fn test_rtl_line_direction() {
+        let font_system = Arc::new(Mutex::new(FontSystem::new()));
+        let shaper = TextShaper::with_config(font_system, FontConfig::default());
+
+        let shaped = shaper.shape_simple("שלום עולם", 24.0, 800.0);
+        assert_eq!(shaped.direction, Direction::RightToLeft);
+        if let Some(min_x) = shaped.glyphs.iter().map(|g| g.x).reduce(f32::min) {
+            assert!(
+                min_x.abs() < 0.01,
+                "RTL glyphs should start at 0, got {}",
+                min_x
+            );
+            assert!(shaped.width < 800.0);
+        }
+
+        let shaped = shaper.shape_simple("Hello", 24.0, 800.0);
+        assert_eq!(shaped.direction, Direction::LeftToRight);
+    }
+
     /// Test font weight configuration
     #[test]
     fn test_font_weight_configuration() {
@@ -55,7 +55,7 @@ pub struct LyricGlyphVertex {
     /// Line index (for per-line effects)
     pub line_index: u32,
     /// Flags: bit 0 = is_active, bit 1 = emphasize, bit 2 = is_bg, bit 3 = is_duet
-    ///        bit 4 = is_translation, bit 5 = is_romanized
+    ///        bit 4 = is_translation, bit 5 = is_romanized, bit 7 = is_rtl
     pub flags: u32,
 
     // === Visual properties (8 bytes) ===
@@ -261,6 +261,15 @@ impl LyricGlyphVertex {
             self.flags &= !32;
         }
     }
+
+    /// Set right-to-left flag (highlight sweeps from the right edge)
+    pub fn set_rtl(&mut self, is_rtl: bool) {
+        if is_rtl {
+            self.flags |= 128;
+        } else {
+            self.flags &= !128;
+        }
+    }
 }
 
 impl Default for LyricGlyphVertex {
@@ -328,6 +337,12 @@ pub struct GlobalUniform {
     pub align_position: f32,
     /// SDF distance range in pixels (typically 4.0-8.0, used for distance extrapolation)
     pub sdf_range: f32,
+    /// Multiplier on emphasis glow/scale (1.0 default)
+    pub emphasis_amount_scale: f32,
+    /// Multiplier on emphasis blur (1.0 default)
+    pub emphasis_blur_scale: f32,
+    /// Padding to 16-byte alignment
+    pub _padding: [f32; 2],
 }
 
 impl Default for GlobalUniform {
@@ -342,6 +357,9 @@ impl Default for GlobalUniform {
             scroll_y: 0.0,
             align_position: 0.35,
             sdf_range: 4.0,
+            emphasis_amount_scale: 1.0,
+            emphasis_blur_scale: 1.0,
+            _padding: [0.0; 2],
         }
     }
 }
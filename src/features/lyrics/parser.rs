@@ -19,6 +19,7 @@ mod ttml;
 mod types;
 mod yrc;
 
+pub use lrc::stringify_lrc;
 pub use online::*;
 pub use types::*;
 
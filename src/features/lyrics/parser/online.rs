@@ -10,10 +10,7 @@ use crate::api::NcmClient;
 
 /// Lyrics cache directory
 fn lyrics_cache_dir() -> PathBuf {
-    dirs::cache_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("rustle")
-        .join("lyrics")
+    crate::utils::cache_dir().join("lyrics")
 }
 
 /// Get cached lyrics file path for a song
@@ -96,6 +93,8 @@ pub async fn fetch_and_cache_lyrics(
         duration: 0,
         song_url: String::new(),
         copyright: crate::api::SongCopyright::Unknown,
+        popularity: 0.0,
+        hires: false,
     };
 
     // Use the existing get_lyrics method which handles caching
@@ -0,0 +1,173 @@
+//! Saving fetched lyrics into local files
+//!
+//! Lyrics Rustle finds for a library song online, in the lyrics cache or
+//! through a plugin are only shown in Rustle. Saving them into the song's
+//! own files lets other players show them too. By default they go in a
+//! `.lrc` sidecar next to the song, which leaves the song itself alone.
+//! Embedded lyrics go in the file's usual lyrics tag, USLT for MP3, the
+//! LYRICS comment for FLAC and Ogg, `©lyr` for M4A, as LRC text: players
+//! that understand timestamps keep them in sync and the rest show the lines
+//! with times in front. MP3 also gets a SYLT frame with the timed lines.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::jobs::{Category, Job};
+use super::lyrics::{self, LyricLineOwned};
+use super::media::tags::{self, TagUpdate};
+use super::plugins::{PluginRegistry, TrackQuery};
+use crate::api::NcmClient;
+use crate::database::DbSong;
+use crate::i18n::Key;
+
+/// Where saved lyrics go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LyricsSaveTarget {
+    /// A `.lrc` file next to the audio file
+    #[default]
+    Sidecar,
+    /// Into the audio file's tags
+    Embed,
+}
+
+impl LyricsSaveTarget {
+    pub const ALL: [Self; 2] = [Self::Sidecar, Self::Embed];
+
+    pub fn label_key(self) -> Key {
+        match self {
+            Self::Embed => Key::LyricsSaveEmbed,
+            Self::Sidecar => Key::LyricsSaveSidecar,
+        }
+    }
+}
+
+/// A library song to save lyrics for
+#[derive(Debug, Clone)]
+pub struct SongLyrics {
+    pub path: PathBuf,
+    /// NCM track the song is linked to
+    pub ncm_id: Option<u64>,
+    /// Details for lyrics plugins
+    pub query: TrackQuery,
+}
+
+impl SongLyrics {
+    pub fn new(song: &DbSong, ncm_id: Option<u64>) -> Self {
+        Self {
+            path: PathBuf::from(&song.file_path),
+            ncm_id,
+            query: TrackQuery {
+                title: song.title.clone(),
+                artist: song.artist.clone(),
+                album: song.album.clone(),
+                duration_secs: song.duration_secs,
+                path: Some(song.file_path.clone()),
+            },
+        }
+    }
+}
+
+/// Whether the file has lyrics of its own, embedded or next to it
+///
+/// Blocking, call off the UI thread.
+pub fn has_own_lyrics(audio_path: &Path) -> bool {
+    super::media::find_lyrics(audio_path).is_some()
+}
+
+/// Lyrics from the sources Rustle shows after the file itself: NCM for a
+/// song linked to one, lyrics plugins otherwise
+async fn find_elsewhere(
+    song: &SongLyrics,
+    client: Option<&NcmClient>,
+    plugins: Arc<PluginRegistry>,
+) -> Option<Vec<LyricLineOwned>> {
+    if let Some(ncm_id) = song.ncm_id {
+        if let Some(cached) = lyrics::load_cached_lyrics(ncm_id) {
+            return Some(cached);
+        }
+        return lyrics::fetch_lyrics(client?, ncm_id).await.ok();
+    }
+
+    let query = song.query.clone();
+    tokio::task::spawn_blocking(move || {
+        let content = plugins.lookup_lyrics(&query)?;
+        let lines = lyrics::parse_lyrics(&content);
+        (!lines.is_empty()).then_some(lines)
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Find lyrics for a song that has none of its own and save them
+///
+/// Returns the file written, or `None` when the song already has lyrics or
+/// none were found.
+pub async fn save_for_song(
+    song: SongLyrics,
+    client: Option<NcmClient>,
+    plugins: Arc<PluginRegistry>,
+    target: LyricsSaveTarget,
+) -> Result<Option<PathBuf>> {
+    let path = song.path.clone();
+    let has_own = Job::new(Category::Disk)
+        .run_blocking(move || has_own_lyrics(&path))
+        .await
+        .unwrap_or(true);
+    if has_own {
+        return Ok(None);
+    }
+    let Some(lines) = find_elsewhere(&song, client.as_ref(), plugins).await else {
+        return Ok(None);
+    };
+
+    Job::new(Category::Disk)
+        .run_blocking(move || save(&song.path, &lines, target))
+        .await
+        .context("Saving lyrics panicked")?
+        .map(Some)
+}
+
+/// Save `lines` for the song at `audio_path`, returning the file written
+///
+/// A sidecar never replaces an existing `.lrc` file. Blocking, call off the
+/// UI thread.
+fn save(audio_path: &Path, lines: &[LyricLineOwned], target: LyricsSaveTarget) -> Result<PathBuf> {
+    let lrc = lyrics::stringify_lrc(lines);
+    if lrc.trim().is_empty() {
+        bail!("No timed lyrics to save");
+    }
+
+    match target {
+        LyricsSaveTarget::Embed => {
+            // Timed like the LRC text, from each line's first word
+            let synced = lines
+                .iter()
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    let start_ms = line.words[0].start_time.min(u32::MAX as u64) as u32;
+                    (start_ms, line.to_line())
+                })
+                .collect();
+            let update = TagUpdate {
+                lyrics: Some(lrc),
+                synced_lyrics: Some(synced),
+                ..Default::default()
+            };
+            tags::write_tags(audio_path, &update)?;
+            Ok(audio_path.to_path_buf())
+        }
+        LyricsSaveTarget::Sidecar => {
+            let path = audio_path.with_extension("lrc");
+            if path.exists() {
+                bail!("{} already exists", path.display());
+            }
+            std::fs::write(&path, lrc)?;
+            Ok(path)
+        }
+    }
+}
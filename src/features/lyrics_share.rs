@@ -0,0 +1,282 @@
+//! Sharing lyrics fixes on LRCLIB
+//!
+//! A local song's own `.lrc` file is where its lyrics get fixed, so that file
+//! is what gets shared, along with the tags and duration LRCLIB matches
+//! lyrics by. Edits are noticed by hashing the file: the hash is remembered
+//! once the lyrics are shared or passed on, and a different one later means
+//! the file was changed again.
+//!
+//! LRCLIB has no accounts. Publishing takes a token earned by proof of work,
+//! a nonce that brings the SHA-256 of the challenge prefix under its target,
+//! and credit goes into the lyrics themselves as the standard `[by:]` tag.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const API: &str = "https://lrclib.net/api";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Lyrics ready to publish, with the track they belong to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submission {
+    pub track_name: String,
+    pub artist_name: String,
+    pub album_name: String,
+    pub duration_secs: u64,
+    /// The LRC file as it is on disk
+    pub lrc: String,
+    /// Name for the `[by:]` tag; `None` shares without adding one
+    pub credit: Option<String>,
+}
+
+impl Submission {
+    /// Synced lyrics as they will be published, empty when the file has no
+    /// timestamps
+    pub fn synced_lyrics(&self) -> String {
+        if has_timestamps(&self.lrc) {
+            with_credit(&self.lrc, self.credit.as_deref())
+        } else {
+            String::new()
+        }
+    }
+
+    pub fn plain_lyrics(&self) -> String {
+        plain_lyrics(&self.lrc)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PublishRequest<'a> {
+    track_name: &'a str,
+    artist_name: &'a str,
+    album_name: &'a str,
+    duration: u64,
+    plain_lyrics: String,
+    synced_lyrics: String,
+}
+
+#[derive(Deserialize)]
+struct Challenge {
+    prefix: String,
+    target: String,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+/// The song's own LRC file, the only lyrics format LRCLIB takes as is
+pub fn lrc_path(audio_path: &Path) -> Option<PathBuf> {
+    super::media::lyrics::get_lyrics_path(audio_path).filter(|path| {
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("lrc"))
+    })
+}
+
+/// Hash telling one version of a lyrics file from another
+pub fn content_hash(lrc: &str) -> String {
+    format!("{:x}", md5::compute(lrc))
+}
+
+/// Publish lyrics to LRCLIB
+///
+/// Solving the challenge keeps a core busy for a while, so it runs on the
+/// blocking pool.
+pub async fn publish(submission: &Submission, proxy: Option<String>) -> Result<()> {
+    let client = crate::api::http::client(proxy.as_deref())?;
+    let challenge: Challenge = client
+        .post(format!("{}/request-challenge", API))
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let target = hex::decode(&challenge.target).context("Invalid challenge target")?;
+    let prefix = challenge.prefix.clone();
+    let nonce = tokio::task::spawn_blocking(move || solve(&prefix, &target)).await?;
+
+    let response = client
+        .post(format!("{}/publish", API))
+        .header("X-Publish-Token", format!("{}:{}", challenge.prefix, nonce))
+        .json(&PublishRequest {
+            track_name: &submission.track_name,
+            artist_name: &submission.artist_name,
+            album_name: &submission.album_name,
+            duration: submission.duration_secs,
+            plain_lyrics: submission.plain_lyrics(),
+            synced_lyrics: submission.synced_lyrics(),
+        })
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let message = response
+            .json::<ApiError>()
+            .await
+            .map(|e| e.message)
+            .unwrap_or_else(|_| status.to_string());
+        bail!(message);
+    }
+    Ok(())
+}
+
+/// First nonce whose hash with `prefix` meets `target`
+fn solve(prefix: &str, target: &[u8]) -> u64 {
+    let mut nonce = 0u64;
+    while !meets_target(&Sha256::digest(format!("{}{}", prefix, nonce)), target) {
+        nonce += 1;
+    }
+    nonce
+}
+
+/// Whether a hash is at most the target, both read as big-endian numbers
+fn meets_target(hash: &[u8], target: &[u8]) -> bool {
+    hash <= target
+}
+
+/// The lyrics without timestamps or tags, as LRCLIB's plain lyrics
+pub fn plain_lyrics(lrc: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in lrc.lines() {
+        let (tags, text) = split_tags(line);
+        // Metadata such as [ar:] or [offset:]
+        if !tags.is_empty() && !tags.iter().any(|tag| is_timestamp(tag)) {
+            continue;
+        }
+        let text = strip_word_times(text);
+        // One blank line between verses at most
+        if text.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(text);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// The LRC credited to `credit` in place of any `[by:]` tag it had;
+/// without a credit it's left as it is
+pub fn with_credit(lrc: &str, credit: Option<&str>) -> String {
+    let Some(credit) = credit.map(str::trim).filter(|c| !c.is_empty()) else {
+        return lrc.to_string();
+    };
+    std::iter::once(format!("[by:{}]", credit))
+        .chain(
+            lrc.lines()
+                .filter(|line| !is_credit_line(line))
+                .map(str::to_string),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn has_timestamps(lrc: &str) -> bool {
+    lrc.lines()
+        .any(|line| split_tags(line).0.iter().any(|tag| is_timestamp(tag)))
+}
+
+fn is_credit_line(line: &str) -> bool {
+    let (tags, text) = split_tags(line);
+    text.is_empty()
+        && tags.len() == 1
+        && tags[0].trim_start().to_ascii_lowercase().starts_with("by:")
+}
+
+/// Split the leading `[...]` tags off a line
+fn split_tags(line: &str) -> (Vec<&str>, &str) {
+    let mut tags = Vec::new();
+    let mut rest = line.trim();
+    while let Some(inner) = rest.strip_prefix('[') {
+        let Some(end) = inner.find(']') else {
+            break;
+        };
+        tags.push(&inner[..end]);
+        rest = inner[end + 1..].trim_start();
+    }
+    (tags, rest)
+}
+
+/// `mm:ss.xx` as opposed to metadata like `ar:Someone`
+fn is_timestamp(tag: &str) -> bool {
+    tag.starts_with(|c: char| c.is_ascii_digit()) && tag.contains(':')
+}
+
+/// Drop the `<mm:ss.xx>` word timings of enhanced LRC
+fn strip_word_times(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        match rest[start + 1..].find('>') {
+            Some(len) if is_timestamp(&rest[start + 1..start + 1 + len]) => {
+                out.push_str(&rest[..start]);
+                rest = &rest[start + len + 2..];
+            }
+            _ => {
+                out.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LRC: &str = "[ar:Someone]\n[by:Old]\n[00:01.00]First line\n[00:04.00]\n[00:05.00]<00:05.00>Second <00:05.50>line\n";
+
+    #[test]
+    fn plain_lyrics_drop_tags_and_timings() {
+        assert_eq!(plain_lyrics(LRC), "First line\n\nSecond line");
+        assert_eq!(plain_lyrics("Just text\n\n\nMore\n"), "Just text\n\nMore");
+    }
+
+    #[test]
+    fn credit_replaces_the_by_tag() {
+        let credited = with_credit(LRC, Some(" Me "));
+        assert!(credited.starts_with("[by:Me]\n[ar:Someone]\n[00:01.00]"));
+        assert!(!credited.contains("[by:Old]"));
+        assert_eq!(with_credit(LRC, None), LRC);
+        assert_eq!(with_credit(LRC, Some("")), LRC);
+    }
+
+    #[test]
+    fn untimed_files_have_no_synced_lyrics() {
+        let submission = Submission {
+            track_name: "Song".to_string(),
+            artist_name: "Artist".to_string(),
+            album_name: String::new(),
+            duration_secs: 200,
+            lrc: "[ar:Artist]\nJust text".to_string(),
+            credit: Some("Me".to_string()),
+        };
+        assert_eq!(submission.synced_lyrics(), "");
+        assert_eq!(submission.plain_lyrics(), "Just text");
+    }
+
+    #[test]
+    fn solved_nonce_meets_the_target() {
+        let mut target = vec![0xff; 32];
+        target[0] = 0x0f;
+        let nonce = solve("prefix", &target);
+        let hash = Sha256::digest(format!("prefix{}", nonce));
+        assert!(meets_target(&hash, &target));
+        assert!(!meets_target(&[0x10; 32], &target));
+        assert_eq!(solve("prefix", &[0xff; 32]), 0);
+    }
+}
@@ -10,8 +10,12 @@ use std::path::{Path, PathBuf};
 
 use super::lyrics::LyricLineOwned;
 
+pub mod chapters;
 pub mod cover;
 pub mod lyrics;
+pub mod mosaic;
+pub mod palette;
+pub mod tags;
 
 pub use cover::CoverArtSource;
 
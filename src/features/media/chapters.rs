@@ -0,0 +1,542 @@
+//! Chapter markers embedded in audio files
+//!
+//! Podcasts and audiobooks mark chapters with ID3v2 `CHAP` frames (MP3) or,
+//! in MP4/M4B files, with a Nero `chpl` atom or a QuickTime chapter text
+//! track. Only the start time and title of each chapter are kept.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+/// Largest `moov` atom read into memory
+const MAX_MOOV_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Going back within this many milliseconds of a chapter start skips to the
+/// chapter before it instead of restarting the current one
+const RESTART_THRESHOLD_MS: u64 = 3000;
+
+/// A chapter within a track
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    /// Start offset in milliseconds
+    pub start_ms: u64,
+    /// Chapter title, empty if the file gives none
+    pub title: String,
+}
+
+/// Read the chapters of an audio file, sorted by start time
+///
+/// Returns nothing for files without at least two chapters.
+pub fn read_chapters(path: &Path) -> Vec<Chapter> {
+    let mut chapters = match read_raw(path) {
+        Ok(chapters) => chapters,
+        Err(e) => {
+            tracing::debug!("No chapters read from {:?}: {}", path, e);
+            return Vec::new();
+        }
+    };
+
+    chapters.sort_by_key(|c| c.start_ms);
+    chapters.dedup_by_key(|c| c.start_ms);
+    if chapters.len() < 2 {
+        chapters.clear();
+    }
+    chapters
+}
+
+/// Index of the chapter playing at `position_ms`
+pub fn chapter_at(chapters: &[Chapter], position_ms: u64) -> Option<usize> {
+    chapters.iter().rposition(|c| c.start_ms <= position_ms)
+}
+
+/// Chapter that "previous chapter" jumps to from `position_ms`
+///
+/// Restarts the current chapter unless playback is near its start.
+pub fn previous_chapter(chapters: &[Chapter], position_ms: u64) -> Option<usize> {
+    let current = chapter_at(chapters, position_ms)?;
+    if position_ms - chapters[current].start_ms > RESTART_THRESHOLD_MS || current == 0 {
+        Some(current)
+    } else {
+        Some(current - 1)
+    }
+}
+
+/// Chapter that "next chapter" jumps to from `position_ms`
+pub fn next_chapter(chapters: &[Chapter], position_ms: u64) -> Option<usize> {
+    chapters.iter().position(|c| c.start_ms > position_ms)
+}
+
+fn read_raw(path: &Path) -> Result<Vec<Chapter>> {
+    let mut file = File::open(path)?;
+    let mut header = [0u8; 10];
+    file.read_exact(&mut header)?;
+
+    if &header[..3] == b"ID3" {
+        let size = syncsafe(&header[6..10]) as usize;
+        let mut tag = vec![0u8; size];
+        file.read_exact(&mut tag)?;
+        return Ok(parse_id3(&tag, header[3], header[5]));
+    }
+    if &header[4..8] == b"ftyp" {
+        return read_mp4(&mut file);
+    }
+    bail!("unsupported container")
+}
+
+// ============ ID3v2 ============
+
+/// Parse `CHAP` frames from an ID3v2 tag body (everything after the header)
+fn parse_id3(tag: &[u8], major: u8, flags: u8) -> Vec<Chapter> {
+    // v2.2 uses three-letter frame IDs and has no chapter frame
+    if !(3..=4).contains(&major) {
+        return Vec::new();
+    }
+
+    let tag = if flags & 0x80 != 0 {
+        resync(tag)
+    } else {
+        tag.to_vec()
+    };
+
+    let mut pos = 0;
+    if flags & 0x40 != 0 && tag.len() >= 4 {
+        // Extended header: v2.3 size excludes its own four bytes
+        pos = if major == 4 {
+            syncsafe(&tag[..4]) as usize
+        } else {
+            4 + be_u32(&tag[..4]) as usize
+        };
+    }
+
+    id3_frames(tag.get(pos..).unwrap_or_default(), major)
+        .filter(|(id, _)| id == b"CHAP")
+        .filter_map(|(_, body)| parse_chap(body, major))
+        .collect()
+}
+
+/// Iterate the frames in `data` as (frame ID, body)
+fn id3_frames(data: &[u8], major: u8) -> impl Iterator<Item = ([u8; 4], &[u8])> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let header = data.get(pos..pos + 10)?;
+        // Padding after the last frame
+        if header[0] == 0 {
+            return None;
+        }
+        let id = [header[0], header[1], header[2], header[3]];
+        let size = if major == 4 {
+            syncsafe(&header[4..8])
+        } else {
+            be_u32(&header[4..8])
+        } as usize;
+        let body = data.get(pos + 10..pos + 10 + size)?;
+        pos += 10 + size;
+        Some((id, body))
+    })
+}
+
+/// Parse one `CHAP` frame: element ID, times, then embedded frames
+fn parse_chap(body: &[u8], major: u8) -> Option<Chapter> {
+    let id_end = body.iter().position(|&b| b == 0)?;
+    let times = body.get(id_end + 1..id_end + 17)?;
+    let start_ms = be_u32(&times[..4]) as u64;
+
+    let title = id3_frames(&body[id_end + 17..], major)
+        .find(|(id, _)| id == b"TIT2")
+        .map(|(_, text)| decode_id3_text(text))
+        .unwrap_or_default();
+
+    Some(Chapter { start_ms, title })
+}
+
+/// Decode an ID3v2 text frame body (encoding byte followed by text)
+fn decode_id3_text(body: &[u8]) -> String {
+    let Some((&encoding, text)) = body.split_first() else {
+        return String::new();
+    };
+    let decoded = match encoding {
+        0 => text.iter().map(|&b| b as char).collect(),
+        1 => match text {
+            [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+            [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+            _ => decode_utf16(text, u16::from_le_bytes),
+        },
+        2 => decode_utf16(text, u16::from_be_bytes),
+        _ => String::from_utf8_lossy(text).into_owned(),
+    };
+    decoded.trim_end_matches('\0').trim().to_string()
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Undo ID3v2 unsynchronisation (every `FF 00` was written for `FF`)
+fn resync(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut prev = 0u8;
+    for &b in data {
+        if !(prev == 0xFF && b == 0) {
+            out.push(b);
+        }
+        prev = b;
+    }
+    out
+}
+
+fn syncsafe(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0, |acc, &b| (acc << 7) | (b & 0x7F) as u32)
+}
+
+// ============ MP4 ============
+
+fn read_mp4(file: &mut File) -> Result<Vec<Chapter>> {
+    let moov = read_moov(file)?;
+
+    if let Some(chpl) = find_atom(&moov, &[b"udta", b"chpl"]) {
+        let chapters = parse_chpl(chpl);
+        if !chapters.is_empty() {
+            return Ok(chapters);
+        }
+    }
+    read_chapter_track(file, &moov)
+}
+
+/// Find the top-level `moov` atom and read its body
+fn read_moov(file: &mut File) -> Result<Vec<u8>> {
+    let file_len = file.metadata()?.len();
+    let mut pos = 0u64;
+
+    while pos + 8 <= file_len {
+        file.seek(SeekFrom::Start(pos))?;
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header)?;
+
+        let (mut size, mut header_len) = (be_u32(&header[..4]) as u64, 8u64);
+        if size == 1 {
+            let mut large = [0u8; 8];
+            file.read_exact(&mut large)?;
+            size = u64::from_be_bytes(large);
+            header_len = 16;
+        } else if size == 0 {
+            size = file_len - pos;
+        }
+        if size < header_len {
+            bail!("malformed atom");
+        }
+
+        if &header[4..8] == b"moov" {
+            let body_len = size - header_len;
+            if body_len > MAX_MOOV_SIZE {
+                bail!("moov atom too large");
+            }
+            let mut body = vec![0u8; body_len as usize];
+            file.read_exact(&mut body)?;
+            return Ok(body);
+        }
+        pos += size;
+    }
+    bail!("no moov atom")
+}
+
+/// Iterate the child atoms in `data` as (type, body)
+fn atoms(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let header = data.get(pos..pos + 8)?;
+        let (size, header_len) = match be_u32(&header[..4]) {
+            0 => (data.len() - pos, 8),
+            1 => (
+                u64::from_be_bytes(data.get(pos + 8..pos + 16)?.try_into().ok()?) as usize,
+                16,
+            ),
+            size => (size as usize, 8),
+        };
+        if size < header_len {
+            return None;
+        }
+        let body = data.get(pos + header_len..pos + size)?;
+        let kind = &header[4..8];
+        pos += size;
+        Some((kind, body))
+    })
+}
+
+/// Follow a path of atom types down from `data`
+fn find_atom<'a>(data: &'a [u8], path: &[&[u8; 4]]) -> Option<&'a [u8]> {
+    path.iter().try_fold(data, |data, kind| {
+        atoms(data).find(|(k, _)| k == kind).map(|(_, body)| body)
+    })
+}
+
+/// Parse a Nero `chpl` atom (start times in 100 ns units)
+fn parse_chpl(body: &[u8]) -> Vec<Chapter> {
+    let Some(&version) = body.first() else {
+        return Vec::new();
+    };
+    let mut pos = if version > 0 { 8 } else { 4 };
+    let Some(&count) = body.get(pos) else {
+        return Vec::new();
+    };
+    pos += 1;
+
+    let mut chapters = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let Some(start) = body.get(pos..pos + 8) else {
+            break;
+        };
+        let start_ms = u64::from_be_bytes(start.try_into().unwrap_or_default()) / 10_000;
+        let Some(&len) = body.get(pos + 8) else {
+            break;
+        };
+        let Some(title) = body.get(pos + 9..pos + 9 + len as usize) else {
+            break;
+        };
+        chapters.push(Chapter {
+            start_ms,
+            title: String::from_utf8_lossy(title).trim().to_string(),
+        });
+        pos += 9 + len as usize;
+    }
+    chapters
+}
+
+/// Read chapters from the QuickTime text track referenced by `tref/chap`
+fn read_chapter_track(file: &mut File, moov: &[u8]) -> Result<Vec<Chapter>> {
+    let traks: Vec<&[u8]> = atoms(moov)
+        .filter(|(kind, _)| *kind == b"trak")
+        .map(|(_, body)| body)
+        .collect();
+
+    let Some(chapter_id) = traks
+        .iter()
+        .find_map(|trak| find_atom(trak, &[b"tref", b"chap"]))
+        .and_then(|chap| chap.get(..4))
+        .map(be_u32)
+    else {
+        bail!("no chapters");
+    };
+    let Some(trak) = traks.iter().find(|trak| track_id(trak) == Some(chapter_id)) else {
+        bail!("chapter track {} missing", chapter_id);
+    };
+
+    let mdhd = find_atom(trak, &[b"mdia", b"mdhd"]).unwrap_or_default();
+    let timescale = match mdhd.first() {
+        Some(1) => mdhd.get(20..24),
+        _ => mdhd.get(12..16),
+    }
+    .map(be_u32)
+    .filter(|&t| t > 0);
+    let Some(timescale) = timescale else {
+        bail!("chapter track has no timescale");
+    };
+
+    let stbl = find_atom(trak, &[b"mdia", b"minf", b"stbl"]).unwrap_or_default();
+    let starts = sample_starts(stbl);
+    let offsets = sample_offsets(stbl);
+
+    let mut chapters = Vec::with_capacity(starts.len());
+    for (start, (offset, size)) in starts.into_iter().zip(offsets) {
+        let mut sample = vec![0u8; size.min(1024) as usize];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut sample)?;
+        chapters.push(Chapter {
+            start_ms: start * 1000 / timescale as u64,
+            title: decode_text_sample(&sample),
+        });
+    }
+    Ok(chapters)
+}
+
+fn track_id(trak: &[u8]) -> Option<u32> {
+    let tkhd = find_atom(trak, &[b"tkhd"])?;
+    match tkhd.first()? {
+        1 => tkhd.get(20..24),
+        _ => tkhd.get(12..16),
+    }
+    .map(be_u32)
+}
+
+/// Start of each sample in track time units, from `stts`
+fn sample_starts(stbl: &[u8]) -> Vec<u64> {
+    let mut starts = Vec::new();
+    let mut time = 0u64;
+    for entry in table(stbl, b"stts", 8) {
+        let (count, delta) = (be_u32(&entry[..4]), be_u32(&entry[4..8]) as u64);
+        for _ in 0..count {
+            starts.push(time);
+            time += delta;
+        }
+    }
+    starts
+}
+
+/// File offset and size of each sample, from `stsz`, `stsc` and `stco`/`co64`
+fn sample_offsets(stbl: &[u8]) -> Vec<(u64, u64)> {
+    let sizes: Vec<u64> = match find_atom(stbl, &[b"stsz"]) {
+        Some(stsz) if stsz.len() >= 12 => {
+            let fixed = be_u32(&stsz[4..8]) as u64;
+            let count = be_u32(&stsz[8..12]) as usize;
+            if fixed > 0 {
+                vec![fixed; count]
+            } else {
+                stsz[12..]
+                    .chunks_exact(4)
+                    .take(count)
+                    .map(|s| be_u32(s) as u64)
+                    .collect()
+            }
+        }
+        _ => return Vec::new(),
+    };
+
+    let mut chunks: Vec<u64> = table(stbl, b"stco", 4).map(|c| be_u32(c) as u64).collect();
+    if chunks.is_empty() {
+        chunks = table(stbl, b"co64", 8)
+            .map(|c| u64::from_be_bytes(c.try_into().unwrap_or_default()))
+            .collect();
+    }
+    let runs: Vec<(usize, usize)> = table(stbl, b"stsc", 12)
+        .map(|e| (be_u32(&e[..4]) as usize, be_u32(&e[4..8]) as usize))
+        .collect();
+
+    let mut samples = sizes.into_iter();
+    let mut offsets = Vec::new();
+    for (chunk_idx, &chunk_offset) in chunks.iter().enumerate() {
+        // stsc chunk numbers are 1-based
+        let per_chunk = runs
+            .iter()
+            .rev()
+            .find(|(first, _)| *first <= chunk_idx + 1)
+            .map_or(1, |(_, n)| *n);
+        let mut offset = chunk_offset;
+        for size in samples.by_ref().take(per_chunk) {
+            offsets.push((offset, size));
+            offset += size;
+        }
+    }
+    offsets
+}
+
+/// Entries of a full-box sample table (version/flags, count, then entries)
+fn table<'a>(stbl: &'a [u8], kind: &[u8; 4], entry_len: usize) -> impl Iterator<Item = &'a [u8]> {
+    let body = find_atom(stbl, &[kind]).unwrap_or_default();
+    let count = body.get(4..8).map_or(0, be_u32) as usize;
+    body.get(8..)
+        .unwrap_or_default()
+        .chunks_exact(entry_len)
+        .take(count)
+}
+
+/// Decode a QuickTime text sample (length-prefixed UTF-8 or UTF-16)
+fn decode_text_sample(sample: &[u8]) -> String {
+    let len = sample
+        .get(..2)
+        .map_or(0, |l| u16::from_be_bytes([l[0], l[1]])) as usize;
+    let text = sample.get(2..2 + len).unwrap_or_default();
+    let decoded = match text {
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        _ => String::from_utf8_lossy(text).into_owned(),
+    };
+    decoded.trim().to_string()
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(start_ms: u64, title: &str) -> Chapter {
+        Chapter {
+            start_ms,
+            title: title.to_string(),
+        }
+    }
+
+    fn id3_frame(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut frame = id.to_vec();
+        frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(body);
+        frame
+    }
+
+    fn chap_frame(element: &str, start_ms: u32, title: &[u8]) -> Vec<u8> {
+        let mut body = element.as_bytes().to_vec();
+        body.push(0);
+        body.extend_from_slice(&start_ms.to_be_bytes());
+        body.extend_from_slice(&(start_ms + 1000).to_be_bytes());
+        body.extend_from_slice(&[0xFF; 8]);
+        body.extend(id3_frame(b"TIT2", title));
+        id3_frame(b"CHAP", &body)
+    }
+
+    #[test]
+    fn reads_id3_chapter_frames() {
+        let mut tag = id3_frame(b"TIT2", b"\x03Episode");
+        tag.extend(chap_frame("ch0", 0, b"\x03Intro"));
+        tag.extend(chap_frame("ch1", 65_000, b"\x01\xFF\xFEN\0e\0w\0s\0"));
+        tag.extend([0; 16]);
+
+        assert_eq!(
+            parse_id3(&tag, 3, 0),
+            [chapter(0, "Intro"), chapter(65_000, "News")]
+        );
+        assert!(parse_id3(&tag, 2, 0).is_empty());
+    }
+
+    #[test]
+    fn decodes_id3_text_encodings() {
+        assert_eq!(decode_id3_text(b"\x00Caf\xE9"), "Café");
+        assert_eq!(decode_id3_text(b"\x02\0O\0K\0\0"), "OK");
+        assert_eq!(decode_id3_text("\x03章节\0".as_bytes()), "章节");
+        assert_eq!(decode_id3_text(b""), "");
+    }
+
+    #[test]
+    fn reads_nero_chapters() {
+        let mut chpl = vec![1, 0, 0, 0, 0, 0, 0, 0, 2];
+        for (start, title) in [(0u64, "One"), (90_000_000, "Two")] {
+            chpl.extend_from_slice(&start.to_be_bytes());
+            chpl.push(title.len() as u8);
+            chpl.extend_from_slice(title.as_bytes());
+        }
+
+        let mut udta = (8 + chpl.len() as u32).to_be_bytes().to_vec();
+        udta.extend_from_slice(b"chpl");
+        udta.extend(chpl);
+        let mut moov = (8 + udta.len() as u32).to_be_bytes().to_vec();
+        moov.extend_from_slice(b"udta");
+        moov.extend(udta);
+
+        let chpl = find_atom(&moov, &[b"udta", b"chpl"]).unwrap();
+        assert_eq!(parse_chpl(chpl), [chapter(0, "One"), chapter(9000, "Two")]);
+    }
+
+    #[test]
+    fn steps_between_chapters() {
+        let chapters = [chapter(0, "A"), chapter(10_000, "B"), chapter(20_000, "C")];
+
+        assert_eq!(chapter_at(&chapters, 15_000), Some(1));
+        assert_eq!(next_chapter(&chapters, 15_000), Some(2));
+        assert_eq!(next_chapter(&chapters, 25_000), None);
+
+        // Well into a chapter: restart it; near its start: go back one
+        assert_eq!(previous_chapter(&chapters, 15_000), Some(1));
+        assert_eq!(previous_chapter(&chapters, 11_000), Some(0));
+        assert_eq!(previous_chapter(&chapters, 1_000), Some(0));
+    }
+}
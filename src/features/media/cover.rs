@@ -3,7 +3,8 @@
 //! Finds cover art from embedded metadata or external files.
 
 use anyhow::{Context, Result};
-use lofty::file::TaggedFileExt;
+use lofty::file::{TaggedFile, TaggedFileExt};
+use lofty::picture::{Picture, PictureType};
 use lofty::probe::Probe;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -93,15 +94,22 @@ pub fn find_cover_art(audio_path: &Path) -> Option<CoverArtSource> {
     None
 }
 
+/// Pick the cover picture out of a file's tags
+///
+/// Looks through every tag (ID3v2 APIC, FLAC picture blocks, MP4 `covr`),
+/// preferring a front cover over whichever picture happens to come first.
+pub fn embedded_picture(tagged_file: &TaggedFile) -> Option<&Picture> {
+    let pictures = || tagged_file.tags().iter().flat_map(|tag| tag.pictures());
+    pictures()
+        .find(|picture| picture.pic_type() == PictureType::CoverFront)
+        .or_else(|| pictures().next())
+}
+
 /// Extract embedded cover art from audio file
 fn extract_embedded_art(audio_path: &Path) -> Option<CoverArtSource> {
     let tagged_file = Probe::open(audio_path).ok()?.read().ok()?;
 
-    let tag = tagged_file
-        .primary_tag()
-        .or_else(|| tagged_file.first_tag())?;
-
-    let picture = tag.pictures().first()?;
+    let picture = embedded_picture(&tagged_file)?;
     let data = picture.data().to_vec();
     let mime = picture
         .mime_type()
@@ -0,0 +1,158 @@
+//! Playlist cover mosaics
+//!
+//! A local playlist without a cover of its own shows a 2x2 grid of the first
+//! four distinct album covers among its songs, or just the first cover when
+//! it has fewer. Mosaic files are named after the covers they are built from,
+//! so an unchanged playlist reuses its cached image and a changed one gets a
+//! fresh file.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use image::RgbImage;
+use image::imageops::{self, FilterType};
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Covers in a full mosaic
+pub const TILES: usize = 4;
+
+/// Edge of one tile in pixels; the mosaic is twice as wide
+const TILE_SIZE: u32 = 256;
+
+/// Local cover files for a mosaic: the first [`TILES`] distinct ones
+pub fn pick_covers(paths: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut covers: Vec<String> = Vec::with_capacity(TILES);
+    for path in paths {
+        if path.is_empty() || path.starts_with("http") || covers.contains(&path) {
+            continue;
+        }
+        covers.push(path);
+        if covers.len() == TILES {
+            break;
+        }
+    }
+    covers
+}
+
+/// Cache file name of a playlist's mosaic built from `covers`
+fn file_name(playlist_id: i64, covers: &[String]) -> String {
+    format!(
+        "playlist_mosaic_{}_{:016x}.jpg",
+        playlist_id,
+        xxh3_64(covers.join("\n").as_bytes())
+    )
+}
+
+/// Cover to show for a playlist, building the mosaic in `dir` if needed
+///
+/// Falls back to the first cover when there are too few readable ones for a
+/// full grid. Older mosaics of the same playlist are removed.
+pub fn ensure(dir: &Path, playlist_id: i64, covers: &[String]) -> Result<Option<PathBuf>> {
+    let first = covers.first().map(PathBuf::from);
+    if covers.len() < TILES {
+        return Ok(first);
+    }
+
+    let path = dir.join(file_name(playlist_id, covers));
+    if path.exists() {
+        return Ok(Some(path));
+    }
+
+    let tiles: Vec<RgbImage> = covers
+        .iter()
+        .filter_map(|cover| image::open(cover).ok())
+        .map(|img| {
+            img.resize_to_fill(TILE_SIZE, TILE_SIZE, FilterType::Triangle)
+                .to_rgb8()
+        })
+        .collect();
+    if tiles.len() < TILES {
+        return Ok(first);
+    }
+
+    std::fs::create_dir_all(dir)?;
+    remove_stale(dir, playlist_id, &path);
+    render(&tiles).save(&path)?;
+    Ok(Some(path))
+}
+
+/// Lay out four square tiles left to right, top to bottom
+fn render(tiles: &[RgbImage]) -> RgbImage {
+    let mut mosaic = RgbImage::new(TILE_SIZE * 2, TILE_SIZE * 2);
+    for (i, tile) in tiles.iter().take(TILES).enumerate() {
+        let x = (i as u32 % 2) * TILE_SIZE;
+        let y = (i as u32 / 2) * TILE_SIZE;
+        imageops::replace(&mut mosaic, tile, x as i64, y as i64);
+    }
+    mosaic
+}
+
+/// Delete earlier mosaics of a playlist other than `keep`
+fn remove_stale(dir: &Path, playlist_id: i64, keep: &Path) {
+    let prefix = format!("playlist_mosaic_{}_", playlist_id);
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_stale = path != keep
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix));
+        if is_stale {
+            if let Err(e) = std::fs::remove_file(&path) {
+                tracing::debug!("Failed to remove old mosaic {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn picks_first_distinct_local_covers() {
+        let paths = ["a", "a", "", "https://x/c.jpg", "b", "c", "b", "d", "e"];
+        let covers = pick_covers(paths.iter().map(|p| p.to_string()));
+        assert_eq!(covers, ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn file_name_follows_covers() {
+        let covers: Vec<String> = ["a", "b", "c", "d"].map(String::from).into();
+        let mut reordered = covers.clone();
+        reordered.swap(0, 1);
+
+        assert_eq!(file_name(7, &covers), file_name(7, &covers));
+        assert_ne!(file_name(7, &covers), file_name(7, &reordered));
+        assert!(file_name(7, &covers).starts_with("playlist_mosaic_7_"));
+    }
+
+    #[test]
+    fn tiles_fill_each_quadrant() {
+        let colors = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 0]];
+        let tiles: Vec<RgbImage> = colors
+            .iter()
+            .map(|&c| RgbImage::from_pixel(TILE_SIZE, TILE_SIZE, Rgb(c)))
+            .collect();
+
+        let mosaic = render(&tiles);
+        assert_eq!(mosaic.dimensions(), (TILE_SIZE * 2, TILE_SIZE * 2));
+        let probe = |x: u32, y: u32| mosaic.get_pixel(x * TILE_SIZE + 5, y * TILE_SIZE + 5).0;
+        assert_eq!(probe(0, 0), colors[0]);
+        assert_eq!(probe(1, 0), colors[1]);
+        assert_eq!(probe(0, 1), colors[2]);
+        assert_eq!(probe(1, 1), colors[3]);
+    }
+
+    #[test]
+    fn too_few_covers_fall_back_to_the_first() {
+        let covers = vec!["/covers/only.jpg".to_string()];
+        let shown = ensure(Path::new("/nonexistent"), 1, &covers).unwrap();
+        assert_eq!(shown, Some(PathBuf::from("/covers/only.jpg")));
+        assert_eq!(ensure(Path::new("/nonexistent"), 1, &[]).unwrap(), None);
+    }
+}
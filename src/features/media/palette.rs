@@ -0,0 +1,428 @@
+//! Cover palette extraction
+//!
+//! Colors are quantized in Oklab, where distances follow perceived color
+//! difference: median cut seeds the clusters, a few k-means rounds refine
+//! them. Results are cached by the hash of the image bytes, in memory for
+//! the session and in the database across restarts, so reopening a page or
+//! replaying a song never decodes the same cover twice.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock};
+
+use iced::Color;
+use parking_lot::Mutex;
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::database::{Database, DbPalette};
+use crate::utils::{ColorPalette, DominantColors};
+
+/// Side length the image is downsampled to before clustering
+const SAMPLE_SIZE: u32 = 32;
+
+/// Number of colors kept per image
+const CLUSTERS: usize = 3;
+
+const KMEANS_ITERATIONS: usize = 10;
+
+/// Palettes kept in memory before the session cache starts over
+const MEMORY_CACHE_CAPACITY: usize = 128;
+
+static RECENT: LazyLock<Mutex<HashMap<String, DominantColors>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Dominant colors of an image file
+///
+/// `None` if the file can't be read or decoded.
+pub async fn dominant_colors(db: Option<Arc<Database>>, path: PathBuf) -> Option<DominantColors> {
+    let bytes = match tokio::fs::read(&path).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to read image {:?}: {}", path, e);
+            return None;
+        }
+    };
+    let hash = format!("{:016x}", xxh3_64(&bytes));
+
+    if let Some(colors) = RECENT.lock().get(&hash) {
+        return Some(colors.clone());
+    }
+
+    if let Some(db) = &db {
+        match db.get_palette(&hash).await {
+            Ok(Some(row)) => {
+                let colors = from_row(&row);
+                remember(hash, &colors);
+                return Some(colors);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to read palette cache: {}", e),
+        }
+    }
+
+    let colors = tokio::task::spawn_blocking(move || extract(&bytes))
+        .await
+        .ok()
+        .flatten();
+    let Some(colors) = colors else {
+        tracing::warn!("Failed to extract colors from {:?}", path);
+        return None;
+    };
+
+    if let Some(db) = &db {
+        if let Err(e) = db.save_palette(&to_row(hash.clone(), &colors)).await {
+            tracing::warn!("Failed to save palette: {}", e);
+        }
+    }
+    remember(hash, &colors);
+    Some(colors)
+}
+
+/// Playlist header palette for a cover, falling back to the default colors
+pub async fn color_palette(db: Option<Arc<Database>>, path: PathBuf) -> ColorPalette {
+    match dominant_colors(db, path).await {
+        Some(colors) => ColorPalette {
+            primary: colors.primary,
+        },
+        None => ColorPalette::default(),
+    }
+}
+
+fn remember(hash: String, colors: &DominantColors) {
+    let mut recent = RECENT.lock();
+    if recent.len() >= MEMORY_CACHE_CAPACITY {
+        recent.clear();
+    }
+    recent.insert(hash, colors.clone());
+}
+
+/// Decode an image and extract its background colors
+fn extract(bytes: &[u8]) -> Option<DominantColors> {
+    let img = match image::load_from_memory(bytes) {
+        Ok(img) => img.to_rgb8(),
+        Err(e) => {
+            tracing::warn!("Failed to decode image: {}", e);
+            return None;
+        }
+    };
+    let img = image::imageops::resize(
+        &img,
+        SAMPLE_SIZE,
+        SAMPLE_SIZE,
+        image::imageops::FilterType::Nearest,
+    );
+
+    let pixels: Vec<Oklab> = img
+        .pixels()
+        .map(|p| Oklab::from_srgb([p[0], p[1], p[2]]))
+        .collect();
+    if pixels.is_empty() {
+        return None;
+    }
+
+    let colors: Vec<[u8; 3]> = quantize(&pixels, CLUSTERS)
+        .into_iter()
+        .map(|c| c.to_srgb())
+        .collect();
+
+    let brightness = colors
+        .iter()
+        .map(|[r, g, b]| (*r as f32 * 0.299 + *g as f32 * 0.587 + *b as f32 * 0.114) / 255.0)
+        .sum::<f32>()
+        / colors.len() as f32;
+
+    Some(DominantColors {
+        primary: to_background_color(colors[0], 0.65, 1.6),
+        secondary: to_background_color(colors[1], 0.50, 1.5),
+        tertiary: to_background_color(colors[2], 0.25, 1.3),
+        brightness,
+    })
+}
+
+/// Darken a cluster color for use as a background, boosting its saturation
+fn to_background_color([r, g, b]: [u8; 3], brightness_factor: f32, saturation_boost: f32) -> Color {
+    let rf = r as f32 / 255.0;
+    let gf = g as f32 / 255.0;
+    let bf = b as f32 / 255.0;
+
+    let max = rf.max(gf).max(bf);
+    let min = rf.min(gf).min(bf);
+    if max - min < 0.01 {
+        return Color::from_rgb(
+            rf * brightness_factor,
+            gf * brightness_factor,
+            bf * brightness_factor,
+        );
+    }
+
+    let avg = (rf + gf + bf) / 3.0;
+    let boost = |v: f32| -> f32 {
+        (avg + (v - avg) * saturation_boost).clamp(0.0, 1.0) * brightness_factor
+    };
+    Color::from_rgb(boost(rf), boost(gf), boost(bf))
+}
+
+/// Cluster colors into exactly `k` centroids, darkest first
+///
+/// Images with fewer distinct colors than `k` repeat their last centroid.
+fn quantize(pixels: &[Oklab], k: usize) -> Vec<Oklab> {
+    let mut centroids = median_cut(pixels, k);
+    let mut assignment = vec![usize::MAX; pixels.len()];
+
+    for _ in 0..KMEANS_ITERATIONS {
+        let mut changed = false;
+        for (pixel, assigned) in pixels.iter().zip(assignment.iter_mut()) {
+            let nearest = centroids
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| pixel.distance(a).total_cmp(&pixel.distance(b)))
+                .map(|(idx, _)| idx)
+                .unwrap_or(0);
+            if *assigned != nearest {
+                *assigned = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0f32; 3], 0usize); centroids.len()];
+        for (pixel, &idx) in pixels.iter().zip(&assignment) {
+            let (sum, count) = &mut sums[idx];
+            sum[0] += pixel.l;
+            sum[1] += pixel.a;
+            sum[2] += pixel.b;
+            *count += 1;
+        }
+        // Empty clusters keep their previous centroid
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+            if count > 0 {
+                let n = count as f32;
+                *centroid = Oklab {
+                    l: sum[0] / n,
+                    a: sum[1] / n,
+                    b: sum[2] / n,
+                };
+            }
+        }
+    }
+
+    // Seeds that ended up with no pixels would only add a stale color
+    let mut counts = vec![0usize; centroids.len()];
+    for &idx in &assignment {
+        if let Some(count) = counts.get_mut(idx) {
+            *count += 1;
+        }
+    }
+    let mut centroids: Vec<Oklab> = centroids
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(centroid, _)| centroid)
+        .collect();
+
+    centroids.sort_by(|a, b| a.l.total_cmp(&b.l));
+    while centroids.len() < k {
+        let last = centroids
+            .last()
+            .copied()
+            .unwrap_or(Oklab::from_srgb([20, 15, 30]));
+        centroids.push(last);
+    }
+    centroids
+}
+
+/// Initial centroids: split the box with the widest spread at its median
+/// until there are `k` boxes, or no box can be split further
+fn median_cut(pixels: &[Oklab], k: usize) -> Vec<Oklab> {
+    let mut boxes: Vec<Vec<Oklab>> = vec![pixels.to_vec()];
+
+    while boxes.len() < k {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, colors)| colors.len() > 1)
+            .map(|(idx, colors)| (idx, widest_axis(colors)))
+            .max_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b));
+        let Some((idx, (axis, range))) = widest else {
+            break;
+        };
+        if range <= f32::EPSILON {
+            break;
+        }
+
+        let mut colors = boxes.swap_remove(idx);
+        colors.sort_by(|a, b| a.axis(axis).total_cmp(&b.axis(axis)));
+        let upper = colors.split_off(colors.len() / 2);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    boxes.iter().map(|colors| Oklab::mean(colors)).collect()
+}
+
+/// Axis with the largest spread and that spread
+fn widest_axis(colors: &[Oklab]) -> (usize, f32) {
+    (0..3)
+        .map(|axis| {
+            let (min, max) = colors.iter().fold((f32::MAX, f32::MIN), |(min, max), c| {
+                (min.min(c.axis(axis)), max.max(c.axis(axis)))
+            });
+            (axis, max - min)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap_or((0, 0.0))
+}
+
+/// A color in the Oklab space
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Oklab {
+    l: f32,
+    a: f32,
+    b: f32,
+}
+
+impl Oklab {
+    fn from_srgb([r, g, b]: [u8; 3]) -> Self {
+        let to_linear = |c: u8| {
+            let c = c as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Self {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+
+    fn to_srgb(self) -> [u8; 3] {
+        let l = (self.l + 0.3963377774 * self.a + 0.2158037573 * self.b).powi(3);
+        let m = (self.l - 0.1055613458 * self.a - 0.0638541728 * self.b).powi(3);
+        let s = (self.l - 0.0894841775 * self.a - 1.2914855480 * self.b).powi(3);
+
+        let r = 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s;
+        let g = -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s;
+        let b = -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s;
+
+        let to_srgb = |c: f32| {
+            let c = c.clamp(0.0, 1.0);
+            let c = if c <= 0.0031308 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (c * 255.0).round() as u8
+        };
+        [to_srgb(r), to_srgb(g), to_srgb(b)]
+    }
+
+    fn axis(&self, axis: usize) -> f32 {
+        match axis {
+            0 => self.l,
+            1 => self.a,
+            _ => self.b,
+        }
+    }
+
+    /// Squared Euclidean distance, roughly perceptual in this space
+    fn distance(&self, other: &Self) -> f32 {
+        let dl = self.l - other.l;
+        let da = self.a - other.a;
+        let db = self.b - other.b;
+        dl * dl + da * da + db * db
+    }
+
+    fn mean(colors: &[Self]) -> Self {
+        let n = colors.len().max(1) as f32;
+        let (l, a, b) = colors
+            .iter()
+            .fold((0.0, 0.0, 0.0), |(l, a, b), c| (l + c.l, a + c.a, b + c.b));
+        Self {
+            l: l / n,
+            a: a / n,
+            b: b / n,
+        }
+    }
+}
+
+fn pack(color: Color) -> i64 {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as i64;
+    (channel(color.r) << 16) | (channel(color.g) << 8) | channel(color.b)
+}
+
+fn unpack(packed: i64) -> Color {
+    Color::from_rgb8(
+        ((packed >> 16) & 0xff) as u8,
+        ((packed >> 8) & 0xff) as u8,
+        (packed & 0xff) as u8,
+    )
+}
+
+fn to_row(image_hash: String, colors: &DominantColors) -> DbPalette {
+    DbPalette {
+        image_hash,
+        primary_color: pack(colors.primary),
+        secondary_color: pack(colors.secondary),
+        tertiary_color: pack(colors.tertiary),
+        brightness: colors.brightness as f64,
+        created_at: 0,
+    }
+}
+
+fn from_row(row: &DbPalette) -> DominantColors {
+    DominantColors {
+        primary: unpack(row.primary_color),
+        secondary: unpack(row.secondary_color),
+        tertiary: unpack(row.tertiary_color),
+        brightness: row.brightness as f32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oklab_round_trip() {
+        for rgb in [[0, 0, 0], [255, 255, 255], [200, 30, 90], [12, 180, 240]] {
+            assert_eq!(Oklab::from_srgb(rgb).to_srgb(), rgb);
+        }
+    }
+
+    #[test]
+    fn test_quantize_separates_colors() {
+        let red = Oklab::from_srgb([220, 20, 20]);
+        let blue = Oklab::from_srgb([20, 20, 200]);
+        let mut pixels = vec![red; 60];
+        pixels.extend(vec![blue; 40]);
+
+        let centroids: Vec<[u8; 3]> = quantize(&pixels, 3)
+            .into_iter()
+            .map(|c| c.to_srgb())
+            .collect();
+        assert_eq!(centroids.len(), 3);
+        // Blue is darker in Oklab, so it sorts first; the missing third
+        // centroid repeats the last one
+        assert_eq!(centroids[0], [20, 20, 200]);
+        assert_eq!(centroids[1], [220, 20, 20]);
+        assert_eq!(centroids[2], [220, 20, 20]);
+    }
+
+    #[test]
+    fn test_packed_colors_round_trip() {
+        let color = Color::from_rgb8(18, 52, 86);
+        assert_eq!(pack(color), 0x123456);
+        assert_eq!(unpack(pack(color)), color);
+    }
+}
@@ -0,0 +1,235 @@
+//! Writing tags to audio files
+//!
+//! Each container's own tag is edited in place of a generic one, so frames
+//! and atoms Rustle doesn't know about survive: ID3v2 for MP3, Vorbis
+//! comments for FLAC and Ogg, and an ilst atom for M4A. The edit is made on
+//! a copy next to the file, which only replaces the original once written,
+//! so a crash midway leaves the original untouched.
+
+use std::fs::OpenOptions;
+use std::io::Seek;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::file::{AudioFile, FileType};
+use lofty::flac::FlacFile;
+use lofty::id3::v2::{BinaryFrame, Frame, FrameId, Id3v2Tag};
+use lofty::mp4::{Atom, AtomData, AtomIdent, Ilst, Mp4File};
+use lofty::mpeg::MpegFile;
+use lofty::ogg::{OggPictureStorage, OpusFile, VorbisComments, VorbisFile};
+use lofty::picture::{Picture, PictureType};
+use lofty::probe::Probe;
+use lofty::tag::{Accessor, ItemKey, Tag, TagType};
+
+/// Tags to write, leaving out fields that are `None`
+#[derive(Debug, Clone, Default)]
+pub struct TagUpdate {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    /// Front cover image bytes, JPEG or PNG
+    pub cover: Option<Vec<u8>>,
+    /// Lyrics, LRC for synced ones
+    pub lyrics: Option<String>,
+    /// Timed lines as (start in milliseconds, text), written to ID3v2 as
+    /// SYLT next to the lyrics text
+    pub synced_lyrics: Option<Vec<(u32, String)>>,
+}
+
+impl TagUpdate {
+    fn cover_picture(&self) -> Result<Option<Picture>> {
+        let Some(cover) = &self.cover else {
+            return Ok(None);
+        };
+        let mut picture =
+            Picture::from_reader(&mut cover.as_slice()).context("Unsupported cover image")?;
+        picture.set_pic_type(PictureType::CoverFront);
+        Ok(Some(picture))
+    }
+
+    fn apply_text(&self, tag: &mut impl Accessor) {
+        if let Some(title) = &self.title {
+            tag.set_title(title.clone());
+        }
+        if let Some(artist) = &self.artist {
+            tag.set_artist(artist.clone());
+        }
+        if let Some(album) = &self.album {
+            tag.set_album(album.clone());
+        }
+    }
+}
+
+/// Write `update` into the file at `path`
+///
+/// The file type is read from its content, so this works on files whose
+/// extension doesn't say what they are yet.
+pub fn write_tags(path: &Path, update: &TagUpdate) -> Result<()> {
+    let file_type = Probe::open(path)
+        .context("Failed to open audio file")?
+        .guess_file_type()?
+        .file_type()
+        .context("Unknown audio format")?;
+
+    let copy = copy_path(path);
+    std::fs::copy(path, &copy).context("Failed to copy audio file")?;
+    let written = write_native(&copy, file_type, update)
+        .and_then(|()| std::fs::rename(&copy, path).context("Failed to replace audio file"));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&copy);
+    }
+    written
+}
+
+/// Hidden sibling the edit is made on, on the same filesystem as `path`
+fn copy_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.tagging", name))
+}
+
+fn write_native(path: &Path, file_type: FileType, update: &TagUpdate) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .context("Failed to open audio file")?;
+    let options = ParseOptions::new();
+
+    match file_type {
+        FileType::Mpeg => {
+            let mut audio = MpegFile::read_from(&mut file, options)?;
+            let mut tag = audio.remove_id3v2().unwrap_or_default();
+            apply_id3v2(&mut tag, update)?;
+            audio.set_id3v2(tag);
+            save(&audio, &mut file)
+        }
+        FileType::Flac => {
+            let mut audio = FlacFile::read_from(&mut file, options)?;
+            let mut tag = audio.remove_vorbis_comments().unwrap_or_default();
+            apply_vorbis(&mut tag, update);
+            audio.set_vorbis_comments(tag);
+            // FLAC keeps pictures in blocks of their own
+            if let Some(picture) = update.cover_picture()? {
+                audio.remove_picture_type(PictureType::CoverFront);
+                audio.insert_picture(picture, None)?;
+            }
+            save(&audio, &mut file)
+        }
+        FileType::Opus => {
+            let mut audio = OpusFile::read_from(&mut file, options)?;
+            apply_vorbis(audio.vorbis_comments_mut(), update);
+            apply_vorbis_cover(audio.vorbis_comments_mut(), update)?;
+            save(&audio, &mut file)
+        }
+        FileType::Vorbis => {
+            let mut audio = VorbisFile::read_from(&mut file, options)?;
+            apply_vorbis(audio.vorbis_comments_mut(), update);
+            apply_vorbis_cover(audio.vorbis_comments_mut(), update)?;
+            save(&audio, &mut file)
+        }
+        FileType::Mp4 => {
+            let mut audio = Mp4File::read_from(&mut file, options)?;
+            let mut tag = audio.remove_ilst().unwrap_or_default();
+            apply_ilst(&mut tag, update)?;
+            audio.set_ilst(tag);
+            save(&audio, &mut file)
+        }
+        other => bail!("Tags can't be written to {:?} files", other),
+    }
+}
+
+fn save(audio: &impl AudioFile, file: &mut std::fs::File) -> Result<()> {
+    file.rewind()?;
+    audio
+        .save_to(file, WriteOptions::default())
+        .context("Failed to save tags")?;
+    file.sync_all().context("Failed to save tags")
+}
+
+fn apply_id3v2(tag: &mut Id3v2Tag, update: &TagUpdate) -> Result<()> {
+    update.apply_text(tag);
+    if let Some(lyrics) = &update.lyrics {
+        // Converting from a generic tag builds the USLT frame, which then
+        // replaces only the tag's own USLT
+        let mut generic = Tag::new(TagType::Id3v2);
+        generic.insert_text(ItemKey::Lyrics, lyrics.clone());
+        for frame in Id3v2Tag::from(generic) {
+            tag.insert(frame);
+        }
+    }
+    if let Some(lines) = &update.synced_lyrics {
+        let id = FrameId::new("SYLT")?;
+        tag.insert(Frame::Binary(BinaryFrame::new(id, sylt_body(lines))));
+    }
+    if let Some(picture) = update.cover_picture()? {
+        tag.insert_picture(picture);
+    }
+    Ok(())
+}
+
+/// Body of an ID3v2.4 SYLT frame holding `lines`
+fn sylt_body(lines: &[(u32, String)]) -> Vec<u8> {
+    // UTF-8, unknown language, millisecond stamps, lyrics, no description
+    let mut body = vec![3, b'X', b'X', b'X', 2, 1, 0];
+    for (start_ms, text) in lines {
+        body.extend_from_slice(text.as_bytes());
+        body.push(0);
+        body.extend_from_slice(&start_ms.to_be_bytes());
+    }
+    body
+}
+
+fn apply_vorbis(tag: &mut VorbisComments, update: &TagUpdate) {
+    update.apply_text(tag);
+    if let Some(lyrics) = &update.lyrics {
+        tag.insert(String::from("LYRICS"), lyrics.clone());
+    }
+}
+
+fn apply_vorbis_cover(tag: &mut VorbisComments, update: &TagUpdate) -> Result<()> {
+    if let Some(picture) = update.cover_picture()? {
+        tag.remove_picture_type(PictureType::CoverFront);
+        tag.insert_picture(picture, None)?;
+    }
+    Ok(())
+}
+
+fn apply_ilst(tag: &mut Ilst, update: &TagUpdate) -> Result<()> {
+    update.apply_text(tag);
+    if let Some(lyrics) = &update.lyrics {
+        tag.replace_atom(Atom::new(
+            AtomIdent::Fourcc(*b"\xa9lyr"),
+            AtomData::UTF8(lyrics.clone()),
+        ));
+    }
+    if let Some(picture) = update.cover_picture()? {
+        tag.replace_atom(Atom::new(
+            AtomIdent::Fourcc(*b"covr"),
+            AtomData::Picture(picture),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_is_a_hidden_sibling() {
+        assert_eq!(
+            copy_path(Path::new("/music/a/song.mp3")),
+            Path::new("/music/a/.song.mp3.tagging")
+        );
+    }
+
+    #[test]
+    fn test_sylt_body() {
+        let body = sylt_body(&[(1500, "Hi".to_string())]);
+        assert_eq!(
+            body,
+            [3, b'X', b'X', b'X', 2, 1, 0, b'H', b'i', 0, 0, 0, 5, 220]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Artwork for the system media session
+//!
+//! Lock screens, SMTC and MPRIS clients load the artwork from the URL they
+//! are handed. A remote URL shows nothing offline and a full-size cover is
+//! slow to pass on to watches and car displays, so each song's cover is
+//! scaled down once into the cache and handed over as a local file.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use image::imageops::FilterType;
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::database::SongId;
+
+/// Longest edge of the cached artwork
+const ARTWORK_SIZE: u32 = 512;
+
+/// Folder the artwork is cached in, pruned along with the cover cache
+pub fn cache_dir() -> PathBuf {
+    crate::utils::covers_cache_dir().join("media")
+}
+
+/// Cached artwork for `song_id` made from `source`
+///
+/// The source is part of the name so a better cover arriving for the same
+/// song gets a new URL; clients tend to keep showing what they fetched for
+/// a URL they have already seen.
+pub fn cache_path(song_id: SongId, source: &str) -> PathBuf {
+    // xxh3 is stable across builds, unlike the standard library's hasher
+    cache_dir().join(format!(
+        "media_{}_{:08x}.jpg",
+        song_id,
+        xxh3_64(source.as_bytes()) as u32
+    ))
+}
+
+/// Scale `cover` down to fit the artwork size and write it to `path` as JPEG
+pub fn write(path: &Path, cover: &[u8]) -> Result<()> {
+    let mut artwork = image::load_from_memory(cover).context("Unreadable cover")?;
+    if artwork.width() > ARTWORK_SIZE || artwork.height() > ARTWORK_SIZE {
+        artwork = artwork.resize(ARTWORK_SIZE, ARTWORK_SIZE, FilterType::Triangle);
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    }
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    // JPEG has no alpha channel
+    artwork
+        .to_rgb8()
+        .save_with_format(&temp, image::ImageFormat::Jpeg)
+        .with_context(|| format!("Failed to write {:?}", temp))?;
+    std::fs::rename(&temp, path).with_context(|| format!("Failed to replace {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_path_follows_source() {
+        let song = SongId::library(42);
+        let thumbnail = cache_path(song, "/covers/cover_42.jpg");
+        assert_eq!(thumbnail, cache_path(song, "/covers/cover_42.jpg"));
+        assert_ne!(thumbnail, cache_path(song, "/covers/cover_42_large.jpg"));
+        assert_ne!(
+            thumbnail,
+            cache_path(SongId::ncm(42), "/covers/cover_42.jpg")
+        );
+    }
+
+    #[test]
+    fn test_write_scales_down() {
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgba8(1024, 768)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("rustle_media_art_{}.jpg", std::process::id()));
+        write(&path, &png).unwrap();
+        let artwork = image::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!((artwork.width(), artwork.height()), (512, 384));
+    }
+}
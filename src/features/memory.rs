@@ -0,0 +1,188 @@
+//! Memory accounting and soft budgets
+//!
+//! A sample combines the process size with the caches that grow over a long
+//! session: cover images pinned in GPU memory, the lyrics glyph atlas, glyph
+//! bitmaps generated ahead of upload, and the SQLite page caches. The debug
+//! overlay shows the latest sample; [`MemoryReport::exceeded`] tells the app
+//! which caches to evict.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+use parking_lot::Mutex;
+
+use crate::features::lyrics::engine::sdf_cache;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Process size above which every evictable cache is trimmed
+pub const PROCESS_BUDGET: u64 = 512 * MIB;
+
+/// Cover images kept decoded while their page isn't showing
+pub const PINNED_IMAGE_BUDGET: u64 = 64 * MIB;
+
+/// Glyph bitmaps waiting to be uploaded to the atlas
+pub const PENDING_GLYPH_BUDGET: u64 = 16 * MIB;
+
+static ATLAS_GLYPHS: AtomicUsize = AtomicUsize::new(0);
+static ATLAS_USED_HEIGHT: AtomicU32 = AtomicU32::new(0);
+static ATLAS_WIDTH: AtomicU32 = AtomicU32::new(0);
+static ATLAS_HEIGHT: AtomicU32 = AtomicU32::new(0);
+
+/// Decoded size per image file, so each header is read only once
+static IMAGE_SIZES: LazyLock<Mutex<HashMap<PathBuf, u64>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Called by the glyph atlas whenever its contents change
+pub fn record_atlas(glyphs: usize, used_height: u32, width: u32, height: u32) {
+    ATLAS_GLYPHS.store(glyphs, Ordering::Relaxed);
+    ATLAS_USED_HEIGHT.store(used_height, Ordering::Relaxed);
+    ATLAS_WIDTH.store(width, Ordering::Relaxed);
+    ATLAS_HEIGHT.store(height, Ordering::Relaxed);
+}
+
+/// Lyrics glyph atlas usage
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AtlasUsage {
+    pub glyphs: usize,
+    pub used_height: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl AtlasUsage {
+    fn current() -> Self {
+        Self {
+            glyphs: ATLAS_GLYPHS.load(Ordering::Relaxed),
+            used_height: ATLAS_USED_HEIGHT.load(Ordering::Relaxed),
+            width: ATLAS_WIDTH.load(Ordering::Relaxed),
+            height: ATLAS_HEIGHT.load(Ordering::Relaxed),
+        }
+    }
+
+    /// GPU memory of the atlas texture (RGBA8), zero before it exists
+    pub fn texture_bytes(&self) -> u64 {
+        self.width as u64 * self.height as u64 * 4
+    }
+
+    /// Share of the atlas rows in use (0-1)
+    pub fn occupancy(&self) -> f32 {
+        if self.height == 0 {
+            0.0
+        } else {
+            self.used_height as f32 / self.height as f32
+        }
+    }
+}
+
+/// What the app knows about its own caches when a sample is taken
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    /// Files behind the cover images pinned in GPU memory
+    pub pinned_images: Vec<PathBuf>,
+    /// Combined ceiling of the SQLite page caches, not what they hold now
+    pub db_cache_limit_bytes: u64,
+    /// Item counts per subsystem
+    pub counters: Vec<(&'static str, usize)>,
+}
+
+/// One memory sample
+#[derive(Debug, Clone, Default)]
+pub struct MemoryReport {
+    /// Resident set size, if the platform reports it
+    pub resident_bytes: Option<u64>,
+    /// Estimated decoded size of the pinned cover images
+    pub pinned_image_bytes: u64,
+    pub pending_glyphs: usize,
+    pub pending_glyph_bytes: u64,
+    pub atlas: AtlasUsage,
+    /// Most the SQLite page caches may grow to
+    pub db_cache_limit_bytes: u64,
+    pub counters: Vec<(&'static str, usize)>,
+}
+
+/// A soft budget that a sample went over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    Process,
+    PinnedImages,
+    PendingGlyphs,
+}
+
+impl MemoryReport {
+    /// Budgets this sample exceeds
+    pub fn exceeded(&self) -> Vec<Budget> {
+        let mut exceeded = Vec::new();
+        if self
+            .resident_bytes
+            .is_some_and(|bytes| bytes > PROCESS_BUDGET)
+        {
+            exceeded.push(Budget::Process);
+        }
+        if self.pinned_image_bytes > PINNED_IMAGE_BUDGET {
+            exceeded.push(Budget::PinnedImages);
+        }
+        if self.pending_glyph_bytes > PENDING_GLYPH_BUDGET {
+            exceeded.push(Budget::PendingGlyphs);
+        }
+        exceeded
+    }
+}
+
+/// Take a sample
+///
+/// Queries the OS and reads image headers, so call this from a blocking task.
+pub fn sample(snapshot: Snapshot) -> MemoryReport {
+    MemoryReport {
+        resident_bytes: crate::platform::memory::resident_bytes(),
+        pinned_image_bytes: decoded_size(&snapshot.pinned_images),
+        pending_glyphs: sdf_cache::global_cache_size(),
+        pending_glyph_bytes: sdf_cache::global_cache_bytes(),
+        atlas: AtlasUsage::current(),
+        db_cache_limit_bytes: snapshot.db_cache_limit_bytes,
+        counters: snapshot.counters,
+    }
+}
+
+/// Estimated RGBA size of the images once decoded
+fn decoded_size(paths: &[PathBuf]) -> u64 {
+    let mut sizes = IMAGE_SIZES.lock();
+    // Forget files that are no longer held
+    let held: HashSet<&PathBuf> = paths.iter().collect();
+    sizes.retain(|path, _| held.contains(path));
+
+    paths
+        .iter()
+        .map(|path| {
+            *sizes.entry(path.clone()).or_insert_with(|| {
+                image::image_dimensions(path)
+                    .map(|(width, height)| width as u64 * height as u64 * 4)
+                    .unwrap_or(0)
+            })
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeded_budgets() {
+        let mut report = MemoryReport::default();
+        assert!(report.exceeded().is_empty());
+
+        report.resident_bytes = Some(PROCESS_BUDGET + 1);
+        report.pending_glyph_bytes = PENDING_GLYPH_BUDGET + 1;
+        assert_eq!(
+            report.exceeded(),
+            vec![Budget::Process, Budget::PendingGlyphs]
+        );
+
+        // Unknown process size never counts as over budget
+        report.resident_bytes = None;
+        assert_eq!(report.exceeded(), vec![Budget::PendingGlyphs]);
+    }
+}
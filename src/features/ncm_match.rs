@@ -0,0 +1,254 @@
+//! "Find this on NCM" for local files
+//!
+//! A local song is searched on NCM by its tags (or file name when untagged).
+//! The closest results by duration and title are then confirmed by
+//! fingerprinting the local file and the start of each candidate's stream.
+//! A candidate whose audio can't be fetched is only accepted when its
+//! metadata agrees almost exactly.
+
+pub mod fingerprint;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use tracing::{debug, warn};
+
+use crate::api::ncm_api::SearchType;
+use crate::api::{NcmClient, SongInfo};
+use fingerprint::{Fingerprint, MATCH_THRESHOLD};
+
+/// Search results considered per lookup
+const SEARCH_LIMIT: u32 = 10;
+/// Best-ranked candidates whose audio is compared
+const VERIFY_CANDIDATES: usize = 3;
+/// Candidates further off in length are a different edit
+const MAX_DURATION_DIFF_SECS: u64 = 5;
+/// Metadata score that stands in for audio we could not fetch
+pub const METADATA_ONLY_SCORE: f32 = 0.95;
+/// Enough of a stream for the fingerprint window even at lossless rates
+const STREAM_HEAD_BYTES: u64 = 4 * 1024 * 1024;
+
+/// What we know about the local file
+#[derive(Debug, Clone)]
+pub struct LocalTrack {
+    pub path: PathBuf,
+    pub title: String,
+    pub artist: String,
+    pub duration_secs: u64,
+}
+
+/// Search keywords: tags when present, else the file name
+pub fn search_keywords(track: &LocalTrack) -> String {
+    let title = track.title.trim();
+    if title.is_empty() {
+        return track
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().replace(['_', '.'], " "))
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+    }
+    format!("{} {}", title, track.artist.trim())
+        .trim()
+        .to_string()
+}
+
+/// How well a search result's metadata fits the local file, from 0.0 to 1.0
+pub fn metadata_score(track: &LocalTrack, candidate: &SongInfo) -> f32 {
+    let duration = if track.duration_secs == 0 {
+        0.5
+    } else {
+        let diff = track.duration_secs.abs_diff(candidate.duration / 1000);
+        (1.0 - diff as f32 / MAX_DURATION_DIFF_SECS as f32).max(0.0)
+    };
+
+    let (local, remote) = (normalize(&track.title), normalize(&candidate.name));
+    let title = if local.is_empty() || remote.is_empty() {
+        0.0
+    } else if local == remote {
+        1.0
+    } else if local.contains(&remote) || remote.contains(&local) {
+        0.6
+    } else {
+        0.0
+    };
+
+    // NCM only reports the first credited artist
+    let (local, remote) = (normalize(&track.artist), normalize(&candidate.singer));
+    let artist = if !local.is_empty() && !remote.is_empty() && local.contains(&remote) {
+        1.0
+    } else {
+        0.0
+    };
+
+    duration * 0.4 + title * 0.4 + artist * 0.2
+}
+
+/// Drop results of a different length and order the rest best first
+pub fn rank_candidates(track: &LocalTrack, candidates: Vec<SongInfo>) -> Vec<(SongInfo, f32)> {
+    let mut ranked: Vec<(SongInfo, f32)> = candidates
+        .into_iter()
+        .filter(|song| {
+            track.duration_secs == 0
+                || track.duration_secs.abs_diff(song.duration / 1000) <= MAX_DURATION_DIFF_SECS
+        })
+        .map(|song| {
+            let score = metadata_score(track, &song);
+            (song, score)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+/// Find the NCM track for a local file, if there is a convincing one
+pub async fn find(client: &NcmClient, track: LocalTrack) -> Result<Option<SongInfo>> {
+    let keywords = search_keywords(&track);
+    if keywords.is_empty() {
+        return Ok(None);
+    }
+
+    let response = client
+        .client
+        .search(&keywords, SearchType::Songs, SEARCH_LIMIT, 0)
+        .await?;
+    let mut ranked = rank_candidates(&track, response.songs);
+    ranked.truncate(VERIFY_CANDIDATES);
+    if ranked.is_empty() {
+        return Ok(None);
+    }
+
+    let path = track.path.clone();
+    let local = tokio::task::spawn_blocking(move || -> Result<Fingerprint> {
+        fingerprint::from_reader(std::io::BufReader::new(std::fs::File::open(path)?))
+    })
+    .await??;
+
+    let ids: Vec<u64> = ranked.iter().map(|(song, _)| song.id).collect();
+    let urls = client.songs_url(&ids).await.unwrap_or_else(|e| {
+        warn!("Failed to get stream URLs for matching: {}", e);
+        Vec::new()
+    });
+
+    let mut fallback = None;
+    for (song, score) in ranked {
+        let url = urls
+            .iter()
+            .find(|u| u.id == song.id && !u.url.is_empty())
+            .map(|u| u.url.clone());
+
+        let remote = match url {
+            Some(url) => stream_fingerprint(url).await,
+            None => Err(anyhow!("no stream URL")),
+        };
+        match remote {
+            Ok(remote) => {
+                let similarity = local.similarity(&remote);
+                debug!(
+                    "Fingerprint similarity with NCM song {} ({}): {:.2}",
+                    song.id, song.name, similarity
+                );
+                if similarity >= MATCH_THRESHOLD {
+                    return Ok(Some(song));
+                }
+            }
+            Err(e) => {
+                debug!("Could not fingerprint NCM song {}: {}", song.id, e);
+                if fallback.is_none() && score >= METADATA_ONLY_SCORE {
+                    fallback = Some(song);
+                }
+            }
+        }
+    }
+
+    Ok(fallback)
+}
+
+/// Fingerprint the start of an NCM stream
+async fn stream_fingerprint(url: String) -> Result<Fingerprint> {
+    let _permit = crate::api::http::host_permit(&url).await;
+    let response = crate::api::http::direct()
+        .get(&url)
+        .header(
+            reqwest::header::RANGE,
+            format!("bytes=0-{}", STREAM_HEAD_BYTES - 1),
+        )
+        .send()
+        .await?
+        .error_for_status()?;
+    let bytes = response.bytes().await?.to_vec();
+
+    let fingerprint =
+        tokio::task::spawn_blocking(move || fingerprint::from_reader(Cursor::new(bytes))).await??;
+    Ok(fingerprint)
+}
+
+/// Lowercase letters and digits only, so punctuation and spacing don't matter
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(title: &str, artist: &str, duration_secs: u64) -> LocalTrack {
+        LocalTrack {
+            path: PathBuf::from("/music/02_Some.Song.flac"),
+            title: title.to_string(),
+            artist: artist.to_string(),
+            duration_secs,
+        }
+    }
+
+    fn song(id: u64, name: &str, singer: &str, duration_secs: u64) -> SongInfo {
+        SongInfo {
+            id,
+            name: name.to_string(),
+            singer: singer.to_string(),
+            duration: duration_secs * 1000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keywords_fall_back_to_file_name() {
+        assert_eq!(search_keywords(&track("晴天", "周杰伦", 0)), "晴天 周杰伦");
+        assert_eq!(search_keywords(&track("Intro", "", 0)), "Intro");
+        assert_eq!(search_keywords(&track(" ", "", 0)), "02 Some Song");
+    }
+
+    #[test]
+    fn exact_metadata_scores_highest() {
+        let local = track("Hello, World!", "Artist A / Artist B", 200);
+
+        let exact = metadata_score(&local, &song(1, "hello world", "Artist A", 200));
+        let live = metadata_score(&local, &song(2, "Hello World (Live)", "Artist A", 203));
+        let other = metadata_score(&local, &song(3, "Goodbye", "Someone", 200));
+
+        assert!(exact >= METADATA_ONLY_SCORE);
+        assert!(live < exact && live < METADATA_ONLY_SCORE);
+        assert!(other < live);
+    }
+
+    #[test]
+    fn ranking_drops_other_lengths() {
+        let local = track("Song", "Artist", 180);
+        let ranked = rank_candidates(
+            &local,
+            vec![
+                song(1, "Song (Remix)", "Artist", 182),
+                song(2, "Song", "Artist", 240),
+                song(3, "Song", "Artist", 181),
+            ],
+        );
+
+        let ids: Vec<u64> = ranked.iter().map(|(song, _)| song.id).collect();
+        assert_eq!(ids, [3, 1]);
+    }
+}
@@ -0,0 +1,247 @@
+//! Compact audio fingerprints for recognising the same recording
+//!
+//! Follows Haitsma & Kalker: audio is folded to mono at a low sample rate
+//! and cut into overlapping frames, and each frame becomes a 32-bit word
+//! whose bits say whether the energy difference between neighbouring bands
+//! grew or shrank since the previous frame. Two encodes of one master keep
+//! most bits, while unrelated audio agrees on about half of them.
+
+use std::io::{Read, Seek};
+
+use anyhow::{Result, anyhow};
+use rodio::{Decoder, Source};
+use spectrum_analyzer::windows::hann_window;
+use spectrum_analyzer::{FrequencyLimit, samples_fft_to_spectrum};
+
+/// Seconds fingerprinted from the start of a track
+pub const FINGERPRINT_SECS: u32 = 15;
+
+/// Similarity at or above which two fingerprints are the same recording
+pub const MATCH_THRESHOLD: f32 = 0.65;
+
+/// Working sample rate; everything above 2 kHz is ignored anyway
+const SAMPLE_RATE: u32 = 5512;
+/// ~0.37 s frames
+const FRAME_SIZE: usize = 2048;
+/// ~12 ms between frames
+const HOP_SIZE: usize = 64;
+
+/// Seconds between the starts of consecutive words
+pub const FRAME_SECS: f64 = HOP_SIZE as f64 / SAMPLE_RATE as f64;
+
+/// Energy bands; neighbouring pairs give the 32 bits of a word
+const BANDS: usize = 33;
+const MIN_FREQ: f32 = 300.0;
+const MAX_FREQ: f32 = 2000.0;
+
+/// Encoder delay and padding shift streams by up to about a second
+const MAX_OFFSET_FRAMES: usize = 86;
+/// Fewest overlapping words worth scoring (~0.75 s)
+const MIN_OVERLAP_FRAMES: usize = 64;
+
+/// One 32-bit word per frame
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fingerprint(Vec<u32>);
+
+impl Fingerprint {
+    /// One word per frame, oldest first
+    pub fn words(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Best share of matching bits over small time offsets, from 0.0 to 1.0
+    pub fn similarity(&self, other: &Fingerprint) -> f32 {
+        let (a, b) = (&self.0, &other.0);
+        let mut best = 0.0f32;
+
+        for shift in 0..=MAX_OFFSET_FRAMES * 2 {
+            let (a_start, b_start) = if shift <= MAX_OFFSET_FRAMES {
+                (MAX_OFFSET_FRAMES - shift, 0)
+            } else {
+                (0, shift - MAX_OFFSET_FRAMES)
+            };
+            if a_start >= a.len() || b_start >= b.len() {
+                continue;
+            }
+            let len = (a.len() - a_start).min(b.len() - b_start);
+            if len < MIN_OVERLAP_FRAMES {
+                continue;
+            }
+
+            let differing: u32 = a[a_start..a_start + len]
+                .iter()
+                .zip(&b[b_start..b_start + len])
+                .map(|(x, y)| (x ^ y).count_ones())
+                .sum();
+            best = best.max(1.0 - differing as f32 / (len * 32) as f32);
+        }
+
+        best
+    }
+}
+
+/// Fingerprint interleaved samples
+pub fn compute(samples: &[f32], channels: u16, sample_rate: u32) -> Fingerprint {
+    let mono = downmix(samples, channels.max(1) as usize);
+    let signal = resample(&mono, sample_rate, SAMPLE_RATE);
+
+    let energies: Vec<[f32; BANDS]> = signal
+        .windows(FRAME_SIZE)
+        .step_by(HOP_SIZE)
+        .map(band_energies)
+        .collect();
+
+    Fingerprint(
+        energies
+            .windows(2)
+            .map(|pair| frame_word(&pair[0], &pair[1]))
+            .collect(),
+    )
+}
+
+/// Decode the start of an audio stream and fingerprint it
+///
+/// A truncated stream is fine; decoding stops at the first bad packet.
+pub fn from_reader<R>(reader: R) -> Result<Fingerprint>
+where
+    R: Read + Seek + Send + Sync + 'static,
+{
+    from_reader_secs(reader, FINGERPRINT_SECS)
+}
+
+/// Decode up to `secs` seconds of an audio stream and fingerprint them
+pub fn from_reader_secs<R>(reader: R, secs: u32) -> Result<Fingerprint>
+where
+    R: Read + Seek + Send + Sync + 'static,
+{
+    let source = Decoder::new(reader)?;
+    let channels = source.channels();
+    let sample_rate = source.sample_rate();
+    let wanted = secs as usize * sample_rate as usize * channels as usize;
+
+    let samples: Vec<f32> = source.take(wanted).collect();
+    let fingerprint = compute(&samples, channels, sample_rate);
+    if fingerprint.0.len() < MIN_OVERLAP_FRAMES {
+        return Err(anyhow!("too little audio to fingerprint"));
+    }
+    Ok(fingerprint)
+}
+
+fn downmix(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels == 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Resample by averaging each output period, which doubles as a low-pass
+fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == 0 || samples.is_empty() {
+        return Vec::new();
+    }
+    let step = from as f64 / to as f64;
+    let out_len = (samples.len() as f64 / step) as usize;
+
+    (0..out_len)
+        .map(|i| {
+            let start = (i as f64 * step) as usize;
+            let end = (((i + 1) as f64 * step) as usize)
+                .max(start + 1)
+                .min(samples.len());
+            samples[start..end].iter().sum::<f32>() / (end - start) as f32
+        })
+        .collect()
+}
+
+fn band_energies(frame: &[f32]) -> [f32; BANDS] {
+    let mut energies = [0.0f32; BANDS];
+    let windowed = hann_window(frame);
+    let Ok(spectrum) = samples_fft_to_spectrum(
+        &windowed,
+        SAMPLE_RATE,
+        FrequencyLimit::Range(MIN_FREQ, MAX_FREQ),
+        None,
+    ) else {
+        return energies;
+    };
+
+    let span = (MAX_FREQ / MIN_FREQ).ln();
+    for (freq, magnitude) in spectrum.data() {
+        let position = (freq.val() / MIN_FREQ).ln() / span;
+        let band = ((position * BANDS as f32) as usize).min(BANDS - 1);
+        energies[band] += magnitude.val() * magnitude.val();
+    }
+    energies
+}
+
+fn frame_word(previous: &[f32; BANDS], current: &[f32; BANDS]) -> u32 {
+    (0..BANDS - 1).fold(0u32, |word, band| {
+        let now = current[band] - current[band + 1];
+        let before = previous[band] - previous[band + 1];
+        (word << 1) | u32::from(now > before)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    /// Two tones that hop to new pitches every 150 ms, chosen by `seed`
+    fn melody(seed: u32, sample_rate: u32, channels: u16, secs: f32) -> Vec<f32> {
+        let mut state = seed;
+        let mut next_pitch = move || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            350.0 + (state >> 8) as f32 / (1u32 << 24) as f32 * 1500.0
+        };
+
+        let segment = (sample_rate as f32 * 0.15) as usize;
+        let total = (sample_rate as f32 * secs) as usize;
+        let mut samples = Vec::with_capacity(total * channels as usize);
+        let (mut low, mut high) = (0.0, 0.0);
+        for n in 0..total {
+            if n % segment == 0 {
+                (low, high) = (next_pitch(), next_pitch());
+            }
+            let t = n as f32 / sample_rate as f32;
+            let value = 0.5 * (TAU * low * t).sin() + 0.3 * (TAU * high * t).sin();
+            samples.extend(std::iter::repeat_n(value, channels as usize));
+        }
+        samples
+    }
+
+    #[test]
+    fn same_recording_matches_across_formats() {
+        let original = compute(&melody(7, 44100, 2, 6.0), 2, 44100);
+        let reencoded = compute(&melody(7, 22050, 1, 6.0), 1, 22050);
+
+        assert!(!original.0.is_empty());
+        assert!(original.similarity(&reencoded) >= MATCH_THRESHOLD);
+    }
+
+    #[test]
+    fn tolerates_a_short_offset() {
+        let samples = melody(7, 22050, 1, 6.0);
+        let full = compute(&samples, 1, 22050);
+        let delayed = compute(&samples[5512..], 1, 22050);
+
+        assert!(full.similarity(&delayed) >= MATCH_THRESHOLD);
+    }
+
+    #[test]
+    fn different_recordings_do_not_match() {
+        let a = compute(&melody(7, 22050, 1, 6.0), 1, 22050);
+        let b = compute(&melody(99, 22050, 1, 6.0), 1, 22050);
+
+        assert!(a.similarity(&b) < MATCH_THRESHOLD);
+    }
+
+    #[test]
+    fn too_little_overlap_scores_zero() {
+        let a = compute(&melody(7, 22050, 1, 0.5), 1, 22050);
+        assert_eq!(a.similarity(&a), 0.0);
+    }
+}
@@ -0,0 +1,278 @@
+//! Publish a local playlist as an NCM playlist
+//!
+//! Every song is looked up on NCM by its tags. Songs linked before through
+//! "Find this on NCM" keep their link, a search result whose metadata agrees
+//! almost exactly is taken as is, and close calls are left for the user to
+//! pick from. Songs left without a match are reported once the playlist has
+//! been created.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use super::ncm_match::{self, LocalTrack, METADATA_ONLY_SCORE};
+use crate::api::ncm_api::SearchType;
+use crate::api::{NcmClient, SongInfo};
+
+/// Search results considered per song
+const SEARCH_LIMIT: u32 = 10;
+/// Results offered when the match is a close call
+const MAX_CHOICES: usize = 3;
+/// Below this a result only shares the length, not the song
+const MIN_PLAUSIBLE_SCORE: f32 = 0.5;
+/// Tracks added per request; NCM puts each batch first in the playlist
+const ADD_BATCH: usize = 200;
+
+/// How a local song maps to NCM
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackMatch {
+    /// Linked before, or a result that clearly is the song
+    Matched { id: u64, label: String },
+    /// Plausible results for the user to pick from; `chosen` indexes them
+    Ambiguous {
+        candidates: Vec<SongInfo>,
+        chosen: Option<usize>,
+    },
+    /// Nothing on NCM fits
+    Unmatched,
+}
+
+/// A song of the local playlist and its NCM match
+#[derive(Debug, Clone)]
+pub struct PublishTrack {
+    /// "Title - Artist" of the local song
+    pub label: String,
+    pub result: TrackMatch,
+}
+
+/// Matches for the songs of a local playlist, in playlist order
+#[derive(Debug, Clone, Default)]
+pub struct PublishPlan {
+    pub name: String,
+    /// Songs in the local playlist
+    pub total: usize,
+    /// Songs looked up so far
+    pub tracks: Vec<PublishTrack>,
+}
+
+impl PublishPlan {
+    pub fn new(name: String, total: usize) -> Self {
+        Self {
+            name,
+            total,
+            tracks: Vec::with_capacity(total),
+        }
+    }
+
+    /// Every song has been looked up
+    pub fn is_complete(&self) -> bool {
+        self.tracks.len() >= self.total
+    }
+
+    /// Pick one of a close call's results, or none to leave the song out
+    pub fn choose(&mut self, track: usize, choice: Option<usize>) {
+        if let Some(PublishTrack {
+            result: TrackMatch::Ambiguous { candidates, chosen },
+            ..
+        }) = self.tracks.get_mut(track)
+        {
+            *chosen = choice.filter(|i| *i < candidates.len());
+        }
+    }
+
+    /// Close calls the user hasn't picked a result for
+    pub fn undecided(&self) -> usize {
+        self.tracks
+            .iter()
+            .filter(|t| matches!(t.result, TrackMatch::Ambiguous { chosen: None, .. }))
+            .count()
+    }
+
+    /// NCM IDs to publish in playlist order, each song once
+    pub fn track_ids(&self) -> Vec<u64> {
+        let mut seen = HashSet::new();
+        self.tracks
+            .iter()
+            .filter_map(|track| match &track.result {
+                TrackMatch::Matched { id, .. } => Some(*id),
+                TrackMatch::Ambiguous {
+                    candidates,
+                    chosen: Some(i),
+                } => candidates.get(*i).map(|song| song.id),
+                _ => None,
+            })
+            .filter(|id| seen.insert(*id))
+            .collect()
+    }
+
+    /// Songs that won't be published: no match, or a close call left undecided
+    pub fn unmatched(&self) -> Vec<String> {
+        self.tracks
+            .iter()
+            .filter(|track| {
+                matches!(
+                    track.result,
+                    TrackMatch::Unmatched | TrackMatch::Ambiguous { chosen: None, .. }
+                )
+            })
+            .map(|track| track.label.clone())
+            .collect()
+    }
+}
+
+/// Sort ranked search results into a match, a close call or nothing
+pub fn classify(ranked: Vec<(SongInfo, f32)>) -> TrackMatch {
+    let mut plausible = ranked
+        .into_iter()
+        .filter(|(_, score)| *score >= MIN_PLAUSIBLE_SCORE);
+    let Some((best, score)) = plausible.next() else {
+        return TrackMatch::Unmatched;
+    };
+    if score >= METADATA_ONLY_SCORE {
+        return TrackMatch::Matched {
+            id: best.id,
+            label: song_label(&best),
+        };
+    }
+    TrackMatch::Ambiguous {
+        candidates: std::iter::once(best)
+            .chain(plausible.map(|(song, _)| song))
+            .take(MAX_CHOICES)
+            .collect(),
+        chosen: None,
+    }
+}
+
+/// Look a local song up on NCM
+pub async fn match_track(client: &NcmClient, track: LocalTrack) -> Result<TrackMatch> {
+    let keywords = ncm_match::search_keywords(&track);
+    if keywords.is_empty() {
+        return Ok(TrackMatch::Unmatched);
+    }
+
+    let response = client
+        .client
+        .search(&keywords, SearchType::Songs, SEARCH_LIMIT, 0)
+        .await?;
+    Ok(classify(ncm_match::rank_candidates(&track, response.songs)))
+}
+
+/// Create the NCM playlist and add the tracks in their local order
+pub async fn publish(client: &NcmClient, name: &str, track_ids: &[u64]) -> Result<u64> {
+    let playlist_id = client.client.playlist_create(name, false).await?;
+    // Each batch lands above the ones added before it, so add the last first
+    for batch in track_ids.chunks(ADD_BATCH).rev() {
+        client
+            .client
+            .playlist_add_tracks(playlist_id, batch)
+            .await?;
+    }
+    Ok(playlist_id)
+}
+
+/// "Title - Artist" as shown in lists
+pub fn song_label(song: &SongInfo) -> String {
+    if song.singer.is_empty() {
+        song.name.clone()
+    } else {
+        format!("{} - {}", song.name, song.singer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: u64) -> SongInfo {
+        SongInfo {
+            id,
+            name: format!("Song {}", id),
+            singer: "Artist".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn track(label: &str, result: TrackMatch) -> PublishTrack {
+        PublishTrack {
+            label: label.to_string(),
+            result,
+        }
+    }
+
+    #[test]
+    fn confident_best_result_is_matched() {
+        let result = classify(vec![(song(1), 1.0), (song(2), 0.98)]);
+        assert_eq!(
+            result,
+            TrackMatch::Matched {
+                id: 1,
+                label: "Song 1 - Artist".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn close_calls_offer_plausible_results() {
+        let result = classify(vec![
+            (song(1), 0.8),
+            (song(2), 0.7),
+            (song(3), 0.6),
+            (song(4), 0.55),
+            (song(5), 0.4),
+        ]);
+        let TrackMatch::Ambiguous { candidates, chosen } = result else {
+            panic!("expected a close call");
+        };
+        assert_eq!(chosen, None);
+        let ids: Vec<u64> = candidates.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn length_alone_is_unmatched() {
+        assert_eq!(classify(vec![(song(1), 0.4)]), TrackMatch::Unmatched);
+        assert_eq!(classify(Vec::new()), TrackMatch::Unmatched);
+    }
+
+    #[test]
+    fn plan_publishes_matches_and_choices_once() {
+        let mut plan = PublishPlan::new("Mix".to_string(), 4);
+        plan.tracks = vec![
+            track(
+                "a",
+                TrackMatch::Matched {
+                    id: 1,
+                    label: String::new(),
+                },
+            ),
+            track(
+                "b",
+                TrackMatch::Ambiguous {
+                    candidates: vec![song(2), song(3)],
+                    chosen: None,
+                },
+            ),
+            track("c", TrackMatch::Unmatched),
+            track(
+                "d",
+                TrackMatch::Matched {
+                    id: 1,
+                    label: String::new(),
+                },
+            ),
+        ];
+        assert!(plan.is_complete());
+        assert_eq!(plan.undecided(), 1);
+        assert_eq!(plan.track_ids(), vec![1]);
+        assert_eq!(plan.unmatched(), vec!["b", "c"]);
+
+        plan.choose(1, Some(1));
+        assert_eq!(plan.undecided(), 0);
+        assert_eq!(plan.track_ids(), vec![1, 3]);
+        assert_eq!(plan.unmatched(), vec!["c"]);
+
+        // Out of range picks leave the song out
+        plan.choose(1, Some(5));
+        assert_eq!(plan.track_ids(), vec![1]);
+    }
+}
@@ -0,0 +1,202 @@
+//! Now-playing output files for streaming software
+//!
+//! Writes the current track into a folder OBS and similar tools can read
+//! text and image sources from: one file per field, a combined line, a JSON
+//! document, and optionally the artwork as a small PNG. Every file is written
+//! next to its final name and renamed into place, so a source never reads a
+//! half-written file.
+//!
+//! Writes are numbered as they are asked for and one that a newer write has
+//! overtaken is dropped, so a slow cover fetch for an earlier song can't
+//! overwrite the files of the song after it.
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use image::imageops::FilterType;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::database::DbSong;
+
+/// Edge length of the artwork PNG
+const ARTWORK_SIZE: u32 = 300;
+
+/// File the artwork is written to
+pub const ARTWORK_FILE: &str = "artwork.png";
+
+/// Latest text write asked for
+static TEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Latest artwork write asked for, counted apart so a play/pause doesn't
+/// drop the artwork of a song change still fetching its cover
+static ARTWORK_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Held while files are replaced, so two writers never share a temp file
+static WRITING: Mutex<()> = Mutex::new(());
+
+/// Number the next text write, making earlier ones stale
+pub fn next_generation() -> u64 {
+    TEXT_GENERATION.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+/// Number the next artwork write, making earlier ones stale
+pub fn next_artwork_generation() -> u64 {
+    ARTWORK_GENERATION.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+/// Folder the files go to unless another is picked
+pub fn default_dir() -> PathBuf {
+    crate::utils::data_dir().join("now-playing")
+}
+
+/// What the files describe
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct NowPlaying {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub duration_secs: i64,
+    /// Cover path or URL the artwork came from
+    pub cover: Option<String>,
+    pub playing: bool,
+}
+
+impl NowPlaying {
+    pub fn new(song: &DbSong, playing: bool) -> Self {
+        Self {
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            album: song.album.clone(),
+            duration_secs: song.duration_secs,
+            cover: song.cover_path.clone(),
+            playing,
+        }
+    }
+
+    /// "Artist - Title", or just the title if the artist is unknown
+    fn line(&self) -> String {
+        if self.artist.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} - {}", self.artist, self.title)
+        }
+    }
+}
+
+/// Contents of each text file; all empty when nothing is playing
+fn text_files(now_playing: Option<&NowPlaying>) -> Result<Vec<(&'static str, String)>> {
+    let Some(np) = now_playing else {
+        return Ok(["title.txt", "artist.txt", "album.txt", "now_playing.txt"]
+            .into_iter()
+            .map(|name| (name, String::new()))
+            .chain([("now_playing.json", "{}".to_string())])
+            .collect());
+    };
+    Ok(vec![
+        ("title.txt", np.title.clone()),
+        ("artist.txt", np.artist.clone()),
+        ("album.txt", np.album.clone()),
+        ("now_playing.txt", np.line()),
+        ("now_playing.json", serde_json::to_string_pretty(np)?),
+    ])
+}
+
+/// Replace `path` with `content` in one step
+fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    std::fs::write(&temp, content).with_context(|| format!("Failed to write {:?}", temp))?;
+    std::fs::rename(&temp, path).with_context(|| format!("Failed to replace {:?}", path))
+}
+
+/// Write the text and JSON files for `now_playing` into `dir`, unless a
+/// newer write than `generation` was asked for
+pub fn write(dir: &Path, now_playing: Option<&NowPlaying>, generation: u64) -> Result<()> {
+    let _writing = WRITING.lock();
+    if TEXT_GENERATION.load(Ordering::SeqCst) != generation {
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    for (name, content) in text_files(now_playing)? {
+        write_atomic(&dir.join(name), content.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Write the artwork PNG into `dir`, transparent when there is no usable
+/// cover, unless a newer artwork write than `generation` was asked for
+pub fn write_artwork(dir: &Path, cover: Option<&[u8]>, generation: u64) -> Result<()> {
+    let artwork = match cover.map(image::load_from_memory) {
+        Some(Ok(cover)) => cover.resize_to_fill(ARTWORK_SIZE, ARTWORK_SIZE, FilterType::Triangle),
+        Some(Err(e)) => {
+            tracing::warn!("Unreadable cover for now-playing artwork: {}", e);
+            image::DynamicImage::new_rgba8(1, 1)
+        }
+        // Keeps the image source in place between songs
+        None => image::DynamicImage::new_rgba8(1, 1),
+    };
+    let mut png = Vec::new();
+    artwork.write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)?;
+
+    let _writing = WRITING.lock();
+    if ARTWORK_GENERATION.load(Ordering::SeqCst) != generation {
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    write_atomic(&dir.join(ARTWORK_FILE), &png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_files() {
+        let np = NowPlaying {
+            title: "Song".to_string(),
+            artist: "Artist".to_string(),
+            ..Default::default()
+        };
+        let files = text_files(Some(&np)).unwrap();
+        assert!(files.contains(&("now_playing.txt", "Artist - Song".to_string())));
+        assert!(files.contains(&("album.txt", String::new())));
+
+        let json = &files
+            .iter()
+            .find(|(name, _)| *name == "now_playing.json")
+            .unwrap()
+            .1;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(value["title"], "Song");
+    }
+
+    #[test]
+    fn test_stale_write_is_dropped() {
+        let dir = std::env::temp_dir().join(format!("rustle-now-playing-{}", std::process::id()));
+        let np = NowPlaying {
+            title: "Old".to_string(),
+            ..Default::default()
+        };
+        let stale = next_generation();
+        let latest = next_generation();
+        write(&dir, None, latest).unwrap();
+        write(&dir, Some(&np), stale).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("title.txt")).unwrap(), "");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_nothing_playing_clears_files() {
+        let files = text_files(None).unwrap();
+        assert_eq!(files.len(), 5);
+        assert!(
+            files
+                .iter()
+                .all(|(name, content)| content.is_empty() || *name == "now_playing.json")
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! Availability check for NCM playlists
+//!
+//! Songs are flagged from the privilege NCM sends with song details: greyed
+//! out or taken down, VIP only for listeners without VIP, or sold as part of
+//! a paid album. Flagged songs can be removed, swapped for another upload of
+//! the same recording that plays, or written out as a list.
+
+use anyhow::Result;
+use tracing::warn;
+
+use super::ncm_match::{self, LocalTrack, METADATA_ONLY_SCORE};
+use crate::api::ncm_api::SearchType;
+use crate::api::{NcmClient, SongCopyright, SongInfo};
+use crate::i18n::{Key, Locale};
+
+/// Search results considered when looking for another upload
+const SEARCH_LIMIT: u32 = 10;
+
+/// Why a song can't be played in full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackProblem {
+    /// Greyed out or removed from NCM
+    Unavailable,
+    /// Needs VIP, which the listener doesn't have
+    VipOnly,
+    /// Part of an album sold separately
+    Paid,
+}
+
+impl TrackProblem {
+    pub fn of(copyright: &SongCopyright, is_vip: bool) -> Option<Self> {
+        match copyright {
+            SongCopyright::Unavailable => Some(Self::Unavailable),
+            SongCopyright::VipOnly | SongCopyright::VipOnlyHighRate if !is_vip => {
+                Some(Self::VipOnly)
+            }
+            SongCopyright::Payment => Some(Self::Paid),
+            _ => None,
+        }
+    }
+
+    /// Get display name for this problem
+    pub fn display_name(&self, locale: Locale) -> &'static str {
+        match self {
+            Self::Unavailable => locale.get(Key::HealthUnavailable),
+            Self::VipOnly => locale.get(Key::HealthVipOnly),
+            Self::Paid => locale.get(Key::HealthPaid),
+        }
+    }
+}
+
+/// A playlist song that won't play
+#[derive(Debug, Clone)]
+pub struct FlaggedTrack {
+    pub song: SongInfo,
+    pub problem: TrackProblem,
+}
+
+/// Result of checking a playlist
+#[derive(Debug, Clone, Default)]
+pub struct HealthReport {
+    /// Every track ID of the playlist, in order
+    pub track_ids: Vec<u64>,
+    /// Flagged songs in playlist order
+    pub flagged: Vec<FlaggedTrack>,
+}
+
+impl HealthReport {
+    /// Flag the songs of a playlist; `track_ids` also covers songs NCM
+    /// returned no details for, which are treated as removed
+    pub fn check(track_ids: Vec<u64>, songs: &[SongInfo], is_vip: bool) -> Self {
+        let flagged = track_ids
+            .iter()
+            .filter_map(|id| match songs.iter().find(|song| song.id == *id) {
+                Some(song) => {
+                    TrackProblem::of(&song.copyright, is_vip).map(|problem| FlaggedTrack {
+                        song: song.clone(),
+                        problem,
+                    })
+                }
+                None => Some(FlaggedTrack {
+                    song: SongInfo {
+                        id: *id,
+                        ..Default::default()
+                    },
+                    problem: TrackProblem::Unavailable,
+                }),
+            })
+            .collect();
+        Self { track_ids, flagged }
+    }
+
+    pub fn count(&self, problem: TrackProblem) -> usize {
+        self.flagged.iter().filter(|t| t.problem == problem).count()
+    }
+
+    /// Songs that can't be played by anyone
+    pub fn unavailable_ids(&self) -> Vec<u64> {
+        self.flagged
+            .iter()
+            .filter(|t| t.problem == TrackProblem::Unavailable)
+            .map(|t| t.song.id)
+            .collect()
+    }
+
+    /// Track order with replaced songs swapped for their alternates
+    pub fn order_with(&self, replacements: &[(u64, u64)]) -> Vec<u64> {
+        self.track_ids
+            .iter()
+            .map(|id| {
+                replacements
+                    .iter()
+                    .find(|(old, _)| old == id)
+                    .map_or(*id, |(_, new)| *new)
+            })
+            .collect()
+    }
+
+    /// One line per flagged song, with the reason from `reason`
+    pub fn export_text(&self, reason: impl Fn(TrackProblem) -> &'static str) -> String {
+        self.flagged
+            .iter()
+            .map(|t| {
+                let song = &t.song;
+                let title = if song.name.is_empty() {
+                    format!("#{}", song.id)
+                } else if song.singer.is_empty() {
+                    song.name.clone()
+                } else {
+                    format!("{} - {}", song.name, song.singer)
+                };
+                let album = if song.album.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", song.album)
+                };
+                format!("{}{}\t{}\n", title, album, reason(t.problem))
+            })
+            .collect()
+    }
+}
+
+/// Pick another upload of the same recording from search results
+///
+/// Results are expected with their privilege, see `find_alternate`.
+pub fn pick_alternate(song: &SongInfo, results: Vec<SongInfo>, is_vip: bool) -> Option<SongInfo> {
+    let track = LocalTrack {
+        path: Default::default(),
+        title: song.name.clone(),
+        artist: song.singer.clone(),
+        duration_secs: song.duration / 1000,
+    };
+    ncm_match::rank_candidates(&track, results)
+        .into_iter()
+        .filter(|(_, score)| *score >= METADATA_ONLY_SCORE)
+        .map(|(result, _)| result)
+        .find(|result| {
+            result.id != song.id
+                && result.copyright != SongCopyright::Unknown
+                && TrackProblem::of(&result.copyright, is_vip).is_none()
+        })
+}
+
+/// Look for another upload of a flagged song that plays
+pub async fn find_alternate(
+    client: &NcmClient,
+    song: &SongInfo,
+    is_vip: bool,
+) -> Result<Option<SongInfo>> {
+    if song.name.is_empty() {
+        return Ok(None);
+    }
+    let keywords = format!("{} {}", song.name, song.singer);
+    let response = client
+        .client
+        .search(keywords.trim(), SearchType::Songs, SEARCH_LIMIT, 0)
+        .await?;
+    let ids: Vec<u64> = response
+        .songs
+        .iter()
+        .map(|result| result.id)
+        .filter(|id| *id != song.id)
+        .collect();
+    if ids.is_empty() {
+        return Ok(None);
+    }
+    // Search results carry no privilege; details do
+    let results = client.client.song_detail(&ids).await?;
+    Ok(pick_alternate(song, results, is_vip))
+}
+
+/// Swap flagged songs for other versions in place, returning how many were
+/// swapped
+pub async fn replace_flagged(
+    client: &NcmClient,
+    playlist_id: u64,
+    report: &HealthReport,
+    is_vip: bool,
+) -> Result<usize> {
+    let mut replacements: Vec<(u64, u64)> = Vec::new();
+    for flagged in &report.flagged {
+        match find_alternate(client, &flagged.song, is_vip).await {
+            // A version already in the playlist would end up there twice
+            Ok(Some(alternate))
+                if !report.track_ids.contains(&alternate.id)
+                    && !replacements.iter().any(|(_, new)| *new == alternate.id) =>
+            {
+                replacements.push((flagged.song.id, alternate.id));
+            }
+            Ok(_) => {}
+            Err(e) => warn!(
+                "Failed to look for another version of {}: {}",
+                flagged.song.id, e
+            ),
+        }
+    }
+    if replacements.is_empty() {
+        return Ok(0);
+    }
+
+    let (old, new): (Vec<u64>, Vec<u64>) = replacements.iter().copied().unzip();
+    client.client.playlist_add_tracks(playlist_id, &new).await?;
+    client
+        .client
+        .playlist_remove_tracks(playlist_id, &old)
+        .await?;
+    // Added songs land first; put each where the song it replaces was
+    client
+        .client
+        .playlist_order_tracks(playlist_id, &report.order_with(&replacements))
+        .await?;
+    Ok(replacements.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: u64, name: &str, copyright: SongCopyright) -> SongInfo {
+        SongInfo {
+            id,
+            name: name.to_string(),
+            singer: "Artist".to_string(),
+            duration: 200_000,
+            copyright,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn vip_songs_are_flagged_only_without_vip() {
+        assert_eq!(
+            TrackProblem::of(&SongCopyright::VipOnly, false),
+            Some(TrackProblem::VipOnly)
+        );
+        assert_eq!(TrackProblem::of(&SongCopyright::VipOnly, true), None);
+        assert_eq!(
+            TrackProblem::of(&SongCopyright::Unavailable, true),
+            Some(TrackProblem::Unavailable)
+        );
+        assert_eq!(TrackProblem::of(&SongCopyright::Unknown, false), None);
+    }
+
+    #[test]
+    fn songs_without_details_count_as_removed() {
+        let songs = vec![
+            song(1, "A", SongCopyright::Free),
+            song(2, "B", SongCopyright::Payment),
+        ];
+        let report = HealthReport::check(vec![1, 2, 3], &songs, false);
+        assert_eq!(report.count(TrackProblem::Paid), 1);
+        assert_eq!(report.unavailable_ids(), vec![3]);
+    }
+
+    #[test]
+    fn order_keeps_replacements_in_place() {
+        let report = HealthReport {
+            track_ids: vec![1, 2, 3],
+            flagged: Vec::new(),
+        };
+        assert_eq!(report.order_with(&[(2, 9)]), vec![1, 9, 3]);
+    }
+
+    #[test]
+    fn alternate_must_play_and_be_the_same_song() {
+        let flagged = song(1, "Song", SongCopyright::Unavailable);
+        let results = vec![
+            song(1, "Song", SongCopyright::Free),
+            song(2, "Other", SongCopyright::Free),
+            song(3, "Song", SongCopyright::VipOnly),
+            song(4, "Song (Live)", SongCopyright::Free),
+            song(5, "Song", SongCopyright::Free),
+        ];
+        let alternate = pick_alternate(&flagged, results.clone(), false);
+        assert_eq!(alternate.map(|s| s.id), Some(5));
+        let alternate = pick_alternate(&flagged, results, true);
+        assert_eq!(alternate.map(|s| s.id), Some(3));
+    }
+
+    #[test]
+    fn export_lists_each_flagged_song() {
+        let songs = vec![song(1, "A", SongCopyright::Unavailable)];
+        let report = HealthReport::check(vec![1, 2], &songs, false);
+        let text = report.export_text(|_| "gone");
+        assert_eq!(text, "A - Artist\tgone\n#2\tgone\n");
+    }
+}
@@ -0,0 +1,138 @@
+//! Incremental refresh of NCM playlists viewed before
+//!
+//! A fully loaded playlist is kept when the user leaves it. Opening it again
+//! shows the kept copy at once; only the playlist's track IDs are fetched, and
+//! the tracks added or removed since are patched into the kept rows instead of
+//! loading every song again.
+
+use std::collections::{HashMap, HashSet};
+
+/// Playlists kept for reopening
+const CAPACITY: usize = 8;
+
+/// Recently left playlists by NCM ID, the least recently left dropped first
+#[derive(Debug)]
+pub struct RecentPlaylists<T> {
+    entries: Vec<(u64, T)>,
+}
+
+impl<T> Default for RecentPlaylists<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> RecentPlaylists<T> {
+    /// Keep a playlist, replacing an older copy of it
+    pub fn insert(&mut self, playlist_id: u64, entry: T) {
+        self.entries.retain(|(id, _)| *id != playlist_id);
+        self.entries.push((playlist_id, entry));
+        if self.entries.len() > CAPACITY {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Take a kept playlist out; it is kept again when left
+    pub fn take(&mut self, playlist_id: u64) -> Option<T> {
+        let index = self.entries.iter().position(|(id, _)| *id == playlist_id)?;
+        Some(self.entries.remove(index).1)
+    }
+}
+
+/// Difference between the kept track list and the latest one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackDiff {
+    /// Tracks the kept copy doesn't have, in playlist order
+    pub added: Vec<u64>,
+    /// Kept tracks no longer in the playlist
+    pub removed: usize,
+}
+
+impl TrackDiff {
+    pub fn between(kept: &[u64], latest: &[u64]) -> Self {
+        let kept_ids: HashSet<u64> = kept.iter().copied().collect();
+        let latest_ids: HashSet<u64> = latest.iter().copied().collect();
+        Self {
+            added: latest
+                .iter()
+                .copied()
+                .filter(|id| !kept_ids.contains(id))
+                .collect(),
+            removed: kept_ids.difference(&latest_ids).count(),
+        }
+    }
+}
+
+/// Arrange kept and added items in the order of the latest track list
+///
+/// Items whose track was removed are dropped. Tracks without an item, such as
+/// added songs that failed to load, are skipped.
+pub fn arrange<T>(
+    kept: Vec<T>,
+    added: Vec<T>,
+    latest: &[u64],
+    id_of: impl Fn(&T) -> u64,
+) -> Vec<T> {
+    let mut by_id: HashMap<u64, T> = kept
+        .into_iter()
+        .chain(added)
+        .map(|item| (id_of(&item), item))
+        .collect();
+    latest.iter().filter_map(|id| by_id.remove(id)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_finds_added_in_playlist_order() {
+        let diff = TrackDiff::between(&[1, 2, 3], &[4, 1, 3, 5]);
+        assert_eq!(diff.added, vec![4, 5]);
+        assert_eq!(diff.removed, 1);
+    }
+
+    #[test]
+    fn reordering_is_not_a_change_in_tracks() {
+        let diff = TrackDiff::between(&[1, 2, 3], &[3, 2, 1]);
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.removed, 0);
+    }
+
+    #[test]
+    fn arrange_follows_latest_order() {
+        let kept = vec![(1, "a"), (2, "b"), (3, "c")];
+        let added = vec![(4, "d")];
+        let arranged = arrange(kept, added, &[4, 3, 1], |item| item.0);
+        assert_eq!(arranged, vec![(4, "d"), (3, "c"), (1, "a")]);
+    }
+
+    #[test]
+    fn arrange_skips_tracks_without_items() {
+        let arranged = arrange(vec![(1, "a")], Vec::new(), &[9, 1], |item| item.0);
+        assert_eq!(arranged, vec![(1, "a")]);
+    }
+
+    #[test]
+    fn least_recently_left_is_dropped() {
+        let mut recent = RecentPlaylists::default();
+        for id in 0..=CAPACITY as u64 {
+            recent.insert(id, id);
+        }
+        assert_eq!(recent.take(0), None);
+        assert_eq!(recent.take(CAPACITY as u64), Some(CAPACITY as u64));
+        assert_eq!(recent.take(CAPACITY as u64), None);
+    }
+
+    #[test]
+    fn keeping_again_replaces_the_old_copy() {
+        let mut recent = RecentPlaylists::default();
+        recent.insert(1, "old");
+        recent.insert(2, "other");
+        recent.insert(1, "new");
+        assert_eq!(recent.take(1), Some("new"));
+        assert_eq!(recent.take(1), None);
+    }
+}
@@ -0,0 +1,275 @@
+//! Plugin system
+//!
+//! Plugins are WebAssembly modules installed as folders under the plugins
+//! directory, each with a `plugin.json` manifest. Enabled plugins are
+//! instantiated in a sandboxed wasmtime runtime and can hook into:
+//! - `metadata`: fill in missing tags for imported local files
+//! - `lyrics`: provide lyrics when nothing local or cached was found
+//! - `audio_dsp`: process samples in the playback chain
+//! - `ui_panel`: show a small panel on the plugin manager page
+
+pub mod host;
+pub mod manifest;
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::audio::SampleProcessor;
+
+pub use host::PluginInstance;
+pub use manifest::{PluginHook, PluginManifest};
+
+/// Get the directory plugins are installed into
+pub fn plugins_dir() -> PathBuf {
+    crate::utils::data_dir().join("plugins")
+}
+
+/// Load state of a discovered plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginStatus {
+    Disabled,
+    Active,
+    Failed(String),
+}
+
+/// A plugin found in the plugins directory
+pub struct PluginEntry {
+    pub manifest: PluginManifest,
+    pub status: PluginStatus,
+    instance: Option<Arc<PluginInstance>>,
+}
+
+/// Track details passed to metadata and lyrics hooks
+#[derive(Debug, Clone, Serialize)]
+pub struct TrackQuery {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub duration_secs: i64,
+    pub path: Option<String>,
+}
+
+/// Tags returned by a metadata hook; missing fields are left unchanged
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MetadataPatch {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub year: Option<i64>,
+    pub genre: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LyricsResponse {
+    content: String,
+}
+
+/// Panel returned by a `ui_panel` hook
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PluginPanel {
+    pub title: String,
+    #[serde(default)]
+    pub lines: Vec<String>,
+    #[serde(default)]
+    pub actions: Vec<PanelAction>,
+}
+
+/// Button on a plugin panel
+#[derive(Debug, Clone, Deserialize)]
+pub struct PanelAction {
+    pub id: String,
+    pub label: String,
+}
+
+/// All discovered plugins and the instances of enabled ones
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<PluginEntry>,
+}
+
+impl std::fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginRegistry")
+            .field("plugins", &self.plugins.len())
+            .field("active", &self.active().count())
+            .finish()
+    }
+}
+
+impl PluginRegistry {
+    /// Discover plugins in `dir` and instantiate the enabled ones
+    ///
+    /// Compiling modules is slow, so call this from a blocking task.
+    pub fn load(dir: &Path, enabled: &[String]) -> Self {
+        let Ok(read_dir) = std::fs::read_dir(dir) else {
+            return Self::default();
+        };
+
+        let mut config = wasmtime::Config::new();
+        config.consume_fuel(true);
+        config.epoch_interruption(true);
+        let engine = match wasmtime::Engine::new(&config) {
+            Ok(engine) => engine,
+            Err(e) => {
+                tracing::error!("Failed to create plugin engine: {}", e);
+                return Self::default();
+            }
+        };
+        start_epoch_ticker(&engine);
+
+        let mut plugins = Vec::new();
+        for plugin_dir in read_dir.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+            let manifest = match PluginManifest::load(&plugin_dir) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    tracing::warn!("Skipping plugin folder {:?}: {:#}", plugin_dir, e);
+                    continue;
+                }
+            };
+
+            if plugins
+                .iter()
+                .any(|p: &PluginEntry| p.manifest.id == manifest.id)
+            {
+                tracing::warn!("Duplicate plugin id '{}' in {:?}", manifest.id, plugin_dir);
+                continue;
+            }
+
+            let (status, instance) = if !enabled.contains(&manifest.id) {
+                (PluginStatus::Disabled, None)
+            } else {
+                match PluginInstance::load(&engine, &manifest, &plugin_dir) {
+                    Ok(instance) => {
+                        tracing::info!("Loaded plugin {} {}", manifest.id, manifest.version);
+                        (PluginStatus::Active, Some(Arc::new(instance)))
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to load plugin {}: {:#}", manifest.id, e);
+                        (PluginStatus::Failed(format!("{:#}", e)), None)
+                    }
+                }
+            };
+
+            plugins.push(PluginEntry {
+                manifest,
+                status,
+                instance,
+            });
+        }
+
+        plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+        Self { plugins }
+    }
+
+    /// All discovered plugins, sorted by name
+    pub fn plugins(&self) -> &[PluginEntry] {
+        &self.plugins
+    }
+
+    fn active(&self) -> impl Iterator<Item = (&PluginManifest, &Arc<PluginInstance>)> {
+        self.plugins
+            .iter()
+            .filter_map(|p| p.instance.as_ref().map(|i| (&p.manifest, i)))
+    }
+
+    fn with_hook(
+        &self,
+        hook: PluginHook,
+    ) -> impl Iterator<Item = (&PluginManifest, &Arc<PluginInstance>)> {
+        self.active().filter(move |(m, _)| m.has_hook(hook))
+    }
+
+    /// Ask metadata plugins for missing tags, first answer wins
+    pub fn lookup_metadata(&self, query: &TrackQuery) -> Option<MetadataPatch> {
+        let input = serde_json::to_string(query).ok()?;
+        self.with_hook(PluginHook::Metadata)
+            .find_map(|(m, instance)| call_hook(m, instance, "rustle_metadata", &input))
+    }
+
+    /// Ask lyrics plugins for lyrics text in any supported format
+    pub fn lookup_lyrics(&self, query: &TrackQuery) -> Option<String> {
+        let input = serde_json::to_string(query).ok()?;
+        self.with_hook(PluginHook::Lyrics)
+            .find_map(|(m, instance)| {
+                call_hook::<LyricsResponse>(m, instance, "rustle_lyrics", &input)
+            })
+            .map(|response| response.content)
+            .filter(|content| !content.trim().is_empty())
+    }
+
+    /// Audio processors for enabled DSP plugins, in name order
+    pub fn audio_processors(&self) -> Vec<Arc<dyn SampleProcessor>> {
+        self.with_hook(PluginHook::AudioDsp)
+            .map(|(_, instance)| instance.clone() as Arc<dyn SampleProcessor>)
+            .collect()
+    }
+
+    /// Render the panels of all enabled UI plugins
+    pub fn panels(&self) -> Vec<(String, PluginPanel)> {
+        self.with_hook(PluginHook::UiPanel)
+            .filter_map(|(m, instance)| {
+                call_hook(m, instance, "rustle_panel", "{}").map(|panel| (m.id.clone(), panel))
+            })
+            .collect()
+    }
+
+    /// Forward a panel button press, returning the updated panel
+    pub fn panel_action(&self, plugin_id: &str, action_id: &str) -> Option<PluginPanel> {
+        let (manifest, instance) = self
+            .with_hook(PluginHook::UiPanel)
+            .find(|(m, _)| m.id == plugin_id)?;
+        let input = serde_json::json!({ "action": action_id }).to_string();
+        call_hook(manifest, instance, "rustle_panel_action", &input)
+    }
+}
+
+/// Advance the engine's epoch every [`host::EPOCH_TICK`] until it is dropped
+fn start_epoch_ticker(engine: &wasmtime::Engine) {
+    let engine = engine.weak();
+    let spawned = std::thread::Builder::new()
+        .name("plugin-epoch".into())
+        .spawn(move || {
+            while let Some(engine) = engine.upgrade() {
+                engine.increment_epoch();
+                drop(engine);
+                std::thread::sleep(host::EPOCH_TICK);
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::error!("Failed to start plugin epoch ticker: {}", e);
+    }
+}
+
+fn call_hook<T: serde::de::DeserializeOwned>(
+    manifest: &PluginManifest,
+    instance: &PluginInstance,
+    export: &str,
+    input: &str,
+) -> Option<T> {
+    match instance.call_json(export, input) {
+        Ok(Some(output)) => match serde_json::from_str(&output) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!(
+                    "Plugin {} returned invalid {} output: {}",
+                    manifest.id,
+                    export,
+                    e
+                );
+                None
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            tracing::warn!("Plugin {} {} failed: {:#}", manifest.id, export, e);
+            None
+        }
+    }
+}
+
+impl SampleProcessor for PluginInstance {
+    fn process(&self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        self.process_audio(samples, channels, sample_rate);
+    }
+}
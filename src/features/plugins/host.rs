@@ -0,0 +1,302 @@
+//! WebAssembly plugin host
+//!
+//! Guest ABI (all structured data is UTF-8 JSON in guest memory):
+//! - `memory`: exported linear memory
+//! - `rustle_alloc(len: i32) -> i32`: allocate `len` bytes for host input
+//! - `rustle_free(ptr: i32, len: i32)`: optional, releases host input/output buffers
+//! - JSON hooks take `(ptr, len)` and return an `i64` packed as `(ptr << 32) | len`,
+//!   or 0 for "no result": `rustle_metadata`, `rustle_lyrics`, `rustle_panel`,
+//!   `rustle_panel_action`
+//! - `rustle_process_audio(ptr: i32, samples: i32, channels: i32, sample_rate: i32)`
+//!   processes interleaved f32 samples in place
+//!
+//! Imports provided to the guest:
+//! - `rustle.log(ptr: i32, len: i32)`: write a message to the application log
+//!
+//! Besides fuel, calls are bounded in wall-clock time by epoch deadlines
+//! counted in [`EPOCH_TICK`]s, and each plugin's linear memory is capped.
+
+use anyhow::{Context, Result, bail};
+use parking_lot::Mutex;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use wasmtime::{
+    Caller, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, Trap,
+};
+
+use super::manifest::PluginManifest;
+
+/// How often the engine's epoch advances
+pub const EPOCH_TICK: Duration = Duration::from_millis(5);
+/// Fuel budget for a single JSON hook call
+const CALL_FUEL: u64 = 500_000_000;
+/// Epoch ticks a single JSON hook call may run for (5s)
+const CALL_TICKS: u64 = 1000;
+/// Fuel budget for processing one audio block
+const DSP_FUEL: u64 = 50_000_000;
+/// Epoch ticks one audio block may take before it is played unprocessed
+const DSP_TICKS: u64 = 2;
+/// How long the audio thread waits for a plugin busy with another hook
+const DSP_LOCK_WAIT: Duration = Duration::from_millis(2);
+/// Largest linear memory a plugin may grow to
+const MAX_MEMORY: usize = 256 * 1024 * 1024;
+/// Largest JSON response a hook may return
+const MAX_OUTPUT_LEN: usize = 16 * 1024 * 1024;
+/// Largest message a plugin may log in one call
+const MAX_LOG_LEN: usize = 64 * 1024;
+
+struct Runtime {
+    store: Store<StoreLimits>,
+    instance: Instance,
+    memory: Memory,
+    /// Guest buffer reused across audio blocks (ptr, capacity in bytes)
+    dsp_buffer: Option<(i32, usize)>,
+}
+
+/// A loaded and instantiated plugin module
+pub struct PluginInstance {
+    id: String,
+    runtime: Mutex<Runtime>,
+    /// Set after the DSP hook traps so a broken plugin stops touching audio
+    dsp_failed: AtomicBool,
+    /// Set while audio blocks time out and pass through unprocessed
+    dsp_bypassed: AtomicBool,
+}
+
+impl PluginInstance {
+    /// Compile and instantiate the plugin's WebAssembly module
+    pub fn load(engine: &Engine, manifest: &PluginManifest, plugin_dir: &Path) -> Result<Self> {
+        let wasm_path = manifest.entry_path(plugin_dir)?;
+        let module = Module::from_file(engine, &wasm_path)
+            .with_context(|| format!("Failed to compile {:?}", wasm_path))?;
+
+        let mut linker: Linker<StoreLimits> = Linker::new(engine);
+        let log_id = manifest.id.clone();
+        linker.func_wrap(
+            "rustle",
+            "log",
+            move |mut caller: Caller<'_, StoreLimits>, ptr: i32, len: i32| {
+                let Some(memory) = caller
+                    .get_export("memory")
+                    .and_then(|export| export.into_memory())
+                else {
+                    return;
+                };
+                let (ptr, len) = (ptr as u32 as usize, len as u32 as usize);
+                if let Err(e) = check_span(memory.data_size(&caller), ptr, len, MAX_LOG_LEN) {
+                    tracing::warn!("[plugin {}] log call ignored: {}", log_id, e);
+                    return;
+                }
+                let mut buf = vec![0u8; len];
+                if memory.read(&caller, ptr, &mut buf).is_ok() {
+                    tracing::info!("[plugin {}] {}", log_id, String::from_utf8_lossy(&buf));
+                }
+            },
+        )?;
+
+        let limits = StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build();
+        let mut store = Store::new(engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(CALL_FUEL)?;
+        store.set_epoch_deadline(CALL_TICKS);
+        let instance = linker.instantiate(&mut store, &module)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .context("Plugin does not export memory")?;
+        instance
+            .get_typed_func::<i32, i32>(&mut store, "rustle_alloc")
+            .context("Plugin does not export rustle_alloc")?;
+
+        Ok(Self {
+            id: manifest.id.clone(),
+            runtime: Mutex::new(Runtime {
+                store,
+                instance,
+                memory,
+                dsp_buffer: None,
+            }),
+            dsp_failed: AtomicBool::new(false),
+            dsp_bypassed: AtomicBool::new(false),
+        })
+    }
+
+    /// Call a JSON hook, returning the plugin's JSON response if it produced one
+    pub fn call_json(&self, export: &str, input: &str) -> Result<Option<String>> {
+        let mut rt = self.runtime.lock();
+        let instance = rt.instance;
+        let memory = rt.memory;
+        let store = &mut rt.store;
+
+        let Ok(func) = instance.get_typed_func::<(i32, i32), i64>(&mut *store, export) else {
+            return Ok(None);
+        };
+        store.set_fuel(CALL_FUEL)?;
+        store.set_epoch_deadline(CALL_TICKS);
+
+        let input_ptr = guest_alloc(store, instance, input.len())?;
+        memory.write(&mut *store, input_ptr as usize, input.as_bytes())?;
+        let packed = func.call(&mut *store, (input_ptr, input.len() as i32))?;
+        guest_free(store, instance, input_ptr, input.len());
+
+        if packed == 0 {
+            return Ok(None);
+        }
+
+        let (out_ptr, out_len) = unpack(packed);
+        check_span(memory.data_size(&*store), out_ptr, out_len, MAX_OUTPUT_LEN)?;
+        let mut output = vec![0u8; out_len];
+        memory.read(&*store, out_ptr, &mut output)?;
+        guest_free(store, instance, out_ptr as i32, out_len);
+
+        Ok(Some(String::from_utf8(output)?))
+    }
+
+    /// Run the audio DSP hook over interleaved samples in place
+    ///
+    /// Returns false when the plugin has failed before, or when the block
+    /// timed out waiting for or running the plugin; the block then plays
+    /// unprocessed, which is logged once per stretch of timeouts.
+    pub fn process_audio(&self, samples: &mut [f32], channels: u16, sample_rate: u32) -> bool {
+        if self.dsp_failed.load(Ordering::Relaxed) {
+            return false;
+        }
+        let Some(mut rt) = self.runtime.try_lock_for(DSP_LOCK_WAIT) else {
+            self.bypass("the plugin is busy with another hook");
+            return false;
+        };
+
+        // Samples are only copied back after the hook returns, so a block
+        // cut short by the deadline plays as it came
+        match run_dsp(&mut rt, samples, channels, sample_rate) {
+            Ok(()) => {
+                if self.dsp_bypassed.swap(false, Ordering::Relaxed) {
+                    tracing::info!("Plugin {} is processing audio again", self.id);
+                }
+                true
+            }
+            Err(e) if e.downcast_ref::<Trap>() == Some(&Trap::Interrupt) => {
+                self.bypass("an audio block ran past its deadline");
+                false
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Plugin {} audio processing failed, disabling DSP: {}",
+                    self.id,
+                    e
+                );
+                self.dsp_failed.store(true, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+
+    /// Note that audio is passing through unprocessed, once per stretch
+    fn bypass(&self, reason: &str) {
+        if !self.dsp_bypassed.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                "Plugin {} timed out, playing audio unprocessed: {}",
+                self.id,
+                reason
+            );
+        }
+    }
+}
+
+fn run_dsp(rt: &mut Runtime, samples: &mut [f32], channels: u16, sample_rate: u32) -> Result<()> {
+    let instance = rt.instance;
+    let memory = rt.memory;
+    let byte_len = std::mem::size_of_val(samples);
+
+    let func = instance
+        .get_typed_func::<(i32, i32, i32, i32), ()>(&mut rt.store, "rustle_process_audio")?;
+    rt.store.set_fuel(DSP_FUEL)?;
+    rt.store.set_epoch_deadline(DSP_TICKS);
+
+    let ptr = match rt.dsp_buffer {
+        Some((ptr, capacity)) if capacity >= byte_len => ptr,
+        previous => {
+            if let Some((ptr, capacity)) = previous {
+                guest_free(&mut rt.store, instance, ptr, capacity);
+            }
+            let ptr = guest_alloc(&mut rt.store, instance, byte_len)?;
+            rt.dsp_buffer = Some((ptr, byte_len));
+            ptr
+        }
+    };
+
+    memory.write(&mut rt.store, ptr as usize, bytemuck::cast_slice(samples))?;
+    func.call(
+        &mut rt.store,
+        (
+            ptr,
+            samples.len() as i32,
+            channels as i32,
+            sample_rate as i32,
+        ),
+    )?;
+    memory.read(&rt.store, ptr as usize, bytemuck::cast_slice_mut(samples))?;
+    Ok(())
+}
+
+fn guest_alloc(store: &mut Store<StoreLimits>, instance: Instance, len: usize) -> Result<i32> {
+    let alloc = instance.get_typed_func::<i32, i32>(&mut *store, "rustle_alloc")?;
+    let ptr = alloc.call(&mut *store, len as i32)?;
+    if ptr == 0 && len > 0 {
+        anyhow::bail!("Plugin failed to allocate {} bytes", len);
+    }
+    Ok(ptr)
+}
+
+fn guest_free(store: &mut Store<StoreLimits>, instance: Instance, ptr: i32, len: usize) {
+    if let Ok(free) = instance.get_typed_func::<(i32, i32), ()>(&mut *store, "rustle_free") {
+        let _ = free.call(&mut *store, (ptr, len as i32));
+    }
+}
+
+/// Check that the guest's `len` bytes at `ptr` lie inside its memory of
+/// `memory_size` bytes and within `cap`, before the host allocates for them
+fn check_span(memory_size: usize, ptr: usize, len: usize, cap: usize) -> Result<()> {
+    if len > cap {
+        bail!("buffer of {} bytes is over the {} byte limit", len, cap);
+    }
+    match ptr.checked_add(len) {
+        Some(end) if end <= memory_size => Ok(()),
+        _ => bail!(
+            "buffer of {} bytes at {} is outside plugin memory",
+            len,
+            ptr
+        ),
+    }
+}
+
+/// Split a packed `(ptr << 32) | len` return value
+fn unpack(packed: i64) -> (usize, usize) {
+    let packed = packed as u64;
+    ((packed >> 32) as usize, (packed & 0xFFFF_FFFF) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack() {
+        assert_eq!(unpack((1024_i64 << 32) | 16), (1024, 16));
+        assert_eq!(
+            unpack(((0x8000_0000_u64 << 32) | 4) as i64),
+            (0x8000_0000, 4)
+        );
+    }
+
+    #[test]
+    fn test_check_span() {
+        assert!(check_span(65536, 1024, 16, MAX_OUTPUT_LEN).is_ok());
+        assert!(check_span(65536, 65520, 16, MAX_OUTPUT_LEN).is_ok());
+        // Past the end of memory, or wrapping around
+        assert!(check_span(65536, 65521, 16, MAX_OUTPUT_LEN).is_err());
+        assert!(check_span(65536, usize::MAX, 16, MAX_OUTPUT_LEN).is_err());
+        // Inside memory but over the cap
+        assert!(check_span(usize::MAX, 0, MAX_LOG_LEN + 1, MAX_LOG_LEN).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Plugin manifest (`plugin.json`) parsing
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// File name of the manifest inside each plugin folder
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// Extension points a plugin can register for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginHook {
+    /// Fill in missing tags for local files during import
+    Metadata,
+    /// Provide lyrics when no local or cached lyrics exist
+    Lyrics,
+    /// Process decoded audio samples in the playback chain
+    AudioDsp,
+    /// Render a panel on the plugin manager page
+    UiPanel,
+}
+
+/// Plugin description read from `plugin.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Unique identifier, used as the key in settings
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    /// WebAssembly module file, relative to the plugin folder
+    #[serde(default = "default_entry")]
+    pub entry: String,
+    /// Hooks this plugin implements
+    #[serde(default)]
+    pub hooks: Vec<PluginHook>,
+}
+
+fn default_entry() -> String {
+    "plugin.wasm".to_string()
+}
+
+impl PluginManifest {
+    /// Parse and validate a manifest from JSON text
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: Self = serde_json::from_str(content).context("Invalid plugin manifest")?;
+
+        if manifest.id.trim().is_empty() {
+            anyhow::bail!("Plugin id must not be empty");
+        }
+        if manifest
+            .id
+            .chars()
+            .any(|c| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        {
+            anyhow::bail!("Plugin id '{}' contains invalid characters", manifest.id);
+        }
+        let mut components = Path::new(&manifest.entry).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            anyhow::bail!("Plugin entry must be a file inside the plugin folder");
+        }
+
+        Ok(manifest)
+    }
+
+    /// Load the manifest from a plugin folder
+    pub fn load(plugin_dir: &Path) -> Result<Self> {
+        let path = plugin_dir.join(MANIFEST_FILE);
+        let content =
+            std::fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        Self::parse(&content)
+    }
+
+    /// Resolve the entry module, making sure it doesn't lead out of the
+    /// plugin folder through a link
+    pub fn entry_path(&self, plugin_dir: &Path) -> Result<PathBuf> {
+        let dir = plugin_dir
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {:?}", plugin_dir))?;
+        let entry = plugin_dir.join(&self.entry);
+        let resolved = entry
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {:?}", entry))?;
+        if !resolved.starts_with(&dir) {
+            anyhow::bail!("Plugin entry must be a file inside the plugin folder");
+        }
+        Ok(resolved)
+    }
+
+    /// Check whether the plugin registered for a hook
+    pub fn has_hook(&self, hook: PluginHook) -> bool {
+        self.hooks.contains(&hook)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minimal_manifest() {
+        let manifest =
+            PluginManifest::parse(r#"{"id": "lrclib", "name": "LRCLIB", "version": "0.1.0"}"#)
+                .unwrap();
+        assert_eq!(manifest.entry, "plugin.wasm");
+        assert!(manifest.hooks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_hooks() {
+        let manifest = PluginManifest::parse(
+            r#"{"id": "x", "name": "X", "version": "1", "hooks": ["lyrics", "audio_dsp"]}"#,
+        )
+        .unwrap();
+        assert!(manifest.has_hook(PluginHook::Lyrics));
+        assert!(manifest.has_hook(PluginHook::AudioDsp));
+        assert!(!manifest.has_hook(PluginHook::UiPanel));
+    }
+
+    #[test]
+    fn test_reject_invalid_id() {
+        assert!(PluginManifest::parse(r#"{"id": "", "name": "X", "version": "1"}"#).is_err());
+        assert!(PluginManifest::parse(r#"{"id": "../x", "name": "X", "version": "1"}"#).is_err());
+    }
+
+    #[test]
+    fn test_reject_entry_outside_folder() {
+        let result = PluginManifest::parse(
+            r#"{"id": "x", "name": "X", "version": "1", "entry": "../evil.wasm"}"#,
+        );
+        assert!(result.is_err());
+
+        for entry in ["..", ".", "/", "/etc/passwd"] {
+            let json = format!(
+                r#"{{"id": "x", "name": "X", "version": "1", "entry": "{}"}}"#,
+                entry
+            );
+            assert!(PluginManifest::parse(&json).is_err(), "accepted {}", entry);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_entry_link_outside_folder() {
+        let root = std::env::temp_dir().join(format!("rustle_plugin_{}", std::process::id()));
+        let dir = root.join("plugin");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("plugin.wasm"), b"").unwrap();
+        std::fs::write(root.join("evil.wasm"), b"").unwrap();
+        let _ = std::fs::remove_file(dir.join("link.wasm"));
+        std::os::unix::fs::symlink(root.join("evil.wasm"), dir.join("link.wasm")).unwrap();
+
+        let manifest = |entry: &str| {
+            let json = format!(
+                r#"{{"id": "x", "name": "X", "version": "1", "entry": "{}"}}"#,
+                entry
+            );
+            PluginManifest::parse(&json).unwrap()
+        };
+        let inside = manifest("plugin.wasm").entry_path(&dir);
+        let outside = manifest("link.wasm").entry_path(&dir);
+        let _ = std::fs::remove_dir_all(&root);
+        assert!(inside.is_ok());
+        assert!(outside.is_err());
+    }
+}
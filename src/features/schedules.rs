@@ -0,0 +1,283 @@
+//! Scheduled playback
+//!
+//! A schedule starts a local playlist at a time of day, on chosen weekdays or
+//! just once, optionally raising the volume from silence over a few minutes
+//! like a wake-up alarm. Schedules are checked every few seconds for as long
+//! as the app runs, minimized to the tray included. One that was missed while
+//! the computer slept still goes off if it wakes within [`MISSED_GRACE_MINUTES`].
+
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDateTime, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{Key, Locale};
+
+/// How late a schedule may still go off
+pub const MISSED_GRACE_MINUTES: u32 = 10;
+
+/// Volume ramp lengths offered, in minutes (0 = start at full volume)
+pub const RAMP_MINUTES: [u32; 6] = [0, 1, 3, 5, 10, 15];
+
+/// Weekday keys, Monday first as in [`Schedule::days`]
+const WEEKDAY_KEYS: [Key; 7] = [
+    Key::WeekdayMon,
+    Key::WeekdayTue,
+    Key::WeekdayWed,
+    Key::WeekdayThu,
+    Key::WeekdayFri,
+    Key::WeekdaySat,
+    Key::WeekdaySun,
+];
+
+/// One scheduled playback
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Schedule {
+    pub enabled: bool,
+    /// Local playlist to play
+    pub playlist_id: Option<i64>,
+    pub hour: u32,
+    pub minute: u32,
+    /// Weekdays to repeat on, bit 0 for Monday; none for a one-off
+    pub days: u8,
+    /// Minutes to raise the volume from silence, 0 to start at full volume
+    pub ramp_minutes: u32,
+    /// Local date it last went off, as `2026-10-12`, so it goes off once a day
+    pub last_run: Option<String>,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            playlist_id: None,
+            hour: 7,
+            minute: 0,
+            days: 0b001_1111,
+            ramp_minutes: 5,
+            last_run: None,
+        }
+    }
+}
+
+impl Schedule {
+    /// Whether it repeats on weekday `day` (0 = Monday)
+    pub fn repeats_on(&self, day: u32) -> bool {
+        self.days & (1 << day) != 0
+    }
+
+    /// Copy with weekday `day` (0 = Monday) switched on or off
+    pub fn with_day(&self, day: u32, on: bool) -> Self {
+        let mut schedule = self.clone();
+        if on {
+            schedule.days |= 1 << day;
+        } else {
+            schedule.days &= !(1 << day);
+        }
+        schedule
+    }
+
+    /// Whether it should go off at local time `now`
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        if !self.enabled || self.playlist_id.is_none() {
+            return false;
+        }
+        let Some(occurrence) = self.last_occurrence(now) else {
+            return false;
+        };
+        if self.last_run.as_deref() == Some(occurrence.date().to_string().as_str()) {
+            return false;
+        }
+        if self.days != 0 && !self.repeats_on(occurrence.weekday().num_days_from_monday()) {
+            return false;
+        }
+        now - occurrence < TimeDelta::minutes(i64::from(MISSED_GRACE_MINUTES))
+    }
+
+    /// Record that it went off for the time due at `now`; one-offs switch
+    /// themselves off
+    pub fn mark_run(&mut self, now: NaiveDateTime) {
+        if let Some(occurrence) = self.last_occurrence(now) {
+            self.last_run = Some(occurrence.date().to_string());
+        }
+        if self.days == 0 {
+            self.enabled = false;
+        }
+    }
+
+    /// The last time of day it was set for at or before `now`: today's, or
+    /// yesterday's before that comes round, so a late evening schedule still
+    /// goes off in the grace minutes after midnight
+    fn last_occurrence(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        let time = NaiveTime::from_hms_opt(self.hour, self.minute, 0)?;
+        let today = now.date().and_time(time);
+        if today <= now {
+            Some(today)
+        } else {
+            Some(now.date().pred_opt()?.and_time(time))
+        }
+    }
+
+    /// Time of day as `07:30`
+    pub fn time_label(&self) -> String {
+        format!("{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// Short localized name of weekday `day` (0 = Monday)
+pub fn weekday_name(day: u32, locale: Locale) -> &'static str {
+    locale.get(WEEKDAY_KEYS[day as usize % 7])
+}
+
+/// Volume raised gradually to `target` after a schedule starts playing
+#[derive(Debug, Clone, Copy)]
+pub struct VolumeRamp {
+    pub target: f32,
+    pub duration: Duration,
+    pub started: iced::time::Instant,
+    /// Volume the ramp last set, to notice the user setting another
+    pub applied: f32,
+}
+
+impl VolumeRamp {
+    pub fn new(target: f32, minutes: u32) -> Self {
+        Self {
+            target,
+            duration: Duration::from_secs(u64::from(minutes) * 60),
+            started: iced::time::Instant::now(),
+            applied: 0.0,
+        }
+    }
+
+    /// Volume `elapsed` into the ramp
+    ///
+    /// Rises along a square curve, since loudness is heard roughly
+    /// logarithmically and a straight line jumps out of silence too fast.
+    pub fn volume_at(&self, elapsed: Duration) -> f32 {
+        if self.duration.is_zero() {
+            return self.target;
+        }
+        let progress = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+        self.target * progress * progress
+    }
+
+    /// Volume now and whether the ramp is over
+    pub fn step(&mut self) -> (f32, bool) {
+        let elapsed = self.started.elapsed();
+        self.applied = self.volume_at(elapsed);
+        (self.applied, elapsed >= self.duration)
+    }
+
+    /// Whether the player volume is no longer the one the ramp set, so it
+    /// was changed some other way and the ramp should leave it be
+    pub fn overridden(&self, volume: f32) -> bool {
+        (volume - self.applied).abs() > 0.005
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(date: (i32, u32, u32), hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(date.0, date.1, date.2)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn weekday_alarm() -> Schedule {
+        Schedule {
+            playlist_id: Some(1),
+            hour: 7,
+            minute: 30,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_due_on_chosen_weekdays_only() {
+        let schedule = weekday_alarm();
+        // 2026-10-12 is a Monday, 2026-10-17 a Saturday
+        assert!(schedule.is_due(at((2026, 10, 12), 7, 30)));
+        assert!(!schedule.is_due(at((2026, 10, 17), 7, 30)));
+        assert!(!schedule.is_due(at((2026, 10, 12), 7, 29)));
+    }
+
+    #[test]
+    fn test_goes_off_once_within_grace() {
+        let mut schedule = weekday_alarm();
+        let late = at((2026, 10, 12), 7, 30 + MISSED_GRACE_MINUTES - 1);
+        assert!(schedule.is_due(late));
+        assert!(!schedule.is_due(at((2026, 10, 12), 7, 30 + MISSED_GRACE_MINUTES)));
+
+        schedule.mark_run(late);
+        assert!(!schedule.is_due(late));
+        assert!(schedule.enabled);
+        assert!(schedule.is_due(at((2026, 10, 13), 7, 30)));
+    }
+
+    #[test]
+    fn test_one_off_switches_itself_off() {
+        let mut schedule = Schedule {
+            days: 0,
+            ..weekday_alarm()
+        };
+        let saturday = at((2026, 10, 17), 7, 31);
+        assert!(schedule.is_due(saturday));
+
+        schedule.mark_run(saturday);
+        assert!(!schedule.enabled);
+        assert!(!schedule.is_due(at((2026, 10, 18), 7, 30)));
+    }
+
+    #[test]
+    fn test_grace_runs_past_midnight() {
+        // Set for Friday night only
+        let mut schedule = Schedule {
+            hour: 23,
+            minute: 55,
+            days: 1 << 4,
+            ..weekday_alarm()
+        };
+        // Friday 2026-10-16 turning into Saturday
+        let after_midnight = at((2026, 10, 17), 0, 3);
+        assert!(schedule.is_due(after_midnight));
+        assert!(!schedule.is_due(at((2026, 10, 17), 0, 5)));
+        assert!(!schedule.is_due(at((2026, 10, 18), 0, 3)));
+
+        schedule.mark_run(after_midnight);
+        assert_eq!(schedule.last_run.as_deref(), Some("2026-10-16"));
+        assert!(!schedule.is_due(at((2026, 10, 17), 0, 4)));
+    }
+
+    #[test]
+    fn test_needs_a_playlist() {
+        let schedule = Schedule {
+            playlist_id: None,
+            ..weekday_alarm()
+        };
+        assert!(!schedule.is_due(at((2026, 10, 12), 7, 30)));
+    }
+
+    #[test]
+    fn test_ramp_rises_to_target() {
+        let ramp = VolumeRamp::new(0.8, 10);
+        assert_eq!(ramp.volume_at(Duration::ZERO), 0.0);
+        assert!((ramp.volume_at(Duration::from_secs(300)) - 0.2).abs() < 1e-6);
+        assert_eq!(ramp.volume_at(Duration::from_secs(900)), 0.8);
+        assert_eq!(VolumeRamp::new(0.8, 0).volume_at(Duration::ZERO), 0.8);
+    }
+
+    #[test]
+    fn test_ramp_notices_other_volume_changes() {
+        let mut ramp = VolumeRamp::new(0.8, 10);
+        assert!(!ramp.overridden(0.0));
+        let (volume, _) = ramp.step();
+        assert!(!ramp.overridden(volume));
+        assert!(ramp.overridden(0.5));
+    }
+}
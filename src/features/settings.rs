@@ -1,12 +1,23 @@
 //! Application settings persistence
 //!
-//! Handles saving and loading user preferences.
+//! Handles saving and loading user preferences. The file records the layout
+//! version it was written with, and files from older builds are upgraded
+//! step by step on load instead of being replaced with defaults.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use super::KeyBindings;
+use super::export::ExportFormat;
+use super::home_layout::HomeLayout;
+use super::hooks::EventHook;
+use super::lyrics_save::LyricsSaveTarget;
+use super::schedules::Schedule;
+use crate::i18n::{Key, Locale};
 
 /// Close behavior when clicking the X button
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -21,19 +32,21 @@ pub enum CloseBehavior {
     MinimizeToTray,
 }
 
-impl std::fmt::Display for CloseBehavior {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CloseBehavior::Ask => write!(f, "询问"),
-            CloseBehavior::Exit => write!(f, "退出"),
-            CloseBehavior::MinimizeToTray => write!(f, "最小化到托盘"),
-        }
-    }
-}
+/// Layout version of the settings file this build writes
+///
+/// Bump it together with a new step in [`MIGRATIONS`] whenever a setting is
+/// renamed, moved or loses its default.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Steps upgrading a settings file by one version, the first from version 0
+const MIGRATIONS: [fn(&mut Value); SETTINGS_VERSION as usize] = [fill_missing_sections];
 
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
+    /// Layout version of the file, 0 for files written before it was kept
+    #[serde(default)]
+    pub version: u32,
     /// What to do when closing the window
     pub close_behavior: CloseBehavior,
     /// Volume level (0.0 to 1.0)
@@ -53,6 +66,18 @@ pub struct Settings {
     /// Network settings
     #[serde(default)]
     pub network: NetworkSettings,
+    /// Plugin settings
+    #[serde(default)]
+    pub plugins: PluginSettings,
+    /// Commands and webhooks run on playback events
+    #[serde(default)]
+    pub hooks: Vec<EventHook>,
+    /// Playlists started at set times
+    #[serde(default)]
+    pub schedules: Vec<Schedule>,
+    /// NCM account settings
+    #[serde(default)]
+    pub account: AccountSettings,
 }
 
 /// Playback-related settings
@@ -81,12 +106,176 @@ pub struct PlaybackSettings {
     /// Music quality setting (0=128k, 1=192k, 2=320k, 3=SQ, 4=Hi-Res)
     #[serde(default = "default_music_quality")]
     pub music_quality: MusicQuality,
+    /// Continue playing the restored song on startup instead of staying paused
+    #[serde(default)]
+    pub resume_on_startup: bool,
+    /// Short sounds confirming likes, queue additions and errors
+    #[serde(default)]
+    pub ui_sounds: bool,
+    /// Where the Auto-DJ play mode finds songs, and how it blends them
+    #[serde(default)]
+    pub auto_dj: AutoDjSettings,
+    /// Seconds the skip-back button jumps
+    #[serde(default = "default_skip_back_secs")]
+    pub skip_back_secs: u32,
+    /// Seconds the skip-forward button jumps
+    #[serde(default = "default_skip_forward_secs")]
+    pub skip_forward_secs: u32,
+    /// Seconds the seek keys and the wheel over the seek bar jump
+    #[serde(default = "default_seek_step_secs")]
+    pub seek_step_secs: u32,
+    /// Volume percent the volume keys and the wheel over the player bar change
+    #[serde(default = "default_volume_step_percent")]
+    pub volume_step_percent: u32,
+    /// Keep playing similar songs when the queue ends
+    #[serde(default)]
+    pub autoplay_similar: bool,
+    /// Rewind a little when resuming after a long pause
+    #[serde(default)]
+    pub smart_resume: SmartResumeSettings,
+    /// Lower or pause playback while a call app records from the microphone
+    #[serde(default)]
+    pub call_ducking: CallDuckingSettings,
+    /// DSP stages per output device name, `""` for the system default
+    #[serde(default)]
+    pub dsp_presets: HashMap<String, DspPresets>,
+    /// Switch the output device to hi-res tracks' own sample rate instead
+    /// of resampling them
+    #[serde(default)]
+    pub match_sample_rate: bool,
+}
+
+/// Headphone DSP stages in the audio chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DspPresets {
+    /// Blend the stereo channels the way speakers do, for headphones
+    pub crossfeed: bool,
+    /// Low shelf boost below 100Hz
+    pub bass_boost: bool,
+    /// Compress loud parts and lift quiet ones
+    pub night_mode: bool,
+}
+
+/// Auto-DJ play mode settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoDjSettings {
+    /// Songs the queue is topped up from
+    pub pool: AutoDjPool,
+    /// Overlap between consecutive songs in seconds, 0 for none
+    pub crossfade_secs: u32,
+}
+
+impl Default for AutoDjSettings {
+    fn default() -> Self {
+        Self {
+            pool: AutoDjPool::LikedSongs,
+            crossfade_secs: 5,
+        }
+    }
+}
+
+/// Smart resume settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmartResumeSettings {
+    pub enabled: bool,
+    /// Pause length in minutes after which resuming rewinds
+    pub after_mins: u32,
+    /// Seconds replayed on resume
+    pub rewind_secs: u32,
+}
+
+impl Default for SmartResumeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            after_mins: 5,
+            rewind_secs: 3,
+        }
+    }
+}
+
+/// What happens to playback while a call is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CallAction {
+    #[default]
+    Off,
+    /// Lower the volume, restoring it afterwards
+    Lower,
+    /// Pause, resuming afterwards
+    Pause,
+}
+
+/// Call ducking settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CallDuckingSettings {
+    pub action: CallAction,
+    /// Volume percent kept while lowered
+    pub lower_percent: u32,
+    /// React to any app recording, not just known call apps
+    pub any_app: bool,
+    /// Seconds the microphone has to stay in use before playback reacts
+    pub after_secs: u32,
+}
+
+impl Default for CallDuckingSettings {
+    fn default() -> Self {
+        Self {
+            action: CallAction::Off,
+            lower_percent: 20,
+            any_app: false,
+            after_secs: 3,
+        }
+    }
+}
+
+impl SmartResumeSettings {
+    /// How far to rewind when resuming after a pause of `paused_for`
+    pub fn rewind_for(&self, paused_for: Duration) -> Duration {
+        if self.enabled && paused_for >= Duration::from_secs(self.after_mins as u64 * 60) {
+            Duration::from_secs(self.rewind_secs as u64)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Song pool the Auto-DJ draws from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoDjPool {
+    /// The NCM account's liked songs
+    #[default]
+    LikedSongs,
+    /// A local playlist
+    Playlist(i64),
+    /// NCM's songs similar to what is playing
+    SimilarSongs,
 }
 
 fn default_music_quality() -> MusicQuality {
     MusicQuality::High // 320k as default
 }
 
+fn default_skip_back_secs() -> u32 {
+    10
+}
+
+fn default_skip_forward_secs() -> u32 {
+    30
+}
+
+fn default_seek_step_secs() -> u32 {
+    5
+}
+
+fn default_volume_step_percent() -> u32 {
+    5
+}
+
 fn default_spectrum_decay() -> f32 {
     0.85
 }
@@ -135,13 +324,50 @@ impl MusicQuality {
         }
     }
 
+    /// Quality for an API rate value, the inverse of `to_api_rate`
+    pub fn from_api_rate(rate: u32) -> Option<Self> {
+        Self::all().into_iter().find(|q| q.to_api_rate() == rate)
+    }
+
+    /// Quality tier of a stream at `bitrate` bps; lossless FLAC tops out at
+    /// 1411.2 kbps, anything above is Hi-Res
+    pub fn from_bitrate(bitrate: u32) -> Self {
+        match bitrate {
+            ..=128_000 => Self::Standard,
+            ..=192_000 => Self::Higher,
+            ..=320_000 => Self::High,
+            ..=1_411_200 => Self::Lossless,
+            _ => Self::HiRes,
+        }
+    }
+
+    /// Qualities a track can be streamed at when its best stream is `bitrate`
+    pub fn available_up_to(bitrate: u32) -> Vec<Self> {
+        let best = Self::from_bitrate(bitrate).to_api_rate();
+        Self::all()
+            .into_iter()
+            .filter(|q| q.to_api_rate() <= best)
+            .collect()
+    }
+
+    /// Short label for the player bar badge
+    pub fn badge(&self) -> &'static str {
+        match self {
+            Self::Standard => "128k",
+            Self::Higher => "192k",
+            Self::High => "HQ",
+            Self::Lossless => "SQ",
+            Self::HiRes => "Hi-Res",
+        }
+    }
+
     /// Get display name for this quality
-    pub fn display_name(&self) -> &'static str {
+    pub fn display_name(&self, locale: Locale) -> &'static str {
         match self {
             Self::Standard => "128kbps",
             Self::Higher => "192kbps",
             Self::High => "320kbps",
-            Self::Lossless => "SQ (无损)",
+            Self::Lossless => locale.get(Key::MusicQualityLossless),
             Self::HiRes => "Hi-Res",
         }
     }
@@ -198,21 +424,21 @@ impl EqualizerPreset {
     }
 
     /// Get display name for the preset
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            EqualizerPreset::Flat => "平坦",
-            EqualizerPreset::Pop => "流行",
-            EqualizerPreset::Rock => "摇滚",
-            EqualizerPreset::Jazz => "爵士",
-            EqualizerPreset::Classical => "古典",
-            EqualizerPreset::Electronic => "电子",
-            EqualizerPreset::HipHop => "嘻哈",
-            EqualizerPreset::Acoustic => "原声",
-            EqualizerPreset::BassBoost => "低音增强",
-            EqualizerPreset::TrebleBoost => "高音增强",
-            EqualizerPreset::Vocal => "人声",
-            EqualizerPreset::Custom => "自定义",
-        }
+    pub fn display_name(&self, locale: Locale) -> &'static str {
+        locale.get(match self {
+            EqualizerPreset::Flat => Key::EqualizerPresetFlat,
+            EqualizerPreset::Pop => Key::EqualizerPresetPop,
+            EqualizerPreset::Rock => Key::EqualizerPresetRock,
+            EqualizerPreset::Jazz => Key::EqualizerPresetJazz,
+            EqualizerPreset::Classical => Key::EqualizerPresetClassical,
+            EqualizerPreset::Electronic => Key::EqualizerPresetElectronic,
+            EqualizerPreset::HipHop => Key::EqualizerPresetHipHop,
+            EqualizerPreset::Acoustic => Key::EqualizerPresetAcoustic,
+            EqualizerPreset::BassBoost => Key::EqualizerPresetBassBoost,
+            EqualizerPreset::TrebleBoost => Key::EqualizerPresetTrebleBoost,
+            EqualizerPreset::Vocal => Key::EqualizerPresetVocal,
+            EqualizerPreset::Custom => Key::EqualizerPresetCustom,
+        })
     }
 
     /// Get equalizer values for this preset
@@ -235,12 +461,6 @@ impl EqualizerPreset {
     }
 }
 
-impl std::fmt::Display for EqualizerPreset {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.display_name())
-    }
-}
-
 /// Display and interface settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisplaySettings {
@@ -251,6 +471,158 @@ pub struct DisplaySettings {
     /// Power saving mode - disables animations and uses simple rendering
     #[serde(default)]
     pub power_saving_mode: bool,
+    /// Lyrics sub-lines shown for songs without their own preference
+    #[serde(default)]
+    pub lyrics_sub_lines: LyricsSubLines,
+    /// Advanced lyrics animation tuning
+    #[serde(default)]
+    pub lyrics_tuning: LyricsTuning,
+    /// Sharing lyrics fixes on LRCLIB
+    #[serde(default)]
+    pub lyrics_share: LyricsShareSettings,
+    /// Saving fetched lyrics into local files
+    #[serde(default)]
+    pub lyrics_save: LyricsSaveSettings,
+    /// Background drawn behind the lyrics page
+    #[serde(default)]
+    pub lyrics_background: BackgroundStyle,
+    /// Blurred artwork behind playlist headers
+    #[serde(default)]
+    pub artwork_backdrop: ArtworkBackdrop,
+    /// Tint the playlist page with colors from its cover
+    #[serde(default = "default_true")]
+    pub cover_theming: bool,
+    /// Homepage section order and visibility
+    #[serde(default)]
+    pub home_layout: HomeLayout,
+}
+
+/// Background drawn behind the lyrics page, all built from the cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundStyle {
+    /// Cover colors flowing around a warped mesh
+    #[default]
+    Flow,
+    /// Soft blobs of cover colors drifting past each other
+    Mesh,
+    /// Cover colors stirred like a liquid
+    Fluid,
+    /// Stars flying out of a dark cover tint
+    Starfield,
+    /// The blurred cover, standing still
+    Blurred,
+}
+
+impl BackgroundStyle {
+    pub const ALL: [Self; 5] = [
+        Self::Flow,
+        Self::Mesh,
+        Self::Fluid,
+        Self::Starfield,
+        Self::Blurred,
+    ];
+}
+
+/// Blurred artwork backdrop behind playlist headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtworkBackdrop {
+    /// Plain palette gradient
+    #[default]
+    Off,
+    /// Backdrop drawn once, no animation frames
+    Still,
+    /// Backdrop slowly flowing like the lyrics page
+    Animated,
+}
+
+/// Which secondary lines the lyrics page shows under each original line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LyricsSubLines {
+    pub translation: bool,
+    pub romanization: bool,
+}
+
+impl Default for LyricsSubLines {
+    fn default() -> Self {
+        Self {
+            translation: true,
+            romanization: true,
+        }
+    }
+}
+
+/// Sharing edited lyrics files on LRCLIB
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LyricsShareSettings {
+    /// Point out lyrics files edited since they were last shared
+    pub offer: bool,
+    /// Name credited in shared lyrics; empty to share without one
+    pub credit: String,
+}
+
+impl Default for LyricsShareSettings {
+    fn default() -> Self {
+        Self {
+            offer: true,
+            credit: String::new(),
+        }
+    }
+}
+
+/// Saving fetched lyrics into local files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LyricsSaveSettings {
+    /// Offer to save lyrics shown for a local song that has none of its own
+    pub offer: bool,
+    pub target: LyricsSaveTarget,
+}
+
+impl Default for LyricsSaveSettings {
+    fn default() -> Self {
+        Self {
+            offer: true,
+            target: LyricsSaveTarget::Sidecar,
+        }
+    }
+}
+
+/// Fine-tuning for the lyrics highlight and scroll animation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LyricsTuning {
+    /// Width of the soft edge of the word highlight, in em
+    pub word_fade_width: f32,
+    /// Multiplier on the glow and lift of long words
+    pub emphasis_amount: f32,
+    /// Multiplier on the blur of long words
+    pub emphasis_blur: f32,
+    /// Stiffness of the line scroll spring
+    pub spring_stiffness: f32,
+    /// Damping of the line scroll spring
+    pub spring_damping: f32,
+    /// Vertical position of the current line (0.0 = top, 1.0 = bottom)
+    pub align_position: f32,
+    /// Seconds after a manual scroll before the lyrics return to the
+    /// current line
+    pub scroll_resume_delay: f32,
+}
+
+impl Default for LyricsTuning {
+    fn default() -> Self {
+        Self {
+            word_fade_width: 0.5,
+            emphasis_amount: 1.0,
+            emphasis_blur: 1.0,
+            spring_stiffness: 90.0,
+            spring_damping: 15.0,
+            align_position: 0.35,
+            scroll_resume_delay: 3.0,
+        }
+    }
 }
 
 /// Storage settings
@@ -260,6 +632,40 @@ pub struct StorageSettings {
     pub max_cache_mb: u64,
     /// Cache directory path
     pub cache_dir: Option<String>,
+    /// Last choices in the export dialog
+    #[serde(default)]
+    pub export: ExportSettings,
+}
+
+/// Song export choices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSettings {
+    pub format: ExportFormat,
+    /// Bitrate in kbps for lossy formats
+    pub bitrate_kbps: u32,
+    /// Folder songs are exported to, `None` for the default
+    pub folder: Option<String>,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Mp3,
+            bitrate_kbps: 320,
+            folder: None,
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Folder songs are exported to
+    pub fn dir(&self) -> PathBuf {
+        self.folder
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(super::export::default_dir)
+    }
 }
 
 /// System settings
@@ -269,6 +675,72 @@ pub struct SystemSettings {
     pub audio_output_device: Option<String>,
     /// Audio buffer size in samples
     pub audio_buffer_size: u32,
+    /// Launch the app when the user logs in
+    #[serde(default)]
+    pub launch_at_login: bool,
+    /// Start hidden in the system tray
+    #[serde(default)]
+    pub start_minimized: bool,
+    /// Desktop notification when a followed artist releases an album
+    #[serde(default)]
+    pub release_notifications: bool,
+    /// Release channel used by the updater
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
+    /// Check for updates on startup
+    #[serde(default = "default_true")]
+    pub auto_check_updates: bool,
+    /// Show feature highlights after an update
+    #[serde(default = "default_true")]
+    pub show_whats_new: bool,
+    /// Version that was running last time, kept by builds before the version
+    /// history moved to the database; only read to seed that history
+    #[serde(default)]
+    pub last_run_version: String,
+    /// Output latency in ms per output device name, `""` for the system default
+    #[serde(default)]
+    pub output_latency_ms: HashMap<String, u32>,
+    /// Now-playing files for streaming software
+    #[serde(default)]
+    pub now_playing: NowPlayingOutput,
+    /// Work out tempo, key and energy of new local songs in the background
+    #[serde(default = "default_true")]
+    pub analyze_songs: bool,
+    /// Allow listen mode to record what other apps are playing
+    #[serde(default)]
+    pub listen_mode: bool,
+}
+
+/// Now-playing files for OBS and other streaming software
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NowPlayingOutput {
+    pub enabled: bool,
+    /// Folder the files are written to, the default one if unset
+    pub directory: Option<String>,
+    /// Also write the artwork as a PNG
+    pub artwork: bool,
+}
+
+impl NowPlayingOutput {
+    /// Folder the files are written to
+    pub fn dir(&self) -> PathBuf {
+        self.directory
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(super::now_playing::default_dir)
+    }
+}
+
+/// Release channel for updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    /// Stable releases only
+    #[default]
+    Stable,
+    /// Stable releases and pre-releases
+    Beta,
 }
 
 /// Proxy type for network settings
@@ -288,18 +760,6 @@ pub enum ProxyType {
     System,
 }
 
-impl std::fmt::Display for ProxyType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ProxyType::None => write!(f, "无代理"),
-            ProxyType::Http => write!(f, "HTTP"),
-            ProxyType::Https => write!(f, "HTTPS"),
-            ProxyType::Socks5 => write!(f, "SOCKS5"),
-            ProxyType::System => write!(f, "系统代理"),
-        }
-    }
-}
-
 /// Network settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkSettings {
@@ -313,6 +773,40 @@ pub struct NetworkSettings {
     pub proxy_username: Option<String>,
     /// Proxy password (optional)
     pub proxy_password: Option<String>,
+    /// Download bandwidth cap in KB/s (0 = unlimited)
+    #[serde(default)]
+    pub bandwidth_limit_kbps: u32,
+    /// Whether to treat the connection as metered
+    #[serde(default)]
+    pub metered_mode: MeteredMode,
+}
+
+/// Metered connection mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MeteredMode {
+    /// Follow the system's connection cost (only reported on Windows)
+    #[default]
+    Auto,
+    /// Always metered
+    On,
+    /// Never metered
+    Off,
+}
+
+/// Plugin settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginSettings {
+    /// Ids of plugins the user has enabled
+    pub enabled: Vec<String>,
+}
+
+/// NCM account settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountSettings {
+    /// Sign in on mobile and web after login to claim the daily cloud beans
+    #[serde(default)]
+    pub daily_check_in: bool,
 }
 
 impl NetworkSettings {
@@ -361,6 +855,17 @@ impl NetworkSettings {
     }
 }
 
+/// Playback behaviour saved for a single playlist, applied when it is played
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaylistPlayback {
+    /// Switch to shuffle and start from a random song
+    pub shuffle: bool,
+    /// Streaming quality while the playlist plays, `None` for the global setting
+    pub quality: Option<MusicQuality>,
+    /// Add the songs after the current queue instead of replacing it
+    pub append_to_queue: bool,
+}
+
 /// Play mode for playback
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -374,17 +879,8 @@ pub enum PlayMode {
     LoopOne,
     /// Random order
     Shuffle,
-}
-
-impl std::fmt::Display for PlayMode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            PlayMode::Sequential => write!(f, "顺序播放"),
-            PlayMode::LoopAll => write!(f, "列表循环"),
-            PlayMode::LoopOne => write!(f, "单曲循环"),
-            PlayMode::Shuffle => write!(f, "随机播放"),
-        }
-    }
+    /// Keep the queue topped up from a song pool, crossfading between songs
+    AutoDj,
 }
 
 impl PlayMode {
@@ -394,24 +890,27 @@ impl PlayMode {
             PlayMode::Sequential => PlayMode::LoopAll,
             PlayMode::LoopAll => PlayMode::LoopOne,
             PlayMode::LoopOne => PlayMode::Shuffle,
-            PlayMode::Shuffle => PlayMode::Sequential,
+            PlayMode::Shuffle => PlayMode::AutoDj,
+            PlayMode::AutoDj => PlayMode::Sequential,
         }
     }
 
     /// Get display name for the mode
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            PlayMode::Sequential => "顺序播放",
-            PlayMode::LoopAll => "列表循环",
-            PlayMode::LoopOne => "单曲循环",
-            PlayMode::Shuffle => "随机播放",
-        }
+    pub fn display_name(&self, locale: Locale) -> &'static str {
+        locale.get(match self {
+            PlayMode::Sequential => Key::PlayModeSequential,
+            PlayMode::LoopAll => Key::PlayModeLoopAll,
+            PlayMode::LoopOne => Key::PlayModeLoopOne,
+            PlayMode::Shuffle => Key::PlayModeShuffle,
+            PlayMode::AutoDj => Key::PlayModeAutoDj,
+        })
     }
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            version: SETTINGS_VERSION,
             close_behavior: CloseBehavior::Ask,
             volume: 1.0,
             play_mode: PlayMode::Sequential,
@@ -421,6 +920,10 @@ impl Default for Settings {
             storage: StorageSettings::default(),
             system: SystemSettings::default(),
             network: NetworkSettings::default(),
+            plugins: PluginSettings::default(),
+            hooks: Vec::new(),
+            schedules: Vec::new(),
+            account: AccountSettings::default(),
         }
     }
 }
@@ -437,6 +940,18 @@ impl Default for PlaybackSettings {
             spectrum_decay: 0.85,
             spectrum_bars_mode: true,
             music_quality: MusicQuality::High, // 320k default
+            resume_on_startup: false,
+            ui_sounds: false,
+            auto_dj: AutoDjSettings::default(),
+            skip_back_secs: default_skip_back_secs(),
+            skip_forward_secs: default_skip_forward_secs(),
+            seek_step_secs: default_seek_step_secs(),
+            volume_step_percent: default_volume_step_percent(),
+            autoplay_similar: false,
+            smart_resume: SmartResumeSettings::default(),
+            call_ducking: CallDuckingSettings::default(),
+            dsp_presets: HashMap::new(),
+            match_sample_rate: false,
         }
     }
 }
@@ -447,6 +962,14 @@ impl Default for DisplaySettings {
             dark_mode: true,
             language: "zh".to_string(),
             power_saving_mode: false,
+            lyrics_sub_lines: LyricsSubLines::default(),
+            lyrics_tuning: LyricsTuning::default(),
+            lyrics_share: LyricsShareSettings::default(),
+            lyrics_save: LyricsSaveSettings::default(),
+            lyrics_background: BackgroundStyle::default(),
+            artwork_backdrop: ArtworkBackdrop::default(),
+            cover_theming: true,
+            home_layout: HomeLayout::default(),
         }
     }
 }
@@ -456,6 +979,7 @@ impl Default for StorageSettings {
         Self {
             max_cache_mb: 1024, // 1GB default
             cache_dir: None,
+            export: ExportSettings::default(),
         }
     }
 }
@@ -465,6 +989,17 @@ impl Default for SystemSettings {
         Self {
             audio_output_device: None,
             audio_buffer_size: 512,
+            launch_at_login: false,
+            start_minimized: false,
+            release_notifications: false,
+            update_channel: UpdateChannel::Stable,
+            auto_check_updates: true,
+            show_whats_new: true,
+            last_run_version: String::new(),
+            output_latency_ms: HashMap::new(),
+            now_playing: NowPlayingOutput::default(),
+            analyze_songs: true,
+            listen_mode: false,
         }
     }
 }
@@ -477,29 +1012,92 @@ impl Default for NetworkSettings {
             proxy_port: 0,
             proxy_username: None,
             proxy_password: None,
+            bandwidth_limit_kbps: 0,
+            metered_mode: MeteredMode::Auto,
         }
     }
 }
 
 impl Settings {
+    /// DSP stages remembered for the selected output device
+    pub fn dsp_presets(&self) -> DspPresets {
+        self.playback
+            .dsp_presets
+            .get(self.output_device_key())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Remember DSP stages for the selected output device
+    pub fn set_dsp_presets(&mut self, presets: DspPresets) {
+        let key = self.output_device_key().to_string();
+        self.playback.dsp_presets.insert(key, presets);
+    }
+
+    /// Output latency in ms remembered for the selected output device
+    pub fn output_latency_ms(&self) -> u32 {
+        self.system
+            .output_latency_ms
+            .get(self.output_device_key())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Remember the output latency for the selected output device
+    pub fn set_output_latency_ms(&mut self, latency_ms: u32) {
+        let key = self.output_device_key().to_string();
+        self.system.output_latency_ms.insert(key, latency_ms);
+    }
+
+    fn output_device_key(&self) -> &str {
+        self.system.audio_output_device.as_deref().unwrap_or("")
+    }
+
     /// Get the settings file path
     pub fn file_path() -> Option<PathBuf> {
-        directories::ProjectDirs::from("com", "rustle", "Rustle")
-            .map(|dirs| dirs.config_dir().join("settings.json"))
+        crate::utils::config_dir().map(|dir| dir.join("settings.json"))
     }
 
-    /// Load settings from file, or return defaults if not found
+    /// Load settings from file, or return defaults in the system language if not found
     pub fn load() -> Self {
-        Self::file_path()
-            .and_then(|path| Self::load_from_file(&path).ok())
-            .unwrap_or_default()
+        let Some(path) = Self::file_path() else {
+            return Self::default();
+        };
+        if !path.exists() {
+            // First run: follow the system language
+            let mut settings = Self::default();
+            settings.display.language = crate::i18n::Language::system().code().to_string();
+            return settings;
+        }
+        Self::load_from_file(&path).unwrap_or_default()
     }
 
-    /// Load settings from a specific file
+    /// Load settings from a specific file, upgrading it from older versions
+    ///
+    /// An upgraded file is kept next to the settings as it was, in case the
+    /// upgrade lost something.
     pub fn load_from_file(path: &Path) -> Result<Self, SettingsError> {
         let content =
             std::fs::read_to_string(path).map_err(|e| SettingsError::Io(e.to_string()))?;
-        serde_json::from_str(&content).map_err(|e| SettingsError::Parse(e.to_string()))
+        let mut value: Value =
+            serde_json::from_str(&content).map_err(|e| SettingsError::Parse(e.to_string()))?;
+        let from = migrate(&mut value);
+        let mut settings: Self =
+            serde_json::from_value(value).map_err(|e| SettingsError::Parse(e.to_string()))?;
+        settings.keybindings.fill_missing_defaults();
+
+        if from < SETTINGS_VERSION {
+            tracing::info!(
+                "Upgraded settings from version {} to {}",
+                from,
+                SETTINGS_VERSION
+            );
+            let backup = path.with_extension(format!("v{}.json.bak", from));
+            if let Err(e) = std::fs::write(&backup, &content) {
+                tracing::warn!("Failed to keep old settings at {:?}: {}", backup, e);
+            }
+        }
+        Ok(settings)
     }
 
     /// Save settings to the default file
@@ -514,7 +1112,14 @@ impl Settings {
     }
 
     /// Save settings to a specific file
+    ///
+    /// Settings loaded from a newer build's file are never written back:
+    /// this build's layout would drop whatever it doesn't know about.
     pub fn save_to_file(&self, path: &Path) -> Result<(), SettingsError> {
+        if self.version > SETTINGS_VERSION {
+            return Err(SettingsError::TooNew(self.version));
+        }
+
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| SettingsError::Io(e.to_string()))?;
@@ -527,11 +1132,87 @@ impl Settings {
     }
 }
 
+/// Bring a settings file up to [`SETTINGS_VERSION`], returning the version
+/// it had
+///
+/// Files from a newer build are left alone; fields this build doesn't know
+/// are ignored, and [`Settings::save_to_file`] won't overwrite the file.
+pub(super) fn migrate(value: &mut Value) -> u32 {
+    let from = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as u32);
+    if from > SETTINGS_VERSION {
+        tracing::warn!(
+            "Settings file is from a newer version ({} > {})",
+            from,
+            SETTINGS_VERSION
+        );
+        return from;
+    }
+    for step in &MIGRATIONS[from as usize..] {
+        step(value);
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), SETTINGS_VERSION.into());
+    }
+    from
+}
+
+/// Version 0 → 1: sections and their fields without a serde default made
+/// older files fail to load as a whole when one was missing; take missing
+/// ones from the version 1 defaults instead
+fn fill_missing_sections(value: &mut Value) {
+    let (Some(object), Value::Object(defaults)) = (value.as_object_mut(), v1_required_fields())
+    else {
+        return;
+    };
+    for (key, default) in defaults {
+        match (object.get_mut(&key), default) {
+            (Some(Value::Object(section)), Value::Object(section_defaults)) => {
+                for (field, default) in section_defaults {
+                    section.entry(field).or_insert(default);
+                }
+            }
+            (Some(_), _) => {}
+            (None, default) => {
+                object.insert(key, default);
+            }
+        }
+    }
+}
+
+/// Fields a version 1 file can't load without, with their defaults then
+///
+/// Frozen with the step rather than taken from [`Settings::default`], so a
+/// later step renaming or moving a field sees the user's value where it was,
+/// not a default filled in under the new name.
+fn v1_required_fields() -> Value {
+    serde_json::json!({
+        "close_behavior": "ask",
+        "volume": 1.0,
+        "play_mode": "sequential",
+        // Missing bindings are filled from the running build on load
+        "keybindings": { "bindings": {} },
+        "playback": {
+            "fade_in_out": false,
+            "volume_normalization": false,
+            "equalizer_enabled": false,
+            "equalizer_values": [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+        },
+        "display": { "dark_mode": true, "language": "zh" },
+        "storage": { "max_cache_mb": 1024, "cache_dir": null },
+        "system": { "audio_output_device": null, "audio_buffer_size": 512 }
+    })
+}
+
 /// Errors that can occur with settings
 #[derive(Debug, Clone)]
 pub enum SettingsError {
     Io(String),
     Parse(String),
+    /// Loaded from a file of a newer layout version, so not saved over it
+    TooNew(u32),
 }
 
 impl std::fmt::Display for SettingsError {
@@ -539,8 +1220,91 @@ impl std::fmt::Display for SettingsError {
         match self {
             SettingsError::Io(e) => write!(f, "IO error: {}", e),
             SettingsError::Parse(e) => write!(f, "Parse error: {}", e),
+            SettingsError::TooNew(version) => write!(
+                f,
+                "Settings file is from a newer version ({}), not overwriting it",
+                version
+            ),
         }
     }
 }
 
 impl std::error::Error for SettingsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unversioned_file_is_upgraded() {
+        // Written before the version was kept, without a storage section and
+        // with a system section missing a field
+        let mut value = serde_json::json!({
+            "close_behavior": "exit",
+            "volume": 0.5,
+            "play_mode": "sequential",
+            "keybindings": Settings::default().keybindings,
+            "playback": Settings::default().playback,
+            "display": Settings::default().display,
+            "system": { "audio_output_device": "Speakers" }
+        });
+        assert_eq!(migrate(&mut value), 0);
+
+        let settings: Settings = serde_json::from_value(value).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.close_behavior, CloseBehavior::Exit);
+        assert_eq!(settings.volume, 0.5);
+        assert_eq!(
+            settings.system.audio_output_device.as_deref(),
+            Some("Speakers")
+        );
+        assert_eq!(settings.system.audio_buffer_size, 512);
+        assert_eq!(settings.storage.max_cache_mb, 1024);
+    }
+
+    #[test]
+    fn test_current_and_newer_files_are_left_alone() {
+        let settings = Settings::default();
+        let mut value = serde_json::to_value(&settings).unwrap();
+        let before = value.clone();
+        assert_eq!(migrate(&mut value), SETTINGS_VERSION);
+        assert_eq!(value, before);
+
+        value["version"] = (SETTINGS_VERSION + 1).into();
+        let newer = value.clone();
+        assert_eq!(migrate(&mut value), SETTINGS_VERSION + 1);
+        assert_eq!(value, newer);
+    }
+
+    #[test]
+    fn test_empty_file_loads_with_v1_defaults() {
+        let mut value = serde_json::json!({});
+        assert_eq!(migrate(&mut value), 0);
+        let settings: Settings = serde_json::from_value(value).unwrap();
+        assert_eq!(settings.volume, 1.0);
+        assert_eq!(settings.system.audio_buffer_size, 512);
+    }
+
+    #[test]
+    fn test_newer_file_is_not_overwritten() {
+        let path =
+            std::env::temp_dir().join(format!("rustle_settings_{}.json", std::process::id()));
+        let newer = serde_json::json!({
+            "version": SETTINGS_VERSION + 1,
+            "future_section": { "enabled": true }
+        });
+        let mut value = serde_json::to_value(Settings::default()).unwrap();
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(newer.as_object().unwrap().clone());
+        std::fs::write(&path, value.to_string()).unwrap();
+
+        let settings = Settings::load_from_file(&path).unwrap();
+        let saved = settings.save_to_file(&path);
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(saved, Err(SettingsError::TooNew(_))));
+        assert!(content.contains("future_section"));
+    }
+}
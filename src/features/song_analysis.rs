@@ -0,0 +1,525 @@
+//! Tempo, key and energy analysis of library songs
+//!
+//! Local songs are decoded once in the background and the results kept in
+//! the database. Tempo comes from the autocorrelation of an onset envelope,
+//! key from a pitch-class profile matched against the Krumhansl-Kessler key
+//! profiles, and energy from loudness nudged by tempo. Estimates are meant for
+//! sorting and picking songs that sit well together, not for beat-matching.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use anyhow::Result;
+use rodio::{Decoder, Source};
+
+use crate::database::DbSongAnalysis;
+
+/// Rate audio is averaged down to before analysis
+const ANALYSIS_RATE: u32 = 11_025;
+
+/// Seconds from the start of a song that are analyzed
+const MAX_ANALYSIS_SECS: u32 = 150;
+
+/// Shortest audio worth estimating a tempo for, in seconds
+const MIN_ANALYSIS_SECS: u32 = 10;
+
+/// Samples per onset envelope frame
+const HOP: usize = 128;
+
+/// Rise in log energy ignored as a steady sound's wobble, about 0.4 dB
+const ONSET_FLOOR: f32 = 0.05;
+
+/// Tempo range searched
+const MIN_BPM: f32 = 60.0;
+const MAX_BPM: f32 = 200.0;
+
+/// Tempo the search leans towards when half or double tempo fit as well
+const PREFERRED_BPM: f32 = 120.0;
+
+/// Autocorrelation below which a song is taken to have no clear beat
+const MIN_PERIODICITY: f32 = 0.1;
+
+/// Samples per frame of the pitch-class profile
+const CHROMA_FRAME: usize = 8192;
+
+/// MIDI notes folded into the pitch-class profile, C2 to B5
+const CHROMA_NOTES: std::ops::Range<u8> = 36..84;
+
+/// Key profile correlation below which no key is given
+const MIN_KEY_CORRELATION: f32 = 0.5;
+
+/// Krumhansl-Kessler probe-tone profiles, starting at the tonic
+const MAJOR_PROFILE: [f32; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+const MINOR_PROFILE: [f32; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "Eb", "E", "F", "F#", "G", "Ab", "A", "Bb", "B",
+];
+
+/// Energy difference under which two songs count as similar
+const SIMILAR_ENERGY: f32 = 0.15;
+
+/// A major or minor key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MusicalKey {
+    /// Pitch class of the tonic, 0 for C
+    pub tonic: u8,
+    pub minor: bool,
+}
+
+impl MusicalKey {
+    /// Key stored as `index`: 0-11 for major keys, 12-23 for minor
+    pub fn from_index(index: i64) -> Option<Self> {
+        match index {
+            0..=11 => Some(Self {
+                tonic: index as u8,
+                minor: false,
+            }),
+            12..=23 => Some(Self {
+                tonic: (index - 12) as u8,
+                minor: true,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn index(self) -> i64 {
+        self.tonic as i64 + if self.minor { 12 } else { 0 }
+    }
+
+    /// Name such as "F#" or "Am"
+    pub fn name(self) -> String {
+        let note = NOTE_NAMES[self.tonic as usize % 12];
+        if self.minor {
+            format!("{}m", note)
+        } else {
+            note.to_string()
+        }
+    }
+
+    /// Position on the Camelot wheel, e.g. (8, 'B') for C major
+    pub fn camelot(self) -> (u8, char) {
+        // Relative keys share a number; each step is a fifth
+        let major = if self.minor {
+            (self.tonic + 3) % 12
+        } else {
+            self.tonic
+        };
+        let number = (major * 7 % 12 + 7) % 12 + 1;
+        (number, if self.minor { 'A' } else { 'B' })
+    }
+
+    /// Camelot code such as "8A"
+    pub fn camelot_code(self) -> String {
+        let (number, letter) = self.camelot();
+        format!("{}{}", number, letter)
+    }
+
+    /// Whether a mix from `self` into `other` stays in key: the same key,
+    /// a neighbour on the wheel, or the relative major or minor
+    pub fn is_harmonic_match(self, other: Self) -> bool {
+        let (a, a_letter) = self.camelot();
+        let (b, b_letter) = other.camelot();
+        if a_letter == b_letter {
+            let step = (a as i8 - b as i8).rem_euclid(12);
+            step == 0 || step == 1 || step == 11
+        } else {
+            a == b
+        }
+    }
+}
+
+/// What analysis found out about a song; fields are `None` where it could
+/// not tell
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SongAnalysis {
+    pub bpm: Option<f32>,
+    pub key: Option<MusicalKey>,
+    /// 0 for quiet and slow up to 1 for loud and fast
+    pub energy: Option<f32>,
+}
+
+impl From<&DbSongAnalysis> for SongAnalysis {
+    fn from(row: &DbSongAnalysis) -> Self {
+        Self {
+            bpm: row.bpm.map(|bpm| bpm as f32),
+            key: row.musical_key.and_then(MusicalKey::from_index),
+            energy: row.energy.map(|energy| energy as f32),
+        }
+    }
+}
+
+impl SongAnalysis {
+    /// How far apart two songs feel, `None` unless both have an energy
+    ///
+    /// Mostly the energy difference, plus a tempo term that treats half and
+    /// double time as the same beat, less a little for keys that mix well.
+    pub fn distance(&self, other: &Self) -> Option<f32> {
+        let energy = (self.energy? - other.energy?).abs();
+        let tempo = match (self.bpm, other.bpm) {
+            (Some(a), Some(b)) => [0.5, 1.0, 2.0]
+                .iter()
+                .map(|factor| (a.ln() - (b * factor).ln()).abs())
+                .fold(f32::MAX, f32::min),
+            _ => 0.2,
+        };
+        let harmonic = match (self.key, other.key) {
+            (Some(a), Some(b)) if a.is_harmonic_match(b) => -0.05,
+            _ => 0.0,
+        };
+        Some((energy + tempo * 0.5 + harmonic).max(0.0))
+    }
+}
+
+/// IDs from `candidates` closest in feel to `seed`, nearest first
+pub fn closest<'a, Id>(
+    seed: &SongAnalysis,
+    candidates: impl IntoIterator<Item = (Id, &'a SongAnalysis)>,
+    count: usize,
+) -> Vec<Id> {
+    let mut scored: Vec<(f32, Id)> = candidates
+        .into_iter()
+        .filter_map(|(id, analysis)| seed.distance(analysis).map(|d| (d, id)))
+        .filter(|(distance, _)| *distance <= SIMILAR_ENERGY)
+        .collect();
+    scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+    scored.into_iter().take(count).map(|(_, id)| id).collect()
+}
+
+/// Analyze the audio file at `path`; blocks while the file is decoded
+pub fn analyze_file(path: &Path) -> Result<SongAnalysis> {
+    let (samples, rate) = decode_mono(path)?;
+    Ok(analyze(&samples, rate))
+}
+
+/// Analyze mono `samples` at `rate`
+fn analyze(samples: &[f32], rate: u32) -> SongAnalysis {
+    if samples.len() < (rate * MIN_ANALYSIS_SECS) as usize {
+        return SongAnalysis::default();
+    }
+    let envelope = onset_envelope(samples);
+    let bpm = detect_bpm(&envelope, rate as f32 / HOP as f32);
+    let key = detect_key(samples, rate);
+    SongAnalysis {
+        bpm,
+        key,
+        energy: Some(energy(samples, bpm)),
+    }
+}
+
+/// Decode up to `MAX_ANALYSIS_SECS` of `path` as mono, averaged down to
+/// about `ANALYSIS_RATE`
+fn decode_mono(path: &Path) -> Result<(Vec<f32>, u32)> {
+    let source = Decoder::new(BufReader::new(File::open(path)?))?;
+    let channels = source.channels().max(1) as usize;
+    let step = (source.sample_rate() / ANALYSIS_RATE).max(1) as usize;
+    let rate = source.sample_rate() / step as u32;
+    let limit = (rate * MAX_ANALYSIS_SECS) as usize;
+
+    let mut samples = Vec::with_capacity(limit);
+    let (mut frame, mut in_frame) = (0.0, 0);
+    let (mut sum, mut in_step) = (0.0, 0);
+    for sample in source {
+        frame += sample;
+        in_frame += 1;
+        if in_frame < channels {
+            continue;
+        }
+        sum += frame / channels as f32;
+        (frame, in_frame) = (0.0, 0);
+        in_step += 1;
+        if in_step < step {
+            continue;
+        }
+        samples.push(sum / step as f32);
+        (sum, in_step) = (0.0, 0);
+        if samples.len() >= limit {
+            break;
+        }
+    }
+    Ok((samples, rate))
+}
+
+/// Rises in log energy from one hop to the next, above `ONSET_FLOOR`
+fn onset_envelope(samples: &[f32]) -> Vec<f32> {
+    let energies: Vec<f32> = samples
+        .chunks_exact(HOP)
+        .map(|hop| (hop.iter().map(|s| s * s).sum::<f32>() / HOP as f32 + 1e-10).ln())
+        .collect();
+    energies
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0] - ONSET_FLOOR).max(0.0))
+        .collect()
+}
+
+/// Tempo whose beat period best repeats in `envelope`, which has
+/// `frame_rate` frames per second
+fn detect_bpm(envelope: &[f32], frame_rate: f32) -> Option<f32> {
+    let mean = envelope.iter().sum::<f32>() / envelope.len().max(1) as f32;
+    let centered: Vec<f32> = envelope.iter().map(|v| v - mean).collect();
+    let power: f32 = centered.iter().map(|v| v * v).sum();
+    if power <= f32::EPSILON {
+        return None;
+    }
+
+    let min_lag = (frame_rate * 60.0 / MAX_BPM).floor() as usize;
+    let max_lag = (frame_rate * 60.0 / MIN_BPM).ceil() as usize;
+    if max_lag + 1 >= centered.len() {
+        return None;
+    }
+    let acf = |lag: usize| -> f32 {
+        centered
+            .iter()
+            .zip(&centered[lag..])
+            .map(|(a, b)| a * b)
+            .sum::<f32>()
+            / power
+    };
+    let correlations: Vec<f32> = (min_lag - 1..=max_lag + 1).map(acf).collect();
+
+    // Weight lags by a log-normal prior so a clean pulse isn't read at half
+    // or double its tempo
+    let weight = |lag: usize| {
+        let bpm = frame_rate * 60.0 / lag as f32;
+        let octaves = (bpm / PREFERRED_BPM).log2();
+        (-0.5 * octaves * octaves).exp()
+    };
+    let (best, _) = (1..correlations.len() - 1)
+        .map(|i| (i, correlations[i] * weight(min_lag - 1 + i)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    if correlations[best] < MIN_PERIODICITY {
+        return None;
+    }
+
+    // Parabolic interpolation between neighbouring lags
+    let (before, peak, after) = (
+        correlations[best - 1],
+        correlations[best],
+        correlations[best + 1],
+    );
+    let curvature = before - 2.0 * peak + after;
+    let offset = if curvature < 0.0 {
+        (0.5 * (before - after) / curvature).clamp(-0.5, 0.5)
+    } else {
+        0.0
+    };
+    let lag = (min_lag - 1 + best) as f32 + offset;
+    let bpm = frame_rate * 60.0 / lag;
+    Some((bpm * 10.0).round() / 10.0)
+}
+
+/// Pitch-class energy of `samples`, C first
+fn chroma(samples: &[f32], rate: u32) -> [f32; 12] {
+    let nyquist = rate as f32 / 2.0;
+    let notes: Vec<(usize, f32)> = CHROMA_NOTES
+        .filter_map(|note| {
+            let freq = 440.0 * 2f32.powf((note as f32 - 69.0) / 12.0);
+            (freq < nyquist * 0.9).then(|| {
+                let omega = 2.0 * std::f32::consts::PI * freq / rate as f32;
+                (note as usize % 12, 2.0 * omega.cos())
+            })
+        })
+        .collect();
+    let window: Vec<f32> = (0..CHROMA_FRAME)
+        .map(|i| {
+            let phase = 2.0 * std::f32::consts::PI * i as f32 / CHROMA_FRAME as f32;
+            0.5 - 0.5 * phase.cos()
+        })
+        .collect();
+
+    let mut chroma = [0.0; 12];
+    let mut windowed = vec![0.0; CHROMA_FRAME];
+    for frame in samples.chunks_exact(CHROMA_FRAME) {
+        for ((out, sample), w) in windowed.iter_mut().zip(frame).zip(&window) {
+            *out = sample * w;
+        }
+        // Goertzel filter per note
+        for &(pitch_class, coeff) in &notes {
+            let (mut s1, mut s2) = (0.0f32, 0.0f32);
+            for &x in &windowed {
+                let s0 = x + coeff * s1 - s2;
+                s2 = s1;
+                s1 = s0;
+            }
+            let power = s1 * s1 + s2 * s2 - coeff * s1 * s2;
+            chroma[pitch_class] += power.max(0.0).sqrt();
+        }
+    }
+    chroma
+}
+
+fn correlation(a: &[f32; 12], b: impl Fn(usize) -> f32) -> f32 {
+    let mean_a = a.iter().sum::<f32>() / 12.0;
+    let mean_b = (0..12).map(&b).sum::<f32>() / 12.0;
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (i, a) in a.iter().enumerate() {
+        let (da, db) = (a - mean_a, b(i) - mean_b);
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+    if var_a <= f32::EPSILON || var_b <= f32::EPSILON {
+        return 0.0;
+    }
+    cov / (var_a * var_b).sqrt()
+}
+
+/// Key whose profile best matches the pitch classes in `samples`
+fn detect_key(samples: &[f32], rate: u32) -> Option<MusicalKey> {
+    let chroma = chroma(samples, rate);
+    let (key, score) = (0..24)
+        .filter_map(MusicalKey::from_index)
+        .map(|key| {
+            let profile = if key.minor {
+                &MINOR_PROFILE
+            } else {
+                &MAJOR_PROFILE
+            };
+            let tonic = key.tonic as usize;
+            let score = correlation(&chroma, |pc| profile[(pc + 12 - tonic) % 12]);
+            (key, score)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    (score >= MIN_KEY_CORRELATION).then_some(key)
+}
+
+/// Loudness between -40 and -10 dBFS RMS, nudged up for faster tempos
+fn energy(samples: &[f32], bpm: Option<f32>) -> f32 {
+    let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len().max(1) as f32).sqrt();
+    let loudness = ((20.0 * (rms + 1e-9).log10() + 40.0) / 30.0).clamp(0.0, 1.0);
+    let pace = bpm
+        .map(|bpm| ((bpm - MIN_BPM) / (MAX_BPM - MIN_BPM)).clamp(0.0, 1.0))
+        .unwrap_or(0.5);
+    ((loudness * 0.7 + pace * 0.3) * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = ANALYSIS_RATE;
+
+    /// Short noise bursts at `bpm` over a quiet hum
+    fn click_track(bpm: f32, secs: u32) -> Vec<f32> {
+        let period = (RATE as f32 * 60.0 / bpm) as usize;
+        let mut seed = 1u32;
+        (0..(RATE * secs) as usize)
+            .map(|i| {
+                let hum = 0.01 * (i as f32 * 0.05).sin();
+                if i % period < 200 {
+                    seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    hum + (seed >> 16) as f32 / 65_536.0 - 0.5
+                } else {
+                    hum
+                }
+            })
+            .collect()
+    }
+
+    /// Chords of MIDI notes, two seconds each
+    fn progression(chords: &[[u8; 3]]) -> Vec<f32> {
+        let len = (RATE * 2) as usize;
+        chords
+            .iter()
+            .flat_map(|chord| {
+                (0..len).map(move |i| {
+                    chord
+                        .iter()
+                        .map(|&note| {
+                            let freq = 440.0 * 2f32.powf((note as f32 - 69.0) / 12.0);
+                            0.2 * (2.0 * std::f32::consts::PI * freq * i as f32 / RATE as f32).sin()
+                        })
+                        .sum::<f32>()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bpm_of_click_track() {
+        for bpm in [90.0, 120.0, 128.0] {
+            let envelope = onset_envelope(&click_track(bpm, 20));
+            let found = detect_bpm(&envelope, RATE as f32 / HOP as f32).unwrap();
+            assert!((found - bpm).abs() < 2.0, "{} detected as {}", bpm, found);
+        }
+    }
+
+    #[test]
+    fn test_no_bpm_without_beat() {
+        let hum: Vec<f32> = (0..RATE * 20).map(|i| (i as f32 * 0.05).sin()).collect();
+        assert_eq!(
+            detect_bpm(&onset_envelope(&hum), RATE as f32 / HOP as f32),
+            None
+        );
+    }
+
+    #[test]
+    fn test_key_of_progressions() {
+        // I-IV-V-I in C major
+        let c_major = progression(&[[60, 64, 67], [65, 69, 72], [67, 71, 74], [60, 64, 67]]);
+        assert_eq!(
+            detect_key(&c_major, RATE),
+            Some(MusicalKey {
+                tonic: 0,
+                minor: false
+            })
+        );
+        // i-iv-V-i in A minor
+        let a_minor = progression(&[[57, 60, 64], [62, 65, 69], [64, 68, 71], [57, 60, 64]]);
+        assert_eq!(
+            detect_key(&a_minor, RATE).map(|key| key.name()),
+            Some("Am".to_string())
+        );
+    }
+
+    #[test]
+    fn test_camelot() {
+        let key = |index| MusicalKey::from_index(index).unwrap();
+        assert_eq!(key(0).camelot_code(), "8B");
+        assert_eq!(key(7).camelot_code(), "9B");
+        assert_eq!(key(21).camelot_code(), "8A");
+        assert_eq!(key(16).camelot_code(), "9A");
+        // B major wraps from 1B to 12B through E major
+        assert_eq!(key(11).camelot_code(), "1B");
+        assert!(key(11).is_harmonic_match(key(4)));
+        assert!(key(0).is_harmonic_match(key(21)));
+        assert!(!key(0).is_harmonic_match(key(2)));
+        for index in 0..24 {
+            assert_eq!(key(index).index(), index);
+        }
+    }
+
+    #[test]
+    fn test_closest_prefers_similar_energy_and_tempo() {
+        let seed = SongAnalysis {
+            bpm: Some(120.0),
+            key: None,
+            energy: Some(0.6),
+        };
+        let close = SongAnalysis {
+            bpm: Some(62.0),
+            ..seed
+        };
+        let slower = SongAnalysis {
+            bpm: Some(100.0),
+            energy: Some(0.62),
+            ..seed
+        };
+        let calm = SongAnalysis {
+            energy: Some(0.2),
+            ..seed
+        };
+        let unknown = SongAnalysis::default();
+        let found = closest(
+            &seed,
+            [(1, &calm), (2, &slower), (3, &close), (4, &unknown)],
+            10,
+        );
+        assert_eq!(found, vec![3, 2]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Local corrections to NCM track metadata
+//!
+//! NCM sometimes has a track under the wrong title, artist or album. The
+//! user can override what is shown without touching anything on the server;
+//! overrides live in the database and are applied wherever an NCM track
+//! becomes a song the app shows, so the queue, player bar, lyrics page,
+//! playlist rows and MPRIS all agree.
+//!
+//! NCM songs are built in many places, some off the UI thread, so the loaded
+//! overrides are kept here instead of being threaded through each of them.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use parking_lot::RwLock;
+
+use crate::database::{DbSong, DbSongOverride};
+use crate::features::song_source;
+
+/// Overrides by NCM track ID
+static OVERRIDES: LazyLock<RwLock<HashMap<u64, SongOverride>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// The metadata fields that can be overridden
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SongTags {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+}
+
+impl SongTags {
+    /// Tags of a song as currently shown
+    pub fn of(song: &DbSong) -> Self {
+        Self {
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            album: song.album.clone(),
+        }
+    }
+
+    /// Write these tags into a song
+    pub fn apply_to(&self, song: &mut DbSong) {
+        song.title.clone_from(&self.title);
+        song.artist.clone_from(&self.artist);
+        song.album.clone_from(&self.album);
+    }
+}
+
+/// What is shown for a track, and what NCM says
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SongOverride {
+    pub tags: SongTags,
+    pub original: SongTags,
+}
+
+impl From<DbSongOverride> for SongOverride {
+    fn from(row: DbSongOverride) -> Self {
+        Self {
+            tags: SongTags {
+                title: row.title,
+                artist: row.artist,
+                album: row.album,
+            },
+            original: SongTags {
+                title: row.original_title,
+                artist: row.original_artist,
+                album: row.original_album,
+            },
+        }
+    }
+}
+
+impl SongOverride {
+    /// Database row for the override of `ncm_id`
+    pub fn to_row(&self, ncm_id: u64) -> DbSongOverride {
+        DbSongOverride {
+            ncm_id: ncm_id as i64,
+            title: self.tags.title.clone(),
+            artist: self.tags.artist.clone(),
+            album: self.tags.album.clone(),
+            original_title: self.original.title.clone(),
+            original_artist: self.original.artist.clone(),
+            original_album: self.original.album.clone(),
+            updated_at: 0,
+        }
+    }
+}
+
+/// Replace the loaded overrides with those read from the database
+pub fn load(rows: Vec<DbSongOverride>) {
+    let overrides = rows
+        .into_iter()
+        .map(|row| (row.ncm_id as u64, SongOverride::from(row)))
+        .collect();
+    *OVERRIDES.write() = overrides;
+}
+
+/// Override for an NCM track
+pub fn get(ncm_id: u64) -> Option<SongOverride> {
+    OVERRIDES.read().get(&ncm_id).cloned()
+}
+
+/// Tags to show for an NCM track, if overridden
+pub fn tags(ncm_id: u64) -> Option<SongTags> {
+    OVERRIDES.read().get(&ncm_id).map(|o| o.tags.clone())
+}
+
+/// Set the override for an NCM track
+pub fn set(ncm_id: u64, song_override: SongOverride) {
+    OVERRIDES.write().insert(ncm_id, song_override);
+}
+
+/// Drop the override for an NCM track, returning it
+pub fn remove(ncm_id: u64) -> Option<SongOverride> {
+    OVERRIDES.write().remove(&ncm_id)
+}
+
+/// Show the overridden tags for `song` if it is an overridden NCM track
+pub fn apply(song: &mut DbSong) {
+    if let Some(tags) = song_source::ncm_id(song).and_then(tags) {
+        tags.apply_to(song);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SongId;
+
+    fn song(id: SongId, title: &str) -> DbSong {
+        DbSong {
+            id,
+            file_path: String::new(),
+            title: title.into(),
+            artist: "NCM Artist".into(),
+            album: "NCM Album".into(),
+            duration_secs: 0,
+            track_number: None,
+            year: None,
+            genre: None,
+            cover_path: None,
+            file_hash: None,
+            file_size: 0,
+            format: None,
+            play_count: 0,
+            last_played: None,
+            last_modified: 0,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_apply_only_touches_overridden_tracks() {
+        let fixed = SongTags {
+            title: "Right Title".into(),
+            artist: "Right Artist".into(),
+            album: "Right Album".into(),
+        };
+        let mut overridden = song(SongId::ncm(9_001), "Wrong Title");
+        set(
+            9_001,
+            SongOverride {
+                tags: fixed.clone(),
+                original: SongTags::of(&overridden),
+            },
+        );
+
+        apply(&mut overridden);
+        assert_eq!(SongTags::of(&overridden), fixed);
+
+        let mut other = song(SongId::ncm(9_002), "Other");
+        apply(&mut other);
+        assert_eq!(other.title, "Other");
+
+        // Library songs are never looked up, even with a matching row ID
+        let mut local = song(SongId::library(9_001), "Local");
+        local.file_path = "/music/local.flac".into();
+        apply(&mut local);
+        assert_eq!(local.title, "Local");
+
+        let removed = remove(9_001).unwrap();
+        assert_eq!(removed.original.title, "Wrong Title");
+    }
+}
@@ -0,0 +1,93 @@
+//! Where a song plays from
+//!
+//! Library songs are files on disk. NCM songs are streamed, unless their
+//! audio has already been downloaded to the song cache. Which one a song is
+//! comes from its [`SongId`](crate::database::SongId).
+
+use std::path::PathBuf;
+
+use crate::database::DbSong;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SongSource {
+    /// A file in the local library
+    Local,
+    /// An NCM track whose audio is in the song cache
+    Cached,
+    /// An NCM track that has to be streamed
+    Ncm,
+}
+
+impl SongSource {
+    /// Where `song` plays from, looking in the song cache for NCM tracks
+    pub fn of(song: &DbSong) -> Self {
+        match ncm_id(song) {
+            None => Self::Local,
+            Some(id) if cached_audio(id).is_some() => Self::Cached,
+            Some(_) => Self::Ncm,
+        }
+    }
+
+    /// Where `song` plays from without looking on disk, taking NCM tracks to
+    /// be streamed
+    pub fn unchecked(song: &DbSong) -> Self {
+        if is_ncm(song) { Self::Ncm } else { Self::Local }
+    }
+
+    /// Whether the song plays without a network connection
+    pub fn is_offline(self) -> bool {
+        self != Self::Ncm
+    }
+}
+
+/// The NCM track behind `song`, or `None` for library songs
+pub fn ncm_id(song: &DbSong) -> Option<u64> {
+    song.id.ncm_id()
+}
+
+/// Whether `song` came from NCM rather than the local library
+pub fn is_ncm(song: &DbSong) -> bool {
+    ncm_id(song).is_some()
+}
+
+/// Downloaded audio for an NCM track, whatever its format
+pub fn cached_audio(ncm_id: u64) -> Option<PathBuf> {
+    crate::utils::find_cached_audio(&crate::utils::songs_cache_dir(), &ncm_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::SongId;
+
+    #[test]
+    fn library_songs_have_no_ncm_id() {
+        assert_eq!(SongId::library(7).ncm_id(), None);
+        assert_eq!(SongId::library(7).library_id(), Some(7));
+        assert_eq!(SongId::ncm(1234).ncm_id(), Some(1234));
+        assert_eq!(SongId::ncm(1234).library_id(), None);
+    }
+
+    #[test]
+    fn saved_ids_round_trip() {
+        for id in [SongId::library(0), SongId::library(7), SongId::ncm(1234)] {
+            let json = serde_json::to_string(&id).unwrap();
+            assert_eq!(serde_json::from_str::<SongId>(&json).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn sessions_saved_before_typed_ids_still_load() {
+        // NCM tracks were saved negated
+        let read = |json: &str| serde_json::from_str::<SongId>(json).unwrap();
+        assert_eq!(read("-42"), SongId::ncm(42));
+        assert_eq!(read("7"), SongId::library(7));
+    }
+
+    #[test]
+    fn only_streamed_tracks_need_the_network() {
+        assert!(SongSource::Local.is_offline());
+        assert!(SongSource::Cached.is_offline());
+        assert!(!SongSource::Ncm.is_offline());
+    }
+}
@@ -0,0 +1,78 @@
+//! Startup trace
+//!
+//! Each init stage runs inside a `startup` tracing span and its duration is
+//! kept for the diagnostics bundle. Time is measured from process start, and
+//! the first drawn frame is checked against the interactive target.
+//!
+//! Only what the first frame needs runs before the window opens; network
+//! clients, the lyrics font system and homepage requests start once the
+//! first frame is up.
+
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tracing::Instrument;
+
+/// Cold start budget from launch to an interactive window
+pub const INTERACTIVE_TARGET: Duration = Duration::from_millis(300);
+
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+
+/// Finished stages with their durations, in completion order
+static STAGES: Mutex<Vec<(&'static str, Duration)>> = Mutex::new(Vec::new());
+
+/// Remember when the process started; call first thing in `main`
+pub fn mark_process_start() {
+    PROCESS_START.get_or_init(Instant::now);
+}
+
+/// Time since [`mark_process_start`]
+pub fn elapsed() -> Duration {
+    PROCESS_START.get_or_init(Instant::now).elapsed()
+}
+
+/// Run a synchronous init stage
+pub fn stage<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let _span = tracing::info_span!("startup", stage = name).entered();
+    let start = Instant::now();
+    let output = f();
+    record(name, start.elapsed());
+    output
+}
+
+/// Run an asynchronous init stage
+pub async fn stage_async<F: Future>(name: &'static str, future: F) -> F::Output {
+    let start = Instant::now();
+    let output = future
+        .instrument(tracing::info_span!("startup", stage = name))
+        .await;
+    record(name, start.elapsed());
+    output
+}
+
+fn record(name: &'static str, took: Duration) {
+    tracing::debug!("Startup stage {} took {:?}", name, took);
+    STAGES.lock().push((name, took));
+}
+
+/// Log the time to the first frame against [`INTERACTIVE_TARGET`]
+pub fn first_frame() {
+    let took = elapsed();
+    STAGES.lock().push(("first frame since launch", took));
+    if took > INTERACTIVE_TARGET {
+        tracing::warn!(
+            "First frame after {:?}, over the {:?} target",
+            took,
+            INTERACTIVE_TARGET
+        );
+    } else {
+        tracing::info!("First frame after {:?}", took);
+    }
+}
+
+/// Recorded stages for diagnostics
+pub fn stages() -> Vec<(&'static str, Duration)> {
+    STAGES.lock().clone()
+}
@@ -0,0 +1,582 @@
+//! Application updater
+//!
+//! Checks GitHub releases for a newer version on the selected channel,
+//! downloads the release asset for this platform in the background and
+//! verifies its ed25519 signature before it can be installed.
+//!
+//! A release may publish bsdiff patches against earlier versions next to the
+//! full binary, named `<asset>.from-<version>.bsdiff`. When there is one for
+//! the running version it is downloaded instead and applied to the installed
+//! file; the result has to pass the full binary's signature, and anything
+//! going wrong falls back to the full download. An interrupted full download
+//! resumes from the partial file with a range request.
+//! Builds without `RUSTLE_UPDATE_PUBLIC_KEY` can still check for updates but
+//! refuse to install anything and point the user at the release page instead.
+
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use base64::Engine as _;
+use ed25519_dalek::{Signature, VerifyingKey};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+
+use super::settings::UpdateChannel;
+
+const RELEASES_API: &str = "https://api.github.com/repos/ArcticFoxNetwork/Rustle/releases";
+
+/// Release page opened when an update can't be installed in place
+pub const RELEASES_PAGE: &str = "https://github.com/ArcticFoxNetwork/Rustle/releases";
+
+/// Base64 ed25519 public key used to verify release assets, set at build time
+const PUBLIC_KEY: Option<&str> = option_env!("RUSTLE_UPDATE_PUBLIC_KEY");
+
+/// Suffix of the detached signature asset published next to each binary
+const SIGNATURE_SUFFIX: &str = ".sig";
+
+/// Suffix of a patch asset, after `<asset>.from-<version>`
+const DELTA_SUFFIX: &str = ".bsdiff";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A semantic version like `0.1.5` or `0.2.0-beta.1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Option<String>,
+}
+
+impl Version {
+    /// Parse a version, accepting an optional leading `v`
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().trim_start_matches('v');
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (s, None),
+        };
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// Version of the running binary
+    pub fn current() -> Self {
+        Self::parse(env!("CARGO_PKG_VERSION")).expect("package version is valid")
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                // A release is newer than any of its pre-releases
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => cmp_prerelease(a, b),
+            })
+    }
+}
+
+/// Order pre-release tags the semver way: identifier by identifier, numbers
+/// numerically and below words, so `rc.2 < rc.10 < rc.final`
+fn cmp_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let order = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            // More identifiers after an equal start rank higher
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    size: u64,
+}
+
+/// A published release
+#[derive(Debug, Clone)]
+pub struct ReleaseInfo {
+    pub version: String,
+    /// Release notes (markdown)
+    pub notes: String,
+    pub page_url: String,
+    /// Installable asset for this platform, if the release has one
+    pub asset: Option<ReleaseAsset>,
+}
+
+/// Binary for this platform and its detached signature
+#[derive(Debug, Clone)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub url: String,
+    pub size: u64,
+    pub signature_url: Option<String>,
+    /// Patch from the running version to this one, if the release has one
+    pub delta_url: Option<String>,
+}
+
+impl ReleaseInfo {
+    fn from_github(release: GithubRelease) -> Self {
+        let asset_name = platform_asset_name();
+        let asset = release
+            .assets
+            .iter()
+            .find(|a| Some(a.name.as_str()) == asset_name)
+            .map(|a| ReleaseAsset {
+                name: a.name.clone(),
+                url: a.browser_download_url.clone(),
+                size: a.size,
+                signature_url: release
+                    .assets
+                    .iter()
+                    .find(|s| s.name == format!("{}{}", a.name, SIGNATURE_SUFFIX))
+                    .map(|s| s.browser_download_url.clone()),
+                delta_url: release
+                    .assets
+                    .iter()
+                    .find(|d| d.name == delta_asset_name(&a.name, &Version::current()))
+                    .map(|d| d.browser_download_url.clone()),
+            });
+
+        Self {
+            version: release.tag_name.trim_start_matches('v').to_string(),
+            notes: release.body.unwrap_or_default(),
+            page_url: release.html_url,
+            asset,
+        }
+    }
+
+    /// Whether this release can be downloaded, verified and installed in place
+    pub fn is_installable(&self) -> bool {
+        PUBLIC_KEY.is_some()
+            && supports_self_install()
+            && self
+                .asset
+                .as_ref()
+                .is_some_and(|a| a.signature_url.is_some())
+    }
+}
+
+/// Release asset name built by CI for this platform
+fn platform_asset_name() -> Option<&'static str> {
+    if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("rustle-linux-x86_64.AppImage")
+    } else if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
+        Some("rustle-windows-x86_64.exe")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Some("rustle-macos-x86_64.dmg")
+    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("rustle-macos-arm64.dmg")
+    } else {
+        None
+    }
+}
+
+/// Name of the patch asset that turns `from` into the release's `asset`
+fn delta_asset_name(asset: &str, from: &Version) -> String {
+    format!("{}.from-{}{}", asset, from, DELTA_SUFFIX)
+}
+
+/// The installed file a patch applies to, where it is the same kind of file
+/// as the release asset
+///
+/// The macOS asset is a disk image, not the app bundle inside it, so macOS
+/// always takes the full download.
+fn installed_file() -> Option<PathBuf> {
+    if cfg!(target_os = "linux") {
+        std::env::var_os("APPIMAGE").map(PathBuf::from)
+    } else if cfg!(target_os = "windows") {
+        std::env::current_exe().ok()
+    } else {
+        None
+    }
+}
+
+/// Whether this installation can replace itself
+///
+/// On Linux only the AppImage can; package-manager installs update through
+/// the package manager.
+fn supports_self_install() -> bool {
+    if cfg!(target_os = "linux") {
+        std::env::var_os("APPIMAGE").is_some()
+    } else {
+        cfg!(any(target_os = "windows", target_os = "macos"))
+    }
+}
+
+fn http_client(proxy: Option<&str>) -> Result<reqwest::Client> {
+    crate::api::http::client(proxy)
+}
+
+/// Find the newest release on `channel` that is newer than the running version
+pub async fn check_for_update(
+    channel: UpdateChannel,
+    proxy: Option<String>,
+) -> Result<Option<ReleaseInfo>> {
+    let client = http_client(proxy.as_deref())?;
+    let releases: Vec<GithubRelease> = client
+        .get(RELEASES_API)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let current = Version::current();
+    let newest = releases
+        .into_iter()
+        .filter(|r| !r.draft)
+        .filter(|r| channel == UpdateChannel::Beta || !r.prerelease)
+        .filter_map(|r| Version::parse(&r.tag_name).map(|v| (v, r)))
+        .filter(|(v, _)| channel == UpdateChannel::Beta || !v.is_prerelease())
+        .filter(|(v, _)| *v > current)
+        .max_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(newest.map(|(_, release)| ReleaseInfo::from_github(release)))
+}
+
+/// Fetch the release for a specific version (used for the changelog)
+pub async fn fetch_release(version: &str, proxy: Option<String>) -> Result<ReleaseInfo> {
+    let client = http_client(proxy.as_deref())?;
+    let release: GithubRelease = client
+        .get(format!("{}/tags/v{}", RELEASES_API, version))
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(ReleaseInfo::from_github(release))
+}
+
+/// Directory holding downloaded updates
+fn updates_dir() -> PathBuf {
+    crate::utils::cache_dir().join("updates")
+}
+
+/// Download and verify the release asset, returning the verified file
+pub async fn download(release: &ReleaseInfo, proxy: Option<String>) -> Result<PathBuf> {
+    let asset = release
+        .asset
+        .as_ref()
+        .context("Release has no asset for this platform")?;
+    let signature_url = asset
+        .signature_url
+        .as_ref()
+        .context("Release asset is not signed")?;
+
+    let dir = updates_dir();
+    tokio::fs::create_dir_all(&dir).await?;
+    let target = dir.join(&asset.name);
+    let partial = dir.join(format!("{}.part", asset.name));
+
+    let client = http_client(proxy.as_deref())?;
+    let signature = client
+        .get(signature_url)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    if let (Some(delta_url), Some(installed)) = (&asset.delta_url, installed_file()) {
+        match apply_delta(&client, delta_url, &installed, &signature, asset.size).await {
+            Ok(data) => {
+                tokio::fs::write(&partial, &data).await?;
+                tokio::fs::rename(&partial, &target).await?;
+                tracing::info!("Update {} patched and verified", release.version);
+                return Ok(target);
+            }
+            Err(e) => tracing::warn!("Update patch failed, downloading in full: {:#}", e),
+        }
+    }
+
+    download_full(&client, asset, &partial).await?;
+    let data = tokio::fs::read(&partial).await?;
+    if let Err(e) = verify_signature(&data, &signature) {
+        let _ = tokio::fs::remove_file(&partial).await;
+        return Err(e);
+    }
+
+    tokio::fs::rename(&partial, &target).await?;
+    tracing::info!("Update {} downloaded and verified", release.version);
+    Ok(target)
+}
+
+/// Download the full asset into `partial`, resuming what is already there
+async fn download_full(
+    client: &reqwest::Client,
+    asset: &ReleaseAsset,
+    partial: &Path,
+) -> Result<()> {
+    let mut offset = tokio::fs::metadata(partial)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+    if offset > asset.size {
+        offset = 0;
+    }
+    // Finished before it could be verified; a range from the end would get a 416
+    if offset == asset.size && offset > 0 {
+        return Ok(());
+    }
+
+    // Resume an interrupted download if the server honours the range
+    let mut request = client.get(&asset.url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let response = request.send().await?.error_for_status()?;
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(partial)
+        .await?;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        super::bandwidth::throttle(chunk.len()).await;
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    Ok(())
+}
+
+/// Download the patch for the running version and apply it to the installed
+/// file, returning the new binary once its signature checks out
+async fn apply_delta(
+    client: &reqwest::Client,
+    delta_url: &str,
+    installed: &Path,
+    signature: &[u8],
+    max_size: u64,
+) -> Result<Vec<u8>> {
+    let response = client.get(delta_url).send().await?.error_for_status()?;
+    let mut patch = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        // A patch bigger than the file it makes is not worth applying
+        if (patch.len() + chunk.len()) as u64 > max_size {
+            bail!("Update patch is larger than the full download");
+        }
+        super::bandwidth::throttle(chunk.len()).await;
+        patch.extend_from_slice(&chunk);
+    }
+    let source = tokio::fs::read(installed)
+        .await
+        .with_context(|| format!("Failed to read {:?}", installed))?;
+
+    let data = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+        let patcher = qbsdiff::Bspatch::new(&patch).context("Damaged update patch")?;
+        // The size comes from the unverified patch; the release lists the real one
+        let target_size = patcher.hint_target_size();
+        if target_size != max_size {
+            bail!(
+                "Update patch makes {} bytes, the release asset is {}",
+                target_size,
+                max_size
+            );
+        }
+        let mut data = vec![0u8; target_size as usize];
+        let mut target = std::io::Cursor::new(data.as_mut_slice());
+        patcher
+            .apply(&source, &mut target)
+            .context("Update patch does not fit the installed version")?;
+        if target.position() != target_size {
+            bail!("Update patch ended early");
+        }
+        Ok(data)
+    })
+    .await??;
+    verify_signature(&data, signature)?;
+    Ok(data)
+}
+
+/// Verify a detached ed25519 signature (raw 64 bytes or base64)
+fn verify_signature(data: &[u8], signature: &[u8]) -> Result<()> {
+    let key = PUBLIC_KEY.context("This build has no update signing key")?;
+    let key: [u8; 32] = base64::engine::general_purpose::STANDARD
+        .decode(key.trim())?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid update public key"))?;
+    let key = VerifyingKey::from_bytes(&key)?;
+
+    let signature = match <[u8; 64]>::try_from(signature) {
+        Ok(raw) => raw,
+        Err(_) => base64::engine::general_purpose::STANDARD
+            .decode(String::from_utf8_lossy(signature).trim())?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid update signature"))?,
+    };
+
+    key.verify_strict(data, &Signature::from_bytes(&signature))
+        .context("Update signature does not match")
+}
+
+/// Put the downloaded update in place and start the new version
+///
+/// The caller should exit right after this returns Ok.
+pub fn install_and_restart(update: &Path) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        // A running executable can be renamed but not overwritten
+        let exe = std::env::current_exe()?;
+        let old = exe.with_extension("exe.old");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(&exe, &old)?;
+        if let Err(e) = std::fs::copy(update, &exe) {
+            let _ = std::fs::rename(&old, &exe);
+            return Err(e.into());
+        }
+        std::process::Command::new(&exe).spawn()?;
+        Ok(())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let appimage =
+            PathBuf::from(std::env::var_os("APPIMAGE").context("Not running from an AppImage")?);
+        let staged = appimage.with_extension("AppImage.new");
+        std::fs::copy(update, &staged)?;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+        std::fs::rename(&staged, &appimage)?;
+        std::process::Command::new(&appimage).spawn()?;
+        Ok(())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        // The disk image holds the app bundle; Finder takes it from here
+        crate::platform::shell::open_path(update)?;
+        Ok(())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    {
+        let _ = update;
+        anyhow::bail!("Updating in place is not supported on this platform")
+    }
+}
+
+/// Remove leftovers from a previous update
+pub fn cleanup() {
+    let _ = std::fs::remove_dir_all(updates_dir());
+    #[cfg(target_os = "windows")]
+    {
+        if let Ok(exe) = std::env::current_exe() {
+            let _ = std::fs::remove_file(exe.with_extension("exe.old"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_parse() {
+        let v = Version::parse("v0.1.5").unwrap();
+        assert_eq!(v.to_string(), "0.1.5");
+        assert!(!v.is_prerelease());
+
+        let v = Version::parse("0.2.0-beta.1").unwrap();
+        assert_eq!(v.to_string(), "0.2.0-beta.1");
+        assert!(v.is_prerelease());
+
+        assert!(Version::parse("nightly").is_none());
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let v = |s| Version::parse(s).unwrap();
+        assert!(v("0.1.6") > v("0.1.5"));
+        assert!(v("0.10.0") > v("0.9.9"));
+        assert!(v("0.2.0") > v("0.2.0-beta.2"));
+        assert!(v("0.2.0-beta.2") > v("0.2.0-beta.1"));
+        assert!(v("0.2.0-beta.1") > v("0.1.9"));
+        assert!(v("0.2.0-rc.10") > v("0.2.0-rc.2"));
+        assert!(v("0.2.0-rc.final") > v("0.2.0-rc.10"));
+        assert!(v("0.2.0-rc.1.1") > v("0.2.0-rc.1"));
+        assert!(v("0.2.0-rc") > v("0.2.0-beta.9"));
+    }
+
+    #[test]
+    fn test_delta_asset_name() {
+        assert_eq!(
+            delta_asset_name(
+                "rustle-windows-x86_64.exe",
+                &Version::parse("0.1.5").unwrap()
+            ),
+            "rustle-windows-x86_64.exe.from-0.1.5.bsdiff"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! What's new after an update
+//!
+//! Each version that runs is recorded in the database, so an upgrade is
+//! noticed even when settings were reset, and Settings → About can list when
+//! each version was first used. Feature highlights for each release ship in
+//! `assets/whats_new.toml`.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use super::updater::Version;
+
+const BUNDLED: &str = include_str!("../../assets/whats_new.toml");
+
+/// Highlights of one release
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub version: String,
+    /// Highlights per locale tag
+    highlights: HashMap<String, Vec<String>>,
+}
+
+impl Release {
+    /// Highlights in the language `tag`, English if it has none
+    pub fn highlights(&self, tag: &str) -> &[String] {
+        self.highlights
+            .get(tag)
+            .or_else(|| self.highlights.get("en"))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+#[derive(Deserialize)]
+struct Bundled {
+    release: Vec<Release>,
+}
+
+static RELEASES: LazyLock<Vec<Release>> = LazyLock::new(|| match parse(BUNDLED) {
+    Ok(releases) => releases,
+    Err(e) => {
+        tracing::error!("Invalid bundled release highlights: {}", e);
+        Vec::new()
+    }
+});
+
+fn parse(source: &str) -> Result<Vec<Release>, toml::de::Error> {
+    let mut releases = toml::from_str::<Bundled>(source)?.release;
+    releases.sort_by(|a, b| Version::parse(&b.version).cmp(&Version::parse(&a.version)));
+    Ok(releases)
+}
+
+/// Every release with highlights, newest first
+pub fn releases() -> &'static [Release] {
+    &RELEASES
+}
+
+/// One version in the what's-new dialog
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub version: String,
+    /// Unix time this version first ran here, if it has
+    pub first_run_at: Option<i64>,
+    /// Bundled highlights, if the release has any
+    pub release: Option<&'static Release>,
+}
+
+/// Releases newer than `previous` up to and including `current`, newest first
+pub fn since(previous: &str, current: &str) -> Vec<Entry> {
+    let (Some(previous), Some(current)) = (Version::parse(previous), Version::parse(current))
+    else {
+        return Vec::new();
+    };
+    releases()
+        .iter()
+        .filter(|release| {
+            Version::parse(&release.version).is_some_and(|v| v > previous && v <= current)
+        })
+        .map(|release| Entry {
+            version: release.version.clone(),
+            first_run_at: None,
+            release: Some(release),
+        })
+        .collect()
+}
+
+/// Versions that ran here, given with when they first did, merged with every
+/// release that has highlights, newest first
+pub fn history(ran: &[(String, i64)]) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = ran
+        .iter()
+        .map(|(version, first_run_at)| Entry {
+            version: version.clone(),
+            first_run_at: Some(*first_run_at),
+            release: releases().iter().find(|r| r.version == *version),
+        })
+        .collect();
+    entries.extend(
+        releases()
+            .iter()
+            .filter(|release| !ran.iter().any(|(version, _)| *version == release.version))
+            .map(|release| Entry {
+                version: release.version.clone(),
+                first_run_at: None,
+                release: Some(release),
+            }),
+    );
+    entries.sort_by(|a, b| Version::parse(&b.version).cmp(&Version::parse(&a.version)));
+    entries
+}
+
+/// Whether going from `previous` to `current` is an upgrade worth announcing
+pub fn is_upgrade(previous: &str, current: &str) -> bool {
+    match (Version::parse(previous), Version::parse(current)) {
+        (Some(previous), Some(current)) => current > previous,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+        [[release]]
+        version = "0.1.0"
+        [release.highlights]
+        en = ["First"]
+
+        [[release]]
+        version = "0.2.0"
+        [release.highlights]
+        en = ["Second"]
+        "zh-CN" = ["第二"]
+    "#;
+
+    #[test]
+    fn test_bundled_highlights_parse() {
+        let releases = parse(BUNDLED).unwrap();
+        assert!(!releases.is_empty());
+        assert!(
+            releases
+                .iter()
+                .all(|r| Version::parse(&r.version).is_some())
+        );
+        assert!(releases.iter().all(|r| !r.highlights("en").is_empty()));
+    }
+
+    #[test]
+    fn test_releases_sorted_newest_first() {
+        let releases = parse(SAMPLE).unwrap();
+        assert_eq!(releases[0].version, "0.2.0");
+        assert_eq!(releases[0].highlights("zh-CN"), ["第二"]);
+        // No Chinese list: falls back to English
+        assert_eq!(releases[1].highlights("zh-CN"), ["First"]);
+    }
+
+    #[test]
+    fn test_history_merges_runs_and_releases() {
+        let newest = &releases()[0].version;
+        let history = history(&[("0.0.1".to_string(), 100)]);
+        assert_eq!(history.last().unwrap().version, "0.0.1");
+        assert_eq!(history.last().unwrap().first_run_at, Some(100));
+        assert!(history.last().unwrap().release.is_none());
+        assert_eq!(history[0].version, *newest);
+        assert!(history[0].first_run_at.is_none());
+    }
+
+    #[test]
+    fn test_is_upgrade() {
+        assert!(is_upgrade("0.1.4", "0.1.5"));
+        assert!(!is_upgrade("0.1.5", "0.1.5"));
+        assert!(!is_upgrade("0.2.0", "0.1.5"));
+        assert!(!is_upgrade("", "0.1.5"));
+    }
+}
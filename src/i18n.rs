@@ -1,50 +1,158 @@
 //! Internationalization (i18n) support for Rustle
-//! Supports multiple languages with easy extensibility
+//!
+//! Translations live in TOML locale files rather than in code so that
+//! community translations can be dropped in without a rebuild.
 //!
 //! Structure:
-//! - mod.rs: Core types (Language, Key, Locale) and translation lookup
-//! - en.rs: English translations
-//! - zh.rs: Chinese translations
+//! - mod.rs: Core types (Language, Direction, Key, Locale) and translation lookup
+//! - catalog.rs: Loading locale files and resolving fallback chains
+//! - `locales/*.toml` at the repository root: the bundled translations
+
+mod catalog;
 
-mod en;
-mod zh;
+use std::sync::LazyLock;
 
-use std::collections::HashMap;
+use catalog::{Catalog, catalogs};
 
-/// Supported languages
+/// A language we have a locale file for
+///
+/// Indexes into the loaded catalogs; the default is English, which every
+/// other language falls back to in the end.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
-pub enum Language {
-    #[default]
-    English,
-    Chinese,
-}
+pub struct Language(usize);
+
+static LANGUAGES: LazyLock<Vec<Language>> =
+    LazyLock::new(|| (0..catalogs().len()).map(Language).collect());
 
 impl Language {
+    fn catalog(&self) -> &'static Catalog {
+        &catalogs()[self.0]
+    }
+
     /// Get language display name
     pub fn display_name(&self) -> &'static str {
-        match self {
-            Language::English => "English",
-            Language::Chinese => "中文",
-        }
+        &self.catalog().name
     }
 
-    /// Get language code
+    /// Get language code, the locale file's tag such as "en" or "zh-CN"
     pub fn code(&self) -> &'static str {
-        match self {
-            Language::English => "en",
-            Language::Chinese => "zh",
-        }
+        &self.catalog().tag
     }
 
-    /// All available languages
+    /// All available languages, bundled ones first
     pub fn all() -> &'static [Language] {
-        &[Language::English, Language::Chinese]
+        &LANGUAGES
+    }
+
+    /// Language for a code stored in settings, English if unknown
+    ///
+    /// Codes saved by older versions ("zh") resolve through the primary
+    /// subtag like any other tag.
+    pub fn from_code(code: &str) -> Self {
+        Self::from_locale_tag(code).unwrap_or_default()
+    }
+
+    /// Language matching a locale tag such as "zh-CN", "zh_TW.UTF-8" or "en_US"
+    ///
+    /// An exact match wins, otherwise the first language sharing the
+    /// primary subtag is used.
+    pub fn from_locale_tag(tag: &str) -> Option<Self> {
+        let tag = tag.split(['.', '@']).next()?.replace('_', "-");
+        catalog::find(catalogs(), &tag)
+            .or_else(|| {
+                let primary = catalog::primary_subtag(&tag);
+                catalogs()
+                    .iter()
+                    .position(|c| catalog::primary_subtag(&c.tag) == primary)
+            })
+            .map(Language)
+    }
+
+    /// The system UI language if it's one we have translations for
+    ///
+    /// Blocking on Windows and macOS, see [`crate::platform::locale`].
+    pub fn system() -> Self {
+        crate::platform::locale::system_locale()
+            .and_then(|tag| Self::from_locale_tag(&tag))
+            .unwrap_or_default()
+    }
+
+    /// Plural category of a count, following the CLDR plural rules
+    pub fn plural_form(&self, count: u64) -> PluralForm {
+        self.catalog().plural.form(count)
+    }
+
+    /// Writing direction, which decides whether the layout is mirrored
+    pub fn direction(&self) -> Direction {
+        self.catalog().direction
+    }
+
+    /// Share of keys the locale file translates itself, from 0.0 to 1.0
+    pub fn completeness(&self) -> f32 {
+        let missing = self.catalog().missing().count();
+        1.0 - missing as f32 / Key::ALL.len() as f32
+    }
+}
+
+/// CLDR plural category
+///
+/// Translations with plural forms list them separated by `|` in the order
+/// of this enum, leaving out the forms their language doesn't use, e.g.
+/// `"{} song|{} songs"` in English. Languages with a single form (Chinese)
+/// give just one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralForm {
+    One,
+    Few,
+    Many,
+    Other,
+}
+
+/// Writing direction of a language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Direction {
+    #[default]
+    LeftToRight,
+    /// Arabic, Hebrew, Persian...: sidebar, player bar and popups are mirrored
+    RightToLeft,
+}
+
+impl Direction {
+    pub fn is_rtl(self) -> bool {
+        self == Direction::RightToLeft
     }
 }
 
-/// Translation keys
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Key {
+/// Declares [`Key`] along with the names locale files refer to it by
+macro_rules! keys {
+    ($($key:ident,)*) => {
+        /// Translation keys
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Key {
+            $($key,)*
+        }
+
+        impl Key {
+            pub const ALL: &'static [Key] = &[$(Key::$key,)*];
+
+            /// Name of the key in locale files
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Key::$key => stringify!($key),)*
+                }
+            }
+
+            pub fn from_name(name: &str) -> Option<Key> {
+                match name {
+                    $(stringify!($key) => Some(Key::$key),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+keys! {
     // App
     AppName,
 
@@ -71,6 +179,37 @@ pub enum Key {
 
     // Search
     SearchPlaceholder,
+    SearchResultsFor,
+    SearchArtistFollow,
+    SearchArtistFollowing,
+    ArtistFollowed,
+    ArtistUnfollowed,
+    NotifyNewRelease,
+    NotifyNewReleases,
+    SearchTabSongs,
+    SearchTabArtists,
+    SearchTabAlbums,
+    SearchTabPlaylists,
+    SearchHeaderArtist,
+    SearchHeaderDuration,
+    SearchPreviousPage,
+    SearchNextPage,
+    Searching,
+    SearchPrompt,
+    SearchNoResults,
+    SearchFailed,
+    SearchHistory,
+    SearchHistoryClear,
+    SearchHistoryEmpty,
+    SearchHotList,
+    SearchHistoryRemove,
+    SearchSourceAll,
+    SearchSourceLocal,
+    SearchSourceOnline,
+    SearchSectionLocal,
+    SearchSectionOnline,
+    SearchShowAllLocal,
+    SearchOnlineEmpty,
 
     // Hero Banner
     HeroTitle,
@@ -79,6 +218,12 @@ pub enum Key {
 
     // Trending
     TrendingSongs,
+    HomeCustomize,
+    HomeCustomizeDone,
+    HomeSectionBanners,
+    HomeSectionTopPicks,
+    HomeSectionToplists,
+    HomeRecentEmpty,
     SeeAll,
 
     // Recently Played
@@ -99,11 +244,23 @@ pub enum Key {
     // Settings Page - Tabs
     SettingsTitle,
     SettingsTabPlayback,
+    SettingsTabSchedules,
     SettingsTabDisplay,
     SettingsTabSystem,
     SettingsTabStorage,
     SettingsTabShortcuts,
     SettingsTabAbout,
+    SettingsSearchPlaceholder,
+    SettingsSearchEmpty,
+    SettingsSearchShow,
+    SettingsReset,
+    SettingsCategoryAll,
+    SettingsCategoryPlayback,
+    SettingsCategoryLyrics,
+    SettingsCategoryNetwork,
+    SettingsCategoryLibrary,
+    SettingsCategoryAppearance,
+    SettingsCategoryAdvanced,
     SettingsTabAccount,
 
     // Settings - Playback Section
@@ -114,6 +271,44 @@ pub enum Key {
     SettingsFadeInOutDesc,
     SettingsVolumeNormalization,
     SettingsVolumeNormalizationDesc,
+    SettingsUiSounds,
+    SettingsUiSoundsDesc,
+    SettingsAutoplaySimilar,
+    SettingsAutoplaySimilarDesc,
+    SettingsSmartResume,
+    SettingsSmartResumeDesc,
+    SettingsSmartResumeAfter,
+    SettingsSmartResumeAfterDesc,
+    SettingsSmartResumeMinutes,
+    SettingsSmartResumeRewind,
+    SettingsSmartResumeRewindDesc,
+    SettingsCallDucking,
+    SettingsCallDuckingDesc,
+    CallActionOff,
+    CallActionLower,
+    CallActionPause,
+    SettingsCallLowerTo,
+    SettingsCallLowerToDesc,
+    SettingsCallAnyApp,
+    SettingsCallAnyAppDesc,
+    SettingsCallAfter,
+    SettingsCallAfterDesc,
+    SettingsSkipBack,
+    SettingsSkipBackDesc,
+    SettingsSkipForward,
+    SettingsSkipForwardDesc,
+    SettingsSeekStep,
+    SettingsSeekStepDesc,
+    SettingsVolumeStep,
+    SettingsVolumeStepDesc,
+    SettingsAutoDjPool,
+    SettingsAutoDjPoolDesc,
+    SettingsAutoDjCrossfade,
+    SettingsAutoDjCrossfadeDesc,
+    SettingsAutoDjCrossfadeOff,
+    AutoDjPoolLiked,
+    AutoDjPoolSimilar,
+    AutoDjPoolPlaylist,
     SettingsEqualizer,
     SettingsEqualizerDesc,
 
@@ -127,6 +322,28 @@ pub enum Key {
     AudioEngineWaveform,
     AudioEngineWaveformDesc,
 
+    // Plugins Page
+    PluginsTitle,
+    PluginsDesc,
+    PluginsOpenFolder,
+    PluginsReload,
+    PluginsEmpty,
+    PluginsLoading,
+    PluginsLoadFailed,
+    PluginHookMetadata,
+    PluginHookLyrics,
+    PluginHookAudioDsp,
+    PluginHookUiPanel,
+
+    // Logs Page
+    LogsTitle,
+    LogsDesc,
+    LogsRefresh,
+    LogsExportDiagnostics,
+    LogsExporting,
+    LogsEmpty,
+    LogsLevel,
+
     // Settings - Account Section
     SettingsAccountTitle,
     SettingsAccountNotLoggedIn,
@@ -140,6 +357,46 @@ pub enum Key {
     SettingsLanguage,
     SettingsPowerSavingMode,
     SettingsPowerSavingModeDesc,
+    SettingsLyricsTranslation,
+    SettingsLyricsTranslationDesc,
+    SettingsLyricsRomanization,
+    SettingsLyricsRomanizationDesc,
+    SettingsLyricsTuning,
+    SettingsLyricsTuningDesc,
+    SettingsLyricsWordFade,
+    SettingsLyricsEmphasisAmount,
+    SettingsLyricsEmphasisBlur,
+    SettingsLyricsSpringStiffness,
+    SettingsLyricsSpringDamping,
+    SettingsLyricsAlignPosition,
+    SettingsLyricsScrollResume,
+    SettingsLyricsTuningPreview,
+    SettingsLyricsTuningReset,
+    SettingsLyricsShareOffer,
+    SettingsLyricsShareOfferDesc,
+    SettingsLyricsShareCredit,
+    SettingsLyricsSaveOffer,
+    SettingsLyricsSaveOfferDesc,
+    SettingsLyricsSaveTarget,
+    SettingsLyricsSaveTargetDesc,
+    SettingsLyricsSaveFolder,
+    SettingsLyricsSaveFolderDesc,
+    SettingsLyricsSaveFolderRun,
+    SettingsLyricsSaveFolderProgress,
+    SettingsArtworkBackdrop,
+    SettingsArtworkBackdropDesc,
+    SettingsCoverTheming,
+    SettingsCoverThemingDesc,
+    SettingsArtworkBackdropOff,
+    SettingsArtworkBackdropStill,
+    SettingsArtworkBackdropAnimated,
+    SettingsLyricsBackground,
+    SettingsLyricsBackgroundDesc,
+    SettingsLyricsBackgroundFlow,
+    SettingsLyricsBackgroundMesh,
+    SettingsLyricsBackgroundFluid,
+    SettingsLyricsBackgroundStarfield,
+    SettingsLyricsBackgroundBlurred,
     SettingsCloseBehavior,
     SettingsCloseBehaviorAsk,
     SettingsCloseBehaviorExit,
@@ -151,6 +408,16 @@ pub enum Key {
     SettingsAudioBuffer,
     SettingsAudioBufferDesc,
     SettingsDefaultDevice,
+    SettingsLaunchAtLogin,
+    SettingsLaunchAtLoginDesc,
+    SettingsStartMinimized,
+    SettingsStartMinimizedDesc,
+    SettingsReleaseNotifications,
+    SettingsReleaseNotificationsDesc,
+    SettingsDailyCheckIn,
+    SettingsDailyCheckInDesc,
+    SettingsResumeOnStartup,
+    SettingsResumeOnStartupDesc,
 
     // Settings - Network Section
     SettingsNetworkTitle,
@@ -160,6 +427,14 @@ pub enum Key {
     SettingsProxyPort,
     SettingsProxyUsername,
     SettingsProxyPassword,
+    SettingsBandwidthLimit,
+    SettingsBandwidthLimitDesc,
+    SettingsBandwidthUnlimited,
+    SettingsMeteredConnection,
+    SettingsMeteredConnectionDesc,
+    SettingsMeteredAuto,
+    SettingsMeteredOn,
+    SettingsMeteredOff,
     SettingsProxyNone,
     SettingsProxySystem,
 
@@ -178,6 +453,7 @@ pub enum Key {
     SettingsShortcutsNavigation,
     SettingsShortcutsUI,
     SettingsShortcutsGeneral,
+    SettingsShortcutsPressKey,
 
     // Settings - About Section
     SettingsAboutTitle,
@@ -202,6 +478,7 @@ pub enum Key {
     ActionToggleQueue,
     ActionToggleSidebar,
     ActionToggleFullscreen,
+    ActionToggleMemoryOverlay,
     ActionEscape,
     ActionDelete,
     ActionSelectAll,
@@ -215,16 +492,37 @@ pub enum Key {
     PlaylistHeaderTitle,
     PlaylistHeaderAlbum,
     PlaylistHeaderAddedDate,
+    PlaylistHeaderTempo,
+    PlaylistHeaderKey,
+    PlaylistSortDefault,
+    PlaylistSortTitle,
+    PlaylistSortArtist,
+    PlaylistSortDuration,
+    PlaylistSortPopularity,
+    PlaylistFilterCached,
+    PlaylistFilterHires,
+    PlaylistLoadingSongs,
+    PlaylistPlaybackShuffle,
+    PlaylistPlaybackQuality,
+    PlaylistPlaybackQualityDefault,
+    PlaylistPlaybackStartMode,
+    PlaylistPlaybackReplaceQueue,
+    PlaylistPlaybackAppendQueue,
+    PlaylistAddedToQueue,
 
     // Discover Page
     DiscoverRecommended,
     DiscoverHot,
     DiscoverSeeAll,
+    DiscoverNewReleases,
     DiscoverDailyRecommend,
     DiscoverDailyRecommendDesc,
     DiscoverDailyRecommendCreator,
     DiscoverLoadFailed,
     DiscoverPlaylistLoadFailed,
+    DiscoverLoadMore,
+    DiscoverLoadMoreFailed,
+    DiscoverRetry,
 
     // Common UI
     Loading,
@@ -232,14 +530,51 @@ pub enum Key {
     Save,
     Delete,
     Refresh,
+    UnknownArtist,
+    UnknownAlbum,
+    LocalPlaylistOwner,
+    PersonalFm,
+    PersonalFmFetchFailed,
+    SongLoadFailed,
 
     // Lyrics Page
     LyricsNoLyrics,
     LyricsPureMusic,
+    LyricsTranslation,
+    LyricsRomanization,
+    SongCredits,
+    CreditsArtists,
+    CreditsAlbum,
+    CreditsCompany,
+    CreditsLoading,
+    CreditsUnavailable,
 
     // Audio Engine
     AudioEngineEqualizerDisabled,
     AudioEngineSpectrum,
+    AudioEngineSpectrumBars,
+    AudioEngineSpectrumLine,
+    AudioEngineDsp,
+    AudioEngineDspDevice,
+    AudioEngineCrossfeed,
+    AudioEngineCrossfeedDesc,
+    AudioEngineBassBoost,
+    AudioEngineBassBoostDesc,
+    AudioEngineNightMode,
+    AudioEngineNightModeDesc,
+    AudioEngineLatency,
+    AudioEngineLatencyDesc,
+    AudioEngineSampleRate,
+    AudioEngineMatchSampleRate,
+    AudioEngineMatchSampleRateDesc,
+    AudioEngineSampleRateNative,
+    AudioEngineSampleRateResampled,
+    LatencyCalibrate,
+    LatencyCalibrationTap,
+    LatencyCalibrationHint,
+    LatencyCalibrationFinish,
+    LatencyCalibrationTooFewTaps,
+    LatencyCalibrationDone,
 
     // Queue Panel
     QueueTitle,
@@ -248,6 +583,9 @@ pub enum Key {
 
     // Playlist View
     PlaylistNoSongs,
+    PlaylistDurationMinutes,
+    PlaylistDurationHours,
+    PlaylistDurationApprox,
 
     // Login Popup
     LoginScanQr,
@@ -255,10 +593,126 @@ pub enum Key {
     LoginRefreshQr,
     LoginLoggedIn,
     LoginLogout,
+    LoginScanWithApp,
+    LoginWaitingForScan,
+    LoginScanned,
+    LoginQrFailed,
+    LoginSuccess,
+    LoginError,
+    LoginFailed,
+    LoginLoggedOut,
+    LoginRequired,
+    LoginSessionExpired,
+    LoginAgain,
+    ApiRateLimited,
+    ApiCaptchaRequired,
+    ApiRegionBlocked,
+    ApiVipRequired,
+    ApiLowerQuality,
+    ApiNetworkError,
+    CheckInClaimed,
+    CheckInFailed,
+    CheckInHistory,
+    CheckInMobile,
+    CheckInWeb,
+
+    // NetEase Cloud Music
+    NcmOfficialOwner,
+    NcmLikedSongs,
+    NcmPlaylistSubscribed,
+    NcmPlaylistUnsubscribed,
+    NcmSongInfoUnavailable,
+    NcmSongUnplayable,
+    NcmOperationFailed,
+    NcmDatabaseError,
 
     // Delete Playlist Dialog
     DeletePlaylistTitle,
     DeletePlaylistConfirm,
+    OpenLinkTitle,
+    OpenLinkConfirm,
+    OpenLinkOpen,
+    OpenLinkFailed,
+
+    // Crash Recovery Dialog
+    CrashDialogTitle,
+    CrashDialogMessage,
+    CrashDialogNoSession,
+    CrashDialogRestore,
+    CrashDialogReport,
+    CrashDialogDismiss,
+
+    // Updater
+    UpdateDialogTitle,
+    UpdateDialogReady,
+    UpdateDialogAvailable,
+    UpdateDialogInstall,
+    UpdateDialogOpenPage,
+    UpdateDialogLater,
+    ChangelogTitle,
+    ChangelogEmpty,
+    ChangelogClose,
+    ChangelogReleaseNotes,
+    ChangelogFirstUsed,
+    ChangelogNoHighlights,
+    ChangelogHistoryTitle,
+    SettingsUpdateChannel,
+    SettingsUpdateChannelDesc,
+    SettingsUpdateChannelStable,
+    SettingsUpdateChannelBeta,
+    SettingsAutoCheckUpdates,
+    SettingsAutoCheckUpdatesDesc,
+    SettingsCheckUpdates,
+    SettingsShowWhatsNew,
+    SettingsShowWhatsNewDesc,
+    SettingsVersionHistory,
+    SettingsVersionHistoryDesc,
+    SettingsVersionHistoryOpen,
+    SettingsProfile,
+    SettingsProfileDesc,
+    SettingsProfileExport,
+    SettingsProfileImport,
+    SettingsProfilePassphrase,
+    SettingsProfilePassphraseDesc,
+    SettingsProfilePassphrasePlaceholder,
+    SettingsProfileExported,
+    SettingsProfileExportFailed,
+    SettingsProfileImported,
+    SettingsProfileImportedLocked,
+    SettingsProfileImportFailed,
+    SettingsProfileWrongPassphrase,
+    SettingsHooks,
+    SettingsHooksDesc,
+    SettingsHooksAdd,
+    SettingsHooksTest,
+    SettingsHooksRemove,
+    SettingsHookTestDone,
+    SettingsHookTestFailed,
+    HookEventTrackChange,
+    HookEventPlay,
+    HookEventPause,
+    HookEventLike,
+    HookEventUnlike,
+    HookActionCommand,
+    HookActionWebhook,
+    SettingsSongAnalysis,
+    SettingsSongAnalysisDesc,
+    SettingsSongAnalysisDone,
+    SettingsSongAnalysisProgress,
+    SettingsSongAnalysisRun,
+    SettingsNowPlayingFiles,
+    SettingsNowPlayingFilesDesc,
+    SettingsNowPlayingFolder,
+    SettingsNowPlayingFolderChange,
+    SettingsNowPlayingFolderOpen,
+    SettingsNowPlayingArtwork,
+    SettingsNowPlayingArtworkDesc,
+    UpdateStatusChecking,
+    UpdateStatusUpToDate,
+    UpdateStatusAvailable,
+    UpdateStatusDownloading,
+    UpdateStatusReady,
+    UpdateStatusFailed,
 
     // Edit Playlist Dialog
     EditPlaylistTitle,
@@ -273,16 +727,285 @@ pub enum Key {
     ExitDialogMessage,
     ExitDialogExit,
     ExitDialogMinimize,
+    ExitDialogRemember,
+
+    // Playback
+    PlayModeSequential,
+    PlayModeLoopAll,
+    PlayModeLoopOne,
+    PlayModeShuffle,
+    PlayModeAutoDj,
+    StopAfterCurrent,
+    StopAfterCurrentOn,
+    StopAfterCurrentOff,
+    BlockCurrentSong,
+    ChaptersTitle,
+    ChapterUntitled,
+    PrevChapter,
+    NextChapter,
+    SkipBack,
+    SkipForward,
+    PlayModeLockedInFm,
+    SeekUnsupported,
+    SeekBuffering,
+    AudioDeviceSwitchFailed,
+    PlaybackError,
+    DownloadFailed,
+    PlaybackStoppedAfterFailures,
+    MusicQualityLossless,
+    PlayerNoSong,
+    PlayerNoSongHint,
+
+    // Equalizer Presets
+    EqualizerPresetFlat,
+    EqualizerPresetPop,
+    EqualizerPresetRock,
+    EqualizerPresetJazz,
+    EqualizerPresetClassical,
+    EqualizerPresetElectronic,
+    EqualizerPresetHipHop,
+    EqualizerPresetAcoustic,
+    EqualizerPresetBassBoost,
+    EqualizerPresetTrebleBoost,
+    EqualizerPresetVocal,
+    EqualizerPresetCustom,
+
+    // Import
+    ImportSelectFolder,
+    ImportScanning,
+    ImportCompleted,
+    ImportDefaultPlaylistName,
+    ImportNoAudioFiles,
+    ImportSucceeded,
+    ImportPartiallyFailed,
+    ImportCancelled,
+    ImportFailed,
+
+    // Relative Time
+    TimeJustNow,
+    TimeMinutesAgo,
+    TimeHoursAgo,
+    TimeDaysAgo,
+    TimeMonthsAgo,
+
+    // Status Messages
+    PlaylistDeleted,
+    UpdateCheckFailed,
+    UpdateInstallFailed,
+    CrashRestoreFailed,
+    SettingsLaunchAtLoginFailed,
+    LogsExported,
+    LogsExportFailed,
+    AnnualReportTitle,
+    AnnualReportDesc,
+    AnnualReportExport,
+    AnnualReportExporting,
+    AnnualReportEmpty,
+    AnnualReportOverview,
+    AnnualReportHours,
+    AnnualReportPlays,
+    AnnualReportSongs,
+    AnnualReportTopSongs,
+    AnnualReportTopArtists,
+    AnnualReportHabits,
+    AnnualReportPeakHour,
+    AnnualReportStreak,
+    AnnualReportStreakDays,
+    AnnualReportStreakFrom,
+    AnnualReportExported,
+    AnnualReportExportFailed,
+    BlocklistTitle,
+    BlocklistDesc,
+    BlocklistArtistPlaceholder,
+    BlocklistBlockArtist,
+    BlocklistArtists,
+    BlocklistNoArtists,
+    BlocklistSongs,
+    BlocklistNoSongs,
+    BlocklistUnblock,
+    TrashTitle,
+    TrashDesc,
+    TrashEmpty,
+    TrashEmptyConfirm,
+    TrashPlaylists,
+    TrashNoPlaylists,
+    TrashSongs,
+    TrashNoSongs,
+    TrashRestore,
+    TrashDeleteForever,
+    TrashDaysLeft,
+    TrashLastDay,
+    SongInfoEdit,
+    SongInfoTitle,
+    SongInfoDesc,
+    SongInfoFieldTitle,
+    SongInfoFieldArtist,
+    SongInfoFieldAlbum,
+    SongInfoNcmValue,
+    SongInfoReset,
+    SettingsListenMode,
+    SettingsListenModeDesc,
+    ListenStart,
+    ListenStop,
+    ListenTitle,
+    ListenPreparing,
+    ListenListening,
+    ListenNoLyrics,
+    ListenNeedsPermission,
+    ListenNothingToMatch,
+    ListenCaptureFailed,
+    SettingsSchedulesTitle,
+    SettingsSchedules,
+    SettingsSchedulesDesc,
+    SettingsSchedulesAdd,
+    SettingsSchedulesRemove,
+    ScheduleOnce,
+    ScheduleRampOff,
+    ScheduleRampMinutes,
+    ScheduleStarted,
+    WeekdayMon,
+    WeekdayTue,
+    WeekdayWed,
+    WeekdayThu,
+    WeekdayFri,
+    WeekdaySat,
+    WeekdaySun,
+    SongBlocked,
+    ArtistBlocked,
+    NcmMatchFind,
+    NcmMatchSearching,
+    NcmMatchLinked,
+    NcmMatchNotFound,
+    NcmMatchFailed,
+    PublishToNcm,
+    PublishMatching,
+    PublishSummary,
+    PublishReviewHint,
+    PublishSkip,
+    PublishConfirm,
+    Publishing,
+    PublishDone,
+    PublishUnmatched,
+    PublishOpen,
+    PublishFailed,
+    HealthCheck,
+    HealthChecking,
+    HealthAllGood,
+    HealthSummary,
+    HealthUnavailable,
+    HealthVipOnly,
+    HealthPaid,
+    HealthReadOnly,
+    HealthRemove,
+    HealthReplace,
+    HealthExport,
+    HealthWorking,
+    HealthRemoved,
+    HealthReplaced,
+    HealthNoAlternates,
+    HealthExported,
+    HealthFailed,
+    HealthUpdateFailed,
+    HealthExportFailed,
+    ExportPlaylist,
+    ExportTitle,
+    ExportSongCount,
+    ExportDesc,
+    ExportFormat,
+    ExportBitrate,
+    ExportFolder,
+    ExportChooseFolder,
+    ExportStart,
+    ExportNothing,
+    ExportProgress,
+    ExportFinished,
+    ExportSkipped,
+    ExportOpenFolder,
+    ProfileLevel,
+    ProfileStats,
+    ProfileRank,
+    ProfileRankWeek,
+    ProfileRankAllTime,
+    ProfileRankHidden,
+    ProfileRankEmpty,
+    ProfilePlays,
+    ProfileCreated,
+    ProfileSubscribed,
+    ProfileNoPlaylists,
+    ProfileLoadFailed,
+    ProfileOpen,
+    LyricsShare,
+    LyricsOpenWindow,
+    LyricsReturnToCurrent,
+    LyricsShareOffer,
+    LyricsShareTitle,
+    LyricsShareHint,
+    LyricsShareCredit,
+    LyricsShareAnonymous,
+    LyricsShareUnsynced,
+    LyricsShareConfirm,
+    LyricsSharing,
+    LyricsShareDone,
+    LyricsShareFailed,
+    LyricsShareEmpty,
+    LyricsSaveToFile,
+    LyricsSaveOffer,
+    LyricsSaveEmbed,
+    LyricsSaveSidecar,
+    LyricsSaved,
+    LyricsSaveNothing,
+    LyricsSaveFailed,
+    LyricsSaveBatchDone,
+    CoverViewerSave,
+    CoverViewerLoadingFull,
+    CoverSaved,
+    CoverSaveFailed,
+    LikedSyncing,
+    LikedSynced,
+    LikedSyncOffline,
+    SongSourceLocal,
+    SongSourceCached,
+    SongSourceNcm,
+    QueueOfflineOnly,
+    QueueNoOfflineSongs,
+    QueueSessions,
+    QueueNoSessions,
+    QueueSessionSongs,
+    QueueSessionRestored,
+    QueueSessionUnavailable,
+    AutoDjFetchFailed,
+    AutoDjPoolEmpty,
+    AutoDjNoSeed,
+    QueueAutoplay,
+    AutoplayStarted,
+    QueueSimilarEnergy,
+    SimilarEnergyNotAnalyzed,
+    SimilarEnergyNoneFound,
+    SimilarEnergyQueued,
+
+    // System Tray
+    TrayPlay,
+    TrayPause,
+    TrayPrevious,
+    TrayNext,
+    TrayFavorite,
+    TrayUnfavorite,
+    TrayPlayMode,
+    TrayUpNext,
+    TrayQueueEmpty,
+    TrayOutputDevice,
+    TraySystemDefault,
+    TrayLyrics,
+    TrayToggleWindow,
+    TrayQuit,
 }
 
 /// Get translation for a key in the specified language
+///
+/// Keys the language doesn't translate come from its fallback chain, e.g.
+/// zh-TW → zh-CN → en.
 pub fn t(lang: Language, key: Key) -> &'static str {
-    let translations: &HashMap<Key, &'static str> = match lang {
-        Language::English => en::translations(),
-        Language::Chinese => zh::translations(),
-    };
-
-    translations.get(&key).copied().unwrap_or("???")
+    lang.catalog().lookup(key).unwrap_or("???")
 }
 
 /// Localization context that can be passed around
@@ -300,4 +1023,82 @@ impl Locale {
     pub fn get(&self, key: Key) -> &'static str {
         t(self.language, key)
     }
+
+    /// Translation for a count, picking the plural form and filling in `{}`
+    pub fn count(&self, key: Key, count: u64) -> String {
+        let forms: Vec<&str> = self.get(key).split('|').collect();
+        let index = self
+            .language
+            .catalog()
+            .plural
+            .forms()
+            .iter()
+            .position(|&form| form == self.language.plural_form(count))
+            .unwrap_or(0);
+        forms[index.min(forms.len() - 1)].replace("{}", &count.to_string())
+    }
+
+    /// Whether the layout should be mirrored for a right-to-left language
+    pub fn is_rtl(&self) -> bool {
+        self.language.direction().is_rtl()
+    }
+
+    /// Put items laid out left to right into reading order
+    pub fn reading_order<T>(&self, mut items: Vec<T>) -> Vec<T> {
+        if self.is_rtl() {
+            items.reverse();
+        }
+        items
+    }
+
+    /// Horizontal alignment of the reading start edge
+    pub fn start(&self) -> iced::Alignment {
+        if self.is_rtl() {
+            iced::Alignment::End
+        } else {
+            iced::Alignment::Start
+        }
+    }
+
+    /// Horizontal alignment of the reading end edge
+    pub fn end(&self) -> iced::Alignment {
+        if self.is_rtl() {
+            iced::Alignment::Start
+        } else {
+            iced::Alignment::End
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(tag: &str) -> Option<&'static str> {
+        Language::from_locale_tag(tag).map(|lang| lang.code())
+    }
+
+    #[test]
+    fn test_language_from_locale_tag() {
+        assert_eq!(code("zh-CN"), Some("zh-CN"));
+        assert_eq!(code("zh_TW.UTF-8"), Some("zh-CN"));
+        assert_eq!(code("en_US"), Some("en"));
+        assert_eq!(code("fr-FR"), None);
+        assert_eq!(Language::from_code("fr"), Language::default());
+        // Code saved by versions before locale files
+        assert_eq!(Language::from_code("zh").code(), "zh-CN");
+    }
+
+    #[test]
+    fn test_plural_forms() {
+        let en = Locale::new(Language::from_code("en"));
+        assert_eq!(en.count(Key::PlaylistSongCount, 1), "1 song");
+        assert_eq!(en.count(Key::PlaylistSongCount, 0), "0 songs");
+        assert_eq!(en.count(Key::PlaylistSongCount, 12), "12 songs");
+
+        // Single form for every count
+        let zh = Locale::new(Language::from_code("zh-CN"));
+        assert_eq!(zh.count(Key::PlaylistSongCount, 1), "1 首歌曲");
+        assert_eq!(zh.count(Key::PlaylistSongCount, 3), "3 首歌曲");
+    }
 }
@@ -0,0 +1,382 @@
+//! Locale catalogs
+//!
+//! The bundled locale files are compiled in; files in the `locales` folder
+//! of the data directory are read once at startup. A user file named after
+//! a bundled tag (e.g. `zh-CN.toml`) patches that translation, any other
+//! tag adds a new language.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use super::{Direction, Key, PluralForm};
+
+/// Locale files shipped with the app, English first since every fallback
+/// chain ends there
+const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../../locales/en.toml")),
+    ("zh-CN", include_str!("../../locales/zh-CN.toml")),
+];
+
+static CATALOGS: LazyLock<Vec<Catalog>> =
+    LazyLock::new(|| load(&crate::utils::data_dir().join("locales")));
+
+pub(super) fn catalogs() -> &'static [Catalog] {
+    &CATALOGS
+}
+
+/// A locale file as written on disk
+#[derive(Deserialize)]
+struct LocaleFile {
+    #[serde(default)]
+    meta: Meta,
+    #[serde(default)]
+    strings: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct Meta {
+    name: Option<String>,
+    direction: Option<String>,
+    plural: Option<String>,
+    fallback: Option<String>,
+}
+
+/// How a language picks between plural forms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PluralRule {
+    /// English, German, Spanish...: one, other
+    OneOther,
+    /// Chinese, Japanese, Korean...: a single form
+    Other,
+    /// Russian, Ukrainian...: one, few, many
+    Slavic,
+}
+
+impl PluralRule {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "one-other" => Some(Self::OneOther),
+            "other" => Some(Self::Other),
+            "slavic" => Some(Self::Slavic),
+            _ => None,
+        }
+    }
+
+    /// Forms this rule uses, in the order translations list them
+    pub(super) fn forms(self) -> &'static [PluralForm] {
+        match self {
+            Self::OneOther => &[PluralForm::One, PluralForm::Other],
+            Self::Other => &[PluralForm::Other],
+            Self::Slavic => &[PluralForm::One, PluralForm::Few, PluralForm::Many],
+        }
+    }
+
+    pub(super) fn form(self, count: u64) -> PluralForm {
+        match self {
+            Self::OneOther if count == 1 => PluralForm::One,
+            Self::OneOther | Self::Other => PluralForm::Other,
+            Self::Slavic => match (count % 10, count % 100) {
+                (1, n) if n != 11 => PluralForm::One,
+                (2..=4, n) if !(12..=14).contains(&n) => PluralForm::Few,
+                _ => PluralForm::Many,
+            },
+        }
+    }
+}
+
+/// One language's translations
+pub(super) struct Catalog {
+    /// Tag the locale file is named after, e.g. "zh-CN"
+    pub tag: String,
+    pub name: String,
+    pub direction: Direction,
+    pub plural: PluralRule,
+    strings: HashMap<Key, String>,
+    fallback: Option<String>,
+    /// Indices of the catalogs to look keys up in, this one first
+    chain: Vec<usize>,
+}
+
+impl Catalog {
+    fn new(tag: &str) -> Self {
+        Self {
+            tag: tag.to_string(),
+            name: tag.to_string(),
+            direction: Direction::LeftToRight,
+            plural: PluralRule::OneOther,
+            strings: HashMap::new(),
+            fallback: None,
+            chain: Vec::new(),
+        }
+    }
+
+    /// Apply a parsed locale file on top of what the catalog already has
+    fn merge(&mut self, file: LocaleFile) {
+        let Meta {
+            name,
+            direction,
+            plural,
+            fallback,
+        } = file.meta;
+        if let Some(name) = name {
+            self.name = name;
+        }
+        match direction.as_deref() {
+            Some("ltr") => self.direction = Direction::LeftToRight,
+            Some("rtl") => self.direction = Direction::RightToLeft,
+            Some(other) => tracing::warn!("Locale {}: unknown direction {:?}", self.tag, other),
+            None => {}
+        }
+        if let Some(plural) = plural {
+            match PluralRule::parse(&plural) {
+                Some(rule) => self.plural = rule,
+                None => tracing::warn!("Locale {}: unknown plural rule {:?}", self.tag, plural),
+            }
+        }
+        if fallback.is_some() {
+            self.fallback = fallback;
+        }
+
+        for (name, value) in file.strings {
+            match Key::from_name(&name) {
+                Some(key) => {
+                    self.strings.insert(key, value);
+                }
+                None => tracing::warn!("Locale {}: unknown key {}", self.tag, name),
+            }
+        }
+    }
+
+    /// Translation of a key, following the fallback chain
+    pub(super) fn lookup(&'static self, key: Key) -> Option<&'static str> {
+        self.chain
+            .iter()
+            .find_map(|&index| CATALOGS[index].strings.get(&key))
+            .map(String::as_str)
+    }
+
+    /// Keys this catalog doesn't translate itself
+    pub(super) fn missing(&self) -> impl Iterator<Item = Key> + '_ {
+        Key::ALL
+            .iter()
+            .copied()
+            .filter(|key| !self.strings.contains_key(key))
+    }
+
+    /// Keys whose translation has a different number of `{}` placeholders
+    /// than `reference`, which would drop or garble the inserted values
+    fn mismatched_placeholders<'a>(
+        &'a self,
+        reference: &'a Catalog,
+    ) -> impl Iterator<Item = Key> + 'a {
+        self.strings.iter().filter_map(move |(key, value)| {
+            let expected = reference.strings.get(key)?;
+            (placeholders(value) != placeholders(expected)).then_some(*key)
+        })
+    }
+}
+
+/// Most `{}` placeholders in any plural form of a translation
+fn placeholders(value: &str) -> usize {
+    value
+        .split('|')
+        .map(|form| form.matches("{}").count())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Language part of a tag: "zh" for "zh-TW"
+pub(super) fn primary_subtag(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+/// Index of the catalog with exactly this tag, ignoring case
+pub(super) fn find(catalogs: &[Catalog], tag: &str) -> Option<usize> {
+    catalogs
+        .iter()
+        .position(|c| c.tag.eq_ignore_ascii_case(tag))
+}
+
+fn load(dir: &Path) -> Vec<Catalog> {
+    let mut catalogs: Vec<Catalog> = BUNDLED
+        .iter()
+        .map(|(tag, source)| {
+            let file = toml::from_str(source).expect("bundled locale file should parse");
+            let mut catalog = Catalog::new(tag);
+            catalog.merge(file);
+            catalog
+        })
+        .collect();
+
+    for (tag, file) in read_dir(dir) {
+        match find(&catalogs, &tag) {
+            Some(index) => {
+                tracing::info!("Locale {}: applying overrides from {:?}", tag, dir);
+                catalogs[index].merge(file);
+            }
+            None => {
+                let mut catalog = Catalog::new(&tag);
+                catalog.merge(file);
+                catalogs.push(catalog);
+            }
+        }
+    }
+
+    for index in 0..catalogs.len() {
+        catalogs[index].chain = fallback_chain(&catalogs, index);
+    }
+
+    report(&catalogs);
+    catalogs
+}
+
+/// Parse every `*.toml` in the user's locales folder, skipping broken files
+fn read_dir(dir: &Path) -> Vec<(String, LocaleFile)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<(String, LocaleFile)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| {
+            let tag = path.file_stem()?.to_str()?.to_string();
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|source| toml::from_str(&source).map_err(|e| e.to_string()));
+            match parsed {
+                Ok(file) => Some((tag, file)),
+                Err(e) => {
+                    tracing::warn!("Skipping locale file {:?}: {}", path, e);
+                    None
+                }
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
+/// Catalogs to look keys up in for `index`: the explicit `fallback` of each
+/// step if it names a known tag, else another catalog for the same language
+/// (zh-TW → zh-CN), and finally English
+fn fallback_chain(catalogs: &[Catalog], index: usize) -> Vec<usize> {
+    let mut chain = vec![index];
+    loop {
+        let current = &catalogs[chain[chain.len() - 1]];
+        let unvisited = |i: &usize| !chain.contains(i);
+        let next = current
+            .fallback
+            .as_deref()
+            .and_then(|tag| find(catalogs, tag))
+            .filter(unvisited)
+            .or_else(|| {
+                let primary = primary_subtag(&current.tag);
+                (0..catalogs.len())
+                    .filter(unvisited)
+                    .find(|&i| primary_subtag(&catalogs[i].tag) == primary)
+            })
+            .or_else(|| Some(0).filter(unvisited));
+        match next {
+            Some(next) => chain.push(next),
+            None => return chain,
+        }
+    }
+}
+
+/// Log how complete each translation is, so translators can see what's left
+fn report(catalogs: &[Catalog]) {
+    let reference = &catalogs[0];
+    for catalog in catalogs {
+        let missing: Vec<&str> = catalog.missing().map(Key::name).collect();
+        if missing.is_empty() {
+            tracing::debug!("Locale {}: complete", catalog.tag);
+        } else {
+            tracing::info!(
+                "Locale {}: {}/{} keys translated, missing: {}",
+                catalog.tag,
+                Key::ALL.len() - missing.len(),
+                Key::ALL.len(),
+                missing.join(", ")
+            );
+        }
+
+        for key in catalog.mismatched_placeholders(reference) {
+            tracing::warn!(
+                "Locale {}: {} has a different number of {{}} placeholders than {}",
+                catalog.tag,
+                key.name(),
+                reference.tag
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled() -> Vec<Catalog> {
+        load(Path::new(""))
+    }
+
+    #[test]
+    fn test_bundled_locales_are_complete() {
+        let catalogs = bundled();
+        for catalog in &catalogs {
+            let missing: Vec<Key> = catalog.missing().collect();
+            assert!(missing.is_empty(), "{} misses {:?}", catalog.tag, missing);
+            let mismatched: Vec<Key> = catalog.mismatched_placeholders(&catalogs[0]).collect();
+            assert!(
+                mismatched.is_empty(),
+                "{} has mismatched placeholders in {:?}",
+                catalog.tag,
+                mismatched
+            );
+        }
+    }
+
+    #[test]
+    fn test_bundled_locales_use_known_keys() {
+        for (tag, source) in BUNDLED {
+            let file: LocaleFile = toml::from_str(source).unwrap();
+            for name in file.strings.keys() {
+                assert!(
+                    Key::from_name(name).is_some(),
+                    "{}: unknown key {}",
+                    tag,
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        let mut catalogs = bundled();
+        catalogs.push(Catalog::new("zh-TW"));
+        let mut fr_ca = Catalog::new("fr-CA");
+        fr_ca.fallback = Some("fr".to_string());
+        catalogs.push(fr_ca);
+
+        // zh-TW → zh-CN → en
+        assert_eq!(fallback_chain(&catalogs, 2), vec![2, 1, 0]);
+        // Unknown explicit fallback goes straight to English
+        assert_eq!(fallback_chain(&catalogs, 3), vec![3, 0]);
+        assert_eq!(fallback_chain(&catalogs, 0), vec![0]);
+    }
+
+    #[test]
+    fn test_slavic_plural_rule() {
+        let rule = PluralRule::Slavic;
+        assert_eq!(rule.form(1), PluralForm::One);
+        assert_eq!(rule.form(21), PluralForm::One);
+        assert_eq!(rule.form(11), PluralForm::Many);
+        assert_eq!(rule.form(3), PluralForm::Few);
+        assert_eq!(rule.form(13), PluralForm::Many);
+        assert_eq!(rule.form(25), PluralForm::Many);
+    }
+}
@@ -15,8 +15,19 @@ mod ui;
 mod utils;
 
 fn main() -> iced::Result {
-    // Initialize tracing for logging
-    tracing_subscriber::fmt::init();
+    features::startup::mark_process_start();
+
+    // Resolve portable mode before anything touches the data directories
+    utils::init_portable_mode();
+
+    // Initialize tracing for logging (stderr + in-app log buffer)
+    features::logs::init();
+    if let Some(root) = utils::portable_root() {
+        tracing::info!("Portable mode: storing data in {}", root.display());
+    }
+
+    // Save session and crash report if the app panics
+    features::crash::install_hook();
 
     // Run the application as a daemon (keeps running when windows are closed)
     // This allows the app to run in the background with system tray
@@ -4,14 +4,32 @@
 //! organized by feature with platform implementations inside each feature module.
 //!
 //! # Structure
+//! - `autostart/` - Launch at login registration
 //! - `tray/` - System tray functionality
 //! - `media_controls/` - Media control integration (MPRIS on Linux)
 //! - `window/` - Window behavior differences
 //! - `theme.rs` - Platform-specific theme constants
 //! - `keybindings.rs` - Keybinding display format
+//! - `locale.rs` - System UI language
+//! - `memory.rs` - Process memory usage
+//! - `notification.rs` - Desktop notifications
+//! - `shell.rs` - Opening files, folders and links in the desktop environment
+//! - `network.rs` - Connection information such as metered status
+//! - `microphone.rs` - Apps recording from the microphone
+//! - `power.rs` - System sleep and wake notifications
+//! - `process.rs` - Spawning helper programs without a console window
 
+pub mod autostart;
 pub mod keybindings;
+pub mod locale;
 pub mod media_controls;
+pub mod memory;
+pub mod microphone;
+pub mod network;
+pub mod notification;
+pub mod power;
+pub mod process;
+pub mod shell;
 pub mod theme;
 pub mod tray;
 pub mod window;
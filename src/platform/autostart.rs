@@ -0,0 +1,54 @@
+//! Launch-at-login registration
+//!
+//! Registers Rustle with the desktop session so it starts when the user logs in:
+//! - Linux: XDG autostart `.desktop` entry
+//! - Windows: `HKCU\...\Run` registry value
+//! - macOS: LaunchAgent property list
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+/// Enable or disable launching Rustle at login
+pub fn set_enabled(enabled: bool) -> anyhow::Result<()> {
+    // Registering would write to the user profile, which portable mode avoids
+    if enabled && crate::utils::portable_root().is_some() {
+        anyhow::bail!("Launch at login is not available in portable mode");
+    }
+
+    let exe = launch_path()?;
+    tracing::info!("Setting launch at login: {} ({:?})", enabled, exe);
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::set_enabled(enabled, &exe)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        windows::set_enabled(enabled, &exe)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        macos::set_enabled(enabled, &exe)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        let _ = (enabled, exe);
+        anyhow::bail!("Launch at login is not supported on this platform")
+    }
+}
+
+/// Program to start at login
+///
+/// An AppImage runs from a mount that only exists while it is running, so
+/// the AppImage file itself is registered rather than the executable in it.
+fn launch_path() -> std::io::Result<std::path::PathBuf> {
+    #[cfg(target_os = "linux")]
+    if let Some(appimage) = std::env::var_os("APPIMAGE").filter(|path| !path.is_empty()) {
+        return Ok(appimage.into());
+    }
+    std::env::current_exe()
+}
@@ -0,0 +1,85 @@
+//! Linux autostart implementation (XDG autostart spec)
+
+use std::path::{Path, PathBuf};
+
+fn desktop_entry_path() -> anyhow::Result<PathBuf> {
+    let config_dir =
+        dirs::config_dir().ok_or_else(|| anyhow::anyhow!("Config directory not found"))?;
+    Ok(config_dir.join("autostart").join("rustle.desktop"))
+}
+
+pub fn set_enabled(enabled: bool, exe: &Path) -> anyhow::Result<()> {
+    let path = desktop_entry_path()?;
+
+    if !enabled {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Rustle\n\
+         Exec={}\n\
+         Icon=rustle\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled=true\n",
+        exec_value(exe)
+    );
+    std::fs::write(&path, entry)?;
+    Ok(())
+}
+
+/// `exe` as a quoted `Exec` argument, escaped per the desktop entry spec
+///
+/// Quoting escapes `"`, `` ` ``, `$` and `\` and field codes need `%%`; the
+/// string-value escaping of the whole key then doubles every backslash.
+fn exec_value(exe: &Path) -> String {
+    let mut quoted = String::from("\"");
+    for c in exe.to_string_lossy().chars() {
+        match c {
+            '"' | '`' | '$' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '%' => quoted.push_str("%%"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    let mut value = String::new();
+    for c in quoted.chars() {
+        match c {
+            '\\' => value.push_str("\\\\"),
+            '\n' => value.push_str("\\n"),
+            '\t' => value.push_str("\\t"),
+            '\r' => value.push_str("\\r"),
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_value_escaping() {
+        assert_eq!(
+            exec_value(Path::new("/opt/My Apps/rustle")),
+            "\"/opt/My Apps/rustle\""
+        );
+        assert_eq!(
+            exec_value(Path::new("/home/a$b/100%/\"x\"/r\\s")),
+            r#""/home/a\\$b/100%%/\\"x\\"/r\\\\s""#
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! macOS autostart implementation (per-user LaunchAgent)
+
+use std::path::{Path, PathBuf};
+
+const LABEL: &str = "com.arcticfox.rustle";
+
+fn launch_agent_path() -> anyhow::Result<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Home directory not found"))?;
+    Ok(home
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{}.plist", LABEL)))
+}
+
+/// Escape a string for use inside a plist `<string>` element
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub fn set_enabled(enabled: bool, exe: &Path) -> anyhow::Result<()> {
+    let path = launch_agent_path()?;
+
+    if !enabled {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let plist = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#,
+        LABEL,
+        xml_escape(&exe.to_string_lossy())
+    );
+    std::fs::write(&path, plist)?;
+    Ok(())
+}
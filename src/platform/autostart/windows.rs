@@ -0,0 +1,32 @@
+//! Windows autostart implementation (per-user Run registry key)
+
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+
+use crate::platform::process::CREATE_NO_WINDOW;
+
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+const VALUE_NAME: &str = "Rustle";
+
+pub fn set_enabled(enabled: bool, exe: &Path) -> anyhow::Result<()> {
+    let mut cmd = Command::new("reg");
+    if enabled {
+        let value = format!("\"{}\"", exe.display());
+        cmd.args([
+            "add", RUN_KEY, "/v", VALUE_NAME, "/t", "REG_SZ", "/d", &value, "/f",
+        ]);
+    } else {
+        cmd.args(["delete", RUN_KEY, "/v", VALUE_NAME, "/f"]);
+    }
+
+    let output = cmd.creation_flags(CREATE_NO_WINDOW).output()?;
+    // Deleting a value that was never written is not an error for us
+    if !output.status.success() && enabled {
+        anyhow::bail!(
+            "reg add failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! System locale
+
+/// The user's preferred UI locale as a BCP 47-ish tag ("zh-CN", "en_US")
+///
+/// Returns `None` if the platform doesn't report one. Blocking (spawns a
+/// process on Windows and macOS), only meant for first-run detection.
+pub fn system_locale() -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::system_locale()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        unix::system_locale().or_else(macos::system_locale)
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        unix::system_locale()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod unix {
+    /// Locale variables in the order POSIX gives them precedence
+    const VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+    pub fn system_locale() -> Option<String> {
+        VARS.iter()
+            .filter_map(|var| std::env::var(var).ok())
+            // "C" and "POSIX" say nothing about the user's language
+            .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    use crate::platform::process::CREATE_NO_WINDOW;
+
+    pub fn system_locale() -> Option<String> {
+        let output = Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "(Get-UICulture).Name",
+            ])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::process::Command;
+
+    /// Apps launched from Finder don't inherit LANG, ask the user defaults
+    pub fn system_locale() -> Option<String> {
+        let output = Command::new("defaults")
+            .args(["read", "-g", "AppleLocale"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!name.is_empty()).then_some(name)
+    }
+}
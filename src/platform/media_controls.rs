@@ -6,6 +6,12 @@
 //! - Windows: Uses System Media Transport Controls (SMTC) via souvlaki
 //! - macOS: Uses MPNowPlayingInfoCenter via souvlaki
 //! - WASM: No-op (not available)
+//!
+//! Bluetooth headphones and car stereos get their track info from these same
+//! paths: BlueZ forwards MPRIS over AVRCP, and Windows/macOS forward the
+//! system media session. On Linux, AVRCP absolute volume arrives as MPRIS
+//! volume changes ([`MediaCommand::SetVolume`]), so the app volume follows
+//! the headphone buttons; elsewhere the system handles headset volume itself.
 
 use tokio::sync::mpsc;
 
@@ -34,13 +40,14 @@ pub enum MediaCommand {
 }
 
 /// Track metadata for media controls
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct MediaMetadata {
     pub track_id: Option<String>,
     pub title: Option<String>,
     pub artists: Vec<String>,
     pub album: Option<String>,
     pub album_artists: Vec<String>,
+    pub track_number: Option<i32>,
     pub length_us: Option<i64>,
     pub art_url: Option<String>,
 }
@@ -116,6 +123,34 @@ pub fn start_media_controls() -> (MediaHandle, mpsc::UnboundedReceiver<MediaComm
     (MediaHandle { _phantom: () }, rx)
 }
 
+/// Artwork URL for a cover: web URLs as they are, local paths as `file://` URLs
+///
+/// Local paths are percent-encoded and use forward slashes, which SMTC and
+/// BlueZ's cover art transfer need to open the file.
+pub fn art_url(cover: &str) -> String {
+    if cover.starts_with("http://") || cover.starts_with("https://") {
+        return cover.to_string();
+    }
+    let path = cover.replace('\\', "/");
+    let encoded: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            // Keep the drive colon in `C:` readable
+            if segment.len() == 2 && segment.ends_with(':') {
+                segment.to_string()
+            } else {
+                urlencoding::encode(segment).into_owned()
+            }
+        })
+        .collect();
+    let encoded = encoded.join("/");
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
 /// Check if media controls are available on this platform
 pub fn is_available() -> bool {
     cfg!(any(
@@ -124,3 +159,26 @@ pub fn is_available() -> bool {
         target_os = "macos"
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn art_url_keeps_web_urls() {
+        let url = "https://p1.music.126.net/cover.jpg?param=300y300";
+        assert_eq!(art_url(url), url);
+    }
+
+    #[test]
+    fn art_url_encodes_local_paths() {
+        assert_eq!(
+            art_url("/home/me/.cache/rustle/covers/My Album #1.jpg"),
+            "file:///home/me/.cache/rustle/covers/My%20Album%20%231.jpg"
+        );
+        assert_eq!(
+            art_url("C:\\Users\\me\\covers\\a b.png"),
+            "file:///C:/Users/me/covers/a%20b.png"
+        );
+    }
+}
@@ -2,10 +2,11 @@
 
 use mpris_server::{
     LocalPlayerInterface, LocalRootInterface, LocalServer, LoopStatus, Metadata, PlaybackRate,
-    PlaybackStatus, Property, Time, TrackId, Volume,
+    PlaybackStatus, Property, Signal, Time, TrackId, Volume,
     zbus::{Result, fdo},
 };
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tokio::sync::mpsc;
 
 use super::{MediaCommand, MediaMetadata, MediaPlaybackStatus, MediaState};
@@ -16,8 +17,7 @@ fn to_mpris_metadata(meta: &MediaMetadata) -> Metadata {
 
     if let Some(ref track_id) = meta.track_id {
         builder = builder.trackid(
-            TrackId::try_from(format!("/org/rustle/track/{}", track_id))
-                .unwrap_or_else(|_| TrackId::NO_TRACK),
+            TrackId::try_from(track_object_path(track_id)).unwrap_or_else(|_| TrackId::NO_TRACK),
         );
     }
 
@@ -37,6 +37,10 @@ fn to_mpris_metadata(meta: &MediaMetadata) -> Metadata {
         builder = builder.album_artist(meta.album_artists.clone());
     }
 
+    if let Some(number) = meta.track_number {
+        builder = builder.track_number(number);
+    }
+
     if let Some(length) = meta.length_us {
         builder = builder.length(Time::from_micros(length));
     }
@@ -48,6 +52,36 @@ fn to_mpris_metadata(meta: &MediaMetadata) -> Metadata {
     builder.build()
 }
 
+/// D-Bus object path for a track ID
+///
+/// Object paths only allow `[A-Za-z0-9_]` per element, so anything else in
+/// the ID is replaced; without a valid ID, AVRCP devices never see the track
+/// change.
+fn track_object_path(track_id: &str) -> String {
+    let element: String = track_id
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() => c,
+            _ => '_',
+        })
+        .collect();
+    format!("/org/rustle/track/{}", element)
+}
+
+/// Position jump, beyond normal playback, that counts as a seek
+const SEEK_THRESHOLD_US: i64 = 1_500_000;
+
+/// Whether `state` jumped from where playback would have carried `last`
+fn has_seeked(last: &MediaState, elapsed_us: i64, state: &MediaState) -> bool {
+    let expected = if last.status == MediaPlaybackStatus::Playing {
+        last.position_us + elapsed_us
+    } else {
+        last.position_us
+    };
+    state.metadata.track_id == last.metadata.track_id
+        && (state.position_us - expected).abs() > SEEK_THRESHOLD_US
+}
+
 impl From<MediaPlaybackStatus> for PlaybackStatus {
     fn from(status: MediaPlaybackStatus) -> Self {
         match status {
@@ -311,6 +345,37 @@ impl LinuxMediaHandle {
     }
 }
 
+/// Properties of `state` that differ from `last`, all of them at first
+fn changed_properties(last: Option<&MediaState>, state: &MediaState) -> Vec<Property> {
+    let mut changed = Vec::new();
+    if last.is_none_or(|l| l.status != state.status) {
+        changed.push(Property::PlaybackStatus(state.status.into()));
+    }
+    if last.is_none_or(|l| l.metadata != state.metadata) {
+        changed.push(Property::Metadata(to_mpris_metadata(&state.metadata)));
+    }
+    // Lets AVRCP absolute volume follow the app volume
+    if last.is_none_or(|l| (l.volume - state.volume).abs() > f64::EPSILON) {
+        changed.push(Property::Volume(state.volume));
+    }
+    if last.is_none_or(|l| l.can_go_next != state.can_go_next) {
+        changed.push(Property::CanGoNext(state.can_go_next));
+    }
+    if last.is_none_or(|l| l.can_go_previous != state.can_go_previous) {
+        changed.push(Property::CanGoPrevious(state.can_go_previous));
+    }
+    if last.is_none_or(|l| l.can_play != state.can_play) {
+        changed.push(Property::CanPlay(state.can_play));
+    }
+    if last.is_none_or(|l| l.can_pause != state.can_pause) {
+        changed.push(Property::CanPause(state.can_pause));
+    }
+    if last.is_none_or(|l| l.can_seek != state.can_seek) {
+        changed.push(Property::CanSeek(state.can_seek));
+    }
+    changed
+}
+
 /// Start MPRIS service
 pub fn start() -> (LinuxMediaHandle, mpsc::UnboundedReceiver<MediaCommand>) {
     let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
@@ -339,17 +404,26 @@ pub fn start() -> (LinuxMediaHandle, mpsc::UnboundedReceiver<MediaCommand>) {
             tokio::select! {
                 _ = server.run() => {}
                 _ = async {
+                    let mut last: Option<(MediaState, Instant)> = None;
                     while let Some(state) = state_rx.recv().await {
-                        // Send PropertiesChanged signal to notify clients like Waybar
-                        let _ = server.properties_changed([
-                            Property::PlaybackStatus(state.status.into()),
-                            Property::Metadata(to_mpris_metadata(&state.metadata)),
-                            Property::CanGoNext(state.can_go_next),
-                            Property::CanGoPrevious(state.can_go_previous),
-                            Property::CanPlay(state.can_play),
-                            Property::CanPause(state.can_pause),
-                            Property::CanSeek(state.can_seek),
-                        ]).await;
+                        // Only signal what changed: updates come every playback
+                        // tick, and Bluetooth devices treat each metadata signal
+                        // as a new track
+                        let changed = changed_properties(last.as_ref().map(|(s, _)| s), &state);
+                        if !changed.is_empty() {
+                            let _ = server.properties_changed(changed).await;
+                        }
+                        if let Some((last_state, at)) = &last {
+                            let elapsed_us = at.elapsed().as_micros() as i64;
+                            if has_seeked(last_state, elapsed_us, &state) {
+                                let _ = server
+                                    .emit(Signal::Seeked {
+                                        position: Time::from_micros(state.position_us),
+                                    })
+                                    .await;
+                            }
+                        }
+                        last = Some((state, Instant::now()));
                     }
                 } => {}
             }
@@ -358,3 +432,54 @@ pub fn start() -> (LinuxMediaHandle, mpsc::UnboundedReceiver<MediaCommand>) {
 
     (LinuxMediaHandle { state, state_tx }, cmd_rx)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_paths_are_valid_object_paths() {
+        assert_eq!(track_object_path("ncm_1234"), "/org/rustle/track/ncm_1234");
+        assert_eq!(track_object_path("a-b.c"), "/org/rustle/track/a_b_c");
+        assert!(TrackId::try_from(track_object_path("ncm_1234")).is_ok());
+    }
+
+    #[test]
+    fn only_changes_are_signalled() {
+        let state = MediaState {
+            status: MediaPlaybackStatus::Playing,
+            volume: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(changed_properties(None, &state).len(), 8);
+        assert!(changed_properties(Some(&state), &state).is_empty());
+
+        let louder = MediaState {
+            volume: 0.6,
+            ..state.clone()
+        };
+        assert!(matches!(
+            changed_properties(Some(&state), &louder).as_slice(),
+            [Property::Volume(_)]
+        ));
+    }
+
+    #[test]
+    fn position_jumps_count_as_seeks() {
+        let playing = MediaState {
+            status: MediaPlaybackStatus::Playing,
+            position_us: 10_000_000,
+            ..Default::default()
+        };
+        let moved_on = MediaState {
+            position_us: 10_100_000,
+            ..playing.clone()
+        };
+        let jumped = MediaState {
+            position_us: 60_000_000,
+            ..playing.clone()
+        };
+        assert!(!has_seeked(&playing, 100_000, &moved_on));
+        assert!(has_seeked(&playing, 100_000, &jumped));
+    }
+}
@@ -46,6 +46,9 @@ struct MetadataCache {
     album: Option<String>,
     cover_url: Option<String>,
     duration: Option<Duration>,
+    /// Metadata last sent, so it's only resent on change; every send
+    /// reloads the artwork on connected Bluetooth devices
+    sent: Option<MediaMetadata>,
 }
 
 impl std::fmt::Debug for SouvlakiMediaHandle {
@@ -58,7 +61,16 @@ impl SouvlakiMediaHandle {
     /// Update media controls state
     pub fn update(&self, state: MediaState) {
         // Update metadata cache
-        {
+        let metadata_changed = {
+            let mut cache = self.metadata_cache.lock().unwrap();
+            if cache.sent.as_ref() == Some(&state.metadata) {
+                false
+            } else {
+                cache.sent = Some(state.metadata.clone());
+                true
+            }
+        };
+        if metadata_changed {
             let mut cache = self.metadata_cache.lock().unwrap();
             cache.title = state.metadata.title.clone();
             cache.artist = if !state.metadata.artists.is_empty() {
@@ -81,6 +93,10 @@ impl SouvlakiMediaHandle {
                 let playback = to_souvlaki_playback(state.status, state.position_us);
                 let _ = controls.set_playback(playback);
 
+                if !metadata_changed {
+                    return;
+                }
+
                 // Set metadata (need to borrow from cache)
                 let cache = self.metadata_cache.lock().unwrap();
                 let metadata = SouvlakiMetadata {
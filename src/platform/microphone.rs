@@ -0,0 +1,308 @@
+//! Apps recording from the microphone
+//!
+//! Windows records which apps are using the microphone in the privacy
+//! consent store, the same place the taskbar microphone icon reads. On Linux
+//! PulseAudio, and PipeWire through its PulseAudio server, lists every
+//! recording stream with the app it belongs to.
+
+/// Names of the apps recording from a microphone right now
+///
+/// Returns `None` where the platform doesn't say. Rustle's own recordings
+/// are left out. Blocking, call off the UI thread.
+pub fn recording_apps() -> Option<Vec<String>> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::recording_apps()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        linux::recording_apps()
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+    use windows_sys::Win32::System::Registry::{
+        HKEY, HKEY_CURRENT_USER, KEY_READ, RRF_RT_REG_QWORD, RegCloseKey, RegEnumKeyExW,
+        RegGetValueW, RegOpenKeyExW,
+    };
+
+    const CONSENT_STORE: &str = r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+
+    /// Subkey desktop apps are listed under, one level below store apps
+    const NON_PACKAGED: &str = "NonPackaged";
+
+    pub fn recording_apps() -> Option<Vec<String>> {
+        let store = Key::open(HKEY_CURRENT_USER, CONSENT_STORE)?;
+        let mut apps = Vec::new();
+        for name in store.subkeys() {
+            if name == NON_PACKAGED {
+                let Some(desktop) = Key::open(store.0, &name) else {
+                    continue;
+                };
+                for name in desktop.subkeys() {
+                    if desktop.in_use(&name) {
+                        apps.push(app_name(&name));
+                    }
+                }
+            } else if store.in_use(&name) {
+                apps.push(app_name(&name));
+            }
+        }
+
+        let own = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().to_lowercase()));
+        apps.retain(|app| own.as_deref() != Some(app.to_lowercase().as_str()));
+        Some(apps)
+    }
+
+    /// Store apps are keyed by package family name, desktop apps by their
+    /// path with `#` for `\`
+    fn app_name(key: &str) -> String {
+        let file = key.rsplit('#').next().unwrap_or(key);
+        let name = match file.rsplit_once('.') {
+            Some((stem, ext)) if ext.eq_ignore_ascii_case("exe") => stem,
+            // Package family names end in "_<publisher hash>"
+            _ => file.split('_').next().unwrap_or(file),
+        };
+        name.to_string()
+    }
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain([0]).collect()
+    }
+
+    /// Registry key open for reading, closed on drop
+    struct Key(HKEY);
+
+    impl Key {
+        fn open(parent: HKEY, path: &str) -> Option<Self> {
+            let path = wide(path);
+            let mut key = std::ptr::null_mut();
+            // SAFETY: `path` is NUL-terminated and `key` outlives the call
+            let status = unsafe { RegOpenKeyExW(parent, path.as_ptr(), 0, KEY_READ, &mut key) };
+            (status == ERROR_SUCCESS).then_some(Self(key))
+        }
+
+        /// Names of the keys directly below this one
+        fn subkeys(&self) -> Vec<String> {
+            // Key names are at most 255 characters
+            let mut name = [0u16; 256];
+            let mut names = Vec::new();
+            for index in 0.. {
+                let mut len = name.len() as u32;
+                // SAFETY: `len` holds the capacity of `name`; the optional
+                // class and write time outputs are null
+                let status = unsafe {
+                    RegEnumKeyExW(
+                        self.0,
+                        index,
+                        name.as_mut_ptr(),
+                        &mut len,
+                        std::ptr::null(),
+                        std::ptr::null_mut(),
+                        std::ptr::null_mut(),
+                        std::ptr::null_mut(),
+                    )
+                };
+                if status != ERROR_SUCCESS {
+                    break;
+                }
+                names.push(String::from_utf16_lossy(&name[..len as usize]));
+            }
+            names
+        }
+
+        /// Whether the app under `subkey` is using the microphone, which the
+        /// store says with a zero stop time
+        fn in_use(&self, subkey: &str) -> bool {
+            let subkey = wide(subkey);
+            let value = wide("LastUsedTimeStop");
+            let mut stop = 0u64;
+            let mut size = std::mem::size_of::<u64>() as u32;
+            // SAFETY: Both names are NUL-terminated and `size` is the size
+            // of `stop`, the only type RRF_RT_REG_QWORD lets through
+            let status = unsafe {
+                RegGetValueW(
+                    self.0,
+                    subkey.as_ptr(),
+                    value.as_ptr(),
+                    RRF_RT_REG_QWORD,
+                    std::ptr::null_mut(),
+                    (&mut stop as *mut u64).cast(),
+                    &mut size,
+                )
+            };
+            status == ERROR_SUCCESS && stop == 0
+        }
+    }
+
+    impl Drop for Key {
+        fn drop(&mut self) {
+            // SAFETY: The key was opened by `Key::open` and is closed once
+            unsafe { RegCloseKey(self.0) };
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::HashSet;
+    use std::process::Command;
+
+    use parking_lot::Mutex;
+
+    /// Recording streams last seen, and which sources were monitors then
+    static KNOWN: Mutex<Known> = Mutex::new(Known {
+        streams: Vec::new(),
+        monitors: Vec::new(),
+    });
+
+    struct Known {
+        streams: Vec<String>,
+        monitors: Vec<String>,
+    }
+
+    /// A recording stream and the app it belongs to
+    struct Stream {
+        index: String,
+        source: String,
+        app: String,
+    }
+
+    fn pactl(args: &[&str]) -> Option<String> {
+        let output = Command::new("pactl").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    pub fn recording_apps() -> Option<Vec<String>> {
+        let streams = parse(&pactl(&["list", "source-outputs"])?, std::process::id());
+
+        // Sources only need listing again when a stream has come or gone
+        let mut known = KNOWN.lock();
+        let indices: Vec<String> = streams.iter().map(|s| s.index.clone()).collect();
+        if indices != known.streams {
+            let monitors = if streams.is_empty() {
+                HashSet::new()
+            } else {
+                monitor_sources(&pactl(&["list", "short", "sources"])?)
+            };
+            known.monitors = monitors.into_iter().collect();
+            known.streams = indices;
+        }
+
+        Some(
+            streams
+                .into_iter()
+                .filter(|stream| !known.monitors.contains(&stream.source))
+                .map(|stream| stream.app)
+                .collect(),
+        )
+    }
+
+    /// Indices of sources that carry what is played rather than a microphone
+    fn monitor_sources(short_list: &str) -> HashSet<String> {
+        short_list
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let index = fields.next()?;
+                let name = fields.next()?;
+                name.ends_with(".monitor").then(|| index.to_string())
+            })
+            .collect()
+    }
+
+    /// Recording streams of other processes
+    fn parse(output: &str, own_pid: u32) -> Vec<Stream> {
+        let mut streams = Vec::new();
+        for stream in output.split("Source Output #").skip(1) {
+            let index = stream.lines().next().unwrap_or_default().trim();
+            let mut source = None;
+            let mut name = None;
+            let mut binary = None;
+            let mut pid = None;
+            for line in stream.lines().map(str::trim) {
+                if let Some(value) = line.strip_prefix("Source: ") {
+                    source = Some(value.trim());
+                } else if let Some((key, value)) = line.split_once(" = ") {
+                    let value = value.trim_matches('"');
+                    match key {
+                        "application.name" => name = Some(value),
+                        "application.process.binary" => binary = Some(value),
+                        "application.process.id" => pid = value.parse::<u32>().ok(),
+                        _ => {}
+                    }
+                }
+            }
+            if pid == Some(own_pid) {
+                continue;
+            }
+            if let Some(app) = name.or(binary) {
+                streams.push(Stream {
+                    index: index.to_string(),
+                    source: source.unwrap_or_default().to_string(),
+                    app: app.to_string(),
+                });
+            }
+        }
+        streams
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const SOURCES: &str = "0\talsa_output.pci.analog-stereo.monitor\tPipeWire\ts32le 2ch 48000Hz\tSUSPENDED\n\
+            1\talsa_input.pci.analog-stereo\tPipeWire\ts32le 2ch 48000Hz\tRUNNING\n";
+
+        const OUTPUTS: &str = r#"Source Output #41
+	Driver: PipeWire
+	Client: 60
+	Source: 1
+	Properties:
+		application.name = "ZOOM VoiceEngine"
+		application.process.id = "2001"
+		application.process.binary = "zoom"
+
+Source Output #42
+	Driver: PipeWire
+	Source: 0
+	Properties:
+		application.name = "cava"
+		application.process.id = "2002"
+
+Source Output #43
+	Driver: PipeWire
+	Source: 1
+	Properties:
+		application.name = "Rustle"
+		application.process.id = "2003"
+"#;
+
+        #[test]
+        fn test_monitor_sources() {
+            let monitors = monitor_sources(SOURCES);
+            assert_eq!(monitors, HashSet::from(["0".to_string()]));
+        }
+
+        #[test]
+        fn test_skips_own_streams() {
+            let streams = parse(OUTPUTS, 2003);
+            let apps: Vec<_> = streams
+                .iter()
+                .map(|s| (s.index.as_str(), s.source.as_str(), s.app.as_str()))
+                .collect();
+            assert_eq!(apps, [("41", "1", "ZOOM VoiceEngine"), ("42", "0", "cava")]);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Network connection information
+
+use std::net::{IpAddr, UdpSocket};
+
+/// Whether the active internet connection is metered
+///
+/// Returns `None` where the platform doesn't expose connection cost; the
+/// user's manual setting applies there. Blocking, call off the UI thread.
+pub fn is_metered() -> Option<bool> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::is_metered()
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        None
+    }
+}
+
+/// Local address of the route to the internet
+///
+/// It changes when traffic moves to another interface or a VPN comes up.
+/// Connecting a UDP socket only picks the route, nothing is sent.
+pub fn route_address() -> Option<IpAddr> {
+    let probe = |bind: &str, target: &str| {
+        let socket = UdpSocket::bind(bind).ok()?;
+        socket.connect(target).ok()?;
+        socket.local_addr().ok().map(|addr| addr.ip())
+    };
+    probe("0.0.0.0:0", "1.1.1.1:80").or_else(|| probe("[::]:0", "[2606:4700:4700::1111]:80"))
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    use crate::platform::process::CREATE_NO_WINDOW;
+
+    /// Prints "<cost type> <roaming> <over limit>" for the internet profile
+    const QUERY: &str = "$p = [Windows.Networking.Connectivity.NetworkInformation,\
+        Windows.Networking.Connectivity,ContentType=WindowsRuntime]::\
+        GetInternetConnectionProfile(); \
+        if ($p) { $c = $p.GetConnectionCost(); \
+        \"$($c.NetworkCostType) $($c.Roaming) $($c.OverDataLimit)\" }";
+
+    pub fn is_metered() -> Option<bool> {
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", QUERY])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut fields = stdout.split_whitespace();
+        let cost_type = fields.next()?;
+        let roaming = fields
+            .next()
+            .is_some_and(|f| f.eq_ignore_ascii_case("true"));
+        let over_limit = fields
+            .next()
+            .is_some_and(|f| f.eq_ignore_ascii_case("true"));
+
+        // NetworkCostType: Unknown, Unrestricted, Fixed, Variable
+        let limited = matches!(cost_type, "Fixed" | "Variable");
+        Some(limited || roaming || over_limit)
+    }
+}
@@ -0,0 +1,59 @@
+//! Desktop notifications through the system's own notification tool
+//!
+//! Title and body are passed as arguments or environment variables rather than
+//! spliced into a script, so album names never need escaping.
+
+use std::process::Command;
+
+/// Show a desktop notification
+pub fn show(title: &str, body: &str) -> std::io::Result<()> {
+    command(title, body).spawn()?;
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn command(title: &str, body: &str) -> Command {
+    let mut cmd = Command::new("notify-send");
+    cmd.args(["--app-name", "Rustle", "--icon", "rustle", title, body]);
+    cmd
+}
+
+#[cfg(target_os = "macos")]
+fn command(title: &str, body: &str) -> Command {
+    let mut cmd = Command::new("osascript");
+    cmd.args([
+        "-e",
+        "on run argv",
+        "-e",
+        "display notification (item 2 of argv) with title (item 1 of argv)",
+        "-e",
+        "end run",
+        title,
+        body,
+    ]);
+    cmd
+}
+
+#[cfg(target_os = "windows")]
+fn command(title: &str, body: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    use crate::platform::process::CREATE_NO_WINDOW;
+
+    const SCRIPT: &str = r#"
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null
+[Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] | Out-Null
+$title = [Security.SecurityElement]::Escape($env:RUSTLE_NOTIFY_TITLE)
+$body = [Security.SecurityElement]::Escape($env:RUSTLE_NOTIFY_BODY)
+$xml = New-Object Windows.Data.Xml.Dom.XmlDocument
+$xml.LoadXml("<toast><visual><binding template='ToastGeneric'><text>$title</text><text>$body</text></binding></visual></toast>")
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('Rustle').Show([Windows.UI.Notifications.ToastNotification]::new($xml))
+"#;
+
+    let mut cmd = Command::new("powershell");
+    cmd.args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .env("RUSTLE_NOTIFY_TITLE", title)
+        .env("RUSTLE_NOTIFY_BODY", body)
+        .creation_flags(CREATE_NO_WINDOW);
+    cmd
+}
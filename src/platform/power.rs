@@ -0,0 +1,226 @@
+//! System sleep and wake notifications
+//!
+//! Linux asks logind, which also lets us hold sleep back for a moment with a
+//! delay inhibitor so playback can pause before the machine goes down.
+//! Windows registers a suspend/resume callback with the power manager.
+//! Elsewhere, or when those aren't available, waking is spotted by the wall
+//! clock jumping ahead of the monotonic one, which stands still while asleep;
+//! there is no warning before sleep in that case.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use futures_util::Stream;
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+
+/// How often the clock fallback compares clocks
+const CLOCK_CHECK: Duration = Duration::from_secs(10);
+
+/// How far the wall clock has to run ahead to count as a sleep, well beyond
+/// what a time sync would adjust it by
+const CLOCK_SLEEP_GAP: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerEvent {
+    /// The system is about to sleep
+    Suspending,
+    /// The system woke up
+    Resumed,
+}
+
+/// Sleep and wake events for as long as the stream is held
+pub fn events() -> impl Stream<Item = PowerEvent> + Send + 'static {
+    async_stream::stream! {
+        let (tx, mut rx) = unbounded_channel();
+        tokio::spawn(watch(tx));
+        while let Some(event) = rx.recv().await {
+            yield event;
+        }
+    }
+}
+
+async fn watch(tx: UnboundedSender<PowerEvent>) {
+    #[cfg(target_os = "linux")]
+    let native = linux::watch(&tx).await;
+    #[cfg(target_os = "windows")]
+    let native = windows::watch(&tx).await;
+
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    match native {
+        // The receiving end is gone
+        Ok(()) => return,
+        Err(e) => tracing::warn!(
+            "System sleep notifications unavailable, watching the clock instead: {}",
+            e
+        ),
+    }
+
+    watch_clock(&tx).await;
+}
+
+async fn watch_clock(tx: &UnboundedSender<PowerEvent>) {
+    let mut ticks = tokio::time::interval(CLOCK_CHECK);
+    ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last = (Instant::now(), SystemTime::now());
+
+    while !tx.is_closed() {
+        ticks.tick().await;
+        let now = (Instant::now(), SystemTime::now());
+        let awake = now.0.duration_since(last.0);
+        let passed = now.1.duration_since(last.1).unwrap_or_default();
+        if passed > awake + CLOCK_SLEEP_GAP && tx.send(PowerEvent::Resumed).is_err() {
+            return;
+        }
+        last = now;
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::time::Duration;
+
+    use anyhow::{Result, bail};
+    use futures_util::StreamExt;
+    use tokio::sync::mpsc::UnboundedSender;
+    use zbus::zvariant::OwnedFd;
+
+    use super::PowerEvent;
+
+    /// How long sleep waits on us once announced; logind caps it anyway
+    const PAUSE_GRACE: Duration = Duration::from_secs(1);
+
+    pub async fn watch(tx: &UnboundedSender<PowerEvent>) -> Result<()> {
+        let connection = zbus::Connection::system().await?;
+        let manager = zbus::Proxy::new(
+            &connection,
+            "org.freedesktop.login1",
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )
+        .await?;
+        let mut signals = manager.receive_signal("PrepareForSleep").await?;
+        let mut inhibitor = Some(inhibit(&manager).await?);
+
+        while let Some(signal) = signals.next().await {
+            let sleeping: bool = signal.body().deserialize()?;
+            if sleeping {
+                if tx.send(PowerEvent::Suspending).is_err() {
+                    return Ok(());
+                }
+                tokio::time::sleep(PAUSE_GRACE).await;
+                // Closing the descriptor lets the system go to sleep
+                drop(inhibitor.take());
+            } else {
+                if tx.send(PowerEvent::Resumed).is_err() {
+                    return Ok(());
+                }
+                match inhibit(&manager).await {
+                    Ok(fd) => inhibitor = Some(fd),
+                    Err(e) => tracing::warn!("Failed to take sleep inhibitor: {}", e),
+                }
+            }
+        }
+        bail!("logind closed the PrepareForSleep signal stream")
+    }
+
+    async fn inhibit(manager: &zbus::Proxy<'_>) -> zbus::Result<OwnedFd> {
+        manager
+            .call(
+                "Inhibit",
+                &("sleep", "Rustle", "Pause playback before sleep", "delay"),
+            )
+            .await
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows {
+    use std::ffi::c_void;
+
+    use anyhow::{Result, bail};
+    use tokio::sync::mpsc::UnboundedSender;
+    use windows_sys::Win32::System::Power::{
+        DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS, HPOWERNOTIFY, RegisterSuspendResumeNotification,
+        UnregisterSuspendResumeNotification,
+    };
+
+    use super::PowerEvent;
+
+    /// Deliver notifications to a callback rather than a window
+    const DEVICE_NOTIFY_CALLBACK: u32 = 2;
+    /// The system is about to suspend
+    const PBT_APMSUSPEND: u32 = 4;
+    /// Sent after any resume (7 follows it when a user woke the machine, so
+    /// it's left out)
+    const PBT_APMRESUMEAUTOMATIC: u32 = 18;
+
+    /// A registered suspend/resume callback, unregistered on drop
+    struct Registration {
+        handle: HPOWERNOTIFY,
+        /// Handed to the callback as its context
+        sender: *mut UnboundedSender<PowerEvent>,
+        /// Kept alive for as long as the registration
+        _params: Box<DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS>,
+    }
+
+    // SAFETY: The pointers are only dereferenced by the callback and freed
+    // after unregistering, neither of which cares about the calling thread
+    unsafe impl Send for Registration {}
+
+    impl Drop for Registration {
+        fn drop(&mut self) {
+            // SAFETY: No callback runs once unregistering returns
+            unsafe {
+                UnregisterSuspendResumeNotification(self.handle);
+                drop(Box::from_raw(self.sender));
+            }
+        }
+    }
+
+    pub async fn watch(tx: &UnboundedSender<PowerEvent>) -> Result<()> {
+        let _registration = register(tx.clone())?;
+        tx.closed().await;
+        Ok(())
+    }
+
+    fn register(tx: UnboundedSender<PowerEvent>) -> Result<Registration> {
+        let sender = Box::into_raw(Box::new(tx));
+        let mut params = Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+            Callback: Some(on_power_event),
+            Context: sender.cast(),
+        });
+        // SAFETY: The parameters and the sender outlive the registration
+        let handle = unsafe {
+            RegisterSuspendResumeNotification(
+                std::ptr::from_mut(params.as_mut()).cast(),
+                DEVICE_NOTIFY_CALLBACK,
+            )
+        };
+        if handle.is_null() {
+            let error = std::io::Error::last_os_error();
+            // SAFETY: Never registered, so nothing else holds the sender
+            drop(unsafe { Box::from_raw(sender) });
+            bail!("Failed to register for suspend notifications: {}", error);
+        }
+        Ok(Registration {
+            handle,
+            sender,
+            _params: params,
+        })
+    }
+
+    unsafe extern "system" fn on_power_event(
+        context: *const c_void,
+        kind: u32,
+        _setting: *const c_void,
+    ) -> u32 {
+        // SAFETY: The context is the sender owned by the registration
+        let tx = unsafe { &*context.cast::<UnboundedSender<PowerEvent>>() };
+        let event = match kind {
+            PBT_APMSUSPEND => PowerEvent::Suspending,
+            PBT_APMRESUMEAUTOMATIC => PowerEvent::Resumed,
+            _ => return 0,
+        };
+        let _ = tx.send(event);
+        0
+    }
+}
@@ -0,0 +1,6 @@
+//! Child process helpers
+
+/// Process creation flag that keeps a console program such as PowerShell or
+/// `reg.exe` from flashing a console window
+#[cfg(target_os = "windows")]
+pub const CREATE_NO_WINDOW: u32 = 0x0800_0000;
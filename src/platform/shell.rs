@@ -0,0 +1,28 @@
+//! Opening files, folders and links with the desktop's default handler
+
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::Command;
+
+/// Open a file or folder with the system default application
+pub fn open_path(path: &Path) -> std::io::Result<()> {
+    open(path.as_os_str())
+}
+
+/// Open a URL in the default browser
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    open(OsStr::new(url))
+}
+
+fn open(target: &OsStr) -> std::io::Result<()> {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    Command::new(program).arg(target).spawn()?;
+    Ok(())
+}
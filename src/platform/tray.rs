@@ -6,7 +6,9 @@
 //! - Windows/macOS: Uses tray-icon
 //! - WASM: No-op (not available)
 
+use crate::audio::AudioDevice;
 use crate::features::PlayMode;
+use crate::i18n::Locale;
 use tokio::sync::mpsc;
 
 // Platform-specific implementations
@@ -20,6 +22,8 @@ mod windows;
 pub enum TrayCommand {
     /// Show window and bring to front (left click behavior for Windows/macOS)
     ShowOrFocusWindow,
+    /// The tray menu is about to open (right click on Windows/macOS)
+    MenuOpening,
     /// Toggle show/hide window (Linux behavior)
     ToggleWindow,
     /// Toggle play/pause
@@ -32,10 +36,30 @@ pub enum TrayCommand {
     SetPlayMode(PlayMode),
     /// Toggle favorite status for current song
     ToggleFavorite,
+    /// Toggle stopping once the current song ends
+    ToggleStopAfterCurrent,
+    /// Jump to a song in the play queue by index
+    PlayQueueIndex(usize),
+    /// Open or close the lyrics page
+    ToggleLyrics,
+    /// Switch audio output device (None = system default)
+    SetOutputDevice(Option<String>),
     /// Quit the application
     Quit,
 }
 
+/// Number of upcoming queue items shown in the tray menu
+pub const UP_NEXT_LEN: usize = 5;
+
+/// Upcoming queue entry shown in the tray menu
+#[derive(Debug, Clone)]
+pub struct TrayQueueItem {
+    /// Index in the play queue
+    pub index: usize,
+    /// Display label ("title - artist")
+    pub label: String,
+}
+
 /// State shared between tray and application
 #[derive(Debug, Clone)]
 pub struct TrayState {
@@ -47,10 +71,20 @@ pub struct TrayState {
     pub artist: Option<String>,
     /// Current play mode
     pub play_mode: PlayMode,
-    /// Current song NCM ID (if NCM song, for favorite toggle)
-    pub ncm_song_id: Option<u64>,
     /// Whether current song is favorited
     pub is_favorited: bool,
+    /// Whether playback stops once the current song ends
+    pub stop_after_current: bool,
+    /// Next songs in the queue (at most `UP_NEXT_LEN`)
+    pub up_next: Vec<TrayQueueItem>,
+    /// Whether the lyrics page is open
+    pub lyrics_open: bool,
+    /// Available audio output devices
+    pub output_devices: Vec<AudioDevice>,
+    /// Selected output device (None = system default)
+    pub output_device: Option<String>,
+    /// Language for menu labels
+    pub locale: Locale,
 }
 
 impl Default for TrayState {
@@ -60,8 +94,13 @@ impl Default for TrayState {
             title: None,
             artist: None,
             play_mode: PlayMode::Sequential,
-            ncm_song_id: None,
             is_favorited: false,
+            stop_after_current: false,
+            up_next: Vec::new(),
+            lyrics_open: false,
+            output_devices: Vec::new(),
+            output_device: None,
+            locale: Locale::default(),
         }
     }
 }
@@ -158,6 +197,9 @@ fn init_tray_internal() -> anyhow::Result<TrayResult> {
 /// - Linux: Uses Task::perform (async)
 /// - Windows/macOS: Uses Task::done (sync, must run on main thread)
 /// - WASM: No-op
+///
+/// If the tray cannot be created the main window is shown, since a window
+/// started minimized would otherwise be unreachable.
 pub fn init_task<F>(on_success: F) -> iced::Task<crate::app::Message>
 where
     F: FnOnce(TrayResult) -> crate::app::Message + Send + 'static,
@@ -168,7 +210,7 @@ where
             Ok(rx) => on_success(rx),
             Err(e) => {
                 tracing::warn!("Failed to start system tray: {}", e);
-                crate::app::Message::Noop
+                crate::app::Message::ShowWindow
             }
         })
     }
@@ -179,7 +221,7 @@ where
             Ok(rx) => on_success(rx),
             Err(e) => {
                 tracing::warn!("Failed to start system tray: {}", e);
-                crate::app::Message::Noop
+                crate::app::Message::ShowWindow
             }
         })
     }
@@ -188,7 +230,7 @@ where
     {
         iced::Task::done(match init_tray_internal() {
             Ok(rx) => on_success(rx),
-            Err(_) => crate::app::Message::Noop,
+            Err(_) => crate::app::Message::ShowWindow,
         })
     }
 }
@@ -2,6 +2,7 @@
 
 use super::{TrayCommand, TrayHandle, TrayState};
 use crate::features::PlayMode;
+use crate::i18n::Key;
 use ksni::{Icon, MenuItem, Status, ToolTip, Tray as KsniTray, TrayMethods, menu::*};
 use tokio::sync::mpsc;
 
@@ -146,7 +147,12 @@ fn create_menu(
     state: &TrayState,
     _tx: &mpsc::UnboundedSender<TrayCommand>,
 ) -> Vec<MenuItem<LinuxTray>> {
-    let play_label = if state.is_playing { "暂停" } else { "播放" };
+    let locale = state.locale;
+    let play_label = if state.is_playing {
+        locale.get(Key::TrayPause)
+    } else {
+        locale.get(Key::TrayPlay)
+    };
     let play_icon = if state.is_playing {
         "media-playback-pause-symbolic"
     } else {
@@ -158,6 +164,7 @@ fn create_menu(
         PlayMode::LoopAll => 1,
         PlayMode::LoopOne => 2,
         PlayMode::Shuffle => 3,
+        PlayMode::AutoDj => 4,
     };
 
     vec![
@@ -193,7 +200,7 @@ fn create_menu(
         }
         .into(),
         StandardItem {
-            label: "上一首".to_string(),
+            label: locale.get(Key::TrayPrevious).to_string(),
             icon_name: "media-skip-backward-symbolic".to_string(),
             activate: Box::new(|tray: &mut LinuxTray| {
                 let _ = tray.tx.send(TrayCommand::PrevTrack);
@@ -202,7 +209,7 @@ fn create_menu(
         }
         .into(),
         StandardItem {
-            label: "下一首".to_string(),
+            label: locale.get(Key::TrayNext).to_string(),
             icon_name: "media-skip-forward-symbolic".to_string(),
             activate: Box::new(|tray: &mut LinuxTray| {
                 let _ = tray.tx.send(TrayCommand::NextTrack);
@@ -211,11 +218,11 @@ fn create_menu(
         }
         .into(),
         // Favorite button
-        if state.ncm_song_id.is_some() {
+        if state.title.is_some() {
             let (fav_label, fav_icon) = if state.is_favorited {
-                ("取消收藏", "starred-symbolic")
+                (locale.get(Key::TrayUnfavorite), "starred-symbolic")
             } else {
-                ("收藏", "non-starred-symbolic")
+                (locale.get(Key::TrayFavorite), "non-starred-symbolic")
             };
             StandardItem {
                 label: fav_label.to_string(),
@@ -228,17 +235,30 @@ fn create_menu(
             .into()
         } else {
             StandardItem {
-                label: "收藏".to_string(),
+                label: locale.get(Key::TrayFavorite).to_string(),
                 icon_name: "non-starred-symbolic".to_string(),
                 enabled: false,
                 ..Default::default()
             }
             .into()
         },
+        // Stop after the current song
+        CheckmarkItem {
+            label: locale.get(Key::StopAfterCurrent).to_string(),
+            checked: state.stop_after_current,
+            enabled: state.title.is_some(),
+            activate: Box::new(|tray: &mut LinuxTray| {
+                let _ = tray.tx.send(TrayCommand::ToggleStopAfterCurrent);
+            }),
+            ..Default::default()
+        }
+        .into(),
         MenuItem::Separator,
+        // Up next submenu
+        up_next_menu(state),
         // Play mode submenu
         SubMenu {
-            label: "播放模式".to_string(),
+            label: locale.get(Key::TrayPlayMode).to_string(),
             icon_name: "media-playlist-consecutive-symbolic".to_string(),
             submenu: vec![
                 RadioGroup {
@@ -249,31 +269,37 @@ fn create_menu(
                             1 => PlayMode::LoopAll,
                             2 => PlayMode::LoopOne,
                             3 => PlayMode::Shuffle,
+                            4 => PlayMode::AutoDj,
                             _ => PlayMode::Sequential,
                         };
                         let _ = tray.tx.send(TrayCommand::SetPlayMode(mode));
                     }),
                     options: vec![
                         RadioItem {
-                            label: "顺序播放".to_string(),
+                            label: PlayMode::Sequential.display_name(locale).to_string(),
                             icon_name: "media-playlist-consecutive-symbolic".to_string(),
                             ..Default::default()
                         },
                         RadioItem {
-                            label: "列表循环".to_string(),
+                            label: PlayMode::LoopAll.display_name(locale).to_string(),
                             icon_name: "media-playlist-repeat-symbolic".to_string(),
                             ..Default::default()
                         },
                         RadioItem {
-                            label: "单曲循环".to_string(),
+                            label: PlayMode::LoopOne.display_name(locale).to_string(),
                             icon_name: "media-playlist-repeat-song-symbolic".to_string(),
                             ..Default::default()
                         },
                         RadioItem {
-                            label: "随机播放".to_string(),
+                            label: PlayMode::Shuffle.display_name(locale).to_string(),
                             icon_name: "media-playlist-shuffle-symbolic".to_string(),
                             ..Default::default()
                         },
+                        RadioItem {
+                            label: PlayMode::AutoDj.display_name(locale).to_string(),
+                            icon_name: "audio-x-generic-symbolic".to_string(),
+                            ..Default::default()
+                        },
                     ],
                     ..Default::default()
                 }
@@ -282,10 +308,22 @@ fn create_menu(
             ..Default::default()
         }
         .into(),
+        // Output device submenu
+        output_device_menu(state),
         MenuItem::Separator,
+        // Lyrics page toggle
+        CheckmarkItem {
+            label: locale.get(Key::TrayLyrics).to_string(),
+            checked: state.lyrics_open,
+            activate: Box::new(|tray: &mut LinuxTray| {
+                let _ = tray.tx.send(TrayCommand::ToggleLyrics);
+            }),
+            ..Default::default()
+        }
+        .into(),
         // Window control
         StandardItem {
-            label: "显示/隐藏窗口".to_string(),
+            label: locale.get(Key::TrayToggleWindow).to_string(),
             icon_name: "view-restore-symbolic".to_string(),
             activate: Box::new(|tray: &mut LinuxTray| {
                 let _ = tray.tx.send(TrayCommand::ToggleWindow);
@@ -296,7 +334,7 @@ fn create_menu(
         MenuItem::Separator,
         // Quit
         StandardItem {
-            label: "退出".to_string(),
+            label: locale.get(Key::TrayQuit).to_string(),
             icon_name: "application-exit-symbolic".to_string(),
             activate: Box::new(|tray: &mut LinuxTray| {
                 let _ = tray.tx.send(TrayCommand::Quit);
@@ -306,3 +344,88 @@ fn create_menu(
         .into(),
     ]
 }
+
+/// "Up next" submenu listing the next few queue items (click to jump)
+fn up_next_menu(state: &TrayState) -> MenuItem<LinuxTray> {
+    let submenu: Vec<MenuItem<LinuxTray>> = if state.up_next.is_empty() {
+        vec![
+            StandardItem {
+                label: state.locale.get(Key::TrayQueueEmpty).to_string(),
+                enabled: false,
+                ..Default::default()
+            }
+            .into(),
+        ]
+    } else {
+        state
+            .up_next
+            .iter()
+            .map(|item| {
+                let index = item.index;
+                StandardItem {
+                    // ksni treats '_' as a mnemonic marker
+                    label: item.label.replace('_', "__"),
+                    activate: Box::new(move |tray: &mut LinuxTray| {
+                        let _ = tray.tx.send(TrayCommand::PlayQueueIndex(index));
+                    }),
+                    ..Default::default()
+                }
+                .into()
+            })
+            .collect()
+    };
+
+    SubMenu {
+        label: state.locale.get(Key::TrayUpNext).to_string(),
+        icon_name: "view-list-symbolic".to_string(),
+        submenu,
+        ..Default::default()
+    }
+    .into()
+}
+
+/// Output device submenu (first entry is the system default)
+fn output_device_menu(state: &TrayState) -> MenuItem<LinuxTray> {
+    let selected = state
+        .output_device
+        .as_ref()
+        .and_then(|name| state.output_devices.iter().position(|d| &d.name == name))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+
+    let mut options = vec![RadioItem {
+        label: state.locale.get(Key::TraySystemDefault).to_string(),
+        ..Default::default()
+    }];
+    options.extend(state.output_devices.iter().map(|device| RadioItem {
+        label: device.description.replace('_', "__"),
+        ..Default::default()
+    }));
+
+    let device_names: Vec<String> = state
+        .output_devices
+        .iter()
+        .map(|d| d.name.clone())
+        .collect();
+
+    SubMenu {
+        label: state.locale.get(Key::TrayOutputDevice).to_string(),
+        icon_name: "audio-speakers-symbolic".to_string(),
+        submenu: vec![
+            RadioGroup {
+                selected,
+                select: Box::new(move |tray: &mut LinuxTray, index| {
+                    let device = index
+                        .checked_sub(1)
+                        .and_then(|i| device_names.get(i).cloned());
+                    let _ = tray.tx.send(TrayCommand::SetOutputDevice(device));
+                }),
+                options,
+                ..Default::default()
+            }
+            .into(),
+        ],
+        ..Default::default()
+    }
+    .into()
+}
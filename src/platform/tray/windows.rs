@@ -2,6 +2,7 @@
 
 use super::{TrayCommand, TrayHandle, TrayState};
 use crate::features::PlayMode;
+use crate::i18n::Key;
 use tokio::sync::mpsc;
 use tray_icon::{
     MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent,
@@ -13,20 +14,44 @@ const PLAY_PAUSE_ID: &str = "play_pause";
 const PREV_TRACK_ID: &str = "prev_track";
 const NEXT_TRACK_ID: &str = "next_track";
 const TOGGLE_FAVORITE_ID: &str = "toggle_favorite";
+const STOP_AFTER_CURRENT_ID: &str = "stop_after_current";
 const SEQUENTIAL_ID: &str = "sequential";
 const LOOP_ALL_ID: &str = "loop_all";
 const LOOP_ONE_ID: &str = "loop_one";
 const SHUFFLE_ID: &str = "shuffle";
+const AUTO_DJ_ID: &str = "auto_dj";
 const TOGGLE_WINDOW_ID: &str = "toggle_window";
+const TOGGLE_LYRICS_ID: &str = "toggle_lyrics";
 const QUIT_ID: &str = "quit";
+/// Dynamic item ID prefixes (suffix carries the queue index / device name)
+const QUEUE_ITEM_PREFIX: &str = "queue_item:";
+const OUTPUT_DEVICE_PREFIX: &str = "output_device:";
+const DEFAULT_OUTPUT_DEVICE_ID: &str = "output_device_default";
+
+/// Menu items that are updated after the menu is created
+struct NativeMenuItems {
+    now_playing: NativeMenuItem,
+    play_pause: NativeMenuItem,
+    prev_track: NativeMenuItem,
+    next_track: NativeMenuItem,
+    favorite: NativeMenuItem,
+    stop_after_current: CheckMenuItem,
+    up_next: Submenu,
+    play_mode: Submenu,
+    sequential: CheckMenuItem,
+    loop_all: CheckMenuItem,
+    loop_one: CheckMenuItem,
+    shuffle: CheckMenuItem,
+    auto_dj: CheckMenuItem,
+    output_device: Submenu,
+    lyrics: CheckMenuItem,
+    toggle_window: NativeMenuItem,
+    quit: NativeMenuItem,
+}
 
 /// Wrapper to make menu items Send+Sync (they're only accessed from main thread)
 struct MenuItemsWrapper {
-    play_pause: *const NativeMenuItem,
-    sequential: *const CheckMenuItem,
-    loop_all: *const CheckMenuItem,
-    loop_one: *const CheckMenuItem,
-    shuffle: *const CheckMenuItem,
+    items: *const NativeMenuItems,
 }
 
 // SAFETY: Menu items are only accessed from the main thread
@@ -37,22 +62,126 @@ static MENU_ITEMS: std::sync::OnceLock<MenuItemsWrapper> = std::sync::OnceLock::
 
 /// Update menu items based on current state
 pub fn update_menu_state(state: &TrayState) {
-    if let Some(items) = MENU_ITEMS.get() {
-        // SAFETY: These pointers are valid for the lifetime of the application
-        unsafe {
-            // Update play/pause label
-            let play_label = if state.is_playing { "暂停" } else { "播放" };
-            (*items.play_pause).set_text(play_label);
-
-            // Update play mode checkmarks
-            (*items.sequential).set_checked(matches!(state.play_mode, PlayMode::Sequential));
-            (*items.loop_all).set_checked(matches!(state.play_mode, PlayMode::LoopAll));
-            (*items.loop_one).set_checked(matches!(state.play_mode, PlayMode::LoopOne));
-            (*items.shuffle).set_checked(matches!(state.play_mode, PlayMode::Shuffle));
+    if let Some(wrapper) = MENU_ITEMS.get() {
+        // SAFETY: The items are leaked and valid for the lifetime of the application
+        let items = unsafe { &*wrapper.items };
+
+        items.now_playing.set_text(now_playing_text(state));
+        set_labels(items, state);
+
+        items.favorite.set_enabled(state.title.is_some());
+
+        items.stop_after_current.set_enabled(state.title.is_some());
+        items
+            .stop_after_current
+            .set_checked(state.stop_after_current);
+
+        // Update play mode checkmarks
+        items
+            .sequential
+            .set_checked(matches!(state.play_mode, PlayMode::Sequential));
+        items
+            .loop_all
+            .set_checked(matches!(state.play_mode, PlayMode::LoopAll));
+        items
+            .loop_one
+            .set_checked(matches!(state.play_mode, PlayMode::LoopOne));
+        items
+            .shuffle
+            .set_checked(matches!(state.play_mode, PlayMode::Shuffle));
+        items
+            .auto_dj
+            .set_checked(matches!(state.play_mode, PlayMode::AutoDj));
+
+        items.lyrics.set_checked(state.lyrics_open);
+
+        fill_up_next(&items.up_next, state);
+        fill_output_devices(&items.output_device, state);
+    }
+}
+
+/// Set every fixed label in the current language (play/pause and favorite
+/// also follow the playback state)
+fn set_labels(items: &NativeMenuItems, state: &TrayState) {
+    let locale = state.locale;
+    items.play_pause.set_text(if state.is_playing {
+        locale.get(Key::TrayPause)
+    } else {
+        locale.get(Key::TrayPlay)
+    });
+    items.prev_track.set_text(locale.get(Key::TrayPrevious));
+    items.next_track.set_text(locale.get(Key::TrayNext));
+    items.favorite.set_text(if state.is_favorited {
+        locale.get(Key::TrayUnfavorite)
+    } else {
+        locale.get(Key::TrayFavorite)
+    });
+    items
+        .stop_after_current
+        .set_text(locale.get(Key::StopAfterCurrent));
+    items.up_next.set_text(locale.get(Key::TrayUpNext));
+    items.play_mode.set_text(locale.get(Key::TrayPlayMode));
+    items
+        .sequential
+        .set_text(PlayMode::Sequential.display_name(locale));
+    items
+        .loop_all
+        .set_text(PlayMode::LoopAll.display_name(locale));
+    items
+        .loop_one
+        .set_text(PlayMode::LoopOne.display_name(locale));
+    items
+        .shuffle
+        .set_text(PlayMode::Shuffle.display_name(locale));
+    items
+        .auto_dj
+        .set_text(PlayMode::AutoDj.display_name(locale));
+    items
+        .output_device
+        .set_text(locale.get(Key::TrayOutputDevice));
+    items.lyrics.set_text(locale.get(Key::TrayLyrics));
+    items
+        .toggle_window
+        .set_text(locale.get(Key::TrayToggleWindow));
+    items.quit.set_text(locale.get(Key::TrayQuit));
+}
+
+/// Map a menu item ID to a tray command
+fn parse_menu_command(id: &str) -> Option<TrayCommand> {
+    match id {
+        PLAY_PAUSE_ID => Some(TrayCommand::PlayPause),
+        PREV_TRACK_ID => Some(TrayCommand::PrevTrack),
+        NEXT_TRACK_ID => Some(TrayCommand::NextTrack),
+        TOGGLE_FAVORITE_ID => Some(TrayCommand::ToggleFavorite),
+        STOP_AFTER_CURRENT_ID => Some(TrayCommand::ToggleStopAfterCurrent),
+        SEQUENTIAL_ID => Some(TrayCommand::SetPlayMode(PlayMode::Sequential)),
+        LOOP_ALL_ID => Some(TrayCommand::SetPlayMode(PlayMode::LoopAll)),
+        LOOP_ONE_ID => Some(TrayCommand::SetPlayMode(PlayMode::LoopOne)),
+        SHUFFLE_ID => Some(TrayCommand::SetPlayMode(PlayMode::Shuffle)),
+        AUTO_DJ_ID => Some(TrayCommand::SetPlayMode(PlayMode::AutoDj)),
+        TOGGLE_WINDOW_ID => Some(TrayCommand::ToggleWindow),
+        TOGGLE_LYRICS_ID => Some(TrayCommand::ToggleLyrics),
+        DEFAULT_OUTPUT_DEVICE_ID => Some(TrayCommand::SetOutputDevice(None)),
+        QUIT_ID => Some(TrayCommand::Quit),
+        _ => {
+            if let Some(index) = id.strip_prefix(QUEUE_ITEM_PREFIX) {
+                index.parse().ok().map(TrayCommand::PlayQueueIndex)
+            } else {
+                id.strip_prefix(OUTPUT_DEVICE_PREFIX)
+                    .map(|name| TrayCommand::SetOutputDevice(Some(name.to_string())))
+            }
         }
     }
 }
 
+/// Leak the updatable menu items and register them for state updates
+fn store_menu_items(items: NativeMenuItems) {
+    let items = Box::leak(Box::new(items));
+    let _ = MENU_ITEMS.set(MenuItemsWrapper {
+        items: items as *const _,
+    });
+}
+
 #[allow(dead_code)]
 pub async fn start_native_tray()
 -> anyhow::Result<(TrayHandle, mpsc::UnboundedReceiver<TrayCommand>)> {
@@ -63,23 +192,10 @@ pub async fn start_native_tray()
     let icon = load_icon()?;
 
     // Create initial menu
-    let (menu, play_pause, sequential, loop_all, loop_one, shuffle) =
-        create_native_menu_with_items(&TrayState::default())?;
+    let (menu, items) = create_native_menu_with_items(&TrayState::default())?;
 
     // Leak menu items and store pointers
-    let play_pause = Box::leak(Box::new(play_pause));
-    let sequential = Box::leak(Box::new(sequential));
-    let loop_all = Box::leak(Box::new(loop_all));
-    let loop_one = Box::leak(Box::new(loop_one));
-    let shuffle = Box::leak(Box::new(shuffle));
-
-    let _ = MENU_ITEMS.set(MenuItemsWrapper {
-        play_pause: play_pause as *const _,
-        sequential: sequential as *const _,
-        loop_all: loop_all as *const _,
-        loop_one: loop_one as *const _,
-        shuffle: shuffle as *const _,
-    });
+    store_menu_items(items);
 
     // Create tray icon
     let tray = TrayIconBuilder::new()
@@ -98,20 +214,7 @@ pub async fn start_native_tray()
     tray_icon::menu::MenuEvent::set_event_handler(Some(
         move |event: tray_icon::menu::MenuEvent| {
             let id_str = event.id.0.as_str();
-            let command = match id_str {
-                PLAY_PAUSE_ID => Some(TrayCommand::PlayPause),
-                PREV_TRACK_ID => Some(TrayCommand::PrevTrack),
-                NEXT_TRACK_ID => Some(TrayCommand::NextTrack),
-                TOGGLE_FAVORITE_ID => Some(TrayCommand::ToggleFavorite),
-                SEQUENTIAL_ID => Some(TrayCommand::SetPlayMode(PlayMode::Sequential)),
-                LOOP_ALL_ID => Some(TrayCommand::SetPlayMode(PlayMode::LoopAll)),
-                LOOP_ONE_ID => Some(TrayCommand::SetPlayMode(PlayMode::LoopOne)),
-                SHUFFLE_ID => Some(TrayCommand::SetPlayMode(PlayMode::Shuffle)),
-                TOGGLE_WINDOW_ID => Some(TrayCommand::ToggleWindow),
-                QUIT_ID => Some(TrayCommand::Quit),
-                _ => None,
-            };
-            if let Some(cmd) = command {
+            if let Some(cmd) = parse_menu_command(id_str) {
                 let _ = cmd_tx_menu.send(cmd);
             }
         },
@@ -126,6 +229,9 @@ pub async fn start_native_tray()
             if button == MouseButton::Left && button_state == MouseButtonState::Up {
                 let _ = cmd_tx.send(TrayCommand::ShowOrFocusWindow);
             }
+            if button == MouseButton::Right && button_state == MouseButtonState::Down {
+                let _ = cmd_tx.send(TrayCommand::MenuOpening);
+            }
         }
         _ => {}
     }));
@@ -151,23 +257,10 @@ pub fn start_native_tray_sync() -> anyhow::Result<(TrayHandle, mpsc::UnboundedRe
     let icon = load_icon()?;
 
     // Create initial menu with items for updates
-    let (menu, play_pause, sequential, loop_all, loop_one, shuffle) =
-        create_native_menu_with_items(&TrayState::default())?;
+    let (menu, items) = create_native_menu_with_items(&TrayState::default())?;
 
     // Leak menu items and store pointers (only if not already set)
-    let play_pause = Box::leak(Box::new(play_pause));
-    let sequential = Box::leak(Box::new(sequential));
-    let loop_all = Box::leak(Box::new(loop_all));
-    let loop_one = Box::leak(Box::new(loop_one));
-    let shuffle = Box::leak(Box::new(shuffle));
-
-    let _ = MENU_ITEMS.set(MenuItemsWrapper {
-        play_pause: play_pause as *const _,
-        sequential: sequential as *const _,
-        loop_all: loop_all as *const _,
-        loop_one: loop_one as *const _,
-        shuffle: shuffle as *const _,
-    });
+    store_menu_items(items);
 
     // Create tray icon
     let tray = TrayIconBuilder::new()
@@ -187,20 +280,7 @@ pub fn start_native_tray_sync() -> anyhow::Result<(TrayHandle, mpsc::UnboundedRe
         move |event: tray_icon::menu::MenuEvent| {
             let id_str = event.id.0.as_str();
             tracing::info!("Menu event received: {}", id_str);
-            let command = match id_str {
-                PLAY_PAUSE_ID => Some(TrayCommand::PlayPause),
-                PREV_TRACK_ID => Some(TrayCommand::PrevTrack),
-                NEXT_TRACK_ID => Some(TrayCommand::NextTrack),
-                TOGGLE_FAVORITE_ID => Some(TrayCommand::ToggleFavorite),
-                SEQUENTIAL_ID => Some(TrayCommand::SetPlayMode(PlayMode::Sequential)),
-                LOOP_ALL_ID => Some(TrayCommand::SetPlayMode(PlayMode::LoopAll)),
-                LOOP_ONE_ID => Some(TrayCommand::SetPlayMode(PlayMode::LoopOne)),
-                SHUFFLE_ID => Some(TrayCommand::SetPlayMode(PlayMode::Shuffle)),
-                TOGGLE_WINDOW_ID => Some(TrayCommand::ToggleWindow),
-                QUIT_ID => Some(TrayCommand::Quit),
-                _ => None,
-            };
-            if let Some(cmd) = command {
+            if let Some(cmd) = parse_menu_command(id_str) {
                 tracing::info!("Sending tray command: {:?}", cmd);
                 if let Err(e) = cmd_tx_menu.send(cmd) {
                     tracing::error!("Failed to send tray command: {}", e);
@@ -222,6 +302,9 @@ pub fn start_native_tray_sync() -> anyhow::Result<(TrayHandle, mpsc::UnboundedRe
                     tracing::error!("Failed to send ShowOrFocusWindow command: {}", e);
                 }
             }
+            if button == MouseButton::Right && button_state == MouseButtonState::Down {
+                let _ = cmd_tx.send(TrayCommand::MenuOpening);
+            }
         }
         _ => {}
     }));
@@ -255,119 +338,216 @@ fn load_icon() -> anyhow::Result<tray_icon::Icon> {
         .map_err(|e| anyhow::anyhow!("Failed to create icon: {}", e))
 }
 
-fn create_native_menu_with_items(
-    state: &TrayState,
-) -> anyhow::Result<(
-    Menu,
-    NativeMenuItem,
-    CheckMenuItem,
-    CheckMenuItem,
-    CheckMenuItem,
-    CheckMenuItem,
-)> {
-    let menu = Menu::new();
-
-    // Now playing info (disabled item)
-    let now_playing_text = if let Some(title) = &state.title {
+fn now_playing_text(state: &TrayState) -> String {
+    if let Some(title) = &state.title {
         match &state.artist {
             Some(artist) => format!("♪ {} - {}", title, artist),
             None => format!("♪ {}", title),
         }
     } else {
         "Rustle Music".to_string()
-    };
+    }
+}
+
+/// Rebuild the "up next" submenu from the queue preview
+fn fill_up_next(submenu: &Submenu, state: &TrayState) {
+    while submenu.remove_at(0).is_some() {}
+
+    if state.up_next.is_empty() {
+        let empty = NativeMenuItem::new(state.locale.get(Key::TrayQueueEmpty), false, None);
+        submenu.append(&empty).ok();
+        return;
+    }
 
-    let now_playing =
-        NativeMenuItem::with_id(MenuId::new("now_playing"), now_playing_text, false, None);
+    for item in &state.up_next {
+        let entry = NativeMenuItem::with_id(
+            MenuId::new(format!("{}{}", QUEUE_ITEM_PREFIX, item.index)),
+            &item.label,
+            true,
+            None,
+        );
+        submenu.append(&entry).ok();
+    }
+}
+
+/// Rebuild the output device submenu (first entry is the system default)
+fn fill_output_devices(submenu: &Submenu, state: &TrayState) {
+    while submenu.remove_at(0).is_some() {}
+
+    let default = CheckMenuItem::with_id(
+        MenuId::new(DEFAULT_OUTPUT_DEVICE_ID),
+        state.locale.get(Key::TraySystemDefault),
+        true,
+        state.output_device.is_none(),
+        None,
+    );
+    submenu.append(&default).ok();
+
+    for device in &state.output_devices {
+        let entry = CheckMenuItem::with_id(
+            MenuId::new(format!("{}{}", OUTPUT_DEVICE_PREFIX, device.name)),
+            &device.description,
+            true,
+            state.output_device.as_deref() == Some(device.name.as_str()),
+            None,
+        );
+        submenu.append(&entry).ok();
+    }
+}
+
+fn create_native_menu_with_items(state: &TrayState) -> anyhow::Result<(Menu, NativeMenuItems)> {
+    let menu = Menu::new();
+
+    // Now playing info (disabled item)
+    let now_playing = NativeMenuItem::with_id(
+        MenuId::new("now_playing"),
+        now_playing_text(state),
+        false,
+        None,
+    );
     menu.append(&now_playing).ok();
 
     // Separator
     menu.append(&PredefinedMenuItem::separator()).ok();
 
-    // Playback controls
-    let play_label = if state.is_playing { "暂停" } else { "播放" };
-    let play_pause = NativeMenuItem::with_id(MenuId::new(PLAY_PAUSE_ID), play_label, true, None);
+    // Playback controls (labels are filled in by `set_labels` below)
+    let play_pause = NativeMenuItem::with_id(MenuId::new(PLAY_PAUSE_ID), "", true, None);
     menu.append(&play_pause).ok();
 
-    let prev_track = NativeMenuItem::with_id(MenuId::new(PREV_TRACK_ID), "上一首", true, None);
+    let prev_track = NativeMenuItem::with_id(MenuId::new(PREV_TRACK_ID), "", true, None);
     menu.append(&prev_track).ok();
 
-    let next_track = NativeMenuItem::with_id(MenuId::new(NEXT_TRACK_ID), "下一首", true, None);
+    let next_track = NativeMenuItem::with_id(MenuId::new(NEXT_TRACK_ID), "", true, None);
     menu.append(&next_track).ok();
 
-    // Favorite button (only for NCM songs)
-    if state.ncm_song_id.is_some() {
-        let fav_label = if state.is_favorited {
-            "取消收藏"
-        } else {
-            "收藏"
-        };
-        let favorite =
-            NativeMenuItem::with_id(MenuId::new(TOGGLE_FAVORITE_ID), fav_label, true, None);
-        menu.append(&favorite).ok();
-    }
+    // Favorite button (enabled while a song is loaded)
+    let favorite = NativeMenuItem::with_id(
+        MenuId::new(TOGGLE_FAVORITE_ID),
+        "",
+        state.title.is_some(),
+        None,
+    );
+    menu.append(&favorite).ok();
+
+    // Stop after the current song
+    let stop_after_current = CheckMenuItem::with_id(
+        MenuId::new(STOP_AFTER_CURRENT_ID),
+        "",
+        state.title.is_some(),
+        state.stop_after_current,
+        None,
+    );
+    menu.append(&stop_after_current).ok();
 
     // Separator
     menu.append(&PredefinedMenuItem::separator()).ok();
 
+    // Up next submenu
+    let up_next = Submenu::new("", true);
+    fill_up_next(&up_next, state);
+    menu.append(&up_next).ok();
+
     // Play mode submenu
-    let play_mode_menu = Submenu::new("播放模式", true);
+    let play_mode = Submenu::new("", true);
 
     let sequential = CheckMenuItem::with_id(
         MenuId::new(SEQUENTIAL_ID),
-        "顺序播放",
+        "",
         true,
         matches!(state.play_mode, PlayMode::Sequential),
         None,
     );
     let loop_all = CheckMenuItem::with_id(
         MenuId::new(LOOP_ALL_ID),
-        "列表循环",
+        "",
         true,
         matches!(state.play_mode, PlayMode::LoopAll),
         None,
     );
     let loop_one = CheckMenuItem::with_id(
         MenuId::new(LOOP_ONE_ID),
-        "单曲循环",
+        "",
         true,
         matches!(state.play_mode, PlayMode::LoopOne),
         None,
     );
     let shuffle = CheckMenuItem::with_id(
         MenuId::new(SHUFFLE_ID),
-        "随机播放",
+        "",
         true,
         matches!(state.play_mode, PlayMode::Shuffle),
         None,
     );
+    let auto_dj = CheckMenuItem::with_id(
+        MenuId::new(AUTO_DJ_ID),
+        "",
+        true,
+        matches!(state.play_mode, PlayMode::AutoDj),
+        None,
+    );
 
-    play_mode_menu.append(&sequential).ok();
-    play_mode_menu.append(&loop_all).ok();
-    play_mode_menu.append(&loop_one).ok();
-    play_mode_menu.append(&shuffle).ok();
+    play_mode.append(&sequential).ok();
+    play_mode.append(&loop_all).ok();
+    play_mode.append(&loop_one).ok();
+    play_mode.append(&shuffle).ok();
+    play_mode.append(&auto_dj).ok();
 
-    menu.append(&play_mode_menu).ok();
+    menu.append(&play_mode).ok();
+
+    // Output device submenu
+    let output_device = Submenu::new("", true);
+    fill_output_devices(&output_device, state);
+    menu.append(&output_device).ok();
 
     // Separator
     menu.append(&PredefinedMenuItem::separator()).ok();
 
+    // Lyrics page toggle
+    let lyrics = CheckMenuItem::with_id(
+        MenuId::new(TOGGLE_LYRICS_ID),
+        "",
+        true,
+        state.lyrics_open,
+        None,
+    );
+    menu.append(&lyrics).ok();
+
     // Window control
-    let toggle_window =
-        NativeMenuItem::with_id(MenuId::new(TOGGLE_WINDOW_ID), "显示/隐藏窗口", true, None);
+    let toggle_window = NativeMenuItem::with_id(MenuId::new(TOGGLE_WINDOW_ID), "", true, None);
     menu.append(&toggle_window).ok();
 
     // Separator
     menu.append(&PredefinedMenuItem::separator()).ok();
 
     // Quit
-    let quit = NativeMenuItem::with_id(MenuId::new(QUIT_ID), "退出", true, None);
+    let quit = NativeMenuItem::with_id(MenuId::new(QUIT_ID), "", true, None);
     menu.append(&quit).ok();
 
-    Ok((menu, play_pause, sequential, loop_all, loop_one, shuffle))
+    let items = NativeMenuItems {
+        now_playing,
+        play_pause,
+        prev_track,
+        next_track,
+        favorite,
+        stop_after_current,
+        up_next,
+        play_mode,
+        sequential,
+        loop_all,
+        loop_one,
+        shuffle,
+        auto_dj,
+        output_device,
+        lyrics,
+        toggle_window,
+        quit,
+    };
+    set_labels(&items, state);
+
+    Ok((menu, items))
 }
 
 #[allow(dead_code)]
 fn create_native_menu(state: &TrayState) -> anyhow::Result<Menu> {
-    create_native_menu_with_items(state).map(|(menu, _, _, _, _, _)| menu)
+    create_native_menu_with_items(state).map(|(menu, _)| menu)
 }
@@ -2,6 +2,8 @@
 //!
 //! Provides unified window behavior functions across platforms.
 //! Handles platform-specific differences in show/hide/minimize behavior.
+//! The main window is the first one opened, so these address
+//! [`iced::window::oldest`] rather than a window opened later.
 
 use iced::Task;
 
@@ -66,3 +68,21 @@ pub fn window_settings() -> iced::window::Settings {
         ..Default::default()
     }
 }
+
+/// Settings for the lyrics window
+///
+/// Unlike the main window it keeps the system title bar, so it can be moved
+/// to another monitor like any other window.
+pub fn lyrics_window_settings() -> iced::window::Settings {
+    iced::window::Settings {
+        size: iced::Size::new(1280.0, 720.0),
+        min_size: Some(iced::Size::new(640.0, 360.0)),
+        exit_on_close_request: false,
+        #[cfg(target_os = "linux")]
+        platform_specific: iced::window::settings::PlatformSpecific {
+            application_id: "rustle".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
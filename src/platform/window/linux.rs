@@ -4,7 +4,7 @@ use iced::Task;
 
 /// Show the window and bring it to front (Linux)
 pub fn show_window<Message: Send + 'static>() -> Task<Message> {
-    iced::window::latest().and_then(|id| {
+    iced::window::oldest().and_then(|id| {
         Task::batch([
             iced::window::set_visible(id, true),
             iced::window::gain_focus(id),
@@ -14,5 +14,5 @@ pub fn show_window<Message: Send + 'static>() -> Task<Message> {
 
 /// Hide the window (Linux)
 pub fn hide_window<Message: Send + 'static>() -> Task<Message> {
-    iced::window::latest().and_then(|id| iced::window::set_visible(id, false))
+    iced::window::oldest().and_then(|id| iced::window::set_visible(id, false))
 }
@@ -7,7 +7,7 @@ use iced::Task;
 /// Show the window and bring it to front (Windows)
 /// Windows needs to restore from minimized state first
 pub fn show_window<Message: Send + 'static>() -> Task<Message> {
-    iced::window::latest().and_then(|id| {
+    iced::window::oldest().and_then(|id| {
         Task::batch([
             iced::window::set_visible(id, true),
             iced::window::minimize(id, false),
@@ -19,7 +19,7 @@ pub fn show_window<Message: Send + 'static>() -> Task<Message> {
 /// Hide the window (Windows)
 /// Windows needs to minimize first, then hide
 pub fn hide_window<Message: Send + 'static>() -> Task<Message> {
-    iced::window::latest().and_then(|id| {
+    iced::window::oldest().and_then(|id| {
         Task::batch([
             iced::window::minimize(id, true),
             iced::window::set_visible(id, false),
@@ -15,7 +15,11 @@
 //! let scale: Animated<f32> = Animated::spring(1.0, Motion::BOUNCY);
 //! ```
 
+mod color;
 mod hover;
 pub mod prelude;
+mod shared;
 
+pub use color::{ColorTransition, lerp_color};
 pub use hover::{HoverAnimations, SingleHoverAnimation};
+pub use shared::{Flight, lerp_rect};
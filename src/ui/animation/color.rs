@@ -0,0 +1,96 @@
+//! Color transitions
+//!
+//! A color that eases toward a new target instead of jumping, e.g. page
+//! chrome following a cover palette that loads after the page is shown.
+
+use iced::Color;
+use iced_anim::Animated;
+use iced_anim::transition::Easing;
+use std::time::{Duration, Instant};
+
+/// Time to reach a new target
+const TRANSITION_DURATION: Duration = Duration::from_millis(600);
+
+/// Color `t` of the way from `from` to `to`
+pub fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    Color::from_rgba(
+        lerp(from.r, to.r),
+        lerp(from.g, to.g),
+        lerp(from.b, to.b),
+        lerp(from.a, to.a),
+    )
+}
+
+/// A color easing toward its latest target
+#[derive(Debug)]
+pub struct ColorTransition {
+    from: Color,
+    to: Color,
+    animation: Animated<f32>,
+}
+
+impl ColorTransition {
+    /// Start out at `color`, not moving
+    pub fn new(color: Color) -> Self {
+        Self {
+            from: color,
+            to: color,
+            animation: Animated::transition(
+                1.0,
+                Easing::EASE_IN_OUT.with_duration(TRANSITION_DURATION),
+            ),
+        }
+    }
+
+    /// Ease toward `target` from wherever the color is now
+    pub fn follow(&mut self, target: Color) {
+        if self.to == target {
+            return;
+        }
+        self.from = self.color();
+        self.to = target;
+        self.animation =
+            Animated::transition(0.0, Easing::EASE_IN_OUT.with_duration(TRANSITION_DURATION));
+        self.animation.update(1.0.into());
+    }
+
+    /// Tick the animation forward in time
+    pub fn tick(&mut self, now: Instant) {
+        self.animation.tick(now);
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.animation.is_animating()
+    }
+
+    /// The color being moved to
+    pub fn target(&self) -> Color {
+        self.to
+    }
+
+    /// The color drawn now
+    pub fn color(&self) -> Color {
+        lerp_color(self.from, self.to, *self.animation.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_starts_from_current_color() {
+        let mut color = ColorTransition::new(Color::BLACK);
+        assert_eq!(color.color(), Color::BLACK);
+        assert!(!color.is_transitioning());
+
+        color.follow(Color::WHITE);
+        assert!(color.is_transitioning());
+        assert_eq!(color.color(), Color::BLACK);
+        assert_eq!(color.target(), Color::WHITE);
+
+        color.tick(Instant::now() + TRANSITION_DURATION * 2);
+        assert_eq!(color.color(), Color::WHITE);
+    }
+}
@@ -0,0 +1,120 @@
+//! Shared-element transitions
+//!
+//! An element flies from where it was on one page to where it sits on the
+//! next, e.g. a playlist cover from its grid card into the playlist header.
+//! Both ends report their bounds through
+//! [`BoundsSensor`](crate::ui::primitives::BoundsSensor); the flight is drawn
+//! above everything while the real element at the far end stays hidden.
+
+use iced::{Point, Rectangle, Size};
+use iced_anim::Animated;
+use iced_anim::transition::Easing;
+use std::time::{Duration, Instant};
+
+/// Time from take-off to landing
+const FLIGHT_DURATION: Duration = Duration::from_millis(380);
+
+/// How long a flight waits for its landing spot before giving up
+const LANDING_TIMEOUT: Duration = Duration::from_millis(600);
+
+/// Rectangle `t` of the way from `from` to `to`
+pub fn lerp_rect(from: Rectangle, to: Rectangle, t: f32) -> Rectangle {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    Rectangle::new(
+        Point::new(lerp(from.x, to.x), lerp(from.y, to.y)),
+        Size::new(lerp(from.width, to.width), lerp(from.height, to.height)),
+    )
+}
+
+/// An element flying between two places
+#[derive(Debug)]
+pub struct Flight<T> {
+    /// What is flying, e.g. a cover image
+    pub content: T,
+    from: Rectangle,
+    to: Option<Rectangle>,
+    animation: Animated<f32>,
+    took_off: Instant,
+}
+
+impl<T> Flight<T> {
+    /// Start a flight from `from`; it moves once its landing spot is known
+    pub fn new(content: T, from: Rectangle) -> Self {
+        Self {
+            content,
+            from,
+            to: None,
+            animation: Animated::transition(
+                0.0,
+                Easing::EASE_IN_OUT.with_duration(FLIGHT_DURATION),
+            ),
+            took_off: Instant::now(),
+        }
+    }
+
+    /// Set where the element lands, following it if it moves mid-flight
+    pub fn land_at(&mut self, to: Rectangle) {
+        if self.to.is_none() {
+            self.animation.update(1.0.into());
+        }
+        self.to = Some(to);
+    }
+
+    /// Tick the animation forward in time
+    pub fn tick(&mut self, now: Instant) {
+        self.animation.tick(now);
+    }
+
+    /// Where the element is drawn now
+    pub fn bounds(&self) -> Rectangle {
+        match self.to {
+            Some(to) => lerp_rect(self.from, to, self.progress()),
+            None => self.from,
+        }
+    }
+
+    /// Progress from take-off (0.0) to landing (1.0)
+    pub fn progress(&self) -> f32 {
+        *self.animation.value()
+    }
+
+    /// Whether the flight has landed, or never found where to land
+    pub fn is_over(&self, now: Instant) -> bool {
+        match self.to {
+            Some(_) => !self.animation.is_animating() && self.progress() >= 1.0,
+            None => now.duration_since(self.took_off) > LANDING_TIMEOUT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, size: f32) -> Rectangle {
+        Rectangle::new(Point::new(x, y), Size::new(size, size))
+    }
+
+    #[test]
+    fn test_lerp_rect() {
+        let from = rect(0.0, 100.0, 160.0);
+        let to = rect(300.0, 60.0, 220.0);
+        assert_eq!(lerp_rect(from, to, 0.0), from);
+        assert_eq!(lerp_rect(from, to, 1.0), to);
+        assert_eq!(lerp_rect(from, to, 0.5), rect(150.0, 80.0, 190.0));
+    }
+
+    #[test]
+    fn test_flight_waits_for_landing_spot() {
+        let now = Instant::now();
+        let mut flight = Flight::new((), rect(10.0, 10.0, 50.0));
+        assert_eq!(flight.bounds(), rect(10.0, 10.0, 50.0));
+        assert!(!flight.is_over(now));
+        assert!(flight.is_over(now + LANDING_TIMEOUT * 2));
+
+        flight.land_at(rect(200.0, 60.0, 220.0));
+        flight.tick(now + FLIGHT_DURATION * 2);
+        assert!(flight.is_over(now + FLIGHT_DURATION * 2));
+        assert_eq!(flight.bounds(), rect(200.0, 60.0, 220.0));
+    }
+}
@@ -16,19 +16,34 @@
 //! - **Components** (this module): Business-specific UI with Message handling
 
 pub mod carousel_banner;
+pub mod chapter_panel;
+pub mod cover_viewer;
+pub mod crash_dialog;
 pub mod delete_playlist_dialog;
 pub mod edit_dialog;
 pub mod exit_dialog;
+pub mod export_card;
+pub mod export_dialog;
+pub mod health_dialog;
 pub mod importing_card;
+pub mod listen_card;
 pub mod login_popup;
+pub mod lyrics_share_dialog;
+pub mod memory_overlay;
+pub mod open_link_dialog;
 pub mod player_bar;
+pub mod player_menu;
 pub mod playlist_grid;
 pub mod playlist_view;
+pub mod publish_dialog;
 pub mod queue_panel;
 pub mod search_bar;
+pub mod search_dropdown;
 pub mod sidebar;
 pub mod sidebar_resize_handle;
+pub mod song_info_dialog;
 pub mod trending_list;
+pub mod update_dialog;
 pub mod window_controls;
 
 pub use importing_card::ImportingPlaylist;
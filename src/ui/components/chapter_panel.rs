@@ -0,0 +1,184 @@
+//! Chapter list popup component
+//!
+//! Lists the chapters of the current song above the player bar; clicking a
+//! chapter jumps to its start.
+
+use iced::widget::{Space, button, column, container, row, scrollable, svg, text};
+use iced::{Alignment, Color, Element, Fill, Length, Padding};
+
+use crate::app::Message;
+use crate::features::media::chapters::Chapter;
+use crate::i18n::{Key, Locale};
+use crate::ui::{icons, theme};
+
+/// Chapter popup width
+pub const CHAPTER_PANEL_WIDTH: f32 = 320.0;
+/// Chapter popup max height
+pub const CHAPTER_PANEL_HEIGHT: f32 = 400.0;
+/// Height of each chapter row (padding 8*2 + content ~18, plus spacing)
+const CHAPTER_ITEM_HEIGHT: f32 = 36.0;
+
+/// Build the chapter popup bubble
+pub fn view(
+    chapters: &[Chapter],
+    current: Option<usize>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let header = row![
+        text(locale.get(Key::ChaptersTitle).to_string())
+            .size(16)
+            .style(move |theme| text::Style {
+                color: Some(theme::text_primary(theme))
+            }),
+        Space::new().width(Fill),
+        text(format!("{}", chapters.len()))
+            .size(12)
+            .style(|theme| text::Style {
+                color: Some(theme::text_muted(theme))
+            }),
+        Space::new().width(8),
+        button(
+            svg(svg::Handle::from_memory(icons::CLOSE.as_bytes()))
+                .width(14)
+                .height(14)
+                .style(|theme, _status| svg::Style {
+                    color: Some(theme::text_muted(theme)),
+                })
+        )
+        .padding(6)
+        .style(theme::transparent_btn)
+        .on_press(Message::ToggleChapters),
+    ]
+    .align_y(Alignment::Center)
+    .padding(Padding::new(12.0).left(16.0).right(12.0));
+
+    let items: Vec<Element<'static, Message>> = chapters
+        .iter()
+        .enumerate()
+        .map(|(idx, chapter)| build_chapter_item(chapter, idx, current == Some(idx), locale))
+        .collect();
+
+    let list = scrollable(
+        column(items)
+            .spacing(2)
+            .padding(Padding::new(0.0).left(8.0).right(8.0).bottom(8.0)),
+    )
+    .height(Length::Fixed(
+        (chapters.len() as f32 * CHAPTER_ITEM_HEIGHT + 8.0).min(CHAPTER_PANEL_HEIGHT - 60.0),
+    ));
+
+    let content = column![header, list].width(CHAPTER_PANEL_WIDTH);
+
+    container(content)
+        .width(CHAPTER_PANEL_WIDTH)
+        .max_height(CHAPTER_PANEL_HEIGHT)
+        .style(|theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(theme::surface_elevated(theme))),
+            border: iced::Border {
+                color: theme::divider(theme),
+                width: 1.0,
+                radius: 12.0.into(),
+            },
+            shadow: iced::Shadow {
+                color: theme::overlay_backdrop(theme, 0.5),
+                offset: iced::Vector::new(0.0, -4.0),
+                blur_radius: 20.0,
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Build a single chapter row
+fn build_chapter_item(
+    chapter: &Chapter,
+    index: usize,
+    is_current: bool,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let start_secs = chapter.start_ms / 1000;
+    let start_str = if start_secs >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            start_secs / 3600,
+            start_secs / 60 % 60,
+            start_secs % 60
+        )
+    } else {
+        format!("{}:{:02}", start_secs / 60, start_secs % 60)
+    };
+
+    let title_str = if chapter.title.is_empty() {
+        locale
+            .get(Key::ChapterUntitled)
+            .replace("{}", &(index + 1).to_string())
+    } else {
+        chapter.title.clone()
+    };
+
+    let indicator: Element<'static, Message> = if is_current {
+        svg(svg::Handle::from_memory(icons::PLAYING.as_bytes()))
+            .width(14)
+            .height(14)
+            .style(|_theme, _status| svg::Style {
+                color: Some(theme::ACCENT_PINK),
+            })
+            .into()
+    } else {
+        text(format!("{}", index + 1))
+            .size(12)
+            .style(|theme| text::Style {
+                color: Some(theme::text_muted(theme)),
+            })
+            .into()
+    };
+
+    let title = text(title_str)
+        .size(13)
+        .width(Fill)
+        .style(move |theme| text::Style {
+            color: Some(if is_current {
+                theme::ACCENT_PINK
+            } else {
+                theme::text_primary(theme)
+            }),
+        });
+
+    let start = text(start_str).size(11).style(|theme| text::Style {
+        color: Some(theme::text_muted(theme)),
+    });
+
+    let item_row = row![
+        container(indicator).width(24).center_x(24),
+        Space::new().width(8),
+        title,
+        start,
+    ]
+    .align_y(Alignment::Center)
+    .padding(Padding::new(8.0).left(8.0).right(8.0));
+
+    button(item_row)
+        .width(Fill)
+        .padding(0)
+        .style(move |theme, status| {
+            let bg_color = if is_current {
+                theme::hover_bg(theme)
+            } else {
+                Color::TRANSPARENT
+            };
+            let bg = match status {
+                button::Status::Hovered | button::Status::Pressed => theme::hover_bg(theme),
+                _ => bg_color,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(bg)),
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .on_press(Message::SeekToChapter(index))
+        .into()
+}
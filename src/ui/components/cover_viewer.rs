@@ -0,0 +1,81 @@
+//! Full-screen cover viewer component
+//!
+//! Scrolling zooms in around the pointer and dragging pans, as the image
+//! viewer widget does.
+
+use iced::widget::{Space, button, column, container, image, opaque, row, svg, text};
+use iced::{Alignment, Color, Element, Fill, Padding};
+
+use crate::app::{CoverViewer, Message};
+use crate::i18n::{Key, Locale};
+use crate::ui::{icons, theme};
+
+/// Largest zoom, relative to the image's own size
+const MAX_SCALE: f32 = 8.0;
+
+/// Build the cover viewer overlay
+pub fn view(
+    viewer: &CoverViewer,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+
+    let save_btn = button(text(locale.get(Key::CoverViewerSave).to_string()).size(14))
+        .padding([8, 16])
+        .style(theme::secondary_button)
+        .on_press(Message::SaveCoverImage);
+
+    let close_btn = button(
+        svg(svg::Handle::from_memory(icons::CLOSE.as_bytes()))
+            .width(18)
+            .height(18)
+            .style(|_theme, _status| svg::Style {
+                color: Some(Color::WHITE),
+            }),
+    )
+    .padding(8)
+    .style(theme::transparent_btn)
+    .on_press(Message::CloseCoverViewer);
+
+    let mut top_bar = row![Space::new().width(Fill)]
+        .spacing(12)
+        .align_y(Alignment::Center);
+    if viewer.loading_full {
+        top_bar = top_bar.push(
+            text(locale.get(Key::CoverViewerLoadingFull).to_string())
+                .size(13)
+                .color(theme::TEXT_MUTED),
+        );
+    }
+    let top_bar = top_bar.push(save_btn).push(close_btn);
+
+    let picture = image::viewer(image::Handle::from_path(&viewer.path))
+        .width(Fill)
+        .height(Fill)
+        .min_scale(1.0)
+        .max_scale(MAX_SCALE);
+
+    let content = column![top_bar, picture]
+        .spacing(12)
+        .padding(Padding::new(16.0).bottom(32.0));
+
+    let backdrop = container(content)
+        .width(Fill)
+        .height(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.85 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    opaque(backdrop).into()
+}
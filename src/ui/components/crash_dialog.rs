@@ -0,0 +1,117 @@
+//! Crash recovery dialog component
+
+use iced::mouse::Interaction;
+use iced::widget::{Space, button, column, container, mouse_area, opaque, row, text};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::Message;
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the dialog shown after the previous run crashed
+pub fn view(
+    has_session: bool,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+
+    let title = text(locale.get(Key::CrashDialogTitle).to_string())
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let message_key = if has_session {
+        Key::CrashDialogMessage
+    } else {
+        Key::CrashDialogNoSession
+    };
+    let message = text(locale.get(message_key).to_string())
+        .size(14)
+        .color(theme::TEXT_SECONDARY);
+
+    let dismiss_btn = button(text(locale.get(Key::CrashDialogDismiss).to_string()).size(14))
+        .padding([10, 20])
+        .style(theme::secondary_button)
+        .on_press(Message::DismissCrashReport);
+
+    let report_btn = button(text(locale.get(Key::CrashDialogReport).to_string()).size(14))
+        .padding([10, 20])
+        .style(theme::secondary_button)
+        .on_press(Message::SubmitCrashReport);
+
+    let mut buttons = row![report_btn, Space::new().width(Fill), dismiss_btn]
+        .spacing(12)
+        .align_y(Alignment::Center);
+
+    if has_session {
+        buttons = buttons.push(
+            button(
+                text(locale.get(Key::CrashDialogRestore).to_string())
+                    .size(14)
+                    .color(Color::WHITE),
+            )
+            .padding([10, 20])
+            .style(theme::primary_button)
+            .on_press(Message::RestoreCrashedSession),
+        );
+    }
+
+    let dialog_content = column![
+        title,
+        Space::new().height(12),
+        message,
+        Space::new().height(24),
+        buttons,
+    ]
+    .width(440)
+    .padding(24);
+
+    let dialog_box = container(dialog_content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    // Backdrop clicks are swallowed so the choice is explicit
+    let event_blocker = mouse_area(backdrop_content).interaction(Interaction::Idle);
+
+    opaque(event_blocker).into()
+}
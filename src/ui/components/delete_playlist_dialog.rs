@@ -28,7 +28,9 @@ pub fn view(playlist_name: &str, animation_progress: f32, locale: Locale) -> Ele
             ..Default::default()
         });
 
-    let message_text = format!("确定要删除歌单「{}」吗？此操作无法撤销。", playlist_name);
+    let message_text = locale
+        .get(Key::DeletePlaylistConfirm)
+        .replace("{}", playlist_name);
     let message = text(message_text).size(14).color(theme::TEXT_SECONDARY);
 
     // Buttons
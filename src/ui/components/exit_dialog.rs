@@ -35,7 +35,7 @@ pub fn view(
         .color(theme::TEXT_SECONDARY);
 
     let remember_checkbox = checkbox(remember_choice)
-        .label("记住我的选择")
+        .label(locale.get(Key::ExitDialogRemember))
         .on_toggle(Message::ExitDialogRememberChanged)
         .text_size(13)
         .spacing(8)
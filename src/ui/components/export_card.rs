@@ -0,0 +1,84 @@
+//! Export progress card component
+//!
+//! Floats above the player bar while songs are being exported.
+
+use iced::widget::{Space, button, column, container, row, svg, text};
+use iced::{Alignment, Element, Fill, Padding};
+
+use crate::app::{ExportState, Message};
+use crate::i18n::{Key, Locale};
+use crate::ui::icons;
+use crate::ui::theme;
+use crate::ui::widgets::{ProgressRing, view_progress_ring_styled};
+
+/// Card width, matching the listen card it stacks with
+pub const EXPORT_CARD_WIDTH: f32 = super::listen_card::LISTEN_CARD_WIDTH;
+
+/// Build the export progress card
+pub fn view(export: &ExportState, locale: Locale) -> Element<'static, Message> {
+    let done = export.done();
+    let progress = if export.total > 0 {
+        done as f32 / export.total as f32
+    } else {
+        0.0
+    };
+    let ring = ProgressRing::new(progress)
+        .stroke_width(2.5)
+        .background_color(theme::SURFACE_LIGHT)
+        .progress_color(theme::ACCENT_PINK);
+
+    let status = locale
+        .get(Key::ExportProgress)
+        .replacen("{}", &(done + 1).min(export.total).to_string(), 1)
+        .replacen("{}", &export.total.to_string(), 1);
+
+    let content = row![
+        view_progress_ring_styled(ring, 28.0),
+        Space::new().width(4),
+        column![
+            text(status).size(12).style(|theme| text::Style {
+                color: Some(theme::text_muted(theme))
+            }),
+            text(export.current.clone().unwrap_or_default())
+                .size(14)
+                .wrapping(text::Wrapping::None)
+                .style(|theme| text::Style {
+                    color: Some(theme::text_primary(theme))
+                }),
+        ]
+        .spacing(2)
+        .width(Fill),
+        button(
+            svg(svg::Handle::from_memory(icons::CLOSE.as_bytes()))
+                .width(14)
+                .height(14)
+                .style(|theme, _status| svg::Style {
+                    color: Some(theme::text_muted(theme)),
+                })
+        )
+        .padding(6)
+        .style(theme::transparent_btn)
+        .on_press(Message::CancelExport),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    container(content)
+        .width(EXPORT_CARD_WIDTH)
+        .padding(Padding::new(12.0).left(16.0))
+        .style(|theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(theme::surface_elevated(theme))),
+            border: iced::Border {
+                color: theme::divider(theme),
+                width: 1.0,
+                radius: 12.0.into(),
+            },
+            shadow: iced::Shadow {
+                color: theme::overlay_backdrop(theme, 0.5),
+                offset: iced::Vector::new(0.0, -4.0),
+                blur_radius: 20.0,
+            },
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,176 @@
+//! Export dialog component
+
+use iced::mouse::Interaction;
+use iced::widget::{Space, button, column, container, mouse_area, opaque, pick_list, row, text};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::{ExportDialog, Message};
+use crate::features::export::{BITRATES, ExportFormat};
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the dialog for choosing how to export a playlist's songs
+pub fn view(
+    dialog: &ExportDialog,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+    let settings = &dialog.settings;
+
+    let title = text(locale.get(Key::ExportTitle).replace("{}", &dialog.name))
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let desc = text(format!(
+        "{} · {}",
+        locale.count(Key::ExportSongCount, dialog.songs.len() as u64),
+        locale.get(Key::ExportDesc)
+    ))
+    .size(13)
+    .color(theme::TEXT_SECONDARY);
+
+    let format_list = pick_list(
+        ExportFormat::ALL.map(ExportFormat::label).to_vec(),
+        Some(settings.format.label()),
+        |label| {
+            ExportFormat::ALL
+                .into_iter()
+                .find(|format| format.label() == label)
+                .map_or(Message::NoOp, Message::ExportFormatChanged)
+        },
+    )
+    .style(theme::settings_pick_list)
+    .menu_style(theme::settings_pick_list_menu)
+    .padding([8, 12]);
+
+    let mut fields = column![field(locale.get(Key::ExportFormat), format_list.into())].spacing(14);
+
+    if settings.format.is_lossy() {
+        let bitrate_list = pick_list(
+            BITRATES.map(format_bitrate).to_vec(),
+            Some(format_bitrate(settings.bitrate_kbps)),
+            |label| {
+                BITRATES
+                    .into_iter()
+                    .find(|kbps| format_bitrate(*kbps) == label)
+                    .map_or(Message::NoOp, Message::ExportBitrateChanged)
+            },
+        )
+        .style(theme::settings_pick_list)
+        .menu_style(theme::settings_pick_list_menu)
+        .padding([8, 12]);
+        fields = fields.push(field(locale.get(Key::ExportBitrate), bitrate_list.into()));
+    }
+
+    let folder = row![
+        text(settings.dir().display().to_string())
+            .size(13)
+            .color(theme::TEXT_MUTED)
+            .width(Fill),
+        button(text(locale.get(Key::ExportChooseFolder).to_string()).size(13))
+            .padding([8, 14])
+            .style(theme::secondary_button)
+            .on_press(Message::PickExportFolder),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center);
+    fields = fields.push(field(locale.get(Key::ExportFolder), folder.into()));
+
+    let cancel_btn = button(text(locale.get(Key::Cancel).to_string()).size(14))
+        .padding([10, 20])
+        .style(theme::secondary_button)
+        .on_press(Message::CloseExport);
+
+    let export_btn = button(
+        text(locale.get(Key::ExportStart).to_string())
+            .size(14)
+            .color(Color::WHITE),
+    )
+    .padding([10, 20])
+    .style(theme::primary_button)
+    .on_press(Message::StartExport);
+
+    let buttons = row![Space::new().width(Fill), cancel_btn, export_btn]
+        .spacing(12)
+        .align_y(Alignment::Center);
+
+    let dialog_content = column![
+        title,
+        Space::new().height(8),
+        desc,
+        Space::new().height(20),
+        fields,
+        Space::new().height(24),
+        buttons,
+    ]
+    .width(440)
+    .padding(24);
+
+    let dialog_box = container(dialog_content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    // Clicking the backdrop cancels
+    let event_blocker = mouse_area(backdrop_content)
+        .interaction(Interaction::Idle)
+        .on_press(Message::CloseExport);
+
+    opaque(event_blocker).into()
+}
+
+/// Label above a control
+fn field(label: &str, control: Element<'static, Message>) -> Element<'static, Message> {
+    column![
+        text(label.to_string())
+            .size(13)
+            .color(theme::TEXT_SECONDARY),
+        control,
+    ]
+    .spacing(6)
+    .into()
+}
+
+fn format_bitrate(kbps: u32) -> String {
+    format!("{} kbps", kbps)
+}
@@ -0,0 +1,211 @@
+//! Song availability report dialog for NCM playlists
+
+use iced::mouse::Interaction;
+use iced::widget::{
+    Column, Space, button, column, container, mouse_area, opaque, row, scrollable, text,
+};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::{HealthDialog, Message};
+use crate::features::playlist_health::{HealthReport, TrackProblem};
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the availability dialog: progress while checking, then the report
+pub fn view(
+    dialog: &HealthDialog,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+    // An action is running on a checked playlist
+    let working = dialog.report.is_some() && dialog.job.is_some();
+
+    let body: Element<'static, Message> = match &dialog.report {
+        None => secondary_text(locale.get(Key::HealthChecking).to_string()),
+        Some(report) => report_view(report, locale),
+    };
+
+    let mut buttons = row![Space::new().width(Fill)]
+        .spacing(12)
+        .align_y(Alignment::Center);
+    if working {
+        buttons = buttons.push(secondary_text(locale.get(Key::HealthWorking).to_string()));
+    } else {
+        let flagged = dialog.report.as_ref().map_or(0, |r| r.flagged.len());
+        let unavailable = dialog
+            .report
+            .as_ref()
+            .map_or(0, |r| r.count(TrackProblem::Unavailable));
+
+        if flagged > 0 {
+            buttons = buttons.push(
+                button(text(locale.get(Key::HealthExport).to_string()).size(14))
+                    .padding([10, 20])
+                    .style(theme::secondary_button)
+                    .on_press(Message::ExportFlaggedTracks),
+            );
+            if dialog.editable {
+                buttons = buttons
+                    .push(
+                        button(text(locale.get(Key::HealthReplace).to_string()).size(14))
+                            .padding([10, 20])
+                            .style(theme::secondary_button)
+                            .on_press(Message::ReplaceFlaggedTracks),
+                    )
+                    .push(
+                        button(text(locale.get(Key::HealthRemove).to_string()).size(14))
+                            .padding([10, 20])
+                            .style(theme::danger_button)
+                            .on_press_maybe(
+                                (unavailable > 0).then_some(Message::RemoveUnavailableTracks),
+                            ),
+                    );
+            }
+        }
+        buttons = buttons.push(
+            button(
+                text(locale.get(Key::Close).to_string())
+                    .size(14)
+                    .color(Color::WHITE),
+            )
+            .padding([10, 20])
+            .style(theme::primary_button)
+            .on_press(Message::ClosePlaylistHealth),
+        );
+    }
+
+    let title = text(dialog.name.clone())
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let mut dialog_content = column![title, Space::new().height(12), body];
+    if dialog
+        .report
+        .as_ref()
+        .is_some_and(|r| !r.flagged.is_empty())
+        && !dialog.editable
+    {
+        dialog_content = dialog_content.push(Space::new().height(8)).push(
+            text(locale.get(Key::HealthReadOnly).to_string())
+                .size(12)
+                .color(theme::TEXT_SECONDARY),
+        );
+    }
+    let dialog_content = dialog_content
+        .push(Space::new().height(24))
+        .push(buttons)
+        .width(560)
+        .padding(24);
+
+    let dialog_box = container(dialog_content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    // Clicking the backdrop closes, unless the playlist is being changed
+    let mut event_blocker = mouse_area(backdrop_content).interaction(Interaction::Idle);
+    if !working {
+        event_blocker = event_blocker.on_press(Message::ClosePlaylistHealth);
+    }
+
+    opaque(event_blocker).into()
+}
+
+/// Counts per problem and the flagged songs
+fn report_view(report: &HealthReport, locale: Locale) -> Element<'static, Message> {
+    if report.flagged.is_empty() {
+        return secondary_text(locale.count(Key::HealthAllGood, report.track_ids.len() as u64));
+    }
+
+    let summary = locale
+        .get(Key::HealthSummary)
+        .replacen(
+            "{}",
+            &report.count(TrackProblem::Unavailable).to_string(),
+            1,
+        )
+        .replacen("{}", &report.count(TrackProblem::VipOnly).to_string(), 1)
+        .replacen("{}", &report.count(TrackProblem::Paid).to_string(), 1);
+
+    let rows = report.flagged.iter().map(|flagged| {
+        let song = &flagged.song;
+        let title = if song.name.is_empty() {
+            format!("#{}", song.id)
+        } else {
+            song.name.clone()
+        };
+        let tag_color = match flagged.problem {
+            TrackProblem::Unavailable => theme::TEXT_MUTED,
+            TrackProblem::VipOnly | TrackProblem::Paid => theme::ACCENT_PINK,
+        };
+        row![
+            column![
+                text(title).size(14).style(|theme| text::Style {
+                    color: Some(theme::text_primary(theme)),
+                }),
+                text(song.singer.clone())
+                    .size(12)
+                    .color(theme::TEXT_SECONDARY),
+            ]
+            .width(Fill),
+            text(flagged.problem.display_name(locale))
+                .size(12)
+                .color(tag_color),
+        ]
+        .align_y(Alignment::Center)
+        .into()
+    });
+
+    column![
+        secondary_text(summary),
+        scrollable(Column::with_children(rows).spacing(8).padding([0, 8]))
+            .height(320)
+            .width(Fill),
+    ]
+    .spacing(12)
+    .into()
+}
+
+fn secondary_text(label: String) -> Element<'static, Message> {
+    text(label).size(14).color(theme::TEXT_SECONDARY).into()
+}
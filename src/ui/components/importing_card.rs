@@ -7,6 +7,7 @@ use iced::widget::{Space, button, column, container, row, text};
 use iced::{Alignment, Element, Fill, Padding};
 
 use crate::app::Message;
+use crate::i18n::{Key, Locale};
 use crate::ui::theme;
 use crate::ui::widgets::{ProgressRing, view_progress_ring_styled};
 
@@ -69,7 +70,7 @@ impl ImportingPlaylist {
 }
 
 /// Build an importing playlist card for the sidebar
-pub fn view(playlist: &ImportingPlaylist) -> Element<'static, Message> {
+pub fn view(playlist: &ImportingPlaylist, locale: Locale) -> Element<'static, Message> {
     let name = playlist.name.clone();
     let progress = playlist.progress;
     let percentage = (progress * 100.0) as u32;
@@ -117,11 +118,11 @@ pub fn view(playlist: &ImportingPlaylist) -> Element<'static, Message> {
 
     // Playlist info
     let status_text = if playlist.completed {
-        "导入完成".to_string()
+        locale.get(Key::ImportCompleted).to_string()
     } else if playlist.total > 0 {
         format!("{}/{}", playlist.current, playlist.total)
     } else {
-        "扫描中...".to_string()
+        locale.get(Key::ImportScanning).to_string()
     };
 
     let completed = playlist.completed;
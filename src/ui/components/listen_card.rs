@@ -0,0 +1,118 @@
+//! Listen mode card component
+//!
+//! Floats above the player bar while listen mode is on, saying what it is
+//! doing and, once a song is recognised, following its lyrics.
+
+use iced::widget::{button, column, container, row, svg, text};
+use iced::{Alignment, Element, Fill, Padding};
+
+use crate::app::{HeardSong, ListenState, Message};
+use crate::i18n::{Key, Locale};
+use crate::ui::icons;
+use crate::ui::pages::find_current_line;
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Card width
+pub const LISTEN_CARD_WIDTH: f32 = 320.0;
+
+/// Build the listen mode card
+pub fn view(listen: &ListenState, locale: Locale) -> Element<'static, Message> {
+    let status = match (&listen.heard, listen.indexing) {
+        (Some(heard), _) => format!("{} · {}", heard.song.title, heard.song.artist),
+        (None, Some((done, total))) => locale
+            .get(Key::ListenPreparing)
+            .replacen("{}", &done.to_string(), 1)
+            .replacen("{}", &total.to_string(), 1),
+        (None, None) => locale.get(Key::ListenListening).to_string(),
+    };
+
+    let header = row![
+        column![
+            text(locale.get(Key::ListenTitle).to_string())
+                .size(12)
+                .style(|theme| text::Style {
+                    color: Some(theme::text_muted(theme))
+                }),
+            text(status).size(14).style(|theme| text::Style {
+                color: Some(theme::text_primary(theme))
+            }),
+        ]
+        .spacing(2)
+        .width(Fill),
+        button(
+            svg(svg::Handle::from_memory(icons::CLOSE.as_bytes()))
+                .width(14)
+                .height(14)
+                .style(|theme, _status| svg::Style {
+                    color: Some(theme::text_muted(theme)),
+                })
+        )
+        .padding(6)
+        .style(theme::transparent_btn)
+        .on_press(Message::ToggleListenMode),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let mut content = column![header].spacing(12);
+    if let Some(heard) = &listen.heard {
+        content = content.push(lyrics(heard, locale));
+    }
+
+    container(content)
+        .width(LISTEN_CARD_WIDTH)
+        .padding(Padding::new(12.0).left(16.0))
+        .style(|theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(theme::surface_elevated(theme))),
+            border: iced::Border {
+                color: theme::divider(theme),
+                width: 1.0,
+                radius: 12.0.into(),
+            },
+            shadow: iced::Shadow {
+                color: theme::overlay_backdrop(theme, 0.5),
+                offset: iced::Vector::new(0.0, -4.0),
+                blur_radius: 20.0,
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Current and next lyric line of the heard song
+fn lyrics(heard: &HeardSong, locale: Locale) -> Element<'static, Message> {
+    if heard.lyrics.is_empty() {
+        return text(locale.get(Key::ListenNoLyrics).to_string())
+            .size(13)
+            .style(|theme| text::Style {
+                color: Some(theme::text_muted(theme)),
+            })
+            .into();
+    }
+
+    let position_ms = heard.position_now().as_millis() as u64;
+    let current = find_current_line(&heard.lyrics, position_ms);
+    let line = |idx: Option<usize>| {
+        idx.and_then(|idx| heard.lyrics.get(idx))
+            .map(|line| line.text.clone())
+            .unwrap_or_default()
+    };
+    let next = current.map_or(0, |idx| idx + 1);
+
+    column![
+        text(line(current))
+            .size(16)
+            .font(iced::Font {
+                weight: BOLD_WEIGHT,
+                ..Default::default()
+            })
+            .style(|theme| text::Style {
+                color: Some(theme::text_primary(theme)),
+            }),
+        text(line(Some(next))).size(13).style(|theme| text::Style {
+            color: Some(theme::text_muted(theme)),
+        }),
+    ]
+    .spacing(6)
+    .into()
+}
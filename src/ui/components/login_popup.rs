@@ -2,13 +2,13 @@
 //!
 //! Displays QR code for NCM login with status messages.
 
-use iced::widget::{Space, button, column, container, image, row, svg, text};
+use iced::widget::{Space, button, column, container, row, svg, text};
 use iced::{Alignment, Color, Element, Fill, Padding};
-use std::path::PathBuf;
 
 use crate::app::Message;
 use crate::app::UserInfo;
 use crate::i18n::{Key, Locale};
+use crate::ui::primitives::{QrCode, view_qr_code};
 use crate::ui::theme::{self, BOLD_WEIGHT};
 
 const POPUP_WIDTH: f32 = 320.0;
@@ -18,7 +18,7 @@ const QR_SIZE: f32 = 200.0;
 /// Build the login popup view
 pub fn view<'a>(
     is_open: bool,
-    qr_code_path: Option<&'a PathBuf>,
+    qr_modules: Option<&'a [Vec<bool>]>,
     qr_status: Option<&'a str>,
     user_info: Option<&'a UserInfo>,
     is_logged_in: bool,
@@ -33,10 +33,10 @@ pub fn view<'a>(
         if let Some(user) = user_info {
             view_logged_in(user, locale)
         } else {
-            view_qr_login(qr_code_path, qr_status, locale)
+            view_qr_login(qr_modules, qr_status, locale)
         }
     } else {
-        view_qr_login(qr_code_path, qr_status, locale)
+        view_qr_login(qr_modules, qr_status, locale)
     };
 
     // Popup container with mouse_area to prevent click-through to backdrop
@@ -76,7 +76,7 @@ pub fn view<'a>(
 
 /// View for QR code login
 fn view_qr_login<'a>(
-    qr_code_path: Option<&'a PathBuf>,
+    qr_modules: Option<&'a [Vec<bool>]>,
     qr_status: Option<&'a str>,
     locale: Locale,
 ) -> Element<'a, Message> {
@@ -90,21 +90,23 @@ fn view_qr_login<'a>(
             color: Some(theme::text_primary(theme)),
         });
 
-    let qr_display: Element<'_, Message> = if let Some(path) = qr_code_path {
-        container(
-            image(path.to_string_lossy().to_string())
-                .width(QR_SIZE)
-                .height(QR_SIZE),
-        )
-        .style(|_theme| container::Style {
+    let qr_display: Element<'_, Message> = if let Some(modules) = qr_modules {
+        // Dark modules on white in every theme keep the code scannable; the
+        // padding is the quiet zone around it
+        container(view_qr_code(
+            QrCode::new(modules).module_color(theme::SURFACE_SECONDARY),
+            QR_SIZE - 16.0,
+        ))
+        .style(|theme| container::Style {
             background: Some(Color::WHITE.into()),
             border: iced::Border {
                 radius: 8.0.into(),
-                ..Default::default()
+                width: 1.0,
+                color: theme::divider(theme),
             },
             ..Default::default()
         })
-        .padding(8)
+        .padding(16)
         .into()
     } else {
         container(
@@ -127,7 +129,7 @@ fn view_qr_login<'a>(
         .into()
     };
 
-    let status_text = text(qr_status.unwrap_or("请使用网易云音乐App扫码"))
+    let status_text = text(qr_status.unwrap_or(locale.get(Key::LoginScanWithApp)))
         .size(14)
         .color(theme::TEXT_SECONDARY);
 
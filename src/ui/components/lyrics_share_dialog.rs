@@ -0,0 +1,228 @@
+//! Dialog previewing a local song's lyrics before sharing them on LRCLIB
+
+use iced::mouse::Interaction;
+use iced::widget::{
+    Space, button, checkbox, column, container, mouse_area, opaque, row, scrollable, text,
+};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::{LyricsShareDialog, LyricsShareStage, Message};
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the share dialog for its current step
+///
+/// `credit_name`: Name to credit from settings, if one is set
+pub fn view(
+    dialog: &LyricsShareDialog,
+    animation_progress: f32,
+    credit_name: Option<&str>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+
+    let body: Element<'static, Message> = match dialog.stage {
+        LyricsShareStage::Preview => preview(dialog, credit_name, locale),
+        LyricsShareStage::Publishing => secondary_text(locale.get(Key::LyricsSharing).to_string()),
+        LyricsShareStage::Done => secondary_text(locale.get(Key::LyricsShareDone).to_string()),
+    };
+
+    let buttons: Element<'static, Message> = match dialog.stage {
+        LyricsShareStage::Preview => {
+            let cancel_btn = button(text(locale.get(Key::Cancel).to_string()).size(14))
+                .padding([10, 20])
+                .style(theme::secondary_button)
+                .on_press(Message::CloseLyricsShareDialog);
+            let share_btn = button(
+                text(locale.get(Key::LyricsShareConfirm).to_string())
+                    .size(14)
+                    .color(Color::WHITE),
+            )
+            .padding([10, 20])
+            .style(theme::primary_button)
+            .on_press(Message::ConfirmLyricsShare);
+            row![Space::new().width(Fill), cancel_btn, share_btn]
+                .spacing(12)
+                .align_y(Alignment::Center)
+                .into()
+        }
+        // Publishing can't be called off once the token is being earned
+        LyricsShareStage::Publishing => Space::new().height(0).into(),
+        LyricsShareStage::Done => {
+            let close_btn = button(text(locale.get(Key::Close).to_string()).size(14))
+                .padding([10, 20])
+                .style(theme::secondary_button)
+                .on_press(Message::CloseLyricsShareDialog);
+            row![Space::new().width(Fill), close_btn]
+                .align_y(Alignment::Center)
+                .into()
+        }
+    };
+
+    let title = text(locale.get(Key::LyricsShareTitle).to_string())
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let dialog_content = column![
+        title,
+        Space::new().height(12),
+        body,
+        Space::new().height(24),
+        buttons,
+    ]
+    .width(560)
+    .padding(24);
+
+    let dialog_box = container(dialog_content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    // Closing from the backdrop would count as passing on the lyrics
+    let event_blocker = mouse_area(backdrop_content).interaction(Interaction::Idle);
+
+    opaque(event_blocker).into()
+}
+
+/// The track, the credit and the lyrics exactly as they will be published
+fn preview(
+    dialog: &LyricsShareDialog,
+    credit_name: Option<&str>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let submission = &dialog.submission;
+
+    let mut track = vec![submission.track_name.clone()];
+    if !submission.artist_name.is_empty() {
+        track.push(submission.artist_name.clone());
+    }
+    if !submission.album_name.is_empty() {
+        track.push(submission.album_name.clone());
+    }
+    track.push(format!(
+        "{}:{:02}",
+        submission.duration_secs / 60,
+        submission.duration_secs % 60
+    ));
+
+    let credit: Element<'static, Message> = match credit_name {
+        Some(name) => credit_checkbox(
+            submission.credit.is_some(),
+            locale.get(Key::LyricsShareCredit).replace("{}", name),
+        ),
+        None => secondary_text(locale.get(Key::LyricsShareAnonymous).to_string()),
+    };
+
+    let mut content = column![
+        text(track.join(" · ")).size(14).style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        }),
+        secondary_text(locale.get(Key::LyricsShareHint).to_string()),
+        credit,
+    ]
+    .spacing(8);
+
+    let synced = submission.synced_lyrics();
+    let lyrics = if synced.is_empty() {
+        content = content.push(secondary_text(
+            locale.get(Key::LyricsShareUnsynced).to_string(),
+        ));
+        submission.plain_lyrics()
+    } else {
+        synced
+    };
+
+    let lyrics_box = container(
+        scrollable(
+            container(text(lyrics).size(13).style(|theme| text::Style {
+                color: Some(theme::text_secondary(theme)),
+            }))
+            .padding([8, 12]),
+        )
+        .height(320)
+        .width(Fill),
+    )
+    .style(|theme| iced::widget::container::Style {
+        background: Some(iced::Background::Color(theme::hover_bg_alpha(theme, 0.05))),
+        border: iced::Border {
+            radius: 8.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    content.push(lyrics_box).into()
+}
+
+fn credit_checkbox(checked: bool, label: String) -> Element<'static, Message> {
+    checkbox(checked)
+        .label(label)
+        .on_toggle(Message::ToggleLyricsShareCredit)
+        .text_size(13)
+        .spacing(8)
+        .style(|theme, status| {
+            let is_checked = matches!(
+                status,
+                checkbox::Status::Active { is_checked: true }
+                    | checkbox::Status::Hovered { is_checked: true }
+            );
+            checkbox::Style {
+                background: iced::Background::Color(if is_checked {
+                    theme::ACCENT_PINK
+                } else {
+                    theme::hover_bg_alpha(theme, 0.1)
+                }),
+                icon_color: theme::BLACK,
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    width: if is_checked { 0.0 } else { 1.0 },
+                    color: theme::hover_bg_alpha(theme, 0.3),
+                },
+                text_color: Some(theme::text_secondary(theme)),
+            }
+        })
+        .into()
+}
+
+fn secondary_text(label: String) -> Element<'static, Message> {
+    text(label).size(14).color(theme::TEXT_SECONDARY).into()
+}
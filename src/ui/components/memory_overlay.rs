@@ -0,0 +1,109 @@
+//! Memory debug overlay
+//!
+//! Compact panel in the top-right corner with the latest memory sample,
+//! each tracked cache against its soft budget, and per-subsystem counters.
+
+use iced::widget::{Space, column, container, row, text};
+use iced::{Alignment, Element, Fill, Padding};
+
+use crate::app::Message;
+use crate::features::memory::{self, MemoryReport};
+use crate::ui::theme;
+
+const LABEL_WIDTH: f32 = 130.0;
+
+/// Build the overlay; shows a placeholder until the first sample arrives
+pub fn view(report: Option<&MemoryReport>) -> Element<'static, Message> {
+    let mut lines = column![text("Memory").size(13).style(|theme| text::Style {
+        color: Some(theme::text_primary(theme)),
+    })]
+    .spacing(4);
+
+    match report {
+        Some(report) => {
+            let resident = report
+                .resident_bytes
+                .map(|bytes| with_budget(bytes, memory::PROCESS_BUDGET))
+                .unwrap_or_else(|| "n/a".to_string());
+            let atlas = &report.atlas;
+
+            lines = lines
+                .push(line("Process (RSS)", resident))
+                .push(line(
+                    "Pinned covers",
+                    with_budget(report.pinned_image_bytes, memory::PINNED_IMAGE_BUDGET),
+                ))
+                .push(line(
+                    "Glyph atlas",
+                    format!(
+                        "{:.0}% · {} glyphs · {}",
+                        atlas.occupancy() * 100.0,
+                        atlas.glyphs,
+                        mb(atlas.texture_bytes())
+                    ),
+                ))
+                .push(line(
+                    "Pending glyphs",
+                    format!(
+                        "{} · {}",
+                        report.pending_glyphs,
+                        with_budget(report.pending_glyph_bytes, memory::PENDING_GLYPH_BUDGET)
+                    ),
+                ))
+                .push(line(
+                    "SQLite cache (max)",
+                    format!("up to {}", mb(report.db_cache_limit_bytes)),
+                ))
+                .push(Space::new().height(4));
+
+            for (name, count) in &report.counters {
+                lines = lines.push(line(name, count.to_string()));
+            }
+        }
+        None => {
+            lines = lines.push(line("Sampling", "…".to_string()));
+        }
+    }
+
+    let panel = container(lines)
+        .padding(Padding::new(12.0).left(14.0).right(14.0))
+        .style(|theme| container::Style {
+            background: Some(iced::Background::Color(theme::surface_elevated(theme))),
+            border: iced::Border {
+                radius: 8.0.into(),
+                width: 1.0,
+                color: theme::border_color(theme),
+            },
+            ..Default::default()
+        });
+
+    container(panel)
+        .width(Fill)
+        .padding(Padding::new(48.0).right(20.0))
+        .align_x(Alignment::End)
+        .into()
+}
+
+fn line(label: &str, value: String) -> Element<'static, Message> {
+    row![
+        text(label.to_string())
+            .size(12)
+            .width(LABEL_WIDTH)
+            .style(|theme| text::Style {
+                color: Some(theme::text_secondary(theme)),
+            }),
+        text(value).size(12).style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        }),
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
+
+fn with_budget(bytes: u64, budget: u64) -> String {
+    format!("{} / {}", mb(bytes), mb(budget))
+}
+
+fn mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
@@ -0,0 +1,105 @@
+//! External link confirmation dialog component
+
+use iced::mouse::Interaction;
+use iced::widget::{Space, button, column, container, mouse_area, opaque, row, text};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::Message;
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the dialog asking before a link is opened in the browser
+pub fn view(url: &str, animation_progress: f32, locale: Locale) -> Element<'_, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+
+    let title = text(locale.get(Key::OpenLinkTitle).to_string())
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let message = text(locale.get(Key::OpenLinkConfirm).to_string())
+        .size(14)
+        .color(theme::TEXT_SECONDARY);
+
+    let link = text(url).size(13).color(theme::ACCENT);
+
+    let cancel_btn = button(text(locale.get(Key::Cancel).to_string()).size(14))
+        .padding([10, 20])
+        .style(theme::secondary_button)
+        .on_press(Message::CancelOpenLink);
+
+    let open_btn = button(
+        text(locale.get(Key::OpenLinkOpen).to_string())
+            .size(14)
+            .color(Color::WHITE),
+    )
+    .padding([10, 20])
+    .style(theme::primary_button)
+    .on_press(Message::ConfirmOpenLink);
+
+    let buttons = row![Space::new().width(Fill), cancel_btn, open_btn]
+        .spacing(12)
+        .align_y(Alignment::Center);
+
+    let dialog_content = column![
+        title,
+        Space::new().height(12),
+        message,
+        Space::new().height(8),
+        link,
+        Space::new().height(24),
+        buttons,
+    ]
+    .width(420)
+    .padding(24);
+
+    let dialog_box = container(dialog_content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    // Clicking the backdrop cancels
+    let event_blocker = mouse_area(backdrop_content)
+        .interaction(Interaction::Idle)
+        .on_press(Message::CancelOpenLink);
+
+    opaque(event_blocker).into()
+}
@@ -1,19 +1,24 @@
 //! Bottom player bar component
 
-use iced::widget::{Space, button, column, container, image, mouse_area, opaque, row, svg, text};
+use iced::widget::{
+    Row, Space, button, column, container, image, mouse_area, opaque, pick_list, row, stack, svg,
+    text, tooltip,
+};
 use iced::{Alignment, Color, Element, Fill, Length, Padding};
 
-use crate::app::Message;
+use crate::app::{Message, SharedElement};
 use crate::database::DbSong;
-use crate::features::PlayMode;
+use crate::features::{MusicQuality, PlayMode};
+use crate::i18n::{Key, Locale};
 use crate::ui::theme::MEDIUM_WEIGHT;
-use crate::ui::widgets::{self, ControlSize, PlayModeButtonSize, SliderSize};
+use crate::ui::widgets::{self, BoundsSensor, ControlSize, PlayModeButtonSize, SliderSize};
 use crate::ui::{icons, theme};
 
 /// Player bar height
 pub const PLAYER_BAR_HEIGHT: f32 = 80.0;
 
 /// Build the player bar
+#[allow(clippy::too_many_arguments)]
 pub fn view(
     current_song: Option<&DbSong>,
     is_playing: bool,
@@ -22,10 +27,16 @@ pub fn view(
     volume: f32,       // 0.0 to 1.0
     _is_seeking: bool, // Whether user is dragging the slider (reserved for future use)
     play_mode: PlayMode,
-    is_buffering: bool,             // Whether streaming is buffering
-    download_progress: Option<f32>, // Download progress 0.0 to 1.0 (None if not streaming)
-    is_fm_mode: bool,               // Whether in Personal FM mode
-    is_first_song: bool,            // Whether at first song in queue
+    is_buffering: bool,                 // Whether streaming is buffering
+    download_progress: Option<f32>,     // Download progress 0.0 to 1.0 (None if not streaming)
+    is_fm_mode: bool,                   // Whether in Personal FM mode
+    is_first_song: bool,                // Whether at first song in queue
+    has_chapters: bool,                 // Whether the current song has chapter markers
+    skip_secs: (u32, u32),              // Skip back and forward intervals in seconds
+    seek_hover_preview: Option<String>, // Lyric/chapter context under the cursor on the seek bar
+    track_quality: Option<(MusicQuality, Vec<MusicQuality>)>, // Streamed quality and the ones available
+    cover_in_flight: bool, // Whether the cover is flying to or from the lyrics page
+    locale: Locale,
 ) -> Element<'static, Message> {
     // Format time as mm:ss
     let format_time = |secs: f32| -> String {
@@ -103,6 +114,11 @@ pub fn view(
                 .into()
             };
 
+        // Where the cover flies from when the lyrics page opens
+        let cover_content = BoundsSensor::new(cover_content)
+            .on_change(|bounds| Message::SharedElementMoved(SharedElement::PlayerBarCover, bounds))
+            .hidden(cover_in_flight);
+
         let cover_btn = button(cover_content)
             .padding(0)
             .style(|_theme, _status| button::Style {
@@ -159,14 +175,20 @@ pub fn view(
 
         let song_details = column![title_btn, artist_btn].spacing(2);
 
-        row![cover_btn, Space::new().width(12), song_details]
-            .align_y(Alignment::Center)
-            .into()
+        Row::with_children(locale.reading_order(vec![
+            cover_btn.into(),
+            Space::new().width(12).into(),
+            song_details.into(),
+        ]))
+        .align_y(Alignment::Center)
+        .into()
     } else {
         // Show placeholder when no song
         let placeholder = column![
-            text("No song playing").size(14).color(theme::TEXT_MUTED),
-            text("Select a song to play")
+            text(locale.get(Key::PlayerNoSong))
+                .size(14)
+                .color(theme::TEXT_MUTED),
+            text(locale.get(Key::PlayerNoSongHint))
                 .size(12)
                 .color(theme::TEXT_MUTED),
         ]
@@ -213,13 +235,16 @@ pub fn view(
         is_first_song,
     );
 
-    let progress_slider = widgets::progress_slider::view_with_download(
+    // The wheel over the seek bar scrubs instead of changing the volume
+    let progress_slider = mouse_area(widgets::progress_slider::view_with_download(
         position,
         download_progress,
         SliderSize::Standard,
-    );
+        seek_hover_preview,
+    ))
+    .on_scroll(Message::SeekWheel);
 
-    let progress_row = row![
+    let mut progress_row = row![
         text(current_time).size(12).color(theme::TEXT_MUTED),
         Space::new().width(8),
         progress_slider,
@@ -227,6 +252,33 @@ pub fn view(
         text(total_time).size(12).color(theme::TEXT_MUTED),
     ]
     .align_y(Alignment::Center);
+    if let Some((quality, available)) = track_quality {
+        progress_row = progress_row
+            .push(Space::new().width(8))
+            .push(quality_badge(quality, available));
+    }
+
+    // Skip buttons either side of the transport controls
+    let (skip_back_secs, skip_forward_secs) = skip_secs;
+    let has_song = current_song.is_some();
+    let controls = Row::with_children(locale.reading_order(vec![
+        skip_button(
+            icons::SKIP_BACK,
+            skip_back_secs,
+            locale.get(Key::SkipBack),
+            has_song.then_some(Message::SkipBack),
+        ),
+        Space::new().width(8).into(),
+        controls,
+        Space::new().width(8).into(),
+        skip_button(
+            icons::SKIP_FORWARD,
+            skip_forward_secs,
+            locale.get(Key::SkipForward),
+            has_song.then_some(Message::SkipForward),
+        ),
+    ]))
+    .align_y(Alignment::Center);
 
     let center_section = column![controls, Space::new().height(4), progress_row,]
         .align_x(Alignment::Center)
@@ -244,7 +296,33 @@ pub fn view(
 
     // Play mode button (using unified widget with FM mode support)
     let play_mode_btn =
-        widgets::play_mode_button::view(play_mode, PlayModeButtonSize::Small, is_fm_mode);
+        widgets::play_mode_button::view(play_mode, PlayModeButtonSize::Small, is_fm_mode, locale);
+
+    // Block button: never play the current song again
+    let block_btn = button(
+        svg(svg::Handle::from_memory(icons::BLOCK.as_bytes()))
+            .width(16)
+            .height(16)
+            .style(|_theme, _status| svg::Style {
+                color: Some(theme::TEXT_SECONDARY),
+            }),
+    )
+    .padding(8)
+    .style(|theme, status| {
+        let bg = match status {
+            button::Status::Hovered => theme::hover_bg(theme),
+            _ => Color::TRANSPARENT,
+        };
+        button::Style {
+            background: Some(iced::Background::Color(bg)),
+            border: iced::Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+    .on_press_maybe(current_song.map(|_| Message::BlockCurrentSong));
 
     // Queue button
     let queue_btn = button(
@@ -272,23 +350,53 @@ pub fn view(
     })
     .on_press(Message::ToggleQueue);
 
-    let right_section = row![
-        play_mode_btn,
-        Space::new().width(8),
-        volume_icon,
-        Space::new().width(8),
-        volume_slider,
-        Space::new().width(12),
-        queue_btn,
-    ]
-    .align_y(Alignment::Center)
-    .width(Length::Shrink);
+    // Chapter navigation, only for songs with chapter markers
+    let mut right_items: Vec<Element<'static, Message>> = Vec::new();
+    if has_chapters {
+        right_items.extend([
+            chapter_button(
+                icons::CHAPTER_PREV,
+                locale.get(Key::PrevChapter),
+                Message::PrevChapter,
+            ),
+            chapter_button(
+                icons::CHAPTERS,
+                locale.get(Key::ChaptersTitle),
+                Message::ToggleChapters,
+            ),
+            chapter_button(
+                icons::CHAPTER_NEXT,
+                locale.get(Key::NextChapter),
+                Message::NextChapter,
+            ),
+            Space::new().width(8).into(),
+        ]);
+    }
+    right_items.extend([
+        block_btn.into(),
+        Space::new().width(4).into(),
+        play_mode_btn.into(),
+        Space::new().width(8).into(),
+        volume_icon.into(),
+        Space::new().width(8).into(),
+        volume_slider.into(),
+        Space::new().width(12).into(),
+        queue_btn.into(),
+    ]);
 
-    // Combine all sections
-    let content = row![left_section, center_section, right_section,]
-        .spacing(16)
+    let right_section = Row::with_children(locale.reading_order(right_items))
         .align_y(Alignment::Center)
-        .padding(Padding::new(12.0).left(16.0).right(16.0));
+        .width(Length::Shrink);
+
+    // Combine all sections, mirrored for right-to-left languages
+    let content = Row::with_children(locale.reading_order(vec![
+        left_section.into(),
+        center_section.into(),
+        right_section.into(),
+    ]))
+    .spacing(16)
+    .align_y(Alignment::Center)
+    .padding(Padding::new(12.0).left(16.0).right(16.0));
 
     // Top border line
     let top_border = container(Space::new().height(0))
@@ -317,7 +425,145 @@ pub fn view(
         .on_release(Message::Noop)
         .on_enter(Message::Noop)
         .on_exit(Message::Noop)
-        .on_move(|_| Message::Noop);
+        .on_move(|_| Message::Noop)
+        .on_right_press(Message::TogglePlayerMenu)
+        .on_scroll(Message::VolumeWheel);
 
     opaque(event_blocker).into()
 }
+
+/// Small icon button with a tooltip for chapter navigation
+fn chapter_button(
+    icon: &'static str,
+    label: &'static str,
+    message: Message,
+) -> Element<'static, Message> {
+    tooltip(
+        button(
+            svg(svg::Handle::from_memory(icon.as_bytes()))
+                .width(16)
+                .height(16)
+                .style(|_theme, _status| svg::Style {
+                    color: Some(theme::TEXT_SECONDARY),
+                }),
+        )
+        .padding(8)
+        .style(|theme, status| {
+            let bg = match status {
+                button::Status::Hovered => theme::hover_bg(theme),
+                _ => Color::TRANSPARENT,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(bg)),
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .on_press(message),
+        text(label).size(12),
+        tooltip::Position::Top,
+    )
+    .gap(4)
+    .style(|theme| iced::widget::container::Style {
+        background: Some(iced::Background::Color(theme::surface_container(theme))),
+        border: iced::Border {
+            radius: 4.0.into(),
+            color: theme::divider(theme),
+            width: 1.0,
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Skip button with the interval drawn inside the arrow
+fn skip_button(
+    icon: &'static str,
+    secs: u32,
+    label: &'static str,
+    message: Option<Message>,
+) -> Element<'static, Message> {
+    let icon = stack![
+        svg(svg::Handle::from_memory(icon.as_bytes()))
+            .width(22)
+            .height(22)
+            .style(|_theme, _status| svg::Style {
+                color: Some(theme::TEXT_SECONDARY),
+            }),
+        container(text(secs.to_string()).size(7).color(theme::TEXT_SECONDARY))
+            .center_x(22)
+            .center_y(22)
+            .padding(Padding::new(0.0).top(3.0)),
+    ];
+
+    tooltip(
+        button(icon)
+            .padding(4)
+            .style(|theme, status| {
+                let bg = match status {
+                    button::Status::Hovered => theme::hover_bg(theme),
+                    _ => Color::TRANSPARENT,
+                };
+                button::Style {
+                    background: Some(iced::Background::Color(bg)),
+                    border: iced::Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            })
+            .on_press_maybe(message),
+        text(label.replace("{}", &secs.to_string())).size(12),
+        tooltip::Position::Top,
+    )
+    .gap(4)
+    .style(|theme| iced::widget::container::Style {
+        background: Some(iced::Background::Color(theme::surface_container(theme))),
+        border: iced::Border {
+            radius: 4.0.into(),
+            color: theme::divider(theme),
+            width: 1.0,
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Badge with the quality the song streams at; opens the qualities the
+/// track is available in so this play can switch
+fn quality_badge(quality: MusicQuality, available: Vec<MusicQuality>) -> Element<'static, Message> {
+    let high_res = matches!(quality, MusicQuality::Lossless | MusicQuality::HiRes);
+    let options: Vec<&'static str> = available.iter().map(MusicQuality::badge).collect();
+    pick_list(options, Some(quality.badge()), move |badge| {
+        available
+            .iter()
+            .find(|q| q.badge() == badge)
+            .map_or(Message::NoOp, |q| Message::SelectTrackQuality(*q))
+    })
+    .text_size(11)
+    .padding([2, 6])
+    .handle(pick_list::Handle::None)
+    .style(move |iced_theme, status| {
+        let color = if high_res {
+            theme::ACCENT_PINK
+        } else {
+            theme::TEXT_MUTED
+        };
+        let base = theme::settings_pick_list(iced_theme, status);
+        pick_list::Style {
+            text_color: color,
+            border: iced::Border {
+                radius: 4.0.into(),
+                width: 1.0,
+                color,
+            },
+            ..base
+        }
+    })
+    .menu_style(theme::settings_pick_list_menu)
+    .into()
+}
@@ -0,0 +1,118 @@
+//! Player context menu component
+//!
+//! Opened by right-clicking the player bar; holds actions on the song that
+//! is playing. Drawn above the player bar at the end of the window, like the
+//! queue popup.
+
+use iced::widget::{Space, button, column, container, mouse_area, opaque, row, svg, text};
+use iced::{Alignment, Element, Fill, Padding};
+
+use crate::app::Message;
+use crate::i18n::{Key, Locale};
+use crate::ui::components::PLAYER_BAR_HEIGHT;
+use crate::ui::{icons, theme};
+
+const WIDTH: f32 = 240.0;
+
+/// Build the menu overlay over the whole content area
+pub fn view(stop_after_current: bool, locale: Locale) -> Element<'static, Message> {
+    let items = column![
+        item(
+            icons::STOP_AFTER,
+            locale.get(Key::StopAfterCurrent),
+            stop_after_current,
+            Message::ToggleStopAfterCurrent,
+        ),
+        item(
+            icons::BLOCK,
+            locale.get(Key::BlockCurrentSong),
+            false,
+            Message::BlockCurrentSong,
+        ),
+    ]
+    .spacing(2);
+
+    let panel = container(items)
+        .width(WIDTH)
+        .padding(6)
+        .style(|theme| container::Style {
+            background: Some(iced::Background::Color(theme::surface_container(theme))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: theme::divider(theme),
+            },
+            shadow: iced::Shadow {
+                color: iced::Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                offset: iced::Vector::new(0.0, 4.0),
+                blur_radius: 16.0,
+            },
+            ..Default::default()
+        });
+
+    let padding = if locale.is_rtl() {
+        Padding::new(0.0).left(20.0).bottom(8.0)
+    } else {
+        Padding::new(0.0).right(20.0).bottom(8.0)
+    };
+
+    // Clicking anywhere outside the panel closes it
+    mouse_area(
+        column![
+            Space::new().height(Fill),
+            container(opaque(panel))
+                .width(Fill)
+                .align_x(locale.end())
+                .padding(padding),
+            Space::new().height(PLAYER_BAR_HEIGHT),
+        ]
+        .width(Fill)
+        .height(Fill),
+    )
+    .on_press(Message::ClosePlayerMenu)
+    .on_right_press(Message::ClosePlayerMenu)
+    .into()
+}
+
+/// A menu entry; `checked` entries show a check mark at the end
+fn item(
+    icon: &'static str,
+    label: &'static str,
+    checked: bool,
+    message: Message,
+) -> Element<'static, Message> {
+    let check: Element<'static, Message> = if checked {
+        svg(svg::Handle::from_memory(icons::CHECK.as_bytes()))
+            .width(14)
+            .height(14)
+            .style(|_theme, _status| svg::Style {
+                color: Some(theme::ACCENT_PINK),
+            })
+            .into()
+    } else {
+        Space::new().width(14).into()
+    };
+
+    button(
+        row![
+            svg(svg::Handle::from_memory(icon.as_bytes()))
+                .width(16)
+                .height(16)
+                .style(|theme, _status| svg::Style {
+                    color: Some(theme::text_secondary(theme)),
+                }),
+            Space::new().width(10),
+            text(label).size(13).style(|theme| text::Style {
+                color: Some(theme::text_primary(theme)),
+            }),
+            Space::new().width(Fill),
+            check,
+        ]
+        .align_y(Alignment::Center),
+    )
+    .width(Fill)
+    .padding([8, 10])
+    .style(theme::nav_item)
+    .on_press(message)
+    .into()
+}
@@ -5,12 +5,13 @@
 use std::collections::HashMap;
 
 use iced::widget::{Space, column, container, image, row, text};
-use iced::{Color, Element, Fill};
+use iced::{Color, Element, Fill, Rectangle, Size};
 
-use crate::api::SongList;
+use crate::api::{AlbumRelease, SongList};
 use crate::app::Message;
+use crate::features::lazy_covers::CoverKey;
 use crate::ui::animation::HoverAnimations;
-use crate::ui::widgets::playlist_card;
+use crate::ui::widgets::{BoundsSensor, lazy_cover, playlist_card};
 
 /// Grid configuration
 const CARD_WIDTH: f32 = 160.0;
@@ -106,7 +107,7 @@ pub fn view<'a>(
                 )
             } else {
                 let cover_handle = covers.get(&playlist.id);
-                playlist_card::view(
+                let card = playlist_card::view(
                     &playlist.name,
                     &playlist.author,
                     cover_handle,
@@ -115,7 +116,27 @@ pub fn view<'a>(
                     Message::PlayDiscoverPlaylist(playlist.id),
                     Message::HoverDiscoverPlaylist(Some(playlist.id)),
                     Message::HoverDiscoverPlaylist(None),
-                )
+                );
+                // Fetch the cover once the card is about to be seen
+                if let Some(handle) = cover_handle {
+                    // The cover is the top square of the card, and flies into
+                    // the playlist header when opened
+                    BoundsSensor::new(card)
+                        .on_press(move |card| {
+                            let cover =
+                                Rectangle::new(card.position(), Size::new(card.width, card.width));
+                            Message::SharedCoverPressed(handle.clone(), cover)
+                        })
+                        .into()
+                } else {
+                    let key = CoverKey::Playlist(playlist.id);
+                    lazy_cover::view(
+                        card,
+                        playlist.id,
+                        Message::CoverShown(key, playlist.cover_img_url.clone()),
+                        Message::CoverHidden(key),
+                    )
+                }
             };
 
             row_items.push(card);
@@ -149,3 +170,33 @@ pub fn view<'a>(
 
     column(content).into()
 }
+
+/// A single row of album release cards, as many as fit the width
+pub fn releases_row<'a>(
+    releases: &'a [AlbumRelease],
+    covers: &'a HashMap<u64, image::Handle>,
+    animations: &'a HoverAnimations<u64>,
+    container_width: f32,
+) -> Element<'a, Message> {
+    let columns = calculate_columns(container_width);
+    let mut row_items: Vec<Element<'a, Message>> = Vec::new();
+
+    for (i, album) in releases.iter().take(columns).enumerate() {
+        if i > 0 {
+            row_items.push(Space::new().width(CARD_SPACING).into());
+        }
+        // There is no album page yet, so opening a release plays it too
+        row_items.push(playlist_card::view(
+            &album.name,
+            &album.artist_name,
+            covers.get(&album.id),
+            animations.get_progress(&album.id),
+            Message::PlayNewRelease(album.id),
+            Message::PlayNewRelease(album.id),
+            Message::HoverDiscoverPlaylist(Some(album.id)),
+            Message::HoverDiscoverPlaylist(None),
+        ));
+    }
+
+    row(row_items).into()
+}
@@ -7,6 +7,7 @@
 //! - Pre-computed display strings (no format! in render loop)
 //! - Pre-loaded image handles (no disk IO in render loop)
 //! - Cached SVG handles (no repeated parsing)
+//! - Songs are borrowed from the playlist, never cloned per frame
 
 use std::cell::RefCell;
 use std::collections::HashSet;
@@ -17,14 +18,22 @@ use iced::widget::{Space, button, column, container, image, row, svg, text};
 use iced::{Alignment, Color, Element, Fill, Length, Padding};
 
 use crate::app::Message;
+use crate::database::SongId;
+use crate::features::blocklist::Blocklist;
+use crate::features::song_analysis::SongAnalysis;
+use crate::features::song_source::SongSource;
 use crate::i18n::{Key, Locale};
 use crate::ui::theme::BOLD_WEIGHT;
-use crate::ui::widgets::{VirtualList, VirtualListState};
+use crate::ui::widgets::{VirtualList, VirtualListState, skeleton};
 use crate::ui::{icons, theme};
 
 /// Song row height constant for virtual list
 pub const SONG_ROW_HEIGHT: f32 = 62.0;
 
+/// Rows built beyond the viewport; covers for these are requested early so
+/// they are usually loaded before the row scrolls into view
+const LIST_OVERSCAN: usize = 8;
+
 /// Pre-cached SVG handles to avoid repeated parsing in render loop
 static PLAY_ICON_HANDLE: LazyLock<svg::Handle> =
     LazyLock::new(|| svg::Handle::from_memory(icons::PLAY.as_bytes()));
@@ -37,6 +46,10 @@ static HEART_OUTLINE_ICON_HANDLE: LazyLock<svg::Handle> =
 static CLOCK_ICON_HANDLE: LazyLock<svg::Handle> =
     LazyLock::new(|| svg::Handle::from_memory(icons::CLOCK.as_bytes()));
 
+/// Widths of the tempo and key columns
+const TEMPO_WIDTH: f32 = 56.0;
+const KEY_WIDTH: f32 = 80.0;
+
 /// Maximum lengths for display text truncation
 const MAX_TITLE_LEN: usize = 28;
 const MAX_ARTIST_LEN: usize = 25;
@@ -45,7 +58,7 @@ const MAX_ARTIST_LEN: usize = 25;
 /// All display strings and image handles are pre-computed for performance
 #[derive(Debug, Clone)]
 pub struct SongItem {
-    pub id: i64,
+    pub id: SongId,
     /// Pre-formatted index string to avoid format! in render loop
     pub index_str: String,
     /// Original title for search/filter
@@ -68,12 +81,26 @@ pub struct SongItem {
     pub pic_url: Option<String>,
     /// Pre-loaded image handle (None = use placeholder)
     pub cover_handle: Option<image::Handle>,
+    /// Where the song plays from, checked when the row is created
+    pub source: SongSource,
+    /// Analyzed tempo, for sorting
+    pub bpm: Option<f32>,
+    /// Pre-formatted tempo, empty until analyzed
+    pub tempo: String,
+    /// Pre-formatted key with its Camelot code, empty until analyzed
+    pub key: String,
+    /// Length in seconds, for sorting NCM playlists
+    pub duration_secs: u64,
+    /// NCM popularity from 0 to 100
+    pub popularity: f32,
+    /// Whether NCM has a Hi-Res version
+    pub hires: bool,
 }
 
 impl SongItem {
     /// Create a new SongItem with pre-computed display values
     pub fn new(
-        id: i64,
+        id: SongId,
         index: usize,
         title: String,
         artist: String,
@@ -81,15 +108,16 @@ impl SongItem {
         duration: String,
         added_date: String,
         cover_path: Option<String>,
+        source: SongSource,
     ) -> Self {
         Self::with_pic_url(
-            id, index, title, artist, album, duration, added_date, cover_path, None,
+            id, index, title, artist, album, duration, added_date, cover_path, None, source,
         )
     }
 
     /// Create a new SongItem with pic_url for lazy loading
     pub fn with_pic_url(
-        id: i64,
+        id: SongId,
         index: usize,
         title: String,
         artist: String,
@@ -98,6 +126,7 @@ impl SongItem {
         added_date: String,
         cover_path: Option<String>,
         pic_url: Option<String>,
+        source: SongSource,
     ) -> Self {
         // Pre-compute display strings
         let display_title = truncate_string(&title, MAX_TITLE_LEN);
@@ -128,8 +157,46 @@ impl SongItem {
             cover_path,
             pic_url,
             cover_handle,
+            source,
+            bpm: None,
+            tempo: String::new(),
+            key: String::new(),
+            duration_secs: 0,
+            popularity: 0.0,
+            hires: false,
         }
     }
+
+    /// Add the NCM details the playlist page sorts and filters by
+    pub fn with_ncm_details(mut self, duration_secs: u64, popularity: f32, hires: bool) -> Self {
+        self.duration_secs = duration_secs;
+        self.popularity = popularity;
+        self.hires = hires;
+        self
+    }
+
+    /// Show new title, artist and album, e.g. after a song override
+    pub fn set_tags(&mut self, title: String, artist: String, album: String) {
+        self.display_title = truncate_string(&title, MAX_TITLE_LEN);
+        self.display_artist = truncate_string(&artist, MAX_ARTIST_LEN);
+        self.display_album = album.clone();
+        self.title = title;
+        self.artist = artist;
+        self.album = album;
+    }
+
+    /// Fill in the tempo and key columns from an analysis
+    pub fn set_analysis(&mut self, analysis: Option<&SongAnalysis>) {
+        self.bpm = analysis.and_then(|a| a.bpm);
+        self.tempo = self
+            .bpm
+            .map(|bpm| format!("{:.0}", bpm))
+            .unwrap_or_default();
+        self.key = analysis
+            .and_then(|a| a.key)
+            .map(|key| format!("{} · {}", key.name(), key.camelot_code()))
+            .unwrap_or_default();
+    }
 }
 
 /// Truncate string with ellipsis if too long
@@ -148,12 +215,14 @@ fn truncate_string(s: &str, max_len: usize) -> String {
 /// Configuration for playlist view columns
 #[derive(Debug, Clone, Copy)]
 pub struct PlaylistColumns {
-    /// Show the like button column (for online playlists)
+    /// Show the like button on hover
     pub show_like: bool,
     /// Show the added date column (for local playlists)
     pub show_added_date: bool,
     /// Show album column
     pub show_album: bool,
+    /// Show the analyzed tempo and key columns (for local songs)
+    pub show_tempo: bool,
 }
 
 impl Default for PlaylistColumns {
@@ -162,17 +231,19 @@ impl Default for PlaylistColumns {
             show_like: true,
             show_added_date: false,
             show_album: true,
+            show_tempo: false,
         }
     }
 }
 
 impl PlaylistColumns {
-    /// Configuration for local playlists (with added date, no like button)
+    /// Configuration for local playlists (with added date, tempo and like button)
     pub fn local() -> Self {
         Self {
-            show_like: false,
+            show_like: true,
             show_added_date: true,
             show_album: true,
+            show_tempo: true,
         }
     }
 
@@ -182,6 +253,7 @@ impl PlaylistColumns {
             show_like: true,
             show_added_date: false,
             show_album: true,
+            show_tempo: false,
         }
     }
 
@@ -192,12 +264,18 @@ impl PlaylistColumns {
             show_like: false,
             show_added_date: false,
             show_album: false,
+            show_tempo: false,
         }
     }
 }
 
-/// Build the song list header row
-pub fn build_header(locale: Locale, columns: PlaylistColumns) -> Element<'static, Message> {
+/// Build the song list header row; the tempo header sorts by tempo, with
+/// `tempo_sort` the current order (ascending when `Some(true)`)
+pub fn build_header(
+    locale: Locale,
+    columns: PlaylistColumns,
+    tempo_sort: Option<bool>,
+) -> Element<'static, Message> {
     let mut header_items: Vec<Element<'static, Message>> = vec![
         container(
             text(locale.get(Key::PlaylistHeaderNumber))
@@ -234,6 +312,39 @@ pub fn build_header(locale: Locale, columns: PlaylistColumns) -> Element<'static
         );
     }
 
+    if columns.show_tempo {
+        let arrow = match tempo_sort {
+            Some(true) => " ↑",
+            Some(false) => " ↓",
+            None => "",
+        };
+        header_items.push(
+            button(
+                text(format!("{}{}", locale.get(Key::PlaylistHeaderTempo), arrow))
+                    .size(13)
+                    .style(|theme| text::Style {
+                        color: Some(theme::header_text(theme)),
+                    }),
+            )
+            .padding(0)
+            .style(theme::transparent_btn)
+            .on_press(Message::SortPlaylistByTempo)
+            .width(TEMPO_WIDTH)
+            .into(),
+        );
+        header_items.push(
+            container(
+                text(locale.get(Key::PlaylistHeaderKey))
+                    .size(13)
+                    .style(|theme| text::Style {
+                        color: Some(theme::header_text(theme)),
+                    }),
+            )
+            .width(KEY_WIDTH)
+            .into(),
+        );
+    }
+
     if columns.show_added_date {
         header_items.push(
             container(
@@ -284,21 +395,98 @@ pub fn build_header(locale: Locale, columns: PlaylistColumns) -> Element<'static
         .into()
 }
 
+/// Placeholder rows while a playlist's first songs are loading
+const SKELETON_ROWS: usize = 12;
+
+/// Build shimmering placeholder rows laid out like the song list
+pub fn build_skeleton_list(columns: PlaylistColumns, phase: f32) -> Element<'static, Message> {
+    let rows = (0..SKELETON_ROWS).map(|i| {
+        // Each row trails the one above, so the shimmer runs down the list
+        let phase = skeleton::delayed(phase, i as f32 * 0.04);
+        // Vary title widths so the rows don't look stamped out
+        let title_width = [180.0, 240.0, 150.0, 210.0][i % 4];
+
+        let mut items: Vec<Element<'static, Message>> = vec![
+            container(skeleton::block(16, 12, 3.0, phase))
+                .width(48)
+                .center_x(48)
+                .into(),
+            skeleton::block(44, 44, 4.0, phase),
+            Space::new().width(14).into(),
+            column![
+                skeleton::block(title_width, 14, 3.0, phase),
+                skeleton::block(title_width * 0.6, 12, 3.0, phase),
+            ]
+            .spacing(8)
+            .into(),
+            Space::new().width(Fill).into(),
+        ];
+        if columns.show_album {
+            items.push(
+                container(skeleton::block(140, 12, 3.0, phase))
+                    .width(200)
+                    .into(),
+            );
+        }
+        if columns.show_tempo {
+            items.push(
+                container(skeleton::block(28, 12, 3.0, phase))
+                    .width(TEMPO_WIDTH)
+                    .into(),
+            );
+            items.push(
+                container(skeleton::block(48, 12, 3.0, phase))
+                    .width(KEY_WIDTH)
+                    .into(),
+            );
+        }
+        if columns.show_added_date {
+            items.push(
+                container(skeleton::block(60, 12, 3.0, phase))
+                    .width(90)
+                    .into(),
+            );
+        }
+        items.push(
+            container(skeleton::block(32, 12, 3.0, phase))
+                .width(50)
+                .center_x(50)
+                .into(),
+        );
+
+        container(row(items).align_y(Alignment::Center))
+            .height(SONG_ROW_HEIGHT)
+            .center_y(SONG_ROW_HEIGHT)
+            .padding(Padding::new(0.0).left(20.0).right(24.0))
+            .into()
+    });
+
+    column(rows).width(Fill).into()
+}
+
 /// Build the virtual song list
+#[allow(clippy::too_many_arguments)]
 pub fn build_list<'a>(
-    songs: Vec<SongItem>,
-    song_animations: &'a crate::ui::animation::HoverAnimations<i64>,
-    liked_songs: HashSet<u64>,
+    songs: SongRows<'a>,
+    song_animations: &'a crate::ui::animation::HoverAnimations<SongId>,
+    liked_songs: HashSet<SongId>,
+    blocklist: &'a Blocklist,
     columns: PlaylistColumns,
     scroll_state: Rc<RefCell<VirtualListState>>,
-    current_playing_id: Option<i64>,
+    current_playing_id: Option<SongId>,
+    accent: Option<Color>,
+    locale: Locale,
 ) -> Element<'a, Message> {
     let song_count = songs.len();
 
     if song_count == 0 {
-        return container(text("暂无歌曲").size(14).style(|theme| text::Style {
-            color: Some(theme::dimmed_text(theme)),
-        }))
+        return container(
+            text(locale.get(Key::PlaylistNoSongs))
+                .size(14)
+                .style(|theme| text::Style {
+                    color: Some(theme::dimmed_text(theme)),
+                }),
+        )
         .width(Fill)
         .padding(Padding::new(32.0))
         .center_x(Fill)
@@ -314,32 +502,39 @@ pub fn build_list<'a>(
     let songs_clone = songs.clone();
     let liked_songs_clone = liked_songs.clone();
     let item_builder = move |index: usize| -> Element<'a, Message> {
-        if index >= songs_clone.len() {
+        let Some(song) = songs_clone.get(index) else {
             return Space::new().height(SONG_ROW_HEIGHT).into();
-        }
+        };
 
-        let song = &songs_clone[index];
         let is_playing = current_playing_id == Some(song.id);
+        let is_blocked = blocklist.blocks(song.id, &song.artist);
         let animation_progress = song_animations.get_progress(&song.id);
         let is_hovered = animation_progress > 0.5;
 
         container(build_song_row(
             song,
             is_playing,
+            is_blocked,
             is_hovered,
             animation_progress,
             &liked_songs_clone,
             columns,
+            accent,
+            locale,
         ))
         .padding(Padding::new(1.0).left(12.0).right(12.0))
         .into()
     };
 
-    VirtualList::new(song_count, SONG_ROW_HEIGHT, item_builder)
-        .state(scroll_state)
+    let mut list = VirtualList::new(song_count, SONG_ROW_HEIGHT, item_builder);
+    if let Some(accent) = accent {
+        list = list.scrollbar_color(accent);
+    }
+    list.state(scroll_state)
         .width(Length::Fill)
         .height(Length::Fill)
         .spacing(0.0)
+        .overscan(LIST_OVERSCAN)
         .on_empty_area(Message::HoverSong(None))
         .on_item_hover(move |index| {
             let song_id = songs_for_hover.get(index).map(|s| s.id);
@@ -348,17 +543,44 @@ pub fn build_list<'a>(
         .into()
 }
 
+/// Small badge in the source's color saying where a song plays from
+pub fn source_badge(source: SongSource, locale: Locale) -> Element<'static, Message> {
+    let (label, color) = match source {
+        SongSource::Local => (Key::SongSourceLocal, Color::from_rgb8(0x1d, 0xb9, 0x54)),
+        SongSource::Cached => (Key::SongSourceCached, theme::ACCENT),
+        SongSource::Ncm => (Key::SongSourceNcm, theme::ACCENT_PINK),
+    };
+    container(text(locale.get(label)).size(10).color(color))
+        .padding([0, 4])
+        .style(move |_theme| container::Style {
+            border: iced::Border {
+                radius: 3.0.into(),
+                width: 1.0,
+                color,
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
 /// Build a single song row with hover effect
 /// Optimized: No disk IO, no string allocations, uses pre-cached handles
+/// Blocked songs keep their row but are drawn dimmed; the playing song is
+/// drawn in `accent` when the page is tinted by its cover.
+#[allow(clippy::too_many_arguments)]
 fn build_song_row(
     song: &SongItem,
     is_playing: bool,
+    is_blocked: bool,
     is_hovered: bool,
     animation_progress: f32,
-    liked_songs: &HashSet<u64>,
+    liked_songs: &HashSet<SongId>,
     columns: PlaylistColumns,
+    accent: Option<Color>,
+    locale: Locale,
 ) -> Element<'static, Message> {
     let song_id = song.id;
+    let playing_color = accent.unwrap_or(theme::ACCENT_PINK);
 
     // Clone strings for 'static lifetime (these are pre-computed, so cheap)
     let index_str = song.index_str.clone();
@@ -381,8 +603,8 @@ fn build_song_row(
         svg(PLAY_ICON_HANDLE.clone())
             .width(16)
             .height(16)
-            .style(|_theme, _status| svg::Style {
-                color: Some(theme::ACCENT_PINK),
+            .style(move |_theme, _status| svg::Style {
+                color: Some(playing_color),
             })
             .into()
     } else {
@@ -403,7 +625,9 @@ fn build_song_row(
             .size(15)
             .style(move |theme| text::Style {
                 color: Some(if is_playing {
-                    theme::ACCENT_PINK
+                    playing_color
+                } else if is_blocked {
+                    theme::dimmed_text(theme)
                 } else {
                     theme::text_primary(theme)
                 })
@@ -412,21 +636,25 @@ fn build_song_row(
                 weight: BOLD_WEIGHT,
                 ..Default::default()
             }),
-        text(display_artist)
-            .size(13)
-            .style(move |theme| text::Style {
-                color: Some(theme::animated_text(theme, animation_progress))
-            }),
+        row![
+            source_badge(song.source, locale),
+            text(display_artist)
+                .size(13)
+                .style(move |theme| text::Style {
+                    color: Some(if is_blocked {
+                        theme::dimmed_text(theme)
+                    } else {
+                        theme::animated_text(theme, animation_progress)
+                    })
+                }),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center),
     ]
     .spacing(3);
 
     // --- Like button handling ---
-    let ncm_song_id = if song_id < 0 {
-        (-song_id) as u64
-    } else {
-        song_id as u64
-    };
-    let is_liked = liked_songs.contains(&ncm_song_id);
+    let is_liked = liked_songs.contains(&song_id);
 
     // Duration or like button (use cached SVG handles)
     let duration_or_like: Element<'static, Message> = if columns.show_like && is_hovered {
@@ -452,7 +680,7 @@ fn build_song_row(
             background: None,
             ..Default::default()
         })
-        .on_press(Message::ToggleFavorite(ncm_song_id))
+        .on_press(Message::ToggleFavorite(song_id))
         .into()
     } else {
         text(duration)
@@ -476,13 +704,34 @@ fn build_song_row(
             text(display_album)
                 .size(14)
                 .style(move |theme| text::Style {
-                    color: Some(theme::animated_text(theme, animation_progress)),
+                    color: Some(if is_blocked {
+                        theme::dimmed_text(theme)
+                    } else {
+                        theme::animated_text(theme, animation_progress)
+                    }),
                 })
                 .width(200)
                 .into(),
         );
     }
 
+    if columns.show_tempo {
+        for (value, width) in [
+            (song.tempo.clone(), TEMPO_WIDTH),
+            (song.key.clone(), KEY_WIDTH),
+        ] {
+            row_items.push(
+                text(value)
+                    .size(14)
+                    .style(move |theme| text::Style {
+                        color: Some(theme::animated_text(theme, animation_progress)),
+                    })
+                    .width(width)
+                    .into(),
+            );
+        }
+    }
+
     if columns.show_added_date {
         row_items.push(
             text(added_date)
@@ -508,6 +757,8 @@ fn build_song_row(
         .style(move |theme, _status| {
             let bg_color = if animation_progress > 0.001 {
                 theme::hover_bg_alpha(theme, 0.12 * animation_progress)
+            } else if let Some(accent) = accent.filter(|_| is_playing) {
+                Color { a: 0.1, ..accent }
             } else {
                 Color::TRANSPARENT
             };
@@ -564,20 +815,175 @@ fn build_song_cover(cover_handle: &Option<image::Handle>) -> Element<'static, Me
     .into()
 }
 
-/// Filter songs by search query (title, artist, album)
-pub fn filter_songs(songs: &[SongItem], query: &str) -> Vec<SongItem> {
-    if query.is_empty() {
-        return songs.to_vec();
+/// Order of the rows of an NCM playlist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaylistSort {
+    /// Playlist order
+    #[default]
+    Default,
+    Title,
+    Artist,
+    /// Shortest first
+    Duration,
+    /// Most popular first
+    Popularity,
+}
+
+impl PlaylistSort {
+    pub const ALL: [Self; 5] = [
+        Self::Default,
+        Self::Title,
+        Self::Artist,
+        Self::Duration,
+        Self::Popularity,
+    ];
+
+    pub fn label_key(self) -> Key {
+        match self {
+            Self::Default => Key::PlaylistSortDefault,
+            Self::Title => Key::PlaylistSortTitle,
+            Self::Artist => Key::PlaylistSortArtist,
+            Self::Duration => Key::PlaylistSortDuration,
+            Self::Popularity => Key::PlaylistSortPopularity,
+        }
     }
+}
 
-    let query_lower = query.to_lowercase();
-    songs
-        .iter()
-        .filter(|song| {
-            song.title.to_lowercase().contains(&query_lower)
-                || song.artist.to_lowercase().contains(&query_lower)
-                || song.album.to_lowercase().contains(&query_lower)
-        })
-        .cloned()
-        .collect()
+/// Which rows of an NCM playlist are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PlaylistFilter {
+    /// Only songs whose audio is in the song cache
+    pub only_cached: bool,
+    /// Only songs with a Hi-Res version
+    pub only_hires: bool,
+}
+
+impl PlaylistFilter {
+    fn keeps(self, song: &SongItem) -> bool {
+        (!self.only_cached || song.source == SongSource::Cached) && (!self.only_hires || song.hires)
+    }
+}
+
+/// Songs shown in the list: the whole playlist or the rows matching a search,
+/// filtered and sorted
+///
+/// Borrows the playlist instead of cloning it so that building the view stays
+/// cheap for playlists with thousands of songs.
+pub struct SongRows<'a> {
+    songs: &'a [SongItem],
+    /// Indices into `songs` when filtered or sorted
+    matches: Option<Vec<usize>>,
+}
+
+impl<'a> SongRows<'a> {
+    /// Filter songs by search query (title, artist, album)
+    pub fn filter(songs: &'a [SongItem], query: &str) -> Self {
+        if query.is_empty() {
+            return Self {
+                songs,
+                matches: None,
+            };
+        }
+
+        let query_lower = query.to_lowercase();
+        let matches = songs
+            .iter()
+            .enumerate()
+            .filter(|(_, song)| {
+                song.title.to_lowercase().contains(&query_lower)
+                    || song.artist.to_lowercase().contains(&query_lower)
+                    || song.album.to_lowercase().contains(&query_lower)
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        Self {
+            songs,
+            matches: Some(matches),
+        }
+    }
+
+    /// Keep only the rows `filter` lets through
+    pub fn keep(mut self, filter: PlaylistFilter) -> Self {
+        if filter == PlaylistFilter::default() {
+            return self;
+        }
+        let songs = self.songs;
+        let rows = self
+            .matches
+            .take()
+            .unwrap_or_else(|| (0..songs.len()).collect())
+            .into_iter()
+            .filter(|&i| filter.keeps(&songs[i]))
+            .collect();
+        self.matches = Some(rows);
+        self
+    }
+
+    /// Order the rows by `sort`; ties keep playlist order
+    pub fn sort(mut self, sort: PlaylistSort) -> Self {
+        if sort == PlaylistSort::Default {
+            return self;
+        }
+        let songs = self.songs;
+        let mut rows = self
+            .matches
+            .take()
+            .unwrap_or_else(|| (0..songs.len()).collect());
+        match sort {
+            PlaylistSort::Default => {}
+            PlaylistSort::Title => rows.sort_by_cached_key(|&i| songs[i].title.to_lowercase()),
+            PlaylistSort::Artist => rows.sort_by_cached_key(|&i| songs[i].artist.to_lowercase()),
+            PlaylistSort::Duration => rows.sort_by_key(|&i| songs[i].duration_secs),
+            PlaylistSort::Popularity => {
+                rows.sort_by(|&a, &b| songs[b].popularity.total_cmp(&songs[a].popularity))
+            }
+        }
+        self.matches = Some(rows);
+        self
+    }
+
+    /// Order the rows by tempo, ascending when `Some(true)`; songs without
+    /// a tempo go last, and `None` keeps playlist order
+    pub fn sort_by_tempo(mut self, order: Option<bool>) -> Self {
+        let Some(ascending) = order else {
+            return self;
+        };
+        let songs = self.songs;
+        let mut rows = self
+            .matches
+            .take()
+            .unwrap_or_else(|| (0..songs.len()).collect());
+        rows.sort_by(|&a, &b| match (songs[a].bpm, songs[b].bpm) {
+            (Some(a), Some(b)) if ascending => a.total_cmp(&b),
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (a, b) => a.is_none().cmp(&b.is_none()),
+        });
+        self.matches = Some(rows);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.matches {
+            Some(matches) => matches.len(),
+            None => self.songs.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Song shown at list row `row`
+    pub fn get(&self, row: usize) -> Option<&'a SongItem> {
+        match &self.matches {
+            Some(matches) => matches.get(row).and_then(|&i| self.songs.get(i)),
+            None => self.songs.get(row),
+        }
+    }
+
+    /// List row showing the song with `id`
+    pub fn position(&self, id: SongId) -> Option<usize> {
+        (0..self.len()).find(|&row| self.get(row).is_some_and(|song| song.id == id))
+    }
 }
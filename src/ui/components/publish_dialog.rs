@@ -0,0 +1,257 @@
+//! Dialog for publishing a local playlist to NCM
+
+use iced::mouse::Interaction;
+use iced::widget::{
+    Column, Space, button, column, container, mouse_area, opaque, row, scrollable, text,
+};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::{Message, PublishDialog, PublishStage};
+use crate::features::ncm_publish::{self, PublishPlan, TrackMatch};
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the publish dialog for its current step
+pub fn view(
+    dialog: &PublishDialog,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+    let plan = &dialog.plan;
+
+    let body: Element<'static, Message> = match &dialog.stage {
+        PublishStage::Matching => secondary_text(
+            locale
+                .get(Key::PublishMatching)
+                .replacen("{}", &plan.tracks.len().to_string(), 1)
+                .replacen("{}", &plan.total.to_string(), 1),
+        ),
+        PublishStage::Review => review(plan, locale),
+        PublishStage::Publishing => secondary_text(locale.get(Key::Publishing).to_string()),
+        PublishStage::Done { unmatched, .. } => done(plan, unmatched, locale),
+    };
+
+    let buttons: Element<'static, Message> = match &dialog.stage {
+        PublishStage::Matching | PublishStage::Review => {
+            let count = plan.track_ids().len();
+            let cancel_btn = button(text(locale.get(Key::Cancel).to_string()).size(14))
+                .padding([10, 20])
+                .style(theme::secondary_button)
+                .on_press(Message::ClosePublishDialog);
+            let publish_btn = button(
+                text(locale.count(Key::PublishConfirm, count as u64))
+                    .size(14)
+                    .color(Color::WHITE),
+            )
+            .padding([10, 20])
+            .style(theme::primary_button)
+            .on_press_maybe(
+                (dialog.stage == PublishStage::Review && count > 0)
+                    .then_some(Message::ConfirmPublish),
+            );
+            row![Space::new().width(Fill), cancel_btn, publish_btn]
+                .spacing(12)
+                .align_y(Alignment::Center)
+                .into()
+        }
+        PublishStage::Publishing => Space::new().height(0).into(),
+        PublishStage::Done { .. } => {
+            let close_btn = button(text(locale.get(Key::Close).to_string()).size(14))
+                .padding([10, 20])
+                .style(theme::secondary_button)
+                .on_press(Message::ClosePublishDialog);
+            let open_btn = button(
+                text(locale.get(Key::PublishOpen).to_string())
+                    .size(14)
+                    .color(Color::WHITE),
+            )
+            .padding([10, 20])
+            .style(theme::primary_button)
+            .on_press(Message::OpenPublishedPlaylist);
+            row![Space::new().width(Fill), close_btn, open_btn]
+                .spacing(12)
+                .align_y(Alignment::Center)
+                .into()
+        }
+    };
+
+    let title = text(plan.name.clone())
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let dialog_content = column![
+        title,
+        Space::new().height(12),
+        body,
+        Space::new().height(24),
+        buttons,
+    ]
+    .width(520)
+    .padding(24);
+
+    let dialog_box = container(dialog_content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    // The backdrop doesn't dismiss; lookups and publishing would be lost
+    let event_blocker = mouse_area(backdrop_content).interaction(Interaction::Idle);
+
+    opaque(event_blocker).into()
+}
+
+/// Counts, then every close call with its results to pick from
+fn review(plan: &PublishPlan, locale: Locale) -> Element<'static, Message> {
+    let summary = locale
+        .get(Key::PublishSummary)
+        .replacen("{}", &plan.track_ids().len().to_string(), 1)
+        .replacen("{}", &plan.undecided().to_string(), 1)
+        .replacen(
+            "{}",
+            &plan
+                .tracks
+                .iter()
+                .filter(|t| t.result == TrackMatch::Unmatched)
+                .count()
+                .to_string(),
+            1,
+        );
+
+    let close_calls: Vec<Element<'static, Message>> = plan
+        .tracks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, track)| match &track.result {
+            TrackMatch::Ambiguous { candidates, chosen } => {
+                let choices = candidates.iter().enumerate().map(|(i, song)| {
+                    let label = if song.album.is_empty() {
+                        ncm_publish::song_label(song)
+                    } else {
+                        format!("{} · {}", ncm_publish::song_label(song), song.album)
+                    };
+                    let style = if *chosen == Some(i) {
+                        theme::primary_button
+                    } else {
+                        theme::secondary_button
+                    };
+                    button(text(label).size(13))
+                        .padding([6, 12])
+                        .width(Fill)
+                        .style(style)
+                        .on_press(Message::ChoosePublishMatch(index, Some(i)))
+                        .into()
+                });
+                let skip = button(text(locale.get(Key::PublishSkip).to_string()).size(13))
+                    .padding([6, 12])
+                    .style(theme::text_button)
+                    .on_press(Message::ChoosePublishMatch(index, None));
+                Some(
+                    column![
+                        text(track.label.clone())
+                            .size(14)
+                            .style(|theme| text::Style {
+                                color: Some(theme::text_primary(theme)),
+                            }),
+                        Column::with_children(choices).spacing(4),
+                        skip,
+                    ]
+                    .spacing(6)
+                    .into(),
+                )
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut content = column![secondary_text(summary)].spacing(8);
+    if !close_calls.is_empty() {
+        content = content
+            .push(secondary_text(
+                locale.get(Key::PublishReviewHint).to_string(),
+            ))
+            .push(
+                scrollable(
+                    Column::with_children(close_calls)
+                        .spacing(16)
+                        .padding([0, 8]),
+                )
+                .height(320)
+                .width(Fill),
+            );
+    }
+    content.into()
+}
+
+/// What was published and the songs left out
+fn done(plan: &PublishPlan, unmatched: &[String], locale: Locale) -> Element<'static, Message> {
+    let mut content = column![secondary_text(
+        locale.count(Key::PublishDone, plan.track_ids().len() as u64)
+    )]
+    .spacing(8);
+    if !unmatched.is_empty() {
+        let songs = unmatched.iter().map(|label| {
+            text(label.clone())
+                .size(13)
+                .color(theme::TEXT_SECONDARY)
+                .into()
+        });
+        content = content
+            .push(
+                text(locale.get(Key::PublishUnmatched).to_string())
+                    .size(14)
+                    .style(|theme| text::Style {
+                        color: Some(theme::text_primary(theme)),
+                    }),
+            )
+            .push(
+                scrollable(Column::with_children(songs).spacing(4))
+                    .height(240)
+                    .width(Fill),
+            );
+    }
+    content.into()
+}
+
+fn secondary_text(label: String) -> Element<'static, Message> {
+    text(label).size(14).color(theme::TEXT_SECONDARY).into()
+}
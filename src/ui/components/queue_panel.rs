@@ -2,12 +2,16 @@
 //!
 //! Shows the current play queue as a popup bubble above the player bar.
 
+use std::collections::{HashMap, HashSet};
+
 use iced::widget::{Space, button, column, container, row, scrollable, svg, text};
 use iced::{Alignment, Color, Element, Fill, Length, Padding};
 
 use crate::app::Message;
-use crate::database::DbSong;
+use crate::database::{DbQueueSnapshot, DbSong, SongId};
+use crate::features::song_source::SongSource;
 use crate::i18n::{Key, Locale};
+use crate::ui::components::playlist_view::source_badge;
 use crate::ui::{icons, theme};
 
 /// Queue popup width
@@ -45,14 +49,29 @@ pub fn calculate_scroll_offset(queue_len: usize, queue_index: Option<usize>) ->
 }
 
 /// Build the queue popup bubble
+///
+/// `sources` says where each song plays from by ID; songs not checked yet
+/// are assumed to stream if they came from NCM. Songs in `autoplay_ids` were
+/// added by autoplay after the queue ended and are tagged as such. With
+/// `sessions` the earlier queues are listed instead of this one.
+#[allow(clippy::too_many_arguments)]
 pub fn view(
     queue: &[DbSong],
     queue_index: Option<usize>,
+    sources: &HashMap<SongId, SongSource>,
+    offline_only: bool,
+    autoplay: bool,
+    autoplay_ids: &HashSet<SongId>,
     locale: Locale,
     is_fm_mode: bool,
+    similar_energy: bool,
+    sessions: Option<&[DbQueueSnapshot]>,
 ) -> Element<'static, Message> {
-    let header_title = if is_fm_mode {
-        "私人FM".to_string()
+    let showing_sessions = sessions.is_some();
+    let header_title = if showing_sessions {
+        locale.get(Key::QueueSessions).to_string()
+    } else if is_fm_mode {
+        locale.get(Key::PersonalFm).to_string()
     } else {
         locale.get(Key::QueueTitle).to_string()
     };
@@ -62,12 +81,55 @@ pub fn view(
             color: Some(theme::text_primary(theme))
         }),
         Space::new().width(Fill),
+        button(text(locale.get(Key::QueueOfflineOnly)).size(11))
+            .padding([3, 8])
+            .style(move |theme, status| {
+                if offline_only {
+                    theme::primary_button(theme, status)
+                } else {
+                    theme::secondary_button(theme, status)
+                }
+            })
+            .on_press(Message::ToggleQueueOfflineOnly),
+        Space::new().width(6),
+        button(text(locale.get(Key::QueueAutoplay)).size(11))
+            .padding([3, 8])
+            .style(move |theme, status| {
+                if autoplay {
+                    theme::primary_button(theme, status)
+                } else {
+                    theme::secondary_button(theme, status)
+                }
+            })
+            .on_press(Message::UpdateAutoplaySimilar(!autoplay)),
+        Space::new().width(6),
+        // Only offered once the current song has been analyzed
+        button(text(locale.get(Key::QueueSimilarEnergy)).size(11))
+            .padding([3, 8])
+            .style(theme::secondary_button)
+            .on_press_maybe(similar_energy.then_some(Message::QueueSimilarEnergy)),
+        Space::new().width(8),
         text(format!("{}", queue.len()))
             .size(12)
             .style(|theme| text::Style {
                 color: Some(theme::text_muted(theme))
             }),
         Space::new().width(8),
+        button(
+            svg(svg::Handle::from_memory(icons::CLOCK.as_bytes()))
+                .width(14)
+                .height(14)
+                .style(move |theme, _status| svg::Style {
+                    color: Some(if showing_sessions {
+                        theme::ACCENT_PINK
+                    } else {
+                        theme::text_muted(theme)
+                    }),
+                })
+        )
+        .padding(6)
+        .style(theme::transparent_btn)
+        .on_press(Message::ToggleQueueSessions),
         button(
             svg(svg::Handle::from_memory(icons::TRASH.as_bytes()))
                 .width(14)
@@ -94,18 +156,41 @@ pub fn view(
     .align_y(Alignment::Center)
     .padding(Padding::new(12.0).left(16.0).right(12.0));
 
+    if let Some(sessions) = sessions {
+        return bubble(column![header, session_list(sessions, locale)]);
+    }
+
     let song_items: Vec<Element<'static, Message>> = queue
         .iter()
         .enumerate()
-        .map(|(idx, song)| {
+        .filter_map(|(idx, song)| {
+            let source = sources
+                .get(&song.id)
+                .copied()
+                .unwrap_or_else(|| SongSource::unchecked(song));
+            if offline_only && !source.is_offline() {
+                return None;
+            }
             let is_current = queue_index == Some(idx);
-            build_queue_item(song.clone(), idx, is_current)
+            Some(build_queue_item(
+                song.clone(),
+                idx,
+                is_current,
+                source,
+                autoplay_ids.contains(&song.id),
+                locale,
+            ))
         })
         .collect();
 
     let song_list: Element<'static, Message> = if song_items.is_empty() {
+        let empty = if queue.is_empty() {
+            Key::QueueEmpty
+        } else {
+            Key::QueueNoOfflineSongs
+        };
         container(
-            text(locale.get(Key::QueueEmpty).to_string())
+            text(locale.get(empty).to_string())
                 .size(14)
                 .style(|theme| text::Style {
                     color: Some(theme::text_muted(theme)),
@@ -126,9 +211,12 @@ pub fn view(
         .into()
     };
 
-    let content = column![header, song_list,].width(QUEUE_PANEL_WIDTH);
+    bubble(column![header, song_list])
+}
 
-    container(content)
+/// Popup surface around the panel content
+fn bubble(content: iced::widget::Column<'static, Message>) -> Element<'static, Message> {
+    container(content.width(QUEUE_PANEL_WIDTH))
         .width(QUEUE_PANEL_WIDTH)
         .max_height(QUEUE_PANEL_HEIGHT)
         .style(|theme| iced::widget::container::Style {
@@ -148,8 +236,113 @@ pub fn view(
         .into()
 }
 
+/// Earlier queues, newest first
+fn session_list(sessions: &[DbQueueSnapshot], locale: Locale) -> Element<'static, Message> {
+    if sessions.is_empty() {
+        return container(
+            text(locale.get(Key::QueueNoSessions).to_string())
+                .size(14)
+                .style(|theme| text::Style {
+                    color: Some(theme::text_muted(theme)),
+                }),
+        )
+        .width(Fill)
+        .padding(32)
+        .center_x(Fill)
+        .into();
+    }
+
+    let items: Vec<Element<'static, Message>> = sessions
+        .iter()
+        .map(|session| build_session_item(session, locale))
+        .collect();
+    scrollable(
+        column(items)
+            .spacing(2)
+            .padding(Padding::new(0.0).left(8.0).right(8.0).bottom(8.0)),
+    )
+    .height(Length::Fixed(QUEUE_PANEL_HEIGHT - 60.0))
+    .into()
+}
+
+/// One earlier queue: when it was last played and what was in it
+fn build_session_item(session: &DbQueueSnapshot, locale: Locale) -> Element<'static, Message> {
+    let time = chrono::DateTime::from_timestamp(session.saved_at, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let when = format!(
+        "{} · {}",
+        crate::utils::format_relative_time(session.saved_at, locale),
+        time
+    );
+    let count = locale.count(Key::QueueSessionSongs, session.song_count as u64);
+    let contents = match &session.first_title {
+        Some(title) => format!("{} · {}", title, count),
+        None => count,
+    };
+
+    let info = column![
+        text(when).size(13).style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        }),
+        text(contents).size(11).style(|theme| text::Style {
+            color: Some(theme::text_muted(theme)),
+        }),
+    ]
+    .spacing(2)
+    .width(Fill);
+
+    button(
+        row![
+            container(
+                svg(svg::Handle::from_memory(icons::QUEUE.as_bytes()))
+                    .width(14)
+                    .height(14)
+                    .style(|theme, _status| svg::Style {
+                        color: Some(theme::text_muted(theme)),
+                    })
+            )
+            .width(24)
+            .center_x(24),
+            Space::new().width(8),
+            info,
+        ]
+        .align_y(Alignment::Center)
+        .padding(Padding::new(8.0).left(8.0).right(8.0)),
+    )
+    .width(Fill)
+    .padding(0)
+    .style(|theme, status| {
+        let background = match status {
+            button::Status::Hovered | button::Status::Pressed => theme::hover_bg(theme),
+            _ => Color::TRANSPARENT,
+        };
+        button::Style {
+            background: Some(iced::Background::Color(background)),
+            border: iced::Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    })
+    .on_press(Message::RestoreQueueSession(session.id))
+    .into()
+}
+
 /// Build a single queue item
-fn build_queue_item(song: DbSong, index: usize, is_current: bool) -> Element<'static, Message> {
+fn build_queue_item(
+    song: DbSong,
+    index: usize,
+    is_current: bool,
+    source: SongSource,
+    from_autoplay: bool,
+    locale: Locale,
+) -> Element<'static, Message> {
     let duration_secs = song.duration_secs as u64;
     let mins = duration_secs / 60;
     let secs = duration_secs % 60;
@@ -185,7 +378,7 @@ fn build_queue_item(song: DbSong, index: usize, is_current: bool) -> Element<'st
         });
 
     let artist_text = if song.artist.is_empty() {
-        "未知艺术家".to_string()
+        locale.get(Key::UnknownArtist).to_string()
     } else {
         song.artist.clone()
     };
@@ -202,7 +395,14 @@ fn build_queue_item(song: DbSong, index: usize, is_current: bool) -> Element<'st
         }),
     });
 
-    let info = column![title, artist].spacing(2).width(Fill);
+    let mut details = row![source_badge(source, locale)]
+        .spacing(6)
+        .align_y(Alignment::Center);
+    if from_autoplay {
+        details = details.push(autoplay_badge(locale));
+    }
+
+    let info = column![title, details.push(artist)].spacing(2).width(Fill);
 
     let duration = text(duration_str).size(11).style(|theme| text::Style {
         color: Some(theme::text_muted(theme)),
@@ -256,3 +456,22 @@ fn build_queue_item(song: DbSong, index: usize, is_current: bool) -> Element<'st
         .on_press(Message::PlayQueueIndex(index))
         .into()
 }
+
+/// Tag for songs autoplay added after the queue ended
+fn autoplay_badge(locale: Locale) -> Element<'static, Message> {
+    container(
+        text(locale.get(Key::QueueAutoplay))
+            .size(10)
+            .color(theme::ACCENT),
+    )
+    .padding([0, 4])
+    .style(|_theme| iced::widget::container::Style {
+        border: iced::Border {
+            radius: 3.0.into(),
+            width: 1.0,
+            color: theme::ACCENT,
+        },
+        ..Default::default()
+    })
+    .into()
+}
@@ -0,0 +1,210 @@
+//! Search dropdown component
+//! Recent searches and hot searches for an empty query, matching history and
+//! NCM suggestions while typing. Drawn as an overlay under the top bar search box.
+
+use iced::widget::{Space, button, column, container, mouse_area, opaque, row, svg, text};
+use iced::{Alignment, Element, Fill};
+
+use crate::app::Message;
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Left edge of the search box: nav padding, back/forward group and the gap after it
+const LEFT: f32 = 16.0 + 36.0 * 2.0 + 1.0 + 12.0 + 16.0;
+/// Just below the search box
+const TOP: f32 = 52.0;
+const WIDTH: f32 = 320.0;
+/// History entries that match the query, shown above suggestions
+const MATCHING_HISTORY: usize = 3;
+const MAX_SUGGESTIONS: usize = 8;
+const MAX_HOT: usize = 10;
+
+/// Build the dropdown overlay; `None` when there is nothing to show
+pub fn view<'a>(
+    query: &str,
+    history: &'a [String],
+    hot_keywords: &'a [String],
+    suggestions: &'a [String],
+    locale: Locale,
+) -> Option<Element<'a, Message>> {
+    let query = query.trim().to_lowercase();
+    let mut items: Vec<Element<'a, Message>> = Vec::new();
+
+    if query.is_empty() {
+        items.push(section_header(
+            locale.get(Key::SearchHistory),
+            (!history.is_empty()).then(|| {
+                button(text(locale.get(Key::SearchHistoryClear)).size(12))
+                    .padding([2, 6])
+                    .style(theme::text_button)
+                    .on_press(Message::ClearSearchHistory)
+                    .into()
+            }),
+        ));
+        if history.is_empty() {
+            items.push(
+                container(
+                    text(locale.get(Key::SearchHistoryEmpty))
+                        .size(13)
+                        .color(theme::TEXT_MUTED),
+                )
+                .padding([6, 10])
+                .into(),
+            );
+        }
+        items.extend(history.iter().map(|keyword| history_row(keyword, locale)));
+
+        if !hot_keywords.is_empty() {
+            items.push(Space::new().height(8).into());
+            items.push(section_header(locale.get(Key::SearchHotList), None));
+            items.extend(
+                hot_keywords
+                    .iter()
+                    .take(MAX_HOT)
+                    .enumerate()
+                    .map(|(i, keyword)| hot_row(i + 1, keyword)),
+            );
+        }
+    } else {
+        let matching: Vec<&String> = history
+            .iter()
+            .filter(|keyword| keyword.to_lowercase().contains(&query))
+            .take(MATCHING_HISTORY)
+            .collect();
+        items.extend(
+            matching
+                .iter()
+                .copied()
+                .map(|keyword| history_row(keyword, locale)),
+        );
+        items.extend(
+            suggestions
+                .iter()
+                .filter(|keyword| !matching.contains(keyword))
+                .take(MAX_SUGGESTIONS)
+                .map(|keyword| keyword_button(icon(crate::ui::icons::SEARCH), keyword)),
+        );
+    }
+
+    if items.is_empty() {
+        return None;
+    }
+
+    let panel = container(column(items).spacing(2))
+        .width(WIDTH)
+        .padding(8)
+        .style(|theme| container::Style {
+            background: Some(iced::Background::Color(theme::surface_container(theme))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: theme::divider(theme),
+            },
+            shadow: iced::Shadow {
+                color: iced::Color::from_rgba(0.0, 0.0, 0.0, 0.3),
+                offset: iced::Vector::new(0.0, 4.0),
+                blur_radius: 16.0,
+            },
+            ..Default::default()
+        });
+
+    // Clicking anywhere below the top bar outside the panel closes it
+    let backdrop = mouse_area(
+        container(row![Space::new().width(LEFT), opaque(panel)])
+            .width(Fill)
+            .height(Fill),
+    )
+    .on_press(Message::CloseSearchDropdown);
+
+    Some(column![Space::new().height(TOP), backdrop].into())
+}
+
+fn section_header<'a>(
+    title: &'a str,
+    action: Option<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let mut header = row![
+        text(title)
+            .size(12)
+            .color(theme::TEXT_SECONDARY)
+            .font(iced::Font {
+                weight: BOLD_WEIGHT,
+                ..Default::default()
+            }),
+        Space::new().width(Fill),
+    ]
+    .align_y(Alignment::Center)
+    .height(24)
+    .padding([0, 10]);
+    if let Some(action) = action {
+        header = header.push(action);
+    }
+    header.into()
+}
+
+/// A history entry with a button to remove it
+fn history_row(keyword: &str, locale: Locale) -> Element<'_, Message> {
+    let remove = button(
+        svg(svg::Handle::from_memory(crate::ui::icons::CLOSE.as_bytes()))
+            .width(14)
+            .height(14)
+            .style(|theme, _status| svg::Style {
+                color: Some(theme::text_secondary(theme)),
+            }),
+    )
+    .padding(6)
+    .style(theme::icon_button)
+    .on_press(Message::DeleteSearchHistory(keyword.to_string()));
+
+    row![
+        keyword_button(icon(crate::ui::icons::CLOCK), keyword),
+        iced::widget::tooltip(
+            remove,
+            text(locale.get(Key::SearchHistoryRemove)).size(12),
+            iced::widget::tooltip::Position::Left,
+        ),
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// A hot search entry with its rank; the top three are highlighted
+fn hot_row(rank: usize, keyword: &str) -> Element<'_, Message> {
+    let rank_text = text(rank.to_string())
+        .size(13)
+        .width(14)
+        .color(if rank <= 3 {
+            theme::ACCENT_PINK
+        } else {
+            theme::TEXT_MUTED
+        });
+    keyword_button(rank_text.into(), keyword)
+}
+
+fn keyword_button<'a>(leading: Element<'a, Message>, keyword: &'a str) -> Element<'a, Message> {
+    button(
+        row![
+            leading,
+            Space::new().width(10),
+            text(keyword).size(13).style(|theme| text::Style {
+                color: Some(theme::text_primary(theme)),
+            }),
+        ]
+        .align_y(Alignment::Center),
+    )
+    .width(Fill)
+    .padding([7, 10])
+    .style(theme::nav_item)
+    .on_press(Message::SelectSearchKeyword(keyword.to_string()))
+    .into()
+}
+
+fn icon<'a>(icon: &'static str) -> Element<'a, Message> {
+    svg(svg::Handle::from_memory(icon.as_bytes()))
+        .width(14)
+        .height(14)
+        .style(|_theme, _status| svg::Style {
+            color: Some(theme::TEXT_MUTED),
+        })
+        .into()
+}
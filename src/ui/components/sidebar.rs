@@ -1,7 +1,11 @@
 //! Left sidebar navigation component
 //! Dark gray panel with logo, menu, library section, and user profile
 
-use iced::widget::{Space, button, column, container, mouse_area, row, scrollable, svg, text};
+use std::collections::HashMap;
+
+use iced::widget::{
+    Space, button, column, container, image, mouse_area, row, scrollable, svg, text,
+};
 use iced::{Alignment, Color, Element, Fill, Padding};
 
 use crate::app::{Message, Route, SidebarId};
@@ -70,6 +74,7 @@ pub fn view(
     user_info: Option<&crate::app::UserInfo>,
     importing_playlist: Option<&ImportingPlaylist>,
     playlists: &[crate::database::DbPlaylist],
+    playlist_mosaics: &HashMap<i64, String>,
     user_playlists: &[crate::api::SongList],
     sidebar_animations: &HoverAnimations<SidebarId>,
     sidebar_width: f32,
@@ -349,10 +354,10 @@ pub fn view(
         },
         ..Default::default()
     })
-    .on_press(if is_logged_in {
-        Message::OpenSettings
-    } else {
-        Message::ToggleLoginPopup
+    .on_press(match user_info {
+        Some(info) if is_logged_in => Message::OpenUserProfile(info.user_id),
+        _ if is_logged_in => Message::OpenSettings,
+        _ => Message::ToggleLoginPopup,
     });
 
     let user_card: Element<'static, Message> = mouse_area(user_card_content)
@@ -365,23 +370,46 @@ pub fn view(
 
     // Show importing playlist if any
     if let Some(playlist) = importing_playlist {
-        library_items.push(importing_card::view(playlist));
+        library_items.push(importing_card::view(playlist, locale));
     }
 
-    // Show local playlists with hover animations
+    // Show local playlists with hover animations, led by their cover
     for playlist in playlists {
         let name = playlist.name.clone();
         let id = playlist.id;
         let is_active = matches!(current_route, Route::Playlist(current_id) if *current_id == id);
         let hover_progress = sidebar_animations.get_progress(&SidebarId::Playlist(id));
-        library_items.push(sidebar_button_animated(
-            crate::ui::icons::MUSIC,
-            name,
-            is_active,
-            hover_progress,
-            SidebarId::Playlist(id),
-            Message::OpenPlaylist(id),
-        ));
+        let cover = playlist
+            .cover_path
+            .as_ref()
+            .filter(|p| !p.starts_with("http") && std::path::Path::new(p).exists())
+            .or_else(|| playlist_mosaics.get(&id));
+        let button = match cover {
+            Some(cover) => sidebar_button_with_icon(
+                container(
+                    image(image::Handle::from_path(cover))
+                        .width(20)
+                        .height(20)
+                        .content_fit(iced::ContentFit::Cover)
+                        .border_radius(4.0),
+                )
+                .into(),
+                name,
+                is_active,
+                hover_progress,
+                SidebarId::Playlist(id),
+                Message::OpenPlaylist(id),
+            ),
+            None => sidebar_button_animated(
+                crate::ui::icons::MUSIC,
+                name,
+                is_active,
+                hover_progress,
+                SidebarId::Playlist(id),
+                Message::OpenPlaylist(id),
+            ),
+        };
+        library_items.push(button);
     }
 
     library_items.push(import_playlist_btn);
@@ -397,8 +425,9 @@ pub fn view(
         library_section.into(),
     ];
 
-    // Only show cloud playlists section if logged in
-    if is_logged_in {
+    // Only show cloud playlists section if logged in, or offline with the
+    // saved liked songs playlist
+    if is_logged_in || !user_playlists.is_empty() {
         let cloud_header = text(locale.get(Key::CloudPlaylistsTitle))
             .size(12)
             .color(theme::TEXT_MUTED)
@@ -508,6 +537,25 @@ fn sidebar_button_animated(
             }),
         });
 
+    sidebar_button_with_icon(
+        icon.into(),
+        label,
+        is_active,
+        hover_progress,
+        sidebar_id,
+        on_press,
+    )
+}
+
+/// Animated sidebar button led by any 20px icon element
+fn sidebar_button_with_icon(
+    icon: Element<'static, Message>,
+    label: String,
+    is_active: bool,
+    hover_progress: f32,
+    sidebar_id: SidebarId,
+    on_press: Message,
+) -> Element<'static, Message> {
     let label_text = text(label).size(14).style(move |theme| text::Style {
         color: Some(if is_active {
             theme::text_primary(theme)
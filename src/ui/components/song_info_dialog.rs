@@ -0,0 +1,182 @@
+//! Song info editor dialog component
+
+use iced::mouse::Interaction;
+use iced::widget::{Space, button, column, container, mouse_area, opaque, row, text, text_input};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::{Message, SongInfoDialog};
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the dialog for correcting an NCM song's title, artist and album
+pub fn view(
+    dialog: &SongInfoDialog,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let opacity = animation_progress;
+
+    let title = text(locale.get(Key::SongInfoTitle).to_string())
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let desc = text(locale.get(Key::SongInfoDesc).to_string())
+        .size(13)
+        .color(theme::TEXT_SECONDARY);
+
+    let fields = column![
+        field(
+            locale.get(Key::SongInfoFieldTitle),
+            &dialog.tags.title,
+            &dialog.original.title,
+            Message::SongInfoTitleChanged,
+            locale,
+        ),
+        field(
+            locale.get(Key::SongInfoFieldArtist),
+            &dialog.tags.artist,
+            &dialog.original.artist,
+            Message::SongInfoArtistChanged,
+            locale,
+        ),
+        field(
+            locale.get(Key::SongInfoFieldAlbum),
+            &dialog.tags.album,
+            &dialog.original.album,
+            Message::SongInfoAlbumChanged,
+            locale,
+        ),
+    ]
+    .spacing(14);
+
+    let reset_btn: Element<'static, Message> = if dialog.overridden {
+        button(text(locale.get(Key::SongInfoReset).to_string()).size(14))
+            .padding([10, 20])
+            .style(theme::secondary_button)
+            .on_press(Message::ResetSongInfo)
+            .into()
+    } else {
+        Space::new().width(0).into()
+    };
+
+    let cancel_btn = button(text(locale.get(Key::Cancel).to_string()).size(14))
+        .padding([10, 20])
+        .style(theme::secondary_button)
+        .on_press(Message::CloseSongInfo);
+
+    let save_btn = button(
+        text(locale.get(Key::Save).to_string())
+            .size(14)
+            .color(Color::WHITE),
+    )
+    .padding([10, 20])
+    .style(theme::primary_button)
+    .on_press_maybe((!dialog.tags.title.trim().is_empty()).then_some(Message::SaveSongInfo));
+
+    let buttons = row![reset_btn, Space::new().width(Fill), cancel_btn, save_btn]
+        .spacing(12)
+        .align_y(Alignment::Center);
+
+    let dialog_content = column![
+        title,
+        Space::new().height(8),
+        desc,
+        Space::new().height(20),
+        fields,
+        Space::new().height(24),
+        buttons,
+    ]
+    .width(440)
+    .padding(24);
+
+    let dialog_box = container(dialog_content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    // Clicking the backdrop cancels
+    let event_blocker = mouse_area(backdrop_content)
+        .interaction(Interaction::Idle)
+        .on_press(Message::CloseSongInfo);
+
+    opaque(event_blocker).into()
+}
+
+/// Labeled input with NCM's value underneath when it differs
+fn field(
+    label: &str,
+    value: &str,
+    original: &str,
+    on_input: fn(String) -> Message,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let label = text(label.to_string())
+        .size(13)
+        .color(theme::TEXT_SECONDARY);
+
+    let input = text_input(original, value)
+        .on_input(on_input)
+        .padding(10)
+        .size(14)
+        .style(|theme, _status| text_input::Style {
+            background: iced::Background::Color(theme::surface_container(theme)),
+            border: iced::Border {
+                color: theme::divider(theme),
+                width: 1.0,
+                radius: 6.0.into(),
+            },
+            icon: theme::TEXT_MUTED,
+            placeholder: theme::TEXT_MUTED,
+            value: theme::TEXT_PRIMARY,
+            selection: theme::ACCENT_PINK,
+        });
+
+    let mut field = column![label, input].spacing(6);
+    if value.trim() != original {
+        field = field.push(
+            text(locale.get(Key::SongInfoNcmValue).replace("{}", original))
+                .size(12)
+                .color(theme::TEXT_MUTED),
+        );
+    }
+    field.into()
+}
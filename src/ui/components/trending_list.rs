@@ -1,29 +1,33 @@
 //! Trending songs list component with hover animations
 //!
-//! Displays the NCM trending chart (飙升榜) with hover effects.
+//! Displays the NCM trending chart (飙升榜) with hover effects. The
+//! homepage reuses it for daily recommendations.
 
 use iced::widget::{Space, button, column, container, image, row, svg, text};
 use iced::{Alignment, Color, Element, Fill, Padding};
 
 use crate::api::SongInfo;
 use crate::app::Message;
+use crate::database::SongId;
+use crate::features::lazy_covers::CoverKey;
 use crate::i18n::{Key, Locale};
 use crate::ui::animation::HoverAnimations;
 use crate::ui::theme::{self, BOLD_WEIGHT, MEDIUM_WEIGHT};
+use crate::ui::widgets::lazy_cover;
 
 const ITEM_HEIGHT: f32 = 64.0;
 const COVER_SIZE: f32 = 48.0;
 
 /// Build the trending songs list view
 pub fn view<'a>(
+    title: &'a str,
+    on_see_all: Message,
     songs: &'a [SongInfo],
     song_covers: &'a std::collections::HashMap<u64, iced::widget::image::Handle>,
     hover_animations: &'a HoverAnimations<u64>,
     locale: Locale,
     is_logged_in: bool,
 ) -> Element<'a, Message> {
-    let title = locale.get(Key::TrendingSongs);
-
     let header = row![
         text(title)
             .size(20)
@@ -37,7 +41,7 @@ pub fn view<'a>(
         Space::new().width(Fill),
         button(text(locale.get(Key::SeeAll)).size(14).color(theme::ACCENT))
             .style(theme::text_button)
-            .on_press(Message::OpenTrendingSongs),
+            .on_press(on_see_all),
     ]
     .align_y(Alignment::Center)
     .padding(Padding::new(0.0).bottom(16.0));
@@ -67,14 +71,25 @@ pub fn view<'a>(
             let hover_progress = hover_animations.get_progress(&song.id);
             let cover_handle = song_covers.get(&song.id);
             let is_hovered = hover_progress > 0.01; // Lower threshold to fix timing issue
-            view_song_item(
+            let item = view_song_item(
                 song,
                 index + 1,
                 is_hovered,
                 hover_progress,
                 cover_handle,
                 is_logged_in,
-            )
+            );
+            if cover_handle.is_none() {
+                let key = CoverKey::Song(song.id);
+                lazy_cover::view(
+                    item,
+                    song.id,
+                    Message::CoverShown(key, song.pic_url.clone()),
+                    Message::CoverHidden(key),
+                )
+            } else {
+                item
+            }
         })
         .collect();
 
@@ -119,7 +134,7 @@ fn view_song_item<'a>(
                     }),
             )
             .style(theme::icon_button)
-            .on_press(Message::ToggleFavorite(song.id))
+            .on_press(Message::ToggleFavorite(SongId::ncm(song.id)))
             .into();
             (favorite_btn, 32.0)
         } else {
@@ -0,0 +1,249 @@
+//! Update prompt and what's-new dialog components
+
+use iced::mouse::Interaction;
+use iced::widget::{Space, button, column, container, mouse_area, opaque, row, scrollable, text};
+use iced::{Alignment, Color, Element, Fill};
+
+use crate::app::{Message, UpdateStatus, WhatsNew};
+use crate::features::updater::Version;
+use crate::features::whats_new::Entry;
+use crate::i18n::{Key, Locale};
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+/// Build the prompt shown when a newer release is available or downloaded
+pub fn prompt_view(
+    status: &UpdateStatus,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let (release, ready) = match status {
+        UpdateStatus::Ready(release, _) => (release, true),
+        UpdateStatus::Available(release) => (release, false),
+        _ => return Space::new().height(0).into(),
+    };
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let message_key = if ready {
+        Key::UpdateDialogReady
+    } else {
+        Key::UpdateDialogAvailable
+    };
+    let message = text(locale.get(message_key).replace("{}", &release.version))
+        .size(14)
+        .color(theme::TEXT_SECONDARY);
+
+    let later_btn = button(text(locale.get(Key::UpdateDialogLater).to_string()).size(14))
+        .padding([10, 20])
+        .style(theme::secondary_button)
+        .on_press(Message::DismissUpdatePrompt);
+
+    let (action_key, action) = if ready {
+        (Key::UpdateDialogInstall, Message::InstallUpdate)
+    } else {
+        (Key::UpdateDialogOpenPage, Message::OpenReleasePage)
+    };
+    let action_btn = button(
+        text(locale.get(action_key).to_string())
+            .size(14)
+            .color(Color::WHITE),
+    )
+    .padding([10, 20])
+    .style(theme::primary_button)
+    .on_press(action);
+
+    let content = column![
+        title(locale.get(Key::UpdateDialogTitle).to_string()),
+        Space::new().height(12),
+        message,
+        Space::new().height(24),
+        row![Space::new().width(Fill), later_btn, action_btn]
+            .spacing(12)
+            .align_y(Alignment::Center),
+    ]
+    .width(440)
+    .padding(24);
+
+    dialog(content.into(), animation_progress)
+}
+
+/// Build the what's-new dialog shown after an update, or the version history
+pub fn changelog_view(
+    whats_new: &WhatsNew,
+    animation_progress: f32,
+    locale: Locale,
+) -> Element<'static, Message> {
+    if animation_progress < 0.01 {
+        return Space::new().height(0).into();
+    }
+
+    let mut body = column![].spacing(20).width(Fill);
+    for entry in &whats_new.entries {
+        body = body.push(entry_view(entry, locale));
+    }
+    let notes = whats_new
+        .notes
+        .as_ref()
+        .map(|release| release.notes.trim())
+        .filter(|notes| !notes.is_empty());
+    if let Some(notes) = notes {
+        body = body.push(
+            column![
+                heading(locale.get(Key::ChangelogReleaseNotes).to_string()),
+                body_text(notes.to_string()),
+            ]
+            .spacing(8),
+        );
+    }
+    if whats_new.entries.is_empty() && notes.is_none() {
+        body = body.push(body_text(locale.get(Key::ChangelogEmpty).to_string()));
+    }
+    let body = scrollable(body).height(320).width(Fill);
+
+    let close_btn = button(
+        text(locale.get(Key::ChangelogClose).to_string())
+            .size(14)
+            .color(Color::WHITE),
+    )
+    .padding([10, 20])
+    .style(theme::primary_button)
+    .on_press(Message::DismissChangelog);
+
+    let title_label = if whats_new.history {
+        locale.get(Key::ChangelogHistoryTitle).to_string()
+    } else {
+        locale
+            .get(Key::ChangelogTitle)
+            .replace("{}", &Version::current().to_string())
+    };
+
+    let content = column![
+        title(title_label),
+        Space::new().height(12),
+        body,
+        Space::new().height(24),
+        row![Space::new().width(Fill), close_btn].align_y(Alignment::Center),
+    ]
+    .width(520)
+    .padding(24);
+
+    dialog(content.into(), animation_progress)
+}
+
+/// One version: its number, when it first ran and its highlights
+fn entry_view(entry: &Entry, locale: Locale) -> Element<'static, Message> {
+    let mut header = row![heading(format!("v{}", entry.version))]
+        .spacing(8)
+        .align_y(Alignment::Center);
+    let first_used = entry
+        .first_run_at
+        .and_then(|at| chrono::DateTime::from_timestamp(at, 0))
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string()
+        });
+    if let Some(date) = first_used {
+        header = header.push(
+            text(locale.get(Key::ChangelogFirstUsed).replace("{}", &date))
+                .size(12)
+                .color(theme::TEXT_SECONDARY),
+        );
+    }
+
+    let highlights = entry
+        .release
+        .map(|release| release.highlights(locale.language.code()))
+        .unwrap_or_default();
+    let mut list = column![].spacing(6);
+    for highlight in highlights {
+        list = list.push(body_text(format!("•  {}", highlight)));
+    }
+    if highlights.is_empty() {
+        list = list.push(
+            text(locale.get(Key::ChangelogNoHighlights).to_string())
+                .size(13)
+                .color(theme::TEXT_SECONDARY),
+        );
+    }
+
+    column![header, list].spacing(8).into()
+}
+
+fn heading(label: String) -> Element<'static, Message> {
+    text(label)
+        .size(15)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        })
+        .into()
+}
+
+fn body_text(content: String) -> Element<'static, Message> {
+    text(content)
+        .size(14)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .width(Fill)
+        .into()
+}
+
+fn title(label: String) -> Element<'static, Message> {
+    text(label)
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Dialog box over a dimmed backdrop that swallows clicks
+fn dialog(content: Element<'static, Message>, opacity: f32) -> Element<'static, Message> {
+    let dialog_box = container(content).style(move |theme| {
+        let (bg, border) = if theme::is_dark_theme(theme) {
+            (0.12, 1.0)
+        } else {
+            (0.96, 0.0)
+        };
+        iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                bg, bg, bg, opacity,
+            ))),
+            border: iced::Border {
+                radius: 12.0.into(),
+                width: 1.0,
+                color: Color::from_rgba(border, border, border, 0.1 * opacity),
+            },
+            ..Default::default()
+        }
+    });
+
+    let backdrop_content = container(dialog_box)
+        .width(Fill)
+        .height(Fill)
+        .center_x(Fill)
+        .center_y(Fill)
+        .style(move |_theme| iced::widget::container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0,
+                0.0,
+                0.0,
+                0.5 * opacity,
+            ))),
+            ..Default::default()
+        });
+
+    let event_blocker = mouse_area(backdrop_content).interaction(Interaction::Idle);
+
+    opaque(event_blocker).into()
+}
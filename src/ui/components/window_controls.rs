@@ -192,14 +192,20 @@ pub fn view<'a>(
 
 /// Build the search bar component for the top bar
 fn search_bar_view(search_query: &str, locale: Locale) -> Element<'_, Message> {
-    let search_icon = svg(svg::Handle::from_memory(
-        crate::ui::icons::SEARCH.as_bytes(),
-    ))
-    .width(16)
-    .height(16)
-    .style(|_theme, _status| svg::Style {
-        color: Some(theme::TEXT_MUTED),
-    });
+    // The icon opens search history and hot searches without typing
+    let search_icon = button(
+        svg(svg::Handle::from_memory(
+            crate::ui::icons::SEARCH.as_bytes(),
+        ))
+        .width(16)
+        .height(16)
+        .style(|_theme, _status| svg::Style {
+            color: Some(theme::TEXT_MUTED),
+        }),
+    )
+    .padding(0)
+    .style(theme::transparent_btn)
+    .on_press(Message::ToggleSearchDropdown);
 
     let input = text_input(locale.get(Key::SearchPlaceholder), search_query)
         .on_input(Message::SearchChanged)
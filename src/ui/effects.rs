@@ -7,8 +7,10 @@
 //! - Vignette and noise/dithering effects
 //! - Bicubic Hermite Patch mesh gradients
 //! - Image preprocessing (blur, contrast, saturation)
+//! - Frosted-glass backdrops behind modals
 
 pub mod background;
+pub mod frosted;
 pub mod image_processing;
 pub mod mesh;
 pub mod textured_background;
@@ -0,0 +1,68 @@
+//! Frosted-glass backdrops
+//!
+//! Modals sit on a blurred copy of the window behind them. The window is
+//! captured once as a modal opens, shrunk, and run through the same box blur
+//! the lyrics background uses; the GPU then scales the small result back up
+//! with linear filtering, which softens it further at no cost.
+
+use image::imageops::{self, FilterType};
+use image::{ImageBuffer, Rgba};
+
+use super::image_processing::{ProcessedImage, blur_image, saturate_image};
+
+/// The snapshot is shrunk by this factor before blurring
+const DOWNSCALE: u32 = 8;
+
+/// Box blur radius and passes, in shrunk pixels
+const BLUR_RADIUS: usize = 4;
+const BLUR_QUALITY: usize = 3;
+
+/// Colours bleed through a little stronger, like Apple's vibrancy
+const SATURATION: f32 = 1.4;
+
+/// Blur an RGBA window snapshot into a small frosted backdrop
+///
+/// Returns `None` if `rgba` doesn't hold `width * height` pixels.
+pub fn frost(rgba: &[u8], width: u32, height: u32) -> Option<ProcessedImage> {
+    let snapshot = ImageBuffer::<Rgba<u8>, &[u8]>::from_raw(width, height, rgba)?;
+    let small_width = (width / DOWNSCALE).max(1);
+    let small_height = (height / DOWNSCALE).max(1);
+
+    let small = imageops::resize(&snapshot, small_width, small_height, FilterType::Triangle);
+    let mut data = small.into_raw();
+    blur_image(
+        &mut data,
+        small_width as usize,
+        small_height as usize,
+        BLUR_RADIUS,
+        BLUR_QUALITY,
+    );
+    saturate_image(&mut data, SATURATION);
+
+    Some(ProcessedImage::from_rgba(small_width, small_height, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frost_shrinks_snapshot() {
+        let rgba = [40u8, 40, 40, 255].repeat(160 * 90);
+        let frosted = frost(&rgba, 160, 90).unwrap();
+        assert_eq!((frosted.width, frosted.height), (20, 11));
+        assert_eq!(frosted.as_rgba().len(), 20 * 11 * 4);
+        // A flat grey stays flat: blurring and saturating don't tint it
+        assert!(
+            frosted
+                .as_rgba()
+                .chunks(4)
+                .all(|p| p[..3].iter().all(|&c| c.abs_diff(40) <= 1))
+        );
+    }
+
+    #[test]
+    fn test_frost_rejects_short_buffer() {
+        assert!(frost(&[0; 16], 160, 90).is_none());
+    }
+}
@@ -22,6 +22,7 @@ use super::image_processing::{
     ImageProcessingParams, ProcessedImage, process_image_for_background,
 };
 use super::mesh::{BhpMesh, ControlPointPreset, MeshVertex, choose_preset_or_random};
+use crate::features::BackgroundStyle;
 
 /// Mesh 顶点数据 (GPU 格式)
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
@@ -153,8 +154,155 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
     
     return result;
 }
+
+// ============ 其他背景样式 (全屏三角形, 不使用 mesh) ============
+
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let uv = vec2f(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    var out: VertexOutput;
+    out.clip_position = vec4f(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2f(uv.x, 1.0 - uv.y);
+    out.color = vec3f(1.0);
+    return out;
+}
+
+// 按宽高比裁切纹理, 像 object-fit: cover
+fn cover_uv(uv: vec2f) -> vec2f {
+    var p = uv - vec2f(0.5);
+    if (uniforms.aspect > 1.0) {
+        p.y = p.y / uniforms.aspect;
+    } else {
+        p.x = p.x * uniforms.aspect;
+    }
+    return p + vec2f(0.5);
+}
+
+fn hash21(p: vec2f) -> f32 {
+    return fract(sin(dot(p, vec2f(127.1, 311.7))) * 43758.5453);
+}
+
+fn value_noise(p: vec2f) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = hash21(i);
+    let b = hash21(i + vec2f(1.0, 0.0));
+    let c = hash21(i + vec2f(0.0, 1.0));
+    let d = hash21(i + vec2f(1.0, 1.0));
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+fn fbm(p: vec2f) -> f32 {
+    var value = 0.0;
+    var amplitude = 0.5;
+    var q = p;
+    for (var i = 0; i < 4; i++) {
+        value += amplitude * value_noise(q);
+        q = q * 2.03 + vec2f(1.7, 9.2);
+        amplitude *= 0.5;
+    }
+    return value;
+}
+
+// 与 fs_main 相同的音量淡化, dithering 和 vignette
+fn finish(rgb: vec3f, uv: vec2f, frag: vec2f) -> vec4f {
+    let alpha_volume_factor = uniforms.alpha * max(0.5, 1.0 - uniforms.volume * 0.5);
+    let dither = INV_255 * gradient_noise(frag) - HALF_INV_255;
+    let vignette = smoothstep(0.8, 0.3, distance(uv, vec2f(0.5)));
+    let mask = 0.6 + vignette * 0.4;
+    return vec4f((rgb * alpha_volume_factor + vec3f(dither)) * mask, alpha_volume_factor);
+}
+
+// Mesh gradient: 封面上几处颜色作为色块, 绕着画面缓慢漂移
+@fragment
+fn fs_mesh(in: VertexOutput) -> @location(0) vec4f {
+    let t = uniforms.time * 0.6;
+    let p = cover_uv(in.uv);
+    var color = vec3f(0.0);
+    var total = 0.0;
+    for (var i = 0; i < 5; i++) {
+        let fi = f32(i);
+        let anchor = vec2f(0.5) + 0.35 * vec2f(
+            sin(t * (0.7 + fi * 0.13) + fi * 1.9),
+            cos(t * (0.5 + fi * 0.11) + fi * 2.7)
+        );
+        let texel = vec2f(fract(fi * 0.37 + 0.1), fract(fi * 0.61 + 0.3));
+        let c = textureSampleLevel(t_texture, s_texture, texel, 0.0).rgb;
+        let d = distance(p, anchor);
+        let weight = 1.0 / (d * d * 6.0 + 0.05);
+        color += c * weight;
+        total += weight;
+    }
+    return finish(color / total, in.uv, in.clip_position.xy);
+}
+
+// Fluid: 多层 domain warping, 像被搅动的液体
+@fragment
+fn fs_fluid(in: VertexOutput) -> @location(0) vec4f {
+    let t = uniforms.time * 0.8 + uniforms.volume;
+    let base = cover_uv(in.uv);
+    let p = base * 2.5;
+    let q = vec2f(
+        fbm(p + vec2f(0.0, t * 0.4)),
+        fbm(p + vec2f(5.2, 1.3) - vec2f(t * 0.3))
+    );
+    let r = vec2f(
+        fbm(p + 3.0 * q + vec2f(1.7, 9.2) + vec2f(t * 0.25)),
+        fbm(p + 3.0 * q + vec2f(8.3, 2.8) - vec2f(t * 0.2))
+    );
+    let warped = base + (r - vec2f(0.5)) * 0.6;
+    let c = textureSampleLevel(t_texture, s_texture, warped, 0.0).rgb;
+    let shade = 0.85 + 0.3 * fbm(p + 4.0 * r);
+    return finish(c * shade, in.uv, in.clip_position.xy);
+}
+
+// Starfield: 暗色封面底色上, 三层星星由远及近飞过
+@fragment
+fn fs_starfield(in: VertexOutput) -> @location(0) vec4f {
+    let tint = textureSampleLevel(t_texture, s_texture, cover_uv(in.uv), 0.0).rgb;
+    var color = tint * 0.18;
+    let star_tint = mix(vec3f(1.0), min(tint * 2.0, vec3f(1.0)), 0.4);
+    let centered = (in.uv - vec2f(0.5))
+        * vec2f(max(uniforms.aspect, 1.0), max(1.0 / uniforms.aspect, 1.0));
+    let speed = 0.15 * (1.0 + uniforms.volume);
+    for (var layer = 0; layer < 3; layer++) {
+        let fl = f32(layer);
+        let depth = fract(fl / 3.0 + uniforms.time * speed);
+        let fade = smoothstep(0.0, 0.3, depth) * smoothstep(1.0, 0.85, depth);
+        let grid = centered * mix(12.0, 0.8, depth) + vec2f(fl * 17.3);
+        let cell = floor(grid);
+        let local = fract(grid) - vec2f(0.5);
+        let h = hash21(cell);
+        let offset = vec2f(hash21(cell + vec2f(3.1)), hash21(cell + vec2f(7.7))) - vec2f(0.5);
+        let d = length(local - offset * 0.7);
+        let star = smoothstep(mix(0.02, 0.06, h), 0.0, d) * step(0.6, h);
+        let twinkle = 0.6 + 0.4 * sin(uniforms.time * 20.0 * h + h * 6.28);
+        color += star_tint * star * twinkle * fade;
+    }
+    return finish(color, in.uv, in.clip_position.xy);
+}
+
+// Blurred: 只显示模糊后的封面
+@fragment
+fn fs_blurred(in: VertexOutput) -> @location(0) vec4f {
+    let c = textureSampleLevel(t_texture, s_texture, cover_uv(in.uv), 0.0).rgb;
+    return finish(c * 0.9, in.uv, in.clip_position.xy);
+}
 "#;
 
+/// Fragment entry of the styles drawn on a fullscreen triangle; the flow
+/// style draws the mesh with `fs_main`
+fn fullscreen_entry(style: BackgroundStyle) -> Option<&'static str> {
+    match style {
+        BackgroundStyle::Flow => None,
+        BackgroundStyle::Mesh => Some("fs_mesh"),
+        BackgroundStyle::Fluid => Some("fs_fluid"),
+        BackgroundStyle::Starfield => Some("fs_starfield"),
+        BackgroundStyle::Blurred => Some("fs_blurred"),
+    }
+}
+
 /// 单个 Mesh 状态 (用于过渡动画)
 #[derive(Clone)]
 pub struct MeshState {
@@ -190,6 +338,8 @@ struct CachedMeshState {
 /// Mesh Gradient Pipeline
 pub struct MeshGradientPipeline {
     pipeline: wgpu::RenderPipeline,
+    /// Pipelines of the other background styles
+    fullscreen_pipelines: Vec<(BackgroundStyle, wgpu::RenderPipeline)>,
     bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
     cached_states: Vec<CachedMeshState>,
@@ -401,40 +551,61 @@ impl shader::Pipeline for MeshGradientPipeline {
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Mesh Gradient Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[vertex_layout],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        let create_pipeline = |label: &str,
+                               vertex_entry: &str,
+                               fragment_entry: &str,
+                               buffers: &[wgpu::VertexBufferLayout]| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some(vertex_entry),
+                    buffers,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(fragment_entry),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        let pipeline = create_pipeline(
+            "Mesh Gradient Pipeline",
+            "vs_main",
+            "fs_main",
+            &[vertex_layout],
+        );
+        let fullscreen_pipelines = BackgroundStyle::ALL
+            .into_iter()
+            .filter_map(|style| {
+                let entry = fullscreen_entry(style)?;
+                let label = format!("Background Pipeline ({})", entry);
+                Some((style, create_pipeline(&label, "vs_fullscreen", entry, &[])))
+            })
+            .collect();
 
         Self {
             pipeline,
+            fullscreen_pipelines,
             bind_group_layout,
             sampler,
             cached_states: Vec::new(),
@@ -449,6 +620,7 @@ pub struct MeshGradientPrimitive {
     pub time: f32,
     pub volume: f32,
     pub aspect: f32,
+    pub style: BackgroundStyle,
 }
 
 impl shader::Primitive for MeshGradientPrimitive {
@@ -483,7 +655,12 @@ impl shader::Primitive for MeshGradientPrimitive {
             return false;
         }
 
-        render_pass.set_pipeline(&pipeline.pipeline);
+        let fullscreen = pipeline
+            .fullscreen_pipelines
+            .iter()
+            .find(|(style, _)| *style == self.style)
+            .map(|(_, fullscreen)| fullscreen);
+        render_pass.set_pipeline(fullscreen.unwrap_or(&pipeline.pipeline));
 
         // 渲染所有 mesh states (从旧到新)
         for state in &self.mesh_states {
@@ -493,6 +670,10 @@ impl shader::Primitive for MeshGradientPrimitive {
                 .find(|c| c.texture_id == state.texture_id)
             {
                 render_pass.set_bind_group(0, &cached.bind_group, &[]);
+                if fullscreen.is_some() {
+                    render_pass.draw(0..3, 0..1);
+                    continue;
+                }
                 render_pass.set_vertex_buffer(0, cached.vertex_buffer.slice(..));
                 render_pass
                     .set_index_buffer(cached.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
@@ -552,6 +733,8 @@ pub struct TexturedBackgroundProgram {
     mesh_states: Vec<MeshState>,
     /// 当前缓存的图片路径，用于避免重复加载
     current_image_path: Option<PathBuf>,
+    /// Palette color behind the current gradient, see `set_palette`
+    current_palette: Option<iced::Color>,
     smoothed_volume: f32,
     target_volume: f32,
     time: f32,
@@ -568,6 +751,7 @@ impl TexturedBackgroundProgram {
         Self {
             mesh_states: Vec::new(),
             current_image_path: None,
+            current_palette: None,
             smoothed_volume: 0.0,
             target_volume: 0.0,
             time: 0.0,
@@ -582,6 +766,12 @@ impl TexturedBackgroundProgram {
             return None;
         }
 
+        Some(Self::gradient_state(DEFAULT_BG_COLORS))
+    }
+
+    /// Mesh state for a smooth gradient between four corner colors
+    /// (top-left, top-right, bottom-left, bottom-right)
+    fn gradient_state(corners: [[f32; 3]; 4]) -> MeshState {
         // 使用大纹理尺寸以获得完全平滑的渐变（512x512）
         let size = 512u32;
         let mut pixels = Vec::with_capacity((size * size * 4) as usize);
@@ -593,10 +783,10 @@ impl TexturedBackgroundProgram {
                 let fy = y as f32 / (size - 1) as f32;
 
                 // 四角颜色
-                let tl = DEFAULT_BG_COLORS[0]; // 左上
-                let tr = DEFAULT_BG_COLORS[1]; // 右上
-                let bl = DEFAULT_BG_COLORS[2]; // 左下
-                let br = DEFAULT_BG_COLORS[3]; // 右下
+                let tl = corners[0]; // 左上
+                let tr = corners[1]; // 右上
+                let bl = corners[2]; // 左下
+                let br = corners[3]; // 右下
 
                 // 使用平滑的 smoothstep 插值
                 let sx = fx * fx * (3.0 - 2.0 * fx);
@@ -638,12 +828,12 @@ impl TexturedBackgroundProgram {
         let colors = vec![[1.0, 1.0, 1.0]; preset.width * preset.height];
         let mesh = BhpMesh::from_preset(&preset, 15, &colors);
 
-        Some(MeshState {
+        MeshState {
             mesh: Arc::new(mesh),
             image: Arc::new(processed),
             texture_id: next_texture_id(),
             alpha: 1.0,
-        })
+        }
     }
 
     /// 设置专辑封面图像
@@ -690,10 +880,38 @@ impl TexturedBackgroundProgram {
 
         self.mesh_states.push(new_state);
         self.current_image_path = path_key;
+        self.current_palette = None;
         self.has_cover = true;
         true
     }
 
+    /// Show a gradient built from a palette color, for pages whose cover
+    /// isn't decoded yet
+    ///
+    /// Crossfades like a new cover; setting the same color again is a no-op.
+    pub fn set_palette(&mut self, primary: iced::Color) {
+        if self.current_palette == Some(primary) && self.has_cover {
+            return;
+        }
+
+        let shade = |k: f32| {
+            [
+                (primary.r * k).min(1.0),
+                (primary.g * k).min(1.0),
+                (primary.b * k).min(1.0),
+            ]
+        };
+        let mut state = Self::gradient_state([shade(1.15), shade(0.9), shade(0.7), shade(0.45)]);
+        if !self.mesh_states.is_empty() {
+            state.alpha = 0.0;
+        }
+
+        self.mesh_states.push(state);
+        self.current_image_path = None;
+        self.current_palette = Some(primary);
+        self.has_cover = true;
+    }
+
     /// 从文件路径设置专辑封面
     ///
     /// # Returns
@@ -726,6 +944,7 @@ impl TexturedBackgroundProgram {
         if self.has_cover {
             self.has_cover = false;
             self.current_image_path = None;
+            self.current_palette = None;
         }
     }
 
@@ -806,13 +1025,31 @@ impl TexturedBackgroundProgram {
             time: self.time,
             volume: self.smoothed_volume,
             aspect,
+            style: BackgroundStyle::Flow,
         }
     }
 
+    /// This background drawn in `style`, e.g. for the lyrics page or a
+    /// preview of each style
+    pub fn styled(&self, style: BackgroundStyle) -> StyledBackground {
+        let mut primitive = self.primitive(1.0);
+        primitive.style = style;
+        StyledBackground { primitive }
+    }
+
     /// 检查是否有活跃的 mesh
     pub fn is_active(&self) -> bool {
         !self.mesh_states.is_empty()
     }
+
+    /// 封面切换或淡出是否仍在进行
+    pub fn is_transitioning(&self) -> bool {
+        if self.has_cover {
+            self.mesh_states.len() > 1 || self.mesh_states.last().is_some_and(|s| s.alpha < 1.0)
+        } else {
+            !self.mesh_states.is_empty()
+        }
+    }
 }
 
 impl Default for TexturedBackgroundProgram {
@@ -844,3 +1081,26 @@ impl shader::Program<crate::app::Message> for TexturedBackgroundProgram {
         self.primitive(bounds.width / bounds.height)
     }
 }
+
+/// A background frozen in one style, owning what it draws
+#[derive(Debug, Clone)]
+pub struct StyledBackground {
+    primitive: MeshGradientPrimitive,
+}
+
+impl shader::Program<crate::app::Message> for StyledBackground {
+    type State = ();
+    type Primitive = MeshGradientPrimitive;
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        _cursor: iced::mouse::Cursor,
+        bounds: Rectangle,
+    ) -> Self::Primitive {
+        MeshGradientPrimitive {
+            aspect: bounds.width / bounds.height,
+            ..self.primitive.clone()
+        }
+    }
+}
@@ -79,6 +79,21 @@ pub const DOWNLOAD: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
     <path d="M19 9h-4V3H9v6H5l7 7 7-7zM5 18v2h14v-2H5z"/>
 </svg>"#;
 
+/// Upload icon (filled)
+pub const UPLOAD: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M5 4v2h14V4H5zm0 10h4v6h6v-6h4l-7-7-7 7z"/>
+</svg>"#;
+
+/// Export icon (filled)
+pub const EXPORT: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M19 12v7H5v-7H3v7c0 1.1.9 2 2 2h14c1.1 0 2-.9 2-2v-7h-2zm-6 .67l2.59-2.58L17 11.5l-5 5-5-5 1.41-1.41L11 12.67V3h2z"/>
+</svg>"#;
+
+/// Shield with check mark (filled)
+pub const SHIELD_CHECK: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M12 1L3 5v6c0 5.55 3.84 10.74 9 12 5.16-1.26 9-6.45 9-12V5l-9-4zm-2 16l-4-4 1.41-1.41L10 14.17l6.59-6.59L18 9l-8 8z"/>
+</svg>"#;
+
 /// Settings/gear icon (filled)
 pub const SETTINGS: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
     <path d="M19.14 12.94c.04-.31.06-.63.06-.94 0-.31-.02-.63-.06-.94l2.03-1.58c.18-.14.23-.41.12-.61l-1.92-3.32c-.12-.22-.37-.29-.59-.22l-2.39.96c-.5-.38-1.03-.7-1.62-.94l-.36-2.54c-.04-.24-.24-.41-.48-.41h-3.84c-.24 0-.43.17-.47.41l-.36 2.54c-.59.24-1.13.57-1.62.94l-2.39-.96c-.22-.08-.47 0-.59.22L2.74 8.87c-.12.21-.08.47.12.61l2.03 1.58c-.04.31-.06.63-.06.94s.02.63.06.94l-2.03 1.58c-.18.14-.23.41-.12.61l1.92 3.32c.12.22.37.29.59.22l2.39-.96c.5.38 1.03.7 1.62.94l.36 2.54c.05.24.24.41.48.41h3.84c.24 0 .44-.17.47-.41l.36-2.54c.59-.24 1.13-.56 1.62-.94l2.39.96c.22.08.47 0 .59-.22l1.92-3.32c.12-.22.07-.47-.12-.61l-2.01-1.58zM12 15.6c-1.98 0-3.6-1.62-3.6-3.6s1.62-3.6 3.6-3.6 3.6 1.62 3.6 3.6-1.62 3.6-3.6 3.6z"/>
@@ -142,6 +157,43 @@ pub const PLAYING: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
     <rect x="19" y="6" width="3" height="12" rx="1"/>
 </svg>"#;
 
+/// Skip back icon (replay arrow)
+pub const SKIP_BACK: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M12 5V1L7 6l5 5V7c3.31 0 6 2.69 6 6s-2.69 6-6 6-6-2.69-6-6H4c0 4.42 3.58 8 8 8s8-3.58 8-8-3.58-8-8-8z"/>
+</svg>"#;
+
+/// Skip forward icon (mirrored replay arrow)
+pub const SKIP_FORWARD: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M12 5V1l5 5-5 5V7c-3.31 0-6 2.69-6 6s2.69 6 6 6 6-2.69 6-6h2c0 4.42-3.58 8-8 8s-8-3.58-8-8 3.58-8 8-8z"/>
+</svg>"#;
+
+/// Chapter list icon (toc)
+pub const CHAPTERS: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M3 9h14V7H3v2zm0 4h14v-2H3v2zm0 4h14v-2H3v2zm16 0h2v-2h-2v2zm0-10v2h2V7h-2zm0 6h2v-2h-2v2z"/>
+</svg>"#;
+
+/// Previous chapter icon (double chevron left)
+pub const CHAPTER_PREV: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M17.59 18L19 16.59 14.42 12 19 7.41 17.59 6l-6 6z"/>
+    <path d="M11 18l1.41-1.41L7.83 12l4.58-4.59L11 6l-6 6z"/>
+</svg>"#;
+
+/// Next chapter icon (double chevron right)
+pub const CHAPTER_NEXT: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M6.41 6L5 7.41 9.58 12 5 16.59 6.41 18l6-6z"/>
+    <path d="M13 6l-1.41 1.41L16.17 12l-4.58 4.59L13 18l6-6z"/>
+</svg>"#;
+
+/// Stop-after-current icon (stop_circle)
+pub const STOP_AFTER: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M8 16h8V8H8v8zm4-14C6.48 2 2 6.48 2 12s4.48 10 10 10 10-4.48 10-10S17.52 2 12 2zm0 18c-4.41 0-8-3.59-8-8s3.59-8 8-8 8 3.59 8 8-3.59 8-8 8z"/>
+</svg>"#;
+
+/// Block icon (circle with slash)
+pub const BLOCK: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M12 2C6.48 2 2 6.48 2 12s4.48 10 10 10 10-4.48 10-10S17.52 2 12 2zM4 12c0-4.42 3.58-8 8-8 1.85 0 3.55.63 4.9 1.69L5.69 16.9C4.63 15.55 4 13.85 4 12zm8 8c-1.85 0-3.55-.63-4.9-1.69L18.31 7.1C19.37 8.45 20 10.15 20 12c0 4.42-3.58 8-8 8z"/>
+</svg>"#;
+
 /// Close/X icon (filled)
 pub const CLOSE: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
     <path d="M19 6.41L17.59 5 12 10.59 6.41 5 5 6.41 10.59 12 5 17.59 6.41 19 12 13.41 17.59 19 19 17.59 13.41 12z"/>
@@ -163,6 +215,11 @@ pub const LOOP_ONE: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
     <text x="12" y="14.5" font-size="7" text-anchor="middle" font-weight="bold">1</text>
 </svg>"#;
 
+/// Auto-DJ icon (queue with a note)
+pub const AUTO_DJ: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M15 6H3v2h12V6zm0 4H3v2h12v-2zM3 16h8v-2H3v2zM17 6v8.18c-.31-.11-.65-.18-1-.18-1.66 0-3 1.34-3 3s1.34 3 3 3 3-1.34 3-3V8h3V6h-5z"/>
+</svg>"#;
+
 /// Minimize icon (line)
 pub const MINIMIZE: &str = r#"<svg viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round">
     <line x1="5" y1="12" x2="19" y2="12"/>
@@ -189,6 +246,10 @@ pub const EQUALIZER: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
 </svg>"#;
 
 /// Chevron down icon (filled)
+pub const CHEVRON_UP: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M7.41 15.41L12 10.83l4.59 4.58L18 14l-6-6-6 6 1.41 1.41z"/>
+</svg>"#;
+
 pub const CHEVRON_DOWN: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
     <path d="M7.41 8.59L12 13.17l4.59-4.58L18 10l-6 6-6-6 1.41-1.41z"/>
 </svg>"#;
@@ -204,3 +265,18 @@ pub const LOADING: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
     <circle cx="6.34" cy="17.66" r="2" opacity="0.4"/>
     <circle cx="17.66" cy="6.34" r="2" opacity="0.8"/>
 </svg>"#;
+
+/// Open in new window icon
+pub const OPEN_IN_NEW: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M19 19H5V5h7V3H5c-1.11 0-2 .9-2 2v14c0 1.1.89 2 2 2h14c1.1 0 2-.9 2-2v-7h-2v7zM14 3v2h3.59l-9.83 9.83 1.41 1.41L19 6.41V10h2V3h-7z"/>
+</svg>"#;
+
+/// Enter fullscreen icon
+pub const FULLSCREEN: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M7 14H5v5h5v-2H7v-3zm-2-4h2V7h3V5H5v5zm12 7h-3v2h5v-5h-2v3zM14 5v2h3v3h2V5h-5z"/>
+</svg>"#;
+
+/// Exit fullscreen icon
+pub const FULLSCREEN_EXIT: &str = r#"<svg viewBox="0 0 24 24" fill="currentColor">
+    <path d="M5 16h3v3h2v-5H5v2zm3-8H5v2h5V5H8v3zm6 11h2v-3h3v-2h-5v5zm2-11V5h-2v5h5V8h-3z"/>
+</svg>"#;
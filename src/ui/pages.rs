@@ -1,13 +1,20 @@
 //! Pages module
 //! Full-page views for the music streaming application
 
+pub mod annual_report;
 pub mod audio_engine;
+pub mod blocklist;
 pub mod discover;
 pub mod home;
+pub mod logs;
 pub mod lyrics;
 pub mod playlist;
+pub mod plugins;
+pub mod profile;
 pub mod search;
 pub mod settings;
+pub mod settings_search;
+pub mod trash;
 
-pub use lyrics::{LyricLine, LyricWord, find_current_line};
+pub use lyrics::{LyricLine, LyricWord, available_sub_lines, find_current_line};
 pub use playlist::{PlaylistSongView, PlaylistView}; // PlaylistSongView used by app when loading playlists
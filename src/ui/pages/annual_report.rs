@@ -0,0 +1,167 @@
+//! Year-in-review page
+//!
+//! Shows the annual report cards built from local play history with a
+//! year picker and the image export action.
+
+use iced::widget::{Space, button, column, container, pick_list, row, scrollable, text};
+use iced::{Alignment, Background, Color, Element, Fill, Font, Padding};
+
+use crate::app::{AnnualReportPageState, Message};
+use crate::features::annual_report::ReportCard;
+use crate::features::annual_report::render::GRADIENTS;
+use crate::i18n::{Key, Locale};
+use crate::ui::theme;
+
+/// On-screen card size, the export's 4:5 ratio scaled down
+const CARD_WIDTH: f32 = 320.0;
+const CARD_HEIGHT: f32 = 400.0;
+
+/// Year-in-review page view
+pub fn view(state: &AnnualReportPageState, locale: Locale) -> Element<'static, Message> {
+    let header = column![
+        text(locale.get(Key::AnnualReportTitle).to_string())
+            .size(28)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme)),
+            }),
+        Space::new().height(6),
+        text(locale.get(Key::AnnualReportDesc).to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+    ];
+
+    let export_label = if state.exporting {
+        locale.get(Key::AnnualReportExporting)
+    } else {
+        locale.get(Key::AnnualReportExport)
+    };
+    let can_export = !state.exporting && !state.cards.is_empty();
+
+    let actions = row![
+        pick_list(
+            state.years.clone(),
+            Some(state.year),
+            Message::SelectAnnualReportYear
+        )
+        .style(theme::settings_pick_list)
+        .menu_style(theme::settings_pick_list_menu)
+        .padding([8, 12]),
+        button(text(export_label.to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press_maybe(can_export.then_some(Message::ExportAnnualReport)),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    let header_container =
+        container(row![header, Space::new().width(Fill), actions].align_y(Alignment::Center))
+            .width(Fill)
+            .padding(
+                Padding::new(40.0)
+                    .top(70.0)
+                    .right(32.0)
+                    .bottom(20.0)
+                    .left(32.0),
+            )
+            .style(|theme| container::Style {
+                background: Some(Background::Color(theme::background(theme))),
+                ..Default::default()
+            });
+
+    let content: Element<'static, Message> = if state.cards.is_empty() {
+        let label = if state.loading {
+            locale.get(Key::Loading).to_string()
+        } else {
+            locale
+                .get(Key::AnnualReportEmpty)
+                .replace("{}", &state.year.to_string())
+        };
+        container(text(label).size(14).style(|theme| text::Style {
+            color: Some(theme::settings_desc(theme)),
+        }))
+        .width(Fill)
+        .height(120)
+        .center_x(Fill)
+        .center_y(120)
+        .into()
+    } else {
+        row(state
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(i, card)| report_card(card, i)))
+        .spacing(24)
+        .wrap()
+        .vertical_spacing(24)
+        .into()
+    };
+
+    let scrollable_content = scrollable(
+        container(content)
+            .width(Fill)
+            .padding(Padding::new(20.0).right(32.0).bottom(60.0).left(32.0)),
+    )
+    .id(iced::widget::Id::new("annual_report_scroll"))
+    .width(Fill)
+    .height(Fill);
+
+    container(
+        column![header_container, scrollable_content]
+            .width(Fill)
+            .height(Fill),
+    )
+    .width(Fill)
+    .height(Fill)
+    .style(theme::main_content)
+    .into()
+}
+
+/// One card with the same gradient as its exported image
+fn report_card(card: &ReportCard, index: usize) -> Element<'static, Message> {
+    let (top, bottom) = GRADIENTS[index % GRADIENTS.len()];
+    let rgb = |c: [u8; 3]| Color::from_rgb8(c[0], c[1], c[2]);
+    let (top, bottom) = (rgb(top), rgb(bottom));
+    let muted = Color::from_rgba(1.0, 1.0, 1.0, 0.8);
+
+    let mut content = column![
+        text(card.title.clone()).size(16).color(muted),
+        Space::new().height(12),
+        text(card.headline.clone())
+            .size(36)
+            .color(Color::WHITE)
+            .font(Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+        Space::new().height(20),
+    ];
+    for (primary, secondary) in &card.rows {
+        let mut entry = column![text(primary.clone()).size(15).color(Color::WHITE)];
+        if !secondary.is_empty() {
+            entry = entry.push(text(secondary.clone()).size(12).color(muted));
+        }
+        content = content.push(entry.spacing(2)).push(Space::new().height(12));
+    }
+
+    container(content)
+        .width(CARD_WIDTH)
+        .height(CARD_HEIGHT)
+        .padding(28)
+        .clip(true)
+        .style(move |_theme| container::Style {
+            background: Some(Background::Gradient(iced::Gradient::Linear(
+                iced::gradient::Linear::new(iced::Radians(std::f32::consts::PI))
+                    .add_stop(0.0, top)
+                    .add_stop(1.0, bottom),
+            ))),
+            border: iced::Border {
+                radius: 16.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
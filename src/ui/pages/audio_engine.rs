@@ -5,15 +5,21 @@
 //! - EQ curve visualization using Canvas
 //! - Preset selection
 //! - Preamp control
+//! - Headphone DSP stages (crossfeed, bass boost, night mode)
+//! - Hi-res output sample rate matching
+//! - Output latency with tap-along calibration
 //! - Professional spectrum analyzer (FFT-based)
 
 use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke, Text};
-use iced::widget::{column, container, pick_list, row, scrollable, text, toggler, Space};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, slider, text, toggler, Space,
+};
 use iced::{Alignment, Background, Color, Element, Fill, Length, Padding, Point, Theme};
 
 use crate::app::Message;
 use crate::audio::{analyzer::FFT_SIZE, AudioAnalysisData};
-use crate::features::{EqualizerPreset, Settings};
+use crate::features::latency::{Calibration, MAX_LATENCY_MS, MIN_TAPS};
+use crate::features::{DspPresets, EqualizerPreset, Settings};
 use crate::i18n::{Key, Locale};
 use crate::ui::theme;
 use crate::ui::widgets::vertical_slider;
@@ -51,6 +57,8 @@ pub fn view(
     settings: &Settings,
     locale: Locale,
     analysis_data: Option<&AudioAnalysisData>,
+    calibration: Option<&Calibration>,
+    sample_rates: Option<(u32, u32)>,
 ) -> Element<'static, Message> {
     // Header with just title
     let header = text(locale.get(Key::AudioEngineTitle).to_string())
@@ -93,6 +101,15 @@ pub fn view(
         // Equalizer section
         equalizer_section(settings, locale),
         Space::new().height(40),
+        // Headphone DSP section
+        dsp_section(settings, locale),
+        Space::new().height(40),
+        // Output sample rate section
+        sample_rate_section(settings, sample_rates, locale),
+        Space::new().height(40),
+        // Output latency section
+        latency_section(settings, calibration, locale),
+        Space::new().height(40),
         // Audio visualization section
         audio_visualization_section(
             left_level,
@@ -151,7 +168,7 @@ fn equalizer_section(settings: &Settings, locale: Locale) -> Element<'static, Me
                 color: Some(theme::settings_desc(theme))
             }),
         Space::new().width(Fill),
-        preset_picker(eq_preset),
+        preset_picker(eq_preset, locale),
     ]
     .align_y(Alignment::Center)
     .width(Fill);
@@ -190,6 +207,255 @@ fn equalizer_section(settings: &Settings, locale: Locale) -> Element<'static, Me
         .into()
 }
 
+/// Headphone DSP stages, remembered for the current output device
+fn dsp_section(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let presets = settings.dsp_presets();
+    let device = settings
+        .system
+        .audio_output_device
+        .clone()
+        .unwrap_or_else(|| locale.get(Key::SettingsDefaultDevice).to_string());
+
+    let title_row = row![
+        text(locale.get(Key::AudioEngineDsp).to_string())
+            .size(18)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme))
+            }),
+        Space::new().width(Fill),
+        text(locale.get(Key::AudioEngineDspDevice).replace("{}", &device))
+            .size(13)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme))
+            }),
+    ]
+    .align_y(Alignment::Center)
+    .width(Fill);
+
+    column![
+        title_row,
+        Space::new().height(12),
+        dsp_stage_row(
+            locale.get(Key::AudioEngineCrossfeed),
+            locale.get(Key::AudioEngineCrossfeedDesc),
+            presets.crossfeed,
+            move |on| DspPresets {
+                crossfeed: on,
+                ..presets
+            },
+        ),
+        dsp_stage_row(
+            locale.get(Key::AudioEngineBassBoost),
+            locale.get(Key::AudioEngineBassBoostDesc),
+            presets.bass_boost,
+            move |on| DspPresets {
+                bass_boost: on,
+                ..presets
+            },
+        ),
+        dsp_stage_row(
+            locale.get(Key::AudioEngineNightMode),
+            locale.get(Key::AudioEngineNightModeDesc),
+            presets.night_mode,
+            move |on| DspPresets {
+                night_mode: on,
+                ..presets
+            },
+        ),
+    ]
+    .spacing(0)
+    .width(Fill)
+    .into()
+}
+
+/// One toggleable DSP stage
+fn dsp_stage_row(
+    label: &'static str,
+    description: &'static str,
+    enabled: bool,
+    with: impl Fn(bool) -> DspPresets + 'static,
+) -> Element<'static, Message> {
+    row![
+        column![
+            text(label).size(15).style(|theme| text::Style {
+                color: Some(theme::settings_label(theme))
+            }),
+            text(description).size(12).style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme))
+            }),
+        ]
+        .spacing(4),
+        Space::new().width(Fill),
+        toggler(enabled)
+            .on_toggle(move |on| Message::UpdateDspPresets(with(on)))
+            .size(18),
+    ]
+    .align_y(Alignment::Center)
+    .padding([12, 0])
+    .width(Fill)
+    .into()
+}
+
+/// "96 kHz" or "44.1 kHz"
+fn format_rate(rate: u32) -> String {
+    if rate % 1000 == 0 {
+        format!("{} kHz", rate / 1000)
+    } else {
+        format!("{:.1} kHz", rate as f32 / 1000.0)
+    }
+}
+
+/// Hi-res sample rate matching, and the rates the current track plays at
+fn sample_rate_section(
+    settings: &Settings,
+    sample_rates: Option<(u32, u32)>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let status = match sample_rates {
+        Some((0, output)) if output > 0 => format_rate(output),
+        Some((source, output)) if output > 0 => {
+            let rates = format!("{} → {}", format_rate(source), format_rate(output));
+            let key = if source == output {
+                Key::AudioEngineSampleRateNative
+            } else {
+                Key::AudioEngineSampleRateResampled
+            };
+            locale.get(key).replace("{}", &rates)
+        }
+        _ => String::new(),
+    };
+
+    let title_row = row![
+        text(locale.get(Key::AudioEngineSampleRate).to_string())
+            .size(18)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme))
+            }),
+        Space::new().width(Fill),
+        text(status).size(13).style(|theme| text::Style {
+            color: Some(theme::settings_value(theme))
+        }),
+    ]
+    .align_y(Alignment::Center)
+    .width(Fill);
+
+    let toggle_row = row![
+        column![
+            text(locale.get(Key::AudioEngineMatchSampleRate))
+                .size(15)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_label(theme))
+                }),
+            text(locale.get(Key::AudioEngineMatchSampleRateDesc))
+                .size(12)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_desc(theme))
+                }),
+        ]
+        .spacing(4),
+        Space::new().width(Fill),
+        toggler(settings.playback.match_sample_rate)
+            .on_toggle(Message::UpdateMatchSampleRate)
+            .size(18),
+    ]
+    .align_y(Alignment::Center)
+    .padding([12, 0])
+    .width(Fill);
+
+    column![title_row, Space::new().height(12), toggle_row]
+        .spacing(0)
+        .width(Fill)
+        .into()
+}
+
+/// Output latency for the current device, and the calibration that measures it
+fn latency_section(
+    settings: &Settings,
+    calibration: Option<&Calibration>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let latency_ms = settings.output_latency_ms();
+
+    let title_row = row![
+        text(locale.get(Key::AudioEngineLatency).to_string())
+            .size(18)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme))
+            }),
+        Space::new().width(Fill),
+        text(format!("{} ms", latency_ms))
+            .size(13)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_value(theme))
+            }),
+        Space::new().width(12),
+        slider(0.0..=MAX_LATENCY_MS as f32, latency_ms as f32, |ms| {
+            Message::UpdateOutputLatency(ms as u32)
+        })
+        .step(10.0)
+        .width(Length::Fixed(180.0)),
+    ]
+    .align_y(Alignment::Center)
+    .width(Fill);
+
+    let description = text(locale.get(Key::AudioEngineLatencyDesc).to_string())
+        .size(12)
+        .style(|theme| text::Style {
+            color: Some(theme::settings_desc(theme)),
+        });
+
+    let controls: Element<'static, Message> = match calibration {
+        None => button(text(locale.get(Key::LatencyCalibrate).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::StartLatencyCalibration)
+            .into(),
+        Some(calibration) => {
+            let taps = calibration.taps();
+            let done = button(text(locale.get(Key::LatencyCalibrationFinish).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press_maybe((taps >= MIN_TAPS).then_some(Message::FinishLatencyCalibration));
+            row![
+                button(text(locale.get(Key::LatencyCalibrationTap).to_string()).size(16))
+                    .style(theme::primary_button)
+                    .padding([12, 32])
+                    .on_press(Message::LatencyCalibrationTap),
+                text(
+                    locale
+                        .get(Key::LatencyCalibrationHint)
+                        .replace("{}", &taps.to_string())
+                )
+                .size(13)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_desc(theme))
+                }),
+                Space::new().width(Fill),
+                done,
+                button(text(locale.get(Key::Cancel).to_string()).size(14))
+                    .style(theme::secondary_button)
+                    .padding([8, 16])
+                    .on_press(Message::CancelLatencyCalibration),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center)
+            .width(Fill)
+            .into()
+        }
+    };
+
+    column![
+        title_row,
+        Space::new().height(4),
+        description,
+        Space::new().height(16),
+        controls
+    ]
+    .spacing(0)
+    .width(Fill)
+    .into()
+}
+
 /// Audio visualization section with spectrum analyzer
 fn audio_visualization_section(
     left_level: f32,
@@ -216,7 +482,7 @@ fn audio_visualization_section(
         });
 
     // Mode dropdown (bars/line) - like equalizer preset picker
-    let mode_picker = spectrum_mode_picker(bars_mode);
+    let mode_picker = spectrum_mode_picker(bars_mode, locale);
 
     // Spectrum analyzer canvas (main visualization)
     let spectrum_height = 280.0;
@@ -286,44 +552,35 @@ fn volume_meter_view(label: &'static str, level: f32, height: f32) -> Element<'s
 }
 
 /// Preset picker dropdown
-fn preset_picker(current: EqualizerPreset) -> Element<'static, Message> {
-    let presets: Vec<EqualizerPreset> = EqualizerPreset::all().to_vec();
-
-    pick_list(presets, Some(current), Message::UpdateEqualizerPreset)
-        .text_size(14)
-        .padding([8, 16])
-        .style(theme::settings_pick_list)
-        .menu_style(theme::settings_pick_list_menu)
-        .into()
-}
-
-/// Spectrum display mode
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SpectrumMode {
-    Bars,
-    Line,
-}
+fn preset_picker(current: EqualizerPreset, locale: Locale) -> Element<'static, Message> {
+    let labels: Vec<&'static str> = EqualizerPreset::all()
+        .iter()
+        .map(|preset| preset.display_name(locale))
+        .collect();
 
-impl std::fmt::Display for SpectrumMode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SpectrumMode::Bars => write!(f, "柱状"),
-            SpectrumMode::Line => write!(f, "曲线"),
-        }
-    }
+    pick_list(labels, Some(current.display_name(locale)), move |label| {
+        let preset = EqualizerPreset::all()
+            .iter()
+            .copied()
+            .find(|preset| preset.display_name(locale) == label)
+            .unwrap_or_default();
+        Message::UpdateEqualizerPreset(preset)
+    })
+    .text_size(14)
+    .padding([8, 16])
+    .style(theme::settings_pick_list)
+    .menu_style(theme::settings_pick_list_menu)
+    .into()
 }
 
 /// Spectrum mode picker dropdown
-fn spectrum_mode_picker(bars_mode: bool) -> Element<'static, Message> {
-    let modes = vec![SpectrumMode::Bars, SpectrumMode::Line];
-    let current = if bars_mode {
-        SpectrumMode::Bars
-    } else {
-        SpectrumMode::Line
-    };
+fn spectrum_mode_picker(bars_mode: bool, locale: Locale) -> Element<'static, Message> {
+    let bars = locale.get(Key::AudioEngineSpectrumBars);
+    let line = locale.get(Key::AudioEngineSpectrumLine);
+    let current = if bars_mode { bars } else { line };
 
-    pick_list(modes, Some(current), |mode| {
-        Message::UpdateSpectrumBarsMode(mode == SpectrumMode::Bars)
+    pick_list(vec![bars, line], Some(current), move |mode| {
+        Message::UpdateSpectrumBarsMode(mode == bars)
     })
     .text_size(14)
     .padding([8, 16])
@@ -0,0 +1,205 @@
+//! Blocklist manager page
+//!
+//! Lists blocked artists and songs with an unblock button each, and lets
+//! the user block an artist by name.
+
+use iced::widget::{Space, button, column, container, row, scrollable, text, text_input};
+use iced::{Alignment, Background, Border, Element, Fill, Padding};
+
+use crate::app::{BlocklistPageState, Message};
+use crate::features::blocklist::Blocklist;
+use crate::i18n::{Key, Locale};
+use crate::ui::theme;
+
+/// Blocklist manager page view
+pub fn view(
+    blocklist: &Blocklist,
+    state: &BlocklistPageState,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let header = column![
+        text(locale.get(Key::BlocklistTitle).to_string())
+            .size(28)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme)),
+            }),
+        Space::new().height(6),
+        text(locale.get(Key::BlocklistDesc).to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+    ];
+
+    let can_add = !state.artist_input.trim().is_empty();
+    let actions = row![
+        text_input(
+            locale.get(Key::BlocklistArtistPlaceholder),
+            &state.artist_input
+        )
+        .on_input(Message::BlockArtistInputChanged)
+        .on_submit_maybe(can_add.then_some(Message::BlockArtist))
+        .padding([8, 12])
+        .width(220)
+        .style(|theme, status| {
+            let border_color = match status {
+                text_input::Status::Focused { .. } => theme::ACCENT_PINK,
+                text_input::Status::Hovered => theme::settings_input_border_hover(theme),
+                _ => theme::settings_input_border(theme),
+            };
+            text_input::Style {
+                background: Background::Color(theme::settings_input_bg(theme)),
+                border: Border {
+                    color: border_color,
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                icon: theme::settings_desc(theme),
+                placeholder: theme::settings_desc(theme),
+                value: theme::settings_label(theme),
+                selection: theme::ACCENT_PINK,
+            }
+        }),
+        button(text(locale.get(Key::BlocklistBlockArtist).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press_maybe(can_add.then_some(Message::BlockArtist)),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    let header_container =
+        container(row![header, Space::new().width(Fill), actions].align_y(Alignment::Center))
+            .width(Fill)
+            .padding(
+                Padding::new(40.0)
+                    .top(70.0)
+                    .right(32.0)
+                    .bottom(20.0)
+                    .left(32.0),
+            )
+            .style(|theme| container::Style {
+                background: Some(Background::Color(theme::background(theme))),
+                ..Default::default()
+            });
+
+    let artists = blocklist.artists().iter().map(|name| {
+        entry(
+            name.clone(),
+            None,
+            Message::UnblockArtist(name.clone()),
+            locale,
+        )
+    });
+    let songs = blocklist.songs().iter().map(|song| {
+        entry(
+            song.title.clone(),
+            Some(song.artist.clone()).filter(|artist| !artist.is_empty()),
+            Message::UnblockSong(song.id),
+            locale,
+        )
+    });
+
+    let content = column![
+        section(
+            locale.get(Key::BlocklistArtists),
+            locale.get(Key::BlocklistNoArtists),
+            artists.collect(),
+        ),
+        section(
+            locale.get(Key::BlocklistSongs),
+            locale.get(Key::BlocklistNoSongs),
+            songs.collect(),
+        ),
+    ]
+    .spacing(32)
+    .width(Fill);
+
+    let scrollable_content = scrollable(
+        container(content)
+            .width(Fill)
+            .padding(Padding::new(20.0).right(32.0).bottom(60.0).left(32.0)),
+    )
+    .id(iced::widget::Id::new("blocklist_scroll"))
+    .width(Fill)
+    .height(Fill);
+
+    container(
+        column![header_container, scrollable_content]
+            .width(Fill)
+            .height(Fill),
+    )
+    .width(Fill)
+    .height(Fill)
+    .style(theme::main_content)
+    .into()
+}
+
+/// Titled list, or a hint when it is empty
+fn section(
+    title: &str,
+    empty: &str,
+    entries: Vec<Element<'static, Message>>,
+) -> Element<'static, Message> {
+    let body: Element<'static, Message> = if entries.is_empty() {
+        text(empty.to_string())
+            .size(13)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            })
+            .into()
+    } else {
+        column(entries).spacing(8).width(Fill).into()
+    };
+
+    column![
+        text(title.to_string()).size(16).style(|theme| text::Style {
+            color: Some(theme::settings_label(theme)),
+        }),
+        body,
+    ]
+    .spacing(12)
+    .width(Fill)
+    .into()
+}
+
+/// Single blocked entry with its unblock button
+fn entry(
+    title: String,
+    subtitle: Option<String>,
+    on_unblock: Message,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let mut label = column![text(title).size(14).style(|theme| text::Style {
+        color: Some(theme::settings_label(theme)),
+    })]
+    .spacing(2);
+    if let Some(subtitle) = subtitle {
+        label = label.push(text(subtitle).size(12).style(|theme| text::Style {
+            color: Some(theme::settings_desc(theme)),
+        }));
+    }
+
+    container(
+        row![
+            label,
+            Space::new().width(Fill),
+            button(text(locale.get(Key::BlocklistUnblock).to_string()).size(13))
+                .style(theme::secondary_button)
+                .padding([6, 12])
+                .on_press(on_unblock),
+        ]
+        .align_y(Alignment::Center),
+    )
+    .width(Fill)
+    .padding([12, 16])
+    .style(|theme| container::Style {
+        background: Some(Background::Color(theme::surface_container(theme))),
+        border: Border {
+            radius: 12.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
@@ -1,17 +1,23 @@
 //! Discover page - Browse recommended and hot playlists
 //!
-//! Displays personalized recommendations (for logged-in users) and
-//! hot playlists in a modern grid layout.
+//! Displays new releases from followed artists, personalized
+//! recommendations (for logged-in users) and hot playlists in a modern
+//! grid layout.
 
-use iced::widget::{button, column, container, scrollable, text, Space};
-use iced::{Element, Fill, Padding};
+use iced::widget::{button, column, container, row, scrollable, svg, text, Space};
+use iced::{Alignment, Element, Fill, Padding};
 
 use crate::app::{DiscoverPageState, DiscoverViewMode, Message};
 use crate::i18n::{Key, Locale};
 use crate::ui::components::playlist_grid;
+use crate::ui::icons;
 use crate::ui::theme;
 use crate::ui::widgets::section_header;
 
+/// Distance from the bottom of the hot playlist grid at which the next page
+/// starts loading
+const LOAD_MORE_DISTANCE: f32 = 600.0;
+
 /// Build the discover page view
 pub fn view<'a>(
     state: &'a DiscoverPageState,
@@ -34,6 +40,23 @@ fn view_overview<'a>(
     let mut content_items: Vec<Element<'a, Message>> = Vec::new();
     let content_width = state.content_width;
 
+    // New releases from followed artists
+    if !state.new_releases.is_empty() {
+        content_items.push(section_header::view(
+            locale.get(Key::DiscoverNewReleases),
+            "",
+            None,
+        ));
+        content_items.push(Space::new().height(16).into());
+        content_items.push(playlist_grid::releases_row(
+            &state.new_releases,
+            &state.release_covers,
+            &state.card_animations,
+            content_width,
+        ));
+        content_items.push(Space::new().height(40).into());
+    }
+
     // Recommended playlists section
     if is_logged_in && !state.recommended_playlists.is_empty() {
         content_items.push(section_header::view(
@@ -144,43 +167,76 @@ fn view_all_hot<'a>(state: &'a DiscoverPageState, locale: Locale) -> Element<'a,
         ),
     ];
 
-    // Load more button if there are more playlists
+    // Footer row while more playlists remain: a spinner while the next page
+    // loads, a retry after a failure, otherwise a manual load button for
+    // when the grid is too short to scroll
     if state.hot_has_more {
-        let load_more_btn = button(
-            text(if state.hot_loading {
-                "加载中..."
-            } else {
-                "加载更多"
-            })
-            .size(14),
-        )
-        .padding(Padding::new(12.0).left(24.0).right(24.0))
-        .style(theme::secondary_button)
-        .on_press_maybe(if state.hot_loading {
-            None
+        let footer: Element<'a, Message> = if state.hot_loading {
+            row![
+                svg(svg::Handle::from_memory(icons::LOADING.as_bytes()))
+                    .width(16)
+                    .height(16)
+                    .style(|theme, _status| svg::Style {
+                        color: Some(theme::text_muted(theme)),
+                    }),
+                text(locale.get(Key::Loading))
+                    .size(14)
+                    .color(theme::TEXT_MUTED),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .into()
+        } else if state.hot_failed {
+            row![
+                text(locale.get(Key::DiscoverLoadMoreFailed))
+                    .size(14)
+                    .color(theme::TEXT_MUTED),
+                button(text(locale.get(Key::DiscoverRetry)).size(14))
+                    .padding(Padding::new(8.0).left(18.0).right(18.0))
+                    .style(theme::secondary_button)
+                    .on_press(Message::LoadMoreHotPlaylists),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center)
+            .into()
         } else {
-            Some(Message::LoadMoreHotPlaylists)
-        });
+            button(text(locale.get(Key::DiscoverLoadMore)).size(14))
+                .padding(Padding::new(12.0).left(24.0).right(24.0))
+                .style(theme::secondary_button)
+                .on_press(Message::LoadMoreHotPlaylists)
+                .into()
+        };
 
         content_items.push(Space::new().height(24).into());
-        content_items.push(
-            container(load_more_btn)
-                .width(Fill)
-                .align_x(iced::Alignment::Center)
-                .into(),
-        );
+        content_items.push(container(footer).width(Fill).center_x(Fill).into());
     }
 
     content_items.push(Space::new().height(40).into());
 
     let content = column(content_items).padding(32);
 
-    let scrollable_content = scrollable(content)
+    let mut scrollable_content = scrollable(content)
         .width(Fill)
         .height(Fill)
         .id(iced::widget::Id::new("discover_scroll"))
         .style(theme::dark_scrollable);
 
+    // Fetch the next page as the grid nears the bottom. After a failure
+    // only the retry button loads again, so scrolling doesn't hammer a
+    // failing request
+    if state.hot_has_more && !state.hot_loading && !state.hot_failed {
+        scrollable_content = scrollable_content.on_scroll(|viewport| {
+            let remaining = viewport.content_bounds().height
+                - viewport.bounds().height
+                - viewport.absolute_offset().y;
+            if remaining < LOAD_MORE_DISTANCE {
+                Message::LoadMoreHotPlaylists
+            } else {
+                Message::NoOp
+            }
+        });
+    }
+
     container(scrollable_content)
         .width(Fill)
         .height(Fill)
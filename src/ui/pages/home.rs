@@ -1,26 +1,105 @@
 //! Home page - "Home" view
-//! Main content area with carousel banner and trending songs from NCM API
+//! Main content area with carousel banner, trending songs and the other
+//! homepage sections, in the order and visibility the user picked
 
-use iced::widget::{column, container, scrollable, Space};
-use iced::{Element, Fill, Padding};
+use iced::widget::{Space, button, column, container, row, scrollable, svg, text, toggler};
+use iced::{Alignment, Element, Fill, Padding};
 
-use crate::app::HomePageState;
 use crate::app::Message;
-use crate::i18n::Locale;
-use crate::ui::components;
+use crate::app::{DiscoverPageState, HomePageState};
+use crate::database::DbSong;
+use crate::features::home_layout::{HomeLayout, HomeSection};
+use crate::i18n::{Key, Locale};
+use crate::ui::components::{self, LibraryItem, playlist_grid};
 use crate::ui::theme;
+use crate::ui::widgets::section_header;
+
+/// Playlist cards shown per homepage grid section
+const GRID_ITEMS: usize = 8;
 
 /// Build the home page view with NCM data
 pub fn view<'a>(
     _search_query: &'a str,
     home_state: &'a HomePageState,
+    discover_state: &'a DiscoverPageState,
+    layout: &HomeLayout,
     locale: Locale,
     is_logged_in: bool,
 ) -> Element<'a, Message> {
-    // Main scrollable content
-    let content = column![
+    let customize_label = if home_state.customizing {
+        Key::HomeCustomizeDone
+    } else {
+        Key::HomeCustomize
+    };
+    let toolbar = row![
+        Space::new().width(Fill),
+        button(
+            text(locale.get(customize_label))
+                .size(14)
+                .color(theme::ACCENT)
+        )
+        .style(theme::text_button)
+        .on_press(Message::ToggleHomeCustomize),
+    ]
+    .align_y(Alignment::Center);
+
+    let mut content = column![toolbar].padding(Padding::new(24.0).top(50.0));
+
+    let entries = layout.sections();
+    for (index, entry) in entries.iter().enumerate() {
+        let body = if entry.enabled {
+            section_view(
+                entry.section,
+                home_state,
+                discover_state,
+                locale,
+                is_logged_in,
+            )
+        } else {
+            None
+        };
+
+        if home_state.customizing {
+            content = content.push(customize_bar(
+                entry.section,
+                entry.enabled,
+                index == 0,
+                index + 1 == entries.len(),
+                locale,
+            ));
+            content = content.push(Space::new().height(12));
+        }
+        if let Some(body) = body {
+            content = content.push(body);
+            content = content.push(Space::new().height(40));
+        }
+    }
+
+    let scrollable_content = scrollable(content)
+        .width(Fill)
+        .height(Fill)
+        .id(iced::widget::Id::new("home_scroll"))
+        .style(theme::dark_scrollable);
+
+    // Compose page
+    container(scrollable_content)
+        .width(Fill)
+        .height(Fill)
+        .style(theme::main_content)
+        .into()
+}
+
+/// One section's content, or `None` when it has nothing to show
+fn section_view<'a>(
+    section: HomeSection,
+    home_state: &'a HomePageState,
+    discover_state: &'a DiscoverPageState,
+    locale: Locale,
+    is_logged_in: bool,
+) -> Option<Element<'a, Message>> {
+    match section {
         // Carousel banner from NCM API
-        components::carousel_banner::view(
+        HomeSection::Banners => Some(components::carousel_banner::view(
             &home_state.banners,
             &home_state.banner_images,
             home_state.current_banner,
@@ -29,30 +108,180 @@ pub fn view<'a>(
             home_state.carousel_direction,
             locale,
             is_logged_in,
-        ),
-        Space::new().height(32),
+        )),
         // Trending songs section (飙升榜)
-        components::trending_list::view(
+        HomeSection::Trending => Some(components::trending_list::view(
+            locale.get(Key::TrendingSongs),
+            Message::OpenTrendingSongs,
             &home_state.trending_songs,
             &home_state.song_covers,
             &home_state.song_hover_animations,
             locale,
             is_logged_in,
+        )),
+        HomeSection::TopPicks => playlist_section(
+            locale.get(Key::HomeSectionTopPicks),
+            Some(Message::SeeAllHot),
+            &home_state.top_picks,
+            discover_state,
+            locale,
         ),
-        Space::new().height(40),
-    ]
-    .padding(Padding::new(24.0).top(50.0));
+        HomeSection::Toplists => playlist_section(
+            locale.get(Key::HomeSectionToplists),
+            None,
+            &home_state.toplists,
+            discover_state,
+            locale,
+        ),
+        // Daily recommendations only exist for a logged-in account
+        HomeSection::DailyRecommend if is_logged_in => Some(components::trending_list::view(
+            locale.get(Key::DiscoverDailyRecommend),
+            Message::OpenNcmPlaylist(0),
+            &home_state.daily_songs,
+            &home_state.song_covers,
+            &home_state.song_hover_animations,
+            locale,
+            is_logged_in,
+        )),
+        HomeSection::DailyRecommend => None,
+        HomeSection::RecentlyPlayed => Some(recent_section(&home_state.recent_songs, locale)),
+    }
+}
 
-    let scrollable_content = scrollable(content)
-        .width(Fill)
-        .height(Fill)
-        .id(iced::widget::Id::new("home_scroll"))
-        .style(theme::dark_scrollable);
+/// Playlist card grid under a section header
+fn playlist_section<'a>(
+    title: &'static str,
+    on_see_all: Option<Message>,
+    playlists: &'a [crate::api::SongList],
+    discover_state: &'a DiscoverPageState,
+    locale: Locale,
+) -> Option<Element<'a, Message>> {
+    if playlists.is_empty() {
+        return None;
+    }
+    Some(
+        column![
+            section_header::view(title, locale.get(Key::SeeAll), on_see_all),
+            Space::new().height(16),
+            playlist_grid::view(
+                playlists,
+                &discover_state.playlist_covers,
+                &discover_state.card_animations,
+                Some(GRID_ITEMS),
+                discover_state.content_width,
+            ),
+        ]
+        .into(),
+    )
+}
 
-    // Compose page
-    container(scrollable_content)
-        .width(Fill)
-        .height(Fill)
-        .style(theme::main_content)
+/// Recently played songs as compact rows
+fn recent_section(songs: &[DbSong], locale: Locale) -> Element<'_, Message> {
+    let header = section_header::view(
+        locale.get(Key::LibraryRecentlyPlayed),
+        locale.get(Key::SeeAll),
+        Some(Message::LibrarySelect(LibraryItem::RecentlyPlayed)),
+    );
+
+    let body: Element<'_, Message> = if songs.is_empty() {
+        text(locale.get(Key::HomeRecentEmpty))
+            .size(14)
+            .color(theme::TEXT_SECONDARY)
+            .into()
+    } else {
+        column(songs.iter().map(|song| -> Element<'_, Message> {
+            button(
+                row![
+                    text(&song.title).size(14).style(|theme| text::Style {
+                        color: Some(theme::text_primary(theme)),
+                    }),
+                    Space::new().width(12),
+                    text(&song.artist).size(12).color(theme::TEXT_SECONDARY),
+                ]
+                .align_y(Alignment::Center),
+            )
+            .width(Fill)
+            .padding([10, 12])
+            .style(theme::nav_item)
+            .on_press(Message::PlaySong(song.id))
+            .into()
+        }))
+        .spacing(2)
         .into()
+    };
+
+    column![header, Space::new().height(12), body].into()
+}
+
+/// Show/hide toggle and reorder buttons for a section in customization mode
+fn customize_bar(
+    section: HomeSection,
+    enabled: bool,
+    is_first: bool,
+    is_last: bool,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let arrow = |icon: &'static str, offset: isize, disabled: bool| {
+        let btn = button(
+            svg(svg::Handle::from_memory(icon.as_bytes()))
+                .width(18)
+                .height(18)
+                .style(|theme, _status| svg::Style {
+                    color: Some(theme::text_primary(theme)),
+                }),
+        )
+        .padding(6)
+        .style(theme::icon_button);
+        if disabled {
+            btn
+        } else {
+            btn.on_press(Message::MoveHomeSection(section, offset))
+        }
+    };
+
+    let label = text(locale.get(section_key(section)))
+        .size(15)
+        .style(move |theme| text::Style {
+            color: Some(if enabled {
+                theme::text_primary(theme)
+            } else {
+                theme::TEXT_SECONDARY
+            }),
+        });
+
+    container(
+        row![
+            label,
+            Space::new().width(Fill),
+            arrow(crate::ui::icons::CHEVRON_UP, -1, is_first),
+            arrow(crate::ui::icons::CHEVRON_DOWN, 1, is_last),
+            Space::new().width(8),
+            toggler(enabled)
+                .on_toggle(move |_| Message::ToggleHomeSection(section))
+                .size(20),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+    )
+    .padding([8, 16])
+    .style(|theme| container::Style {
+        background: Some(theme::hover_bg(theme).into()),
+        border: iced::Border {
+            radius: 8.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+fn section_key(section: HomeSection) -> Key {
+    match section {
+        HomeSection::Banners => Key::HomeSectionBanners,
+        HomeSection::Trending => Key::TrendingSongs,
+        HomeSection::TopPicks => Key::HomeSectionTopPicks,
+        HomeSection::Toplists => Key::HomeSectionToplists,
+        HomeSection::DailyRecommend => Key::DiscoverDailyRecommend,
+        HomeSection::RecentlyPlayed => Key::LibraryRecentlyPlayed,
+    }
 }
@@ -0,0 +1,175 @@
+//! Log viewer page
+//!
+//! Shows the in-memory application log with a minimum-level filter and
+//! the diagnostic bundle export action.
+
+use iced::widget::{Space, button, column, container, pick_list, row, scrollable, text};
+use iced::{Alignment, Background, Color, Element, Fill, Font, Padding, Theme};
+use tracing::Level;
+
+use crate::app::{LogsPageState, Message};
+use crate::features::logs::{LogLevelFilter, LogRecord};
+use crate::i18n::{Key, Locale};
+use crate::ui::theme;
+
+/// Maximum number of rows rendered at once (newest kept)
+const MAX_VISIBLE_RECORDS: usize = 1000;
+
+/// Log viewer page view
+pub fn view(state: &LogsPageState, locale: Locale) -> Element<'static, Message> {
+    let header = column![
+        text(locale.get(Key::LogsTitle).to_string())
+            .size(28)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme)),
+            }),
+        Space::new().height(6),
+        text(locale.get(Key::LogsDesc).to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+    ];
+
+    let export_label = if state.exporting {
+        locale.get(Key::LogsExporting)
+    } else {
+        locale.get(Key::LogsExportDiagnostics)
+    };
+
+    let actions = row![
+        text(locale.get(Key::LogsLevel).to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_label(theme)),
+            }),
+        pick_list(
+            LogLevelFilter::ALL,
+            Some(state.level),
+            Message::SetLogLevelFilter
+        )
+        .style(theme::settings_pick_list)
+        .menu_style(theme::settings_pick_list_menu)
+        .padding([8, 12]),
+        button(text(locale.get(Key::LogsRefresh).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::RefreshLogs),
+        button(text(export_label.to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press_maybe((!state.exporting).then_some(Message::ExportDiagnostics)),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    let header_container =
+        container(row![header, Space::new().width(Fill), actions].align_y(Alignment::Center))
+            .width(Fill)
+            .padding(
+                Padding::new(40.0)
+                    .top(70.0)
+                    .right(32.0)
+                    .bottom(20.0)
+                    .left(32.0),
+            )
+            .style(|theme| container::Style {
+                background: Some(Background::Color(theme::background(theme))),
+                ..Default::default()
+            });
+
+    let mut visible: Vec<&LogRecord> = state
+        .records
+        .iter()
+        .rev()
+        .filter(|record| state.level.allows(record.level))
+        .take(MAX_VISIBLE_RECORDS)
+        .collect();
+    visible.reverse();
+
+    let content: Element<'static, Message> = if visible.is_empty() {
+        container(
+            text(locale.get(Key::LogsEmpty).to_string())
+                .size(14)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_desc(theme)),
+                }),
+        )
+        .width(Fill)
+        .height(120)
+        .center_x(Fill)
+        .center_y(120)
+        .into()
+    } else {
+        column(visible.into_iter().map(log_row))
+            .spacing(2)
+            .width(Fill)
+            .into()
+    };
+
+    let scrollable_content = scrollable(
+        container(content)
+            .width(Fill)
+            .padding(Padding::new(20.0).right(32.0).bottom(60.0).left(32.0)),
+    )
+    .id(iced::widget::Id::new("logs_scroll"))
+    .width(Fill)
+    .height(Fill);
+
+    container(
+        column![header_container, scrollable_content]
+            .width(Fill)
+            .height(Fill),
+    )
+    .width(Fill)
+    .height(Fill)
+    .style(theme::main_content)
+    .into()
+}
+
+fn level_color(level: Level, theme: &Theme) -> Color {
+    match level {
+        Level::ERROR => theme::danger(theme),
+        Level::WARN => theme::ACCENT_PINK,
+        Level::INFO => theme::settings_label(theme),
+        _ => theme::settings_desc(theme),
+    }
+}
+
+/// Single log line: time, level, target and message
+fn log_row(record: &LogRecord) -> Element<'static, Message> {
+    let level = record.level;
+
+    row![
+        text(record.timestamp.format("%H:%M:%S%.3f").to_string())
+            .size(12)
+            .font(Font::MONOSPACE)
+            .width(96)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+        text(level.to_string())
+            .size(12)
+            .font(Font::MONOSPACE)
+            .width(48)
+            .style(move |theme| text::Style {
+                color: Some(level_color(level, theme)),
+            }),
+        text(record.target.clone())
+            .size(12)
+            .font(Font::MONOSPACE)
+            .width(220)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+        text(record.message.clone())
+            .size(12)
+            .font(Font::MONOSPACE)
+            .width(Fill)
+            .style(move |theme| text::Style {
+                color: Some(level_color(level, theme)),
+            }),
+    ]
+    .spacing(12)
+    .into()
+}
@@ -6,28 +6,78 @@
 
 use std::sync::Arc;
 
-use iced::widget::{Space, button, column, container, mouse_area, opaque, row, shader, svg, text};
+use iced::widget::{
+    Space, button, column, container, mouse_area, opaque, row, scrollable, shader, svg, text,
+    tooltip,
+};
 use iced::{Alignment, Color, Element, Fill, Length, Padding};
 
-use crate::app::Message;
+use crate::api::{CreditName, SongCredits};
+use crate::app::{Message, SharedElement};
 use crate::database::DbSong;
-use crate::features::PlayMode;
 use crate::features::lyrics::engine::{LyricLineData, LyricsEngine};
+use crate::features::song_source;
+use crate::features::{BackgroundStyle, LyricsSubLines, PlayMode};
+use crate::i18n::{Key, Locale};
 use crate::ui::effects::textured_background::TexturedBackgroundProgram;
 use crate::ui::icons;
 use crate::ui::theme::{self, BOLD_WEIGHT};
 use crate::ui::widgets::{self, ControlSize, PlayModeButtonSize, SliderSize};
 
+/// Whether the current song has an NCM track
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SongLink {
+    /// NCM song, or a local song linked to one
+    Ncm,
+    /// Local song that can be looked up on NCM
+    Unlinked,
+    /// Local song being looked up
+    Matching,
+}
+
+/// Where the page is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LyricsHost {
+    /// Over the main window; `cover_in_flight` while the cover is drawn
+    /// flying from or to the player bar
+    Overlay { cover_in_flight: bool },
+    /// In a window of its own, for a second monitor
+    Window { fullscreen: bool },
+}
+
+/// What the credits view shows in place of the lyrics
+#[derive(Debug, Clone, Copy)]
+pub enum CreditsView<'a> {
+    Hidden,
+    Loading,
+    Unavailable,
+    Loaded(&'a SongCredits),
+}
+
 /// Build the lyrics page view
 ///
 /// `animation_progress`: 0.0 = hidden at bottom, 1.0 = fully visible
 /// `cached_engine_lines`: Pre-computed engine lines (Arc for O(1) clone, thread-safe)
+/// `background_style`: How the cover is drawn behind the page
 /// `power_saving_mode`: When true, use simple text rendering instead of SDF engine
-/// `is_liked`: Whether the current song is in user's favorites
+/// `link`: The song's NCM track, if any; unlinked songs can be looked up
+/// `is_liked`: Whether the song is a favorite
 /// `download_progress`: Download progress for streaming songs (0.0 to 1.0)
 /// `is_fm_mode`: Whether in Personal FM mode
+/// `sub_lines`: Sub-lines currently shown; `available_sub_lines`: sub-lines
+/// the lyrics have, only those get a toggle
+/// `credits`: Credits view replacing the lyrics; its toggle needs an NCM link
+/// `shareable`: Set when the song's own LRC file can be shared on LRCLIB,
+/// true when an edit is waiting to be shared
+/// `savable`: The lyrics shown came from elsewhere than the song's own
+/// files and can be saved into them
+/// `scroll_resume_in`: Seconds until manually scrolled lyrics return to the
+/// current line, while they are scrolled
+/// `host`: The main window or the lyrics window, which get different
+/// window buttons
 pub fn view<'a>(
     song: &'a DbSong,
+    cover_path: Option<&'a str>,
     is_playing: bool,
     position: f32, // 0.0 to 1.0
     duration_secs: f32,
@@ -38,31 +88,72 @@ pub fn view<'a>(
     _bg_colors: &crate::utils::DominantColors,
     _bg_shader: &'a crate::ui::effects::background::LyricsBackgroundProgram,
     textured_bg_shader: &'a TexturedBackgroundProgram,
+    background_style: BackgroundStyle,
     lyrics_engine: Option<&'a std::cell::RefCell<LyricsEngine>>,
     power_saving_mode: bool,
+    link: SongLink,
     is_liked: bool,
     download_progress: Option<f32>,
     is_fm_mode: bool,
+    sub_lines: LyricsSubLines,
+    available_sub_lines: LyricsSubLines,
+    seek_hover_preview: Option<String>,
+    credits: CreditsView<'a>,
+    shareable: Option<bool>,
+    savable: bool,
+    scroll_resume_in: Option<f32>,
+    host: LyricsHost,
+    locale: Locale,
 ) -> Element<'a, Message> {
+    let has_ncm_link = link == SongLink::Ncm;
     let left_panel = build_left_panel(
         song,
+        cover_path,
+        host,
         is_playing,
         position,
         duration_secs,
         play_mode,
+        link,
         is_liked,
         download_progress,
         is_fm_mode,
+        seek_hover_preview,
+        locale,
     );
-    let right_panel = if power_saving_mode {
-        // Power saving mode: use simple text rendering
-        build_simple_lyrics_panel(cached_engine_lines, position * duration_secs * 1000.0)
-    } else {
-        build_right_panel_engine(
-            cached_engine_lines,
-            lyrics_engine,
-            position * duration_secs * 1000.0,
-        )
+    let right_panel = match credits {
+        CreditsView::Hidden if power_saving_mode => {
+            // Power saving mode: use simple text rendering
+            build_simple_lyrics_panel(
+                cached_engine_lines,
+                position * duration_secs * 1000.0,
+                locale,
+            )
+        }
+        CreditsView::Hidden => {
+            let lyrics = build_right_panel_engine(
+                cached_engine_lines,
+                lyrics_engine,
+                position * duration_secs * 1000.0,
+                locale,
+            );
+            match scroll_resume_in {
+                Some(secs) => iced::widget::stack![
+                    lyrics,
+                    container(return_to_current_button(secs, locale))
+                        .width(Fill)
+                        .height(Fill)
+                        .align_x(Alignment::Center)
+                        .align_y(Alignment::End)
+                        .padding(Padding::new(0.0).bottom(24.0)),
+                ]
+                .into(),
+                None => lyrics,
+            }
+        }
+        CreditsView::Loading => credits_placeholder(locale.get(Key::CreditsLoading)),
+        CreditsView::Unavailable => credits_placeholder(locale.get(Key::CreditsUnavailable)),
+        CreditsView::Loaded(credits) => build_credits_panel(credits, locale),
     };
 
     // Main layout: left panel (40%) + right panel (60%)
@@ -221,17 +312,117 @@ pub fn view<'a>(
     .style(close_btn_style)
     .on_press(Message::RequestClose);
 
-    let top_right_buttons = row![
-        settings_btn,
-        Space::new().width(4),
-        minimize_btn,
-        Space::new().width(4),
-        maximize_btn,
-        Space::new().width(4),
-        close_btn,
-    ]
-    .align_y(Alignment::Center);
+    let mut sub_line_toggles = row![].spacing(8).align_y(Alignment::Center);
+    if available_sub_lines.translation {
+        sub_line_toggles = sub_line_toggles.push(sub_line_toggle(
+            locale.get(Key::LyricsTranslation),
+            sub_lines.translation,
+            Message::ToggleLyricsTranslation,
+        ));
+    }
+    if available_sub_lines.romanization {
+        sub_line_toggles = sub_line_toggles.push(sub_line_toggle(
+            locale.get(Key::LyricsRomanization),
+            sub_lines.romanization,
+            Message::ToggleLyricsRomanization,
+        ));
+    }
+    if has_ncm_link {
+        sub_line_toggles = sub_line_toggles.push(sub_line_toggle(
+            locale.get(Key::SongCredits),
+            !matches!(credits, CreditsView::Hidden),
+            Message::ToggleSongCredits,
+        ));
+    }
+    if let Some(edited) = shareable {
+        sub_line_toggles = sub_line_toggles.push(sub_line_toggle(
+            locale.get(Key::LyricsShare),
+            edited,
+            Message::OpenLyricsShare,
+        ));
+    }
+    if savable {
+        sub_line_toggles = sub_line_toggles.push(sub_line_toggle(
+            locale.get(Key::LyricsSaveToFile),
+            false,
+            Message::SaveLyricsToFile,
+        ));
+    }
 
+    let window_buttons = match host {
+        LyricsHost::Overlay { .. } => {
+            let open_window_btn = tooltip(
+                button(
+                    svg(svg::Handle::from_memory(icons::OPEN_IN_NEW.as_bytes()))
+                        .width(14)
+                        .height(14)
+                        .style(|_theme, _status| svg::Style {
+                            color: Some(theme::TEXT_PRIMARY),
+                        }),
+                )
+                .width(32)
+                .height(32)
+                .style(icon_btn_style)
+                .on_press(Message::OpenLyricsWindow),
+                text(locale.get(Key::LyricsOpenWindow)).size(12),
+                tooltip::Position::Bottom,
+            )
+            .gap(4)
+            .style(|theme| container::Style {
+                background: Some(iced::Background::Color(theme::surface_container(theme))),
+                border: iced::Border {
+                    radius: 4.0.into(),
+                    color: theme::divider(theme),
+                    width: 1.0,
+                },
+                ..Default::default()
+            });
+            row![
+                open_window_btn,
+                Space::new().width(4),
+                settings_btn,
+                Space::new().width(4),
+                minimize_btn,
+                Space::new().width(4),
+                maximize_btn,
+                Space::new().width(4),
+                close_btn,
+            ]
+        }
+        LyricsHost::Window { fullscreen } => {
+            let icon = if fullscreen {
+                icons::FULLSCREEN_EXIT
+            } else {
+                icons::FULLSCREEN
+            };
+            let fullscreen_btn = button(
+                svg(svg::Handle::from_memory(icon.as_bytes()))
+                    .width(14)
+                    .height(14)
+                    .style(|_theme, _status| svg::Style {
+                        color: Some(theme::TEXT_PRIMARY),
+                    }),
+            )
+            .width(32)
+            .height(32)
+            .style(icon_btn_style)
+            .on_press(Message::ToggleLyricsWindowFullscreen);
+            row![
+                fullscreen_btn,
+                Space::new().width(4),
+                close_btn.on_press(Message::CloseLyricsWindow),
+            ]
+        }
+    };
+
+    let top_right_buttons =
+        row![sub_line_toggles, Space::new().width(12), window_buttons].align_y(Alignment::Center);
+
+    // The lyrics window closes from its own buttons instead
+    let back_btn: Element<'a, Message> = match host {
+        LyricsHost::Overlay { .. } => back_btn.into(),
+        LyricsHost::Window { .. } => Space::new().into(),
+    };
     let top_bar = row![back_btn, Space::new().width(Fill), top_right_buttons,]
         .align_y(Alignment::Center)
         .padding(20);
@@ -244,7 +435,9 @@ pub fn view<'a>(
     let slide_offset = (1.0 - animation_progress) * 30.0;
 
     // Use WGPU textured shader for animated background
-    let shader_bg = shader(textured_bg_shader).width(Fill).height(Fill);
+    let shader_bg = shader(textured_bg_shader.styled(background_style))
+        .width(Fill)
+        .height(Fill);
 
     let content_with_shader = iced::widget::stack![
         shader_bg,
@@ -262,16 +455,84 @@ pub fn view<'a>(
     opaque(container(content_with_shader).width(Fill).height(Fill)).into()
 }
 
+/// Floating button over manually scrolled lyrics, counting down to the
+/// automatic return
+fn return_to_current_button(secs: f32, locale: Locale) -> Element<'static, Message> {
+    let label = locale
+        .get(Key::LyricsReturnToCurrent)
+        .replace("{}", &format!("{}", secs.ceil() as u32));
+    button(text(label).size(13).color(theme::TEXT_PRIMARY))
+        .padding([8, 18])
+        .style(|_theme, status| {
+            let alpha = match status {
+                button::Status::Hovered => 0.3,
+                button::Status::Pressed => 0.4,
+                _ => 0.18,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    1.0, 1.0, 1.0, alpha,
+                ))),
+                text_color: theme::TEXT_PRIMARY,
+                border: iced::Border {
+                    radius: 18.0.into(),
+                    width: 1.0,
+                    color: Color::from_rgba(1.0, 1.0, 1.0, 0.3),
+                },
+                ..Default::default()
+            }
+        })
+        .on_press(Message::ResumeLyricsAutoScroll)
+        .into()
+}
+
+/// Pill button that shows or hides one kind of lyrics sub-line
+fn sub_line_toggle<'a>(label: &'a str, active: bool, message: Message) -> Element<'a, Message> {
+    button(text(label).size(13))
+        .padding([4, 12])
+        .style(move |_theme, status| {
+            let alpha = match (active, status) {
+                (true, button::Status::Hovered) => 0.3,
+                (true, _) => 0.22,
+                (false, button::Status::Hovered) => 0.1,
+                (false, _) => 0.0,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    1.0, 1.0, 1.0, alpha,
+                ))),
+                text_color: if active {
+                    theme::TEXT_PRIMARY
+                } else {
+                    Color::from_rgba(1.0, 1.0, 1.0, 0.6)
+                },
+                border: iced::Border {
+                    radius: 14.0.into(),
+                    width: 1.0,
+                    color: Color::from_rgba(1.0, 1.0, 1.0, 0.3),
+                },
+                ..Default::default()
+            }
+        })
+        .on_press(message)
+        .into()
+}
+
 /// Build the left panel with cover, song info, and controls
 fn build_left_panel<'a>(
     song: &'a DbSong,
+    cover_path: Option<&'a str>,
+    host: LyricsHost,
     is_playing: bool,
     position: f32,
     duration_secs: f32,
     play_mode: PlayMode,
+    link: SongLink,
     is_liked: bool,
     download_progress: Option<f32>,
     is_fm_mode: bool,
+    seek_hover_preview: Option<String>,
+    locale: Locale,
 ) -> Element<'a, Message> {
     // Format time as mm:ss
     let format_time = |secs: f32| -> String {
@@ -283,8 +544,19 @@ fn build_left_panel<'a>(
     let current_time = format_time(position * duration_secs);
     let total_time = format_time(duration_secs);
 
-    // Cover image - square aspect ratio using custom widget
-    let cover = widgets::square_cover::view(song.cover_path.as_deref());
+    // Cover image - square aspect ratio using custom widget; it's where the
+    // player bar cover flies to when the page opens
+    let cover: Element<'a, Message> = match host {
+        LyricsHost::Overlay { cover_in_flight } => mouse_area(
+            widgets::BoundsSensor::new(widgets::square_cover::view(cover_path))
+                .on_change(|bounds| Message::SharedElementMoved(SharedElement::LyricsCover, bounds))
+                .hidden(cover_in_flight),
+        )
+        .interaction(iced::mouse::Interaction::Pointer)
+        .on_press(Message::OpenCoverViewer)
+        .into(),
+        LyricsHost::Window { .. } => widgets::square_cover::view(cover_path),
+    };
 
     // Song title
     let title = text(&song.title)
@@ -299,8 +571,12 @@ fn build_left_panel<'a>(
     let artist = text(&song.artist).size(18).color(theme::TEXT_SECONDARY);
 
     // Progress bar - using unified widget with download progress
-    let progress_slider =
-        widgets::progress_slider::view_with_download(position, download_progress, SliderSize::Full);
+    let progress_slider = widgets::progress_slider::view_with_download(
+        position,
+        download_progress,
+        SliderSize::Full,
+        seek_hover_preview,
+    );
 
     let time_row = row![
         text(current_time).size(12).color(theme::TEXT_MUTED),
@@ -314,66 +590,53 @@ fn build_left_panel<'a>(
 
     // Play mode button - using unified widget
     let play_mode_btn =
-        widgets::play_mode_button::view(play_mode, PlayModeButtonSize::Large, is_fm_mode);
+        widgets::play_mode_button::view(play_mode, PlayModeButtonSize::Large, is_fm_mode, locale);
 
-    // Like button - only for NCM songs (negative ID)
-    let like_btn: Element<'a, Message> = if song.id < 0 {
-        let ncm_id = (-song.id) as u64;
-        let heart_icon = if is_liked {
-            icons::HEART
-        } else {
-            icons::HEART_OUTLINE
-        };
-        let heart_color = if is_liked {
-            theme::ACCENT_PINK
-        } else {
-            theme::TEXT_SECONDARY
-        };
-        button(
-            svg(svg::Handle::from_memory(heart_icon.as_bytes()))
-                .width(22)
-                .height(22)
-                .style(move |_theme, _status| svg::Style {
-                    color: Some(heart_color),
-                }),
-        )
-        .padding(10)
-        .style(|theme, status| {
-            let bg = match status {
-                button::Status::Hovered => theme::hover_bg(theme),
-                _ => Color::TRANSPARENT,
-            };
-            button::Style {
-                background: Some(iced::Background::Color(bg)),
-                border: iced::Border {
-                    radius: 21.0.into(),
-                    ..Default::default()
-                },
-                ..Default::default()
-            }
-        })
-        .on_press(Message::ToggleFavorite(ncm_id))
-        .into()
+    // Like button for every song
+    let heart_icon = if is_liked {
+        icons::HEART
     } else {
-        // Local songs - show disabled like button
-        button(
-            svg(svg::Handle::from_memory(icons::HEART.as_bytes()))
-                .width(22)
-                .height(22)
-                .style(|_theme, _status| svg::Style {
-                    color: Some(theme::icon_muted(&iced::Theme::Dark)),
-                }),
-        )
-        .padding(10)
-        .style(|_theme, _status| button::Style {
-            background: Some(iced::Background::Color(Color::TRANSPARENT)),
-            border: iced::Border {
-                radius: 21.0.into(),
-                ..Default::default()
-            },
-            ..Default::default()
-        })
-        .into()
+        icons::HEART_OUTLINE
+    };
+    let heart_color = if is_liked {
+        theme::ACCENT_PINK
+    } else {
+        theme::TEXT_SECONDARY
+    };
+    let like_btn = button(
+        svg(svg::Handle::from_memory(heart_icon.as_bytes()))
+            .width(22)
+            .height(22)
+            .style(move |_theme, _status| svg::Style {
+                color: Some(heart_color),
+            }),
+    )
+    .padding(10)
+    .style(round_icon_button)
+    .on_press(Message::ToggleFavorite(song.id));
+
+    // Local songs without an NCM track can be looked up on NCM, and NCM
+    // songs can have their details corrected
+    let find_btn: Element<'a, Message> = match link {
+        SongLink::Ncm if song_source::is_ncm(song) => labeled_icon_button(
+            icons::EDIT,
+            locale.get(Key::SongInfoEdit),
+            Some(Message::EditSongInfo),
+        ),
+        SongLink::Ncm => Space::new().width(0).into(),
+        SongLink::Unlinked | SongLink::Matching => {
+            let matching = link == SongLink::Matching;
+            let (icon, label) = if matching {
+                (icons::LOADING, locale.get(Key::NcmMatchSearching))
+            } else {
+                (icons::SEARCH, locale.get(Key::NcmMatchFind))
+            };
+            labeled_icon_button(
+                icon,
+                label,
+                (!matching).then_some(Message::MatchSongOnNcm(song.id)),
+            )
+        }
     };
 
     let controls = row![
@@ -381,6 +644,7 @@ fn build_left_panel<'a>(
         Space::new().width(Fill),
         playback_controls,
         Space::new().width(Fill),
+        find_btn,
         like_btn,
     ]
     .align_y(Alignment::Center)
@@ -415,12 +679,161 @@ fn build_left_panel<'a>(
     .into()
 }
 
+/// Credits panel: performers, album and label, then the roles NCM lists
+fn build_credits_panel(credits: &SongCredits, locale: Locale) -> Element<'static, Message> {
+    let mut rows: Vec<Element<'static, Message>> = Vec::new();
+    if !credits.artists.is_empty() {
+        rows.push(credit_row(
+            locale.get(Key::CreditsArtists).to_string(),
+            credit_names(&credits.artists),
+        ));
+    }
+    if !credits.album.is_empty() {
+        rows.push(credit_row(
+            locale.get(Key::CreditsAlbum).to_string(),
+            text(credits.album.clone())
+                .size(16)
+                .color(theme::TEXT_PRIMARY)
+                .into(),
+        ));
+    }
+    if let Some(company) = &credits.company {
+        rows.push(credit_row(
+            locale.get(Key::CreditsCompany).to_string(),
+            text(company.clone())
+                .size(16)
+                .color(theme::TEXT_PRIMARY)
+                .into(),
+        ));
+    }
+    for entry in &credits.entries {
+        rows.push(credit_row(entry.role.clone(), credit_names(&entry.names)));
+    }
+
+    let title = text(locale.get(Key::SongCredits).to_string())
+        .size(28)
+        .color(theme::TEXT_PRIMARY)
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        });
+
+    let content = column![title, Space::new().height(24), column(rows).spacing(16)]
+        .width(Fill)
+        .padding(Padding::new(20.0).top(60.0));
+
+    container(scrollable(content))
+        .width(Fill)
+        .height(Fill)
+        .into()
+}
+
+/// One credit: role on the left, value on the right
+fn credit_row(role: String, value: Element<'static, Message>) -> Element<'static, Message> {
+    row![
+        text(role).size(14).width(120).color(theme::TEXT_MUTED),
+        container(value).width(Fill),
+    ]
+    .spacing(16)
+    .into()
+}
+
+/// Credited names separated by slashes; NCM artists link to a search for them
+fn credit_names(names: &[CreditName]) -> Element<'static, Message> {
+    let mut items = row![].spacing(6).align_y(Alignment::Center);
+    for (idx, name) in names.iter().enumerate() {
+        if idx > 0 {
+            items = items.push(
+                text("/")
+                    .size(16)
+                    .color(Color::from_rgba(1.0, 1.0, 1.0, 0.4)),
+            );
+        }
+        items = items.push(match name.id {
+            Some(_) => button(text(name.name.clone()).size(16))
+                .padding(0)
+                .style(|_theme, status| button::Style {
+                    text_color: match status {
+                        button::Status::Hovered | button::Status::Pressed => theme::ACCENT_PINK,
+                        _ => theme::TEXT_PRIMARY,
+                    },
+                    ..Default::default()
+                })
+                .on_press(Message::OpenCreditArtist(name.name.clone()))
+                .into(),
+            None => Element::from(text(name.name.clone()).size(16).color(theme::TEXT_PRIMARY)),
+        });
+    }
+    items.wrap().into()
+}
+
+/// Centered status text shown while credits load or when there are none
+fn credits_placeholder(message: &str) -> Element<'static, Message> {
+    container(text(message.to_string()).size(18).color(theme::TEXT_MUTED))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+}
+
+/// Round icon button with its label in a tooltip
+fn labeled_icon_button<'a>(
+    icon: &'static str,
+    label: &str,
+    on_press: Option<Message>,
+) -> Element<'a, Message> {
+    tooltip(
+        button(
+            svg(svg::Handle::from_memory(icon.as_bytes()))
+                .width(22)
+                .height(22)
+                .style(|_theme, _status| svg::Style {
+                    color: Some(theme::TEXT_SECONDARY),
+                }),
+        )
+        .padding(10)
+        .style(round_icon_button)
+        .on_press_maybe(on_press),
+        text(label.to_string()).size(12),
+        tooltip::Position::Top,
+    )
+    .gap(4)
+    .style(|theme| container::Style {
+        background: Some(iced::Background::Color(theme::surface_container(theme))),
+        border: iced::Border {
+            radius: 4.0.into(),
+            color: theme::divider(theme),
+            width: 1.0,
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Round icon button next to the playback controls
+fn round_icon_button(theme: &iced::Theme, status: button::Status) -> button::Style {
+    let bg = match status {
+        button::Status::Hovered => theme::hover_bg(theme),
+        _ => Color::TRANSPARENT,
+    };
+    button::Style {
+        background: Some(iced::Background::Color(bg)),
+        border: iced::Border {
+            radius: 21.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
 /// Build the right panel with the Apple Music-style engine
 /// Uses pre-computed cached_engine_lines to avoid per-frame conversion
 fn build_right_panel_engine<'a>(
     cached_engine_lines: Option<&Arc<Vec<LyricLineData>>>,
     lyrics_engine: Option<&'a std::cell::RefCell<LyricsEngine>>,
     current_time_ms: f32,
+    locale: Locale,
 ) -> Element<'a, Message> {
     // Check if we have cached engine lines
     let engine_lines = match cached_engine_lines {
@@ -436,7 +849,9 @@ fn build_right_panel_engine<'a>(
                             color: Some(theme::icon_muted(&iced::Theme::Dark)),
                         }),
                     Space::new().height(16),
-                    text("纯音乐，请欣赏").size(18).color(theme::TEXT_MUTED),
+                    text(locale.get(Key::LyricsPureMusic))
+                        .size(18)
+                        .color(theme::TEXT_MUTED),
                 ]
                 .align_x(Alignment::Center),
             )
@@ -532,6 +947,7 @@ fn build_simple_lyrics_panel_from_engine_lines(
 fn build_simple_lyrics_panel(
     cached_engine_lines: Option<&Arc<Vec<LyricLineData>>>,
     current_time_ms: f32,
+    locale: Locale,
 ) -> Element<'static, Message> {
     // Check if we have cached engine lines
     let engine_lines = match cached_engine_lines {
@@ -547,7 +963,9 @@ fn build_simple_lyrics_panel(
                             color: Some(theme::icon_muted(&iced::Theme::Dark)),
                         }),
                     Space::new().height(16),
-                    text("暂无歌词").size(18).color(theme::TEXT_MUTED),
+                    text(locale.get(Key::LyricsNoLyrics))
+                        .size(18)
+                        .color(theme::TEXT_MUTED),
                 ]
                 .align_x(Alignment::Center),
             )
@@ -677,3 +1095,12 @@ pub fn find_current_line(lyrics: &[LyricLine], position_ms: u64) -> Option<usize
 
     current
 }
+
+/// Which sub-lines appear anywhere in the lyrics
+pub fn available_sub_lines(lyrics: &[LyricLine]) -> LyricsSubLines {
+    let has = |sub_line: &Option<String>| sub_line.as_ref().is_some_and(|s| !s.is_empty());
+    LyricsSubLines {
+        translation: lyrics.iter().any(|line| has(&line.translated)),
+        romanization: lyrics.iter().any(|line| has(&line.romanized)),
+    }
+}
@@ -10,18 +10,31 @@ use std::collections::HashSet;
 use std::rc::Rc;
 
 use iced::widget::{
-    button, column, container, image, mouse_area, row, svg, text, text_input, Space,
+    Space, button, column, container, image, mouse_area, pick_list, row, shader, stack, svg, text,
+    text_input, toggler, tooltip,
 };
-use iced::{Alignment, Color, Element, Fill, Padding};
+use iced::{Alignment, Color, Element, Fill, Length, Padding};
 
-use crate::app::Message;
+use crate::app::{Message, SharedElement};
+use crate::database::SongId;
+use crate::features::blocklist::Blocklist;
+use crate::features::favorites::LikedSync;
+use crate::features::{MusicQuality, PlaylistPlayback};
 use crate::i18n::{Key, Locale};
-use crate::ui::components::playlist_view::{self, PlaylistColumns, SongItem};
+use crate::ui::animation::lerp_color;
+use crate::ui::components::playlist_view::{
+    self, PlaylistColumns, PlaylistFilter, PlaylistSort, SongItem,
+};
+use crate::ui::effects::textured_background::TexturedBackgroundProgram;
 use crate::ui::theme::BOLD_WEIGHT;
-use crate::ui::widgets::VirtualListState;
+use crate::ui::widgets::{BoundsSensor, VirtualListState, skeleton};
 use crate::ui::{icons, theme};
 use crate::utils::ColorPalette;
 
+/// Height of the artwork backdrop; taller than the header so the
+/// gradient fades out before the backdrop ends
+const BACKDROP_HEIGHT: f32 = 460.0;
+
 /// Playlist data for display
 #[derive(Debug, Clone)]
 pub struct PlaylistView {
@@ -34,7 +47,8 @@ pub struct PlaylistView {
     /// Creator user ID (for NCM playlists, 0 for local)
     pub creator_id: u64,
     pub song_count: u32,
-    pub total_duration: String,
+    /// Combined length of the loaded songs, formatted per locale when drawn
+    pub total_duration_secs: u64,
     pub like_count: String,
     pub songs: Vec<PlaylistSongView>,
     /// Extracted color palette from cover
@@ -48,22 +62,56 @@ pub struct PlaylistView {
 /// Song item in playlist (alias for SongItem)
 pub type PlaylistSongView = SongItem;
 
+/// "About 1 hour 5 minutes"; minutes only when shorter than an hour
+fn format_total_duration(locale: Locale, total_secs: u64) -> String {
+    let total_mins = total_secs / 60;
+    let hours = total_mins / 60;
+    if hours == 0 {
+        return locale.count(Key::PlaylistDurationMinutes, total_mins);
+    }
+    let span = format!(
+        "{} {}",
+        locale.count(Key::PlaylistDurationHours, hours),
+        locale.count(Key::PlaylistDurationMinutes, total_mins % 60)
+    );
+    locale.get(Key::PlaylistDurationApprox).replace("{}", &span)
+}
+
 /// Build the playlist detail page
 pub fn view<'a>(
-    playlist: &PlaylistView,
-    song_animations: &'a crate::ui::animation::HoverAnimations<i64>,
+    playlist: &'a PlaylistView,
+    song_animations: &'a crate::ui::animation::HoverAnimations<SongId>,
     icon_animations: &crate::ui::animation::HoverAnimations<crate::app::IconId>,
     search_animation: &crate::ui::animation::SingleHoverAnimation,
     search_expanded: bool,
     search_query: &str,
-    liked_songs: HashSet<u64>,
+    tempo_sort: Option<bool>,
+    sort: PlaylistSort,
+    filter: PlaylistFilter,
+    liked_songs: HashSet<SongId>,
+    blocklist: &'a Blocklist,
+    mosaic: Option<&str>,
     locale: Locale,
     scroll_state: Rc<RefCell<VirtualListState>>,
     current_user_id: Option<u64>,
-    current_playing_id: Option<i64>,
+    current_playing_id: Option<SongId>,
+    load_progress: Option<(usize, usize)>,
+    skeleton: Option<f32>,
+    cover_in_flight: bool,
+    backdrop: Option<&'a TexturedBackgroundProgram>,
+    tint: Option<Color>,
+    playback_panel: Option<PlaylistPlayback>,
+    liked_sync: Option<LikedSync>,
 ) -> Element<'a, Message> {
-    let palette = playlist.palette.clone();
-    let header = build_header(playlist, locale);
+    let accent = tint.map(accent_color);
+    let header = build_header(
+        playlist,
+        mosaic,
+        liked_sync,
+        skeleton,
+        cover_in_flight,
+        locale,
+    );
     let controls = build_controls(
         playlist,
         icon_animations,
@@ -72,21 +120,39 @@ pub fn view<'a>(
         search_query,
         locale,
         current_user_id,
+        load_progress,
+        playback_panel.is_some(),
+        accent,
     );
 
     // Filter songs based on search query
-    let filtered_songs = playlist_view::filter_songs(&playlist.songs, search_query);
+    let filtered_songs = playlist_view::SongRows::filter(&playlist.songs, search_query)
+        .keep(filter)
+        .sort(sort)
+        .sort_by_tempo(tempo_sort);
 
     // Content with gradient that extends through controls
-    let header_and_controls = column![header, controls,].spacing(0).width(Fill);
+    let mut header_and_controls = column![header, controls,].spacing(0).width(Fill);
+    if !playlist.is_local && !playlist.songs.is_empty() {
+        header_and_controls = header_and_controls.push(build_sort_bar(sort, filter, locale));
+    }
+    if let Some(playback) = playback_panel {
+        header_and_controls = header_and_controls.push(build_playback_panel(playback, locale));
+    }
 
     // Wrap header+controls in gradient container
-    // Use the extracted palette colors directly for a more vibrant look
-    let primary = palette.primary;
+    // Use the cover's palette color directly for a more vibrant look
+    let primary = tint.unwrap_or_else(|| ColorPalette::default().primary);
     // Apply slight boost for brighter gradient
     let top_r = (primary.r * 1.1 + 0.05).min(1.0);
     let top_g = (primary.g * 1.05 + 0.03).min(1.0);
     let top_b = (primary.b * 1.08 + 0.04).min(1.0);
+    // Let the artwork backdrop show through the upper part of the header
+    let (top_alpha, mid_alpha) = if backdrop.is_some() {
+        (0.35, 0.75)
+    } else {
+        (1.0, 1.0)
+    };
 
     let gradient_section = container(header_and_controls)
         .width(Fill)
@@ -114,13 +180,14 @@ pub fn view<'a>(
                 background: Some(iced::Background::Gradient(iced::Gradient::Linear(
                     // Top to bottom gradient with palette colors
                     iced::gradient::Linear::new(iced::Radians(std::f32::consts::PI))
-                        .add_stop(0.0, Color::from_rgb(adj_r, adj_g, adj_b))
+                        .add_stop(0.0, Color::from_rgba(adj_r, adj_g, adj_b, top_alpha))
                         .add_stop(
                             0.55,
-                            Color::from_rgb(
+                            Color::from_rgba(
                                 adj_r * 0.6 + bottom_color.r * 0.4,
                                 adj_g * 0.55 + bottom_color.g * 0.4,
                                 adj_b * 0.58 + bottom_color.b * 0.4,
+                                mid_alpha,
                             ),
                         )
                         .add_stop(1.0, bottom_color),
@@ -135,33 +202,62 @@ pub fn view<'a>(
     } else {
         PlaylistColumns::online()
     };
-    let song_list_header = playlist_view::build_header(locale, columns);
-
-    // Use virtual list for song rows
-    let song_list = playlist_view::build_list(
-        filtered_songs,
-        song_animations,
-        liked_songs,
-        columns,
-        scroll_state,
-        current_playing_id,
-    );
+    let song_list_header = playlist_view::build_header(locale, columns, tempo_sort);
+
+    // Use virtual list for song rows, placeholders until the first batch arrives
+    let song_list = match skeleton {
+        Some(phase) if playlist.songs.is_empty() => {
+            playlist_view::build_skeleton_list(columns, phase)
+        }
+        _ => playlist_view::build_list(
+            filtered_songs,
+            song_animations,
+            liked_songs,
+            blocklist,
+            columns,
+            scroll_state,
+            current_playing_id,
+            accent,
+            locale,
+        ),
+    };
 
     let content = column![gradient_section, song_list_header, song_list,]
         .spacing(0)
         .width(Fill);
 
-    content.into()
+    match backdrop {
+        Some(backdrop) => {
+            let backdrop_layer = column![
+                shader(backdrop)
+                    .width(Fill)
+                    .height(Length::Fixed(BACKDROP_HEIGHT)),
+                Space::new().width(Fill).height(Fill),
+            ]
+            .height(Fill);
+            stack![backdrop_layer, content].into()
+        }
+        None => content.into(),
+    }
 }
 
 /// Build the playlist header
-fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Message> {
-    // Cover image - prefer playlist cover_path, fallback to first song cover, then placeholder
+fn build_header(
+    playlist: &PlaylistView,
+    mosaic: Option<&str>,
+    liked_sync: Option<LikedSync>,
+    skeleton: Option<f32>,
+    cover_in_flight: bool,
+    locale: Locale,
+) -> Element<'static, Message> {
+    // Cover image - prefer playlist cover_path, then the generated mosaic, then
+    // the first song cover, then placeholder
     // Only use local file paths, not URLs
     let cover_path_opt: Option<&str> = playlist
         .cover_path
         .as_deref()
         .filter(|p| !p.starts_with("http") && std::path::Path::new(p).exists())
+        .or(mosaic)
         .or_else(|| {
             playlist
                 .songs
@@ -193,10 +289,17 @@ fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Mes
             ..Default::default()
         })
         .into()
+    } else if let Some(phase) = skeleton {
+        skeleton::block(220, 220, 8.0, phase)
     } else {
         // Placeholder
         build_cover_placeholder()
     };
+    // Where a card's cover lands when the playlist is opened from a grid
+    let cover: Element<'static, Message> = BoundsSensor::new(cover)
+        .on_change(|bounds| Message::SharedElementMoved(SharedElement::PlaylistHeaderCover, bounds))
+        .hidden(cover_in_flight)
+        .into();
 
     // Playlist type label - larger font
     let type_label = text(locale.get(Key::PlaylistTypeLabel))
@@ -207,17 +310,29 @@ fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Mes
 
     // Playlist title - larger font for big screens
     // Use Inter or system sans-serif with bold weight
-    let title = text(playlist.name.clone())
-        .size(72)
-        .line_height(iced::widget::text::LineHeight::Relative(1.0))
-        .style(|theme| text::Style {
-            color: Some(theme::text_primary(theme)),
-        })
-        .font(iced::Font {
-            family: iced::font::Family::SansSerif,
-            weight: BOLD_WEIGHT,
-            ..Default::default()
-        });
+    if let Some(phase) = skeleton {
+        // Name and owner are known when opened from the sidebar; otherwise
+        // the whole header shimmers until the details arrive
+        let title: Element<'static, Message> = if playlist.name.is_empty() {
+            skeleton::block(420, 56, 6.0, phase)
+        } else {
+            build_title(&playlist.name)
+        };
+        let info = column![
+            type_label,
+            Space::new().height(12),
+            title,
+            Space::new().height(18),
+            skeleton::block(280, 14, 3.0, skeleton::delayed(phase, 0.04)),
+        ]
+        .spacing(0);
+
+        return row![cover, Space::new().width(28), info,]
+            .align_y(Alignment::End)
+            .padding(Padding::new(36.0).top(60.0).bottom(12.0))
+            .into();
+    }
+    let title = build_title(&playlist.name);
 
     // Description (slightly muted but readable)
     let description = if let Some(desc) = &playlist.description {
@@ -254,14 +369,13 @@ fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Mes
 
     // Owner and stats - better spacing and brighter colors
     let song_count = playlist.song_count;
-    let duration = playlist.total_duration.clone();
+    let duration = format_total_duration(locale, playlist.total_duration_secs);
     let is_local = playlist.is_local;
     let like_count = playlist.like_count.clone();
 
-    // Build stats row - use proper dot separator with spacing
-    let mut stats_items: Vec<Element<'static, Message>> = vec![
-        owner_avatar.into(),
-        Space::new().width(8).into(),
+    let owner = row![
+        owner_avatar,
+        Space::new().width(8),
         // Owner name is bright white and bold
         text(owner_name)
             .size(14)
@@ -271,9 +385,27 @@ fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Mes
             .font(iced::Font {
                 weight: BOLD_WEIGHT,
                 ..Default::default()
-            })
-            .into(),
-    ];
+            }),
+    ]
+    .align_y(Alignment::Center);
+
+    // The owner of an NCM playlist links to their profile
+    let owner: Element<'static, Message> = if !is_local && playlist.creator_id != 0 {
+        tooltip(
+            button(owner)
+                .padding(0)
+                .style(theme::transparent_btn)
+                .on_press(Message::OpenUserProfile(playlist.creator_id)),
+            text(locale.get(Key::ProfileOpen)).size(12),
+            tooltip::Position::Bottom,
+        )
+        .into()
+    } else {
+        owner.into()
+    };
+
+    // Build stats row - use proper dot separator with spacing
+    let mut stats_items: Vec<Element<'static, Message>> = vec![owner];
 
     // Only show like count for non-local playlists
     if !is_local && !like_count.is_empty() {
@@ -309,16 +441,12 @@ fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Mes
     );
     stats_items.push(Space::new().width(6).into());
     stats_items.push(
-        text(
-            locale
-                .get(Key::PlaylistSongCount)
-                .replace("{}", &song_count.to_string()),
-        )
-        .size(14)
-        .style(|theme| text::Style {
-            color: Some(theme::text_secondary(theme)),
-        })
-        .into(),
+        text(locale.count(Key::PlaylistSongCount, song_count as u64))
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::text_secondary(theme)),
+            })
+            .into(),
     );
     stats_items.push(Space::new().width(6).into());
     stats_items.push(
@@ -339,6 +467,33 @@ fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Mes
             .into(),
     );
 
+    // Whether the saved liked songs are up to date with NCM
+    if let Some(sync) = liked_sync {
+        let label = match sync {
+            LikedSync::Syncing => Key::LikedSyncing,
+            LikedSync::Synced => Key::LikedSynced,
+            LikedSync::Offline => Key::LikedSyncOffline,
+        };
+        stats_items.push(Space::new().width(6).into());
+        stats_items.push(
+            text("·")
+                .size(14)
+                .style(|theme| text::Style {
+                    color: Some(theme::header_text(theme)),
+                })
+                .into(),
+        );
+        stats_items.push(Space::new().width(6).into());
+        stats_items.push(
+            text(locale.get(label))
+                .size(14)
+                .style(|theme| text::Style {
+                    color: Some(theme::text_secondary(theme)),
+                })
+                .into(),
+        );
+    }
+
     let stats = row(stats_items).align_y(Alignment::Center);
 
     // Info column - description closer to title, farther from stats
@@ -360,6 +515,34 @@ fn build_header(playlist: &PlaylistView, locale: Locale) -> Element<'static, Mes
         .into()
 }
 
+/// A cover color brightened enough to stand out against the page
+fn accent_color(color: Color) -> Color {
+    let scale = 0.95 / color.r.max(color.g).max(color.b).max(0.05);
+    let boost = |c: f32| (c * scale * 0.85 + 0.15).min(1.0);
+    Color::from_rgb(boost(color.r), boost(color.g), boost(color.b))
+}
+
+/// Relative luminance, for picking text drawn on top of a color
+fn luminance(color: Color) -> f32 {
+    0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b
+}
+
+/// Playlist title in the header
+fn build_title(name: &str) -> Element<'static, Message> {
+    text(name.to_string())
+        .size(72)
+        .line_height(iced::widget::text::LineHeight::Relative(1.0))
+        .style(|theme| text::Style {
+            color: Some(theme::text_primary(theme)),
+        })
+        .font(iced::Font {
+            family: iced::font::Family::SansSerif,
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        })
+        .into()
+}
+
 /// Build the control buttons (play, like, download, etc.)
 fn build_controls<'a>(
     playlist: &PlaylistView,
@@ -369,6 +552,9 @@ fn build_controls<'a>(
     search_query: &str,
     locale: Locale,
     current_user_id: Option<u64>,
+    load_progress: Option<(usize, usize)>,
+    playback_panel_open: bool,
+    accent: Option<Color>,
 ) -> Element<'a, Message> {
     use crate::app::IconId;
 
@@ -397,13 +583,22 @@ fn build_controls<'a>(
     let icon_size = base_icon_size * scale;
     let btn_radius = btn_size / 2.0;
 
-    // Color: lighter pink -> slightly lighter on hover
+    // Color: cover accent or lighter pink -> slightly lighter on hover
     let progress = icon_animations.get_progress(&IconId::PlayButton);
-    let play_bg = Color::from_rgb(
-        1.0,
-        0.412 + (0.494 - 0.412) * progress,
-        0.706 + (0.753 - 0.706) * progress,
-    );
+    let play_bg = match accent {
+        Some(accent) => lerp_color(accent, Color::WHITE, 0.15 * progress),
+        None => Color::from_rgb(
+            1.0,
+            0.412 + (0.494 - 0.412) * progress,
+            0.706 + (0.753 - 0.706) * progress,
+        ),
+    };
+    // Keep the triangle readable on dark cover colors
+    let play_icon_color = if luminance(play_bg) > 0.4 {
+        theme::BLACK
+    } else {
+        Color::WHITE
+    };
 
     // Build from inside out:
     // 1. SVG icon
@@ -420,8 +615,8 @@ fn build_controls<'a>(
                     svg(svg::Handle::from_memory(icons::PLAY.as_bytes()))
                         .width(icon_size)
                         .height(icon_size)
-                        .style(|_theme, _status| svg::Style {
-                            color: Some(theme::BLACK),
+                        .style(move |_theme, _status| svg::Style {
+                            color: Some(play_icon_color),
                         }),
                 )
                 .padding(Padding {
@@ -519,6 +714,28 @@ fn build_controls<'a>(
         .on_exit(Message::HoverIcon(None));
 
         control_items.push(delete_btn.into());
+
+        // Publish the local playlist as an NCM playlist
+        control_items.push(Space::new().width(8).into());
+        let publish_color = get_icon_color(IconId::Publish);
+        let publish_btn = mouse_area(tooltip(
+            button(
+                svg(svg::Handle::from_memory(icons::UPLOAD.as_bytes()))
+                    .width(22)
+                    .height(22)
+                    .style(move |_theme, _status| svg::Style {
+                        color: Some(publish_color),
+                    }),
+            )
+            .style(theme::transparent_btn)
+            .on_press(Message::PublishPlaylistToNcm(playlist_id)),
+            text(locale.get(Key::PublishToNcm)).size(12),
+            tooltip::Position::Bottom,
+        ))
+        .on_enter(Message::HoverIcon(Some(IconId::Publish)))
+        .on_exit(Message::HoverIcon(None));
+
+        control_items.push(publish_btn.into());
     } else if !is_local {
         // For cloud playlists, show like button only if not own playlist
         if !is_own_playlist {
@@ -569,10 +786,96 @@ fn build_controls<'a>(
         .on_exit(Message::HoverIcon(None));
 
         control_items.push(download_btn.into());
+
+        // Availability check; daily recommendations (ID 0) have no track list
+        if playlist_id != 0 {
+            control_items.push(Space::new().width(16).into());
+            let health_color = get_icon_color(IconId::Health);
+            let health_btn = mouse_area(tooltip(
+                button(
+                    svg(svg::Handle::from_memory(icons::SHIELD_CHECK.as_bytes()))
+                        .width(22)
+                        .height(22)
+                        .style(move |_theme, _status| svg::Style {
+                            color: Some(health_color),
+                        }),
+                )
+                .style(theme::transparent_btn)
+                .on_press(Message::CheckPlaylistHealth(playlist_id)),
+                text(locale.get(Key::HealthCheck)).size(12),
+                tooltip::Position::Bottom,
+            ))
+            .on_enter(Message::HoverIcon(Some(IconId::Health)))
+            .on_exit(Message::HoverIcon(None));
+
+            control_items.push(health_btn.into());
+        }
     }
 
+    // Export the songs that are on this device
+    control_items.push(Space::new().width(16).into());
+    let export_color = get_icon_color(IconId::Export);
+    let export_btn = mouse_area(tooltip(
+        button(
+            svg(svg::Handle::from_memory(icons::EXPORT.as_bytes()))
+                .width(22)
+                .height(22)
+                .style(move |_theme, _status| svg::Style {
+                    color: Some(export_color),
+                }),
+        )
+        .style(theme::transparent_btn)
+        .on_press(Message::ExportPlaylist(playlist_id)),
+        text(locale.get(Key::ExportPlaylist)).size(12),
+        tooltip::Position::Bottom,
+    ))
+    .on_enter(Message::HoverIcon(Some(IconId::Export)))
+    .on_exit(Message::HoverIcon(None));
+    control_items.push(export_btn.into());
+
+    // Playback options saved for this playlist
+    control_items.push(Space::new().width(16).into());
+    let options_color = if playback_panel_open {
+        theme::ACCENT_PINK
+    } else {
+        get_icon_color(IconId::PlaybackOptions)
+    };
+    let options_btn = mouse_area(
+        button(
+            svg(svg::Handle::from_memory(icons::SETTINGS.as_bytes()))
+                .width(22)
+                .height(22)
+                .style(move |_theme, _status| svg::Style {
+                    color: Some(options_color),
+                }),
+        )
+        .style(theme::transparent_btn)
+        .on_press(Message::TogglePlaylistPlaybackPanel),
+    )
+    .on_enter(Message::HoverIcon(Some(IconId::PlaybackOptions)))
+    .on_exit(Message::HoverIcon(None));
+    control_items.push(options_btn.into());
+
     control_items.push(Space::new().width(Fill).into());
 
+    // Large playlists keep loading in the background after the first rows show up
+    if let Some((loaded, total)) = load_progress {
+        let progress = format!("{}/{}", loaded, total);
+        control_items.push(
+            text(
+                locale
+                    .get(Key::PlaylistLoadingSongs)
+                    .replace("{}", &progress),
+            )
+            .size(13)
+            .style(|theme| text::Style {
+                color: Some(theme::text_secondary(theme)),
+            })
+            .into(),
+        );
+        control_items.push(Space::new().width(20).into());
+    }
+
     // Animated search component - expands from right to left
     let search_progress = search_animation.progress();
     let search_color = get_icon_color(IconId::Search);
@@ -679,6 +982,150 @@ fn build_controls<'a>(
     container(controls).width(Fill).into()
 }
 
+/// Build the playback options row (shuffle, quality, start mode)
+fn build_playback_panel<'a>(playback: PlaylistPlayback, locale: Locale) -> Element<'a, Message> {
+    let label = |key: Key| {
+        text(locale.get(key)).size(14).style(|theme| text::Style {
+            color: Some(theme::text_secondary(theme)),
+        })
+    };
+
+    let shuffle = toggler(playback.shuffle)
+        .label(locale.get(Key::PlaylistPlaybackShuffle))
+        .text_size(14)
+        .size(20)
+        .on_toggle(move |shuffle| {
+            Message::UpdatePlaylistPlayback(PlaylistPlayback {
+                shuffle,
+                ..playback
+            })
+        });
+
+    let qualities: Vec<(Option<MusicQuality>, String)> = std::iter::once((
+        None,
+        locale.get(Key::PlaylistPlaybackQualityDefault).to_string(),
+    ))
+    .chain(
+        MusicQuality::all()
+            .into_iter()
+            .map(|q| (Some(q), q.display_name(locale).to_string())),
+    )
+    .collect();
+    let quality_labels: Vec<String> = qualities.iter().map(|(_, l)| l.clone()).collect();
+    let current_quality = qualities
+        .iter()
+        .find(|(q, _)| *q == playback.quality)
+        .map(|(_, l)| l.clone());
+    let quality = pick_list(quality_labels, current_quality, move |value| {
+        let quality = qualities
+            .iter()
+            .find(|(_, l)| *l == value)
+            .and_then(|(q, _)| *q);
+        Message::UpdatePlaylistPlayback(PlaylistPlayback {
+            quality,
+            ..playback
+        })
+    })
+    .style(theme::settings_pick_list)
+    .menu_style(theme::settings_pick_list_menu)
+    .text_size(14)
+    .padding([6, 10]);
+
+    let replace_label = locale.get(Key::PlaylistPlaybackReplaceQueue).to_string();
+    let append_label = locale.get(Key::PlaylistPlaybackAppendQueue).to_string();
+    let current_start = if playback.append_to_queue {
+        append_label.clone()
+    } else {
+        replace_label.clone()
+    };
+    let start_mode = pick_list(
+        vec![replace_label, append_label.clone()],
+        Some(current_start),
+        move |value| {
+            Message::UpdatePlaylistPlayback(PlaylistPlayback {
+                append_to_queue: value == append_label,
+                ..playback
+            })
+        },
+    )
+    .style(theme::settings_pick_list)
+    .menu_style(theme::settings_pick_list_menu)
+    .text_size(14)
+    .padding([6, 10]);
+
+    row![
+        shuffle,
+        Space::new().width(32),
+        label(Key::PlaylistPlaybackQuality),
+        quality,
+        Space::new().width(32),
+        label(Key::PlaylistPlaybackStartMode),
+        start_mode,
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .padding(Padding::new(0.0).left(36.0).right(36.0).bottom(16.0))
+    .into()
+}
+
+/// Order and filters for an NCM playlist's loaded songs
+fn build_sort_bar<'a>(
+    sort: PlaylistSort,
+    filter: PlaylistFilter,
+    locale: Locale,
+) -> Element<'a, Message> {
+    let sort_list = pick_list(
+        PlaylistSort::ALL
+            .map(|s| locale.get(s.label_key()))
+            .to_vec(),
+        Some(locale.get(sort.label_key())),
+        move |label| {
+            PlaylistSort::ALL
+                .into_iter()
+                .find(|s| locale.get(s.label_key()) == label)
+                .map_or(Message::NoOp, Message::PlaylistSortChanged)
+        },
+    )
+    .style(theme::settings_pick_list)
+    .menu_style(theme::settings_pick_list_menu)
+    .text_size(14)
+    .padding([6, 10]);
+
+    let only_cached = toggler(filter.only_cached)
+        .label(locale.get(Key::PlaylistFilterCached))
+        .text_size(14)
+        .size(20)
+        .on_toggle(move |only_cached| {
+            Message::PlaylistFilterChanged(PlaylistFilter {
+                only_cached,
+                ..filter
+            })
+        });
+
+    let only_hires = toggler(filter.only_hires)
+        .label(locale.get(Key::PlaylistFilterHires))
+        .text_size(14)
+        .size(20)
+        .on_toggle(move |only_hires| {
+            Message::PlaylistFilterChanged(PlaylistFilter {
+                only_hires,
+                ..filter
+            })
+        });
+
+    row![
+        sort_list,
+        Space::new().width(32),
+        only_cached,
+        Space::new().width(22),
+        only_hires,
+    ]
+    .spacing(10)
+    .align_y(Alignment::Center)
+    .padding(Padding::new(0.0).left(36.0).right(36.0).bottom(16.0))
+    .into()
+}
+
 /// Build cover placeholder (music icon on dark background)
 fn build_cover_placeholder() -> Element<'static, Message> {
     container(
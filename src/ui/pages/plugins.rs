@@ -0,0 +1,263 @@
+//! Plugin manager page
+//!
+//! Lists plugins found in the plugins directory with enable toggles,
+//! load errors, registered hooks, and panels rendered by UI plugins.
+
+use iced::widget::{Space, button, column, container, row, scrollable, text, toggler};
+use iced::{Alignment, Background, Border, Element, Fill, Padding};
+
+use crate::app::{Message, PluginsPageState};
+use crate::features::plugins::{
+    PluginEntry, PluginHook, PluginPanel, PluginRegistry, PluginStatus,
+};
+use crate::i18n::{Key, Locale};
+use crate::ui::theme;
+
+/// Plugin manager page view
+pub fn view(
+    registry: &PluginRegistry,
+    state: &PluginsPageState,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let header = column![
+        text(locale.get(Key::PluginsTitle).to_string())
+            .size(28)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme)),
+            }),
+        Space::new().height(6),
+        text(locale.get(Key::PluginsDesc).to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+    ];
+
+    let actions = row![
+        button(text(locale.get(Key::PluginsOpenFolder).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::OpenPluginsFolder),
+        button(text(locale.get(Key::PluginsReload).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press_maybe((!state.loading).then_some(Message::ReloadPlugins)),
+    ]
+    .spacing(12);
+
+    let header_container =
+        container(row![header, Space::new().width(Fill), actions].align_y(Alignment::Center))
+            .width(Fill)
+            .padding(
+                Padding::new(40.0)
+                    .top(70.0)
+                    .right(32.0)
+                    .bottom(20.0)
+                    .left(32.0),
+            )
+            .style(|theme| container::Style {
+                background: Some(Background::Color(theme::background(theme))),
+                ..Default::default()
+            });
+
+    let content: Element<'static, Message> = if state.loading && registry.plugins().is_empty() {
+        placeholder(locale.get(Key::PluginsLoading))
+    } else if registry.plugins().is_empty() {
+        placeholder(locale.get(Key::PluginsEmpty))
+    } else {
+        column(registry.plugins().iter().map(|plugin| {
+            let panel = state
+                .panels
+                .iter()
+                .find(|(id, _)| *id == plugin.manifest.id)
+                .map(|(_, panel)| panel);
+            plugin_card(plugin, panel, locale)
+        }))
+        .spacing(16)
+        .width(Fill)
+        .into()
+    };
+
+    let scrollable_content = scrollable(
+        container(content)
+            .width(Fill)
+            .padding(Padding::new(20.0).right(32.0).bottom(60.0).left(32.0)),
+    )
+    .id(iced::widget::Id::new("plugins_scroll"))
+    .width(Fill)
+    .height(Fill);
+
+    container(
+        column![header_container, scrollable_content]
+            .width(Fill)
+            .height(Fill),
+    )
+    .width(Fill)
+    .height(Fill)
+    .style(theme::main_content)
+    .into()
+}
+
+fn placeholder(message: &str) -> Element<'static, Message> {
+    container(
+        text(message.to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+    )
+    .width(Fill)
+    .height(120)
+    .center_x(Fill)
+    .center_y(120)
+    .into()
+}
+
+fn hook_label(hook: PluginHook, locale: Locale) -> &'static str {
+    match hook {
+        PluginHook::Metadata => locale.get(Key::PluginHookMetadata),
+        PluginHook::Lyrics => locale.get(Key::PluginHookLyrics),
+        PluginHook::AudioDsp => locale.get(Key::PluginHookAudioDsp),
+        PluginHook::UiPanel => locale.get(Key::PluginHookUiPanel),
+    }
+}
+
+fn hook_badge(label: &str) -> Element<'static, Message> {
+    container(text(label.to_string()).size(11).style(|theme| text::Style {
+        color: Some(theme::settings_desc(theme)),
+    }))
+    .padding([2, 8])
+    .style(|theme| container::Style {
+        border: Border {
+            color: theme::divider(theme),
+            width: 1.0,
+            radius: 10.0.into(),
+        },
+        ..Default::default()
+    })
+    .into()
+}
+
+/// Single plugin row with its panel (if any)
+fn plugin_card(
+    plugin: &PluginEntry,
+    panel: Option<&PluginPanel>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let manifest = &plugin.manifest;
+    let plugin_id = manifest.id.clone();
+    let enabled = plugin.status != PluginStatus::Disabled;
+
+    let mut subtitle = format!("{} · {}", manifest.version, manifest.id);
+    if !manifest.author.is_empty() {
+        subtitle = format!("{} · {}", subtitle, manifest.author);
+    }
+
+    let title_row = row![
+        text(manifest.name.clone())
+            .size(16)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_label(theme)),
+            }),
+        Space::new().width(12),
+        text(subtitle).size(12).style(|theme| text::Style {
+            color: Some(theme::settings_desc(theme)),
+        }),
+        Space::new().width(Fill),
+        toggler(enabled)
+            .on_toggle(move |on| Message::TogglePlugin(plugin_id.clone(), on))
+            .size(24),
+    ]
+    .align_y(Alignment::Center);
+
+    let mut body = column![title_row].spacing(8);
+
+    if !manifest.description.is_empty() {
+        body = body.push(
+            text(manifest.description.clone())
+                .size(13)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_desc(theme)),
+                }),
+        );
+    }
+
+    if !manifest.hooks.is_empty() {
+        body = body.push(
+            row(manifest
+                .hooks
+                .iter()
+                .map(|hook| hook_badge(hook_label(*hook, locale))))
+            .spacing(6),
+        );
+    }
+
+    if let PluginStatus::Failed(error) = &plugin.status {
+        body = body.push(
+            text(format!("{}: {}", locale.get(Key::PluginsLoadFailed), error))
+                .size(12)
+                .style(|theme| text::Style {
+                    color: Some(theme::danger(theme)),
+                }),
+        );
+    }
+
+    if let Some(panel) = panel {
+        body = body.push(panel_view(&manifest.id, panel));
+    }
+
+    container(body)
+        .width(Fill)
+        .padding(16)
+        .style(|theme| container::Style {
+            background: Some(Background::Color(theme::surface_container(theme))),
+            border: Border {
+                radius: 12.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Panel rendered by a UI plugin
+fn panel_view(plugin_id: &str, panel: &PluginPanel) -> Element<'static, Message> {
+    let divider = container(Space::new().width(Fill).height(1)).style(|theme| container::Style {
+        background: Some(Background::Color(theme::divider(theme))),
+        ..Default::default()
+    });
+
+    let mut content = column![
+        divider,
+        text(panel.title.clone())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_label(theme)),
+            }),
+    ]
+    .spacing(8);
+
+    for line in &panel.lines {
+        content = content.push(text(line.clone()).size(13).style(|theme| text::Style {
+            color: Some(theme::settings_value(theme)),
+        }));
+    }
+
+    if !panel.actions.is_empty() {
+        content = content.push(
+            row(panel.actions.iter().map(|action| {
+                button(text(action.label.clone()).size(13))
+                    .style(theme::secondary_button)
+                    .padding([6, 12])
+                    .on_press(Message::PluginPanelAction(
+                        plugin_id.to_string(),
+                        action.id.clone(),
+                    ))
+                    .into()
+            }))
+            .spacing(8),
+        );
+    }
+
+    content.into()
+}
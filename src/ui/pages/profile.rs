@@ -0,0 +1,358 @@
+//! NCM user profile page
+//!
+//! Shows a user's avatar, level and listening stats, their listening rank
+//! for last week or all time, and the playlists they made or saved.
+
+use iced::widget::{Column, Space, button, column, container, image, row, scrollable, svg, text};
+use iced::{Alignment, Background, Element, Fill, Padding};
+
+use crate::api::{PlayRecord, SongList};
+use crate::app::{DiscoverPageState, Message, ProfilePageState};
+use crate::i18n::{Key, Locale};
+use crate::ui::components::playlist_grid;
+use crate::ui::icons;
+use crate::ui::theme::{self, BOLD_WEIGHT};
+
+const AVATAR_SIZE: f32 = 96.0;
+
+/// Songs shown in the listening rank
+const RANK_LEN: usize = 20;
+
+/// Profile page view; playlist covers are shared with the discover page
+pub fn view<'a>(
+    state: &'a ProfilePageState,
+    discover: &'a DiscoverPageState,
+    locale: Locale,
+) -> Element<'a, Message> {
+    let header_container = container(header(state, locale))
+        .width(Fill)
+        .padding(
+            Padding::new(40.0)
+                .top(70.0)
+                .right(32.0)
+                .bottom(20.0)
+                .left(32.0),
+        )
+        .style(|theme| container::Style {
+            background: Some(Background::Color(theme::background(theme))),
+            ..Default::default()
+        });
+
+    let content: Element<'a, Message> = if state.profile.is_none() {
+        Space::new().height(0).into()
+    } else {
+        column![
+            rank_section(state, locale),
+            playlist_section(
+                locale.get(Key::ProfileCreated),
+                &state.playlists.created,
+                discover,
+                locale,
+            ),
+            playlist_section(
+                locale.get(Key::ProfileSubscribed),
+                &state.playlists.subscribed,
+                discover,
+                locale,
+            ),
+        ]
+        .spacing(40)
+        .width(Fill)
+        .into()
+    };
+
+    let scrollable_content = scrollable(
+        container(content)
+            .width(Fill)
+            .padding(Padding::new(20.0).right(32.0).bottom(60.0).left(32.0)),
+    )
+    .id(iced::widget::Id::new("profile_scroll"))
+    .width(Fill)
+    .height(Fill)
+    .style(theme::dark_scrollable);
+
+    container(
+        column![header_container, scrollable_content]
+            .width(Fill)
+            .height(Fill),
+    )
+    .width(Fill)
+    .height(Fill)
+    .style(theme::main_content)
+    .into()
+}
+
+/// Avatar, name, level and stats, or the loading state
+fn header(state: &ProfilePageState, locale: Locale) -> Element<'static, Message> {
+    let Some(profile) = &state.profile else {
+        let label = if state.failed {
+            locale.get(Key::ProfileLoadFailed)
+        } else {
+            locale.get(Key::Loading)
+        };
+        return text(label.to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            })
+            .into();
+    };
+
+    let avatar: Element<'static, Message> = match &state.avatar {
+        Some(handle) => image(handle.clone())
+            .width(AVATAR_SIZE)
+            .height(AVATAR_SIZE)
+            .content_fit(iced::ContentFit::Cover)
+            .border_radius(AVATAR_SIZE / 2.0)
+            .into(),
+        None => container(
+            svg(svg::Handle::from_memory(icons::USER.as_bytes()))
+                .width(40)
+                .height(40)
+                .style(|theme, _status| svg::Style {
+                    color: Some(theme::text_secondary(theme)),
+                }),
+        )
+        .width(AVATAR_SIZE)
+        .height(AVATAR_SIZE)
+        .center_x(AVATAR_SIZE)
+        .center_y(AVATAR_SIZE)
+        .style(|theme| container::Style {
+            background: Some(Background::Color(theme::border_color(theme))),
+            border: iced::Border {
+                radius: (AVATAR_SIZE / 2.0).into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into(),
+    };
+
+    let mut badges = row![badge(
+        locale
+            .get(Key::ProfileLevel)
+            .replace("{}", &profile.level.to_string())
+    )]
+    .spacing(6);
+    if profile.vip_type > 0 {
+        badges = badges.push(badge("VIP".to_string()));
+    }
+
+    let name = row![
+        text(profile.nickname.clone())
+            .size(28)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme)),
+            })
+            .font(iced::Font {
+                weight: BOLD_WEIGHT,
+                ..Default::default()
+            }),
+        badges,
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    let stats = locale
+        .get(Key::ProfileStats)
+        .replacen("{}", &profile.listen_songs.to_string(), 1)
+        .replacen("{}", &profile.follows.to_string(), 1)
+        .replacen("{}", &profile.followeds.to_string(), 1);
+
+    let mut details = column![name].spacing(8);
+    if !profile.signature.is_empty() {
+        details =
+            details.push(
+                text(profile.signature.clone())
+                    .size(14)
+                    .style(|theme| text::Style {
+                        color: Some(theme::text_secondary(theme)),
+                    }),
+            );
+    }
+    details = details.push(text(stats).size(13).style(|theme| text::Style {
+        color: Some(theme::settings_desc(theme)),
+    }));
+
+    row![avatar, details]
+        .spacing(24)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+fn badge(label: String) -> Element<'static, Message> {
+    container(text(label).size(11).color(theme::ACCENT_PINK))
+        .padding([2, 8])
+        .style(|_theme| container::Style {
+            border: iced::Border {
+                radius: 10.0.into(),
+                width: 1.0,
+                color: theme::ACCENT_PINK,
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Listening rank with its last week / all time switch
+fn rank_section(state: &ProfilePageState, locale: Locale) -> Element<'static, Message> {
+    let range_button = |label: Key, all_time: bool| {
+        let active = state.rank_all_time == all_time;
+        button(text(locale.get(label).to_string()).size(13))
+            .padding([6, 12])
+            .style(if active {
+                theme::primary_button
+            } else {
+                theme::secondary_button
+            })
+            .on_press_maybe((!active).then_some(Message::SetProfileRankAllTime(all_time)))
+    };
+
+    let mut title = row![section_title(locale.get(Key::ProfileRank))]
+        .spacing(8)
+        .align_y(Alignment::Center);
+    if !state.rank_hidden {
+        title = title
+            .push(Space::new().width(Fill))
+            .push(range_button(Key::ProfileRankWeek, false))
+            .push(range_button(Key::ProfileRankAllTime, true));
+    }
+
+    let body: Element<'static, Message> = if state.rank_hidden {
+        hint(locale.get(Key::ProfileRankHidden))
+    } else if state.rank_loading {
+        hint(locale.get(Key::Loading))
+    } else if state.rank.is_empty() {
+        hint(locale.get(Key::ProfileRankEmpty))
+    } else {
+        Column::with_children(
+            state
+                .rank
+                .iter()
+                .take(RANK_LEN)
+                .enumerate()
+                .map(|(i, record)| rank_row(i, record, locale)),
+        )
+        .spacing(4)
+        .width(Fill)
+        .into()
+    };
+
+    column![title, body].spacing(16).width(Fill).into()
+}
+
+/// One ranked song; clicking it plays the song
+fn rank_row(index: usize, record: &PlayRecord, locale: Locale) -> Element<'static, Message> {
+    let song = &record.song;
+    let score = record.score.min(100) as f32 / 100.0;
+    let plays = if record.play_count > 0 {
+        locale.count(Key::ProfilePlays, record.play_count)
+    } else {
+        String::new()
+    };
+
+    let bar = container(Space::new().width(Fill).height(4))
+        .width(iced::Length::FillPortion((score * 100.0).max(1.0) as u16))
+        .style(|_theme| container::Style {
+            background: Some(Background::Color(theme::ACCENT_PINK)),
+            border: iced::Border {
+                radius: 2.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    let bar = row![
+        bar,
+        Space::new().width(iced::Length::FillPortion(
+            ((1.0 - score) * 100.0).max(1.0) as u16
+        )),
+    ]
+    .width(160);
+
+    button(
+        row![
+            text((index + 1).to_string())
+                .size(15)
+                .width(32)
+                .style(|theme| text::Style {
+                    color: Some(theme::dimmed_text(theme)),
+                }),
+            column![
+                text(song.name.clone()).size(14).style(|theme| text::Style {
+                    color: Some(theme::text_primary(theme)),
+                }),
+                text(song.singer.clone())
+                    .size(12)
+                    .style(|theme| text::Style {
+                        color: Some(theme::text_secondary(theme)),
+                    }),
+            ]
+            .spacing(2)
+            .width(Fill),
+            text(plays).size(12).style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+            bar,
+        ]
+        .spacing(16)
+        .align_y(Alignment::Center),
+    )
+    .width(Fill)
+    .padding([8, 12])
+    .style(|theme, status| button::Style {
+        background: matches!(status, button::Status::Hovered)
+            .then(|| Background::Color(theme::hover_bg_alpha(theme, 0.08))),
+        border: iced::Border {
+            radius: 8.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .on_press(Message::PlayNcmSong(song.clone()))
+    .into()
+}
+
+fn playlist_section<'a>(
+    title: &str,
+    playlists: &'a [SongList],
+    discover: &'a DiscoverPageState,
+    locale: Locale,
+) -> Element<'a, Message> {
+    let body = if playlists.is_empty() {
+        hint(locale.get(Key::ProfileNoPlaylists))
+    } else {
+        playlist_grid::view(
+            playlists,
+            &discover.playlist_covers,
+            &discover.card_animations,
+            None,
+            discover.content_width,
+        )
+    };
+    column![section_title(title), body]
+        .spacing(16)
+        .width(Fill)
+        .into()
+}
+
+fn section_title(title: &str) -> Element<'static, Message> {
+    text(title.to_string())
+        .size(18)
+        .style(|theme| text::Style {
+            color: Some(theme::settings_label(theme)),
+        })
+        .font(iced::Font {
+            weight: BOLD_WEIGHT,
+            ..Default::default()
+        })
+        .into()
+}
+
+fn hint(label: &str) -> Element<'static, Message> {
+    text(label.to_string())
+        .size(13)
+        .style(|theme| text::Style {
+            color: Some(theme::settings_desc(theme)),
+        })
+        .into()
+}
@@ -3,11 +3,13 @@
 //! Displays search results for songs, artists, albums, and playlists
 //! with tabbed navigation and pagination.
 
+use std::collections::HashSet;
+
 use iced::widget::{button, column, container, row, scrollable, text, Space};
 use iced::{Alignment, Element, Fill, Length, Padding};
 
-use crate::app::{Message, SearchPageState, SearchTab};
-use crate::i18n::Locale;
+use crate::app::{Message, SearchPageState, SearchSource, SearchTab};
+use crate::i18n::{Key, Locale};
 use crate::ui::theme;
 
 use crate::ui::primitives::virtual_list::VirtualList;
@@ -15,17 +17,38 @@ use crate::ui::primitives::virtual_list::VirtualList;
 /// Page size for pagination
 const PAGE_SIZE: u32 = 50;
 const SONG_ROW_HEIGHT: f32 = 64.0;
+/// Local matches listed above the online results before "Show all"
+const LOCAL_PREVIEW: usize = 5;
 
 /// Build the search results page view
-pub fn view<'a>(state: &'a SearchPageState, locale: Locale) -> Element<'a, Message> {
+pub fn view<'a>(
+    state: &'a SearchPageState,
+    followed_artists: &HashSet<u64>,
+    locale: Locale,
+) -> Element<'a, Message> {
     if state.keyword.is_empty() {
         return empty_search_state(locale);
     }
 
+    // The keyword is bold, the words around it muted, in whatever order
+    // the language puts them
+    let (before, after) = locale
+        .get(Key::SearchResultsFor)
+        .split_once("{}")
+        .unwrap_or(("", ""));
+    let muted = |label: &'static str| {
+        text(label)
+            .size(28)
+            .style(|theme| iced::widget::text::Style {
+                color: Some(theme::text_muted(theme)),
+            })
+    };
+
     // Fixed header section (Title + Tabs)
-    let header_section = column![
+    let mut header_section = column![
         // Header with keyword
         row![
+            muted(before),
             text(&state.keyword)
                 .size(28)
                 .style(|theme| iced::widget::text::Style {
@@ -35,173 +58,75 @@ pub fn view<'a>(state: &'a SearchPageState, locale: Locale) -> Element<'a, Messa
                     weight: iced::font::Weight::Bold,
                     ..Default::default()
                 }),
-            text(" 的相关搜索")
-                .size(28)
-                .style(|theme| iced::widget::text::Style {
-                    color: Some(theme::text_muted(theme)),
-                }),
+            muted(after),
         ]
         .align_y(Alignment::Center),
         Space::new().height(24),
         // Search tabs
-        search_tabs(state.active_tab),
-        Space::new().height(24),
-    ]
-    .padding(Padding::new(32.0).top(80.0).bottom(0.0));
+        search_tabs(state.active_tab, locale),
+    ];
+    // Only songs exist in the local library
+    if state.active_tab == SearchTab::Songs {
+        header_section = header_section
+            .push(Space::new().height(12))
+            .push(source_chips(state, locale));
+    }
+    let header_section = header_section
+        .push(Space::new().height(24))
+        .padding(Padding::new(32.0).top(80.0).bottom(0.0));
 
     // Content area
-    let content: Element<'a, Message> = if state.loading {
-        loading_state()
-    } else {
-        match state.active_tab {
-            SearchTab::Songs => {
-                if state.songs.is_empty() {
-                    empty_results_state(&state.keyword)
-                } else {
-                    // Use VirtualList for high performance song list
-                    let song_count = state.songs.len();
-                    let songs = std::rc::Rc::new(state.songs.clone());
-                    let songs_for_builder = songs.clone();
-                    let songs_for_hover = songs.clone();
-
-                    let song_animations = state.song_animations.clone();
-                    let current_page = state.current_page;
-
-                    let table_header = search_table_header();
-
-                    let virtual_list = VirtualList::new(song_count, SONG_ROW_HEIGHT, move |index| {
-                        if index >= songs_for_builder.len() {
-                            return Space::new().into();
-                        }
-
-                        let song = &songs_for_builder[index];
-                        let hover_progress = song_animations.get_progress(&song.id);
-                        let song_clone = song.clone();
-                        let index_num = current_page * PAGE_SIZE + index as u32 + 1;
-                        let duration_secs = song.duration / 1000;
-                        let duration_str = format!("{}:{:02}", duration_secs / 60, duration_secs % 60);
-
-                        let song_row = button(
-                            row![
-                                text(format!("{:02}", index_num))
-                                    .size(13)
-                                    .style(|theme| iced::widget::text::Style {
-                                        color: Some(theme::text_muted(theme)),
-                                    })
-                                    .width(40),
-                                column![text(song.name.clone())
-                                    .size(14)
-                                    .style(move |theme| iced::widget::text::Style {
-                                        color: Some(theme::animated_text(theme, hover_progress)),
-                                    }),]
-                                .width(Fill),
-                                text(song.singer.clone())
-                                    .size(13)
-                                    .style(|theme| iced::widget::text::Style {
-                                        color: Some(theme::text_secondary(theme)),
-                                    })
-                                    .width(Length::FillPortion(2)),
-                                text(song.album.clone())
-                                    .size(13)
-                                    .style(|theme| iced::widget::text::Style {
-                                        color: Some(theme::text_muted(theme)),
-                                    })
-                                    .width(Length::FillPortion(2)),
-                                text(duration_str)
-                                    .size(13)
-                                    .style(|theme| iced::widget::text::Style {
-                                        color: Some(theme::text_muted(theme)),
-                                    })
-                                    .width(60),
-                            ]
-                            .spacing(12)
-                            .align_y(Alignment::Center)
-                            .padding(Padding::new(10.0).left(12.0).right(12.0)),
-                        )
-                        .style(move |theme, status| song_row_style(theme, status, hover_progress))
-                        .on_press(Message::PlaySearchSong(song_clone))
-                        .width(Fill);
-
-                        Element::from(song_row)
-                    })
-                    .state(state.scroll_state.clone())
-                    .on_item_hover(move |index| {
-                        if index < songs_for_hover.len() {
-                            Message::HoverSearchSong(Some(songs_for_hover[index].id))
-                        } else {
-                            Message::HoverSearchSong(None)
-                        }
-                    })
-                    .on_empty_area(Message::HoverSearchSong(None))
-                    .height(Length::Fill);
-
-                    let list_section = column![
-                        table_header,
-                        Space::new().height(8),
-                        container(virtual_list).height(Fill).width(Fill),
-                    ]
-                    .padding(Padding::new(32.0).top(0.0));
-
-                    if state.total_count > PAGE_SIZE {
-                        column![
-                            list_section.height(Fill),
-                            Space::new().height(16),
-                            pagination(state),
-                            Space::new().height(32),
-                        ]
-                        .height(Fill)
-                        .into()
-                    } else {
-                        column![list_section.height(Fill), Space::new().height(32),]
-                            .height(Fill)
-                            .into()
-                    }
+    let content: Element<'a, Message> = match state.active_tab {
+        // Local results don't wait for the online request
+        SearchTab::Songs => song_results(state, locale),
+        _ if state.loading => loading_state(locale),
+        SearchTab::Albums | SearchTab::Artists => {
+            let content = if state.albums.is_empty() {
+                empty_results_state(&state.keyword, locale)
+            } else {
+                let grid = grid_results(state, state.active_tab, followed_artists, locale);
+                let mut col = column![grid];
+
+                if state.total_count > PAGE_SIZE {
+                    col = col
+                        .push(Space::new().height(24))
+                        .push(pagination(state, locale));
                 }
-            }
-            SearchTab::Albums | SearchTab::Artists => {
-                let content = if state.albums.is_empty() {
-                    empty_results_state(&state.keyword)
-                } else {
-                    let grid = grid_results(state, SearchTab::Albums);
-                    let mut col = column![grid];
-
-                    if state.total_count > PAGE_SIZE {
-                        col = col.push(Space::new().height(24)).push(pagination(state));
-                    }
-                    col = col.push(Space::new().height(40));
-
-                    col.padding(Padding::new(32.0).top(0.0)).into()
-                };
-
-                scrollable(content)
-                    .width(Fill)
-                    .height(Fill)
-                    .id(iced::widget::Id::new("search_scroll"))
-                    .style(theme::dark_scrollable)
-                    .into()
-            }
-            SearchTab::Playlists => {
-                let content = if state.playlists.is_empty() {
-                    empty_results_state(&state.keyword)
-                } else {
-                    let grid = grid_results(state, SearchTab::Playlists);
-                    let mut col = column![grid];
-
-                    if state.total_count > PAGE_SIZE {
-                        col = col.push(Space::new().height(24)).push(pagination(state));
-                    }
-                    col = col.push(Space::new().height(40));
-
-                    col.padding(Padding::new(32.0).top(0.0)).into()
-                };
-
-                scrollable(content)
-                    .width(Fill)
-                    .height(Fill)
-                    .id(iced::widget::Id::new("search_scroll"))
-                    .style(theme::dark_scrollable)
-                    .into()
-            }
+                col = col.push(Space::new().height(40));
+
+                col.padding(Padding::new(32.0).top(0.0)).into()
+            };
+
+            scrollable(content)
+                .width(Fill)
+                .height(Fill)
+                .id(iced::widget::Id::new("search_scroll"))
+                .style(theme::dark_scrollable)
+                .into()
+        }
+        SearchTab::Playlists => {
+            let content = if state.playlists.is_empty() {
+                empty_results_state(&state.keyword, locale)
+            } else {
+                let grid = grid_results(state, SearchTab::Playlists, followed_artists, locale);
+                let mut col = column![grid];
+
+                if state.total_count > PAGE_SIZE {
+                    col = col
+                        .push(Space::new().height(24))
+                        .push(pagination(state, locale));
+                }
+                col = col.push(Space::new().height(40));
+
+                col.padding(Padding::new(32.0).top(0.0)).into()
+            };
+
+            scrollable(content)
+                .width(Fill)
+                .height(Fill)
+                .id(iced::widget::Id::new("search_scroll"))
+                .style(theme::dark_scrollable)
+                .into()
         }
     };
 
@@ -212,13 +137,315 @@ pub fn view<'a>(state: &'a SearchPageState, locale: Locale) -> Element<'a, Messa
         .into()
 }
 
+/// Song results for the active source filter; with both sources, local
+/// matches come first in their own section
+fn song_results<'a>(state: &'a SearchPageState, locale: Locale) -> Element<'a, Message> {
+    let show_local = match state.source {
+        SearchSource::All => state.current_page == 0 && !state.local_songs.is_empty(),
+        SearchSource::Local => {
+            if state.local_songs.is_empty() {
+                return empty_results_state(&state.keyword, locale);
+            }
+            return column![
+                search_table_header(locale),
+                Space::new().height(8),
+                local_song_list(state),
+                Space::new().height(32),
+            ]
+            .padding(Padding::new(32.0).top(0.0))
+            .height(Fill)
+            .into();
+        }
+        SearchSource::Online => false,
+    };
+
+    if !show_local {
+        return if state.loading {
+            loading_state(locale)
+        } else if state.songs.is_empty() {
+            empty_results_state(&state.keyword, locale)
+        } else {
+            online_song_list(state, locale)
+        };
+    }
+
+    let local_count = state.local_songs.len();
+    let show_all = (local_count > LOCAL_PREVIEW).then(|| {
+        button(
+            text(
+                locale
+                    .get(Key::SearchShowAllLocal)
+                    .replace("{}", &local_count.to_string()),
+            )
+            .size(13),
+        )
+        .padding([4, 8])
+        .style(theme::text_button)
+        .on_press(Message::SearchSourceChanged(SearchSource::Local))
+        .into()
+    });
+    let local_rows = state
+        .local_songs
+        .iter()
+        .take(LOCAL_PREVIEW)
+        .enumerate()
+        .map(|(i, song)| local_song_row(i, song));
+    let local_section = column![
+        section_header(locale.get(Key::SearchSectionLocal), show_all),
+        Space::new().height(8),
+        search_table_header(locale),
+        Space::new().height(8),
+        column(local_rows),
+        Space::new().height(24),
+        section_header(locale.get(Key::SearchSectionOnline), None),
+        Space::new().height(8),
+    ]
+    .padding(Padding::new(32.0).top(0.0).bottom(0.0));
+
+    let online: Element<'a, Message> = if state.loading {
+        loading_state(locale)
+    } else if state.songs.is_empty() {
+        container(
+            text(locale.get(Key::SearchOnlineEmpty))
+                .size(14)
+                .style(|theme| iced::widget::text::Style {
+                    color: Some(theme::text_muted(theme)),
+                }),
+        )
+        .padding(Padding::new(16.0).left(44.0))
+        .into()
+    } else {
+        online_song_list(state, locale)
+    };
+
+    column![local_section, online].height(Fill).into()
+}
+
+/// NCM song results in a virtual list, with pagination when there is more
+/// than one page
+fn online_song_list<'a>(state: &'a SearchPageState, locale: Locale) -> Element<'a, Message> {
+    // Borrow the results rather than cloning them on every frame
+    let songs_for_builder = &state.songs;
+    let songs_for_hover = &state.songs;
+    let song_animations = &state.song_animations;
+    let current_page = state.current_page;
+
+    let virtual_list = VirtualList::new(state.songs.len(), SONG_ROW_HEIGHT, move |index| {
+        let Some(song) = songs_for_builder.get(index) else {
+            return Space::new().into();
+        };
+        song_row(
+            current_page * PAGE_SIZE + index as u32 + 1,
+            &song.name,
+            &song.singer,
+            &song.album,
+            song.duration / 1000,
+            song_animations.get_progress(&song.id),
+            Message::PlaySearchSong(song.clone()),
+        )
+    })
+    .state(state.scroll_state.clone())
+    .on_item_hover(move |index| {
+        if index < songs_for_hover.len() {
+            Message::HoverSearchSong(Some(songs_for_hover[index].id))
+        } else {
+            Message::HoverSearchSong(None)
+        }
+    })
+    .on_empty_area(Message::HoverSearchSong(None))
+    .height(Length::Fill);
+
+    let list_section = column![
+        search_table_header(locale),
+        Space::new().height(8),
+        container(virtual_list).height(Fill).width(Fill),
+    ]
+    .padding(Padding::new(32.0).top(0.0));
+
+    if state.total_count > PAGE_SIZE {
+        column![
+            list_section.height(Fill),
+            Space::new().height(16),
+            pagination(state, locale),
+            Space::new().height(32),
+        ]
+        .height(Fill)
+        .into()
+    } else {
+        column![list_section.height(Fill), Space::new().height(32),]
+            .height(Fill)
+            .into()
+    }
+}
+
+/// Every local match in a virtual list
+fn local_song_list<'a>(state: &'a SearchPageState) -> Element<'a, Message> {
+    let songs = &state.local_songs;
+    let virtual_list = VirtualList::new(songs.len(), SONG_ROW_HEIGHT, move |index| {
+        match songs.get(index) {
+            Some(song) => local_song_row(index, song),
+            None => Space::new().into(),
+        }
+    })
+    .state(state.scroll_state.clone())
+    .height(Length::Fill);
+
+    container(virtual_list).height(Fill).width(Fill).into()
+}
+
+fn local_song_row(index: usize, song: &crate::database::DbSong) -> Element<'_, Message> {
+    song_row(
+        index as u32 + 1,
+        &song.title,
+        &song.artist,
+        &song.album,
+        song.duration_secs.max(0) as u64,
+        0.0,
+        Message::PlaySong(song.id),
+    )
+}
+
+/// One song result row: number, title, artist, album and duration
+fn song_row<'a>(
+    number: u32,
+    title: &'a str,
+    artist: &'a str,
+    album: &'a str,
+    duration_secs: u64,
+    hover_progress: f32,
+    on_press: Message,
+) -> Element<'a, Message> {
+    let duration_str = format!("{}:{:02}", duration_secs / 60, duration_secs % 60);
+
+    button(
+        row![
+            text(format!("{:02}", number))
+                .size(13)
+                .style(|theme| iced::widget::text::Style {
+                    color: Some(theme::text_muted(theme)),
+                })
+                .width(40),
+            column![
+                text(title)
+                    .size(14)
+                    .style(move |theme| iced::widget::text::Style {
+                        color: Some(theme::animated_text(theme, hover_progress)),
+                    }),
+            ]
+            .width(Fill),
+            text(artist)
+                .size(13)
+                .style(|theme| iced::widget::text::Style {
+                    color: Some(theme::text_secondary(theme)),
+                })
+                .width(Length::FillPortion(2)),
+            text(album)
+                .size(13)
+                .style(|theme| iced::widget::text::Style {
+                    color: Some(theme::text_muted(theme)),
+                })
+                .width(Length::FillPortion(2)),
+            text(duration_str)
+                .size(13)
+                .style(|theme| iced::widget::text::Style {
+                    color: Some(theme::text_muted(theme)),
+                })
+                .width(60),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center)
+        .padding(Padding::new(10.0).left(12.0).right(12.0)),
+    )
+    .style(move |theme, status| song_row_style(theme, status, hover_progress))
+    .on_press(on_press)
+    .width(Fill)
+    .into()
+}
+
+/// Title of a results section with an optional action on the right
+fn section_header<'a>(
+    title: &'a str,
+    action: Option<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let mut header = row![
+        text(title)
+            .size(16)
+            .style(|theme| iced::widget::text::Style {
+                color: Some(theme::text_primary(theme)),
+            })
+            .font(iced::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+        Space::new().width(Fill),
+    ]
+    .align_y(Alignment::Center)
+    .height(28);
+    if let Some(action) = action {
+        header = header.push(action);
+    }
+    header.into()
+}
+
+/// Source filter chips for song results, with local and online counts
+fn source_chips<'a>(state: &SearchPageState, locale: Locale) -> Element<'a, Message> {
+    let local_count = state.local_songs.len();
+    let chips = [
+        (
+            SearchSource::All,
+            locale.get(Key::SearchSourceAll).to_string(),
+        ),
+        (
+            SearchSource::Local,
+            format!("{} · {}", locale.get(Key::SearchSourceLocal), local_count),
+        ),
+        (
+            SearchSource::Online,
+            if state.loading {
+                locale.get(Key::SearchSourceOnline).to_string()
+            } else {
+                format!(
+                    "{} · {}",
+                    locale.get(Key::SearchSourceOnline),
+                    state.total_count
+                )
+            },
+        ),
+    ];
+
+    let chip_buttons: Vec<Element<'a, Message>> = chips
+        .into_iter()
+        .map(|(source, label)| {
+            let is_active = state.source == source;
+            button(text(label).size(13))
+                .padding(Padding::new(5.0).left(14.0).right(14.0))
+                .style(move |theme, status| chip_style(theme, status, is_active))
+                .on_press(Message::SearchSourceChanged(source))
+                .into()
+        })
+        .collect();
+
+    row(chip_buttons).spacing(8).into()
+}
+
+/// Source chip style: outlined, filled with the accent when active
+fn chip_style(theme: &iced::Theme, status: button::Status, is_active: bool) -> button::Style {
+    let mut style = tab_button_style(theme, status, is_active);
+    style.border = iced::Border {
+        radius: 14.0.into(),
+        width: if is_active { 0.0 } else { 1.0 },
+        color: theme::border_color(theme),
+    };
+    style
+}
+
 /// Search tabs component
-fn search_tabs(active_tab: SearchTab) -> Element<'static, Message> {
+fn search_tabs(active_tab: SearchTab, locale: Locale) -> Element<'static, Message> {
     let tabs = [
-        (SearchTab::Songs, "单曲"),
-        (SearchTab::Artists, "歌手"),
-        (SearchTab::Albums, "专辑"),
-        (SearchTab::Playlists, "歌单"),
+        (SearchTab::Songs, locale.get(Key::SearchTabSongs)),
+        (SearchTab::Artists, locale.get(Key::SearchTabArtists)),
+        (SearchTab::Albums, locale.get(Key::SearchTabAlbums)),
+        (SearchTab::Playlists, locale.get(Key::SearchTabPlaylists)),
     ];
 
     let tab_buttons: Vec<Element<'static, Message>> = tabs
@@ -292,7 +519,7 @@ fn tabs_container_style(theme: &iced::Theme) -> container::Style {
 }
 
 /// Search table header
-fn search_table_header() -> Element<'static, Message> {
+fn search_table_header(locale: Locale) -> Element<'static, Message> {
     row![
         text("#")
             .size(12)
@@ -300,25 +527,25 @@ fn search_table_header() -> Element<'static, Message> {
                 color: Some(theme::text_muted(theme)),
             })
             .width(40),
-        text("标题")
+        text(locale.get(Key::PlaylistHeaderTitle))
             .size(12)
             .style(|theme| iced::widget::text::Style {
                 color: Some(theme::text_muted(theme)),
             })
             .width(Fill),
-        text("歌手")
+        text(locale.get(Key::SearchHeaderArtist))
             .size(12)
             .style(|theme| iced::widget::text::Style {
                 color: Some(theme::text_muted(theme)),
             })
             .width(Length::FillPortion(2)),
-        text("专辑")
+        text(locale.get(Key::PlaylistHeaderAlbum))
             .size(12)
             .style(|theme| iced::widget::text::Style {
                 color: Some(theme::text_muted(theme)),
             })
             .width(Length::FillPortion(2)),
-        text("时长")
+        text(locale.get(Key::SearchHeaderDuration))
             .size(12)
             .style(|theme| iced::widget::text::Style {
                 color: Some(theme::text_muted(theme)),
@@ -355,7 +582,12 @@ fn song_row_style(
 }
 
 /// Grid view for albums and playlists
-fn grid_results<'a>(state: &'a SearchPageState, tab: SearchTab) -> Element<'a, Message> {
+fn grid_results<'a>(
+    state: &'a SearchPageState,
+    tab: SearchTab,
+    followed_artists: &HashSet<u64>,
+    locale: Locale,
+) -> Element<'a, Message> {
     let items = match tab {
         SearchTab::Albums | SearchTab::Artists => &state.albums,
         SearchTab::Playlists => &state.playlists,
@@ -379,7 +611,18 @@ fn grid_results<'a>(state: &'a SearchPageState, tab: SearchTab) -> Element<'a, M
             let item_id = item.id;
             let item_tab = tab;
 
-            let card = grid_card(item, hover_progress, item_id, item_tab);
+            // Artists have no author line; show whether they are followed instead
+            let subtitle = if tab == SearchTab::Artists {
+                locale.get(if followed_artists.contains(&item.id) {
+                    Key::SearchArtistFollowing
+                } else {
+                    Key::SearchArtistFollow
+                })
+            } else {
+                item.author.as_str()
+            };
+
+            let card = grid_card(item, subtitle, hover_progress, item_id, item_tab);
             row_items.push(card);
 
             if row_items.len() < columns * 2 - 1 {
@@ -403,9 +646,10 @@ fn grid_results<'a>(state: &'a SearchPageState, tab: SearchTab) -> Element<'a, M
     column(rows).into()
 }
 
-/// Grid card for album/playlist
+/// Grid card for album/playlist/artist
 fn grid_card<'a>(
     item: &'a crate::api::SongList,
+    subtitle: &'a str,
     hover_progress: f32,
     item_id: u64,
     tab: SearchTab,
@@ -427,7 +671,7 @@ fn grid_card<'a>(
                 color: Some(theme::text_primary(theme)),
             })
             .width(CARD_WIDTH),
-        text(&item.author)
+        text(subtitle)
             .size(12)
             .style(|theme| iced::widget::text::Style {
                 color: Some(theme::text_muted(theme)),
@@ -477,14 +721,14 @@ fn cover_placeholder_style(theme: &iced::Theme, hover_progress: f32) -> containe
 }
 
 /// Pagination component
-fn pagination<'a>(state: &'a SearchPageState) -> Element<'a, Message> {
+fn pagination<'a>(state: &'a SearchPageState, locale: Locale) -> Element<'a, Message> {
     let total_pages = (state.total_count + PAGE_SIZE - 1) / PAGE_SIZE;
     let current_page = state.current_page;
 
     let mut items: Vec<Element<'a, Message>> = Vec::new();
 
     // Previous button
-    let prev_btn = button(text("上一页").size(13))
+    let prev_btn = button(text(locale.get(Key::SearchPreviousPage)).size(13))
         .padding(Padding::new(8.0).left(16.0).right(16.0))
         .style(theme::secondary_button)
         .on_press_maybe(if current_page > 0 {
@@ -505,7 +749,7 @@ fn pagination<'a>(state: &'a SearchPageState) -> Element<'a, Message> {
     );
 
     // Next button
-    let next_btn = button(text("下一页").size(13))
+    let next_btn = button(text(locale.get(Key::SearchNextPage)).size(13))
         .padding(Padding::new(8.0).left(16.0).right(16.0))
         .style(theme::secondary_button)
         .on_press_maybe(if current_page + 1 < total_pages {
@@ -522,24 +766,28 @@ fn pagination<'a>(state: &'a SearchPageState) -> Element<'a, Message> {
 }
 
 /// Loading state
-fn loading_state<'a>() -> Element<'a, Message> {
-    container(text("搜索中...").size(16).style(|theme| iced::widget::text::Style {
-        color: Some(theme::text_muted(theme)),
-    }))
-        .width(Fill)
-        .height(200)
-        .center_x(Fill)
-        .center_y(200)
-        .into()
+fn loading_state<'a>(locale: Locale) -> Element<'a, Message> {
+    container(
+        text(locale.get(Key::Searching))
+            .size(16)
+            .style(|theme| iced::widget::text::Style {
+                color: Some(theme::text_muted(theme)),
+            }),
+    )
+    .width(Fill)
+    .height(200)
+    .center_x(Fill)
+    .center_y(200)
+    .into()
 }
 
 /// Empty search state (no keyword entered)
-fn empty_search_state<'a>(_locale: Locale) -> Element<'a, Message> {
+fn empty_search_state<'a>(locale: Locale) -> Element<'a, Message> {
     container(
         column![
             text("🔍").size(48),
             Space::new().height(16),
-            text("输入关键词开始搜索")
+            text(locale.get(Key::SearchPrompt))
                 .size(16)
                 .style(|theme| iced::widget::text::Style {
                     color: Some(theme::text_muted(theme)),
@@ -556,12 +804,12 @@ fn empty_search_state<'a>(_locale: Locale) -> Element<'a, Message> {
 }
 
 /// Empty results state
-fn empty_results_state<'a>(keyword: &str) -> Element<'a, Message> {
+fn empty_results_state<'a>(keyword: &str, locale: Locale) -> Element<'a, Message> {
     container(
         column![
             text("🔍").size(48),
             Space::new().height(16),
-            text(format!("未找到 \"{}\" 的相关结果", keyword))
+            text(locale.get(Key::SearchNoResults).replace("{}", keyword))
                 .size(16)
                 .style(|theme| iced::widget::text::Style {
                     color: Some(theme::text_muted(theme)),
@@ -5,37 +5,76 @@
 //! Clicking tab scrolls to corresponding section
 
 use iced::widget::{
-    button, column, container, pick_list, row, scrollable, svg, text, text_input, toggler, Space,
+    button, column, container, pick_list, row, scrollable, slider, svg, text, text_input, toggler,
+    Space,
 };
-use iced::{Alignment, Background, Border, Color, Element, Fill, Padding};
+use iced::{Alignment, Background, Border, Color, Element, Fill, Length, Padding};
 
-use crate::app::{Message, SettingsSection};
+use crate::app::{Message, SettingsSection, UpdateStatus};
 use crate::audio::get_audio_devices;
-use crate::features::{Action, KeyBindings, Settings};
-use crate::i18n::{Key, Locale};
+use crate::database::{DbCheckIn, DbPlaylist};
+use crate::features::hooks::{self, EventHook, HookAction, HookEvent};
+use crate::features::lyrics_save::LyricsSaveTarget;
+use crate::features::schedules::{self, Schedule};
+use crate::features::{Action, KeyBindings, LyricsTuning, NowPlayingOutput, Settings};
+use crate::i18n::{Key, Language, Locale};
+use crate::ui::effects::textured_background::TexturedBackgroundProgram;
+use crate::ui::pages::settings_search::{self, SettingsCategory};
 use crate::ui::theme;
 
 /// Settings page view with fixed header and all sections on one scrollable page
+///
+/// While a search query or category is set, matching settings are listed in
+/// place of the sections.
 pub fn view(
     settings: &Settings,
     active_section: SettingsSection,
+    search_query: &str,
+    search_category: Option<SettingsCategory>,
     locale: Locale,
     editing_keybinding: Option<Action>,
+    lyrics_tuning_expanded: bool,
     is_logged_in: bool,
     user_info: Option<&crate::app::UserInfo>,
     cache_stats: Option<&crate::cache::CacheStats>,
+    update_status: &UpdateStatus,
+    playlists: &[DbPlaylist],
+    check_in_history: &[DbCheckIn],
+    profile_passphrase: &str,
+    analyzed_songs: usize,
+    analysis_progress: Option<(usize, usize)>,
+    listening: bool,
+    lyrics_save_progress: Option<(usize, usize)>,
+    lyrics_background: &TexturedBackgroundProgram,
 ) -> Element<'static, Message> {
-    // Fixed header: title + tabs
-    let header = column![
-        text(locale.get(Key::SettingsTitle).to_string())
-            .size(32)
-            .style(|theme| text::Style {
-                color: Some(theme::settings_title(theme))
-            }),
-        Space::new().height(24),
-        tab_bar(active_section, locale),
+    let searching = !search_query.trim().is_empty() || search_category.is_some();
+
+    // Fixed header: title and search, category chips, then tabs
+    let search_input = text_input(locale.get(Key::SettingsSearchPlaceholder), search_query)
+        .on_input(Message::SettingsSearchChanged)
+        .padding([8, 12])
+        .width(240)
+        .style(input_style);
+    let mut header = column![
+        row![
+            text(locale.get(Key::SettingsTitle).to_string())
+                .size(32)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_title(theme))
+                }),
+            Space::new().width(Fill),
+            search_input,
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(16),
+        settings_search::category_chips(search_category, locale),
     ]
     .width(Fill);
+    if !searching {
+        header = header
+            .push(Space::new().height(16))
+            .push(tab_bar(active_section, locale));
+    }
 
     let header_container = container(header)
         .width(Fill)
@@ -51,14 +90,46 @@ pub fn view(
             ..Default::default()
         });
 
+    if searching {
+        let results = scrollable(
+            container(settings_search::view(
+                search_query,
+                search_category,
+                settings,
+                locale,
+            ))
+            .width(Fill)
+            .padding(Padding::new(20.0).right(32.0).bottom(60.0).left(32.0)),
+        )
+        .width(Fill)
+        .height(Fill)
+        .id(iced::widget::Id::new("settings_search_scroll"));
+
+        return container(column![header_container, results].width(Fill).height(Fill))
+            .width(Fill)
+            .height(Fill)
+            .style(theme::main_content)
+            .into();
+    }
+
     // All sections on one page
     let all_sections = all_sections_content(
         settings,
         locale,
         editing_keybinding,
+        lyrics_tuning_expanded,
         is_logged_in,
         user_info,
         cache_stats,
+        update_status,
+        playlists,
+        check_in_history,
+        profile_passphrase,
+        analyzed_songs,
+        analysis_progress,
+        listening,
+        lyrics_save_progress,
+        lyrics_background,
     );
 
     let scrollable_content = scrollable(
@@ -97,6 +168,10 @@ fn tab_bar(active_section: SettingsSection, locale: Locale) -> Element<'static,
             SettingsSection::Playback,
             locale.get(Key::SettingsTabPlayback),
         ),
+        (
+            SettingsSection::Schedules,
+            locale.get(Key::SettingsTabSchedules),
+        ),
         (
             SettingsSection::Display,
             locale.get(Key::SettingsTabDisplay),
@@ -188,30 +263,58 @@ fn all_sections_content(
     settings: &Settings,
     locale: Locale,
     editing_keybinding: Option<Action>,
+    lyrics_tuning_expanded: bool,
     is_logged_in: bool,
     user_info: Option<&crate::app::UserInfo>,
     cache_stats: Option<&crate::cache::CacheStats>,
+    update_status: &UpdateStatus,
+    playlists: &[DbPlaylist],
+    check_in_history: &[DbCheckIn],
+    profile_passphrase: &str,
+    analyzed_songs: usize,
+    analysis_progress: Option<(usize, usize)>,
+    listening: bool,
+    lyrics_save_progress: Option<(usize, usize)>,
+    lyrics_background: &TexturedBackgroundProgram,
 ) -> Element<'static, Message> {
     column![
         // Account section
         section_header(locale.get(Key::SettingsAccountTitle)),
         Space::new().height(16),
-        account_section(is_logged_in, user_info, locale),
+        account_section(is_logged_in, user_info, settings, check_in_history, locale),
         Space::new().height(40),
         // Playback section
         section_header(locale.get(Key::SettingsPlaybackTitle)),
         Space::new().height(16),
-        playback_section(settings, locale),
+        playback_section(settings, playlists, locale),
+        Space::new().height(40),
+        // Schedules section
+        section_header(locale.get(Key::SettingsSchedulesTitle)),
+        Space::new().height(16),
+        schedules_section(&settings.schedules, playlists, locale),
         Space::new().height(40),
         // Display section
         section_header(locale.get(Key::SettingsDisplayTitle)),
         Space::new().height(16),
-        display_section(settings, locale),
+        display_section(
+            settings,
+            lyrics_tuning_expanded,
+            lyrics_save_progress,
+            lyrics_background,
+            locale
+        ),
         Space::new().height(40),
         // System section
         section_header(locale.get(Key::SettingsSystemTitle)),
         Space::new().height(16),
-        system_section(settings, locale),
+        system_section(
+            settings,
+            locale,
+            profile_passphrase,
+            analyzed_songs,
+            analysis_progress,
+            listening
+        ),
         Space::new().height(40),
         // Network section
         section_header(locale.get(Key::SettingsNetworkTitle)),
@@ -231,7 +334,7 @@ fn all_sections_content(
         // About section
         section_header(locale.get(Key::SettingsAboutTitle)),
         Space::new().height(16),
-        about_section(locale),
+        about_section(settings, update_status, locale),
     ]
     .spacing(0)
     .width(Fill)
@@ -241,6 +344,8 @@ fn all_sections_content(
 fn account_section(
     is_logged_in: bool,
     user_info: Option<&crate::app::UserInfo>,
+    settings: &Settings,
+    check_in_history: &[DbCheckIn],
     locale: Locale,
 ) -> Element<'static, Message> {
     // Account section
@@ -323,6 +428,8 @@ fn account_section(
                         .on_press(Message::Logout)
                         .into()
                 ),
+                divider(),
+                daily_check_in_rows(settings, check_in_history, locale),
             ]
             .spacing(0)
             .into()
@@ -368,6 +475,58 @@ fn account_section(
     }
 }
 
+/// Daily check-in toggle with the rewards it has claimed
+fn daily_check_in_rows(
+    settings: &Settings,
+    history: &[DbCheckIn],
+    locale: Locale,
+) -> Element<'static, Message> {
+    let toggle_row = setting_row(
+        locale.get(Key::SettingsDailyCheckIn),
+        Some(locale.get(Key::SettingsDailyCheckInDesc)),
+        toggler(settings.account.daily_check_in)
+            .on_toggle(Message::UpdateDailyCheckIn)
+            .size(24)
+            .into(),
+    );
+    if history.is_empty() {
+        return toggle_row;
+    }
+
+    let entries = history.iter().map(|entry| -> Element<'static, Message> {
+        let date = chrono::DateTime::from_timestamp(entry.claimed_at, 0)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let platform = locale.get(if entry.platform == 0 {
+            Key::CheckInMobile
+        } else {
+            Key::CheckInWeb
+        });
+        text(format!("{}  ·  {}  ·  +{}", date, platform, entry.points))
+            .size(13)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            })
+            .into()
+    });
+
+    column![
+        toggle_row,
+        divider(),
+        setting_row(
+            locale.get(Key::CheckInHistory),
+            None,
+            column(entries).spacing(6).align_x(Alignment::End).into()
+        ),
+    ]
+    .spacing(0)
+    .into()
+}
+
 fn section_header(title: &str) -> Element<'static, Message> {
     text(title.to_string())
         .size(18)
@@ -413,30 +572,34 @@ fn setting_row<'a>(
     .into()
 }
 
-fn playback_section(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+fn playback_section(
+    settings: &Settings,
+    playlists: &[DbPlaylist],
+    locale: Locale,
+) -> Element<'static, Message> {
     use crate::features::MusicQuality;
 
     // Build music quality options
     let quality_options: Vec<String> = MusicQuality::all()
         .iter()
-        .map(|q| q.display_name().to_string())
+        .map(|q| q.display_name(locale).to_string())
         .collect();
 
-    let current_quality = settings.playback.music_quality.display_name().to_string();
+    let current_quality = settings
+        .playback
+        .music_quality
+        .display_name(locale)
+        .to_string();
 
     column![
         setting_row(
             locale.get(Key::SettingsMusicQuality),
             Some(locale.get(Key::SettingsMusicQualityDesc)),
-            styled_pick_list(quality_options, Some(current_quality), |value| {
-                let quality = match value.as_str() {
-                    "128kbps" => MusicQuality::Standard,
-                    "192kbps" => MusicQuality::Higher,
-                    "320kbps" => MusicQuality::High,
-                    "SQ (无损)" => MusicQuality::Lossless,
-                    "Hi-Res" => MusicQuality::HiRes,
-                    _ => MusicQuality::High,
-                };
+            styled_pick_list(quality_options, Some(current_quality), move |value| {
+                let quality = MusicQuality::all()
+                    .into_iter()
+                    .find(|q| q.display_name(locale) == value)
+                    .unwrap_or(MusicQuality::High);
                 Message::UpdateMusicQuality(quality)
             },)
         ),
@@ -459,22 +622,284 @@ fn playback_section(settings: &Settings, locale: Locale) -> Element<'static, Mes
                 .into()
         ),
         divider(),
+        setting_row(
+            locale.get(Key::SettingsUiSounds),
+            Some(locale.get(Key::SettingsUiSoundsDesc)),
+            toggler(settings.playback.ui_sounds)
+                .on_toggle(Message::UpdateUiSounds)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsAutoplaySimilar),
+            Some(locale.get(Key::SettingsAutoplaySimilarDesc)),
+            toggler(settings.playback.autoplay_similar)
+                .on_toggle(Message::UpdateAutoplaySimilar)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsSmartResume),
+            Some(locale.get(Key::SettingsSmartResumeDesc)),
+            toggler(settings.playback.smart_resume.enabled)
+                .on_toggle(Message::UpdateSmartResume)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        smart_resume_after_row(settings, locale),
+        divider(),
+        smart_resume_rewind_row(settings, locale),
+        divider(),
+        call_action_row(settings, locale),
+        divider(),
+        call_lower_row(settings, locale),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsCallAnyApp),
+            Some(locale.get(Key::SettingsCallAnyAppDesc)),
+            toggler(settings.playback.call_ducking.any_app)
+                .on_toggle(Message::UpdateCallAnyApp)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        call_after_row(settings, locale),
+        divider(),
+        skip_interval_row(
+            locale.get(Key::SettingsSkipBack),
+            locale.get(Key::SettingsSkipBackDesc),
+            settings.playback.skip_back_secs,
+            Message::UpdateSkipBackSecs,
+        ),
+        divider(),
+        skip_interval_row(
+            locale.get(Key::SettingsSkipForward),
+            locale.get(Key::SettingsSkipForwardDesc),
+            settings.playback.skip_forward_secs,
+            Message::UpdateSkipForwardSecs,
+        ),
+        divider(),
+        seek_step_row(settings, locale),
+        divider(),
+        volume_step_row(settings, locale),
+        divider(),
+        auto_dj_pool_row(settings, playlists, locale),
+        divider(),
+        auto_dj_crossfade_row(settings, locale),
+        divider(),
         // Audio Engine entry - clickable row to navigate to audio engine page
-        audio_engine_entry_row(locale),
+        entry_row(locale.get(Key::AudioEngineTitle), Message::OpenAudioEngine),
     ]
     .spacing(0)
     .into()
 }
 
-/// Audio engine entry row - clickable to navigate to audio engine page
-fn audio_engine_entry_row(locale: Locale) -> Element<'static, Message> {
+/// Skip interval presets in seconds
+const SKIP_SECS: [u32; 6] = [5, 10, 15, 30, 45, 60];
+
+fn skip_interval_row(
+    title: &'static str,
+    description: &'static str,
+    current: u32,
+    on_change: fn(u32) -> Message,
+) -> Element<'static, Message> {
+    let labels: Vec<String> = SKIP_SECS.iter().map(|secs| format!("{} s", secs)).collect();
+
+    setting_row(
+        title,
+        Some(description),
+        styled_pick_list(labels, Some(format!("{} s", current)), move |value| {
+            let secs = SKIP_SECS
+                .into_iter()
+                .find(|secs| format!("{} s", secs) == value)
+                .unwrap_or(current);
+            on_change(secs)
+        }),
+    )
+}
+
+/// Seek key and seek bar wheel presets in seconds
+const SEEK_STEP_SECS: [u32; 5] = [1, 2, 5, 10, 15];
+
+/// Volume key and player bar wheel presets in percent
+const VOLUME_STEP_PERCENT: [u32; 5] = [1, 2, 5, 10, 20];
+
+fn seek_step_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let current = settings.playback.seek_step_secs;
+    let labels: Vec<String> = SEEK_STEP_SECS
+        .iter()
+        .map(|secs| format!("{} s", secs))
+        .collect();
+
+    setting_row(
+        locale.get(Key::SettingsSeekStep),
+        Some(locale.get(Key::SettingsSeekStepDesc)),
+        styled_pick_list(labels, Some(format!("{} s", current)), move |value| {
+            let secs = SEEK_STEP_SECS
+                .into_iter()
+                .find(|secs| format!("{} s", secs) == value)
+                .unwrap_or(current);
+            Message::UpdateSeekStepSecs(secs)
+        }),
+    )
+}
+
+fn volume_step_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let current = settings.playback.volume_step_percent;
+    let labels: Vec<String> = VOLUME_STEP_PERCENT
+        .iter()
+        .map(|percent| format!("{}%", percent))
+        .collect();
+
+    setting_row(
+        locale.get(Key::SettingsVolumeStep),
+        Some(locale.get(Key::SettingsVolumeStepDesc)),
+        styled_pick_list(labels, Some(format!("{}%", current)), move |value| {
+            let percent = VOLUME_STEP_PERCENT
+                .into_iter()
+                .find(|percent| format!("{}%", percent) == value)
+                .unwrap_or(current);
+            Message::UpdateVolumeStepPercent(percent)
+        }),
+    )
+}
+
+/// Pause lengths in minutes after which smart resume rewinds
+const SMART_RESUME_AFTER_MINS: [u32; 5] = [1, 5, 10, 30, 60];
+
+/// Smart resume rewind presets in seconds
+const SMART_RESUME_REWIND_SECS: [u32; 4] = [2, 3, 4, 5];
+
+fn smart_resume_after_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let label = move |mins: u32| {
+        locale
+            .get(Key::SettingsSmartResumeMinutes)
+            .replace("{}", &mins.to_string())
+    };
+    let current = settings.playback.smart_resume.after_mins;
+    let labels: Vec<String> = SMART_RESUME_AFTER_MINS.into_iter().map(label).collect();
+
+    setting_row(
+        locale.get(Key::SettingsSmartResumeAfter),
+        Some(locale.get(Key::SettingsSmartResumeAfterDesc)),
+        styled_pick_list(labels, Some(label(current)), move |value| {
+            let mins = SMART_RESUME_AFTER_MINS
+                .into_iter()
+                .find(|mins| label(*mins) == value)
+                .unwrap_or(current);
+            Message::UpdateSmartResumeAfter(mins)
+        }),
+    )
+}
+
+fn smart_resume_rewind_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let current = settings.playback.smart_resume.rewind_secs;
+    let labels: Vec<String> = SMART_RESUME_REWIND_SECS
+        .iter()
+        .map(|secs| format!("{} s", secs))
+        .collect();
+
+    setting_row(
+        locale.get(Key::SettingsSmartResumeRewind),
+        Some(locale.get(Key::SettingsSmartResumeRewindDesc)),
+        styled_pick_list(labels, Some(format!("{} s", current)), move |value| {
+            let secs = SMART_RESUME_REWIND_SECS
+                .into_iter()
+                .find(|secs| format!("{} s", secs) == value)
+                .unwrap_or(current);
+            Message::UpdateSmartResumeRewind(secs)
+        }),
+    )
+}
+
+fn auto_dj_pool_row(
+    settings: &Settings,
+    playlists: &[DbPlaylist],
+    locale: Locale,
+) -> Element<'static, Message> {
+    use crate::features::AutoDjPool;
+
+    let mut options = vec![
+        (
+            AutoDjPool::LikedSongs,
+            locale.get(Key::AutoDjPoolLiked).to_string(),
+        ),
+        (
+            AutoDjPool::SimilarSongs,
+            locale.get(Key::AutoDjPoolSimilar).to_string(),
+        ),
+    ];
+    options.extend(playlists.iter().map(|playlist| {
+        (
+            AutoDjPool::Playlist(playlist.id),
+            locale
+                .get(Key::AutoDjPoolPlaylist)
+                .replace("{}", &playlist.name),
+        )
+    }));
+
+    // A deleted playlist shows as no selection until another pool is picked
+    let current = options
+        .iter()
+        .find(|(pool, _)| *pool == settings.playback.auto_dj.pool)
+        .map(|(_, label)| label.clone());
+    let labels: Vec<String> = options.iter().map(|(_, label)| label.clone()).collect();
+
+    setting_row(
+        locale.get(Key::SettingsAutoDjPool),
+        Some(locale.get(Key::SettingsAutoDjPoolDesc)),
+        styled_pick_list(labels, current, move |value| {
+            let pool = options
+                .iter()
+                .find(|(_, label)| *label == value)
+                .map(|(pool, _)| *pool)
+                .unwrap_or_default();
+            Message::UpdateAutoDjPool(pool)
+        }),
+    )
+}
+
+/// Crossfade presets in seconds (0 = off)
+const CROSSFADE_SECS: [u32; 5] = [0, 3, 5, 8, 12];
+
+fn format_crossfade(secs: u32, locale: Locale) -> String {
+    if secs == 0 {
+        locale.get(Key::SettingsAutoDjCrossfadeOff).to_string()
+    } else {
+        format!("{} s", secs)
+    }
+}
+
+fn auto_dj_crossfade_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let labels: Vec<String> = CROSSFADE_SECS
+        .iter()
+        .map(|&secs| format_crossfade(secs, locale))
+        .collect();
+    let current = format_crossfade(settings.playback.auto_dj.crossfade_secs, locale);
+
+    setting_row(
+        locale.get(Key::SettingsAutoDjCrossfade),
+        Some(locale.get(Key::SettingsAutoDjCrossfadeDesc)),
+        styled_pick_list(labels, Some(current), move |value| {
+            let secs = CROSSFADE_SECS
+                .into_iter()
+                .find(|&secs| format_crossfade(secs, locale) == value)
+                .unwrap_or(0);
+            Message::UpdateAutoDjCrossfade(secs)
+        }),
+    )
+}
+
+/// Entry row - clickable to navigate to a sub-page
+fn entry_row(title: &str, on_press: Message) -> Element<'static, Message> {
     let content = row![
         // Title only
-        text(locale.get(Key::AudioEngineTitle).to_string())
-            .size(15)
-            .style(|theme| text::Style {
-                color: Some(theme::settings_label(theme))
-            }),
+        text(title.to_string()).size(15).style(|theme| text::Style {
+            color: Some(theme::settings_label(theme))
+        }),
         Space::new().width(Fill),
         // Chevron right icon
         svg(svg::Handle::from_memory(
@@ -492,106 +917,709 @@ fn audio_engine_entry_row(locale: Locale) -> Element<'static, Message> {
     button(container(content).padding([16, 0]))
         .width(Fill)
         .padding(0)
-        .style(|theme, status| {
-            let bg = match status {
-                button::Status::Hovered => Some(Background::Color(theme::hover_bg(theme))),
-                button::Status::Pressed => Some(Background::Color(theme::hover_bg(theme))),
-                _ => None,
-            };
-            button::Style {
-                background: bg,
-                border: Border::default(),
-                text_color: Color::WHITE,
-                ..Default::default()
-            }
-        })
-        .on_press(Message::OpenAudioEngine)
+        .style(entry_row_style)
+        .on_press(on_press)
         .into()
 }
 
-fn display_section(settings: &Settings, locale: Locale) -> Element<'static, Message> {
-    use crate::features::CloseBehavior;
-
-    let close_behavior_options = vec![
-        locale.get(Key::SettingsCloseBehaviorAsk).to_string(),
-        locale.get(Key::SettingsCloseBehaviorExit).to_string(),
-        locale.get(Key::SettingsCloseBehaviorMinimize).to_string(),
-    ];
+fn entry_row_style(theme: &iced::Theme, status: button::Status) -> button::Style {
+    let bg = match status {
+        button::Status::Hovered => Some(Background::Color(theme::hover_bg(theme))),
+        button::Status::Pressed => Some(Background::Color(theme::hover_bg(theme))),
+        _ => None,
+    };
+    button::Style {
+        background: bg,
+        border: Border::default(),
+        text_color: Color::WHITE,
+        ..Default::default()
+    }
+}
 
-    let current_close_behavior = match settings.close_behavior {
-        CloseBehavior::Ask => locale.get(Key::SettingsCloseBehaviorAsk).to_string(),
-        CloseBehavior::Exit => locale.get(Key::SettingsCloseBehaviorExit).to_string(),
-        CloseBehavior::MinimizeToTray => locale.get(Key::SettingsCloseBehaviorMinimize).to_string(),
+/// Collapsible group of sliders for the lyrics animation; changes apply to
+/// the lyrics page as they are made
+fn lyrics_tuning_group(
+    tuning: LyricsTuning,
+    expanded: bool,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let chevron = if expanded {
+        crate::ui::icons::CHEVRON_DOWN
+    } else {
+        crate::ui::icons::CHEVRON_RIGHT
     };
+    let header = button(
+        container(
+            row![
+                column![
+                    text(locale.get(Key::SettingsLyricsTuning).to_string())
+                        .size(15)
+                        .style(|theme| text::Style {
+                            color: Some(theme::settings_label(theme))
+                        }),
+                    text(locale.get(Key::SettingsLyricsTuningDesc).to_string())
+                        .size(12)
+                        .style(|theme| text::Style {
+                            color: Some(theme::settings_desc(theme))
+                        }),
+                ]
+                .spacing(4),
+                Space::new().width(Fill),
+                svg(svg::Handle::from_memory(chevron.as_bytes()))
+                    .width(20)
+                    .height(20)
+                    .style(|theme, _status| svg::Style {
+                        color: Some(theme::settings_desc(theme)),
+                    }),
+            ]
+            .align_y(Alignment::Center)
+            .width(Fill),
+        )
+        .padding([16, 0]),
+    )
+    .width(Fill)
+    .padding(0)
+    .style(entry_row_style)
+    .on_press(Message::ToggleLyricsTuningPanel);
 
-    let ask_label = locale.get(Key::SettingsCloseBehaviorAsk).to_string();
-    let exit_label = locale.get(Key::SettingsCloseBehaviorExit).to_string();
+    if !expanded {
+        return header.into();
+    }
+
+    let actions = row![
+        Space::new().width(Fill),
+        button(text(locale.get(Key::SettingsLyricsTuningPreview).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::OpenLyricsPage),
+        button(text(locale.get(Key::SettingsLyricsTuningReset).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::ResetLyricsTuning),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .width(Fill);
 
     column![
-        setting_row(
-            locale.get(Key::SettingsDarkMode),
-            None,
-            toggler(settings.display.dark_mode)
-                .on_toggle(Message::UpdateDarkMode)
-                .size(24)
-                .into()
+        header,
+        tuning_slider(
+            locale.get(Key::SettingsLyricsWordFade),
+            0.1..=1.5,
+            0.05,
+            tuning.word_fade_width,
+            format!("{:.2} em", tuning.word_fade_width),
+            move |v| LyricsTuning {
+                word_fade_width: v,
+                ..tuning
+            },
         ),
-        divider(),
-        setting_row(
-            locale.get(Key::SettingsLanguage),
-            None,
-            styled_pick_list(
-                vec!["简体中文".to_string(), "English".to_string()],
-                Some(if settings.display.language == "zh" {
-                    "简体中文".to_string()
-                } else {
-                    "English".to_string()
-                }),
-                |value| {
-                    let lang = if value == "简体中文" { "zh" } else { "en" };
-                    Message::UpdateAppLanguage(lang.to_string())
-                },
-            )
+        tuning_slider(
+            locale.get(Key::SettingsLyricsEmphasisAmount),
+            0.0..=2.0,
+            0.05,
+            tuning.emphasis_amount,
+            format!("{:.0}%", tuning.emphasis_amount * 100.0),
+            move |v| LyricsTuning {
+                emphasis_amount: v,
+                ..tuning
+            },
         ),
-        divider(),
-        setting_row(
-            locale.get(Key::SettingsPowerSavingMode),
-            Some(locale.get(Key::SettingsPowerSavingModeDesc)),
-            toggler(settings.display.power_saving_mode)
-                .on_toggle(Message::UpdatePowerSavingMode)
-                .size(24)
-                .into()
+        tuning_slider(
+            locale.get(Key::SettingsLyricsEmphasisBlur),
+            0.0..=2.0,
+            0.05,
+            tuning.emphasis_blur,
+            format!("{:.0}%", tuning.emphasis_blur * 100.0),
+            move |v| LyricsTuning {
+                emphasis_blur: v,
+                ..tuning
+            },
         ),
-        divider(),
-        setting_row(
-            locale.get(Key::SettingsCloseBehavior),
-            None,
-            styled_pick_list(
-                close_behavior_options,
-                Some(current_close_behavior),
-                move |value| {
-                    let behavior = if value == ask_label {
-                        CloseBehavior::Ask
-                    } else if value == exit_label {
-                        CloseBehavior::Exit
-                    } else {
-                        CloseBehavior::MinimizeToTray
-                    };
-                    Message::UpdateCloseBehavior(behavior)
-                },
-            )
+        tuning_slider(
+            locale.get(Key::SettingsLyricsSpringStiffness),
+            30.0..=200.0,
+            5.0,
+            tuning.spring_stiffness,
+            format!("{:.0}", tuning.spring_stiffness),
+            move |v| LyricsTuning {
+                spring_stiffness: v,
+                ..tuning
+            },
+        ),
+        tuning_slider(
+            locale.get(Key::SettingsLyricsSpringDamping),
+            5.0..=40.0,
+            1.0,
+            tuning.spring_damping,
+            format!("{:.0}", tuning.spring_damping),
+            move |v| LyricsTuning {
+                spring_damping: v,
+                ..tuning
+            },
+        ),
+        tuning_slider(
+            locale.get(Key::SettingsLyricsAlignPosition),
+            0.1..=0.7,
+            0.01,
+            tuning.align_position,
+            format!("{:.0}%", tuning.align_position * 100.0),
+            move |v| LyricsTuning {
+                align_position: v,
+                ..tuning
+            },
+        ),
+        tuning_slider(
+            locale.get(Key::SettingsLyricsScrollResume),
+            1.0..=10.0,
+            0.5,
+            tuning.scroll_resume_delay,
+            format!("{:.1} s", tuning.scroll_resume_delay),
+            move |v| LyricsTuning {
+                scroll_resume_delay: v,
+                ..tuning
+            },
         ),
+        container(actions).padding([12, 0]),
     ]
+    .padding(Padding::new(0.0).left(16.0))
     .spacing(0)
     .into()
 }
 
-fn system_section(settings: &Settings, locale: Locale) -> Element<'static, Message> {
-    // Get real audio devices from PulseAudio/PipeWire
-    let audio_devices = get_audio_devices();
-    let default_device_label = locale.get(Key::SettingsDefaultDevice).to_string();
-
-    // Build display names list (descriptions) and keep track of internal names
+/// Slider row for one lyrics tuning value, with the current value shown
+/// next to it
+fn tuning_slider(
+    label: &str,
+    range: std::ops::RangeInclusive<f32>,
+    step: f32,
+    value: f32,
+    value_label: String,
+    update: impl Fn(f32) -> LyricsTuning + 'static,
+) -> Element<'static, Message> {
+    let on_change = move |v| Message::UpdateLyricsTuning(update(v));
+    setting_row(
+        label,
+        None,
+        row![
+            text(value_label).size(13).style(|theme| text::Style {
+                color: Some(theme::settings_value(theme))
+            }),
+            slider(range, value, on_change)
+                .step(step)
+                .width(Length::Fixed(180.0)),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center)
+        .into(),
+    )
+}
+
+fn call_action_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    use crate::features::CallAction;
+
+    let label = move |action: CallAction| {
+        match action {
+            CallAction::Off => locale.get(Key::CallActionOff),
+            CallAction::Lower => locale.get(Key::CallActionLower),
+            CallAction::Pause => locale.get(Key::CallActionPause),
+        }
+        .to_string()
+    };
+    let actions = [CallAction::Off, CallAction::Lower, CallAction::Pause];
+    let labels: Vec<String> = actions.into_iter().map(label).collect();
+
+    setting_row(
+        locale.get(Key::SettingsCallDucking),
+        Some(locale.get(Key::SettingsCallDuckingDesc)),
+        styled_pick_list(
+            labels,
+            Some(label(settings.playback.call_ducking.action)),
+            move |value| {
+                let action = actions
+                    .into_iter()
+                    .find(|action| label(*action) == value)
+                    .unwrap_or_default();
+                Message::UpdateCallAction(action)
+            },
+        ),
+    )
+}
+
+fn call_lower_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    use crate::features::call_ducking::LOWER_PERCENTS;
+
+    let current = settings.playback.call_ducking.lower_percent;
+    let labels: Vec<String> = LOWER_PERCENTS.iter().map(|p| format!("{}%", p)).collect();
+
+    setting_row(
+        locale.get(Key::SettingsCallLowerTo),
+        Some(locale.get(Key::SettingsCallLowerToDesc)),
+        styled_pick_list(labels, Some(format!("{}%", current)), move |value| {
+            let percent = LOWER_PERCENTS
+                .into_iter()
+                .find(|p| format!("{}%", p) == value)
+                .unwrap_or(current);
+            Message::UpdateCallLowerPercent(percent)
+        }),
+    )
+}
+
+fn call_after_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    use crate::features::call_ducking::AFTER_SECS;
+
+    let current = settings.playback.call_ducking.after_secs;
+    let labels: Vec<String> = AFTER_SECS
+        .iter()
+        .map(|secs| format!("{} s", secs))
+        .collect();
+
+    setting_row(
+        locale.get(Key::SettingsCallAfter),
+        Some(locale.get(Key::SettingsCallAfterDesc)),
+        styled_pick_list(labels, Some(format!("{} s", current)), move |value| {
+            let secs = AFTER_SECS
+                .into_iter()
+                .find(|secs| format!("{} s", secs) == value)
+                .unwrap_or(current);
+            Message::UpdateCallAfter(secs)
+        }),
+    )
+}
+
+/// Scheduled playback: a header with an add button, then one entry per schedule
+fn schedules_section(
+    schedules: &[Schedule],
+    playlists: &[DbPlaylist],
+    locale: Locale,
+) -> Element<'static, Message> {
+    let header = setting_row(
+        locale.get(Key::SettingsSchedules),
+        Some(locale.get(Key::SettingsSchedulesDesc)),
+        button(text(locale.get(Key::SettingsSchedulesAdd).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::AddSchedule)
+            .into(),
+    );
+
+    let hours: Vec<String> = (0..24).map(|hour| format!("{:02}", hour)).collect();
+    let minutes: Vec<String> = (0..60)
+        .step_by(5)
+        .map(|minute| format!("{:02}", minute))
+        .collect();
+    let ramps: Vec<String> = schedules::RAMP_MINUTES
+        .iter()
+        .map(|&minutes| format_ramp(minutes, locale))
+        .collect();
+    let playlist_names: Vec<String> = playlists.iter().map(|p| p.name.clone()).collect();
+
+    let mut rows = column![header].spacing(12);
+    for (index, schedule) in schedules.iter().enumerate() {
+        let on_toggle = edit_schedule(index, schedule, |s, enabled: bool| s.enabled = enabled);
+        let on_hour = edit_schedule(index, schedule, |s, value: String| {
+            s.hour = value.parse().unwrap_or(s.hour);
+        });
+        let on_minute = edit_schedule(index, schedule, |s, value: String| {
+            s.minute = value.parse().unwrap_or(s.minute);
+        });
+        let ids: Vec<(String, i64)> = playlists.iter().map(|p| (p.name.clone(), p.id)).collect();
+        let on_playlist = edit_schedule(index, schedule, move |s, value: String| {
+            s.playlist_id = ids
+                .iter()
+                .find(|(name, _)| *name == value)
+                .map(|(_, id)| *id);
+        });
+        let on_ramp = edit_schedule(index, schedule, move |s, value: String| {
+            s.ramp_minutes = schedules::RAMP_MINUTES
+                .into_iter()
+                .find(|&minutes| format_ramp(minutes, locale) == value)
+                .unwrap_or_default();
+        });
+
+        // A deleted playlist shows as no selection until another is picked
+        let playlist = schedule.playlist_id.and_then(|id| {
+            playlists
+                .iter()
+                .find(|p| p.id == id)
+                .map(|p| p.name.clone())
+        });
+
+        let times = row![
+            toggler(schedule.enabled).on_toggle(on_toggle).size(24),
+            styled_pick_list(
+                hours.clone(),
+                Some(format!("{:02}", schedule.hour)),
+                on_hour
+            ),
+            text(":").size(15),
+            styled_pick_list(
+                minutes.clone(),
+                Some(format!("{:02}", schedule.minute)),
+                on_minute
+            ),
+            styled_pick_list(playlist_names.clone(), playlist, on_playlist),
+            styled_pick_list(
+                ramps.clone(),
+                Some(format_ramp(schedule.ramp_minutes, locale)),
+                on_ramp
+            ),
+            Space::new().width(Fill),
+            button(text(locale.get(Key::SettingsSchedulesRemove).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press(Message::RemoveSchedule(index)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let mut days = row![].spacing(6).align_y(Alignment::Center);
+        for day in 0..7 {
+            let on = schedule.repeats_on(day);
+            days = days.push(
+                button(text(schedules::weekday_name(day, locale)).size(13))
+                    .style(if on {
+                        theme::primary_button
+                    } else {
+                        theme::secondary_button
+                    })
+                    .padding([6, 10])
+                    .on_press(Message::UpdateSchedule(index, schedule.with_day(day, !on))),
+            );
+        }
+        if schedule.days == 0 {
+            days = days.push(
+                text(locale.get(Key::ScheduleOnce).to_string())
+                    .size(12)
+                    .style(|theme| text::Style {
+                        color: Some(theme::settings_desc(theme)),
+                    }),
+            );
+        }
+
+        rows = rows.push(column![times, days].spacing(8));
+    }
+
+    container(rows).padding(Padding::ZERO.bottom(16.0)).into()
+}
+
+fn format_ramp(minutes: u32, locale: Locale) -> String {
+    if minutes == 0 {
+        locale.get(Key::ScheduleRampOff).to_string()
+    } else {
+        locale
+            .get(Key::ScheduleRampMinutes)
+            .replace("{}", &minutes.to_string())
+    }
+}
+
+/// Message handler replacing schedule `index` with an edited copy of `schedule`
+fn edit_schedule<T>(
+    index: usize,
+    schedule: &Schedule,
+    edit: impl Fn(&mut Schedule, T) + Clone + 'static,
+) -> impl Fn(T) -> Message + Clone + 'static {
+    let schedule = schedule.clone();
+    move |value| {
+        let mut schedule = schedule.clone();
+        edit(&mut schedule, value);
+        Message::UpdateSchedule(index, schedule)
+    }
+}
+
+fn display_section(
+    settings: &Settings,
+    lyrics_tuning_expanded: bool,
+    lyrics_save_progress: Option<(usize, usize)>,
+    lyrics_background: &TexturedBackgroundProgram,
+    locale: Locale,
+) -> Element<'static, Message> {
+    use crate::features::CloseBehavior;
+
+    let close_behavior_options = vec![
+        locale.get(Key::SettingsCloseBehaviorAsk).to_string(),
+        locale.get(Key::SettingsCloseBehaviorExit).to_string(),
+        locale.get(Key::SettingsCloseBehaviorMinimize).to_string(),
+    ];
+
+    let current_close_behavior = match settings.close_behavior {
+        CloseBehavior::Ask => locale.get(Key::SettingsCloseBehaviorAsk).to_string(),
+        CloseBehavior::Exit => locale.get(Key::SettingsCloseBehaviorExit).to_string(),
+        CloseBehavior::MinimizeToTray => locale.get(Key::SettingsCloseBehaviorMinimize).to_string(),
+    };
+
+    let ask_label = locale.get(Key::SettingsCloseBehaviorAsk).to_string();
+    let exit_label = locale.get(Key::SettingsCloseBehaviorExit).to_string();
+
+    column![
+        setting_row(
+            locale.get(Key::SettingsDarkMode),
+            None,
+            toggler(settings.display.dark_mode)
+                .on_toggle(Message::UpdateDarkMode)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsLanguage),
+            None,
+            styled_pick_list(
+                Language::all().iter().map(language_label).collect(),
+                Some(language_label(&Language::from_code(
+                    &settings.display.language
+                ))),
+                |value| {
+                    let lang = Language::all()
+                        .iter()
+                        .find(|lang| language_label(lang) == value)
+                        .copied()
+                        .unwrap_or_default();
+                    Message::UpdateAppLanguage(lang.code().to_string())
+                },
+            )
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsPowerSavingMode),
+            Some(locale.get(Key::SettingsPowerSavingModeDesc)),
+            toggler(settings.display.power_saving_mode)
+                .on_toggle(Message::UpdatePowerSavingMode)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        artwork_backdrop_row(settings, locale),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsCoverTheming),
+            Some(locale.get(Key::SettingsCoverThemingDesc)),
+            toggler(settings.display.cover_theming)
+                .on_toggle(Message::UpdateCoverTheming)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        lyrics_background_gallery(settings, lyrics_background, locale),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsLyricsTranslation),
+            Some(locale.get(Key::SettingsLyricsTranslationDesc)),
+            toggler(settings.display.lyrics_sub_lines.translation)
+                .on_toggle(Message::UpdateLyricsTranslationDefault)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsLyricsRomanization),
+            Some(locale.get(Key::SettingsLyricsRomanizationDesc)),
+            toggler(settings.display.lyrics_sub_lines.romanization)
+                .on_toggle(Message::UpdateLyricsRomanizationDefault)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        lyrics_tuning_group(
+            settings.display.lyrics_tuning,
+            lyrics_tuning_expanded,
+            locale
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsLyricsShareOffer),
+            Some(locale.get(Key::SettingsLyricsShareOfferDesc)),
+            toggler(settings.display.lyrics_share.offer)
+                .on_toggle(Message::UpdateLyricsShareOffer)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row_with_input(
+            locale.get(Key::SettingsLyricsShareCredit),
+            "",
+            &settings.display.lyrics_share.credit,
+            Message::UpdateLyricsShareCredit,
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsLyricsSaveOffer),
+            Some(locale.get(Key::SettingsLyricsSaveOfferDesc)),
+            toggler(settings.display.lyrics_save.offer)
+                .on_toggle(Message::UpdateLyricsSaveOffer)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        lyrics_save_target_row(settings, locale),
+        divider(),
+        lyrics_save_folder_row(lyrics_save_progress, locale),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsCloseBehavior),
+            None,
+            styled_pick_list(
+                close_behavior_options,
+                Some(current_close_behavior),
+                move |value| {
+                    let behavior = if value == ask_label {
+                        CloseBehavior::Ask
+                    } else if value == exit_label {
+                        CloseBehavior::Exit
+                    } else {
+                        CloseBehavior::MinimizeToTray
+                    };
+                    Message::UpdateCloseBehavior(behavior)
+                },
+            )
+        ),
+    ]
+    .spacing(0)
+    .into()
+}
+
+fn artwork_backdrop_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    use crate::features::ArtworkBackdrop;
+
+    let options: Vec<(ArtworkBackdrop, String)> = [
+        (ArtworkBackdrop::Off, Key::SettingsArtworkBackdropOff),
+        (ArtworkBackdrop::Still, Key::SettingsArtworkBackdropStill),
+        (
+            ArtworkBackdrop::Animated,
+            Key::SettingsArtworkBackdropAnimated,
+        ),
+    ]
+    .into_iter()
+    .map(|(backdrop, key)| (backdrop, locale.get(key).to_string()))
+    .collect();
+    let labels: Vec<String> = options.iter().map(|(_, label)| label.clone()).collect();
+    let current = options
+        .iter()
+        .find(|(backdrop, _)| *backdrop == settings.display.artwork_backdrop)
+        .map(|(_, label)| label.clone());
+
+    setting_row(
+        locale.get(Key::SettingsArtworkBackdrop),
+        Some(locale.get(Key::SettingsArtworkBackdropDesc)),
+        styled_pick_list(labels, current, move |value| {
+            let backdrop = options
+                .iter()
+                .find(|(_, label)| *label == value)
+                .map(|(backdrop, _)| *backdrop)
+                .unwrap_or_default();
+            Message::UpdateArtworkBackdrop(backdrop)
+        }),
+    )
+}
+
+/// Size of each background preview
+const BACKGROUND_PREVIEW_WIDTH: f32 = 128.0;
+const BACKGROUND_PREVIEW_HEIGHT: f32 = 80.0;
+
+/// Lyrics background styles side by side, each drawn from the current cover
+fn lyrics_background_gallery(
+    settings: &Settings,
+    background: &TexturedBackgroundProgram,
+    locale: Locale,
+) -> Element<'static, Message> {
+    use crate::features::BackgroundStyle;
+
+    let selected = settings.display.lyrics_background;
+    let cards: Vec<Element<'static, Message>> = BackgroundStyle::ALL
+        .into_iter()
+        .map(|style| {
+            let key = match style {
+                BackgroundStyle::Flow => Key::SettingsLyricsBackgroundFlow,
+                BackgroundStyle::Mesh => Key::SettingsLyricsBackgroundMesh,
+                BackgroundStyle::Fluid => Key::SettingsLyricsBackgroundFluid,
+                BackgroundStyle::Starfield => Key::SettingsLyricsBackgroundStarfield,
+                BackgroundStyle::Blurred => Key::SettingsLyricsBackgroundBlurred,
+            };
+            let is_selected = style == selected;
+            let preview = iced::widget::shader(background.styled(style))
+                .width(BACKGROUND_PREVIEW_WIDTH)
+                .height(BACKGROUND_PREVIEW_HEIGHT);
+
+            button(
+                column![
+                    preview,
+                    text(locale.get(key).to_string())
+                        .size(12)
+                        .style(move |theme| text::Style {
+                            color: Some(if is_selected {
+                                theme::ACCENT_PINK
+                            } else {
+                                theme::settings_desc(theme)
+                            }),
+                        }),
+                ]
+                .spacing(6)
+                .align_x(Alignment::Center),
+            )
+            .padding(4)
+            .style(move |theme, status| button::Style {
+                background: match status {
+                    button::Status::Hovered => {
+                        Some(Background::Color(theme::hover_bg_alpha(theme, 0.05)))
+                    }
+                    _ => None,
+                },
+                border: Border {
+                    color: if is_selected {
+                        theme::ACCENT_PINK
+                    } else {
+                        Color::TRANSPARENT
+                    },
+                    width: 2.0,
+                    radius: 8.0.into(),
+                },
+                ..Default::default()
+            })
+            .on_press(Message::UpdateLyricsBackground(style))
+            .into()
+        })
+        .collect();
+
+    column![
+        setting_row(
+            locale.get(Key::SettingsLyricsBackground),
+            Some(locale.get(Key::SettingsLyricsBackgroundDesc)),
+            Space::new().into(),
+        ),
+        row(cards).spacing(12).wrap(),
+    ]
+    .padding(Padding::new(0.0).bottom(16.0))
+    .into()
+}
+
+/// Language name for the picker, with how much of it is translated when
+/// a locale file doesn't cover every string
+fn language_label(lang: &Language) -> String {
+    let completeness = lang.completeness();
+    if completeness < 1.0 {
+        format!(
+            "{} ({:.0}%)",
+            lang.display_name(),
+            (completeness * 100.0).floor()
+        )
+    } else {
+        lang.display_name().to_string()
+    }
+}
+
+fn system_section(
+    settings: &Settings,
+    locale: Locale,
+    profile_passphrase: &str,
+    analyzed_songs: usize,
+    analysis_progress: Option<(usize, usize)>,
+    listening: bool,
+) -> Element<'static, Message> {
+    // Get real audio devices from PulseAudio/PipeWire
+    let audio_devices = get_audio_devices();
+    let default_device_label = locale.get(Key::SettingsDefaultDevice).to_string();
+
+    // Build display names list (descriptions) and keep track of internal names
     let mut display_names: Vec<String> = vec![default_device_label.clone()];
     for device in &audio_devices {
         display_names.push(device.description.clone());
@@ -612,26 +1640,386 @@ fn system_section(settings: &Settings, locale: Locale) -> Element<'static, Messa
     let devices_for_closure = audio_devices.clone();
     let default_label = default_device_label.clone();
 
-    column![setting_row(
-        locale.get(Key::SettingsAudioDevice),
-        None,
-        styled_pick_list(display_names, Some(current_display), move |display_value| {
-            // Convert display name back to internal name
-            let device = if display_value == default_label {
-                None
-            } else {
-                devices_for_closure
-                    .iter()
-                    .find(|d| d.description == display_value)
-                    .map(|d| d.name.clone())
-            };
-            Message::UpdateAudioOutputDevice(device)
-        },)
-    ),]
+    column![
+        setting_row(
+            locale.get(Key::SettingsAudioDevice),
+            None,
+            styled_pick_list(display_names, Some(current_display), move |display_value| {
+                // Convert display name back to internal name
+                let device = if display_value == default_label {
+                    None
+                } else {
+                    devices_for_closure
+                        .iter()
+                        .find(|d| d.description == display_value)
+                        .map(|d| d.name.clone())
+                };
+                Message::UpdateAudioOutputDevice(device)
+            })
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsLaunchAtLogin),
+            Some(locale.get(Key::SettingsLaunchAtLoginDesc)),
+            toggler(settings.system.launch_at_login)
+                .on_toggle(Message::UpdateLaunchAtLogin)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsStartMinimized),
+            Some(locale.get(Key::SettingsStartMinimizedDesc)),
+            toggler(settings.system.start_minimized)
+                .on_toggle(Message::UpdateStartMinimized)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsReleaseNotifications),
+            Some(locale.get(Key::SettingsReleaseNotificationsDesc)),
+            toggler(settings.system.release_notifications)
+                .on_toggle(Message::UpdateReleaseNotifications)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsResumeOnStartup),
+            Some(locale.get(Key::SettingsResumeOnStartupDesc)),
+            toggler(settings.playback.resume_on_startup)
+                .on_toggle(Message::UpdateResumeOnStartup)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        song_analysis_row(settings, analyzed_songs, analysis_progress, locale),
+        divider(),
+        listen_mode_row(settings, listening, locale),
+        divider(),
+        // Blocklist entry - songs and artists that are never played
+        entry_row(locale.get(Key::BlocklistTitle), Message::OpenBlocklist),
+        divider(),
+        // Trash entry - deleted playlists and removed songs, kept for 30 days
+        entry_row(locale.get(Key::TrashTitle), Message::OpenTrash),
+        divider(),
+        // Annual report entry - year-in-review built from play history
+        entry_row(
+            locale.get(Key::AnnualReportTitle),
+            Message::OpenAnnualReport
+        ),
+        divider(),
+        // Plugins entry - clickable row to navigate to plugin manager page
+        entry_row(locale.get(Key::PluginsTitle), Message::OpenPlugins),
+        divider(),
+        // Logs entry - log viewer and diagnostics export
+        entry_row(locale.get(Key::LogsTitle), Message::OpenLogs),
+        divider(),
+        // Settings profile - everything above in one file for another machine
+        setting_row(
+            locale.get(Key::SettingsProfile),
+            Some(locale.get(Key::SettingsProfileDesc)),
+            row![
+                button(text(locale.get(Key::SettingsProfileImport).to_string()).size(14))
+                    .style(theme::secondary_button)
+                    .padding([8, 16])
+                    .on_press(Message::ImportSettingsProfile),
+                button(text(locale.get(Key::SettingsProfileExport).to_string()).size(14))
+                    .style(theme::secondary_button)
+                    .padding([8, 16])
+                    .on_press(Message::ExportSettingsProfile),
+            ]
+            .spacing(8)
+            .into()
+        ),
+        setting_row(
+            locale.get(Key::SettingsProfilePassphrase),
+            Some(locale.get(Key::SettingsProfilePassphraseDesc)),
+            text_input(
+                locale.get(Key::SettingsProfilePassphrasePlaceholder),
+                profile_passphrase
+            )
+            .secure(true)
+            .on_input(Message::UpdateSettingsProfilePassphrase)
+            .padding([8, 12])
+            .width(200)
+            .style(input_style)
+            .into()
+        ),
+        divider(),
+        now_playing_rows(&settings.system.now_playing, locale),
+        divider(),
+        hooks_rows(&settings.hooks, locale),
+    ]
     .spacing(0)
     .into()
 }
 
+/// Background tempo and key analysis, with how far it has got
+fn song_analysis_row(
+    settings: &Settings,
+    analyzed_songs: usize,
+    progress: Option<(usize, usize)>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let status = match progress {
+        Some((done, total)) => locale
+            .get(Key::SettingsSongAnalysisProgress)
+            .replacen("{}", &done.to_string(), 1)
+            .replacen("{}", &total.to_string(), 1),
+        None => locale.count(Key::SettingsSongAnalysisDone, analyzed_songs as u64),
+    };
+    let description = format!("{} {}", locale.get(Key::SettingsSongAnalysisDesc), status);
+
+    let mut controls = row![].spacing(12).align_y(Alignment::Center);
+    if !settings.system.analyze_songs {
+        controls = controls.push(
+            button(text(locale.get(Key::SettingsSongAnalysisRun).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press_maybe(progress.is_none().then_some(Message::AnalyzeLibrary)),
+        );
+    }
+    controls = controls.push(
+        toggler(settings.system.analyze_songs)
+            .on_toggle(Message::UpdateSongAnalysis)
+            .size(24),
+    );
+
+    setting_row(
+        locale.get(Key::SettingsSongAnalysis),
+        Some(description.as_str()),
+        controls.into(),
+    )
+}
+
+/// Whether saved lyrics are embedded or written next to the song
+fn lyrics_save_target_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let options: Vec<String> = LyricsSaveTarget::ALL
+        .iter()
+        .map(|target| locale.get(target.label_key()).to_string())
+        .collect();
+    let current = locale
+        .get(settings.display.lyrics_save.target.label_key())
+        .to_string();
+
+    setting_row(
+        locale.get(Key::SettingsLyricsSaveTarget),
+        Some(locale.get(Key::SettingsLyricsSaveTargetDesc)),
+        styled_pick_list(options, Some(current), move |label| {
+            LyricsSaveTarget::ALL
+                .into_iter()
+                .find(|target| locale.get(target.label_key()) == label)
+                .map_or(Message::NoOp, Message::UpdateLyricsSaveTarget)
+        }),
+    )
+}
+
+/// Saving lyrics for the songs of a folder, with its progress while it runs
+fn lyrics_save_folder_row(
+    progress: Option<(usize, usize)>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let mut description = locale.get(Key::SettingsLyricsSaveFolderDesc).to_string();
+    let control = match progress {
+        Some((done, total)) => {
+            description.push(' ');
+            description.push_str(
+                &locale
+                    .get(Key::SettingsLyricsSaveFolderProgress)
+                    .replacen("{}", &done.to_string(), 1)
+                    .replacen("{}", &total.to_string(), 1),
+            );
+            button(text(locale.get(Key::Cancel).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press(Message::CancelLyricsSaveBatch)
+        }
+        None => button(text(locale.get(Key::SettingsLyricsSaveFolderRun).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::PickLyricsSaveFolder),
+    };
+
+    setting_row(
+        locale.get(Key::SettingsLyricsSaveFolder),
+        Some(description.as_str()),
+        control.into(),
+    )
+}
+
+/// Permission for listen mode to record other apps, and a button to start it
+fn listen_mode_row(
+    settings: &Settings,
+    listening: bool,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let mut controls = row![].spacing(12).align_y(Alignment::Center);
+    if settings.system.listen_mode {
+        let label = if listening {
+            Key::ListenStop
+        } else {
+            Key::ListenStart
+        };
+        controls = controls.push(
+            button(text(locale.get(label).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press(Message::ToggleListenMode),
+        );
+    }
+    controls = controls.push(
+        toggler(settings.system.listen_mode)
+            .on_toggle(Message::UpdateListenPermission)
+            .size(24),
+    );
+
+    setting_row(
+        locale.get(Key::SettingsListenMode),
+        Some(locale.get(Key::SettingsListenModeDesc)),
+        controls.into(),
+    )
+}
+
+/// Now-playing output files for streaming software
+fn now_playing_rows(output: &NowPlayingOutput, locale: Locale) -> Element<'static, Message> {
+    let toggle = setting_row(
+        locale.get(Key::SettingsNowPlayingFiles),
+        Some(locale.get(Key::SettingsNowPlayingFilesDesc)),
+        toggler(output.enabled)
+            .on_toggle(Message::UpdateNowPlayingOutput)
+            .size(24)
+            .into(),
+    );
+    if !output.enabled {
+        return toggle;
+    }
+
+    let folder = setting_row(
+        locale.get(Key::SettingsNowPlayingFolder),
+        Some(output.dir().to_string_lossy().as_ref()),
+        row![
+            button(text(locale.get(Key::SettingsNowPlayingFolderChange).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press(Message::PickNowPlayingFolder),
+            button(text(locale.get(Key::SettingsNowPlayingFolderOpen).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press(Message::OpenNowPlayingFolder),
+        ]
+        .spacing(8)
+        .into(),
+    );
+    let artwork = setting_row(
+        locale.get(Key::SettingsNowPlayingArtwork),
+        Some(locale.get(Key::SettingsNowPlayingArtworkDesc)),
+        toggler(output.artwork)
+            .on_toggle(Message::UpdateNowPlayingArtwork)
+            .size(24)
+            .into(),
+    );
+    column![toggle, divider(), folder, divider(), artwork].into()
+}
+
+/// Event hooks: a header with an add button, then one row per hook
+fn hooks_rows(hooks: &[EventHook], locale: Locale) -> Element<'static, Message> {
+    let variables = hooks::VARIABLES
+        .iter()
+        .map(|name| format!("{{{}}}", name))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let description = locale.get(Key::SettingsHooksDesc).replace("{}", &variables);
+    let header = setting_row(
+        locale.get(Key::SettingsHooks),
+        Some(description.as_str()),
+        button(text(locale.get(Key::SettingsHooksAdd).to_string()).size(14))
+            .style(theme::secondary_button)
+            .padding([8, 16])
+            .on_press(Message::AddEventHook)
+            .into(),
+    );
+
+    let mut rows = column![header].spacing(8);
+    for (index, hook) in hooks.iter().enumerate() {
+        let events: Vec<String> = HookEvent::all()
+            .iter()
+            .map(|e| e.display_name(locale).to_string())
+            .collect();
+        let actions: Vec<String> = HookAction::all()
+            .iter()
+            .map(|a| a.display_name(locale).to_string())
+            .collect();
+        let placeholder = match hook.action {
+            HookAction::Command => "notify-send \"{title}\" \"{artist}\"",
+            HookAction::Webhook => "http://localhost:8123/api/webhook/rustle",
+        };
+
+        let on_event = edit_hook(index, hook, move |hook, value: String| {
+            hook.event = HookEvent::all()
+                .into_iter()
+                .find(|e| e.display_name(locale) == value)
+                .unwrap_or_default();
+        });
+        let on_action = edit_hook(index, hook, move |hook, value: String| {
+            hook.action = HookAction::all()
+                .into_iter()
+                .find(|a| a.display_name(locale) == value)
+                .unwrap_or_default();
+        });
+        let on_target = edit_hook(index, hook, |hook, target: String| hook.target = target);
+        let on_toggle = edit_hook(index, hook, |hook, enabled: bool| hook.enabled = enabled);
+
+        rows = rows.push(
+            row![
+                styled_pick_list(
+                    events,
+                    Some(hook.event.display_name(locale).to_string()),
+                    on_event
+                ),
+                styled_pick_list(
+                    actions,
+                    Some(hook.action.display_name(locale).to_string()),
+                    on_action
+                ),
+                text_input(placeholder, &hook.target)
+                    .on_input(on_target)
+                    .padding([8, 12])
+                    .width(Fill)
+                    .style(input_style),
+                toggler(hook.enabled).on_toggle(on_toggle).size(24),
+                button(text(locale.get(Key::SettingsHooksTest).to_string()).size(14))
+                    .style(theme::secondary_button)
+                    .padding([8, 16])
+                    .on_press(Message::TestEventHook(index)),
+                button(text(locale.get(Key::SettingsHooksRemove).to_string()).size(14))
+                    .style(theme::secondary_button)
+                    .padding([8, 16])
+                    .on_press(Message::RemoveEventHook(index)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        );
+    }
+
+    container(rows).padding(Padding::ZERO.bottom(16.0)).into()
+}
+
+/// Message handler replacing hook `index` with an edited copy of `hook`
+fn edit_hook<T>(
+    index: usize,
+    hook: &EventHook,
+    edit: impl Fn(&mut EventHook, T) + Clone + 'static,
+) -> impl Fn(T) -> Message + Clone + 'static {
+    let hook = hook.clone();
+    move |value| {
+        let mut hook = hook.clone();
+        edit(&mut hook, value);
+        Message::UpdateEventHook(index, hook)
+    }
+}
+
 fn network_section(settings: &Settings, locale: Locale) -> Element<'static, Message> {
     use crate::features::ProxyType;
 
@@ -714,9 +2102,86 @@ fn network_section(settings: &Settings, locale: Locale) -> Element<'static, Mess
         ));
     }
 
+    items.push(divider());
+    items.push(bandwidth_limit_row(settings, locale));
+    items.push(divider());
+    items.push(metered_mode_row(settings, locale));
+
     column(items).spacing(0).into()
 }
 
+/// Bandwidth limit presets in KB/s (0 = unlimited)
+const BANDWIDTH_LIMITS_KBPS: [u32; 6] = [0, 256, 512, 1024, 2048, 5120];
+
+fn format_bandwidth_limit(kbps: u32, locale: Locale) -> String {
+    if kbps == 0 {
+        locale.get(Key::SettingsBandwidthUnlimited).to_string()
+    } else if kbps % 1024 == 0 {
+        format!("{} MB/s", kbps / 1024)
+    } else {
+        format!("{} KB/s", kbps)
+    }
+}
+
+fn bandwidth_limit_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    let options: Vec<(u32, String)> = BANDWIDTH_LIMITS_KBPS
+        .iter()
+        .map(|&kbps| (kbps, format_bandwidth_limit(kbps, locale)))
+        .collect();
+    let labels: Vec<String> = options.iter().map(|(_, label)| label.clone()).collect();
+    let current = format_bandwidth_limit(settings.network.bandwidth_limit_kbps, locale);
+
+    setting_row(
+        locale.get(Key::SettingsBandwidthLimit),
+        Some(locale.get(Key::SettingsBandwidthLimitDesc)),
+        styled_pick_list(labels, Some(current), move |value| {
+            let kbps = options
+                .iter()
+                .find(|(_, label)| *label == value)
+                .map(|(kbps, _)| *kbps)
+                .unwrap_or(0);
+            Message::UpdateBandwidthLimit(kbps)
+        }),
+    )
+}
+
+fn metered_mode_row(settings: &Settings, locale: Locale) -> Element<'static, Message> {
+    use crate::features::MeteredMode;
+
+    let auto_label = locale.get(Key::SettingsMeteredAuto).to_string();
+    let on_label = locale.get(Key::SettingsMeteredOn).to_string();
+    let off_label = locale.get(Key::SettingsMeteredOff).to_string();
+
+    // Only Windows reports connection cost; elsewhere the setting is manual
+    let detectable = cfg!(target_os = "windows");
+    let current = match settings.network.metered_mode {
+        MeteredMode::Auto if detectable => auto_label.clone(),
+        MeteredMode::On => on_label.clone(),
+        MeteredMode::Auto | MeteredMode::Off => off_label.clone(),
+    };
+    let mut options = Vec::new();
+    if detectable {
+        options.push(auto_label.clone());
+    }
+    options.push(on_label.clone());
+    options.push(off_label);
+
+    setting_row(
+        locale.get(Key::SettingsMeteredConnection),
+        Some(locale.get(Key::SettingsMeteredConnectionDesc)),
+        styled_pick_list(options, Some(current), move |value| {
+            let mode = if value == auto_label {
+                MeteredMode::Auto
+            } else if value == on_label {
+                MeteredMode::On
+            } else {
+                MeteredMode::Off
+            };
+            Message::UpdateMeteredMode(mode)
+        }),
+    )
+}
+
 /// Setting row with text input - handles lifetime issues by creating owned strings
 fn setting_row_with_input<F>(
     label: &str,
@@ -741,25 +2206,7 @@ where
                 .on_input(on_input)
                 .padding([8, 12])
                 .width(200)
-                .style(|theme, status| {
-                    let border_color = match status {
-                        text_input::Status::Focused { .. } => theme::ACCENT_PINK,
-                        text_input::Status::Hovered => theme::settings_input_border_hover(theme),
-                        _ => theme::settings_input_border(theme),
-                    };
-                    text_input::Style {
-                        background: iced::Background::Color(theme::settings_input_bg(theme)),
-                        border: Border {
-                            color: border_color,
-                            width: 1.0,
-                            radius: 4.0.into(),
-                        },
-                        icon: theme::settings_desc(theme),
-                        placeholder: theme::settings_desc(theme),
-                        value: theme::settings_label(theme),
-                        selection: theme::ACCENT_PINK,
-                    }
-                }),
+                .style(input_style),
         ]
         .align_y(Alignment::Center)
         .width(Fill),
@@ -768,15 +2215,34 @@ where
     .into()
 }
 
+/// Text input style shared by settings rows
+fn input_style(theme: &iced::Theme, status: text_input::Status) -> text_input::Style {
+    let border_color = match status {
+        text_input::Status::Focused { .. } => theme::ACCENT_PINK,
+        text_input::Status::Hovered => theme::settings_input_border_hover(theme),
+        _ => theme::settings_input_border(theme),
+    };
+    text_input::Style {
+        background: iced::Background::Color(theme::settings_input_bg(theme)),
+        border: Border {
+            color: border_color,
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+        icon: theme::settings_desc(theme),
+        placeholder: theme::settings_desc(theme),
+        value: theme::settings_label(theme),
+        selection: theme::ACCENT_PINK,
+    }
+}
+
 fn storage_section(
     settings: &Settings,
     locale: Locale,
     cache_stats: Option<&crate::cache::CacheStats>,
 ) -> Element<'static, Message> {
     // Get cache directory path
-    let cache_dir = dirs::cache_dir()
-        .map(|p| p.join("rustle"))
-        .unwrap_or_else(|| std::path::PathBuf::from("~/.cache/rustle"));
+    let cache_dir = crate::utils::cache_dir();
     let cache_path_str = cache_dir.to_string_lossy().to_string();
 
     // Use cached stats if available, otherwise calculate on-demand
@@ -877,7 +2343,11 @@ fn format_size_bytes(bytes: u64) -> String {
     }
 }
 
-fn about_section(_locale: Locale) -> Element<'static, Message> {
+fn about_section(
+    settings: &Settings,
+    update_status: &UpdateStatus,
+    locale: Locale,
+) -> Element<'static, Message> {
     use once_cell::sync::Lazy;
 
     static ICON_DATA: &[u8] = include_bytes!("../../../assets/icons/icon_256.png");
@@ -922,7 +2392,7 @@ fn about_section(_locale: Locale) -> Element<'static, Message> {
         color: Some(theme::settings_desc(theme)),
     });
 
-    container(
+    let app_info = container(
         column![
             icon,
             Space::new().height(16),
@@ -938,7 +2408,110 @@ fn about_section(_locale: Locale) -> Element<'static, Message> {
     )
     .width(Fill)
     .center_x(Fill)
-    .padding([40, 0])
+    .padding([40, 0]);
+
+    column![app_info, update_rows(settings, update_status, locale)]
+        .spacing(0)
+        .into()
+}
+
+/// Release channel, auto-check toggle, manual check and what's new
+fn update_rows(
+    settings: &Settings,
+    update_status: &UpdateStatus,
+    locale: Locale,
+) -> Element<'static, Message> {
+    use crate::features::UpdateChannel;
+
+    let stable_label = locale.get(Key::SettingsUpdateChannelStable).to_string();
+    let beta_label = locale.get(Key::SettingsUpdateChannelBeta).to_string();
+    let current_channel = match settings.system.update_channel {
+        UpdateChannel::Stable => stable_label.clone(),
+        UpdateChannel::Beta => beta_label.clone(),
+    };
+    let channels = vec![stable_label, beta_label.clone()];
+
+    let status = match update_status {
+        UpdateStatus::Idle => None,
+        UpdateStatus::Checking => Some(locale.get(Key::UpdateStatusChecking).to_string()),
+        UpdateStatus::UpToDate => Some(locale.get(Key::UpdateStatusUpToDate).to_string()),
+        UpdateStatus::Available(release) => Some(
+            locale
+                .get(Key::UpdateStatusAvailable)
+                .replace("{}", &release.version),
+        ),
+        UpdateStatus::Downloading(release) => Some(
+            locale
+                .get(Key::UpdateStatusDownloading)
+                .replace("{}", &release.version),
+        ),
+        UpdateStatus::Ready(release, _) => Some(
+            locale
+                .get(Key::UpdateStatusReady)
+                .replace("{}", &release.version),
+        ),
+        UpdateStatus::Failed(error) => {
+            Some(locale.get(Key::UpdateStatusFailed).replace("{}", error))
+        }
+    };
+    let busy = matches!(
+        update_status,
+        UpdateStatus::Checking | UpdateStatus::Downloading(_)
+    );
+
+    column![
+        setting_row(
+            locale.get(Key::SettingsUpdateChannel),
+            Some(locale.get(Key::SettingsUpdateChannelDesc)),
+            styled_pick_list(channels, Some(current_channel), move |value| {
+                let channel = if value == beta_label {
+                    UpdateChannel::Beta
+                } else {
+                    UpdateChannel::Stable
+                };
+                Message::UpdateUpdateChannel(channel)
+            }),
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsAutoCheckUpdates),
+            Some(locale.get(Key::SettingsAutoCheckUpdatesDesc)),
+            toggler(settings.system.auto_check_updates)
+                .on_toggle(Message::UpdateAutoCheckUpdates)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsCheckUpdates),
+            status.as_deref(),
+            button(text(locale.get(Key::SettingsCheckUpdates).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press_maybe((!busy).then_some(Message::CheckForUpdates(true)))
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsShowWhatsNew),
+            Some(locale.get(Key::SettingsShowWhatsNewDesc)),
+            toggler(settings.system.show_whats_new)
+                .on_toggle(Message::UpdateShowWhatsNew)
+                .size(24)
+                .into()
+        ),
+        divider(),
+        setting_row(
+            locale.get(Key::SettingsVersionHistory),
+            Some(locale.get(Key::SettingsVersionHistoryDesc)),
+            button(text(locale.get(Key::SettingsVersionHistoryOpen).to_string()).size(14))
+                .style(theme::secondary_button)
+                .padding([8, 16])
+                .on_press(Message::OpenVersionHistory)
+                .into()
+        ),
+    ]
+    .spacing(0)
     .into()
 }
 
@@ -964,6 +2537,7 @@ fn shortcuts_section(
         (Action::GoSearch, Key::ActionGoSearch),
         (Action::ToggleQueue, Key::ActionToggleQueue),
         (Action::ToggleFullscreen, Key::ActionToggleFullscreen),
+        (Action::ToggleMemoryOverlay, Key::ActionToggleMemoryOverlay),
     ];
 
     // Build left column
@@ -972,7 +2546,13 @@ fn shortcuts_section(
         .map(|(action, key)| {
             let shortcut_text = keybindings.display_for_action(action);
             let is_editing = editing_keybinding == Some(*action);
-            shortcut_row(*action, locale.get(*key), &shortcut_text, is_editing)
+            shortcut_row(
+                *action,
+                locale.get(*key),
+                &shortcut_text,
+                is_editing,
+                locale,
+            )
         })
         .collect();
 
@@ -982,7 +2562,13 @@ fn shortcuts_section(
         .map(|(action, key)| {
             let shortcut_text = keybindings.display_for_action(action);
             let is_editing = editing_keybinding == Some(*action);
-            shortcut_row(*action, locale.get(*key), &shortcut_text, is_editing)
+            shortcut_row(
+                *action,
+                locale.get(*key),
+                &shortcut_text,
+                is_editing,
+                locale,
+            )
         })
         .collect();
 
@@ -1000,10 +2586,11 @@ fn shortcut_row(
     action_name: &str,
     shortcut: &str,
     is_editing: bool,
+    locale: Locale,
 ) -> Element<'static, Message> {
     let shortcut_display: Element<'static, Message> = if is_editing {
         container(
-            text("Press key...".to_string())
+            text(locale.get(Key::SettingsShortcutsPressKey))
                 .size(13)
                 .color(theme::ACCENT_PINK),
         )
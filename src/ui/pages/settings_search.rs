@@ -0,0 +1,760 @@
+//! Settings search
+//!
+//! Every setting on the settings page listed with a category, so one can be
+//! found by name or description without knowing which section holds it,
+//! and put back to its default from the results.
+
+use iced::widget::{Space, button, column, container, row, text};
+use iced::{Alignment, Background, Border, Element, Fill, Padding};
+
+use crate::app::{Message, SettingsSection};
+use crate::features::Settings;
+use crate::i18n::{Key, Language, Locale};
+use crate::ui::theme;
+
+use SettingsCategory as C;
+use SettingsSection as S;
+
+/// Groups settings are searched and listed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsCategory {
+    Playback,
+    Lyrics,
+    Network,
+    Library,
+    Appearance,
+    Advanced,
+}
+
+impl SettingsCategory {
+    pub const ALL: [Self; 6] = [
+        Self::Playback,
+        Self::Lyrics,
+        Self::Network,
+        Self::Library,
+        Self::Appearance,
+        Self::Advanced,
+    ];
+
+    pub fn label_key(self) -> Key {
+        match self {
+            Self::Playback => Key::SettingsCategoryPlayback,
+            Self::Lyrics => Key::SettingsCategoryLyrics,
+            Self::Network => Key::SettingsCategoryNetwork,
+            Self::Library => Key::SettingsCategoryLibrary,
+            Self::Appearance => Key::SettingsCategoryAppearance,
+            Self::Advanced => Key::SettingsCategoryAdvanced,
+        }
+    }
+}
+
+/// A setting as search sees it
+pub struct Entry {
+    pub category: SettingsCategory,
+    /// Section of the settings page showing the setting
+    pub section: SettingsSection,
+    pub title: Key,
+    pub description: Option<Key>,
+    reset: fn(&Settings, &Settings) -> Option<Message>,
+}
+
+impl Entry {
+    /// Message putting the setting back to its default, `None` while it is
+    /// already there or for settings without a single value
+    pub fn reset_message(&self, settings: &Settings, defaults: &Settings) -> Option<Message> {
+        (self.reset)(settings, defaults)
+    }
+
+    /// Whether every lowercase term appears in the title, description or
+    /// category name
+    fn matches(&self, terms: &[String], locale: Locale) -> bool {
+        let mut haystack = locale.get(self.title).to_lowercase();
+        if let Some(description) = self.description {
+            haystack.push('\n');
+            haystack.push_str(&locale.get(description).to_lowercase());
+        }
+        haystack.push('\n');
+        haystack.push_str(&locale.get(self.category.label_key()).to_lowercase());
+        terms.iter().all(|term| haystack.contains(term.as_str()))
+    }
+}
+
+const fn entry(
+    category: SettingsCategory,
+    section: SettingsSection,
+    title: Key,
+    description: Option<Key>,
+    reset: fn(&Settings, &Settings) -> Option<Message>,
+) -> Entry {
+    Entry {
+        category,
+        section,
+        title,
+        description,
+        reset,
+    }
+}
+
+/// For settings without a single value to go back to
+fn no_reset(_: &Settings, _: &Settings) -> Option<Message> {
+    None
+}
+
+/// `message` with the default when the setting has moved away from it
+fn differs<T: PartialEq + Clone>(
+    current: &T,
+    default: &T,
+    message: fn(T) -> Message,
+) -> Option<Message> {
+    (current != default).then(|| message(default.clone()))
+}
+
+/// Reset sending `message` with the default of the given settings field
+macro_rules! reset {
+    ($($field:ident).+, $message:expr) => {
+        |s, d| differs(&s.$($field).+, &d.$($field).+, $message)
+    };
+}
+
+/// Every searchable setting, in page order within each category
+static ENTRIES: &[Entry] = &[
+    // Playback
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsMusicQuality,
+        Some(Key::SettingsMusicQualityDesc),
+        reset!(playback.music_quality, Message::UpdateMusicQuality),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsFadeInOut,
+        Some(Key::SettingsFadeInOutDesc),
+        reset!(playback.fade_in_out, Message::UpdateFadeInOut),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsVolumeNormalization,
+        Some(Key::SettingsVolumeNormalizationDesc),
+        reset!(
+            playback.volume_normalization,
+            Message::UpdateVolumeNormalization
+        ),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsAutoplaySimilar,
+        Some(Key::SettingsAutoplaySimilarDesc),
+        reset!(playback.autoplay_similar, Message::UpdateAutoplaySimilar),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsSmartResume,
+        Some(Key::SettingsSmartResumeDesc),
+        reset!(playback.smart_resume.enabled, Message::UpdateSmartResume),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsSmartResumeAfter,
+        Some(Key::SettingsSmartResumeAfterDesc),
+        reset!(
+            playback.smart_resume.after_mins,
+            Message::UpdateSmartResumeAfter
+        ),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsSmartResumeRewind,
+        Some(Key::SettingsSmartResumeRewindDesc),
+        reset!(
+            playback.smart_resume.rewind_secs,
+            Message::UpdateSmartResumeRewind
+        ),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsCallDucking,
+        Some(Key::SettingsCallDuckingDesc),
+        reset!(playback.call_ducking.action, Message::UpdateCallAction),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsCallLowerTo,
+        Some(Key::SettingsCallLowerToDesc),
+        reset!(
+            playback.call_ducking.lower_percent,
+            Message::UpdateCallLowerPercent
+        ),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsCallAnyApp,
+        Some(Key::SettingsCallAnyAppDesc),
+        reset!(playback.call_ducking.any_app, Message::UpdateCallAnyApp),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsCallAfter,
+        Some(Key::SettingsCallAfterDesc),
+        reset!(playback.call_ducking.after_secs, Message::UpdateCallAfter),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsSkipBack,
+        Some(Key::SettingsSkipBackDesc),
+        reset!(playback.skip_back_secs, Message::UpdateSkipBackSecs),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsSkipForward,
+        Some(Key::SettingsSkipForwardDesc),
+        reset!(playback.skip_forward_secs, Message::UpdateSkipForwardSecs),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsSeekStep,
+        Some(Key::SettingsSeekStepDesc),
+        reset!(playback.seek_step_secs, Message::UpdateSeekStepSecs),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsVolumeStep,
+        Some(Key::SettingsVolumeStepDesc),
+        reset!(
+            playback.volume_step_percent,
+            Message::UpdateVolumeStepPercent
+        ),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsAutoDjPool,
+        Some(Key::SettingsAutoDjPoolDesc),
+        reset!(playback.auto_dj.pool, Message::UpdateAutoDjPool),
+    ),
+    entry(
+        C::Playback,
+        S::Playback,
+        Key::SettingsAutoDjCrossfade,
+        Some(Key::SettingsAutoDjCrossfadeDesc),
+        reset!(
+            playback.auto_dj.crossfade_secs,
+            Message::UpdateAutoDjCrossfade
+        ),
+    ),
+    entry(
+        C::Playback,
+        S::Schedules,
+        Key::SettingsSchedules,
+        Some(Key::SettingsSchedulesDesc),
+        no_reset,
+    ),
+    entry(
+        C::Playback,
+        S::System,
+        Key::SettingsAudioDevice,
+        None,
+        reset!(system.audio_output_device, Message::UpdateAudioOutputDevice),
+    ),
+    entry(
+        C::Playback,
+        S::System,
+        Key::SettingsResumeOnStartup,
+        Some(Key::SettingsResumeOnStartupDesc),
+        reset!(playback.resume_on_startup, Message::UpdateResumeOnStartup),
+    ),
+    // Lyrics
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsTranslation,
+        Some(Key::SettingsLyricsTranslationDesc),
+        reset!(
+            display.lyrics_sub_lines.translation,
+            Message::UpdateLyricsTranslationDefault
+        ),
+    ),
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsRomanization,
+        Some(Key::SettingsLyricsRomanizationDesc),
+        reset!(
+            display.lyrics_sub_lines.romanization,
+            Message::UpdateLyricsRomanizationDefault
+        ),
+    ),
+    entry(
+        C::Lyrics,
+        S::Playback,
+        Key::SettingsLyricsTuning,
+        Some(Key::SettingsLyricsTuningDesc),
+        |s, d| {
+            (s.display.lyrics_tuning != d.display.lyrics_tuning)
+                .then_some(Message::ResetLyricsTuning)
+        },
+    ),
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsShareOffer,
+        Some(Key::SettingsLyricsShareOfferDesc),
+        reset!(display.lyrics_share.offer, Message::UpdateLyricsShareOffer),
+    ),
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsShareCredit,
+        None,
+        reset!(
+            display.lyrics_share.credit,
+            Message::UpdateLyricsShareCredit
+        ),
+    ),
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsSaveOffer,
+        Some(Key::SettingsLyricsSaveOfferDesc),
+        reset!(display.lyrics_save.offer, Message::UpdateLyricsSaveOffer),
+    ),
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsSaveTarget,
+        Some(Key::SettingsLyricsSaveTargetDesc),
+        reset!(display.lyrics_save.target, Message::UpdateLyricsSaveTarget),
+    ),
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsSaveFolder,
+        Some(Key::SettingsLyricsSaveFolderDesc),
+        no_reset,
+    ),
+    entry(
+        C::Lyrics,
+        S::Display,
+        Key::SettingsLyricsBackground,
+        Some(Key::SettingsLyricsBackgroundDesc),
+        reset!(display.lyrics_background, Message::UpdateLyricsBackground),
+    ),
+    // Network
+    entry(
+        C::Network,
+        S::Account,
+        Key::SettingsDailyCheckIn,
+        Some(Key::SettingsDailyCheckInDesc),
+        reset!(account.daily_check_in, Message::UpdateDailyCheckIn),
+    ),
+    entry(
+        C::Network,
+        S::Network,
+        Key::SettingsProxyType,
+        None,
+        reset!(network.proxy_type, Message::UpdateProxyType),
+    ),
+    entry(
+        C::Network,
+        S::Network,
+        Key::SettingsBandwidthLimit,
+        Some(Key::SettingsBandwidthLimitDesc),
+        reset!(network.bandwidth_limit_kbps, Message::UpdateBandwidthLimit),
+    ),
+    entry(
+        C::Network,
+        S::Network,
+        Key::SettingsMeteredConnection,
+        Some(Key::SettingsMeteredConnectionDesc),
+        reset!(network.metered_mode, Message::UpdateMeteredMode),
+    ),
+    // Library
+    entry(
+        C::Library,
+        S::System,
+        Key::SettingsReleaseNotifications,
+        Some(Key::SettingsReleaseNotificationsDesc),
+        reset!(
+            system.release_notifications,
+            Message::UpdateReleaseNotifications
+        ),
+    ),
+    entry(
+        C::Library,
+        S::System,
+        Key::SettingsSongAnalysis,
+        Some(Key::SettingsSongAnalysisDesc),
+        reset!(system.analyze_songs, Message::UpdateSongAnalysis),
+    ),
+    entry(
+        C::Library,
+        S::System,
+        Key::SettingsListenMode,
+        Some(Key::SettingsListenModeDesc),
+        reset!(system.listen_mode, Message::UpdateListenPermission),
+    ),
+    entry(
+        C::Library,
+        S::Storage,
+        Key::SettingsCacheLocation,
+        None,
+        no_reset,
+    ),
+    entry(
+        C::Library,
+        S::Storage,
+        Key::SettingsMaxCache,
+        None,
+        reset!(storage.max_cache_mb, Message::UpdateMaxCacheMb),
+    ),
+    entry(
+        C::Library,
+        S::Storage,
+        Key::SettingsClearCache,
+        Some(Key::SettingsClearCacheDesc),
+        no_reset,
+    ),
+    // Appearance
+    entry(
+        C::Appearance,
+        S::Display,
+        Key::SettingsDarkMode,
+        None,
+        reset!(display.dark_mode, Message::UpdateDarkMode),
+    ),
+    entry(
+        C::Appearance,
+        S::Display,
+        Key::SettingsLanguage,
+        None,
+        |s, _| {
+            // A fresh install follows the system language
+            let system = Language::system().code().to_string();
+            differs(&s.display.language, &system, Message::UpdateAppLanguage)
+        },
+    ),
+    entry(
+        C::Appearance,
+        S::Display,
+        Key::SettingsPowerSavingMode,
+        Some(Key::SettingsPowerSavingModeDesc),
+        reset!(display.power_saving_mode, Message::UpdatePowerSavingMode),
+    ),
+    entry(
+        C::Appearance,
+        S::Display,
+        Key::SettingsCoverTheming,
+        Some(Key::SettingsCoverThemingDesc),
+        reset!(display.cover_theming, Message::UpdateCoverTheming),
+    ),
+    entry(
+        C::Appearance,
+        S::Display,
+        Key::SettingsCloseBehavior,
+        None,
+        reset!(close_behavior, Message::UpdateCloseBehavior),
+    ),
+    entry(
+        C::Appearance,
+        S::Display,
+        Key::SettingsArtworkBackdrop,
+        Some(Key::SettingsArtworkBackdropDesc),
+        reset!(display.artwork_backdrop, Message::UpdateArtworkBackdrop),
+    ),
+    entry(
+        C::Appearance,
+        S::Playback,
+        Key::SettingsUiSounds,
+        Some(Key::SettingsUiSoundsDesc),
+        reset!(playback.ui_sounds, Message::UpdateUiSounds),
+    ),
+    // Advanced
+    entry(
+        C::Advanced,
+        S::System,
+        Key::SettingsLaunchAtLogin,
+        Some(Key::SettingsLaunchAtLoginDesc),
+        reset!(system.launch_at_login, Message::UpdateLaunchAtLogin),
+    ),
+    entry(
+        C::Advanced,
+        S::System,
+        Key::SettingsStartMinimized,
+        Some(Key::SettingsStartMinimizedDesc),
+        reset!(system.start_minimized, Message::UpdateStartMinimized),
+    ),
+    entry(
+        C::Advanced,
+        S::System,
+        Key::SettingsProfile,
+        Some(Key::SettingsProfileDesc),
+        no_reset,
+    ),
+    entry(
+        C::Advanced,
+        S::System,
+        Key::SettingsNowPlayingFiles,
+        Some(Key::SettingsNowPlayingFilesDesc),
+        reset!(system.now_playing.enabled, Message::UpdateNowPlayingOutput),
+    ),
+    entry(
+        C::Advanced,
+        S::System,
+        Key::SettingsNowPlayingArtwork,
+        Some(Key::SettingsNowPlayingArtworkDesc),
+        reset!(system.now_playing.artwork, Message::UpdateNowPlayingArtwork),
+    ),
+    entry(
+        C::Advanced,
+        S::System,
+        Key::SettingsHooks,
+        Some(Key::SettingsHooksDesc),
+        no_reset,
+    ),
+    entry(
+        C::Advanced,
+        S::Shortcuts,
+        Key::SettingsShortcutsTitle,
+        None,
+        no_reset,
+    ),
+    entry(
+        C::Advanced,
+        S::About,
+        Key::SettingsUpdateChannel,
+        Some(Key::SettingsUpdateChannelDesc),
+        reset!(system.update_channel, Message::UpdateUpdateChannel),
+    ),
+    entry(
+        C::Advanced,
+        S::About,
+        Key::SettingsAutoCheckUpdates,
+        Some(Key::SettingsAutoCheckUpdatesDesc),
+        reset!(system.auto_check_updates, Message::UpdateAutoCheckUpdates),
+    ),
+    entry(
+        C::Advanced,
+        S::About,
+        Key::SettingsShowWhatsNew,
+        Some(Key::SettingsShowWhatsNewDesc),
+        reset!(system.show_whats_new, Message::UpdateShowWhatsNew),
+    ),
+    entry(
+        C::Advanced,
+        S::About,
+        Key::SettingsVersionHistory,
+        Some(Key::SettingsVersionHistoryDesc),
+        no_reset,
+    ),
+];
+
+/// Settings matching every word of `query`, within `category` when one is
+/// picked
+pub fn search(
+    query: &str,
+    category: Option<SettingsCategory>,
+    locale: Locale,
+) -> Vec<&'static Entry> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    ENTRIES
+        .iter()
+        .filter(|entry| category.is_none_or(|category| entry.category == category))
+        .filter(|entry| entry.matches(&terms, locale))
+        .collect()
+}
+
+/// Category filter chips, the first one clearing the filter
+pub fn category_chips(
+    active: Option<SettingsCategory>,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let chips = std::iter::once((None, Key::SettingsCategoryAll))
+        .chain(
+            SettingsCategory::ALL
+                .into_iter()
+                .map(|category| (Some(category), category.label_key())),
+        )
+        .map(|(category, label)| {
+            let is_active = active == category;
+            button(text(locale.get(label).to_string()).size(13))
+                .padding(Padding::new(5.0).left(14.0).right(14.0))
+                .style(move |theme, status| chip_style(theme, status, is_active))
+                .on_press(Message::SettingsCategorySelected(category))
+                .into()
+        });
+
+    row(chips).spacing(8).into()
+}
+
+/// Search results grouped by category, in place of the settings sections
+pub fn view(
+    query: &str,
+    category: Option<SettingsCategory>,
+    settings: &Settings,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let entries = search(query, category, locale);
+    if entries.is_empty() {
+        return container(
+            text(locale.get(Key::SettingsSearchEmpty).to_string())
+                .size(14)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_desc(theme)),
+                }),
+        )
+        .padding([40, 0])
+        .width(Fill)
+        .center_x(Fill)
+        .into();
+    }
+
+    let defaults = Settings::default();
+    let mut content = column![].spacing(0).width(Fill);
+    for group in SettingsCategory::ALL {
+        let rows: Vec<_> = entries
+            .iter()
+            .filter(|entry| entry.category == group)
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        content = content.push(
+            text(locale.get(group.label_key()).to_string())
+                .size(18)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_section_title(theme)),
+                }),
+        );
+        for entry in rows {
+            content = content.push(result_row(
+                entry,
+                entry.reset_message(settings, &defaults),
+                locale,
+            ));
+        }
+        content = content.push(Space::new().height(32));
+    }
+    content.into()
+}
+
+fn result_row(entry: &Entry, reset: Option<Message>, locale: Locale) -> Element<'static, Message> {
+    let mut label = column![
+        text(locale.get(entry.title).to_string())
+            .size(15)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_label(theme))
+            })
+    ]
+    .spacing(4);
+    if let Some(description) = entry.description {
+        label = label.push(
+            text(locale.get(description).to_string())
+                .size(12)
+                .style(|theme| text::Style {
+                    color: Some(theme::settings_desc(theme)),
+                }),
+        );
+    }
+
+    let mut actions = row![].spacing(8).align_y(Alignment::Center);
+    if let Some(reset) = reset {
+        actions = actions.push(
+            button(text(locale.get(Key::SettingsReset).to_string()).size(13))
+                .padding([6, 14])
+                .style(theme::secondary_button)
+                .on_press(reset),
+        );
+    }
+    actions = actions.push(
+        button(text(locale.get(Key::SettingsSearchShow).to_string()).size(13))
+            .padding([6, 14])
+            .style(theme::secondary_button)
+            .on_press(Message::ScrollToSection(entry.section)),
+    );
+
+    container(
+        row![label.width(Fill), actions]
+            .spacing(16)
+            .align_y(Alignment::Center)
+            .width(Fill),
+    )
+    .padding([14, 0])
+    .into()
+}
+
+/// Category chip: outlined, filled with the accent when active
+fn chip_style(theme: &iced::Theme, status: button::Status, is_active: bool) -> button::Style {
+    let background = if is_active {
+        Some(Background::Color(theme::ACCENT_PINK))
+    } else if matches!(status, button::Status::Hovered) {
+        Some(Background::Color(theme::hover_bg(theme)))
+    } else {
+        None
+    };
+    button::Style {
+        background,
+        text_color: if is_active {
+            iced::Color::WHITE
+        } else {
+            theme::settings_label(theme)
+        },
+        border: Border {
+            radius: 14.0.into(),
+            width: if is_active { 0.0 } else { 1.0 },
+            color: theme::border_color(theme),
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_needs_every_term() {
+        let locale = Locale::new(Language::from_code("en"));
+        let found = search("skip back", None, locale);
+        assert!(found.iter().any(|e| e.title == Key::SettingsSkipBack));
+        assert!(found.iter().all(|e| e.title != Key::SettingsSkipForward));
+
+        let lyrics = search("", Some(SettingsCategory::Lyrics), locale);
+        assert!(!lyrics.is_empty());
+        assert!(
+            lyrics
+                .iter()
+                .all(|e| e.category == SettingsCategory::Lyrics)
+        );
+    }
+
+    #[test]
+    fn test_reset_only_when_changed() {
+        let defaults = Settings::default();
+        let mut settings = Settings::default();
+        let fade = ENTRIES
+            .iter()
+            .find(|e| e.title == Key::SettingsFadeInOut)
+            .unwrap();
+        assert!(fade.reset_message(&settings, &defaults).is_none());
+
+        settings.playback.fade_in_out = !defaults.playback.fade_in_out;
+        assert!(matches!(
+            fade.reset_message(&settings, &defaults),
+            Some(Message::UpdateFadeInOut(v)) if v == defaults.playback.fade_in_out
+        ));
+    }
+}
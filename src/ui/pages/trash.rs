@@ -0,0 +1,208 @@
+//! Recently deleted page
+//!
+//! Lists deleted playlists and songs removed from the library with how long
+//! each has left before it is purged, and lets the user restore them or
+//! delete them for good.
+
+use iced::widget::{Space, button, column, container, row, scrollable, text};
+use iced::{Alignment, Background, Border, Element, Fill, Padding};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::{Message, TrashPageState};
+use crate::database::trash_days_left;
+use crate::i18n::{Key, Locale};
+use crate::ui::theme;
+
+/// Recently deleted page view
+pub fn view(state: &TrashPageState, locale: Locale) -> Element<'static, Message> {
+    let header = column![
+        text(locale.get(Key::TrashTitle).to_string())
+            .size(28)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_title(theme)),
+            }),
+        Space::new().height(6),
+        text(locale.get(Key::TrashDesc).to_string())
+            .size(14)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            }),
+    ];
+
+    let is_empty = state.playlists.is_empty() && state.songs.is_empty();
+    let empty_label = if state.confirming_empty {
+        Key::TrashEmptyConfirm
+    } else {
+        Key::TrashEmpty
+    };
+    let empty_button = button(text(locale.get(empty_label).to_string()).size(14))
+        .style(theme::secondary_button)
+        .padding([8, 16])
+        .on_press_maybe((!is_empty).then_some(Message::EmptyTrash));
+
+    let header_container =
+        container(row![header, Space::new().width(Fill), empty_button].align_y(Alignment::Center))
+            .width(Fill)
+            .padding(
+                Padding::new(40.0)
+                    .top(70.0)
+                    .right(32.0)
+                    .bottom(20.0)
+                    .left(32.0),
+            )
+            .style(|theme| container::Style {
+                background: Some(Background::Color(theme::background(theme))),
+                ..Default::default()
+            });
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let playlists = state.playlists.iter().map(|playlist| {
+        let details = format!(
+            "{} · {}",
+            locale.count(Key::PlaylistSongCount, playlist.song_count.max(0) as u64),
+            time_left(playlist.deleted_at, now, locale)
+        );
+        entry(
+            playlist.name.clone(),
+            details,
+            Message::RestoreTrashedPlaylist(playlist.id),
+            Message::PurgeTrashedPlaylist(playlist.id),
+            locale,
+        )
+    });
+    let songs = state.songs.iter().map(|song| {
+        let time_left = time_left(song.deleted_at, now, locale);
+        let details = if song.artist.is_empty() {
+            time_left
+        } else {
+            format!("{} · {}", song.artist, time_left)
+        };
+        entry(
+            song.title.clone(),
+            details,
+            Message::RestoreTrashedSong(song.id),
+            Message::PurgeTrashedSong(song.id),
+            locale,
+        )
+    });
+
+    let content = column![
+        section(
+            locale.get(Key::TrashPlaylists),
+            locale.get(Key::TrashNoPlaylists),
+            playlists.collect(),
+        ),
+        section(
+            locale.get(Key::TrashSongs),
+            locale.get(Key::TrashNoSongs),
+            songs.collect(),
+        ),
+    ]
+    .spacing(32)
+    .width(Fill);
+
+    let scrollable_content = scrollable(
+        container(content)
+            .width(Fill)
+            .padding(Padding::new(20.0).right(32.0).bottom(60.0).left(32.0)),
+    )
+    .id(iced::widget::Id::new("trash_scroll"))
+    .width(Fill)
+    .height(Fill);
+
+    container(
+        column![header_container, scrollable_content]
+            .width(Fill)
+            .height(Fill),
+    )
+    .width(Fill)
+    .height(Fill)
+    .style(theme::main_content)
+    .into()
+}
+
+/// How long until an item is purged
+fn time_left(deleted_at: i64, now: i64, locale: Locale) -> String {
+    match trash_days_left(deleted_at, now) {
+        0 => locale.get(Key::TrashLastDay).to_string(),
+        days => locale.count(Key::TrashDaysLeft, days as u64),
+    }
+}
+
+/// Titled list, or a hint when it is empty
+fn section(
+    title: &str,
+    empty: &str,
+    entries: Vec<Element<'static, Message>>,
+) -> Element<'static, Message> {
+    let body: Element<'static, Message> = if entries.is_empty() {
+        text(empty.to_string())
+            .size(13)
+            .style(|theme| text::Style {
+                color: Some(theme::settings_desc(theme)),
+            })
+            .into()
+    } else {
+        column(entries).spacing(8).width(Fill).into()
+    };
+
+    column![
+        text(title.to_string()).size(16).style(|theme| text::Style {
+            color: Some(theme::settings_label(theme)),
+        }),
+        body,
+    ]
+    .spacing(12)
+    .width(Fill)
+    .into()
+}
+
+/// Single trashed item with its restore and delete buttons
+fn entry(
+    title: String,
+    details: String,
+    on_restore: Message,
+    on_delete: Message,
+    locale: Locale,
+) -> Element<'static, Message> {
+    let label = column![
+        text(title).size(14).style(|theme| text::Style {
+            color: Some(theme::settings_label(theme)),
+        }),
+        text(details).size(12).style(|theme| text::Style {
+            color: Some(theme::settings_desc(theme)),
+        }),
+    ]
+    .spacing(2);
+
+    container(
+        row![
+            label,
+            Space::new().width(Fill),
+            button(text(locale.get(Key::TrashRestore).to_string()).size(13))
+                .style(theme::secondary_button)
+                .padding([6, 12])
+                .on_press(on_restore),
+            button(text(locale.get(Key::TrashDeleteForever).to_string()).size(13))
+                .style(theme::secondary_button)
+                .padding([6, 12])
+                .on_press(on_delete),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+    )
+    .width(Fill)
+    .padding([12, 16])
+    .style(|theme| container::Style {
+        background: Some(Background::Color(theme::surface_container(theme))),
+        border: Border {
+            radius: 12.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .into()
+}
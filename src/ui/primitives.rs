@@ -13,13 +13,19 @@
 //! # Contents
 //!
 //! - [`SquareCoverWidget`] - Maintains 1:1 aspect ratio for cover art
+//! - [`BoundsSensor`] - Reports where an element sits, for shared-element transitions
 //! - [`ProgressRing`] - Circular progress indicator using Canvas
+//! - [`QrCode`] - QR code drawn from its module matrix using Canvas
 //! - [`VirtualList`] - High-performance virtualized list
 
+pub mod bounds_sensor;
 pub mod progress_ring;
+pub mod qr_code;
 pub mod square_cover;
 pub mod virtual_list;
 
+pub use bounds_sensor::BoundsSensor;
 pub use progress_ring::{ProgressRing, view_progress_ring_styled};
+pub use qr_code::{QrCode, view_qr_code};
 pub use square_cover::view as square_cover;
 pub use virtual_list::{VirtualList, VirtualListState};
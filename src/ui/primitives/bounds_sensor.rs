@@ -0,0 +1,196 @@
+//! Bounds sensor primitive
+//!
+//! Wraps an element and reports where it sits in the window, so a
+//! transition can fly a stand-in between two places. It reports the bounds
+//! when pressed and/or whenever they change, and can hide its content while
+//! the stand-in is drawn over it.
+//!
+//! # Design
+//!
+//! This is a primitive component - it uses generic Message types and
+//! does not depend on application-specific types.
+
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Tree, Widget};
+use iced::advanced::{Clipboard, Shell};
+use iced::mouse::{self, Cursor};
+use iced::{Element, Event, Length, Rectangle, Size, window};
+
+/// Bounds last reported by a sensor
+#[derive(Default)]
+struct State {
+    reported: Option<Rectangle>,
+}
+
+/// Wrapper reporting the bounds of its content
+pub struct BoundsSensor<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    on_press: Option<Box<dyn Fn(Rectangle) -> Message + 'a>>,
+    on_change: Option<Box<dyn Fn(Rectangle) -> Message + 'a>>,
+    hidden: bool,
+}
+
+impl<'a, Message, Theme, Renderer> BoundsSensor<'a, Message, Theme, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        Self {
+            content: content.into(),
+            on_press: None,
+            on_change: None,
+            hidden: false,
+        }
+    }
+
+    /// Report the bounds when the left button goes down on the content,
+    /// before the content handles the press
+    pub fn on_press(mut self, on_press: impl Fn(Rectangle) -> Message + 'a) -> Self {
+        self.on_press = Some(Box::new(on_press));
+        self
+    }
+
+    /// Report the bounds when first drawn and whenever they change
+    pub fn on_change(mut self, on_change: impl Fn(Rectangle) -> Message + 'a) -> Self {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    /// Keep the layout but skip drawing the content
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for BoundsSensor<'a, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn tag(&self) -> widget::tree::Tag {
+        widget::tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> widget::tree::State {
+        widget::tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget_mut()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                if cursor.is_over(bounds) =>
+            {
+                if let Some(on_press) = &self.on_press {
+                    shell.publish(on_press(bounds));
+                }
+            }
+            Event::Window(window::Event::RedrawRequested(_)) => {
+                let state = tree.state.downcast_mut::<State>();
+                if let Some(on_change) = &self.on_change {
+                    if state.reported != Some(bounds) {
+                        state.reported = Some(bounds);
+                        shell.publish(on_change(bounds));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+    ) {
+        if self.hidden {
+            return;
+        }
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<BoundsSensor<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(sensor: BoundsSensor<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(sensor)
+    }
+}
@@ -0,0 +1,74 @@
+//! QR code primitive
+//!
+//! Draws a QR code straight from its module matrix using iced's Canvas, so
+//! a code can be shown without encoding it to an image first.
+
+use iced::widget::Canvas;
+use iced::widget::canvas::{Frame, Geometry, Path, Program};
+use iced::{Color, Element, Point, Renderer, Size, Theme, mouse};
+
+/// QR code drawn from its module matrix
+#[derive(Debug, Clone, Copy)]
+pub struct QrCode<'a> {
+    /// Modules row by row, `true` for dark
+    pub modules: &'a [Vec<bool>],
+    /// Color of the dark modules
+    pub module_color: Color,
+}
+
+impl<'a> QrCode<'a> {
+    pub fn new(modules: &'a [Vec<bool>]) -> Self {
+        Self {
+            modules,
+            module_color: Color::BLACK,
+        }
+    }
+
+    pub fn module_color(mut self, color: Color) -> Self {
+        self.module_color = color;
+        self
+    }
+}
+
+impl<Message> Program<Message> for QrCode<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: iced::Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let count = self.modules.len();
+        if count == 0 {
+            return vec![frame.into_geometry()];
+        }
+
+        // Whole-pixel modules keep anti-aliased seams out of the code
+        let side = bounds.width.min(bounds.height);
+        let module = (side / count as f32).floor().max(1.0);
+        let offset = ((side - module * count as f32) / 2.0).max(0.0);
+
+        let path = Path::new(|builder| {
+            for (y, row) in self.modules.iter().enumerate() {
+                for (x, _) in row.iter().enumerate().filter(|(_, dark)| **dark) {
+                    builder.rectangle(
+                        Point::new(offset + x as f32 * module, offset + y as f32 * module),
+                        Size::new(module, module),
+                    );
+                }
+            }
+        });
+        frame.fill(&path, self.module_color);
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Create a QR code element `size` pixels square
+pub fn view_qr_code<'a, Message: 'a>(qr: QrCode<'a>, size: f32) -> Element<'a, Message> {
+    Canvas::new(qr).width(size).height(size).into()
+}
@@ -14,7 +14,8 @@
 //!
 //! - Tree Diffing: Preserves widget state (focus, animations, etc.) when scrolling
 //! - Scrollbar: Visual indicator of scroll position
-//! - Buffer items: Renders extra items above/below viewport for smooth scrolling
+//! - Overscan: Renders extra items above/below viewport for smooth scrolling
+//! - Anchoring: Keeps the top item in place when the list is refreshed
 //! - Element Caching: Each item_builder is called only once per frame
 //! - Optimized: Minimizes item_builder calls per frame
 
@@ -27,7 +28,7 @@ use iced::{Color, Element, Event, Length, Point, Rectangle, Size};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-/// Buffer items to render above and below the visible area
+/// Default number of items rendered above and below the visible area
 const BUFFER_ITEMS: usize = 3;
 
 /// Scrollbar configuration
@@ -47,6 +48,8 @@ pub struct VirtualListState {
     pub item_count: usize,
     /// Item height
     pub item_height: f32,
+    /// Extra items rendered above and below the viewport
+    pub overscan: usize,
 }
 
 impl Default for VirtualListState {
@@ -56,6 +59,7 @@ impl Default for VirtualListState {
             viewport_height: 0.0,
             item_count: 0,
             item_height: 62.0,
+            overscan: BUFFER_ITEMS,
         }
     }
 }
@@ -68,6 +72,7 @@ impl VirtualListState {
             viewport_height: 0.0,
             item_count,
             item_height,
+            overscan: BUFFER_ITEMS,
         }
     }
 
@@ -85,8 +90,8 @@ impl VirtualListState {
         let first_visible = (self.scroll_offset / self.item_height).floor() as usize;
         let visible_count = (self.viewport_height / self.item_height).ceil() as usize + 1;
 
-        let start = first_visible.saturating_sub(BUFFER_ITEMS);
-        let end = (first_visible + visible_count + BUFFER_ITEMS).min(self.item_count);
+        let start = first_visible.saturating_sub(self.overscan);
+        let end = (first_visible + visible_count + self.overscan).min(self.item_count);
 
         (start, end)
     }
@@ -106,6 +111,31 @@ impl VirtualListState {
     pub fn max_scroll(&self) -> f32 {
         (self.total_height() - self.viewport_height).max(0.0)
     }
+
+    /// First visible item and how many pixels it is scrolled past its top
+    pub fn anchor(&self) -> (usize, f32) {
+        if self.item_height <= 0.0 {
+            return (0, 0.0);
+        }
+        let index = (self.scroll_offset / self.item_height).floor();
+        (
+            index as usize,
+            self.scroll_offset - index * self.item_height,
+        )
+    }
+
+    /// Scroll so that `index` is at the top again, `within` pixels past its top
+    ///
+    /// Used to keep the same item in view when items are inserted above it.
+    /// The offset is clamped on the next layout.
+    pub fn restore_anchor(&mut self, index: usize, within: f32) {
+        self.scroll_offset = index as f32 * self.item_height + within;
+    }
+
+    /// Scroll back to the first item
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0.0;
+    }
 }
 
 /// A virtual list widget that only renders visible items
@@ -127,6 +157,10 @@ where
     height: Length,
     /// Whether to show scrollbar
     show_scrollbar: bool,
+    /// Scrollbar thumb color; its alpha follows hover and drag
+    scrollbar_color: Color,
+    /// Extra items rendered above and below the viewport
+    overscan: usize,
     /// Message to send when mouse moves over empty area (not over any item)
     on_empty_area: Option<Message>,
     /// Function to create hover message for an item index
@@ -151,6 +185,8 @@ where
             width: Length::Fill,
             height: Length::Fill,
             show_scrollbar: true,
+            scrollbar_color: Color::WHITE,
+            overscan: BUFFER_ITEMS,
             on_empty_area: None,
             on_item_hover: None,
         }
@@ -185,6 +221,18 @@ where
         self
     }
 
+    /// Set the scrollbar thumb color
+    pub fn scrollbar_color(mut self, color: Color) -> Self {
+        self.scrollbar_color = color;
+        self
+    }
+
+    /// Set how many items beyond the viewport are built on each side
+    pub fn overscan(mut self, items: usize) -> Self {
+        self.overscan = items;
+        self
+    }
+
     /// Set a message to send when mouse moves over empty area (not over any item)
     /// This is useful for clearing hover states when mouse leaves all items
     pub fn on_empty_area(mut self, message: Message) -> Self {
@@ -269,6 +317,7 @@ where
         // Update state
         {
             let mut state = self.state.borrow_mut();
+            state.overscan = self.overscan;
             state.update(self.item_count, self.item_height, size.height);
         }
 
@@ -807,7 +856,10 @@ where
                     shadow: iced::Shadow::default(),
                     snap: true,
                 },
-                Color::from_rgba(1.0, 1.0, 1.0, alpha),
+                Color {
+                    a: alpha,
+                    ..self.scrollbar_color
+                },
             );
         }
     }
@@ -16,18 +16,23 @@
 //! - **Widgets** (this module): Composable UI patterns
 //! - **Components** (`crate::ui::components`): Business-specific UI with Message handling
 
+pub mod frosted_backdrop;
+pub mod lazy_cover;
 pub mod multi_track_slider;
 pub mod play_mode_button;
 pub mod playback_controls;
 pub mod playlist_card;
 pub mod progress_slider;
 pub mod section_header;
+pub mod shared_element;
+pub mod skeleton;
 mod toast;
 mod vertical_slider;
 
 // Re-export from primitives for backward compatibility
 pub use crate::ui::primitives::{
-    ProgressRing, VirtualList, VirtualListState, square_cover, view_progress_ring_styled,
+    BoundsSensor, ProgressRing, VirtualList, VirtualListState, square_cover,
+    view_progress_ring_styled,
 };
 
 pub use play_mode_button::ButtonSize as PlayModeButtonSize;
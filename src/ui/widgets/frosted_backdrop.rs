@@ -0,0 +1,25 @@
+//! Frosted-glass layer behind modals
+//!
+//! Stretches the small blurred snapshot from
+//! [`effects::frosted`](crate::ui::effects::frosted) over the window and fades
+//! it in with the modal on top.
+
+use iced::widget::{Space, image};
+use iced::{ContentFit, Element, Fill};
+
+/// Blurred snapshot over the whole window at `opacity`
+pub fn view<'a, Message: 'a>(
+    frosted: Option<&image::Handle>,
+    opacity: f32,
+) -> Element<'a, Message> {
+    match frosted {
+        Some(handle) if opacity > 0.01 => image(handle.clone())
+            .width(Fill)
+            .height(Fill)
+            .content_fit(ContentFit::Fill)
+            .filter_method(image::FilterMethod::Linear)
+            .opacity(opacity)
+            .into(),
+        _ => Space::new().width(0).height(0).into(),
+    }
+}
@@ -0,0 +1,31 @@
+//! Lazy cover widget
+//!
+//! Wraps a card whose cover has not arrived yet and reports when the card
+//! scrolls into or out of view, so cover downloads follow what is on screen.
+
+use iced::Element;
+use iced::widget::sensor;
+
+/// How far outside the viewport a card already counts as shown (px)
+const ANTICIPATE: f32 = 200.0;
+
+/// Watch the visibility of a card identified by `key`
+///
+/// # Arguments
+/// * `content` - The card itself
+/// * `key` - Identity of the card, so a different card in the same place is reported again
+/// * `on_show` - Message sent when the card comes into view
+/// * `on_hide` - Message sent when the card leaves the view
+pub fn view<'a, Message: Clone + 'a>(
+    content: impl Into<Element<'a, Message>>,
+    key: u64,
+    on_show: Message,
+    on_hide: Message,
+) -> Element<'a, Message> {
+    sensor(content)
+        .key(key)
+        .anticipate(ANTICIPATE)
+        .on_show(move |_size| on_show.clone())
+        .on_hide(on_hide)
+        .into()
+}
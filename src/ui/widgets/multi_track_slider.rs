@@ -29,6 +29,8 @@ pub struct MultiTrackSlider<'a, Message> {
     secondary_value: Option<f32>,
     on_change: Box<dyn Fn(f32) -> Message + 'a>,
     on_release: Option<Message>,
+    /// Called with the value under the cursor, or `None` once it leaves
+    on_hover: Option<Box<dyn Fn(Option<f32>) -> Message + 'a>>,
     width: Length,
     height: f32,
     style: Box<dyn Fn(&Theme, Status) -> Style + 'a>,
@@ -54,6 +56,7 @@ where
             secondary_value: None,
             on_change: Box::new(on_change),
             on_release: None,
+            on_hover: None,
             width: Length::Fill,
             height: Self::DEFAULT_HEIGHT,
             style: Box::new(default_style),
@@ -72,6 +75,15 @@ where
         self
     }
 
+    /// Report the value under the cursor while hovering the slider
+    pub fn on_hover<F>(mut self, on_hover: F) -> Self
+    where
+        F: 'a + Fn(Option<f32>) -> Message,
+    {
+        self.on_hover = Some(Box::new(on_hover));
+        self
+    }
+
     pub fn width(mut self, width: impl Into<Length>) -> Self {
         self.width = width.into();
         self
@@ -179,8 +191,31 @@ where
                     state.is_dragging = false;
                 }
             }
+            Event::Mouse(mouse::Event::CursorLeft) => {
+                if let Some(on_hover) = &self.on_hover {
+                    if state.hover_value.take().is_some() {
+                        shell.publish(on_hover(None));
+                    }
+                }
+            }
             Event::Mouse(mouse::Event::CursorMoved { .. })
             | Event::Touch(touch::Event::FingerMoved { .. }) => {
+                if let Some(on_hover) = &self.on_hover {
+                    let hovered = if state.is_dragging {
+                        cursor.land().position().and_then(locate)
+                    } else {
+                        cursor.position_over(bounds).and_then(locate)
+                    };
+                    let changed = match (state.hover_value, hovered) {
+                        (Some(old), Some(new)) => (old - new).abs() > f32::EPSILON,
+                        (None, None) => false,
+                        _ => true,
+                    };
+                    if changed {
+                        state.hover_value = hovered;
+                        shell.publish(on_hover(hovered));
+                    }
+                }
                 if state.is_dragging {
                     if let Some(pos) = cursor.land().position() {
                         if let Some(new_value) = locate(pos) {
@@ -418,9 +453,11 @@ where
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 struct State {
     is_dragging: bool,
+    /// Last value reported through `on_hover`
+    hover_value: Option<f32>,
 }
 
 /// Status of the slider
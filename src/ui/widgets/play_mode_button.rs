@@ -8,6 +8,7 @@ use iced::{Color, Element};
 
 use crate::app::Message;
 use crate::features::PlayMode;
+use crate::i18n::{Key, Locale};
 use crate::ui::{icons, theme};
 
 /// Size variant for play mode button
@@ -43,17 +44,23 @@ impl ButtonSize {
 }
 
 /// Build the play mode button with tooltip
-pub fn view(play_mode: PlayMode, size: ButtonSize, is_fm_mode: bool) -> Element<'static, Message> {
+pub fn view(
+    play_mode: PlayMode,
+    size: ButtonSize,
+    is_fm_mode: bool,
+    locale: Locale,
+) -> Element<'static, Message> {
     let (play_mode_icon, play_mode_tooltip) = if is_fm_mode {
-        (icons::RADIO, "私人FM")
+        (icons::RADIO, locale.get(Key::PersonalFm))
     } else {
         let icon = match play_mode {
             PlayMode::Sequential => icons::PLAY_SEQUENTIAL,
             PlayMode::LoopAll => icons::LOOP_ALL,
             PlayMode::LoopOne => icons::LOOP_ONE,
             PlayMode::Shuffle => icons::SHUFFLE,
+            PlayMode::AutoDj => icons::AUTO_DJ,
         };
-        (icon, play_mode.display_name())
+        (icon, play_mode.display_name(locale))
     };
 
     let icon_size = size.icon_size();
@@ -61,7 +68,7 @@ pub fn view(play_mode: PlayMode, size: ButtonSize, is_fm_mode: bool) -> Element<
     let radius = size.radius();
 
     let on_press = if is_fm_mode {
-        Message::ShowToast("私人FM模式下无法更改播放模式".to_string())
+        Message::ShowToast(locale.get(Key::PlayModeLockedInFm).to_string())
     } else {
         Message::CyclePlayMode
     };
@@ -3,7 +3,7 @@
 //! Provides a reusable progress slider with consistent styling.
 //! Used by both the player bar and lyrics page.
 
-use iced::widget::slider;
+use iced::widget::{container, slider, text, tooltip};
 use iced::{Color, Element, Length};
 
 use super::multi_track_slider::{self, MultiTrackSlider};
@@ -25,10 +25,12 @@ pub enum SliderSize {
 /// * `position` - Current playback position (0.0 to 1.0)
 /// * `download_progress` - Download progress (0.0 to 1.0), None if not streaming
 /// * `size` - Size variant
+/// * `hover_preview` - Lyric/chapter context shown next to the cursor while hovering
 pub fn view_with_download(
     position: f32,
     download_progress: Option<f32>,
     size: SliderSize,
+    hover_preview: Option<String>,
 ) -> Element<'static, Message> {
     let clamped_position = position.clamp(0.0, 1.0);
 
@@ -38,9 +40,10 @@ pub fn view_with_download(
     };
 
     // Use multi-track slider for download progress display
-    MultiTrackSlider::new(0.0..=1.0, clamped_position, Message::SeekPreview)
+    let slider = MultiTrackSlider::new(0.0..=1.0, clamped_position, Message::SeekPreview)
         .secondary(download_progress)
         .on_release(Message::SeekRelease)
+        .on_hover(Message::SeekHover)
         .width(width)
         .height(16)
         .step(0.001)
@@ -75,8 +78,28 @@ pub fn view_with_download(
                     border_color: Color::TRANSPARENT,
                 },
             }
+        });
+
+    match hover_preview {
+        Some(preview) => tooltip(
+            slider,
+            text(preview).size(12),
+            tooltip::Position::FollowCursor,
+        )
+        .gap(8)
+        .padding(8)
+        .style(|theme| container::Style {
+            background: Some(iced::Background::Color(theme::surface_container(theme))),
+            border: iced::Border {
+                radius: 4.0.into(),
+                color: theme::divider(theme),
+                width: 1.0,
+            },
+            ..Default::default()
         })
-        .into()
+        .into(),
+        None => slider.into(),
+    }
 }
 
 /// Build a volume slider
@@ -0,0 +1,27 @@
+//! Stand-in for an element in a shared-element transition
+//!
+//! Draws a cover image at absolute window coordinates, above the pages it
+//! flies between.
+
+use iced::widget::{Space, column, image, row};
+use iced::{Element, Rectangle};
+
+/// Cover image drawn at `bounds` with corners rounded by `radius`
+pub fn cover<'a, Message: 'a>(
+    handle: image::Handle,
+    bounds: Rectangle,
+    radius: f32,
+) -> Element<'a, Message> {
+    column![
+        Space::new().height(bounds.y.max(0.0)),
+        row![
+            Space::new().width(bounds.x.max(0.0)),
+            image(handle)
+                .width(bounds.width)
+                .height(bounds.height)
+                .content_fit(iced::ContentFit::Cover)
+                .border_radius(radius),
+        ],
+    ]
+    .into()
+}
@@ -0,0 +1,73 @@
+//! Shimmer placeholders for content that is still loading
+//!
+//! A light band sweeps across each block. Callers offset the phase of lower
+//! blocks a little, so the sweep reads as one wave running down the page.
+
+use std::time::Duration;
+
+use iced::widget::container;
+use iced::{Background, Border, Color, Element, Gradient, Length, Radians, gradient};
+
+use crate::ui::theme;
+
+/// Time for one sweep across a block
+const SWEEP: Duration = Duration::from_millis(1400);
+
+/// Width of the light band, as a fraction of the block
+const BAND: f32 = 0.3;
+
+/// Shimmer phase in `0.0..1.0` at `elapsed` into the animation
+pub fn phase(elapsed: Duration) -> f32 {
+    (elapsed.as_secs_f32() / SWEEP.as_secs_f32()).fract()
+}
+
+/// `phase` moved back by `delay` of a sweep, for blocks further down
+pub fn delayed(phase: f32, delay: f32) -> f32 {
+    (phase - delay).rem_euclid(1.0)
+}
+
+/// Placeholder block with the light band at `phase`
+pub fn block<'a, Message: 'a>(
+    width: impl Into<Length>,
+    height: impl Into<Length>,
+    radius: f32,
+    phase: f32,
+) -> Element<'a, Message> {
+    // The band starts and ends fully outside the block
+    let center = -BAND + phase * (1.0 + 2.0 * BAND);
+
+    container(iced::widget::Space::new())
+        .width(width)
+        .height(height)
+        .style(move |theme| {
+            let base = theme::placeholder_bg(theme);
+            let light = mix(base, theme::text_primary(theme), 0.08);
+            let stop = |offset: f32| offset.clamp(0.0, 1.0);
+
+            container::Style {
+                background: Some(Background::Gradient(Gradient::Linear(
+                    gradient::Linear::new(Radians(std::f32::consts::FRAC_PI_2))
+                        .add_stop(0.0, base)
+                        .add_stop(stop(center - BAND / 2.0), base)
+                        .add_stop(stop(center), light)
+                        .add_stop(stop(center + BAND / 2.0), base)
+                        .add_stop(1.0, base),
+                ))),
+                border: Border {
+                    radius: radius.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .into()
+}
+
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    Color::from_rgba(
+        a.r + (b.r - a.r) * t,
+        a.g + (b.g - a.g) * t,
+        a.b + (b.b - a.b) * t,
+        a.a,
+    )
+}
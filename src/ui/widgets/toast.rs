@@ -3,7 +3,7 @@
 //! Modern dark minimalist toast notifications.
 //! Follows Shadcn UI / Spotify style: dark surface with accent color accents.
 
-use iced::widget::{Space, container, row, text};
+use iced::widget::{Space, button, container, row, text};
 use iced::{Alignment, Element, Padding};
 
 use crate::ui::theme;
@@ -88,7 +88,11 @@ impl Toast {
 /// - Subtle border for depth
 /// - Accent color only on icon (not background)
 /// - Soft shadow for floating effect
-pub fn view_toast<'a, Message: 'a>(toast: &Toast) -> Element<'a, Message> {
+/// - Optional action button after the message (e.g. "Log in")
+pub fn view_toast<'a, Message: Clone + 'a>(
+    toast: &Toast,
+    action: Option<(&'a str, Message)>,
+) -> Element<'a, Message> {
     if !toast.visible {
         return Space::new().width(0).height(0).into();
     }
@@ -117,6 +121,19 @@ pub fn view_toast<'a, Message: 'a>(toast: &Toast) -> Element<'a, Message> {
         color: Some(theme::text_primary(theme)),
     });
 
+    // Action button, accent colored like the icon
+    let action_widget: Element<'a, Message> = match action {
+        Some((label, on_press)) => row![
+            Space::new().width(16),
+            button(text(label).size(13).color(accent_color))
+                .padding(Padding::new(2.0).left(6.0).right(6.0))
+                .style(theme::text_button)
+                .on_press(on_press),
+        ]
+        .into(),
+        None => Space::new().width(0).height(0).into(),
+    };
+
     // Toast content
     let content = row![
         accent_bar,
@@ -124,6 +141,7 @@ pub fn view_toast<'a, Message: 'a>(toast: &Toast) -> Element<'a, Message> {
         icon_widget,
         Space::new().width(10),
         message_widget,
+        action_widget,
     ]
     .align_y(Alignment::Center)
     .padding(Padding::new(14.0).left(12.0).right(20.0));
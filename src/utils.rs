@@ -5,6 +5,8 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::error;
 
+use crate::i18n::{Key, Locale};
+
 // ============================================================================
 // Image Extensions
 // ============================================================================
@@ -28,7 +30,7 @@ pub fn find_cached_image(dir: &Path, stem: &str) -> Option<PathBuf> {
 }
 
 // ============================================================================
-// Color Extraction
+// Color Palettes
 // ============================================================================
 
 /// Extracted color palette from an image (simple 2-color version)
@@ -46,31 +48,7 @@ impl Default for ColorPalette {
     }
 }
 
-impl ColorPalette {
-    /// Extract dominant color from an image file
-    pub fn from_image_path(path: &Path) -> Self {
-        match DominantColors::extract_from_path(path) {
-            Some(colors) => {
-                tracing::debug!(
-                    "Extracted colors from {:?}: primary=({:.2}, {:.2}, {:.2})",
-                    path,
-                    colors.primary.r,
-                    colors.primary.g,
-                    colors.primary.b
-                );
-                Self {
-                    primary: colors.primary,
-                }
-            }
-            None => {
-                tracing::warn!("Failed to extract colors from {:?}, using default", path);
-                Self::default()
-            }
-        }
-    }
-}
-
-/// Dominant colors extracted from an image using k-means clustering
+/// Dominant colors of an image, see [`crate::features::media::palette`]
 #[derive(Debug, Clone, Default)]
 pub struct DominantColors {
     /// Primary dominant color
@@ -93,149 +71,68 @@ impl DominantColors {
             brightness: 0.1,
         }
     }
+}
 
-    /// Extract dominant colors from an image file path (string version)
-    pub fn from_image_path(path: &str) -> Option<Self> {
-        Self::extract_from_path(Path::new(path))
-    }
+// ============================================================================
+// Portable Mode
+// ============================================================================
 
-    /// Extract dominant colors from an image file path
-    pub fn extract_from_path(path: &Path) -> Option<Self> {
-        let img = match image::open(path) {
-            Ok(img) => img,
-            Err(e) => {
-                tracing::warn!("Failed to open image {:?}: {}", path, e);
-                return None;
-            }
-        };
-        let img = img.to_rgb8();
-        let img = image::imageops::resize(&img, 32, 32, image::imageops::FilterType::Nearest);
+/// Marker file next to the executable that enables portable mode
+pub const PORTABLE_FLAG_FILE: &str = "portable.flag";
 
-        let mut pixels: Vec<(u8, u8, u8)> = Vec::new();
-        for pixel in img.pixels() {
-            pixels.push((pixel[0], pixel[1], pixel[2]));
-        }
+/// Command line switch that enables portable mode
+pub const PORTABLE_ARG: &str = "--portable";
 
-        if pixels.is_empty() {
-            return None;
-        }
+/// Directory (beside the executable) holding all data in portable mode
+const PORTABLE_DATA_DIR: &str = "RustleData";
 
-        let colors = kmeans_colors(&pixels, 3);
-
-        let brightness = colors
-            .iter()
-            .map(|(r, g, b)| (*r as f32 * 0.299 + *g as f32 * 0.587 + *b as f32 * 0.114) / 255.0)
-            .sum::<f32>()
-            / colors.len() as f32;
-
-        let to_background_color =
-            |r: u8, g: u8, b: u8, brightness_factor: f32, saturation_boost: f32| -> Color {
-                let rf = r as f32 / 255.0;
-                let gf = g as f32 / 255.0;
-                let bf = b as f32 / 255.0;
-
-                let max = rf.max(gf).max(bf);
-                let min = rf.min(gf).min(bf);
-                let delta = max - min;
-
-                let (r_out, g_out, b_out) = if delta < 0.01 {
-                    (
-                        rf * brightness_factor,
-                        gf * brightness_factor,
-                        bf * brightness_factor,
-                    )
-                } else {
-                    let avg = (rf + gf + bf) / 3.0;
-                    let boost = |v: f32| -> f32 {
-                        let diff = v - avg;
-                        (avg + diff * saturation_boost).clamp(0.0, 1.0) * brightness_factor
-                    };
-                    (boost(rf), boost(gf), boost(bf))
-                };
-
-                Color::from_rgb(r_out, g_out, b_out)
-            };
-
-        Some(Self {
-            primary: to_background_color(colors[0].0, colors[0].1, colors[0].2, 0.65, 1.6),
-            secondary: to_background_color(colors[1].0, colors[1].1, colors[1].2, 0.50, 1.5),
-            tertiary: to_background_color(colors[2].0, colors[2].1, colors[2].2, 0.25, 1.3),
-            brightness,
-        })
-    }
-}
+static PORTABLE_ROOT: std::sync::OnceLock<Option<PathBuf>> = std::sync::OnceLock::new();
 
-/// Simple k-means clustering for color extraction
-fn kmeans_colors(pixels: &[(u8, u8, u8)], k: usize) -> Vec<(u8, u8, u8)> {
-    if pixels.is_empty() || k == 0 {
-        return vec![(20, 15, 30); k];
-    }
-
-    let mut centroids: Vec<(f32, f32, f32)> = (0..k)
-        .map(|i| {
-            let idx = i * pixels.len() / k;
-            let p = pixels[idx.min(pixels.len() - 1)];
-            (p.0 as f32, p.1 as f32, p.2 as f32)
-        })
-        .collect();
-
-    for _ in 0..10 {
-        let mut clusters: Vec<Vec<(u8, u8, u8)>> = vec![Vec::new(); k];
-
-        for &pixel in pixels {
-            let mut min_dist = f32::MAX;
-            let mut min_idx = 0;
-
-            for (idx, centroid) in centroids.iter().enumerate() {
-                let dist = color_distance(pixel, *centroid);
-                if dist < min_dist {
-                    min_dist = dist;
-                    min_idx = idx;
-                }
-            }
+/// Detect portable mode from the command line or the flag file
+///
+/// Must run at startup before any data path is resolved.
+pub fn init_portable_mode() {
+    PORTABLE_ROOT.get_or_init(|| {
+        let exe_dir = std::env::current_exe()
+            .ok()?
+            .parent()
+            .map(Path::to_path_buf)?;
+        let enabled = std::env::args().any(|arg| arg == PORTABLE_ARG)
+            || exe_dir.join(PORTABLE_FLAG_FILE).exists();
+        enabled.then(|| exe_dir.join(PORTABLE_DATA_DIR))
+    });
+}
 
-            clusters[min_idx].push(pixel);
-        }
+/// Root data directory when running in portable mode
+pub fn portable_root() -> Option<&'static Path> {
+    PORTABLE_ROOT.get().and_then(|root| root.as_deref())
+}
 
-        for (idx, cluster) in clusters.iter().enumerate() {
-            if !cluster.is_empty() {
-                let sum: (u32, u32, u32) = cluster.iter().fold((0, 0, 0), |acc, p| {
-                    (acc.0 + p.0 as u32, acc.1 + p.1 as u32, acc.2 + p.2 as u32)
-                });
-                let len = cluster.len() as f32;
-                centroids[idx] = (sum.0 as f32 / len, sum.1 as f32 / len, sum.2 as f32 / len);
-            }
-        }
+/// Get the application data directory (database, cookies, plugins)
+pub fn data_dir() -> PathBuf {
+    if let Some(root) = portable_root() {
+        return root.join("data");
     }
-
-    centroids.sort_by(|a, b| {
-        let brightness_a = a.0 * 0.299 + a.1 * 0.587 + a.2 * 0.114;
-        let brightness_b = b.0 * 0.299 + b.1 * 0.587 + b.2 * 0.114;
-        brightness_a
-            .partial_cmp(&brightness_b)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
-
-    centroids
-        .iter()
-        .map(|(r, g, b)| (*r as u8, *g as u8, *b as u8))
-        .collect()
+    directories::ProjectDirs::from("com", "rustle", "Rustle")
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
 }
 
-/// Calculate squared distance between a pixel and a centroid
-fn color_distance(pixel: (u8, u8, u8), centroid: (f32, f32, f32)) -> f32 {
-    let dr = pixel.0 as f32 - centroid.0;
-    let dg = pixel.1 as f32 - centroid.1;
-    let db = pixel.2 as f32 - centroid.2;
-    dr * dr + dg * dg + db * db
+/// Get the application config directory (settings)
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join("config"));
+    }
+    directories::ProjectDirs::from("com", "rustle", "Rustle")
+        .map(|dirs| dirs.config_dir().to_path_buf())
 }
 
 // ============================================================================
 // Time & Path Utilities
 // ============================================================================
 
-/// Format timestamp as relative time (e.g., "2天前")
-pub fn format_relative_time(timestamp: i64) -> String {
+/// Format timestamp as relative time (e.g., "2 days ago")
+pub fn format_relative_time(timestamp: i64, locale: Locale) -> String {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs() as i64)
@@ -247,21 +144,23 @@ pub fn format_relative_time(timestamp: i64) -> String {
     let diff_days = diff_hours / 24;
 
     if diff_days > 30 {
-        let months = diff_days / 30;
-        format!("{}个月前", months)
+        locale.count(Key::TimeMonthsAgo, (diff_days / 30) as u64)
     } else if diff_days > 0 {
-        format!("{}天前", diff_days)
+        locale.count(Key::TimeDaysAgo, diff_days as u64)
     } else if diff_hours > 0 {
-        format!("{}小时前", diff_hours)
+        locale.count(Key::TimeHoursAgo, diff_hours as u64)
     } else if diff_mins > 0 {
-        format!("{}分钟前", diff_mins)
+        locale.count(Key::TimeMinutesAgo, diff_mins as u64)
     } else {
-        "刚刚".to_string()
+        locale.get(Key::TimeJustNow).to_string()
     }
 }
 
 /// Get the base cache directory for rustle
 pub fn cache_dir() -> PathBuf {
+    if let Some(root) = portable_root() {
+        return root.join("cache");
+    }
     dirs::cache_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("rustle")
@@ -401,8 +300,8 @@ fn detect_image_format(bytes: &[u8]) -> &'static str {
 /// * `client` - The NCM client for downloading
 /// * `url` - The image URL
 /// * `base_path` - The base local path (extension will be replaced based on actual format)
-/// * `width` - Resize width (for NCM image API)
-/// * `height` - Resize height (for NCM image API)
+/// * `width` - Resize width (for NCM image API), 0 for the original size
+/// * `height` - Resize height (for NCM image API), 0 for the original size
 pub async fn download_img(
     client: &crate::api::NcmClient,
     url: &str,
@@ -480,6 +379,33 @@ pub async fn download_cover(
     download_img(client, pic_url, path, 200, 200).await
 }
 
+/// Download the high-resolution cover of a song, for the lyrics page
+pub async fn download_large_cover(
+    client: &crate::api::NcmClient,
+    song_id: u64,
+    pic_url: &str,
+) -> Option<PathBuf> {
+    if pic_url.is_empty() {
+        return None;
+    }
+    let path = covers_cache_dir().join(format!("cover_{}_large.jpg", song_id));
+    download_img(client, pic_url, path, 1024, 1024).await
+}
+
+/// Download the original cover of a song, as large as NCM has it, for the
+/// cover viewer
+pub async fn download_full_cover(
+    client: &crate::api::NcmClient,
+    song_id: u64,
+    pic_url: &str,
+) -> Option<PathBuf> {
+    if pic_url.is_empty() {
+        return None;
+    }
+    let path = covers_cache_dir().join(format!("cover_{}_full.jpg", song_id));
+    download_img(client, pic_url, path, 0, 0).await
+}
+
 /// Download a banner image
 pub async fn download_banner(
     client: &crate::api::NcmClient,